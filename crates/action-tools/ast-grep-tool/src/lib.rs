@@ -15,6 +15,8 @@
  */
 
 use async_trait::async_trait;
+use rhema_action_tool::platform::tool_command;
+use rhema_action_tool::{tool_available, LimitedCommand};
 use rhema_action_tool::{ActionError, ActionIntent, ActionResult, SafetyLevel};
 use rhema_action_tool::{ToolResult, TransformationTool};
 use tracing::{info, warn};
@@ -27,6 +29,10 @@ impl TransformationTool for AstGrepTool {
     async fn execute(&self, intent: &ActionIntent) -> ActionResult<ToolResult> {
         info!("Executing ast-grep for intent: {}", intent.id);
 
+        if intent.dry_run {
+            return self.preview(intent).await;
+        }
+
         let start = std::time::Instant::now();
 
         // Extract file paths from intent
@@ -61,6 +67,9 @@ impl TransformationTool for AstGrepTool {
             errors,
             warnings,
             duration: start.elapsed(),
+            cached: false,
+            diagnostics: Vec::new(),
+            failed_tests: Vec::new(),
         })
     }
 
@@ -84,13 +93,7 @@ impl TransformationTool for AstGrepTool {
     }
 
     async fn is_available(&self) -> bool {
-        // Check if ast-grep is installed
-        tokio::process::Command::new("sg")
-            .arg("--version")
-            .output()
-            .await
-            .map(|output| output.status.success())
-            .unwrap_or(false)
+        tool_available("ast-grep").await
     }
 }
 
@@ -131,9 +134,9 @@ impl AstGrepTool {
         }
 
         // Execute ast-grep
-        let output = tokio::process::Command::new("sg")
+        let output = tool_command("sg")
             .args(&[pattern, file_path, "--json"])
-            .output()
+            .limited_output()
             .await
             .map_err(|e| ActionError::ToolExecution {
                 tool: "ast-grep".to_string(),
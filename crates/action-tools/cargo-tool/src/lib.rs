@@ -15,11 +15,28 @@
  */
 
 use async_trait::async_trait;
-use rhema_action_tool::{ActionError, ActionIntent, ActionResult, SafetyLevel};
-use rhema_action_tool::{ToolResult, TransformationTool, ValidationTool};
+use futures::stream::{self, StreamExt};
+use rhema_action_tool::platform::tool_command;
+use rhema_action_tool::{tool_available, LimitedCommand};
+use rhema_action_tool::{
+    ActionError, ActionIntent, ActionResult, Diagnostic, SafetyLevel, Severity,
+};
+use rhema_action_tool::{ToolResult, ToolchainProfile, TransformationTool, ValidationTool};
 use serde_json::Value;
+use std::collections::BTreeSet;
+use std::future::Future;
+use std::path::{Path, PathBuf};
 use tracing::{error, info};
 
+/// Target kinds `cargo metadata` reports for library targets
+const LIB_KINDS: &[&str] = &["lib", "rlib", "dylib", "cdylib", "staticlib", "proc-macro"];
+
+/// nextest only emits libtest JSON with this set, as the format is unstable
+const NEXTEST_LIBTEST_JSON_ENV: &str = "NEXTEST_EXPERIMENTAL_LIBTEST_JSON";
+
+/// Environment variable cargo reads its `-j` build jobs from
+const CARGO_BUILD_JOBS_ENV: &str = "CARGO_BUILD_JOBS";
+
 /// Cargo validation and transformation tool
 pub struct CargoTool;
 
@@ -33,6 +50,8 @@ pub enum CargoCommand {
     Fmt,
     Audit,
     Outdated,
+    /// `cargo nextest run`, reporting each test
+    Nextest,
 }
 
 /// Cargo operation result
@@ -43,17 +62,68 @@ pub struct CargoResult {
     pub output: String,
     pub errors: Vec<String>,
     pub warnings: Vec<String>,
+    pub diagnostics: Vec<Diagnostic>,
+    /// Tests run, for commands that report tests individually
+    pub tests: Vec<TestOutcome>,
     pub duration: std::time::Duration,
 }
 
+impl CargoResult {
+    /// Names of the tests that failed
+    pub fn failed_tests(&self) -> impl Iterator<Item = String> + '_ {
+        self.tests
+            .iter()
+            .filter(|test| test.status == TestStatus::Failed)
+            .map(TestOutcome::to_string)
+    }
+}
+
+/// Outcome of a single test
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestOutcome {
+    /// Test path within its binary, e.g. `parser::tests::parses_empty_input`
+    pub name: String,
+    /// nextest binary ID, e.g. `my-crate` or `my-crate::integration`
+    pub binary: Option<String>,
+    pub status: TestStatus,
+    pub duration: Option<std::time::Duration>,
+    /// Captured output of a failed test
+    pub output: Option<String>,
+}
+
+impl std::fmt::Display for TestOutcome {
+    /// The binary ID and test name, as nextest prints them
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.binary {
+            Some(binary) => write!(f, "{} {}", binary, self.name),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestStatus {
+    Passed,
+    Failed,
+    Ignored,
+}
+
 /// Workspace member information
 #[derive(Debug, Clone)]
 pub struct WorkspaceMember {
     pub name: String,
+    /// Directory relative to the workspace root, `.` for the root package
     pub path: String,
     pub package_type: PackageType,
 }
 
+impl WorkspaceMember {
+    /// Whether this is the package of the workspace root manifest
+    pub fn is_root(&self) -> bool {
+        self.path == "."
+    }
+}
+
 /// Package type classification
 #[derive(Debug, Clone, PartialEq)]
 pub enum PackageType {
@@ -68,6 +138,10 @@ pub enum PackageType {
 pub struct WorkspaceInfo {
     pub root_path: String,
     pub members: Vec<WorkspaceMember>,
+    /// Names of the members cargo operates on at the root without `--workspace`
+    pub default_members: Vec<String>,
+    /// Paths listed in `exclude`
+    pub exclude: Vec<String>,
     pub workspace_config: Option<Value>,
 }
 
@@ -76,6 +150,9 @@ pub struct WorkspaceInfo {
 pub struct CargoConfig {
     pub commands: Vec<CargoCommand>,
     pub parallel: bool,
+    /// Targets run at once when `parallel` is set; defaults to
+    /// [`DEFAULT_MAX_PARALLEL`]
+    pub max_parallel: Option<usize>,
     pub json_output: bool,
     pub verbose: bool,
     pub workspace_mode: WorkspaceMode,
@@ -96,11 +173,98 @@ pub enum WorkspaceMode {
     SelectedMembers,
 }
 
+/// Cargo processes run at once by default. Each compiles with several jobs
+/// already, and members of one workspace wait on its target directory lock,
+/// so more processes mostly oversubscribe the CPU
+pub const DEFAULT_MAX_PARALLEL: usize = 2;
+
+impl CargoConfig {
+    /// Number of targets cargo runs on at once
+    pub fn concurrency(&self) -> usize {
+        if !self.parallel {
+            return 1;
+        }
+        self.max_parallel.unwrap_or(DEFAULT_MAX_PARALLEL).max(1)
+    }
+
+    /// Build jobs of each cargo process, passed as `CARGO_BUILD_JOBS` so that
+    /// concurrent processes split the CPUs between them. `None` leaves a
+    /// single process at cargo's default of one job per CPU
+    pub fn jobs_per_process(&self) -> Option<usize> {
+        let concurrency = self.concurrency();
+        if concurrency == 1 {
+            return None;
+        }
+        let cpus = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        Some((cpus / concurrency).max(1))
+    }
+}
+
+/// Directory cargo runs in, with the label its results are prefixed with
+#[derive(Debug, Clone, PartialEq)]
+struct CargoTarget {
+    label: Option<String>,
+    dir: PathBuf,
+}
+
+impl CargoTarget {
+    fn new(dir: &Path, label: Option<&str>) -> Self {
+        Self {
+            label: label.map(str::to_string),
+            dir: dir.to_path_buf(),
+        }
+    }
+
+    fn member(root: &Path, member: &WorkspaceMember) -> Self {
+        Self {
+            label: Some(member.name.clone()),
+            dir: root.join(&member.path),
+        }
+    }
+
+    /// Label the result of running `command` here, turning a failure to run
+    /// cargo into a failed result
+    fn finish(&self, command: &CargoCommand, result: ActionResult<CargoResult>) -> CargoResult {
+        match (result, &self.label) {
+            (Ok(result), None) => result,
+            (Ok(mut result), Some(label)) => {
+                result.output = format!("[{}] {}", label, result.output);
+                result
+            }
+            (Err(e), label) => {
+                error!(
+                    "Failed to execute {:?} in {}: {}",
+                    command,
+                    self.dir.display(),
+                    e
+                );
+                let (output, error) = match label {
+                    Some(label) => (format!("[{}] Failed", label), format!("{}: {}", label, e)),
+                    None => (String::new(), e.to_string()),
+                };
+                CargoResult {
+                    command: command.clone(),
+                    success: false,
+                    output,
+                    diagnostics: vec![Diagnostic::error("cargo", &error)],
+                    errors: vec![error],
+                    warnings: vec![],
+                    tests: Vec::new(),
+                    duration: std::time::Duration::ZERO,
+                }
+            }
+        }
+    }
+}
+
 impl Default for CargoConfig {
     fn default() -> Self {
         Self {
             commands: vec![CargoCommand::Check],
             parallel: true,
+            max_parallel: None,
             json_output: true,
             verbose: false,
             workspace_mode: WorkspaceMode::RootAndMembers,
@@ -134,34 +298,46 @@ impl ValidationTool for CargoTool {
                 errors: vec![],
                 warnings: vec![],
                 duration: start.elapsed(),
+                cached: false,
+                diagnostics: Vec::new(),
+                failed_tests: Vec::new(),
             });
         }
 
-        // Run cargo commands for each project
+        // Run cargo commands on the targets of every project
         let mut all_errors = Vec::new();
         let mut all_warnings = Vec::new();
         let mut all_changes = Vec::new();
+        let mut all_diagnostics = Vec::new();
+        let mut failed_tests = Vec::new();
 
+        let mut targets = Vec::new();
         for cargo_file in &cargo_files {
-            match self
-                .run_cargo_commands_with_workspace(cargo_file, &config)
-                .await
-            {
-                Ok(results) => {
-                    for result in results {
-                        all_errors.extend(result.errors);
-                        all_warnings.extend(result.warnings);
-                        if !result.output.is_empty() {
-                            all_changes.push(result.output);
-                        }
-                    }
-                }
+            match self.resolve_targets(cargo_file, &config, false).await {
+                Ok(project_targets) => targets.extend(project_targets),
                 Err(e) => {
-                    all_errors.push(format!("Cargo operations failed for {}: {}", cargo_file, e))
+                    let message = format!("Cargo operations failed for {}: {}", cargo_file, e);
+                    all_diagnostics.push(Diagnostic::error("cargo", &message));
+                    all_errors.push(message);
                 }
             }
         }
 
+        let jobs: Vec<_> = targets
+            .iter()
+            .map(|target| self.validate_target(target, &config))
+            .collect();
+        let results = run_bounded(jobs, config.concurrency()).await;
+        for result in results {
+            failed_tests.extend(result.failed_tests());
+            all_errors.extend(result.errors);
+            all_warnings.extend(result.warnings);
+            all_diagnostics.extend(result.diagnostics);
+            if !result.output.is_empty() {
+                all_changes.push(result.output);
+            }
+        }
+
         let success = all_errors.is_empty();
 
         Ok(ToolResult {
@@ -174,9 +350,16 @@ impl ValidationTool for CargoTool {
             errors: all_errors,
             warnings: all_warnings,
             duration: start.elapsed(),
+            cached: false,
+            diagnostics: all_diagnostics,
+            failed_tests,
         })
     }
 
+    fn applies_to(&self, profile: &ToolchainProfile) -> bool {
+        profile.has_toolchain("cargo")
+    }
+
     fn name(&self) -> &str {
         "cargo"
     }
@@ -186,13 +369,7 @@ impl ValidationTool for CargoTool {
     }
 
     async fn is_available(&self) -> bool {
-        // Check if Cargo is installed
-        tokio::process::Command::new("cargo")
-            .args(&["--version"])
-            .output()
-            .await
-            .map(|output| output.status.success())
-            .unwrap_or(false)
+        tool_available("cargo").await
     }
 }
 
@@ -201,6 +378,10 @@ impl TransformationTool for CargoTool {
     async fn execute(&self, intent: &ActionIntent) -> ActionResult<ToolResult> {
         info!("Executing Cargo transformations for intent: {}", intent.id);
 
+        if intent.dry_run {
+            return self.preview(intent).await;
+        }
+
         let start = std::time::Instant::now();
         let config = self.parse_config(intent);
 
@@ -222,25 +403,31 @@ impl TransformationTool for CargoTool {
         let mut all_errors = Vec::new();
         let mut all_warnings = Vec::new();
         let mut all_changes = Vec::new();
+        let mut all_diagnostics = Vec::new();
 
+        let mut targets = Vec::new();
         for cargo_file in &cargo_files {
-            match self
-                .run_transformation_commands_with_workspace(cargo_file, &config)
-                .await
-            {
-                Ok(results) => {
-                    for result in results {
-                        all_errors.extend(result.errors);
-                        all_warnings.extend(result.warnings);
-                        if !result.output.is_empty() {
-                            all_changes.push(result.output);
-                        }
-                    }
+            match self.resolve_targets(cargo_file, &config, true).await {
+                Ok(project_targets) => targets.extend(project_targets),
+                Err(e) => {
+                    let message = format!("Cargo transformation failed for {}: {}", cargo_file, e);
+                    all_diagnostics.push(Diagnostic::error("cargo", &message));
+                    all_errors.push(message);
                 }
-                Err(e) => all_errors.push(format!(
-                    "Cargo transformation failed for {}: {}",
-                    cargo_file, e
-                )),
+            }
+        }
+
+        let jobs: Vec<_> = targets
+            .iter()
+            .map(|target| self.transform_target(target, &config))
+            .collect();
+        let results = run_bounded(jobs, config.concurrency()).await;
+        for result in results {
+            all_errors.extend(result.errors);
+            all_warnings.extend(result.warnings);
+            all_diagnostics.extend(result.diagnostics);
+            if !result.output.is_empty() {
+                all_changes.push(result.output);
             }
         }
 
@@ -256,6 +443,9 @@ impl TransformationTool for CargoTool {
             errors: all_errors,
             warnings: all_warnings,
             duration: start.elapsed(),
+            cached: false,
+            diagnostics: all_diagnostics,
+            failed_tests: Vec::new(),
         })
     }
 
@@ -276,13 +466,7 @@ impl TransformationTool for CargoTool {
     }
 
     async fn is_available(&self) -> bool {
-        // Check if Cargo is installed
-        tokio::process::Command::new("cargo")
-            .args(&["--version"])
-            .output()
-            .await
-            .map(|output| output.status.success())
-            .unwrap_or(false)
+        tool_available("cargo").await
     }
 }
 
@@ -305,6 +489,7 @@ impl CargoTool {
                                 "fmt" => Some(CargoCommand::Fmt),
                                 "audit" => Some(CargoCommand::Audit),
                                 "outdated" => Some(CargoCommand::Outdated),
+                                "nextest" => Some(CargoCommand::Nextest),
                                 _ => None,
                             })
                         })
@@ -316,6 +501,10 @@ impl CargoTool {
                 config.parallel = parallel.as_bool().unwrap_or(true);
             }
 
+            if let Some(max_parallel) = intent.metadata.get("max_parallel") {
+                config.max_parallel = max_parallel.as_u64().map(|n| n as usize);
+            }
+
             if let Some(json_output) = intent.metadata.get("json_output") {
                 config.json_output = json_output.as_bool().unwrap_or(true);
             }
@@ -360,629 +549,277 @@ impl CargoTool {
     }
 
     /// Detect workspace information from a Cargo.toml file
+    ///
+    /// Members come from `cargo metadata`, which resolves member globs,
+    /// `exclude` and `default-members` exactly as cargo does. When cargo is
+    /// missing or rejects the workspace, the manifests are read directly and
+    /// the same rules applied here.
     async fn detect_workspace(
         &self,
         cargo_file: &str,
     ) -> Result<Option<WorkspaceInfo>, ActionError> {
-        let project_dir = std::path::Path::new(cargo_file)
+        let project_dir = Path::new(cargo_file)
             .parent()
             .ok_or_else(|| ActionError::Validation("Invalid Cargo.toml path".to_string()))?;
 
-        // Read and parse Cargo.toml
         let cargo_content = tokio::fs::read_to_string(cargo_file)
             .await
             .map_err(|e| ActionError::Validation(format!("Failed to read Cargo.toml: {}", e)))?;
-
-        // Simple TOML parsing for workspace detection
-        if !cargo_content.contains("[workspace]") {
+        let manifest = parse_manifest(&cargo_content)?;
+        let Some(workspace) = manifest.get("workspace").and_then(toml::Value::as_table) else {
             return Ok(None); // Not a workspace
-        }
-
-        let mut workspace_info = WorkspaceInfo {
-            root_path: project_dir.to_string_lossy().to_string(),
-            members: Vec::new(),
-            workspace_config: None,
         };
 
-        // Extract workspace members
-        if let Some(members_section) = self.extract_workspace_members(&cargo_content) {
-            for member_path in members_section {
-                let member_cargo_path = project_dir.join(&member_path).join("Cargo.toml");
-                if member_cargo_path.exists() {
-                    if let Ok(member_info) = self.get_package_info(&member_cargo_path).await {
-                        workspace_info.members.push(WorkspaceMember {
-                            name: member_info.name,
-                            path: member_path,
-                            package_type: member_info.package_type,
-                        });
-                    }
-                }
-            }
+        let (members, default_members) = match self.cargo_metadata(project_dir).await {
+            Some(metadata) => members_from_metadata(&metadata),
+            None => None,
         }
+        .unwrap_or_default();
+        let (members, default_members) = if members.is_empty() {
+            let members = self
+                .members_from_manifests(project_dir, &cargo_content)
+                .await;
+            (members, None)
+        } else {
+            (members, default_members)
+        };
+        let default_members = default_members
+            .unwrap_or_else(|| resolve_default_members(project_dir, workspace, &members));
 
-        // Extract workspace configuration
-        workspace_info.workspace_config = self.extract_workspace_config(&cargo_content);
-
-        Ok(Some(workspace_info))
+        Ok(Some(WorkspaceInfo {
+            root_path: project_dir.to_string_lossy().to_string(),
+            members,
+            default_members,
+            exclude: string_array(workspace.get("exclude")),
+            workspace_config: self.extract_workspace_config(&cargo_content),
+        }))
     }
 
-    /// Extract workspace members from Cargo.toml content
-    fn extract_workspace_members(&self, cargo_content: &str) -> Option<Vec<String>> {
-        let mut members = Vec::new();
-        let lines: Vec<&str> = cargo_content.lines().collect();
-
-        let mut in_workspace_section = false;
-        let mut in_members_section = false;
-
-        for line in lines {
-            let trimmed = line.trim();
-
-            if trimmed == "[workspace]" {
-                in_workspace_section = true;
-                continue;
-            }
-
-            if in_workspace_section && trimmed == "members = [" {
-                in_members_section = true;
-                continue;
-            }
-
-            if in_members_section {
-                if trimmed == "]" {
-                    break;
-                }
-
-                // Skip empty lines and comments
-                if trimmed.is_empty() || trimmed.starts_with('#') {
-                    continue;
-                }
+    /// `cargo metadata` of the workspace at `dir`, if cargo can produce it
+    async fn cargo_metadata(&self, dir: &Path) -> Option<Value> {
+        let output = tool_command("cargo")
+            .args(["metadata", "--format-version", "1", "--no-deps"])
+            .current_dir(dir)
+            .limited_output()
+            .await
+            .ok()?;
+        if !output.status.success() {
+            info!(
+                "cargo metadata failed in {}, reading manifests instead",
+                dir.display()
+            );
+            return None;
+        }
+        serde_json::from_slice(&output.stdout).ok()
+    }
 
-                // Extract member path from quoted string
-                if trimmed.starts_with('"') {
-                    let end_quote = trimmed[1..].find('"');
-                    if let Some(end_pos) = end_quote {
-                        let member = trimmed[1..end_pos + 1].to_string();
-                        members.push(member);
-                    }
-                }
-            }
+    /// Members of the workspace read from the manifests: directories
+    /// matching the member patterns outside `exclude`, plus the root package
+    async fn members_from_manifests(
+        &self,
+        root: &Path,
+        cargo_content: &str,
+    ) -> Vec<WorkspaceMember> {
+        let Ok(manifest) = parse_manifest(cargo_content) else {
+            return Vec::new();
+        };
+        let exclude = manifest
+            .get("workspace")
+            .map(|workspace| string_array(workspace.get("exclude")))
+            .unwrap_or_default();
+        let patterns = self
+            .extract_workspace_members(cargo_content)
+            .unwrap_or_default();
+
+        let mut paths = expand_members(root, &patterns, &exclude);
+        if manifest.contains_key("package") {
+            paths.insert(".".to_string());
+        }
 
-            // Exit workspace section if we encounter another section
-            if in_workspace_section && trimmed.starts_with('[') && trimmed != "[workspace]" {
-                in_workspace_section = false;
-                in_members_section = false;
+        let mut members = Vec::new();
+        for path in paths {
+            match self
+                .get_package_info(&root.join(&path).join("Cargo.toml"))
+                .await
+            {
+                Ok(info) => members.push(WorkspaceMember {
+                    name: info.name,
+                    path,
+                    package_type: info.package_type,
+                }),
+                Err(e) => info!("Skipping workspace member {}: {}", path, e),
             }
         }
+        members
+    }
 
-        if members.is_empty() {
-            None
-        } else {
-            Some(members)
-        }
+    /// Member patterns of the `[workspace]` table, as written
+    fn extract_workspace_members(&self, cargo_content: &str) -> Option<Vec<String>> {
+        let manifest = parse_manifest(cargo_content).ok()?;
+        let members = string_array(manifest.get("workspace")?.get("members"));
+        (!members.is_empty()).then_some(members)
     }
 
-    /// Extract workspace configuration from Cargo.toml content
+    /// The `[workspace]` table without its member lists, e.g. `resolver`,
+    /// `package`, `dependencies` and `lints`
     fn extract_workspace_config(&self, cargo_content: &str) -> Option<Value> {
-        // Simple extraction of workspace configuration
-        // In a real implementation, you'd use a proper TOML parser
-        let mut config = serde_json::Map::new();
-
-        if cargo_content.contains("resolver = \"2\"") {
-            config.insert("resolver".to_string(), Value::String("2".to_string()));
+        let manifest = parse_manifest(cargo_content).ok()?;
+        let mut workspace = manifest.get("workspace")?.as_table()?.clone();
+        for key in ["members", "default-members", "exclude"] {
+            workspace.remove(key);
         }
-
-        if !config.is_empty() {
-            Some(Value::Object(config))
-        } else {
-            None
+        if workspace.is_empty() {
+            return None;
         }
+        serde_json::to_value(workspace).ok()
     }
 
-    /// Get package information from a Cargo.toml file
-    async fn get_package_info(
-        &self,
-        cargo_path: &std::path::Path,
-    ) -> Result<PackageInfo, ActionError> {
+    /// Get package information from a Cargo.toml file, classifying the
+    /// package by its declared targets and the ones cargo discovers in `src`
+    async fn get_package_info(&self, cargo_path: &Path) -> Result<PackageInfo, ActionError> {
         let content = tokio::fs::read_to_string(cargo_path)
             .await
             .map_err(|e| ActionError::Validation(format!("Failed to read Cargo.toml: {}", e)))?;
-
-        let mut name = String::new();
-        let mut has_lib = false;
-        let mut has_bin = false;
-
-        for line in content.lines() {
-            let trimmed = line.trim();
-
-            if trimmed.starts_with("name = ") {
-                if let Some(n) = trimmed.strip_prefix("name = ") {
-                    name = n.trim_matches('"').to_string();
-                }
-            }
-
-            if trimmed == "[lib]" {
-                has_lib = true;
-            }
-
-            if trimmed == "[[bin]]" {
-                has_bin = true;
-            }
-        }
-
-        let package_type = match (has_lib, has_bin) {
-            (true, true) => PackageType::Both,
-            (true, false) => PackageType::Library,
-            (false, true) => PackageType::Binary,
-            (false, false) => PackageType::Unknown,
+        let manifest = parse_manifest(&content)?;
+
+        let name = manifest
+            .get("package")
+            .and_then(|package| package.get("name"))
+            .and_then(toml::Value::as_str)
+            .ok_or_else(|| {
+                ActionError::Validation(format!("{} has no package name", cargo_path.display()))
+            })?
+            .to_string();
+        let dir = cargo_path.parent().unwrap_or(Path::new("."));
+        let auto = |key: &str| {
+            manifest
+                .get("package")
+                .and_then(|package| package.get(key))
+                .and_then(toml::Value::as_bool)
+                .unwrap_or(true)
         };
 
-        Ok(PackageInfo { name, package_type })
+        let has_lib =
+            manifest.contains_key("lib") || (auto("autolib") && dir.join("src/lib.rs").is_file());
+        let has_bin = manifest
+            .get("bin")
+            .and_then(toml::Value::as_array)
+            .is_some_and(|bins| !bins.is_empty())
+            || (auto("autobins")
+                && (dir.join("src/main.rs").is_file() || dir.join("src/bin").is_dir()));
+
+        Ok(PackageInfo {
+            name,
+            package_type: package_type(has_lib, has_bin),
+        })
     }
 
-    /// Run cargo commands with workspace support
-    async fn run_cargo_commands_with_workspace(
+    /// Directories the commands run in for a Cargo.toml, according to the
+    /// workspace mode. Transformations run on members only, since formatting
+    /// or fixing the root would rewrite the members a second time.
+    async fn resolve_targets(
         &self,
         cargo_file: &str,
         config: &CargoConfig,
-    ) -> ActionResult<Vec<CargoResult>> {
-        let project_dir = std::path::Path::new(cargo_file)
+        transform: bool,
+    ) -> ActionResult<Vec<CargoTarget>> {
+        let project_dir = Path::new(cargo_file)
             .parent()
             .ok_or_else(|| ActionError::Validation("Invalid Cargo.toml path".to_string()))?;
 
-        // Detect if this is a workspace
-        let workspace_info = self.detect_workspace(cargo_file).await?;
+        // Not a workspace, run on the project itself
+        let Some(workspace) = self.detect_workspace(cargo_file).await? else {
+            return Ok(vec![CargoTarget::new(project_dir, None)]);
+        };
 
-        let mut results = Vec::new();
+        let root = CargoTarget::new(project_dir, Some("workspace"));
+        let members = |members: Vec<&WorkspaceMember>| {
+            members
+                .into_iter()
+                .map(|member| CargoTarget::member(project_dir, member))
+                .collect::<Vec<_>>()
+        };
 
-        match &workspace_info {
-            Some(workspace) => {
-                // Handle workspace execution
-                match config.workspace_mode {
-                    WorkspaceMode::RootOnly => {
-                        // Execute only on workspace root
-                        for command in &config.commands {
-                            match self
-                                .execute_cargo_command(project_dir, command, config)
-                                .await
-                            {
-                                Ok(result) => results.push(result),
-                                Err(e) => {
-                                    error!(
-                                        "Failed to execute {:?} for workspace root: {}",
-                                        command, e
-                                    );
-                                    results.push(CargoResult {
-                                        command: command.clone(),
-                                        success: false,
-                                        output: String::new(),
-                                        errors: vec![e.to_string()],
-                                        warnings: vec![],
-                                        duration: std::time::Duration::ZERO,
-                                    });
-                                }
-                            }
-                        }
-                    }
-                    WorkspaceMode::AllMembers => {
-                        // Execute on all workspace members
-                        for member in &workspace.members {
-                            let member_path = project_dir.join(&member.path);
-                            for command in &config.commands {
-                                match self
-                                    .execute_cargo_command(&member_path, command, config)
-                                    .await
-                                {
-                                    Ok(mut result) => {
-                                        result.output =
-                                            format!("[{}] {}", member.name, result.output);
-                                        results.push(result);
-                                    }
-                                    Err(e) => {
-                                        error!(
-                                            "Failed to execute {:?} for member {}: {}",
-                                            command, member.name, e
-                                        );
-                                        results.push(CargoResult {
-                                            command: command.clone(),
-                                            success: false,
-                                            output: format!("[{}] Failed", member.name),
-                                            errors: vec![format!("{}: {}", member.name, e)],
-                                            warnings: vec![],
-                                            duration: std::time::Duration::ZERO,
-                                        });
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    WorkspaceMode::RootAndMembers => {
-                        // Execute on workspace root and all members
-                        // First, execute on root
-                        for command in &config.commands {
-                            match self
-                                .execute_cargo_command(project_dir, command, config)
-                                .await
-                            {
-                                Ok(mut result) => {
-                                    result.output = format!("[workspace] {}", result.output);
-                                    results.push(result);
-                                }
-                                Err(e) => {
-                                    error!(
-                                        "Failed to execute {:?} for workspace root: {}",
-                                        command, e
-                                    );
-                                    results.push(CargoResult {
-                                        command: command.clone(),
-                                        success: false,
-                                        output: "[workspace] Failed".to_string(),
-                                        errors: vec![format!("workspace: {}", e)],
-                                        warnings: vec![],
-                                        duration: std::time::Duration::ZERO,
-                                    });
-                                }
-                            }
-                        }
-
-                        // Then execute on members
-                        for member in &workspace.members {
-                            let member_path = project_dir.join(&member.path);
-                            for command in &config.commands {
-                                match self
-                                    .execute_cargo_command(&member_path, command, config)
-                                    .await
-                                {
-                                    Ok(mut result) => {
-                                        result.output =
-                                            format!("[{}] {}", member.name, result.output);
-                                        results.push(result);
-                                    }
-                                    Err(e) => {
-                                        error!(
-                                            "Failed to execute {:?} for member {}: {}",
-                                            command, member.name, e
-                                        );
-                                        results.push(CargoResult {
-                                            command: command.clone(),
-                                            success: false,
-                                            output: format!("[{}] Failed", member.name),
-                                            errors: vec![format!("{}: {}", member.name, e)],
-                                            warnings: vec![],
-                                            duration: std::time::Duration::ZERO,
-                                        });
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    WorkspaceMode::SelectedMembers => {
-                        // Execute only on selected members
-                        let selected_members =
-                            self.get_selected_members(&workspace.members, config);
-                        for member in selected_members {
-                            let member_path = project_dir.join(&member.path);
-                            for command in &config.commands {
-                                match self
-                                    .execute_cargo_command(&member_path, command, config)
-                                    .await
-                                {
-                                    Ok(mut result) => {
-                                        result.output =
-                                            format!("[{}] {}", member.name, result.output);
-                                        results.push(result);
-                                    }
-                                    Err(e) => {
-                                        error!(
-                                            "Failed to execute {:?} for member {}: {}",
-                                            command, member.name, e
-                                        );
-                                        results.push(CargoResult {
-                                            command: command.clone(),
-                                            success: false,
-                                            output: format!("[{}] Failed", member.name),
-                                            errors: vec![format!("{}: {}", member.name, e)],
-                                            warnings: vec![],
-                                            duration: std::time::Duration::ZERO,
-                                        });
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+        Ok(match config.workspace_mode {
+            WorkspaceMode::RootOnly => vec![root],
+            WorkspaceMode::AllMembers => members(workspace.members.iter().collect()),
+            WorkspaceMode::RootAndMembers if transform => {
+                members(workspace.members.iter().collect())
             }
-            None => {
-                // Not a workspace, execute normally
-                for command in &config.commands {
-                    match self
-                        .execute_cargo_command(project_dir, command, config)
-                        .await
-                    {
-                        Ok(result) => results.push(result),
-                        Err(e) => {
-                            error!("Failed to execute {:?} for {}: {}", command, cargo_file, e);
-                            results.push(CargoResult {
-                                command: command.clone(),
-                                success: false,
-                                output: String::new(),
-                                errors: vec![e.to_string()],
-                                warnings: vec![],
-                                duration: std::time::Duration::ZERO,
-                            });
-                        }
-                    }
-                }
+            WorkspaceMode::RootAndMembers => {
+                // The root run already covers a root package
+                let mut targets = vec![root];
+                targets.extend(members(
+                    workspace.members.iter().filter(|m| !m.is_root()).collect(),
+                ));
+                targets
             }
-        }
-
-        Ok(results)
+            WorkspaceMode::SelectedMembers => {
+                members(self.get_selected_members(&workspace.members, config))
+            }
+        })
     }
 
-    /// Run transformation commands with workspace support
-    async fn run_transformation_commands_with_workspace(
+    /// Run the configured commands on one target, in order: they share the
+    /// target's build directory and `fmt` rewrites its sources
+    async fn validate_target(
         &self,
-        cargo_file: &str,
+        target: &CargoTarget,
         config: &CargoConfig,
-    ) -> ActionResult<Vec<CargoResult>> {
-        let project_dir = std::path::Path::new(cargo_file)
-            .parent()
-            .ok_or_else(|| ActionError::Validation("Invalid Cargo.toml path".to_string()))?;
-
-        // Detect if this is a workspace
-        let workspace_info = self.detect_workspace(cargo_file).await?;
-
+    ) -> Vec<CargoResult> {
         let mut results = Vec::new();
-
-        match &workspace_info {
-            Some(workspace) => {
-                // Handle workspace transformation
-                match config.workspace_mode {
-                    WorkspaceMode::RootOnly => {
-                        // Transform only workspace root
-                        if config.commands.contains(&CargoCommand::Fmt) {
-                            match self.execute_cargo_fmt(project_dir, config).await {
-                                Ok(mut result) => {
-                                    result.output = format!("[workspace] {}", result.output);
-                                    results.push(result);
-                                }
-                                Err(e) => {
-                                    error!("Failed to execute fmt for workspace root: {}", e);
-                                    results.push(CargoResult {
-                                        command: CargoCommand::Fmt,
-                                        success: false,
-                                        output: "[workspace] Failed".to_string(),
-                                        errors: vec![format!("workspace: {}", e)],
-                                        warnings: vec![],
-                                        duration: std::time::Duration::ZERO,
-                                    });
-                                }
-                            }
-                        }
-
-                        if config.commands.contains(&CargoCommand::Clippy) {
-                            match self.execute_cargo_clippy_fix(project_dir, config).await {
-                                Ok(mut result) => {
-                                    result.output = format!("[workspace] {}", result.output);
-                                    results.push(result);
-                                }
-                                Err(e) => {
-                                    error!(
-                                        "Failed to execute clippy fix for workspace root: {}",
-                                        e
-                                    );
-                                    results.push(CargoResult {
-                                        command: CargoCommand::Clippy,
-                                        success: false,
-                                        output: "[workspace] Failed".to_string(),
-                                        errors: vec![format!("workspace: {}", e)],
-                                        warnings: vec![],
-                                        duration: std::time::Duration::ZERO,
-                                    });
-                                }
-                            }
-                        }
-                    }
-                    WorkspaceMode::AllMembers | WorkspaceMode::RootAndMembers => {
-                        // Transform all workspace members
-                        for member in &workspace.members {
-                            let member_path = project_dir.join(&member.path);
-
-                            if config.commands.contains(&CargoCommand::Fmt) {
-                                match self.execute_cargo_fmt(&member_path, config).await {
-                                    Ok(mut result) => {
-                                        result.output =
-                                            format!("[{}] {}", member.name, result.output);
-                                        results.push(result);
-                                    }
-                                    Err(e) => {
-                                        error!(
-                                            "Failed to execute fmt for member {}: {}",
-                                            member.name, e
-                                        );
-                                        results.push(CargoResult {
-                                            command: CargoCommand::Fmt,
-                                            success: false,
-                                            output: format!("[{}] Failed", member.name),
-                                            errors: vec![format!("{}: {}", member.name, e)],
-                                            warnings: vec![],
-                                            duration: std::time::Duration::ZERO,
-                                        });
-                                    }
-                                }
-                            }
-
-                            if config.commands.contains(&CargoCommand::Clippy) {
-                                match self.execute_cargo_clippy_fix(&member_path, config).await {
-                                    Ok(mut result) => {
-                                        result.output =
-                                            format!("[{}] {}", member.name, result.output);
-                                        results.push(result);
-                                    }
-                                    Err(e) => {
-                                        error!(
-                                            "Failed to execute clippy fix for member {}: {}",
-                                            member.name, e
-                                        );
-                                        results.push(CargoResult {
-                                            command: CargoCommand::Clippy,
-                                            success: false,
-                                            output: format!("[{}] Failed", member.name),
-                                            errors: vec![format!("{}: {}", member.name, e)],
-                                            warnings: vec![],
-                                            duration: std::time::Duration::ZERO,
-                                        });
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    WorkspaceMode::SelectedMembers => {
-                        // Transform only selected members
-                        let selected_members =
-                            self.get_selected_members(&workspace.members, config);
-                        for member in selected_members {
-                            let member_path = project_dir.join(&member.path);
-
-                            if config.commands.contains(&CargoCommand::Fmt) {
-                                match self.execute_cargo_fmt(&member_path, config).await {
-                                    Ok(mut result) => {
-                                        result.output =
-                                            format!("[{}] {}", member.name, result.output);
-                                        results.push(result);
-                                    }
-                                    Err(e) => {
-                                        error!(
-                                            "Failed to execute fmt for member {}: {}",
-                                            member.name, e
-                                        );
-                                        results.push(CargoResult {
-                                            command: CargoCommand::Fmt,
-                                            success: false,
-                                            output: format!("[{}] Failed", member.name),
-                                            errors: vec![format!("{}: {}", member.name, e)],
-                                            warnings: vec![],
-                                            duration: std::time::Duration::ZERO,
-                                        });
-                                    }
-                                }
-                            }
-
-                            if config.commands.contains(&CargoCommand::Clippy) {
-                                match self.execute_cargo_clippy_fix(&member_path, config).await {
-                                    Ok(mut result) => {
-                                        result.output =
-                                            format!("[{}] {}", member.name, result.output);
-                                        results.push(result);
-                                    }
-                                    Err(e) => {
-                                        error!(
-                                            "Failed to execute clippy fix for member {}: {}",
-                                            member.name, e
-                                        );
-                                        results.push(CargoResult {
-                                            command: CargoCommand::Clippy,
-                                            success: false,
-                                            output: format!("[{}] Failed", member.name),
-                                            errors: vec![format!("{}: {}", member.name, e)],
-                                            warnings: vec![],
-                                            duration: std::time::Duration::ZERO,
-                                        });
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-            None => {
-                // Not a workspace, execute normally
-                if config.commands.contains(&CargoCommand::Fmt) {
-                    match self.execute_cargo_fmt(project_dir, config).await {
-                        Ok(result) => results.push(result),
-                        Err(e) => {
-                            error!("Failed to execute fmt for {}: {}", cargo_file, e);
-                            results.push(CargoResult {
-                                command: CargoCommand::Fmt,
-                                success: false,
-                                output: String::new(),
-                                errors: vec![e.to_string()],
-                                warnings: vec![],
-                                duration: std::time::Duration::ZERO,
-                            });
-                        }
-                    }
-                }
-
-                if config.commands.contains(&CargoCommand::Clippy) {
-                    match self.execute_cargo_clippy_fix(project_dir, config).await {
-                        Ok(result) => results.push(result),
-                        Err(e) => {
-                            error!("Failed to execute clippy fix for {}: {}", cargo_file, e);
-                            results.push(CargoResult {
-                                command: CargoCommand::Clippy,
-                                success: false,
-                                output: String::new(),
-                                errors: vec![e.to_string()],
-                                warnings: vec![],
-                                duration: std::time::Duration::ZERO,
-                            });
-                        }
-                    }
-                }
-            }
+        for command in &config.commands {
+            let result = self
+                .execute_cargo_command(&target.dir, command, config)
+                .await;
+            results.push(target.finish(command, result));
         }
+        results
+    }
 
-        Ok(results)
+    /// Format and apply clippy fixes to one target
+    async fn transform_target(
+        &self,
+        target: &CargoTarget,
+        config: &CargoConfig,
+    ) -> Vec<CargoResult> {
+        let mut results = Vec::new();
+        if config.commands.contains(&CargoCommand::Fmt) {
+            let result = self.execute_cargo_fmt(&target.dir, config).await;
+            results.push(target.finish(&CargoCommand::Fmt, result));
+        }
+        if config.commands.contains(&CargoCommand::Clippy) {
+            let result = self.execute_cargo_clippy_fix(&target.dir, config).await;
+            results.push(target.finish(&CargoCommand::Clippy, result));
+        }
+        results
     }
 
-    /// Get selected members based on filter and exclude configuration
+    /// Filter workspace members by `member_filter` and `exclude_members`,
+    /// matching either the package name or its directory
     fn get_selected_members<'a>(
         &self,
         members: &'a [WorkspaceMember],
         config: &CargoConfig,
     ) -> Vec<&'a WorkspaceMember> {
+        let matches = |member: &WorkspaceMember, names: &[String]| {
+            names
+                .iter()
+                .any(|name| *name == member.name || *name == member.path)
+        };
         members
             .iter()
-            .filter(|member| {
-                // Apply member filter
-                if let Some(ref filter) = config.member_filter {
-                    if !filter.contains(&member.name) {
-                        return false;
-                    }
-                }
-
-                // Apply exclude filter
-                if let Some(ref exclude) = config.exclude_members {
-                    if exclude.contains(&member.name) {
-                        return false;
-                    }
-                }
-
-                true
+            .filter(|member| match &config.member_filter {
+                Some(filter) => matches(member, filter),
+                None => true,
+            })
+            .filter(|member| match &config.exclude_members {
+                Some(exclude) => !matches(member, exclude),
+                None => true,
             })
             .collect()
     }
 
-    /// Run cargo commands for validation (legacy method)
-    async fn run_cargo_commands(
-        &self,
-        cargo_file: &str,
-        config: &CargoConfig,
-    ) -> ActionResult<Vec<CargoResult>> {
-        self.run_cargo_commands_with_workspace(cargo_file, config)
-            .await
-    }
-
-    /// Run transformation commands (legacy method)
-    async fn run_transformation_commands(
-        &self,
-        cargo_file: &str,
-        config: &CargoConfig,
-    ) -> ActionResult<Vec<CargoResult>> {
-        self.run_transformation_commands_with_workspace(cargo_file, config)
-            .await
-    }
-
     /// Execute a specific cargo command
     async fn execute_cargo_command(
         &self,
@@ -994,25 +831,55 @@ impl CargoTool {
 
         let (_cmd, args) = self.build_command_args(command, config);
 
-        let output = tokio::process::Command::new("cargo")
-            .args(&args)
-            .current_dir(project_dir)
-            .output()
+        let mut cargo = tool_command("cargo");
+        cargo.args(&args).current_dir(project_dir);
+        if let Some(jobs) = config.jobs_per_process() {
+            cargo.env(CARGO_BUILD_JOBS_ENV, jobs.to_string());
+        }
+        if *command == CargoCommand::Nextest {
+            cargo.env(NEXTEST_LIBTEST_JSON_ENV, "1");
+        }
+        let output = cargo
+            .limited_output()
             .await
             .map_err(|e| ActionError::ToolExecution {
                 tool: "cargo".to_string(),
                 message: format!("Failed to run cargo {:?}: {}", command, e),
             })?;
 
-        let (errors, warnings) = self.parse_cargo_output(&output, command, config);
+        let (mut errors, warnings, mut diagnostics) =
+            self.parse_cargo_output(&output, command, config);
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+
+        let (output_text, tests) = if *command == CargoCommand::Nextest {
+            let tests = parse_libtest_json(&stdout);
+            for test in tests
+                .iter()
+                .filter(|test| test.status == TestStatus::Failed)
+            {
+                let message = format!("test {} failed", test);
+                let mut diagnostic = Diagnostic::error("cargo", &message).with_code("test_failed");
+                if let Some((file, line, column)) = test.output.as_deref().and_then(panic_location)
+                {
+                    diagnostic = diagnostic.at(&file, Some(line), Some(column));
+                }
+                diagnostics.push(diagnostic);
+                errors.push(message);
+            }
+            (test_summary(&tests), tests)
+        } else {
+            (stdout, Vec::new())
+        };
         let success = output.status.success() && errors.is_empty();
 
         Ok(CargoResult {
             command: command.clone(),
             success,
-            output: String::from_utf8_lossy(&output.stdout).to_string(),
+            output: output_text,
             errors,
             warnings,
+            diagnostics,
+            tests,
             duration: start.elapsed(),
         })
     }
@@ -1033,17 +900,18 @@ impl CargoTool {
             args.push("--verbose");
         }
 
-        let output = tokio::process::Command::new("cargo")
+        let output = tool_command("cargo")
             .args(&args)
             .current_dir(project_dir)
-            .output()
+            .limited_output()
             .await
             .map_err(|e| ActionError::ToolExecution {
                 tool: "cargo".to_string(),
                 message: format!("Failed to run cargo fmt: {}", e),
             })?;
 
-        let (errors, warnings) = self.parse_cargo_output(&output, &CargoCommand::Fmt, config);
+        let (errors, warnings, diagnostics) =
+            self.parse_cargo_output(&output, &CargoCommand::Fmt, config);
         let success = output.status.success() && errors.is_empty();
 
         Ok(CargoResult {
@@ -1052,6 +920,8 @@ impl CargoTool {
             output: "Code formatting completed".to_string(),
             errors,
             warnings,
+            diagnostics,
+            tests: Vec::new(),
             duration: start.elapsed(),
         })
     }
@@ -1072,17 +942,21 @@ impl CargoTool {
             args.push("--verbose");
         }
 
-        let output = tokio::process::Command::new("cargo")
-            .args(&args)
-            .current_dir(project_dir)
-            .output()
+        let mut cargo = tool_command("cargo");
+        cargo.args(&args).current_dir(project_dir);
+        if let Some(jobs) = config.jobs_per_process() {
+            cargo.env(CARGO_BUILD_JOBS_ENV, jobs.to_string());
+        }
+        let output = cargo
+            .limited_output()
             .await
             .map_err(|e| ActionError::ToolExecution {
                 tool: "cargo".to_string(),
                 message: format!("Failed to run cargo clippy --fix: {}", e),
             })?;
 
-        let (errors, warnings) = self.parse_cargo_output(&output, &CargoCommand::Clippy, config);
+        let (errors, warnings, diagnostics) =
+            self.parse_cargo_output(&output, &CargoCommand::Clippy, config);
         let success = output.status.success() && errors.is_empty();
 
         Ok(CargoResult {
@@ -1091,6 +965,8 @@ impl CargoTool {
             output: "Clippy auto-fix completed".to_string(),
             errors,
             warnings,
+            diagnostics,
+            tests: Vec::new(),
             duration: start.elapsed(),
         })
     }
@@ -1146,6 +1022,15 @@ impl CargoTool {
                     args.push("--message-format=json");
                 }
             }
+            CargoCommand::Nextest => {
+                // Test results are always read as libtest JSON; keep going
+                // after a failure so every failing test is reported
+                args.extend(["nextest", "run", "--no-fail-fast"]);
+                args.extend(["--message-format", "libtest-json"]);
+                if config.json_output {
+                    args.extend(["--cargo-message-format", "json"]);
+                }
+            }
         }
 
         if config.verbose {
@@ -1161,41 +1046,33 @@ impl CargoTool {
         output: &std::process::Output,
         _command: &CargoCommand,
         config: &CargoConfig,
-    ) -> (Vec<String>, Vec<String>) {
+    ) -> (Vec<String>, Vec<String>, Vec<Diagnostic>) {
         let mut errors = Vec::new();
         let mut warnings = Vec::new();
+        let mut diagnostics = Vec::new();
 
         if config.json_output {
             // Try to parse JSON output
             if let Ok(json_str) = String::from_utf8(output.stdout.clone()) {
                 for line in json_str.lines() {
-                    if let Ok(json) = serde_json::from_str::<Value>(line) {
-                        if let Some(message) = json.get("message") {
-                            if let Some(level) = message.get("level") {
-                                let msg = message
-                                    .get("message")
-                                    .and_then(|m| m.as_str())
-                                    .unwrap_or("");
-                                let span = message.get("spans").and_then(|s| s.get(0));
-                                let file = span
-                                    .and_then(|s| s.get("file_name"))
-                                    .and_then(|f| f.as_str())
-                                    .unwrap_or("");
-                                let line = span
-                                    .and_then(|s| s.get("line_start"))
-                                    .and_then(|l| l.as_u64())
-                                    .unwrap_or(0);
-
-                                let formatted_msg = format!("{}:{}: {}", file, line, msg);
-
-                                match level.as_str() {
-                                    Some("error") => errors.push(formatted_msg),
-                                    Some("warning") => warnings.push(formatted_msg),
-                                    _ => {}
-                                }
-                            }
-                        }
+                    let Ok(json) = serde_json::from_str::<Value>(line) else {
+                        continue;
+                    };
+                    let Some(diagnostic) = json.get("message").and_then(rustc_diagnostic) else {
+                        continue;
+                    };
+                    let formatted_msg = format!(
+                        "{}:{}: {}",
+                        diagnostic.file.as_deref().unwrap_or(""),
+                        diagnostic.line.unwrap_or(0),
+                        diagnostic.message
+                    );
+                    match diagnostic.severity {
+                        Severity::Error => errors.push(formatted_msg),
+                        Severity::Warning => warnings.push(formatted_msg),
+                        Severity::Info => {}
                     }
+                    diagnostics.push(diagnostic);
                 }
             }
         }
@@ -1205,12 +1082,14 @@ impl CargoTool {
         for line in stderr.lines() {
             if line.contains("error:") {
                 errors.push(line.to_string());
+                diagnostics.push(Diagnostic::error("cargo", line.trim()));
             } else if line.contains("warning:") {
                 warnings.push(line.to_string());
+                diagnostics.push(Diagnostic::warning("cargo", line.trim()));
             }
         }
 
-        (errors, warnings)
+        (errors, warnings, diagnostics)
     }
 }
 
@@ -1221,5 +1100,312 @@ struct PackageInfo {
     package_type: PackageType,
 }
 
+/// Run target jobs at most `concurrency` at a time; the results keep the
+/// order of the jobs
+async fn run_bounded<Fut>(jobs: Vec<Fut>, concurrency: usize) -> Vec<CargoResult>
+where
+    Fut: Future<Output = Vec<CargoResult>>,
+{
+    stream::iter(jobs)
+        .buffered(concurrency.max(1))
+        .concat()
+        .await
+}
+
+/// Diagnostic of a rustc or clippy message from `--message-format=json`,
+/// located at its primary span
+fn rustc_diagnostic(message: &Value) -> Option<Diagnostic> {
+    let severity = match message.get("level")?.as_str()? {
+        "error" => Severity::Error,
+        "warning" => Severity::Warning,
+        "note" | "help" => Severity::Info,
+        _ => return None,
+    };
+    let text = message.get("message").and_then(Value::as_str).unwrap_or("");
+    let mut diagnostic = Diagnostic::new("cargo", severity, text);
+
+    let spans = message.get("spans").and_then(Value::as_array);
+    let span = spans.and_then(|spans| {
+        spans
+            .iter()
+            .find(|span| span.get("is_primary").and_then(Value::as_bool) == Some(true))
+            .or_else(|| spans.first())
+    });
+    if let Some(span) = span {
+        let number = |key: &str| {
+            span.get(key)
+                .and_then(Value::as_u64)
+                .and_then(|n| u32::try_from(n).ok())
+        };
+        if let Some(file) = span.get("file_name").and_then(Value::as_str) {
+            diagnostic = diagnostic.at(file, number("line_start"), number("column_start"));
+        }
+    }
+    if let Some(code) = message
+        .get("code")
+        .and_then(|code| code.get("code"))
+        .and_then(Value::as_str)
+    {
+        diagnostic = diagnostic.with_code(code);
+    }
+    Some(diagnostic)
+}
+
+/// Tests reported by `cargo nextest run --message-format libtest-json`.
+/// Test names take the form `<binary id>$<test path>`.
+fn parse_libtest_json(output: &str) -> Vec<TestOutcome> {
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter(|event| event.get("type").and_then(Value::as_str) == Some("test"))
+        .filter_map(|event| {
+            let status = match event.get("event")?.as_str()? {
+                "ok" => TestStatus::Passed,
+                "failed" => TestStatus::Failed,
+                "ignored" => TestStatus::Ignored,
+                _ => return None, // started
+            };
+            let full_name = event.get("name")?.as_str()?;
+            let (binary, name) = match full_name.split_once('$') {
+                Some((binary, name)) => (Some(binary.to_string()), name.to_string()),
+                None => (None, full_name.to_string()),
+            };
+            Some(TestOutcome {
+                name,
+                binary,
+                status,
+                duration: event
+                    .get("exec_time")
+                    .and_then(Value::as_f64)
+                    .and_then(|secs| std::time::Duration::try_from_secs_f64(secs).ok()),
+                output: event
+                    .get("stdout")
+                    .and_then(Value::as_str)
+                    .filter(|output| !output.is_empty())
+                    .map(str::to_string),
+            })
+        })
+        .collect()
+}
+
+/// Source location of the first panic in a test's output, from a line such
+/// as `thread 'tests::parses' panicked at src/parser.rs:42:9:`
+fn panic_location(output: &str) -> Option<(String, u32, u32)> {
+    let (_, rest) = output.split_once(" panicked at ")?;
+    let location = rest.lines().next()?.trim().trim_end_matches([':', ',']);
+    let mut parts = location.rsplitn(3, ':');
+    let column = parts.next()?.parse().ok()?;
+    let line = parts.next()?.parse().ok()?;
+    let file = parts.next()?.trim_matches('\'');
+    Some((file.to_string(), line, column))
+}
+
+fn test_summary(tests: &[TestOutcome]) -> String {
+    let count = |status| tests.iter().filter(|test| test.status == status).count();
+    format!(
+        "{} tests: {} passed, {} failed, {} ignored",
+        tests.len(),
+        count(TestStatus::Passed),
+        count(TestStatus::Failed),
+        count(TestStatus::Ignored)
+    )
+}
+
+fn parse_manifest(content: &str) -> Result<toml::Table, ActionError> {
+    content
+        .parse::<toml::Table>()
+        .map_err(|e| ActionError::Validation(format!("Failed to parse Cargo.toml: {}", e)))
+}
+
+fn string_array(value: Option<&toml::Value>) -> Vec<String> {
+    value
+        .and_then(toml::Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn package_type(has_lib: bool, has_bin: bool) -> PackageType {
+    match (has_lib, has_bin) {
+        (true, true) => PackageType::Both,
+        (true, false) => PackageType::Library,
+        (false, true) => PackageType::Binary,
+        (false, false) => PackageType::Unknown,
+    }
+}
+
+/// Members and default members reported by `cargo metadata`; default
+/// members are `None` for cargo versions that do not report them
+fn members_from_metadata(metadata: &Value) -> Option<(Vec<WorkspaceMember>, Option<Vec<String>>)> {
+    let root = Path::new(metadata.get("workspace_root")?.as_str()?);
+    let ids = |key: &str| -> Option<Vec<&str>> {
+        metadata
+            .get(key)
+            .and_then(Value::as_array)
+            .map(|ids| ids.iter().filter_map(Value::as_str).collect())
+    };
+    let member_ids = ids("workspace_members")?;
+    let default_ids = ids("workspace_default_members");
+
+    let mut members = Vec::new();
+    let mut default_members = Vec::new();
+    for package in metadata.get("packages")?.as_array()? {
+        let Some(id) = package.get("id").and_then(Value::as_str) else {
+            continue;
+        };
+        if !member_ids.contains(&id) {
+            continue;
+        }
+        let name = package.get("name").and_then(Value::as_str)?.to_string();
+        let manifest_path = Path::new(package.get("manifest_path").and_then(Value::as_str)?);
+        let kinds: Vec<&str> = package
+            .get("targets")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|target| target.get("kind").and_then(Value::as_array))
+            .flatten()
+            .filter_map(Value::as_str)
+            .collect();
+
+        if default_ids.as_ref().is_some_and(|ids| ids.contains(&id)) {
+            default_members.push(name.clone());
+        }
+        members.push(WorkspaceMember {
+            name,
+            path: relative_path(root, manifest_path.parent()?),
+            package_type: package_type(
+                kinds.iter().any(|kind| LIB_KINDS.contains(kind)),
+                kinds.contains(&"bin"),
+            ),
+        });
+    }
+    members.sort_by(|a, b| a.path.cmp(&b.path));
+    Some((members, default_ids.map(|_| default_members)))
+}
+
+/// Default members by cargo's rules: `default-members` when set, otherwise
+/// the root package, otherwise every member
+fn resolve_default_members(
+    root: &Path,
+    workspace: &toml::Table,
+    members: &[WorkspaceMember],
+) -> Vec<String> {
+    let patterns = string_array(workspace.get("default-members"));
+    let names = |filter: &dyn Fn(&WorkspaceMember) -> bool| {
+        members
+            .iter()
+            .filter(|member| filter(member))
+            .map(|member| member.name.clone())
+            .collect()
+    };
+    if !patterns.is_empty() {
+        let paths = expand_members(root, &patterns, &[]);
+        let root_listed = patterns.iter().any(|p| normalize_member_path(p) == ".");
+        return names(&|member| paths.contains(&member.path) || (root_listed && member.is_root()));
+    }
+    if members.iter().any(WorkspaceMember::is_root) {
+        return names(&WorkspaceMember::is_root);
+    }
+    names(&|_| true)
+}
+
+/// Directories with a Cargo.toml matching member `patterns`, which may use
+/// `*` and `?` within path components, minus anything under an `exclude` path
+fn expand_members(root: &Path, patterns: &[String], exclude: &[String]) -> BTreeSet<String> {
+    let exclude: Vec<String> = exclude.iter().map(|p| normalize_member_path(p)).collect();
+    let mut paths = BTreeSet::new();
+
+    for pattern in patterns {
+        let mut candidates = vec![String::new()];
+        for component in normalize_member_path(pattern).split('/') {
+            let mut next = Vec::new();
+            for base in &candidates {
+                if !component.contains(['*', '?']) {
+                    next.push(join_member_path(base, component));
+                    continue;
+                }
+                let Ok(entries) = std::fs::read_dir(root.join(base)) else {
+                    continue;
+                };
+                for entry in entries.flatten() {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    if entry.path().is_dir() && wildcard_match(component, &name) {
+                        next.push(join_member_path(base, &name));
+                    }
+                }
+            }
+            candidates = next;
+        }
+        paths.extend(
+            candidates
+                .into_iter()
+                .filter(|path| root.join(path).join("Cargo.toml").is_file()),
+        );
+    }
+
+    paths.retain(|path| {
+        !exclude
+            .iter()
+            .any(|excluded| Path::new(path).starts_with(excluded))
+    });
+    paths
+}
+
+/// Member path without a leading `./` or trailing `/`
+fn normalize_member_path(path: &str) -> String {
+    let path = path.trim().trim_end_matches('/');
+    let path = path.strip_prefix("./").unwrap_or(path);
+    if path.is_empty() {
+        ".".to_string()
+    } else {
+        path.to_string()
+    }
+}
+
+fn join_member_path(base: &str, component: &str) -> String {
+    match base {
+        "" | "." => component.to_string(),
+        _ => format!("{}/{}", base, component),
+    }
+}
+
+fn relative_path(root: &Path, dir: &Path) -> String {
+    match dir.strip_prefix(root) {
+        Ok(relative) if relative.as_os_str().is_empty() => ".".to_string(),
+        Ok(relative) => relative.to_string_lossy().replace('\\', "/"),
+        Err(_) => dir.to_string_lossy().to_string(),
+    }
+}
+
+/// Glob match of a single path component supporting `*` and `?`
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = star {
+            p = star_p + 1;
+            n = star_n + 1;
+            star = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
 #[cfg(test)]
 mod tests;
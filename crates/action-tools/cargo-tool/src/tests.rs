@@ -60,6 +60,7 @@ async fn test_parse_config_custom() {
     intent.metadata = json!({
         "commands": ["check", "clippy", "test"],
         "parallel": false,
+        "max_parallel": 4,
         "json_output": false,
         "verbose": true,
         "workspace_mode": "all_members",
@@ -77,6 +78,8 @@ async fn test_parse_config_custom() {
         ]
     );
     assert!(!config.parallel);
+    assert_eq!(config.max_parallel, Some(4));
+    assert_eq!(config.concurrency(), 1);
     assert!(!config.json_output);
     assert!(config.verbose);
     assert_eq!(config.workspace_mode, WorkspaceMode::AllMembers);
@@ -140,6 +143,7 @@ async fn test_build_command_args() {
     let config = CargoConfig {
         commands: vec![],
         parallel: true,
+        max_parallel: None,
         json_output: true,
         verbose: false,
         workspace_mode: WorkspaceMode::RootAndMembers,
@@ -196,6 +200,7 @@ async fn test_build_command_args_verbose() {
     let config = CargoConfig {
         commands: vec![],
         parallel: true,
+        max_parallel: None,
         json_output: true,
         verbose: true,
         workspace_mode: WorkspaceMode::RootAndMembers,
@@ -216,6 +221,7 @@ async fn test_parse_cargo_output_json() {
     let config = CargoConfig {
         commands: vec![],
         parallel: true,
+        max_parallel: None,
         json_output: true,
         verbose: false,
         workspace_mode: WorkspaceMode::RootAndMembers,
@@ -225,7 +231,7 @@ async fn test_parse_cargo_output_json() {
 
     // Mock JSON output
     let json_output = r#"{"message":{"level":"error","message":"expected `;`, found `}`","spans":[{"file_name":"src/main.rs","line_start":15}]}}
-{"message":{"level":"warning","message":"unused variable: `x`","spans":[{"file_name":"src/lib.rs","line_start":10}]}}"#;
+{"message":{"level":"warning","message":"unused variable: `x`","code":{"code":"unused_variables"},"spans":[{"file_name":"src/macros.rs","line_start":3,"column_start":1,"is_primary":false},{"file_name":"src/lib.rs","line_start":10,"column_start":9,"is_primary":true}]}}"#;
 
     let output = std::process::Output {
         status: std::process::ExitStatus::from_raw(1),
@@ -233,12 +239,21 @@ async fn test_parse_cargo_output_json() {
         stderr: vec![],
     };
 
-    let (errors, warnings) = tool.parse_cargo_output(&output, &CargoCommand::Check, &config);
+    let (errors, warnings, diagnostics) =
+        tool.parse_cargo_output(&output, &CargoCommand::Check, &config);
 
     assert_eq!(errors.len(), 1);
     assert_eq!(warnings.len(), 1);
     assert!(errors[0].contains("src/main.rs:15"));
     assert!(warnings[0].contains("src/lib.rs:10"));
+    assert_eq!(
+        diagnostics[1],
+        Diagnostic::warning("cargo", "unused variable: `x`")
+            .at("src/lib.rs", Some(10), Some(9))
+            .with_code("unused_variables")
+    );
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert_eq!(diagnostics[0].column, None);
 }
 
 #[tokio::test]
@@ -247,6 +262,7 @@ async fn test_parse_cargo_output_stderr() {
     let config = CargoConfig {
         commands: vec![],
         parallel: true,
+        max_parallel: None,
         json_output: false,
         verbose: false,
         workspace_mode: WorkspaceMode::RootAndMembers,
@@ -260,10 +276,12 @@ async fn test_parse_cargo_output_stderr() {
         stderr: b"error: expected `;`, found `}`\nwarning: unused variable: `x`".to_vec(),
     };
 
-    let (errors, warnings) = tool.parse_cargo_output(&output, &CargoCommand::Check, &config);
+    let (errors, warnings, diagnostics) =
+        tool.parse_cargo_output(&output, &CargoCommand::Check, &config);
 
     assert_eq!(errors.len(), 1);
     assert_eq!(warnings.len(), 1);
+    assert_eq!(diagnostics.len(), 2);
     assert!(errors[0].contains("error:"));
     assert!(warnings[0].contains("warning:"));
 }
@@ -313,6 +331,8 @@ async fn test_cargo_result_structure() {
         output: "Success".to_string(),
         errors: vec![],
         warnings: vec!["Warning".to_string()],
+        diagnostics: vec![],
+        tests: vec![],
         duration: std::time::Duration::from_secs(1),
     };
 
@@ -414,6 +434,8 @@ async fn test_workspace_info_structure() {
                 package_type: PackageType::Binary,
             },
         ],
+        default_members: vec!["core".to_string(), "api".to_string()],
+        exclude: vec![],
         workspace_config: Some(json!({
             "resolver": "2"
         })),
@@ -451,6 +473,7 @@ async fn test_get_selected_members() {
     let config = CargoConfig {
         commands: vec![],
         parallel: true,
+        max_parallel: None,
         json_output: true,
         verbose: false,
         workspace_mode: WorkspaceMode::SelectedMembers,
@@ -465,6 +488,7 @@ async fn test_get_selected_members() {
     let config = CargoConfig {
         commands: vec![],
         parallel: true,
+        max_parallel: None,
         json_output: true,
         verbose: false,
         workspace_mode: WorkspaceMode::SelectedMembers,
@@ -481,6 +505,7 @@ async fn test_get_selected_members() {
     let config = CargoConfig {
         commands: vec![],
         parallel: true,
+        max_parallel: None,
         json_output: true,
         verbose: false,
         workspace_mode: WorkspaceMode::SelectedMembers,
@@ -497,6 +522,7 @@ async fn test_get_selected_members() {
     let config = CargoConfig {
         commands: vec![],
         parallel: true,
+        max_parallel: None,
         json_output: true,
         verbose: false,
         workspace_mode: WorkspaceMode::SelectedMembers,
@@ -512,6 +538,17 @@ async fn test_get_selected_members() {
     assert_eq!(selected.len(), 2);
     assert_eq!(selected[0].name, "core");
     assert_eq!(selected[1].name, "api");
+
+    // Members can also be named by path
+    let config = CargoConfig {
+        workspace_mode: WorkspaceMode::SelectedMembers,
+        member_filter: Some(vec!["crates/api".to_string()]),
+        ..CargoConfig::default()
+    };
+
+    let selected = tool.get_selected_members(&members, &config);
+    assert_eq!(selected.len(), 1);
+    assert_eq!(selected[0].name, "api");
 }
 
 #[tokio::test]
@@ -589,3 +626,250 @@ members = ["crates/core"]
     let config = tool.extract_workspace_config(cargo_content);
     assert!(config.is_none());
 }
+
+#[tokio::test]
+async fn test_detect_workspace_globs_and_excludes() {
+    let tool = CargoTool;
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::write(
+        root.join("Cargo.toml"),
+        r#"[package]
+name = "app"
+version = "0.1.0"
+edition = "2021"
+
+[workspace]
+members = ["crates/*", "tools/gen"]
+exclude = ["crates/scratch"]
+default-members = [".", "crates/core"]
+resolver = "2"
+"#,
+    )
+    .unwrap();
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::write(root.join("src/main.rs"), "fn main() {}\n").unwrap();
+    for (path, file) in [
+        ("crates/core", "lib.rs"),
+        ("crates/cli", "main.rs"),
+        ("crates/scratch", "lib.rs"),
+        ("tools/gen", "main.rs"),
+    ] {
+        let name = path.rsplit('/').next().unwrap();
+        std::fs::create_dir_all(root.join(path).join("src")).unwrap();
+        std::fs::write(
+            root.join(path).join("Cargo.toml"),
+            format!(
+                "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+                name
+            ),
+        )
+        .unwrap();
+        std::fs::write(root.join(path).join("src").join(file), "").unwrap();
+    }
+
+    let cargo_file = root.join("Cargo.toml").to_string_lossy().to_string();
+    let workspace = tool.detect_workspace(&cargo_file).await.unwrap().unwrap();
+    let members: Vec<(&str, &str, &PackageType)> = workspace
+        .members
+        .iter()
+        .map(|m| (m.name.as_str(), m.path.as_str(), &m.package_type))
+        .collect();
+    assert_eq!(
+        members,
+        vec![
+            ("app", ".", &PackageType::Binary),
+            ("cli", "crates/cli", &PackageType::Binary),
+            ("core", "crates/core", &PackageType::Library),
+            ("gen", "tools/gen", &PackageType::Binary),
+        ]
+    );
+    let mut default_members = workspace.default_members.clone();
+    default_members.sort();
+    assert_eq!(default_members, vec!["app", "core"]);
+    assert_eq!(workspace.exclude, vec!["crates/scratch"]);
+    assert_eq!(workspace.workspace_config.unwrap()["resolver"], "2");
+
+    // The root run covers the root package, transformations skip the root
+    let config = CargoConfig::default();
+    let labels = |targets: Vec<CargoTarget>| -> Vec<String> {
+        targets.into_iter().filter_map(|t| t.label).collect()
+    };
+    let targets = tool
+        .resolve_targets(&cargo_file, &config, false)
+        .await
+        .unwrap();
+    assert_eq!(labels(targets), vec!["workspace", "cli", "core", "gen"]);
+    let targets = tool
+        .resolve_targets(&cargo_file, &config, true)
+        .await
+        .unwrap();
+    assert_eq!(labels(targets), vec!["app", "cli", "core", "gen"]);
+}
+
+#[tokio::test]
+async fn test_members_from_metadata() {
+    let metadata = json!({
+        "workspace_root": "/ws",
+        "workspace_members": ["path+file:///ws/crates/core#0.1.0", "path+file:///ws#app@0.1.0"],
+        "workspace_default_members": ["path+file:///ws#app@0.1.0"],
+        "packages": [
+            {
+                "id": "path+file:///ws/crates/core#0.1.0",
+                "name": "core",
+                "manifest_path": "/ws/crates/core/Cargo.toml",
+                "targets": [{"kind": ["proc-macro"]}]
+            },
+            {
+                "id": "path+file:///ws#app@0.1.0",
+                "name": "app",
+                "manifest_path": "/ws/Cargo.toml",
+                "targets": [{"kind": ["lib"]}, {"kind": ["bin"]}]
+            }
+        ]
+    });
+
+    let (members, default_members) = members_from_metadata(&metadata).unwrap();
+    assert_eq!(members.len(), 2);
+    assert!(members[0].is_root());
+    assert_eq!(members[0].package_type, PackageType::Both);
+    assert_eq!(members[1].path, "crates/core");
+    assert_eq!(members[1].package_type, PackageType::Library);
+    assert_eq!(default_members, Some(vec!["app".to_string()]));
+}
+
+#[tokio::test]
+async fn test_run_bounded_limits_concurrency() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let targets: Vec<CargoTarget> = (0..8)
+        .map(|i| CargoTarget::new(std::path::Path::new("."), Some(&format!("crate-{}", i))))
+        .collect();
+    let running = AtomicUsize::new(0);
+    let peak = AtomicUsize::new(0);
+
+    let config = CargoConfig {
+        max_parallel: Some(3),
+        ..CargoConfig::default()
+    };
+    let jobs: Vec<_> = targets
+        .iter()
+        .map(|target| {
+            let (running, peak) = (&running, &peak);
+            async move {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+                vec![target.finish(
+                    &CargoCommand::Check,
+                    Err(ActionError::Validation("skipped".to_string())),
+                )]
+            }
+        })
+        .collect();
+    let results = run_bounded(jobs, config.concurrency()).await;
+
+    assert_eq!(peak.load(Ordering::SeqCst), 3);
+    assert_eq!(results.len(), 8);
+    assert_eq!(results[0].output, "[crate-0] Failed");
+    assert_eq!(
+        results[7].errors,
+        vec!["crate-7: Validation error: skipped"]
+    );
+
+    let config = CargoConfig {
+        parallel: false,
+        max_parallel: Some(3),
+        ..CargoConfig::default()
+    };
+    assert_eq!(config.concurrency(), 1);
+    assert_eq!(config.jobs_per_process(), None);
+
+    // By default a couple of processes split the CPUs between them
+    let config = CargoConfig::default();
+    assert_eq!(config.concurrency(), DEFAULT_MAX_PARALLEL);
+    let cpus = std::thread::available_parallelism().unwrap().get();
+    assert_eq!(
+        config.jobs_per_process(),
+        Some((cpus / DEFAULT_MAX_PARALLEL).max(1))
+    );
+}
+
+#[test]
+fn test_nextest_command_and_config() {
+    let tool = CargoTool;
+    let mut intent = ActionIntent::new(
+        "nextest",
+        ActionType::Test,
+        "Run tests with nextest",
+        vec!["Cargo.toml".to_string()],
+        SafetyLevel::Low,
+    );
+    intent.metadata = json!({ "commands": ["nextest"] });
+    let config = tool.parse_config(&intent);
+    assert_eq!(config.commands, vec![CargoCommand::Nextest]);
+
+    let (_, args) = tool.build_command_args(&CargoCommand::Nextest, &config);
+    assert_eq!(
+        args,
+        vec![
+            "nextest",
+            "run",
+            "--no-fail-fast",
+            "--message-format",
+            "libtest-json",
+            "--cargo-message-format",
+            "json"
+        ]
+    );
+}
+
+#[test]
+fn test_parse_libtest_json() {
+    let output = [
+        r#"{"type":"suite","event":"started","test_count":3,"nextest":{"crate":"my-crate","test_binary":"my-crate","kind":"lib"}}"#,
+        r#"{"type":"test","event":"started","name":"my-crate$parser::tests::parses"}"#,
+        r#"{"type":"test","event":"ok","name":"my-crate$parser::tests::parses","exec_time":0.012}"#,
+        r#"{"type":"test","event":"failed","name":"my-crate$parser::tests::rejects","exec_time":0.004,"stdout":"\nthread 'parser::tests::rejects' panicked at src/parser.rs:42:9:\nassertion failed: result.is_err()\n"}"#,
+        r#"{"type":"test","event":"ignored","name":"my-crate::integration$slow_roundtrip"}"#,
+        r#"{"type":"suite","event":"failed","passed":1,"failed":1,"ignored":1,"exec_time":0.02}"#,
+    ]
+    .join("\n");
+
+    let tests = parse_libtest_json(&output);
+    assert_eq!(tests.len(), 3);
+    assert_eq!(tests[0].name, "parser::tests::parses");
+    assert_eq!(tests[0].binary.as_deref(), Some("my-crate"));
+    assert_eq!(tests[0].status, TestStatus::Passed);
+    assert_eq!(
+        tests[0].duration,
+        Some(std::time::Duration::from_millis(12))
+    );
+    assert_eq!(tests[1].status, TestStatus::Failed);
+    assert_eq!(tests[2].status, TestStatus::Ignored);
+    assert_eq!(tests[2].to_string(), "my-crate::integration slow_roundtrip");
+    assert_eq!(
+        test_summary(&tests),
+        "3 tests: 1 passed, 1 failed, 1 ignored"
+    );
+
+    let result = CargoResult {
+        command: CargoCommand::Nextest,
+        success: false,
+        output: String::new(),
+        errors: vec![],
+        warnings: vec![],
+        diagnostics: vec![],
+        tests,
+        duration: std::time::Duration::ZERO,
+    };
+    assert_eq!(
+        result.failed_tests().collect::<Vec<_>>(),
+        vec!["my-crate parser::tests::rejects"]
+    );
+    assert_eq!(
+        panic_location(result.tests[1].output.as_deref().unwrap()),
+        Some(("src/parser.rs".to_string(), 42, 9))
+    );
+}
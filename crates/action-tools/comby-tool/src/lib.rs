@@ -15,8 +15,10 @@
  */
 
 use async_trait::async_trait;
+use rhema_action_tool::platform::tool_command;
+use rhema_action_tool::{tool_available, LimitedCommand};
 use rhema_action_tool::{ActionError, ActionIntent, ActionResult, SafetyLevel};
-use rhema_action_tool::{ToolResult, TransformationTool};
+use rhema_action_tool::{ToolResult, ToolchainProfile, TransformationTool};
 use tracing::{info, warn};
 
 /// Comby transformation tool
@@ -27,6 +29,10 @@ impl TransformationTool for CombyTool {
     async fn execute(&self, intent: &ActionIntent) -> ActionResult<ToolResult> {
         info!("Executing comby for intent: {}", intent.id);
 
+        if intent.dry_run {
+            return self.preview(intent).await;
+        }
+
         let start = std::time::Instant::now();
 
         // Extract file paths from intent
@@ -61,6 +67,9 @@ impl TransformationTool for CombyTool {
             errors,
             warnings,
             duration: start.elapsed(),
+            cached: false,
+            diagnostics: Vec::new(),
+            failed_tests: Vec::new(),
         })
     }
 
@@ -68,6 +77,11 @@ impl TransformationTool for CombyTool {
         true // Comby supports many languages
     }
 
+    /// Comby matches any language, so it only runs when named explicitly
+    fn applies_to(&self, _profile: &ToolchainProfile) -> bool {
+        false
+    }
+
     fn safety_level(&self) -> SafetyLevel {
         SafetyLevel::Medium
     }
@@ -81,13 +95,7 @@ impl TransformationTool for CombyTool {
     }
 
     async fn is_available(&self) -> bool {
-        // Check if comby is installed
-        tokio::process::Command::new("comby")
-            .arg("--version")
-            .output()
-            .await
-            .map(|output| output.status.success())
-            .unwrap_or(false)
+        tool_available("comby").await
     }
 }
 
@@ -139,9 +147,9 @@ impl CombyTool {
         }
 
         // Execute comby
-        let output = tokio::process::Command::new("comby")
+        let output = tool_command("comby")
             .args(&[pattern, rewrite, file_path, "--in-place", "--timeout", "30"])
-            .output()
+            .limited_output()
             .await
             .map_err(|e| ActionError::ToolExecution {
                 tool: "comby".to_string(),
@@ -0,0 +1,702 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use async_trait::async_trait;
+use rhema_action_tool::platform::{normalize_path, relative_path, tool_command};
+use rhema_action_tool::{tool_available, LimitedCommand};
+use rhema_action_tool::{ActionError, ActionIntent, ActionResult};
+use rhema_action_tool::{SafetyTool, ToolResult};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Section of `.rhema/repository.yaml` holding the guard policy
+pub const CONFIG_SECTION: &str = "dependency_guard";
+
+/// Intent metadata key listing the dependencies an action may add, e.g.
+/// `["serde", "npm:lodash", "pypi:requests"]`
+pub const DECLARED_DEPENDENCIES_KEY: &str = "allowed_dependencies";
+
+/// What happens to undeclared dependency additions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GuardMode {
+    /// Fail the safety check
+    Block,
+    /// Pass only when the intent goes through an approval workflow
+    RequireApproval,
+}
+
+/// Dependency guard policy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DependencyGuardConfig {
+    pub mode: GuardMode,
+
+    /// Dependencies any action may add without declaring them
+    pub allowlist: Vec<String>,
+
+    /// Treat dependency removals like undeclared additions
+    pub block_removals: bool,
+}
+
+impl Default for DependencyGuardConfig {
+    fn default() -> Self {
+        Self {
+            mode: GuardMode::Block,
+            allowlist: Vec::new(),
+            block_removals: false,
+        }
+    }
+}
+
+impl DependencyGuardConfig {
+    /// Load the policy from the repository config and policy bundle, falling
+    /// back to defaults
+    pub fn load(repo_root: &Path) -> ActionResult<Self> {
+        let value = rhema_core::policy::repository_config(repo_root)
+            .map_err(|e| ActionError::Configuration(e.to_string()))?;
+        match value.get(CONFIG_SECTION) {
+            Some(section) => serde_yaml::from_value(section.clone()).map_err(|e| {
+                ActionError::Configuration(format!(
+                    "Invalid {} section in the repository config: {}",
+                    CONFIG_SECTION, e
+                ))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+/// Package ecosystem of a manifest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Ecosystem {
+    Cargo,
+    Npm,
+    Python,
+}
+
+impl Ecosystem {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.file_name()?.to_str()? {
+            "Cargo.toml" => Some(Ecosystem::Cargo),
+            "package.json" => Some(Ecosystem::Npm),
+            "pyproject.toml" => Some(Ecosystem::Python),
+            _ => None,
+        }
+    }
+
+    /// Prefix used when declaring dependencies, e.g. `npm:lodash`
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            Ecosystem::Cargo => "cargo",
+            Ecosystem::Npm => "npm",
+            Ecosystem::Python => "pypi",
+        }
+    }
+
+    fn normalize_name(&self, name: &str) -> String {
+        match self {
+            // PEP 503 name normalization
+            Ecosystem::Python => name.to_lowercase().replace(['_', '.'], "-"),
+            Ecosystem::Cargo | Ecosystem::Npm => name.to_string(),
+        }
+    }
+}
+
+/// Dependencies keyed by `(section, name)` with their version requirement
+pub type DependencySet = BTreeMap<(String, String), String>;
+
+/// Parse the dependency sections of a manifest
+pub fn parse_manifest(ecosystem: Ecosystem, content: &str) -> ActionResult<DependencySet> {
+    if content.trim().is_empty() {
+        return Ok(DependencySet::new());
+    }
+    match ecosystem {
+        Ecosystem::Cargo => parse_cargo(&parse_toml(content)?),
+        Ecosystem::Npm => parse_package_json(content),
+        Ecosystem::Python => parse_pyproject(&parse_toml(content)?),
+    }
+}
+
+fn parse_toml(content: &str) -> ActionResult<toml::Table> {
+    content
+        .parse::<toml::Table>()
+        .map_err(|e| ActionError::Validation(format!("Invalid TOML manifest: {}", e)))
+}
+
+const CARGO_SECTIONS: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+fn parse_cargo(manifest: &toml::Table) -> ActionResult<DependencySet> {
+    let mut deps = DependencySet::new();
+    for section in CARGO_SECTIONS {
+        collect_toml_table(&mut deps, section, manifest.get(section), Ecosystem::Cargo);
+    }
+    if let Some(workspace) = manifest.get("workspace").and_then(|w| w.as_table()) {
+        collect_toml_table(
+            &mut deps,
+            "workspace.dependencies",
+            workspace.get("dependencies"),
+            Ecosystem::Cargo,
+        );
+    }
+    if let Some(targets) = manifest.get("target").and_then(|t| t.as_table()) {
+        for (target, table) in targets {
+            for section in CARGO_SECTIONS {
+                collect_toml_table(
+                    &mut deps,
+                    &format!("target.{}.{}", target, section),
+                    table.get(section),
+                    Ecosystem::Cargo,
+                );
+            }
+        }
+    }
+    Ok(deps)
+}
+
+fn collect_toml_table(
+    deps: &mut DependencySet,
+    section: &str,
+    table: Option<&toml::Value>,
+    ecosystem: Ecosystem,
+) {
+    let Some(table) = table.and_then(|t| t.as_table()) else {
+        return;
+    };
+    for (name, spec) in table {
+        if ecosystem == Ecosystem::Python && name == "python" {
+            continue;
+        }
+        deps.insert(
+            (section.to_string(), ecosystem.normalize_name(name)),
+            toml_version(spec),
+        );
+    }
+}
+
+fn toml_version(spec: &toml::Value) -> String {
+    match spec {
+        toml::Value::String(version) => version.clone(),
+        toml::Value::Table(table) => {
+            if let Some(version) = table.get("version").and_then(|v| v.as_str()) {
+                version.to_string()
+            } else if let Some(path) = table.get("path").and_then(|v| v.as_str()) {
+                format!("path:{}", path)
+            } else if let Some(git) = table.get("git").and_then(|v| v.as_str()) {
+                format!("git:{}", git)
+            } else if table.get("workspace").and_then(|v| v.as_bool()) == Some(true) {
+                "workspace".to_string()
+            } else {
+                "*".to_string()
+            }
+        }
+        other => other.to_string(),
+    }
+}
+
+const NPM_SECTIONS: [&str; 4] = [
+    "dependencies",
+    "devDependencies",
+    "peerDependencies",
+    "optionalDependencies",
+];
+
+fn parse_package_json(content: &str) -> ActionResult<DependencySet> {
+    let manifest: serde_json::Value = serde_json::from_str(content)?;
+    let mut deps = DependencySet::new();
+    for section in NPM_SECTIONS {
+        if let Some(table) = manifest.get(section).and_then(|s| s.as_object()) {
+            for (name, version) in table {
+                deps.insert(
+                    (section.to_string(), name.clone()),
+                    version.as_str().unwrap_or("*").to_string(),
+                );
+            }
+        }
+    }
+    Ok(deps)
+}
+
+fn parse_pyproject(manifest: &toml::Table) -> ActionResult<DependencySet> {
+    let mut deps = DependencySet::new();
+
+    if let Some(project) = manifest.get("project").and_then(|p| p.as_table()) {
+        collect_pep508(
+            &mut deps,
+            "project.dependencies",
+            project.get("dependencies"),
+        );
+        if let Some(groups) = project
+            .get("optional-dependencies")
+            .and_then(|g| g.as_table())
+        {
+            for (group, requirements) in groups {
+                collect_pep508(
+                    &mut deps,
+                    &format!("project.optional-dependencies.{}", group),
+                    Some(requirements),
+                );
+            }
+        }
+    }
+
+    if let Some(poetry) = manifest
+        .get("tool")
+        .and_then(|t| t.get("poetry"))
+        .and_then(|p| p.as_table())
+    {
+        for section in ["dependencies", "dev-dependencies"] {
+            collect_toml_table(
+                &mut deps,
+                &format!("tool.poetry.{}", section),
+                poetry.get(section),
+                Ecosystem::Python,
+            );
+        }
+        if let Some(groups) = poetry.get("group").and_then(|g| g.as_table()) {
+            for (group, table) in groups {
+                collect_toml_table(
+                    &mut deps,
+                    &format!("tool.poetry.group.{}.dependencies", group),
+                    table.get("dependencies"),
+                    Ecosystem::Python,
+                );
+            }
+        }
+    }
+
+    Ok(deps)
+}
+
+/// Collect PEP 508 requirement strings such as `requests>=2.31; python_version>"3.8"`
+fn collect_pep508(deps: &mut DependencySet, section: &str, requirements: Option<&toml::Value>) {
+    let Some(requirements) = requirements.and_then(|r| r.as_array()) else {
+        return;
+    };
+    for requirement in requirements.iter().filter_map(|r| r.as_str()) {
+        let requirement = requirement.split(';').next().unwrap_or_default().trim();
+        let name_end = requirement
+            .find(|c: char| !(c.is_alphanumeric() || matches!(c, '-' | '_' | '.')))
+            .unwrap_or(requirement.len());
+        let (name, rest) = requirement.split_at(name_end);
+        if name.is_empty() {
+            continue;
+        }
+        let rest = rest.trim();
+        // Drop extras, e.g. `uvicorn[standard]>=0.23`
+        let version = match rest.strip_prefix('[') {
+            Some(after) => after.split_once(']').map_or("", |(_, v)| v).trim(),
+            None => rest,
+        };
+        deps.insert(
+            (section.to_string(), Ecosystem::Python.normalize_name(name)),
+            if version.is_empty() { "*" } else { version }.to_string(),
+        );
+    }
+}
+
+/// How a dependency changed between two versions of a manifest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Upgraded,
+    Downgraded,
+    /// Requirement changed in a way that is not a plain version bump
+    Changed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyChange {
+    pub ecosystem: Ecosystem,
+    pub manifest: String,
+    pub section: String,
+    pub name: String,
+    pub kind: ChangeKind,
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+impl std::fmt::Display for DependencyChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let what = match (&self.from, &self.to) {
+            (Some(from), Some(to)) => format!("{} → {}", from, to),
+            (None, Some(to)) => to.clone(),
+            (Some(from), None) => from.clone(),
+            (None, None) => String::new(),
+        };
+        write!(
+            f,
+            "{:?} {}:{} ({}) in {} [{}]",
+            self.kind,
+            self.ecosystem.prefix(),
+            self.name,
+            what,
+            self.manifest,
+            self.section
+        )
+    }
+}
+
+/// Classify every dependency that differs between `before` and `after`
+pub fn diff_dependencies(
+    ecosystem: Ecosystem,
+    manifest: &str,
+    before: &DependencySet,
+    after: &DependencySet,
+) -> Vec<DependencyChange> {
+    let change =
+        |(section, name): &(String, String), kind, from: Option<&String>, to: Option<&String>| {
+            DependencyChange {
+                ecosystem,
+                manifest: manifest.to_string(),
+                section: section.clone(),
+                name: name.clone(),
+                kind,
+                from: from.cloned(),
+                to: to.cloned(),
+            }
+        };
+
+    let mut changes = Vec::new();
+    for (key, version) in after {
+        match before.get(key) {
+            None => changes.push(change(key, ChangeKind::Added, None, Some(version))),
+            Some(previous) if previous != version => {
+                let kind = match compare_versions(previous, version) {
+                    Some(Ordering::Less) => ChangeKind::Upgraded,
+                    Some(Ordering::Greater) => ChangeKind::Downgraded,
+                    _ => ChangeKind::Changed,
+                };
+                changes.push(change(key, kind, Some(previous), Some(version)));
+            }
+            Some(_) => {}
+        }
+    }
+    for (key, version) in before {
+        if !after.contains_key(key) {
+            changes.push(change(key, ChangeKind::Removed, Some(version), None));
+        }
+    }
+    changes
+}
+
+/// Compare the leading numeric components of two version requirements
+fn compare_versions(a: &str, b: &str) -> Option<Ordering> {
+    fn components(version: &str) -> Option<Vec<u64>> {
+        let version = version.trim_start_matches(|c: char| !c.is_ascii_digit());
+        let numbers: Vec<u64> = version
+            .split(|c: char| !c.is_ascii_digit())
+            .take_while(|part| !part.is_empty())
+            .take(3)
+            .map(|part| part.parse().ok())
+            .collect::<Option<_>>()?;
+        (!numbers.is_empty()).then_some(numbers)
+    }
+    let (a, b) = (components(a)?, components(b)?);
+    Some(a.cmp(&b)).filter(|ordering| ordering.is_ne())
+}
+
+/// Whether a declaration such as `serde` or `npm:lodash` covers the change
+fn is_declared(declarations: &[String], change: &DependencyChange) -> bool {
+    declarations.iter().any(|declaration| {
+        let (prefix, name) = match declaration.split_once(':') {
+            Some((prefix, name)) => (Some(prefix), name),
+            None => (None, declaration.as_str()),
+        };
+        prefix.is_none_or(|p| p == change.ecosystem.prefix())
+            && change.ecosystem.normalize_name(name.trim()) == change.name
+    })
+}
+
+/// Blocks dependency additions that the action intent did not declare.
+///
+/// Manifests in the intent's scope are diffed against `HEAD`. Additions must be
+/// listed in the intent's `allowed_dependencies` metadata or the repository
+/// allowlist; otherwise the check fails, or, in `require_approval` mode, passes
+/// with a warning only when the intent requires approval.
+#[derive(Default)]
+pub struct DependencyGuardTool {
+    config: Option<DependencyGuardConfig>,
+}
+
+impl DependencyGuardTool {
+    pub fn with_config(config: DependencyGuardConfig) -> Self {
+        Self {
+            config: Some(config),
+        }
+    }
+}
+
+#[async_trait]
+impl SafetyTool for DependencyGuardTool {
+    async fn check(&self, intent: &ActionIntent) -> ActionResult<ToolResult> {
+        info!(
+            "Running dependency change detection for intent: {}",
+            intent.id
+        );
+        let start = std::time::Instant::now();
+
+        let cwd = std::env::current_dir()?;
+        let manifests: Vec<(PathBuf, Ecosystem)> = intent
+            .scope
+            .iter()
+            .map(|file| cwd.join(file))
+            .filter_map(|path| Ecosystem::from_path(&path).map(|eco| (path, eco)))
+            .collect();
+
+        let mut changes = Vec::new();
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+
+        let declared: Vec<String> = intent
+            .metadata
+            .get(DECLARED_DEPENDENCIES_KEY)
+            .and_then(|d| d.as_array())
+            .map(|d| {
+                d.iter()
+                    .filter_map(|v| v.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let approval_required = intent
+            .approval_workflow
+            .get("required")
+            .and_then(|r| r.as_bool())
+            .unwrap_or(false);
+
+        let mut detected = Vec::new();
+        for (path, ecosystem) in &manifests {
+            let Some((repo_root, previous)) = head_version(path).await else {
+                warnings.push(format!(
+                    "Cannot diff {}: no git history to compare against",
+                    path.display()
+                ));
+                continue;
+            };
+            let config = match &self.config {
+                Some(config) => config.clone(),
+                None => DependencyGuardConfig::load(&repo_root)?,
+            };
+            let relative = relative_path(path, &repo_root);
+            let current = match std::fs::read_to_string(path) {
+                Ok(content) => content,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+                Err(e) => return Err(e.into()),
+            };
+
+            let before = parse_manifest(*ecosystem, &previous.unwrap_or_default())?;
+            let after = parse_manifest(*ecosystem, &current)?;
+
+            for change in diff_dependencies(*ecosystem, &relative, &before, &after) {
+                let gated = match change.kind {
+                    ChangeKind::Added => true,
+                    ChangeKind::Removed => config.block_removals,
+                    _ => false,
+                };
+                let allowed =
+                    is_declared(&declared, &change) || is_declared(&config.allowlist, &change);
+
+                if gated && !allowed {
+                    match config.mode {
+                        GuardMode::Block => {
+                            errors.push(format!("Undeclared dependency change: {}", change))
+                        }
+                        GuardMode::RequireApproval if approval_required => warnings.push(format!(
+                            "Dependency change requires approval: {}",
+                            change
+                        )),
+                        GuardMode::RequireApproval => errors.push(format!(
+                            "Dependency change requires approval but the intent has no approval workflow: {}",
+                            change
+                        )),
+                    }
+                } else if change.kind == ChangeKind::Removed
+                    || change.kind == ChangeKind::Downgraded
+                {
+                    warnings.push(change.to_string());
+                }
+                changes.push(change.to_string());
+                detected.push(change);
+            }
+        }
+
+        Ok(ToolResult {
+            success: errors.is_empty(),
+            changes,
+            output: format!(
+                "Detected {} dependency changes in {} manifests",
+                detected.len(),
+                manifests.len()
+            ),
+            errors,
+            warnings,
+            duration: start.elapsed(),
+            cached: false,
+            diagnostics: Vec::new(),
+            failed_tests: Vec::new(),
+        })
+    }
+
+    fn name(&self) -> &str {
+        "dependency_check"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn is_available(&self) -> bool {
+        tool_available("git").await
+    }
+}
+
+/// Repository root and `HEAD` content of a file; `None` outside a git repository.
+/// The content is `None` when the file is not in `HEAD`.
+async fn head_version(path: &Path) -> Option<(PathBuf, Option<String>)> {
+    let dir = path.parent()?;
+    let git = |args: Vec<String>| async move {
+        let output = tool_command("git")
+            .arg("-C")
+            .arg(dir)
+            .args(&args)
+            .limited_output()
+            .await
+            .map_err(|e| warn!("Failed to run git: {}", e))
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+    };
+
+    let root = PathBuf::from(
+        git(vec!["rev-parse".into(), "--show-toplevel".into()])
+            .await?
+            .trim(),
+    );
+    let relative = normalize_path(
+        &path
+            .canonicalize()
+            .unwrap_or_else(|_| path.to_path_buf())
+            .strip_prefix(root.canonicalize().ok()?)
+            .ok()?
+            .to_string_lossy(),
+    );
+    let previous = git(vec!["show".into(), format!("HEAD:{}", relative)]).await;
+    Some((root, previous))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changes(ecosystem: Ecosystem, before: &str, after: &str) -> Vec<DependencyChange> {
+        diff_dependencies(
+            ecosystem,
+            "manifest",
+            &parse_manifest(ecosystem, before).unwrap(),
+            &parse_manifest(ecosystem, after).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_cargo_changes_are_classified() {
+        let before = r#"
+[dependencies]
+serde = "1.0"
+tokio = { version = "1.28", features = ["full"] }
+log = "0.4"
+
+[dev-dependencies]
+tempfile = "3.8"
+"#;
+        let after = r#"
+[dependencies]
+serde = "1.0"
+tokio = { version = "1.35", features = ["full"] }
+reqwest = "0.11"
+
+[dev-dependencies]
+tempfile = "3.2"
+"#;
+        let changes = changes(Ecosystem::Cargo, before, after);
+        let kind = |name: &str| changes.iter().find(|c| c.name == name).map(|c| c.kind);
+
+        assert_eq!(changes.len(), 4);
+        assert_eq!(kind("tokio"), Some(ChangeKind::Upgraded));
+        assert_eq!(kind("reqwest"), Some(ChangeKind::Added));
+        assert_eq!(kind("log"), Some(ChangeKind::Removed));
+        assert_eq!(kind("tempfile"), Some(ChangeKind::Downgraded));
+    }
+
+    #[test]
+    fn test_package_json_and_pyproject_parsing() {
+        let changes = changes(
+            Ecosystem::Npm,
+            r#"{"dependencies": {"react": "^18.2.0"}}"#,
+            r#"{"dependencies": {"react": "^18.2.0"}, "devDependencies": {"jest": "^29.0.0"}}"#,
+        );
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].section, "devDependencies");
+        assert_eq!(changes[0].kind, ChangeKind::Added);
+
+        let deps = parse_manifest(
+            Ecosystem::Python,
+            r#"
+[project]
+dependencies = ["requests>=2.31; python_version > '3.8'", "Uvicorn[standard]>=0.23", "click"]
+
+[tool.poetry.dependencies]
+python = "^3.11"
+Django_Rest = "^3.14"
+"#,
+        )
+        .unwrap();
+        let get =
+            |section: &str, name: &str| deps.get(&(section.to_string(), name.to_string())).cloned();
+        assert_eq!(
+            get("project.dependencies", "requests"),
+            Some(">=2.31".into())
+        );
+        assert_eq!(
+            get("project.dependencies", "uvicorn"),
+            Some(">=0.23".into())
+        );
+        assert_eq!(get("project.dependencies", "click"), Some("*".into()));
+        assert_eq!(
+            get("tool.poetry.dependencies", "django-rest"),
+            Some("^3.14".into())
+        );
+        assert_eq!(get("tool.poetry.dependencies", "python"), None);
+    }
+
+    #[test]
+    fn test_declarations_match_by_ecosystem_and_name() {
+        let change = &changes(
+            Ecosystem::Npm,
+            "{}",
+            r#"{"dependencies": {"lodash": "^4.17.21"}}"#,
+        )[0];
+        assert!(is_declared(&["lodash".to_string()], change));
+        assert!(is_declared(&["npm:lodash".to_string()], change));
+        assert!(!is_declared(&["cargo:lodash".to_string()], change));
+        assert!(!is_declared(&["underscore".to_string()], change));
+    }
+}
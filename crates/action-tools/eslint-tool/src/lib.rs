@@ -15,8 +15,11 @@
  */
 
 use async_trait::async_trait;
+use rhema_action_tool::platform::tool_command;
+use rhema_action_tool::{tool_available, LimitedCommand};
 use rhema_action_tool::{ActionError, ActionIntent, ActionResult, SafetyLevel};
-use rhema_action_tool::{ToolResult, TransformationTool};
+use rhema_action_tool::{Diagnostic, Severity, ToolResult, TransformationTool};
+use serde_json::Value;
 use tracing::{info, warn};
 
 /// ESLint transformation tool
@@ -27,6 +30,10 @@ impl TransformationTool for ESLintTool {
     async fn execute(&self, intent: &ActionIntent) -> ActionResult<ToolResult> {
         info!("Executing eslint for intent: {}", intent.id);
 
+        if intent.dry_run {
+            return self.preview(intent).await;
+        }
+
         let start = std::time::Instant::now();
 
         // Extract file paths from intent
@@ -40,12 +47,26 @@ impl TransformationTool for ESLintTool {
         // Execute eslint on each file
         let mut changes = Vec::new();
         let mut errors = Vec::new();
-        let warnings = Vec::new();
+        let mut warnings = Vec::new();
+        let mut diagnostics = Vec::new();
 
         for file in files {
             match self.execute_eslint_on_file(file).await {
-                Ok(change) => changes.push(change),
-                Err(e) => errors.push(format!("Failed to lint {}: {}", file, e)),
+                Ok((change, remaining)) => {
+                    changes.push(change);
+                    for diagnostic in remaining {
+                        match diagnostic.severity {
+                            Severity::Error => errors.push(diagnostic.to_string()),
+                            _ => warnings.push(diagnostic.to_string()),
+                        }
+                        diagnostics.push(diagnostic);
+                    }
+                }
+                Err(e) => {
+                    let message = format!("Failed to lint {}: {}", file, e);
+                    diagnostics.push(Diagnostic::error("eslint", &message));
+                    errors.push(message);
+                }
             }
         }
 
@@ -58,6 +79,9 @@ impl TransformationTool for ESLintTool {
             errors,
             warnings,
             duration: start.elapsed(),
+            cached: false,
+            diagnostics,
+            failed_tests: Vec::new(),
         })
     }
 
@@ -78,19 +102,17 @@ impl TransformationTool for ESLintTool {
     }
 
     async fn is_available(&self) -> bool {
-        // Check if eslint is installed
-        tokio::process::Command::new("npx")
-            .args(&["eslint", "--version"])
-            .output()
-            .await
-            .map(|output| output.status.success())
-            .unwrap_or(false)
+        tool_available("eslint").await
     }
 }
 
 impl ESLintTool {
-    /// Execute eslint on a specific file
-    async fn execute_eslint_on_file(&self, file_path: &str) -> ActionResult<String> {
+    /// Execute eslint on a specific file. Returns the change and the
+    /// problems left after fixing.
+    async fn execute_eslint_on_file(
+        &self,
+        file_path: &str,
+    ) -> ActionResult<(String, Vec<Diagnostic>)> {
         info!("Executing eslint on file: {}", file_path);
 
         // Check if file exists
@@ -101,32 +123,68 @@ impl ESLintTool {
             )));
         }
 
-        // Execute eslint with auto-fix
-        let output = tokio::process::Command::new("npx")
-            .args(&["eslint", "--fix", file_path])
-            .output()
+        // Execute eslint with auto-fix; exit code 1 only means problems remain
+        let output = tool_command("npx")
+            .args(["eslint", "--fix", "--format", "json", file_path])
+            .limited_output()
             .await
             .map_err(|e| ActionError::ToolExecution {
                 tool: "eslint".to_string(),
                 message: format!("Failed to execute eslint: {}", e),
             })?;
 
-        if output.status.success() {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            let stderr = String::from_utf8_lossy(&output.stderr);
-
-            info!("ESLint stdout: {}", stdout);
-            if !stderr.is_empty() {
-                warn!("ESLint stderr: {}", stderr);
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        match (output.status.code(), parse_eslint_json(&stdout)) {
+            (Some(0) | Some(1), Some(diagnostics)) => {
+                if !stderr.is_empty() {
+                    warn!("ESLint stderr: {}", stderr);
+                }
+                Ok((
+                    format!("Successfully linted and fixed {}", file_path),
+                    diagnostics,
+                ))
             }
-
-            Ok(format!("Successfully linted and fixed {}", file_path))
-        } else {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            Err(ActionError::ToolExecution {
+            _ => Err(ActionError::ToolExecution {
                 tool: "eslint".to_string(),
                 message: format!("ESLint failed for {}: {}", file_path, stderr),
-            })
+            }),
         }
     }
 }
+
+/// Diagnostics of ESLint's `json` formatter output
+fn parse_eslint_json(stdout: &str) -> Option<Vec<Diagnostic>> {
+    let results: Vec<Value> = serde_json::from_str(stdout.trim()).ok()?;
+    let mut diagnostics = Vec::new();
+    for result in &results {
+        let file = result.get("filePath").and_then(Value::as_str).unwrap_or("");
+        let messages = result.get("messages").and_then(Value::as_array);
+        for message in messages.into_iter().flatten() {
+            let severity = match message.get("severity").and_then(Value::as_u64) {
+                Some(2) => Severity::Error,
+                _ => Severity::Warning,
+            };
+            let number = |key: &str| {
+                message
+                    .get(key)
+                    .and_then(Value::as_u64)
+                    .and_then(|n| u32::try_from(n).ok())
+            };
+            let text = message.get("message").and_then(Value::as_str).unwrap_or("");
+            let mut diagnostic = Diagnostic::new("eslint", severity, text).at(
+                file,
+                number("line"),
+                number("column"),
+            );
+            if let Some(rule) = message.get("ruleId").and_then(Value::as_str) {
+                diagnostic = diagnostic.with_code(rule);
+            }
+            diagnostics.push(diagnostic);
+        }
+    }
+    Some(diagnostics)
+}
+
+#[cfg(test)]
+mod tests;
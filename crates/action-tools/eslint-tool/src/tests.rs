@@ -0,0 +1,36 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[test]
+fn test_parse_eslint_json() {
+    let stdout = r#"[{"filePath":"/repo/src/app.js","messages":[
+        {"ruleId":"no-unused-vars","severity":2,"message":"'x' is defined but never used.","line":3,"column":7},
+        {"ruleId":null,"severity":1,"message":"File ignored by default.","fatal":false}
+    ],"errorCount":1,"warningCount":1}]"#;
+
+    let diagnostics = parse_eslint_json(stdout).unwrap();
+    assert_eq!(
+        diagnostics[0],
+        Diagnostic::error("eslint", "'x' is defined but never used.")
+            .at("/repo/src/app.js", Some(3), Some(7))
+            .with_code("no-unused-vars")
+    );
+    assert_eq!(diagnostics[1].severity, Severity::Warning);
+    assert_eq!(diagnostics[1].code, None);
+    assert!(parse_eslint_json("Oops! Something went wrong!").is_none());
+}
@@ -0,0 +1,739 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use async_trait::async_trait;
+use rhema_action_tool::platform::tool_command;
+use rhema_action_tool::{tool_available, LimitedCommand};
+use rhema_action_tool::{ActionError, ActionIntent, ActionResult, SafetyLevel};
+use rhema_action_tool::{ToolResult, ToolchainProfile, TransformationTool, ValidationTool};
+use std::path::{Path, PathBuf};
+use tracing::{error, info};
+
+/// Go toolchain validation and transformation tool
+pub struct GoTool;
+
+/// Supported Go commands
+#[derive(Debug, Clone, PartialEq)]
+pub enum GoCommand {
+    /// `go build ./...`
+    Build,
+    /// `go vet ./...`
+    Vet,
+    /// `go test ./...`
+    Test,
+    /// `gofmt -l` when validating, `gofmt -w` when transforming
+    Fmt,
+    /// `golangci-lint run`, with `--fix` when transforming
+    Lint,
+}
+
+/// Go operation result
+#[derive(Debug, Clone)]
+pub struct GoResult {
+    pub command: GoCommand,
+    pub success: bool,
+    pub output: String,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+    pub duration: std::time::Duration,
+}
+
+/// A module listed in a `go.work` file
+#[derive(Debug, Clone)]
+pub struct GoModule {
+    /// Module path from the `module` directive
+    pub name: String,
+    /// Directory relative to the workspace root
+    pub path: String,
+}
+
+/// Workspace information from a `go.work` file
+#[derive(Debug, Clone)]
+pub struct GoWorkspace {
+    pub root_path: String,
+    pub go_version: Option<String>,
+    pub modules: Vec<GoModule>,
+}
+
+/// Go tool configuration
+#[derive(Debug, Clone)]
+pub struct GoConfig {
+    pub commands: Vec<GoCommand>,
+    /// Run golangci-lint even when `lint` is not among the commands
+    pub lint: bool,
+    pub verbose: bool,
+    /// Build tags passed to build, vet and test
+    pub build_tags: Vec<String>,
+    pub workspace_mode: WorkspaceMode,
+    pub module_filter: Option<Vec<String>>,
+    pub exclude_modules: Option<Vec<String>>,
+}
+
+/// Workspace execution mode
+#[derive(Debug, Clone, PartialEq)]
+pub enum WorkspaceMode {
+    /// Execute in the directory holding `go.work` only
+    RootOnly,
+    /// Execute in every module listed in `go.work`
+    AllModules,
+    /// Execute in the workspace root and every module
+    RootAndModules,
+    /// Execute only in the modules selected by `module_filter`
+    SelectedModules,
+}
+
+impl Default for GoConfig {
+    fn default() -> Self {
+        Self {
+            commands: vec![GoCommand::Build, GoCommand::Vet, GoCommand::Fmt],
+            lint: false,
+            verbose: false,
+            build_tags: Vec::new(),
+            workspace_mode: WorkspaceMode::AllModules,
+            module_filter: None,
+            exclude_modules: None,
+        }
+    }
+}
+
+impl GoConfig {
+    fn runs(&self, command: &GoCommand) -> bool {
+        self.commands.contains(command) || (*command == GoCommand::Lint && self.lint)
+    }
+}
+
+/// Directory a command runs in, labelled by module name
+#[derive(Debug, Clone, PartialEq)]
+struct GoTarget {
+    label: String,
+    dir: PathBuf,
+}
+
+#[async_trait]
+impl ValidationTool for GoTool {
+    async fn validate(&self, intent: &ActionIntent) -> ActionResult<ToolResult> {
+        info!("Running Go validation for intent: {}", intent.id);
+
+        let start = std::time::Instant::now();
+        let config = self.parse_config(intent);
+        let targets = self.resolve_targets(&intent.scope, &config).await?;
+
+        if targets.is_empty() {
+            return Ok(ToolResult {
+                success: true,
+                changes: vec![],
+                output: "No Go modules found to validate".to_string(),
+                errors: vec![],
+                warnings: vec![],
+                duration: start.elapsed(),
+                cached: false,
+                diagnostics: Vec::new(),
+                failed_tests: Vec::new(),
+            });
+        }
+
+        let commands = [
+            GoCommand::Build,
+            GoCommand::Vet,
+            GoCommand::Test,
+            GoCommand::Fmt,
+            GoCommand::Lint,
+        ];
+        let results = self
+            .run_on_targets(&targets, &commands, &config, false)
+            .await;
+        Ok(self.collect_results(results, &targets, "validation", start))
+    }
+
+    fn applies_to(&self, profile: &ToolchainProfile) -> bool {
+        profile.has_toolchain("go")
+    }
+
+    fn name(&self) -> &str {
+        "go"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn is_available(&self) -> bool {
+        tool_available("go").await
+    }
+}
+
+#[async_trait]
+impl TransformationTool for GoTool {
+    async fn execute(&self, intent: &ActionIntent) -> ActionResult<ToolResult> {
+        info!("Executing Go transformations for intent: {}", intent.id);
+
+        if intent.dry_run {
+            return self.preview(intent).await;
+        }
+
+        let start = std::time::Instant::now();
+        let config = self.parse_config(intent);
+        let targets = self.resolve_targets(&intent.scope, &config).await?;
+
+        if targets.is_empty() {
+            return Err(ActionError::Validation(
+                "No Go modules found for transformation".to_string(),
+            ));
+        }
+
+        // Formatting and lint fixes rewrite files; build, vet and test do not
+        let results = self
+            .run_on_targets(&targets, &[GoCommand::Fmt, GoCommand::Lint], &config, true)
+            .await;
+        Ok(self.collect_results(results, &targets, "transformations", start))
+    }
+
+    fn supports_language(&self, language: &str) -> bool {
+        language == "go"
+    }
+
+    fn safety_level(&self) -> SafetyLevel {
+        SafetyLevel::Medium
+    }
+
+    fn name(&self) -> &str {
+        "go"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn is_available(&self) -> bool {
+        tool_available("go").await
+    }
+}
+
+impl GoTool {
+    /// Parse configuration from intent metadata
+    fn parse_config(&self, intent: &ActionIntent) -> GoConfig {
+        let mut config = GoConfig::default();
+        let metadata = &intent.metadata;
+        if metadata.is_null() {
+            return config;
+        }
+
+        if let Some(commands) = metadata.get("commands").and_then(|c| c.as_array()) {
+            config.commands = commands
+                .iter()
+                .filter_map(|cmd| match cmd.as_str()? {
+                    "build" => Some(GoCommand::Build),
+                    "vet" => Some(GoCommand::Vet),
+                    "test" => Some(GoCommand::Test),
+                    "fmt" | "gofmt" => Some(GoCommand::Fmt),
+                    "lint" | "golangci-lint" => Some(GoCommand::Lint),
+                    _ => None,
+                })
+                .collect();
+        }
+
+        if let Some(lint) = metadata.get("lint") {
+            config.lint = lint.as_bool().unwrap_or(false);
+        }
+
+        if let Some(verbose) = metadata.get("verbose") {
+            config.verbose = verbose.as_bool().unwrap_or(false);
+        }
+
+        if let Some(tags) = string_list(metadata.get("build_tags")) {
+            config.build_tags = tags;
+        }
+
+        if let Some(workspace_mode) = metadata.get("workspace_mode") {
+            config.workspace_mode = match workspace_mode.as_str() {
+                Some("root_only") => WorkspaceMode::RootOnly,
+                Some("all_modules") => WorkspaceMode::AllModules,
+                Some("root_and_modules") => WorkspaceMode::RootAndModules,
+                Some("selected_modules") => WorkspaceMode::SelectedModules,
+                _ => WorkspaceMode::AllModules,
+            };
+        }
+
+        config.module_filter = string_list(metadata.get("module_filter"));
+        config.exclude_modules = string_list(metadata.get("exclude_modules"));
+        config
+    }
+
+    /// Directories to run in for the `go.work`, `go.mod` and `.go` files in
+    /// scope. A `.go` file stands for its enclosing module, and modules already
+    /// covered by a workspace in scope are not run twice.
+    async fn resolve_targets(
+        &self,
+        scope: &[String],
+        config: &GoConfig,
+    ) -> ActionResult<Vec<GoTarget>> {
+        let mut targets: Vec<GoTarget> = Vec::new();
+        let mut push = |target: GoTarget| {
+            if !targets.iter().any(|t| t.dir == target.dir) {
+                targets.push(target);
+            }
+        };
+
+        for entry in scope.iter().filter(|f| f.ends_with("go.work")) {
+            let workspace = self.detect_workspace(entry).await?;
+            let root = PathBuf::from(&workspace.root_path);
+            let modules = || {
+                workspace.modules.iter().map(|module| GoTarget {
+                    label: module.name.clone(),
+                    dir: root.join(&module.path),
+                })
+            };
+            let root_target = GoTarget {
+                label: "workspace".to_string(),
+                dir: root.clone(),
+            };
+            match config.workspace_mode {
+                WorkspaceMode::RootOnly => push(root_target),
+                WorkspaceMode::AllModules => modules().for_each(&mut push),
+                WorkspaceMode::RootAndModules => {
+                    push(root_target);
+                    modules().for_each(&mut push);
+                }
+                WorkspaceMode::SelectedModules => self
+                    .get_selected_modules(&workspace.modules, config)
+                    .into_iter()
+                    .for_each(|module| {
+                        push(GoTarget {
+                            label: module.name.clone(),
+                            dir: root.join(&module.path),
+                        })
+                    }),
+            }
+        }
+
+        for entry in scope
+            .iter()
+            .filter(|f| f.ends_with(".go") || f.ends_with("go.mod"))
+        {
+            let Some(go_mod) = find_go_mod(Path::new(entry)) else {
+                continue;
+            };
+            let content = tokio::fs::read_to_string(&go_mod)
+                .await
+                .map_err(|e| ActionError::Validation(format!("Failed to read go.mod: {}", e)))?;
+            let dir = go_mod.parent().unwrap_or(Path::new(".")).to_path_buf();
+            push(GoTarget {
+                label: module_name(&content).unwrap_or_else(|| dir.display().to_string()),
+                dir,
+            });
+        }
+
+        Ok(targets)
+    }
+
+    /// Read the modules a `go.work` file uses
+    async fn detect_workspace(&self, go_work: &str) -> ActionResult<GoWorkspace> {
+        let root = Path::new(go_work)
+            .parent()
+            .ok_or_else(|| ActionError::Validation("Invalid go.work path".to_string()))?;
+        let content = tokio::fs::read_to_string(go_work)
+            .await
+            .map_err(|e| ActionError::Validation(format!("Failed to read go.work: {}", e)))?;
+
+        let mut modules = Vec::new();
+        for path in self.extract_workspace_modules(&content) {
+            let go_mod = root.join(&path).join("go.mod");
+            let name = match tokio::fs::read_to_string(&go_mod).await {
+                Ok(module) => module_name(&module).unwrap_or_else(|| path.clone()),
+                // go itself rejects the workspace, so let the command report it
+                Err(_) => path.clone(),
+            };
+            modules.push(GoModule { name, path });
+        }
+
+        Ok(GoWorkspace {
+            root_path: root.to_string_lossy().to_string(),
+            go_version: content
+                .lines()
+                .find_map(|line| strip_comment(line).strip_prefix("go "))
+                .map(|version| version.trim().to_string()),
+            modules,
+        })
+    }
+
+    /// Directories from the `use` directives of `go.work` content, both the
+    /// single-line and the parenthesized block form
+    fn extract_workspace_modules(&self, content: &str) -> Vec<String> {
+        let mut modules = Vec::new();
+        let mut in_use_block = false;
+
+        for line in content.lines() {
+            let trimmed = strip_comment(line);
+            if in_use_block {
+                if trimmed == ")" {
+                    in_use_block = false;
+                } else if !trimmed.is_empty() {
+                    modules.push(trimmed.trim_matches('"').to_string());
+                }
+                continue;
+            }
+            let Some(rest) = trimmed.strip_prefix("use") else {
+                continue;
+            };
+            if !rest.starts_with(|c: char| c.is_whitespace() || c == '(') {
+                continue;
+            }
+            match rest.trim() {
+                "(" => in_use_block = true,
+                path => modules.push(path.trim_matches('"').to_string()),
+            }
+        }
+
+        modules
+    }
+
+    /// Filter workspace modules by `module_filter` and `exclude_modules`,
+    /// matching either the module path or its directory
+    fn get_selected_modules<'a>(
+        &self,
+        modules: &'a [GoModule],
+        config: &GoConfig,
+    ) -> Vec<&'a GoModule> {
+        let matches = |module: &GoModule, names: &[String]| {
+            names
+                .iter()
+                .any(|name| *name == module.name || *name == module.path)
+        };
+        modules
+            .iter()
+            .filter(|module| match &config.module_filter {
+                Some(filter) => matches(module, filter),
+                None => true,
+            })
+            .filter(|module| match &config.exclude_modules {
+                Some(exclude) => !matches(module, exclude),
+                None => true,
+            })
+            .collect()
+    }
+
+    /// Run each configured command in `commands` on every target
+    async fn run_on_targets(
+        &self,
+        targets: &[GoTarget],
+        commands: &[GoCommand],
+        config: &GoConfig,
+        fix: bool,
+    ) -> Vec<GoResult> {
+        let mut results = Vec::new();
+        for target in targets {
+            for command in commands.iter().filter(|command| config.runs(command)) {
+                let result = match command {
+                    GoCommand::Fmt => self.execute_gofmt(&target.dir, fix).await,
+                    GoCommand::Lint => self.execute_golangci_lint(&target.dir, config, fix).await,
+                    _ => self.execute_go_command(&target.dir, command, config).await,
+                };
+                match result {
+                    Ok(mut result) => {
+                        result.output = format!("[{}] {}", target.label, result.output);
+                        results.push(result);
+                    }
+                    Err(e) => {
+                        error!(
+                            "Failed to execute {:?} for {}: {}",
+                            command, target.label, e
+                        );
+                        results.push(GoResult {
+                            command: command.clone(),
+                            success: false,
+                            output: format!("[{}] Failed", target.label),
+                            errors: vec![format!("{}: {}", target.label, e)],
+                            warnings: vec![],
+                            duration: std::time::Duration::ZERO,
+                        });
+                    }
+                }
+            }
+        }
+        results
+    }
+
+    fn collect_results(
+        &self,
+        results: Vec<GoResult>,
+        targets: &[GoTarget],
+        kind: &str,
+        start: std::time::Instant,
+    ) -> ToolResult {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        let mut changes = Vec::new();
+        for result in results {
+            errors.extend(result.errors);
+            warnings.extend(result.warnings);
+            if !result.output.is_empty() {
+                changes.push(result.output);
+            }
+        }
+
+        ToolResult {
+            success: errors.is_empty(),
+            changes,
+            output: format!("Go {} completed for {} modules", kind, targets.len()),
+            errors,
+            warnings,
+            duration: start.elapsed(),
+            cached: false,
+            diagnostics: Vec::new(),
+            failed_tests: Vec::new(),
+        }
+    }
+
+    /// Execute `go build`, `go vet` or `go test` on every package of a module
+    async fn execute_go_command(
+        &self,
+        dir: &Path,
+        command: &GoCommand,
+        config: &GoConfig,
+    ) -> ActionResult<GoResult> {
+        let start = std::time::Instant::now();
+        let args = self.build_command_args(command, config);
+
+        let output = tool_command("go")
+            .args(&args)
+            .current_dir(dir)
+            .limited_output()
+            .await
+            .map_err(|e| ActionError::ToolExecution {
+                tool: "go".to_string(),
+                message: format!("Failed to run go {:?}: {}", command, e),
+            })?;
+
+        let (mut errors, warnings) = self.parse_go_output(&output.stderr);
+        if !output.status.success() && errors.is_empty() {
+            errors.push(format!(
+                "go {} failed: {}",
+                args[0],
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        Ok(GoResult {
+            command: command.clone(),
+            success: errors.is_empty(),
+            output: String::from_utf8_lossy(&output.stdout).to_string(),
+            errors,
+            warnings,
+            duration: start.elapsed(),
+        })
+    }
+
+    /// List unformatted files, and rewrite them when `fix` is set
+    async fn execute_gofmt(&self, dir: &Path, fix: bool) -> ActionResult<GoResult> {
+        let start = std::time::Instant::now();
+        let run = |args: Vec<String>| async move {
+            tool_command("gofmt")
+                .args(&args)
+                .current_dir(dir)
+                .limited_output()
+                .await
+                .map_err(|e| ActionError::ToolExecution {
+                    tool: "gofmt".to_string(),
+                    message: format!("Failed to run gofmt: {}", e),
+                })
+        };
+
+        let listed = run(vec!["-l".to_string(), ".".to_string()]).await?;
+        let (syntax_errors, _) = self.parse_go_output(&listed.stderr);
+        let unformatted: Vec<String> = String::from_utf8_lossy(&listed.stdout)
+            .lines()
+            .map(str::to_string)
+            .collect();
+
+        if !fix || unformatted.is_empty() {
+            let mut errors = syntax_errors;
+            errors.extend(
+                unformatted
+                    .iter()
+                    .map(|file| format!("{}: not gofmt-formatted", file)),
+            );
+            return Ok(GoResult {
+                command: GoCommand::Fmt,
+                success: errors.is_empty(),
+                output: format!("{} files need formatting", unformatted.len()),
+                errors,
+                warnings: vec![],
+                duration: start.elapsed(),
+            });
+        }
+
+        let mut args = vec!["-w".to_string()];
+        args.extend(unformatted.iter().cloned());
+        let written = run(args).await?;
+        let (mut errors, warnings) = self.parse_go_output(&written.stderr);
+        errors.extend(syntax_errors);
+
+        Ok(GoResult {
+            command: GoCommand::Fmt,
+            success: errors.is_empty(),
+            output: format!("Formatted {}", unformatted.join(", ")),
+            errors,
+            warnings,
+            duration: start.elapsed(),
+        })
+    }
+
+    /// Run golangci-lint; issues it cannot fix fail validation but are only
+    /// warnings after a transformation
+    async fn execute_golangci_lint(
+        &self,
+        dir: &Path,
+        config: &GoConfig,
+        fix: bool,
+    ) -> ActionResult<GoResult> {
+        let start = std::time::Instant::now();
+        if !tool_available("golangci-lint").await {
+            return Ok(GoResult {
+                command: GoCommand::Lint,
+                success: true,
+                output: "golangci-lint skipped".to_string(),
+                errors: vec![],
+                warnings: vec!["golangci-lint is not installed; lint skipped".to_string()],
+                duration: start.elapsed(),
+            });
+        }
+
+        let mut args = vec!["run".to_string()];
+        if fix {
+            args.push("--fix".to_string());
+        }
+        if !config.build_tags.is_empty() {
+            args.push(format!("--build-tags={}", config.build_tags.join(",")));
+        }
+        if config.verbose {
+            args.push("--verbose".to_string());
+        }
+        args.push("./...".to_string());
+
+        let output = tool_command("golangci-lint")
+            .args(&args)
+            .current_dir(dir)
+            .limited_output()
+            .await
+            .map_err(|e| ActionError::ToolExecution {
+                tool: "golangci-lint".to_string(),
+                message: format!("Failed to run golangci-lint: {}", e),
+            })?;
+
+        let (issues, mut warnings) = self.parse_go_output(&output.stdout);
+        let errors = if fix {
+            warnings.extend(issues);
+            vec![]
+        } else {
+            issues
+        };
+
+        Ok(GoResult {
+            command: GoCommand::Lint,
+            success: errors.is_empty(),
+            output: if fix {
+                "golangci-lint fixes applied".to_string()
+            } else {
+                "golangci-lint completed".to_string()
+            },
+            errors,
+            warnings,
+            duration: start.elapsed(),
+        })
+    }
+
+    /// Build arguments for build, vet and test. Fmt and lint have their own
+    /// runners and map to their nearest `go` subcommands here.
+    fn build_command_args(&self, command: &GoCommand, config: &GoConfig) -> Vec<String> {
+        let mut args = vec![match command {
+            GoCommand::Build => "build",
+            GoCommand::Vet | GoCommand::Lint => "vet",
+            GoCommand::Test => "test",
+            GoCommand::Fmt => "fmt",
+        }
+        .to_string()];
+
+        if !config.build_tags.is_empty() {
+            args.push(format!("-tags={}", config.build_tags.join(",")));
+        }
+        if config.verbose {
+            args.push("-v".to_string());
+        }
+        args.push("./...".to_string());
+        args
+    }
+
+    /// Split compiler, vet and lint output into errors and warnings.
+    /// Diagnostics have the form `file.go:line:col: message`; `# package`
+    /// headers and build noise are dropped.
+    fn parse_go_output(&self, output: &[u8]) -> (Vec<String>, Vec<String>) {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+
+        for line in String::from_utf8_lossy(output).lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let is_diagnostic = trimmed
+                .split_once(".go:")
+                .is_some_and(|(_, rest)| rest.starts_with(|c: char| c.is_ascii_digit()));
+            if is_diagnostic {
+                errors.push(trimmed.to_string());
+            } else if trimmed.starts_with("warning:") || trimmed.starts_with("level=warning") {
+                warnings.push(trimmed.to_string());
+            }
+        }
+
+        (errors, warnings)
+    }
+}
+
+/// Nearest `go.mod` for a `go.mod` or `.go` path in scope
+fn find_go_mod(path: &Path) -> Option<PathBuf> {
+    if path.file_name().is_some_and(|name| name == "go.mod") {
+        return path.exists().then(|| path.to_path_buf());
+    }
+    path.ancestors()
+        .skip(1)
+        .map(|dir| dir.join("go.mod"))
+        .find(|candidate| candidate.exists())
+}
+
+/// Module path from the `module` directive of go.mod content
+fn module_name(go_mod: &str) -> Option<String> {
+    go_mod.lines().find_map(|line| {
+        strip_comment(line)
+            .strip_prefix("module ")
+            .map(|name| name.trim().trim_matches('"').to_string())
+    })
+}
+
+fn strip_comment(line: &str) -> &str {
+    line.split("//").next().unwrap_or_default().trim()
+}
+
+fn string_list(value: Option<&serde_json::Value>) -> Option<Vec<String>> {
+    value.and_then(|v| v.as_array()).map(|items| {
+        items
+            .iter()
+            .filter_map(|item| item.as_str().map(str::to_string))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,211 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use rhema_action_tool::environment::probe_for;
+use rhema_action_tool::platform::resolve_program;
+use rhema_action_tool::{ActionIntent, ActionType, SafetyLevel};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_go_tool_creation() {
+    let tool = GoTool;
+    assert_eq!(ValidationTool::name(&tool), "go");
+    assert_eq!(TransformationTool::name(&tool), "go");
+    assert_eq!(ValidationTool::version(&tool), "1.0.0");
+}
+
+#[test]
+fn test_parse_config_default() {
+    let tool = GoTool;
+    let intent = ActionIntent::new(
+        "test",
+        ActionType::Test,
+        "Validate Go modules",
+        vec![],
+        SafetyLevel::Low,
+    );
+
+    let config = tool.parse_config(&intent);
+    assert_eq!(
+        config.commands,
+        vec![GoCommand::Build, GoCommand::Vet, GoCommand::Fmt]
+    );
+    assert_eq!(config.workspace_mode, WorkspaceMode::AllModules);
+    assert!(!config.runs(&GoCommand::Lint));
+}
+
+#[test]
+fn test_parse_config_custom() {
+    let tool = GoTool;
+    let mut intent = ActionIntent::new(
+        "test",
+        ActionType::Test,
+        "Validate Go modules",
+        vec![],
+        SafetyLevel::Low,
+    );
+    intent.metadata = json!({
+        "commands": ["vet", "test"],
+        "lint": true,
+        "build_tags": ["integration"],
+        "workspace_mode": "selected_modules",
+        "module_filter": ["example.com/api"]
+    });
+
+    let config = tool.parse_config(&intent);
+    assert_eq!(config.commands, vec![GoCommand::Vet, GoCommand::Test]);
+    assert!(config.runs(&GoCommand::Lint));
+    assert_eq!(config.workspace_mode, WorkspaceMode::SelectedModules);
+    assert_eq!(
+        tool.build_command_args(&GoCommand::Vet, &config),
+        vec!["vet", "-tags=integration", "./..."]
+    );
+}
+
+#[tokio::test]
+async fn test_validation_with_no_modules() {
+    let tool = GoTool;
+    let intent = ActionIntent::new(
+        "test",
+        ActionType::Test,
+        "Validate Go modules",
+        vec!["README.md".to_string()],
+        SafetyLevel::Low,
+    );
+
+    let result = tool.validate(&intent).await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.output, "No Go modules found to validate");
+}
+
+#[tokio::test]
+async fn test_validate_with_go_vet() {
+    let tool = GoTool;
+    assert!(probe_for("go").is_some());
+    assert!(probe_for("golangci-lint").is_some());
+    assert_eq!(
+        ValidationTool::is_available(&tool).await,
+        resolve_program("go").is_some()
+    );
+    if !ValidationTool::is_available(&tool).await {
+        return;
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("go.mod"),
+        "module example.com/app\n\ngo 1.21\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("main.go"),
+        "package main\n\nimport \"fmt\"\n\nfunc main() {\n\tfmt.Printf(\"%d\\n\", \"text\")\n}\n",
+    )
+    .unwrap();
+    let mut intent = ActionIntent::new(
+        "test",
+        ActionType::Test,
+        "Validate Go modules",
+        vec![dir.path().join("go.mod").to_string_lossy().to_string()],
+        SafetyLevel::Low,
+    );
+    intent.metadata = json!({
+        "commands": ["vet"],
+        "lint": true
+    });
+
+    let result = tool.validate(&intent).await.unwrap();
+    assert!(!result.success);
+    assert!(result.errors.iter().any(|e| e.contains("main.go:6")));
+    if !tool_available("golangci-lint").await {
+        assert!(result
+            .warnings
+            .iter()
+            .any(|w| w.contains("golangci-lint is not installed")));
+    }
+}
+
+#[tokio::test]
+async fn test_workspace_modules_and_targets() {
+    let tool = GoTool;
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path();
+    std::fs::write(
+        root.join("go.work"),
+        "go 1.22\n\nuse (\n\t./api // service\n\t./core\n)\nuse ./tools\n",
+    )
+    .unwrap();
+    for (path, module) in [
+        ("api", "example.com/api"),
+        ("core", "example.com/core"),
+        ("tools", "example.com/tools"),
+    ] {
+        std::fs::create_dir_all(root.join(path).join("cmd")).unwrap();
+        std::fs::write(
+            root.join(path).join("go.mod"),
+            format!("module {}\n\ngo 1.22\n", module),
+        )
+        .unwrap();
+    }
+
+    let go_work = root.join("go.work").to_string_lossy().to_string();
+    let workspace = tool.detect_workspace(&go_work).await.unwrap();
+    assert_eq!(workspace.go_version.as_deref(), Some("1.22"));
+    let names: Vec<&str> = workspace.modules.iter().map(|m| m.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec!["example.com/api", "example.com/core", "example.com/tools"]
+    );
+
+    // A .go file in a module the workspace already covers adds nothing
+    let main_go = root.join("api/cmd/main.go").to_string_lossy().to_string();
+    let config = GoConfig {
+        workspace_mode: WorkspaceMode::SelectedModules,
+        module_filter: Some(vec!["example.com/api".to_string(), "core".to_string()]),
+        exclude_modules: Some(vec!["core".to_string()]),
+        ..GoConfig::default()
+    };
+    let targets = tool
+        .resolve_targets(&[go_work.clone(), main_go], &config)
+        .await
+        .unwrap();
+    assert_eq!(targets.len(), 1);
+    assert_eq!(targets[0].label, "example.com/api");
+
+    let config = GoConfig {
+        workspace_mode: WorkspaceMode::RootAndModules,
+        ..GoConfig::default()
+    };
+    let targets = tool.resolve_targets(&[go_work], &config).await.unwrap();
+    assert_eq!(targets.len(), 4);
+    assert_eq!(targets[0].label, "workspace");
+}
+
+#[tokio::test]
+async fn test_parse_go_output() {
+    let tool = GoTool;
+    let stderr = b"# example.com/api\n./main.go:10:2: undefined: foo\nvet: ./x.go:3:1: unreachable code\nwarning: GOPATH set to GOROOT\ngo: downloading example.com/dep v1.0.0\n";
+    let (errors, warnings) = tool.parse_go_output(stderr);
+    assert_eq!(
+        errors,
+        vec![
+            "./main.go:10:2: undefined: foo",
+            "vet: ./x.go:3:1: unreachable code"
+        ]
+    );
+    assert_eq!(warnings, vec!["warning: GOPATH set to GOROOT"]);
+}
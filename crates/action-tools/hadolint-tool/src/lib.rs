@@ -0,0 +1,302 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use async_trait::async_trait;
+use rhema_action_tool::platform::tool_command;
+use rhema_action_tool::{tool_available, LimitedCommand};
+use rhema_action_tool::{ActionError, ActionIntent, ActionResult};
+use rhema_action_tool::{Diagnostic, Severity, ToolResult, ToolchainProfile, ValidationTool};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use tracing::info;
+
+/// Hadolint validation tool for Dockerfiles
+pub struct HadolintTool;
+
+/// Hadolint finding levels, from most to least severe
+const LEVELS: &[&str] = &["error", "warning", "info", "style"];
+
+/// How a rule's findings are reported
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleSeverity {
+    Error,
+    Warning,
+    Info,
+    /// Not reported at all
+    Ignore,
+}
+
+impl RuleSeverity {
+    fn parse(level: &str) -> Option<Self> {
+        match level {
+            "error" => Some(Self::Error),
+            "warning" => Some(Self::Warning),
+            "info" | "style" => Some(Self::Info),
+            "ignore" | "off" => Some(Self::Ignore),
+            _ => None,
+        }
+    }
+}
+
+/// Hadolint tool configuration
+#[derive(Debug, Clone, PartialEq)]
+pub struct HadolintConfig {
+    /// Lowest hadolint level reported as an error; findings below it are
+    /// warnings (`warning`) or notes (`info`, `style`)
+    pub failure_threshold: String,
+    /// Severity per rule, keyed by code (`DL3008`) or code prefix (`DL30*`);
+    /// the longest matching key wins over the hadolint level
+    pub rules: BTreeMap<String, RuleSeverity>,
+    /// Registries `FROM` images may come from, checked by DL3026
+    pub trusted_registries: Vec<String>,
+}
+
+impl Default for HadolintConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: "error".to_string(),
+            rules: BTreeMap::new(),
+            trusted_registries: Vec::new(),
+        }
+    }
+}
+
+impl HadolintConfig {
+    /// Severity of a finding with `code` that hadolint reported at `level`,
+    /// or `None` when the rule is ignored
+    pub fn classify(&self, code: &str, level: &str) -> Option<Severity> {
+        let rule = self
+            .rules
+            .iter()
+            .filter(|(pattern, _)| match pattern.strip_suffix('*') {
+                Some(prefix) => code.starts_with(prefix),
+                None => code == pattern.as_str(),
+            })
+            .max_by_key(|(pattern, _)| pattern.len())
+            .map(|(_, severity)| *severity);
+        match rule {
+            Some(RuleSeverity::Error) => Some(Severity::Error),
+            Some(RuleSeverity::Warning) => Some(Severity::Warning),
+            Some(RuleSeverity::Info) => Some(Severity::Info),
+            Some(RuleSeverity::Ignore) => None,
+            None => {
+                let rank = |level: &str| LEVELS.iter().position(|known| *known == level);
+                let threshold = rank(&self.failure_threshold).unwrap_or(0);
+                match rank(level) {
+                    Some(rank) if rank <= threshold => Some(Severity::Error),
+                    Some(1) => Some(Severity::Warning),
+                    // Unknown levels are treated as errors rather than lost
+                    None => Some(Severity::Error),
+                    Some(_) => Some(Severity::Info),
+                }
+            }
+        }
+    }
+
+    /// Codes ignored outright, passed to hadolint so it skips them
+    fn ignored_codes(&self) -> impl Iterator<Item = &String> {
+        self.rules.iter().filter_map(|(code, severity)| {
+            (*severity == RuleSeverity::Ignore && !code.ends_with('*')).then_some(code)
+        })
+    }
+}
+
+#[async_trait]
+impl ValidationTool for HadolintTool {
+    async fn validate(&self, intent: &ActionIntent) -> ActionResult<ToolResult> {
+        info!("Running hadolint validation for intent: {}", intent.id);
+
+        let start = std::time::Instant::now();
+        let files = dockerfiles(&intent.scope);
+        if files.is_empty() {
+            return Ok(ToolResult {
+                success: true,
+                changes: vec![],
+                output: "No Dockerfiles found to validate".to_string(),
+                errors: vec![],
+                warnings: vec![],
+                duration: start.elapsed(),
+                cached: false,
+                diagnostics: Vec::new(),
+                failed_tests: Vec::new(),
+            });
+        }
+
+        let config = self.parse_config(intent);
+        let output = tool_command("hadolint")
+            .args(self.build_command_args(&files, &config))
+            .limited_output()
+            .await
+            .map_err(|e| ActionError::ToolExecution {
+                tool: "hadolint".to_string(),
+                message: format!("Failed to run hadolint: {}", e),
+            })?;
+
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        let parsed = parse_hadolint_json(&String::from_utf8_lossy(&output.stdout), &config);
+        // `--no-fail` keeps findings from failing the run, so a failure or
+        // missing JSON output means a missing file or a bad option
+        if !output.status.success() || parsed.is_none() {
+            errors.push(format!(
+                "hadolint failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        let diagnostics = parsed.unwrap_or_default();
+        for diagnostic in &diagnostics {
+            match diagnostic.severity {
+                Severity::Error => errors.push(diagnostic.to_string()),
+                Severity::Warning => warnings.push(diagnostic.to_string()),
+                Severity::Info => {}
+            }
+        }
+
+        Ok(ToolResult {
+            success: errors.is_empty(),
+            changes: vec![],
+            output: format!(
+                "hadolint reported {} issues in {} Dockerfiles",
+                diagnostics.len(),
+                files.len()
+            ),
+            errors,
+            warnings,
+            duration: start.elapsed(),
+            cached: false,
+            diagnostics,
+            failed_tests: Vec::new(),
+        })
+    }
+
+    fn applies_to(&self, profile: &ToolchainProfile) -> bool {
+        profile.has_language("dockerfile")
+    }
+
+    fn name(&self) -> &str {
+        "hadolint"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn is_available(&self) -> bool {
+        tool_available("hadolint").await
+    }
+}
+
+impl HadolintTool {
+    /// Parse configuration from intent metadata
+    fn parse_config(&self, intent: &ActionIntent) -> HadolintConfig {
+        let mut config = HadolintConfig::default();
+        let metadata = &intent.metadata;
+        if metadata.is_null() {
+            return config;
+        }
+
+        if let Some(threshold) = metadata
+            .get("failure_threshold")
+            .and_then(Value::as_str)
+            .filter(|level| LEVELS.contains(level))
+        {
+            config.failure_threshold = threshold.to_string();
+        }
+
+        if let Some(rules) = metadata.get("rules").and_then(Value::as_object) {
+            config.rules = rules
+                .iter()
+                .filter_map(|(code, level)| {
+                    let severity = RuleSeverity::parse(level.as_str()?)?;
+                    Some((code.to_uppercase(), severity))
+                })
+                .collect();
+        }
+
+        if let Some(registries) = metadata.get("trusted_registries").and_then(Value::as_array) {
+            config.trusted_registries = registries
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect();
+        }
+
+        config
+    }
+
+    fn build_command_args(&self, files: &[String], config: &HadolintConfig) -> Vec<String> {
+        let mut args = vec!["--format=json".to_string(), "--no-fail".to_string()];
+        for code in config.ignored_codes() {
+            args.push(format!("--ignore={}", code));
+        }
+        for registry in &config.trusted_registries {
+            args.push(format!("--trusted-registry={}", registry));
+        }
+        args.extend(files.iter().cloned());
+        args
+    }
+}
+
+/// Dockerfiles in scope: `Dockerfile`, `Containerfile`, `Dockerfile.<name>`
+/// and `<name>.Dockerfile`
+fn dockerfiles(scope: &[String]) -> Vec<String> {
+    let mut files: Vec<String> = Vec::new();
+    for entry in scope {
+        let name = entry.rsplit(['/', '\\']).next().unwrap_or(entry);
+        let is_dockerfile = matches!(name, "Dockerfile" | "Containerfile")
+            || name.starts_with("Dockerfile.")
+            || name.ends_with(".Dockerfile")
+            || name.ends_with(".dockerfile");
+        if is_dockerfile && !files.contains(entry) {
+            files.push(entry.clone());
+        }
+    }
+    files
+}
+
+/// Diagnostics of hadolint's `json` output format, classified by `config`.
+/// Findings of ignored rules are dropped
+fn parse_hadolint_json(stdout: &str, config: &HadolintConfig) -> Option<Vec<Diagnostic>> {
+    let findings: Vec<Value> = serde_json::from_str(stdout.trim()).ok()?;
+    Some(
+        findings
+            .iter()
+            .filter_map(|finding| {
+                let text = |key: &str| finding.get(key).and_then(Value::as_str).unwrap_or("");
+                let number = |key: &str| {
+                    finding
+                        .get(key)
+                        .and_then(Value::as_u64)
+                        .and_then(|n| u32::try_from(n).ok())
+                };
+                let code = text("code");
+                let severity = config.classify(code, text("level"))?;
+                let mut diagnostic = Diagnostic::new("hadolint", severity, text("message")).at(
+                    text("file"),
+                    number("line"),
+                    number("column"),
+                );
+                if !code.is_empty() {
+                    diagnostic = diagnostic.with_code(code);
+                }
+                Some(diagnostic)
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests;
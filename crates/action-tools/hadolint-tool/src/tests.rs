@@ -0,0 +1,202 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use rhema_action_tool::environment::probe_for;
+use rhema_action_tool::platform::resolve_program;
+use rhema_action_tool::{ActionIntent, ActionType, SafetyLevel};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_hadolint_tool_creation() {
+    let tool = HadolintTool;
+    assert_eq!(ValidationTool::name(&tool), "hadolint");
+    assert_eq!(ValidationTool::version(&tool), "1.0.0");
+}
+
+#[test]
+fn test_parse_config_default() {
+    let tool = HadolintTool;
+    let intent = ActionIntent::new(
+        "test",
+        ActionType::Cleanup,
+        "Harden container images",
+        vec![],
+        SafetyLevel::Low,
+    );
+
+    assert_eq!(tool.parse_config(&intent), HadolintConfig::default());
+}
+
+#[test]
+fn test_parse_config_custom() {
+    let tool = HadolintTool;
+    let mut intent = ActionIntent::new(
+        "test",
+        ActionType::Cleanup,
+        "Harden container images",
+        vec![],
+        SafetyLevel::Low,
+    );
+    intent.metadata = json!({
+        "failure_threshold": "warning",
+        "rules": {"dl3008": "ignore", "DL30*": "error", "DL3059": "info", "DL3013": "bogus"},
+        "trusted_registries": ["registry.example.com"]
+    });
+
+    let config = tool.parse_config(&intent);
+    assert_eq!(config.failure_threshold, "warning");
+    assert_eq!(config.rules.len(), 3);
+
+    let files = dockerfiles(&[
+        "Dockerfile".to_string(),
+        "api/Dockerfile.dev".to_string(),
+        "worker/build.Dockerfile".to_string(),
+        "docs/dockerfiles.md".to_string(),
+        "Dockerfile".to_string(),
+    ]);
+    assert_eq!(
+        files,
+        vec![
+            "Dockerfile",
+            "api/Dockerfile.dev",
+            "worker/build.Dockerfile"
+        ]
+    );
+    assert_eq!(
+        tool.build_command_args(&files[..1], &config),
+        vec![
+            "--format=json",
+            "--no-fail",
+            "--ignore=DL3008",
+            "--trusted-registry=registry.example.com",
+            "Dockerfile"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_validation_with_no_dockerfiles() {
+    let tool = HadolintTool;
+    let intent = ActionIntent::new(
+        "test",
+        ActionType::Cleanup,
+        "Harden container images",
+        vec!["README.md".to_string()],
+        SafetyLevel::Low,
+    );
+
+    let result = tool.validate(&intent).await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.output, "No Dockerfiles found to validate");
+}
+
+#[tokio::test]
+async fn test_validate_with_hadolint() {
+    let tool = HadolintTool;
+    assert!(probe_for("hadolint").is_some());
+    assert_eq!(
+        ValidationTool::is_available(&tool).await,
+        resolve_program("hadolint").is_some()
+    );
+    if !ValidationTool::is_available(&tool).await {
+        return;
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let dockerfile = dir.path().join("Dockerfile");
+    std::fs::write(
+        &dockerfile,
+        "FROM debian:12\nRUN apt-get update && apt-get install -y curl\n",
+    )
+    .unwrap();
+    let intent = ActionIntent::new(
+        "test",
+        ActionType::Cleanup,
+        "Harden container images",
+        vec![dockerfile.to_string_lossy().to_string()],
+        SafetyLevel::Low,
+    );
+
+    let result = tool.validate(&intent).await.unwrap();
+    assert!(result.success);
+    assert!(result.warnings.iter().any(|w| w.contains("[DL3008]")));
+}
+
+#[test]
+fn test_classify_by_threshold_and_rules() {
+    let mut config = HadolintConfig::default();
+    assert_eq!(
+        config.classify("DL3008", "warning"),
+        Some(Severity::Warning)
+    );
+    assert_eq!(config.classify("DL3002", "error"), Some(Severity::Error));
+    assert_eq!(config.classify("SC2086", "info"), Some(Severity::Info));
+
+    config.failure_threshold = "warning".to_string();
+    assert_eq!(config.classify("DL3008", "warning"), Some(Severity::Error));
+    assert_eq!(config.classify("DL3048", "style"), Some(Severity::Info));
+
+    // The most specific rule wins over the hadolint level
+    config
+        .rules
+        .insert("DL3*".to_string(), RuleSeverity::Warning);
+    config
+        .rules
+        .insert("DL3007".to_string(), RuleSeverity::Error);
+    config
+        .rules
+        .insert("DL3059".to_string(), RuleSeverity::Ignore);
+    assert_eq!(
+        config.classify("DL3008", "warning"),
+        Some(Severity::Warning)
+    );
+    assert_eq!(config.classify("DL3007", "warning"), Some(Severity::Error));
+    assert_eq!(config.classify("DL3059", "info"), None);
+}
+
+#[test]
+fn test_parse_hadolint_json() {
+    let stdout = r#"[
+        {"code": "DL3008", "column": 1, "file": "Dockerfile", "level": "warning", "line": 4,
+         "message": "Pin versions in apt get install."},
+        {"code": "DL3059", "column": 1, "file": "Dockerfile", "level": "info", "line": 6,
+         "message": "Multiple consecutive `RUN` instructions. Consider consolidation."},
+        {"code": "DL3002", "column": 1, "file": "Dockerfile", "level": "warning", "line": 9,
+         "message": "Last USER should not be root"}
+    ]"#;
+    let mut config = HadolintConfig::default();
+    config
+        .rules
+        .insert("DL3002".to_string(), RuleSeverity::Error);
+    config
+        .rules
+        .insert("DL3059".to_string(), RuleSeverity::Ignore);
+    let diagnostics = parse_hadolint_json(stdout, &config).unwrap();
+
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "Dockerfile:4:1: [DL3008] Pin versions in apt get install."
+    );
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert_eq!(diagnostics[1].severity, Severity::Error);
+    assert_eq!(parse_hadolint_json("[]", &config), Some(vec![]));
+    assert_eq!(
+        parse_hadolint_json("openBinaryFile: does not exist", &config),
+        None
+    );
+}
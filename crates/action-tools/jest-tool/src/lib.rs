@@ -15,13 +15,189 @@
  */
 
 use async_trait::async_trait;
+use rhema_action_tool::platform::tool_command;
+use rhema_action_tool::{tool_available, LimitedCommand};
 use rhema_action_tool::{ActionError, ActionIntent, ActionResult};
-use rhema_action_tool::{ToolResult, ValidationTool};
+use rhema_action_tool::{Diagnostic, ToolResult, ToolchainProfile, ValidationTool};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::time::Duration;
 use tracing::{info, warn};
 
 /// Jest validation tool
 pub struct JestTool;
 
+/// Extensions of the source files coverage is collected from
+const SOURCE_EXTENSIONS: &[&str] = &[".js", ".jsx", ".ts", ".tsx", ".mjs", ".cjs"];
+
+/// Jest tool configuration
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JestConfig {
+    /// Run with `--coverage`
+    pub coverage: bool,
+    /// Minimum coverage percentages, enforced when `coverage` is set
+    pub thresholds: CoverageThresholds,
+}
+
+/// Minimum coverage percentages per metric, as in Jest's `coverageThreshold`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CoverageThresholds {
+    pub lines: Option<f64>,
+    pub statements: Option<f64>,
+    pub functions: Option<f64>,
+    pub branches: Option<f64>,
+}
+
+impl CoverageThresholds {
+    fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Metrics of `summary` below their threshold, e.g.
+    /// `branches coverage 62.50% is below the 70% threshold`
+    pub fn shortfalls(&self, summary: &CoverageSummary) -> Vec<String> {
+        [
+            ("lines", self.lines, summary.lines),
+            ("statements", self.statements, summary.statements),
+            ("functions", self.functions, summary.functions),
+            ("branches", self.branches, summary.branches),
+        ]
+        .into_iter()
+        .filter_map(|(metric, threshold, coverage)| {
+            let threshold = threshold?;
+            (coverage.percent() < threshold).then(|| {
+                format!(
+                    "{} coverage {:.2}% is below the {}% threshold",
+                    metric,
+                    coverage.percent(),
+                    threshold
+                )
+            })
+        })
+        .collect()
+    }
+}
+
+/// Covered and total count of one coverage metric
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Coverage {
+    pub covered: u64,
+    pub total: u64,
+}
+
+impl Coverage {
+    /// Percentage covered; nothing to cover counts as fully covered, as in Jest
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            100.0
+        } else {
+            self.covered as f64 * 100.0 / self.total as f64
+        }
+    }
+
+    fn add(&mut self, covered: bool) {
+        self.total += 1;
+        if covered {
+            self.covered += 1;
+        }
+    }
+}
+
+/// Coverage over every file Jest instrumented
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CoverageSummary {
+    pub lines: Coverage,
+    pub statements: Coverage,
+    pub functions: Coverage,
+    pub branches: Coverage,
+}
+
+impl std::fmt::Display for CoverageSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "lines {:.2}%, statements {:.2}%, functions {:.2}%, branches {:.2}%",
+            self.lines.percent(),
+            self.statements.percent(),
+            self.functions.percent(),
+            self.branches.percent()
+        )
+    }
+}
+
+/// Parsed Jest `--json` report
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JestReport {
+    pub suites: Vec<SuiteResult>,
+    /// Present when Jest ran with `--coverage`
+    pub coverage: Option<CoverageSummary>,
+}
+
+impl JestReport {
+    /// Every test of every suite
+    pub fn tests(&self) -> impl Iterator<Item = &TestOutcome> {
+        self.suites.iter().flat_map(|suite| suite.tests.iter())
+    }
+
+    /// Suite file and full name of the failed tests, joined with `›` as Jest
+    /// prints them, followed by suites that failed without running a test
+    pub fn failed_tests(&self) -> Vec<String> {
+        let mut failed = Vec::new();
+        for suite in &self.suites {
+            failed.extend(
+                suite
+                    .tests
+                    .iter()
+                    .filter(|test| test.status == TestStatus::Failed)
+                    .map(|test| format!("{} › {}", suite.file, test.name)),
+            );
+            if suite.failed_to_run() {
+                failed.push(suite.file.clone());
+            }
+        }
+        failed
+    }
+}
+
+/// Results of one test file
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuiteResult {
+    pub file: String,
+    pub passed: bool,
+    /// Why the suite failed, e.g. a syntax error, when it is not a test
+    pub message: Option<String>,
+    pub tests: Vec<TestOutcome>,
+}
+
+impl SuiteResult {
+    /// Failed without a failing test, so the file itself is broken
+    fn failed_to_run(&self) -> bool {
+        !self.passed && !self.tests.iter().any(|t| t.status == TestStatus::Failed)
+    }
+}
+
+/// Outcome of a single test
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestOutcome {
+    /// Describe blocks and title, e.g. `parser handles empty input`
+    pub name: String,
+    pub status: TestStatus,
+    pub duration: Option<Duration>,
+    /// 1-based line of the test
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+    /// Failure messages of a failed test
+    pub failures: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestStatus {
+    Passed,
+    Failed,
+    /// Skipped, pending or `todo`
+    Skipped,
+}
+
 #[async_trait]
 impl ValidationTool for JestTool {
     async fn validate(&self, intent: &ActionIntent) -> ActionResult<ToolResult> {
@@ -38,17 +214,7 @@ impl ValidationTool for JestTool {
         }
 
         // Find test files and related source files
-        let test_files: Vec<&String> = files
-            .iter()
-            .filter(|f| {
-                f.contains("test")
-                    || f.contains("spec")
-                    || f.ends_with(".test.js")
-                    || f.ends_with(".test.ts")
-                    || f.ends_with(".spec.js")
-                    || f.ends_with(".spec.ts")
-            })
-            .collect();
+        let test_files: Vec<&String> = files.iter().filter(|f| is_test_file(f)).collect();
 
         if test_files.is_empty() {
             return Ok(ToolResult {
@@ -58,36 +224,100 @@ impl ValidationTool for JestTool {
                 errors: vec![],
                 warnings: vec!["No test files found in scope".to_string()],
                 duration: start.elapsed(),
+                cached: false,
+                diagnostics: Vec::new(),
+                failed_tests: Vec::new(),
             });
         }
 
-        // Run Jest tests
-        let mut changes = Vec::new();
+        let config = self.parse_config(intent);
+        let source_files: Vec<&String> = files
+            .iter()
+            .filter(|f| !is_test_file(f) && SOURCE_EXTENSIONS.iter().any(|ext| f.ends_with(ext)))
+            .collect();
+        let report = self
+            .run_jest_tests(&test_files, &source_files, &config)
+            .await?;
+
         let mut errors = Vec::new();
-        let warnings = Vec::new();
+        let mut warnings = Vec::new();
+        let mut diagnostics = Vec::new();
+        for suite in &report.suites {
+            for test in suite
+                .tests
+                .iter()
+                .filter(|t| t.status == TestStatus::Failed)
+            {
+                let message = test
+                    .failures
+                    .first()
+                    .and_then(|failure| failure.lines().find(|line| !line.trim().is_empty()))
+                    .unwrap_or("test failed")
+                    .trim();
+                let diagnostic = Diagnostic::error("jest", format!("{}: {}", test.name, message))
+                    .at(suite.file.as_str(), test.line, test.column);
+                errors.push(diagnostic.to_string());
+                diagnostics.push(diagnostic);
+            }
+            if suite.failed_to_run() {
+                let message = suite
+                    .message
+                    .as_deref()
+                    .and_then(|message| message.lines().find(|line| !line.trim().is_empty()))
+                    .unwrap_or("test suite failed to run")
+                    .trim();
+                let diagnostic =
+                    Diagnostic::error("jest", message).at(suite.file.as_str(), None, None);
+                errors.push(diagnostic.to_string());
+                diagnostics.push(diagnostic);
+            }
+        }
 
-        match self.run_jest_tests(&test_files).await {
-            Ok(output) => {
-                changes.push("Jest tests completed successfully".to_string());
-                if !output.is_empty() {
-                    changes.push(format!("Jest output: {}", output));
+        let tests: Vec<&TestOutcome> = report.tests().collect();
+        let count = |status| tests.iter().filter(|t| t.status == status).count();
+        let mut output = format!(
+            "Jest ran {} tests in {} suites: {} passed, {} failed, {} skipped",
+            tests.len(),
+            report.suites.len(),
+            count(TestStatus::Passed),
+            count(TestStatus::Failed),
+            count(TestStatus::Skipped)
+        );
+        let mut changes = Vec::new();
+        if config.coverage {
+            match &report.coverage {
+                Some(summary) => {
+                    output.push_str(&format!("; coverage: {}", summary));
+                    errors.extend(config.thresholds.shortfalls(summary));
+                }
+                None if config.thresholds.is_empty() => {
+                    warnings.push("Jest reported no coverage".to_string())
                 }
+                None => errors
+                    .push("Jest reported no coverage to check against the thresholds".to_string()),
             }
-            Err(e) => errors.push(format!("Jest tests failed: {}", e)),
         }
-
-        let success = errors.is_empty();
+        if errors.is_empty() {
+            changes.push("Jest tests completed successfully".to_string());
+        }
 
         Ok(ToolResult {
-            success,
+            success: errors.is_empty(),
             changes,
-            output: format!("Ran Jest tests on {} files", test_files.len()),
+            output,
             errors,
             warnings,
             duration: start.elapsed(),
+            cached: false,
+            diagnostics,
+            failed_tests: report.failed_tests(),
         })
     }
 
+    fn applies_to(&self, profile: &ToolchainProfile) -> bool {
+        profile.has_framework("jest")
+    }
+
     fn name(&self) -> &str {
         "jest"
     }
@@ -97,48 +327,236 @@ impl ValidationTool for JestTool {
     }
 
     async fn is_available(&self) -> bool {
-        // Check if Jest is installed
-        tokio::process::Command::new("npx")
-            .args(&["jest", "--version"])
-            .output()
-            .await
-            .map(|output| output.status.success())
-            .unwrap_or(false)
+        tool_available("jest").await
     }
 }
 
 impl JestTool {
+    /// Parse configuration from intent metadata
+    fn parse_config(&self, intent: &ActionIntent) -> JestConfig {
+        let mut config = JestConfig::default();
+        let metadata = &intent.metadata;
+        if metadata.is_null() {
+            return config;
+        }
+
+        config.coverage = metadata
+            .get("coverage")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+
+        // A single percentage applies to every metric
+        match metadata.get("coverage_threshold") {
+            Some(Value::Number(percent)) => {
+                let percent = percent.as_f64();
+                config.thresholds = CoverageThresholds {
+                    lines: percent,
+                    statements: percent,
+                    functions: percent,
+                    branches: percent,
+                };
+            }
+            Some(Value::Object(metrics)) => {
+                let metric = |name: &str| metrics.get(name).and_then(Value::as_f64);
+                config.thresholds = CoverageThresholds {
+                    lines: metric("lines"),
+                    statements: metric("statements"),
+                    functions: metric("functions"),
+                    branches: metric("branches"),
+                };
+            }
+            _ => {}
+        }
+        // Thresholds need coverage, unless it was turned off explicitly
+        if !config.thresholds.is_empty() && metadata.get("coverage").is_none() {
+            config.coverage = true;
+        }
+
+        config
+    }
+
+    fn build_command_args(
+        &self,
+        test_files: &[&String],
+        source_files: &[&String],
+        config: &JestConfig,
+    ) -> Vec<String> {
+        let mut args: Vec<String> = [
+            "jest",
+            "--passWithNoTests",
+            "--json",
+            "--testLocationInResults",
+        ]
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect();
+        if config.coverage {
+            args.push("--coverage".to_string());
+            // Measure the changed sources rather than whatever the tests load
+            for file in source_files {
+                args.push(format!("--collectCoverageFrom={}", file));
+            }
+        }
+        args.extend(test_files.iter().map(|f| f.to_string()));
+        args
+    }
+
     /// Run Jest tests on specified files
-    async fn run_jest_tests(&self, test_files: &[&String]) -> ActionResult<String> {
+    async fn run_jest_tests(
+        &self,
+        test_files: &[&String],
+        source_files: &[&String],
+        config: &JestConfig,
+    ) -> ActionResult<JestReport> {
         info!("Running Jest tests on {} files", test_files.len());
 
         // Execute Jest
-        let output = tokio::process::Command::new("npx")
-            .args(&["jest", "--passWithNoTests", "--verbose", "--json"])
-            .args(test_files.iter().map(|f| f.as_str()))
-            .output()
+        let output = tool_command("npx")
+            .args(self.build_command_args(test_files, source_files, config))
+            .env("FORCE_COLOR", "0")
+            .limited_output()
             .await
             .map_err(|e| ActionError::ToolExecution {
                 tool: "jest".to_string(),
                 message: format!("Failed to execute Jest: {}", e),
             })?;
 
-        if output.status.success() {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            let stderr = String::from_utf8_lossy(&output.stderr);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !stderr.is_empty() {
+            warn!("Jest stderr: {}", stderr);
+        }
 
-            info!("Jest stdout: {}", stdout);
-            if !stderr.is_empty() {
-                warn!("Jest stderr: {}", stderr);
+        // Jest exits with an error when tests fail, but still prints its
+        // report; only a missing report means Jest itself failed
+        parse_jest_json(&String::from_utf8_lossy(&output.stdout)).ok_or_else(|| {
+            ActionError::ToolExecution {
+                tool: "jest".to_string(),
+                message: format!("Jest did not produce a report: {}", stderr.trim()),
             }
+        })
+    }
+}
 
-            Ok(stdout.to_string())
-        } else {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            Err(ActionError::ToolExecution {
-                tool: "jest".to_string(),
-                message: format!("Jest tests failed: {}", stderr),
+fn is_test_file(file: &str) -> bool {
+    file.contains("test")
+        || file.contains("spec")
+        || file.ends_with(".test.js")
+        || file.ends_with(".test.ts")
+        || file.ends_with(".spec.js")
+        || file.ends_with(".spec.ts")
+}
+
+/// Parse Jest's `--json` report, skipping anything printed before it
+fn parse_jest_json(stdout: &str) -> Option<JestReport> {
+    let report: Value = serde_json::from_str(&stdout[stdout.find('{')?..]).ok()?;
+    let suites = report
+        .get("testResults")?
+        .as_array()?
+        .iter()
+        .map(|suite| {
+            let text = |key: &str| suite.get(key).and_then(Value::as_str).unwrap_or("");
+            let tests = suite
+                .get("assertionResults")
+                .and_then(Value::as_array)
+                .map(|tests| tests.iter().map(parse_test).collect())
+                .unwrap_or_default();
+            SuiteResult {
+                file: text("name").to_string(),
+                passed: text("status") != "failed",
+                message: Some(text("message").trim())
+                    .filter(|message| !message.is_empty())
+                    .map(str::to_string),
+                tests,
+            }
+        })
+        .collect();
+    let coverage = report
+        .get("coverageMap")
+        .and_then(Value::as_object)
+        .map(summarize_coverage);
+    Some(JestReport { suites, coverage })
+}
+
+fn parse_test(test: &Value) -> TestOutcome {
+    let text = |key: &str| test.get(key).and_then(Value::as_str).unwrap_or("");
+    let location = |key: &str| {
+        test.get("location")
+            .and_then(|location| location.get(key))
+            .and_then(Value::as_u64)
+            .and_then(|n| u32::try_from(n).ok())
+    };
+    let name = match text("fullName") {
+        "" => text("title"),
+        full_name => full_name,
+    };
+    let status = match text("status") {
+        "passed" => TestStatus::Passed,
+        "failed" => TestStatus::Failed,
+        _ => TestStatus::Skipped,
+    };
+    TestOutcome {
+        name: name.to_string(),
+        status,
+        duration: test
+            .get("duration")
+            .and_then(Value::as_u64)
+            .map(Duration::from_millis),
+        line: location("line"),
+        // Jest reports 0-based columns
+        column: location("column").map(|column| column + 1),
+        failures: test
+            .get("failureMessages")
+            .and_then(Value::as_array)
+            .map(|messages| {
+                messages
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect()
             })
+            .unwrap_or_default(),
+    }
+}
+
+/// Totals of an istanbul coverage map, counted the way istanbul's summary
+/// does: a line is covered when any statement starting on it ran
+fn summarize_coverage(map: &serde_json::Map<String, Value>) -> CoverageSummary {
+    let mut summary = CoverageSummary::default();
+    for file in map.values() {
+        // Older reporters wrap the file coverage in `data`
+        let file = file.get("data").unwrap_or(file);
+        let hits = |key: &str| {
+            file.get(key)
+                .and_then(Value::as_object)
+                .into_iter()
+                .flat_map(|counts| counts.iter())
+        };
+
+        let mut lines: BTreeMap<u64, bool> = BTreeMap::new();
+        for (id, count) in hits("s") {
+            let covered = count.as_u64().unwrap_or(0) > 0;
+            summary.statements.add(covered);
+            if let Some(line) = file
+                .pointer(&format!("/statementMap/{}/start/line", id))
+                .and_then(Value::as_u64)
+            {
+                *lines.entry(line).or_default() |= covered;
+            }
+        }
+        for covered in lines.into_values() {
+            summary.lines.add(covered);
+        }
+        for (_, count) in hits("f") {
+            summary.functions.add(count.as_u64().unwrap_or(0) > 0);
+        }
+        for (_, counts) in hits("b") {
+            for count in counts.as_array().into_iter().flatten() {
+                summary.branches.add(count.as_u64().unwrap_or(0) > 0);
+            }
         }
     }
+    summary
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,295 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use rhema_action_tool::environment::probe_for;
+use rhema_action_tool::platform::resolve_program;
+use rhema_action_tool::{ActionIntent, ActionType, SafetyLevel};
+use rhema_action_tool::{ResourceLimits, RuntimeContext, ToolExecution};
+use serde_json::json;
+use tokio_util::sync::CancellationToken;
+
+#[tokio::test]
+async fn test_jest_tool_creation() {
+    let tool = JestTool;
+    assert_eq!(ValidationTool::name(&tool), "jest");
+    assert_eq!(ValidationTool::version(&tool), "1.0.0");
+}
+
+#[test]
+fn test_parse_config_default() {
+    let tool = JestTool;
+    let intent = ActionIntent::new(
+        "test",
+        ActionType::Cleanup,
+        "Cover the parser",
+        vec!["src/parser.test.ts".to_string()],
+        SafetyLevel::Low,
+    );
+
+    assert_eq!(tool.parse_config(&intent), JestConfig::default());
+}
+
+#[test]
+fn test_parse_config_custom() {
+    let tool = JestTool;
+    let mut intent = ActionIntent::new(
+        "test",
+        ActionType::Cleanup,
+        "Cover the parser",
+        vec!["src/parser.test.ts".to_string()],
+        SafetyLevel::Low,
+    );
+    intent.metadata = json!({"coverage_threshold": 80});
+    let config = tool.parse_config(&intent);
+    assert!(config.coverage);
+    assert_eq!(config.thresholds.branches, Some(80.0));
+
+    intent.metadata = json!({"coverage": false, "coverage_threshold": 80});
+    assert!(!tool.parse_config(&intent).coverage);
+
+    intent.metadata = json!({
+        "coverage": true,
+        "coverage_threshold": {"lines": 90, "branches": 75.5}
+    });
+    let config = tool.parse_config(&intent);
+    assert_eq!(
+        config.thresholds,
+        CoverageThresholds {
+            lines: Some(90.0),
+            branches: Some(75.5),
+            ..Default::default()
+        }
+    );
+
+    let test = "src/parser.test.ts".to_string();
+    let source = "src/parser.ts".to_string();
+    assert_eq!(
+        tool.build_command_args(&[&test], &[&source], &config),
+        vec![
+            "jest",
+            "--passWithNoTests",
+            "--json",
+            "--testLocationInResults",
+            "--coverage",
+            "--collectCoverageFrom=src/parser.ts",
+            "src/parser.test.ts",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_validation_with_no_test_files() {
+    let tool = JestTool;
+    let intent = ActionIntent::new(
+        "test",
+        ActionType::Cleanup,
+        "Cover the parser",
+        vec!["src/parser.ts".to_string()],
+        SafetyLevel::Low,
+    );
+
+    let result = tool.validate(&intent).await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.output, "No test files found in scope");
+}
+
+/// Runs in a project whose `node_modules/.bin/jest` reports one failing
+/// test, passed to the tool as the execution's working directory, so `npx`
+/// resolves it without installing anything
+#[cfg(unix)]
+#[tokio::test]
+async fn test_validate_with_project_jest() {
+    use std::os::unix::fs::PermissionsExt;
+
+    assert!(probe_for("jest").unwrap().local_bin.is_some());
+    let project = tempfile::tempdir().unwrap();
+    let bin = project.path().join("node_modules/.bin");
+    std::fs::create_dir_all(&bin).unwrap();
+    std::fs::write(project.path().join("package.json"), r#"{"name": "parser"}"#).unwrap();
+    let report = json!({
+        "numFailedTests": 1,
+        "testResults": [{
+            "name": "/repo/src/parser.test.ts",
+            "status": "failed",
+            "message": "",
+            "assertionResults": [{
+                "fullName": "parser rejects bad tokens",
+                "status": "failed",
+                "failureMessages": ["Error: expect(received).toThrow()"],
+                "location": {"line": 9, "column": 2}
+            }]
+        }]
+    });
+    let script = format!(
+        "#!/bin/sh\n[ \"$1\" = --version ] && echo 29.7.0 && exit 0\necho '{}'\nexit 1\n",
+        report
+    );
+    std::fs::write(bin.join("jest"), script).unwrap();
+    std::fs::set_permissions(bin.join("jest"), std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let tool = JestTool;
+    let intent = ActionIntent::new(
+        "test",
+        ActionType::Cleanup,
+        "Cover the parser",
+        vec!["src/parser.test.ts".to_string()],
+        SafetyLevel::Low,
+    );
+    let execution = ToolExecution::new(ResourceLimits::default(), CancellationToken::new())
+        .with_runtime(RuntimeContext {
+            working_dir: Some(project.path().to_path_buf()),
+            ..RuntimeContext::default()
+        });
+    let (available, result) = execution
+        .run(async {
+            if !ValidationTool::is_available(&tool).await {
+                return (false, None);
+            }
+            (true, Some(tool.validate(&intent).await.unwrap()))
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(available, resolve_program("npx").is_some());
+    if let Some(result) = result {
+        assert!(!result.success);
+        assert_eq!(
+            result.failed_tests,
+            vec!["/repo/src/parser.test.ts › parser rejects bad tokens"]
+        );
+    }
+}
+
+#[test]
+fn test_parse_jest_json_results() {
+    let stdout = json!({
+        "numFailedTests": 1,
+        "testResults": [
+            {
+                "name": "/repo/src/parser.test.ts",
+                "status": "failed",
+                "message": "",
+                "assertionResults": [
+                    {
+                        "fullName": "parser handles empty input",
+                        "title": "handles empty input",
+                        "status": "passed",
+                        "duration": 4,
+                        "failureMessages": [],
+                        "location": {"line": 3, "column": 2}
+                    },
+                    {
+                        "fullName": "parser rejects bad tokens",
+                        "title": "rejects bad tokens",
+                        "status": "failed",
+                        "duration": 7,
+                        "failureMessages": ["\nError: expect(received).toThrow()\n    at Object.<anonymous>"],
+                        "location": {"line": 9, "column": 2}
+                    },
+                    {"fullName": "parser streams", "status": "todo", "failureMessages": []}
+                ]
+            },
+            {
+                "name": "/repo/src/lexer.test.ts",
+                "status": "failed",
+                "message": "  ● Test suite failed to run\n\n    SyntaxError: Unexpected token",
+                "assertionResults": []
+            }
+        ]
+    })
+    .to_string();
+    let report = parse_jest_json(&format!("Determining test suites...\n{}", stdout)).unwrap();
+
+    assert_eq!(report.suites.len(), 2);
+    assert_eq!(report.tests().count(), 3);
+    assert_eq!(report.coverage, None);
+    let failed = &report.suites[0].tests[1];
+    assert_eq!(failed.status, TestStatus::Failed);
+    assert_eq!((failed.line, failed.column), (Some(9), Some(3)));
+    assert_eq!(failed.duration, Some(Duration::from_millis(7)));
+    assert_eq!(report.suites[0].tests[2].status, TestStatus::Skipped);
+    assert_eq!(
+        report.failed_tests(),
+        vec![
+            "/repo/src/parser.test.ts › parser rejects bad tokens",
+            "/repo/src/lexer.test.ts",
+        ]
+    );
+    assert!(parse_jest_json("npx: command not found").is_none());
+}
+
+#[test]
+fn test_coverage_summary_and_thresholds() {
+    let stdout = json!({
+        "testResults": [],
+        "coverageMap": {
+            "/repo/src/parser.ts": {
+                "path": "/repo/src/parser.ts",
+                "statementMap": {
+                    "0": {"start": {"line": 1, "column": 0}, "end": {"line": 1, "column": 10}},
+                    "1": {"start": {"line": 1, "column": 12}, "end": {"line": 1, "column": 20}},
+                    "2": {"start": {"line": 2, "column": 0}, "end": {"line": 2, "column": 10}},
+                    "3": {"start": {"line": 4, "column": 0}, "end": {"line": 4, "column": 10}}
+                },
+                "s": {"0": 1, "1": 0, "2": 3, "3": 0},
+                "f": {"0": 2, "1": 0},
+                "b": {"0": [1, 0], "1": [4, 2]}
+            }
+        }
+    })
+    .to_string();
+    let summary = parse_jest_json(&stdout).unwrap().coverage.unwrap();
+    assert_eq!(
+        summary.statements,
+        Coverage {
+            covered: 2,
+            total: 4
+        }
+    );
+    assert_eq!(
+        summary.lines,
+        Coverage {
+            covered: 2,
+            total: 3
+        }
+    );
+    assert_eq!(
+        summary.functions,
+        Coverage {
+            covered: 1,
+            total: 2
+        }
+    );
+    assert_eq!(
+        summary.branches,
+        Coverage {
+            covered: 3,
+            total: 4
+        }
+    );
+    assert_eq!(Coverage::default().percent(), 100.0);
+
+    let thresholds = CoverageThresholds {
+        lines: Some(60.0),
+        branches: Some(80.0),
+        ..Default::default()
+    };
+    assert_eq!(
+        thresholds.shortfalls(&summary),
+        vec!["branches coverage 75.00% is below the 80% threshold"]
+    );
+}
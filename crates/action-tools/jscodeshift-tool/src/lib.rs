@@ -15,8 +15,11 @@
  */
 
 use async_trait::async_trait;
+use rhema_action_tool::platform::tool_command;
+use rhema_action_tool::{tool_available, LimitedCommand};
 use rhema_action_tool::{
-    ActionError, ActionIntent, ActionResult, SafetyLevel, ToolResult, TransformationTool,
+    ActionError, ActionIntent, ActionResult, SafetyLevel, ToolResult, ToolchainProfile,
+    TransformationTool,
 };
 use tracing::{info, warn};
 
@@ -28,6 +31,10 @@ impl TransformationTool for JscodeshiftTool {
     async fn execute(&self, intent: &ActionIntent) -> ActionResult<ToolResult> {
         info!("Executing jscodeshift for intent: {}", intent.id);
 
+        if intent.dry_run {
+            return self.preview(intent).await;
+        }
+
         let start = std::time::Instant::now();
 
         // Extract file paths from intent
@@ -69,6 +76,9 @@ impl TransformationTool for JscodeshiftTool {
             errors,
             warnings,
             duration: start.elapsed(),
+            cached: false,
+            diagnostics: Vec::new(),
+            failed_tests: Vec::new(),
         })
     }
 
@@ -76,6 +86,11 @@ impl TransformationTool for JscodeshiftTool {
         matches!(language, "javascript" | "typescript" | "jsx" | "tsx")
     }
 
+    /// Codemods only run when named explicitly
+    fn applies_to(&self, _profile: &ToolchainProfile) -> bool {
+        false
+    }
+
     fn safety_level(&self) -> SafetyLevel {
         SafetyLevel::Medium
     }
@@ -89,13 +104,7 @@ impl TransformationTool for JscodeshiftTool {
     }
 
     async fn is_available(&self) -> bool {
-        // Check if jscodeshift is installed
-        tokio::process::Command::new("npx")
-            .args(&["jscodeshift", "--version"])
-            .output()
-            .await
-            .map(|output| output.status.success())
-            .unwrap_or(false)
+        tool_available("jscodeshift").await
     }
 }
 
@@ -207,7 +216,7 @@ module.exports = function(fileInfo, api, options) {
         };
 
         // Execute jscodeshift using npx
-        let output = tokio::process::Command::new("npx")
+        let output = tool_command("npx")
             .args(&[
                 "jscodeshift",
                 "--transform",
@@ -223,7 +232,7 @@ module.exports = function(fileInfo, api, options) {
                 "--run-in-band", // Run transformations sequentially
                 file_path,
             ])
-            .output()
+            .limited_output()
             .await
             .map_err(|e| ActionError::ToolExecution {
                 tool: "jscodeshift".to_string(),
@@ -0,0 +1,503 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use async_trait::async_trait;
+use regex::Regex;
+use rhema_action_tool::platform::{normalize_path, relative_path, tool_command};
+use rhema_action_tool::LimitedCommand;
+use rhema_action_tool::{ActionError, ActionIntent, ActionResult, SafetyLevel};
+use rhema_action_tool::{SafetyTool, ToolResult};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Section of `.rhema/repository.yaml` holding the compliance policy
+pub const CONFIG_SECTION: &str = "license_compliance";
+
+/// License header and protected file policy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LicenseComplianceConfig {
+    /// Text every license header must contain
+    pub required_markers: Vec<String>,
+
+    /// Header new files must carry; `{year}` matches any four-digit year
+    pub header_template: Option<String>,
+
+    /// File extensions that require a header
+    pub extensions: Vec<String>,
+
+    /// Number of leading lines searched for the header
+    pub header_lines: usize,
+
+    /// Globs (relative to the repo root) that are exempt from header checks
+    pub exclude: Vec<String>,
+
+    /// Globs for files that may only change at `protected_safety_level` or above
+    pub protected_files: Vec<String>,
+
+    pub protected_safety_level: SafetyLevel,
+}
+
+impl Default for LicenseComplianceConfig {
+    fn default() -> Self {
+        Self {
+            required_markers: vec!["Licensed under the Apache License, Version 2.0".to_string()],
+            header_template: None,
+            extensions: [
+                "rs", "ts", "tsx", "js", "jsx", "py", "go", "java", "kt", "swift", "c", "h", "cc",
+                "cpp", "hpp", "sh",
+            ]
+            .iter()
+            .map(|ext| ext.to_string())
+            .collect(),
+            header_lines: 25,
+            exclude: Vec::new(),
+            protected_files: [
+                "LICENSE",
+                "LICENSE.*",
+                "COPYING*",
+                "SECURITY.md",
+                "CODEOWNERS",
+                ".github/CODEOWNERS",
+                "docs/CODEOWNERS",
+            ]
+            .iter()
+            .map(|pattern| pattern.to_string())
+            .collect(),
+            protected_safety_level: SafetyLevel::High,
+        }
+    }
+}
+
+impl LicenseComplianceConfig {
+    /// Load the policy from the repository config and policy bundle, falling
+    /// back to defaults
+    pub fn load(repo_root: &Path) -> ActionResult<Self> {
+        let value = rhema_core::policy::repository_config(repo_root)
+            .map_err(|e| ActionError::Configuration(e.to_string()))?;
+        match value.get(CONFIG_SECTION) {
+            Some(section) => serde_yaml::from_value(section.clone()).map_err(|e| {
+                ActionError::Configuration(format!(
+                    "Invalid {} section in the repository config: {}",
+                    CONFIG_SECTION, e
+                ))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+
+    fn is_protected(&self, relative: &str) -> bool {
+        matches_any(&self.protected_files, relative)
+    }
+
+    fn needs_header(&self, relative: &str) -> bool {
+        let extension = Path::new(relative)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_default();
+        self.extensions.iter().any(|ext| ext == extension) && !matches_any(&self.exclude, relative)
+    }
+
+    /// Whether the leading lines contain every required marker
+    pub fn has_header(&self, content: &str) -> bool {
+        let region = header_region(content, self.header_lines).join(" ");
+        self.required_markers
+            .iter()
+            .all(|marker| region.contains(marker.as_str()))
+    }
+
+    /// Whether the leading lines follow the header template, line by line
+    pub fn matches_template(&self, content: &str) -> bool {
+        let Some(template) = &self.header_template else {
+            return true;
+        };
+        let region = header_region(content, self.header_lines);
+        let mut lines = region.iter();
+        template
+            .lines()
+            .map(normalize_line)
+            .filter(|line| !line.is_empty())
+            .all(|expected| {
+                let pattern = format!(
+                    "^{}$",
+                    regex::escape(&expected).replace(r"\{year\}", r"\d{4}")
+                );
+                let Ok(re) = Regex::new(&pattern) else {
+                    return false;
+                };
+                lines.any(|line| re.is_match(line))
+            })
+    }
+}
+
+fn matches_any(patterns: &[String], relative: &str) -> bool {
+    // Patterns are written with `/`; paths from Windows callers may use `\`
+    let relative = &normalize_path(relative);
+    let file_name = Path::new(relative)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(relative);
+    patterns.iter().any(|pattern| {
+        glob::Pattern::new(pattern)
+            .map(|glob| {
+                glob.matches(relative) || (!pattern.contains('/') && glob.matches(file_name))
+            })
+            .unwrap_or(false)
+    })
+}
+
+/// Leading lines with comment syntax and blank lines removed
+fn header_region(content: &str, max_lines: usize) -> Vec<String> {
+    content
+        .lines()
+        .take(max_lines)
+        .filter(|line| !line.starts_with("#!"))
+        .map(normalize_line)
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+fn normalize_line(line: &str) -> String {
+    let mut line = line.trim();
+    for suffix in ["*/", "-->"] {
+        line = line.strip_suffix(suffix).unwrap_or(line).trim_end();
+    }
+    for prefix in ["<!--", "/*", "//", "--", "*", "#"] {
+        if let Some(rest) = line.strip_prefix(prefix) {
+            line = rest;
+            break;
+        }
+    }
+    line.trim().to_string()
+}
+
+/// How a file in the intent's scope differs from `HEAD`
+#[derive(Debug, Clone, PartialEq)]
+enum FileState {
+    New,
+    Modified {
+        previous: String,
+    },
+    Unchanged,
+    Deleted,
+    /// No git history to compare against
+    Unknown,
+}
+
+impl FileState {
+    fn describe(&self) -> &'static str {
+        match self {
+            FileState::New => "created",
+            FileState::Modified { .. } => "modified",
+            FileState::Unchanged => "unchanged",
+            FileState::Deleted => "deleted",
+            FileState::Unknown => "in scope",
+        }
+    }
+}
+
+fn safety_rank(level: SafetyLevel) -> u8 {
+    match level {
+        SafetyLevel::Low => 0,
+        SafetyLevel::Medium => 1,
+        SafetyLevel::High => 2,
+        SafetyLevel::Critical => 3,
+    }
+}
+
+/// Checks that license headers survive transformations, that new files carry
+/// the configured header, and that protected policy files only change under
+/// an elevated safety level. The policy is read from the `license_compliance`
+/// section of `.rhema/repository.yaml` unless one is supplied.
+#[derive(Default)]
+pub struct LicenseComplianceTool {
+    config: Option<LicenseComplianceConfig>,
+}
+
+impl LicenseComplianceTool {
+    pub fn with_config(config: LicenseComplianceConfig) -> Self {
+        Self {
+            config: Some(config),
+        }
+    }
+}
+
+#[async_trait]
+impl SafetyTool for LicenseComplianceTool {
+    async fn check(&self, intent: &ActionIntent) -> ActionResult<ToolResult> {
+        info!("Running license compliance check for intent: {}", intent.id);
+        let start = std::time::Instant::now();
+
+        let cwd = std::env::current_dir()?;
+        let files: Vec<PathBuf> = intent.scope.iter().map(|file| cwd.join(file)).collect();
+        let Some(first) = files.first() else {
+            return Ok(ToolResult {
+                success: true,
+                changes: vec![],
+                output: "No files to check for license compliance".to_string(),
+                errors: vec![],
+                warnings: vec![],
+                duration: start.elapsed(),
+                cached: false,
+                diagnostics: Vec::new(),
+                failed_tests: Vec::new(),
+            });
+        };
+
+        let repo_root = find_repo_root(first).unwrap_or_else(|| cwd.clone());
+        let config = match &self.config {
+            Some(config) => config.clone(),
+            None => LicenseComplianceConfig::load(&repo_root)?,
+        };
+        let git = Git::open(&repo_root).await;
+
+        let mut changes = Vec::new();
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+
+        for path in &files {
+            let relative = relative_path(path, &repo_root);
+            let state = match &git {
+                Some(git) => git.file_state(&relative, path).await,
+                None => FileState::Unknown,
+            };
+
+            if config.is_protected(&relative) {
+                let changed = state != FileState::Unchanged;
+                if changed
+                    && safety_rank(intent.safety_level) < safety_rank(config.protected_safety_level)
+                {
+                    errors.push(format!(
+                        "Protected file {} was {} under safety level {:?}; {:?} or higher is required",
+                        relative,
+                        state.describe(),
+                        intent.safety_level,
+                        config.protected_safety_level
+                    ));
+                } else {
+                    changes.push(format!("{}: protected file check passed", relative));
+                }
+                continue;
+            }
+
+            if !config.needs_header(&relative)
+                || matches!(state, FileState::Deleted | FileState::Unchanged)
+            {
+                continue;
+            }
+
+            let content = match std::fs::read_to_string(path) {
+                Ok(content) => content,
+                Err(e) => {
+                    warnings.push(format!("Could not read {}: {}", relative, e));
+                    continue;
+                }
+            };
+
+            match &state {
+                FileState::New => {
+                    if !config.has_header(&content) || !config.matches_template(&content) {
+                        errors.push(format!(
+                            "New file {} is missing the required license header",
+                            relative
+                        ));
+                    } else {
+                        changes.push(format!("{}: license header present", relative));
+                    }
+                }
+                FileState::Modified { previous } => {
+                    let had_header = config.has_header(previous);
+                    let has_header = config.has_header(&content);
+                    if had_header && !has_header {
+                        errors.push(format!(
+                            "{} lost its license header during the transformation",
+                            relative
+                        ));
+                    } else if !has_header {
+                        warnings.push(format!("{} has no license header", relative));
+                    } else {
+                        changes.push(format!("{}: license header retained", relative));
+                    }
+                }
+                FileState::Unknown => {
+                    if config.has_header(&content) {
+                        changes.push(format!("{}: license header present", relative));
+                    } else {
+                        warnings.push(format!(
+                            "{} has no license header (no git history to compare against)",
+                            relative
+                        ));
+                    }
+                }
+                FileState::Deleted | FileState::Unchanged => {}
+            }
+        }
+
+        Ok(ToolResult {
+            success: errors.is_empty(),
+            changes,
+            output: format!(
+                "License compliance check completed for {} files",
+                files.len()
+            ),
+            errors,
+            warnings,
+            duration: start.elapsed(),
+            cached: false,
+            diagnostics: Vec::new(),
+            failed_tests: Vec::new(),
+        })
+    }
+
+    fn name(&self) -> &str {
+        "license_compliance"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn is_available(&self) -> bool {
+        true
+    }
+}
+
+/// Nearest ancestor containing `.git` or `.rhema`
+fn find_repo_root(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| dir.join(".git").exists() || dir.join(".rhema").is_dir())
+        .map(Path::to_path_buf)
+}
+
+/// Git repository rooted exactly at the repo root
+struct Git {
+    root: PathBuf,
+}
+
+impl Git {
+    async fn open(root: &Path) -> Option<Self> {
+        let git = Self {
+            root: root.to_path_buf(),
+        };
+        let toplevel = git.run(&["rev-parse", "--show-toplevel"]).await?;
+        let toplevel = PathBuf::from(toplevel.trim()).canonicalize().ok()?;
+        if toplevel != root.canonicalize().ok()? {
+            return None;
+        }
+        Some(git)
+    }
+
+    async fn run(&self, args: &[&str]) -> Option<String> {
+        let output = tool_command("git")
+            .arg("-C")
+            .arg(&self.root)
+            .args(args)
+            .limited_output()
+            .await
+            .map_err(|e| warn!("Failed to run git: {}", e))
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    async fn file_state(&self, relative: &str, path: &Path) -> FileState {
+        let previous = self.run(&["show", &format!("HEAD:{}", relative)]).await;
+        match (path.exists(), previous) {
+            (true, None) => FileState::New,
+            (true, Some(previous)) => match std::fs::read_to_string(path) {
+                Ok(current) if current == previous => FileState::Unchanged,
+                _ => FileState::Modified { previous },
+            },
+            (false, Some(_)) => FileState::Deleted,
+            (false, None) => FileState::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rhema_action_tool::ActionType;
+
+    const HEADER: &str = "/*\n * Copyright 2025 Cory Parent\n *\n * Licensed under the Apache License, Version 2.0 (the \"License\");\n */\n";
+
+    #[test]
+    fn test_header_detection_ignores_comment_syntax() {
+        let config = LicenseComplianceConfig::default();
+        assert!(config.has_header(&format!("{}\nfn main() {{}}\n", HEADER)));
+        assert!(config.has_header(
+            "#!/usr/bin/env python3\n# Licensed under the Apache License, Version 2.0\n"
+        ));
+        assert!(!config.has_header("fn main() {}\n"));
+    }
+
+    #[test]
+    fn test_template_year_placeholder() {
+        let config = LicenseComplianceConfig {
+            header_template: Some(
+                "Copyright {year} Cory Parent\n\nLicensed under the Apache License, Version 2.0 (the \"License\");"
+                    .to_string(),
+            ),
+            ..Default::default()
+        };
+        assert!(config.matches_template(HEADER));
+        assert!(!config.matches_template(&HEADER.replace("Cory Parent", "Someone Else")));
+    }
+
+    #[test]
+    fn test_protected_globs() {
+        let config = LicenseComplianceConfig::default();
+        assert!(config.is_protected("LICENSE"));
+        assert!(config.is_protected("LICENSE.md"));
+        assert!(config.is_protected(".github/CODEOWNERS"));
+        assert!(config.is_protected("crates/foo/SECURITY.md"));
+        assert!(!config.is_protected("src/license.rs"));
+    }
+
+    #[tokio::test]
+    async fn test_protected_file_requires_elevated_safety_level() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(temp.path().join(".rhema")).unwrap();
+        let license = temp.path().join("LICENSE");
+        std::fs::write(&license, "Apache License\n").unwrap();
+        let scope = vec![license.to_string_lossy().to_string()];
+        let tool = LicenseComplianceTool::default();
+
+        let intent = ActionIntent::new(
+            "intent-1",
+            ActionType::Configuration,
+            "Touch license",
+            scope.clone(),
+            SafetyLevel::Medium,
+        );
+        let result = tool.check(&intent).await.unwrap();
+        assert!(!result.success);
+        assert!(result.errors[0].contains("Protected file LICENSE"));
+
+        let intent = ActionIntent::new(
+            "intent-2",
+            ActionType::Configuration,
+            "Touch license",
+            scope,
+            SafetyLevel::Critical,
+        );
+        assert!(tool.check(&intent).await.unwrap().success);
+    }
+}
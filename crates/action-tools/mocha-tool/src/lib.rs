@@ -15,8 +15,10 @@
  */
 
 use async_trait::async_trait;
+use rhema_action_tool::platform::tool_command;
+use rhema_action_tool::{tool_available, LimitedCommand};
 use rhema_action_tool::{ActionError, ActionIntent, ActionResult};
-use rhema_action_tool::{ToolResult, ValidationTool};
+use rhema_action_tool::{ToolResult, ToolchainProfile, ValidationTool};
 use tracing::{info, warn};
 
 /// Mocha validation tool
@@ -58,6 +60,9 @@ impl ValidationTool for MochaTool {
                 errors: vec![],
                 warnings: vec!["No test files found in scope".to_string()],
                 duration: start.elapsed(),
+                cached: false,
+                diagnostics: Vec::new(),
+                failed_tests: Vec::new(),
             });
         }
 
@@ -85,9 +90,16 @@ impl ValidationTool for MochaTool {
             errors,
             warnings,
             duration: start.elapsed(),
+            cached: false,
+            diagnostics: Vec::new(),
+            failed_tests: Vec::new(),
         })
     }
 
+    fn applies_to(&self, profile: &ToolchainProfile) -> bool {
+        profile.has_framework("mocha")
+    }
+
     fn name(&self) -> &str {
         "mocha"
     }
@@ -97,13 +109,7 @@ impl ValidationTool for MochaTool {
     }
 
     async fn is_available(&self) -> bool {
-        // Check if Mocha is installed
-        tokio::process::Command::new("npx")
-            .args(&["mocha", "--version"])
-            .output()
-            .await
-            .map(|output| output.status.success())
-            .unwrap_or(false)
+        tool_available("mocha").await
     }
 }
 
@@ -113,10 +119,10 @@ impl MochaTool {
         info!("Running Mocha tests on {} files", test_files.len());
 
         // Execute Mocha
-        let output = tokio::process::Command::new("npx")
+        let output = tool_command("npx")
             .args(&["mocha", "--reporter", "spec", "--timeout", "5000"])
             .args(test_files.iter().map(|f| f.as_str()))
-            .output()
+            .limited_output()
             .await
             .map_err(|e| ActionError::ToolExecution {
                 tool: "mocha".to_string(),
@@ -15,6 +15,8 @@
  */
 
 use async_trait::async_trait;
+use rhema_action_tool::platform::tool_command;
+use rhema_action_tool::{tool_available, LimitedCommand};
 use rhema_action_tool::{ActionError, ActionIntent, ActionResult, SafetyLevel};
 use rhema_action_tool::{ToolResult, TransformationTool};
 use tracing::{info, warn};
@@ -27,6 +29,10 @@ impl TransformationTool for PrettierTool {
     async fn execute(&self, intent: &ActionIntent) -> ActionResult<ToolResult> {
         info!("Executing prettier for intent: {}", intent.id);
 
+        if intent.dry_run {
+            return self.preview(intent).await;
+        }
+
         let start = std::time::Instant::now();
 
         // Extract file paths from intent
@@ -58,6 +64,9 @@ impl TransformationTool for PrettierTool {
             errors,
             warnings,
             duration: start.elapsed(),
+            cached: false,
+            diagnostics: Vec::new(),
+            failed_tests: Vec::new(),
         })
     }
 
@@ -81,13 +90,7 @@ impl TransformationTool for PrettierTool {
     }
 
     async fn is_available(&self) -> bool {
-        // Check if prettier is installed
-        tokio::process::Command::new("npx")
-            .args(&["prettier", "--version"])
-            .output()
-            .await
-            .map(|output| output.status.success())
-            .unwrap_or(false)
+        tool_available("prettier").await
     }
 }
 
@@ -105,9 +108,9 @@ impl PrettierTool {
         }
 
         // Execute prettier
-        let output = tokio::process::Command::new("npx")
+        let output = tool_command("npx")
             .args(&["prettier", "--write", file_path])
-            .output()
+            .limited_output()
             .await
             .map_err(|e| ActionError::ToolExecution {
                 tool: "prettier".to_string(),
@@ -15,8 +15,10 @@
  */
 
 use async_trait::async_trait;
+use rhema_action_tool::platform::tool_command;
+use rhema_action_tool::{tool_available, LimitedCommand};
 use rhema_action_tool::{ActionError, ActionIntent, ActionResult};
-use rhema_action_tool::{ToolResult, ValidationTool};
+use rhema_action_tool::{ToolResult, ToolchainProfile, ValidationTool};
 use tracing::{info, warn};
 
 /// PyTest validation tool
@@ -51,6 +53,9 @@ impl ValidationTool for PyTestTool {
                 errors: vec![],
                 warnings: vec!["No Python test files found in scope".to_string()],
                 duration: start.elapsed(),
+                cached: false,
+                diagnostics: Vec::new(),
+                failed_tests: Vec::new(),
             });
         }
 
@@ -78,9 +83,16 @@ impl ValidationTool for PyTestTool {
             errors,
             warnings,
             duration: start.elapsed(),
+            cached: false,
+            diagnostics: Vec::new(),
+            failed_tests: Vec::new(),
         })
     }
 
+    fn applies_to(&self, profile: &ToolchainProfile) -> bool {
+        profile.has_framework("pytest")
+    }
+
     fn name(&self) -> &str {
         "pytest"
     }
@@ -90,13 +102,7 @@ impl ValidationTool for PyTestTool {
     }
 
     async fn is_available(&self) -> bool {
-        // Check if PyTest is installed
-        tokio::process::Command::new("pytest")
-            .arg("--version")
-            .output()
-            .await
-            .map(|output| output.status.success())
-            .unwrap_or(false)
+        tool_available("pytest").await
     }
 }
 
@@ -106,10 +112,10 @@ impl PyTestTool {
         info!("Running PyTest on {} files", test_files.len());
 
         // Execute PyTest
-        let output = tokio::process::Command::new("pytest")
+        let output = tool_command("pytest")
             .args(&["--verbose", "--tb=short"])
             .args(test_files.iter().map(|f| f.as_str()))
-            .output()
+            .limited_output()
             .await
             .map_err(|e| ActionError::ToolExecution {
                 tool: "pytest".to_string(),
@@ -0,0 +1,574 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use async_trait::async_trait;
+use rhema_action_tool::platform::tool_command;
+use rhema_action_tool::{tool_available, LimitedCommand};
+use rhema_action_tool::{ActionError, ActionIntent, ActionResult, SafetyLevel};
+use rhema_action_tool::{Diagnostic, Severity, ToolResult, ToolchainProfile};
+use rhema_action_tool::{TransformationTool, ValidationTool};
+use serde_json::Value;
+use std::path::Path;
+use tracing::info;
+
+/// Python formatting, linting and type checking tool
+pub struct PythonTool;
+
+/// Supported Python commands
+#[derive(Debug, Clone, PartialEq)]
+pub enum PythonCommand {
+    /// `ruff check`, with `--fix` when transforming
+    Ruff,
+    /// `black --check` when validating, `black` when transforming
+    Black,
+    /// `mypy`; validation only
+    Mypy,
+}
+
+impl PythonCommand {
+    fn program(&self) -> &'static str {
+        match self {
+            PythonCommand::Ruff => "ruff",
+            PythonCommand::Black => "black",
+            PythonCommand::Mypy => "mypy",
+        }
+    }
+}
+
+/// Python command result
+#[derive(Debug, Clone)]
+pub struct PythonResult {
+    pub command: PythonCommand,
+    pub success: bool,
+    pub output: String,
+    pub changes: Vec<String>,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Python tool configuration
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PythonConfig {
+    /// Commands to run; when unset, transformations run ruff and black and
+    /// validation runs ruff and mypy
+    pub commands: Option<Vec<PythonCommand>>,
+    /// Maximum line length for ruff and black
+    pub line_length: Option<u32>,
+    /// Minimum Python version as `py311`
+    pub target_version: Option<String>,
+    /// Run mypy with `--strict`
+    pub strict: bool,
+}
+
+impl PythonConfig {
+    /// Commands to run, in order; when none are configured, `defaults`
+    fn commands_or(&self, defaults: &[PythonCommand]) -> Vec<PythonCommand> {
+        self.commands.clone().unwrap_or_else(|| defaults.to_vec())
+    }
+}
+
+#[async_trait]
+impl ValidationTool for PythonTool {
+    async fn validate(&self, intent: &ActionIntent) -> ActionResult<ToolResult> {
+        info!("Running Python validation for intent: {}", intent.id);
+
+        let start = std::time::Instant::now();
+        let config = self.parse_config(intent);
+        let paths = python_paths(&intent.scope);
+        if paths.is_empty() {
+            return Ok(ToolResult {
+                success: true,
+                changes: vec![],
+                output: "No Python files found to validate".to_string(),
+                errors: vec![],
+                warnings: vec![],
+                duration: start.elapsed(),
+                cached: false,
+                diagnostics: Vec::new(),
+                failed_tests: Vec::new(),
+            });
+        }
+
+        let commands = config.commands_or(&[PythonCommand::Ruff, PythonCommand::Mypy]);
+        let results = self.run_commands(&paths, &commands, &config, false).await?;
+        Ok(self.collect_results(results, &paths, "validation", start))
+    }
+
+    fn applies_to(&self, profile: &ToolchainProfile) -> bool {
+        profile.has_language("python")
+    }
+
+    fn name(&self) -> &str {
+        "python"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn is_available(&self) -> bool {
+        tool_available("ruff").await || tool_available("mypy").await
+    }
+}
+
+#[async_trait]
+impl TransformationTool for PythonTool {
+    async fn execute(&self, intent: &ActionIntent) -> ActionResult<ToolResult> {
+        info!("Executing Python transformations for intent: {}", intent.id);
+
+        if intent.dry_run {
+            return self.preview(intent).await;
+        }
+
+        let start = std::time::Instant::now();
+        let config = self.parse_config(intent);
+        let paths = python_paths(&intent.scope);
+        if paths.is_empty() {
+            return Err(ActionError::Validation(
+                "No Python files found for transformation".to_string(),
+            ));
+        }
+
+        // mypy has nothing to fix; it only runs during validation
+        let mut commands = config.commands_or(&[PythonCommand::Ruff, PythonCommand::Black]);
+        commands.retain(|command| *command != PythonCommand::Mypy);
+        let results = self.run_commands(&paths, &commands, &config, true).await?;
+        Ok(self.collect_results(results, &paths, "transformations", start))
+    }
+
+    fn supports_language(&self, language: &str) -> bool {
+        language == "python"
+    }
+
+    fn safety_level(&self) -> SafetyLevel {
+        SafetyLevel::Low
+    }
+
+    fn name(&self) -> &str {
+        "python"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn is_available(&self) -> bool {
+        tool_available("ruff").await || tool_available("black").await
+    }
+}
+
+impl PythonTool {
+    /// Parse configuration from intent metadata
+    fn parse_config(&self, intent: &ActionIntent) -> PythonConfig {
+        let mut config = PythonConfig::default();
+        let metadata = &intent.metadata;
+        if metadata.is_null() {
+            return config;
+        }
+
+        if let Some(commands) = metadata.get("commands").and_then(|c| c.as_array()) {
+            config.commands = Some(
+                commands
+                    .iter()
+                    .filter_map(|cmd| match cmd.as_str()? {
+                        "ruff" | "lint" => Some(PythonCommand::Ruff),
+                        "black" | "fmt" | "format" => Some(PythonCommand::Black),
+                        "mypy" | "typecheck" => Some(PythonCommand::Mypy),
+                        _ => None,
+                    })
+                    .collect(),
+            );
+        }
+
+        config.line_length = metadata
+            .get("line_length")
+            .and_then(Value::as_u64)
+            .and_then(|length| u32::try_from(length).ok());
+
+        config.target_version = metadata
+            .get("target_version")
+            .and_then(Value::as_str)
+            .and_then(normalize_target_version);
+
+        if let Some(strict) = metadata.get("strict") {
+            config.strict = strict.as_bool().unwrap_or(false);
+        }
+
+        config
+    }
+
+    /// Run each command over all paths; a command whose program is not
+    /// installed is skipped with a warning
+    async fn run_commands(
+        &self,
+        paths: &[String],
+        commands: &[PythonCommand],
+        config: &PythonConfig,
+        fix: bool,
+    ) -> ActionResult<Vec<PythonResult>> {
+        let mut results = Vec::new();
+        for command in commands {
+            let program = command.program();
+            if !tool_available(program).await {
+                results.push(PythonResult {
+                    command: command.clone(),
+                    success: true,
+                    output: format!("{} skipped", program),
+                    changes: vec![],
+                    errors: vec![],
+                    warnings: vec![format!("{} is not installed; skipped", program)],
+                    diagnostics: vec![],
+                });
+                continue;
+            }
+
+            let args = self.build_command_args(command, paths, config, fix);
+            let output = tool_command(program)
+                .args(&args)
+                .limited_output()
+                .await
+                .map_err(|e| ActionError::ToolExecution {
+                    tool: program.to_string(),
+                    message: format!("Failed to run {}: {}", program, e),
+                })?;
+            results.push(self.parse_output(command, &output, fix));
+        }
+        Ok(results)
+    }
+
+    /// Arguments for a command over `paths`
+    fn build_command_args(
+        &self,
+        command: &PythonCommand,
+        paths: &[String],
+        config: &PythonConfig,
+        fix: bool,
+    ) -> Vec<String> {
+        let mut args = Vec::new();
+        match command {
+            PythonCommand::Ruff => {
+                args.extend(["check", "--output-format", "json"].map(String::from));
+                if fix {
+                    args.push("--fix".to_string());
+                }
+                if let Some(length) = config.line_length {
+                    args.push(format!("--line-length={}", length));
+                }
+                if let Some(version) = &config.target_version {
+                    args.push(format!("--target-version={}", version));
+                }
+            }
+            PythonCommand::Black => {
+                if !fix {
+                    args.push("--check".to_string());
+                }
+                if let Some(length) = config.line_length {
+                    args.push(format!("--line-length={}", length));
+                }
+                if let Some(version) = &config.target_version {
+                    args.push(format!("--target-version={}", version));
+                }
+            }
+            PythonCommand::Mypy => {
+                args.extend(["--show-column-numbers", "--no-error-summary"].map(String::from));
+                if config.strict {
+                    args.push("--strict".to_string());
+                }
+                if let Some(version) = config.target_version.as_deref().and_then(dotted_version) {
+                    args.push(format!("--python-version={}", version));
+                }
+            }
+        }
+        args.extend(paths.iter().cloned());
+        args
+    }
+
+    /// Turn a command's output into changes, errors and diagnostics. Lint
+    /// issues left after `--fix` are warnings; in validation they are errors.
+    fn parse_output(
+        &self,
+        command: &PythonCommand,
+        output: &std::process::Output,
+        fix: bool,
+    ) -> PythonResult {
+        let program = command.program();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let mut result = PythonResult {
+            command: command.clone(),
+            success: true,
+            output: String::new(),
+            changes: vec![],
+            errors: vec![],
+            warnings: vec![],
+            diagnostics: vec![],
+        };
+
+        match command {
+            PythonCommand::Ruff => match parse_ruff_json(&stdout) {
+                Some(diagnostics) => {
+                    result.output = format!("ruff reported {} issues", diagnostics.len());
+                    result.diagnostics = diagnostics;
+                }
+                None if output.status.success() => {
+                    result.output = "ruff completed".to_string();
+                }
+                None => result
+                    .errors
+                    .push(format!("ruff failed: {}", stderr.trim())),
+            },
+            PythonCommand::Black => {
+                let (formatted, problems) = parse_black_output(&stderr);
+                if fix {
+                    result.output = format!("black formatted {} files", formatted.len());
+                    result.changes = formatted
+                        .iter()
+                        .map(|file| format!("Formatted {}", file))
+                        .collect();
+                } else {
+                    result.output = format!("{} files need formatting", formatted.len());
+                    result.diagnostics = formatted
+                        .iter()
+                        .map(|file| {
+                            Diagnostic::error("black", "would reformat").at(file, None, None)
+                        })
+                        .collect();
+                }
+                result.errors.extend(problems);
+                if !output.status.success() && fix && result.errors.is_empty() {
+                    result
+                        .errors
+                        .push(format!("black failed: {}", stderr.trim()));
+                }
+            }
+            PythonCommand::Mypy => {
+                result.diagnostics = parse_mypy_output(&stdout);
+                result.output = format!("mypy reported {} issues", result.diagnostics.len());
+                if !output.status.success()
+                    && !result
+                        .diagnostics
+                        .iter()
+                        .any(|diagnostic| diagnostic.severity == Severity::Error)
+                {
+                    result.errors.push(format!(
+                        "mypy failed: {}",
+                        if stderr.trim().is_empty() {
+                            stdout.trim()
+                        } else {
+                            stderr.trim()
+                        }
+                    ));
+                }
+            }
+        }
+
+        if fix {
+            for diagnostic in &mut result.diagnostics {
+                if diagnostic.severity == Severity::Error {
+                    diagnostic.severity = Severity::Warning;
+                }
+            }
+        }
+        for diagnostic in &result.diagnostics {
+            match diagnostic.severity {
+                Severity::Error => result.errors.push(diagnostic.to_string()),
+                Severity::Warning => result.warnings.push(diagnostic.to_string()),
+                Severity::Info => {}
+            }
+        }
+        result.success = result.errors.is_empty();
+        info!("{}: {}", program, result.output);
+        result
+    }
+
+    fn collect_results(
+        &self,
+        results: Vec<PythonResult>,
+        paths: &[String],
+        kind: &str,
+        start: std::time::Instant,
+    ) -> ToolResult {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        let mut changes = Vec::new();
+        let mut diagnostics = Vec::new();
+        for result in results {
+            errors.extend(result.errors);
+            warnings.extend(result.warnings);
+            diagnostics.extend(result.diagnostics);
+            changes.extend(result.changes);
+            if !result.output.is_empty() {
+                changes.push(result.output);
+            }
+        }
+
+        ToolResult {
+            success: errors.is_empty(),
+            changes,
+            output: format!("Python {} completed for {} paths", kind, paths.len()),
+            errors,
+            warnings,
+            duration: start.elapsed(),
+            cached: false,
+            diagnostics,
+            failed_tests: Vec::new(),
+        }
+    }
+}
+
+/// Python files and directories in scope; a `pyproject.toml` stands for its
+/// directory
+fn python_paths(scope: &[String]) -> Vec<String> {
+    let mut paths: Vec<String> = Vec::new();
+    for entry in scope {
+        let path = Path::new(entry);
+        let target = if entry.ends_with(".py") || entry.ends_with(".pyi") || path.is_dir() {
+            entry.clone()
+        } else if path
+            .file_name()
+            .is_some_and(|name| name == "pyproject.toml")
+        {
+            match path.parent() {
+                Some(dir) if !dir.as_os_str().is_empty() => dir.display().to_string(),
+                _ => ".".to_string(),
+            }
+        } else {
+            continue;
+        };
+        if !paths.contains(&target) {
+            paths.push(target);
+        }
+    }
+    paths
+}
+
+/// `py311` from `py311`, `3.11` or `python3.11`
+fn normalize_target_version(version: &str) -> Option<String> {
+    let version = version.trim().to_lowercase();
+    let digits = version
+        .strip_prefix("python")
+        .or_else(|| version.strip_prefix("py"))
+        .unwrap_or(&version)
+        .replace('.', "");
+    (digits.len() >= 2 && digits.chars().all(|c| c.is_ascii_digit()))
+        .then(|| format!("py{}", digits))
+}
+
+/// `3.11` from `py311`, as mypy expects it
+fn dotted_version(target: &str) -> Option<String> {
+    let digits = target.strip_prefix("py")?;
+    let (major, minor) = digits.split_at_checked(1)?;
+    (!minor.is_empty()).then(|| format!("{}.{}", major, minor))
+}
+
+/// Diagnostics of ruff's `json` output format
+fn parse_ruff_json(stdout: &str) -> Option<Vec<Diagnostic>> {
+    let violations: Vec<Value> = serde_json::from_str(stdout.trim()).ok()?;
+    Some(
+        violations
+            .iter()
+            .map(|violation| {
+                let text = violation
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .unwrap_or("");
+                let file = violation
+                    .get("filename")
+                    .and_then(Value::as_str)
+                    .unwrap_or("");
+                let number = |key: &str| {
+                    violation
+                        .get("location")
+                        .and_then(|location| location.get(key))
+                        .and_then(Value::as_u64)
+                        .and_then(|n| u32::try_from(n).ok())
+                };
+                let mut diagnostic =
+                    Diagnostic::error("ruff", text).at(file, number("row"), number("column"));
+                if let Some(code) = violation.get("code").and_then(Value::as_str) {
+                    diagnostic = diagnostic.with_code(code);
+                }
+                diagnostic
+            })
+            .collect(),
+    )
+}
+
+/// Files black reformatted or would reformat, and the files it failed on
+fn parse_black_output(stderr: &str) -> (Vec<String>, Vec<String>) {
+    let mut formatted = Vec::new();
+    let mut problems = Vec::new();
+    for line in stderr.lines().map(str::trim) {
+        if let Some(file) = line
+            .strip_prefix("would reformat ")
+            .or_else(|| line.strip_prefix("reformatted "))
+        {
+            formatted.push(file.to_string());
+        } else if line.starts_with("error: cannot format") {
+            problems.push(line.to_string());
+        }
+    }
+    (formatted, problems)
+}
+
+/// Diagnostics of mypy output lines such as
+/// `app/models.py:12:5: error: Incompatible return value  [return-value]`
+fn parse_mypy_output(stdout: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for line in stdout.lines() {
+        let Some((location, severity, message)) = [
+            (": error: ", Severity::Error),
+            (": warning: ", Severity::Warning),
+            (": note: ", Severity::Info),
+        ]
+        .into_iter()
+        .find_map(|(marker, severity)| {
+            line.split_once(marker)
+                .map(|(location, message)| (location, severity, message.trim_end()))
+        }) else {
+            continue;
+        };
+
+        let (file, line_number, column) = match location.rsplit_once(':') {
+            Some((head, last)) => match head.rsplit_once(':') {
+                Some((file, line)) if line.parse::<u32>().is_ok() => {
+                    (file, line.parse().ok(), last.parse().ok())
+                }
+                _ => (head, last.parse().ok(), None),
+            },
+            None => (location, None, None),
+        };
+
+        let (message, code) = match message
+            .strip_suffix(']')
+            .and_then(|rest| rest.rsplit_once("  ["))
+        {
+            Some((message, code)) => (message, Some(code)),
+            None => (message, None),
+        };
+        let mut diagnostic =
+            Diagnostic::new("mypy", severity, message).at(file, line_number, column);
+        if let Some(code) = code {
+            diagnostic = diagnostic.with_code(code);
+        }
+        diagnostics.push(diagnostic);
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests;
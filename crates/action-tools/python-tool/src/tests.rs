@@ -0,0 +1,214 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use rhema_action_tool::environment::probe_for;
+use rhema_action_tool::platform::resolve_program;
+use rhema_action_tool::{ActionIntent, ActionType, SafetyLevel};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_python_tool_creation() {
+    let tool = PythonTool;
+    assert_eq!(ValidationTool::name(&tool), "python");
+    assert_eq!(TransformationTool::name(&tool), "python");
+    assert_eq!(ValidationTool::version(&tool), "1.0.0");
+}
+
+#[test]
+fn test_parse_config_default() {
+    let tool = PythonTool;
+    let intent = ActionIntent::new(
+        "test",
+        ActionType::Cleanup,
+        "Format Python sources",
+        vec![],
+        SafetyLevel::Low,
+    );
+
+    let config = tool.parse_config(&intent);
+    assert_eq!(config, PythonConfig::default());
+    assert_eq!(
+        config.commands_or(&[PythonCommand::Ruff, PythonCommand::Black]),
+        vec![PythonCommand::Ruff, PythonCommand::Black]
+    );
+}
+
+#[test]
+fn test_parse_config_custom() {
+    let tool = PythonTool;
+    let mut intent = ActionIntent::new(
+        "test",
+        ActionType::Cleanup,
+        "Format Python sources",
+        vec![],
+        SafetyLevel::Low,
+    );
+    intent.metadata = json!({
+        "commands": ["ruff", "mypy"],
+        "line_length": 100,
+        "target_version": "3.11",
+        "strict": true
+    });
+
+    let config = tool.parse_config(&intent);
+    assert_eq!(
+        config.commands,
+        Some(vec![PythonCommand::Ruff, PythonCommand::Mypy])
+    );
+    assert_eq!(config.line_length, Some(100));
+    assert_eq!(config.target_version.as_deref(), Some("py311"));
+
+    let paths = vec!["app".to_string()];
+    assert_eq!(
+        tool.build_command_args(&PythonCommand::Ruff, &paths, &config, true),
+        vec![
+            "check",
+            "--output-format",
+            "json",
+            "--fix",
+            "--line-length=100",
+            "--target-version=py311",
+            "app"
+        ]
+    );
+    assert_eq!(
+        tool.build_command_args(&PythonCommand::Black, &paths, &config, false),
+        vec![
+            "--check",
+            "--line-length=100",
+            "--target-version=py311",
+            "app"
+        ]
+    );
+    assert_eq!(
+        tool.build_command_args(&PythonCommand::Mypy, &paths, &config, false),
+        vec![
+            "--show-column-numbers",
+            "--no-error-summary",
+            "--strict",
+            "--python-version=3.11",
+            "app"
+        ]
+    );
+}
+
+#[test]
+fn test_python_paths() {
+    let scope = vec![
+        "app/main.py".to_string(),
+        "stubs/api.pyi".to_string(),
+        "pyproject.toml".to_string(),
+        "service/pyproject.toml".to_string(),
+        "README.md".to_string(),
+        "app/main.py".to_string(),
+    ];
+    assert_eq!(
+        python_paths(&scope),
+        vec!["app/main.py", "stubs/api.pyi", ".", "service"]
+    );
+    assert_eq!(
+        normalize_target_version("python3.12").as_deref(),
+        Some("py312")
+    );
+    assert_eq!(normalize_target_version("latest"), None);
+}
+
+#[test]
+fn test_parse_tool_output() {
+    let ruff = r#"[{"code": "F401", "message": "`os` imported but unused",
+        "filename": "app/main.py", "location": {"row": 1, "column": 8}}]"#;
+    let diagnostics = parse_ruff_json(ruff).unwrap();
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "app/main.py:1:8: [F401] `os` imported but unused"
+    );
+    assert!(parse_ruff_json("error: invalid config").is_none());
+
+    let mypy = "app/models.py:12:5: error: Incompatible return value type (got \"int\", expected \"str\")  [return-value]\n\
+                app/models.py:3: note: See https://mypy.readthedocs.io\n";
+    let diagnostics = parse_mypy_output(mypy);
+    assert_eq!(diagnostics.len(), 2);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert_eq!(diagnostics[0].code.as_deref(), Some("return-value"));
+    assert_eq!(diagnostics[0].line, Some(12));
+    assert_eq!(diagnostics[0].column, Some(5));
+    assert_eq!(diagnostics[1].severity, Severity::Info);
+    assert_eq!(diagnostics[1].line, Some(3));
+    assert_eq!(diagnostics[1].column, None);
+
+    let black = "reformatted app/main.py\n\
+                 error: cannot format app/broken.py: Cannot parse: 1:4\n\
+                 All done! 1 file reformatted, 1 file failed to reformat.\n";
+    let (formatted, problems) = parse_black_output(black);
+    assert_eq!(formatted, vec!["app/main.py"]);
+    assert_eq!(problems.len(), 1);
+}
+
+#[tokio::test]
+async fn test_validation_with_no_python_files() {
+    let tool = PythonTool;
+    let intent = ActionIntent::new(
+        "test",
+        ActionType::Test,
+        "Test validation",
+        vec!["src/main.rs".to_string(), "README.md".to_string()],
+        SafetyLevel::Low,
+    );
+
+    let result = tool.validate(&intent).await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.output, "No Python files found to validate");
+}
+
+#[tokio::test]
+async fn test_validate_with_ruff() {
+    let tool = PythonTool;
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("main.py");
+    std::fs::write(&file, "import os\n").unwrap();
+    let mut intent = ActionIntent::new(
+        "test",
+        ActionType::Test,
+        "Lint Python sources",
+        vec![file.to_string_lossy().to_string()],
+        SafetyLevel::Low,
+    );
+    intent.metadata = json!({"commands": ["ruff"]});
+
+    // Availability follows the installed programs, which need probes
+    for program in ["ruff", "black", "mypy"] {
+        assert!(probe_for(program).is_some(), "no probe for {}", program);
+    }
+    assert_eq!(
+        ValidationTool::is_available(&tool).await,
+        resolve_program("ruff").is_some() || resolve_program("mypy").is_some()
+    );
+
+    let result = tool.validate(&intent).await.unwrap();
+    if tool_available("ruff").await {
+        assert!(!result.success);
+        assert!(result
+            .diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.code.as_deref() == Some("F401")));
+    } else {
+        assert!(result.success);
+        assert!(result
+            .warnings
+            .contains(&"ruff is not installed; skipped".to_string()));
+    }
+}
@@ -33,6 +33,9 @@ impl SafetyTool for SecurityScanningTool {
             errors: vec![],
             warnings: vec![],
             duration: std::time::Duration::from_secs(1),
+            cached: false,
+            diagnostics: Vec::new(),
+            failed_tests: Vec::new(),
         })
     }
 
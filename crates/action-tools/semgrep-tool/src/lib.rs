@@ -0,0 +1,677 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use async_trait::async_trait;
+use rhema_action_tool::platform::{relative_path, tool_command};
+use rhema_action_tool::{tool_available, LimitedCommand};
+use rhema_action_tool::{ActionError, ActionIntent, ActionResult};
+use rhema_action_tool::{Diagnostic, SafetyTool, Severity, ToolResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Section of `.rhema/repository.yaml` holding the semgrep policy
+pub const CONFIG_SECTION: &str = "semgrep";
+
+/// Severity of a semgrep finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FindingSeverity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl FindingSeverity {
+    /// Map a rule severity: `INFO`, `WARNING` and `ERROR` for classic rules,
+    /// `LOW` to `CRITICAL` for rules using the newer scale
+    pub fn from_semgrep(severity: &str) -> Self {
+        match severity.to_ascii_uppercase().as_str() {
+            "CRITICAL" => FindingSeverity::Critical,
+            "ERROR" | "HIGH" => FindingSeverity::High,
+            "WARNING" | "MEDIUM" => FindingSeverity::Medium,
+            _ => FindingSeverity::Low,
+        }
+    }
+
+    fn diagnostic_severity(&self) -> Severity {
+        match self {
+            FindingSeverity::Low => Severity::Info,
+            FindingSeverity::Medium => Severity::Warning,
+            FindingSeverity::High | FindingSeverity::Critical => Severity::Error,
+        }
+    }
+}
+
+impl fmt::Display for FindingSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FindingSeverity::Low => write!(f, "low"),
+            FindingSeverity::Medium => write!(f, "medium"),
+            FindingSeverity::High => write!(f, "high"),
+            FindingSeverity::Critical => write!(f, "critical"),
+        }
+    }
+}
+
+/// Semgrep scanning policy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SemgrepConfig {
+    /// Values passed to `--config`: registry rulesets such as `p/security-audit`
+    /// or rule files relative to the repository root
+    pub rulesets: Vec<String>,
+
+    /// Introduced findings at or above this severity fail the check
+    pub block_severity: FindingSeverity,
+
+    /// Only block findings of security rules, i.e. rules in the `security`
+    /// category or mapped to a CWE
+    pub security_only: bool,
+
+    /// Rule ids that are never reported
+    pub exclude_rules: Vec<String>,
+}
+
+impl Default for SemgrepConfig {
+    fn default() -> Self {
+        Self {
+            rulesets: vec!["p/security-audit".to_string()],
+            block_severity: FindingSeverity::High,
+            security_only: true,
+            exclude_rules: Vec::new(),
+        }
+    }
+}
+
+impl SemgrepConfig {
+    /// Load the policy from the repository config and policy bundle, falling
+    /// back to defaults
+    pub fn load(repo_root: &Path) -> ActionResult<Self> {
+        let value = rhema_core::policy::repository_config(repo_root)
+            .map_err(|e| ActionError::Configuration(e.to_string()))?;
+        match value.get(CONFIG_SECTION) {
+            Some(section) => serde_yaml::from_value(section.clone()).map_err(|e| {
+                ActionError::Configuration(format!(
+                    "Invalid {} section in the repository config: {}",
+                    CONFIG_SECTION, e
+                ))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Whether an introduced finding fails the check
+    pub fn blocks(&self, finding: &SemgrepFinding) -> bool {
+        finding.severity >= self.block_severity && (finding.security || !self.security_only)
+    }
+
+    /// `--config` values, with rule files resolved against the repository root
+    /// so the baseline scan of a temporary directory finds them too
+    fn config_args(&self, repo_root: &Path) -> Vec<String> {
+        self.rulesets
+            .iter()
+            .map(|ruleset| {
+                let local = repo_root.join(ruleset);
+                if local.exists() {
+                    local.to_string_lossy().to_string()
+                } else {
+                    ruleset.clone()
+                }
+            })
+            .collect()
+    }
+}
+
+/// A finding reported by semgrep
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SemgrepFinding {
+    pub check_id: String,
+    /// Path relative to the scanned directory
+    pub path: String,
+    pub line: u32,
+    pub column: u32,
+    pub end_line: u32,
+    pub severity: FindingSeverity,
+    pub message: String,
+    /// The rule is in the `security` category or mapped to a CWE
+    pub security: bool,
+    pub cwe: Vec<String>,
+    /// Source lines the finding covers, with whitespace collapsed
+    pub source: String,
+}
+
+impl SemgrepFinding {
+    /// Rule, file and matched source; stable when unrelated lines move
+    fn fingerprint(&self) -> (&str, &str, &str) {
+        (&self.check_id, &self.path, &self.source)
+    }
+
+    fn diagnostic(&self) -> Diagnostic {
+        Diagnostic::new(
+            "semgrep",
+            self.severity.diagnostic_severity(),
+            &self.message,
+        )
+        .at(&self.path, Some(self.line), Some(self.column))
+        .with_code(&self.check_id)
+    }
+
+    /// Fill in `source` from the scanned file
+    fn read_source(&mut self, scanned_root: &Path) {
+        let Ok(content) = std::fs::read_to_string(scanned_root.join(&self.path)) else {
+            return;
+        };
+        self.source = matched_source(&content, self.line, self.end_line);
+    }
+}
+
+/// `path:line: severity [check_id] message`
+impl fmt::Display for SemgrepFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {} [{}] {}",
+            self.path, self.line, self.severity, self.check_id, self.message
+        )?;
+        if !self.cwe.is_empty() {
+            write!(f, " ({})", self.cwe.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Lines `start..=end` (1-based) with runs of whitespace collapsed
+fn matched_source(content: &str, start: u32, end: u32) -> String {
+    let start = start.max(1) as usize;
+    let end = (end as usize).max(start);
+    content
+        .lines()
+        .skip(start - 1)
+        .take(end - start + 1)
+        .flat_map(str::split_whitespace)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Findings and error messages of a `semgrep --json` run
+pub fn parse_semgrep_json(output: &str) -> ActionResult<(Vec<SemgrepFinding>, Vec<String>)> {
+    let report: serde_json::Value = serde_json::from_str(output)?;
+
+    let findings = report
+        .get("results")
+        .and_then(|r| r.as_array())
+        .map(|results| results.iter().filter_map(parse_result).collect())
+        .unwrap_or_default();
+
+    let errors = report
+        .get("errors")
+        .and_then(|e| e.as_array())
+        .map(|errors| {
+            errors
+                .iter()
+                .map(|error| {
+                    error
+                        .get("message")
+                        .and_then(|m| m.as_str())
+                        .map(|m| m.trim().to_string())
+                        .unwrap_or_else(|| error.to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok((findings, errors))
+}
+
+fn parse_result(result: &serde_json::Value) -> Option<SemgrepFinding> {
+    let position = |key: &str, field: &str| {
+        result
+            .get(key)
+            .and_then(|p| p.get(field))
+            .and_then(|v| v.as_u64())
+            .map(|v| v as u32)
+    };
+    let extra = result.get("extra");
+    let metadata = extra.and_then(|e| e.get("metadata"));
+
+    let cwe: Vec<String> = match metadata.and_then(|m| m.get("cwe")) {
+        Some(serde_json::Value::String(cwe)) => vec![cwe.clone()],
+        Some(serde_json::Value::Array(cwes)) => cwes
+            .iter()
+            .filter_map(|c| c.as_str().map(str::to_string))
+            .collect(),
+        _ => Vec::new(),
+    };
+    let category = metadata
+        .and_then(|m| m.get("category"))
+        .and_then(|c| c.as_str())
+        .unwrap_or_default();
+    let line = position("start", "line")?;
+
+    Some(SemgrepFinding {
+        check_id: result.get("check_id")?.as_str()?.to_string(),
+        path: result.get("path")?.as_str()?.to_string(),
+        line,
+        column: position("start", "col").unwrap_or(1),
+        end_line: position("end", "line").unwrap_or(line),
+        severity: FindingSeverity::from_semgrep(
+            extra
+                .and_then(|e| e.get("severity"))
+                .and_then(|s| s.as_str())
+                .unwrap_or_default(),
+        ),
+        message: extra
+            .and_then(|e| e.get("message"))
+            .and_then(|m| m.as_str())
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+        security: category.eq_ignore_ascii_case("security") || !cwe.is_empty(),
+        cwe,
+        source: String::new(),
+    })
+}
+
+/// Split `current` findings into those introduced since `baseline` and those
+/// that were already there. Findings are matched by rule, file and matched
+/// source, so code that only moved is not reported as new.
+pub fn split_introduced<'a>(
+    current: &'a [SemgrepFinding],
+    baseline: &[SemgrepFinding],
+) -> (Vec<&'a SemgrepFinding>, Vec<&'a SemgrepFinding>) {
+    let mut remaining: HashMap<(&str, &str, &str), usize> = HashMap::new();
+    for finding in baseline {
+        *remaining.entry(finding.fingerprint()).or_default() += 1;
+    }
+
+    current
+        .iter()
+        .partition(|finding| match remaining.get_mut(&finding.fingerprint()) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                false
+            }
+            _ => true,
+        })
+}
+
+/// Runs semgrep over the files in the intent scope and blocks security
+/// findings the change introduces.
+///
+/// The same files are scanned as they are in `HEAD`; findings that were
+/// already there are reported but never block. Introduced findings fail the
+/// check when they reach the configured `block_severity`.
+#[derive(Default)]
+pub struct SemgrepTool {
+    config: Option<SemgrepConfig>,
+}
+
+impl SemgrepTool {
+    pub fn with_config(config: SemgrepConfig) -> Self {
+        Self {
+            config: Some(config),
+        }
+    }
+}
+
+#[async_trait]
+impl SafetyTool for SemgrepTool {
+    async fn check(&self, intent: &ActionIntent) -> ActionResult<ToolResult> {
+        info!("Running semgrep scan for intent: {}", intent.id);
+        let start = std::time::Instant::now();
+
+        let cwd = std::env::current_dir()?;
+        let files: Vec<PathBuf> = intent
+            .scope
+            .iter()
+            .map(|file| cwd.join(file))
+            .filter(|path| path.is_file())
+            .collect();
+        let mut result = ToolResult {
+            success: true,
+            changes: Vec::new(),
+            output: String::new(),
+            errors: Vec::new(),
+            warnings: Vec::new(),
+            duration: start.elapsed(),
+            cached: false,
+            diagnostics: Vec::new(),
+            failed_tests: Vec::new(),
+        };
+        let Some(first) = files.first() else {
+            result.output = "No files to scan with semgrep".to_string();
+            return Ok(result);
+        };
+        if !tool_available("semgrep").await {
+            result.output = "semgrep is not installed; security scan skipped".to_string();
+            result.warnings.push(result.output.clone());
+            return Ok(result);
+        }
+
+        let repo_root = find_repo_root(first).unwrap_or_else(|| cwd.clone());
+        let config = match &self.config {
+            Some(config) => config.clone(),
+            None => SemgrepConfig::load(&repo_root)?,
+        };
+        let relative: Vec<String> = files
+            .iter()
+            .map(|path| relative_path(path, &repo_root))
+            .collect();
+
+        let (current, scan_errors) =
+            run_semgrep(&config, &repo_root, &repo_root, &relative).await?;
+        result.warnings.extend(scan_errors);
+
+        let baseline = match Git::open(&repo_root).await {
+            Some(git) => {
+                let dir = tempfile::tempdir()?;
+                let mut previous = Vec::new();
+                for file in &relative {
+                    if let Some(content) = git.show_head(file).await {
+                        let path = dir.path().join(file);
+                        if let Some(parent) = path.parent() {
+                            std::fs::create_dir_all(parent)?;
+                        }
+                        std::fs::write(path, content)?;
+                        previous.push(file.clone());
+                    }
+                }
+                if previous.is_empty() {
+                    Vec::new()
+                } else {
+                    run_semgrep(&config, &repo_root, dir.path(), &previous)
+                        .await?
+                        .0
+                }
+            }
+            None => {
+                result.warnings.push(
+                    "No git history to compare against; every semgrep finding counts as introduced"
+                        .to_string(),
+                );
+                Vec::new()
+            }
+        };
+
+        let (introduced, existing) = split_introduced(&current, &baseline);
+        for finding in &introduced {
+            if config.blocks(finding) {
+                result
+                    .errors
+                    .push(format!("Introduced finding: {}", finding));
+            } else if finding.severity >= FindingSeverity::Medium {
+                result
+                    .warnings
+                    .push(format!("Introduced finding: {}", finding));
+            }
+            result.changes.push(finding.to_string());
+        }
+        for finding in &existing {
+            if config.blocks(finding) {
+                result
+                    .warnings
+                    .push(format!("Pre-existing finding: {}", finding));
+            }
+        }
+        result.diagnostics = current.iter().map(SemgrepFinding::diagnostic).collect();
+
+        result.success = result.errors.is_empty();
+        result.output = format!(
+            "semgrep reported {} findings in {} files ({} introduced, {} blocking)",
+            current.len(),
+            relative.len(),
+            introduced.len(),
+            result.errors.len()
+        );
+        result.duration = start.elapsed();
+        Ok(result)
+    }
+
+    fn name(&self) -> &str {
+        "semgrep"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn is_available(&self) -> bool {
+        tool_available("semgrep").await
+    }
+}
+
+/// Scan `files`, relative to `scan_root`, with the configured rulesets
+async fn run_semgrep(
+    config: &SemgrepConfig,
+    repo_root: &Path,
+    scan_root: &Path,
+    files: &[String],
+) -> ActionResult<(Vec<SemgrepFinding>, Vec<String>)> {
+    let mut command = tool_command("semgrep");
+    command.current_dir(scan_root).args([
+        "scan",
+        "--json",
+        "--quiet",
+        "--metrics=off",
+        "--disable-version-check",
+    ]);
+    for ruleset in config.config_args(repo_root) {
+        command.arg("--config").arg(ruleset);
+    }
+    for rule in &config.exclude_rules {
+        command.arg("--exclude-rule").arg(rule);
+    }
+    command.args(files);
+
+    let output = command
+        .limited_output()
+        .await
+        .map_err(|e| ActionError::ToolExecution {
+            tool: "semgrep".to_string(),
+            message: format!("Failed to run semgrep: {}", e),
+        })?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if stdout.trim().is_empty() {
+        return Err(ActionError::ToolExecution {
+            tool: "semgrep".to_string(),
+            message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+
+    let (mut findings, errors) = parse_semgrep_json(&stdout)?;
+    for finding in &mut findings {
+        finding.read_source(scan_root);
+    }
+    Ok((findings, errors))
+}
+
+fn find_repo_root(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| dir.join(".git").exists() || dir.join(".rhema").is_dir())
+        .map(Path::to_path_buf)
+}
+
+/// Git repository rooted exactly at the repo root
+struct Git {
+    root: PathBuf,
+}
+
+impl Git {
+    async fn open(root: &Path) -> Option<Self> {
+        let git = Self {
+            root: root.to_path_buf(),
+        };
+        let toplevel = git.run(&["rev-parse", "--show-toplevel"]).await?;
+        let toplevel = PathBuf::from(toplevel.trim()).canonicalize().ok()?;
+        if toplevel != root.canonicalize().ok()? {
+            return None;
+        }
+        Some(git)
+    }
+
+    async fn run(&self, args: &[&str]) -> Option<String> {
+        let output = tool_command("git")
+            .arg("-C")
+            .arg(&self.root)
+            .args(args)
+            .limited_output()
+            .await
+            .map_err(|e| warn!("Failed to run git: {}", e))
+            .ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    /// Content of a file in `HEAD`; `None` when the file is new
+    async fn show_head(&self, relative: &str) -> Option<String> {
+        self.run(&["show", &format!("HEAD:{}", relative)]).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPORT: &str = r#"{
+      "results": [
+        {
+          "check_id": "python.lang.security.audit.eval-detected",
+          "path": "app/views.py",
+          "start": {"line": 12, "col": 5, "offset": 210},
+          "end": {"line": 12, "col": 27, "offset": 232},
+          "extra": {
+            "message": "Detected the use of eval().",
+            "severity": "WARNING",
+            "metadata": {"category": "security", "cwe": ["CWE-95: Eval Injection"]},
+            "lines": "requires login"
+          }
+        },
+        {
+          "check_id": "python.lang.correctness.useless-comparison",
+          "path": "app/views.py",
+          "start": {"line": 20, "col": 1},
+          "end": {"line": 21, "col": 3},
+          "extra": {"message": "Useless comparison", "severity": "ERROR", "metadata": {"category": "correctness"}}
+        },
+        {
+          "check_id": "python.django.security.injection.sql.raw-query",
+          "path": "app/models.py",
+          "start": {"line": 3, "col": 9},
+          "end": {"line": 3, "col": 40},
+          "extra": {"message": "Raw SQL", "severity": "ERROR", "metadata": {"cwe": "CWE-89"}}
+        }
+      ],
+      "errors": [{"type": "SyntaxError", "message": "Syntax error at line app/broken.py:1 "}]
+    }"#;
+
+    fn finding(check_id: &str, source: &str) -> SemgrepFinding {
+        SemgrepFinding {
+            check_id: check_id.to_string(),
+            path: "app.py".to_string(),
+            line: 1,
+            column: 1,
+            end_line: 1,
+            severity: FindingSeverity::High,
+            message: String::new(),
+            security: true,
+            cwe: Vec::new(),
+            source: source.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_report_parsing_and_severity_mapping() {
+        let (findings, errors) = parse_semgrep_json(REPORT).unwrap();
+        assert_eq!(findings.len(), 3);
+        assert_eq!(errors, vec!["Syntax error at line app/broken.py:1"]);
+
+        let eval = &findings[0];
+        assert_eq!(eval.severity, FindingSeverity::Medium);
+        assert!(eval.security);
+        assert_eq!((eval.line, eval.column), (12, 5));
+
+        let comparison = &findings[1];
+        assert_eq!(comparison.severity, FindingSeverity::High);
+        assert!(!comparison.security);
+        assert_eq!(comparison.end_line, 21);
+
+        let sql = &findings[2];
+        assert!(sql.security);
+        assert_eq!(sql.cwe, vec!["CWE-89"]);
+
+        let config = SemgrepConfig::default();
+        assert!(!config.blocks(eval));
+        assert!(!config.blocks(comparison));
+        assert!(config.blocks(sql));
+
+        assert_eq!(
+            FindingSeverity::from_semgrep("critical"),
+            FindingSeverity::Critical
+        );
+        assert_eq!(FindingSeverity::from_semgrep("INFO"), FindingSeverity::Low);
+    }
+
+    #[test]
+    fn test_only_new_findings_are_introduced() {
+        let source = "import os\n\nresult = eval(  user_input )\n";
+        assert_eq!(matched_source(source, 3, 3), "result = eval( user_input )");
+
+        let baseline = vec![finding("eval", "eval(x)"), finding("eval", "eval(y)")];
+        let current = vec![
+            finding("eval", "eval(y)"),
+            finding("eval", "eval(x)"),
+            finding("eval", "eval(x)"),
+            finding("exec", "exec(x)"),
+        ];
+        let (introduced, existing) = split_introduced(&current, &baseline);
+        assert_eq!(existing.len(), 2);
+        let introduced: Vec<_> = introduced
+            .iter()
+            .map(|f| (f.check_id.as_str(), f.source.as_str()))
+            .collect();
+        assert_eq!(introduced, vec![("eval", "eval(x)"), ("exec", "exec(x)")]);
+    }
+
+    #[test]
+    fn test_config_section_is_loaded() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join(".rhema")).unwrap();
+        std::fs::write(
+            dir.path().join(".rhema").join("repository.yaml"),
+            "semgrep:\n  rulesets: [\"p/owasp-top-ten\", \"rules/internal.yml\"]\n  block_severity: medium\n",
+        )
+        .unwrap();
+        std::fs::create_dir(dir.path().join("rules")).unwrap();
+        std::fs::write(dir.path().join("rules").join("internal.yml"), "rules: []\n").unwrap();
+
+        let config = SemgrepConfig::load(dir.path()).unwrap();
+        assert_eq!(config.block_severity, FindingSeverity::Medium);
+        assert!(config.security_only);
+        let args = config.config_args(dir.path());
+        assert_eq!(args[0], "p/owasp-top-ten");
+        assert_eq!(
+            PathBuf::from(&args[1]),
+            dir.path().join("rules").join("internal.yml")
+        );
+    }
+}
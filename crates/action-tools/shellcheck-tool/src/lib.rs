@@ -0,0 +1,230 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use async_trait::async_trait;
+use rhema_action_tool::platform::tool_command;
+use rhema_action_tool::{tool_available, LimitedCommand};
+use rhema_action_tool::{ActionError, ActionIntent, ActionResult};
+use rhema_action_tool::{Diagnostic, Severity, ToolResult, ToolchainProfile, ValidationTool};
+use serde_json::Value;
+use tracing::info;
+
+/// ShellCheck validation tool for shell scripts
+pub struct ShellCheckTool;
+
+/// ShellCheck tool configuration
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ShellCheckConfig {
+    /// Lowest level reported: `error`, `warning`, `info` or `style`
+    pub severity: Option<String>,
+    /// Dialect to assume for scripts without a shebang, e.g. `bash`
+    pub shell: Option<String>,
+    /// Codes to ignore, as `SC2086` or `2086`
+    pub exclude: Vec<String>,
+    /// Follow `source` statements into other files
+    pub external_sources: bool,
+}
+
+#[async_trait]
+impl ValidationTool for ShellCheckTool {
+    async fn validate(&self, intent: &ActionIntent) -> ActionResult<ToolResult> {
+        info!("Running ShellCheck validation for intent: {}", intent.id);
+
+        let start = std::time::Instant::now();
+        let files = shell_files(&intent.scope);
+        if files.is_empty() {
+            return Ok(ToolResult {
+                success: true,
+                changes: vec![],
+                output: "No shell scripts found to validate".to_string(),
+                errors: vec![],
+                warnings: vec![],
+                duration: start.elapsed(),
+                cached: false,
+                diagnostics: Vec::new(),
+                failed_tests: Vec::new(),
+            });
+        }
+
+        let config = self.parse_config(intent);
+        let output = tool_command("shellcheck")
+            .args(self.build_command_args(&files, &config))
+            .limited_output()
+            .await
+            .map_err(|e| ActionError::ToolExecution {
+                tool: "shellcheck".to_string(),
+                message: format!("Failed to run shellcheck: {}", e),
+            })?;
+
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        let diagnostics =
+            parse_shellcheck_json(&String::from_utf8_lossy(&output.stdout)).unwrap_or_default();
+        // Exit code 1 means findings; anything else is a missing file or bad option
+        if !matches!(output.status.code(), Some(0 | 1)) {
+            errors.push(format!(
+                "shellcheck failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        for diagnostic in &diagnostics {
+            match diagnostic.severity {
+                Severity::Error => errors.push(diagnostic.to_string()),
+                Severity::Warning => warnings.push(diagnostic.to_string()),
+                Severity::Info => {}
+            }
+        }
+
+        Ok(ToolResult {
+            success: errors.is_empty(),
+            changes: vec![],
+            output: format!(
+                "ShellCheck reported {} issues in {} scripts",
+                diagnostics.len(),
+                files.len()
+            ),
+            errors,
+            warnings,
+            duration: start.elapsed(),
+            cached: false,
+            diagnostics,
+            failed_tests: Vec::new(),
+        })
+    }
+
+    fn applies_to(&self, profile: &ToolchainProfile) -> bool {
+        profile.has_language("shell")
+    }
+
+    fn name(&self) -> &str {
+        "shellcheck"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn is_available(&self) -> bool {
+        tool_available("shellcheck").await
+    }
+}
+
+impl ShellCheckTool {
+    /// Parse configuration from intent metadata
+    fn parse_config(&self, intent: &ActionIntent) -> ShellCheckConfig {
+        let mut config = ShellCheckConfig::default();
+        let metadata = &intent.metadata;
+        if metadata.is_null() {
+            return config;
+        }
+
+        config.severity = metadata
+            .get("severity")
+            .and_then(Value::as_str)
+            .filter(|level| ["error", "warning", "info", "style"].contains(level))
+            .map(str::to_string);
+
+        config.shell = metadata
+            .get("shell")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+
+        if let Some(exclude) = metadata.get("exclude").and_then(Value::as_array) {
+            config.exclude = exclude
+                .iter()
+                .filter_map(|code| match code {
+                    Value::Number(number) => Some(format!("SC{}", number)),
+                    Value::String(code) if code.starts_with("SC") => Some(code.clone()),
+                    Value::String(code) => Some(format!("SC{}", code)),
+                    _ => None,
+                })
+                .collect();
+        }
+
+        if let Some(external) = metadata.get("external_sources") {
+            config.external_sources = external.as_bool().unwrap_or(false);
+        }
+
+        config
+    }
+
+    fn build_command_args(&self, files: &[String], config: &ShellCheckConfig) -> Vec<String> {
+        let mut args = vec!["--format=json".to_string()];
+        if let Some(severity) = &config.severity {
+            args.push(format!("--severity={}", severity));
+        }
+        if let Some(shell) = &config.shell {
+            args.push(format!("--shell={}", shell));
+        }
+        if !config.exclude.is_empty() {
+            args.push(format!("--exclude={}", config.exclude.join(",")));
+        }
+        if config.external_sources {
+            args.push("--external-sources".to_string());
+        }
+        args.extend(files.iter().cloned());
+        args
+    }
+}
+
+/// Shell scripts in scope
+fn shell_files(scope: &[String]) -> Vec<String> {
+    let mut files: Vec<String> = Vec::new();
+    for entry in scope {
+        if (entry.ends_with(".sh") || entry.ends_with(".bash")) && !files.contains(entry) {
+            files.push(entry.clone());
+        }
+    }
+    files
+}
+
+/// Diagnostics of ShellCheck's `json` output format. `error` findings are
+/// errors, `warning` findings warnings, and `info` and `style` findings notes
+fn parse_shellcheck_json(stdout: &str) -> Option<Vec<Diagnostic>> {
+    let findings: Vec<Value> = serde_json::from_str(stdout.trim()).ok()?;
+    Some(
+        findings
+            .iter()
+            .map(|finding| {
+                let severity = match finding.get("level").and_then(Value::as_str) {
+                    Some("error") => Severity::Error,
+                    Some("warning") => Severity::Warning,
+                    _ => Severity::Info,
+                };
+                let number = |key: &str| {
+                    finding
+                        .get(key)
+                        .and_then(Value::as_u64)
+                        .and_then(|n| u32::try_from(n).ok())
+                };
+                let file = finding.get("file").and_then(Value::as_str).unwrap_or("");
+                let message = finding.get("message").and_then(Value::as_str).unwrap_or("");
+                let mut diagnostic = Diagnostic::new("shellcheck", severity, message).at(
+                    file,
+                    number("line"),
+                    number("column"),
+                );
+                if let Some(code) = number("code") {
+                    diagnostic = diagnostic.with_code(format!("SC{}", code));
+                }
+                diagnostic
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,153 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use rhema_action_tool::environment::probe_for;
+use rhema_action_tool::platform::resolve_program;
+use rhema_action_tool::{ActionIntent, ActionType, SafetyLevel};
+use serde_json::json;
+
+#[tokio::test]
+async fn test_shellcheck_tool_creation() {
+    let tool = ShellCheckTool;
+    assert_eq!(ValidationTool::name(&tool), "shellcheck");
+    assert_eq!(ValidationTool::version(&tool), "1.0.0");
+}
+
+#[test]
+fn test_parse_config_default() {
+    let tool = ShellCheckTool;
+    let intent = ActionIntent::new(
+        "test",
+        ActionType::Cleanup,
+        "Tidy deploy scripts",
+        vec![],
+        SafetyLevel::Low,
+    );
+
+    assert_eq!(tool.parse_config(&intent), ShellCheckConfig::default());
+}
+
+#[test]
+fn test_parse_config_custom() {
+    let tool = ShellCheckTool;
+    let mut intent = ActionIntent::new(
+        "test",
+        ActionType::Cleanup,
+        "Tidy deploy scripts",
+        vec![],
+        SafetyLevel::Low,
+    );
+    intent.metadata = json!({
+        "severity": "warning",
+        "shell": "bash",
+        "exclude": [2086, "SC2034", "1091"],
+        "external_sources": true
+    });
+
+    let config = tool.parse_config(&intent);
+    assert_eq!(config.exclude, vec!["SC2086", "SC2034", "SC1091"]);
+
+    let files = shell_files(&[
+        "scripts/deploy.sh".to_string(),
+        "scripts/env.bash".to_string(),
+        "README.md".to_string(),
+        "scripts/deploy.sh".to_string(),
+    ]);
+    assert_eq!(files, vec!["scripts/deploy.sh", "scripts/env.bash"]);
+    assert_eq!(
+        tool.build_command_args(&files, &config),
+        vec![
+            "--format=json",
+            "--severity=warning",
+            "--shell=bash",
+            "--exclude=SC2086,SC2034,SC1091",
+            "--external-sources",
+            "scripts/deploy.sh",
+            "scripts/env.bash"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_validation_with_no_scripts() {
+    let tool = ShellCheckTool;
+    let intent = ActionIntent::new(
+        "test",
+        ActionType::Cleanup,
+        "Tidy deploy scripts",
+        vec!["README.md".to_string()],
+        SafetyLevel::Low,
+    );
+
+    let result = tool.validate(&intent).await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.output, "No shell scripts found to validate");
+}
+
+#[tokio::test]
+async fn test_validate_with_shellcheck() {
+    let tool = ShellCheckTool;
+    assert!(probe_for("shellcheck").is_some());
+    assert_eq!(
+        ValidationTool::is_available(&tool).await,
+        resolve_program("shellcheck").is_some()
+    );
+    if !ValidationTool::is_available(&tool).await {
+        return;
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let script = dir.path().join("deploy.sh");
+    std::fs::write(&script, "#!/bin/sh\nunused=1\n").unwrap();
+    let intent = ActionIntent::new(
+        "test",
+        ActionType::Cleanup,
+        "Tidy deploy scripts",
+        vec![script.to_string_lossy().to_string()],
+        SafetyLevel::Low,
+    );
+
+    let result = tool.validate(&intent).await.unwrap();
+    assert!(result.success);
+    assert!(result.warnings.iter().any(|w| w.contains("[SC2034]")));
+}
+
+#[test]
+fn test_parse_shellcheck_json() {
+    let stdout = r#"[
+        {"file": "deploy.sh", "line": 3, "endLine": 3, "column": 6, "endColumn": 10,
+         "level": "warning", "code": 2086, "message": "Double quote to prevent globbing and word splitting.", "fix": null},
+        {"file": "deploy.sh", "line": 7, "endLine": 7, "column": 1, "endColumn": 2,
+         "level": "error", "code": 1073, "message": "Couldn't parse this if expression.", "fix": null},
+        {"file": "deploy.sh", "line": 9, "endLine": 9, "column": 1, "endColumn": 5,
+         "level": "style", "code": 2006, "message": "Use $(...) notation instead of legacy backticks.", "fix": null}
+    ]"#;
+    let diagnostics = parse_shellcheck_json(stdout).unwrap();
+
+    assert_eq!(diagnostics.len(), 3);
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "deploy.sh:3:6: [SC2086] Double quote to prevent globbing and word splitting."
+    );
+    assert_eq!(diagnostics[1].severity, Severity::Error);
+    assert_eq!(diagnostics[2].severity, Severity::Info);
+    assert_eq!(parse_shellcheck_json("[]"), Some(vec![]));
+    assert_eq!(
+        parse_shellcheck_json("shellcheck: deploy.sh: No such file"),
+        None
+    );
+}
@@ -15,6 +15,8 @@
  */
 
 use async_trait::async_trait;
+use rhema_action_tool::platform::{python_command, python_interpreter, tool_command};
+use rhema_action_tool::{tool_available, LimitedCommand};
 use rhema_action_tool::{ActionError, ActionIntent, ActionResult, SafetyLevel};
 use rhema_action_tool::{SafetyTool, ToolResult};
 use tracing::info;
@@ -58,6 +60,9 @@ impl SafetyTool for SyntaxValidationTool {
             errors,
             warnings,
             duration: start.elapsed(),
+            cached: false,
+            diagnostics: Vec::new(),
+            failed_tests: Vec::new(),
         })
     }
 
@@ -71,26 +76,11 @@ impl SafetyTool for SyntaxValidationTool {
 
     async fn is_available(&self) -> bool {
         // Check if basic syntax validation tools are available
-        let node_available = tokio::process::Command::new("node")
-            .arg("--version")
-            .output()
-            .await
-            .map(|output| output.status.success())
-            .unwrap_or(false);
+        let node_available = tool_available("node").await;
 
-        let python_available = tokio::process::Command::new("python3")
-            .arg("--version")
-            .output()
-            .await
-            .map(|output| output.status.success())
-            .unwrap_or(false);
+        let python_available = python_interpreter().await.is_some();
 
-        let rust_available = tokio::process::Command::new("rustc")
-            .arg("--version")
-            .output()
-            .await
-            .map(|output| output.status.success())
-            .unwrap_or(false);
+        let rust_available = tool_available("rustc").await;
 
         node_available || python_available || rust_available
     }
@@ -124,9 +114,9 @@ impl SyntaxValidationTool {
 
     /// Validate JavaScript/TypeScript syntax
     async fn validate_javascript_syntax(&self, file_path: &str) -> ActionResult<String> {
-        let output = tokio::process::Command::new("node")
+        let output = tool_command("node")
             .args(&["--check", file_path])
-            .output()
+            .limited_output()
             .await
             .map_err(|e| ActionError::ToolExecution {
                 tool: "syntax_validation".to_string(),
@@ -146,9 +136,10 @@ impl SyntaxValidationTool {
 
     /// Validate Python syntax
     async fn validate_python_syntax(&self, file_path: &str) -> ActionResult<String> {
-        let output = tokio::process::Command::new("python3")
+        let output = python_command()
+            .await
             .args(&["-m", "py_compile", file_path])
-            .output()
+            .limited_output()
             .await
             .map_err(|e| ActionError::ToolExecution {
                 tool: "syntax_validation".to_string(),
@@ -168,9 +159,9 @@ impl SyntaxValidationTool {
 
     /// Validate Rust syntax
     async fn validate_rust_syntax(&self, file_path: &str) -> ActionResult<String> {
-        let output = tokio::process::Command::new("rustc")
+        let output = tool_command("rustc")
             .args(&["--emit=metadata", "--crate-type=lib", file_path])
-            .output()
+            .limited_output()
             .await
             .map_err(|e| ActionError::ToolExecution {
                 tool: "syntax_validation".to_string(),
@@ -0,0 +1,745 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use async_trait::async_trait;
+use rhema_action_tool::platform::tool_command;
+use rhema_action_tool::{tool_available, LimitedCommand};
+use rhema_action_tool::{ActionError, ActionIntent, ActionResult, SafetyLevel};
+use rhema_action_tool::{Diagnostic, Severity, ToolResult, ToolchainProfile};
+use rhema_action_tool::{TransformationTool, ValidationTool};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use tracing::{error, info};
+
+/// Terraform validation and formatting tool
+pub struct TerraformTool;
+
+/// Supported Terraform commands
+#[derive(Debug, Clone, PartialEq)]
+pub enum TerraformCommand {
+    /// `terraform init -backend=false` then `terraform validate`; validation only
+    Validate,
+    /// `terraform fmt -check` when validating, `terraform fmt -write` when transforming
+    Fmt,
+    /// `tflint`; validation only
+    Tflint,
+}
+
+impl TerraformCommand {
+    fn program(&self) -> &'static str {
+        match self {
+            TerraformCommand::Validate | TerraformCommand::Fmt => "terraform",
+            TerraformCommand::Tflint => "tflint",
+        }
+    }
+}
+
+/// Terraform command result
+#[derive(Debug, Clone)]
+pub struct TerraformResult {
+    pub command: TerraformCommand,
+    pub success: bool,
+    pub output: String,
+    pub changes: Vec<String>,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// A directory of `.tf` files
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerraformModule {
+    pub dir: PathBuf,
+    /// Not called as a local module by another module in scope
+    pub root: bool,
+}
+
+/// Terraform tool configuration
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerraformConfig {
+    pub commands: Vec<TerraformCommand>,
+    /// Run tflint even when it is not among the commands
+    pub tflint: bool,
+    /// Validate child modules on their own too, not just through their roots
+    pub validate_child_modules: bool,
+    /// Terraform workspace selected with `TF_WORKSPACE`
+    pub workspace: Option<String>,
+    pub module_filter: Option<Vec<String>>,
+    pub exclude_modules: Option<Vec<String>>,
+}
+
+impl Default for TerraformConfig {
+    fn default() -> Self {
+        Self {
+            commands: vec![TerraformCommand::Validate, TerraformCommand::Fmt],
+            tflint: false,
+            validate_child_modules: false,
+            workspace: None,
+            module_filter: None,
+            exclude_modules: None,
+        }
+    }
+}
+
+impl TerraformConfig {
+    fn runs(&self, command: &TerraformCommand) -> bool {
+        self.commands.contains(command) || (*command == TerraformCommand::Tflint && self.tflint)
+    }
+
+    /// Whether `module` passes `module_filter` and `exclude_modules`, matched
+    /// against its directory or any trailing part of it
+    fn selects(&self, module: &TerraformModule) -> bool {
+        let matches = |names: &[String]| {
+            names
+                .iter()
+                .any(|name| module.dir.ends_with(name.trim_end_matches('/')))
+        };
+        self.module_filter.as_deref().is_none_or(matches)
+            && !self.exclude_modules.as_deref().is_some_and(matches)
+    }
+}
+
+#[async_trait]
+impl ValidationTool for TerraformTool {
+    async fn validate(&self, intent: &ActionIntent) -> ActionResult<ToolResult> {
+        info!("Running Terraform validation for intent: {}", intent.id);
+
+        let start = std::time::Instant::now();
+        let config = self.parse_config(intent);
+        let modules = self.resolve_modules(&intent.scope, &config)?;
+        if modules.is_empty() {
+            return Ok(ToolResult {
+                success: true,
+                changes: vec![],
+                output: "No Terraform modules found to validate".to_string(),
+                errors: vec![],
+                warnings: vec![],
+                duration: start.elapsed(),
+                cached: false,
+                diagnostics: Vec::new(),
+                failed_tests: Vec::new(),
+            });
+        }
+
+        let commands = [
+            TerraformCommand::Validate,
+            TerraformCommand::Fmt,
+            TerraformCommand::Tflint,
+        ];
+        let results = self
+            .run_on_modules(&modules, &commands, &config, false)
+            .await;
+        Ok(self.collect_results(results, &modules, "validation", start))
+    }
+
+    fn applies_to(&self, profile: &ToolchainProfile) -> bool {
+        profile.has_language("terraform")
+    }
+
+    fn name(&self) -> &str {
+        "terraform"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn is_available(&self) -> bool {
+        tool_available("terraform").await
+    }
+}
+
+#[async_trait]
+impl TransformationTool for TerraformTool {
+    async fn execute(&self, intent: &ActionIntent) -> ActionResult<ToolResult> {
+        info!(
+            "Executing Terraform transformations for intent: {}",
+            intent.id
+        );
+
+        if intent.dry_run {
+            return self.preview(intent).await;
+        }
+
+        let start = std::time::Instant::now();
+        let config = self.parse_config(intent);
+        let modules = self.resolve_modules(&intent.scope, &config)?;
+        if modules.is_empty() {
+            return Err(ActionError::Validation(
+                "No Terraform modules found for transformation".to_string(),
+            ));
+        }
+
+        // Only formatting rewrites files
+        let results = self
+            .run_on_modules(&modules, &[TerraformCommand::Fmt], &config, true)
+            .await;
+        Ok(self.collect_results(results, &modules, "transformations", start))
+    }
+
+    fn supports_language(&self, language: &str) -> bool {
+        language == "terraform"
+    }
+
+    fn safety_level(&self) -> SafetyLevel {
+        SafetyLevel::Medium
+    }
+
+    fn name(&self) -> &str {
+        "terraform"
+    }
+
+    fn version(&self) -> &str {
+        "1.0.0"
+    }
+
+    async fn is_available(&self) -> bool {
+        tool_available("terraform").await
+    }
+}
+
+impl TerraformTool {
+    /// Parse configuration from intent metadata
+    fn parse_config(&self, intent: &ActionIntent) -> TerraformConfig {
+        let mut config = TerraformConfig::default();
+        let metadata = &intent.metadata;
+        if metadata.is_null() {
+            return config;
+        }
+
+        if let Some(commands) = metadata.get("commands").and_then(Value::as_array) {
+            config.commands = commands
+                .iter()
+                .filter_map(|cmd| match cmd.as_str()? {
+                    "validate" => Some(TerraformCommand::Validate),
+                    "fmt" | "format" => Some(TerraformCommand::Fmt),
+                    "tflint" | "lint" => Some(TerraformCommand::Tflint),
+                    _ => None,
+                })
+                .collect();
+        }
+
+        if let Some(tflint) = metadata.get("tflint") {
+            config.tflint = tflint.as_bool().unwrap_or(false);
+        }
+
+        if let Some(children) = metadata.get("validate_child_modules") {
+            config.validate_child_modules = children.as_bool().unwrap_or(false);
+        }
+
+        config.workspace = metadata
+            .get("workspace")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        config.module_filter = string_list(metadata.get("module_filter"));
+        config.exclude_modules = string_list(metadata.get("exclude_modules"));
+        config
+    }
+
+    /// Modules for the Terraform files and directories in scope. A file
+    /// stands for its directory; a directory for every module beneath it.
+    fn resolve_modules(
+        &self,
+        scope: &[String],
+        config: &TerraformConfig,
+    ) -> ActionResult<Vec<TerraformModule>> {
+        let mut dirs = BTreeSet::new();
+        for entry in scope {
+            let path = Path::new(entry);
+            if path.is_dir() {
+                find_module_dirs(path, &mut dirs)?;
+            } else if is_terraform_file(path) {
+                dirs.insert(
+                    path.parent()
+                        .filter(|dir| !dir.as_os_str().is_empty())
+                        .unwrap_or(Path::new("."))
+                        .to_path_buf(),
+                );
+            }
+        }
+
+        let mut called = BTreeSet::new();
+        for dir in &dirs {
+            for source in local_module_sources(dir)? {
+                called.insert(normalize(&dir.join(source)));
+            }
+        }
+
+        Ok(dirs
+            .into_iter()
+            .map(|dir| TerraformModule {
+                root: !called.contains(&normalize(&dir)),
+                dir,
+            })
+            .filter(|module| config.selects(module))
+            .collect())
+    }
+
+    /// Run each configured command on every module. Validation runs on root
+    /// modules, which load their local child modules, unless child modules
+    /// are validated on their own too.
+    async fn run_on_modules(
+        &self,
+        modules: &[TerraformModule],
+        commands: &[TerraformCommand],
+        config: &TerraformConfig,
+        fix: bool,
+    ) -> Vec<TerraformResult> {
+        let mut results = Vec::new();
+        for module in modules {
+            let label = module.dir.display().to_string();
+            for command in commands.iter().filter(|command| config.runs(command)) {
+                if *command == TerraformCommand::Validate
+                    && !module.root
+                    && !config.validate_child_modules
+                {
+                    continue;
+                }
+                let result = match command {
+                    TerraformCommand::Validate => self.execute_validate(&module.dir, config).await,
+                    TerraformCommand::Fmt => self.execute_fmt(&module.dir, fix).await,
+                    TerraformCommand::Tflint => self.execute_tflint(&module.dir).await,
+                };
+                match result {
+                    Ok(mut result) => {
+                        result.output = format!("[{}] {}", label, result.output);
+                        results.push(result);
+                    }
+                    Err(e) => {
+                        error!("Failed to execute {:?} for {}: {}", command, label, e);
+                        results.push(TerraformResult {
+                            command: command.clone(),
+                            success: false,
+                            output: format!("[{}] Failed", label),
+                            changes: vec![],
+                            errors: vec![format!("{}: {}", label, e)],
+                            warnings: vec![],
+                            diagnostics: vec![],
+                        });
+                    }
+                }
+            }
+        }
+        results
+    }
+
+    fn collect_results(
+        &self,
+        results: Vec<TerraformResult>,
+        modules: &[TerraformModule],
+        kind: &str,
+        start: std::time::Instant,
+    ) -> ToolResult {
+        let mut errors = Vec::new();
+        let mut warnings = Vec::new();
+        let mut changes = Vec::new();
+        let mut diagnostics = Vec::new();
+        for result in results {
+            errors.extend(result.errors);
+            warnings.extend(result.warnings);
+            diagnostics.extend(result.diagnostics);
+            changes.extend(result.changes);
+            if !result.output.is_empty() {
+                changes.push(result.output);
+            }
+        }
+
+        ToolResult {
+            success: errors.is_empty(),
+            changes,
+            output: format!("Terraform {} completed for {} modules", kind, modules.len()),
+            errors,
+            warnings,
+            duration: start.elapsed(),
+            cached: false,
+            diagnostics,
+            failed_tests: Vec::new(),
+        }
+    }
+
+    /// Initialize the module without a backend and validate it. Providers
+    /// and modules are installed into a temporary data directory, and a lock
+    /// file that `init` creates is removed again, so the tree is unchanged.
+    async fn execute_validate(
+        &self,
+        dir: &Path,
+        config: &TerraformConfig,
+    ) -> ActionResult<TerraformResult> {
+        let data_dir = tempfile::tempdir()?;
+        let lock_file = dir.join(".terraform.lock.hcl");
+        let had_lock_file = lock_file.exists();
+        let run = |args: &[&str]| {
+            let mut command = tool_command("terraform");
+            command
+                .args(args)
+                .current_dir(dir)
+                .env("TF_DATA_DIR", data_dir.path())
+                .env("TF_IN_AUTOMATION", "1")
+                .env("TF_INPUT", "0");
+            if let Some(workspace) = &config.workspace {
+                command.env("TF_WORKSPACE", workspace);
+            }
+            command
+        };
+
+        let mut init_args = vec!["init", "-backend=false", "-input=false", "-no-color"];
+        if had_lock_file {
+            init_args.push("-lockfile=readonly");
+        }
+        let init = run(&init_args).limited_output().await;
+        if !had_lock_file {
+            let _ = std::fs::remove_file(&lock_file);
+        }
+        let init = init.map_err(|e| ActionError::ToolExecution {
+            tool: "terraform".to_string(),
+            message: format!("Failed to run terraform init: {}", e),
+        })?;
+        if !init.status.success() {
+            return Ok(TerraformResult {
+                command: TerraformCommand::Validate,
+                success: false,
+                output: "terraform init failed".to_string(),
+                changes: vec![],
+                errors: vec![format!(
+                    "{}: terraform init failed: {}",
+                    dir.display(),
+                    String::from_utf8_lossy(&init.stderr).trim()
+                )],
+                warnings: vec![],
+                diagnostics: vec![],
+            });
+        }
+
+        let output = run(&["validate", "-json", "-no-color"])
+            .limited_output()
+            .await
+            .map_err(|e| ActionError::ToolExecution {
+                tool: "terraform".to_string(),
+                message: format!("Failed to run terraform validate: {}", e),
+            })?;
+
+        let mut result = empty_result(TerraformCommand::Validate);
+        match parse_validate_json(&String::from_utf8_lossy(&output.stdout), dir) {
+            Some(diagnostics) => {
+                result.output = format!("terraform validate reported {} issues", diagnostics.len());
+                result.diagnostics = diagnostics;
+            }
+            None => result.errors.push(format!(
+                "{}: terraform validate failed: {}",
+                dir.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )),
+        }
+        Ok(finish(result, false))
+    }
+
+    /// List unformatted files, and rewrite them when `fix` is set
+    async fn execute_fmt(&self, dir: &Path, fix: bool) -> ActionResult<TerraformResult> {
+        let args: &[&str] = if fix {
+            &["fmt", "-write=true", "-list=true", "-no-color"]
+        } else {
+            &["fmt", "-check", "-list=true", "-no-color"]
+        };
+        let output = tool_command("terraform")
+            .args(args)
+            .current_dir(dir)
+            .limited_output()
+            .await
+            .map_err(|e| ActionError::ToolExecution {
+                tool: "terraform".to_string(),
+                message: format!("Failed to run terraform fmt: {}", e),
+            })?;
+
+        let files: Vec<String> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|file| dir.join(file).display().to_string())
+            .collect();
+        let stderr = String::from_utf8_lossy(&output.stderr);
+
+        let mut result = empty_result(TerraformCommand::Fmt);
+        if fix {
+            result.output = format!("terraform fmt formatted {} files", files.len());
+            result.changes = files
+                .iter()
+                .map(|file| format!("Formatted {}", file))
+                .collect();
+            if !output.status.success() {
+                result
+                    .errors
+                    .push(format!("terraform fmt failed: {}", stderr.trim()));
+            }
+        } else {
+            result.output = format!("{} files need formatting", files.len());
+            result.diagnostics = files
+                .iter()
+                .map(|file| {
+                    Diagnostic::error("terraform", "not terraform fmt-formatted")
+                        .at(file, None, None)
+                })
+                .collect();
+            // Exit code 3 only reports unformatted files; syntax errors are on stderr
+            if !stderr.trim().is_empty() {
+                result
+                    .errors
+                    .push(format!("terraform fmt failed: {}", stderr.trim()));
+            }
+        }
+        Ok(finish(result, fix))
+    }
+
+    /// Run tflint when it is installed
+    async fn execute_tflint(&self, dir: &Path) -> ActionResult<TerraformResult> {
+        let mut result = empty_result(TerraformCommand::Tflint);
+        if !tool_available("tflint").await {
+            result.output = "tflint skipped".to_string();
+            result
+                .warnings
+                .push("tflint is not installed; lint skipped".to_string());
+            return Ok(result);
+        }
+
+        let output = tool_command(TerraformCommand::Tflint.program())
+            .args(["--format=json", "--no-color"])
+            .current_dir(dir)
+            .limited_output()
+            .await
+            .map_err(|e| ActionError::ToolExecution {
+                tool: "tflint".to_string(),
+                message: format!("Failed to run tflint: {}", e),
+            })?;
+
+        match parse_tflint_json(&String::from_utf8_lossy(&output.stdout), dir) {
+            Some((diagnostics, failures)) => {
+                result.output = format!("tflint reported {} issues", diagnostics.len());
+                result.diagnostics = diagnostics;
+                result.errors.extend(failures);
+            }
+            None => result.errors.push(format!(
+                "{}: tflint failed: {}",
+                dir.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )),
+        }
+        Ok(finish(result, false))
+    }
+}
+
+fn empty_result(command: TerraformCommand) -> TerraformResult {
+    TerraformResult {
+        command,
+        success: true,
+        output: String::new(),
+        changes: vec![],
+        errors: vec![],
+        warnings: vec![],
+        diagnostics: vec![],
+    }
+}
+
+/// Sort diagnostics into errors and warnings. Issues left after a
+/// transformation are warnings; in validation they are errors.
+fn finish(mut result: TerraformResult, fix: bool) -> TerraformResult {
+    if fix {
+        for diagnostic in &mut result.diagnostics {
+            if diagnostic.severity == Severity::Error {
+                diagnostic.severity = Severity::Warning;
+            }
+        }
+    }
+    for diagnostic in &result.diagnostics {
+        match diagnostic.severity {
+            Severity::Error => result.errors.push(diagnostic.to_string()),
+            Severity::Warning => result.warnings.push(diagnostic.to_string()),
+            Severity::Info => {}
+        }
+    }
+    result.success = result.errors.is_empty();
+    info!("{:?}: {}", result.command, result.output);
+    result
+}
+
+/// Files of a module's configuration
+fn is_module_file(path: &Path) -> bool {
+    let name = path.to_string_lossy();
+    name.ends_with(".tf") || name.ends_with(".tf.json")
+}
+
+fn is_terraform_file(path: &Path) -> bool {
+    is_module_file(path) || path.to_string_lossy().ends_with(".tfvars")
+}
+
+/// Directories under `dir` holding `.tf` files, skipping hidden directories
+/// such as `.terraform`
+fn find_module_dirs(dir: &Path, dirs: &mut BTreeSet<PathBuf>) -> ActionResult<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let hidden = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        if path.is_dir() && !hidden && !path.is_symlink() {
+            find_module_dirs(&path, dirs)?;
+        } else if path.is_file() && is_module_file(&path) {
+            dirs.insert(dir.to_path_buf());
+        }
+    }
+    Ok(())
+}
+
+/// `source` paths of the local modules a module calls, e.g. `./modules/vpc`
+fn local_module_sources(dir: &Path) -> ActionResult<Vec<String>> {
+    let mut sources = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "tf") {
+            continue;
+        }
+        let content = std::fs::read_to_string(&path)?;
+        sources.extend(content.lines().filter_map(|line| {
+            let value = line
+                .trim()
+                .strip_prefix("source")?
+                .trim_start()
+                .strip_prefix('=')?;
+            let source = value.trim().strip_prefix('"')?.split('"').next()?;
+            (source.starts_with("./") || source.starts_with("../")).then(|| source.to_string())
+        }));
+    }
+    Ok(sources)
+}
+
+/// `path` without `.` components and with `..` applied, for comparing module
+/// directories that were reached through different relative paths
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir if normalized.file_name().is_some() => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Diagnostics of `terraform validate -json`, with file names placed in `dir`
+fn parse_validate_json(stdout: &str, dir: &Path) -> Option<Vec<Diagnostic>> {
+    let report: Value = serde_json::from_str(stdout.trim()).ok()?;
+    let diagnostics = report.get("diagnostics")?.as_array()?;
+    Some(
+        diagnostics
+            .iter()
+            .map(|item| {
+                let severity = match item.get("severity").and_then(Value::as_str) {
+                    Some("warning") => Severity::Warning,
+                    _ => Severity::Error,
+                };
+                let summary = item.get("summary").and_then(Value::as_str).unwrap_or("");
+                let message = match item.get("detail").and_then(Value::as_str) {
+                    Some(detail) if !detail.is_empty() => format!("{}: {}", summary, detail),
+                    _ => summary.to_string(),
+                };
+                located(
+                    Diagnostic::new("terraform", severity, message),
+                    item.get("range"),
+                    dir,
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Issues and failures of `tflint --format=json`, with file names placed in `dir`
+fn parse_tflint_json(stdout: &str, dir: &Path) -> Option<(Vec<Diagnostic>, Vec<String>)> {
+    let report: Value = serde_json::from_str(stdout.trim()).ok()?;
+    let diagnostics = report
+        .get("issues")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(|issue| {
+            let rule = issue.get("rule");
+            let severity = match rule
+                .and_then(|rule| rule.get("severity"))
+                .and_then(Value::as_str)
+            {
+                Some("error") => Severity::Error,
+                Some("warning") => Severity::Warning,
+                _ => Severity::Info,
+            };
+            let message = issue.get("message").and_then(Value::as_str).unwrap_or("");
+            let mut diagnostic = located(
+                Diagnostic::new("tflint", severity, message),
+                issue.get("range"),
+                dir,
+            );
+            if let Some(name) = rule
+                .and_then(|rule| rule.get("name"))
+                .and_then(Value::as_str)
+            {
+                diagnostic = diagnostic.with_code(name);
+            }
+            diagnostic
+        })
+        .collect();
+    let failures = report
+        .get("errors")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|failure| failure.get("message").and_then(Value::as_str))
+        .map(|message| format!("{}: tflint: {}", dir.display(), message))
+        .collect();
+    Some((diagnostics, failures))
+}
+
+/// Place a diagnostic at the `range` Terraform and tflint report
+fn located(diagnostic: Diagnostic, range: Option<&Value>, dir: &Path) -> Diagnostic {
+    let Some(file) = range
+        .and_then(|range| range.get("filename"))
+        .and_then(Value::as_str)
+    else {
+        return diagnostic;
+    };
+    let number = |key: &str| {
+        range
+            .and_then(|range| range.get("start"))
+            .and_then(|start| start.get(key))
+            .and_then(Value::as_u64)
+            .and_then(|n| u32::try_from(n).ok())
+    };
+    diagnostic.at(
+        dir.join(file).display().to_string(),
+        number("line"),
+        number("column"),
+    )
+}
+
+fn string_list(value: Option<&Value>) -> Option<Vec<String>> {
+    value.and_then(|v| v.as_array()).map(|items| {
+        items
+            .iter()
+            .filter_map(|item| item.as_str().map(str::to_string))
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,231 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+use rhema_action_tool::environment::probe_for;
+use rhema_action_tool::platform::resolve_program;
+use rhema_action_tool::{ActionIntent, ActionType, SafetyLevel};
+use serde_json::json;
+use std::fs;
+use tempfile::TempDir;
+
+fn infra() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+    fs::create_dir_all(root.join("modules/vpc")).unwrap();
+    fs::create_dir_all(root.join(".terraform/modules/cached")).unwrap();
+    fs::write(
+        root.join("main.tf"),
+        "module \"vpc\" {\n  source = \"./modules/vpc\"\n}\n\nmodule \"s3\" {\n  source  = \"terraform-aws-modules/s3-bucket/aws\"\n}\n",
+    )
+    .unwrap();
+    fs::write(root.join("prod.tfvars"), "region = \"eu-west-1\"\n").unwrap();
+    fs::write(root.join("modules/vpc/main.tf"), "variable \"cidr\" {}\n").unwrap();
+    fs::write(root.join(".terraform/modules/cached/main.tf"), "").unwrap();
+    dir
+}
+
+#[tokio::test]
+async fn test_terraform_tool_creation() {
+    let tool = TerraformTool;
+    assert_eq!(ValidationTool::name(&tool), "terraform");
+    assert_eq!(TransformationTool::name(&tool), "terraform");
+    assert_eq!(ValidationTool::version(&tool), "1.0.0");
+}
+
+#[test]
+fn test_parse_config_default() {
+    let tool = TerraformTool;
+    let intent = ActionIntent::new(
+        "test",
+        ActionType::Configuration,
+        "Tag every bucket",
+        vec![],
+        SafetyLevel::Medium,
+    );
+
+    let config = tool.parse_config(&intent);
+    assert_eq!(config, TerraformConfig::default());
+    assert!(!config.runs(&TerraformCommand::Tflint));
+}
+
+#[test]
+fn test_parse_config_custom() {
+    let tool = TerraformTool;
+    let mut intent = ActionIntent::new(
+        "test",
+        ActionType::Configuration,
+        "Tag every bucket",
+        vec![],
+        SafetyLevel::Medium,
+    );
+    intent.metadata = json!({
+        "commands": ["fmt"],
+        "tflint": true,
+        "validate_child_modules": true,
+        "workspace": "staging",
+        "exclude_modules": ["modules/vpc"]
+    });
+
+    let config = tool.parse_config(&intent);
+    assert_eq!(config.commands, vec![TerraformCommand::Fmt]);
+    assert!(config.runs(&TerraformCommand::Tflint));
+    assert!(config.validate_child_modules);
+    assert_eq!(config.workspace.as_deref(), Some("staging"));
+}
+
+#[tokio::test]
+async fn test_validation_with_no_modules() {
+    let tool = TerraformTool;
+    let intent = ActionIntent::new(
+        "test",
+        ActionType::Configuration,
+        "Tag every bucket",
+        vec!["README.md".to_string()],
+        SafetyLevel::Medium,
+    );
+
+    let result = tool.validate(&intent).await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.output, "No Terraform modules found to validate");
+}
+
+#[tokio::test]
+async fn test_validate_with_terraform() {
+    let tool = TerraformTool;
+    assert!(probe_for("terraform").is_some());
+    assert!(probe_for("tflint").is_some());
+    assert_eq!(
+        ValidationTool::is_available(&tool).await,
+        resolve_program("terraform").is_some()
+    );
+    if !ValidationTool::is_available(&tool).await {
+        return;
+    }
+
+    let dir = TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("main.tf"),
+        "output \"region\" {\n  value = var.region\n}\n",
+    )
+    .unwrap();
+    let intent = ActionIntent::new(
+        "test",
+        ActionType::Configuration,
+        "Tag every bucket",
+        vec![dir.path().display().to_string()],
+        SafetyLevel::Medium,
+    );
+
+    let result = tool.validate(&intent).await.unwrap();
+    assert!(!result.success);
+    assert!(result
+        .errors
+        .iter()
+        .any(|e| e.contains("Reference to undeclared input variable")));
+    assert!(!dir.path().join(".terraform").exists());
+}
+
+#[test]
+fn test_resolve_modules_finds_roots_and_children() {
+    let dir = infra();
+    let root = dir.path().to_path_buf();
+    let tool = TerraformTool;
+
+    let modules = tool
+        .resolve_modules(&[root.display().to_string()], &TerraformConfig::default())
+        .unwrap();
+    assert_eq!(
+        modules,
+        vec![
+            TerraformModule {
+                dir: root.clone(),
+                root: true
+            },
+            TerraformModule {
+                dir: root.join("modules/vpc"),
+                root: false
+            },
+        ]
+    );
+
+    // A file stands for its module; alone, a child module is its own root
+    let modules = tool
+        .resolve_modules(
+            &[root.join("modules/vpc/main.tf").display().to_string()],
+            &TerraformConfig::default(),
+        )
+        .unwrap();
+    assert_eq!(modules.len(), 1);
+    assert!(modules[0].root);
+
+    let config = TerraformConfig {
+        exclude_modules: Some(vec!["modules/vpc".to_string()]),
+        ..TerraformConfig::default()
+    };
+    let modules = tool
+        .resolve_modules(&[root.display().to_string()], &config)
+        .unwrap();
+    assert_eq!(modules.len(), 1);
+    assert_eq!(modules[0].dir, root);
+}
+
+#[test]
+fn test_parse_validate_and_tflint_json() {
+    let dir = Path::new("infra");
+    let validate = r#"{
+        "format_version": "1.0", "valid": false, "error_count": 1, "warning_count": 1,
+        "diagnostics": [
+            {"severity": "error", "summary": "Unsupported argument",
+             "detail": "An argument named \"acl\" is not expected here.",
+             "range": {"filename": "main.tf", "start": {"line": 4, "column": 3, "byte": 40}}},
+            {"severity": "warning", "summary": "Deprecated attribute", "detail": ""}
+        ]
+    }"#;
+    let diagnostics = parse_validate_json(validate, dir).unwrap();
+    assert_eq!(
+        diagnostics[0].to_string(),
+        format!(
+            "{}:4:3: Unsupported argument: An argument named \"acl\" is not expected here.",
+            dir.join("main.tf").display()
+        )
+    );
+    assert_eq!(diagnostics[1].severity, Severity::Warning);
+    assert_eq!(diagnostics[1].file, None);
+    assert_eq!(parse_validate_json("Error: not json", dir), None);
+
+    let tflint = r#"{
+        "issues": [
+            {"rule": {"name": "terraform_unused_declarations", "severity": "warning", "link": ""},
+             "message": "variable \"cidr\" is declared but not used",
+             "range": {"filename": "variables.tf", "start": {"line": 1, "column": 1}}}
+        ],
+        "errors": [{"message": "Failed to load configurations"}]
+    }"#;
+    let (diagnostics, failures) = parse_tflint_json(tflint, dir).unwrap();
+    assert_eq!(
+        diagnostics[0].code.as_deref(),
+        Some("terraform_unused_declarations")
+    );
+    assert_eq!(diagnostics[0].severity, Severity::Warning);
+    assert_eq!(
+        failures,
+        vec![format!(
+            "{}: tflint: Failed to load configurations",
+            dir.display()
+        )]
+    );
+}
@@ -15,8 +15,10 @@
  */
 
 use async_trait::async_trait;
+use rhema_action_tool::platform::tool_command;
+use rhema_action_tool::{tool_available, LimitedCommand};
 use rhema_action_tool::{ActionError, ActionIntent, ActionResult};
-use rhema_action_tool::{ToolResult, ValidationTool};
+use rhema_action_tool::{Diagnostic, Severity, ToolResult, ToolchainProfile, ValidationTool};
 use tracing::info;
 
 /// TypeScript validation tool
@@ -45,17 +47,33 @@ impl ValidationTool for TypeScriptTool {
                 errors: vec![],
                 warnings: vec![],
                 duration: start.elapsed(),
+                cached: false,
+                diagnostics: Vec::new(),
+                failed_tests: Vec::new(),
             });
         }
 
         // Run TypeScript compiler check
         let mut errors = Vec::new();
-        let warnings = Vec::new();
+        let mut warnings = Vec::new();
+        let mut diagnostics = Vec::new();
 
         for file in &ts_files {
             match self.validate_typescript_file(file).await {
-                Ok(_) => {}
-                Err(e) => errors.push(format!("TypeScript error in {}: {}", file, e)),
+                Ok(found) => {
+                    for diagnostic in found {
+                        match diagnostic.severity {
+                            Severity::Error => errors.push(diagnostic.to_string()),
+                            _ => warnings.push(diagnostic.to_string()),
+                        }
+                        diagnostics.push(diagnostic);
+                    }
+                }
+                Err(e) => {
+                    let message = format!("TypeScript error in {}: {}", file, e);
+                    diagnostics.push(Diagnostic::error("typescript", &message));
+                    errors.push(message);
+                }
             }
         }
 
@@ -71,9 +89,17 @@ impl ValidationTool for TypeScriptTool {
             errors,
             warnings,
             duration: start.elapsed(),
+            cached: false,
+            diagnostics,
+            failed_tests: Vec::new(),
         })
     }
 
+    /// Type-check scopes written in TypeScript
+    fn applies_to(&self, profile: &ToolchainProfile) -> bool {
+        profile.has_language("typescript")
+    }
+
     fn name(&self) -> &str {
         "typescript"
     }
@@ -83,36 +109,85 @@ impl ValidationTool for TypeScriptTool {
     }
 
     async fn is_available(&self) -> bool {
-        // Check if TypeScript is installed
-        tokio::process::Command::new("npx")
-            .args(&["tsc", "--version"])
-            .output()
-            .await
-            .map(|output| output.status.success())
-            .unwrap_or(false)
+        tool_available("typescript").await
     }
 }
 
 impl TypeScriptTool {
-    /// Validate a TypeScript file
-    async fn validate_typescript_file(&self, file_path: &str) -> ActionResult<()> {
-        let output = tokio::process::Command::new("npx")
-            .args(&["tsc", "--noEmit", file_path])
-            .output()
+    /// Type-check a TypeScript file, returning the compiler's diagnostics
+    async fn validate_typescript_file(&self, file_path: &str) -> ActionResult<Vec<Diagnostic>> {
+        let output = tool_command("npx")
+            .args(["tsc", "--noEmit", "--pretty", "false", file_path])
+            .limited_output()
             .await
             .map_err(|e| ActionError::ToolExecution {
                 tool: "typescript".to_string(),
                 message: format!("Failed to run TypeScript check: {}", e),
             })?;
 
-        if output.status.success() {
-            Ok(())
+        // tsc reports diagnostics on stdout
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let diagnostics = parse_tsc_output(&stdout);
+        if output.status.success() || !diagnostics.is_empty() {
+            Ok(diagnostics)
         } else {
             let error = String::from_utf8_lossy(&output.stderr);
             Err(ActionError::ToolExecution {
                 tool: "typescript".to_string(),
-                message: format!("TypeScript validation failed: {}", error),
+                message: format!("TypeScript validation failed: {}{}", stdout, error),
             })
         }
     }
 }
+
+/// Diagnostics of `tsc --pretty false` output, one per line such as
+/// `src/app.ts(12,5): error TS2322: Type 'string' is not assignable...`.
+/// Continuation lines are appended to the message they belong to.
+fn parse_tsc_output(output: &str) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    for line in output.lines() {
+        match parse_tsc_line(line) {
+            Some(diagnostic) => diagnostics.push(diagnostic),
+            None if line.starts_with(' ') && !line.trim().is_empty() => {
+                if let Some(last) = diagnostics.last_mut() {
+                    last.message.push('\n');
+                    last.message.push_str(line.trim());
+                }
+            }
+            None => {}
+        }
+    }
+    diagnostics
+}
+
+fn parse_tsc_line(line: &str) -> Option<Diagnostic> {
+    // The location is optional: `error TS6053: File 'x.ts' not found.`
+    let (location, rest) = match line.find("): ") {
+        Some(end) if line[..end].contains('(') => (Some(&line[..end]), &line[end + 3..]),
+        _ => (None, line),
+    };
+    let (severity, rest) = if let Some(rest) = rest.strip_prefix("error ") {
+        (Severity::Error, rest)
+    } else if let Some(rest) = rest.strip_prefix("warning ") {
+        (Severity::Warning, rest)
+    } else if let Some(rest) = rest.strip_prefix("message ") {
+        (Severity::Info, rest)
+    } else {
+        return None;
+    };
+    let (code, message) = rest.split_once(": ")?;
+    if !code.starts_with("TS") {
+        return None;
+    }
+
+    let mut diagnostic = Diagnostic::new("typescript", severity, message).with_code(code);
+    if let Some(location) = location {
+        let (file, position) = location.rsplit_once('(')?;
+        let (line, column) = position.split_once(',')?;
+        diagnostic = diagnostic.at(file, line.parse().ok(), column.parse().ok());
+    }
+    Some(diagnostic)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,41 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::*;
+
+#[test]
+fn test_parse_tsc_output() {
+    let output = "src/app.ts(12,5): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+src/app.ts(20,1): error TS2345: Argument of type '{}' is not assignable.\n  Property 'id' is missing.\n\
+error TS6053: File 'missing.ts' not found.\n";
+
+    let diagnostics = parse_tsc_output(output);
+    assert_eq!(diagnostics.len(), 3);
+    assert_eq!(
+        diagnostics[0],
+        Diagnostic::error(
+            "typescript",
+            "Type 'string' is not assignable to type 'number'."
+        )
+        .at("src/app.ts", Some(12), Some(5))
+        .with_code("TS2322")
+    );
+    assert!(diagnostics[1]
+        .message
+        .ends_with("\nProperty 'id' is missing."));
+    assert_eq!(diagnostics[2].file, None);
+    assert_eq!(diagnostics[2].code.as_deref(), Some("TS6053"));
+}
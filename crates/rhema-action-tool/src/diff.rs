@@ -0,0 +1,174 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Line diffs of file contents.
+//!
+//! Shared by the sandbox, which reports the changes tools made as a unified
+//! diff, and by the approval views that render proposed changes.
+
+/// One line of a line diff
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLine<'a> {
+    Context(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Line diff by longest common subsequence, after trimming the common prefix
+/// and suffix so the table only spans the changed region
+pub fn diff_lines<'a>(before: &'a str, after: &'a str) -> Vec<DiffLine<'a>> {
+    let old: Vec<&str> = before.lines().collect();
+    let new: Vec<&str> = after.lines().collect();
+
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    let (n, m) = (old_mid.len(), new_mid.len());
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_mid[i] == new_mid[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops: Vec<DiffLine> = old[..prefix]
+        .iter()
+        .copied()
+        .map(DiffLine::Context)
+        .collect();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old_mid[i] == new_mid[j] {
+            ops.push(DiffLine::Context(old_mid[i]));
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(DiffLine::Removed(old_mid[i]));
+            i += 1;
+        } else {
+            ops.push(DiffLine::Added(new_mid[j]));
+            j += 1;
+        }
+    }
+    ops.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .copied()
+            .map(DiffLine::Context),
+    );
+    ops
+}
+
+/// Git-style unified diff of one file; `None` stands for a missing file
+pub fn unified_diff(
+    path: &str,
+    before: Option<&str>,
+    after: Option<&str>,
+    context_lines: usize,
+) -> String {
+    let mut out = format!(
+        "--- {}\n+++ {}\n",
+        before.map_or("/dev/null".to_string(), |_| format!("a/{}", path)),
+        after.map_or("/dev/null".to_string(), |_| format!("b/{}", path)),
+    );
+
+    let ops = diff_lines(before.unwrap_or(""), after.unwrap_or(""));
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for (index, op) in ops.iter().enumerate() {
+        if matches!(op, DiffLine::Context(_)) {
+            continue;
+        }
+        let start = index.saturating_sub(context_lines);
+        let end = (index + context_lines + 1).min(ops.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    for (start, end) in hunks {
+        let old_before = count_lines(&ops[..start], true);
+        let new_before = count_lines(&ops[..start], false);
+        let old_len = count_lines(&ops[start..end], true);
+        let new_len = count_lines(&ops[start..end], false);
+        out.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(old_before, old_len),
+            hunk_range(new_before, new_len)
+        ));
+        for op in &ops[start..end] {
+            let (marker, line) = match op {
+                DiffLine::Context(line) => (' ', line),
+                DiffLine::Removed(line) => ('-', line),
+                DiffLine::Added(line) => ('+', line),
+            };
+            out.push(marker);
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Lines of the old (`old == true`) or new side within `ops`
+fn count_lines(ops: &[DiffLine], old: bool) -> usize {
+    ops.iter()
+        .filter(|op| match op {
+            DiffLine::Context(_) => true,
+            DiffLine::Removed(_) => old,
+            DiffLine::Added(_) => !old,
+        })
+        .count()
+}
+
+fn hunk_range(before: usize, len: usize) -> String {
+    if len == 0 {
+        format!("{},0", before)
+    } else {
+        format!("{},{}", before + 1, len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unified_diff_of_modified_added_and_deleted_files() {
+        let modified = unified_diff("src/a.js", Some("a\nb\nc\n"), Some("a\nB\nc\n"), 3);
+        assert_eq!(
+            modified,
+            "--- a/src/a.js\n+++ b/src/a.js\n@@ -1,3 +1,3 @@\n a\n-b\n+B\n c\n"
+        );
+
+        let added = unified_diff("new.js", None, Some("x\n"), 3);
+        assert!(added.starts_with("--- /dev/null\n+++ b/new.js\n@@ -0,0 +1,1 @@\n+x\n"));
+
+        let deleted = unified_diff("old.js", Some("x\n"), None, 3);
+        assert!(deleted.contains("+++ /dev/null\n@@ -1,1 +0,0 @@\n-x\n"));
+    }
+}
@@ -0,0 +1,552 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Cached tool availability keyed by an environment fingerprint.
+//!
+//! Checking whether a tool is installed means running its `--version` probe,
+//! which for `npx`-launched tools takes seconds. Probe results are cached per
+//! machine in `<cache dir>/rhema/tool-environment.json` together with a
+//! fingerprint of `PATH` and of the files the probe runs. A cached result is
+//! reused until the fingerprint changes or it is older than
+//! [`MAX_PROBE_AGE_HOURS`], which catches changes the fingerprint cannot see,
+//! such as a globally installed npm package.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::time::UNIX_EPOCH;
+
+use crate::limits::{LimitedCommand, ToolExecution};
+use crate::platform::{resolve_program, tool_command};
+
+/// File name of the per-machine cache in the user cache directory
+pub const ENVIRONMENT_CACHE_FILE: &str = "tool-environment.json";
+
+/// Cached probe results older than this are probed again
+pub const MAX_PROBE_AGE_HOURS: i64 = 24;
+
+/// How to check that a tool is installed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToolProbe<'a> {
+    /// Name the tool is registered and reported under
+    pub tool: &'a str,
+    pub program: &'a str,
+    pub args: &'a [&'a str],
+    /// Project-local executable the probe runs through `npx`, relative to the
+    /// working directory
+    pub local_bin: Option<&'a str>,
+    /// How to install the tool when it is missing
+    pub install_hint: &'a str,
+}
+
+/// Probes of the built-in action tools and the programs they rely on
+pub const TOOL_PROBES: &[ToolProbe<'static>] = &[
+    ToolProbe {
+        tool: "jscodeshift",
+        program: "npx",
+        args: &["jscodeshift", "--version"],
+        local_bin: Some("node_modules/.bin/jscodeshift"),
+        install_hint: "npm install --save-dev jscodeshift",
+    },
+    ToolProbe {
+        tool: "comby",
+        program: "comby",
+        args: &["--version"],
+        local_bin: None,
+        install_hint: "brew install comby, or see https://comby.dev/docs/get-started",
+    },
+    ToolProbe {
+        tool: "ast-grep",
+        program: "sg",
+        args: &["--version"],
+        local_bin: None,
+        install_hint: "npm install --global @ast-grep/cli, or cargo install ast-grep --locked",
+    },
+    ToolProbe {
+        tool: "prettier",
+        program: "npx",
+        args: &["prettier", "--version"],
+        local_bin: Some("node_modules/.bin/prettier"),
+        install_hint: "npm install --save-dev prettier",
+    },
+    ToolProbe {
+        tool: "eslint",
+        program: "npx",
+        args: &["eslint", "--version"],
+        local_bin: Some("node_modules/.bin/eslint"),
+        install_hint: "npm install --save-dev eslint",
+    },
+    ToolProbe {
+        tool: "typescript",
+        program: "npx",
+        args: &["tsc", "--version"],
+        local_bin: Some("node_modules/.bin/tsc"),
+        install_hint: "npm install --save-dev typescript",
+    },
+    ToolProbe {
+        tool: "jest",
+        program: "npx",
+        args: &["jest", "--version"],
+        local_bin: Some("node_modules/.bin/jest"),
+        install_hint: "npm install --save-dev jest",
+    },
+    ToolProbe {
+        tool: "mocha",
+        program: "npx",
+        args: &["mocha", "--version"],
+        local_bin: Some("node_modules/.bin/mocha"),
+        install_hint: "npm install --save-dev mocha",
+    },
+    ToolProbe {
+        tool: "pytest",
+        program: "pytest",
+        args: &["--version"],
+        local_bin: None,
+        install_hint: "pip install pytest",
+    },
+    ToolProbe {
+        tool: "go",
+        program: "go",
+        args: &["version"],
+        local_bin: None,
+        install_hint: "see https://go.dev/doc/install",
+    },
+    ToolProbe {
+        tool: "golangci-lint",
+        program: "golangci-lint",
+        args: &["--version"],
+        local_bin: None,
+        install_hint: "see https://golangci-lint.run/welcome/install/",
+    },
+    ToolProbe {
+        tool: "ruff",
+        program: "ruff",
+        args: &["--version"],
+        local_bin: None,
+        install_hint: "pip install ruff",
+    },
+    ToolProbe {
+        tool: "black",
+        program: "black",
+        args: &["--version"],
+        local_bin: None,
+        install_hint: "pip install black",
+    },
+    ToolProbe {
+        tool: "mypy",
+        program: "mypy",
+        args: &["--version"],
+        local_bin: None,
+        install_hint: "pip install mypy",
+    },
+    ToolProbe {
+        tool: "semgrep",
+        program: "semgrep",
+        args: &["--version"],
+        local_bin: None,
+        install_hint: "pip install semgrep, or brew install semgrep",
+    },
+    ToolProbe {
+        tool: "shellcheck",
+        program: "shellcheck",
+        args: &["--version"],
+        local_bin: None,
+        install_hint: "apt install shellcheck, or brew install shellcheck",
+    },
+    ToolProbe {
+        tool: "terraform",
+        program: "terraform",
+        args: &["version"],
+        local_bin: None,
+        install_hint: "see https://developer.hashicorp.com/terraform/install",
+    },
+    ToolProbe {
+        tool: "tflint",
+        program: "tflint",
+        args: &["--version"],
+        local_bin: None,
+        install_hint: "brew install tflint, or see https://github.com/terraform-linters/tflint",
+    },
+    ToolProbe {
+        tool: "hadolint",
+        program: "hadolint",
+        args: &["--version"],
+        local_bin: None,
+        install_hint: "brew install hadolint, or see https://github.com/hadolint/hadolint",
+    },
+    ToolProbe {
+        tool: "cargo",
+        program: "cargo",
+        args: &["--version"],
+        local_bin: None,
+        install_hint: "install Rust with rustup: https://rustup.rs",
+    },
+    ToolProbe {
+        tool: "rustc",
+        program: "rustc",
+        args: &["--version"],
+        local_bin: None,
+        install_hint: "install Rust with rustup: https://rustup.rs",
+    },
+    ToolProbe {
+        tool: "node",
+        program: "node",
+        args: &["--version"],
+        local_bin: None,
+        install_hint: "install Node.js: https://nodejs.org",
+    },
+    ToolProbe {
+        tool: "git",
+        program: "git",
+        args: &["--version"],
+        local_bin: None,
+        install_hint: "install git: https://git-scm.com/downloads",
+    },
+];
+
+/// Built-in probe for a tool
+pub fn probe_for(tool: &str) -> Option<&'static ToolProbe<'static>> {
+    TOOL_PROBES.iter().find(|probe| probe.tool == tool)
+}
+
+/// Size and modification time of a file a probe depends on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileStamp {
+    pub path: PathBuf,
+    pub size: u64,
+    /// Seconds since the Unix epoch
+    pub modified: Option<u64>,
+}
+
+impl FileStamp {
+    fn of(path: &Path) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some(Self {
+            path: path.to_path_buf(),
+            size: metadata.len(),
+            modified: metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|since| since.as_secs()),
+        })
+    }
+}
+
+/// Everything a probe result depends on
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnvironmentFingerprint {
+    pub path_var: String,
+    /// The resolved program and, for `npx` tools, the project-local binary
+    pub files: Vec<FileStamp>,
+}
+
+impl EnvironmentFingerprint {
+    pub fn of(probe: &ToolProbe<'_>, cwd: &Path) -> Self {
+        let program = resolve_program(probe.program).and_then(|path| FileStamp::of(&path));
+        let local = probe
+            .local_bin
+            .and_then(|bin| FileStamp::of(&cwd.join(bin)));
+        Self {
+            path_var: std::env::var_os("PATH")
+                .map(|path| path.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            files: program.into_iter().chain(local).collect(),
+        }
+    }
+}
+
+/// Result of probing a tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ToolStatus {
+    pub tool: String,
+    pub program: String,
+    /// Resolved program path; `None` when it is not on `PATH`
+    pub path: Option<PathBuf>,
+    pub version: Option<String>,
+    pub available: bool,
+    pub checked_at: DateTime<Utc>,
+    pub fingerprint: EnvironmentFingerprint,
+    /// Served from the cache instead of probed
+    #[serde(skip)]
+    pub cached: bool,
+}
+
+/// Probe results cached by environment fingerprint
+pub struct EnvironmentCache {
+    path: Option<PathBuf>,
+    entries: Mutex<HashMap<String, ToolStatus>>,
+}
+
+impl EnvironmentCache {
+    /// A cache that is never persisted
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Load the cache persisted at `path`; a missing or unreadable file starts empty
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let entries = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self {
+            path: Some(path),
+            entries: Mutex::new(entries),
+        }
+    }
+
+    /// Location of the per-machine cache
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("rhema").join(ENVIRONMENT_CACHE_FILE))
+    }
+
+    /// The per-machine cache shared by every tool in this process
+    pub fn machine() -> &'static Self {
+        static CACHE: OnceLock<EnvironmentCache> = OnceLock::new();
+        CACHE.get_or_init(|| match Self::default_path() {
+            Some(path) => Self::load(path),
+            None => Self::in_memory(),
+        })
+    }
+
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Status of a tool, probing it only when the cached result is stale
+    pub async fn status(&self, probe: &ToolProbe<'_>) -> ToolStatus {
+        let cwd = probe_dir();
+        let key = cache_key(probe, &cwd);
+        let fingerprint = EnvironmentFingerprint::of(probe, &cwd);
+
+        let cached = self.entries().get(&key).cloned();
+        if let Some(status) = cached {
+            let fresh = Utc::now() - status.checked_at < Duration::hours(MAX_PROBE_AGE_HOURS);
+            if fresh && status.fingerprint == fingerprint {
+                return ToolStatus {
+                    cached: true,
+                    ..status
+                };
+            }
+        }
+        self.probe(probe, key, fingerprint).await
+    }
+
+    /// Probe a tool regardless of the cached result
+    pub async fn refresh(&self, probe: &ToolProbe<'_>) -> ToolStatus {
+        let cwd = probe_dir();
+        let key = cache_key(probe, &cwd);
+        let fingerprint = EnvironmentFingerprint::of(probe, &cwd);
+        self.probe(probe, key, fingerprint).await
+    }
+
+    async fn probe(
+        &self,
+        probe: &ToolProbe<'_>,
+        key: String,
+        fingerprint: EnvironmentFingerprint,
+    ) -> ToolStatus {
+        let output = tool_command(probe.program)
+            .args(probe.args)
+            .limited_output()
+            .await
+            .ok()
+            .filter(|output| output.status.success());
+        let status = ToolStatus {
+            tool: probe.tool.to_string(),
+            program: probe.program.to_string(),
+            path: resolve_program(probe.program),
+            version: output.as_ref().and_then(|output| {
+                first_line(&output.stdout).or_else(|| first_line(&output.stderr))
+            }),
+            available: output.is_some(),
+            checked_at: Utc::now(),
+            fingerprint,
+            cached: false,
+        };
+
+        let snapshot = {
+            let mut entries = self.entries();
+            entries.insert(key, status.clone());
+            entries.clone()
+        };
+        // The cache only saves probes; failing to persist it costs a re-probe
+        let _ = self.save(&snapshot);
+        status
+    }
+
+    fn entries(&self) -> MutexGuard<'_, HashMap<String, ToolStatus>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn save(&self, entries: &HashMap<String, ToolStatus>) -> std::io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(entries)?;
+        let staging = path.with_extension(format!("{}.tmp", std::process::id()));
+        std::fs::write(&staging, content)?;
+        std::fs::rename(&staging, path)
+    }
+}
+
+/// Whether a built-in tool is installed, using the per-machine cache
+pub async fn tool_available(tool: &str) -> bool {
+    match probe_for(tool) {
+        Some(probe) => EnvironmentCache::machine().status(probe).await.available,
+        None => false,
+    }
+}
+
+/// Directory tools are probed in: the working directory of the tool
+/// execution the task runs in, where its commands run too, or else the
+/// process working directory
+fn probe_dir() -> PathBuf {
+    ToolExecution::current()
+        .and_then(|execution| execution.runtime().working_dir.clone())
+        .unwrap_or_else(|| std::env::current_dir().unwrap_or_default())
+}
+
+/// Tools run through `npx` resolve per project, so they are cached per
+/// working directory
+fn cache_key(probe: &ToolProbe<'_>, cwd: &Path) -> String {
+    match probe.local_bin {
+        Some(_) => format!("{}@{}", probe.tool, cwd.display()),
+        None => probe.tool.to_string(),
+    }
+}
+
+fn first_line(output: &[u8]) -> Option<String> {
+    String::from_utf8_lossy(output)
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_probes_are_unique() {
+        let mut names: Vec<&str> = TOOL_PROBES.iter().map(|probe| probe.tool).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), TOOL_PROBES.len());
+        assert_eq!(probe_for("jest").unwrap().args, &["jest", "--version"]);
+        assert_eq!(probe_for("ast-grep").unwrap().program, "sg");
+    }
+
+    #[test]
+    fn test_probes_cover_go_toolchain() {
+        assert_eq!(probe_for("go").unwrap().args, &["version"]);
+        assert!(probe_for("golangci-lint").is_some());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_probe_cached_until_program_changes() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let program = dir.path().join("fake-tool");
+        let install = |version: &str| {
+            std::fs::write(&program, format!("#!/bin/sh\necho fake {}\n", version)).unwrap();
+            std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o755)).unwrap();
+        };
+        install("1.0");
+
+        let program_path = program.to_string_lossy().to_string();
+        let probe = ToolProbe {
+            tool: "fake",
+            program: &program_path,
+            args: &["--version"],
+            local_bin: None,
+            install_hint: "",
+        };
+        let cache_path = dir.path().join("cache").join(ENVIRONMENT_CACHE_FILE);
+        let cache = EnvironmentCache::load(&cache_path);
+
+        let first = cache.status(&probe).await;
+        assert!(first.available && !first.cached);
+        assert_eq!(first.version.as_deref(), Some("fake 1.0"));
+
+        // Reloaded from disk, the result is served without probing
+        let cache = EnvironmentCache::load(&cache_path);
+        let second = cache.status(&probe).await;
+        assert!(second.cached);
+        assert_eq!(second.version.as_deref(), Some("fake 1.0"));
+
+        // Replacing the program changes the fingerprint
+        install("10.0");
+        let upgraded = cache.status(&probe).await;
+        assert!(!upgraded.cached);
+        assert_eq!(upgraded.version.as_deref(), Some("fake 10.0"));
+
+        let missing = ToolProbe {
+            program: "/nonexistent/fake-tool",
+            ..probe
+        };
+        assert!(!cache.refresh(&missing).await.available);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_probe_runs_in_execution_working_dir() {
+        use crate::limits::ResourceLimits;
+        use crate::runtime::RuntimeContext;
+        use std::os::unix::fs::PermissionsExt;
+        use tokio_util::sync::CancellationToken;
+
+        let project = tempfile::tempdir().unwrap();
+        let local = project.path().join("node_modules/.bin/fake-tool");
+        std::fs::create_dir_all(local.parent().unwrap()).unwrap();
+        std::fs::write(&local, "#!/bin/sh\necho fake 2.0\n").unwrap();
+        std::fs::set_permissions(&local, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let probe = ToolProbe {
+            tool: "fake",
+            program: "sh",
+            args: &["-c", "./node_modules/.bin/fake-tool"],
+            local_bin: Some("node_modules/.bin/fake-tool"),
+            install_hint: "",
+        };
+        let cache = EnvironmentCache::in_memory();
+        assert!(!cache.status(&probe).await.available);
+
+        let execution = ToolExecution::new(ResourceLimits::default(), CancellationToken::new())
+            .with_runtime(RuntimeContext {
+                working_dir: Some(project.path().to_path_buf()),
+                ..RuntimeContext::default()
+            });
+        let status = execution.run(cache.status(&probe)).await.unwrap();
+        assert!(status.available && !status.cached);
+        assert_eq!(status.version.as_deref(), Some("fake 2.0"));
+        assert_eq!(status.fingerprint.files.len(), 2);
+    }
+}
@@ -14,13 +14,23 @@
  * limitations under the License.
  */
 
+pub mod diff;
+pub mod environment;
 pub mod error;
+pub mod limits;
+pub mod platform;
 pub mod result;
+pub mod runtime;
+pub mod sandbox;
 pub mod traits;
 pub mod types;
 
 // Re-export commonly used items for convenience
+pub use environment::{tool_available, EnvironmentCache, ToolProbe, ToolStatus};
 pub use error::{ActionError, ActionResult};
-pub use result::ToolResult;
+pub use limits::{LimitBreach, LimitedCommand, ResourceLimits, ToolExecution};
+pub use result::{Diagnostic, Severity, ToolResult};
+pub use runtime::RuntimeContext;
+pub use sandbox::{Sandbox, SandboxChange};
 pub use traits::{SafetyTool, TransformationTool, ValidationTool};
-pub use types::{ActionIntent, ActionType, SafetyLevel};
+pub use types::{ActionIntent, ActionType, SafetyLevel, ToolchainProfile};
@@ -0,0 +1,544 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Resource limits and cancellation for external tool processes.
+//!
+//! Every tool invocation runs inside a [`ToolExecution`] carrying the
+//! invocation's limits, wall-clock deadline and cancellation token. Commands
+//! started with [`LimitedCommand::limited_output`] inside that execution run
+//! in their own process group with a CPU-time rlimit, have their output
+//! capped, and are killed as a group when the deadline passes or the
+//! execution is cancelled. Whatever they printed before that is kept as the
+//! partial output of the invocation. They also get the execution's
+//! [`RuntimeContext`]. Outside an execution `limited_output` behaves like
+//! `Command::output`.
+
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::process::{Output, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::process::Command;
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+
+use crate::error::{ActionError, ActionResult};
+use crate::runtime::RuntimeContext;
+use crate::types::{ActionIntent, SafetyLevel};
+
+/// Key of the intent metadata object overriding the safety level defaults
+pub const RESOURCE_LIMITS_METADATA_KEY: &str = "resource_limits";
+
+/// How long output readers may keep draining after the process group is killed
+const DRAIN_GRACE: Duration = Duration::from_secs(1);
+
+/// Extra time a tool future gets past the deadline to stop its own commands
+/// and report their partial output before it is dropped
+const BACKSTOP_GRACE: Duration = Duration::from_secs(2);
+
+/// Limits applied to one tool invocation; `None` means unlimited
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResourceLimits {
+    /// CPU seconds per process, enforced with `RLIMIT_CPU` on Unix
+    pub cpu_time_secs: Option<u64>,
+
+    /// Wall-clock seconds for the whole invocation
+    pub wall_clock_secs: Option<u64>,
+
+    /// Bytes kept from each of stdout and stderr per process
+    pub max_output_bytes: Option<usize>,
+}
+
+impl ResourceLimits {
+    /// Defaults for a safety level; riskier intents get less room
+    pub fn for_safety_level(level: &SafetyLevel) -> Self {
+        let (wall_clock_secs, max_output_bytes) = match level {
+            SafetyLevel::Low => (600, 8 << 20),
+            SafetyLevel::Medium => (300, 4 << 20),
+            SafetyLevel::High => (180, 2 << 20),
+            SafetyLevel::Critical => (120, 1 << 20),
+        };
+        Self {
+            // Compilers and test runners use several cores
+            cpu_time_secs: Some(wall_clock_secs * 4),
+            wall_clock_secs: Some(wall_clock_secs),
+            max_output_bytes: Some(max_output_bytes),
+        }
+    }
+
+    /// Safety level defaults overridden by the intent's `resource_limits` metadata
+    pub fn for_intent(intent: &ActionIntent) -> ActionResult<Self> {
+        let defaults = Self::for_safety_level(&intent.safety_level);
+        let Some(value) = intent.metadata.get(RESOURCE_LIMITS_METADATA_KEY) else {
+            return Ok(defaults);
+        };
+        let overrides: Self = serde_json::from_value(value.clone()).map_err(|e| {
+            ActionError::Validation(format!(
+                "Invalid {} in intent {}: {}",
+                RESOURCE_LIMITS_METADATA_KEY, intent.id, e
+            ))
+        })?;
+        Ok(Self {
+            cpu_time_secs: overrides.cpu_time_secs.or(defaults.cpu_time_secs),
+            wall_clock_secs: overrides.wall_clock_secs.or(defaults.wall_clock_secs),
+            max_output_bytes: overrides.max_output_bytes.or(defaults.max_output_bytes),
+        })
+    }
+
+    pub fn wall_clock(&self) -> Option<Duration> {
+        self.wall_clock_secs.map(Duration::from_secs)
+    }
+}
+
+/// A limit a tool invocation ran into
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "limit", rename_all = "snake_case")]
+pub enum LimitBreach {
+    WallClock { limit_secs: u64 },
+    CpuTime { limit_secs: u64 },
+    OutputSize { limit_bytes: usize },
+    Cancelled,
+}
+
+impl LimitBreach {
+    /// Whether the invocation was stopped rather than just trimmed
+    pub fn is_fatal(&self) -> bool {
+        !matches!(self, LimitBreach::OutputSize { .. })
+    }
+}
+
+impl std::fmt::Display for LimitBreach {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LimitBreach::WallClock { limit_secs } => {
+                write!(f, "wall-clock limit of {}s exceeded", limit_secs)
+            }
+            LimitBreach::CpuTime { limit_secs } => {
+                write!(f, "CPU time limit of {}s exceeded", limit_secs)
+            }
+            LimitBreach::OutputSize { limit_bytes } => {
+                write!(f, "output truncated at {} bytes", limit_bytes)
+            }
+            LimitBreach::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+tokio::task_local! {
+    static EXECUTION: ToolExecution;
+}
+
+struct ExecutionState {
+    limits: ResourceLimits,
+    deadline: Option<Instant>,
+    cancel: CancellationToken,
+    breaches: Mutex<Vec<LimitBreach>>,
+    partial_stdout: Mutex<String>,
+    partial_stderr: Mutex<String>,
+    process_groups: Mutex<Vec<u32>>,
+}
+
+/// Limits, deadline and cancellation shared by the commands of one tool invocation
+#[derive(Clone)]
+pub struct ToolExecution {
+    state: Arc<ExecutionState>,
+    runtime: Arc<RuntimeContext>,
+}
+
+impl ToolExecution {
+    /// Start an execution; the wall-clock deadline starts counting now
+    pub fn new(limits: ResourceLimits, cancel: CancellationToken) -> Self {
+        let deadline = limits.wall_clock().map(|limit| Instant::now() + limit);
+        Self {
+            state: Arc::new(ExecutionState {
+                limits,
+                deadline,
+                cancel,
+                breaches: Mutex::new(Vec::new()),
+                partial_stdout: Mutex::new(String::new()),
+                partial_stderr: Mutex::new(String::new()),
+                process_groups: Mutex::new(Vec::new()),
+            }),
+            runtime: Arc::new(RuntimeContext::default()),
+        }
+    }
+
+    /// Inject `runtime` into the commands started in this execution
+    pub fn with_runtime(mut self, runtime: RuntimeContext) -> Self {
+        self.runtime = Arc::new(runtime);
+        self
+    }
+
+    pub fn runtime(&self) -> &RuntimeContext {
+        &self.runtime
+    }
+
+    /// The execution the current task runs in, if any
+    pub fn current() -> Option<Self> {
+        EXECUTION.try_with(|execution| execution.clone()).ok()
+    }
+
+    pub fn limits(&self) -> &ResourceLimits {
+        &self.state.limits
+    }
+
+    pub fn cancel_token(&self) -> &CancellationToken {
+        &self.state.cancel
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancel.is_cancelled()
+    }
+
+    /// Time left before the deadline
+    pub fn remaining(&self) -> Option<Duration> {
+        self.state
+            .deadline
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Run a tool future with this execution visible to the commands it starts.
+    ///
+    /// Limited commands stop themselves at the deadline or on cancellation.
+    /// The future is dropped a grace period later, so tools stuck outside a
+    /// limited command still stop; `None` is returned in that case.
+    pub async fn run<F: Future>(&self, future: F) -> Option<F::Output> {
+        let scoped = EXECUTION.scope(self.clone(), future);
+        let outcome = tokio::select! {
+            output = scoped => Some(output),
+            _ = async {
+                self.expired().await;
+                tokio::time::sleep(BACKSTOP_GRACE).await;
+            } => {
+                self.record(self.deadline_breach());
+                None
+            }
+            _ = async {
+                self.state.cancel.cancelled().await;
+                tokio::time::sleep(BACKSTOP_GRACE).await;
+            } => {
+                self.record(LimitBreach::Cancelled);
+                None
+            }
+        };
+        if outcome.is_none() {
+            self.kill_all();
+        }
+        outcome
+    }
+
+    /// Limits the invocation ran into, in order
+    pub fn breaches(&self) -> Vec<LimitBreach> {
+        self.state.breaches.lock().unwrap().clone()
+    }
+
+    /// Output of the commands that were stopped by a limit
+    pub fn partial_output(&self) -> (String, String) {
+        (
+            self.state.partial_stdout.lock().unwrap().clone(),
+            self.state.partial_stderr.lock().unwrap().clone(),
+        )
+    }
+
+    /// Kill every process group still running for this execution
+    pub fn kill_all(&self) {
+        for pgid in self.state.process_groups.lock().unwrap().drain(..) {
+            kill_process_group(pgid);
+        }
+    }
+
+    fn record(&self, breach: LimitBreach) {
+        let mut breaches = self.state.breaches.lock().unwrap();
+        if !breaches.contains(&breach) {
+            breaches.push(breach);
+        }
+    }
+
+    fn deadline_breach(&self) -> LimitBreach {
+        LimitBreach::WallClock {
+            limit_secs: self.state.limits.wall_clock_secs.unwrap_or_default(),
+        }
+    }
+
+    async fn expired(&self) {
+        match self.state.deadline {
+            Some(deadline) => tokio::time::sleep_until(deadline).await,
+            None => std::future::pending().await,
+        }
+    }
+
+    async fn output(&self, command: &mut Command) -> io::Result<Output> {
+        if self.is_cancelled() {
+            self.record(LimitBreach::Cancelled);
+            return Err(io::Error::new(io::ErrorKind::Interrupted, "tool cancelled"));
+        }
+
+        self.runtime.apply(command);
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        #[cfg(unix)]
+        {
+            command.process_group(0);
+            if let Some(cpu) = self.state.limits.cpu_time_secs {
+                // SAFETY: setrlimit is async-signal-safe and touches no parent state
+                unsafe {
+                    command.pre_exec(move || {
+                        nix::sys::resource::setrlimit(
+                            nix::sys::resource::Resource::RLIMIT_CPU,
+                            cpu,
+                            cpu + 1,
+                        )
+                        .map_err(io::Error::from)
+                    });
+                }
+            }
+        }
+
+        let mut child = command.spawn()?;
+        let pid = child.id();
+        if let Some(pid) = pid {
+            self.state.process_groups.lock().unwrap().push(pid);
+        }
+        let max_bytes = self.state.limits.max_output_bytes;
+        let stdout = tokio::spawn(read_capped(child.stdout.take(), max_bytes));
+        let stderr = tokio::spawn(read_capped(child.stderr.take(), max_bytes));
+
+        let stopped_by = tokio::select! {
+            status = child.wait() => Ok(status?),
+            _ = self.expired() => Err(self.deadline_breach()),
+            _ = self.state.cancel.cancelled() => Err(LimitBreach::Cancelled),
+        };
+        if let Some(pid) = pid {
+            self.state
+                .process_groups
+                .lock()
+                .unwrap()
+                .retain(|p| *p != pid);
+        }
+        let mut stopped = stopped_by.is_err();
+        let status = match stopped_by {
+            Ok(status) => status,
+            Err(breach) => {
+                if let Some(pid) = pid {
+                    kill_process_group(pid);
+                }
+                let _ = child.start_kill();
+                self.record(breach);
+                child.wait().await?
+            }
+        };
+
+        let drain = |handle: tokio::task::JoinHandle<io::Result<(Vec<u8>, bool)>>| async move {
+            match tokio::time::timeout(DRAIN_GRACE, handle).await {
+                Ok(Ok(Ok(read))) => read,
+                _ => (Vec::new(), false),
+            }
+        };
+        let (stdout, stdout_truncated) = drain(stdout).await;
+        let (stderr, stderr_truncated) = drain(stderr).await;
+        if stdout_truncated || stderr_truncated {
+            self.record(LimitBreach::OutputSize {
+                limit_bytes: max_bytes.unwrap_or_default(),
+            });
+        }
+        if let Some(limit_secs) = self.state.limits.cpu_time_secs {
+            if killed_for_cpu(&status) {
+                self.record(LimitBreach::CpuTime { limit_secs });
+                stopped = true;
+            }
+        }
+        if stopped {
+            self.state
+                .partial_stdout
+                .lock()
+                .unwrap()
+                .push_str(&String::from_utf8_lossy(&stdout));
+            self.state
+                .partial_stderr
+                .lock()
+                .unwrap()
+                .push_str(&String::from_utf8_lossy(&stderr));
+        }
+
+        Ok(Output {
+            status,
+            stdout,
+            stderr,
+        })
+    }
+}
+
+/// Read a pipe to the end, keeping at most `max_bytes`; the rest is drained so
+/// the child never blocks on a full pipe
+async fn read_capped<R: AsyncRead + Unpin>(
+    reader: Option<R>,
+    max_bytes: Option<usize>,
+) -> io::Result<(Vec<u8>, bool)> {
+    let Some(mut reader) = reader else {
+        return Ok((Vec::new(), false));
+    };
+    let mut kept = Vec::new();
+    let mut truncated = false;
+    let mut chunk = [0u8; 8192];
+    loop {
+        let read = reader.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok((kept, truncated));
+        }
+        let room = max_bytes.map_or(read, |max| max.saturating_sub(kept.len()).min(read));
+        kept.extend_from_slice(&chunk[..room]);
+        truncated |= room < read;
+    }
+}
+
+#[cfg(unix)]
+fn kill_process_group(pgid: u32) {
+    use nix::sys::signal::{killpg, Signal};
+    use nix::unistd::Pid;
+    // The group may already be gone
+    let _ = killpg(Pid::from_raw(pgid as i32), Signal::SIGKILL);
+}
+
+#[cfg(not(unix))]
+fn kill_process_group(_pgid: u32) {
+    // Without process groups the child itself is killed through kill_on_drop
+}
+
+#[cfg(unix)]
+fn killed_for_cpu(status: &std::process::ExitStatus) -> bool {
+    use std::os::unix::process::ExitStatusExt;
+    // SIGXCPU at the soft limit, SIGKILL from the kernel at the hard limit
+    matches!(status.signal(), Some(signal) if signal == nix::sys::signal::Signal::SIGXCPU as i32)
+}
+
+#[cfg(not(unix))]
+fn killed_for_cpu(_status: &std::process::ExitStatus) -> bool {
+    false
+}
+
+/// `Command::output` that honours the current [`ToolExecution`]
+pub trait LimitedCommand {
+    fn limited_output(&mut self) -> Pin<Box<dyn Future<Output = io::Result<Output>> + Send + '_>>;
+}
+
+impl LimitedCommand for Command {
+    fn limited_output(&mut self) -> Pin<Box<dyn Future<Output = io::Result<Output>> + Send + '_>> {
+        Box::pin(async move {
+            match ToolExecution::current() {
+                Some(execution) => execution.output(self).await,
+                None => self.output().await,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ActionType;
+
+    fn intent(level: SafetyLevel) -> ActionIntent {
+        ActionIntent::new("limits", ActionType::Test, "Limits", vec![], level)
+    }
+
+    #[test]
+    fn test_metadata_overrides_safety_level_defaults() {
+        let mut intent = intent(SafetyLevel::High);
+        assert_eq!(
+            ResourceLimits::for_intent(&intent).unwrap(),
+            ResourceLimits::for_safety_level(&SafetyLevel::High)
+        );
+
+        intent.metadata = serde_json::json!({
+            "resource_limits": { "wall_clock_secs": 5, "max_output_bytes": 64 }
+        });
+        let limits = ResourceLimits::for_intent(&intent).unwrap();
+        assert_eq!(limits.wall_clock_secs, Some(5));
+        assert_eq!(limits.max_output_bytes, Some(64));
+        assert_eq!(
+            limits.cpu_time_secs,
+            ResourceLimits::for_safety_level(&SafetyLevel::High).cpu_time_secs
+        );
+
+        intent.metadata = serde_json::json!({ "resource_limits": { "wall_clock_secs": "soon" } });
+        assert!(ResourceLimits::for_intent(&intent).is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stuck_command_is_killed_with_partial_output() {
+        let execution = ToolExecution::new(
+            ResourceLimits {
+                wall_clock_secs: Some(1),
+                ..ResourceLimits::default()
+            },
+            CancellationToken::new(),
+        );
+
+        let started = std::time::Instant::now();
+        let output = execution
+            .run(async {
+                Command::new("sh")
+                    .args(["-c", "echo started; sleep 30"])
+                    .limited_output()
+                    .await
+            })
+            .await;
+
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert!(output.is_none() || !output.unwrap().unwrap().status.success());
+        assert!(execution
+            .breaches()
+            .contains(&LimitBreach::WallClock { limit_secs: 1 }));
+        assert!(execution.partial_output().0.contains("started"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_output_is_capped() {
+        let execution = ToolExecution::new(
+            ResourceLimits {
+                max_output_bytes: Some(4),
+                ..ResourceLimits::default()
+            },
+            CancellationToken::new(),
+        );
+
+        let output = execution
+            .run(async {
+                Command::new("sh")
+                    .args(["-c", "echo 0123456789"])
+                    .limited_output()
+                    .await
+            })
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(output.stdout, b"0123");
+        assert_eq!(
+            execution.breaches(),
+            vec![LimitBreach::OutputSize { limit_bytes: 4 }]
+        );
+    }
+}
@@ -0,0 +1,265 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Platform abstraction for launching external tools and comparing paths.
+//!
+//! Action tools shell out to `npx`, `python`, `cargo` and friends. On Windows
+//! most of those are `.cmd` shims found through `PATHEXT`, Python is usually
+//! `python` or the `py` launcher rather than `python3`, and paths arrive with
+//! backslashes. Tools should build commands and compare paths through this
+//! module instead of hardcoding POSIX conventions.
+
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+use tokio::sync::OnceCell;
+
+/// Extensions tried when `PATHEXT` is not set
+const DEFAULT_PATHEXT: &str = ".COM;.EXE;.BAT;.CMD";
+
+/// Build a command for an external tool.
+///
+/// On Windows the program is resolved through `PATH` and `PATHEXT`, so
+/// `npx` finds `npx.cmd`. Batch shims are launched by the standard library
+/// through `cmd.exe /c` with arguments escaped for cmd's parser. Elsewhere
+/// the program is spawned as given.
+pub fn tool_command(program: &str) -> Command {
+    if cfg!(windows) {
+        if let Some(resolved) = resolve_program(program) {
+            return Command::new(resolved);
+        }
+    }
+    Command::new(program)
+}
+
+/// Locate `program` on `PATH`, honouring `PATHEXT` on Windows
+pub fn resolve_program(program: &str) -> Option<PathBuf> {
+    let path_var = std::env::var_os("PATH")?;
+    let extensions = if cfg!(windows) {
+        let pathext = std::env::var("PATHEXT").unwrap_or_else(|_| DEFAULT_PATHEXT.to_string());
+        pathext
+            .split(';')
+            .filter(|ext| !ext.is_empty())
+            .map(str::to_string)
+            .collect()
+    } else {
+        Vec::new()
+    };
+    resolve_in(program, &path_var, &extensions)
+}
+
+/// Resolve `program` against an explicit search path and extension list
+fn resolve_in(program: &str, path_var: &OsStr, extensions: &[String]) -> Option<PathBuf> {
+    let candidates = |dir: &Path| -> Vec<PathBuf> {
+        let base = dir.join(program);
+        let has_extension = Path::new(program).extension().is_some_and(|ext| {
+            extensions.iter().any(|known| {
+                known
+                    .trim_start_matches('.')
+                    .eq_ignore_ascii_case(&ext.to_string_lossy())
+            })
+        });
+        if extensions.is_empty() || has_extension {
+            return vec![base];
+        }
+        extensions
+            .iter()
+            .map(|ext| {
+                let mut name = OsString::from(program);
+                name.push(ext.to_ascii_lowercase());
+                dir.join(name)
+            })
+            .collect()
+    };
+
+    // Programs given with a directory component are not searched for
+    if program.contains('/') || program.contains('\\') {
+        return candidates(Path::new(""))
+            .into_iter()
+            .find(|candidate| candidate.is_file());
+    }
+
+    std::env::split_paths(path_var)
+        .flat_map(|dir| candidates(&dir))
+        .find(|candidate| candidate.is_file())
+}
+
+/// A Python 3 interpreter and the arguments needed to select it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PythonInterpreter {
+    pub program: String,
+    pub args: Vec<String>,
+}
+
+impl PythonInterpreter {
+    /// Build a command running this interpreter
+    pub fn command(&self) -> Command {
+        let mut command = tool_command(&self.program);
+        command.args(&self.args);
+        command
+    }
+}
+
+/// Interpreters tried in order of preference for the current platform
+pub fn python_candidates() -> Vec<PythonInterpreter> {
+    let candidate = |program: &str, args: &[&str]| PythonInterpreter {
+        program: program.to_string(),
+        args: args.iter().map(|arg| arg.to_string()).collect(),
+    };
+    if cfg!(windows) {
+        // `python3` on Windows is often the Microsoft Store alias stub, so it
+        // goes last behind the real interpreter and the `py` launcher
+        vec![
+            candidate("python", &[]),
+            candidate("py", &["-3"]),
+            candidate("python3", &[]),
+        ]
+    } else {
+        vec![candidate("python3", &[]), candidate("python", &[])]
+    }
+}
+
+/// Detect the Python 3 interpreter once per process
+pub async fn python_interpreter() -> Option<&'static PythonInterpreter> {
+    static INTERPRETER: OnceCell<Option<PythonInterpreter>> = OnceCell::const_new();
+    INTERPRETER
+        .get_or_init(|| async {
+            for candidate in python_candidates() {
+                let output = candidate.command().arg("--version").output().await;
+                if let Ok(output) = output {
+                    // Python 2 prints its version to stderr
+                    let version = format!(
+                        "{}{}",
+                        String::from_utf8_lossy(&output.stdout),
+                        String::from_utf8_lossy(&output.stderr)
+                    );
+                    if output.status.success() && version.contains("Python 3") {
+                        return Some(candidate);
+                    }
+                }
+            }
+            None
+        })
+        .await
+        .as_ref()
+}
+
+/// Command running the detected Python 3 interpreter, falling back to the
+/// platform's preferred name when none was found so the spawn error is clear
+pub async fn python_command() -> Command {
+    match python_interpreter().await {
+        Some(interpreter) => interpreter.command(),
+        None => python_candidates()[0].command(),
+    }
+}
+
+/// Normalize a path for comparison: forward slashes, no `.` components and
+/// no duplicate or trailing separators
+pub fn normalize_path(path: &str) -> String {
+    let unified = path.replace('\\', "/");
+    let absolute = unified.starts_with('/');
+    let parts: Vec<&str> = unified
+        .split('/')
+        .filter(|part| !part.is_empty() && *part != ".")
+        .collect();
+    let joined = parts.join("/");
+    if absolute {
+        format!("/{}", joined)
+    } else if joined.is_empty() {
+        ".".to_string()
+    } else {
+        joined
+    }
+}
+
+/// `path` relative to `root`, normalized; paths outside `root` are returned
+/// normalized as-is
+pub fn relative_path(path: &Path, root: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    normalize_path(&relative.to_string_lossy())
+}
+
+/// Whether `path` is `scope` or lies beneath it.
+///
+/// Separators are normalized first, and the comparison ignores case on
+/// Windows where the file system does.
+pub fn path_in_scope(path: &str, scope: &str) -> bool {
+    let (path, scope) = if cfg!(windows) {
+        (
+            normalize_path(path).to_lowercase(),
+            normalize_path(scope).to_lowercase(),
+        )
+    } else {
+        (normalize_path(path), normalize_path(scope))
+    };
+    scope == "."
+        || path == scope
+        || path
+            .strip_prefix(&scope)
+            .is_some_and(|rest| rest.starts_with('/') || scope.ends_with('/'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_and_scope_matching() {
+        assert_eq!(normalize_path("src\\lib.rs"), "src/lib.rs");
+        assert_eq!(
+            normalize_path("./src//nested/./mod.rs/"),
+            "src/nested/mod.rs"
+        );
+        assert_eq!(normalize_path("/repo/src"), "/repo/src");
+        assert_eq!(normalize_path("C:\\repo\\src"), "C:/repo/src");
+        assert_eq!(normalize_path("./"), ".");
+
+        assert_eq!(
+            relative_path(Path::new("/repo/src/lib.rs"), Path::new("/repo")),
+            "src/lib.rs"
+        );
+
+        assert!(path_in_scope("src\\api\\handler.rs", "src/api"));
+        assert!(path_in_scope("./src/api", "src/api/"));
+        assert!(path_in_scope("anything", "."));
+        assert!(!path_in_scope("src/api-v2/handler.rs", "src/api"));
+        assert!(!path_in_scope("src", "src/api"));
+    }
+
+    #[test]
+    fn test_resolve_with_extensions() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("npx.cmd"), "@echo off").unwrap();
+        std::fs::write(dir.path().join("tool"), "").unwrap();
+        let path_var = std::env::join_paths([dir.path()]).unwrap();
+        let windows_exts = vec![".EXE".to_string(), ".CMD".to_string()];
+
+        assert_eq!(
+            resolve_in("npx", &path_var, &windows_exts),
+            Some(dir.path().join("npx.cmd"))
+        );
+        assert_eq!(
+            resolve_in("npx.cmd", &path_var, &windows_exts),
+            Some(dir.path().join("npx.cmd"))
+        );
+        assert_eq!(resolve_in("npx", &path_var, &[]), None);
+        assert_eq!(
+            resolve_in("tool", &path_var, &[]),
+            Some(dir.path().join("tool"))
+        );
+        assert_eq!(resolve_in("missing", &path_var, &windows_exts), None);
+    }
+}
@@ -14,6 +14,8 @@
  * limitations under the License.
  */
 
+use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::time::Duration;
 
 /// Result from tool execution
@@ -25,4 +27,141 @@ pub struct ToolResult {
     pub errors: Vec<String>,
     pub warnings: Vec<String>,
     pub duration: Duration,
+    /// Served from the result cache instead of running the tool
+    pub cached: bool,
+    /// Findings with their location and rule, for tools that report them.
+    /// `errors` and `warnings` still carry every finding as text.
+    pub diagnostics: Vec<Diagnostic>,
+    /// Names of the tests that failed, for test runners that report tests
+    /// individually
+    pub failed_tests: Vec<String>,
+}
+
+impl ToolResult {
+    /// Diagnostics of one severity
+    pub fn diagnostics_of(&self, severity: Severity) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(move |diagnostic| diagnostic.severity == severity)
+    }
+}
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Error,
+    Warning,
+    /// Notes, hints and suggestions
+    Info,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Info => write!(f, "info"),
+        }
+    }
+}
+
+/// A finding reported by a tool
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// File the finding is in, as the tool reported it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// 1-based line
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    /// 1-based column
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<u32>,
+    pub severity: Severity,
+    /// Rule or error code, e.g. `E0308`, `no-unused-vars` or `TS2322`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    pub message: String,
+    pub tool: String,
+}
+
+impl Diagnostic {
+    pub fn new(tool: impl Into<String>, severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            file: None,
+            line: None,
+            column: None,
+            severity,
+            code: None,
+            message: message.into(),
+            tool: tool.into(),
+        }
+    }
+
+    pub fn error(tool: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(tool, Severity::Error, message)
+    }
+
+    pub fn warning(tool: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(tool, Severity::Warning, message)
+    }
+
+    /// Place the diagnostic in a file, at a line and column when known
+    pub fn at(mut self, file: impl Into<String>, line: Option<u32>, column: Option<u32>) -> Self {
+        self.file = Some(file.into());
+        self.line = line;
+        self.column = column;
+        self
+    }
+
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+}
+
+/// `file:line:column: [code] message`, leaving out what is unknown
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}", file)?;
+            if let Some(line) = self.line {
+                write!(f, ":{}", line)?;
+                if let Some(column) = self.column {
+                    write!(f, ":{}", column)?;
+                }
+            }
+            write!(f, ": ")?;
+        }
+        if let Some(code) = &self.code {
+            write!(f, "[{}] ", code)?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnostic_display_and_serialization() {
+        let located = Diagnostic::error("typescript", "Type 'string' is not assignable")
+            .at("src/app.ts", Some(12), Some(5))
+            .with_code("TS2322");
+        assert_eq!(
+            located.to_string(),
+            "src/app.ts:12:5: [TS2322] Type 'string' is not assignable"
+        );
+        assert_eq!(
+            Diagnostic::warning("cargo", "unused manifest key").to_string(),
+            "unused manifest key"
+        );
+
+        let json = serde_json::to_value(&located).unwrap();
+        assert_eq!(json["severity"], "error");
+        assert_eq!(json["code"], "TS2322");
+        assert_eq!(serde_json::from_value::<Diagnostic>(json).unwrap(), located);
+    }
 }
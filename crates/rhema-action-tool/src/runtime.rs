@@ -0,0 +1,134 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Environment and working directory injected into the processes a tool
+//! spawns.
+//!
+//! The action layer resolves the `runtime` declared by an intent's scopes and
+//! metadata, checks it against the repository allowlist, and hands the result
+//! to the [`ToolExecution`](crate::limits::ToolExecution) the tool runs in.
+//! Every command started with
+//! [`LimitedCommand::limited_output`](crate::limits::LimitedCommand) inside
+//! that execution then gets the variables, and the working directory unless
+//! the tool chose its own. Values that came from secrets are redacted from
+//! what the tool reports.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use tokio::process::Command;
+
+use crate::result::ToolResult;
+
+/// Key of the intent metadata object declaring the tool runtime
+pub const RUNTIME_METADATA_KEY: &str = "runtime";
+
+/// Replacement for secret values in tool output
+const REDACTED: &str = "[REDACTED]";
+
+/// Resolved runtime of one tool invocation
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuntimeContext {
+    pub env: BTreeMap<String, String>,
+    pub working_dir: Option<PathBuf>,
+    /// Values that must not appear in tool output
+    pub secrets: Vec<String>,
+}
+
+impl RuntimeContext {
+    pub fn is_empty(&self) -> bool {
+        self.env.is_empty() && self.working_dir.is_none()
+    }
+
+    /// Set the variables on `command`, and the working directory when the
+    /// tool did not pick one
+    pub fn apply(&self, command: &mut Command) {
+        command.envs(&self.env);
+        if let Some(dir) = &self.working_dir {
+            if command.as_std().get_current_dir().is_none() {
+                command.current_dir(dir);
+            }
+        }
+    }
+
+    /// `text` with every secret value replaced
+    pub fn redact(&self, text: &str) -> String {
+        self.secrets.iter().fold(text.to_string(), |text, secret| {
+            text.replace(secret, REDACTED)
+        })
+    }
+
+    /// Redact secrets from everything a tool reported
+    pub fn redact_result(&self, result: &mut ToolResult) {
+        if self.secrets.is_empty() {
+            return;
+        }
+        result.output = self.redact(&result.output);
+        for text in result
+            .changes
+            .iter_mut()
+            .chain(result.errors.iter_mut())
+            .chain(result.warnings.iter_mut())
+        {
+            *text = self.redact(text);
+        }
+        for diagnostic in &mut result.diagnostics {
+            diagnostic.message = self.redact(&diagnostic.message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::limits::{LimitedCommand, ResourceLimits, ToolExecution};
+    use tokio_util::sync::CancellationToken;
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_execution_injects_runtime_and_redacts_secrets() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let runtime = RuntimeContext {
+            env: BTreeMap::from([(
+                "DATABASE_URL".to_string(),
+                "postgres://s3cret@db".to_string(),
+            )]),
+            working_dir: Some(dir.path().to_path_buf()),
+            secrets: vec!["s3cret".to_string()],
+        };
+        let execution = ToolExecution::new(ResourceLimits::default(), CancellationToken::new())
+            .with_runtime(runtime.clone());
+
+        let output = execution
+            .run(async {
+                let mut command = Command::new("sh");
+                command.args(["-c", "echo \"$DATABASE_URL\"; pwd"]);
+                command.limited_output().await.unwrap()
+            })
+            .await
+            .unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let mut lines = stdout.lines();
+        assert_eq!(lines.next(), Some("postgres://s3cret@db"));
+        assert_eq!(
+            std::fs::canonicalize(lines.next().unwrap()).unwrap(),
+            std::fs::canonicalize(dir.path()).unwrap()
+        );
+        assert_eq!(
+            runtime.redact(&stdout).lines().next(),
+            Some("postgres://[REDACTED]@db")
+        );
+    }
+}
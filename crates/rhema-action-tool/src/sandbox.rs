@@ -0,0 +1,497 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Sandboxed execution of transformation tools.
+//!
+//! Transformation tools such as jscodeshift, comby and prettier rewrite files
+//! in place. In a [`Sandbox`] they rewrite copies instead: the files of an
+//! intent's scope are copied into a temporary workspace under the same
+//! relative paths, and [`Sandbox::rebase_intent`] points the intent at the
+//! copies. [`Sandbox::changes`] compares the copies with the files they were
+//! taken from. The caller reviews the unified diff of those changes, runs its
+//! safety checks, and only then writes them to the real tree with
+//! [`Sandbox::apply`]. Intents opt in with `"sandbox": true` in their metadata.
+//!
+//! [`preview`] uses the same machinery for dry runs: the tool runs against a
+//! sandbox, its result reports the would-be changes as unified diffs, and the
+//! sandbox is dropped without applying anything.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Component, Path, PathBuf};
+
+use crate::diff;
+use crate::error::{ActionError, ActionResult};
+use crate::result::ToolResult;
+use crate::traits::TransformationTool;
+use crate::types::ActionIntent;
+
+/// Key of the intent metadata flag requesting a sandboxed run
+pub const SANDBOX_METADATA_KEY: &str = "sandbox";
+
+/// Directories that are never copied into a sandbox
+const SKIPPED_DIRS: [&str; 3] = [".git", "node_modules", "target"];
+
+/// Whether the intent asked for its tools to run in a sandbox
+pub fn requested(intent: &ActionIntent) -> bool {
+    intent
+        .metadata
+        .get(SANDBOX_METADATA_KEY)
+        .and_then(serde_json::Value::as_bool)
+        .unwrap_or(false)
+}
+
+/// A file a sandboxed run changed; `None` stands for a missing file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SandboxChange {
+    /// Path relative to the sandbox base, with `/` separators
+    pub path: String,
+    pub before: Option<Vec<u8>>,
+    pub after: Option<Vec<u8>>,
+}
+
+impl SandboxChange {
+    /// Unified diff of the change; binary files are only named
+    pub fn unified_diff(&self, context_lines: usize) -> String {
+        let before = self.before.as_deref().map(std::str::from_utf8).transpose();
+        let after = self.after.as_deref().map(std::str::from_utf8).transpose();
+        match (before, after) {
+            (Ok(before), Ok(after)) => diff::unified_diff(&self.path, before, after, context_lines),
+            _ => format!("Binary files a/{0} and b/{0} differ\n", self.path),
+        }
+    }
+}
+
+/// Unified diff of all changes of a sandboxed run
+pub fn render_diff(changes: &[SandboxChange]) -> String {
+    changes
+        .iter()
+        .map(|change| change.unified_diff(3))
+        .collect()
+}
+
+/// Run `tool` against a sandbox copy of the intent's scope, relative to
+/// `base`, and return its result with `changes` holding one unified diff per
+/// file it would change. Nothing outside the sandbox is written, and sandbox
+/// paths in the tool's messages are made relative again.
+pub async fn preview<T>(tool: &T, intent: &ActionIntent, base: &Path) -> ActionResult<ToolResult>
+where
+    T: TransformationTool + ?Sized,
+{
+    let sandbox = Sandbox::create(intent, base, None)?;
+    let mut rebased = sandbox.rebase_intent(intent)?;
+    rebased.dry_run = false;
+
+    let mut result = tool.execute(&rebased).await?;
+    result.changes = sandbox
+        .changes()?
+        .iter()
+        .map(|change| change.unified_diff(3))
+        .collect();
+
+    let prefix = format!(
+        "{}{}",
+        sandbox.workspace().display(),
+        std::path::MAIN_SEPARATOR
+    );
+    let unsandbox = |text: &mut String| {
+        if text.contains(&prefix) {
+            *text = text.replace(&prefix, "");
+        }
+    };
+    unsandbox(&mut result.output);
+    result.errors.iter_mut().for_each(unsandbox);
+    result.warnings.iter_mut().for_each(unsandbox);
+    result
+        .diagnostics
+        .iter_mut()
+        .filter_map(|diagnostic| diagnostic.file.as_mut())
+        .for_each(unsandbox);
+    Ok(result)
+}
+
+/// Temporary copy of an intent's scope that tools run against
+#[derive(Debug)]
+pub struct Sandbox {
+    /// Directory the intent's scope paths are relative to
+    base: PathBuf,
+    workspace: PathBuf,
+    /// Scope entries relative to `base`
+    roots: Vec<PathBuf>,
+    /// Content of every copied file when the sandbox was created
+    originals: BTreeMap<String, Vec<u8>>,
+    keep: bool,
+}
+
+impl Sandbox {
+    /// Copy the intent's scope, relative to `base`, into a new workspace
+    /// under `root` or the system temp directory
+    pub fn create(intent: &ActionIntent, base: &Path, root: Option<&Path>) -> ActionResult<Self> {
+        let roots = intent
+            .scope
+            .iter()
+            .map(|target| relative_to(base, target))
+            .collect::<ActionResult<Vec<_>>>()?;
+
+        let root = root.map_or_else(std::env::temp_dir, Path::to_path_buf);
+        let workspace = root.join(format!(
+            "rhema-sandbox-{}-{}",
+            sanitize(&intent.id),
+            uuid::Uuid::new_v4().simple()
+        ));
+        std::fs::create_dir_all(&workspace)?;
+
+        let mut sandbox = Self {
+            base: base.to_path_buf(),
+            workspace,
+            roots,
+            originals: BTreeMap::new(),
+            keep: false,
+        };
+        sandbox.originals = collect_files(&sandbox.base, &sandbox.roots)?;
+        for (path, content) in &sandbox.originals {
+            let target = sandbox.workspace.join(path);
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(target, content)?;
+        }
+        Ok(sandbox)
+    }
+
+    /// Directory the tools run in
+    pub fn workspace(&self) -> &Path {
+        &self.workspace
+    }
+
+    /// Keep the workspace on disk when the sandbox is dropped
+    pub fn keep(&mut self) {
+        self.keep = true;
+    }
+
+    /// The intent with its scope pointing at the sandbox copies
+    pub fn rebase_intent(&self, intent: &ActionIntent) -> ActionResult<ActionIntent> {
+        let mut rebased = intent.clone();
+        rebased.scope = intent
+            .scope
+            .iter()
+            .map(|target| {
+                let relative = relative_to(&self.base, target)?;
+                Ok(self.workspace.join(relative).to_string_lossy().to_string())
+            })
+            .collect::<ActionResult<_>>()?;
+        Ok(rebased)
+    }
+
+    /// Files the tools added, modified or deleted in the sandbox
+    pub fn changes(&self) -> ActionResult<Vec<SandboxChange>> {
+        let current = collect_files(&self.workspace, &self.roots)?;
+        let paths: BTreeSet<&String> = self.originals.keys().chain(current.keys()).collect();
+        Ok(paths
+            .into_iter()
+            .filter_map(|path| {
+                let before = self.originals.get(path);
+                let after = current.get(path);
+                (before != after).then(|| SandboxChange {
+                    path: path.clone(),
+                    before: before.cloned(),
+                    after: after.cloned(),
+                })
+            })
+            .collect())
+    }
+
+    /// Write approved changes to the real tree. Every file must still match
+    /// the content the sandbox was created from; nothing is written
+    /// otherwise. Returns the changed paths.
+    pub fn apply(&self, changes: &[SandboxChange]) -> ActionResult<Vec<String>> {
+        for change in changes {
+            let target = self.base.join(&change.path);
+            let current = match std::fs::read(&target) {
+                Ok(content) => Some(content),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+                Err(e) => return Err(e.into()),
+            };
+            if current != change.before {
+                return Err(ActionError::Validation(format!(
+                    "{} changed since the sandbox was created; run the intent again",
+                    change.path
+                )));
+            }
+        }
+
+        for change in changes {
+            let target = self.base.join(&change.path);
+            match &change.after {
+                Some(content) => {
+                    if let Some(parent) = target.parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                    std::fs::write(&target, content)?;
+                }
+                None => std::fs::remove_file(&target)?,
+            }
+        }
+        Ok(changes.iter().map(|change| change.path.clone()).collect())
+    }
+}
+
+impl Drop for Sandbox {
+    fn drop(&mut self) {
+        if !self.keep {
+            let _ = std::fs::remove_dir_all(&self.workspace);
+        }
+    }
+}
+
+/// `target` relative to `base`; scope entries may not leave `base`
+fn relative_to(base: &Path, target: &str) -> ActionResult<PathBuf> {
+    let path = Path::new(target);
+    let relative = if path.is_absolute() {
+        path.strip_prefix(base)
+            .map(Path::to_path_buf)
+            .map_err(|_| {
+                ActionError::Validation(format!(
+                    "Cannot sandbox {}: it is outside {}",
+                    target,
+                    base.display()
+                ))
+            })?
+    } else {
+        path.to_path_buf()
+    };
+    if relative
+        .components()
+        .any(|component| matches!(component, Component::ParentDir))
+    {
+        return Err(ActionError::Validation(format!(
+            "Cannot sandbox {}: it is outside {}",
+            target,
+            base.display()
+        )));
+    }
+    Ok(relative)
+}
+
+/// Files under each of `roots` in `dir`, keyed by path relative to `dir`
+fn collect_files(dir: &Path, roots: &[PathBuf]) -> ActionResult<BTreeMap<String, Vec<u8>>> {
+    let mut files = BTreeMap::new();
+    for root in roots {
+        walk(dir, &dir.join(root), &mut files)?;
+    }
+    Ok(files)
+}
+
+fn walk(dir: &Path, path: &Path, files: &mut BTreeMap<String, Vec<u8>>) -> ActionResult<()> {
+    let metadata = match std::fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+    if metadata.is_dir() {
+        let skipped = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| SKIPPED_DIRS.contains(&name));
+        if skipped || path.is_symlink() {
+            return Ok(());
+        }
+        for entry in std::fs::read_dir(path)? {
+            walk(dir, &entry?.path(), files)?;
+        }
+    } else if let Ok(relative) = path.strip_prefix(dir) {
+        let key = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        files.insert(key, std::fs::read(path)?);
+    }
+    Ok(())
+}
+
+fn sanitize(id: &str) -> String {
+    id.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ActionType, SafetyLevel};
+    use async_trait::async_trait;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Rewrites `let` to `const` in every file of the scope
+    struct ConstTool;
+
+    #[async_trait]
+    impl TransformationTool for ConstTool {
+        async fn execute(&self, intent: &ActionIntent) -> ActionResult<ToolResult> {
+            let mut output = String::new();
+            for dir in &intent.scope {
+                for entry in fs::read_dir(dir)? {
+                    let path = entry?.path();
+                    if path.is_file() {
+                        let content = fs::read_to_string(&path)?;
+                        fs::write(&path, content.replace("let ", "const "))?;
+                        output.push_str(&format!("rewrote {}\n", path.display()));
+                    }
+                }
+            }
+            Ok(ToolResult {
+                success: true,
+                changes: vec![],
+                output,
+                errors: vec![],
+                warnings: vec![],
+                duration: std::time::Duration::ZERO,
+                cached: false,
+                diagnostics: vec![],
+                failed_tests: vec![],
+            })
+        }
+
+        fn supports_language(&self, _language: &str) -> bool {
+            true
+        }
+
+        fn safety_level(&self) -> SafetyLevel {
+            SafetyLevel::Low
+        }
+
+        fn name(&self) -> &str {
+            "const"
+        }
+
+        fn version(&self) -> &str {
+            "1.0.0"
+        }
+
+        async fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    fn project() -> (TempDir, TempDir, ActionIntent) {
+        let base = TempDir::new().unwrap();
+        fs::create_dir_all(base.path().join("src/node_modules/dep")).unwrap();
+        fs::write(base.path().join("src/a.js"), "let a = 1;\n").unwrap();
+        fs::write(base.path().join("src/b.js"), "let b = 2;\n").unwrap();
+        fs::write(base.path().join("src/node_modules/dep/index.js"), "").unwrap();
+        fs::write(base.path().join("README.md"), "outside the scope\n").unwrap();
+        let intent = ActionIntent::new(
+            "intent/1",
+            ActionType::Refactor,
+            "Rename a",
+            vec!["src".to_string()],
+            SafetyLevel::Medium,
+        );
+        (base, TempDir::new().unwrap(), intent)
+    }
+
+    #[test]
+    fn test_tool_changes_stay_in_the_sandbox_until_applied() {
+        let (base, root, intent) = project();
+        let sandbox = Sandbox::create(&intent, base.path(), Some(root.path())).unwrap();
+        assert!(!sandbox.workspace().join("README.md").exists());
+        assert!(!sandbox.workspace().join("src/node_modules").exists());
+
+        let rebased = sandbox.rebase_intent(&intent).unwrap();
+        let src = PathBuf::from(&rebased.scope[0]);
+        fs::write(src.join("a.js"), "const a = 1;\n").unwrap();
+        fs::remove_file(src.join("b.js")).unwrap();
+        fs::write(src.join("c.js"), "let c = 3;\n").unwrap();
+
+        let changes = sandbox.changes().unwrap();
+        let paths: Vec<&str> = changes.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(paths, vec!["src/a.js", "src/b.js", "src/c.js"]);
+        let diff = render_diff(&changes);
+        assert!(diff.contains(
+            "--- a/src/a.js\n+++ b/src/a.js\n@@ -1,1 +1,1 @@\n-let a = 1;\n+const a = 1;\n"
+        ));
+        assert!(diff.contains("--- a/src/b.js\n+++ /dev/null\n"));
+        assert_eq!(
+            fs::read_to_string(base.path().join("src/a.js")).unwrap(),
+            "let a = 1;\n"
+        );
+
+        sandbox.apply(&changes).unwrap();
+        assert_eq!(
+            fs::read_to_string(base.path().join("src/a.js")).unwrap(),
+            "const a = 1;\n"
+        );
+        assert!(!base.path().join("src/b.js").exists());
+        assert!(base.path().join("src/c.js").exists());
+
+        let workspace = sandbox.workspace().to_path_buf();
+        drop(sandbox);
+        assert!(!workspace.exists());
+    }
+
+    #[test]
+    fn test_apply_refuses_files_changed_outside_the_sandbox() {
+        let (base, root, intent) = project();
+        let sandbox = Sandbox::create(&intent, base.path(), Some(root.path())).unwrap();
+        fs::write(sandbox.workspace().join("src/a.js"), "const a = 1;\n").unwrap();
+        fs::write(base.path().join("src/a.js"), "let a = 42;\n").unwrap();
+
+        let changes = sandbox.changes().unwrap();
+        assert!(sandbox.apply(&changes).is_err());
+        assert_eq!(
+            fs::read_to_string(base.path().join("src/a.js")).unwrap(),
+            "let a = 42;\n"
+        );
+
+        let outside = ActionIntent::new(
+            "intent/2",
+            ActionType::Refactor,
+            "Escape",
+            vec!["../etc/passwd".to_string()],
+            SafetyLevel::Medium,
+        );
+        assert!(Sandbox::create(&outside, base.path(), Some(root.path())).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_preview_reports_diffs_without_writing() {
+        let (base, _root, mut intent) = project();
+        intent.scope = vec![base.path().join("src").to_string_lossy().to_string()];
+        intent.dry_run = true;
+
+        let result = preview(&ConstTool, &intent, base.path()).await.unwrap();
+        assert_eq!(
+            result.changes,
+            vec![
+                "--- a/src/a.js\n+++ b/src/a.js\n@@ -1,1 +1,1 @@\n-let a = 1;\n+const a = 1;\n",
+                "--- a/src/b.js\n+++ b/src/b.js\n@@ -1,1 +1,1 @@\n-let b = 2;\n+const b = 2;\n",
+            ]
+        );
+        assert!(result.output.contains("rewrote src"));
+        assert!(!result.output.contains("rhema-sandbox-"));
+        assert_eq!(
+            fs::read_to_string(base.path().join("src/a.js")).unwrap(),
+            "let a = 1;\n"
+        );
+    }
+}
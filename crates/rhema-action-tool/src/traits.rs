@@ -14,7 +14,7 @@
  * limitations under the License.
  */
 
-use crate::{ActionIntent, ActionResult, SafetyLevel, ToolResult};
+use crate::{ActionIntent, ActionResult, SafetyLevel, ToolResult, ToolchainProfile};
 use async_trait::async_trait;
 
 /// Trait for transformation tools
@@ -23,9 +23,24 @@ pub trait TransformationTool: Send + Sync {
     /// Execute the tool with the given intent
     async fn execute(&self, intent: &ActionIntent) -> ActionResult<ToolResult>;
 
+    /// Run the tool without touching the real tree and report the would-be
+    /// changes as unified diffs. `execute` hands dry-run intents to this.
+    async fn preview(&self, intent: &ActionIntent) -> ActionResult<ToolResult> {
+        crate::sandbox::preview(self, intent, &std::env::current_dir()?).await
+    }
+
     /// Check if the tool supports the given language
     fn supports_language(&self, language: &str) -> bool;
 
+    /// Whether the tool is selected automatically for scopes with this
+    /// profile; by default when it supports one of their languages
+    fn applies_to(&self, profile: &ToolchainProfile) -> bool {
+        profile
+            .languages
+            .iter()
+            .any(|language| self.supports_language(language))
+    }
+
     /// Get the safety level of this tool
     fn safety_level(&self) -> SafetyLevel;
 
@@ -45,6 +60,12 @@ pub trait ValidationTool: Send + Sync {
     /// Run validation with the given intent
     async fn validate(&self, intent: &ActionIntent) -> ActionResult<ToolResult>;
 
+    /// Whether the tool is selected automatically for scopes with this
+    /// profile; tools that keep the default only run when named explicitly
+    fn applies_to(&self, _profile: &ToolchainProfile) -> bool {
+        false
+    }
+
     /// Get the name of this tool
     fn name(&self) -> &str;
 
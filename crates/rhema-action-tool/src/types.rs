@@ -16,6 +16,7 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
 
 /// Safety levels for actions
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -43,6 +44,37 @@ pub enum ActionType {
     Custom(String),
 }
 
+/// Languages, frameworks and toolchains detected in the scopes an intent
+/// touches, used to pick the tools that apply to it
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolchainProfile {
+    /// e.g. `rust`, `typescript`, `python`
+    pub languages: BTreeSet<String>,
+    /// e.g. `jest`, `pytest`, `react`
+    pub frameworks: BTreeSet<String>,
+    /// e.g. `cargo`, `npm`, `go`, `poetry`
+    pub toolchains: BTreeSet<String>,
+}
+
+impl ToolchainProfile {
+    pub fn has_language(&self, language: &str) -> bool {
+        self.languages.contains(language)
+    }
+
+    pub fn has_framework(&self, framework: &str) -> bool {
+        self.frameworks.contains(framework)
+    }
+
+    pub fn has_toolchain(&self, toolchain: &str) -> bool {
+        self.toolchains.contains(toolchain)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.languages.is_empty() && self.frameworks.is_empty() && self.toolchains.is_empty()
+    }
+}
+
 /// Action intent describing what should be done
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActionIntent {
@@ -63,6 +95,10 @@ pub struct ActionIntent {
     pub priority: Option<String>,
     pub estimated_effort: Option<String>,
     pub dependencies: Option<Vec<String>>,
+    /// Return the would-be changes as unified diffs in
+    /// `ToolResult::changes` instead of writing them
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 impl ActionIntent {
@@ -90,6 +126,7 @@ impl ActionIntent {
             priority: None,
             estimated_effort: None,
             dependencies: None,
+            dry_run: false,
         }
     }
 }
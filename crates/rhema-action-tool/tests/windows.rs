@@ -0,0 +1,73 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Windows integration tests for tool invocation, run by the
+//! `windows-action-tools` CI job.
+
+#![cfg(windows)]
+
+use rhema_action_tool::platform::{
+    path_in_scope, python_interpreter, relative_path, resolve_program, tool_command,
+};
+use std::path::Path;
+
+#[tokio::test]
+async fn test_cmd_shims_are_resolved_and_run() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("rhema-shim-test.cmd"),
+        "@echo off\r\necho shim:%1\r\n",
+    )
+    .unwrap();
+
+    let mut paths = vec![dir.path().to_path_buf()];
+    paths.extend(std::env::split_paths(&std::env::var_os("PATH").unwrap()));
+    std::env::set_var("PATH", std::env::join_paths(paths).unwrap());
+
+    let resolved = resolve_program("rhema-shim-test").unwrap();
+    assert!(resolved
+        .to_string_lossy()
+        .to_lowercase()
+        .ends_with("rhema-shim-test.cmd"));
+
+    let output = tool_command("rhema-shim-test")
+        .arg("src/lib.rs")
+        .output()
+        .await
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("shim:src/lib.rs"));
+}
+
+#[tokio::test]
+async fn test_npx_and_python_are_found() {
+    // Both ship with the GitHub Windows runner image
+    let npx = tool_command("npx").arg("--version").output().await.unwrap();
+    assert!(npx.status.success());
+
+    let python = python_interpreter().await.expect("no Python 3 interpreter");
+    let output = python.command().arg("--version").output().await.unwrap();
+    assert!(output.status.success());
+}
+
+#[test]
+fn test_windows_paths_match_scopes() {
+    assert!(path_in_scope("SRC\\Api\\handler.rs", "src/api"));
+    assert_eq!(
+        relative_path(Path::new("C:\\repo\\src\\lib.rs"), Path::new("C:\\repo")),
+        "src/lib.rs"
+    );
+}
@@ -15,18 +15,23 @@
  */
 
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use serde::{Deserialize, Serialize};
 use tracing::{info, warn};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
+use rhema_core::events::{EventOutbox, ExportEvent, ExportEventType};
 
+use crate::attestation::{tool_versions, ActionAttestor};
+use crate::audit::{ActionAuditTrail, AuditEntry, AuditEvent, FileComment};
+use crate::diff_view::{apply_changes, DiffRenderer, DiffViewerRegistry, ProposedChange, RenderedArtifact};
 use crate::schema::{ActionIntent, SafetyLevel};
 use crate::error::{ActionError, ActionResult};
 
 /// Approval request
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApprovalRequest {
     pub id: String,
     pub intent_id: String,
@@ -36,10 +41,16 @@ pub struct ApprovalRequest {
     pub status: ApprovalStatus,
     pub comments: Vec<ApprovalComment>,
     pub expires_at: DateTime<Utc>,
+    /// Reviewer who approved or rejected the request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decided_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decided_at: Option<DateTime<Utc>>,
 }
 
 /// Approval status
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ApprovalStatus {
     Pending,
     Approved,
@@ -49,13 +60,61 @@ pub enum ApprovalStatus {
 }
 
 /// Approval comment
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApprovalComment {
     pub id: String,
     pub author: String,
     pub content: String,
     pub timestamp: DateTime<Utc>,
     pub is_decision: bool,
+    /// File the comment is anchored to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+}
+
+impl ApprovalComment {
+    pub fn new(author: &str, content: &str, is_decision: bool) -> Self {
+        Self {
+            id: Uuid::new_v4().simple().to_string(),
+            author: author.to_string(),
+            content: content.to_string(),
+            timestamp: Utc::now(),
+            is_decision,
+            path: None,
+            line: None,
+        }
+    }
+
+    /// Comment anchored to a file of the proposed changes
+    pub fn on_file(author: &str, comment: &FileComment) -> Self {
+        Self {
+            path: Some(comment.path.clone()),
+            line: comment.line,
+            ..Self::new(author, &comment.body, false)
+        }
+    }
+}
+
+/// Verdict an approval UI submits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewVerdict {
+    Approve,
+    Reject,
+}
+
+/// Review submitted by an approval UI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewSubmission {
+    pub reviewer: String,
+    pub verdict: ReviewVerdict,
+    /// Overall comment; required when rejecting
+    #[serde(default)]
+    pub comment: Option<String>,
+    #[serde(default)]
+    pub file_comments: Vec<FileComment>,
 }
 
 /// Approval policy configuration
@@ -151,6 +210,14 @@ pub enum ApprovalEventType {
 /// Approval workflow manager
 pub struct ApprovalWorkflow {
     requests: Arc<RwLock<HashMap<String, ApprovalRequest>>>,
+    /// Proposed changes of requests submitted for review, by request id
+    changes: Arc<RwLock<HashMap<String, Vec<ProposedChange>>>>,
+    /// Intents of requests submitted for review, by request id
+    intents: Arc<RwLock<HashMap<String, ActionIntent>>>,
+    viewers: DiffViewerRegistry,
+    audit_trail: Option<ActionAuditTrail>,
+    attestor: Option<ActionAttestor>,
+    event_outbox: Option<EventOutbox>,
     notification_channels: Vec<String>,
     default_timeout: u64, // seconds
 }
@@ -162,6 +229,12 @@ impl ApprovalWorkflow {
         
         let workflow = Self {
             requests: Arc::new(RwLock::new(HashMap::new())),
+            changes: Arc::new(RwLock::new(HashMap::new())),
+            intents: Arc::new(RwLock::new(HashMap::new())),
+            viewers: DiffViewerRegistry::default(),
+            audit_trail: None,
+            attestor: None,
+            event_outbox: None,
             notification_channels: vec!["console".to_string(), "email".to_string()],
             default_timeout: 3600, // 1 hour
         };
@@ -170,6 +243,29 @@ impl ApprovalWorkflow {
         Ok(workflow)
     }
 
+    /// Persist requests, comments and decisions to an audit trail
+    pub fn with_audit_trail(mut self, audit_trail: ActionAuditTrail) -> Self {
+        self.audit_trail = Some(audit_trail);
+        self
+    }
+
+    /// Sign and store the provenance of every application of approved changes
+    pub fn with_attestor(mut self, attestor: ActionAttestor) -> Self {
+        self.attestor = Some(attestor);
+        self
+    }
+
+    /// Export an `approval_granted` event for every approval
+    pub fn with_event_outbox(mut self, outbox: EventOutbox) -> Self {
+        self.event_outbox = Some(outbox);
+        self
+    }
+
+    /// Register a diff view for approval UIs, replacing any view with the same name
+    pub fn register_view(&mut self, renderer: Arc<dyn DiffRenderer>) {
+        self.viewers.register(renderer);
+    }
+
     /// Initialize the approval workflow (stub)
     pub async fn initialize() -> ActionResult<()> {
         info!("ApprovalWorkflow initialized (stub)");
@@ -204,6 +300,8 @@ impl ApprovalWorkflow {
             status: ApprovalStatus::Pending,
             comments: Vec::new(),
             expires_at,
+            decided_by: None,
+            decided_at: None,
         };
         
         // Store the request
@@ -213,7 +311,7 @@ impl ApprovalWorkflow {
         }
         
         // Send notifications
-        self.send_approval_notifications(&request, &intent).await?;
+        self.send_approval_notifications(&request, intent).await?;
         
         // For now, simulate approval process
         // In a real implementation, this would wait for human input
@@ -302,51 +400,9 @@ impl ApprovalWorkflow {
     pub async fn approve_request(&self, request_id: &str, approver: &str, comment: Option<&str>) -> ActionResult<()> {
         info!("Approving request: {} by {}", request_id, approver);
         
-        let mut requests = self.requests.write().await;
-        
-        if let Some(request) = requests.get_mut(request_id) {
-            // Check if the approver is authorized
-            if !request.approvers.contains(&approver.to_string()) {
-                return Err(ActionError::approval(format!(
-                    "User {} is not authorized to approve this request",
-                    approver
-                )));
-            }
-            
-            // Check if request is still pending
-            if request.status != ApprovalStatus::Pending {
-                return Err(ActionError::approval(format!(
-                    "Request is not pending (status: {:?})",
-                    request.status
-                )));
-            }
-            
-            // Check if request has expired
-            if Utc::now() > request.expires_at {
-                request.status = ApprovalStatus::Expired;
-                return Err(ActionError::approval("Request has expired"));
-            }
-            
-            // Update status
-            request.status = ApprovalStatus::Approved;
-            
-            // Add approval comment
-            if let Some(comment_text) = comment {
-                let comment = ApprovalComment {
-                    id: Uuid::new_v4().simple().to_string(),
-                    author: approver.to_string(),
-                    content: comment_text.to_string(),
-                    timestamp: Utc::now(),
-                    is_decision: true,
-                };
-                request.comments.push(comment);
-            }
-            
-            info!("Request approved successfully: {}", request_id);
-        } else {
-            return Err(ActionError::approval(format!("Request not found: {}", request_id)));
-        }
+        self.decide(request_id, approver, ApprovalStatus::Approved, comment, &[]).await?;
         
+        info!("Request approved successfully: {}", request_id);
         Ok(())
     }
     
@@ -354,52 +410,102 @@ impl ApprovalWorkflow {
     pub async fn reject_request(&self, request_id: &str, approver: &str, reason: &str) -> ActionResult<()> {
         info!("Rejecting request: {} by {}", request_id, approver);
         
-        let mut requests = self.requests.write().await;
+        self.decide(request_id, approver, ApprovalStatus::Rejected, Some(reason), &[]).await?;
         
-        if let Some(request) = requests.get_mut(request_id) {
-            // Check if the approver is authorized
-            if !request.approvers.contains(&approver.to_string()) {
-                return Err(ActionError::approval(format!(
-                    "User {} is not authorized to reject this request",
-                    approver
-                )));
-            }
-            
-            // Check if request is still pending
-            if request.status != ApprovalStatus::Pending {
-                return Err(ActionError::approval(format!(
-                    "Request is not pending (status: {:?})",
-                    request.status
-                )));
-            }
-            
-            // Check if request has expired
-            if Utc::now() > request.expires_at {
-                request.status = ApprovalStatus::Expired;
-                return Err(ActionError::approval("Request has expired"));
-            }
-            
-            // Update status
-            request.status = ApprovalStatus::Rejected;
-            
-            // Add rejection comment
-            let comment = ApprovalComment {
-                id: Uuid::new_v4().simple().to_string(),
-                author: approver.to_string(),
-                content: reason.to_string(),
-                timestamp: Utc::now(),
-                is_decision: true,
-            };
-            request.comments.push(comment);
-            
-            info!("Request rejected successfully: {}", request_id);
+        info!("Request rejected successfully: {}", request_id);
+        Ok(())
+    }
+    
+    /// Record an approver's decision. The decision is refused if it cannot be
+    /// written to the audit trail.
+    async fn decide(
+        &self,
+        request_id: &str,
+        approver: &str,
+        decision: ApprovalStatus,
+        comment: Option<&str>,
+        file_comments: &[FileComment],
+    ) -> ActionResult<()> {
+        let (verb, event) = if decision == ApprovalStatus::Approved {
+            ("approve", AuditEvent::Approved)
         } else {
-            return Err(ActionError::approval(format!("Request not found: {}", request_id)));
+            ("reject", AuditEvent::Rejected)
+        };
+        
+        let mut requests = self.requests.write().await;
+        let request = requests
+            .get_mut(request_id)
+            .ok_or_else(|| ActionError::approval(format!("Request not found: {}", request_id)))?;
+        
+        // Check if the approver is authorized
+        if !request.approvers.contains(&approver.to_string()) {
+            return Err(ActionError::approval(format!(
+                "User {} is not authorized to {} this request",
+                approver, verb
+            )));
+        }
+        
+        // Check if request is still pending
+        if request.status != ApprovalStatus::Pending {
+            return Err(ActionError::approval(format!(
+                "Request is not pending (status: {:?})",
+                request.status
+            )));
+        }
+        
+        // Check if request has expired
+        if Utc::now() > request.expires_at {
+            request.status = ApprovalStatus::Expired;
+            return Err(ActionError::approval("Request has expired"));
+        }
+        
+        self.audit(
+            AuditEntry::new(event, &request.intent_id, request_id, approver)
+                .with_comment(comment)
+                .with_file_comments(file_comments.to_vec()),
+        )?;
+        
+        request.status = decision;
+        request.decided_by = Some(approver.to_string());
+        request.decided_at = Some(Utc::now());
+        for file_comment in file_comments {
+            request.comments.push(ApprovalComment::on_file(approver, file_comment));
+        }
+        if let Some(comment_text) = comment {
+            request.comments.push(ApprovalComment::new(approver, comment_text, true));
+        }
+        
+        if request.status == ApprovalStatus::Approved {
+            self.export_approval(request, approver, comment);
         }
         
         Ok(())
     }
     
+    fn export_approval(&self, request: &ApprovalRequest, approver: &str, comment: Option<&str>) {
+        let Some(outbox) = &self.event_outbox else {
+            return;
+        };
+        let data = serde_json::json!({
+            "request_id": request.id,
+            "intent_id": request.intent_id,
+            "approver": approver,
+            "comment": comment,
+        });
+        let event = ExportEvent::new(ExportEventType::ApprovalGranted, "rhema-action", data)
+            .with_subject(request.intent_id.clone());
+        if let Err(e) = outbox.publish(&event) {
+            warn!("Failed to export approval of request {}: {}", request.id, e);
+        }
+    }
+    
+    fn audit(&self, entry: AuditEntry) -> ActionResult<()> {
+        match &self.audit_trail {
+            Some(audit_trail) => audit_trail.append(&entry),
+            None => Ok(()),
+        }
+    }
+    
     /// Add a comment to a request
     pub async fn add_comment(&self, request_id: &str, author: &str, content: &str) -> ActionResult<()> {
         info!("Adding comment to request: {} by {}", request_id, author);
@@ -407,14 +513,11 @@ impl ApprovalWorkflow {
         let mut requests = self.requests.write().await;
         
         if let Some(request) = requests.get_mut(request_id) {
-            let comment = ApprovalComment {
-                id: Uuid::new_v4().simple().to_string(),
-                author: author.to_string(),
-                content: content.to_string(),
-                timestamp: Utc::now(),
-                is_decision: false,
-            };
-            request.comments.push(comment);
+            self.audit(
+                AuditEntry::new(AuditEvent::CommentAdded, &request.intent_id, request_id, author)
+                    .with_comment(Some(content)),
+            )?;
+            request.comments.push(ApprovalComment::new(author, content, false));
             
             info!("Comment added successfully to request: {}", request_id);
         } else {
@@ -424,6 +527,120 @@ impl ApprovalWorkflow {
         Ok(())
     }
     
+    /// Open an approval request for the changes an intent proposes, to be
+    /// decided through [`ApprovalWorkflow::submit_review`]. Returns the request id.
+    pub async fn submit_for_review(&self, intent: &ActionIntent, changes: Vec<ProposedChange>) -> ActionResult<String> {
+        info!("Submitting {} proposed changes of intent {} for review", changes.len(), intent.id);
+        
+        let approvers = intent.approval_workflow.approvers.clone().unwrap_or_default();
+        if approvers.is_empty() {
+            return Err(ActionError::approval(format!(
+                "No approvers specified for intent: {}",
+                intent.id
+            )));
+        }
+        let timeout = match intent.approval_workflow.timeout {
+            0 => self.default_timeout,
+            timeout => timeout,
+        };
+        
+        let request = ApprovalRequest {
+            id: Uuid::new_v4().simple().to_string(),
+            intent_id: intent.id.clone(),
+            requested_by: intent.created_by.clone().unwrap_or_else(|| "system".to_string()),
+            requested_at: Utc::now(),
+            approvers,
+            status: ApprovalStatus::Pending,
+            comments: Vec::new(),
+            expires_at: Utc::now() + chrono::Duration::seconds(timeout as i64),
+            decided_by: None,
+            decided_at: None,
+        };
+        
+        self.audit(
+            AuditEntry::new(AuditEvent::ApprovalRequested, &intent.id, &request.id, &request.requested_by)
+                .with_files(changes.iter().map(|change| change.path.clone()).collect()),
+        )?;
+        
+        self.changes.write().await.insert(request.id.clone(), changes);
+        self.intents.write().await.insert(request.id.clone(), intent.clone());
+        self.requests.write().await.insert(request.id.clone(), request.clone());
+        self.send_approval_notifications(&request, intent).await?;
+        
+        Ok(request.id)
+    }
+    
+    /// Names of the diff views approval UIs can render
+    pub fn available_views(&self) -> Vec<String> {
+        self.viewers.names()
+    }
+    
+    /// Changes proposed by a request
+    pub async fn proposed_changes(&self, request_id: &str) -> ActionResult<Vec<ProposedChange>> {
+        if !self.requests.read().await.contains_key(request_id) {
+            return Err(ActionError::approval(format!("Request not found: {}", request_id)));
+        }
+        Ok(self.changes.read().await.get(request_id).cloned().unwrap_or_default())
+    }
+    
+    /// Render a request's proposed changes with one of the available views
+    pub async fn render_changes(&self, request_id: &str, view: &str) -> ActionResult<RenderedArtifact> {
+        let changes = self.proposed_changes(request_id).await?;
+        self.viewers.render(request_id, view, &changes)
+    }
+    
+    /// Decide a request from an approval UI. Returns the resulting status.
+    pub async fn submit_review(&self, request_id: &str, submission: &ReviewSubmission) -> ActionResult<ApprovalStatus> {
+        info!("Review of request {} submitted by {}", request_id, submission.reviewer);
+        
+        let comment = submission.comment.as_deref().filter(|comment| !comment.trim().is_empty());
+        let decision = match submission.verdict {
+            ReviewVerdict::Approve => ApprovalStatus::Approved,
+            ReviewVerdict::Reject if comment.is_none() => {
+                return Err(ActionError::approval("A rejection needs a comment"));
+            }
+            ReviewVerdict::Reject => ApprovalStatus::Rejected,
+        };
+        
+        self.decide(request_id, &submission.reviewer, decision.clone(), comment, &submission.file_comments).await?;
+        Ok(decision)
+    }
+    
+    /// Apply the changes of an approved request under `repo_root`. Returns the
+    /// changed files.
+    pub async fn apply_approved_changes(&self, request_id: &str, repo_root: &Path, applied_by: &str) -> ActionResult<Vec<String>> {
+        let request = self
+            .get_request(request_id)
+            .await
+            .ok_or_else(|| ActionError::approval(format!("Request not found: {}", request_id)))?;
+        if request.status != ApprovalStatus::Approved {
+            return Err(ActionError::approval(format!(
+                "Request is not approved (status: {:?})",
+                request.status
+            )));
+        }
+        
+        let changes = self.proposed_changes(request_id).await?;
+        let applied = apply_changes(repo_root, &changes)?;
+        self.audit(
+            AuditEntry::new(AuditEvent::ChangesApplied, &request.intent_id, request_id, applied_by)
+                .with_files(applied.clone()),
+        )?;
+        
+        let intent = self.intents.read().await.get(request_id).cloned();
+        if let (Some(attestor), Some(intent)) = (&self.attestor, intent) {
+            let tools = tool_versions(&intent).await;
+            let path = attestor.attest(&request, &intent, &changes, &tools)?;
+            self.audit(
+                AuditEntry::new(AuditEvent::Attested, &request.intent_id, request_id, applied_by)
+                    .with_files(vec![path.display().to_string()]),
+            )?;
+        }
+        
+        info!("Applied {} approved changes of request {}", applied.len(), request_id);
+        Ok(applied)
+    }
+    
     /// Get approval request by ID
     pub async fn get_request(&self, request_id: &str) -> Option<ApprovalRequest> {
         let requests = self.requests.read().await;
@@ -471,18 +688,18 @@ impl ApprovalWorkflow {
                 )));
             }
             
+            self.audit(AuditEntry::new(
+                AuditEvent::Cancelled,
+                &request.intent_id,
+                request_id,
+                cancelled_by,
+            ))?;
+            
             // Update status
             request.status = ApprovalStatus::Cancelled;
             
             // Add cancellation comment
-            let comment = ApprovalComment {
-                id: Uuid::new_v4().simple().to_string(),
-                author: cancelled_by.to_string(),
-                content: "Request cancelled".to_string(),
-                timestamp: Utc::now(),
-                is_decision: true,
-            };
-            request.comments.push(comment);
+            request.comments.push(ApprovalComment::new(cancelled_by, "Request cancelled", true));
             
             info!("Request cancelled successfully: {}", request_id);
         } else {
@@ -614,6 +831,8 @@ impl ApprovalWorkflow {
             status: enhanced_request.status.clone(),
             comments: enhanced_request.comments.clone(),
             expires_at: enhanced_request.expires_at,
+            decided_by: None,
+            decided_at: None,
         });
         
         // Send notifications if not auto-approved
@@ -850,6 +1069,67 @@ mod tests {
         assert!(stats.approved_requests > 0 || stats.rejected_requests > 0);
     }
 
+    #[tokio::test]
+    async fn test_review_from_approval_ui_is_audited_and_applied() {
+        let repo = tempfile::TempDir::new().unwrap();
+        std::fs::write(repo.path().join("lib.rs"), "fn old() {}\n").unwrap();
+        let audit_trail = ActionAuditTrail::for_repository(repo.path());
+        let workflow = ApprovalWorkflow::new().await.unwrap().with_audit_trail(audit_trail.clone());
+        
+        let mut intent = ActionIntent::new(
+            "test-review",
+            ActionType::Refactor,
+            "Rename old",
+            vec!["lib.rs".to_string()],
+            SafetyLevel::High,
+        );
+        intent.add_approver("user1");
+        let changes = vec![ProposedChange::new(
+            "lib.rs",
+            Some("fn old() {}\n".to_string()),
+            Some("fn new() {}\n".to_string()),
+        )];
+        let request_id = workflow.submit_for_review(&intent, changes).await.unwrap();
+        
+        let artifact = workflow.render_changes(&request_id, "unified").await.unwrap();
+        assert!(artifact.content.contains("-fn old() {}\n+fn new() {}\n"));
+        
+        // Applying before approval and rejecting without a comment are refused
+        assert!(workflow.apply_approved_changes(&request_id, repo.path(), "user1").await.is_err());
+        let mut submission = ReviewSubmission {
+            reviewer: "user1".to_string(),
+            verdict: ReviewVerdict::Reject,
+            comment: None,
+            file_comments: vec![FileComment {
+                path: "lib.rs".to_string(),
+                line: Some(1),
+                body: "Keep the doc comment".to_string(),
+            }],
+        };
+        assert!(workflow.submit_review(&request_id, &submission).await.is_err());
+        
+        submission.verdict = ReviewVerdict::Approve;
+        let status = workflow.submit_review(&request_id, &submission).await.unwrap();
+        assert_eq!(status, ApprovalStatus::Approved);
+        let request = workflow.get_request(&request_id).await.unwrap();
+        assert_eq!(request.comments[0].line, Some(1));
+        
+        let applied = workflow.apply_approved_changes(&request_id, repo.path(), "user1").await.unwrap();
+        assert_eq!(applied, vec!["lib.rs"]);
+        assert_eq!(std::fs::read_to_string(repo.path().join("lib.rs")).unwrap(), "fn new() {}\n");
+        
+        let events: Vec<AuditEvent> = audit_trail
+            .entries_for_intent("test-review")
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.event)
+            .collect();
+        assert_eq!(
+            events,
+            vec![AuditEvent::ApprovalRequested, AuditEvent::Approved, AuditEvent::ChangesApplied]
+        );
+    }
+
     #[tokio::test]
     async fn test_cleanup_expired_requests() {
         let workflow = ApprovalWorkflow::new().await.unwrap();
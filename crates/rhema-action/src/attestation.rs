@@ -0,0 +1,723 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Signed provenance of applied changes.
+//!
+//! When approved changes are applied, an [`ActionAttestor`] writes an in-toto
+//! statement with a SLSA provenance predicate next to the audit trail, in
+//! `.rhema/audit/attestations/<request>.intoto.json`. The statement's subjects
+//! are the files written and the unified diff of the change. Its predicate
+//! records the intent, the tools and their versions, digests of the files as
+//! they were before the change and the approvals of the request.
+//!
+//! The statement is wrapped in a DSSE envelope signed with an ed25519 key.
+//! The private key is read from `RHEMA_ATTESTATION_KEY` (base64url) when set,
+//! as in CI, and otherwise kept in `.rhema/auth/attestation.key`, generated on
+//! first use and kept out of git. Verification needs only the public key,
+//! which is written to `.rhema/attestation.pub` to be committed.
+
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine as _;
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use rand::RngCore;
+use rhema_action_tool::environment::probe_for;
+use rhema_action_tool::EnvironmentCache;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::approval::ApprovalRequest;
+use crate::audit::{ActionAuditTrail, AuditEvent};
+use crate::diff_view::{DiffRenderer, ProposedChange, UnifiedDiffRenderer};
+use crate::error::{ActionError, ActionResult};
+use crate::schema::ActionIntent;
+
+/// Attestation directory relative to the repository root
+pub const ATTESTATION_DIR: &str = ".rhema/audit/attestations";
+
+/// Environment variable holding the private signing key, base64url-encoded
+pub const ATTESTATION_KEY_ENV: &str = "RHEMA_ATTESTATION_KEY";
+
+/// Public key attestations are verified with, relative to the repository root
+pub const ATTESTATION_PUBLIC_KEY_FILE: &str = ".rhema/attestation.pub";
+
+pub const STATEMENT_TYPE: &str = "https://in-toto.io/Statement/v1";
+pub const PROVENANCE_PREDICATE_TYPE: &str = "https://slsa.dev/provenance/v1";
+pub const PAYLOAD_TYPE: &str = "application/vnd.in-toto+json";
+
+/// Build type of provenance for action-applied changes
+pub const ACTION_BUILD_TYPE: &str = "https://github.com/fugue-ai/rhema/action-protocol/v1";
+
+const BUILDER_ID: &str = "https://github.com/fugue-ai/rhema";
+const KEY_FILE: &str = "attestation.key";
+const DIFF_MEDIA_TYPE: &str = "text/x-diff";
+
+/// An artifact identified by name and digest
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceDescriptor {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub digest: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub annotations: BTreeMap<String, serde_json::Value>,
+}
+
+impl ResourceDescriptor {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Descriptor carrying the SHA-256 digest of `content`
+    pub fn of_content(name: impl Into<String>, content: &[u8]) -> Self {
+        let mut descriptor = Self::new(name);
+        descriptor
+            .digest
+            .insert("sha256".to_string(), sha256_hex(content));
+        descriptor
+    }
+
+    pub fn sha256(&self) -> Option<&str> {
+        self.digest.get("sha256").map(String::as_str)
+    }
+
+    fn with_annotation(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.annotations.insert(key.to_string(), value.into());
+        self
+    }
+
+    fn annotation(&self, key: &str) -> Option<&str> {
+        self.annotations.get(key).and_then(|value| value.as_str())
+    }
+}
+
+/// in-toto statement about the changes of one approval request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Statement {
+    #[serde(rename = "_type")]
+    pub statement_type: String,
+    pub subject: Vec<ResourceDescriptor>,
+    pub predicate_type: String,
+    pub predicate: Provenance,
+}
+
+impl Statement {
+    /// Reviewers whose approval the provenance records
+    pub fn approvers(&self) -> Vec<String> {
+        self.predicate
+            .run_details
+            .byproducts
+            .iter()
+            .filter_map(|byproduct| byproduct.annotation("approvedBy"))
+            .map(str::to_string)
+            .collect()
+    }
+
+    pub fn request_id(&self) -> Option<String> {
+        self.predicate
+            .build_definition
+            .internal_parameters
+            .get("requestId")
+            .and_then(|id| id.as_str())
+            .map(str::to_string)
+    }
+}
+
+/// SLSA provenance predicate
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Provenance {
+    pub build_definition: BuildDefinition,
+    pub run_details: RunDetails,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BuildDefinition {
+    pub build_type: String,
+    /// The intent that produced the changes
+    pub external_parameters: serde_json::Value,
+    /// The approval request the changes were applied from
+    pub internal_parameters: serde_json::Value,
+    /// Files before the change and the tools involved, with their versions
+    pub resolved_dependencies: Vec<ResourceDescriptor>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunDetails {
+    pub builder: Builder,
+    pub metadata: RunMetadata,
+    /// Approvals of the request
+    #[serde(default)]
+    pub byproducts: Vec<ResourceDescriptor>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Builder {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub version: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunMetadata {
+    pub invocation_id: String,
+    pub started_on: DateTime<Utc>,
+    pub finished_on: DateTime<Utc>,
+}
+
+/// DSSE envelope around a serialized statement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Envelope {
+    pub payload_type: String,
+    /// Base64-encoded statement
+    pub payload: String,
+    pub signatures: Vec<EnvelopeSignature>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnvelopeSignature {
+    pub keyid: String,
+    pub sig: String,
+}
+
+/// A tool that took part in an action and the version it reported
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolVersion {
+    pub name: String,
+    pub version: Option<String>,
+}
+
+/// Versions of the tools an intent names, from the machine's tool
+/// environment cache
+pub async fn tool_versions(intent: &ActionIntent) -> Vec<ToolVersion> {
+    let mut names: Vec<&String> = intent
+        .transformation
+        .tools
+        .iter()
+        .chain(&intent.transformation.validation)
+        .collect();
+    names.sort();
+    names.dedup();
+
+    let mut tools = Vec::new();
+    for name in names {
+        let version = match probe_for(name) {
+            Some(probe) => EnvironmentCache::machine().status(probe).await.version,
+            None => None,
+        };
+        tools.push(ToolVersion {
+            name: name.clone(),
+            version,
+        });
+    }
+    tools
+}
+
+/// Outcome of verifying an attestation
+#[derive(Debug, Clone)]
+pub struct AttestationVerification {
+    pub statement: Statement,
+    pub keyid: String,
+    /// Attested files whose current content differs from the attested digest
+    pub drifted: Vec<String>,
+    /// Approvers named in the provenance without an approval in the audit trail
+    pub unrecorded_approvals: Vec<String>,
+}
+
+impl AttestationVerification {
+    /// Whether the audit trail backs every attested approval
+    pub fn approvals_recorded(&self) -> bool {
+        self.unrecorded_approvals.is_empty()
+    }
+}
+
+/// Writes and verifies attestations of one repository
+#[derive(Debug, Clone)]
+pub struct ActionAttestor {
+    repo_root: PathBuf,
+    dir: PathBuf,
+    key_path: PathBuf,
+    /// Verify with this key instead of the committed public key
+    public_key: Option<VerifyingKey>,
+}
+
+impl ActionAttestor {
+    pub fn for_repository(repo_root: &Path) -> Self {
+        Self {
+            repo_root: repo_root.to_path_buf(),
+            dir: repo_root.join(ATTESTATION_DIR),
+            key_path: repo_root
+                .join(".rhema")
+                .join(rhema_core::agent_tokens::AUTH_DIR)
+                .join(KEY_FILE),
+            public_key: None,
+        }
+    }
+
+    /// Verify attestations with `public_key` rather than the key in
+    /// `.rhema/attestation.pub`
+    pub fn with_public_key(mut self, public_key: VerifyingKey) -> Self {
+        self.public_key = Some(public_key);
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Attestation file of an approval request
+    pub fn path_for(&self, request_id: &str) -> PathBuf {
+        self.dir.join(format!("{}.intoto.json", request_id))
+    }
+
+    /// Provenance of the changes of an applied request
+    pub fn statement(
+        &self,
+        request: &ApprovalRequest,
+        intent: &ActionIntent,
+        changes: &[ProposedChange],
+        tools: &[ToolVersion],
+    ) -> ActionResult<Statement> {
+        let diff = UnifiedDiffRenderer::default().render(changes)?;
+        let mut subject: Vec<ResourceDescriptor> = changes
+            .iter()
+            .filter_map(|change| {
+                let after = change.after.as_ref()?;
+                Some(ResourceDescriptor::of_content(
+                    &change.path,
+                    after.as_bytes(),
+                ))
+            })
+            .collect();
+        let mut diff_subject =
+            ResourceDescriptor::of_content(format!("{}.diff", request.id), diff.as_bytes());
+        diff_subject.media_type = Some(DIFF_MEDIA_TYPE.to_string());
+        subject.push(diff_subject);
+
+        let inputs = changes.iter().filter_map(|change| {
+            let before = change.before.as_ref()?;
+            Some(ResourceDescriptor::of_content(
+                &change.path,
+                before.as_bytes(),
+            ))
+        });
+        let tool_dependencies = tools.iter().map(|tool| {
+            let descriptor = ResourceDescriptor::new(format!("tool:{}", tool.name));
+            match &tool.version {
+                Some(version) => descriptor.with_annotation("version", version.as_str()),
+                None => descriptor,
+            }
+        });
+
+        let approvals = match (&request.decided_by, request.decided_at) {
+            (Some(actor), Some(at)) => {
+                let comment = request
+                    .comments
+                    .iter()
+                    .rev()
+                    .find(|comment| comment.is_decision && &comment.author == actor);
+                let approval = ResourceDescriptor::new(format!("approval:{}", actor))
+                    .with_annotation("approvedBy", actor.as_str())
+                    .with_annotation("approvedAt", at.to_rfc3339());
+                vec![match comment {
+                    Some(comment) => approval.with_annotation("comment", comment.content.as_str()),
+                    None => approval,
+                }]
+            }
+            _ => Vec::new(),
+        };
+
+        let external_parameters =
+            serde_json::to_value(intent).map_err(|e| ActionError::serialization(e.to_string()))?;
+        Ok(Statement {
+            statement_type: STATEMENT_TYPE.to_string(),
+            subject,
+            predicate_type: PROVENANCE_PREDICATE_TYPE.to_string(),
+            predicate: Provenance {
+                build_definition: BuildDefinition {
+                    build_type: ACTION_BUILD_TYPE.to_string(),
+                    external_parameters,
+                    internal_parameters: serde_json::json!({
+                        "requestId": request.id,
+                        "requestedBy": request.requested_by,
+                    }),
+                    resolved_dependencies: inputs.chain(tool_dependencies).collect(),
+                },
+                run_details: RunDetails {
+                    builder: Builder {
+                        id: BUILDER_ID.to_string(),
+                        version: BTreeMap::from([(
+                            "rhema-action".to_string(),
+                            env!("CARGO_PKG_VERSION").to_string(),
+                        )]),
+                    },
+                    metadata: RunMetadata {
+                        invocation_id: request.id.clone(),
+                        started_on: request.requested_at,
+                        finished_on: Utc::now(),
+                    },
+                    byproducts: approvals,
+                },
+            },
+        })
+    }
+
+    /// Sign the provenance of an applied request and store it. Returns the
+    /// attestation file.
+    pub fn attest(
+        &self,
+        request: &ApprovalRequest,
+        intent: &ActionIntent,
+        changes: &[ProposedChange],
+        tools: &[ToolVersion],
+    ) -> ActionResult<PathBuf> {
+        let statement = self.statement(request, intent, changes, tools)?;
+        let envelope = self.sign(&statement)?;
+
+        std::fs::create_dir_all(&self.dir)
+            .map_err(|e| ActionError::file_operation(self.dir.clone(), e.to_string()))?;
+        let path = self.path_for(&request.id);
+        let content = serde_json::to_string_pretty(&envelope)
+            .map_err(|e| ActionError::serialization(e.to_string()))?;
+        std::fs::write(&path, content)
+            .map_err(|e| ActionError::file_operation(path.clone(), e.to_string()))?;
+        Ok(path)
+    }
+
+    /// Wrap a statement in an envelope signed with the attestation key
+    pub fn sign(&self, statement: &Statement) -> ActionResult<Envelope> {
+        let payload =
+            serde_json::to_vec(statement).map_err(|e| ActionError::serialization(e.to_string()))?;
+        let key = self.signing_key()?;
+        let signature = key.sign(&pae(PAYLOAD_TYPE, &payload));
+        Ok(Envelope {
+            payload_type: PAYLOAD_TYPE.to_string(),
+            payload: STANDARD.encode(&payload),
+            signatures: vec![EnvelopeSignature {
+                keyid: key_id(&key.verifying_key()),
+                sig: STANDARD.encode(signature.to_bytes()),
+            }],
+        })
+    }
+
+    /// Check an attestation's signature, then compare its subjects with the
+    /// working tree and its approvals with the audit trail
+    pub fn verify(&self, path: &Path) -> ActionResult<AttestationVerification> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| ActionError::file_operation(path.to_path_buf(), e.to_string()))?;
+        let envelope: Envelope = serde_json::from_str(&content)
+            .map_err(|e| ActionError::deserialization(format!("{}: {}", path.display(), e)))?;
+        if envelope.payload_type != PAYLOAD_TYPE {
+            return Err(ActionError::validation(format!(
+                "Unexpected payload type '{}'",
+                envelope.payload_type
+            )));
+        }
+        let payload = STANDARD
+            .decode(&envelope.payload)
+            .map_err(|e| ActionError::deserialization(format!("Attestation payload: {}", e)))?;
+
+        let key = self.verifying_key()?;
+        let keyid = key_id(&key);
+        let signature = envelope
+            .signatures
+            .iter()
+            .find(|signature| signature.keyid == keyid)
+            .ok_or_else(|| {
+                ActionError::validation(format!("Attestation is not signed by key {}", keyid))
+            })?;
+        let sig = STANDARD
+            .decode(&signature.sig)
+            .ok()
+            .and_then(|sig| Signature::from_slice(&sig).ok())
+            .ok_or_else(|| ActionError::validation("Malformed attestation signature"))?;
+        key.verify(&pae(&envelope.payload_type, &payload), &sig)
+            .map_err(|_| ActionError::validation("Attestation signature does not match"))?;
+
+        let statement: Statement = serde_json::from_slice(&payload)
+            .map_err(|e| ActionError::deserialization(format!("Attestation statement: {}", e)))?;
+        if statement.statement_type != STATEMENT_TYPE
+            || statement.predicate_type != PROVENANCE_PREDICATE_TYPE
+        {
+            return Err(ActionError::validation(format!(
+                "Unsupported statement {} with predicate {}",
+                statement.statement_type, statement.predicate_type
+            )));
+        }
+
+        let drifted = statement
+            .subject
+            .iter()
+            .filter(|subject| subject.media_type.is_none())
+            .filter(|subject| {
+                let current = std::fs::read(self.repo_root.join(&subject.name)).ok();
+                current.map(|content| sha256_hex(&content)).as_deref() != subject.sha256()
+            })
+            .map(|subject| subject.name.clone())
+            .collect();
+
+        let request_id = statement.request_id().unwrap_or_default();
+        let audit = ActionAuditTrail::for_repository(&self.repo_root).entries()?;
+        let unrecorded_approvals = statement
+            .approvers()
+            .into_iter()
+            .filter(|actor| {
+                !audit.iter().any(|entry| {
+                    entry.event == AuditEvent::Approved
+                        && &entry.actor == actor
+                        && entry.request_id == request_id
+                })
+            })
+            .collect();
+
+        Ok(AttestationVerification {
+            statement,
+            keyid,
+            drifted,
+            unrecorded_approvals,
+        })
+    }
+
+    /// Public key of the signing key, to publish for verification
+    pub fn public_key(&self) -> ActionResult<String> {
+        Ok(URL_SAFE_NO_PAD.encode(self.signing_key()?.verifying_key().as_bytes()))
+    }
+
+    /// The key attestations are verified with: the one given with
+    /// `with_public_key`, else the committed public key file
+    fn verifying_key(&self) -> ActionResult<VerifyingKey> {
+        if let Some(key) = self.public_key {
+            return Ok(key);
+        }
+        let path = self.repo_root.join(ATTESTATION_PUBLIC_KEY_FILE);
+        if !path.exists() {
+            return Err(ActionError::not_found(format!(
+                "attestation public key (pass one or commit {})",
+                ATTESTATION_PUBLIC_KEY_FILE
+            )));
+        }
+        let encoded = std::fs::read_to_string(&path)
+            .map_err(|e| ActionError::file_operation(path.clone(), e.to_string()))?;
+        parse_public_key(&encoded)
+    }
+
+    /// The private key: `RHEMA_ATTESTATION_KEY`, else the key file, generated
+    /// together with the public key file on first use
+    fn signing_key(&self) -> ActionResult<SigningKey> {
+        if let Ok(encoded) = std::env::var(ATTESTATION_KEY_ENV) {
+            return decode_key(&encoded).map(|seed| SigningKey::from_bytes(&seed));
+        }
+        if self.key_path.exists() {
+            let encoded = std::fs::read_to_string(&self.key_path)
+                .map_err(|e| ActionError::file_operation(self.key_path.clone(), e.to_string()))?;
+            return decode_key(&encoded).map(|seed| SigningKey::from_bytes(&seed));
+        }
+        // Keep the private key out of git, as agent tokens do
+        if let Some(parent) = self.key_path.parent() {
+            if !parent.exists() {
+                std::fs::create_dir_all(parent).map_err(|e| {
+                    ActionError::file_operation(parent.to_path_buf(), e.to_string())
+                })?;
+            }
+            let gitignore = parent.join(".gitignore");
+            if !gitignore.exists() {
+                std::fs::write(&gitignore, "*\n")
+                    .map_err(|e| ActionError::file_operation(gitignore, e.to_string()))?;
+            }
+        }
+        let mut seed = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut seed);
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        options
+            .open(&self.key_path)
+            .and_then(|mut file| file.write_all(URL_SAFE_NO_PAD.encode(seed).as_bytes()))
+            .map_err(|e| ActionError::file_operation(self.key_path.clone(), e.to_string()))?;
+
+        let key = SigningKey::from_bytes(&seed);
+        let public_path = self.repo_root.join(ATTESTATION_PUBLIC_KEY_FILE);
+        std::fs::write(
+            &public_path,
+            format!(
+                "{}\n",
+                URL_SAFE_NO_PAD.encode(key.verifying_key().as_bytes())
+            ),
+        )
+        .map_err(|e| ActionError::file_operation(public_path, e.to_string()))?;
+        Ok(key)
+    }
+}
+
+/// Parse a base64url-encoded ed25519 public key
+pub fn parse_public_key(encoded: &str) -> ActionResult<VerifyingKey> {
+    let bytes = decode_key(encoded)?;
+    VerifyingKey::from_bytes(&bytes)
+        .map_err(|e| ActionError::configuration(format!("Invalid attestation public key: {}", e)))
+}
+
+fn decode_key(encoded: &str) -> ActionResult<[u8; 32]> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(encoded.trim())
+        .map_err(|e| ActionError::configuration(format!("Corrupt attestation key: {}", e)))?;
+    bytes
+        .try_into()
+        .map_err(|_| ActionError::configuration("Attestation keys must be 32 bytes"))
+}
+
+/// Key id: `ed25519:` and the first 16 hex digits of the public key's digest
+fn key_id(key: &VerifyingKey) -> String {
+    format!("ed25519:{}", &sha256_hex(key.as_bytes())[..16])
+}
+
+/// DSSE pre-authentication encoding
+fn pae(payload_type: &str, payload: &[u8]) -> Vec<u8> {
+    let mut encoded = format!(
+        "DSSEv1 {} {} {} ",
+        payload_type.len(),
+        payload_type,
+        payload.len()
+    )
+    .into_bytes();
+    encoded.extend_from_slice(payload);
+    encoded
+}
+
+fn sha256_hex(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::approval::{ApprovalWorkflow, ReviewSubmission, ReviewVerdict};
+    use crate::schema::{ActionType, SafetyLevel};
+
+    #[tokio::test]
+    async fn test_applied_changes_are_attested_and_verified() {
+        let repo = tempfile::TempDir::new().unwrap();
+        std::fs::write(repo.path().join("lib.rs"), "fn old() {}\n").unwrap();
+        let attestor = ActionAttestor::for_repository(repo.path());
+        let workflow = ApprovalWorkflow::new()
+            .await
+            .unwrap()
+            .with_audit_trail(ActionAuditTrail::for_repository(repo.path()))
+            .with_attestor(attestor.clone());
+
+        let mut intent = ActionIntent::new(
+            "test-attest",
+            ActionType::Refactor,
+            "Rename old",
+            vec!["lib.rs".to_string()],
+            SafetyLevel::High,
+        );
+        intent.add_approver("user1");
+        let changes = vec![
+            ProposedChange::new(
+                "lib.rs",
+                Some("fn old() {}\n".to_string()),
+                Some("fn new() {}\n".to_string()),
+            ),
+            ProposedChange::new("new.rs", None, Some("fn added() {}\n".to_string())),
+        ];
+        let request_id = workflow.submit_for_review(&intent, changes).await.unwrap();
+        let submission = ReviewSubmission {
+            reviewer: "user1".to_string(),
+            verdict: ReviewVerdict::Approve,
+            comment: Some("Looks good".to_string()),
+            file_comments: Vec::new(),
+        };
+        workflow
+            .submit_review(&request_id, &submission)
+            .await
+            .unwrap();
+        workflow
+            .apply_approved_changes(&request_id, repo.path(), "user1")
+            .await
+            .unwrap();
+
+        let path = attestor.path_for(&request_id);
+        let verification = attestor.verify(&path).unwrap();
+        let statement = &verification.statement;
+        assert_eq!(statement.request_id(), Some(request_id.clone()));
+        assert_eq!(statement.approvers(), vec!["user1"]);
+        assert_eq!(
+            statement.subject[0].sha256(),
+            Some(sha256_hex(b"fn new() {}\n").as_str())
+        );
+        assert_eq!(
+            statement.subject[2].media_type.as_deref(),
+            Some(DIFF_MEDIA_TYPE)
+        );
+        assert_eq!(
+            statement.predicate.build_definition.resolved_dependencies[0].sha256(),
+            Some(sha256_hex(b"fn old() {}\n").as_str())
+        );
+        assert!(verification.drifted.is_empty());
+        assert!(verification.approvals_recorded());
+
+        // Only the public key is needed to verify, and the private key is ignored by git
+        let public_key = attestor.public_key().unwrap();
+        assert_eq!(
+            std::fs::read_to_string(repo.path().join(ATTESTATION_PUBLIC_KEY_FILE))
+                .unwrap()
+                .trim(),
+            public_key
+        );
+        assert_eq!(
+            std::fs::read_to_string(repo.path().join(".rhema/auth/.gitignore")).unwrap(),
+            "*\n"
+        );
+        std::fs::remove_file(repo.path().join(".rhema/auth").join(KEY_FILE)).unwrap();
+        assert!(attestor.verify(&path).is_ok());
+        let other = SigningKey::from_bytes(&[7u8; 32]).verifying_key();
+        assert!(attestor
+            .clone()
+            .with_public_key(other)
+            .verify(&path)
+            .is_err());
+
+        // Later edits show up as drift; a tampered statement fails the signature
+        std::fs::write(repo.path().join("lib.rs"), "fn newer() {}\n").unwrap();
+        assert_eq!(attestor.verify(&path).unwrap().drifted, vec!["lib.rs"]);
+
+        let mut envelope: Envelope =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let mut tampered = statement.clone();
+        tampered.predicate.run_details.byproducts.clear();
+        envelope.payload = STANDARD.encode(serde_json::to_vec(&tampered).unwrap());
+        std::fs::write(&path, serde_json::to_string(&envelope).unwrap()).unwrap();
+        assert!(attestor.verify(&path).is_err());
+    }
+}
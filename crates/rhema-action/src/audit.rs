@@ -0,0 +1,176 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Append-only audit trail of action approvals.
+//!
+//! Every approval request, reviewer comment, decision and application of
+//! approved changes is appended as one JSON line to
+//! `.rhema/audit/actions.jsonl` in the repository. Signed provenance of
+//! applied changes is stored beside it, see [`crate::attestation`].
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::error::{ActionError, ActionResult};
+
+/// Audit trail location relative to the repository root
+pub const AUDIT_TRAIL_PATH: &str = ".rhema/audit/actions.jsonl";
+
+/// What happened to an approval request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEvent {
+    ApprovalRequested,
+    CommentAdded,
+    Approved,
+    Rejected,
+    Cancelled,
+    ChangesApplied,
+    /// Provenance of applied changes was signed and stored
+    Attested,
+}
+
+/// Reviewer comment on a file, optionally anchored to a line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileComment {
+    pub path: String,
+    /// Line in the proposed version of the file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    pub body: String,
+}
+
+/// One audit trail record
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    pub event: AuditEvent,
+    pub intent_id: String,
+    pub request_id: String,
+    pub actor: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub file_comments: Vec<FileComment>,
+    /// Files the event concerns, such as the changes proposed or applied
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+}
+
+impl AuditEntry {
+    pub fn new(
+        event: AuditEvent,
+        intent_id: impl Into<String>,
+        request_id: impl Into<String>,
+        actor: impl Into<String>,
+    ) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            event,
+            intent_id: intent_id.into(),
+            request_id: request_id.into(),
+            actor: actor.into(),
+            comment: None,
+            file_comments: Vec::new(),
+            files: Vec::new(),
+        }
+    }
+
+    pub fn with_comment(mut self, comment: Option<&str>) -> Self {
+        self.comment = comment.map(str::to_string);
+        self
+    }
+
+    pub fn with_file_comments(mut self, file_comments: Vec<FileComment>) -> Self {
+        self.file_comments = file_comments;
+        self
+    }
+
+    pub fn with_files(mut self, files: Vec<String>) -> Self {
+        self.files = files;
+        self
+    }
+}
+
+/// JSON-lines audit trail file
+#[derive(Debug, Clone)]
+pub struct ActionAuditTrail {
+    path: PathBuf,
+}
+
+impl ActionAuditTrail {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The audit trail of the repository at `repo_root`
+    pub fn for_repository(repo_root: &Path) -> Self {
+        Self::new(repo_root.join(AUDIT_TRAIL_PATH))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn append(&self, entry: &AuditEntry) -> ActionResult<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| ActionError::file_operation(parent.to_path_buf(), e.to_string()))?;
+        }
+        let line =
+            serde_json::to_string(entry).map_err(|e| ActionError::serialization(e.to_string()))?;
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| ActionError::file_operation(self.path.clone(), e.to_string()))?;
+        writeln!(file, "{}", line)
+            .map_err(|e| ActionError::file_operation(self.path.clone(), e.to_string()))
+    }
+
+    /// All entries, oldest first
+    pub fn entries(&self) -> ActionResult<Vec<AuditEntry>> {
+        let content = match std::fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(ActionError::file_operation(
+                    self.path.clone(),
+                    e.to_string(),
+                ))
+            }
+        };
+        content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                serde_json::from_str(line)
+                    .map_err(|e| ActionError::deserialization(format!("{}: {}", line, e)))
+            })
+            .collect()
+    }
+
+    /// Entries recorded for one intent, oldest first
+    pub fn entries_for_intent(&self, intent_id: &str) -> ActionResult<Vec<AuditEntry>> {
+        Ok(self
+            .entries()?
+            .into_iter()
+            .filter(|entry| entry.intent_id == intent_id)
+            .collect())
+    }
+}
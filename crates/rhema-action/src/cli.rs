@@ -18,8 +18,12 @@ use clap::Subcommand;
 use std::path::PathBuf;
 use tracing::info;
 
+use crate::attestation::{parse_public_key, ActionAttestor};
 use crate::error::{ActionError, ActionResult};
+use crate::orchestration::{OrchestrationPlan, PrStrategy, RefactorOrchestrator, SubIntentStatus};
+use crate::pipeline::ActionSafetyPipeline;
 use crate::schema::{ActionIntent, ActionType, SafetyLevel};
+use crate::worktree::repository_root;
 // Pipeline functions will be implemented as needed
 async fn execute_action(intent: &ActionIntent) -> ActionResult<ExecutionResult> {
     // TODO: Implement action execution
@@ -114,6 +118,21 @@ pub enum IntentSubcommands {
         dry_run: bool,
     },
 
+    /// Split a repository-wide intent per scope and run it in dependency order
+    Orchestrate {
+        /// Intent file path
+        #[arg(value_name = "INTENT_FILE")]
+        intent_file: String,
+
+        /// Deliver one combined pull request or a stack of per-scope pull requests
+        #[arg(long, value_enum, default_value = "combined")]
+        strategy: PrStrategy,
+
+        /// Show the per-scope plan without executing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+
     /// Rollback action
     Rollback {
         /// Intent ID
@@ -249,6 +268,35 @@ pub enum IntentSubcommands {
         #[arg(long, value_name = "REASON")]
         reason: String,
     },
+
+    /// Signed provenance of applied changes
+    Attest {
+        #[command(subcommand)]
+        command: AttestSubcommands,
+    },
+}
+
+/// Attestation subcommands
+#[derive(Subcommand)]
+pub enum AttestSubcommands {
+    /// Verify the attestation of an approval request
+    Verify {
+        /// Approval request ID or attestation file
+        #[arg(value_name = "REQUEST_OR_FILE")]
+        attestation: String,
+
+        /// Fail when attested files no longer match the working tree
+        #[arg(long)]
+        check_tree: bool,
+
+        /// Public key (base64url) or key file to verify with, instead of
+        /// .rhema/attestation.pub
+        #[arg(long, value_name = "KEY_OR_FILE")]
+        public_key: Option<String>,
+    },
+
+    /// Print the public key of the signing key, creating the key if needed
+    PublicKey,
 }
 
 /// CLI handler for action protocol commands
@@ -280,6 +328,11 @@ impl ActionCli {
             } => {
                 Self::handle_execute(intent_file, require_approval, skip_validation, dry_run).await
             }
+            IntentSubcommands::Orchestrate {
+                intent_file,
+                strategy,
+                dry_run,
+            } => Self::handle_orchestrate(intent_file, strategy, dry_run).await,
             IntentSubcommands::Rollback {
                 intent_id,
                 force,
@@ -323,6 +376,14 @@ impl ActionCli {
             IntentSubcommands::Reject { intent_id, reason } => {
                 Self::handle_reject(intent_id, reason).await
             }
+            IntentSubcommands::Attest { command } => match command {
+                AttestSubcommands::Verify {
+                    attestation,
+                    check_tree,
+                    public_key,
+                } => Self::handle_attest_verify(attestation, check_tree, public_key).await,
+                AttestSubcommands::PublicKey => Self::handle_attest_public_key().await,
+            },
         }
     }
 
@@ -472,6 +533,81 @@ impl ActionCli {
         Ok(())
     }
 
+    /// Handle orchestrate command
+    async fn handle_orchestrate(
+        intent_file: String,
+        strategy: PrStrategy,
+        dry_run: bool,
+    ) -> ActionResult<()> {
+        info!("Orchestrating action from file: {}", intent_file);
+
+        let intent = Self::load_intent_from_file(&intent_file).await?;
+        let cwd = std::env::current_dir().map_err(|e| {
+            ActionError::configuration(format!("Failed to get current directory: {}", e))
+        })?;
+        let repo_root = repository_root(&cwd)?;
+        let plan = OrchestrationPlan::for_repository(&intent, &repo_root)?;
+
+        println!(
+            "🧭 {} scopes in {} waves",
+            plan.sub_intents.len(),
+            plan.waves.len()
+        );
+        for (wave, indices) in plan.waves.iter().enumerate() {
+            println!("Wave {}:", wave + 1);
+            for &index in indices {
+                let sub = &plan.sub_intents[index];
+                let after = if sub.depends_on.is_empty() {
+                    String::new()
+                } else {
+                    format!(" (after {})", sub.depends_on.join(", "))
+                };
+                println!(
+                    "  - {}: {}{}",
+                    sub.scope,
+                    sub.intent.scope.join(", "),
+                    after
+                );
+            }
+        }
+        if dry_run {
+            return Ok(());
+        }
+
+        let pipeline = ActionSafetyPipeline::new()
+            .await
+            .map_err(|e| ActionError::pipeline("initialize", e.to_string()))?;
+        let report = RefactorOrchestrator::new(pipeline, repo_root)
+            .with_strategy(strategy)
+            .execute(&plan)
+            .await?;
+
+        for outcome in &report.outcomes {
+            let icon = match outcome.status {
+                SubIntentStatus::Succeeded => "✅",
+                SubIntentStatus::Failed => "❌",
+                SubIntentStatus::Skipped => "⏭️",
+            };
+            println!(
+                "{} {}: {} changes, {} errors, {} warnings",
+                icon,
+                outcome.scope,
+                outcome.changes.len(),
+                outcome.errors.len(),
+                outcome.warnings.len()
+            );
+            for error in &outcome.errors {
+                println!("    {}", error);
+            }
+        }
+        for url in &report.pull_requests {
+            println!("🔗 {}", url);
+        }
+
+        info!("Orchestration completed in {}ms", report.duration_ms);
+        Ok(())
+    }
+
     /// Handle rollback command
     async fn handle_rollback(
         intent_id: String,
@@ -647,6 +783,77 @@ impl ActionCli {
         Ok(())
     }
 
+    /// Handle attest verify command
+    async fn handle_attest_verify(
+        attestation: String,
+        check_tree: bool,
+        public_key: Option<String>,
+    ) -> ActionResult<()> {
+        info!("Verifying attestation: {}", attestation);
+
+        let cwd = std::env::current_dir().map_err(|e| {
+            ActionError::configuration(format!("Failed to get current directory: {}", e))
+        })?;
+        let mut attestor = ActionAttestor::for_repository(&repository_root(&cwd)?);
+        if let Some(key) = public_key {
+            let encoded = match PathBuf::from(&key) {
+                path if path.is_file() => std::fs::read_to_string(&path)
+                    .map_err(|e| ActionError::file_operation(path, e.to_string()))?,
+                _ => key,
+            };
+            attestor = attestor.with_public_key(parse_public_key(&encoded)?);
+        }
+        let path = match PathBuf::from(&attestation) {
+            path if path.is_file() => path,
+            _ => attestor.path_for(&attestation),
+        };
+        let verification = attestor.verify(&path)?;
+        let statement = &verification.statement;
+
+        println!("✅ Signature valid ({})", verification.keyid);
+        println!(
+            "Request: {}",
+            statement.request_id().unwrap_or_else(|| "-".to_string())
+        );
+        for subject in &statement.subject {
+            println!(
+                "  {} sha256:{}",
+                subject.name,
+                subject.sha256().unwrap_or("-")
+            );
+        }
+        println!("Approved by: {}", statement.approvers().join(", "));
+
+        for actor in &verification.unrecorded_approvals {
+            println!("❌ Approval by {} is missing from the audit trail", actor);
+        }
+        for file in &verification.drifted {
+            println!("⚠️  {} changed since it was attested", file);
+        }
+        if !verification.approvals_recorded() {
+            return Err(ActionError::validation(
+                "Attested approvals are not backed by the audit trail",
+            ));
+        }
+        if check_tree && !verification.drifted.is_empty() {
+            return Err(ActionError::validation(format!(
+                "{} attested file(s) differ from the working tree",
+                verification.drifted.len()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Handle attest public-key command
+    async fn handle_attest_public_key() -> ActionResult<()> {
+        let cwd = std::env::current_dir().map_err(|e| {
+            ActionError::configuration(format!("Failed to get current directory: {}", e))
+        })?;
+        let attestor = ActionAttestor::for_repository(&repository_root(&cwd)?);
+        println!("{}", attestor.public_key()?);
+        Ok(())
+    }
+
     /// Load intent from file
     async fn load_intent_from_file(file_path: &str) -> ActionResult<ActionIntent> {
         let content = tokio::fs::read_to_string(file_path).await.map_err(|e| {
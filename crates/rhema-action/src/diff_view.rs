@@ -0,0 +1,594 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Rendering and application of the changes an intent proposes.
+//!
+//! Approval requests carry the file changes awaiting review. Approval UIs show
+//! them through pluggable [`DiffRenderer`]s: a unified diff, a side-by-side HTML
+//! view, and an entry-level diff of Rhema context files that reports added,
+//! removed and modified entries instead of changed lines. Once a request is
+//! approved the same changes are applied to the working tree.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::error::{ActionError, ActionResult};
+use rhema_action_tool::diff::{self, diff_lines, DiffLine};
+
+/// Built-in unified diff view
+pub const UNIFIED_VIEW: &str = "unified";
+
+/// Built-in side-by-side HTML view
+pub const SIDE_BY_SIDE_VIEW: &str = "side-by-side";
+
+/// Built-in entry-level context diff view
+pub const ENTRY_VIEW: &str = "entries";
+
+/// Longest field value shown in entry-level diffs
+const MAX_VALUE_CHARS: usize = 80;
+
+/// A single file change awaiting approval
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProposedChange {
+    /// Path relative to the repository root
+    pub path: String,
+    /// Content before the change; `None` for new files
+    pub before: Option<String>,
+    /// Content after the change; `None` for deleted files
+    pub after: Option<String>,
+}
+
+/// Kind of a proposed change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Deleted,
+    Modified,
+}
+
+impl ProposedChange {
+    pub fn new(path: impl Into<String>, before: Option<String>, after: Option<String>) -> Self {
+        Self {
+            path: path.into(),
+            before,
+            after,
+        }
+    }
+
+    pub fn kind(&self) -> ChangeKind {
+        match (&self.before, &self.after) {
+            (None, _) => ChangeKind::Added,
+            (_, None) => ChangeKind::Deleted,
+            _ => ChangeKind::Modified,
+        }
+    }
+
+    fn before_text(&self) -> &str {
+        self.before.as_deref().unwrap_or("")
+    }
+
+    fn after_text(&self) -> &str {
+        self.after.as_deref().unwrap_or("")
+    }
+}
+
+/// Apply approved changes under `root`. Every file must still match the
+/// content the change was proposed against; nothing is written otherwise.
+/// Returns the changed paths.
+pub fn apply_changes(root: &Path, changes: &[ProposedChange]) -> ActionResult<Vec<String>> {
+    for change in changes {
+        let target = root.join(&change.path);
+        let current = match std::fs::read_to_string(&target) {
+            Ok(content) => Some(content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(ActionError::file_operation(target, e.to_string())),
+        };
+        if current != change.before {
+            return Err(ActionError::invalid_state(format!(
+                "{} changed since the change was proposed; re-render and review it again",
+                change.path
+            )));
+        }
+    }
+
+    let mut applied = Vec::new();
+    for change in changes {
+        let target = root.join(&change.path);
+        match &change.after {
+            Some(content) => {
+                if let Some(parent) = target.parent() {
+                    std::fs::create_dir_all(parent).map_err(|e| {
+                        ActionError::file_operation(parent.to_path_buf(), e.to_string())
+                    })?;
+                }
+                std::fs::write(&target, content)
+                    .map_err(|e| ActionError::file_operation(target.clone(), e.to_string()))?;
+            }
+            None => std::fs::remove_file(&target)
+                .map_err(|e| ActionError::file_operation(target.clone(), e.to_string()))?,
+        }
+        applied.push(change.path.clone());
+    }
+    Ok(applied)
+}
+
+/// Renders proposed changes into an artifact an approval UI can display
+pub trait DiffRenderer: Send + Sync {
+    /// Name approval UIs request the view by
+    fn name(&self) -> &str;
+
+    /// MIME type of the rendered artifact
+    fn content_type(&self) -> &str;
+
+    fn render(&self, changes: &[ProposedChange]) -> ActionResult<String>;
+}
+
+/// A rendered view of an approval request's changes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenderedArtifact {
+    pub request_id: String,
+    pub view: String,
+    pub content_type: String,
+    pub content: String,
+    pub rendered_at: DateTime<Utc>,
+}
+
+/// Diff views available to approval UIs
+pub struct DiffViewerRegistry {
+    renderers: Vec<Arc<dyn DiffRenderer>>,
+}
+
+impl Default for DiffViewerRegistry {
+    fn default() -> Self {
+        Self {
+            renderers: vec![
+                Arc::new(UnifiedDiffRenderer::default()),
+                Arc::new(SideBySideHtmlRenderer),
+                Arc::new(EntryDiffRenderer::default()),
+            ],
+        }
+    }
+}
+
+impl DiffViewerRegistry {
+    /// Add a view, replacing any view with the same name
+    pub fn register(&mut self, renderer: Arc<dyn DiffRenderer>) {
+        self.renderers.retain(|r| r.name() != renderer.name());
+        self.renderers.push(renderer);
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.renderers
+            .iter()
+            .map(|r| r.name().to_string())
+            .collect()
+    }
+
+    pub fn render(
+        &self,
+        request_id: &str,
+        view: &str,
+        changes: &[ProposedChange],
+    ) -> ActionResult<RenderedArtifact> {
+        let renderer = self
+            .renderers
+            .iter()
+            .find(|r| r.name() == view)
+            .ok_or_else(|| ActionError::not_found(format!("diff view '{}'", view)))?;
+        Ok(RenderedArtifact {
+            request_id: request_id.to_string(),
+            view: view.to_string(),
+            content_type: renderer.content_type().to_string(),
+            content: renderer.render(changes)?,
+            rendered_at: Utc::now(),
+        })
+    }
+}
+
+/// Git-style unified diff
+#[derive(Debug, Clone)]
+pub struct UnifiedDiffRenderer {
+    /// Unchanged lines shown around each change
+    pub context_lines: usize,
+}
+
+impl Default for UnifiedDiffRenderer {
+    fn default() -> Self {
+        Self { context_lines: 3 }
+    }
+}
+
+impl DiffRenderer for UnifiedDiffRenderer {
+    fn name(&self) -> &str {
+        UNIFIED_VIEW
+    }
+
+    fn content_type(&self) -> &str {
+        "text/x-diff"
+    }
+
+    fn render(&self, changes: &[ProposedChange]) -> ActionResult<String> {
+        Ok(changes
+            .iter()
+            .map(|change| unified_diff(change, self.context_lines))
+            .collect())
+    }
+}
+
+/// HTML table per file with the old content on the left and the new on the right
+#[derive(Debug, Clone, Default)]
+pub struct SideBySideHtmlRenderer;
+
+impl DiffRenderer for SideBySideHtmlRenderer {
+    fn name(&self) -> &str {
+        SIDE_BY_SIDE_VIEW
+    }
+
+    fn content_type(&self) -> &str {
+        "text/html"
+    }
+
+    fn render(&self, changes: &[ProposedChange]) -> ActionResult<String> {
+        let mut html = String::from("<div class=\"rhema-diff\">\n");
+        for change in changes {
+            html.push_str(&format!(
+                "<table class=\"rhema-diff-file {}\">\n<caption>{}</caption>\n",
+                kind_label(change.kind()),
+                escape_html(&change.path)
+            ));
+            for (left, right) in side_by_side_rows(change) {
+                html.push_str("<tr>");
+                push_cell(&mut html, left);
+                push_cell(&mut html, right);
+                html.push_str("</tr>\n");
+            }
+            html.push_str("</table>\n");
+        }
+        html.push_str("</div>\n");
+        Ok(html)
+    }
+}
+
+/// Entry-level diff of Rhema context files. Sequences of entries keyed by `id`
+/// are compared entry by entry and field by field; other files fall back to a
+/// unified diff.
+#[derive(Debug, Clone, Default)]
+pub struct EntryDiffRenderer {
+    fallback: UnifiedDiffRenderer,
+}
+
+impl DiffRenderer for EntryDiffRenderer {
+    fn name(&self) -> &str {
+        ENTRY_VIEW
+    }
+
+    fn content_type(&self) -> &str {
+        "text/markdown"
+    }
+
+    fn render(&self, changes: &[ProposedChange]) -> ActionResult<String> {
+        let mut out = String::new();
+        for change in changes {
+            out.push_str(&format!(
+                "### {} ({})\n\n",
+                change.path,
+                kind_label(change.kind())
+            ));
+            match entry_diff(change) {
+                Some(lines) if lines.is_empty() => out.push_str("No entry changes\n"),
+                Some(lines) => {
+                    for line in lines {
+                        out.push_str(&line);
+                        out.push('\n');
+                    }
+                }
+                None => {
+                    out.push_str("```diff\n");
+                    out.push_str(&unified_diff(change, self.fallback.context_lines));
+                    out.push_str("```\n");
+                }
+            }
+            out.push('\n');
+        }
+        Ok(out)
+    }
+}
+
+fn unified_diff(change: &ProposedChange, context_lines: usize) -> String {
+    diff::unified_diff(
+        &change.path,
+        change.before.as_deref(),
+        change.after.as_deref(),
+        context_lines,
+    )
+}
+
+/// One side of a side-by-side row: line number, text and whether it changed
+type Cell<'a> = Option<(usize, &'a str, bool)>;
+
+/// Rows pairing removed lines with the added lines that replace them
+fn side_by_side_rows(change: &ProposedChange) -> Vec<(Cell<'_>, Cell<'_>)> {
+    let ops = diff_lines(change.before_text(), change.after_text());
+    let mut rows = Vec::new();
+    let (mut old_no, mut new_no) = (0, 0);
+    let mut index = 0;
+    while index < ops.len() {
+        if let DiffLine::Context(line) = ops[index] {
+            old_no += 1;
+            new_no += 1;
+            rows.push((Some((old_no, line, false)), Some((new_no, line, false))));
+            index += 1;
+            continue;
+        }
+
+        let mut removed = Vec::new();
+        let mut added = Vec::new();
+        while index < ops.len() {
+            match ops[index] {
+                DiffLine::Removed(line) => {
+                    old_no += 1;
+                    removed.push((old_no, line, true));
+                }
+                DiffLine::Added(line) => {
+                    new_no += 1;
+                    added.push((new_no, line, true));
+                }
+                DiffLine::Context(_) => break,
+            }
+            index += 1;
+        }
+        for row in 0..removed.len().max(added.len()) {
+            rows.push((removed.get(row).copied(), added.get(row).copied()));
+        }
+    }
+    rows
+}
+
+fn push_cell(html: &mut String, cell: Cell) {
+    match cell {
+        Some((number, line, changed)) => html.push_str(&format!(
+            "<td class=\"line-no\">{}</td><td class=\"{}\">{}</td>",
+            number,
+            if changed { "changed" } else { "context" },
+            escape_html(line)
+        )),
+        None => html.push_str("<td class=\"line-no\"></td><td class=\"empty\"></td>"),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn kind_label(kind: ChangeKind) -> &'static str {
+    match kind {
+        ChangeKind::Added => "added",
+        ChangeKind::Deleted => "deleted",
+        ChangeKind::Modified => "modified",
+    }
+}
+
+/// Entry-level diff lines, or `None` when the file is not a YAML context file
+/// holding entries with ids
+fn entry_diff(change: &ProposedChange) -> Option<Vec<String>> {
+    let extension = Path::new(&change.path).extension()?.to_str()?;
+    if extension != "yaml" && extension != "yml" {
+        return None;
+    }
+    let before = entry_collections(change.before.as_deref())?;
+    let after = entry_collections(change.after.as_deref())?;
+    if before.is_empty() && after.is_empty() {
+        return None;
+    }
+
+    let mut lines = Vec::new();
+    let keys: std::collections::BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    let empty = BTreeMap::new();
+    for key in keys {
+        let old = before.get(key).unwrap_or(&empty);
+        let new = after.get(key).unwrap_or(&empty);
+        let mut section = Vec::new();
+
+        for (id, entry) in new {
+            match old.get(id) {
+                None => section.push(format!("- **added** `{}`{}", id, entry_label(entry))),
+                Some(previous) if previous != entry => {
+                    section.push(format!("- **modified** `{}`{}", id, entry_label(entry)));
+                    for (field, from, to) in field_changes(previous, entry) {
+                        section.push(format!("  - `{}`: {} → {}", field, from, to));
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+        for (id, entry) in old {
+            if !new.contains_key(id) {
+                section.push(format!("- **removed** `{}`{}", id, entry_label(entry)));
+            }
+        }
+
+        if !section.is_empty() {
+            lines.push(format!("**{}**", key));
+            lines.append(&mut section);
+        }
+    }
+    Some(lines)
+}
+
+/// Top-level sequences of mappings with an `id`, keyed by collection name and id.
+/// `None` when the content is not YAML.
+fn entry_collections(content: Option<&str>) -> Option<BTreeMap<String, BTreeMap<String, Value>>> {
+    let mut collections = BTreeMap::new();
+    let Some(content) = content else {
+        return Some(collections);
+    };
+    let document: Value = serde_yaml::from_str(content).ok()?;
+    let Value::Mapping(mapping) = document else {
+        return Some(collections);
+    };
+
+    for (key, value) in mapping {
+        let (Some(key), Value::Sequence(items)) = (key.as_str(), value) else {
+            continue;
+        };
+        let entries: BTreeMap<String, Value> = items
+            .into_iter()
+            .filter_map(|item| {
+                let id = item.get("id").and_then(scalar_text)?;
+                Some((id, item))
+            })
+            .collect();
+        if !entries.is_empty() {
+            collections.insert(key.to_string(), entries);
+        }
+    }
+    Some(collections)
+}
+
+fn entry_label(entry: &Value) -> String {
+    ["title", "name", "description"]
+        .iter()
+        .find_map(|field| entry.get(*field).and_then(scalar_text))
+        .map(|label| format!(" {}", truncate(&label)))
+        .unwrap_or_default()
+}
+
+/// Fields that differ between two versions of an entry
+fn field_changes(old: &Value, new: &Value) -> Vec<(String, String, String)> {
+    let (Value::Mapping(old), Value::Mapping(new)) = (old, new) else {
+        return vec![("(entry)".to_string(), show(Some(old)), show(Some(new)))];
+    };
+    let mut fields: Vec<String> = old
+        .keys()
+        .chain(new.keys())
+        .filter_map(scalar_text)
+        .collect();
+    fields.sort();
+    fields.dedup();
+
+    fields
+        .into_iter()
+        .filter_map(|field| {
+            let key = Value::String(field.clone());
+            let (from, to) = (old.get(&key), new.get(&key));
+            (from != to).then(|| (field, show(from), show(to)))
+        })
+        .collect()
+}
+
+fn show(value: Option<&Value>) -> String {
+    match value {
+        None => "_unset_".to_string(),
+        Some(value) => {
+            let text = scalar_text(value).unwrap_or_else(|| {
+                serde_yaml::to_string(value)
+                    .unwrap_or_default()
+                    .trim()
+                    .replace('\n', " ")
+            });
+            format!("`{}`", truncate(&text))
+        }
+    }
+}
+
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(flag) => Some(flag.to_string()),
+        _ => None,
+    }
+}
+
+fn truncate(text: &str) -> String {
+    if text.chars().count() <= MAX_VALUE_CHARS {
+        text.to_string()
+    } else {
+        let mut short: String = text.chars().take(MAX_VALUE_CHARS - 1).collect();
+        short.push('…');
+        short
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_views_render_changes() {
+        let change = ProposedChange::new(
+            ".rhema/todos.yaml",
+            Some("todos:\n- id: t1\n  title: Write docs\n  status: pending\n- id: t2\n  title: Old\n".into()),
+            Some("todos:\n- id: t1\n  title: Write docs\n  status: done\n- id: t3\n  title: New\n".into()),
+        );
+        let registry = DiffViewerRegistry::default();
+
+        let unified = registry
+            .render("r1", UNIFIED_VIEW, &[change.clone()])
+            .unwrap();
+        assert!(unified.content.contains("@@ -1,6 +1,6 @@"));
+        assert!(unified.content.contains("\n-  status: pending\n"));
+        assert!(unified.content.contains("\n+  status: done\n"));
+
+        let html = registry
+            .render("r1", SIDE_BY_SIDE_VIEW, &[change.clone()])
+            .unwrap();
+        assert_eq!(html.content_type, "text/html");
+        assert!(html
+            .content
+            .contains("<td class=\"changed\">  status: done</td>"));
+
+        let entries = registry
+            .render("r1", ENTRY_VIEW, &[change])
+            .unwrap()
+            .content;
+        assert!(entries.contains("- **added** `t3` New"));
+        assert!(entries.contains("- **removed** `t2` Old"));
+        assert!(entries.contains("  - `status`: `pending` → `done`"));
+
+        assert!(registry.render("r1", "missing", &[]).is_err());
+    }
+
+    #[test]
+    fn test_apply_changes_refuses_stale_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "one\n").unwrap();
+        let changes = vec![
+            ProposedChange::new("a.txt", Some("one\n".into()), Some("two\n".into())),
+            ProposedChange::new("nested/b.txt", None, Some("new\n".into())),
+        ];
+
+        std::fs::write(dir.path().join("a.txt"), "edited\n").unwrap();
+        assert!(apply_changes(dir.path(), &changes).is_err());
+        assert!(!dir.path().join("nested/b.txt").exists());
+
+        std::fs::write(dir.path().join("a.txt"), "one\n").unwrap();
+        let applied = apply_changes(dir.path(), &changes).unwrap();
+        assert_eq!(applied, vec!["a.txt", "nested/b.txt"]);
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("a.txt")).unwrap(),
+            "two\n"
+        );
+    }
+}
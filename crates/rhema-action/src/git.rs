@@ -14,12 +14,20 @@
  * limitations under the License.
  */
 
-use git2::{BranchType, DiffFormat, DiffOptions, Repository, Signature};
+use git2::{BranchType, DiffFormat, DiffOptions, IndexAddOption, Repository, Signature};
+use rhema_git::git::workflow::{default_git_flow_config, BranchConventions};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
-use tracing::info;
+use std::process::Command;
+use tracing::{info, warn};
 
 use crate::error::{ActionError, ActionResult};
-use crate::schema::ActionIntent;
+use crate::pipeline::ExecutionResult;
+use crate::schema::{ActionIntent, ActionType, SafetyLevel};
+
+/// Section of `.rhema/repository.yaml` configuring git delivery of intents
+pub const ACTION_GIT_CONFIG_SECTION: &str = "action_git";
 
 /// Git operation result
 #[derive(Debug, Clone)]
@@ -53,12 +61,176 @@ pub struct GitCommitInfo {
     pub files_changed: Vec<String>,
 }
 
+/// How an executed intent is delivered through git
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GitDeliveryPolicy {
+    /// Run the intent on its own branch
+    pub isolated_branch: bool,
+
+    /// Commit the tool changes on the intent branch
+    pub commit: bool,
+
+    /// Push the intent branch to the remote
+    pub push: bool,
+
+    /// Open a pull request for the intent branch
+    pub pull_request: bool,
+
+    /// Open the pull request as a draft
+    pub draft: bool,
+}
+
+impl GitDeliveryPolicy {
+    /// Whether the policy asks for any git operation at all
+    pub fn is_enabled(&self) -> bool {
+        self.isolated_branch || self.commit || self.push || self.pull_request
+    }
+}
+
+/// Git delivery configuration for action intents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ActionGitConfig {
+    /// Branch name template. Supports `{prefix}`, `{feature_prefix}`,
+    /// `{hotfix_prefix}`, `{action_type}`, `{intent_id}` and `{safety_level}`
+    pub branch_template: String,
+
+    /// Branch conventions of the repository workflow; defaults to GitFlow
+    pub branch_conventions: Option<BranchConventions>,
+
+    /// Pull request base branch; defaults to the workflow's develop or main branch
+    pub base_branch: Option<String>,
+
+    /// Remote to push intent branches to
+    pub remote: String,
+
+    /// Delivery policy per safety level; missing levels do nothing
+    pub policies: BTreeMap<SafetyLevel, GitDeliveryPolicy>,
+}
+
+impl Default for ActionGitConfig {
+    fn default() -> Self {
+        Self {
+            branch_template: "{prefix}{action_type}-{intent_id}".to_string(),
+            branch_conventions: None,
+            base_branch: None,
+            remote: "origin".to_string(),
+            policies: BTreeMap::new(),
+        }
+    }
+}
+
+impl ActionGitConfig {
+    /// Load the configuration from the repository config and policy bundle,
+    /// falling back to defaults
+    pub fn load(repo_root: &Path) -> ActionResult<Self> {
+        let value = rhema_core::policy::repository_config(repo_root)
+            .map_err(|e| ActionError::configuration(e.to_string()))?;
+        match value.get(ACTION_GIT_CONFIG_SECTION) {
+            Some(section) => serde_yaml::from_value(section.clone()).map_err(|e| {
+                ActionError::configuration(format!(
+                    "Invalid {} section in the repository config: {}",
+                    ACTION_GIT_CONFIG_SECTION, e
+                ))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Delivery policy for a safety level
+    pub fn policy_for(&self, level: &SafetyLevel) -> GitDeliveryPolicy {
+        self.policies.get(level).cloned().unwrap_or_default()
+    }
+
+    /// Branch conventions of the configured workflow
+    pub fn conventions(&self) -> BranchConventions {
+        self.branch_conventions
+            .clone()
+            .unwrap_or_else(|| default_git_flow_config().branch_conventions)
+    }
+
+    /// Base branch pull requests are opened against
+    pub fn base_branch(&self) -> String {
+        self.base_branch.clone().unwrap_or_else(|| {
+            let conventions = self.conventions();
+            conventions
+                .develop_branch
+                .unwrap_or(conventions.main_branch)
+        })
+    }
+
+    /// Render the branch name for an intent from the template
+    pub fn branch_name(&self, intent: &ActionIntent) -> String {
+        let conventions = self.conventions();
+        let prefix = match intent.action_type {
+            ActionType::BugFix | ActionType::Security => &conventions.hotfix_prefix,
+            _ => &conventions.feature_prefix,
+        };
+
+        let name = self
+            .branch_template
+            .replace("{prefix}", prefix)
+            .replace("{feature_prefix}", &conventions.feature_prefix)
+            .replace("{hotfix_prefix}", &conventions.hotfix_prefix)
+            .replace(
+                "{action_type}",
+                &intent.action_type.to_string().to_lowercase(),
+            )
+            .replace("{intent_id}", &intent.id)
+            .replace("{safety_level}", &intent.safety_level.to_string());
+        sanitize_branch_name(&name)
+    }
+}
+
+/// Replace characters git does not accept in reference names
+fn sanitize_branch_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| match c {
+            c if c.is_whitespace() || c.is_control() => '-',
+            '~' | '^' | ':' | '?' | '*' | '[' | '\\' => '-',
+            c => c,
+        })
+        .collect();
+    while sanitized.contains("..") {
+        sanitized = sanitized.replace("..", ".");
+    }
+    sanitized
+        .trim_matches(|c| c == '/' || c == '.')
+        .trim_end_matches(".lock")
+        .to_string()
+}
+
+/// Outcome of delivering an intent through git
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IntentDelivery {
+    /// Branch the intent ran on
+    pub branch: String,
+
+    /// Branch that was checked out before the intent started
+    pub original_branch: String,
+
+    /// Commit holding the tool changes, if anything was committed
+    pub commit_hash: Option<String>,
+
+    /// Whether the branch was pushed
+    pub pushed: bool,
+
+    /// URL of the opened pull request
+    pub pull_request_url: Option<String>,
+
+    /// Steps to undo the intent
+    pub rollback_instructions: Vec<String>,
+}
+
 /// Git integration for action protocol
 pub struct ActionGitIntegration {
     repository: Option<Repository>,
     working_directory: String,
     branch_prefix: String,
     commit_prefix: String,
+    config: ActionGitConfig,
 }
 
 impl ActionGitIntegration {
@@ -80,12 +252,25 @@ impl ActionGitIntegration {
             working_directory,
             branch_prefix: "action/".to_string(),
             commit_prefix: "action: ".to_string(),
+            config: ActionGitConfig::default(),
         };
 
         info!("Action Git Integration initialized successfully");
         Ok(integration)
     }
 
+    /// Create a Git integration that delivers intents according to `config`
+    pub async fn with_config(config: ActionGitConfig) -> ActionResult<Self> {
+        let mut integration = Self::new().await?;
+        integration.config = config;
+        Ok(integration)
+    }
+
+    /// Delivery configuration in use
+    pub fn config(&self) -> &ActionGitConfig {
+        &self.config
+    }
+
     /// Initialize the git integration (stub)
     pub async fn initialize() -> ActionResult<()> {
         info!("ActionGitIntegration initialized (stub)");
@@ -287,18 +472,26 @@ impl ActionGitIntegration {
         }
     }
 
-    /// Create a pull request
+    /// Create a pull request from the current branch
     pub fn create_pull_request(
         &self,
         intent: &ActionIntent,
         base_branch: &str,
     ) -> ActionResult<String> {
-        if let Some(repo) = &self.repository {
+        if self.repository.is_some() {
             let current_branch = self.get_current_branch()?;
+            let body = format!(
+                "{}\n\nIntent ID: `{}`\nAction Type: {}\nSafety Level: {}",
+                intent.description, intent.id, intent.action_type, intent.safety_level
+            );
 
-            // For now, just return a placeholder PR URL
-            // In a real implementation, this would use GitHub/GitLab API
-            let pr_url = format!("https://github.com/example/repo/pull/{}", intent.id);
+            let pr_url = self.open_pull_request(
+                &self.commit_subject(intent),
+                &body,
+                base_branch,
+                &current_branch,
+                false,
+            )?;
 
             info!("Created pull request: {}", pr_url);
             Ok(pr_url)
@@ -761,6 +954,319 @@ impl ActionGitIntegration {
             Err(ActionError::git("push", "Not a Git repository"))
         }
     }
+
+    /// Prepare git delivery for an intent before its tools run.
+    ///
+    /// Checks out the intent branch when the intent's safety level asks for an
+    /// isolated branch. Returns `None` when the policy does not use git.
+    pub async fn start_intent(
+        &self,
+        intent: &ActionIntent,
+    ) -> ActionResult<Option<IntentDelivery>> {
+        let policy = self.config.policy_for(&intent.safety_level);
+        if !policy.is_enabled() {
+            return Ok(None);
+        }
+
+        let original_branch = self.get_current_branch()?;
+        let branch = if policy.isolated_branch {
+            let branch_name = self.config.branch_name(intent);
+            self.switch_to_new_branch(&branch_name)?;
+            info!("Intent {} runs on branch {}", intent.id, branch_name);
+            branch_name
+        } else {
+            original_branch.clone()
+        };
+
+        Ok(Some(IntentDelivery {
+            branch,
+            original_branch,
+            commit_hash: None,
+            pushed: false,
+            pull_request_url: None,
+            rollback_instructions: Vec::new(),
+        }))
+    }
+
+    /// Create a branch at HEAD and point HEAD at it, keeping the working tree
+    pub fn switch_to_new_branch(&self, branch_name: &str) -> ActionResult<()> {
+        let repo = self
+            .repository
+            .as_ref()
+            .ok_or_else(|| ActionError::git("create_branch", "Not a Git repository"))?;
+
+        let commit = repo
+            .head()
+            .and_then(|head| head.peel_to_commit())
+            .map_err(|e| {
+                ActionError::git("create_branch", format!("Failed to resolve HEAD: {}", e))
+            })?;
+
+        repo.branch(branch_name, &commit, false).map_err(|e| {
+            ActionError::git(
+                "create_branch",
+                format!("Failed to create branch {}: {}", branch_name, e),
+            )
+        })?;
+
+        repo.set_head(&format!("refs/heads/{}", branch_name))
+            .map_err(|e| ActionError::git("set_head", format!("Failed to set HEAD: {}", e)))
+    }
+
+    /// Commit, push and open a pull request for an executed intent, as far as
+    /// the intent's safety level allows. Failed executions are left uncommitted.
+    pub async fn finish_intent(
+        &self,
+        intent: &ActionIntent,
+        mut delivery: IntentDelivery,
+        result: &ExecutionResult,
+    ) -> ActionResult<IntentDelivery> {
+        let policy = self.config.policy_for(&intent.safety_level);
+
+        if !result.success {
+            warn!(
+                "Intent {} failed; leaving changes uncommitted on {}",
+                intent.id, delivery.branch
+            );
+        } else if policy.commit || policy.push || policy.pull_request {
+            delivery.commit_hash = self.commit_intent_changes(intent, result)?;
+
+            if delivery.commit_hash.is_some() && (policy.push || policy.pull_request) {
+                self.push_to_remote(&self.config.remote, &delivery.branch)
+                    .await?;
+                delivery.pushed = true;
+            }
+
+            if delivery.pushed && policy.pull_request {
+                let url = self.open_pull_request(
+                    &self.commit_subject(intent),
+                    &self.pull_request_body(intent, result, &delivery),
+                    &self.config.base_branch(),
+                    &delivery.branch,
+                    policy.draft,
+                )?;
+                info!("Opened pull request for intent {}: {}", intent.id, url);
+                delivery.pull_request_url = Some(url);
+            }
+        }
+
+        delivery.rollback_instructions = self.rollback_instructions(&delivery);
+        Ok(delivery)
+    }
+
+    /// Stage everything under the intent scope and commit it with a structured
+    /// message. Returns `None` when the tools changed nothing.
+    pub(crate) fn commit_intent_changes(
+        &self,
+        intent: &ActionIntent,
+        result: &ExecutionResult,
+    ) -> ActionResult<Option<String>> {
+        let repo = self
+            .repository
+            .as_ref()
+            .ok_or_else(|| ActionError::git("commit", "Not a Git repository"))?;
+        let workdir = repo
+            .workdir()
+            .unwrap_or_else(|| Path::new(&self.working_directory));
+
+        let pathspecs: Vec<String> = if intent.scope.is_empty() {
+            vec![".".to_string()]
+        } else {
+            intent
+                .scope
+                .iter()
+                .map(|path| {
+                    Path::new(path)
+                        .strip_prefix(workdir)
+                        .map(|relative| relative.to_string_lossy().to_string())
+                        .unwrap_or_else(|_| path.clone())
+                })
+                .collect()
+        };
+
+        let mut index = repo
+            .index()
+            .map_err(|e| ActionError::git("get_index", format!("Failed to get index: {}", e)))?;
+        index
+            .add_all(pathspecs.iter(), IndexAddOption::DEFAULT, None)
+            .and_then(|_| index.update_all(pathspecs.iter(), None))
+            .and_then(|_| index.write())
+            .map_err(|e| {
+                ActionError::git("stage_files", format!("Failed to stage scope: {}", e))
+            })?;
+        let tree_id = index
+            .write_tree()
+            .map_err(|e| ActionError::git("write_tree", format!("Failed to write tree: {}", e)))?;
+
+        let parent = repo
+            .head()
+            .and_then(|head| head.peel_to_commit())
+            .map_err(|e| ActionError::git("get_head", format!("Failed to resolve HEAD: {}", e)))?;
+        if parent.tree_id() == tree_id {
+            info!("Intent {} produced no changes to commit", intent.id);
+            return Ok(None);
+        }
+
+        let tree = repo
+            .find_tree(tree_id)
+            .map_err(|e| ActionError::git("find_tree", format!("Failed to find tree: {}", e)))?;
+        let signature =
+            Signature::now("Rhema Action Protocol", "action@rhema.dev").map_err(|e| {
+                ActionError::git(
+                    "create_signature",
+                    format!("Failed to create signature: {}", e),
+                )
+            })?;
+
+        let commit_id = repo
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                &self.intent_commit_message(intent, result),
+                &tree,
+                &[&parent],
+            )
+            .map_err(|e| ActionError::git("commit", format!("Failed to commit: {}", e)))?;
+
+        Ok(Some(commit_id.to_string()))
+    }
+
+    /// First line of the commit message and pull request title for an intent
+    pub(crate) fn commit_subject(&self, intent: &ActionIntent) -> String {
+        let summary = intent.description.lines().next().unwrap_or_default().trim();
+        let subject = format!("{}{}", self.commit_prefix, summary);
+        if subject.chars().count() > 72 {
+            format!("{}...", subject.chars().take(69).collect::<String>())
+        } else {
+            subject
+        }
+    }
+
+    /// Structured commit message listing the tool changes and intent trailers
+    pub fn intent_commit_message(&self, intent: &ActionIntent, result: &ExecutionResult) -> String {
+        let mut message = self.commit_subject(intent);
+        message.push_str("\n\n");
+
+        if !result.changes.is_empty() {
+            for change in &result.changes {
+                message.push_str(&format!("- {}\n", change));
+            }
+            message.push('\n');
+        }
+
+        message.push_str(&format!("Intent-Id: {}\n", intent.id));
+        message.push_str(&format!("Action-Type: {}\n", intent.action_type));
+        message.push_str(&format!("Safety-Level: {}\n", intent.safety_level));
+        if !intent.scope.is_empty() {
+            message.push_str(&format!("Scope: {}\n", intent.scope.join(", ")));
+        }
+        message
+    }
+
+    /// Pull request body with the intent, diagnostics summary and rollback steps
+    pub fn pull_request_body(
+        &self,
+        intent: &ActionIntent,
+        result: &ExecutionResult,
+        delivery: &IntentDelivery,
+    ) -> String {
+        let mut body = String::from("## Intent\n\n");
+        body.push_str(&format!("{}\n\n", intent.description));
+        body.push_str(&format!(
+            "- **Intent ID:** `{}`\n- **Action type:** {}\n- **Safety level:** {}\n",
+            intent.id, intent.action_type, intent.safety_level
+        ));
+        if !intent.scope.is_empty() {
+            body.push_str(&format!("- **Scope:** {}\n", intent.scope.join(", ")));
+        }
+
+        body.push_str("\n## Diagnostics\n\n");
+        body.push_str(&format!(
+            "{} changes, {} errors, {} warnings in {:?}\n",
+            result.changes.len(),
+            result.errors.len(),
+            result.warnings.len(),
+            result.duration
+        ));
+        for change in &result.changes {
+            body.push_str(&format!("\n- {}", change));
+        }
+        for warning in &result.warnings {
+            body.push_str(&format!("\n- ⚠️ {}", warning));
+        }
+
+        body.push_str("\n\n## Rollback\n\n");
+        for step in self.rollback_instructions(delivery) {
+            body.push_str(&format!("- {}\n", step));
+        }
+        body
+    }
+
+    /// Steps that undo a delivered intent
+    fn rollback_instructions(&self, delivery: &IntentDelivery) -> Vec<String> {
+        let mut steps = Vec::new();
+        let isolated = delivery.branch != delivery.original_branch;
+
+        if delivery.pushed {
+            steps.push(
+                "Close the pull request without merging, or revert the merge commit if it was already merged"
+                    .to_string(),
+            );
+            steps.push(format!(
+                "`git push {} --delete {}`",
+                self.config.remote, delivery.branch
+            ));
+        }
+        if isolated {
+            steps.push(format!(
+                "`git checkout {} && git branch -D {}`",
+                delivery.original_branch, delivery.branch
+            ));
+        } else if let Some(commit) = &delivery.commit_hash {
+            steps.push(format!("`git revert {}`", commit));
+        } else {
+            steps.push("`git checkout -- .` to discard the uncommitted tool changes".to_string());
+        }
+        steps
+    }
+
+    /// Open a pull request with the GitHub CLI and return its URL
+    pub(crate) fn open_pull_request(
+        &self,
+        title: &str,
+        body: &str,
+        base_branch: &str,
+        head_branch: &str,
+        draft: bool,
+    ) -> ActionResult<String> {
+        let mut command = Command::new("gh");
+        command
+            .current_dir(&self.working_directory)
+            .args(["pr", "create", "--title", title, "--body", body])
+            .args(["--base", base_branch, "--head", head_branch]);
+        if draft {
+            command.arg("--draft");
+        }
+
+        let output = command
+            .output()
+            .map_err(|e| ActionError::external_tool("gh", format!("Failed to run gh: {}", e)))?;
+        if !output.status.success() {
+            return Err(ActionError::external_tool(
+                "gh",
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .rev()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .map(str::to_string)
+            .ok_or_else(|| ActionError::external_tool("gh", "No pull request URL in output"))
+    }
 }
 
 /// Commit information
@@ -838,4 +1344,105 @@ mod tests {
         assert!(current_branch_result.is_ok() || current_branch_result.is_err());
         assert!(current_commit_result.is_ok() || current_commit_result.is_err());
     }
+
+    fn execution_result(changes: &[&str]) -> ExecutionResult {
+        ExecutionResult {
+            success: true,
+            changes: changes.iter().map(|c| c.to_string()).collect(),
+            errors: vec![],
+            warnings: vec![],
+            duration: std::time::Duration::from_millis(10),
+            delivery: None,
+            diff: None,
+        }
+    }
+
+    #[test]
+    fn test_branch_name_follows_workflow_conventions() {
+        let config = ActionGitConfig::default();
+        let feature = ActionIntent::new(
+            "intent 42",
+            ActionType::Refactor,
+            "Rename module",
+            vec![],
+            SafetyLevel::Low,
+        );
+        let fix = ActionIntent::new(
+            "intent-43",
+            ActionType::BugFix,
+            "Fix parser",
+            vec![],
+            SafetyLevel::High,
+        );
+
+        assert_eq!(config.branch_name(&feature), "feature/refactor-intent-42");
+        assert_eq!(config.branch_name(&fix), "hotfix/bugfix-intent-43");
+        assert_eq!(config.base_branch(), "develop");
+        assert!(!config.policy_for(&SafetyLevel::Critical).is_enabled());
+    }
+
+    #[tokio::test]
+    async fn test_intent_runs_on_isolated_branch_and_commits() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        std::fs::write(temp_dir.path().join("lib.rs"), "fn old() {}\n").unwrap();
+        {
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new("lib.rs")).unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let signature = Signature::now("Test", "test@example.com").unwrap();
+            repo.commit(Some("HEAD"), &signature, &signature, "initial", &tree, &[])
+                .unwrap();
+        }
+        let original_branch = repo.head().unwrap().shorthand().unwrap().to_string();
+
+        let mut config = ActionGitConfig::default();
+        config.policies.insert(
+            SafetyLevel::Medium,
+            GitDeliveryPolicy {
+                isolated_branch: true,
+                commit: true,
+                ..Default::default()
+            },
+        );
+        let integration = ActionGitIntegration {
+            repository: Some(repo),
+            working_directory: temp_dir.path().to_string_lossy().to_string(),
+            branch_prefix: "action/".to_string(),
+            commit_prefix: "action: ".to_string(),
+            config,
+        };
+        let intent = ActionIntent::new(
+            "rename-001",
+            ActionType::Refactor,
+            "Rename old to new",
+            vec!["lib.rs".to_string()],
+            SafetyLevel::Medium,
+        );
+
+        let delivery = integration.start_intent(&intent).await.unwrap().unwrap();
+        assert_eq!(delivery.branch, "feature/refactor-rename-001");
+        assert_eq!(integration.get_current_branch().unwrap(), delivery.branch);
+
+        std::fs::write(temp_dir.path().join("lib.rs"), "fn new() {}\n").unwrap();
+        let result = execution_result(&["Renamed old to new in lib.rs"]);
+        let delivery = integration
+            .finish_intent(&intent, delivery, &result)
+            .await
+            .unwrap();
+
+        let commit_hash = delivery.commit_hash.clone().unwrap();
+        let repo = integration.repository.as_ref().unwrap();
+        let commit = repo
+            .find_commit(git2::Oid::from_str(&commit_hash).unwrap())
+            .unwrap();
+        let message = commit.message().unwrap();
+        assert!(message.starts_with("action: Rename old to new\n"));
+        assert!(message.contains("- Renamed old to new in lib.rs"));
+        assert!(message.contains("Intent-Id: rename-001"));
+        assert!(!delivery.pushed);
+        assert!(delivery.pull_request_url.is_none());
+        assert!(delivery.rollback_instructions[0]
+            .contains(&format!("git checkout {}", original_branch)));
+    }
 }
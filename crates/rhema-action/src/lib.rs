@@ -21,14 +21,21 @@
 //! to include a comprehensive "action" layer with safety controls, validation pipelines,
 //! and human oversight.
 
+pub mod approval;
+pub mod attestation;
+pub mod audit;
 pub mod cli;
+pub mod diff_view;
 pub mod error;
 pub mod git;
+pub mod orchestration;
 pub mod pipeline;
 pub mod rollback;
 pub mod schema;
+pub mod tool_cache;
 pub mod tools;
 pub mod validation;
+pub mod worktree;
 
 // Re-export shared types
 pub use rhema_action_tool::{
@@ -37,8 +44,14 @@ pub use rhema_action_tool::{
 };
 
 // Re-export internal types
+pub use approval::{ApprovalStatus, ApprovalWorkflow, ReviewSubmission, ReviewVerdict};
+pub use attestation::{ActionAttestor, AttestationVerification, Envelope, Statement};
+pub use audit::{ActionAuditTrail, AuditEntry, AuditEvent, FileComment};
+pub use diff_view::{DiffRenderer, DiffViewerRegistry, ProposedChange, RenderedArtifact};
 pub use error::ActionError as LocalActionError;
+pub use orchestration::{OrchestrationPlan, OrchestrationReport, PrStrategy, RefactorOrchestrator};
 pub use schema::{ActionIntent as ActionConfig, ActionType, ApprovalWorkflow as ActionContext};
+pub use tool_cache::{ToolCacheConfig, ToolCacheStats, ToolResultCache};
 pub use tools::ToolRegistry;
 
 use anyhow::Result;
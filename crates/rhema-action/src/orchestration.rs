@@ -0,0 +1,704 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Orchestration of repository-wide refactors.
+//!
+//! A large intent is split into one sub-intent per scope it touches. The
+//! sub-intents run in the dependency order of the scope graph, so a scope is
+//! refactored after the scopes it depends on, and their diagnostics are
+//! aggregated into one report. Delivery follows the git policy of the parent
+//! intent's safety level, either as one combined pull request with a commit
+//! per scope or as a stack of pull requests, one per scope.
+
+use clap::ValueEnum;
+use rhema_core::scope::{discover_scopes, Scope};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Component, Path, PathBuf};
+use std::time::Instant;
+use tracing::{info, warn};
+
+use crate::error::{ActionError, ActionResult};
+use crate::git::{ActionGitConfig, ActionGitIntegration, IntentDelivery};
+use crate::pipeline::{ActionSafetyPipeline, ExecutionResult};
+use crate::schema::ActionIntent;
+
+/// Name of the sub-intent covering paths outside every scope
+pub const UNSCOPED: &str = "(unscoped)";
+
+/// How the sub-intents of an orchestrated refactor are delivered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum PrStrategy {
+    /// One branch and pull request with a commit per scope
+    #[default]
+    Combined,
+    /// A branch and pull request per scope, each based on the previous one
+    Stacked,
+}
+
+/// The part of an orchestrated refactor confined to one scope
+#[derive(Debug, Clone)]
+pub struct SubIntent {
+    /// Scope name, or [`UNSCOPED`]
+    pub scope: String,
+
+    /// Scopes of the same refactor this scope depends on
+    pub depends_on: Vec<String>,
+
+    /// Intent limited to the paths of this scope
+    pub intent: ActionIntent,
+}
+
+/// A refactor split into per-scope sub-intents and ordered into waves
+#[derive(Debug, Clone)]
+pub struct OrchestrationPlan {
+    pub parent: ActionIntent,
+    pub sub_intents: Vec<SubIntent>,
+
+    /// Indices into `sub_intents`; a wave only depends on earlier waves
+    pub waves: Vec<Vec<usize>>,
+}
+
+impl OrchestrationPlan {
+    /// Plan a refactor against the scopes discovered in `repo_root`
+    pub fn for_repository(parent: &ActionIntent, repo_root: &Path) -> ActionResult<Self> {
+        let scopes = discover_scopes(repo_root)
+            .map_err(|e| ActionError::configuration(format!("Failed to discover scopes: {}", e)))?;
+        Self::split(parent, repo_root, &scopes)
+    }
+
+    /// Split `parent` into one sub-intent per scope owning part of its paths.
+    /// Paths containing nested scopes are divided so every file is handled by
+    /// its deepest scope.
+    pub fn split(parent: &ActionIntent, repo_root: &Path, scopes: &[Scope]) -> ActionResult<Self> {
+        let scope_dirs: Vec<PathBuf> = scopes
+            .iter()
+            .map(|scope| scope.path.parent().unwrap_or(&scope.path).to_path_buf())
+            .collect();
+
+        let mut owned: BTreeMap<Option<usize>, Vec<PathBuf>> = BTreeMap::new();
+        let targets = if parent.scope.is_empty() {
+            vec![".".to_string()]
+        } else {
+            parent.scope.clone()
+        };
+        for target in &targets {
+            let path = normalize(&repo_root.join(target));
+            if !path.exists() {
+                return Err(ActionError::validation(format!(
+                    "Intent path {} does not exist",
+                    target
+                )));
+            }
+            partition(
+                &path,
+                nearest_scope(&path, &scope_dirs),
+                &scope_dirs,
+                &mut owned,
+            )?;
+        }
+
+        let units: Vec<Option<usize>> = owned.keys().copied().collect();
+        let mut sub_intents = Vec::new();
+        for (owner, paths) in &owned {
+            let scope = owner
+                .map(|index| scopes[index].definition.name.clone())
+                .unwrap_or_else(|| UNSCOPED.to_string());
+
+            let mut depends_on = Vec::new();
+            if let Some(index) = owner {
+                for dependency in scopes[*index].get_dependency_paths() {
+                    let resolved =
+                        resolve_dependency(&dependency, *index, scopes, &scope_dirs, repo_root);
+                    if let Some(dep) = resolved.filter(|dep| units.contains(&Some(*dep))) {
+                        let name = scopes[dep].definition.name.clone();
+                        if dep != *index && !depends_on.contains(&name) {
+                            depends_on.push(name);
+                        }
+                    }
+                }
+            }
+
+            let mut intent = parent.clone();
+            intent.id = sub_intent_id(&parent.id, &scope);
+            intent.description = format!("[{}] {}", scope, parent.description);
+            intent.scope = paths
+                .iter()
+                .map(|path| match path.strip_prefix(repo_root) {
+                    Ok(relative) if relative.as_os_str().is_empty() => ".".to_string(),
+                    Ok(relative) => relative.to_string_lossy().to_string(),
+                    Err(_) => path.to_string_lossy().to_string(),
+                })
+                .collect();
+            intent.dependencies = (!depends_on.is_empty()).then(|| {
+                depends_on
+                    .iter()
+                    .map(|dep| sub_intent_id(&parent.id, dep))
+                    .collect()
+            });
+            intent
+                .metadata
+                .get_or_insert_with(Default::default)
+                .insert("orchestrated_by".to_string(), parent.id.clone().into());
+
+            sub_intents.push(SubIntent {
+                scope,
+                depends_on,
+                intent,
+            });
+        }
+
+        let waves = schedule(&sub_intents)?;
+        Ok(Self {
+            parent: parent.clone(),
+            sub_intents,
+            waves,
+        })
+    }
+}
+
+/// Group sub-intents into waves whose dependencies all ran in earlier waves
+fn schedule(sub_intents: &[SubIntent]) -> ActionResult<Vec<Vec<usize>>> {
+    let mut done: HashSet<&str> = HashSet::new();
+    let mut remaining: Vec<usize> = (0..sub_intents.len()).collect();
+    let mut waves = Vec::new();
+
+    while !remaining.is_empty() {
+        let (mut ready, blocked): (Vec<usize>, Vec<usize>) =
+            remaining.into_iter().partition(|&index| {
+                sub_intents[index]
+                    .depends_on
+                    .iter()
+                    .all(|dep| done.contains(dep.as_str()))
+            });
+        if ready.is_empty() {
+            let cycle: Vec<&str> = blocked
+                .iter()
+                .map(|&index| sub_intents[index].scope.as_str())
+                .collect();
+            return Err(ActionError::validation(format!(
+                "Circular dependency between scopes: {}",
+                cycle.join(", ")
+            )));
+        }
+        ready.sort_by(|a, b| sub_intents[*a].scope.cmp(&sub_intents[*b].scope));
+        done.extend(ready.iter().map(|&index| sub_intents[index].scope.as_str()));
+        waves.push(ready);
+        remaining = blocked;
+    }
+    Ok(waves)
+}
+
+/// Assign `path` to `owner`, descending into directories that contain nested
+/// scopes. Hidden entries such as `.git` and `.rhema` are skipped while
+/// descending.
+fn partition(
+    path: &Path,
+    owner: Option<usize>,
+    scope_dirs: &[PathBuf],
+    owned: &mut BTreeMap<Option<usize>, Vec<PathBuf>>,
+) -> ActionResult<()> {
+    let owner = scope_dirs.iter().position(|dir| dir == path).or(owner);
+    let has_nested = path.is_dir()
+        && scope_dirs
+            .iter()
+            .any(|dir| dir != path && dir.starts_with(path));
+    if !has_nested {
+        owned.entry(owner).or_default().push(path.to_path_buf());
+        return Ok(());
+    }
+
+    let mut entries: Vec<PathBuf> = std::fs::read_dir(path)
+        .map_err(|e| ActionError::file_operation(path.to_path_buf(), e.to_string()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|entry| {
+            !entry
+                .file_name()
+                .and_then(|name| name.to_str())
+                .map_or(false, |name| name.starts_with('.'))
+        })
+        .collect();
+    entries.sort();
+    for entry in entries {
+        partition(&entry, owner, scope_dirs, owned)?;
+    }
+    Ok(())
+}
+
+/// Index of the deepest scope directory containing `path`
+fn nearest_scope(path: &Path, scope_dirs: &[PathBuf]) -> Option<usize> {
+    scope_dirs
+        .iter()
+        .enumerate()
+        .filter(|(_, dir)| path.starts_with(dir))
+        .max_by_key(|(_, dir)| dir.components().count())
+        .map(|(index, _)| index)
+}
+
+/// Resolve a scope dependency given as a scope name, a path from the
+/// repository root or a path relative to the depending scope
+fn resolve_dependency(
+    dependency: &str,
+    from: usize,
+    scopes: &[Scope],
+    scope_dirs: &[PathBuf],
+    repo_root: &Path,
+) -> Option<usize> {
+    if let Some(index) = scopes
+        .iter()
+        .position(|scope| scope.definition.name == dependency)
+    {
+        return Some(index);
+    }
+    let candidates = [
+        normalize(&repo_root.join(dependency)),
+        normalize(&scope_dirs[from].join(dependency)),
+    ];
+    scopes.iter().enumerate().position(|(index, scope)| {
+        candidates
+            .iter()
+            .any(|candidate| *candidate == scope_dirs[index] || *candidate == scope.path)
+    })
+}
+
+/// Resolve `.` and `..` without touching the filesystem
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+fn sub_intent_id(parent_id: &str, scope: &str) -> String {
+    let slug: String = scope
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("{}-{}", parent_id, slug.trim_matches('-'))
+}
+
+/// What happened to a sub-intent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubIntentStatus {
+    Succeeded,
+    Failed,
+    /// Not run because a scope it depends on did not succeed
+    Skipped,
+}
+
+/// Diagnostics and delivery of one sub-intent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubIntentOutcome {
+    pub scope: String,
+    pub intent_id: String,
+    pub wave: usize,
+    pub status: SubIntentStatus,
+    pub changes: Vec<String>,
+    pub errors: Vec<String>,
+    pub warnings: Vec<String>,
+    pub duration_ms: u64,
+    pub branch: Option<String>,
+    pub commit_hash: Option<String>,
+    pub pull_request_url: Option<String>,
+}
+
+/// Aggregated result of an orchestrated refactor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrchestrationReport {
+    pub intent_id: String,
+    pub strategy: PrStrategy,
+    /// Outcomes in execution order
+    pub outcomes: Vec<SubIntentOutcome>,
+    /// Opened pull requests, bottom of the stack first
+    pub pull_requests: Vec<String>,
+    pub duration_ms: u64,
+}
+
+impl OrchestrationReport {
+    pub fn success(&self) -> bool {
+        self.outcomes
+            .iter()
+            .all(|outcome| outcome.status == SubIntentStatus::Succeeded)
+    }
+
+    pub fn count(&self, status: SubIntentStatus) -> usize {
+        self.outcomes
+            .iter()
+            .filter(|outcome| outcome.status == status)
+            .count()
+    }
+
+    /// Per-scope diagnostics as markdown, used in the combined pull request
+    pub fn diagnostics_markdown(&self) -> String {
+        let mut markdown = String::from(
+            "| Scope | Status | Changes | Errors | Warnings |\n|---|---|---|---|---|\n",
+        );
+        for outcome in &self.outcomes {
+            let status = match outcome.status {
+                SubIntentStatus::Succeeded => "✅ succeeded",
+                SubIntentStatus::Failed => "❌ failed",
+                SubIntentStatus::Skipped => "⏭️ skipped",
+            };
+            markdown.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n",
+                outcome.scope,
+                status,
+                outcome.changes.len(),
+                outcome.errors.len(),
+                outcome.warnings.len()
+            ));
+        }
+        for outcome in &self.outcomes {
+            if outcome.errors.is_empty() && outcome.warnings.is_empty() {
+                continue;
+            }
+            markdown.push_str(&format!("\n### {}\n\n", outcome.scope));
+            for error in &outcome.errors {
+                markdown.push_str(&format!("- ❌ {}\n", error));
+            }
+            for warning in &outcome.warnings {
+                markdown.push_str(&format!("- ⚠️ {}\n", warning));
+            }
+        }
+        markdown
+    }
+}
+
+/// Runs an orchestration plan through the safety pipeline.
+///
+/// Sub-intents run one at a time, wave by wave, because they share the
+/// working tree and HEAD.
+pub struct RefactorOrchestrator {
+    pipeline: ActionSafetyPipeline,
+    repo_root: PathBuf,
+    strategy: PrStrategy,
+}
+
+impl RefactorOrchestrator {
+    pub fn new(pipeline: ActionSafetyPipeline, repo_root: impl Into<PathBuf>) -> Self {
+        Self {
+            pipeline,
+            repo_root: repo_root.into(),
+            strategy: PrStrategy::default(),
+        }
+    }
+
+    pub fn with_strategy(mut self, strategy: PrStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Execute every sub-intent in dependency order and deliver the results
+    pub async fn execute(&self, plan: &OrchestrationPlan) -> ActionResult<OrchestrationReport> {
+        let start = Instant::now();
+        let git_config = ActionGitConfig::load(&self.repo_root)?;
+        let policy = git_config.policy_for(&plan.parent.safety_level);
+        let git = if policy.is_enabled() {
+            Some(ActionGitIntegration::with_config(git_config).await?)
+        } else {
+            None
+        };
+        let commits = policy.commit || policy.push || policy.pull_request;
+        let stacked = self.strategy == PrStrategy::Stacked && policy.isolated_branch;
+
+        let mut report = OrchestrationReport {
+            intent_id: plan.parent.id.clone(),
+            strategy: self.strategy,
+            outcomes: Vec::new(),
+            pull_requests: Vec::new(),
+            duration_ms: 0,
+        };
+
+        // Branch shared by all scopes, and the base of the next stacked branch
+        let mut shared_branch = None;
+        let mut stack_base = String::new();
+        if let Some(git) = &git {
+            stack_base = git.config().base_branch();
+            if policy.isolated_branch && !stacked {
+                let branch = git.config().branch_name(&plan.parent);
+                git.switch_to_new_branch(&branch)?;
+                shared_branch = Some(branch);
+            } else if !policy.isolated_branch {
+                shared_branch = Some(git.get_current_branch()?);
+            }
+        }
+
+        let mut blocked: HashSet<&str> = HashSet::new();
+        for (wave, indices) in plan.waves.iter().enumerate() {
+            for &index in indices {
+                let sub = &plan.sub_intents[index];
+                let mut outcome = SubIntentOutcome {
+                    scope: sub.scope.clone(),
+                    intent_id: sub.intent.id.clone(),
+                    wave,
+                    status: SubIntentStatus::Skipped,
+                    changes: Vec::new(),
+                    errors: Vec::new(),
+                    warnings: Vec::new(),
+                    duration_ms: 0,
+                    branch: shared_branch.clone(),
+                    commit_hash: None,
+                    pull_request_url: None,
+                };
+
+                if let Some(dependency) = sub
+                    .depends_on
+                    .iter()
+                    .find(|dep| blocked.contains(dep.as_str()))
+                {
+                    outcome
+                        .errors
+                        .push(format!("Skipped because {} did not succeed", dependency));
+                    blocked.insert(&sub.scope);
+                    report.outcomes.push(outcome);
+                    continue;
+                }
+
+                info!(
+                    "Running sub-intent {} for scope {}",
+                    sub.intent.id, sub.scope
+                );
+                let result = match self.pipeline.execute_without_delivery(&sub.intent).await {
+                    Ok(result) => result,
+                    Err(e) => ExecutionResult {
+                        success: false,
+                        changes: Vec::new(),
+                        errors: vec![e.to_string()],
+                        warnings: Vec::new(),
+                        duration: std::time::Duration::ZERO,
+                        delivery: None,
+                        diff: None,
+                    },
+                };
+                outcome.status = if result.success {
+                    SubIntentStatus::Succeeded
+                } else {
+                    warn!(
+                        "Sub-intent {} failed; its dependents are skipped",
+                        sub.intent.id
+                    );
+                    blocked.insert(&sub.scope);
+                    SubIntentStatus::Failed
+                };
+                outcome.changes = result.changes.clone();
+                outcome.errors = result.errors.clone();
+                outcome.warnings = result.warnings.clone();
+                outcome.duration_ms = result.duration.as_millis() as u64;
+
+                if let Some(git) = git.as_ref().filter(|_| result.success && commits) {
+                    if stacked {
+                        let branch = git.config().branch_name(&sub.intent);
+                        git.switch_to_new_branch(&branch)?;
+                        outcome.branch = Some(branch);
+                    }
+                    outcome.commit_hash = git.commit_intent_changes(&sub.intent, &result)?;
+
+                    if stacked && outcome.commit_hash.is_some() {
+                        let branch = outcome.branch.clone().unwrap_or_default();
+                        self.deliver_stacked(git, sub, &result, &mut outcome, &stack_base)
+                            .await?;
+                        report
+                            .pull_requests
+                            .extend(outcome.pull_request_url.clone());
+                        stack_base = branch;
+                    }
+                }
+                report.outcomes.push(outcome);
+            }
+        }
+
+        if let (Some(git), Some(branch)) = (&git, &shared_branch) {
+            let committed = report.outcomes.iter().any(|o| o.commit_hash.is_some());
+            if committed && (policy.push || policy.pull_request) {
+                git.push_to_remote(&git.config().remote, branch).await?;
+                if policy.pull_request {
+                    let url = git.open_pull_request(
+                        &git.commit_subject(&plan.parent),
+                        &self.combined_body(plan, &report),
+                        &stack_base,
+                        branch,
+                        policy.draft || !report.success(),
+                    )?;
+                    info!(
+                        "Opened combined pull request for {}: {}",
+                        plan.parent.id, url
+                    );
+                    for outcome in &mut report.outcomes {
+                        if outcome.commit_hash.is_some() {
+                            outcome.pull_request_url = Some(url.clone());
+                        }
+                    }
+                    report.pull_requests.push(url);
+                }
+            }
+        }
+
+        report.duration_ms = start.elapsed().as_millis() as u64;
+        Ok(report)
+    }
+
+    /// Push a stacked branch and open its pull request against the branch below
+    async fn deliver_stacked(
+        &self,
+        git: &ActionGitIntegration,
+        sub: &SubIntent,
+        result: &ExecutionResult,
+        outcome: &mut SubIntentOutcome,
+        base: &str,
+    ) -> ActionResult<()> {
+        let policy = git.config().policy_for(&sub.intent.safety_level);
+        if !(policy.push || policy.pull_request) {
+            return Ok(());
+        }
+        let branch = outcome.branch.clone().unwrap_or_default();
+        git.push_to_remote(&git.config().remote, &branch).await?;
+
+        if policy.pull_request {
+            let delivery = IntentDelivery {
+                branch: branch.clone(),
+                original_branch: base.to_string(),
+                commit_hash: outcome.commit_hash.clone(),
+                pushed: true,
+                pull_request_url: None,
+                rollback_instructions: Vec::new(),
+            };
+            let url = git.open_pull_request(
+                &git.commit_subject(&sub.intent),
+                &git.pull_request_body(&sub.intent, result, &delivery),
+                base,
+                &branch,
+                policy.draft,
+            )?;
+            info!("Opened pull request for scope {}: {}", sub.scope, url);
+            outcome.pull_request_url = Some(url);
+        }
+        Ok(())
+    }
+
+    fn combined_body(&self, plan: &OrchestrationPlan, report: &OrchestrationReport) -> String {
+        let parent = &plan.parent;
+        let mut body = String::from("## Intent\n\n");
+        body.push_str(&format!("{}\n\n", parent.description));
+        body.push_str(&format!(
+            "- **Intent ID:** `{}`\n- **Action type:** {}\n- **Safety level:** {}\n",
+            parent.id, parent.action_type, parent.safety_level
+        ));
+        body.push_str(&format!(
+            "- **Scopes:** {} succeeded, {} failed, {} skipped\n",
+            report.count(SubIntentStatus::Succeeded),
+            report.count(SubIntentStatus::Failed),
+            report.count(SubIntentStatus::Skipped)
+        ));
+        body.push_str("\n## Diagnostics\n\n");
+        body.push_str(&report.diagnostics_markdown());
+        body.push_str("\n## Rollback\n\n");
+        body.push_str("- Close the pull request without merging, or revert the merge commit\n");
+        body.push_str("- Each scope is a separate commit and can be reverted on its own\n");
+        body
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::{ActionType, SafetyLevel};
+    use tempfile::TempDir;
+
+    fn write_scope(root: &Path, dir: &str, name: &str, dependencies: &[&str]) {
+        let rhema = root.join(dir).join(".rhema");
+        std::fs::create_dir_all(&rhema).unwrap();
+        let mut yaml = format!("name: {}\nscope_type: library\nversion: 1.0.0\n", name);
+        if !dependencies.is_empty() {
+            yaml.push_str("dependencies:\n");
+            for dependency in dependencies {
+                yaml.push_str(&format!(
+                    "  - path: {}\n    dependency_type: required\n",
+                    dependency
+                ));
+            }
+        }
+        std::fs::write(rhema.join("rhema.yaml"), yaml).unwrap();
+    }
+
+    #[test]
+    fn test_split_orders_scopes_by_dependencies() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write_scope(root, "crates/core", "core", &[]);
+        write_scope(root, "crates/api", "api", &["../core"]);
+        write_scope(root, "apps/web", "web", &["api"]);
+        for file in [
+            "crates/core/lib.rs",
+            "crates/api/lib.rs",
+            "apps/web/main.ts",
+            "README.md",
+        ] {
+            std::fs::write(root.join(file), "").unwrap();
+        }
+
+        let parent = ActionIntent::new(
+            "rename-client",
+            ActionType::Refactor,
+            "Rename Client to ApiClient",
+            vec![".".to_string()],
+            SafetyLevel::Medium,
+        );
+        let plan = OrchestrationPlan::for_repository(&parent, root).unwrap();
+
+        let waves: Vec<Vec<&str>> = plan
+            .waves
+            .iter()
+            .map(|wave| {
+                wave.iter()
+                    .map(|&i| plan.sub_intents[i].scope.as_str())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            waves,
+            vec![vec![UNSCOPED, "core"], vec!["api"], vec!["web"]]
+        );
+
+        let api = plan.sub_intents.iter().find(|s| s.scope == "api").unwrap();
+        assert_eq!(api.intent.id, "rename-client-api");
+        assert_eq!(api.intent.scope, vec!["crates/api".to_string()]);
+        assert_eq!(
+            api.intent.dependencies,
+            Some(vec!["rename-client-core".to_string()])
+        );
+        let unscoped = plan
+            .sub_intents
+            .iter()
+            .find(|s| s.scope == UNSCOPED)
+            .unwrap();
+        assert_eq!(unscoped.intent.scope, vec!["README.md".to_string()]);
+    }
+}
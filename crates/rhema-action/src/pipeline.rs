@@ -15,12 +15,48 @@
  */
 
 use anyhow::Result;
+use rhema_config::event_export::EventExportConfig;
+use rhema_core::ai_policy::{resolve_ai_policy, AutonomyLevel};
+use rhema_core::events::{ExportEvent, ExportEventType};
+use rhema_core::toolchain::{resolve_toolchains, ScopeToolchains};
+use std::path::Path;
 use std::sync::Arc;
 use tracing::{error, info, warn};
 
+use crate::git::{ActionGitConfig, ActionGitIntegration, IntentDelivery};
 use crate::schema::{ActionIntent as SchemaActionIntent, ActionType, SafetyLevel};
 use crate::tools::ToolRegistry;
-use rhema_action_tool::{ActionError, ActionIntent, ActionResult, ToolResult};
+use crate::worktree::{repository_root, IntentWorktree, IsolationConfig, IsolationMode};
+use rhema_action_tool::sandbox::{self, Sandbox};
+use rhema_action_tool::{
+    ActionError, ActionIntent, ActionResult, Diagnostic, ToolResult, ToolchainProfile,
+};
+
+/// Intent metadata naming the transformation tools to run instead of the
+/// ones selected from the scope toolchains
+pub const TRANSFORMATION_TOOLS_KEY: &str = "transformation_tools";
+
+/// Intent metadata naming the validation tools to run instead of the ones
+/// selected from the scope toolchains
+pub const VALIDATION_TOOLS_KEY: &str = "validation_tools";
+
+/// Tools chosen for an intent
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SelectedTools {
+    pub transformations: Vec<String>,
+    pub validations: Vec<String>,
+}
+
+/// Changes, errors, warnings, diagnostics and failed tests gathered from
+/// several tool runs
+#[derive(Default)]
+struct Collected {
+    changes: Vec<String>,
+    errors: Vec<String>,
+    warnings: Vec<String>,
+    diagnostics: Vec<Diagnostic>,
+    failed_tests: Vec<String>,
+}
 
 /// Action safety pipeline for executing actions with safety checks
 pub struct ActionSafetyPipeline {
@@ -44,12 +80,92 @@ impl ActionSafetyPipeline {
 
     /// Execute an action with safety checks
     pub async fn execute_action(&self, intent: &SchemaActionIntent) -> Result<ExecutionResult> {
+        self.run(intent, true).await
+    }
+
+    /// Execute an action with safety checks, leaving its changes in the
+    /// working tree for the caller to commit
+    pub async fn execute_without_delivery(
+        &self,
+        intent: &SchemaActionIntent,
+    ) -> Result<ExecutionResult> {
+        self.run(intent, false).await
+    }
+
+    async fn run(&self, intent: &SchemaActionIntent, deliver: bool) -> Result<ExecutionResult> {
         info!("Executing action: {}", intent.id);
 
         let start = std::time::Instant::now();
 
+        // Refuse intents that violate the AI policy of a touched scope
+        self.check_scope_policies(intent)?;
+
+        // Move onto the intent's branch before any tool touches the tree
+        let git_config = ActionGitConfig::load(&std::env::current_dir()?)
+            .map_err(|e| anyhow::anyhow!("Failed to load action git config: {}", e))?;
+        let git = if deliver
+            && !intent.dry_run
+            && git_config.policy_for(&intent.safety_level).is_enabled()
+        {
+            Some(
+                ActionGitIntegration::with_config(git_config)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("Failed to initialize git integration: {}", e))?,
+            )
+        } else {
+            None
+        };
+        let delivery = match &git {
+            Some(git) => git
+                .start_intent(intent)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to prepare intent branch: {}", e))?,
+            None => None,
+        };
+
+        // Run tools in a throwaway worktree when isolation is enabled; dry
+        // runs always use a sandbox
+        let cwd = std::env::current_dir()?;
+        let isolation = IsolationConfig::load(&cwd)
+            .map_err(|e| anyhow::anyhow!("Failed to load isolation config: {}", e))?;
+        let worktree = match isolation.mode {
+            IsolationMode::Worktree if !intent.dry_run => Some(
+                IntentWorktree::create(&repository_root(&cwd)?, intent, &isolation)
+                    .map_err(|e| anyhow::anyhow!("Failed to create worktree: {}", e))?,
+            ),
+            _ => None,
+        };
+        let run_intent = match &worktree {
+            Some(worktree) => worktree.rebase_intent(intent)?,
+            None => intent.clone(),
+        };
+
         // Convert schema intent to shared intent
-        let shared_intent = self.convert_to_shared_intent(intent);
+        let shared_intent = self.convert_to_shared_intent(&run_intent);
+
+        // Point the tools at a copy of the scope when sandboxing is enabled
+        let sandbox = if worktree.is_none()
+            && (intent.dry_run
+                || isolation.mode == IsolationMode::Sandbox
+                || sandbox::requested(&shared_intent))
+        {
+            Some(
+                Sandbox::create(&shared_intent, &cwd, isolation.worktree_root.as_deref())
+                    .map_err(|e| anyhow::anyhow!("Failed to create sandbox: {}", e))?,
+            )
+        } else {
+            None
+        };
+        let shared_intent = match &sandbox {
+            Some(sandbox) => {
+                // The sandbox already keeps a dry run away from the real tree,
+                // so the tools run normally inside it and their changes add up
+                let mut rebased = sandbox.rebase_intent(&shared_intent)?;
+                rebased.dry_run = false;
+                rebased
+            }
+            None => shared_intent,
+        };
 
         // Execute based on action type
         let result = match intent.action_type {
@@ -67,6 +183,9 @@ impl ActionSafetyPipeline {
                     errors: vec![],
                     warnings: vec![],
                     duration: std::time::Duration::from_secs(1),
+                    cached: false,
+                    diagnostics: Vec::new(),
+                    failed_tests: Vec::new(),
                 }
             }
             ActionType::Test => self.execute_test_action(&shared_intent).await?,
@@ -77,16 +196,54 @@ impl ActionSafetyPipeline {
             ActionType::Custom(_) => self.execute_default_action(&shared_intent).await?,
         };
 
+        let mut diff = None;
+        let result = match (worktree, sandbox) {
+            (Some(worktree), _) => {
+                self.finish_isolated_execution(
+                    worktree,
+                    intent,
+                    &shared_intent,
+                    result,
+                    isolation.keep_on_failure,
+                )
+                .await?
+            }
+            (None, Some(sandbox)) => {
+                let (result, rendered) = self
+                    .finish_sandboxed_execution(
+                        sandbox,
+                        intent,
+                        &shared_intent,
+                        result,
+                        isolation.keep_on_failure,
+                    )
+                    .await?;
+                diff = Some(rendered);
+                result
+            }
+            (None, None) => result,
+        };
+
         let duration = start.elapsed();
 
-        let execution_result = ExecutionResult {
+        let mut execution_result = ExecutionResult {
             success: result.success,
             changes: result.changes,
             errors: result.errors,
             warnings: result.warnings,
             duration,
+            delivery: None,
+            diff,
         };
 
+        if let (Some(git), Some(delivery)) = (&git, delivery) {
+            let delivery = git
+                .finish_intent(intent, delivery, &execution_result)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to deliver intent through git: {}", e))?;
+            execution_result.delivery = Some(delivery);
+        }
+
         if execution_result.success {
             info!("Action execution completed successfully in {:?}", duration);
         } else {
@@ -97,46 +254,188 @@ impl ActionSafetyPipeline {
             );
         }
 
+        if !intent.dry_run {
+            self.export_execution(intent, &execution_result);
+        }
+
         Ok(execution_result)
     }
 
-    /// Execute refactor action
-    async fn execute_refactor_action(&self, intent: &ActionIntent) -> Result<ToolResult> {
-        info!("Executing refactor action");
+    /// Publish an `action_executed` event when the repository exports events.
+    /// Export problems are logged and never fail the action.
+    fn export_execution(&self, intent: &SchemaActionIntent, result: &ExecutionResult) {
+        let repo_root = match std::env::current_dir()
+            .map_err(|e| e.to_string())
+            .and_then(|cwd| repository_root(&cwd).map_err(|e| e.to_string()))
+        {
+            Ok(repo_root) => repo_root,
+            Err(e) => {
+                warn!("Skipping event export for {}: {}", intent.id, e);
+                return;
+            }
+        };
+        let outbox = match EventExportConfig::load(&repo_root) {
+            Ok(config) => config.outbox(&repo_root),
+            Err(e) => {
+                warn!("Skipping event export for {}: {}", intent.id, e);
+                None
+            }
+        };
+        let Some(outbox) = outbox else {
+            return;
+        };
 
-        // Run transformation tools
-        let transformations = vec![
-            ("prettier", "Code formatting"),
-            ("eslint", "Code linting"),
-            ("ast-grep", "AST-based transformations"),
-        ];
+        let data = serde_json::json!({
+            "intent_id": intent.id,
+            "action_type": intent.action_type,
+            "description": intent.description,
+            "scope": intent.scope,
+            "success": result.success,
+            "changes": result.changes,
+            "errors": result.errors,
+            "duration_ms": result.duration.as_millis() as u64,
+            "delivery": result.delivery,
+        });
+        let event = ExportEvent::new(ExportEventType::ActionExecuted, "rhema-action", data)
+            .with_subject(intent.id.clone());
+        if let Err(e) = outbox.publish(&event) {
+            warn!("Failed to export execution of {}: {}", intent.id, e);
+        }
+    }
 
-        let mut all_changes = Vec::new();
-        let mut all_errors = Vec::new();
-        let mut all_warnings = Vec::new();
+    /// Run the post-execution safety checks inside the worktree and apply the
+    /// tool changes to the main working tree only if everything passed
+    async fn finish_isolated_execution(
+        &self,
+        mut worktree: IntentWorktree,
+        intent: &SchemaActionIntent,
+        shared_intent: &ActionIntent,
+        mut result: ToolResult,
+        keep_on_failure: bool,
+    ) -> Result<ToolResult> {
+        self.run_post_execution_checks(intent, shared_intent, &mut result)
+            .await;
+
+        if result.success {
+            let files = worktree
+                .merge_back()
+                .map_err(|e| anyhow::anyhow!("Failed to merge worktree changes: {}", e))?;
+            info!("Applied {} files from the isolated worktree", files.len());
+        } else {
+            warn!(
+                "Discarding worktree changes for intent {} after failed checks",
+                intent.id
+            );
+            if keep_on_failure {
+                worktree.keep();
+                result.warnings.push(format!(
+                    "Worktree kept for inspection at {}",
+                    worktree.path().display()
+                ));
+            }
+        }
+        Ok(result)
+    }
 
-        for (tool_name, description) in transformations {
-            match self.tool_registry.execute_tool(tool_name, intent).await {
-                Ok(result) => {
-                    all_changes.extend(result.changes);
-                    all_errors.extend(result.errors);
-                    all_warnings.extend(result.warnings);
-                }
-                Err(e) => {
-                    error!("{} failed: {:?}", description, e);
-                    all_errors.push(format!("{} failed: {:?}", description, e));
+    /// Run the post-execution safety checks against the sandbox copies and
+    /// apply the sandbox diff to the working tree only if everything passed.
+    /// Dry runs never apply it and report one diff per file as their changes.
+    /// Returns the diff either way.
+    async fn finish_sandboxed_execution(
+        &self,
+        mut sandbox: Sandbox,
+        intent: &SchemaActionIntent,
+        shared_intent: &ActionIntent,
+        mut result: ToolResult,
+        keep_on_failure: bool,
+    ) -> Result<(ToolResult, String)> {
+        self.run_post_execution_checks(intent, shared_intent, &mut result)
+            .await;
+
+        let changes = sandbox
+            .changes()
+            .map_err(|e| anyhow::anyhow!("Failed to diff sandbox: {}", e))?;
+        let diff = sandbox::render_diff(&changes);
+        if intent.dry_run {
+            info!(
+                "Dry run of intent {}: {} files would change",
+                intent.id,
+                changes.len()
+            );
+            result.changes = changes
+                .iter()
+                .map(|change| change.unified_diff(3))
+                .collect();
+        } else if result.success {
+            let files = sandbox
+                .apply(&changes)
+                .map_err(|e| anyhow::anyhow!("Failed to apply sandbox changes: {}", e))?;
+            info!("Applied {} files from the sandbox", files.len());
+        } else {
+            warn!(
+                "Discarding sandbox changes for intent {} after failed checks",
+                intent.id
+            );
+            if keep_on_failure {
+                sandbox.keep();
+                result.warnings.push(format!(
+                    "Sandbox kept for inspection at {}",
+                    sandbox.workspace().display()
+                ));
+            }
+        }
+        Ok((result, diff))
+    }
+
+    /// Add the errors and warnings of the intent's post-execution safety
+    /// checks to a successful result
+    async fn run_post_execution_checks(
+        &self,
+        intent: &SchemaActionIntent,
+        shared_intent: &ActionIntent,
+        result: &mut ToolResult,
+    ) {
+        if !result.success {
+            return;
+        }
+        for check in &intent.safety_checks.post_execution {
+            match self
+                .tool_registry
+                .execute_safety_check(check, shared_intent)
+                .await
+            {
+                Ok(check_result) => {
+                    result.errors.extend(check_result.errors);
+                    result.warnings.extend(check_result.warnings);
                 }
+                Err(e) => result
+                    .errors
+                    .push(format!("Safety check {} failed: {:?}", check, e)),
             }
         }
+        result.success = result.errors.is_empty();
+    }
+
+    /// Execute refactor action
+    async fn execute_refactor_action(&self, intent: &ActionIntent) -> Result<ToolResult> {
+        info!("Executing refactor action");
 
-        let changes_count = all_changes.len();
+        let tools = self.select_tools(intent).await;
+        let mut collected = Collected::default();
+        self.run_transformations(intent, &tools.transformations, &mut collected)
+            .await;
+
+        let changes_count = collected.changes.len();
         Ok(ToolResult {
-            success: all_errors.is_empty(),
-            changes: all_changes,
+            success: collected.errors.is_empty(),
+            changes: collected.changes,
             output: format!("Refactor action completed with {} changes", changes_count),
-            errors: all_errors,
-            warnings: all_warnings,
+            errors: collected.errors,
+            warnings: collected.warnings,
             duration: std::time::Duration::from_secs(1), // Placeholder
+            cached: false,
+            diagnostics: collected.diagnostics,
+            failed_tests: collected.failed_tests,
         })
     }
 
@@ -144,39 +443,21 @@ impl ActionSafetyPipeline {
     async fn execute_bugfix_action(&self, intent: &ActionIntent) -> Result<ToolResult> {
         info!("Executing bugfix action");
 
-        // Run validation tools first
-        let validations = vec![
-            ("typescript", "TypeScript validation"),
-            ("jest", "Jest tests"),
-        ];
-
-        let mut all_errors = Vec::new();
-        let mut all_warnings = Vec::new();
-
-        for (tool_name, description) in validations {
-            match self
-                .tool_registry
-                .execute_validation(tool_name, intent)
-                .await
-            {
-                Ok(result) => {
-                    all_errors.extend(result.errors);
-                    all_warnings.extend(result.warnings);
-                }
-                Err(e) => {
-                    error!("{} failed: {:?}", description, e);
-                    all_errors.push(format!("{} failed: {:?}", description, e));
-                }
-            }
-        }
+        let tools = self.select_tools(intent).await;
+        let mut collected = Collected::default();
+        self.run_validations(intent, &tools.validations, &mut collected)
+            .await;
 
         Ok(ToolResult {
-            success: all_errors.is_empty(),
+            success: collected.errors.is_empty(),
             changes: vec!["Bugfix validation completed".to_string()],
             output: "Bugfix action completed".to_string(),
-            errors: all_errors,
-            warnings: all_warnings,
+            errors: collected.errors,
+            warnings: collected.warnings,
             duration: std::time::Duration::from_secs(1), // Placeholder
+            cached: false,
+            diagnostics: collected.diagnostics,
+            failed_tests: collected.failed_tests,
         })
     }
 
@@ -184,60 +465,144 @@ impl ActionSafetyPipeline {
     async fn execute_feature_action(&self, intent: &ActionIntent) -> Result<ToolResult> {
         info!("Executing feature action");
 
-        // Run comprehensive validation and transformation
-        let mut all_changes = Vec::new();
-        let mut all_errors = Vec::new();
-        let mut all_warnings = Vec::new();
+        // Run transformations, then validate the result
+        let tools = self.select_tools(intent).await;
+        let mut collected = Collected::default();
+        self.run_transformations(intent, &tools.transformations, &mut collected)
+            .await;
+        self.run_validations(intent, &tools.validations, &mut collected)
+            .await;
 
-        // Run transformations
-        let transformations = vec![("prettier", "Code formatting"), ("eslint", "Code linting")];
+        let changes_count = collected.changes.len();
+        Ok(ToolResult {
+            success: collected.errors.is_empty(),
+            changes: collected.changes,
+            output: format!("Feature action completed with {} changes", changes_count),
+            errors: collected.errors,
+            warnings: collected.warnings,
+            duration: std::time::Duration::from_secs(1), // Placeholder
+            cached: false,
+            diagnostics: collected.diagnostics,
+            failed_tests: collected.failed_tests,
+        })
+    }
+
+    /// Pick the tools for an intent: those named in its metadata, otherwise
+    /// the registered tools that apply to the toolchains of its scopes
+    pub async fn select_tools(&self, intent: &ActionIntent) -> SelectedTools {
+        let named = |key: &str| {
+            intent
+                .metadata
+                .get(key)
+                .and_then(|v| v.as_array())
+                .map(|names| {
+                    names
+                        .iter()
+                        .filter_map(|name| name.as_str().map(str::to_string))
+                        .collect::<Vec<_>>()
+                })
+        };
+        let (transformations, validations) =
+            (named(TRANSFORMATION_TOOLS_KEY), named(VALIDATION_TOOLS_KEY));
+        if let (Some(transformations), Some(validations)) = (&transformations, &validations) {
+            return SelectedTools {
+                transformations: transformations.clone(),
+                validations: validations.clone(),
+            };
+        }
+
+        let profile = toolchain_profile(&intent.scope);
+        let selected = SelectedTools {
+            transformations: match transformations {
+                Some(names) => names,
+                None => {
+                    self.tool_registry
+                        .applicable_transformation_tools(&profile)
+                        .await
+                }
+            },
+            validations: match validations {
+                Some(names) => names,
+                None => {
+                    self.tool_registry
+                        .applicable_validation_tools(&profile)
+                        .await
+                }
+            },
+        };
+        info!(
+            "Selected tools for {} (languages: {:?}, frameworks: {:?}, toolchains: {:?}): transformations {:?}, validations {:?}",
+            intent.id,
+            profile.languages,
+            profile.frameworks,
+            profile.toolchains,
+            selected.transformations,
+            selected.validations
+        );
+        selected
+    }
 
-        for (tool_name, description) in transformations {
+    async fn run_transformations(
+        &self,
+        intent: &ActionIntent,
+        tools: &[String],
+        collected: &mut Collected,
+    ) {
+        if tools.is_empty() {
+            collected
+                .warnings
+                .push("No transformation tools apply to the intent's scopes".to_string());
+        }
+        for tool_name in tools {
             match self.tool_registry.execute_tool(tool_name, intent).await {
                 Ok(result) => {
-                    all_changes.extend(result.changes);
-                    all_errors.extend(result.errors);
-                    all_warnings.extend(result.warnings);
+                    collected.changes.extend(result.changes);
+                    collected.errors.extend(result.errors);
+                    collected.warnings.extend(result.warnings);
+                    collected.diagnostics.extend(result.diagnostics);
+                    collected.failed_tests.extend(result.failed_tests);
                 }
                 Err(e) => {
-                    error!("{} failed: {:?}", description, e);
-                    all_errors.push(format!("{} failed: {:?}", description, e));
+                    error!("{} failed: {:?}", tool_name, e);
+                    collected
+                        .errors
+                        .push(format!("{} failed: {:?}", tool_name, e));
                 }
             }
         }
+    }
 
-        // Run validations
-        let validations = vec![
-            ("typescript", "TypeScript validation"),
-            ("jest", "Jest tests"),
-        ];
-
-        for (tool_name, description) in validations {
+    async fn run_validations(
+        &self,
+        intent: &ActionIntent,
+        tools: &[String],
+        collected: &mut Collected,
+    ) {
+        if tools.is_empty() {
+            collected
+                .warnings
+                .push("No validation tools apply to the intent's scopes".to_string());
+        }
+        for tool_name in tools {
             match self
                 .tool_registry
                 .execute_validation(tool_name, intent)
                 .await
             {
                 Ok(result) => {
-                    all_errors.extend(result.errors);
-                    all_warnings.extend(result.warnings);
+                    collected.errors.extend(result.errors);
+                    collected.warnings.extend(result.warnings);
+                    collected.diagnostics.extend(result.diagnostics);
+                    collected.failed_tests.extend(result.failed_tests);
                 }
                 Err(e) => {
-                    error!("{} failed: {:?}", description, e);
-                    all_errors.push(format!("{} failed: {:?}", description, e));
+                    error!("{} failed: {:?}", tool_name, e);
+                    collected
+                        .errors
+                        .push(format!("{} failed: {:?}", tool_name, e));
                 }
             }
         }
-
-        let changes_count = all_changes.len();
-        Ok(ToolResult {
-            success: all_errors.is_empty(),
-            changes: all_changes,
-            output: format!("Feature action completed with {} changes", changes_count),
-            errors: all_errors,
-            warnings: all_warnings,
-            duration: std::time::Duration::from_secs(1), // Placeholder
-        })
     }
 
     /// Execute security action
@@ -248,6 +613,8 @@ impl ActionSafetyPipeline {
         let security_checks = vec![
             ("security_scanning", "Security scanning"),
             ("syntax_validation", "Syntax validation"),
+            ("license_compliance", "License compliance"),
+            ("semgrep", "Semgrep scan"),
         ];
 
         let mut all_errors = Vec::new();
@@ -277,6 +644,9 @@ impl ActionSafetyPipeline {
             errors: all_errors,
             warnings: all_warnings,
             duration: std::time::Duration::from_secs(1), // Placeholder
+            cached: false,
+            diagnostics: Vec::new(),
+            failed_tests: Vec::new(),
         })
     }
 
@@ -317,9 +687,42 @@ impl ActionSafetyPipeline {
             errors: all_errors,
             warnings: all_warnings,
             duration: std::time::Duration::from_secs(1), // Placeholder
+            cached: false,
+            diagnostics: Vec::new(),
+            failed_tests: Vec::new(),
         })
     }
 
+    /// Check the intent against the AI policy of every scope it touches
+    fn check_scope_policies(&self, intent: &SchemaActionIntent) -> Result<()> {
+        let level = match intent.safety_level {
+            SafetyLevel::Low => AutonomyLevel::Low,
+            SafetyLevel::Medium => AutonomyLevel::Medium,
+            SafetyLevel::High => AutonomyLevel::High,
+            SafetyLevel::Critical => AutonomyLevel::Critical,
+        };
+        let operation = intent.action_type.to_string();
+
+        // Intent paths are relative to the repository root
+        let cwd = std::env::current_dir()?;
+        let repo_root = repository_root(&cwd).unwrap_or(cwd);
+        for target in &intent.scope {
+            let policy = resolve_ai_policy(&repo_root.join(target))?;
+            policy.check_operation(&operation)?;
+
+            if !intent.approval_workflow.required && !policy.allows_autonomous(level) {
+                return Err(anyhow::anyhow!(
+                    "Action {} at safety level {} exceeds the autonomous limit ({}) for {}; approval is required",
+                    intent.id,
+                    level,
+                    policy.max_autonomous_safety,
+                    target
+                ));
+            }
+        }
+        Ok(())
+    }
+
     /// Convert schema intent to shared intent
     fn convert_to_shared_intent(&self, intent: &SchemaActionIntent) -> ActionIntent {
         ActionIntent {
@@ -373,6 +776,7 @@ impl ActionSafetyPipeline {
             priority: intent.priority.clone(),
             estimated_effort: intent.estimated_effort.clone(),
             dependencies: intent.dependencies.clone(),
+            dry_run: intent.dry_run,
         }
     }
 
@@ -380,20 +784,21 @@ impl ActionSafetyPipeline {
     async fn execute_test_action(&self, intent: &ActionIntent) -> Result<ToolResult> {
         info!("Executing test action");
 
-        // Run Jest tests
-        let jest_result = self
-            .tool_registry
-            .execute_validation("jest", intent)
-            .await
-            .map_err(|e| anyhow::anyhow!("Jest validation failed: {:?}", e))?;
+        let tools = self.select_tools(intent).await;
+        let mut collected = Collected::default();
+        self.run_validations(intent, &tools.validations, &mut collected)
+            .await;
 
         Ok(ToolResult {
-            success: jest_result.success,
+            success: collected.errors.is_empty(),
             changes: vec!["Test execution completed".to_string()],
             output: "Test action completed".to_string(),
-            errors: jest_result.errors,
-            warnings: jest_result.warnings,
+            errors: collected.errors,
+            warnings: collected.warnings,
             duration: std::time::Duration::from_secs(1),
+            cached: false,
+            diagnostics: collected.diagnostics,
+            failed_tests: collected.failed_tests,
         })
     }
 
@@ -415,6 +820,9 @@ impl ActionSafetyPipeline {
             errors: syntax_result.errors,
             warnings: syntax_result.warnings,
             duration: std::time::Duration::from_secs(1),
+            cached: false,
+            diagnostics: Vec::new(),
+            failed_tests: Vec::new(),
         })
     }
 
@@ -422,20 +830,22 @@ impl ActionSafetyPipeline {
     async fn execute_dependency_action(&self, intent: &ActionIntent) -> Result<ToolResult> {
         info!("Executing dependency action");
 
-        // Run cargo validation
-        let cargo_result = self
-            .tool_registry
-            .execute_validation("cargo", intent)
-            .await
-            .map_err(|e| anyhow::anyhow!("Cargo validation failed: {:?}", e))?;
+        // Build and check the scopes with their own toolchains
+        let tools = self.select_tools(intent).await;
+        let mut collected = Collected::default();
+        self.run_validations(intent, &tools.validations, &mut collected)
+            .await;
 
         Ok(ToolResult {
-            success: cargo_result.success,
+            success: collected.errors.is_empty(),
             changes: vec!["Dependency action completed".to_string()],
             output: "Dependency action completed".to_string(),
-            errors: cargo_result.errors,
-            warnings: cargo_result.warnings,
+            errors: collected.errors,
+            warnings: collected.warnings,
             duration: std::time::Duration::from_secs(1),
+            cached: false,
+            diagnostics: collected.diagnostics,
+            failed_tests: collected.failed_tests,
         })
     }
 
@@ -457,6 +867,9 @@ impl ActionSafetyPipeline {
             errors: syntax_result.errors,
             warnings: syntax_result.warnings,
             duration: std::time::Duration::from_secs(1),
+            cached: false,
+            diagnostics: Vec::new(),
+            failed_tests: Vec::new(),
         })
     }
 
@@ -478,6 +891,9 @@ impl ActionSafetyPipeline {
             errors: type_result.errors,
             warnings: type_result.warnings,
             duration: std::time::Duration::from_secs(1),
+            cached: false,
+            diagnostics: Vec::new(),
+            failed_tests: Vec::new(),
         })
     }
 
@@ -499,10 +915,29 @@ impl ActionSafetyPipeline {
             errors: syntax_result.errors,
             warnings: syntax_result.warnings,
             duration: std::time::Duration::from_secs(1),
+            cached: false,
+            diagnostics: Vec::new(),
+            failed_tests: Vec::new(),
         })
     }
 }
 
+/// Merged toolchains of the paths an intent touches
+fn toolchain_profile(scope: &[String]) -> ToolchainProfile {
+    let mut merged = ScopeToolchains::default();
+    for target in scope {
+        match resolve_toolchains(Path::new(target)) {
+            Ok(toolchains) => merged.merge(&toolchains),
+            Err(e) => warn!("Could not detect toolchains for {}: {}", target, e),
+        }
+    }
+    ToolchainProfile {
+        languages: merged.languages,
+        frameworks: merged.frameworks,
+        toolchains: merged.toolchains,
+    }
+}
+
 /// Execution result
 #[derive(Debug, Clone)]
 pub struct ExecutionResult {
@@ -511,4 +946,8 @@ pub struct ExecutionResult {
     pub errors: Vec<String>,
     pub warnings: Vec<String>,
     pub duration: std::time::Duration,
+    /// Branch, commit and pull request the intent was delivered through
+    pub delivery: Option<IntentDelivery>,
+    /// Unified diff of the changes a sandboxed or dry run made, applied or not
+    pub diff: Option<String>,
 }
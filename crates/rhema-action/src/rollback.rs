@@ -15,7 +15,9 @@
  */
 
 use chrono::{DateTime, Utc};
-use std::collections::HashMap;
+use rhema_core::gc::{ArtifactCollector, Garbage, GcConfig};
+use rhema_core::RhemaResult;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -510,6 +512,17 @@ impl RollbackManager {
         Ok(vec!["Snapshot rollback completed".to_string()])
     }
 
+    /// Directory backups are written to
+    pub fn backup_directory(&self) -> &Path {
+        &self.backup_directory
+    }
+
+    /// Garbage collector for backup directories this manager no longer tracks
+    pub async fn gc_collector(&self) -> RollbackBackupCollector {
+        let live = self.backups.read().await.keys().cloned().collect();
+        RollbackBackupCollector::new(self.backup_directory.clone(), live)
+    }
+
     /// Get backup by ID
     pub async fn get_backup(&self, backup_id: &str) -> Option<Backup> {
         let backups = self.backups.read().await;
@@ -621,6 +634,58 @@ pub struct BackupStats {
     pub max_backups: usize,
 }
 
+/// Collects backup directories left behind by managers that exited.
+///
+/// The backup index only lives in memory, so this must be registered by the
+/// process that owns the [`RollbackManager`]; anything it does not know about
+/// is reclaimed once past the retention window.
+pub struct RollbackBackupCollector {
+    directory: PathBuf,
+    live: HashSet<String>,
+}
+
+impl RollbackBackupCollector {
+    pub fn new(directory: PathBuf, live: HashSet<String>) -> Self {
+        Self { directory, live }
+    }
+
+    fn is_backup_id(name: &str) -> bool {
+        name.len() == 32 && name.chars().all(|c| c.is_ascii_hexdigit())
+    }
+}
+
+impl ArtifactCollector for RollbackBackupCollector {
+    fn name(&self) -> &str {
+        "rollback_backups"
+    }
+
+    fn collect(&self, repo_root: &Path, config: &GcConfig) -> RhemaResult<Vec<Garbage>> {
+        if !self.directory.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut garbage = Vec::new();
+        for entry in std::fs::read_dir(&self.directory)? {
+            let path = entry?.path();
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            if !path.is_dir() || !Self::is_backup_id(name) || self.live.contains(name) {
+                continue;
+            }
+            if config.past_retention(&path) {
+                garbage.push(Garbage::path(
+                    self.name(),
+                    repo_root,
+                    &path,
+                    "backup not tracked by any rollback manager",
+                ));
+            }
+        }
+        Ok(garbage)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -714,4 +779,27 @@ mod tests {
         assert!(stats.total_backups > 0);
         assert!(stats.total_size > 0);
     }
+
+    #[test]
+    fn test_gc_collector_skips_live_backups() {
+        let temp_dir = TempDir::new().unwrap();
+        let live = "a".repeat(32);
+        let orphan = "b".repeat(32);
+        for name in [&live, &orphan, &"not-a-backup".to_string()] {
+            std::fs::create_dir_all(temp_dir.path().join(name)).unwrap();
+        }
+
+        let collector = RollbackBackupCollector::new(
+            temp_dir.path().to_path_buf(),
+            HashSet::from([live.clone()]),
+        );
+        let config = GcConfig {
+            retention_days: 0,
+            ..GcConfig::default()
+        };
+        let garbage = collector.collect(Path::new("/repo"), &config).unwrap();
+
+        assert_eq!(garbage.len(), 1);
+        assert_eq!(garbage[0].path, temp_dir.path().join(orphan));
+    }
 }
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use tracing::{info, warn};
 use crate::schema::{ActionIntent, SafetyLevel, ActionType};
 use crate::error::{ActionError, ActionResult};
+use rhema_action_tool::platform::normalize_path;
 
 /// Safety rule configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -211,8 +212,10 @@ impl SafetyRulesEngine {
                 self.evaluate_string_condition(description, &condition.operator, &condition.value)
             }
             "scope" => {
-                let scope = &intent.scope;
-                self.evaluate_list_condition(scope, &condition.operator, &condition.value)
+                // Compare scope paths with `/` separators regardless of platform
+                let scope: Vec<String> = intent.scope.iter().map(|path| normalize_path(path)).collect();
+                let expected = normalize_path_value(&condition.value);
+                self.evaluate_list_condition(&scope, &condition.operator, &expected)
             }
             "tools" => {
                 let tools = &intent.transformation.tools;
@@ -1079,6 +1082,17 @@ impl SafetyRulesEngine {
     }
 }
 
+/// Normalize the path strings in a condition value (a string or list of strings)
+fn normalize_path_value(value: &serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::String(path) => serde_json::Value::String(normalize_path(path)),
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.iter().map(normalize_path_value).collect())
+        }
+        other => other.clone(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1158,4 +1172,34 @@ mod tests {
         engine.disable_rule("test_rule").unwrap();
         assert_eq!(engine.get_enabled_rules().len(), 0);
     }
-} 
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_scope_condition_ignores_path_separators() {
+        let engine = SafetyRulesEngine::new();
+        let intent = ActionIntent::new(
+            "windows-scope",
+            ActionType::Refactor,
+            "Refactor with Windows paths",
+            vec![".\\src\\api\\handler.rs".to_string()],
+            SafetyLevel::Low,
+        );
+
+        let condition = SafetyCondition {
+            field: "scope".to_string(),
+            operator: ConditionOperator::In,
+            value: serde_json::json!(["src/api/handler.rs"]),
+            description: "Touches the API handler".to_string(),
+        };
+        let (matched, _) = engine.evaluate_condition(&condition, &intent).await.unwrap();
+        assert!(matched);
+
+        let condition = SafetyCondition {
+            field: "scope".to_string(),
+            operator: ConditionOperator::Contains,
+            value: serde_json::json!("src\\api/"),
+            description: "Touches the API module".to_string(),
+        };
+        let (matched, _) = engine.evaluate_condition(&condition, &intent).await.unwrap();
+        assert!(matched);
+    }
+}
@@ -16,6 +16,7 @@
 
 use chrono::{DateTime, Utc};
 use clap::ValueEnum;
+use rhema_coordination::PlannedWork;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fmt;
@@ -289,6 +290,10 @@ pub struct ActionIntent {
 
     /// Dependencies (other intents)
     pub dependencies: Option<Vec<String>>,
+
+    /// Preview the changes as unified diffs without writing them
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 impl ActionIntent {
@@ -334,6 +339,7 @@ impl ActionIntent {
             priority: None,
             estimated_effort: None,
             dependencies: None,
+            dry_run: false,
         }
     }
 
@@ -382,6 +388,7 @@ impl ActionIntent {
             "mocha",
             "pytest",
             "cargo",
+            "go",
             "npm",
         ];
 
@@ -407,8 +414,10 @@ impl ActionIntent {
             "test_execution",
             "lint_checking",
             "security_scanning",
+            "license_compliance",
             "performance_check",
             "dependency_check",
+            "semgrep",
         ];
 
         for check in &self.safety_checks.pre_execution {
@@ -504,6 +513,15 @@ impl ActionIntent {
             .unwrap()
             .push(approver.into());
     }
+
+    /// Work this intent plans for `agent_id`, so conflicts with other agents
+    /// can be predicted before it runs
+    pub fn planned_work(&self, agent_id: impl Into<String>) -> PlannedWork {
+        self.scope.iter().fold(
+            PlannedWork::new(agent_id, &self.id, &self.description),
+            |work, path| work.with_path(path),
+        )
+    }
 }
 
 /// Action status tracking
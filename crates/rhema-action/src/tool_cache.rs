@@ -0,0 +1,377 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Result cache for validation and safety tools.
+//!
+//! Results are keyed by the tool name and version, a hash of the content of
+//! every file in the intent's scope and a hash of the intent's tool
+//! configuration. Editing any file in scope produces a new key, so a cached
+//! result is only ever served for byte-identical input. Transformation tools
+//! modify the tree and are never cached.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use walkdir::WalkDir;
+
+use crate::error::{ActionError, ActionResult};
+use rhema_action_tool::{ActionIntent, ToolResult};
+
+/// Section of `.rhema/repository.yaml` configuring the tool result cache
+pub const TOOL_CACHE_CONFIG_SECTION: &str = "action_tool_cache";
+
+/// Tool result cache configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolCacheConfig {
+    pub enabled: bool,
+
+    /// Seconds a cached result stays valid
+    pub ttl_secs: u64,
+
+    /// Oldest entries are evicted beyond this
+    pub max_entries: usize,
+}
+
+impl Default for ToolCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl_secs: 3600,
+            max_entries: 1024,
+        }
+    }
+}
+
+impl ToolCacheConfig {
+    /// Load the configuration from the repository config and policy bundle,
+    /// falling back to defaults
+    pub fn load(repo_root: &Path) -> ActionResult<Self> {
+        let value = rhema_core::policy::repository_config(repo_root)
+            .map_err(|e| ActionError::configuration(e.to_string()))?;
+        match value.get(TOOL_CACHE_CONFIG_SECTION) {
+            Some(section) => serde_yaml::from_value(section.clone()).map_err(|e| {
+                ActionError::configuration(format!(
+                    "Invalid {} section in the repository config: {}",
+                    TOOL_CACHE_CONFIG_SECTION, e
+                ))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn ttl(&self) -> Duration {
+        Duration::from_secs(self.ttl_secs)
+    }
+}
+
+/// Identity of one tool run
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ToolCacheKey {
+    pub tool: String,
+    /// Adapter version, followed by the installed binary's version for
+    /// probed tools, e.g. `1.0.0+9.4.0`
+    pub version: String,
+    /// SHA-256 over the paths and content of every file in scope
+    pub content_hash: String,
+    /// SHA-256 over the intent's action type and tool configuration
+    pub config_hash: String,
+}
+
+impl ToolCacheKey {
+    /// Key for running `tool` on `intent`, resolving relative scope paths against `root`
+    pub fn for_intent(
+        tool: &str,
+        version: &str,
+        intent: &ActionIntent,
+        root: &Path,
+    ) -> std::io::Result<Self> {
+        Ok(Self {
+            tool: tool.to_string(),
+            version: version.to_string(),
+            content_hash: hash_scope(&intent.scope, root)?,
+            config_hash: hash_config(intent),
+        })
+    }
+}
+
+/// Hash every file under the scope paths in a stable order
+fn hash_scope(scope: &[String], root: &Path) -> std::io::Result<String> {
+    let mut files: Vec<PathBuf> = Vec::new();
+    let mut hasher = Sha256::new();
+    for entry in scope {
+        let path = root.join(entry);
+        if !path.exists() {
+            // A missing path is part of the input too
+            hasher.update(b"missing:");
+            hasher.update(entry.as_bytes());
+            hasher.update([0]);
+            continue;
+        }
+        for file in WalkDir::new(&path).follow_links(true) {
+            let file = file.map_err(std::io::Error::from)?;
+            if file.file_type().is_file() {
+                files.push(file.into_path());
+            }
+        }
+    }
+    files.sort();
+    files.dedup();
+
+    for file in files {
+        hasher.update(file.to_string_lossy().as_bytes());
+        hasher.update([0]);
+        hasher.update(std::fs::read(&file)?);
+        hasher.update([0]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn hash_config(intent: &ActionIntent) -> String {
+    let config = serde_json::json!({
+        "action_type": intent.action_type,
+        "metadata": intent.metadata,
+        "transformation": intent.transformation,
+        "safety_checks": intent.safety_checks,
+    });
+    format!("{:x}", Sha256::digest(config.to_string().as_bytes()))
+}
+
+#[derive(Debug, Clone)]
+struct CacheEntry {
+    result: ToolResult,
+    stored_at: Instant,
+}
+
+/// Cache counters
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ToolCacheStats {
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// In-memory tool result cache shared by the executions of one registry
+pub struct ToolResultCache {
+    config: ToolCacheConfig,
+    entries: RwLock<HashMap<ToolCacheKey, CacheEntry>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ToolResultCache {
+    pub fn new(config: ToolCacheConfig) -> Self {
+        Self {
+            config,
+            entries: RwLock::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn config(&self) -> &ToolCacheConfig {
+        &self.config
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Cached result for the key, marked as cached; expired entries are dropped
+    pub async fn get(&self, key: &ToolCacheKey) -> Option<ToolResult> {
+        if !self.config.enabled {
+            return None;
+        }
+
+        let mut entries = self.entries.write().await;
+        match entries.get(key) {
+            Some(entry) if entry.stored_at.elapsed() < self.config.ttl() => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                let mut result = entry.result.clone();
+                result.cached = true;
+                Some(result)
+            }
+            Some(_) => {
+                entries.remove(key);
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Store a fresh result, evicting the oldest entry when full
+    pub async fn insert(&self, key: ToolCacheKey, result: ToolResult) {
+        if !self.config.enabled || self.config.max_entries == 0 {
+            return;
+        }
+
+        let mut entries = self.entries.write().await;
+        if entries.len() >= self.config.max_entries && !entries.contains_key(&key) {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.stored_at)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(
+            key,
+            CacheEntry {
+                result,
+                stored_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Drop every cached result of a tool, returning how many were dropped
+    pub async fn invalidate_tool(&self, tool: &str) -> usize {
+        let mut entries = self.entries.write().await;
+        let before = entries.len();
+        entries.retain(|key, _| key.tool != tool);
+        before - entries.len()
+    }
+
+    /// Drop expired entries, returning how many were dropped
+    pub async fn purge_expired(&self) -> usize {
+        let ttl = self.config.ttl();
+        let mut entries = self.entries.write().await;
+        let before = entries.len();
+        entries.retain(|_, entry| entry.stored_at.elapsed() < ttl);
+        before - entries.len()
+    }
+
+    pub async fn clear(&self) {
+        self.entries.write().await.clear();
+    }
+
+    pub async fn stats(&self) -> ToolCacheStats {
+        ToolCacheStats {
+            entries: self.entries.read().await.len(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rhema_action_tool::{ActionType, SafetyLevel};
+    use tempfile::TempDir;
+
+    fn intent() -> ActionIntent {
+        ActionIntent::new(
+            "cache-test",
+            ActionType::Refactor,
+            "Cache test",
+            vec!["src".to_string()],
+            SafetyLevel::Low,
+        )
+    }
+
+    fn result() -> ToolResult {
+        ToolResult {
+            success: true,
+            changes: vec![],
+            output: "ok".to_string(),
+            errors: vec![],
+            warnings: vec![],
+            duration: Duration::from_millis(5),
+            cached: false,
+            diagnostics: Vec::new(),
+            failed_tests: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_key_follows_file_content() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/main.rs"), "fn main() {}").unwrap();
+
+        let first = ToolCacheKey::for_intent("cargo", "1.0", &intent(), dir.path()).unwrap();
+        let again = ToolCacheKey::for_intent("cargo", "1.0", &intent(), dir.path()).unwrap();
+        assert_eq!(first, again);
+
+        std::fs::write(dir.path().join("src/main.rs"), "fn main() { todo!() }").unwrap();
+        let edited = ToolCacheKey::for_intent("cargo", "1.0", &intent(), dir.path()).unwrap();
+        assert_ne!(first.content_hash, edited.content_hash);
+        assert_eq!(first.config_hash, edited.config_hash);
+
+        let mut configured = intent();
+        configured.metadata = serde_json::json!({ "strict": true });
+        let reconfigured =
+            ToolCacheKey::for_intent("cargo", "1.0", &configured, dir.path()).unwrap();
+        assert_ne!(first.config_hash, reconfigured.config_hash);
+    }
+
+    #[tokio::test]
+    async fn test_hit_is_marked_cached_and_expires() {
+        let cache = ToolResultCache::new(ToolCacheConfig::default());
+        let key = ToolCacheKey {
+            tool: "jest".to_string(),
+            version: "1.0".to_string(),
+            content_hash: "a".to_string(),
+            config_hash: "b".to_string(),
+        };
+
+        assert!(cache.get(&key).await.is_none());
+        cache.insert(key.clone(), result()).await;
+        assert!(cache.get(&key).await.unwrap().cached);
+        assert_eq!(
+            cache.stats().await,
+            ToolCacheStats {
+                entries: 1,
+                hits: 1,
+                misses: 1
+            }
+        );
+
+        let expiring = ToolResultCache::new(ToolCacheConfig {
+            ttl_secs: 0,
+            ..ToolCacheConfig::default()
+        });
+        expiring.insert(key.clone(), result()).await;
+        assert!(expiring.get(&key).await.is_none());
+        assert_eq!(expiring.stats().await.entries, 0);
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_tool() {
+        let cache = ToolResultCache::new(ToolCacheConfig::default());
+        for (tool, hash) in [("jest", "1"), ("jest", "2"), ("mocha", "1")] {
+            let key = ToolCacheKey {
+                tool: tool.to_string(),
+                version: "1.0".to_string(),
+                content_hash: hash.to_string(),
+                config_hash: String::new(),
+            };
+            cache.insert(key, result()).await;
+        }
+
+        assert_eq!(cache.invalidate_tool("jest").await, 2);
+        assert_eq!(cache.stats().await.entries, 1);
+    }
+}
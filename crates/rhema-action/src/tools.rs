@@ -15,14 +15,23 @@
  */
 
 use std::collections::HashMap;
+use std::future::Future;
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::RwLock;
-use tracing::{info, warn};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
 
+use crate::tool_cache::{ToolCacheConfig, ToolCacheKey, ToolResultCache};
+
+use rhema_action_tool::environment::probe_for;
+use rhema_action_tool::runtime::RUNTIME_METADATA_KEY;
 use rhema_action_tool::{
-    ActionError, ActionIntent, ActionResult, SafetyTool, ToolResult, TransformationTool,
-    ValidationTool,
+    ActionError, ActionIntent, ActionResult, EnvironmentCache, ResourceLimits, RuntimeContext,
+    SafetyTool, ToolExecution, ToolResult, ToolchainProfile, TransformationTool, ValidationTool,
 };
+use rhema_core::tool_runtime::{scope_runtime, RuntimeSpec, ToolRuntimePolicy};
 
 // Import tool implementations from dedicated crates
 use rhema_action_ast_grep::AstGrepTool;
@@ -32,12 +41,20 @@ use rhema_action_jscodeshift::JscodeshiftTool;
 use rhema_action_prettier::PrettierTool;
 
 use rhema_action_cargo::CargoTool;
+use rhema_action_go::GoTool;
+use rhema_action_hadolint::HadolintTool;
 use rhema_action_jest::JestTool;
 use rhema_action_mocha::MochaTool;
 use rhema_action_pytest::PyTestTool;
+use rhema_action_python::PythonTool;
+use rhema_action_shellcheck::ShellCheckTool;
+use rhema_action_terraform::TerraformTool;
 use rhema_action_typescript::TypeScriptTool;
 
+use rhema_action_dependency_guard::DependencyGuardTool;
+use rhema_action_license_compliance::LicenseComplianceTool;
 use rhema_action_security_scanning::SecurityScanningTool;
+use rhema_action_semgrep::SemgrepTool;
 use rhema_action_syntax_validation::SyntaxValidationTool;
 use rhema_action_test_coverage::TestCoverageTool;
 use rhema_action_type_checking::TypeCheckingTool;
@@ -47,6 +64,8 @@ pub struct ToolRegistry {
     transformation_tools: Arc<RwLock<HashMap<String, Box<dyn TransformationTool>>>>,
     validation_tools: Arc<RwLock<HashMap<String, Box<dyn ValidationTool>>>>,
     safety_tools: Arc<RwLock<HashMap<String, Box<dyn SafetyTool>>>>,
+    cache: Arc<ToolResultCache>,
+    cancellations: Arc<RwLock<HashMap<String, CancellationToken>>>,
 }
 
 impl ToolRegistry {
@@ -54,10 +73,17 @@ impl ToolRegistry {
     pub async fn new() -> ActionResult<Self> {
         info!("Initializing Tool Registry");
 
+        let cache_config = match std::env::current_dir() {
+            Ok(cwd) => ToolCacheConfig::load(&cwd)
+                .map_err(|e| ActionError::Configuration(e.to_string()))?,
+            Err(_) => ToolCacheConfig::default(),
+        };
         let registry = Self {
             transformation_tools: Arc::new(RwLock::new(HashMap::new())),
             validation_tools: Arc::new(RwLock::new(HashMap::new())),
             safety_tools: Arc::new(RwLock::new(HashMap::new())),
+            cache: Arc::new(ToolResultCache::new(cache_config)),
+            cancellations: Arc::new(RwLock::new(HashMap::new())),
         };
 
         // Register built-in tools
@@ -67,6 +93,17 @@ impl ToolRegistry {
         Ok(registry)
     }
 
+    /// Replace the result cache configuration, dropping anything cached so far
+    pub fn with_cache_config(mut self, config: ToolCacheConfig) -> Self {
+        self.cache = Arc::new(ToolResultCache::new(config));
+        self
+    }
+
+    /// Result cache of validation and safety tools, for stats and explicit invalidation
+    pub fn cache(&self) -> &ToolResultCache {
+        &self.cache
+    }
+
     /// Initialize the tool registry (stub)
     pub async fn initialize() -> ActionResult<()> {
         info!("ToolRegistry initialized (stub)");
@@ -94,6 +131,12 @@ impl ToolRegistry {
             .await;
         self.register_transformation_tool("eslint", Box::new(ESLintTool))
             .await;
+        self.register_transformation_tool("go", Box::new(GoTool))
+            .await;
+        self.register_transformation_tool("python", Box::new(PythonTool))
+            .await;
+        self.register_transformation_tool("terraform", Box::new(TerraformTool))
+            .await;
 
         // Register validation tools
         self.register_validation_tool("typescript", Box::new(TypeScriptTool))
@@ -106,6 +149,16 @@ impl ToolRegistry {
             .await;
         self.register_validation_tool("cargo", Box::new(CargoTool))
             .await;
+        self.register_validation_tool("go", Box::new(GoTool))
+            .await;
+        self.register_validation_tool("python", Box::new(PythonTool))
+            .await;
+        self.register_validation_tool("shellcheck", Box::new(ShellCheckTool))
+            .await;
+        self.register_validation_tool("terraform", Box::new(TerraformTool))
+            .await;
+        self.register_validation_tool("hadolint", Box::new(HadolintTool))
+            .await;
 
         // Register safety tools
         self.register_safety_tool("syntax_validation", Box::new(SyntaxValidationTool))
@@ -116,6 +169,15 @@ impl ToolRegistry {
             .await;
         self.register_safety_tool("security_scanning", Box::new(SecurityScanningTool))
             .await;
+        self.register_safety_tool(
+            "license_compliance",
+            Box::new(LicenseComplianceTool::default()),
+        )
+        .await;
+        self.register_safety_tool("dependency_check", Box::new(DependencyGuardTool::default()))
+            .await;
+        self.register_safety_tool("semgrep", Box::new(SemgrepTool::default()))
+            .await;
 
         info!("Built-in tools registered successfully");
         Ok(())
@@ -166,7 +228,9 @@ impl ToolRegistry {
             }
 
             // Execute the tool
-            let result = tool.execute(intent).await?;
+            let (result, _) = self
+                .run_limited(tool_name, intent, tool.execute(intent))
+                .await?;
 
             if result.success {
                 info!("Transformation tool {} executed successfully", tool_name);
@@ -194,6 +258,14 @@ impl ToolRegistry {
         if let Some(tool) = tools.get(tool_name) {
             info!("Executing validation tool: {}", tool_name);
 
+            let key = self.cache_key(tool_name, tool.version(), intent).await;
+            if let Some(key) = &key {
+                if let Some(result) = self.cache.get(key).await {
+                    info!("Validation tool {} result served from cache", tool_name);
+                    return Ok(result);
+                }
+            }
+
             // Check if tool is available
             if !tool.is_available().await {
                 return Err(ActionError::ToolExecution {
@@ -203,7 +275,12 @@ impl ToolRegistry {
             }
 
             // Execute the tool
-            let result = tool.validate(intent).await?;
+            let (result, clean) = self
+                .run_limited(tool_name, intent, tool.validate(intent))
+                .await?;
+            if let (Some(key), true) = (key, clean) {
+                self.cache.insert(key, result.clone()).await;
+            }
 
             if result.success {
                 info!("Validation tool {} executed successfully", tool_name);
@@ -231,6 +308,14 @@ impl ToolRegistry {
         if let Some(tool) = tools.get(tool_name) {
             info!("Executing safety tool: {}", tool_name);
 
+            let key = self.cache_key(tool_name, tool.version(), intent).await;
+            if let Some(key) = &key {
+                if let Some(result) = self.cache.get(key).await {
+                    info!("Safety tool {} result served from cache", tool_name);
+                    return Ok(result);
+                }
+            }
+
             // Check if tool is available
             if !tool.is_available().await {
                 return Err(ActionError::ToolExecution {
@@ -240,7 +325,12 @@ impl ToolRegistry {
             }
 
             // Execute the tool
-            let result = tool.check(intent).await?;
+            let (result, clean) = self
+                .run_limited(tool_name, intent, tool.check(intent))
+                .await?;
+            if let (Some(key), true) = (key, clean) {
+                self.cache.insert(key, result.clone()).await;
+            }
 
             if result.success {
                 info!("Safety tool {} executed successfully", tool_name);
@@ -257,6 +347,151 @@ impl ToolRegistry {
         }
     }
 
+    /// Cancel the running tool invocations of an intent, killing their processes.
+    ///
+    /// Returns `false` when no tool has run for the intent yet.
+    pub async fn cancel_intent(&self, intent_id: &str) -> bool {
+        match self.cancellations.read().await.get(intent_id) {
+            Some(token) => {
+                info!("Cancelling tool execution for intent {}", intent_id);
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Cancellation token shared by the invocations of an intent; a cancelled
+    /// token is replaced so the intent can be retried
+    async fn intent_cancellation(&self, intent_id: &str) -> CancellationToken {
+        let mut cancellations = self.cancellations.write().await;
+        let token = cancellations
+            .entry(intent_id.to_string())
+            .or_insert_with(CancellationToken::new);
+        if token.is_cancelled() {
+            *token = CancellationToken::new();
+        }
+        token.clone()
+    }
+
+    /// Run one tool invocation under the intent's resource limits, with the
+    /// runtime its scopes and metadata declare.
+    ///
+    /// A tool stopped by its wall-clock or CPU limit, or by cancellation, yields
+    /// a failed result carrying its partial output instead of an error. The flag
+    /// is `false` when any limit was hit, so the result must not be cached.
+    async fn run_limited<F>(
+        &self,
+        tool_name: &str,
+        intent: &ActionIntent,
+        run: F,
+    ) -> ActionResult<(ToolResult, bool)>
+    where
+        F: Future<Output = ActionResult<ToolResult>>,
+    {
+        let limits = ResourceLimits::for_intent(intent)?;
+        let runtime = tool_runtime(tool_name, intent)?;
+        let execution = ToolExecution::new(limits, self.intent_cancellation(&intent.id).await)
+            .with_runtime(runtime.clone());
+        let started = Instant::now();
+        let outcome = execution.run(run).await.map(|outcome| {
+            outcome.map(|mut result| {
+                runtime.redact_result(&mut result);
+                result
+            })
+        });
+
+        let breaches = execution.breaches();
+        let (fatal, trimmed): (Vec<_>, Vec<_>) =
+            breaches.iter().partition(|breach| breach.is_fatal());
+        let describe = |breaches: &[&rhema_action_tool::LimitBreach]| -> Vec<String> {
+            breaches
+                .iter()
+                .map(|breach| format!("{}: {}", tool_name, breach))
+                .collect()
+        };
+
+        match outcome {
+            Some(Ok(mut result)) if fatal.is_empty() => {
+                result.warnings.extend(describe(&trimmed));
+                Ok((result, breaches.is_empty()))
+            }
+            Some(Err(e)) if fatal.is_empty() => Err(e),
+            outcome => {
+                warn!(
+                    "Tool {} stopped for intent {}: {}",
+                    tool_name,
+                    intent.id,
+                    describe(&fatal).join(", ")
+                );
+                let (stdout, stderr) = execution.partial_output();
+                let mut result = match outcome {
+                    Some(Ok(result)) => result,
+                    Some(Err(e)) => ToolResult {
+                        success: false,
+                        changes: vec![],
+                        output: String::new(),
+                        errors: vec![e.to_string()],
+                        warnings: vec![],
+                        duration: started.elapsed(),
+                        cached: false,
+                        diagnostics: Vec::new(),
+                        failed_tests: Vec::new(),
+                    },
+                    None => ToolResult {
+                        success: false,
+                        changes: vec![],
+                        output: String::new(),
+                        errors: vec![],
+                        warnings: vec![],
+                        duration: started.elapsed(),
+                        cached: false,
+                        diagnostics: Vec::new(),
+                        failed_tests: Vec::new(),
+                    },
+                };
+                result.success = false;
+                let mut errors = describe(&fatal);
+                errors.append(&mut result.errors);
+                result.errors = errors;
+                if !stderr.is_empty() {
+                    result.errors.push(runtime.redact(&stderr));
+                }
+                if result.output.is_empty() {
+                    result.output = runtime.redact(&stdout);
+                }
+                result.warnings.extend(describe(&trimmed));
+                Ok((result, false))
+            }
+        }
+    }
+
+    /// Cache key for a tool run, or `None` when caching is off, the scope can't
+    /// be read or the run has a runtime, whose services and secrets are not
+    /// part of the key
+    async fn cache_key(
+        &self,
+        tool: &str,
+        version: &str,
+        intent: &ActionIntent,
+    ) -> Option<ToolCacheKey> {
+        if !self.cache.is_enabled() {
+            return None;
+        }
+        if !matches!(runtime_spec(intent), Ok(spec) if spec.is_empty()) {
+            return None;
+        }
+        let root = std::env::current_dir().ok()?;
+        let version = tool_version(tool, version).await;
+        match ToolCacheKey::for_intent(tool, &version, intent, &root) {
+            Ok(key) => Some(key),
+            Err(e) => {
+                debug!("Not caching {} result: {}", tool, e);
+                None
+            }
+        }
+    }
+
     /// List all available transformation tools
     pub async fn list_transformation_tools(&self) -> Vec<String> {
         let tools = self.transformation_tools.read().await;
@@ -274,6 +509,100 @@ impl ToolRegistry {
         let tools = self.safety_tools.read().await;
         tools.keys().cloned().collect()
     }
+
+    /// Names of the transformation tools that apply to a toolchain profile
+    pub async fn applicable_transformation_tools(&self, profile: &ToolchainProfile) -> Vec<String> {
+        let tools = self.transformation_tools.read().await;
+        let mut names: Vec<String> = tools
+            .iter()
+            .filter(|(_, tool)| tool.applies_to(profile))
+            .map(|(name, _)| name.clone())
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Names of the validation tools that apply to a toolchain profile
+    pub async fn applicable_validation_tools(&self, profile: &ToolchainProfile) -> Vec<String> {
+        let tools = self.validation_tools.read().await;
+        let mut names: Vec<String> = tools
+            .iter()
+            .filter(|(_, tool)| tool.applies_to(profile))
+            .map(|(name, _)| name.clone())
+            .collect();
+        names.sort();
+        names
+    }
+}
+
+/// Adapter version and, for tools with a probe, the version the installed
+/// binary reports, so upgrading the binary invalidates cached results
+async fn tool_version(tool: &str, adapter_version: &str) -> String {
+    match probe_for(tool) {
+        Some(probe) => {
+            let status = EnvironmentCache::machine().status(probe).await;
+            format!(
+                "{}+{}",
+                adapter_version,
+                status.version.as_deref().unwrap_or("unknown")
+            )
+        }
+        None => adapter_version.to_string(),
+    }
+}
+
+/// Runtime declared by the scopes an intent touches, overridden by its
+/// `runtime` metadata
+fn runtime_spec(intent: &ActionIntent) -> ActionResult<RuntimeSpec> {
+    let mut spec = RuntimeSpec::default();
+    for target in &intent.scope {
+        match scope_runtime(Path::new(target)) {
+            Ok(Some(scope)) => spec.merge(scope),
+            Ok(None) => {}
+            Err(e) => warn!("Could not read the tool runtime for {}: {}", target, e),
+        }
+    }
+    if let Some(value) = intent.metadata.get(RUNTIME_METADATA_KEY) {
+        let declared: RuntimeSpec = serde_json::from_value(value.clone()).map_err(|e| {
+            ActionError::Validation(format!(
+                "Invalid {} in intent {}: {}",
+                RUNTIME_METADATA_KEY, intent.id, e
+            ))
+        })?;
+        spec.merge(declared);
+    }
+    Ok(spec)
+}
+
+/// Resolve an intent's runtime against the repository allowlist and check
+/// that the services it needs are up
+fn tool_runtime(tool_name: &str, intent: &ActionIntent) -> ActionResult<RuntimeContext> {
+    let spec = runtime_spec(intent)?;
+    if spec.is_empty() {
+        return Ok(RuntimeContext::default());
+    }
+
+    let cwd = std::env::current_dir()?;
+    let root = rhema_core::utils::find_repo_root_from(&cwd).unwrap_or_else(|_| cwd.clone());
+    let policy =
+        ToolRuntimePolicy::load(&root).map_err(|e| ActionError::Configuration(e.to_string()))?;
+    let resolved = spec
+        .resolve(&policy, &cwd)
+        .map_err(|e| ActionError::Configuration(e.to_string()))?;
+
+    let unavailable = spec.unavailable_services(&resolved, &policy);
+    if !unavailable.is_empty() {
+        return Err(ActionError::ToolExecution {
+            tool: tool_name.to_string(),
+            message: unavailable.join("; "),
+        });
+    }
+
+    Ok(RuntimeContext {
+        env: resolved.env,
+        working_dir: resolved.working_dir,
+        secrets: resolved.secrets,
+    })
 }
 
 #[cfg(test)]
@@ -301,6 +630,44 @@ mod tests {
         assert!(!safety_tools.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_applicable_tools_follow_the_profile() {
+        let registry = ToolRegistry::new().await.unwrap();
+        let profile = |languages: &[&str], frameworks: &[&str], toolchains: &[&str]| {
+            let set = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+            ToolchainProfile {
+                languages: set(languages),
+                frameworks: set(frameworks),
+                toolchains: set(toolchains),
+            }
+        };
+
+        let rust = profile(&["rust"], &[], &["cargo"]);
+        assert_eq!(
+            registry.applicable_validation_tools(&rust).await,
+            vec!["cargo"]
+        );
+        assert_eq!(
+            registry.applicable_transformation_tools(&rust).await,
+            vec!["ast-grep"]
+        );
+
+        let web = profile(&["javascript", "typescript"], &["jest"], &["npm"]);
+        assert_eq!(
+            registry.applicable_validation_tools(&web).await,
+            vec!["jest", "typescript"]
+        );
+        assert_eq!(
+            registry.applicable_transformation_tools(&web).await,
+            vec!["ast-grep", "eslint", "prettier"]
+        );
+
+        assert!(registry
+            .applicable_validation_tools(&ToolchainProfile::default())
+            .await
+            .is_empty());
+    }
+
     #[tokio::test]
     async fn test_jscodeshift_tool() {
         let tool = JscodeshiftTool;
@@ -335,4 +702,150 @@ mod tests {
         assert!(tool_result.success);
         assert!(!tool_result.changes.is_empty());
     }
+
+    struct CountingTool {
+        runs: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait::async_trait]
+    impl ValidationTool for CountingTool {
+        async fn validate(&self, _intent: &ActionIntent) -> ActionResult<ToolResult> {
+            self.runs.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(ToolResult {
+                success: true,
+                changes: vec![],
+                output: "validated".to_string(),
+                errors: vec![],
+                warnings: vec![],
+                duration: std::time::Duration::from_millis(1),
+                cached: false,
+                diagnostics: Vec::new(),
+                failed_tests: Vec::new(),
+            })
+        }
+
+        fn name(&self) -> &str {
+            "counting"
+        }
+
+        fn version(&self) -> &str {
+            "1.0.0"
+        }
+
+        async fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validation_results_are_cached() {
+        let registry = ToolRegistry::new()
+            .await
+            .unwrap()
+            .with_cache_config(ToolCacheConfig::default());
+        let runs = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        registry
+            .register_validation_tool("counting", Box::new(CountingTool { runs: runs.clone() }))
+            .await;
+
+        let intent = ActionIntent::new(
+            "test-cache",
+            ActionType::Refactor,
+            "Test result caching",
+            vec!["src/".to_string()],
+            SafetyLevel::Low,
+        );
+
+        let first = registry
+            .execute_validation("counting", &intent)
+            .await
+            .unwrap();
+        let second = registry
+            .execute_validation("counting", &intent)
+            .await
+            .unwrap();
+        assert!(!first.cached);
+        assert!(second.cached);
+        assert_eq!(runs.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        registry.cache().invalidate_tool("counting").await;
+        let third = registry
+            .execute_validation("counting", &intent)
+            .await
+            .unwrap();
+        assert!(!third.cached);
+        assert_eq!(runs.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        // Probed tools are keyed on the installed binary's version too
+        assert_eq!(tool_version("counting", "1.0.0").await, "1.0.0");
+        assert!(tool_version("git", "1.0.0").await.starts_with("1.0.0+"));
+    }
+
+    #[cfg(unix)]
+    struct StuckTool;
+
+    #[cfg(unix)]
+    #[async_trait::async_trait]
+    impl ValidationTool for StuckTool {
+        async fn validate(&self, _intent: &ActionIntent) -> ActionResult<ToolResult> {
+            use rhema_action_tool::LimitedCommand;
+
+            let output = tokio::process::Command::new("sh")
+                .args(["-c", "echo working; sleep 30"])
+                .limited_output()
+                .await?;
+            Ok(ToolResult {
+                success: output.status.success(),
+                changes: vec![],
+                output: String::new(),
+                errors: vec![],
+                warnings: vec![],
+                duration: std::time::Duration::from_secs(30),
+                cached: false,
+                diagnostics: Vec::new(),
+                failed_tests: Vec::new(),
+            })
+        }
+
+        fn name(&self) -> &str {
+            "stuck"
+        }
+
+        fn version(&self) -> &str {
+            "1.0.0"
+        }
+
+        async fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stuck_tool_reports_partial_result() {
+        let registry = ToolRegistry::new().await.unwrap();
+        registry
+            .register_validation_tool("stuck", Box::new(StuckTool))
+            .await;
+
+        let mut intent = ActionIntent::new(
+            "test-limits",
+            ActionType::Test,
+            "Test resource limits",
+            vec!["src/".to_string()],
+            SafetyLevel::Low,
+        );
+        intent.metadata = serde_json::json!({ "resource_limits": { "wall_clock_secs": 1 } });
+
+        let started = std::time::Instant::now();
+        let result = registry.execute_validation("stuck", &intent).await.unwrap();
+        assert!(started.elapsed() < std::time::Duration::from_secs(10));
+        assert!(!result.success);
+        assert!(result.errors[0].contains("wall-clock limit of 1s exceeded"));
+        assert!(result.output.contains("working"));
+
+        // A stopped run is not served from the cache
+        let again = registry.execute_validation("stuck", &intent).await.unwrap();
+        assert!(!again.cached);
+    }
 }
@@ -150,6 +150,39 @@ impl ActionValidator {
             }
         }
 
+        // License headers and protected files apply to every action type
+        let license_result = self
+            .tool_registry
+            .execute_safety_check("license_compliance", &shared_intent)
+            .await
+            .map_err(|e| anyhow::anyhow!("License compliance check failed: {:?}", e))?;
+        if !license_result.success {
+            validation_errors.extend(license_result.errors);
+        }
+        validation_warnings.extend(license_result.warnings);
+
+        // Any action may touch a manifest, so undeclared dependencies are checked everywhere
+        let dependency_result = self
+            .tool_registry
+            .execute_safety_check("dependency_check", &shared_intent)
+            .await
+            .map_err(|e| anyhow::anyhow!("Dependency check failed: {:?}", e))?;
+        if !dependency_result.success {
+            validation_errors.extend(dependency_result.errors);
+        }
+        validation_warnings.extend(dependency_result.warnings);
+
+        // Block security findings the change introduces before it is approved
+        let semgrep_result = self
+            .tool_registry
+            .execute_safety_check("semgrep", &shared_intent)
+            .await
+            .map_err(|e| anyhow::anyhow!("Semgrep scan failed: {:?}", e))?;
+        if !semgrep_result.success {
+            validation_errors.extend(semgrep_result.errors);
+        }
+        validation_warnings.extend(semgrep_result.warnings);
+
         let success = validation_errors.is_empty();
         let duration = start.elapsed();
 
@@ -408,6 +441,7 @@ impl ActionValidator {
             priority: intent.priority.clone(),
             estimated_effort: intent.estimated_effort.clone(),
             dependencies: intent.dependencies.clone(),
+            dry_run: intent.dry_run,
         }
     }
 
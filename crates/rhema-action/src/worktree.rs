@@ -0,0 +1,432 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Worktree-based isolation for action execution.
+//!
+//! In worktree mode every intent runs inside a temporary `git worktree` that
+//! starts from `HEAD` plus the developer's uncommitted changes. Tool changes are
+//! only applied back to the main working tree once execution and the intent's
+//! post-execution safety checks pass.
+
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use tracing::{info, warn};
+
+use crate::diff_view::ProposedChange;
+use crate::error::{ActionError, ActionResult};
+use crate::schema::ActionIntent;
+
+/// Section of `.rhema/repository.yaml` configuring execution isolation
+pub const ISOLATION_CONFIG_SECTION: &str = "action_isolation";
+
+/// Where action tools run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IsolationMode {
+    /// Tools modify the developer's working tree directly
+    #[default]
+    InPlace,
+    /// Tools run in a temporary worktree that is merged back on success
+    Worktree,
+    /// Tools run against a copy of the intent's scope; the resulting diff is
+    /// applied once the post-execution safety checks pass
+    Sandbox,
+}
+
+/// Execution isolation configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IsolationConfig {
+    pub mode: IsolationMode,
+
+    /// Directory to create worktrees and sandboxes in; defaults to the system
+    /// temp directory
+    pub worktree_root: Option<PathBuf>,
+
+    /// Keep the worktree or sandbox of a failed intent for inspection
+    pub keep_on_failure: bool,
+}
+
+impl IsolationConfig {
+    /// Load the configuration from the repository config and policy bundle,
+    /// falling back to defaults
+    pub fn load(repo_root: &Path) -> ActionResult<Self> {
+        let value = rhema_core::policy::repository_config(repo_root)
+            .map_err(|e| ActionError::configuration(e.to_string()))?;
+        match value.get(ISOLATION_CONFIG_SECTION) {
+            Some(section) => serde_yaml::from_value(section.clone()).map_err(|e| {
+                ActionError::configuration(format!(
+                    "Invalid {} section in the repository config: {}",
+                    ISOLATION_CONFIG_SECTION, e
+                ))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+/// Temporary git worktree an intent executes in
+#[derive(Debug)]
+pub struct IntentWorktree {
+    repo_root: PathBuf,
+    path: PathBuf,
+    /// Tree of the worktree before any tool ran, used to isolate tool changes
+    baseline_tree: String,
+    keep: bool,
+}
+
+impl IntentWorktree {
+    /// Create a worktree for an intent from `HEAD` and the developer's
+    /// uncommitted changes
+    pub fn create(
+        repo_root: &Path,
+        intent: &ActionIntent,
+        config: &IsolationConfig,
+    ) -> ActionResult<Self> {
+        let root = config
+            .worktree_root
+            .clone()
+            .unwrap_or_else(std::env::temp_dir);
+        std::fs::create_dir_all(&root)
+            .map_err(|e| ActionError::file_operation(root.clone(), e.to_string()))?;
+        let path = root.join(format!(
+            "rhema-action-{}-{}",
+            sanitize(&intent.id),
+            uuid::Uuid::new_v4().simple()
+        ));
+
+        git(
+            repo_root,
+            &[
+                "worktree",
+                "add",
+                "--detach",
+                &path.to_string_lossy(),
+                "HEAD",
+            ],
+        )?;
+        let mut worktree = Self {
+            repo_root: repo_root.to_path_buf(),
+            path,
+            baseline_tree: String::new(),
+            keep: false,
+        };
+
+        worktree.copy_local_changes()?;
+        worktree.baseline_tree = worktree.snapshot()?;
+
+        info!(
+            "Created worktree {} for intent {}",
+            worktree.path.display(),
+            intent.id
+        );
+        Ok(worktree)
+    }
+
+    /// Path of the worktree
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Keep the worktree on disk when it is dropped
+    pub fn keep(&mut self) {
+        self.keep = true;
+    }
+
+    /// The intent with its scope rewritten to point into the worktree
+    pub fn rebase_intent(&self, intent: &ActionIntent) -> ActionResult<ActionIntent> {
+        let cwd = std::env::current_dir().map_err(|e| {
+            ActionError::configuration(format!("Failed to get current directory: {}", e))
+        })?;
+
+        let mut rebased = intent.clone();
+        rebased.scope = intent
+            .scope
+            .iter()
+            .map(|target| self.map_path(&cwd.join(target)))
+            .collect();
+        Ok(rebased)
+    }
+
+    fn map_path(&self, path: &Path) -> String {
+        match path.strip_prefix(&self.repo_root) {
+            Ok(relative) => self.path.join(relative).to_string_lossy().to_string(),
+            Err(_) => path.to_string_lossy().to_string(),
+        }
+    }
+
+    /// Files the tools changed inside the worktree
+    pub fn changed_files(&self) -> ActionResult<Vec<String>> {
+        self.snapshot()?;
+        let output = git(
+            &self.path,
+            &["diff", "--cached", "--name-only", &self.baseline_tree],
+        )?;
+        Ok(output.lines().map(str::to_string).collect())
+    }
+
+    /// The tool changes as proposed changes for an approval request. Files that
+    /// are not valid UTF-8 are left out.
+    pub fn proposed_changes(&self) -> ActionResult<Vec<ProposedChange>> {
+        let mut changes = Vec::new();
+        for file in self.changed_files()? {
+            let spec = format!("{}:{}", self.baseline_tree, file);
+            let before = git_bytes(&self.path, &["cat-file", "blob", &spec]).ok();
+            let after = std::fs::read(self.path.join(&file)).ok();
+
+            let (Ok(before), Ok(after)) = (
+                before.map(String::from_utf8).transpose(),
+                after.map(String::from_utf8).transpose(),
+            ) else {
+                warn!("Leaving binary file {} out of the proposed changes", file);
+                continue;
+            };
+            changes.push(ProposedChange::new(file, before, after));
+        }
+        Ok(changes)
+    }
+
+    /// Apply the tool changes to the main working tree. Returns the changed files.
+    pub fn merge_back(&self) -> ActionResult<Vec<String>> {
+        let files = self.changed_files()?;
+        if files.is_empty() {
+            return Ok(files);
+        }
+
+        let patch = git(
+            &self.path,
+            &["diff", "--cached", "--binary", &self.baseline_tree],
+        )?;
+        git_with_input(
+            &self.repo_root,
+            &["apply", "--binary", "--whitespace=nowarn", "-"],
+            &patch,
+        )?;
+
+        info!(
+            "Merged {} changed files from {} back into {}",
+            files.len(),
+            self.path.display(),
+            self.repo_root.display()
+        );
+        Ok(files)
+    }
+
+    /// Bring tracked modifications and untracked files of the main working tree
+    /// into the worktree so tools see what the developer sees
+    fn copy_local_changes(&self) -> ActionResult<()> {
+        let patch = git(&self.repo_root, &["diff", "HEAD", "--binary"])?;
+        if !patch.trim().is_empty() {
+            git_with_input(&self.path, &["apply", "--binary", "-"], &patch)?;
+        }
+
+        let untracked = git(
+            &self.repo_root,
+            &["ls-files", "--others", "--exclude-standard"],
+        )?;
+        for file in untracked.lines().filter(|line| !line.is_empty()) {
+            let target = self.path.join(file);
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| {
+                    ActionError::file_operation(parent.to_path_buf(), e.to_string())
+                })?;
+            }
+            std::fs::copy(self.repo_root.join(file), &target)
+                .map_err(|e| ActionError::file_operation(target.clone(), e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Stage everything in the worktree and return the resulting tree id
+    fn snapshot(&self) -> ActionResult<String> {
+        git(&self.path, &["add", "--all"])?;
+        Ok(git(&self.path, &["write-tree"])?.trim().to_string())
+    }
+}
+
+impl Drop for IntentWorktree {
+    fn drop(&mut self) {
+        if self.keep {
+            info!("Keeping worktree {}", self.path.display());
+            return;
+        }
+        let path = self.path.to_string_lossy().to_string();
+        if let Err(e) = git(&self.repo_root, &["worktree", "remove", "--force", &path]) {
+            warn!("Failed to remove worktree {}: {}", path, e);
+        }
+    }
+}
+
+/// Repository root containing `path`
+pub fn repository_root(path: &Path) -> ActionResult<PathBuf> {
+    Ok(PathBuf::from(
+        git(path, &["rev-parse", "--show-toplevel"])?.trim(),
+    ))
+}
+
+fn sanitize(id: &str) -> String {
+    id.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn git(dir: &Path, args: &[&str]) -> ActionResult<String> {
+    Ok(String::from_utf8_lossy(&git_bytes(dir, args)?).to_string())
+}
+
+fn git_bytes(dir: &Path, args: &[&str]) -> ActionResult<Vec<u8>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| ActionError::git(args[0], format!("Failed to run git: {}", e)))?;
+    if !output.status.success() {
+        return Err(ActionError::git(
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(output.stdout)
+}
+
+fn git_with_input(dir: &Path, args: &[&str], input: &str) -> ActionResult<()> {
+    let mut child = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| ActionError::git(args[0], format!("Failed to run git: {}", e)))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input.as_bytes())
+            .map_err(|e| ActionError::git(args[0], format!("Failed to write to git: {}", e)))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| ActionError::git(args[0], format!("Failed to run git: {}", e)))?;
+    if !output.status.success() {
+        return Err(ActionError::git(
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::{ActionType, SafetyLevel};
+    use tempfile::TempDir;
+
+    fn init_repo() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        git(dir.path(), &["init", "--quiet"]).unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "fn old() {}\n").unwrap();
+        git(dir.path(), &["add", "lib.rs"]).unwrap();
+        git(
+            dir.path(),
+            &[
+                "-c",
+                "user.name=Test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "--quiet",
+                "-m",
+                "initial",
+            ],
+        )
+        .unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_worktree_changes_merge_back_only_when_asked() {
+        let repo = init_repo();
+        let root = repository_root(repo.path()).unwrap();
+        // Uncommitted edits are visible inside the worktree
+        std::fs::write(root.join("lib.rs"), "fn local() {}\n").unwrap();
+
+        let intent = ActionIntent::new(
+            "rename-001",
+            ActionType::Refactor,
+            "Rename",
+            vec![root.join("lib.rs").to_string_lossy().to_string()],
+            SafetyLevel::Low,
+        );
+        let worktree_root = TempDir::new().unwrap();
+        let config = IsolationConfig {
+            mode: IsolationMode::Worktree,
+            worktree_root: Some(worktree_root.path().to_path_buf()),
+            keep_on_failure: false,
+        };
+        let worktree = IntentWorktree::create(&root, &intent, &config).unwrap();
+        let rebased = worktree.rebase_intent(&intent).unwrap();
+        assert!(rebased.scope[0].starts_with(&*worktree.path().to_string_lossy()));
+
+        let isolated = worktree.path().join("lib.rs");
+        assert_eq!(
+            std::fs::read_to_string(&isolated).unwrap(),
+            "fn local() {}\n"
+        );
+
+        std::fs::write(&isolated, "fn new() {}\n").unwrap();
+        std::fs::write(worktree.path().join("added.rs"), "fn added() {}\n").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(root.join("lib.rs")).unwrap(),
+            "fn local() {}\n"
+        );
+
+        // Proposed changes are relative to the developer's working tree
+        let proposed = worktree.proposed_changes().unwrap();
+        assert_eq!(
+            proposed,
+            vec![
+                ProposedChange::new("added.rs", None, Some("fn added() {}\n".to_string())),
+                ProposedChange::new(
+                    "lib.rs",
+                    Some("fn local() {}\n".to_string()),
+                    Some("fn new() {}\n".to_string())
+                ),
+            ]
+        );
+
+        let merged = worktree.merge_back().unwrap();
+        assert_eq!(merged, vec!["added.rs".to_string(), "lib.rs".to_string()]);
+        assert_eq!(
+            std::fs::read_to_string(root.join("lib.rs")).unwrap(),
+            "fn new() {}\n"
+        );
+        assert!(root.join("added.rs").exists());
+
+        let path = worktree.path().to_path_buf();
+        drop(worktree);
+        assert!(!path.exists());
+    }
+}
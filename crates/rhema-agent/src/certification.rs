@@ -0,0 +1,475 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Capability certification through simulated tasks.
+//!
+//! Claiming a capability such as `rust-refactor` at registration does not make
+//! it trusted. A [`CertificationSuite`] pairs the capability with a simulation
+//! fixture of canned tasks and a passing bar; the [`CertificationHarness`]
+//! runs the agent through the suite with side effects virtualized and records
+//! the outcome in the [`AgentRegistry`].
+//!
+//! Requests name the capability they exercise and the safety level of the
+//! action in their metadata. For an untrusted capability, only low safety-level
+//! actions are allowed, and only once someone has approved them.
+
+use crate::agent::{AgentCapability, AgentId, AgentRequest};
+use crate::error::{AgentError, AgentResult};
+use crate::registry::AgentRegistry;
+use crate::simulation::{
+    SimulationFixture, SimulationHarness, SimulationOptions, SimulationReport,
+};
+use chrono::{DateTime, Duration, Utc};
+use rhema_action_tool::SafetyLevel;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Request metadata key naming the capability a request exercises
+pub const CAPABILITY_METADATA_KEY: &str = "capability";
+
+/// Request metadata key with the safety level of the requested action
+pub const SAFETY_LEVEL_METADATA_KEY: &str = "safety_level";
+
+/// Request metadata key recording who approved a request
+pub const APPROVED_BY_METADATA_KEY: &str = "approved_by";
+
+/// Canned tasks an agent must pass before a capability is trusted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CertificationSuite {
+    /// Capability certified by passing, e.g. `rust-refactor`
+    pub capability: String,
+    #[serde(default)]
+    pub description: String,
+    /// Repository snapshot and the tasks to run against it
+    pub fixture: SimulationFixture,
+    /// Lowest simulation score that passes
+    #[serde(default = "default_min_score")]
+    pub min_score: f64,
+    /// Constraint violations tolerated across the suite
+    #[serde(default)]
+    pub max_violations: usize,
+    /// Days a passing certification stays valid; forever when unset
+    #[serde(default)]
+    pub valid_for_days: Option<i64>,
+}
+
+fn default_min_score() -> f64 {
+    90.0
+}
+
+impl CertificationSuite {
+    /// Load a suite from a YAML file
+    pub fn load(path: &Path) -> AgentResult<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| AgentError::StorageError {
+            reason: format!(
+                "Failed to read certification suite {}: {}",
+                path.display(),
+                e
+            ),
+        })?;
+        serde_yaml::from_str(&content).map_err(|e| AgentError::DeserializationError {
+            reason: format!("Invalid certification suite {}: {}", path.display(), e),
+        })
+    }
+
+    /// Judge a simulation report of this suite
+    pub fn judge(&self, agent_id: &AgentId, report: &SimulationReport) -> CapabilityCertification {
+        let mut failures: Vec<String> = report
+            .tasks
+            .iter()
+            .filter(|task| !task.completed)
+            .map(|task| match &task.error {
+                Some(error) => format!("{}: {}", task.task_id, error),
+                None => format!(
+                    "{}: {}/{} expectations met",
+                    task.task_id, task.expectations_met, task.expectations_total
+                ),
+            })
+            .collect();
+        if report.violations > self.max_violations {
+            failures.push(format!(
+                "{} constraint violations, {} allowed",
+                report.violations, self.max_violations
+            ));
+        }
+        let passed = report.score >= self.min_score && report.violations <= self.max_violations;
+        let certified_at = Utc::now();
+
+        CapabilityCertification {
+            agent_id: agent_id.clone(),
+            capability: self.capability.clone(),
+            suite: self.fixture.name.clone(),
+            passed,
+            score: report.score,
+            tasks_completed: report.tasks_completed,
+            tasks_total: report.tasks.len(),
+            violations: report.violations,
+            failures,
+            certified_at,
+            expires_at: self
+                .valid_for_days
+                .filter(|_| passed)
+                .map(|days| certified_at + Duration::days(days)),
+        }
+    }
+}
+
+/// Outcome of running an agent through a certification suite
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapabilityCertification {
+    pub agent_id: AgentId,
+    pub capability: String,
+    /// Name of the fixture the suite ran
+    pub suite: String,
+    pub passed: bool,
+    pub score: f64,
+    pub tasks_completed: usize,
+    pub tasks_total: usize,
+    pub violations: usize,
+    /// Tasks not completed and limits exceeded, when the suite failed
+    pub failures: Vec<String>,
+    pub certified_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl CapabilityCertification {
+    /// Whether the certification makes the capability trusted at `now`
+    pub fn is_trusted_at(&self, now: DateTime<Utc>) -> bool {
+        self.passed && self.expires_at.map_or(true, |expires| now < expires)
+    }
+}
+
+/// Whether a request may run given the trust in its capability
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TrustDecision {
+    Allowed,
+    /// Untrusted capability, low safety level, not yet approved
+    RequiresApproval {
+        reason: String,
+    },
+    Denied {
+        reason: String,
+    },
+}
+
+/// Name under which a capability is certified; custom capabilities use
+/// their own name
+pub fn capability_name(capability: &AgentCapability) -> String {
+    match capability {
+        AgentCapability::Custom(name) => name.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Parse a safety level as written in request metadata
+pub fn parse_safety_level(value: &str) -> Option<SafetyLevel> {
+    match value.to_lowercase().as_str() {
+        "low" => Some(SafetyLevel::Low),
+        "medium" => Some(SafetyLevel::Medium),
+        "high" => Some(SafetyLevel::High),
+        "critical" => Some(SafetyLevel::Critical),
+        _ => None,
+    }
+}
+
+/// Decide whether `request` may run given whether the capability it
+/// exercises is trusted
+pub(crate) fn trust_decision(
+    request: &AgentRequest,
+    capability: &str,
+    trusted: bool,
+) -> TrustDecision {
+    if trusted {
+        return TrustDecision::Allowed;
+    }
+    let level = request
+        .metadata
+        .get(SAFETY_LEVEL_METADATA_KEY)
+        .and_then(|level| parse_safety_level(level));
+    match level {
+        Some(SafetyLevel::Low) => {
+            if request.metadata.contains_key(APPROVED_BY_METADATA_KEY) {
+                TrustDecision::Allowed
+            } else {
+                TrustDecision::RequiresApproval {
+                    reason: format!(
+                        "capability '{}' is not certified; low safety-level actions need approval",
+                        capability
+                    ),
+                }
+            }
+        }
+        Some(level) => TrustDecision::Denied {
+            reason: format!(
+                "capability '{}' is not certified; {:?} safety-level actions are not allowed",
+                capability, level
+            ),
+        },
+        None => TrustDecision::Denied {
+            reason: format!(
+                "capability '{}' is not certified and the request declares no safety level",
+                capability
+            ),
+        },
+    }
+}
+
+/// Runs agents through certification suites and records the results
+pub struct CertificationHarness {
+    suites: BTreeMap<String, CertificationSuite>,
+    options: SimulationOptions,
+}
+
+impl CertificationHarness {
+    pub fn new() -> Self {
+        Self {
+            suites: BTreeMap::new(),
+            options: SimulationOptions::default(),
+        }
+    }
+
+    /// Add a suite, replacing any suite for the same capability
+    pub fn with_suite(mut self, suite: CertificationSuite) -> Self {
+        self.suites.insert(suite.capability.clone(), suite);
+        self
+    }
+
+    pub fn with_options(mut self, options: SimulationOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Load every `.yaml` suite in a directory
+    pub fn load_dir(mut self, dir: &Path) -> AgentResult<Self> {
+        let entries = std::fs::read_dir(dir).map_err(|e| AgentError::StorageError {
+            reason: format!("Failed to read suites in {}: {}", dir.display(), e),
+        })?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|s| s.to_str()) == Some("yaml") {
+                self = self.with_suite(CertificationSuite::load(&path)?);
+            }
+        }
+        Ok(self)
+    }
+
+    pub fn suite(&self, capability: &str) -> Option<&CertificationSuite> {
+        self.suites.get(capability)
+    }
+
+    /// Certify every capability the agent claims that has a suite
+    pub async fn certify(
+        &self,
+        registry: &AgentRegistry,
+        agent_id: &AgentId,
+    ) -> AgentResult<Vec<CapabilityCertification>> {
+        let entry = registry.get_entry(agent_id).await?;
+        let mut certifications = Vec::new();
+        for capability in entry.capabilities.iter().map(capability_name) {
+            if self.suites.contains_key(&capability) {
+                certifications.push(
+                    self.certify_capability(registry, agent_id, &capability)
+                        .await?,
+                );
+            }
+        }
+        Ok(certifications)
+    }
+
+    /// Run the suite for one capability and record the outcome, pass or fail
+    pub async fn certify_capability(
+        &self,
+        registry: &AgentRegistry,
+        agent_id: &AgentId,
+        capability: &str,
+    ) -> AgentResult<CapabilityCertification> {
+        let suite =
+            self.suites
+                .get(capability)
+                .ok_or_else(|| AgentError::CapabilityNotAvailable {
+                    capability: format!("{} (no certification suite)", capability),
+                })?;
+        let entry = registry.get_entry(agent_id).await?;
+        if !entry
+            .capabilities
+            .iter()
+            .any(|claimed| capability_name(claimed) == capability)
+        {
+            return Err(AgentError::CapabilityNotAvailable {
+                capability: format!("{} (not claimed by {})", capability, agent_id),
+            });
+        }
+
+        let harness =
+            SimulationHarness::new(suite.fixture.clone())?.with_options(self.options.clone());
+        let agent = registry.get_agent(agent_id).await?;
+        let report = {
+            let mut agent = agent.write().await;
+            harness.run(agent.as_mut()).await?
+        };
+
+        let certification = suite.judge(agent_id, &report);
+        registry.record_certification(certification.clone()).await?;
+        Ok(certification)
+    }
+}
+
+impl Default for CertificationHarness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::{AgentConfig, BaseAgent};
+    use crate::simulation::{SimulationConstraints, SimulationTask, TaskExpectation};
+
+    fn suite() -> CertificationSuite {
+        CertificationSuite {
+            capability: "rust-refactor".to_string(),
+            description: String::new(),
+            fixture: SimulationFixture {
+                name: "refactor-basics".to_string(),
+                description: None,
+                recorded_at: Utc::now(),
+                branch: "main".to_string(),
+                files: BTreeMap::new(),
+                tasks: vec![SimulationTask {
+                    id: "rename".to_string(),
+                    description: "Rename a function".to_string(),
+                    request_type: "refactor".to_string(),
+                    payload: serde_json::json!({}),
+                    expectations: vec![TaskExpectation::Succeeds],
+                    timeout: None,
+                }],
+                constraints: SimulationConstraints::default(),
+                task_timeout: 5,
+            },
+            min_score: 90.0,
+            max_violations: 0,
+            valid_for_days: Some(30),
+        }
+    }
+
+    fn request(level: &str) -> AgentRequest {
+        let mut request = AgentRequest::new("refactor".to_string(), serde_json::json!({}));
+        request.metadata.insert(
+            CAPABILITY_METADATA_KEY.to_string(),
+            "rust-refactor".to_string(),
+        );
+        request
+            .metadata
+            .insert(SAFETY_LEVEL_METADATA_KEY.to_string(), level.to_string());
+        request
+    }
+
+    #[tokio::test]
+    async fn test_failed_certification_restricts_requests() {
+        let registry = AgentRegistry::new();
+        let config = AgentConfig {
+            name: "refactorer".to_string(),
+            capabilities: vec![AgentCapability::Custom("rust-refactor".to_string())],
+            ..Default::default()
+        };
+        registry
+            .register(Box::new(BaseAgent::new("refactorer".to_string(), config)))
+            .await
+            .unwrap();
+        let agent_id = "refactorer".to_string();
+
+        let harness = CertificationHarness::new().with_suite(suite());
+        let certifications = harness.certify(&registry, &agent_id).await.unwrap();
+        assert_eq!(certifications.len(), 1);
+        assert!(!certifications[0].passed);
+        assert_eq!(certifications[0].failures.len(), 1);
+        assert!(
+            !registry
+                .is_capability_trusted(&agent_id, "rust-refactor")
+                .await
+        );
+
+        assert!(matches!(
+            registry
+                .authorize_request(&agent_id, &request("low"))
+                .await
+                .unwrap(),
+            TrustDecision::RequiresApproval { .. }
+        ));
+        let mut approved = request("low");
+        approved
+            .metadata
+            .insert(APPROVED_BY_METADATA_KEY.to_string(), "alice".to_string());
+        assert_eq!(
+            registry
+                .authorize_request(&agent_id, &approved)
+                .await
+                .unwrap(),
+            TrustDecision::Allowed
+        );
+        assert!(matches!(
+            registry
+                .authorize_request(&agent_id, &request("high"))
+                .await
+                .unwrap(),
+            TrustDecision::Denied { .. }
+        ));
+
+        let mut passed = certifications[0].clone();
+        passed.passed = true;
+        registry.record_certification(passed).await.unwrap();
+        assert_eq!(
+            registry
+                .authorize_request(&agent_id, &request("critical"))
+                .await
+                .unwrap(),
+            TrustDecision::Allowed
+        );
+    }
+
+    #[test]
+    fn test_judge_applies_thresholds_and_expiry() {
+        let suite = suite();
+        let report = SimulationReport {
+            agent: "refactorer".to_string(),
+            fixture: "refactor-basics".to_string(),
+            started_at: Utc::now(),
+            tasks: Vec::new(),
+            tasks_completed: 1,
+            completion_rate: 1.0,
+            violations: 1,
+            total_tokens: 0,
+            estimated_cost: 0.0,
+            score: 95.0,
+            commits: Vec::new(),
+            commands: Vec::new(),
+            files_changed: Vec::new(),
+        };
+        let failed = suite.judge(&"refactorer".to_string(), &report);
+        assert!(!failed.passed);
+        assert!(failed.expires_at.is_none());
+
+        let clean = SimulationReport {
+            violations: 0,
+            ..report
+        };
+        let passed = suite.judge(&"refactorer".to_string(), &clean);
+        assert!(passed.is_trusted_at(Utc::now()));
+        assert!(!passed.is_trusted_at(Utc::now() + Duration::days(31)));
+    }
+}
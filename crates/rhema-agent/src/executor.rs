@@ -15,6 +15,7 @@
  */
 
 use crate::agent::{Agent, AgentId, AgentRequest, AgentResponse, AgentState};
+use crate::certification::TrustDecision;
 use crate::error::{AgentError, AgentResult};
 use crate::registry::AgentRegistry;
 use chrono::{DateTime, Utc};
@@ -243,6 +244,14 @@ impl AgentExecutor {
             });
         }
 
+        // Untrusted capabilities only run approved, low safety-level actions
+        match self.registry.authorize_request(agent_id, &request).await? {
+            TrustDecision::Allowed => {}
+            TrustDecision::RequiresApproval { reason } | TrustDecision::Denied { reason } => {
+                return Err(AgentError::PermissionDenied { operation: reason });
+            }
+        }
+
         // Create execution context
         let policy = self.get_execution_policy(&request).await;
         let mut context = ExecutionContext::new(agent_id.clone(), request.clone(), policy.clone());
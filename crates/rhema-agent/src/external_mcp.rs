@@ -0,0 +1,270 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Tools and resources of external MCP servers as an agent.
+//!
+//! [`ExternalMcpAgent`] wraps the connections opened by
+//! [`rhema_mcp::ExternalMcpClients`]. Each external tool becomes a
+//! `mcp:server.tool` capability, so other agents find it through the
+//! registry like any other capability, and requests of type
+//! [`MCP_CALL_TOOL`] or [`MCP_READ_RESOURCE`] are forwarded to the server
+//! that owns the namespaced name.
+
+use crate::agent::{
+    Agent, AgentCapability, AgentConfig, AgentContext, AgentId, AgentMessage, AgentRequest,
+    AgentResponse, AgentState, AgentStatus, AgentType, HealthStatus, ResourceUsage,
+};
+use crate::error::{AgentError, AgentResult};
+use async_trait::async_trait;
+use chrono::Utc;
+use rhema_mcp::ExternalMcpClients;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// Request type calling a tool; payload `{"tool": "server.tool", "arguments": {...}}`
+pub const MCP_CALL_TOOL: &str = "mcp.call_tool";
+
+/// Request type reading a resource; payload `{"uri": "server:uri"}`
+pub const MCP_READ_RESOURCE: &str = "mcp.read_resource";
+
+/// Request type listing the namespaced tools and resources
+pub const MCP_LIST: &str = "mcp.list";
+
+/// Capability offered for an external tool
+pub fn tool_capability(namespaced_tool: &str) -> AgentCapability {
+    AgentCapability::Custom(format!("mcp:{}", namespaced_tool))
+}
+
+/// Capability offered when any external server has resources
+pub fn resources_capability() -> AgentCapability {
+    AgentCapability::Custom("mcp:resources".to_string())
+}
+
+/// Agent exposing external MCP servers to the framework
+pub struct ExternalMcpAgent {
+    id: AgentId,
+    config: AgentConfig,
+    context: AgentContext,
+    clients: Arc<ExternalMcpClients>,
+}
+
+impl ExternalMcpAgent {
+    /// Build the agent, with one capability per tool the servers offer
+    pub async fn discover(id: AgentId, clients: Arc<ExternalMcpClients>) -> AgentResult<Self> {
+        let tools = clients.tools().await.map_err(execution_failed)?;
+        let resources = clients.resources().await.map_err(execution_failed)?;
+
+        let mut capabilities: Vec<AgentCapability> = tools
+            .iter()
+            .map(|tool| tool_capability(&tool.name))
+            .collect();
+        if !resources.is_empty() {
+            capabilities.push(resources_capability());
+        }
+
+        let config = AgentConfig {
+            name: "External MCP servers".to_string(),
+            description: Some(format!(
+                "Tools and resources of {}",
+                clients.servers().join(", ")
+            )),
+            agent_type: AgentType::Custom("ExternalMcp".to_string()),
+            capabilities,
+            max_concurrent_tasks: 8,
+            task_timeout: clients.config().request_timeout_secs,
+            retry_attempts: 1,
+            ..AgentConfig::default()
+        };
+        Ok(Self {
+            context: AgentContext::new(id.clone()),
+            id,
+            config,
+            clients,
+        })
+    }
+
+    pub fn clients(&self) -> &Arc<ExternalMcpClients> {
+        &self.clients
+    }
+
+    async fn dispatch(&self, request: &AgentRequest) -> AgentResult<Value> {
+        let field = |name: &str| {
+            request
+                .payload
+                .get(name)
+                .and_then(Value::as_str)
+                .ok_or_else(|| AgentError::ValidationError {
+                    reason: format!("{} requests need a '{}' field", request.request_type, name),
+                })
+        };
+        match request.request_type.as_str() {
+            MCP_CALL_TOOL => {
+                let tool = field("tool")?;
+                if !self.has_capability(&tool_capability(tool)) {
+                    return Err(AgentError::CapabilityNotAvailable {
+                        capability: tool.to_string(),
+                    });
+                }
+                let arguments = request
+                    .payload
+                    .get("arguments")
+                    .cloned()
+                    .unwrap_or_else(|| json!({}));
+                self.clients
+                    .call_tool(tool, arguments)
+                    .await
+                    .map_err(execution_failed)
+            }
+            MCP_READ_RESOURCE => self
+                .clients
+                .read_resource(field("uri")?)
+                .await
+                .map_err(execution_failed),
+            MCP_LIST => Ok(json!({
+                "tools": self.clients.tools().await.map_err(execution_failed)?,
+                "resources": self.clients.resources().await.map_err(execution_failed)?,
+            })),
+            other => Err(AgentError::ValidationError {
+                reason: format!(
+                    "Unsupported request type for external MCP servers: {}",
+                    other
+                ),
+            }),
+        }
+    }
+}
+
+fn execution_failed(error: rhema_core::RhemaError) -> AgentError {
+    AgentError::ExecutionFailed {
+        reason: error.to_string(),
+    }
+}
+
+#[async_trait]
+impl Agent for ExternalMcpAgent {
+    fn id(&self) -> &AgentId {
+        &self.id
+    }
+
+    fn config(&self) -> &AgentConfig {
+        &self.config
+    }
+
+    fn context(&self) -> &AgentContext {
+        &self.context
+    }
+
+    fn context_mut(&mut self) -> &mut AgentContext {
+        &mut self.context
+    }
+
+    async fn initialize(&mut self) -> AgentResult<()> {
+        self.update_state(AgentState::Initializing);
+        Ok(())
+    }
+
+    async fn start(&mut self) -> AgentResult<()> {
+        self.update_state(AgentState::Ready);
+        Ok(())
+    }
+
+    async fn stop(&mut self) -> AgentResult<()> {
+        self.update_state(AgentState::Stopped);
+        Ok(())
+    }
+
+    async fn handle_message(
+        &mut self,
+        _message: AgentMessage,
+    ) -> AgentResult<Option<AgentMessage>> {
+        Ok(None)
+    }
+
+    async fn execute_task(&mut self, request: AgentRequest) -> AgentResult<AgentResponse> {
+        let started = std::time::Instant::now();
+        self.set_current_task(Some(request.id.clone()));
+        let result = self.dispatch(&request).await;
+        self.set_current_task(None);
+        self.record_task_completion(result.is_ok());
+
+        let elapsed = started.elapsed().as_millis() as u64;
+        Ok(match result {
+            Ok(payload) => AgentResponse::success(request.id, payload),
+            Err(e) => AgentResponse::error(request.id, e.to_string()),
+        }
+        .with_execution_time(elapsed))
+    }
+
+    async fn get_status(&self) -> AgentResult<AgentStatus> {
+        Ok(AgentStatus {
+            agent_id: self.id.clone(),
+            state: self.context.state.clone(),
+            current_task: self.context.current_task.clone(),
+            health: self.check_health().await?,
+            resources: ResourceUsage::default(),
+            timestamp: Utc::now(),
+        })
+    }
+
+    /// Warning while some configured servers could not be connected
+    async fn check_health(&self) -> AgentResult<HealthStatus> {
+        Ok(
+            match (self.clients.servers().len(), self.clients.failures().len()) {
+                (_, 0) => HealthStatus::Healthy,
+                (0, _) => HealthStatus::Critical,
+                _ => HealthStatus::Warning,
+            },
+        )
+    }
+
+    fn capabilities(&self) -> &[AgentCapability] {
+        &self.config.capabilities
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rhema_mcp::McpClientsConfig;
+
+    #[tokio::test]
+    async fn test_agent_without_servers_rejects_unknown_tools() {
+        let clients = ExternalMcpClients::connect(McpClientsConfig::default())
+            .await
+            .unwrap();
+        let mut agent = ExternalMcpAgent::discover("external-mcp".to_string(), Arc::new(clients))
+            .await
+            .unwrap();
+        assert!(agent.capabilities().is_empty());
+        assert_eq!(agent.check_health().await.unwrap(), HealthStatus::Healthy);
+
+        let request = AgentRequest::new(
+            MCP_CALL_TOOL.to_string(),
+            json!({"tool": "jira.search", "arguments": {"q": "bug"}}),
+        );
+        let response = agent.execute_task(request).await.unwrap();
+        assert!(response.error.unwrap().contains("jira.search"));
+
+        let listing = agent
+            .execute_task(AgentRequest::new(MCP_LIST.to_string(), json!({})))
+            .await
+            .unwrap();
+        assert_eq!(
+            listing.payload.unwrap(),
+            json!({"tools": [], "resources": []})
+        );
+    }
+}
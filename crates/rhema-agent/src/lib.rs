@@ -16,14 +16,17 @@
 
 pub mod agent;
 pub mod capabilities;
+pub mod certification;
 pub mod communication;
 pub mod coordinator;
 pub mod error;
 pub mod executor;
+pub mod external_mcp;
 pub mod lifecycle;
 pub mod metrics;
 pub mod policies;
 pub mod registry;
+pub mod simulation;
 pub mod workflow;
 // Re-export main components for easy access
 pub use agent::{
@@ -31,14 +34,22 @@ pub use agent::{
     AgentResponse, AgentState, AgentType, BaseAgent,
 };
 pub use capabilities::{CapabilityManager, CapabilityRequest, CapabilityResponse};
+pub use certification::{
+    CapabilityCertification, CertificationHarness, CertificationSuite, TrustDecision,
+};
 pub use communication::{MessageBroker, MessageHandler, MessagePriority, MessageType};
 pub use coordinator::{AgentCoordinator, CoordinationPolicy, CoordinationResult};
 pub use error::{AgentError, AgentResult};
 pub use executor::{AgentExecutor, ExecutionContext, ExecutionPolicy, ExecutionResult};
+pub use external_mcp::{ExternalMcpAgent, MCP_CALL_TOOL, MCP_LIST, MCP_READ_RESOURCE};
 pub use lifecycle::{AgentLifecycle, LifecycleEvent, LifecycleState};
 pub use metrics::{AgentMetrics, MetricsCollector, PerformanceMetrics};
 pub use policies::{Policy, PolicyEnforcement, PolicyEngine, PolicyViolation};
 pub use registry::{AgentRegistry, RegistryEntry, RegistryQuery};
+pub use simulation::{
+    compare_reports, SimulatedEffect, SimulationFixture, SimulationHarness, SimulationReport,
+    SimulationTask, TaskExpectation, VirtualWorkspace,
+};
 pub use workflow::{
     WorkflowCondition, WorkflowDefinition, WorkflowEngine, WorkflowExecutionContext, WorkflowStats,
     WorkflowStatus, WorkflowStep, WorkflowStepType,
@@ -19,6 +19,10 @@ use crate::agent::{
     AgentMessage, AgentRequest, AgentResponse, AgentState, AgentStatus, AgentType,
     CoordinationMessage, CustomMessage, HealthStatus, ResourceUsage,
 };
+use crate::certification::{
+    capability_name, trust_decision, CapabilityCertification, TrustDecision,
+    CAPABILITY_METADATA_KEY,
+};
 use crate::error::{AgentError, AgentResult};
 use chrono::{DateTime, Utc};
 use dashmap::DashMap;
@@ -46,6 +50,8 @@ pub struct RegistryEntry {
     pub last_activity: DateTime<Utc>,
     /// Agent metadata
     pub metadata: HashMap<String, String>,
+    /// Latest certification per capability name, passed or not
+    pub certifications: HashMap<String, CapabilityCertification>,
 }
 
 impl RegistryEntry {
@@ -65,6 +71,7 @@ impl RegistryEntry {
             registered_at: now,
             last_activity: now,
             metadata: HashMap::new(),
+            certifications: HashMap::new(),
         }
     }
 
@@ -249,6 +256,7 @@ impl AgentRegistry {
             registered_at: Utc::now(),
             last_activity: Utc::now(),
             metadata: HashMap::new(),
+            certifications: HashMap::new(),
         };
 
         // Store agent and entry
@@ -496,6 +504,61 @@ impl AgentRegistry {
         Ok(())
     }
 
+    /// Record the outcome of a certification run, replacing any earlier one
+    /// for the same capability
+    pub async fn record_certification(
+        &self,
+        certification: CapabilityCertification,
+    ) -> AgentResult<()> {
+        let mut entry = self
+            .entries
+            .get_mut(&certification.agent_id)
+            .ok_or_else(|| AgentError::AgentNotFound {
+                agent_id: certification.agent_id.clone(),
+            })?;
+        entry
+            .certifications
+            .insert(certification.capability.clone(), certification);
+        Ok(())
+    }
+
+    /// Whether the agent holds a current, passing certification for the capability
+    pub async fn is_capability_trusted(&self, agent_id: &AgentId, capability: &str) -> bool {
+        self.entries
+            .get(agent_id)
+            .and_then(|entry| {
+                entry
+                    .certifications
+                    .get(capability)
+                    .map(|certification| certification.is_trusted_at(Utc::now()))
+            })
+            .unwrap_or(false)
+    }
+
+    /// Decide whether the agent may run `request`. Requests that name no
+    /// capability are not subject to certification.
+    pub async fn authorize_request(
+        &self,
+        agent_id: &AgentId,
+        request: &AgentRequest,
+    ) -> AgentResult<TrustDecision> {
+        let Some(capability) = request.metadata.get(CAPABILITY_METADATA_KEY) else {
+            return Ok(TrustDecision::Allowed);
+        };
+        let entry = self.get_entry(agent_id).await?;
+        if !entry
+            .capabilities
+            .iter()
+            .any(|claimed| capability_name(claimed) == *capability)
+        {
+            return Err(AgentError::CapabilityNotAvailable {
+                capability: capability.clone(),
+            });
+        }
+        let trusted = self.is_capability_trusted(agent_id, capability).await;
+        Ok(trust_decision(request, capability, trusted))
+    }
+
     /// Shutdown the registry
     pub async fn shutdown(&self) -> AgentResult<()> {
         // Stop all agents
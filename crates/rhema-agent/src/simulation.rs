@@ -0,0 +1,1045 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Agent simulation against recorded repository fixtures.
+//!
+//! A [`SimulationFixture`] is a snapshot of a repository's context files and
+//! selected source files, plus the tasks and constraints to evaluate against
+//! it. The [`SimulationHarness`] hands each task to an agent through the normal
+//! [`Agent::execute_task`] entry point with the fixture's files in the payload.
+//! Agents describe the side effects they want (file writes, commits, commands)
+//! as [`SimulatedEffect`]s in their response instead of performing them; the
+//! harness applies them to an in-memory [`VirtualWorkspace`], so nothing
+//! touches the real file system or git repository.
+//!
+//! Each run produces a [`SimulationReport`] scoring task completion,
+//! constraint violations and token cost. Reports from different agents or
+//! agent revisions run on the same fixture can be compared side by side.
+
+use crate::agent::{Agent, AgentRequest, AgentResponse, ResponseStatus};
+use crate::error::{AgentError, AgentResult};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Request metadata key telling agents they run under simulation
+pub const SIMULATION_METADATA_KEY: &str = "simulation";
+
+/// Directories never captured when recording a fixture
+const SKIPPED_DIRS: &[&str] = &[".git", "target", "node_modules", ".rhema-snapshots"];
+
+/// Options for recording a fixture from a repository
+#[derive(Debug, Clone)]
+pub struct RecordOptions {
+    /// Fixture name
+    pub name: String,
+    /// Source file extensions to capture in addition to context files
+    pub code_extensions: Vec<String>,
+    /// Files larger than this are skipped
+    pub max_file_bytes: u64,
+    /// Stop after this many files
+    pub max_files: usize,
+}
+
+impl Default for RecordOptions {
+    fn default() -> Self {
+        Self {
+            name: "fixture".to_string(),
+            code_extensions: vec!["rs".to_string(), "ts".to_string(), "py".to_string()],
+            max_file_bytes: 256 * 1024,
+            max_files: 2000,
+        }
+    }
+}
+
+/// Constraints an agent must respect while running a fixture
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SimulationConstraints {
+    /// Path prefixes the agent must not modify
+    pub protected_paths: Vec<String>,
+    /// Regex patterns for commands the agent must not run
+    pub forbidden_commands: Vec<String>,
+    /// Token budget per task
+    pub max_tokens_per_task: Option<u64>,
+    /// Files a single task may change
+    pub max_files_changed_per_task: Option<usize>,
+}
+
+/// Check evaluated against the workspace after a task to decide completion
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TaskExpectation {
+    /// The agent responded with success
+    Succeeds,
+    FileExists {
+        path: String,
+    },
+    FileAbsent {
+        path: String,
+    },
+    /// File content matches a regex
+    FileMatches {
+        path: String,
+        pattern: String,
+    },
+    /// File content is the same as in the fixture
+    FileUnchanged {
+        path: String,
+    },
+    /// A commit whose message contains the text was recorded
+    CommitCreated {
+        message_contains: String,
+    },
+    /// A response payload field has the expected value
+    ResponseField {
+        pointer: String,
+        equals: serde_json::Value,
+    },
+}
+
+/// Task the agent is asked to perform
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationTask {
+    pub id: String,
+    #[serde(default)]
+    pub description: String,
+    pub request_type: String,
+    #[serde(default)]
+    pub payload: serde_json::Value,
+    #[serde(default)]
+    pub expectations: Vec<TaskExpectation>,
+    /// Timeout in seconds, defaults to the fixture's
+    #[serde(default)]
+    pub timeout: Option<u64>,
+}
+
+/// Recorded repository snapshot with the tasks to run against it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationFixture {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub recorded_at: DateTime<Utc>,
+    /// Branch checked out when the fixture was recorded
+    #[serde(default = "default_branch")]
+    pub branch: String,
+    /// Repository-relative path to file content
+    pub files: BTreeMap<String, String>,
+    #[serde(default)]
+    pub tasks: Vec<SimulationTask>,
+    #[serde(default)]
+    pub constraints: SimulationConstraints,
+    /// Default task timeout in seconds
+    #[serde(default = "default_task_timeout")]
+    pub task_timeout: u64,
+}
+
+fn default_branch() -> String {
+    "main".to_string()
+}
+
+fn default_task_timeout() -> u64 {
+    60
+}
+
+impl SimulationFixture {
+    /// Record the context files and selected source files of a repository
+    pub fn record(repo_root: &Path, options: &RecordOptions) -> AgentResult<Self> {
+        let mut files = BTreeMap::new();
+        collect_files(repo_root, repo_root, options, &mut files)?;
+
+        let branch = std::fs::read_to_string(repo_root.join(".git").join("HEAD"))
+            .ok()
+            .and_then(|head| {
+                head.trim()
+                    .strip_prefix("ref: refs/heads/")
+                    .map(str::to_string)
+            })
+            .unwrap_or_else(default_branch);
+
+        Ok(Self {
+            name: options.name.clone(),
+            description: None,
+            recorded_at: Utc::now(),
+            branch,
+            files,
+            tasks: Vec::new(),
+            constraints: SimulationConstraints::default(),
+            task_timeout: default_task_timeout(),
+        })
+    }
+
+    /// Load a fixture from a YAML file
+    pub fn load(path: &Path) -> AgentResult<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| AgentError::StorageError {
+            reason: format!("Failed to read fixture {}: {}", path.display(), e),
+        })?;
+        serde_yaml::from_str(&content).map_err(|e| AgentError::DeserializationError {
+            reason: format!("Invalid fixture {}: {}", path.display(), e),
+        })
+    }
+
+    /// Save the fixture as YAML
+    pub fn save(&self, path: &Path) -> AgentResult<()> {
+        let content = serde_yaml::to_string(self).map_err(|e| AgentError::SerializationError {
+            reason: e.to_string(),
+        })?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| AgentError::StorageError {
+                reason: e.to_string(),
+            })?;
+        }
+        std::fs::write(path, content).map_err(|e| AgentError::StorageError {
+            reason: format!("Failed to write fixture {}: {}", path.display(), e),
+        })
+    }
+
+    /// Context files only, keyed by path
+    pub fn context_files(&self) -> BTreeMap<String, String> {
+        self.files
+            .iter()
+            .filter(|(path, _)| is_context_file(path))
+            .map(|(path, content)| (path.clone(), content.clone()))
+            .collect()
+    }
+}
+
+fn is_context_file(path: &str) -> bool {
+    path.starts_with(".rhema/")
+        || path.contains("/.rhema/")
+        || path == "rhema.yaml"
+        || path.ends_with("/rhema.yaml")
+}
+
+fn collect_files(
+    root: &Path,
+    dir: &Path,
+    options: &RecordOptions,
+    files: &mut BTreeMap<String, String>,
+) -> AgentResult<()> {
+    let entries = std::fs::read_dir(dir).map_err(|e| AgentError::StorageError {
+        reason: format!("Failed to read {}: {}", dir.display(), e),
+    })?;
+    let mut entries: Vec<PathBuf> = entries.filter_map(|e| e.ok().map(|e| e.path())).collect();
+    entries.sort();
+
+    for path in entries {
+        if files.len() >= options.max_files {
+            break;
+        }
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if path.is_dir() {
+            if !SKIPPED_DIRS.contains(&name.as_str()) {
+                collect_files(root, &path, options, files)?;
+            }
+            continue;
+        }
+
+        let relative = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let extension = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let wanted = (is_context_file(&relative) && matches!(extension.as_str(), "yaml" | "yml"))
+            || options.code_extensions.contains(&extension);
+        let small = std::fs::metadata(&path)
+            .map(|m| m.len() <= options.max_file_bytes)
+            .unwrap_or(false);
+        if wanted && small {
+            // Binary or non-UTF-8 files are not useful to agents
+            if let Ok(content) = std::fs::read_to_string(&path) {
+                files.insert(relative, content);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Side effect requested by an agent, applied to the virtual workspace
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SimulatedEffect {
+    WriteFile { path: String, content: String },
+    DeleteFile { path: String },
+    GitBranch { name: String },
+    GitCommit { message: String },
+    RunCommand { command: String },
+}
+
+/// Commit recorded by the virtual git repository
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VirtualCommit {
+    pub branch: String,
+    pub message: String,
+    pub files: Vec<String>,
+}
+
+/// In-memory file system and git history seeded from a fixture
+#[derive(Debug, Clone)]
+pub struct VirtualWorkspace {
+    original: BTreeMap<String, String>,
+    files: BTreeMap<String, String>,
+    branch: String,
+    commits: Vec<VirtualCommit>,
+    commands: Vec<String>,
+    /// Files changed since the last commit
+    staged: Vec<String>,
+}
+
+impl VirtualWorkspace {
+    pub fn new(fixture: &SimulationFixture) -> Self {
+        Self {
+            original: fixture.files.clone(),
+            files: fixture.files.clone(),
+            branch: fixture.branch.clone(),
+            commits: Vec::new(),
+            commands: Vec::new(),
+            staged: Vec::new(),
+        }
+    }
+
+    pub fn read(&self, path: &str) -> Option<&str> {
+        self.files.get(path).map(String::as_str)
+    }
+
+    pub fn files(&self) -> &BTreeMap<String, String> {
+        &self.files
+    }
+
+    pub fn branch(&self) -> &str {
+        &self.branch
+    }
+
+    pub fn commits(&self) -> &[VirtualCommit] {
+        &self.commits
+    }
+
+    /// Commands the agent asked to run; none are executed
+    pub fn commands(&self) -> &[String] {
+        &self.commands
+    }
+
+    /// Paths whose content differs from the fixture
+    pub fn changed_files(&self) -> Vec<String> {
+        let mut changed: Vec<String> = self
+            .files
+            .iter()
+            .filter(|(path, content)| self.original.get(*path) != Some(*content))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.extend(
+            self.original
+                .keys()
+                .filter(|path| !self.files.contains_key(*path))
+                .cloned(),
+        );
+        changed.sort();
+        changed
+    }
+
+    fn apply(&mut self, effect: &SimulatedEffect) {
+        match effect {
+            SimulatedEffect::WriteFile { path, content } => {
+                self.files.insert(path.clone(), content.clone());
+                self.stage(path);
+            }
+            SimulatedEffect::DeleteFile { path } => {
+                if self.files.remove(path).is_some() {
+                    self.stage(path);
+                }
+            }
+            SimulatedEffect::GitBranch { name } => self.branch = name.clone(),
+            SimulatedEffect::GitCommit { message } => {
+                self.commits.push(VirtualCommit {
+                    branch: self.branch.clone(),
+                    message: message.clone(),
+                    files: std::mem::take(&mut self.staged),
+                });
+            }
+            SimulatedEffect::RunCommand { command } => self.commands.push(command.clone()),
+        }
+    }
+
+    fn stage(&mut self, path: &str) {
+        if !self.staged.iter().any(|p| p == path) {
+            self.staged.push(path.to_string());
+        }
+    }
+}
+
+/// Constraint broken by an agent during a task
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConstraintViolation {
+    pub task_id: String,
+    pub constraint: String,
+    pub detail: String,
+}
+
+/// Outcome of one task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskScore {
+    pub task_id: String,
+    pub completed: bool,
+    pub status: ResponseStatus,
+    pub expectations_met: usize,
+    pub expectations_total: usize,
+    /// Descriptions of expectations that were not met
+    pub failed_expectations: Vec<String>,
+    pub violations: Vec<ConstraintViolation>,
+    pub tokens: u64,
+    pub duration_ms: u64,
+    pub files_changed: usize,
+    pub error: Option<String>,
+}
+
+/// Pricing used to turn token counts into cost
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationOptions {
+    pub cost_per_1k_tokens: f64,
+    /// Points deducted from the score per violation
+    pub violation_penalty: f64,
+}
+
+impl Default for SimulationOptions {
+    fn default() -> Self {
+        Self {
+            cost_per_1k_tokens: 0.002,
+            violation_penalty: 10.0,
+        }
+    }
+}
+
+/// Scored result of running an agent against a fixture
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationReport {
+    pub agent: String,
+    pub fixture: String,
+    pub started_at: DateTime<Utc>,
+    pub tasks: Vec<TaskScore>,
+    pub tasks_completed: usize,
+    pub completion_rate: f64,
+    pub violations: usize,
+    pub total_tokens: u64,
+    pub estimated_cost: f64,
+    /// Completion percentage minus violation penalties, floored at zero
+    pub score: f64,
+    pub commits: Vec<VirtualCommit>,
+    pub commands: Vec<String>,
+    pub files_changed: Vec<String>,
+}
+
+impl SimulationReport {
+    /// Render the report as Markdown
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "# Simulation: {} on {}\n\n\
+             Score **{:.1}** | {}/{} tasks completed | {} violations | {} tokens (${:.4})\n\n\
+             | Task | Completed | Expectations | Violations | Tokens | Duration |\n\
+             |------|-----------|--------------|------------|--------|----------|\n",
+            self.agent,
+            self.fixture,
+            self.score,
+            self.tasks_completed,
+            self.tasks.len(),
+            self.violations,
+            self.total_tokens,
+            self.estimated_cost
+        );
+        for task in &self.tasks {
+            out.push_str(&format!(
+                "| {} | {} | {}/{} | {} | {} | {}ms |\n",
+                task.task_id,
+                if task.completed { "yes" } else { "no" },
+                task.expectations_met,
+                task.expectations_total,
+                task.violations.len(),
+                task.tokens,
+                task.duration_ms
+            ));
+        }
+
+        let problems: Vec<String> = self
+            .tasks
+            .iter()
+            .flat_map(|task| {
+                task.violations
+                    .iter()
+                    .map(|v| format!("- `{}` violated {}: {}", v.task_id, v.constraint, v.detail))
+                    .chain(
+                        task.failed_expectations.iter().map(move |e| {
+                            format!("- `{}` expectation not met: {}", task.task_id, e)
+                        }),
+                    )
+            })
+            .collect();
+        if !problems.is_empty() {
+            out.push_str("\n## Problems\n\n");
+            out.push_str(&problems.join("\n"));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Side-by-side comparison of reports, typically from different agents or
+/// agent revisions on the same fixture
+pub fn compare_reports(reports: &[SimulationReport]) -> String {
+    let mut out = String::from(
+        "| Agent | Fixture | Score | Completed | Violations | Tokens | Cost |\n\
+         |-------|---------|-------|-----------|------------|--------|------|\n",
+    );
+    let mut sorted: Vec<&SimulationReport> = reports.iter().collect();
+    sorted.sort_by(|a, b| b.score.total_cmp(&a.score));
+    for report in sorted {
+        out.push_str(&format!(
+            "| {} | {} | {:.1} | {}/{} | {} | {} | ${:.4} |\n",
+            report.agent,
+            report.fixture,
+            report.score,
+            report.tasks_completed,
+            report.tasks.len(),
+            report.violations,
+            report.total_tokens,
+            report.estimated_cost
+        ));
+    }
+    out
+}
+
+/// Runs agents against a fixture with side effects virtualized
+pub struct SimulationHarness {
+    fixture: SimulationFixture,
+    options: SimulationOptions,
+    forbidden_commands: Vec<Regex>,
+}
+
+impl SimulationHarness {
+    pub fn new(fixture: SimulationFixture) -> AgentResult<Self> {
+        let forbidden_commands = fixture
+            .constraints
+            .forbidden_commands
+            .iter()
+            .map(|pattern| {
+                Regex::new(pattern).map_err(|e| AgentError::InvalidConfiguration {
+                    reason: format!("Invalid forbidden command pattern '{}': {}", pattern, e),
+                })
+            })
+            .collect::<AgentResult<Vec<_>>>()?;
+
+        Ok(Self {
+            fixture,
+            options: SimulationOptions::default(),
+            forbidden_commands,
+        })
+    }
+
+    pub fn with_options(mut self, options: SimulationOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn fixture(&self) -> &SimulationFixture {
+        &self.fixture
+    }
+
+    /// Run every fixture task against the agent in order, sharing one
+    /// workspace so later tasks see earlier changes
+    pub async fn run(&self, agent: &mut dyn Agent) -> AgentResult<SimulationReport> {
+        let started_at = Utc::now();
+        let mut workspace = VirtualWorkspace::new(&self.fixture);
+        let mut tasks = Vec::new();
+
+        for task in &self.fixture.tasks {
+            tasks.push(self.run_task(agent, task, &mut workspace).await);
+        }
+
+        let tasks_completed = tasks.iter().filter(|t| t.completed).count();
+        let completion_rate = if tasks.is_empty() {
+            0.0
+        } else {
+            tasks_completed as f64 / tasks.len() as f64
+        };
+        let violations: usize = tasks.iter().map(|t| t.violations.len()).sum();
+        let total_tokens: u64 = tasks.iter().map(|t| t.tokens).sum();
+        let score =
+            (completion_rate * 100.0 - violations as f64 * self.options.violation_penalty).max(0.0);
+
+        Ok(SimulationReport {
+            agent: agent.name().to_string(),
+            fixture: self.fixture.name.clone(),
+            started_at,
+            tasks,
+            tasks_completed,
+            completion_rate,
+            violations,
+            total_tokens,
+            estimated_cost: total_tokens as f64 / 1000.0 * self.options.cost_per_1k_tokens,
+            score,
+            commits: workspace.commits().to_vec(),
+            commands: workspace.commands().to_vec(),
+            files_changed: workspace.changed_files(),
+        })
+    }
+
+    async fn run_task(
+        &self,
+        agent: &mut dyn Agent,
+        task: &SimulationTask,
+        workspace: &mut VirtualWorkspace,
+    ) -> TaskScore {
+        let mut request = AgentRequest::new(
+            task.request_type.clone(),
+            serde_json::json!({
+                "task": task.payload,
+                "description": task.description,
+                "branch": workspace.branch(),
+                "files": workspace.files(),
+            }),
+        );
+        request
+            .metadata
+            .insert(SIMULATION_METADATA_KEY.to_string(), "true".to_string());
+        request
+            .metadata
+            .insert("task_id".to_string(), task.id.clone());
+
+        let timeout = Duration::from_secs(task.timeout.unwrap_or(self.fixture.task_timeout));
+        let start = Instant::now();
+        let outcome = tokio::time::timeout(timeout, agent.execute_task(request)).await;
+        let duration_ms = start.elapsed().as_millis() as u64;
+
+        let response = match outcome {
+            Ok(Ok(response)) => response,
+            Ok(Err(e)) => {
+                return self.failed_task(task, ResponseStatus::Error, e.to_string(), duration_ms)
+            }
+            Err(_) => {
+                return self.failed_task(
+                    task,
+                    ResponseStatus::Timeout,
+                    format!("Timed out after {}s", timeout.as_secs()),
+                    duration_ms,
+                )
+            }
+        };
+
+        let (effects, tokens) = match parse_response(&response) {
+            Ok(parsed) => parsed,
+            Err(e) => return self.failed_task(task, response.status, e.to_string(), duration_ms),
+        };
+
+        let before = workspace.changed_files();
+        let mut violations = Vec::new();
+        for effect in &effects {
+            match self.check_effect(task, effect) {
+                // Blocked effects are recorded but never applied
+                Some(violation) => violations.push(violation),
+                None => workspace.apply(effect),
+            }
+        }
+
+        let files_changed = workspace
+            .changed_files()
+            .into_iter()
+            .filter(|path| !before.contains(path))
+            .count();
+        let constraints = &self.fixture.constraints;
+        if let Some(max) = constraints.max_files_changed_per_task {
+            if files_changed > max {
+                violations.push(violation(
+                    task,
+                    "max_files_changed_per_task",
+                    format!("changed {} files, limit is {}", files_changed, max),
+                ));
+            }
+        }
+        if let Some(max) = constraints.max_tokens_per_task {
+            if tokens > max {
+                violations.push(violation(
+                    task,
+                    "max_tokens_per_task",
+                    format!("used {} tokens, budget is {}", tokens, max),
+                ));
+            }
+        }
+
+        let failed_expectations: Vec<String> = task
+            .expectations
+            .iter()
+            .filter(|expectation| !expectation_met(expectation, &response, workspace))
+            .map(|expectation| format!("{:?}", expectation))
+            .collect();
+        let expectations_total = task.expectations.len();
+
+        TaskScore {
+            task_id: task.id.clone(),
+            completed: response.status == ResponseStatus::Success && failed_expectations.is_empty(),
+            status: response.status.clone(),
+            expectations_met: expectations_total - failed_expectations.len(),
+            expectations_total,
+            failed_expectations,
+            violations,
+            tokens,
+            duration_ms,
+            files_changed,
+            error: response.error.clone(),
+        }
+    }
+
+    fn check_effect(
+        &self,
+        task: &SimulationTask,
+        effect: &SimulatedEffect,
+    ) -> Option<ConstraintViolation> {
+        match effect {
+            SimulatedEffect::WriteFile { path, .. } | SimulatedEffect::DeleteFile { path } => self
+                .fixture
+                .constraints
+                .protected_paths
+                .iter()
+                .find(|prefix| path.starts_with(prefix.as_str()))
+                .map(|prefix| {
+                    violation(
+                        task,
+                        "protected_paths",
+                        format!("modified {} (protected by '{}')", path, prefix),
+                    )
+                }),
+            SimulatedEffect::RunCommand { command } => self
+                .forbidden_commands
+                .iter()
+                .find(|pattern| pattern.is_match(command))
+                .map(|pattern| {
+                    violation(
+                        task,
+                        "forbidden_commands",
+                        format!("ran '{}' (matches '{}')", command, pattern.as_str()),
+                    )
+                }),
+            SimulatedEffect::GitBranch { .. } | SimulatedEffect::GitCommit { .. } => None,
+        }
+    }
+
+    fn failed_task(
+        &self,
+        task: &SimulationTask,
+        status: ResponseStatus,
+        error: String,
+        duration_ms: u64,
+    ) -> TaskScore {
+        TaskScore {
+            task_id: task.id.clone(),
+            completed: false,
+            status,
+            expectations_met: 0,
+            expectations_total: task.expectations.len(),
+            failed_expectations: task
+                .expectations
+                .iter()
+                .map(|e| format!("{:?}", e))
+                .collect(),
+            violations: Vec::new(),
+            tokens: 0,
+            duration_ms,
+            files_changed: 0,
+            error: Some(error),
+        }
+    }
+}
+
+fn violation(task: &SimulationTask, constraint: &str, detail: String) -> ConstraintViolation {
+    ConstraintViolation {
+        task_id: task.id.clone(),
+        constraint: constraint.to_string(),
+        detail,
+    }
+}
+
+/// Extract requested effects and token usage from an agent response.
+///
+/// Agents list effects under `effects` and report usage under `tokens`,
+/// either as a number or as `{"prompt": n, "completion": m}`.
+fn parse_response(response: &AgentResponse) -> AgentResult<(Vec<SimulatedEffect>, u64)> {
+    let Some(payload) = &response.payload else {
+        return Ok((Vec::new(), 0));
+    };
+
+    let effects = match payload.get("effects") {
+        Some(effects) => serde_json::from_value(effects.clone()).map_err(|e| {
+            AgentError::DeserializationError {
+                reason: format!("Invalid simulated effects: {}", e),
+            }
+        })?,
+        None => Vec::new(),
+    };
+
+    let tokens = match payload.get("tokens") {
+        Some(serde_json::Value::Number(n)) => n.as_u64().unwrap_or(0),
+        Some(serde_json::Value::Object(usage)) => {
+            usage.values().filter_map(serde_json::Value::as_u64).sum()
+        }
+        _ => 0,
+    };
+
+    Ok((effects, tokens))
+}
+
+fn expectation_met(
+    expectation: &TaskExpectation,
+    response: &AgentResponse,
+    workspace: &VirtualWorkspace,
+) -> bool {
+    match expectation {
+        TaskExpectation::Succeeds => response.status == ResponseStatus::Success,
+        TaskExpectation::FileExists { path } => workspace.read(path).is_some(),
+        TaskExpectation::FileAbsent { path } => workspace.read(path).is_none(),
+        TaskExpectation::FileMatches { path, pattern } => match Regex::new(pattern) {
+            Ok(regex) => workspace.read(path).is_some_and(|c| regex.is_match(c)),
+            Err(_) => false,
+        },
+        TaskExpectation::FileUnchanged { path } => {
+            workspace.read(path) == workspace.original.get(path).map(String::as_str)
+        }
+        TaskExpectation::CommitCreated { message_contains } => workspace
+            .commits()
+            .iter()
+            .any(|c| c.message.contains(message_contains.as_str())),
+        TaskExpectation::ResponseField { pointer, equals } => response
+            .payload
+            .as_ref()
+            .and_then(|p| p.pointer(pointer))
+            .is_some_and(|value| value == equals),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::{
+        AgentCapability, AgentConfig, AgentContext, AgentId, AgentMessage, AgentStatus,
+        HealthStatus, ResourceUsage,
+    };
+    use async_trait::async_trait;
+
+    /// Agent that replies with a fixed set of effects
+    struct ScriptedAgent {
+        id: AgentId,
+        config: AgentConfig,
+        context: AgentContext,
+        effects: serde_json::Value,
+    }
+
+    impl ScriptedAgent {
+        fn new(effects: serde_json::Value) -> Self {
+            let id = "scripted".to_string();
+            Self {
+                context: AgentContext::new(id.clone()),
+                config: AgentConfig {
+                    name: "scripted".to_string(),
+                    ..Default::default()
+                },
+                id,
+                effects,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Agent for ScriptedAgent {
+        fn id(&self) -> &AgentId {
+            &self.id
+        }
+        fn config(&self) -> &AgentConfig {
+            &self.config
+        }
+        fn context(&self) -> &AgentContext {
+            &self.context
+        }
+        fn context_mut(&mut self) -> &mut AgentContext {
+            &mut self.context
+        }
+        async fn initialize(&mut self) -> AgentResult<()> {
+            Ok(())
+        }
+        async fn start(&mut self) -> AgentResult<()> {
+            Ok(())
+        }
+        async fn stop(&mut self) -> AgentResult<()> {
+            Ok(())
+        }
+        async fn handle_message(
+            &mut self,
+            _message: AgentMessage,
+        ) -> AgentResult<Option<AgentMessage>> {
+            Ok(None)
+        }
+        async fn execute_task(&mut self, request: AgentRequest) -> AgentResult<AgentResponse> {
+            assert_eq!(
+                request
+                    .metadata
+                    .get(SIMULATION_METADATA_KEY)
+                    .map(String::as_str),
+                Some("true")
+            );
+            assert!(request.payload["files"]["src/lib.rs"].is_string());
+            Ok(AgentResponse::success(
+                request.id,
+                serde_json::json!({
+                    "effects": self.effects,
+                    "tokens": {"prompt": 1200, "completion": 300}
+                }),
+            ))
+        }
+        async fn get_status(&self) -> AgentResult<AgentStatus> {
+            Ok(AgentStatus {
+                agent_id: self.id.clone(),
+                state: self.context.state.clone(),
+                current_task: self.context.current_task.clone(),
+                health: HealthStatus::Healthy,
+                resources: ResourceUsage::default(),
+                timestamp: Utc::now(),
+            })
+        }
+        async fn check_health(&self) -> AgentResult<HealthStatus> {
+            Ok(HealthStatus::Healthy)
+        }
+        fn capabilities(&self) -> &[AgentCapability] {
+            &self.config.capabilities
+        }
+    }
+
+    fn fixture() -> SimulationFixture {
+        let mut files = BTreeMap::new();
+        files.insert("src/lib.rs".to_string(), "fn old() {}\n".to_string());
+        files.insert(".rhema/todos.yaml".to_string(), "todos: []\n".to_string());
+        SimulationFixture {
+            name: "small-repo".to_string(),
+            description: None,
+            recorded_at: Utc::now(),
+            branch: "main".to_string(),
+            files,
+            tasks: vec![SimulationTask {
+                id: "rename".to_string(),
+                description: "Rename old to new".to_string(),
+                request_type: "refactor".to_string(),
+                payload: serde_json::json!({"from": "old", "to": "new"}),
+                expectations: vec![
+                    TaskExpectation::Succeeds,
+                    TaskExpectation::FileMatches {
+                        path: "src/lib.rs".to_string(),
+                        pattern: "fn new".to_string(),
+                    },
+                    TaskExpectation::FileUnchanged {
+                        path: ".rhema/todos.yaml".to_string(),
+                    },
+                    TaskExpectation::CommitCreated {
+                        message_contains: "rename".to_string(),
+                    },
+                ],
+                timeout: None,
+            }],
+            constraints: SimulationConstraints {
+                protected_paths: vec![".rhema/".to_string()],
+                forbidden_commands: vec![r"^git\s+push".to_string()],
+                max_tokens_per_task: Some(2000),
+                max_files_changed_per_task: None,
+            },
+            task_timeout: 5,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_well_behaved_agent_completes_task() {
+        let harness = SimulationHarness::new(fixture()).unwrap();
+        let mut agent = ScriptedAgent::new(serde_json::json!([
+            {"type": "git_branch", "name": "rename-old"},
+            {"type": "write_file", "path": "src/lib.rs", "content": "fn new() {}\n"},
+            {"type": "git_commit", "message": "rename old to new"},
+            {"type": "run_command", "command": "cargo test"}
+        ]));
+
+        let report = harness.run(&mut agent).await.unwrap();
+        assert_eq!(report.tasks_completed, 1);
+        assert_eq!(report.violations, 0);
+        assert_eq!(report.score, 100.0);
+        assert_eq!(report.total_tokens, 1500);
+        assert_eq!(report.files_changed, vec!["src/lib.rs"]);
+        assert_eq!(report.commits[0].branch, "rename-old");
+        assert_eq!(report.commits[0].files, vec!["src/lib.rs"]);
+        assert_eq!(report.commands, vec!["cargo test"]);
+        assert!(report
+            .to_markdown()
+            .contains("| rename | yes | 4/4 | 0 | 1500 |"));
+    }
+
+    #[tokio::test]
+    async fn test_violations_are_blocked_and_scored() {
+        let harness = SimulationHarness::new(fixture()).unwrap();
+        let mut agent = ScriptedAgent::new(serde_json::json!([
+            {"type": "write_file", "path": ".rhema/todos.yaml", "content": "todos: [x]\n"},
+            {"type": "run_command", "command": "git push --force"}
+        ]));
+
+        let report = harness.run(&mut agent).await.unwrap();
+        let task = &report.tasks[0];
+        assert!(!task.completed);
+        assert_eq!(report.violations, 2);
+        // The protected file was never modified
+        assert!(report.files_changed.is_empty());
+        assert!(report.commands.is_empty());
+        assert_eq!(task.expectations_met, 2);
+        assert_eq!(report.score, 0.0);
+
+        let table = compare_reports(&[report]);
+        assert!(table.contains("| scripted | small-repo | 0.0 | 0/1 | 2 | 1500 |"));
+    }
+
+    #[test]
+    fn test_record_fixture_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join(".rhema")).unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("target")).unwrap();
+        std::fs::write(root.join(".rhema/knowledge.yaml"), "entries: []\n").unwrap();
+        std::fs::write(root.join("src/main.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(root.join("target/build.rs"), "// generated\n").unwrap();
+        std::fs::write(root.join("README.md"), "# readme\n").unwrap();
+
+        let fixture = SimulationFixture::record(root, &RecordOptions::default()).unwrap();
+        assert_eq!(
+            fixture.files.keys().collect::<Vec<_>>(),
+            vec![".rhema/knowledge.yaml", "src/main.rs"]
+        );
+        assert_eq!(fixture.context_files().len(), 1);
+
+        let path = root.join("fixtures/repo.yaml");
+        fixture.save(&path).unwrap();
+        let loaded = SimulationFixture::load(&path).unwrap();
+        assert_eq!(loaded.files, fixture.files);
+    }
+}
@@ -51,6 +51,7 @@ async fn main() -> RhemaResult<()> {
         last_heartbeat: chrono::Utc::now(),
         is_online: true,
         performance_metrics: AgentPerformanceMetrics::default(),
+        liveness: Default::default(),
     };
 
     println!("✅ Test agent created");
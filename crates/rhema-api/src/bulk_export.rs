@@ -0,0 +1,735 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Streaming export of all scope context for very large repositories.
+//!
+//! Scopes are discovered lazily in a deterministic order and exported one
+//! context file at a time, so memory stays bounded by the largest single
+//! context file plus the write buffer. A checkpoint is written after every
+//! scope; an interrupted or budget-limited run resumes from its token.
+
+use crate::redaction::{RedactionConfig, RedactionReport, Redactor};
+use crate::{RateLimitConfig, RhemaError, RhemaResult, Scope};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tracing::{debug, info};
+use walkdir::WalkDir;
+
+/// Context files exported per scope, with the key holding their entries
+const CONTEXT_FILES: &[(&str, &str, &str)] = &[
+    ("knowledge", "knowledge.yaml", "entries"),
+    ("todos", "todos.yaml", "todos"),
+    ("decisions", "decisions.yaml", "decisions"),
+    ("patterns", "patterns.yaml", "patterns"),
+    ("conventions", "conventions.yaml", "conventions"),
+];
+
+/// Name of the archive manifest written when an archive export completes
+pub const ARCHIVE_MANIFEST: &str = "manifest.json";
+
+/// Output layout of a bulk export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkExportFormat {
+    /// A single newline-delimited JSON file
+    Ndjson,
+    /// A directory of NDJSON parts of bounded size plus a manifest
+    Archive,
+}
+
+/// Bulk export settings
+#[derive(Debug, Clone)]
+pub struct BulkExportConfig {
+    pub format: BulkExportFormat,
+
+    /// Output file (NDJSON) or directory (archive)
+    pub output: PathBuf,
+
+    /// Maximum records per archive part
+    pub chunk_records: u64,
+
+    /// Maximum bytes per archive part
+    pub chunk_bytes: u64,
+
+    /// Capacity of the output write buffer
+    pub write_buffer_bytes: usize,
+
+    /// Stop after this many scopes in one run, returning a checkpoint token
+    pub max_scopes: Option<usize>,
+
+    /// Redact records before they are written
+    pub redaction: Option<RedactionConfig>,
+}
+
+impl BulkExportConfig {
+    pub fn new(format: BulkExportFormat, output: PathBuf) -> Self {
+        Self {
+            format,
+            output,
+            chunk_records: 10_000,
+            chunk_bytes: 16 * 1024 * 1024,
+            write_buffer_bytes: 256 * 1024,
+            max_scopes: None,
+            redaction: None,
+        }
+    }
+
+    /// File the checkpoint of an unfinished export is kept in
+    pub fn checkpoint_path(&self) -> PathBuf {
+        match self.format {
+            BulkExportFormat::Ndjson => {
+                let mut name = self.output.as_os_str().to_owned();
+                name.push(".checkpoint");
+                PathBuf::from(name)
+            }
+            BulkExportFormat::Archive => self.output.join("checkpoint.json"),
+        }
+    }
+}
+
+/// A file of exported records
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportPart {
+    /// File name, relative to the archive directory for archives
+    pub file: String,
+    pub records: u64,
+    pub bytes: u64,
+}
+
+/// Progress of an export, restorable from its token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportCheckpoint {
+    pub format: BulkExportFormat,
+
+    /// `.rhema` directory (relative to the repository root) of the last
+    /// fully exported scope
+    pub last_scope: Option<PathBuf>,
+    pub scopes_exported: usize,
+    pub records: u64,
+
+    /// Parts written so far; the last one is still being appended to
+    pub parts: Vec<ExportPart>,
+}
+
+impl ExportCheckpoint {
+    fn new(format: BulkExportFormat) -> Self {
+        Self {
+            format,
+            last_scope: None,
+            scopes_exported: 0,
+            records: 0,
+            parts: Vec::new(),
+        }
+    }
+
+    /// Opaque token that resumes the export
+    pub fn to_token(&self) -> String {
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        json.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// Restore a checkpoint from its token
+    pub fn from_token(token: &str) -> RhemaResult<Self> {
+        let invalid = || RhemaError::InvalidInput("Invalid export checkpoint token".to_string());
+        let token = token.trim();
+        if !token.len().is_multiple_of(2) {
+            return Err(invalid());
+        }
+        let bytes = (0..token.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&token[i..i + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| invalid())?;
+        serde_json::from_slice(&bytes).map_err(|_| invalid())
+    }
+}
+
+/// Outcome of one export run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkExportReport {
+    /// Scopes exported by this run
+    pub scopes_exported: usize,
+
+    /// Records written by this run
+    pub records: u64,
+    pub total_scopes_exported: usize,
+    pub total_records: u64,
+    pub parts: Vec<ExportPart>,
+
+    /// Whether every scope has been exported
+    pub complete: bool,
+
+    /// Token to continue with when the run stopped early
+    pub checkpoint_token: Option<String>,
+
+    /// What redaction withheld during this run, when redaction is enabled
+    pub redaction: Option<RedactionReport>,
+}
+
+/// Token bucket pacing context file reads
+struct Pacer {
+    interval: Option<Duration>,
+    burst: f64,
+    tokens: f64,
+    last: Instant,
+}
+
+impl Pacer {
+    fn new(rate_limit: &RateLimitConfig) -> Self {
+        let interval = (rate_limit.requests_per_minute > 0)
+            .then(|| Duration::from_secs_f64(60.0 / rate_limit.requests_per_minute as f64));
+        let burst = rate_limit.burst_size.max(1) as f64;
+        Self {
+            interval,
+            burst,
+            tokens: burst,
+            last: Instant::now(),
+        }
+    }
+
+    async fn acquire(&mut self) {
+        let Some(interval) = self.interval else {
+            return;
+        };
+        let now = Instant::now();
+        let refill = now.duration_since(self.last).as_secs_f64() / interval.as_secs_f64();
+        self.tokens = (self.tokens + refill).min(self.burst);
+        self.last = now;
+
+        if self.tokens < 1.0 {
+            tokio::time::sleep(interval.mul_f64(1.0 - self.tokens)).await;
+            self.tokens = 1.0;
+            self.last = Instant::now();
+        }
+        self.tokens -= 1.0;
+    }
+}
+
+/// Appends records to the current part, rolling archive parts over
+struct PartWriter {
+    config: BulkExportConfig,
+    writer: BufWriter<File>,
+}
+
+impl PartWriter {
+    /// Open the current part of `checkpoint`, discarding anything written
+    /// after the checkpoint was taken
+    fn open(config: &BulkExportConfig, checkpoint: &mut ExportCheckpoint) -> RhemaResult<Self> {
+        if config.format == BulkExportFormat::Archive {
+            fs::create_dir_all(&config.output)?;
+            remove_stale_parts(&config.output, checkpoint.parts.len())?;
+        } else if let Some(parent) = config.output.parent() {
+            if !parent.as_os_str().is_empty() {
+                fs::create_dir_all(parent)?;
+            }
+        }
+
+        if checkpoint.parts.is_empty() {
+            checkpoint.parts.push(ExportPart {
+                file: part_name(config, 0),
+                records: 0,
+                bytes: 0,
+            });
+        }
+        let current = checkpoint.parts.last().expect("at least one part");
+        let writer = open_part(config, current)?;
+        Ok(Self {
+            config: config.clone(),
+            writer,
+        })
+    }
+
+    fn write(
+        &mut self,
+        record: &serde_json::Value,
+        checkpoint: &mut ExportCheckpoint,
+    ) -> RhemaResult<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+
+        let current = checkpoint.parts.last().expect("at least one part");
+        if self.config.format == BulkExportFormat::Archive
+            && current.records > 0
+            && (current.records >= self.config.chunk_records
+                || current.bytes + line.len() as u64 > self.config.chunk_bytes)
+        {
+            self.writer.flush()?;
+            let part = ExportPart {
+                file: part_name(&self.config, checkpoint.parts.len()),
+                records: 0,
+                bytes: 0,
+            };
+            self.writer = open_part(&self.config, &part)?;
+            debug!("Started export part {}", part.file);
+            checkpoint.parts.push(part);
+        }
+
+        self.writer.write_all(&line)?;
+        let current = checkpoint.parts.last_mut().expect("at least one part");
+        current.records += 1;
+        current.bytes += line.len() as u64;
+        checkpoint.records += 1;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> RhemaResult<()> {
+        self.writer.flush()?;
+        self.writer.get_ref().sync_data()?;
+        Ok(())
+    }
+}
+
+fn part_name(config: &BulkExportConfig, index: usize) -> String {
+    match config.format {
+        BulkExportFormat::Ndjson => config
+            .output
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "export.ndjson".to_string()),
+        BulkExportFormat::Archive => format!("part-{:05}.ndjson", index),
+    }
+}
+
+fn part_path(config: &BulkExportConfig, part: &ExportPart) -> PathBuf {
+    match config.format {
+        BulkExportFormat::Ndjson => config.output.clone(),
+        BulkExportFormat::Archive => config.output.join(&part.file),
+    }
+}
+
+/// Open a part for appending after its checkpointed length
+fn open_part(config: &BulkExportConfig, part: &ExportPart) -> RhemaResult<BufWriter<File>> {
+    let file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(false)
+        .open(part_path(config, part))?;
+    file.set_len(part.bytes)?;
+    let mut writer = BufWriter::with_capacity(config.write_buffer_bytes, file);
+    std::io::Seek::seek(writer.get_mut(), std::io::SeekFrom::End(0))?;
+    Ok(writer)
+}
+
+/// Remove archive parts numbered `keep` or higher, and any old manifest
+fn remove_stale_parts(dir: &Path, keep: usize) -> RhemaResult<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        let index = name
+            .strip_prefix("part-")
+            .and_then(|rest| rest.strip_suffix(".ndjson"))
+            .and_then(|n| n.parse::<usize>().ok());
+        if index.map_or(name == ARCHIVE_MANIFEST, |i| i >= keep) {
+            fs::remove_file(&path)?;
+        }
+    }
+    Ok(())
+}
+
+/// `.rhema` directories under the repository root, in sorted depth-first
+/// order so that runs agree on which scopes are already done
+fn scope_dirs(repo_root: &Path) -> impl Iterator<Item = PathBuf> + '_ {
+    WalkDir::new(repo_root)
+        .follow_links(true)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| e.file_name() != ".git")
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_dir() && e.file_name() == ".rhema")
+        .map(|e| e.into_path())
+}
+
+/// Streams every scope's context into chunked output
+pub struct BulkExporter {
+    repo_root: PathBuf,
+    config: BulkExportConfig,
+    pacer: Pacer,
+    redactor: Option<Redactor>,
+}
+
+impl BulkExporter {
+    pub fn new(repo_root: PathBuf, config: BulkExportConfig, rate_limit: &RateLimitConfig) -> Self {
+        Self {
+            repo_root,
+            config,
+            pacer: Pacer::new(rate_limit),
+            redactor: None,
+        }
+    }
+
+    /// Export scopes, continuing after `resume` when given
+    pub async fn run(&mut self, resume: Option<&str>) -> RhemaResult<BulkExportReport> {
+        let mut checkpoint = match resume {
+            Some(token) => {
+                let checkpoint = ExportCheckpoint::from_token(token)?;
+                if checkpoint.format != self.config.format {
+                    return Err(RhemaError::InvalidInput(format!(
+                        "Checkpoint was taken for a {:?} export, not {:?}",
+                        checkpoint.format, self.config.format
+                    )));
+                }
+                checkpoint
+            }
+            None => ExportCheckpoint::new(self.config.format),
+        };
+        self.redactor = self
+            .config
+            .redaction
+            .as_ref()
+            .map(Redactor::new)
+            .transpose()?;
+        let (start_scopes, start_records) = (checkpoint.scopes_exported, checkpoint.records);
+        let mut writer = PartWriter::open(&self.config, &mut checkpoint)?;
+
+        let mut complete = true;
+        let mut exported = 0;
+        let repo_root = self.repo_root.clone();
+        for scope_dir in scope_dirs(&repo_root) {
+            let relative = scope_dir
+                .strip_prefix(&repo_root)
+                .unwrap_or(&scope_dir)
+                .to_path_buf();
+            if checkpoint
+                .last_scope
+                .as_ref()
+                .is_some_and(|last| relative <= *last)
+            {
+                continue;
+            }
+            if self.config.max_scopes.is_some_and(|max| exported >= max) {
+                complete = false;
+                break;
+            }
+
+            let Ok(scope) = Scope::new(scope_dir.clone()) else {
+                debug!(
+                    "Skipping {} without a valid scope file",
+                    scope_dir.display()
+                );
+                continue;
+            };
+            self.export_scope(&scope, &relative, &mut writer, &mut checkpoint)
+                .await?;
+
+            writer.flush()?;
+            checkpoint.last_scope = Some(relative);
+            checkpoint.scopes_exported += 1;
+            exported += 1;
+            self.save_checkpoint(&checkpoint)?;
+        }
+        writer.flush()?;
+
+        let checkpoint_token = if complete {
+            self.finish(&checkpoint)?;
+            None
+        } else {
+            Some(checkpoint.to_token())
+        };
+        info!(
+            "Bulk export wrote {} records from {} scopes{}",
+            checkpoint.records - start_records,
+            exported,
+            if complete { "" } else { " (paused)" }
+        );
+
+        Ok(BulkExportReport {
+            scopes_exported: checkpoint.scopes_exported - start_scopes,
+            records: checkpoint.records - start_records,
+            total_scopes_exported: checkpoint.scopes_exported,
+            total_records: checkpoint.records,
+            parts: checkpoint.parts,
+            complete,
+            checkpoint_token,
+            redaction: self.redactor.take().map(Redactor::into_report),
+        })
+    }
+
+    async fn export_scope(
+        &mut self,
+        scope: &Scope,
+        relative: &Path,
+        writer: &mut PartWriter,
+        checkpoint: &mut ExportCheckpoint,
+    ) -> RhemaResult<()> {
+        let scope_path = relative
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_else(|| ".".to_string());
+
+        let mut definition = serde_json::to_value(&scope.definition)?;
+        if let Some(redactor) = self.redactor.as_mut() {
+            definition = redactor.redact_document(&scope_path, definition);
+        }
+        writer.write(
+            &json!({
+                "type": "scope",
+                "scope": scope_path,
+                "definition": definition,
+            }),
+            checkpoint,
+        )?;
+
+        for (kind, file_name, key) in CONTEXT_FILES {
+            let Some(path) = scope.get_file(file_name) else {
+                continue;
+            };
+            self.pacer.acquire().await;
+
+            let content = fs::read_to_string(path)?;
+            let document: serde_json::Value =
+                serde_yaml::from_str(&content).map_err(|e| RhemaError::InvalidYaml {
+                    file: path.display().to_string(),
+                    message: e.to_string(),
+                })?;
+            drop(content);
+
+            match document.get(*key).and_then(|v| v.as_array()) {
+                Some(entries) => {
+                    for (index, entry) in entries.iter().enumerate() {
+                        let entry = match self.redactor.as_mut() {
+                            Some(redactor) => {
+                                match redactor.redact_entry(&scope_path, kind, index, entry.clone())
+                                {
+                                    Some(entry) => entry,
+                                    None => continue,
+                                }
+                            }
+                            None => entry.clone(),
+                        };
+                        writer.write(
+                            &json!({
+                                "type": "entry",
+                                "scope": scope_path,
+                                "kind": kind,
+                                "entry": entry,
+                            }),
+                            checkpoint,
+                        )?;
+                    }
+                }
+                None => {
+                    let document = match self.redactor.as_mut() {
+                        Some(redactor) => redactor.redact_document(&scope_path, document),
+                        None => document,
+                    };
+                    writer.write(
+                        &json!({
+                            "type": "document",
+                            "scope": scope_path,
+                            "kind": kind,
+                            "document": document,
+                        }),
+                        checkpoint,
+                    )?
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn save_checkpoint(&self, checkpoint: &ExportCheckpoint) -> RhemaResult<()> {
+        let path = self.config.checkpoint_path();
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, checkpoint.to_token())?;
+        fs::rename(tmp, path)?;
+        Ok(())
+    }
+
+    /// Write the archive manifest and drop the checkpoint of a finished export
+    fn finish(&self, checkpoint: &ExportCheckpoint) -> RhemaResult<()> {
+        if self.config.format == BulkExportFormat::Archive {
+            let manifest = json!({
+                "scopes": checkpoint.scopes_exported,
+                "records": checkpoint.records,
+                "parts": checkpoint.parts,
+                "exported_at": chrono::Utc::now(),
+            });
+            fs::write(
+                self.config.output.join(ARCHIVE_MANIFEST),
+                serde_json::to_vec_pretty(&manifest)?,
+            )?;
+        }
+        let checkpoint_path = self.config.checkpoint_path();
+        if checkpoint_path.exists() {
+            fs::remove_file(checkpoint_path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Read the token of an unfinished export from its checkpoint file
+pub fn saved_checkpoint(config: &BulkExportConfig) -> RhemaResult<Option<String>> {
+    let path = config.checkpoint_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+    Ok(Some(fs::read_to_string(path)?.trim().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn scope(root: &Path, dir: &str, todos: usize) {
+        let rhema = root.join(dir).join(".rhema");
+        fs::create_dir_all(&rhema).unwrap();
+        fs::write(
+            rhema.join("rhema.yaml"),
+            format!(
+                "name: {}\nscope_type: service\nversion: 1.0.0\n",
+                dir.replace('/', "-")
+            ),
+        )
+        .unwrap();
+        let items: String = (0..todos)
+            .map(|i| format!("  - id: t{}\n    title: Todo {}\n", i, i))
+            .collect();
+        fs::write(rhema.join("todos.yaml"), format!("todos:\n{}", items)).unwrap();
+    }
+
+    fn unlimited() -> RateLimitConfig {
+        RateLimitConfig {
+            requests_per_minute: 0,
+            burst_size: 0,
+        }
+    }
+
+    fn lines(path: &Path) -> Vec<serde_json::Value> {
+        fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_resumed_ndjson_export_matches_single_run() {
+        let repo = TempDir::new().unwrap();
+        for (dir, todos) in [("a", 2), ("b", 3), ("c/d", 1)] {
+            scope(repo.path(), dir, todos);
+        }
+        let out = TempDir::new().unwrap();
+
+        let full = out.path().join("full.ndjson");
+        let mut exporter = BulkExporter::new(
+            repo.path().to_path_buf(),
+            BulkExportConfig::new(BulkExportFormat::Ndjson, full.clone()),
+            &unlimited(),
+        );
+        let report = exporter.run(None).await.unwrap();
+        assert!(report.complete);
+        assert_eq!(report.total_scopes_exported, 3);
+        assert_eq!(report.total_records, 3 + 6);
+
+        let resumed = out.path().join("resumed.ndjson");
+        let mut config = BulkExportConfig::new(BulkExportFormat::Ndjson, resumed.clone());
+        config.max_scopes = Some(1);
+        let mut token = None;
+        for _ in 0..2 {
+            let mut exporter =
+                BulkExporter::new(repo.path().to_path_buf(), config.clone(), &unlimited());
+            let report = exporter.run(token.as_deref()).await.unwrap();
+            // Simulate a crash after the checkpoint by leaving a torn record behind
+            fs::OpenOptions::new()
+                .append(true)
+                .open(&resumed)
+                .unwrap()
+                .write_all(b"{\"torn\":")
+                .unwrap();
+            token = report.checkpoint_token;
+        }
+        let mut exporter =
+            BulkExporter::new(repo.path().to_path_buf(), config.clone(), &unlimited());
+        let report = exporter.run(token.as_deref()).await.unwrap();
+        assert!(report.complete);
+        assert_eq!(report.scopes_exported, 1);
+        assert!(!config.checkpoint_path().exists());
+
+        assert_eq!(lines(&full), lines(&resumed));
+        assert_eq!(lines(&full)[0]["scope"], "a");
+        assert_eq!(lines(&full)[1]["kind"], "todos");
+    }
+
+    #[tokio::test]
+    async fn test_archive_parts_are_bounded() {
+        let repo = TempDir::new().unwrap();
+        scope(repo.path(), "svc", 7);
+        let temp = TempDir::new().unwrap();
+        let out = temp.path().join("archive");
+
+        let mut config = BulkExportConfig::new(BulkExportFormat::Archive, out.clone());
+        config.chunk_records = 3;
+        let mut exporter = BulkExporter::new(repo.path().to_path_buf(), config, &unlimited());
+        let report = exporter.run(None).await.unwrap();
+
+        assert_eq!(report.total_records, 8);
+        assert_eq!(report.parts.len(), 3);
+        assert!(report.parts.iter().all(|p| p.records <= 3));
+        assert_eq!(lines(&out.join("part-00002.ndjson")).len(), 2);
+        assert!(out.join(ARCHIVE_MANIFEST).exists());
+    }
+
+    #[tokio::test]
+    async fn test_redacted_export_omits_private_entries() {
+        let repo = TempDir::new().unwrap();
+        scope(repo.path(), "svc", 0);
+        fs::write(
+            repo.path().join("svc/.rhema/todos.yaml"),
+            "todos:\n  - id: t0\n    title: Ping ops@example.com\n  - id: t1\n    title: Vendor contract\n    visibility: private\n",
+        )
+        .unwrap();
+        let out_dir = TempDir::new().unwrap();
+        let out = out_dir.path().join("shared.ndjson");
+
+        let mut config = BulkExportConfig::new(BulkExportFormat::Ndjson, out.clone());
+        config.redaction = Some(RedactionConfig {
+            strip_private: true,
+            rules: vec![crate::redaction::RedactionRule {
+                name: "email".to_string(),
+                pattern: r"\S+@\S+".to_string(),
+                replacement: "[email]".to_string(),
+            }],
+        });
+        let mut exporter = BulkExporter::new(repo.path().to_path_buf(), config, &unlimited());
+        let report = exporter.run(None).await.unwrap();
+
+        let records = lines(&out);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1]["entry"]["title"], "Ping [email]");
+        let redaction = report.redaction.unwrap();
+        assert_eq!(redaction.dropped_entries.len(), 1);
+        assert_eq!(redaction.dropped_entries[0].id, "t1");
+        assert_eq!(redaction.rule_matches["email"], 1);
+    }
+
+    #[test]
+    fn test_checkpoint_token_round_trip() {
+        let mut checkpoint = ExportCheckpoint::new(BulkExportFormat::Archive);
+        checkpoint.last_scope = Some(PathBuf::from("services/auth/.rhema"));
+        checkpoint.records = 42;
+        let token = checkpoint.to_token();
+        assert_eq!(ExportCheckpoint::from_token(&token).unwrap(), checkpoint);
+        assert!(ExportCheckpoint::from_token("zz").is_err());
+    }
+}
@@ -0,0 +1,156 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Compatibility shims for the unversioned [`Rhema`] API.
+//!
+//! The sync scope and context-file methods on [`Rhema`] predate the
+//! versioned [`crate::v1`] facade. They keep working until the next major
+//! release, but the first call of each in a process logs a deprecation
+//! warning naming its replacement, and [`used_deprecations`] lists the ones
+//! a process has called so integrators can find them before they go.
+//! First-party binaries that have not migrated yet silence the warnings with
+//! [`set_deprecation_warnings`].
+
+use crate::v1::Client;
+use crate::Rhema;
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tracing::warn;
+
+/// A legacy method and what replaces it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deprecation {
+    /// Method on [`Rhema`]
+    pub method: &'static str,
+    /// API version that deprecated it
+    pub since: &'static str,
+    /// Stable replacement
+    pub replacement: &'static str,
+}
+
+/// Every deprecated method of the unversioned API
+pub const DEPRECATIONS: &[Deprecation] = &[
+    Deprecation {
+        method: "discover_scopes",
+        since: "1.0.0",
+        replacement: "v1::Client::scopes",
+    },
+    Deprecation {
+        method: "list_scopes",
+        since: "1.0.0",
+        replacement: "v1::Client::scopes",
+    },
+    Deprecation {
+        method: "get_scope",
+        since: "1.0.0",
+        replacement: "v1::Client::scope",
+    },
+    Deprecation {
+        method: "load_scope",
+        since: "1.0.0",
+        replacement: "v1::Client::scope",
+    },
+    Deprecation {
+        method: "load_knowledge",
+        since: "1.0.0",
+        replacement: "v1::Client::knowledge",
+    },
+    Deprecation {
+        method: "load_todos",
+        since: "1.0.0",
+        replacement: "v1::Client::todos",
+    },
+    Deprecation {
+        method: "load_decisions",
+        since: "1.0.0",
+        replacement: "v1::Client::decisions",
+    },
+    Deprecation {
+        method: "load_patterns",
+        since: "1.0.0",
+        replacement: "v1::Client::patterns",
+    },
+    Deprecation {
+        method: "load_conventions",
+        since: "1.0.0",
+        replacement: "v1::Client::conventions",
+    },
+];
+
+static WARNINGS_ENABLED: AtomicBool = AtomicBool::new(true);
+static USED: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+
+/// Turn the runtime deprecation warnings on or off for this process
+pub fn set_deprecation_warnings(enabled: bool) {
+    WARNINGS_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Deprecated methods this process has called, warned about or not
+pub fn used_deprecations() -> Vec<Deprecation> {
+    let used = USED.lock().unwrap_or_else(|e| e.into_inner());
+    DEPRECATIONS
+        .iter()
+        .filter(|deprecation| used.contains(deprecation.method))
+        .copied()
+        .collect()
+}
+
+/// Record a call of a deprecated method, warning on the first one
+pub(crate) fn deprecated(method: &'static str) {
+    let first_call = USED
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(method);
+    if !first_call || !WARNINGS_ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    if let Some(deprecation) = DEPRECATIONS.iter().find(|d| d.method == method) {
+        warn!(
+            "Rhema::{} is deprecated since API {} and will be removed in the next major version; use {} instead",
+            deprecation.method, deprecation.since, deprecation.replacement
+        );
+    }
+}
+
+/// Wrap an existing instance in the v1 facade, keeping its caches and
+/// coordination state
+impl From<Rhema> for Client {
+    fn from(rhema: Rhema) -> Self {
+        Client::from_legacy(rhema)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deprecated_calls_are_recorded_once() {
+        set_deprecation_warnings(false);
+        deprecated("load_todos");
+        deprecated("load_todos");
+        let used = used_deprecations();
+        assert_eq!(used.iter().filter(|d| d.method == "load_todos").count(), 1);
+        assert_eq!(
+            used.iter()
+                .find(|d| d.method == "load_todos")
+                .unwrap()
+                .replacement,
+            "v1::Client::todos"
+        );
+    }
+}
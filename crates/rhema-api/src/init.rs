@@ -17,7 +17,7 @@
 use crate::{Rhema, RhemaResult};
 use colored::*;
 use std::fs;
-use std::path::PathBuf;
+use std::path::Path;
 
 pub fn run(
     rhema: &Rhema,
@@ -119,6 +119,11 @@ pub fn run(
         schema_version: Some(rhema_core::CURRENT_SCHEMA_VERSION.to_string()),
         dependencies: None,
         protocol_info: Some(protocol_info),
+        ai_policy: None,
+        freshness: None,
+        toolchains: None,
+        runtime: None,
+        entry_templates: None,
         custom: custom_fields,
     };
 
@@ -196,7 +201,7 @@ fn display_analysis_results(
     Ok(())
 }
 
-fn create_template_files(scope_path: &PathBuf) -> RhemaResult<()> {
+pub(crate) fn create_template_files(scope_path: &Path) -> RhemaResult<()> {
     // Create knowledge.yaml template
     let knowledge_template = r#"# Knowledge Base
 # This file contains insights, learnings, and domain knowledge for this scope
@@ -246,7 +251,7 @@ conventions: []
 }
 
 /// Create default protocol information
-fn create_default_protocol_info(scope_type: &str) -> rhema_core::schema::ProtocolInfo {
+pub(crate) fn create_default_protocol_info(scope_type: &str) -> rhema_core::schema::ProtocolInfo {
     let concepts = vec![
         rhema_core::schema::ConceptDefinition {
             name: "Scope".to_string(),
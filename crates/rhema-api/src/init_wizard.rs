@@ -0,0 +1,713 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Project detection and setup planning for the interactive `init` wizard.
+//!
+//! [`ProjectDetection`] inspects the repository, [`WizardPlan::propose`]
+//! turns the findings into editable defaults, and [`WizardPlan::apply`]
+//! writes the selected scopes plus a commented `.rhema/repository.yaml`.
+
+use crate::RhemaResult;
+use rhema_config::RepositoryConfig;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Repository configuration written by the wizard, read by
+/// [`RepositoryConfig::load`]
+pub const CONFIG_FILE: &str = "repository.yaml";
+
+/// Custom repository configuration key listing the enabled action tools
+pub const ACTION_TOOLS_KEY: &str = "action_tools";
+
+/// Git workflow templates offered by the wizard, with a short description
+pub const WORKFLOW_TEMPLATES: &[(&str, &str)] = &[
+    (
+        "gitflow",
+        "develop/release/hotfix branches for scheduled releases",
+    ),
+    (
+        "github_flow",
+        "short-lived branches merged to main via pull requests",
+    ),
+    (
+        "gitlab_flow",
+        "main plus environment branches for staged deployment",
+    ),
+    (
+        "trunk_based",
+        "small commits straight to main behind feature flags",
+    ),
+];
+
+/// Action tools that can be enabled for the action pipeline
+pub const ACTION_TOOLS: &[&str] = &[
+    "cargo",
+    "eslint",
+    "prettier",
+    "typescript",
+    "jest",
+    "mocha",
+    "pytest",
+    "jscodeshift",
+    "ast-grep",
+    "comby",
+    "syntax_validation",
+    "type_checking",
+    "test_coverage",
+    "security_scanning",
+];
+
+/// Directories never searched for package manifests
+const SKIP_DIRS: &[&str] = &[
+    ".git",
+    ".rhema",
+    "node_modules",
+    "target",
+    "dist",
+    "build",
+    "vendor",
+    ".venv",
+    "venv",
+    "__pycache__",
+];
+
+/// Maximum directory depth searched for package manifests
+const MAX_PACKAGE_DEPTH: usize = 4;
+
+/// Continuous integration provider
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CiProvider {
+    GitHubActions,
+    GitLabCi,
+    CircleCi,
+    Jenkins,
+    AzurePipelines,
+    Buildkite,
+    TravisCi,
+}
+
+impl CiProvider {
+    /// Marker file or directory that identifies the provider
+    fn marker(self) -> &'static str {
+        match self {
+            CiProvider::GitHubActions => ".github/workflows",
+            CiProvider::GitLabCi => ".gitlab-ci.yml",
+            CiProvider::CircleCi => ".circleci",
+            CiProvider::Jenkins => "Jenkinsfile",
+            CiProvider::AzurePipelines => "azure-pipelines.yml",
+            CiProvider::Buildkite => ".buildkite",
+            CiProvider::TravisCi => ".travis.yml",
+        }
+    }
+
+    const ALL: [CiProvider; 7] = [
+        CiProvider::GitHubActions,
+        CiProvider::GitLabCi,
+        CiProvider::CircleCi,
+        CiProvider::Jenkins,
+        CiProvider::AzurePipelines,
+        CiProvider::Buildkite,
+        CiProvider::TravisCi,
+    ];
+}
+
+impl fmt::Display for CiProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            CiProvider::GitHubActions => "GitHub Actions",
+            CiProvider::GitLabCi => "GitLab CI",
+            CiProvider::CircleCi => "CircleCI",
+            CiProvider::Jenkins => "Jenkins",
+            CiProvider::AzurePipelines => "Azure Pipelines",
+            CiProvider::Buildkite => "Buildkite",
+            CiProvider::TravisCi => "Travis CI",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Tool that declares a monorepo layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MonorepoTool {
+    CargoWorkspace,
+    NpmWorkspaces,
+    Pnpm,
+    Lerna,
+    Nx,
+    Turborepo,
+    GoWorkspace,
+}
+
+impl fmt::Display for MonorepoTool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            MonorepoTool::CargoWorkspace => "Cargo workspace",
+            MonorepoTool::NpmWorkspaces => "npm/yarn workspaces",
+            MonorepoTool::Pnpm => "pnpm workspace",
+            MonorepoTool::Lerna => "Lerna",
+            MonorepoTool::Nx => "Nx",
+            MonorepoTool::Turborepo => "Turborepo",
+            MonorepoTool::GoWorkspace => "Go workspace",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A buildable package found below the repository root
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DetectedPackage {
+    /// Path relative to the repository root (empty for the root package)
+    pub path: PathBuf,
+    pub name: String,
+    pub language: String,
+    pub build_system: String,
+    /// Whether the package looks like a library rather than a deployable
+    pub is_library: bool,
+}
+
+/// Characteristics of the repository relevant to setup
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectDetection {
+    pub languages: Vec<String>,
+    pub build_systems: Vec<String>,
+    pub ci_providers: Vec<CiProvider>,
+    pub monorepo_tools: Vec<MonorepoTool>,
+    pub packages: Vec<DetectedPackage>,
+}
+
+impl ProjectDetection {
+    /// Inspect the repository rooted at `repo_root`
+    pub fn detect(repo_root: &Path) -> RhemaResult<Self> {
+        let mut detection = ProjectDetection {
+            ci_providers: CiProvider::ALL
+                .into_iter()
+                .filter(|p| repo_root.join(p.marker()).exists())
+                .collect(),
+            monorepo_tools: detect_monorepo_tools(repo_root),
+            ..Default::default()
+        };
+
+        let walker = WalkDir::new(repo_root)
+            .max_depth(MAX_PACKAGE_DEPTH)
+            .follow_links(false)
+            .into_iter()
+            .filter_entry(|e| {
+                e.depth() == 0
+                    || !e.file_type().is_dir()
+                    || !SKIP_DIRS.contains(&e.file_name().to_string_lossy().as_ref())
+            });
+        for entry in walker {
+            let entry = entry?;
+            if !entry.file_type().is_dir() {
+                continue;
+            }
+            if let Some(package) = inspect_package(repo_root, entry.path())? {
+                push_unique(&mut detection.languages, &package.language);
+                push_unique(&mut detection.build_systems, &package.build_system);
+                detection.packages.push(package);
+            }
+        }
+        detection.packages.sort_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(detection)
+    }
+
+    /// Whether the repository hosts several independently built packages
+    pub fn is_monorepo(&self) -> bool {
+        !self.monorepo_tools.is_empty()
+            || self
+                .packages
+                .iter()
+                .filter(|p| !p.path.as_os_str().is_empty())
+                .count()
+                > 1
+    }
+}
+
+/// A scope the wizard offers to create
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposedScope {
+    /// Directory relative to the repository root that will hold `.rhema`
+    pub path: PathBuf,
+    pub name: String,
+    pub scope_type: String,
+    pub description: String,
+    pub selected: bool,
+}
+
+/// Everything the wizard will write, editable before [`WizardPlan::apply`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WizardPlan {
+    pub detection: ProjectDetection,
+    pub scopes: Vec<ProposedScope>,
+    /// One of the names in [`WORKFLOW_TEMPLATES`]
+    pub workflow: String,
+    /// Enabled entries of [`ACTION_TOOLS`]
+    pub tools: Vec<String>,
+}
+
+/// Outcome of applying a plan
+#[derive(Debug, Clone, Default)]
+pub struct WizardReport {
+    pub created_scopes: Vec<PathBuf>,
+    /// Selected scopes that already had a `rhema.yaml`
+    pub existing_scopes: Vec<PathBuf>,
+    pub config_path: PathBuf,
+}
+
+impl WizardPlan {
+    /// Derive default choices from a detection
+    pub fn propose(repo_root: &Path, detection: ProjectDetection) -> Self {
+        let root_name = repo_root
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("repository")
+            .to_string();
+
+        let mut scopes = vec![ProposedScope {
+            path: PathBuf::new(),
+            name: root_name.clone(),
+            scope_type: if detection.is_monorepo() {
+                "monorepo".to_string()
+            } else {
+                detection
+                    .packages
+                    .iter()
+                    .find(|p| p.path.as_os_str().is_empty())
+                    .map(|p| scope_type_for(p).to_string())
+                    .unwrap_or_else(|| "service".to_string())
+            },
+            description: format!("Repository-wide context for {}", root_name),
+            selected: true,
+        }];
+
+        if detection.is_monorepo() {
+            scopes.extend(
+                detection
+                    .packages
+                    .iter()
+                    .filter(|p| !p.path.as_os_str().is_empty())
+                    .map(|p| ProposedScope {
+                        path: p.path.clone(),
+                        name: p.name.clone(),
+                        scope_type: scope_type_for(p).to_string(),
+                        description: format!("{} {} package", p.language, p.build_system),
+                        selected: true,
+                    }),
+            );
+        }
+
+        let workflow = recommend_workflow(&detection).to_string();
+        let tools = recommend_tools(repo_root, &detection);
+
+        Self {
+            detection,
+            scopes,
+            workflow,
+            tools,
+        }
+    }
+
+    /// Repository configuration with the wizard's choices applied, starting
+    /// from the existing `.rhema/repository.yaml` when there is one
+    pub fn repository_config(&self, repo_root: &Path) -> RhemaResult<RepositoryConfig> {
+        let mut config = if repo_root.join(".rhema").join(CONFIG_FILE).exists() {
+            RepositoryConfig::load(repo_root)?
+        } else {
+            RepositoryConfig::new(repo_root)
+        };
+        config.workflow.workflow_type = self.workflow.clone();
+        config.custom.insert(
+            ACTION_TOOLS_KEY.to_string(),
+            serde_json::json!({ "enabled": self.tools }),
+        );
+        Ok(config)
+    }
+
+    /// Render the repository configuration with explanatory comments
+    pub fn render_config(&self, repo_root: &Path) -> RhemaResult<String> {
+        let yaml = serde_yaml::to_string(&self.repository_config(repo_root)?)?;
+
+        let mut out = String::new();
+        out.push_str("# Rhema repository configuration\n");
+        out.push_str("# Generated by `rhema init --wizard`; edit freely.\n");
+        out.push_str("#\n# Detected project:\n");
+        push_list(&mut out, "languages", &self.detection.languages);
+        push_list(&mut out, "build systems", &self.detection.build_systems);
+        let ci: Vec<String> = self
+            .detection
+            .ci_providers
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        push_list(&mut out, "ci", &ci);
+        out.push_str(&format!("#   monorepo: {}\n", self.detection.is_monorepo()));
+        out.push_str("#\n# Scopes created by the wizard, relative to the repository root:\n");
+        for scope in self.scopes.iter().filter(|s| s.selected) {
+            let path = if scope.path.as_os_str().is_empty() {
+                ".".to_string()
+            } else {
+                scope.path.display().to_string()
+            };
+            out.push_str(&format!(
+                "#   {} ({}, {})\n",
+                path, scope.name, scope.scope_type
+            ));
+        }
+        out.push('\n');
+
+        for line in yaml.lines() {
+            if line == "workflow:" {
+                out.push_str("# Git workflow template used for branch naming and automation.\n");
+                out.push_str("# Available templates:\n");
+                for (name, description) in WORKFLOW_TEMPLATES {
+                    out.push_str(&format!("#   {:<12} {}\n", name, description));
+                }
+            } else if line == format!("{}:", ACTION_TOOLS_KEY) {
+                out.push_str("# Action tools the action pipeline may run.\n");
+                out.push_str(&format!("# Available tools: {}\n", ACTION_TOOLS.join(", ")));
+            }
+            out.push_str(line);
+            out.push('\n');
+        }
+        Ok(out)
+    }
+
+    /// Create the selected scopes and write `.rhema/repository.yaml`
+    pub fn apply(&self, repo_root: &Path) -> RhemaResult<WizardReport> {
+        let mut report = WizardReport::default();
+
+        for scope in self.scopes.iter().filter(|s| s.selected) {
+            let scope_path = repo_root.join(&scope.path).join(".rhema");
+            if scope_path.join("rhema.yaml").exists() {
+                report.existing_scopes.push(scope_path);
+                continue;
+            }
+            fs::create_dir_all(&scope_path)?;
+
+            let rhema_scope = rhema_core::schema::RhemaScope {
+                name: scope.name.clone(),
+                scope_type: scope.scope_type.clone(),
+                description: Some(scope.description.clone()),
+                version: "1.0.0".to_string(),
+                schema_version: Some(rhema_core::CURRENT_SCHEMA_VERSION.to_string()),
+                dependencies: None,
+                protocol_info: Some(crate::init::create_default_protocol_info(&scope.scope_type)),
+                ai_policy: None,
+                freshness: None,
+                toolchains: None,
+                runtime: None,
+                entry_templates: None,
+                custom: std::collections::HashMap::new(),
+            };
+            fs::write(
+                scope_path.join("rhema.yaml"),
+                serde_yaml::to_string(&rhema_scope)?,
+            )?;
+            crate::init::create_template_files(&scope_path)?;
+            report.created_scopes.push(scope_path);
+        }
+
+        let rhema_dir = repo_root.join(".rhema");
+        fs::create_dir_all(&rhema_dir)?;
+        let config = self.render_config(repo_root)?;
+        report.config_path = rhema_dir.join(CONFIG_FILE);
+        fs::write(&report.config_path, config)?;
+
+        Ok(report)
+    }
+}
+
+fn detect_monorepo_tools(repo_root: &Path) -> Vec<MonorepoTool> {
+    let read = |name: &str| fs::read_to_string(repo_root.join(name)).unwrap_or_default();
+    let mut tools = Vec::new();
+
+    if read("Cargo.toml").contains("[workspace]") {
+        tools.push(MonorepoTool::CargoWorkspace);
+    }
+    if read("package.json").contains("\"workspaces\"") {
+        tools.push(MonorepoTool::NpmWorkspaces);
+    }
+    for (marker, tool) in [
+        ("pnpm-workspace.yaml", MonorepoTool::Pnpm),
+        ("lerna.json", MonorepoTool::Lerna),
+        ("nx.json", MonorepoTool::Nx),
+        ("turbo.json", MonorepoTool::Turborepo),
+        ("go.work", MonorepoTool::GoWorkspace),
+    ] {
+        if repo_root.join(marker).exists() {
+            tools.push(tool);
+        }
+    }
+    tools
+}
+
+/// Recognise a package manifest in `dir`
+fn inspect_package(repo_root: &Path, dir: &Path) -> RhemaResult<Option<DetectedPackage>> {
+    let rel_path = dir.strip_prefix(repo_root)?.to_path_buf();
+    let dir_name = dir
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("root")
+        .to_string();
+
+    let (name, language, build_system, is_library) = if dir.join("Cargo.toml").exists() {
+        let manifest = fs::read_to_string(dir.join("Cargo.toml"))?;
+        let parsed: toml::Value = toml::from_str(&manifest).unwrap_or(toml::Value::Integer(0));
+        let Some(name) = parsed
+            .get("package")
+            .and_then(|p| p.get("name"))
+            .and_then(|n| n.as_str())
+        else {
+            // Virtual workspace manifests describe no package of their own
+            return Ok(None);
+        };
+        let is_library = dir.join("src/lib.rs").exists() && !dir.join("src/main.rs").exists();
+        (name.to_string(), "Rust", "cargo", is_library)
+    } else if dir.join("package.json").exists() {
+        let manifest = fs::read_to_string(dir.join("package.json"))?;
+        let parsed: serde_json::Value =
+            serde_json::from_str(&manifest).unwrap_or(serde_json::Value::Null);
+        let name = parsed
+            .get("name")
+            .and_then(|n| n.as_str())
+            .unwrap_or(&dir_name)
+            .to_string();
+        let language = if dir.join("tsconfig.json").exists() {
+            "TypeScript"
+        } else {
+            "JavaScript"
+        };
+        let build_system = if dir.join("pnpm-lock.yaml").exists() {
+            "pnpm"
+        } else if dir.join("yarn.lock").exists() {
+            "yarn"
+        } else {
+            "npm"
+        };
+        let is_library = parsed.get("bin").is_none()
+            && parsed.get("scripts").and_then(|s| s.get("start")).is_none();
+        (name, language, build_system, is_library)
+    } else if dir.join("go.mod").exists() {
+        let is_library = !dir.join("main.go").exists() && !dir.join("cmd").exists();
+        (dir_name, "Go", "go", is_library)
+    } else if dir.join("pyproject.toml").exists() || dir.join("setup.py").exists() {
+        let build_system = if dir.join("poetry.lock").exists() {
+            "poetry"
+        } else {
+            "pip"
+        };
+        (dir_name, "Python", build_system, false)
+    } else if dir.join("pom.xml").exists() {
+        (dir_name, "Java", "maven", false)
+    } else if dir.join("build.gradle").exists() || dir.join("build.gradle.kts").exists() {
+        (dir_name, "Java", "gradle", false)
+    } else {
+        return Ok(None);
+    };
+
+    Ok(Some(DetectedPackage {
+        path: rel_path,
+        name,
+        language: language.to_string(),
+        build_system: build_system.to_string(),
+        is_library,
+    }))
+}
+
+fn scope_type_for(package: &DetectedPackage) -> &'static str {
+    let parent = package
+        .path
+        .parent()
+        .and_then(|p| p.file_name())
+        .and_then(|n| n.to_str())
+        .unwrap_or("");
+    match parent {
+        "apps" | "services" | "cmd" => "service",
+        "libs" | "packages" | "crates" => "library",
+        _ if package.is_library => "library",
+        _ => "service",
+    }
+}
+
+fn recommend_workflow(detection: &ProjectDetection) -> &'static str {
+    if detection.is_monorepo() {
+        "trunk_based"
+    } else if detection.ci_providers.contains(&CiProvider::GitHubActions) {
+        "github_flow"
+    } else if detection.ci_providers.contains(&CiProvider::GitLabCi) {
+        "gitlab_flow"
+    } else {
+        "gitflow"
+    }
+}
+
+fn recommend_tools(repo_root: &Path, detection: &ProjectDetection) -> Vec<String> {
+    let has = |language: &str| detection.languages.iter().any(|l| l == language);
+    let package_json_mentions = |needle: &str| {
+        detection
+            .packages
+            .iter()
+            .filter(|p| matches!(p.language.as_str(), "JavaScript" | "TypeScript"))
+            .any(|p| {
+                fs::read_to_string(repo_root.join(&p.path).join("package.json"))
+                    .map(|c| c.contains(needle))
+                    .unwrap_or(false)
+            })
+    };
+
+    let mut tools = vec!["syntax_validation"];
+    if has("Rust") {
+        tools.push("cargo");
+    }
+    if has("JavaScript") || has("TypeScript") {
+        tools.extend(["eslint", "prettier"]);
+        if package_json_mentions("\"jest\"") {
+            tools.push("jest");
+        }
+        if package_json_mentions("\"mocha\"") {
+            tools.push("mocha");
+        }
+    }
+    if has("TypeScript") {
+        tools.extend(["typescript", "type_checking"]);
+    }
+    if has("Python") {
+        tools.push("pytest");
+    }
+    tools.push("security_scanning");
+
+    ACTION_TOOLS
+        .iter()
+        .filter(|t| tools.contains(t))
+        .map(|t| t.to_string())
+        .collect()
+}
+
+fn push_unique(values: &mut Vec<String>, value: &str) {
+    if !values.iter().any(|v| v == value) {
+        values.push(value.to_string());
+    }
+}
+
+fn push_list(out: &mut String, label: &str, values: &[String]) {
+    let values = if values.is_empty() {
+        "none".to_string()
+    } else {
+        values.join(", ")
+    };
+    out.push_str(&format!("#   {}: {}\n", label, values));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_detects_cargo_workspace_monorepo() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "Cargo.toml",
+            "[workspace]\nmembers = [\"crates/*\"]\n",
+        );
+        write(
+            root,
+            "crates/core/Cargo.toml",
+            "[package]\nname = \"core\"\n",
+        );
+        write(root, "crates/core/src/lib.rs", "");
+        write(
+            root,
+            "apps/web/package.json",
+            "{\"name\": \"web\", \"devDependencies\": {\"jest\": \"1\"}}",
+        );
+        write(root, "apps/web/tsconfig.json", "{}");
+        write(root, ".github/workflows/ci.yml", "on: push\n");
+        write(
+            root,
+            "target/debug/Cargo.toml",
+            "[package]\nname = \"ignored\"\n",
+        );
+
+        let detection = ProjectDetection::detect(root).unwrap();
+        assert!(detection.is_monorepo());
+        assert_eq!(detection.ci_providers, vec![CiProvider::GitHubActions]);
+        assert_eq!(detection.monorepo_tools, vec![MonorepoTool::CargoWorkspace]);
+        assert_eq!(detection.packages.len(), 2);
+
+        let plan = WizardPlan::propose(root, detection);
+        assert_eq!(plan.workflow, "trunk_based");
+        assert_eq!(plan.scopes.len(), 3);
+        let core = plan.scopes.iter().find(|s| s.name == "core").unwrap();
+        assert_eq!(core.scope_type, "library");
+        assert!(plan.tools.contains(&"cargo".to_string()));
+        assert!(plan.tools.contains(&"jest".to_string()));
+        assert!(!plan.tools.contains(&"pytest".to_string()));
+    }
+
+    #[test]
+    fn test_apply_writes_scopes_and_repository_config() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(root, "pyproject.toml", "[project]\nname = \"svc\"\n");
+
+        let plan = WizardPlan::propose(root, ProjectDetection::detect(root).unwrap());
+        assert_eq!(plan.workflow, "gitflow");
+
+        let report = plan.apply(root).unwrap();
+        assert_eq!(report.created_scopes.len(), 1);
+        assert!(root.join(".rhema/rhema.yaml").exists());
+        assert!(root.join(".rhema/todos.yaml").exists());
+
+        assert_eq!(report.config_path, root.join(".rhema/repository.yaml"));
+        let content = fs::read_to_string(&report.config_path).unwrap();
+        assert!(content.contains("#   trunk_based"));
+        assert!(content.contains("# Available tools: cargo, eslint"));
+
+        let config = RepositoryConfig::load(root).unwrap();
+        assert_eq!(config.workflow.workflow_type, "gitflow");
+        assert_eq!(
+            config.custom[ACTION_TOOLS_KEY]["enabled"],
+            serde_json::json!(["pytest", "syntax_validation", "security_scanning"])
+        );
+
+        // Re-running keeps existing scopes and settings the wizard doesn't manage
+        let mut config = config;
+        config.scopes.naming_convention = "kebab-case".to_string();
+        config.save(root).unwrap();
+        let mut plan = plan;
+        plan.workflow = "trunk_based".to_string();
+        let again = plan.apply(root).unwrap();
+        assert_eq!(again.existing_scopes.len(), 1);
+        let config = RepositoryConfig::load(root).unwrap();
+        assert_eq!(config.workflow.workflow_type, "trunk_based");
+        assert_eq!(config.scopes.naming_convention, "kebab-case");
+    }
+}
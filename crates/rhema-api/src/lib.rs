@@ -20,6 +20,8 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{error, info, instrument, warn};
 
+use rhema_core::cache_report::{CacheActivity, CacheTierReport, EvictionCause};
+
 // Re-export types from core crate
 pub use rhema_core::{schema::*, scope, RhemaError, RhemaResult, Scope};
 
@@ -64,8 +66,23 @@ pub use security::{
     AccessControl, AuditLogEntry, AuditLogger, InputSanitizer, SecurityConfig, SecurityManager,
 };
 
+// Bulk export module
+pub mod bulk_export;
+pub use bulk_export::{
+    BulkExportConfig, BulkExportFormat, BulkExportReport, BulkExporter, ExportCheckpoint,
+};
+
+// Redaction module
+pub mod redaction;
+pub use redaction::{RedactionConfig, RedactionReport, RedactionRule, Redactor};
+
 // Init module
 pub mod init;
+pub mod init_wizard;
+
+// Versioned facade and shims for the unversioned API
+pub mod compat;
+pub mod v1;
 pub use init::run as init_run;
 
 // Tests module
@@ -137,6 +154,9 @@ pub struct Rhema {
     rate_limit_config: RateLimitConfig,
     cache: Arc<RwLock<HashMap<String, serde_yaml::Value>>>,
     scope_cache: Arc<RwLock<HashMap<String, Scope>>>,
+    /// Hits, misses and evictions of the query and scope caches
+    query_cache_activity: Arc<RwLock<CacheActivity>>,
+    scope_cache_activity: Arc<RwLock<CacheActivity>>,
     /// Coordination system for agent communication
     coordination_system: Option<Arc<RealTimeCoordinationSystem>>,
     /// Coordination integration for external systems
@@ -155,6 +175,8 @@ impl Rhema {
             rate_limit_config: RateLimitConfig::default(),
             cache: Arc::new(RwLock::new(HashMap::new())),
             scope_cache: Arc::new(RwLock::new(HashMap::new())),
+            query_cache_activity: Arc::default(),
+            scope_cache_activity: Arc::default(),
             coordination_system: None,
             coordination_integration: None,
         })
@@ -178,6 +200,8 @@ impl Rhema {
             rate_limit_config: RateLimitConfig::default(),
             cache: Arc::new(RwLock::new(HashMap::new())),
             scope_cache: Arc::new(RwLock::new(HashMap::new())),
+            query_cache_activity: Arc::default(),
+            scope_cache_activity: Arc::default(),
             coordination_system: None,
             coordination_integration: None,
         })
@@ -206,6 +230,8 @@ impl Rhema {
             rate_limit_config,
             cache: Arc::new(RwLock::new(HashMap::new())),
             scope_cache: Arc::new(RwLock::new(HashMap::new())),
+            query_cache_activity: Arc::default(),
+            scope_cache_activity: Arc::default(),
             coordination_system: None,
             coordination_integration: None,
         })
@@ -404,8 +430,10 @@ impl Rhema {
         let cache_key = format!("query:{}", query);
         if let Some(cached_result) = self.cache.read().await.get(&cache_key) {
             info!("Cache hit for query: {}", query);
+            self.query_cache_activity.write().await.hit(&cache_key);
             return Ok(cached_result.clone());
         }
+        self.query_cache_activity.write().await.miss();
 
         // Execute query with retry logic
         let mut attempts = 0;
@@ -441,8 +469,10 @@ impl Rhema {
         let cache_key = "discovered_scopes".to_string();
         if let Some(cached_scopes) = self.scope_cache.read().await.get(&cache_key) {
             info!("Using cached scopes");
+            self.scope_cache_activity.write().await.hit(&cache_key);
             return Ok(vec![cached_scopes.clone()]);
         }
+        self.scope_cache_activity.write().await.miss();
 
         // Discover scopes
         let scopes = scope::discover_scopes(&self.repo_root)?;
@@ -463,8 +493,10 @@ impl Rhema {
         let cache_key = format!("scope:{}", path);
         if let Some(cached_scope) = self.scope_cache.read().await.get(&cache_key) {
             info!("Using cached scope: {}", path);
+            self.scope_cache_activity.write().await.hit(&cache_key);
             return Ok(cached_scope.clone());
         }
+        self.scope_cache_activity.write().await.miss();
 
         // Get scope
         let scope = scope::get_scope(&self.repo_root, path)?;
@@ -505,32 +537,38 @@ impl Rhema {
         Ok(())
     }
 
+    /// Streaming exporter for all scopes, paced by this instance's rate limit
+    pub fn bulk_exporter(&self, config: BulkExportConfig) -> BulkExporter {
+        BulkExporter::new(self.repo_root.clone(), config, &self.rate_limit_config)
+    }
+
     /// Discover all scopes in the repository (legacy sync version)
     pub fn discover_scopes(&self) -> RhemaResult<Vec<Scope>> {
-        Ok(scope::discover_scopes(&self.repo_root)?)
+        compat::deprecated("discover_scopes");
+        self.scopes()
     }
 
     /// Get a specific scope by path (legacy sync version)
     pub fn get_scope(&self, path: &str) -> RhemaResult<Scope> {
-        println!("DEBUG: get_scope called with path: '{}'", path);
+        compat::deprecated("get_scope");
+        self.find_scope(path)
+    }
 
-        // First try to find by name
-        println!("DEBUG: Trying to find scope by name...");
+    pub(crate) fn scopes(&self) -> RhemaResult<Vec<Scope>> {
+        scope::discover_scopes(&self.repo_root)
+    }
+
+    /// Find a scope by name, then by path
+    pub(crate) fn find_scope(&self, path: &str) -> RhemaResult<Scope> {
         if let Ok(scope) = scope::get_scope_by_name(&self.repo_root, path) {
-            println!("DEBUG: Found scope by name: {}", scope.definition.name);
             return Ok(scope);
         }
-
-        println!("DEBUG: Not found by name, trying by path...");
-        // If not found by name, try by path
-        let result = scope::get_scope(&self.repo_root, path);
-        println!("DEBUG: Path lookup result: {:?}", result);
-        Ok(result?)
+        scope::get_scope(&self.repo_root, path)
     }
 
     /// Get the path for a specific scope
     pub fn scope_path(&self, scope_name: &str) -> RhemaResult<PathBuf> {
-        let scope = self.get_scope(scope_name)?;
+        let scope = self.find_scope(scope_name)?;
         Ok(scope.path)
     }
 
@@ -542,7 +580,7 @@ impl Rhema {
     /// Get current scope path
     pub fn get_current_scope_path(&self) -> RhemaResult<PathBuf> {
         // Discover all scopes in the repository
-        let scopes = self.discover_scopes()?;
+        let scopes = self.scopes()?;
 
         // If there's only one scope, return it
         if scopes.len() == 1 {
@@ -647,118 +685,150 @@ impl Rhema {
 
     /// Load knowledge for a specific scope (legacy sync version)
     pub fn load_knowledge(&self, scope_name: &str) -> RhemaResult<Knowledge> {
-        let scope = self.get_scope(scope_name)?;
-        let knowledge_path = scope.path.join("knowledge.yaml");
-        if knowledge_path.exists() {
-            let content = std::fs::read_to_string(&knowledge_path)?;
-            let knowledge: Knowledge = serde_yaml::from_str(&content)?;
-            Ok(knowledge)
-        } else {
-            Ok(Knowledge {
-                entries: Vec::new(),
-                categories: None,
-                custom: HashMap::new(),
-            })
-        }
+        compat::deprecated("load_knowledge");
+        self.read_knowledge(scope_name)
     }
 
     /// Load todos for a specific scope
     pub fn load_todos(&self, scope_name: &str) -> RhemaResult<Todos> {
-        let scope = self.get_scope(scope_name)?;
-        let todos_path = scope.path.join("todos.yaml");
-        if todos_path.exists() {
-            let content = std::fs::read_to_string(&todos_path)?;
-            let todos: Todos = serde_yaml::from_str(&content)?;
-            Ok(todos)
-        } else {
-            Ok(Todos {
-                todos: Vec::new(),
-                custom: HashMap::new(),
-            })
-        }
+        compat::deprecated("load_todos");
+        self.read_todos(scope_name)
     }
 
     /// Load decisions for a specific scope
     pub fn load_decisions(&self, scope_name: &str) -> RhemaResult<Decisions> {
-        let scope = self.get_scope(scope_name)?;
-        let decisions_path = scope.path.join("decisions.yaml");
-        if decisions_path.exists() {
-            let content = std::fs::read_to_string(&decisions_path)?;
-            let decisions: Decisions = serde_yaml::from_str(&content)?;
-            Ok(decisions)
-        } else {
-            Ok(Decisions {
-                decisions: Vec::new(),
-                custom: HashMap::new(),
-            })
-        }
+        compat::deprecated("load_decisions");
+        self.read_decisions(scope_name)
     }
 
     /// Load patterns for a specific scope
     pub fn load_patterns(&self, scope_name: &str) -> RhemaResult<Patterns> {
-        let scope = self.get_scope(scope_name)?;
-        let patterns_path = scope.path.join("patterns.yaml");
-        if patterns_path.exists() {
-            let content = std::fs::read_to_string(&patterns_path)?;
-            let patterns: Patterns = serde_yaml::from_str(&content)?;
-            Ok(patterns)
-        } else {
-            Ok(Patterns {
-                patterns: Vec::new(),
-                custom: HashMap::new(),
-            })
-        }
+        compat::deprecated("load_patterns");
+        self.read_patterns(scope_name)
     }
 
     /// Load conventions for a specific scope
     pub fn load_conventions(&self, scope_name: &str) -> RhemaResult<Conventions> {
-        let scope = self.get_scope(scope_name)?;
-        let conventions_path = scope.path.join("conventions.yaml");
-        if conventions_path.exists() {
-            let content = std::fs::read_to_string(&conventions_path)?;
-            let conventions: Conventions = serde_yaml::from_str(&content)?;
-            Ok(conventions)
-        } else {
-            Ok(Conventions {
-                conventions: Vec::new(),
-                custom: HashMap::new(),
-            })
-        }
+        compat::deprecated("load_conventions");
+        self.read_conventions(scope_name)
     }
 
     /// Load scope by name
     pub fn load_scope(&self, name: &str) -> RhemaResult<Scope> {
-        self.get_scope(name)
+        compat::deprecated("load_scope");
+        self.find_scope(name)
     }
 
     /// List all scopes
     pub fn list_scopes(&self) -> RhemaResult<Vec<Scope>> {
-        self.discover_scopes()
+        compat::deprecated("list_scopes");
+        self.scopes()
+    }
+
+    /// Parse a context file of a scope, or `empty` when the scope has none
+    fn read_context_file<T: serde::de::DeserializeOwned>(
+        &self,
+        scope_name: &str,
+        file_name: &str,
+        empty: impl FnOnce() -> T,
+    ) -> RhemaResult<T> {
+        let scope = self.find_scope(scope_name)?;
+        let path = scope.path.join(file_name);
+        if !path.exists() {
+            return Ok(empty());
+        }
+        let content = std::fs::read_to_string(&path)?;
+        Ok(serde_yaml::from_str(&content)?)
+    }
+
+    pub(crate) fn read_knowledge(&self, scope_name: &str) -> RhemaResult<Knowledge> {
+        self.read_context_file(scope_name, "knowledge.yaml", || Knowledge {
+            entries: Vec::new(),
+            categories: None,
+            custom: HashMap::new(),
+        })
+    }
+
+    pub(crate) fn read_todos(&self, scope_name: &str) -> RhemaResult<Todos> {
+        self.read_context_file(scope_name, "todos.yaml", || Todos {
+            todos: Vec::new(),
+            custom: HashMap::new(),
+        })
+    }
+
+    pub(crate) fn read_decisions(&self, scope_name: &str) -> RhemaResult<Decisions> {
+        self.read_context_file(scope_name, "decisions.yaml", || Decisions {
+            decisions: Vec::new(),
+            custom: HashMap::new(),
+        })
+    }
+
+    pub(crate) fn read_patterns(&self, scope_name: &str) -> RhemaResult<Patterns> {
+        self.read_context_file(scope_name, "patterns.yaml", || Patterns {
+            patterns: Vec::new(),
+            custom: HashMap::new(),
+        })
+    }
+
+    pub(crate) fn read_conventions(&self, scope_name: &str) -> RhemaResult<Conventions> {
+        self.read_context_file(scope_name, "conventions.yaml", || Conventions {
+            conventions: Vec::new(),
+            custom: HashMap::new(),
+        })
     }
 
     /// Clear all caches
     #[instrument(skip_all)]
     pub async fn clear_caches(&self) -> RhemaResult<()> {
         let mut cache = self.cache.write().await;
+        self.query_cache_activity
+            .write()
+            .await
+            .evict(EvictionCause::Invalidated, cache.len() as u64);
         cache.clear();
 
         let mut scope_cache = self.scope_cache.write().await;
+        self.scope_cache_activity
+            .write()
+            .await
+            .evict(EvictionCause::Invalidated, scope_cache.len() as u64);
         scope_cache.clear();
 
         info!("All caches cleared");
         Ok(())
     }
 
-    /// Get cache statistics
+    /// Get statistics of the query and scope caches
+    ///
+    /// Both caches are unbounded and keep entries until cleared, so the
+    /// reports carry no limits or TTL.
     #[instrument(skip_all)]
-    pub async fn get_cache_stats(&self) -> RhemaResult<HashMap<String, usize>> {
-        let cache_size = self.cache.read().await.len();
-        let scope_cache_size = self.scope_cache.read().await.len();
-
-        Ok(HashMap::from([
-            ("query_cache_size".to_string(), cache_size),
-            ("scope_cache_size".to_string(), scope_cache_size),
-        ]))
+    pub async fn get_cache_stats(&self) -> RhemaResult<Vec<CacheTierReport>> {
+        let mut query = CacheTierReport::new("api", "query");
+        {
+            let cache = self.cache.read().await;
+            query.entries = cache.len();
+            query.memory_bytes = cache
+                .iter()
+                .map(|(key, value)| {
+                    (key.len() + serde_yaml::to_string(value).map_or(0, |s| s.len())) as u64
+                })
+                .sum();
+        }
+        self.query_cache_activity.read().await.fill(&mut query);
+
+        let mut scope = CacheTierReport::new("api", "scope");
+        {
+            let scope_cache = self.scope_cache.read().await;
+            scope.entries = scope_cache.len();
+            scope.memory_bytes = scope_cache
+                .keys()
+                .map(|key| (key.len() + std::mem::size_of::<Scope>()) as u64)
+                .sum();
+        }
+        self.scope_cache_activity.read().await.fill(&mut scope);
+
+        Ok(vec![query, scope])
     }
 
     // ===== COORDINATION METHODS =====
@@ -0,0 +1,139 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Version 1 of the Rhema API.
+//!
+//! Everything reachable from this module follows semver: within API 1.x,
+//! items are only added, never removed or changed incompatibly. Breaking
+//! changes ship as a new `v2` module next to this one, and `v1` stays until
+//! the major release after that. `scripts/check-public-api.sh` compares the
+//! module's surface with `crates/rhema-api/public-api/v1.txt` in CI, so an
+//! internal refactor that leaks into it fails the build.
+
+use crate::Rhema;
+use std::path::{Path, PathBuf};
+
+pub use rhema_core::schema::{Conventions, Decisions, Knowledge, Patterns, Todos};
+pub use rhema_core::{RhemaError, RhemaResult, Scope};
+pub use rhema_query::{QueryProvenance, QueryResult};
+
+/// API version this module implements
+pub const VERSION: &str = crate::API_VERSION;
+
+/// Stable entry point to a repository's context
+pub struct Client {
+    inner: Rhema,
+}
+
+impl Client {
+    /// Open the repository containing the current directory
+    pub fn discover() -> RhemaResult<Self> {
+        Ok(Self::from_legacy(Rhema::new()?))
+    }
+
+    /// Open the repository at `repo_root`
+    pub fn open(repo_root: impl Into<PathBuf>) -> RhemaResult<Self> {
+        Ok(Self::from_legacy(Rhema::new_from_path(repo_root.into())?))
+    }
+
+    pub(crate) fn from_legacy(inner: Rhema) -> Self {
+        Self { inner }
+    }
+
+    pub fn repo_root(&self) -> &Path {
+        self.inner.repo_root()
+    }
+
+    pub fn version(&self) -> &'static str {
+        VERSION
+    }
+
+    /// All scopes in the repository
+    pub async fn scopes(&self) -> RhemaResult<Vec<Scope>> {
+        self.inner.scopes()
+    }
+
+    /// A scope by name or path
+    pub async fn scope(&self, name: &str) -> RhemaResult<Scope> {
+        self.inner.find_scope(name)
+    }
+
+    /// Run a CQL query
+    pub async fn query(&self, query: &str) -> RhemaResult<serde_yaml::Value> {
+        self.inner.query(query)
+    }
+
+    /// Run a CQL query and report where each result came from
+    pub async fn query_with_provenance(
+        &self,
+        query: &str,
+    ) -> RhemaResult<(serde_yaml::Value, QueryProvenance)> {
+        self.inner.query_with_provenance(query)
+    }
+
+    pub async fn knowledge(&self, scope: &str) -> RhemaResult<Knowledge> {
+        self.inner.read_knowledge(scope)
+    }
+
+    pub async fn todos(&self, scope: &str) -> RhemaResult<Todos> {
+        self.inner.read_todos(scope)
+    }
+
+    pub async fn decisions(&self, scope: &str) -> RhemaResult<Decisions> {
+        self.inner.read_decisions(scope)
+    }
+
+    pub async fn patterns(&self, scope: &str) -> RhemaResult<Patterns> {
+        self.inner.read_patterns(scope)
+    }
+
+    pub async fn conventions(&self, scope: &str) -> RhemaResult<Conventions> {
+        self.inner.read_conventions(scope)
+    }
+
+    /// The unversioned instance behind this client, for calls v1 does not
+    /// cover yet; nothing reached through it is covered by semver
+    pub fn legacy(&self) -> &Rhema {
+        &self.inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[tokio::test]
+    async fn test_client_reads_context_files() {
+        let temp = tempfile::TempDir::new().unwrap();
+        fs::create_dir(temp.path().join(".git")).unwrap();
+        let scope = temp.path().join("api/.rhema");
+        fs::create_dir_all(&scope).unwrap();
+        fs::write(
+            scope.join("rhema.yaml"),
+            "name: api\nscope_type: service\nversion: \"1.0.0\"\n",
+        )
+        .unwrap();
+
+        let client = Client::open(temp.path()).unwrap();
+        assert_eq!(client.version(), "1.0.0");
+        assert_eq!(client.scopes().await.unwrap().len(), 1);
+        assert_eq!(client.scope("api").await.unwrap().definition.name, "api");
+        // A scope without the file reads as empty rather than failing
+        assert!(client.todos("api").await.unwrap().todos.is_empty());
+        assert!(client.scope("missing").await.is_err());
+    }
+}
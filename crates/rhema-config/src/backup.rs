@@ -15,7 +15,9 @@
  */
 
 use crate::{Config, ConfigError};
-use chrono::{DateTime, Utc};
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, NaiveTime, Utc, Weekday};
+use rhema_core::jobs::{Job, JobContext, JobHandler, JobQueue, JobSpec, JobStatus};
 use rhema_core::RhemaResult;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -38,7 +40,91 @@ pub enum BackupFrequency {
     Daily,
     Weekly,
     Monthly,
-    Custom(String), // Cron-like expression
+    Custom(String), // Interval such as "30m", "6h" or "2d"
+}
+
+/// Job kind backing up the global configuration
+pub const CONFIG_BACKUP_JOB: &str = "config.backup";
+
+impl BackupSchedule {
+    /// First time after `now` the schedule fires; `time` is read as UTC
+    pub fn next_run(&self, now: DateTime<Utc>) -> RhemaResult<DateTime<Utc>> {
+        let time = NaiveTime::parse_from_str(&self.time, "%H:%M").map_err(|e| {
+            ConfigError::BackupFailed(format!("Invalid backup time {}: {}", self.time, e))
+        })?;
+        let at = |offset_days: i64| {
+            (now.date_naive() + chrono::Duration::days(offset_days))
+                .and_time(time)
+                .and_utc()
+        };
+
+        let next = match &self.frequency {
+            BackupFrequency::Daily => (0..=1).map(at).find(|run| *run > now),
+            BackupFrequency::Weekly => {
+                let weekday = match &self.day_of_week {
+                    Some(day) => day.parse::<Weekday>().map_err(|_| {
+                        ConfigError::BackupFailed(format!("Invalid backup day of week {}", day))
+                    })?,
+                    None => now.weekday(),
+                };
+                (0..=7)
+                    .map(at)
+                    .find(|run| run.weekday() == weekday && *run > now)
+            }
+            BackupFrequency::Monthly => {
+                // Days past the 28th do not exist in every month
+                let day = self.day_of_month.unwrap_or(1).clamp(1, 28);
+                (0..=62).map(at).find(|run| run.day() == day && *run > now)
+            }
+            BackupFrequency::Custom(_) => Some(now + self.interval()?),
+        };
+        next.ok_or_else(|| {
+            ConfigError::BackupFailed("Backup schedule never fires".to_string()).into()
+        })
+    }
+
+    /// Time between runs. Monthly schedules repeat every 30 days
+    pub fn interval(&self) -> RhemaResult<chrono::Duration> {
+        let interval = match &self.frequency {
+            BackupFrequency::Daily => chrono::Duration::days(1),
+            BackupFrequency::Weekly => chrono::Duration::weeks(1),
+            BackupFrequency::Monthly => chrono::Duration::days(30),
+            BackupFrequency::Custom(interval) => parse_interval(interval).ok_or_else(|| {
+                ConfigError::BackupFailed(format!("Invalid backup interval {}", interval))
+            })?,
+        };
+        Ok(interval)
+    }
+}
+
+fn parse_interval(interval: &str) -> Option<chrono::Duration> {
+    let interval = interval.trim();
+    let split = interval.len().checked_sub(1)?;
+    let count: i64 = interval[..split].parse().ok().filter(|count| *count > 0)?;
+    match &interval[split..] {
+        "s" => Some(chrono::Duration::seconds(count)),
+        "m" => Some(chrono::Duration::minutes(count)),
+        "h" => Some(chrono::Duration::hours(count)),
+        "d" => Some(chrono::Duration::days(count)),
+        "w" => Some(chrono::Duration::weeks(count)),
+        _ => None,
+    }
+}
+
+/// Backs up the global configuration from the job queue
+pub struct BackupJob;
+
+#[async_trait]
+impl JobHandler for BackupJob {
+    async fn run(&self, _job: &Job, _context: &JobContext) -> RhemaResult<serde_json::Value> {
+        let global_config = super::GlobalConfig::load()?;
+        let mut manager = BackupManager::new(&global_config)?;
+        let report = manager.backup_all(&global_config, &HashMap::new(), &HashMap::new())?;
+        if report.backups_created.is_empty() && !report.backups_failed.is_empty() {
+            return Err(ConfigError::BackupFailed(report.backups_failed[0].error.clone()).into());
+        }
+        Ok(serde_json::to_value(&report)?)
+    }
 }
 
 /// Detailed backup statistics
@@ -622,36 +708,35 @@ impl BackupManager {
         &self.backup_directory
     }
 
-    /// Schedule automatic backup
-    pub async fn schedule_automatic_backup(&self, schedule: &BackupSchedule) -> RhemaResult<()> {
-        tracing::info!("Scheduling automatic backup with schedule: {:?}", schedule);
-
-        // Here we would integrate with a task scheduler
-        // For now, we'll just log the schedule
-        match schedule.frequency {
-            BackupFrequency::Daily => {
-                tracing::info!("Daily backup scheduled at {}", schedule.time);
-            }
-            BackupFrequency::Weekly => {
-                tracing::info!(
-                    "Weekly backup scheduled on {:?} at {}",
-                    schedule.day_of_week,
-                    schedule.time
-                );
-            }
-            BackupFrequency::Monthly => {
-                tracing::info!(
-                    "Monthly backup scheduled on day {:?} at {}",
-                    schedule.day_of_month,
-                    schedule.time
-                );
-            }
-            BackupFrequency::Custom(ref interval) => {
-                tracing::info!("Custom backup scheduled with interval: {}", interval);
+    /// Schedule automatic backups on the job queue, replacing any backup
+    /// already queued. Returns the first backup job, or `None` when the
+    /// schedule is disabled
+    pub async fn schedule_automatic_backup(
+        &self,
+        queue: &JobQueue,
+        schedule: &BackupSchedule,
+    ) -> RhemaResult<Option<Job>> {
+        for job in queue.list(Some(JobStatus::Queued))? {
+            if job.kind == CONFIG_BACKUP_JOB {
+                queue.cancel(&job.id)?;
             }
         }
+        if !schedule.enabled {
+            return Ok(None);
+        }
 
-        Ok(())
+        let run_after = schedule.next_run(Utc::now())?;
+        let interval = schedule
+            .interval()?
+            .to_std()
+            .map_err(|e| ConfigError::BackupFailed(format!("Invalid backup interval: {}", e)))?;
+        let job = queue.enqueue(
+            JobSpec::new(CONFIG_BACKUP_JOB, serde_json::Value::Null)
+                .with_run_after(run_after)
+                .with_repeat_every(interval),
+        )?;
+        tracing::info!("Scheduled configuration backup {} at {}", job.id, run_after);
+        Ok(Some(job))
     }
 
     /// Optimize compression for better performance and size
@@ -920,3 +1005,36 @@ impl BackupManager {
         "config".to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule(frequency: BackupFrequency) -> BackupSchedule {
+        BackupSchedule {
+            frequency,
+            time: "02:30".to_string(),
+            day_of_week: Some("sunday".to_string()),
+            day_of_month: Some(31),
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn computes_next_backup_run() {
+        // A Wednesday afternoon
+        let now = "2025-01-15T14:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let next = |frequency| schedule(frequency).next_run(now).unwrap().to_rfc3339();
+
+        assert_eq!(next(BackupFrequency::Daily), "2025-01-16T02:30:00+00:00");
+        assert_eq!(next(BackupFrequency::Weekly), "2025-01-19T02:30:00+00:00");
+        assert_eq!(next(BackupFrequency::Monthly), "2025-01-28T02:30:00+00:00");
+        assert_eq!(
+            next(BackupFrequency::Custom("6h".to_string())),
+            "2025-01-15T20:00:00+00:00"
+        );
+        assert!(schedule(BackupFrequency::Custom("soon".to_string()))
+            .interval()
+            .is_err());
+    }
+}
@@ -29,6 +29,8 @@ pub struct ComprehensiveValidator {
     cache_ttl: u64,
     validation_level: ValidationLevel,
     auto_fix: bool,
+    #[cfg(feature = "wasm-plugins")]
+    plugins: Option<Arc<crate::wasm_plugins::WasmValidatorHost>>,
 }
 
 /// Validation level for comprehensive validation
@@ -93,6 +95,8 @@ impl ComprehensiveValidator {
             cache_ttl: 300, // 5 minutes default
             validation_level: ValidationLevel::Standard,
             auto_fix: false,
+            #[cfg(feature = "wasm-plugins")]
+            plugins: None,
         })
     }
 
@@ -116,9 +120,18 @@ impl ComprehensiveValidator {
             cache_ttl,
             validation_level,
             auto_fix,
+            #[cfg(feature = "wasm-plugins")]
+            plugins: None,
         })
     }
 
+    /// Run user-defined WASM validators on context files at every validation level
+    #[cfg(feature = "wasm-plugins")]
+    pub fn with_plugins(mut self, plugins: Arc<crate::wasm_plugins::WasmValidatorHost>) -> Self {
+        self.plugins = Some(plugins);
+        self
+    }
+
     /// Validate a single configuration file
     pub async fn validate_config_file<P: AsRef<Path>>(
         &self,
@@ -271,7 +284,13 @@ impl ComprehensiveValidator {
             all_issues.extend(complete_issues);
         }
 
-        // 4. Auto-fix if enabled
+        // 4. User-defined plugins
+        #[cfg(feature = "wasm-plugins")]
+        if let Some(plugins) = &self.plugins {
+            all_issues.extend(plugins.validate(config_value, schema_type, context));
+        }
+
+        // 5. Auto-fix if enabled
         if self.auto_fix {
             let fixed_issues = self.auto_fix_issues(&mut all_issues, config_value).await?;
             all_issues = fixed_issues;
@@ -0,0 +1,255 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Routing of exported events to external event buses.
+//!
+//! The `event_export` section of `.rhema/repository.yaml` names the sinks
+//! (webhooks, NATS subjects, Kafka topics through a REST proxy) and routes
+//! each event type to some of them.
+
+use rhema_core::events::{EventOutbox, ExportEventType};
+use rhema_core::policy::repository_config;
+use rhema_core::{RhemaError, RhemaResult};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::time::Duration;
+
+/// Section of `.rhema/repository.yaml` configuring event export
+pub const EVENT_EXPORT_CONFIG_SECTION: &str = "event_export";
+
+/// Route entry matching every event type
+pub const ALL_EVENTS: &str = "*";
+
+/// Where events are delivered
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EventSinkConfig {
+    /// HTTP POST of the JSON envelope
+    Webhook {
+        url: String,
+        #[serde(default)]
+        headers: BTreeMap<String, String>,
+        /// Environment variable holding a bearer token
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        token_env: Option<String>,
+    },
+    /// Publish to a NATS JetStream stream capturing the subject; `{type}` is
+    /// replaced by the event type
+    Nats {
+        /// Server address, e.g. `nats://localhost:4222`
+        url: String,
+        #[serde(default = "default_nats_subject")]
+        subject: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        token_env: Option<String>,
+    },
+    /// Produce to a Kafka topic through a Kafka REST proxy; `{type}` is
+    /// replaced by the event type
+    Kafka {
+        rest_proxy_url: String,
+        topic: String,
+    },
+}
+
+fn default_nats_subject() -> String {
+    "rhema.events.{type}".to_string()
+}
+
+/// Event types delivered to some sinks
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventRoute {
+    /// Event type names, or `*` for all
+    pub events: Vec<String>,
+    pub sinks: Vec<String>,
+}
+
+impl EventRoute {
+    pub fn matches(&self, event_type: ExportEventType) -> bool {
+        self.events
+            .iter()
+            .any(|name| name == ALL_EVENTS || name == event_type.name())
+    }
+}
+
+/// Event export configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventExportConfig {
+    pub enabled: bool,
+
+    pub sinks: BTreeMap<String, EventSinkConfig>,
+
+    pub routes: Vec<EventRoute>,
+
+    /// Failed deliveries to a sink before the event is dead-lettered for it
+    pub max_attempts: u32,
+
+    /// Delay before the first retry, doubled on every further failure
+    pub retry_backoff_secs: u64,
+
+    pub max_backoff_secs: u64,
+
+    /// Seconds between delivery rounds of a running bridge
+    pub flush_interval_secs: u64,
+
+    /// Timeout of a single delivery
+    pub timeout_secs: u64,
+}
+
+impl Default for EventExportConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            sinks: BTreeMap::new(),
+            routes: Vec::new(),
+            max_attempts: 10,
+            retry_backoff_secs: 5,
+            max_backoff_secs: 900,
+            flush_interval_secs: 10,
+            timeout_secs: 10,
+        }
+    }
+}
+
+impl EventExportConfig {
+    /// Load and validate the configuration from the repository config and
+    /// policy bundle, falling back to defaults
+    pub fn load(repo_root: &Path) -> RhemaResult<Self> {
+        let value = repository_config(repo_root)?;
+        let config: Self = match value.get(EVENT_EXPORT_CONFIG_SECTION) {
+            Some(section) => {
+                serde_yaml::from_value(section.clone()).map_err(|e| Self::invalid(e.to_string()))?
+            }
+            None => Self::default(),
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Reject routes naming unknown event types or sinks
+    pub fn validate(&self) -> RhemaResult<()> {
+        for route in &self.routes {
+            for name in &route.events {
+                if name != ALL_EVENTS && name.parse::<ExportEventType>().is_err() {
+                    return Err(Self::invalid(format!("unknown event type '{}'", name)));
+                }
+            }
+            for sink in &route.sinks {
+                if !self.sinks.contains_key(sink) {
+                    return Err(Self::invalid(format!("route to unknown sink '{}'", sink)));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn invalid(message: String) -> RhemaError {
+        RhemaError::ConfigError(format!(
+            "Invalid {} section in the repository config: {}",
+            EVENT_EXPORT_CONFIG_SECTION, message
+        ))
+    }
+
+    /// Sinks an event type is routed to, in route order without repeats
+    pub fn sinks_for(&self, event_type: ExportEventType) -> Vec<&str> {
+        let mut sinks: Vec<&str> = Vec::new();
+        for route in self.routes.iter().filter(|route| route.matches(event_type)) {
+            for sink in &route.sinks {
+                if !sinks.contains(&sink.as_str()) {
+                    sinks.push(sink);
+                }
+            }
+        }
+        sinks
+    }
+
+    /// Outbox producers should publish to, `None` when export is off
+    pub fn outbox(&self, repo_root: &Path) -> Option<EventOutbox> {
+        (self.enabled && !self.routes.is_empty()).then(|| EventOutbox::for_repository(repo_root))
+    }
+
+    /// Delay before retrying after `attempts` failed deliveries
+    pub fn backoff(&self, attempts: u32) -> Duration {
+        let factor = 2u64.saturating_pow(attempts.saturating_sub(1));
+        Duration::from_secs(
+            self.retry_backoff_secs
+                .saturating_mul(factor)
+                .min(self.max_backoff_secs),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routes_select_sinks_per_event_type() {
+        let config: EventExportConfig = serde_yaml::from_str(
+            r#"
+enabled: true
+sinks:
+  audit:
+    type: webhook
+    url: https://hooks.example.com/rhema
+  bus:
+    type: nats
+    url: nats://localhost:4222
+  stream:
+    type: kafka
+    rest_proxy_url: http://localhost:8082
+    topic: rhema-{type}
+routes:
+  - events: ["*"]
+    sinks: [bus]
+  - events: [approval_granted, action_executed]
+    sinks: [audit, bus]
+  - events: [conflict_detected]
+    sinks: [stream]
+"#,
+        )
+        .unwrap();
+        config.validate().unwrap();
+
+        assert_eq!(
+            config.sinks_for(ExportEventType::ApprovalGranted),
+            vec!["bus", "audit"]
+        );
+        assert_eq!(
+            config.sinks_for(ExportEventType::ConflictDetected),
+            vec!["bus", "stream"]
+        );
+        assert_eq!(
+            config.sinks["bus"],
+            EventSinkConfig::Nats {
+                url: "nats://localhost:4222".to_string(),
+                subject: "rhema.events.{type}".to_string(),
+                token_env: None,
+            }
+        );
+        assert_eq!(config.backoff(1), Duration::from_secs(5));
+        assert_eq!(config.backoff(3), Duration::from_secs(20));
+        assert_eq!(config.backoff(30), Duration::from_secs(900));
+
+        let mut broken = config.clone();
+        broken.routes[0].sinks.push("missing".to_string());
+        assert!(broken.validate().is_err());
+        broken.routes[0].sinks.pop();
+        broken.routes[0].events.push("deployed".to_string());
+        assert!(broken.validate().is_err());
+    }
+}
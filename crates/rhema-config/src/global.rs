@@ -19,13 +19,14 @@ use crate::{
 };
 use chrono::{DateTime, Utc};
 use rhema_core::RhemaResult;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use validator::Validate;
 /// Global configuration for Rhema CLI
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Validate)]
 pub struct GlobalConfig {
     /// Configuration version
     #[validate(length(min = 1))]
@@ -67,7 +68,7 @@ pub struct GlobalConfig {
 }
 
 /// User configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Validate)]
 pub struct UserConfig {
     /// User ID
     #[validate(length(min = 1))]
@@ -92,7 +93,7 @@ pub struct UserConfig {
 }
 
 /// User preferences
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct UserPreferences {
     /// Default output format
     pub default_output_format: String,
@@ -123,7 +124,7 @@ pub struct UserPreferences {
 }
 
 /// Notification preferences
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct NotificationPreferences {
     /// Enable email notifications
     pub email_enabled: bool,
@@ -142,7 +143,7 @@ pub struct NotificationPreferences {
 }
 
 /// Notification frequency
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum NotificationFrequency {
     Immediate,
     Hourly,
@@ -152,7 +153,7 @@ pub enum NotificationFrequency {
 }
 
 /// UI preferences
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct UIPreferences {
     /// Theme
     pub theme: String,
@@ -177,7 +178,7 @@ pub struct UIPreferences {
 }
 
 /// Application configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Validate)]
 pub struct ApplicationConfig {
     /// Application name
     #[validate(length(min = 1))]
@@ -201,7 +202,7 @@ pub struct ApplicationConfig {
 }
 
 /// Application settings
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AppSettings {
     /// Enable debug mode
     pub debug_mode: bool,
@@ -235,7 +236,7 @@ pub struct AppSettings {
 }
 
 /// Feature flags
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct FeatureFlags {
     /// Enable experimental features
     pub experimental_features: bool,
@@ -260,7 +261,7 @@ pub struct FeatureFlags {
 }
 
 /// Plugin configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PluginConfig {
     /// Plugin directory
     pub plugin_directory: PathBuf,
@@ -279,7 +280,7 @@ pub struct PluginConfig {
 }
 
 /// Environment configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Validate)]
 pub struct EnvironmentConfig {
     /// Current environment
     pub current: ConfigEnvironment,
@@ -295,7 +296,7 @@ pub struct EnvironmentConfig {
 }
 
 /// Environment settings
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct EnvironmentSettings {
     /// Environment name
     pub name: String,
@@ -314,7 +315,7 @@ pub struct EnvironmentSettings {
 }
 
 /// Path configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PathConfig {
     /// Home directory
     pub home: PathBuf,
@@ -342,7 +343,7 @@ pub struct PathConfig {
 }
 
 /// Security configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Validate)]
 pub struct SecurityConfig {
     /// Encryption settings
     pub encryption: EncryptionConfig,
@@ -361,7 +362,7 @@ pub struct SecurityConfig {
 }
 
 /// Encryption configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct EncryptionConfig {
     /// Enable encryption
     pub enabled: bool,
@@ -389,7 +390,7 @@ pub struct EncryptionConfig {
 }
 
 /// Authentication configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AuthenticationConfig {
     /// Authentication method
     pub method: String,
@@ -417,7 +418,7 @@ pub struct AuthenticationConfig {
 }
 
 /// Authorization configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AuthorizationConfig {
     /// Role-based access control
     pub rbac_enabled: bool,
@@ -445,7 +446,7 @@ pub struct AuthorizationConfig {
 }
 
 /// Audit configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AuditConfig {
     /// Audit logging enabled
     pub enabled: bool,
@@ -467,7 +468,7 @@ pub struct AuditConfig {
 }
 
 /// Compliance configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ComplianceConfig {
     /// Compliance framework
     pub framework: String,
@@ -486,7 +487,7 @@ pub struct ComplianceConfig {
 }
 
 /// Performance configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Validate)]
 pub struct PerformanceConfig {
     /// Cache settings
     pub cache: CacheConfig,
@@ -502,7 +503,7 @@ pub struct PerformanceConfig {
 }
 
 /// Cache configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CacheConfig {
     /// Cache enabled
     pub enabled: bool,
@@ -527,7 +528,7 @@ pub struct CacheConfig {
 }
 
 /// Threading configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ThreadingConfig {
     /// Max threads
     pub max_threads: u32,
@@ -543,7 +544,7 @@ pub struct ThreadingConfig {
 }
 
 /// Memory configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MemoryConfig {
     /// Max memory usage (MB)
     pub max_memory_usage: u64,
@@ -559,7 +560,7 @@ pub struct MemoryConfig {
 }
 
 /// Network configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct NetworkConfig {
     /// Connection timeout (seconds)
     pub connection_timeout: u64,
@@ -581,7 +582,7 @@ pub struct NetworkConfig {
 }
 
 /// Proxy configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProxyConfig {
     /// Proxy enabled
     pub enabled: bool,
@@ -600,7 +601,7 @@ pub struct ProxyConfig {
 }
 
 /// Integration configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Validate)]
 pub struct IntegrationConfig {
     /// Git integration
     pub git: GitIntegrationConfig,
@@ -619,7 +620,7 @@ pub struct IntegrationConfig {
 }
 
 /// Git integration configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct GitIntegrationConfig {
     /// Git enabled
     pub enabled: bool,
@@ -644,7 +645,7 @@ pub struct GitIntegrationConfig {
 }
 
 /// Git credentials
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct GitCredentials {
     /// Username
     pub username: Option<String>,
@@ -663,7 +664,7 @@ pub struct GitCredentials {
 }
 
 /// IDE integration configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct IDEIntegrationConfig {
     /// IDE enabled
     pub enabled: bool,
@@ -682,7 +683,7 @@ pub struct IDEIntegrationConfig {
 }
 
 /// CI/CD integration configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CICDIntegrationConfig {
     /// CI/CD enabled
     pub enabled: bool,
@@ -701,7 +702,7 @@ pub struct CICDIntegrationConfig {
 }
 
 /// Cloud integration configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CloudIntegrationConfig {
     /// Cloud enabled
     pub enabled: bool,
@@ -720,7 +721,7 @@ pub struct CloudIntegrationConfig {
 }
 
 /// Cloud credentials
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CloudCredentials {
     /// Access key ID
     pub access_key_id: Option<String>,
@@ -739,7 +740,7 @@ pub struct CloudCredentials {
 }
 
 /// External service configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ExternalServiceConfig {
     /// Service enabled
     pub enabled: bool,
@@ -1,11 +1,13 @@
 pub mod backup;
 pub mod comprehensive_validator;
 pub mod config;
+pub mod event_export;
 pub mod global;
 pub mod invariants;
 pub mod lock;
 pub mod migration;
 pub mod repository;
+pub mod schema_export;
 pub mod schema_validator;
 pub mod scope;
 pub mod security;
@@ -16,6 +18,8 @@ pub mod types;
 pub mod validation;
 pub mod validation_rules;
 pub mod validator;
+#[cfg(feature = "wasm-plugins")]
+pub mod wasm_plugins;
 
 // Re-export core types
 pub use rhema_core::{RhemaError, RhemaResult};
@@ -32,14 +36,16 @@ pub use types::{
 
 // Re-export specific types from modules
 pub use backup::{
-    BackupFormat, BackupFrequency, BackupManager, BackupRecord, BackupReport, BackupSchedule,
-    BackupSummary, DetailedBackupStats, RestoreReport, RestoreSummary, RestoredConfig,
+    BackupFormat, BackupFrequency, BackupJob, BackupManager, BackupRecord, BackupReport,
+    BackupSchedule, BackupSummary, DetailedBackupStats, RestoreReport, RestoreSummary,
+    RestoredConfig, CONFIG_BACKUP_JOB,
 };
 pub use comprehensive_validator::{
     ComprehensiveValidationIssue, ComprehensiveValidationReport, ComprehensiveValidationResult,
     ComprehensiveValidationStatistics, ComprehensiveValidationSummary, ComprehensiveValidator,
     ValidationCategory,
 };
+pub use event_export::{EventExportConfig, EventRoute, EventSinkConfig};
 pub use global::GlobalConfig;
 pub use invariants::{
     AgentValidator, ContextValidator, DependencyValidator, LockValidator, SyncValidator,
@@ -57,6 +63,7 @@ pub use migration::{
     MigrationReport, MigrationStep, MigrationStepType, MigrationSummary,
 };
 pub use repository::RepositoryConfig;
+pub use schema_export::{SchemaDocument, SchemaExporter};
 pub use schema_validator::{
     SchemaType, SchemaValidationIssue, SchemaValidationResult, SchemaValidationStatistics,
     SchemaValidator,
@@ -80,6 +87,8 @@ pub use validation_rules::{
     RuleCondition, RuleEvaluationResult, RuleSet, RuleType, SchemaOverride, ValidationRule,
     ValidationRulesConfig, ValidationRulesManager, ValidationRulesStatistics,
 };
+#[cfg(feature = "wasm-plugins")]
+pub use wasm_plugins::{PluginIssue, WasmValidatorConfig, WasmValidatorHost, WasmValidatorsConfig};
 
 // Error type conversions
 impl From<ConfigError> for RhemaError {
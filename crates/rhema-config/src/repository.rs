@@ -17,13 +17,14 @@
 use crate::{Config, ConfigAuditLog, ConfigHealth, ConfigStats, CURRENT_CONFIG_VERSION};
 use chrono::{DateTime, Utc};
 use rhema_core::RhemaResult;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use validator::Validate;
 /// Repository configuration for Rhema CLI
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Validate)]
 pub struct RepositoryConfig {
     /// Configuration version
     #[validate(length(min = 1))]
@@ -65,7 +66,7 @@ pub struct RepositoryConfig {
 }
 
 /// Repository information
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Validate)]
 pub struct RepositoryInfo {
     /// Repository name
     #[validate(length(min = 1))]
@@ -94,7 +95,7 @@ pub struct RepositoryInfo {
 }
 
 /// Repository type
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub enum RepositoryType {
     Git,
     SVN,
@@ -103,7 +104,7 @@ pub enum RepositoryType {
 }
 
 /// Repository visibility
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub enum RepositoryVisibility {
     Public,
     Private,
@@ -111,7 +112,7 @@ pub enum RepositoryVisibility {
 }
 
 /// Repository settings
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Validate)]
 pub struct RepositorySettings {
     /// Default branch
     pub default_branch: String,
@@ -136,7 +137,7 @@ pub struct RepositorySettings {
 }
 
 /// Branch protection configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct BranchProtectionConfig {
     /// Protected branches
     pub protected_branches: Vec<String>,
@@ -164,7 +165,7 @@ pub struct BranchProtectionConfig {
 }
 
 /// Commit conventions
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CommitConventions {
     /// Conventional commits enabled
     pub conventional_commits: bool,
@@ -186,7 +187,7 @@ pub struct CommitConventions {
 }
 
 /// Code review configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CodeReviewConfig {
     /// Code review required
     pub required: bool,
@@ -208,7 +209,7 @@ pub struct CodeReviewConfig {
 }
 
 /// Testing configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct TestingConfig {
     /// Test framework
     pub framework: String,
@@ -230,7 +231,7 @@ pub struct TestingConfig {
 }
 
 /// Coverage requirements
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CoverageRequirements {
     /// Minimum coverage percentage
     pub minimum_coverage: f64,
@@ -243,7 +244,7 @@ pub struct CoverageRequirements {
 }
 
 /// Documentation configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DocumentationConfig {
     /// Documentation directory
     pub documentation_directory: PathBuf,
@@ -262,7 +263,7 @@ pub struct DocumentationConfig {
 }
 
 /// Deployment configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DeploymentConfig {
     /// Deployment environments
     pub environments: Vec<DeploymentEnvironment>,
@@ -278,7 +279,7 @@ pub struct DeploymentConfig {
 }
 
 /// Deployment environment
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DeploymentEnvironment {
     /// Environment name
     pub name: String,
@@ -297,7 +298,7 @@ pub struct DeploymentEnvironment {
 }
 
 /// Deployment strategy
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum DeploymentStrategy {
     Rolling,
     BlueGreen,
@@ -306,7 +307,7 @@ pub enum DeploymentStrategy {
 }
 
 /// Rollback configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RollbackConfig {
     /// Auto-rollback enabled
     pub auto_rollback: bool,
@@ -322,7 +323,7 @@ pub struct RollbackConfig {
 }
 
 /// Health check
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct HealthCheck {
     /// Health check name
     pub name: String,
@@ -341,7 +342,7 @@ pub struct HealthCheck {
 }
 
 /// Scope configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ScopeConfig {
     /// Default scope type
     pub default_scope_type: String,
@@ -360,7 +361,7 @@ pub struct ScopeConfig {
 }
 
 /// Scope inheritance configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ScopeInheritanceConfig {
     /// Enable inheritance
     pub enabled: bool,
@@ -373,7 +374,7 @@ pub struct ScopeInheritanceConfig {
 }
 
 /// Inheritance rule
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct InheritanceRule {
     /// Rule name
     pub name: String,
@@ -392,7 +393,7 @@ pub struct InheritanceRule {
 }
 
 /// Override behavior
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum OverrideBehavior {
     Allow,
     Deny,
@@ -400,7 +401,7 @@ pub enum OverrideBehavior {
 }
 
 /// Scope validation configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ScopeValidationConfig {
     /// Validation enabled
     pub enabled: bool,
@@ -413,7 +414,7 @@ pub struct ScopeValidationConfig {
 }
 
 /// Validation rule
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ValidationRule {
     /// Rule name
     pub name: String,
@@ -429,7 +430,7 @@ pub struct ValidationRule {
 }
 
 /// Validation severity
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, PartialOrd)]
 pub enum ValidationSeverity {
     Info,
     Warning,
@@ -438,7 +439,7 @@ pub enum ValidationSeverity {
 }
 
 /// Workflow configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WorkflowConfig {
     /// Workflow type
     pub workflow_type: String,
@@ -454,7 +455,7 @@ pub struct WorkflowConfig {
 }
 
 /// Workflow step
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WorkflowStep {
     /// Step name
     pub name: String,
@@ -473,7 +474,7 @@ pub struct WorkflowStep {
 }
 
 /// Workflow trigger
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WorkflowTrigger {
     /// Trigger name
     pub name: String,
@@ -486,7 +487,7 @@ pub struct WorkflowTrigger {
 }
 
 /// Workflow condition
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WorkflowCondition {
     /// Condition name
     pub name: String,
@@ -499,7 +500,7 @@ pub struct WorkflowCondition {
 }
 
 /// Repository security configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RepositorySecurityConfig {
     /// Security scanning
     pub security_scanning: SecurityScanningConfig,
@@ -515,7 +516,7 @@ pub struct RepositorySecurityConfig {
 }
 
 /// Security scanning configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SecurityScanningConfig {
     /// Scanning enabled
     pub enabled: bool,
@@ -531,7 +532,7 @@ pub struct SecurityScanningConfig {
 }
 
 /// Vulnerability thresholds
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct VulnerabilityThresholds {
     /// Critical threshold
     pub critical: u32,
@@ -547,7 +548,7 @@ pub struct VulnerabilityThresholds {
 }
 
 /// Access control configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AccessControlConfig {
     /// Access control enabled
     pub enabled: bool,
@@ -560,7 +561,7 @@ pub struct AccessControlConfig {
 }
 
 /// Access level
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AccessLevel {
     /// Level name
     pub name: String,
@@ -573,7 +574,7 @@ pub struct AccessLevel {
 }
 
 /// Access policy
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AccessPolicy {
     /// Policy name
     pub name: String,
@@ -586,7 +587,7 @@ pub struct AccessPolicy {
 }
 
 /// Access rule
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AccessRule {
     /// Rule name
     pub name: String,
@@ -599,14 +600,14 @@ pub struct AccessRule {
 }
 
 /// Policy effect
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum PolicyEffect {
     Allow,
     Deny,
 }
 
 /// Secrets management configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SecretsManagementConfig {
     /// Secrets management enabled
     pub enabled: bool,
@@ -622,7 +623,7 @@ pub struct SecretsManagementConfig {
 }
 
 /// Secrets rotation configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SecretsRotationConfig {
     /// Rotation enabled
     pub enabled: bool,
@@ -635,7 +636,7 @@ pub struct SecretsRotationConfig {
 }
 
 /// Compliance configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ComplianceConfig {
     /// Compliance framework
     pub framework: String,
@@ -648,7 +649,7 @@ pub struct ComplianceConfig {
 }
 
 /// Compliance rule
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ComplianceRule {
     /// Rule name
     pub name: String,
@@ -664,7 +665,7 @@ pub struct ComplianceRule {
 }
 
 /// Compliance reporting configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ComplianceReportingConfig {
     /// Reporting enabled
     pub enabled: bool,
@@ -680,7 +681,7 @@ pub struct ComplianceReportingConfig {
 }
 
 /// Repository integration configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RepositoryIntegrationConfig {
     /// CI/CD integration
     pub cicd: CICDIntegrationConfig,
@@ -696,7 +697,7 @@ pub struct RepositoryIntegrationConfig {
 }
 
 /// CI/CD integration configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CICDIntegrationConfig {
     /// CI/CD enabled
     pub enabled: bool,
@@ -712,7 +713,7 @@ pub struct CICDIntegrationConfig {
 }
 
 /// Pipeline configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PipelineConfig {
     /// Pipeline stages
     pub stages: Vec<PipelineStage>,
@@ -725,7 +726,7 @@ pub struct PipelineConfig {
 }
 
 /// Pipeline stage
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct PipelineStage {
     /// Stage name
     pub name: String,
@@ -741,7 +742,7 @@ pub struct PipelineStage {
 }
 
 /// Issue tracking integration configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct IssueTrackingIntegrationConfig {
     /// Issue tracking enabled
     pub enabled: bool,
@@ -757,7 +758,7 @@ pub struct IssueTrackingIntegrationConfig {
 }
 
 /// Communication integration configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CommunicationIntegrationConfig {
     /// Communication enabled
     pub enabled: bool,
@@ -770,7 +771,7 @@ pub struct CommunicationIntegrationConfig {
 }
 
 /// Communication channel
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CommunicationChannel {
     /// Channel name
     pub name: String,
@@ -783,7 +784,7 @@ pub struct CommunicationChannel {
 }
 
 /// Notification settings
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct NotificationSettings {
     /// Notification events
     pub events: Vec<String>,
@@ -796,7 +797,7 @@ pub struct NotificationSettings {
 }
 
 /// Monitoring integration configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MonitoringIntegrationConfig {
     /// Monitoring enabled
     pub enabled: bool,
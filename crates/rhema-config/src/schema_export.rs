@@ -0,0 +1,282 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! JSON Schema export for configuration and context files.
+//!
+//! Configuration schemas are generated from the Rust types, so they always
+//! match what the loaders accept. Scope and context file schemas are the
+//! canonical documents under `schemas/` that [`crate::SchemaValidator`]
+//! validates against. Exported schemas can be used for editor completion
+//! (yaml-language-server) and for validation in CI.
+
+use crate::{GlobalConfig, RepositoryConfig};
+use rhema_core::{RhemaError, RhemaResult};
+use schemars::gen::SchemaSettings;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Meta-schema declared by every exported document
+pub const META_SCHEMA: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Base URL of the published schema `$id`s
+pub const SCHEMA_BASE_URL: &str = "https://rhema.dev/schemas/v1/";
+
+/// File written next to the schemas mapping each one to the files it describes
+pub const ASSOCIATIONS_FILE: &str = "yaml-schemas.json";
+
+/// A single exportable JSON Schema document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaDocument {
+    /// Short name used on the command line (e.g. `repository-config`)
+    pub name: String,
+
+    /// File name the schema is exported as
+    pub file_name: String,
+
+    /// Glob patterns of the files this schema describes
+    pub file_patterns: Vec<String>,
+
+    /// The schema itself
+    pub schema: Value,
+}
+
+impl SchemaDocument {
+    /// Generate a schema document from a Rust type
+    pub fn generate<T: JsonSchema>(name: &str, file_patterns: &[&str]) -> Self {
+        let mut settings = SchemaSettings::draft2019_09();
+        settings.meta_schema = Some(META_SCHEMA.to_string());
+        let root = settings.into_generator().into_root_schema_for::<T>();
+
+        let mut schema = serde_json::to_value(root).unwrap_or(Value::Null);
+        if let Value::Object(object) = &mut schema {
+            object.insert(
+                "$id".to_string(),
+                Value::String(format!("{}{}", SCHEMA_BASE_URL, name)),
+            );
+        }
+
+        Self::new(name, schema, file_patterns)
+    }
+
+    /// Wrap a hand-maintained schema document
+    pub fn from_json(name: &str, json: &str, file_patterns: &[&str]) -> RhemaResult<Self> {
+        let schema = serde_json::from_str(json).map_err(|e| {
+            RhemaError::SchemaValidation(format!("Invalid bundled schema '{}': {}", name, e))
+        })?;
+        Ok(Self::new(name, schema, file_patterns))
+    }
+
+    fn new(name: &str, schema: Value, file_patterns: &[&str]) -> Self {
+        Self {
+            name: name.to_string(),
+            file_name: format!("{}.json", name),
+            file_patterns: file_patterns.iter().map(|p| p.to_string()).collect(),
+            schema,
+        }
+    }
+}
+
+/// Collects schema documents and writes them to disk
+#[derive(Debug, Clone, Default)]
+pub struct SchemaExporter {
+    documents: Vec<SchemaDocument>,
+}
+
+impl SchemaExporter {
+    /// Create an exporter with every schema known to this crate
+    pub fn new() -> RhemaResult<Self> {
+        let mut exporter = Self::default();
+        exporter.add(SchemaDocument::generate::<GlobalConfig>(
+            "global-config",
+            &["**/rhema/global.yaml"],
+        ));
+        exporter.add(SchemaDocument::generate::<RepositoryConfig>(
+            "repository-config",
+            &[".rhema/repository.yaml"],
+        ));
+
+        for (name, json, patterns) in BUNDLED_SCHEMAS {
+            exporter.add(SchemaDocument::from_json(name, json, patterns)?);
+        }
+        Ok(exporter)
+    }
+
+    /// Add a schema, replacing any existing document with the same name.
+    ///
+    /// Crates that `rhema-config` cannot depend on (such as the MCP daemon)
+    /// register their configuration schemas this way.
+    pub fn add(&mut self, document: SchemaDocument) {
+        self.documents
+            .retain(|existing| existing.name != document.name);
+        self.documents.push(document);
+    }
+
+    /// All registered documents
+    pub fn documents(&self) -> &[SchemaDocument] {
+        &self.documents
+    }
+
+    /// Look up a document by name
+    pub fn get(&self, name: &str) -> Option<&SchemaDocument> {
+        self.documents.iter().find(|document| document.name == name)
+    }
+
+    /// Mapping of schema file to the file patterns it applies to, in the
+    /// format of the `yaml.schemas` editor setting
+    pub fn associations(&self, schema_dir: &str) -> BTreeMap<String, Vec<String>> {
+        self.documents
+            .iter()
+            .filter(|document| !document.file_patterns.is_empty())
+            .map(|document| {
+                let path = if schema_dir.is_empty() {
+                    document.file_name.clone()
+                } else {
+                    format!(
+                        "{}/{}",
+                        schema_dir.trim_end_matches('/'),
+                        document.file_name
+                    )
+                };
+                (path, document.file_patterns.clone())
+            })
+            .collect()
+    }
+
+    /// Write the selected schemas (all when `names` is empty) and the
+    /// association file into `output_dir`, returning the paths written
+    pub fn export(&self, output_dir: &Path, names: &[String]) -> RhemaResult<Vec<PathBuf>> {
+        for name in names {
+            if self.get(name).is_none() {
+                return Err(RhemaError::InvalidInput(format!(
+                    "Unknown schema '{}' (available: {})",
+                    name,
+                    self.documents
+                        .iter()
+                        .map(|document| document.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )));
+            }
+        }
+
+        fs::create_dir_all(output_dir)?;
+        let mut written = Vec::new();
+        let mut selected = SchemaExporter::default();
+        for document in &self.documents {
+            if !names.is_empty() && !names.contains(&document.name) {
+                continue;
+            }
+            let path = output_dir.join(&document.file_name);
+            fs::write(
+                &path,
+                serde_json::to_string_pretty(&document.schema)? + "\n",
+            )?;
+            written.push(path);
+            selected.add(document.clone());
+        }
+
+        let associations_path = output_dir.join(ASSOCIATIONS_FILE);
+        let associations = selected.associations(&output_dir.to_string_lossy());
+        fs::write(
+            &associations_path,
+            serde_json::to_string_pretty(&associations)? + "\n",
+        )?;
+        written.push(associations_path);
+
+        Ok(written)
+    }
+}
+
+/// Canonical scope and context file schemas shipped with Rhema
+const BUNDLED_SCHEMAS: &[(&str, &str, &[&str])] = &[
+    (
+        "scope",
+        include_str!("../../../schemas/scope.json"),
+        &["**/rhema.yaml", "**/.rhema/scope.yaml"],
+    ),
+    (
+        "knowledge",
+        include_str!("../../../schemas/knowledge.json"),
+        &["**/.rhema/knowledge.yaml"],
+    ),
+    (
+        "todos",
+        include_str!("../../../schemas/todos.json"),
+        &["**/.rhema/todos.yaml"],
+    ),
+    (
+        "decisions",
+        include_str!("../../../schemas/decisions.json"),
+        &["**/.rhema/decisions.yaml"],
+    ),
+    (
+        "patterns",
+        include_str!("../../../schemas/patterns.json"),
+        &["**/.rhema/patterns.yaml"],
+    ),
+    (
+        "conventions",
+        include_str!("../../../schemas/conventions.json"),
+        &["**/.rhema/conventions.yaml"],
+    ),
+    (
+        "lock",
+        include_str!("../../../schemas/lock.json"),
+        &["**/rhema.lock"],
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_writes_schemas_and_associations() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let exporter = SchemaExporter::new().unwrap();
+
+        let repository = exporter.get("repository-config").unwrap();
+        assert_eq!(repository.schema["$schema"], META_SCHEMA);
+        assert_eq!(
+            repository.schema["$id"],
+            format!("{}repository-config", SCHEMA_BASE_URL)
+        );
+        assert!(repository.schema["properties"]["scopes"].is_object());
+
+        let written = exporter.export(temp_dir.path(), &[]).unwrap();
+        assert_eq!(written.len(), exporter.documents().len() + 1);
+
+        let associations: BTreeMap<String, Vec<String>> = serde_json::from_str(
+            &fs::read_to_string(temp_dir.path().join(ASSOCIATIONS_FILE)).unwrap(),
+        )
+        .unwrap();
+        assert!(associations
+            .values()
+            .any(|patterns| patterns.contains(&"**/.rhema/todos.yaml".to_string())));
+
+        let only = exporter
+            .export(&temp_dir.path().join("subset"), &["todos".to_string()])
+            .unwrap();
+        assert_eq!(only.len(), 2);
+        assert!(exporter
+            .export(temp_dir.path(), &["missing".to_string()])
+            .is_err());
+    }
+}
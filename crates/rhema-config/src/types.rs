@@ -14,10 +14,11 @@
  * limitations under the License.
  */
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Configuration environment types
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 pub enum ConfigEnvironment {
     Development,
     Testing,
@@ -27,7 +28,7 @@ pub enum ConfigEnvironment {
 }
 
 /// Configuration audit log
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ConfigAuditLog {
     pub entries: Vec<ConfigAuditEntry>,
 }
@@ -41,7 +42,7 @@ impl ConfigAuditLog {
 }
 
 /// Configuration audit entry
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ConfigAuditEntry {
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub action: String,
@@ -50,7 +51,7 @@ pub struct ConfigAuditEntry {
 }
 
 /// Configuration health status
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ConfigHealth {
     pub status: ConfigHealthStatus,
     pub last_check: chrono::DateTime<chrono::Utc>,
@@ -58,7 +59,7 @@ pub struct ConfigHealth {
 }
 
 /// Configuration health status
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub enum ConfigHealthStatus {
     Healthy,
     Warning,
@@ -67,7 +68,7 @@ pub enum ConfigHealthStatus {
 }
 
 /// Configuration statistics
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ConfigStats {
     pub total_configs: usize,
     pub valid_configs: usize,
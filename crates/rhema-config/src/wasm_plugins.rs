@@ -0,0 +1,438 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! User-defined context validators compiled to WebAssembly.
+//!
+//! A plugin is a core WASM module with no imports that exports:
+//!
+//! - `memory`
+//! - `rhema_alloc(len: i32) -> i32`, returning a buffer for the input
+//! - `rhema_validate(ptr: i32, len: i32) -> i64`, returning the output
+//!   location packed as `(ptr << 32) | len`
+//!
+//! The input is a JSON [`PluginInput`] and the output a JSON array of
+//! [`PluginIssue`]. Each call runs in a fresh store with a fuel budget and a
+//! memory cap, so a plugin cannot touch the host, keep state between files
+//! or run unbounded.
+
+use crate::comprehensive_validator::{ComprehensiveValidationIssue, ValidationCategory};
+use crate::schema_validator::SchemaType;
+use crate::{ConfigError, ConfigIssueSeverity};
+use rhema_core::RhemaResult;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use tracing::{debug, warn};
+use wasmtime::{Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+/// Context file types plugins run against when `applies_to` is empty
+const CONTEXT_TYPES: [&str; 5] = ["knowledge", "todos", "decisions", "patterns", "conventions"];
+
+fn default_fuel() -> u64 {
+    50_000_000
+}
+
+fn default_max_memory_bytes() -> usize {
+    32 * 1024 * 1024
+}
+
+fn default_max_output_bytes() -> usize {
+    1024 * 1024
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Configuration for one validation plugin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WasmValidatorConfig {
+    pub name: String,
+    /// Path to the `.wasm` module, relative to the plugins file
+    pub path: PathBuf,
+    /// Schema types to run on (`todos`, `knowledge`, ...); empty means all context files
+    #[serde(default)]
+    pub applies_to: Vec<String>,
+    /// Instruction budget per file
+    #[serde(default = "default_fuel")]
+    pub fuel: u64,
+    /// Linear memory cap
+    #[serde(default = "default_max_memory_bytes")]
+    pub max_memory_bytes: usize,
+    /// Largest issue list accepted from the plugin
+    #[serde(default = "default_max_output_bytes")]
+    pub max_output_bytes: usize,
+    /// Report plugin failures as warnings instead of errors
+    #[serde(default)]
+    pub fail_open: bool,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+impl WasmValidatorConfig {
+    pub fn new(name: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        Self {
+            name: name.into(),
+            path: path.into(),
+            applies_to: Vec::new(),
+            fuel: default_fuel(),
+            max_memory_bytes: default_max_memory_bytes(),
+            max_output_bytes: default_max_output_bytes(),
+            fail_open: false,
+            enabled: true,
+        }
+    }
+
+    fn applies_to(&self, schema_type: &SchemaType) -> bool {
+        let name = schema_type.as_str();
+        if self.applies_to.is_empty() {
+            CONTEXT_TYPES.contains(&name)
+        } else {
+            self.applies_to.iter().any(|t| t == name)
+        }
+    }
+}
+
+/// Plugins file, conventionally `.rhema/validators.yaml`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WasmValidatorsConfig {
+    #[serde(default)]
+    pub plugins: Vec<WasmValidatorConfig>,
+}
+
+/// What a plugin receives for each file
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginInput<'a> {
+    pub schema_type: &'a str,
+    pub file: String,
+    /// Entries of the file's main list (`todos`, `entries`, `decisions`, ...)
+    pub entries: &'a [Value],
+    /// The whole parsed document
+    pub document: &'a Value,
+}
+
+/// An issue reported by a plugin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginIssue {
+    /// `critical`, `error`, `warning` or `info`
+    pub severity: String,
+    #[serde(default)]
+    pub path: String,
+    pub message: String,
+    #[serde(default)]
+    pub code: Option<String>,
+}
+
+impl PluginIssue {
+    fn severity(&self) -> ConfigIssueSeverity {
+        match self.severity.to_ascii_lowercase().as_str() {
+            "critical" => ConfigIssueSeverity::Critical,
+            "error" => ConfigIssueSeverity::Error,
+            "info" => ConfigIssueSeverity::Info,
+            _ => ConfigIssueSeverity::Warning,
+        }
+    }
+}
+
+struct LoadedPlugin {
+    config: WasmValidatorConfig,
+    module: Module,
+}
+
+struct PluginState {
+    limits: StoreLimits,
+}
+
+/// Compiled validation plugins sharing one engine
+pub struct WasmValidatorHost {
+    engine: Engine,
+    plugins: Vec<LoadedPlugin>,
+}
+
+impl WasmValidatorHost {
+    pub fn new() -> RhemaResult<Self> {
+        let mut config = wasmtime::Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config)
+            .map_err(|e| ConfigError::ValidationError(format!("WASM engine error: {}", e)))?;
+        Ok(Self {
+            engine,
+            plugins: Vec::new(),
+        })
+    }
+
+    /// Load the plugins listed in a YAML file. Plugin paths are relative to it.
+    pub fn from_config_file<P: AsRef<Path>>(path: P) -> RhemaResult<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        let config: WasmValidatorsConfig = serde_yaml::from_str(&content)?;
+        let base = path.parent().unwrap_or_else(|| Path::new("."));
+
+        let mut host = Self::new()?;
+        for mut plugin in config.plugins.into_iter().filter(|p| p.enabled) {
+            if plugin.path.is_relative() {
+                plugin.path = base.join(&plugin.path);
+            }
+            let bytes = std::fs::read(&plugin.path)?;
+            host.add_plugin(plugin, &bytes)?;
+        }
+        Ok(host)
+    }
+
+    /// Compile and register a plugin from module bytes (binary or text format)
+    pub fn add_plugin(&mut self, config: WasmValidatorConfig, bytes: &[u8]) -> RhemaResult<()> {
+        let module = Module::new(&self.engine, bytes).map_err(|e| {
+            ConfigError::ValidationError(format!("Invalid plugin '{}': {}", config.name, e))
+        })?;
+        if module.imports().next().is_some() {
+            return Err(ConfigError::ValidationError(format!(
+                "Plugin '{}' must not import host functions",
+                config.name
+            ))
+            .into());
+        }
+        debug!("Loaded validation plugin {}", config.name);
+        self.plugins.push(LoadedPlugin { config, module });
+        Ok(())
+    }
+
+    pub fn plugin_names(&self) -> Vec<&str> {
+        self.plugins
+            .iter()
+            .map(|p| p.config.name.as_str())
+            .collect()
+    }
+
+    /// Run every applicable plugin on a parsed file. A plugin that traps,
+    /// runs out of fuel or returns malformed output yields one issue instead
+    /// of failing the whole validation.
+    pub fn validate(
+        &self,
+        document: &Value,
+        schema_type: &SchemaType,
+        file: &Path,
+    ) -> Vec<ComprehensiveValidationIssue> {
+        let entries = entries_of(document, schema_type);
+        let input = PluginInput {
+            schema_type: schema_type.as_str(),
+            file: file.display().to_string(),
+            entries,
+            document,
+        };
+        let input = match serde_json::to_vec(&input) {
+            Ok(input) => input,
+            Err(e) => {
+                warn!("Could not encode plugin input: {}", e);
+                return Vec::new();
+            }
+        };
+
+        let mut issues = Vec::new();
+        for plugin in self
+            .plugins
+            .iter()
+            .filter(|p| p.config.applies_to(schema_type))
+        {
+            match self.run(plugin, &input) {
+                Ok(found) => {
+                    issues.extend(found.into_iter().map(|issue| ComprehensiveValidationIssue {
+                        severity: issue.severity(),
+                        category: ValidationCategory::Custom,
+                        path: issue.path,
+                        message: issue.message,
+                        code: issue.code.unwrap_or_else(|| "PLUGIN_ISSUE".to_string()),
+                        details: Some(serde_json::json!({ "plugin": plugin.config.name })),
+                        auto_fixable: false,
+                        suggested_fix: None,
+                    }))
+                }
+                Err(e) => {
+                    warn!("Validation plugin {} failed: {}", plugin.config.name, e);
+                    issues.push(ComprehensiveValidationIssue {
+                        severity: if plugin.config.fail_open {
+                            ConfigIssueSeverity::Warning
+                        } else {
+                            ConfigIssueSeverity::Error
+                        },
+                        category: ValidationCategory::Custom,
+                        path: String::new(),
+                        message: format!(
+                            "Validation plugin '{}' failed: {}",
+                            plugin.config.name, e
+                        ),
+                        code: "PLUGIN_FAILED".to_string(),
+                        details: Some(serde_json::json!({ "plugin": plugin.config.name })),
+                        auto_fixable: false,
+                        suggested_fix: None,
+                    });
+                }
+            }
+        }
+        issues
+    }
+
+    fn run(&self, plugin: &LoadedPlugin, input: &[u8]) -> Result<Vec<PluginIssue>, String> {
+        let config = &plugin.config;
+        let mut store = Store::new(
+            &self.engine,
+            PluginState {
+                limits: StoreLimitsBuilder::new()
+                    .memory_size(config.max_memory_bytes)
+                    .instances(1)
+                    .build(),
+            },
+        );
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(config.fuel).map_err(|e| e.to_string())?;
+
+        let linker = Linker::new(&self.engine);
+        let instance = linker
+            .instantiate(&mut store, &plugin.module)
+            .map_err(|e| e.to_string())?;
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or("module does not export `memory`")?;
+        let alloc = instance
+            .get_typed_func::<i32, i32>(&mut store, "rhema_alloc")
+            .map_err(|e| e.to_string())?;
+        let validate = instance
+            .get_typed_func::<(i32, i32), i64>(&mut store, "rhema_validate")
+            .map_err(|e| e.to_string())?;
+
+        let len = i32::try_from(input.len()).map_err(|_| "input too large".to_string())?;
+        let ptr = alloc.call(&mut store, len).map_err(trap_message)?;
+        memory
+            .write(&mut store, ptr as u32 as usize, input)
+            .map_err(|e| e.to_string())?;
+
+        let packed = validate
+            .call(&mut store, (ptr, len))
+            .map_err(trap_message)? as u64;
+        let (out_ptr, out_len) = ((packed >> 32) as usize, (packed & 0xffff_ffff) as usize);
+        if out_len > config.max_output_bytes {
+            return Err(format!(
+                "output of {} bytes exceeds the {} byte limit",
+                out_len, config.max_output_bytes
+            ));
+        }
+        let mut output = vec![0u8; out_len];
+        memory
+            .read(&store, out_ptr, &mut output)
+            .map_err(|e| e.to_string())?;
+
+        serde_json::from_slice(&output).map_err(|e| format!("malformed output: {}", e))
+    }
+}
+
+fn trap_message(error: wasmtime::Error) -> String {
+    match error.downcast_ref::<wasmtime::Trap>() {
+        Some(wasmtime::Trap::OutOfFuel) => "exceeded its fuel budget".to_string(),
+        Some(trap) => trap.to_string(),
+        None => error.to_string(),
+    }
+}
+
+/// The main entry list of a context file
+fn entries_of<'a>(document: &'a Value, schema_type: &SchemaType) -> &'a [Value] {
+    let key = match schema_type {
+        SchemaType::Knowledge => "entries",
+        other => other.as_str(),
+    };
+    document
+        .get(key)
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or(&[])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// A plugin that ignores its input and always reports `issues`
+    fn constant_plugin(issues: &str) -> String {
+        format!(
+            r#"(module
+                (memory (export "memory") 1)
+                (data (i32.const 0) "{}")
+                (func (export "rhema_alloc") (param i32) (result i32) (i32.const 4096))
+                (func (export "rhema_validate") (param i32 i32) (result i64) (i64.const {})))"#,
+            issues.replace('"', "\\\""),
+            issues.len()
+        )
+    }
+
+    #[test]
+    fn test_plugin_issues_are_reported() {
+        let mut host = WasmValidatorHost::new().unwrap();
+        let output = r#"[{"severity":"error","path":"todos[0].title","message":"Title must start with a ticket id","code":"ORG001"}]"#;
+        host.add_plugin(
+            WasmValidatorConfig::new("naming", "naming.wasm"),
+            constant_plugin(output).as_bytes(),
+        )
+        .unwrap();
+
+        let document = json!({"todos": [{"id": "1", "title": "fix it"}]});
+        let issues = host.validate(&document, &SchemaType::Todos, Path::new("todos.yaml"));
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, "ORG001");
+        assert_eq!(issues[0].severity, ConfigIssueSeverity::Error);
+        assert_eq!(issues[0].category, ValidationCategory::Custom);
+
+        // Only context files by default
+        assert!(host
+            .validate(&document, &SchemaType::Lock, Path::new("rhema.lock"))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_runaway_plugin_is_stopped() {
+        let mut host = WasmValidatorHost::new().unwrap();
+        let looping = r#"(module
+            (memory (export "memory") 1)
+            (func (export "rhema_alloc") (param i32) (result i32) (i32.const 0))
+            (func (export "rhema_validate") (param i32 i32) (result i64)
+                (loop $spin (br $spin))
+                (i64.const 0)))"#;
+        let mut config = WasmValidatorConfig::new("spin", "spin.wasm");
+        config.fuel = 10_000;
+        config.fail_open = true;
+        host.add_plugin(config, looping.as_bytes()).unwrap();
+
+        let issues = host.validate(
+            &json!({"entries": []}),
+            &SchemaType::Knowledge,
+            Path::new("knowledge.yaml"),
+        );
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].code, "PLUGIN_FAILED");
+        assert_eq!(issues[0].severity, ConfigIssueSeverity::Warning);
+        assert!(issues[0].message.contains("fuel"));
+    }
+
+    #[test]
+    fn test_plugins_cannot_import_host_functions() {
+        let mut host = WasmValidatorHost::new().unwrap();
+        let importing = r#"(module (import "wasi_snapshot_preview1" "fd_write" (func (param i32 i32 i32 i32) (result i32))))"#;
+        assert!(host
+            .add_plugin(
+                WasmValidatorConfig::new("io", "io.wasm"),
+                importing.as_bytes()
+            )
+            .is_err());
+    }
+}
@@ -14,11 +14,16 @@
  * limitations under the License.
  */
 
+use super::task_scoring::Task;
+use crate::distributed::locking::{DistributedLockBackend, InMemoryLockBackend, LockLease};
 use chrono::{DateTime, Utc};
+use rhema_core::events::{EventOutbox, ExportEvent, ExportEventType};
 use rhema_core::RhemaResult;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -57,6 +62,8 @@ pub enum ConflictSeverity {
 /// Conflict status
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ConflictStatus {
+    /// Expected from planned work; nothing has been edited yet
+    Predicted,
     Detected,
     UnderReview,
     Resolving,
@@ -181,6 +188,164 @@ pub struct ConflictDetails {
     pub custom: Option<serde_json::Value>,
 }
 
+/// Work an agent has been assigned but not started yet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlannedWork {
+    /// Agent the work is assigned to
+    pub agent_id: String,
+    /// Task or intent the work comes from
+    pub work_id: String,
+    /// Work description
+    pub description: String,
+    /// Scopes the work touches
+    pub scopes: Vec<String>,
+    /// Files or directories the work is expected to edit
+    pub paths: Vec<PathBuf>,
+    /// Planning timestamp
+    pub planned_at: DateTime<Utc>,
+}
+
+impl PlannedWork {
+    pub fn new(
+        agent_id: impl Into<String>,
+        work_id: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        Self {
+            agent_id: agent_id.into(),
+            work_id: work_id.into(),
+            description: description.into(),
+            scopes: Vec::new(),
+            paths: Vec::new(),
+            planned_at: Utc::now(),
+        }
+    }
+
+    pub fn with_scope(mut self, scope: impl Into<String>) -> Self {
+        self.scopes.push(scope.into());
+        self
+    }
+
+    pub fn with_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.paths.push(path.into());
+        self
+    }
+
+    /// Work for a task assigned to an agent. Files the task expects to edit
+    /// are read from a `files` list in the task metadata
+    pub fn from_task(task: &Task) -> Option<Self> {
+        let agent_id = task.assigned_to.as_ref()?;
+        let mut work = Self::new(agent_id, &task.id, &task.title);
+        if !task.scope.is_empty() {
+            work = work.with_scope(&task.scope);
+        }
+        if let Some(files) = task.metadata.get("files").and_then(|v| v.as_array()) {
+            for file in files.iter().filter_map(|f| f.as_str()) {
+                work = work.with_path(file);
+            }
+        }
+        Some(work)
+    }
+}
+
+/// Suggested way to avoid a predicted conflict
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ConflictAvoidance {
+    /// Start one work item only after the other has finished
+    Serialize { first: String, then: String },
+    /// Give the shared paths and scopes to one agent and let the other work
+    /// on the rest
+    SplitScope {
+        owner: String,
+        shared_paths: Vec<PathBuf>,
+        shared_scopes: Vec<String>,
+    },
+}
+
+/// Conflict expected between two planned work items
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PredictedConflict {
+    /// Stable ID derived from the two work items
+    pub id: String,
+    /// Work planned first
+    pub first_work: String,
+    /// Work planned later
+    pub second_work: String,
+    /// Agents of the two work items
+    pub agents: Vec<String>,
+    /// Paths both work items expect to edit, at the narrower of each pair
+    pub overlapping_paths: Vec<PathBuf>,
+    /// Scopes both work items touch
+    pub overlapping_scopes: Vec<String>,
+    /// Error when both edit the same file, warning otherwise
+    pub severity: ConflictSeverity,
+    /// Ways to avoid the conflict, preferred first
+    pub suggestions: Vec<ConflictAvoidance>,
+    /// Prediction timestamp
+    pub predicted_at: DateTime<Utc>,
+}
+
+impl PredictedConflict {
+    pub fn description(&self) -> String {
+        let mut overlap: Vec<String> = self
+            .overlapping_paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect();
+        overlap.extend(
+            self.overlapping_scopes
+                .iter()
+                .map(|s| format!("scope {}", s)),
+        );
+        format!(
+            "{} ({}) and {} ({}) both plan to change {}",
+            self.agents[0],
+            self.first_work,
+            self.agents[1],
+            self.second_work,
+            overlap.join(", ")
+        )
+    }
+
+    fn to_conflict(&self) -> Conflict {
+        let conflict_type = if self.overlapping_paths.is_empty() {
+            ConflictType::Custom("scope_overlap".to_string())
+        } else {
+            ConflictType::FileModification
+        };
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "work_ids".to_string(),
+            serde_json::json!([self.first_work, self.second_work]),
+        );
+        Conflict {
+            id: self.id.clone(),
+            conflict_type,
+            severity: self.severity.clone(),
+            status: ConflictStatus::Predicted,
+            description: self.description(),
+            involved_agents: self.agents.clone(),
+            affected_scope: self
+                .overlapping_scopes
+                .first()
+                .cloned()
+                .unwrap_or_else(|| "file-system".to_string()),
+            detected_at: self.predicted_at,
+            resolved_at: None,
+            resolution_strategy: None,
+            resolution_notes: None,
+            details: ConflictDetails {
+                file_modification: None,
+                dependency: None,
+                resource: None,
+                custom: serde_json::to_value(self).ok(),
+            },
+            metadata,
+        }
+    }
+}
+
 /// Conflict resolution result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConflictResolution {
@@ -308,6 +473,14 @@ pub struct ConflictPreventionSystem {
     detection_patterns: Vec<DetectionPattern>,
     /// System configuration
     config: ConflictPreventionConfig,
+    /// Backend issuing file write leases
+    lock_backend: Arc<dyn DistributedLockBackend>,
+    /// Leases currently held through this system
+    file_leases: HashMap<PathBuf, LockLease>,
+    /// Work assigned to agents but not finished, in planning order
+    planned_work: Vec<PlannedWork>,
+    /// Outbox receiving a `conflict_detected` event per new conflict
+    event_outbox: Option<EventOutbox>,
 }
 
 /// File access information
@@ -510,6 +683,10 @@ impl ConflictPreventionSystem {
                 enable_dependency_tracking: true,
                 enable_resource_tracking: true,
             },
+            lock_backend: Arc::new(InMemoryLockBackend::new()),
+            file_leases: HashMap::new(),
+            planned_work: Vec::new(),
+            event_outbox: None,
         }
     }
 
@@ -524,9 +701,30 @@ impl ConflictPreventionSystem {
             resource_tracking: HashMap::new(),
             detection_patterns: Vec::new(),
             config,
+            lock_backend: Arc::new(InMemoryLockBackend::new()),
+            file_leases: HashMap::new(),
+            planned_work: Vec::new(),
+            event_outbox: None,
         }
     }
 
+    /// Share file leases with other daemons through `backend`
+    pub fn with_lock_backend(mut self, backend: Arc<dyn DistributedLockBackend>) -> Self {
+        self.lock_backend = backend;
+        self
+    }
+
+    /// Export every newly detected or predicted conflict through `outbox`
+    pub fn with_event_outbox(mut self, outbox: EventOutbox) -> Self {
+        self.event_outbox = Some(outbox);
+        self
+    }
+
+    /// Backend used for file leases
+    pub fn lock_backend(&self) -> Arc<dyn DistributedLockBackend> {
+        self.lock_backend.clone()
+    }
+
     /// Detect conflicts automatically
     pub async fn detect_conflicts(&mut self) -> RhemaResult<Vec<Conflict>> {
         let mut detected_conflicts = Vec::new();
@@ -555,6 +753,9 @@ impl ConflictPreventionSystem {
 
         // Add detected conflicts to system
         for conflict in &detected_conflicts {
+            if !self.conflicts.contains_key(&conflict.id) {
+                self.export_conflict(conflict);
+            }
             self.conflicts.insert(conflict.id.clone(), conflict.clone());
         }
 
@@ -1245,6 +1446,152 @@ impl ConflictPreventionSystem {
         });
     }
 
+    /// Acquire an exclusive write lease on a file for `agent`
+    pub async fn acquire_file_lease(
+        &mut self,
+        file_path: PathBuf,
+        agent: &str,
+        ttl: Duration,
+    ) -> RhemaResult<LockLease> {
+        let key = file_path.to_string_lossy().to_string();
+        match self.lock_backend.acquire(&key, agent, ttl).await? {
+            Some(lease) => {
+                if let Some(info) = self.file_access_tracking.get_mut(&file_path) {
+                    info.owner_agent = Some(agent.to_string());
+                }
+                self.file_leases.insert(file_path, lease.clone());
+                Ok(lease)
+            }
+            None => Err(ConflictPreventionError::FileAccessDenied(format!(
+                "{} is leased by another agent",
+                file_path.display()
+            ))
+            .into()),
+        }
+    }
+
+    /// Extend a lease held through this system
+    pub async fn renew_file_lease(
+        &mut self,
+        file_path: &Path,
+        ttl: Duration,
+    ) -> RhemaResult<LockLease> {
+        let lease = self.file_leases.get(file_path).ok_or_else(|| {
+            ConflictPreventionError::FileAccessDenied(format!(
+                "No lease held on {}",
+                file_path.display()
+            ))
+        })?;
+
+        match self.lock_backend.renew(lease, ttl).await? {
+            Some(renewed) => {
+                self.file_leases
+                    .insert(file_path.to_path_buf(), renewed.clone());
+                Ok(renewed)
+            }
+            None => {
+                self.file_leases.remove(file_path);
+                Err(ConflictPreventionError::FileAccessDenied(format!(
+                    "Lease on {} was lost",
+                    file_path.display()
+                ))
+                .into())
+            }
+        }
+    }
+
+    /// Release a lease, returning whether it was still held
+    pub async fn release_file_lease(&mut self, file_path: &Path) -> RhemaResult<bool> {
+        match self.file_leases.remove(file_path) {
+            Some(lease) => self.lock_backend.release(&lease).await,
+            None => Ok(false),
+        }
+    }
+
+    fn export_conflict(&self, conflict: &Conflict) {
+        let Some(outbox) = &self.event_outbox else {
+            return;
+        };
+        let data = serde_json::to_value(conflict).unwrap_or_default();
+        let event = ExportEvent::new(
+            ExportEventType::ConflictDetected,
+            "rhema-coordination",
+            data,
+        )
+        .with_subject(conflict.id.clone());
+        if let Err(e) = outbox.publish(&event) {
+            tracing::warn!("Failed to export conflict {}: {}", conflict.id, e);
+        }
+    }
+
+    /// Lease held on a file through this system
+    pub fn file_lease(&self, file_path: &Path) -> Option<&LockLease> {
+        self.file_leases.get(file_path)
+    }
+
+    /// Register work an agent is about to start and predict conflicts with
+    /// work other agents have planned, before anything is edited. Each
+    /// prediction is also recorded as a conflict in the `Predicted` state
+    pub fn plan_work(&mut self, work: PlannedWork) -> Vec<PredictedConflict> {
+        self.planned_work
+            .retain(|planned| planned.work_id != work.work_id);
+        let predictions: Vec<PredictedConflict> = self
+            .planned_work
+            .iter()
+            .filter(|planned| planned.agent_id != work.agent_id)
+            .filter_map(|planned| predict_conflict(planned, &work))
+            .collect();
+        for prediction in &predictions {
+            let conflict = prediction.to_conflict();
+            if !self.conflicts.contains_key(&conflict.id) {
+                self.export_conflict(&conflict);
+            }
+            self.conflicts.insert(conflict.id.clone(), conflict);
+        }
+        self.planned_work.push(work);
+        predictions
+    }
+
+    /// Conflicts predicted between all planned work
+    pub fn predict_conflicts(&self) -> Vec<PredictedConflict> {
+        let mut predictions = Vec::new();
+        for (i, earlier) in self.planned_work.iter().enumerate() {
+            for later in &self.planned_work[i + 1..] {
+                if earlier.agent_id != later.agent_id {
+                    predictions.extend(predict_conflict(earlier, later));
+                }
+            }
+        }
+        predictions
+    }
+
+    /// Forget finished or abandoned work and resolve the conflicts predicted
+    /// for it. Returns whether the work was planned
+    pub fn complete_work(&mut self, work_id: &str) -> bool {
+        let planned = self.planned_work.len();
+        self.planned_work
+            .retain(|planned| planned.work_id != work_id);
+
+        for conflict in self.conflicts.values_mut() {
+            let involved = conflict
+                .metadata
+                .get("work_ids")
+                .and_then(|ids| ids.as_array())
+                .is_some_and(|ids| ids.iter().any(|id| id.as_str() == Some(work_id)));
+            if conflict.status == ConflictStatus::Predicted && involved {
+                conflict.status = ConflictStatus::Resolved;
+                conflict.resolved_at = Some(Utc::now());
+                conflict.resolution_notes = Some(format!("Work {} finished", work_id));
+            }
+        }
+        self.planned_work.len() < planned
+    }
+
+    /// Work planned and not yet finished
+    pub fn planned_work(&self) -> &[PlannedWork] {
+        &self.planned_work
+    }
+
     /// Get active conflicts
     pub fn get_active_conflicts(&self) -> Vec<&Conflict> {
         self.conflicts
@@ -1278,6 +1625,77 @@ impl ConflictPreventionSystem {
     }
 }
 
+/// Whether one path contains the other
+fn paths_overlap(a: &Path, b: &Path) -> bool {
+    let empty = Path::new("");
+    a != empty && b != empty && (a.starts_with(b) || b.starts_with(a))
+}
+
+fn predict_conflict(earlier: &PlannedWork, later: &PlannedWork) -> Option<PredictedConflict> {
+    let mut overlapping_paths = Vec::new();
+    let mut same_file = false;
+    for a in &earlier.paths {
+        for b in later.paths.iter().filter(|b| paths_overlap(a, b)) {
+            same_file |= a == b;
+            let narrower = if a.starts_with(b) { a } else { b };
+            if !overlapping_paths.contains(narrower) {
+                overlapping_paths.push(narrower.clone());
+            }
+        }
+    }
+    let overlapping_scopes: Vec<String> = earlier
+        .scopes
+        .iter()
+        .filter(|a| {
+            later
+                .scopes
+                .iter()
+                .any(|b| paths_overlap(Path::new(a.as_str()), Path::new(b)))
+        })
+        .cloned()
+        .collect();
+    if overlapping_paths.is_empty() && overlapping_scopes.is_empty() {
+        return None;
+    }
+    overlapping_paths.sort();
+
+    let mut suggestions = vec![ConflictAvoidance::Serialize {
+        first: earlier.work_id.clone(),
+        then: later.work_id.clone(),
+    }];
+    // Splitting only helps when both sides have work outside the overlap
+    let has_other_work = |work: &PlannedWork| {
+        work.paths.is_empty()
+            || work
+                .paths
+                .iter()
+                .any(|p| !overlapping_paths.iter().any(|o| paths_overlap(p, o)))
+    };
+    if has_other_work(earlier) && has_other_work(later) {
+        suggestions.push(ConflictAvoidance::SplitScope {
+            owner: earlier.agent_id.clone(),
+            shared_paths: overlapping_paths.clone(),
+            shared_scopes: overlapping_scopes.clone(),
+        });
+    }
+
+    Some(PredictedConflict {
+        id: format!("predicted-{}-{}", earlier.work_id, later.work_id),
+        first_work: earlier.work_id.clone(),
+        second_work: later.work_id.clone(),
+        agents: vec![earlier.agent_id.clone(), later.agent_id.clone()],
+        overlapping_paths,
+        overlapping_scopes,
+        severity: if same_file {
+            ConflictSeverity::Error
+        } else {
+            ConflictSeverity::Warning
+        },
+        suggestions,
+        predicted_at: Utc::now(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1371,4 +1789,79 @@ mod tests {
 
         assert!(system.add_prevention_rule(rule).is_ok());
     }
+
+    #[tokio::test]
+    async fn test_file_leases_are_shared_through_backend() {
+        let backend: Arc<dyn DistributedLockBackend> = Arc::new(InMemoryLockBackend::new());
+        let mut daemon_a = ConflictPreventionSystem::new().with_lock_backend(backend.clone());
+        let mut daemon_b = ConflictPreventionSystem::new().with_lock_backend(backend);
+        let file = PathBuf::from("services/api/.rhema/todos.yaml");
+        let ttl = Duration::from_secs(30);
+
+        let lease = daemon_a
+            .acquire_file_lease(file.clone(), "agent-a", ttl)
+            .await
+            .unwrap();
+        assert!(daemon_b
+            .acquire_file_lease(file.clone(), "agent-b", ttl)
+            .await
+            .is_err());
+
+        assert!(daemon_a.release_file_lease(&file).await.unwrap());
+        let next = daemon_b
+            .acquire_file_lease(file.clone(), "agent-b", ttl)
+            .await
+            .unwrap();
+        assert!(next.fencing_token > lease.fencing_token);
+    }
+
+    #[test]
+    fn test_predicts_conflicts_from_planned_work() {
+        let mut system = ConflictPreventionSystem::new();
+        let first = PlannedWork::new("agent-a", "task-1", "Refactor the API client")
+            .with_scope("services/api")
+            .with_path("services/api/src/client.rs")
+            .with_path("services/api/src/retry.rs")
+            .with_path("services/api/Cargo.toml");
+        assert!(system.plan_work(first).is_empty());
+
+        let second = PlannedWork::new("agent-b", "task-2", "Add request tracing")
+            .with_path("services/api/src")
+            .with_path("services/web/src/trace.ts");
+        let predictions = system.plan_work(second);
+        assert_eq!(predictions.len(), 1);
+        let prediction = &predictions[0];
+        assert_eq!(prediction.severity, ConflictSeverity::Warning);
+        assert_eq!(prediction.overlapping_paths.len(), 2);
+        assert_eq!(
+            prediction.suggestions[0],
+            ConflictAvoidance::Serialize {
+                first: "task-1".to_string(),
+                then: "task-2".to_string(),
+            }
+        );
+        assert!(matches!(
+            prediction.suggestions[1],
+            ConflictAvoidance::SplitScope { .. }
+        ));
+        assert_eq!(
+            system.get_conflict(&prediction.id).unwrap().status,
+            ConflictStatus::Predicted
+        );
+
+        // Work conflicts with other agents' work, never with the same agent's
+        let own = PlannedWork::new("agent-a", "task-3", "Fix retries")
+            .with_path("services/api/src/retry.rs");
+        let predictions = system.plan_work(own);
+        assert_eq!(predictions.len(), 1);
+        assert_eq!(predictions[0].first_work, "task-2");
+        assert_eq!(system.predict_conflicts().len(), 2);
+
+        assert!(system.complete_work("task-2"));
+        assert_eq!(
+            system.get_conflict(&prediction.id).unwrap().status,
+            ConflictStatus::Resolved
+        );
+        assert_eq!(system.predict_conflicts().len(), 0);
+    }
 }
@@ -0,0 +1,470 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Agent groups for targeting messages and session invitations.
+//!
+//! A group has static members, a dynamic selector over capabilities, roles
+//! (agent types) and scopes, or both. Targets are expanded against the
+//! registered agents at send time, so agents that register later are picked
+//! up without redefining the group.
+
+use super::real_time_coordination::{AgentInfo, AgentStatus};
+use chrono::{DateTime, Utc};
+use rhema_core::{RhemaError, RhemaResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// Dynamic membership rule. Every non-empty criterion must match.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GroupSelector {
+    /// Capabilities the agent must all have
+    pub capabilities: Vec<String>,
+    /// Agent types (roles), any of which matches
+    pub roles: Vec<String>,
+    /// Assigned scopes, any of which matches
+    pub scopes: Vec<String>,
+    /// Skip agents that are offline or failed
+    pub online_only: bool,
+}
+
+impl GroupSelector {
+    /// Whether the selector has no criteria and would match every agent
+    pub fn is_empty(&self) -> bool {
+        self.capabilities.is_empty() && self.roles.is_empty() && self.scopes.is_empty()
+    }
+
+    pub fn matches(&self, agent: &AgentInfo) -> bool {
+        if self.online_only && !is_reachable(agent) {
+            return false;
+        }
+        self.capabilities
+            .iter()
+            .all(|c| agent.capabilities.contains(c))
+            && (self.roles.is_empty() || self.roles.contains(&agent.agent_type))
+            && (self.scopes.is_empty() || self.scopes.contains(&agent.assigned_scope))
+    }
+}
+
+fn is_reachable(agent: &AgentInfo) -> bool {
+    agent.is_online && !matches!(agent.status, AgentStatus::Offline | AgentStatus::Failed)
+}
+
+/// Messages allowed to a group within a sliding window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GroupRateLimit {
+    pub max_messages: usize,
+    pub per_seconds: u64,
+}
+
+/// A named group of agents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AgentGroup {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Agents that are always members while registered
+    #[serde(default)]
+    pub members: Vec<String>,
+    /// Agents matching this selector are members as well
+    #[serde(default)]
+    pub selector: Option<GroupSelector>,
+    #[serde(default)]
+    pub rate_limit: Option<GroupRateLimit>,
+}
+
+impl AgentGroup {
+    /// A group with a fixed member list
+    pub fn with_members(name: impl Into<String>, members: Vec<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            members,
+            selector: None,
+            rate_limit: None,
+        }
+    }
+
+    /// A group whose membership is computed from a selector
+    pub fn with_selector(name: impl Into<String>, selector: GroupSelector) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            members: Vec::new(),
+            selector: Some(selector),
+            rate_limit: None,
+        }
+    }
+
+    pub fn with_rate_limit(mut self, max_messages: usize, per_seconds: u64) -> Self {
+        self.rate_limit = Some(GroupRateLimit {
+            max_messages,
+            per_seconds,
+        });
+        self
+    }
+
+    fn includes(&self, agent: &AgentInfo) -> bool {
+        self.members.contains(&agent.id)
+            || self
+                .selector
+                .as_ref()
+                .is_some_and(|s| !s.is_empty() && s.matches(agent))
+    }
+}
+
+/// Static group definitions, usually loaded from YAML
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AgentGroupsConfig {
+    #[serde(default)]
+    pub groups: Vec<AgentGroup>,
+}
+
+impl AgentGroupsConfig {
+    pub fn load(path: &Path) -> RhemaResult<Self> {
+        let content = std::fs::read_to_string(path)?;
+        serde_yaml::from_str(&content).map_err(|e| RhemaError::InvalidYaml {
+            file: path.display().to_string(),
+            message: e.to_string(),
+        })
+    }
+}
+
+/// Who a group message or session invitation is addressed to
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum GroupTarget {
+    /// A defined group by name
+    Group(String),
+    /// Online agents with this capability
+    Capability(String),
+    /// Online agents of this type
+    Role(String),
+}
+
+impl GroupTarget {
+    /// Key used for delivery statistics and rate limiting
+    pub fn key(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for GroupTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GroupTarget::Group(name) => write!(f, "group:{}", name),
+            GroupTarget::Capability(capability) => write!(f, "capability:{}", capability),
+            GroupTarget::Role(role) => write!(f, "role:{}", role),
+        }
+    }
+}
+
+impl FromStr for GroupTarget {
+    type Err = RhemaError;
+
+    /// Parses `group:NAME`, `capability:NAME` or `role:NAME`; a bare name is a group
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, value) = s.split_once([':', '=']).unwrap_or(("group", s));
+        let value = value.trim();
+        if value.is_empty() {
+            return Err(RhemaError::InvalidInput(format!(
+                "Group target '{}' has no name",
+                s
+            )));
+        }
+        match kind.trim() {
+            "group" => Ok(GroupTarget::Group(value.to_string())),
+            "capability" => Ok(GroupTarget::Capability(value.to_string())),
+            "role" => Ok(GroupTarget::Role(value.to_string())),
+            other => Err(RhemaError::InvalidInput(format!(
+                "Unknown group target kind '{}'; use group, capability or role",
+                other
+            ))),
+        }
+    }
+}
+
+/// Delivery statistics for one group target
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GroupDeliveryStats {
+    /// Group messages sent
+    pub messages_sent: u64,
+    /// Recipients the messages expanded to, summed over messages
+    pub recipients_targeted: u64,
+    pub deliveries_succeeded: u64,
+    pub deliveries_failed: u64,
+    /// Sends refused by the group's rate limit
+    pub rate_limited: u64,
+    /// Sends that expanded to no agents
+    pub empty_expansions: u64,
+    pub last_sent_at: Option<DateTime<Utc>>,
+}
+
+/// Result of sending to a group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupSendReport {
+    pub target: GroupTarget,
+    pub message_id: String,
+    /// Agents the target expanded to at send time
+    pub recipients: Vec<String>,
+    pub delivered: usize,
+    pub failed: usize,
+}
+
+/// Group definitions, delivery statistics and rate-limit windows
+#[derive(Debug, Default)]
+pub struct GroupRegistry {
+    groups: HashMap<String, AgentGroup>,
+    stats: HashMap<String, GroupDeliveryStats>,
+    windows: HashMap<String, VecDeque<Instant>>,
+}
+
+impl GroupRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or replace a group
+    pub fn define(&mut self, group: AgentGroup) -> RhemaResult<()> {
+        if group.name.trim().is_empty() {
+            return Err(RhemaError::InvalidInput(
+                "Group name cannot be empty".to_string(),
+            ));
+        }
+        if let Some(limit) = &group.rate_limit {
+            if limit.max_messages == 0 || limit.per_seconds == 0 {
+                return Err(RhemaError::InvalidInput(format!(
+                    "Group '{}' rate limit must allow at least one message per second window",
+                    group.name
+                )));
+            }
+        }
+        self.windows
+            .remove(&GroupTarget::Group(group.name.clone()).key());
+        self.groups.insert(group.name.clone(), group);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<AgentGroup> {
+        self.windows
+            .remove(&GroupTarget::Group(name.to_string()).key());
+        self.groups.remove(name)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&AgentGroup> {
+        self.groups.get(name)
+    }
+
+    pub fn groups(&self) -> Vec<&AgentGroup> {
+        let mut groups: Vec<&AgentGroup> = self.groups.values().collect();
+        groups.sort_by(|a, b| a.name.cmp(&b.name));
+        groups
+    }
+
+    /// Expand a target to agent IDs, sorted and de-duplicated. Static members
+    /// must still be registered; ad-hoc capability and role targets only
+    /// include reachable agents.
+    pub fn expand<'a>(
+        &self,
+        target: &GroupTarget,
+        agents: impl IntoIterator<Item = &'a AgentInfo>,
+    ) -> RhemaResult<Vec<String>> {
+        let members: BTreeSet<String> = match target {
+            GroupTarget::Group(name) => {
+                let group = self.groups.get(name).ok_or_else(|| {
+                    RhemaError::NotFound(format!("Agent group '{}' is not defined", name))
+                })?;
+                agents
+                    .into_iter()
+                    .filter(|agent| group.includes(agent))
+                    .map(|agent| agent.id.clone())
+                    .collect()
+            }
+            GroupTarget::Capability(capability) => agents
+                .into_iter()
+                .filter(|agent| is_reachable(agent) && agent.capabilities.contains(capability))
+                .map(|agent| agent.id.clone())
+                .collect(),
+            GroupTarget::Role(role) => agents
+                .into_iter()
+                .filter(|agent| is_reachable(agent) && &agent.agent_type == role)
+                .map(|agent| agent.id.clone())
+                .collect(),
+        };
+        Ok(members.into_iter().collect())
+    }
+
+    /// Take a slot in the target's rate-limit window, if it has one
+    pub fn check_rate(&mut self, target: &GroupTarget, now: Instant) -> RhemaResult<()> {
+        let GroupTarget::Group(name) = target else {
+            return Ok(());
+        };
+        let Some(limit) = self.groups.get(name).and_then(|g| g.rate_limit.clone()) else {
+            return Ok(());
+        };
+
+        let key = target.key();
+        let window = Duration::from_secs(limit.per_seconds);
+        let sent = self.windows.entry(key.clone()).or_default();
+        while sent
+            .front()
+            .is_some_and(|at| now.duration_since(*at) >= window)
+        {
+            sent.pop_front();
+        }
+        if sent.len() >= limit.max_messages {
+            self.stats.entry(key).or_default().rate_limited += 1;
+            return Err(RhemaError::RateLimitError(format!(
+                "Group '{}' allows {} messages per {}s",
+                name, limit.max_messages, limit.per_seconds
+            )));
+        }
+        sent.push_back(now);
+        Ok(())
+    }
+
+    pub fn record_empty(&mut self, target: &GroupTarget) {
+        self.stats.entry(target.key()).or_default().empty_expansions += 1;
+    }
+
+    pub fn record_delivery(&mut self, report: &GroupSendReport) {
+        let stats = self.stats.entry(report.target.key()).or_default();
+        stats.messages_sent += 1;
+        stats.recipients_targeted += report.recipients.len() as u64;
+        stats.deliveries_succeeded += report.delivered as u64;
+        stats.deliveries_failed += report.failed as u64;
+        stats.last_sent_at = Some(Utc::now());
+    }
+
+    pub fn stats(&self, target: &GroupTarget) -> Option<&GroupDeliveryStats> {
+        self.stats.get(&target.key())
+    }
+
+    /// Statistics for every target that has been used, keyed by target
+    pub fn all_stats(&self) -> &HashMap<String, GroupDeliveryStats> {
+        &self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::real_time_coordination::AgentPerformanceMetrics;
+
+    fn agent(id: &str, agent_type: &str, capabilities: &[&str], online: bool) -> AgentInfo {
+        AgentInfo {
+            id: id.to_string(),
+            name: id.to_string(),
+            agent_type: agent_type.to_string(),
+            status: AgentStatus::Idle,
+            current_task_id: None,
+            assigned_scope: "core".to_string(),
+            capabilities: capabilities.iter().map(|c| c.to_string()).collect(),
+            last_heartbeat: Utc::now(),
+            is_online: online,
+            performance_metrics: AgentPerformanceMetrics {
+                tasks_completed: 0,
+                tasks_failed: 0,
+                avg_completion_time_seconds: 0.0,
+                success_rate: 1.0,
+                collaboration_score: 0.5,
+                avg_response_time_ms: 100.0,
+            },
+            liveness: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_expansion_combines_members_and_selector() {
+        let agents = vec![
+            agent("a", "reviewer", &["rust"], true),
+            agent("b", "reviewer", &["python"], true),
+            agent("c", "builder", &["rust"], false),
+            agent("d", "builder", &["go"], true),
+        ];
+        let mut registry = GroupRegistry::new();
+        registry
+            .define(AgentGroup {
+                members: vec!["d".to_string(), "ghost".to_string()],
+                ..AgentGroup::with_selector(
+                    "rustaceans",
+                    GroupSelector {
+                        capabilities: vec!["rust".to_string()],
+                        ..Default::default()
+                    },
+                )
+            })
+            .unwrap();
+
+        let group = GroupTarget::Group("rustaceans".to_string());
+        assert_eq!(
+            registry.expand(&group, &agents).unwrap(),
+            vec!["a", "c", "d"]
+        );
+
+        let rust = GroupTarget::Capability("rust".to_string());
+        assert_eq!(registry.expand(&rust, &agents).unwrap(), vec!["a"]);
+
+        let reviewers: GroupTarget = "role:reviewer".parse().unwrap();
+        assert_eq!(
+            registry.expand(&reviewers, &agents).unwrap(),
+            vec!["a", "b"]
+        );
+
+        assert!(registry
+            .expand(&GroupTarget::Group("missing".to_string()), &agents)
+            .is_err());
+    }
+
+    #[test]
+    fn test_group_rate_limit_window() {
+        let mut registry = GroupRegistry::new();
+        registry
+            .define(AgentGroup::with_members("ops", vec![]).with_rate_limit(2, 60))
+            .unwrap();
+        let target = GroupTarget::Group("ops".to_string());
+        let start = Instant::now();
+
+        assert!(registry.check_rate(&target, start).is_ok());
+        assert!(registry.check_rate(&target, start).is_ok());
+        assert!(matches!(
+            registry.check_rate(&target, start),
+            Err(RhemaError::RateLimitError(_))
+        ));
+        assert_eq!(registry.stats(&target).unwrap().rate_limited, 1);
+        assert!(registry
+            .check_rate(&target, start + Duration::from_secs(60))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_target_parsing() {
+        assert_eq!(
+            "capability=rust".parse::<GroupTarget>().unwrap(),
+            GroupTarget::Capability("rust".to_string())
+        );
+        assert_eq!(
+            "reviewers".parse::<GroupTarget>().unwrap(),
+            GroupTarget::Group("reviewers".to_string())
+        );
+        assert!("team:x".parse::<GroupTarget>().is_err());
+        assert!("role:".parse::<GroupTarget>().is_err());
+    }
+}
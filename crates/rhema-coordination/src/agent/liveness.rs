@@ -0,0 +1,371 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Adaptive heartbeats and phi-accrual failure detection.
+//!
+//! Rather than declaring an agent dead after a fixed timeout, the monitor
+//! keeps a window of each agent's heartbeat inter-arrival times and computes
+//! the suspicion level `phi` from how overdue the next heartbeat is relative
+//! to that history. Each agent also negotiates its own heartbeat interval:
+//! steady, busy agents heartbeat less often, jittery agents more often.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+/// Heartbeat negotiation and failure detection settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LivenessConfig {
+    /// Shortest heartbeat interval negotiated with an agent
+    pub min_heartbeat_interval_ms: u64,
+    /// Longest heartbeat interval negotiated with an agent
+    pub max_heartbeat_interval_ms: u64,
+    /// Heartbeat intervals remembered per agent
+    pub window_size: usize,
+    /// Heartbeats needed before intervals are negotiated and phi, rather
+    /// than the fixed agent timeout, decides that an agent failed
+    pub min_samples: usize,
+    /// Lower bound on the interval standard deviation, so perfectly regular
+    /// heartbeats do not make the detector hair-triggered
+    pub min_std_deviation_ms: u64,
+    /// Lateness tolerated on top of the mean interval, such as GC pauses
+    pub acceptable_pause_ms: u64,
+    /// Suspicion level at which an agent is considered failed
+    pub phi_threshold: f64,
+    /// Agents whose liveness confidence is below this are not given work
+    pub min_routing_confidence: f64,
+}
+
+impl Default for LivenessConfig {
+    fn default() -> Self {
+        Self {
+            min_heartbeat_interval_ms: 1_000,
+            max_heartbeat_interval_ms: 60_000,
+            window_size: 100,
+            min_samples: 5,
+            min_std_deviation_ms: 500,
+            acceptable_pause_ms: 3_000,
+            phi_threshold: 8.0,
+            min_routing_confidence: 0.5,
+        }
+    }
+}
+
+/// Liveness of one agent as last assessed by the coordinator
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AgentLiveness {
+    /// Confidence (0.0-1.0) that the agent is alive: 1.0 right after a
+    /// heartbeat, 0.0 once phi reaches the failure threshold
+    pub confidence: f64,
+    /// Current suspicion level
+    pub phi: f64,
+    /// Heartbeat interval negotiated with the agent
+    pub heartbeat_interval_ms: u64,
+    /// Standard deviation of the agent's heartbeat intervals
+    pub jitter_ms: f64,
+}
+
+impl Default for AgentLiveness {
+    fn default() -> Self {
+        Self {
+            confidence: 1.0,
+            phi: 0.0,
+            heartbeat_interval_ms: 30_000,
+            jitter_ms: 0.0,
+        }
+    }
+}
+
+/// Phi-accrual failure detector over a sliding window of heartbeat
+/// intervals (Hayashibara et al.), using the logistic approximation of the
+/// normal distribution
+#[derive(Debug, Clone)]
+pub struct PhiAccrualDetector {
+    intervals: VecDeque<f64>,
+    window_size: usize,
+    /// Interval assumed until heartbeats have been observed
+    bootstrap_ms: f64,
+}
+
+impl PhiAccrualDetector {
+    pub fn new(window_size: usize, bootstrap_interval: Duration) -> Self {
+        Self {
+            intervals: VecDeque::with_capacity(window_size.min(1024)),
+            window_size: window_size.max(1),
+            bootstrap_ms: bootstrap_interval.as_secs_f64() * 1000.0,
+        }
+    }
+
+    /// Record the time between two consecutive heartbeats
+    pub fn record(&mut self, interval: Duration) {
+        if self.intervals.len() == self.window_size {
+            self.intervals.pop_front();
+        }
+        self.intervals.push_back(interval.as_secs_f64() * 1000.0);
+    }
+
+    /// Intervals observed so far, up to the window size
+    pub fn samples(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn mean_ms(&self) -> f64 {
+        if self.intervals.is_empty() {
+            return self.bootstrap_ms;
+        }
+        self.intervals.iter().sum::<f64>() / self.intervals.len() as f64
+    }
+
+    pub fn std_deviation_ms(&self) -> f64 {
+        if self.intervals.len() < 2 {
+            return self.mean_ms() / 4.0;
+        }
+        let mean = self.mean_ms();
+        let variance = self
+            .intervals
+            .iter()
+            .map(|interval| (interval - mean).powi(2))
+            .sum::<f64>()
+            / self.intervals.len() as f64;
+        variance.sqrt()
+    }
+
+    /// Suspicion level `elapsed` after the last heartbeat: the probability
+    /// that a live agent's heartbeat is this late is `10^-phi`
+    pub fn phi(&self, elapsed: Duration, min_std_deviation_ms: f64, pause_ms: f64) -> f64 {
+        let elapsed_ms = elapsed.as_secs_f64() * 1000.0;
+        let mean = self.mean_ms() + pause_ms;
+        let std_deviation = self.std_deviation_ms().max(min_std_deviation_ms).max(1.0);
+
+        let y = (elapsed_ms - mean) / std_deviation;
+        let e = (-y * (1.5976 + 0.070566 * y * y)).exp();
+        let phi = if elapsed_ms > mean {
+            -(e / (1.0 + e)).log10()
+        } else {
+            -(1.0 - 1.0 / (1.0 + e)).log10()
+        };
+        // exp() overflows to infinity for very early or very late heartbeats
+        if phi.is_finite() {
+            phi.max(0.0)
+        } else if elapsed_ms > mean {
+            f64::MAX
+        } else {
+            0.0
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TrackedAgent {
+    detector: PhiAccrualDetector,
+    interval: Duration,
+}
+
+/// Heartbeat history and negotiated intervals of every registered agent
+#[derive(Debug, Clone)]
+pub struct LivenessMonitor {
+    config: LivenessConfig,
+    /// Interval agents start with, the coordinator's heartbeat interval
+    base_interval: Duration,
+    agents: HashMap<String, TrackedAgent>,
+}
+
+impl LivenessMonitor {
+    pub fn new(config: LivenessConfig, base_interval: Duration) -> Self {
+        Self {
+            config,
+            base_interval,
+            agents: HashMap::new(),
+        }
+    }
+
+    pub fn config(&self) -> &LivenessConfig {
+        &self.config
+    }
+
+    /// Start tracking an agent, forgetting any earlier history
+    pub fn register(&mut self, agent_id: &str) -> AgentLiveness {
+        let tracked = TrackedAgent {
+            detector: PhiAccrualDetector::new(self.config.window_size, self.base_interval),
+            interval: self.base_interval,
+        };
+        let liveness = self.assess_tracked(&tracked, Duration::ZERO);
+        self.agents.insert(agent_id.to_string(), tracked);
+        liveness
+    }
+
+    pub fn remove(&mut self, agent_id: &str) {
+        self.agents.remove(agent_id);
+    }
+
+    /// Record a heartbeat that arrived `since_last` after the previous one
+    /// and renegotiate the agent's interval. `workload` is the share of the
+    /// agent's capacity in use, from 0.0 to 1.0.
+    pub fn heartbeat(
+        &mut self,
+        agent_id: &str,
+        since_last: Duration,
+        workload: f64,
+    ) -> AgentLiveness {
+        if !self.agents.contains_key(agent_id) {
+            self.register(agent_id);
+        }
+        let config = self.config.clone();
+        let base_interval = self.base_interval;
+        let tracked = self
+            .agents
+            .get_mut(agent_id)
+            .expect("agent was just registered");
+
+        tracked.detector.record(since_last);
+        tracked.interval = if tracked.detector.samples() < config.min_samples {
+            base_interval
+        } else {
+            negotiate_interval(&config, &tracked.detector, workload)
+        };
+
+        let tracked = tracked.clone();
+        self.assess_tracked(&tracked, Duration::ZERO)
+    }
+
+    /// Liveness of an agent whose last heartbeat was `elapsed` ago
+    pub fn assess(&self, agent_id: &str, elapsed: Duration) -> AgentLiveness {
+        match self.agents.get(agent_id) {
+            Some(tracked) => self.assess_tracked(tracked, elapsed),
+            None => AgentLiveness::default(),
+        }
+    }
+
+    /// Whether phi says the agent failed. Agents with too few heartbeats
+    /// for a reliable estimate are left to the fixed agent timeout.
+    pub fn is_failed(&self, agent_id: &str, elapsed: Duration) -> bool {
+        self.agents.get(agent_id).is_some_and(|tracked| {
+            tracked.detector.samples() >= self.config.min_samples
+                && self.phi(&tracked.detector, elapsed) >= self.config.phi_threshold
+        })
+    }
+
+    fn phi(&self, detector: &PhiAccrualDetector, elapsed: Duration) -> f64 {
+        detector.phi(
+            elapsed,
+            self.config.min_std_deviation_ms as f64,
+            self.config.acceptable_pause_ms as f64,
+        )
+    }
+
+    fn assess_tracked(&self, tracked: &TrackedAgent, elapsed: Duration) -> AgentLiveness {
+        let phi = self.phi(&tracked.detector, elapsed);
+        let confidence = if self.config.phi_threshold > 0.0 {
+            (1.0 - phi / self.config.phi_threshold).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        AgentLiveness {
+            confidence,
+            phi,
+            heartbeat_interval_ms: tracked.interval.as_millis() as u64,
+            jitter_ms: if tracked.detector.samples() < 2 {
+                0.0
+            } else {
+                tracked.detector.std_deviation_ms()
+            },
+        }
+    }
+}
+
+/// Interval between the configured bounds: steady heartbeats stretch it,
+/// jitter shrinks it so the detector gets more samples, and busy agents are
+/// asked to heartbeat less often so heartbeats do not compete with work
+fn negotiate_interval(
+    config: &LivenessConfig,
+    detector: &PhiAccrualDetector,
+    workload: f64,
+) -> Duration {
+    let min = config.min_heartbeat_interval_ms as f64;
+    let max = config
+        .max_heartbeat_interval_ms
+        .max(config.min_heartbeat_interval_ms) as f64;
+    let mean = detector.mean_ms().max(1.0);
+    let stability = (1.0 - detector.std_deviation_ms() / mean).clamp(0.0, 1.0);
+    let workload = workload.clamp(0.0, 1.0);
+
+    let interval = min + (max - min) * stability * (0.5 + 0.5 * workload);
+    Duration::from_millis(interval.round() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor() -> LivenessMonitor {
+        LivenessMonitor::new(LivenessConfig::default(), Duration::from_secs(10))
+    }
+
+    #[test]
+    fn test_phi_grows_with_lateness() {
+        let mut detector = PhiAccrualDetector::new(10, Duration::from_secs(1));
+        for interval in [950, 1000, 1050, 1000, 980, 1020] {
+            detector.record(Duration::from_millis(interval));
+        }
+
+        let on_time = detector.phi(Duration::from_millis(500), 100.0, 0.0);
+        let late = detector.phi(Duration::from_millis(1500), 100.0, 0.0);
+        let very_late = detector.phi(Duration::from_secs(5), 100.0, 0.0);
+        assert!(on_time < 0.1, "phi {}", on_time);
+        assert!(late > on_time && very_late > late);
+        assert_eq!(very_late, f64::MAX);
+    }
+
+    #[test]
+    fn test_steady_agents_negotiate_longer_intervals_than_jittery_ones() {
+        let mut monitor = monitor();
+        monitor.register("steady");
+        monitor.register("jittery");
+
+        let mut steady = AgentLiveness::default();
+        let mut jittery = AgentLiveness::default();
+        for i in 0..10 {
+            steady = monitor.heartbeat("steady", Duration::from_millis(2_000), 0.0);
+            let interval = if i % 2 == 0 { 500 } else { 3_500 };
+            jittery = monitor.heartbeat("jittery", Duration::from_millis(interval), 0.0);
+        }
+        assert!(steady.heartbeat_interval_ms > jittery.heartbeat_interval_ms);
+        assert!(jittery.jitter_ms > steady.jitter_ms);
+
+        let busy = monitor.heartbeat("steady", Duration::from_millis(2_000), 1.0);
+        assert!(busy.heartbeat_interval_ms > steady.heartbeat_interval_ms);
+    }
+
+    #[test]
+    fn test_failure_needs_history_and_lateness() {
+        let mut monitor = monitor();
+        monitor.register("agent");
+        assert!(!monitor.is_failed("agent", Duration::from_secs(600)));
+
+        for _ in 0..5 {
+            monitor.heartbeat("agent", Duration::from_secs(1), 0.0);
+        }
+        assert!(!monitor.is_failed("agent", Duration::from_secs(1)));
+        assert!(monitor.assess("agent", Duration::from_secs(1)).confidence > 0.9);
+        assert!(monitor.is_failed("agent", Duration::from_secs(30)));
+        assert_eq!(
+            monitor.assess("agent", Duration::from_secs(30)).confidence,
+            0.0
+        );
+    }
+}
@@ -19,11 +19,14 @@ pub mod conflict_analysis;
 pub mod conflict_prevention;
 pub mod constraint_system;
 pub mod coordination;
+pub mod groups;
+pub mod liveness;
 pub mod lock_context;
 pub mod lock_context_integration;
 pub mod ml_conflict_prediction;
 pub mod patterns;
 pub mod real_time_coordination;
+pub mod session_summary;
 pub mod state;
 pub mod task_scoring;
 
@@ -38,9 +41,17 @@ pub use conflict_analysis::{
     LearningInsights, PerformanceMetrics, PredictionStatistics, Recommendation, ReportData,
     ReportType, ResolutionStatistics, TrendAnalysis,
 };
-pub use conflict_prevention::{ConflictPreventionSystem, ConflictType, ResolutionStrategy};
+pub use conflict_prevention::{
+    ConflictAvoidance, ConflictPreventionSystem, ConflictType, PlannedWork, PredictedConflict,
+    ResolutionStrategy,
+};
 pub use constraint_system::{Constraint, ConstraintSystem, ConstraintViolation};
 pub use coordination::{SyncCoordinator, SyncError, SyncStatus};
+pub use groups::{
+    AgentGroup, AgentGroupsConfig, GroupDeliveryStats, GroupRateLimit, GroupRegistry,
+    GroupSelector, GroupSendReport, GroupTarget,
+};
+pub use liveness::{AgentLiveness, LivenessConfig, LivenessMonitor, PhiAccrualDetector};
 pub use lock_context::{LockFileAIContext, LockFileContextProvider};
 pub use lock_context_integration::LockFileAIIntegration;
 pub use ml_conflict_prediction::{
@@ -53,5 +64,6 @@ pub use patterns::{
     PatternResult,
 };
 pub use real_time_coordination::{AgentMessage, AgentStatus, RealTimeCoordinationSystem};
+pub use session_summary::{SessionSummary, SessionSummaryWriter, WrittenSummary};
 pub use state::{AgentManager, AgentState, StateTransition};
 pub use task_scoring::{TaskScore, TaskScoringFactors, TaskScoringSystem};
@@ -18,10 +18,17 @@
 // TODO: Integrate with Syneidesis gRPC library for enhanced performance and production readiness
 // Current implementation provides the foundation for gRPC service integration
 
+use super::groups::{
+    AgentGroup, AgentGroupsConfig, GroupDeliveryStats, GroupRegistry, GroupSendReport, GroupTarget,
+};
+use super::liveness::{AgentLiveness, LivenessConfig, LivenessMonitor};
+use super::session_summary::{SessionSummary, SessionSummaryWriter, WrittenSummary};
+use crate::chaos::{ChaosConfig, ChaosInjector};
 use chrono::{DateTime, Utc};
-use rhema_core::RhemaResult;
+use rhema_core::{RhemaError, RhemaResult};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use thiserror::Error;
 use tokio::sync::{broadcast, mpsc, RwLock};
@@ -40,6 +47,17 @@ pub enum AgentStatus {
     Failed,
 }
 
+impl AgentStatus {
+    /// Share of the agent's capacity in use, for heartbeat negotiation
+    pub fn workload(&self) -> f64 {
+        match self {
+            AgentStatus::Busy | AgentStatus::Working => 1.0,
+            AgentStatus::Blocked | AgentStatus::Collaborating => 0.5,
+            AgentStatus::Idle | AgentStatus::Offline | AgentStatus::Failed => 0.0,
+        }
+    }
+}
+
 /// Message types for agent communication
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum MessageType {
@@ -137,6 +155,9 @@ pub struct AgentInfo {
     pub is_online: bool,
     /// Performance metrics
     pub performance_metrics: AgentPerformanceMetrics,
+    /// Liveness confidence and negotiated heartbeat interval
+    #[serde(default)]
+    pub liveness: AgentLiveness,
 }
 
 /// Agent performance metrics
@@ -1181,6 +1202,25 @@ pub struct RealTimeCoordinationSystem {
     performance_monitor: Option<Arc<PerformanceMonitor>>,
     /// Consensus manager
     consensus_manager: Option<Arc<RwLock<ConsensusManager>>>,
+    /// Fault injector, present only in chaos mode
+    chaos: Option<Arc<ChaosInjector>>,
+    /// Agent groups, their delivery statistics and rate limits
+    groups: Arc<RwLock<GroupRegistry>>,
+    /// Writer for summaries of completed sessions, if enabled
+    session_summaries: Option<Arc<SessionSummaryWriter>>,
+    /// Scope (`.rhema` directory) each session's summary is written to
+    session_scopes: Arc<RwLock<HashMap<String, PathBuf>>>,
+    /// Heartbeat history and negotiated intervals of each agent
+    liveness: Arc<RwLock<LivenessMonitor>>,
+}
+
+/// Outcome of a single delivery attempt
+enum DeliveryAttempt {
+    Delivered,
+    /// Lost to an injected fault; worth retrying
+    Dropped,
+    /// The recipient has no open channel
+    Failed,
 }
 
 /// Coordination system configuration
@@ -1239,12 +1279,18 @@ impl RealTimeCoordinationSystem {
             encryption: None,
             performance_monitor: None,
             consensus_manager: None,
+            chaos: None,
+            groups: Arc::new(RwLock::new(GroupRegistry::new())),
+            session_summaries: None,
+            session_scopes: Arc::new(RwLock::new(HashMap::new())),
+            liveness: liveness_monitor(&CoordinationConfig::default()),
         }
     }
 
     /// Create a new coordination system with custom configuration
     pub fn with_config(config: CoordinationConfig) -> Self {
         let (broadcast_tx, _) = broadcast::channel(1000);
+        let liveness = liveness_monitor(&config);
 
         Self {
             agents: Arc::new(RwLock::new(HashMap::new())),
@@ -1270,6 +1316,11 @@ impl RealTimeCoordinationSystem {
             encryption: None,
             performance_monitor: None,
             consensus_manager: None,
+            chaos: None,
+            groups: Arc::new(RwLock::new(GroupRegistry::new())),
+            session_summaries: None,
+            session_scopes: Arc::new(RwLock::new(HashMap::new())),
+            liveness,
         }
     }
 
@@ -1279,6 +1330,7 @@ impl RealTimeCoordinationSystem {
         advanced_config: AdvancedCoordinationConfig,
     ) -> Self {
         let (broadcast_tx, _) = broadcast::channel(1000);
+        let liveness = liveness_monitor(&config);
 
         // Initialize advanced features based on configuration
         let load_balancer = if advanced_config.enable_load_balancing {
@@ -1338,15 +1390,56 @@ impl RealTimeCoordinationSystem {
             } else {
                 None
             },
+            chaos: None,
+            groups: Arc::new(RwLock::new(GroupRegistry::new())),
+            session_summaries: None,
+            session_scopes: Arc::new(RwLock::new(HashMap::new())),
+            liveness,
         }
     }
 
+    /// Enable chaos mode, injecting faults as described by `config`
+    pub fn enable_chaos(&mut self, config: ChaosConfig) -> RhemaResult<Arc<ChaosInjector>> {
+        let injector = Arc::new(ChaosInjector::new(config)?);
+        self.chaos = Some(Arc::clone(&injector));
+        Ok(injector)
+    }
+
+    /// Fault injector, if chaos mode is enabled
+    pub fn chaos(&self) -> Option<Arc<ChaosInjector>> {
+        self.chaos.clone()
+    }
+
+    /// Replace the heartbeat negotiation and failure detection settings.
+    /// Heartbeat history recorded so far is discarded.
+    pub fn configure_liveness(&mut self, config: LivenessConfig) {
+        self.liveness = Arc::new(RwLock::new(LivenessMonitor::new(
+            config,
+            std::time::Duration::from_secs(self.config.heartbeat_interval_seconds),
+        )));
+    }
+
+    /// Write a summary of every session into scope context when it completes
+    pub fn enable_session_summaries(&mut self, writer: SessionSummaryWriter) {
+        self.session_summaries = Some(Arc::new(writer));
+    }
+
+    /// Write the summary of a session into the scope whose `.rhema`
+    /// directory is `scope_path` instead of the writer's default scope
+    pub async fn set_session_scope(&self, session_id: &str, scope_path: PathBuf) {
+        self.session_scopes
+            .write()
+            .await
+            .insert(session_id.to_string(), scope_path);
+    }
+
     /// Register an agent
-    pub async fn register_agent(&self, agent_info: AgentInfo) -> RhemaResult<()> {
+    pub async fn register_agent(&self, mut agent_info: AgentInfo) -> RhemaResult<()> {
         let (tx, _rx) = mpsc::channel(100);
 
         {
             let mut agents = self.agents.write().await;
+            agent_info.liveness = self.liveness.write().await.register(&agent_info.id);
             agents.insert(agent_info.id.clone(), agent_info.clone());
         }
 
@@ -1386,6 +1479,7 @@ impl RealTimeCoordinationSystem {
         {
             let mut agents = self.agents.write().await;
             agents.remove(agent_id);
+            self.liveness.write().await.remove(agent_id);
         }
 
         {
@@ -1424,23 +1518,7 @@ impl RealTimeCoordinationSystem {
 
         // Send to specific recipients
         if !message.recipient_ids.is_empty() {
-            let channels = self.message_channels.read().await;
-            let mut delivered = 0;
-
-            for recipient_id in &message.recipient_ids {
-                if let Some(tx) = channels.get(recipient_id) {
-                    if let Ok(()) = tx.send(message.clone()).await {
-                        delivered += 1;
-                    }
-                }
-            }
-
-            // Update delivery stats
-            {
-                let mut stats = self.stats.lock().unwrap();
-                stats.messages_delivered += delivered;
-                stats.messages_failed += message.recipient_ids.len() - delivered;
-            }
+            self.deliver_to_recipients(&message).await;
         } else {
             // Broadcast message
             let _ = self.broadcast_tx.send(message.clone());
@@ -1455,6 +1533,95 @@ impl RealTimeCoordinationSystem {
         Ok(())
     }
 
+    /// Deliver a message to each of its recipients, returning how many succeeded
+    async fn deliver_to_recipients(&self, message: &AgentMessage) -> usize {
+        let mut delivered = 0;
+
+        for recipient_id in &message.recipient_ids {
+            if self.deliver(message, recipient_id).await {
+                delivered += 1;
+            }
+        }
+
+        // Update delivery stats
+        {
+            let mut stats = self.stats.lock().unwrap();
+            stats.messages_delivered += delivered;
+            stats.messages_failed += message.recipient_ids.len() - delivered;
+        }
+
+        delivered
+    }
+
+    /// Deliver a message to one recipient. In chaos mode with fault tolerance
+    /// enabled, dropped attempts are retried and the outcome is reported to
+    /// the recipient's circuit breaker; otherwise delivery is a single send.
+    async fn deliver(&self, message: &AgentMessage, recipient_id: &str) -> bool {
+        let fault_tolerance = self
+            .chaos
+            .as_ref()
+            .and(self.advanced_config.as_ref())
+            .filter(|config| config.enable_fault_tolerance)
+            .map(|config| config.fault_tolerance_config.clone());
+
+        let Some(fault_tolerance) = fault_tolerance else {
+            return matches!(
+                self.try_deliver(message, recipient_id).await,
+                DeliveryAttempt::Delivered
+            );
+        };
+
+        if !self.can_agent_execute(recipient_id).await {
+            return false;
+        }
+
+        let mut attempts = 0;
+        let delivered = loop {
+            attempts += 1;
+            match self.try_deliver(message, recipient_id).await {
+                DeliveryAttempt::Delivered => break true,
+                DeliveryAttempt::Dropped if attempts <= fault_tolerance.max_retry_attempts => {
+                    tokio::time::sleep(std::time::Duration::from_millis(
+                        fault_tolerance.retry_delay_ms,
+                    ))
+                    .await;
+                }
+                _ => break false,
+            }
+        };
+
+        if delivered {
+            self.report_agent_success(recipient_id).await;
+        } else {
+            self.report_agent_failure(recipient_id).await;
+        }
+        delivered
+    }
+
+    async fn try_deliver(&self, message: &AgentMessage, recipient_id: &str) -> DeliveryAttempt {
+        if let Some(chaos) = &self.chaos {
+            if let Some(delay) = chaos.message_delay(message, recipient_id) {
+                tokio::time::sleep(delay).await;
+            }
+            if !chaos.link_allowed(&message.sender_id, recipient_id)
+                || chaos.drop_message(message, recipient_id)
+            {
+                return DeliveryAttempt::Dropped;
+            }
+        }
+
+        let tx = self
+            .message_channels
+            .read()
+            .await
+            .get(recipient_id)
+            .cloned();
+        match tx {
+            Some(tx) if tx.send(message.clone()).await.is_ok() => DeliveryAttempt::Delivered,
+            _ => DeliveryAttempt::Failed,
+        }
+    }
+
     /// Broadcast a message to all agents
     pub async fn broadcast_message(&self, message: AgentMessage) -> RhemaResult<()> {
         let broadcast_message = AgentMessage {
@@ -1551,28 +1718,107 @@ impl RealTimeCoordinationSystem {
         }
     }
 
-    /// Leave a coordination session
+    /// Leave a coordination session, completing it when the last participant
+    /// leaves
     pub async fn leave_session(&self, session_id: &str, agent_id: &str) -> RhemaResult<()> {
-        let mut sessions = self.sessions.write().await;
+        let completed = {
+            let mut sessions = self.sessions.write().await;
+            let session = sessions
+                .get_mut(session_id)
+                .ok_or_else(|| CoordinationError::SessionNotFound(session_id.to_string()))?;
 
-        if let Some(session) = sessions.get_mut(session_id) {
             session.participants.retain(|id| id != agent_id);
+            if session.participants.is_empty() && session.status == SessionStatus::Active {
+                Some(self.complete(session))
+            } else {
+                None
+            }
+        };
 
-            if session.participants.is_empty() {
-                session.status = SessionStatus::Completed;
-                session.ended_at = Some(Utc::now());
+        // The agent has left either way; a failed summary must not undo that
+        if let Some(session) = completed {
+            if let Err(e) = self.write_session_summary(&session).await {
+                error!("Failed to write summary of session {}: {}", session.id, e);
+            }
+        }
 
-                // Update stats
-                {
-                    let mut stats = self.stats.lock().unwrap();
-                    stats.active_sessions = stats.active_sessions.saturating_sub(1);
-                }
+        Ok(())
+    }
+
+    /// End an active session for all participants, returning the entries its
+    /// summary was written to if session summaries are enabled
+    pub async fn end_session(&self, session_id: &str) -> RhemaResult<Option<WrittenSummary>> {
+        let session = {
+            let mut sessions = self.sessions.write().await;
+            let session = sessions
+                .get_mut(session_id)
+                .ok_or_else(|| CoordinationError::SessionNotFound(session_id.to_string()))?;
+            if session.status != SessionStatus::Active {
+                return Err(CoordinationError::SessionNotFound(
+                    "Session is not active".to_string(),
+                )
+                .into());
             }
+            self.complete(session)
+        };
 
-            Ok(())
-        } else {
-            Err(CoordinationError::SessionNotFound(session_id.to_string()).into())
+        self.write_session_summary(&session).await
+    }
+
+    /// Record a decision reached in an active session
+    pub async fn record_session_decision(
+        &self,
+        session_id: &str,
+        decision: SessionDecision,
+    ) -> RhemaResult<()> {
+        let mut sessions = self.sessions.write().await;
+        let session = sessions
+            .get_mut(session_id)
+            .ok_or_else(|| CoordinationError::SessionNotFound(session_id.to_string()))?;
+        if session.status != SessionStatus::Active {
+            return Err(
+                CoordinationError::SessionNotFound("Session is not active".to_string()).into(),
+            );
+        }
+
+        session.decisions.push(decision);
+        Ok(())
+    }
+
+    /// Mark a session completed, returning a snapshot of it
+    fn complete(&self, session: &mut CoordinationSession) -> CoordinationSession {
+        session.status = SessionStatus::Completed;
+        session.ended_at = Some(Utc::now());
+
+        // Update stats
+        {
+            let mut stats = self.stats.lock().unwrap();
+            stats.active_sessions = stats.active_sessions.saturating_sub(1);
         }
+
+        session.clone()
+    }
+
+    async fn write_session_summary(
+        &self,
+        session: &CoordinationSession,
+    ) -> RhemaResult<Option<WrittenSummary>> {
+        let Some(writer) = &self.session_summaries else {
+            return Ok(None);
+        };
+
+        let summary = SessionSummary::from_session(session);
+        let scope_path = self.session_scopes.write().await.remove(&session.id);
+        let written = match scope_path {
+            Some(scope_path) => writer.write_to(&scope_path, &summary)?,
+            None => writer.write(&summary)?,
+        };
+        info!(
+            "Wrote summary of session {} to {}",
+            session.id,
+            written.scope_path.display()
+        );
+        Ok(Some(written))
     }
 
     /// Send message to a session
@@ -1581,9 +1827,12 @@ impl RealTimeCoordinationSystem {
         session_id: &str,
         message: AgentMessage,
     ) -> RhemaResult<()> {
-        let sessions = self.sessions.read().await;
+        let session_message = {
+            let mut sessions = self.sessions.write().await;
+            let session = sessions
+                .get_mut(session_id)
+                .ok_or_else(|| CoordinationError::SessionNotFound(session_id.to_string()))?;
 
-        if let Some(session) = sessions.get(session_id) {
             if session.status != SessionStatus::Active {
                 return Err(CoordinationError::SessionNotFound(
                     "Session is not active".to_string(),
@@ -1591,16 +1840,171 @@ impl RealTimeCoordinationSystem {
                 .into());
             }
 
-            // Send to all session participants
+            // Send to all session participants, keeping the message in the
+            // session transcript for its summary
             let session_message = AgentMessage {
                 recipient_ids: session.participants.clone(),
                 ..message
             };
+            session.messages.push(session_message.clone());
+            session_message
+        };
 
-            self.send_message(session_message).await
-        } else {
-            Err(CoordinationError::SessionNotFound(session_id.to_string()).into())
+        self.send_message(session_message).await
+    }
+
+    /// Define or replace an agent group
+    pub async fn define_group(&self, group: AgentGroup) -> RhemaResult<()> {
+        self.groups.write().await.define(group)
+    }
+
+    /// Remove an agent group, keeping its delivery statistics
+    pub async fn remove_group(&self, name: &str) -> Option<AgentGroup> {
+        self.groups.write().await.remove(name)
+    }
+
+    /// Load static group definitions from a YAML file, returning how many were defined
+    pub async fn load_groups(&self, path: &std::path::Path) -> RhemaResult<usize> {
+        let config = AgentGroupsConfig::load(path)?;
+        let count = config.groups.len();
+        let mut groups = self.groups.write().await;
+        for group in config.groups {
+            groups.define(group)?;
         }
+        Ok(count)
+    }
+
+    /// Expand a group target to the agents it currently covers
+    pub async fn resolve_group_target(&self, target: &GroupTarget) -> RhemaResult<Vec<String>> {
+        let agents = self.agents.read().await;
+        self.groups.read().await.expand(target, agents.values())
+    }
+
+    /// Send a message to every agent a group target expands to at send time.
+    /// The sender is never a recipient. A target that matches no agents is an
+    /// error rather than a broadcast.
+    pub async fn send_group_message(
+        &self,
+        target: &GroupTarget,
+        message: AgentMessage,
+    ) -> RhemaResult<GroupSendReport> {
+        self.validate_message(&message)?;
+
+        let mut recipients = self.resolve_group_target(target).await?;
+        recipients.retain(|id| id != &message.sender_id);
+        if recipients.is_empty() {
+            self.groups.write().await.record_empty(target);
+            return Err(
+                CoordinationError::AgentNotFound(format!("No agents match {}", target)).into(),
+            );
+        }
+
+        self.groups
+            .write()
+            .await
+            .check_rate(target, std::time::Instant::now())?;
+
+        let mut message = AgentMessage {
+            recipient_ids: recipients.clone(),
+            ..message
+        };
+        message
+            .metadata
+            .insert("group_target".to_string(), target.to_string());
+
+        {
+            let mut history = self.message_history.lock().unwrap();
+            history.push_back(message.clone());
+            if history.len() > self.config.max_message_history {
+                history.pop_front();
+            }
+        }
+        {
+            let mut stats = self.stats.lock().unwrap();
+            stats.total_messages += 1;
+        }
+
+        let delivered = self.deliver_to_recipients(&message).await;
+        let report = GroupSendReport {
+            target: target.clone(),
+            message_id: message.id.clone(),
+            failed: recipients.len() - delivered,
+            recipients,
+            delivered,
+        };
+        self.groups.write().await.record_delivery(&report);
+
+        Ok(report)
+    }
+
+    /// Create a session whose participants are the given agents plus every
+    /// agent the group targets expand to
+    pub async fn create_group_session(
+        &self,
+        topic: String,
+        participants: Vec<String>,
+        targets: &[GroupTarget],
+    ) -> RhemaResult<String> {
+        let mut invited = participants;
+        for target in targets {
+            for agent_id in self.resolve_group_target(target).await? {
+                if !invited.contains(&agent_id) {
+                    invited.push(agent_id);
+                }
+            }
+        }
+
+        if invited.is_empty() {
+            return Err(CoordinationError::AgentNotFound(
+                "Session invitation matched no agents".to_string(),
+            )
+            .into());
+        }
+
+        self.create_session(topic, invited).await
+    }
+
+    /// Invite every agent a group target expands to into an active session,
+    /// returning the agents that were added
+    pub async fn invite_group_to_session(
+        &self,
+        session_id: &str,
+        target: &GroupTarget,
+    ) -> RhemaResult<Vec<String>> {
+        let invited = self.resolve_group_target(target).await?;
+        let mut sessions = self.sessions.write().await;
+        let session = sessions
+            .get_mut(session_id)
+            .ok_or_else(|| CoordinationError::SessionNotFound(session_id.to_string()))?;
+
+        if session.status != SessionStatus::Active {
+            return Err(
+                CoordinationError::SessionNotFound("Session is not active".to_string()).into(),
+            );
+        }
+
+        let added: Vec<String> = invited
+            .into_iter()
+            .filter(|id| !session.participants.contains(id))
+            .collect();
+        if session.participants.len() + added.len() > self.config.max_session_participants {
+            return Err(
+                CoordinationError::PermissionDenied("Too many participants".to_string()).into(),
+            );
+        }
+
+        session.participants.extend(added.iter().cloned());
+        Ok(added)
+    }
+
+    /// Delivery statistics for one group target
+    pub async fn get_group_stats(&self, target: &GroupTarget) -> Option<GroupDeliveryStats> {
+        self.groups.read().await.stats(target).cloned()
+    }
+
+    /// Delivery statistics for every group target used so far
+    pub async fn get_all_group_stats(&self) -> HashMap<String, GroupDeliveryStats> {
+        self.groups.read().await.all_stats().clone()
     }
 
     /// Request a resource
@@ -1659,25 +2063,123 @@ impl RealTimeCoordinationSystem {
         agent_id: &str,
         status: AgentStatus,
     ) -> RhemaResult<()> {
+        if let Some(delay) = self
+            .chaos
+            .as_ref()
+            .and_then(|chaos| chaos.heartbeat_delay(agent_id))
+        {
+            if !self.agents.read().await.contains_key(agent_id) {
+                return Err(CoordinationError::AgentNotFound(agent_id.to_string()).into());
+            }
+            let agents = Arc::clone(&self.agents);
+            let liveness = Arc::clone(&self.liveness);
+            let agent_id = agent_id.to_string();
+            tokio::spawn(async move {
+                tokio::time::sleep(delay).await;
+                if let Some(agent) = agents.write().await.get_mut(&agent_id) {
+                    agent.status = status;
+                    record_heartbeat(agent, &mut *liveness.write().await);
+                }
+            });
+            return Ok(());
+        }
+
         let mut agents = self.agents.write().await;
 
         if let Some(agent) = agents.get_mut(agent_id) {
             agent.status = status;
-            agent.last_heartbeat = Utc::now();
+            record_heartbeat(agent, &mut *self.liveness.write().await);
             Ok(())
         } else {
             Err(CoordinationError::AgentNotFound(agent_id.to_string()).into())
         }
     }
 
+    /// Crash an agent: its channel is closed and it stops heartbeating, but it
+    /// stays registered until the heartbeat monitor expires it. Requires chaos
+    /// mode.
+    pub async fn crash_agent(&self, agent_id: &str) -> RhemaResult<()> {
+        let chaos = self.chaos.as_ref().ok_or_else(|| {
+            RhemaError::CoordinationError("Chaos mode is not enabled".to_string())
+        })?;
+
+        {
+            let mut agents = self.agents.write().await;
+            let agent = agents
+                .get_mut(agent_id)
+                .ok_or_else(|| CoordinationError::AgentNotFound(agent_id.to_string()))?;
+            agent.status = AgentStatus::Failed;
+            agent.is_online = false;
+        }
+        self.message_channels.write().await.remove(agent_id);
+        chaos.record_crash(agent_id);
+        Ok(())
+    }
+
+    /// Crash agents at random according to the chaos configuration,
+    /// returning the agents that crashed
+    pub async fn chaos_tick(&self) -> Vec<String> {
+        let Some(chaos) = &self.chaos else {
+            return Vec::new();
+        };
+
+        let mut agent_ids: Vec<String> = self
+            .agents
+            .read()
+            .await
+            .values()
+            .filter(|agent| agent.is_online)
+            .map(|agent| agent.id.clone())
+            .collect();
+        // Sort so the same seed picks the same agents
+        agent_ids.sort();
+
+        let mut crashed = Vec::new();
+        for agent_id in agent_ids {
+            if chaos.should_crash(&agent_id) && self.crash_agent(&agent_id).await.is_ok() {
+                crashed.push(agent_id);
+            }
+        }
+        crashed
+    }
+
+    /// Record a heartbeat from an agent without changing its status,
+    /// returning the heartbeat interval negotiated for it
+    pub async fn heartbeat(&self, agent_id: &str) -> RhemaResult<std::time::Duration> {
+        let mut agents = self.agents.write().await;
+        let agent = agents
+            .get_mut(agent_id)
+            .ok_or_else(|| CoordinationError::AgentNotFound(agent_id.to_string()))?;
+        record_heartbeat(agent, &mut *self.liveness.write().await);
+        Ok(std::time::Duration::from_millis(
+            agent.liveness.heartbeat_interval_ms,
+        ))
+    }
+
+    /// Reassess every agent's liveness from the time since its last heartbeat
+    pub async fn refresh_liveness(&self) {
+        let now = Utc::now();
+        let mut agents = self.agents.write().await;
+        let liveness = self.liveness.read().await;
+        for agent in agents.values_mut() {
+            let elapsed = now
+                .signed_duration_since(agent.last_heartbeat)
+                .to_std()
+                .unwrap_or_default();
+            agent.liveness = liveness.assess(&agent.id, elapsed);
+        }
+    }
+
     /// Get agent information
     pub async fn get_agent_info(&self, agent_id: &str) -> Option<AgentInfo> {
+        self.refresh_liveness().await;
         let agents = self.agents.read().await;
         agents.get(agent_id).cloned()
     }
 
     /// Get all agents
     pub async fn get_all_agents(&self) -> Vec<AgentInfo> {
+        self.refresh_liveness().await;
         let agents = self.agents.read().await;
         agents.values().cloned().collect()
     }
@@ -1721,41 +2223,51 @@ impl RealTimeCoordinationSystem {
 
     /// Start heartbeat monitoring
     pub async fn start_heartbeat_monitoring(&self) {
-        let agents = Arc::clone(&self.agents);
-        let config = self.config.clone();
+        let system = self.clone();
 
         tokio::spawn(async move {
             let mut interval = tokio::time::interval(std::time::Duration::from_secs(
-                config.heartbeat_interval_seconds,
+                system.config.heartbeat_interval_seconds,
             ));
 
             loop {
                 interval.tick().await;
+                system.expire_timed_out_agents().await;
+            }
+        });
+    }
 
-                let now = Utc::now();
-                let mut agents_to_remove = Vec::new();
-
-                {
-                    let mut agents_guard = agents.write().await;
-                    for (agent_id, agent) in agents_guard.iter_mut() {
-                        let time_since_heartbeat = now.signed_duration_since(agent.last_heartbeat);
-                        if time_since_heartbeat.num_seconds() > config.agent_timeout_seconds as i64
-                        {
-                            agents_to_remove.push(agent_id.clone());
-                        }
-                    }
-                }
+    /// Unregister agents that the phi-accrual detector considers failed, or
+    /// whose last heartbeat is older than the agent timeout, returning their
+    /// IDs
+    pub async fn expire_timed_out_agents(&self) -> Vec<String> {
+        self.refresh_liveness().await;
+        let now = Utc::now();
+        let mut agents_to_remove: Vec<String> = {
+            let agents = self.agents.read().await;
+            let liveness = self.liveness.read().await;
+            agents
+                .values()
+                .filter(|agent| {
+                    let elapsed = now.signed_duration_since(agent.last_heartbeat);
+                    elapsed.num_seconds() > self.config.agent_timeout_seconds as i64
+                        || liveness.is_failed(&agent.id, elapsed.to_std().unwrap_or_default())
+                })
+                .map(|agent| agent.id.clone())
+                .collect()
+        };
+        agents_to_remove.sort();
 
-                // Remove timed out agents
-                for agent_id in agents_to_remove {
-                    let mut agents_guard = agents.write().await;
-                    agents_guard.remove(&agent_id);
-                }
+        for agent_id in &agents_to_remove {
+            if let Err(e) = self.unregister_agent(agent_id).await {
+                error!("Failed to expire agent {}: {}", agent_id, e);
             }
-        });
+        }
+        agents_to_remove
     }
 
-    /// Select agent using load balancer
+    /// Select agent using load balancer. Agents whose liveness confidence is
+    /// below the configured minimum are never selected.
     pub async fn select_agent_for_task(
         &self,
         task_requirements: Option<Vec<String>>,
@@ -1765,23 +2277,27 @@ impl RealTimeCoordinationSystem {
             let lb_guard = load_balancer.write().await;
             lb_guard.select_agent(&available_agents, task_requirements)
         } else {
-            // Fallback to simple selection
+            // Fallback to the most certainly alive agent
+            self.refresh_liveness().await;
+            let min_confidence = self.liveness.read().await.config().min_routing_confidence;
             let agents = self.agents.read().await;
             agents
-                .iter()
-                .filter(|(_, agent)| agent.is_online && agent.status != AgentStatus::Offline)
-                .next()
-                .map(|(id, _)| id.clone())
+                .values()
+                .filter(|agent| is_assignable(agent, min_confidence))
+                .max_by(|a, b| a.liveness.confidence.total_cmp(&b.liveness.confidence))
+                .map(|agent| agent.id.clone())
         }
     }
 
     /// Get available agents for load balancing
     async fn get_available_agents(&self) -> Vec<String> {
+        self.refresh_liveness().await;
+        let min_confidence = self.liveness.read().await.config().min_routing_confidence;
         let agents = self.agents.read().await;
         agents
-            .iter()
-            .filter(|(_, agent)| agent.is_online && agent.status != AgentStatus::Offline)
-            .map(|(id, _)| id.clone())
+            .values()
+            .filter(|agent| is_assignable(agent, min_confidence))
+            .map(|agent| agent.id.clone())
             .collect()
     }
 
@@ -2068,6 +2584,32 @@ impl RealTimeCoordinationSystem {
     }
 }
 
+/// Whether work may be routed to `agent`
+fn is_assignable(agent: &AgentInfo, min_confidence: f64) -> bool {
+    agent.is_online
+        && agent.status != AgentStatus::Offline
+        && agent.liveness.confidence >= min_confidence
+}
+
+/// Liveness monitor whose agents start at the configured heartbeat interval
+fn liveness_monitor(config: &CoordinationConfig) -> Arc<RwLock<LivenessMonitor>> {
+    Arc::new(RwLock::new(LivenessMonitor::new(
+        LivenessConfig::default(),
+        std::time::Duration::from_secs(config.heartbeat_interval_seconds),
+    )))
+}
+
+/// Record a heartbeat from `agent`, renegotiating its heartbeat interval
+fn record_heartbeat(agent: &mut AgentInfo, liveness: &mut LivenessMonitor) {
+    let now = Utc::now();
+    let since_last = now
+        .signed_duration_since(agent.last_heartbeat)
+        .to_std()
+        .unwrap_or_default();
+    agent.last_heartbeat = now;
+    agent.liveness = liveness.heartbeat(&agent.id, since_last, agent.status.workload());
+}
+
 impl Default for CoordinationConfig {
     fn default() -> Self {
         Self {
@@ -2207,6 +2749,7 @@ mod tests {
                 collaboration_score: 0.5,
                 avg_response_time_ms: 100.0,
             },
+            liveness: Default::default(),
         };
 
         assert!(system.register_agent(agent_info).await.is_ok());
@@ -2238,6 +2781,7 @@ mod tests {
                 collaboration_score: 0.5,
                 avg_response_time_ms: 100.0,
             },
+            liveness: Default::default(),
         };
 
         system.register_agent(agent_info).await.unwrap();
@@ -2263,6 +2807,94 @@ mod tests {
         assert_eq!(stats.total_messages, 2); // Including welcome message
     }
 
+    #[tokio::test]
+    async fn test_group_message_expands_at_send_time() {
+        use crate::agent::groups::GroupSelector;
+
+        let system = RealTimeCoordinationSystem::new();
+        system
+            .define_group(
+                AgentGroup::with_selector(
+                    "reviewers",
+                    GroupSelector {
+                        roles: vec!["reviewer".to_string()],
+                        ..Default::default()
+                    },
+                )
+                .with_rate_limit(1, 60),
+            )
+            .await
+            .unwrap();
+        let reviewers = GroupTarget::Group("reviewers".to_string());
+
+        for (id, role) in [("r1", "reviewer"), ("r2", "reviewer"), ("b1", "builder")] {
+            system
+                .register_agent(AgentInfo {
+                    id: id.to_string(),
+                    name: id.to_string(),
+                    agent_type: role.to_string(),
+                    status: AgentStatus::Idle,
+                    current_task_id: None,
+                    assigned_scope: "test-scope".to_string(),
+                    capabilities: vec!["rust".to_string()],
+                    last_heartbeat: Utc::now(),
+                    is_online: true,
+                    performance_metrics: AgentPerformanceMetrics::default(),
+                    liveness: Default::default(),
+                })
+                .await
+                .unwrap();
+        }
+        let _r2_stream = system.get_message_stream("r2").await.unwrap();
+
+        let message = AgentMessage {
+            id: Uuid::new_v4().to_string(),
+            message_type: MessageType::DecisionRequest,
+            priority: MessagePriority::Normal,
+            sender_id: "r1".to_string(),
+            recipient_ids: vec![],
+            content: "Please review".to_string(),
+            payload: None,
+            timestamp: Utc::now(),
+            requires_ack: false,
+            expires_at: None,
+            metadata: HashMap::new(),
+        };
+
+        let report = system
+            .send_group_message(&reviewers, message.clone())
+            .await
+            .unwrap();
+        assert_eq!(report.recipients, vec!["r2".to_string()]);
+        assert_eq!(report.delivered, 1);
+
+        // The group allows one message per minute
+        assert!(system
+            .send_group_message(&reviewers, message.clone())
+            .await
+            .is_err());
+        let stats = system.get_group_stats(&reviewers).await.unwrap();
+        assert_eq!(stats.messages_sent, 1);
+        assert_eq!(stats.rate_limited, 1);
+
+        // Ad-hoc targets never fall back to a broadcast
+        assert!(system
+            .send_group_message(&GroupTarget::Capability("go".to_string()), message)
+            .await
+            .is_err());
+
+        let session_id = system
+            .create_group_session(
+                "review".to_string(),
+                vec![],
+                &[GroupTarget::Capability("rust".to_string())],
+            )
+            .await
+            .unwrap();
+        let sessions = system.sessions.read().await;
+        assert_eq!(sessions[&session_id].participants, vec!["b1", "r1", "r2"]);
+    }
+
     #[tokio::test]
     async fn test_session_creation() {
         let system = RealTimeCoordinationSystem::new();
@@ -2286,6 +2918,7 @@ mod tests {
                 collaboration_score: 0.5,
                 avg_response_time_ms: 100.0,
             },
+            liveness: Default::default(),
         };
 
         let agent2 = AgentInfo {
@@ -2306,6 +2939,7 @@ mod tests {
                 collaboration_score: 0.5,
                 avg_response_time_ms: 100.0,
             },
+            liveness: Default::default(),
         };
 
         system.register_agent(agent1).await.unwrap();
@@ -2326,6 +2960,58 @@ mod tests {
         assert_eq!(stats.active_sessions, 1);
     }
 
+    #[tokio::test]
+    async fn test_completed_session_summary_written_to_scope() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut system = RealTimeCoordinationSystem::new();
+        system.enable_session_summaries(SessionSummaryWriter::new(temp.path()));
+
+        let agent = AgentInfo {
+            id: "agent1".to_string(),
+            name: "Agent 1".to_string(),
+            agent_type: "test".to_string(),
+            status: AgentStatus::Idle,
+            current_task_id: None,
+            assigned_scope: "test-scope".to_string(),
+            capabilities: vec!["test".to_string()],
+            last_heartbeat: Utc::now(),
+            is_online: true,
+            performance_metrics: AgentPerformanceMetrics::default(),
+            liveness: Default::default(),
+        };
+        system.register_agent(agent).await.unwrap();
+
+        let session_id = system
+            .create_session("Release plan".to_string(), vec!["agent1".to_string()])
+            .await
+            .unwrap();
+        system
+            .send_session_message(
+                &session_id,
+                AgentMessage {
+                    id: Uuid::new_v4().to_string(),
+                    message_type: MessageType::TaskAssignment,
+                    priority: MessagePriority::Normal,
+                    sender_id: "agent1".to_string(),
+                    recipient_ids: vec![],
+                    content: "Write the changelog".to_string(),
+                    payload: None,
+                    timestamp: Utc::now(),
+                    requires_ack: false,
+                    expires_at: None,
+                    metadata: HashMap::new(),
+                },
+            )
+            .await
+            .unwrap();
+
+        let written = system.end_session(&session_id).await.unwrap().unwrap();
+        assert_eq!(written.todo_ids.len(), 1);
+        assert!(temp.path().join("knowledge.yaml").exists());
+        assert_eq!(system.get_stats().active_sessions, 0);
+        assert!(system.end_session(&session_id).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_advanced_coordination_features() {
         let mut advanced_config = AdvancedCoordinationConfig::default();
@@ -2370,6 +3056,7 @@ mod tests {
             last_heartbeat: Utc::now(),
             is_online: true,
             performance_metrics: AgentPerformanceMetrics::default(),
+            liveness: Default::default(),
         };
 
         let agent2 = AgentInfo {
@@ -2383,6 +3070,7 @@ mod tests {
             last_heartbeat: Utc::now(),
             is_online: true,
             performance_metrics: AgentPerformanceMetrics::default(),
+            liveness: Default::default(),
         };
 
         system.register_agent(agent1).await.unwrap();
@@ -2399,6 +3087,54 @@ mod tests {
         assert!(selected_agent.is_some());
     }
 
+    #[tokio::test]
+    async fn test_shaky_agents_are_not_assigned_work() {
+        let system = RealTimeCoordinationSystem::new();
+        for id in ["steady", "shaky"] {
+            system
+                .register_agent(AgentInfo {
+                    id: id.to_string(),
+                    name: id.to_string(),
+                    agent_type: "test".to_string(),
+                    status: AgentStatus::Idle,
+                    current_task_id: None,
+                    assigned_scope: "test-scope".to_string(),
+                    capabilities: vec!["test".to_string()],
+                    last_heartbeat: Utc::now(),
+                    is_online: true,
+                    performance_metrics: AgentPerformanceMetrics::default(),
+                    liveness: Default::default(),
+                })
+                .await
+                .unwrap();
+            for _ in 0..5 {
+                system.heartbeat(id).await.unwrap();
+            }
+        }
+
+        // The shaky agent goes quiet long after its usual heartbeat
+        system
+            .agents
+            .write()
+            .await
+            .get_mut("shaky")
+            .unwrap()
+            .last_heartbeat = Utc::now() - chrono::Duration::seconds(60);
+
+        let shaky = system.get_agent_info("shaky").await.unwrap();
+        assert!(shaky.liveness.confidence < 0.5);
+        let steady = system.get_agent_info("steady").await.unwrap();
+        assert!(steady.liveness.confidence > 0.9);
+
+        for _ in 0..5 {
+            assert_eq!(
+                system.select_agent_for_task(None).await.as_deref(),
+                Some("steady")
+            );
+        }
+        assert_eq!(system.expire_timed_out_agents().await, vec!["shaky"]);
+    }
+
     #[tokio::test]
     async fn test_circuit_breaker() {
         let mut advanced_config = AdvancedCoordinationConfig::default();
@@ -2571,6 +3307,7 @@ mod tests {
             last_heartbeat: Utc::now(),
             is_online: true,
             performance_metrics: AgentPerformanceMetrics::default(),
+            liveness: Default::default(),
         };
 
         let agent2 = AgentInfo {
@@ -2584,6 +3321,7 @@ mod tests {
             last_heartbeat: Utc::now(),
             is_online: true,
             performance_metrics: AgentPerformanceMetrics::default(),
+            liveness: Default::default(),
         };
 
         system.register_agent(agent1).await.unwrap();
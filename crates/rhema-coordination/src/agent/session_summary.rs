@@ -0,0 +1,442 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Summaries of completed coordination sessions.
+//!
+//! When a session ends, its participants, decisions and outstanding action
+//! items are written into a scope's context: the summary as a knowledge
+//! entry, each settled decision as a decision entry and each open action
+//! item as a todo. Every entry is stored as pending review, so nothing an
+//! agent discussion produced reaches queries until a reviewer approves it.
+
+use super::real_time_coordination::{
+    AgentMessage, CoordinationSession, MessagePriority, MessageType, SessionDecision,
+};
+use chrono::{DateTime, Utc};
+use rhema_core::file_ops::{
+    get_or_create_decisions_file, get_or_create_knowledge_file, get_or_create_todos_file,
+    read_yaml_file, write_yaml_file,
+};
+use rhema_core::review::pending_fields;
+use rhema_core::schema::{
+    DecisionEntry, DecisionStatus, Decisions, Knowledge, KnowledgeEntry, Priority, TodoEntry,
+    TodoStatus, Todos,
+};
+use rhema_core::RhemaResult;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Message metadata key marking a message as an action item; its value, if
+/// not empty, is used as the item title instead of the message content
+pub const ACTION_ITEM_KEY: &str = "action_item";
+
+/// Message metadata key naming the agent an action item is assigned to
+pub const ASSIGNEE_KEY: &str = "assignee";
+
+/// Message metadata key on a task completion naming the message it completes
+pub const COMPLETES_KEY: &str = "completes";
+
+/// Knowledge category of written summaries
+pub const SUMMARY_CATEGORY: &str = "session-summary";
+
+/// Author recorded on the pending entries when none is configured
+const DEFAULT_AUTHOR: &str = "coordination";
+
+/// A decision reached in a session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SummarizedDecision {
+    pub topic: String,
+    pub description: String,
+    /// Selected option, `None` if the session ended before one was chosen
+    pub outcome: Option<String>,
+    pub options: Vec<String>,
+    pub decision_maker: String,
+    pub votes: usize,
+}
+
+impl From<&SessionDecision> for SummarizedDecision {
+    fn from(decision: &SessionDecision) -> Self {
+        Self {
+            topic: decision.topic.clone(),
+            description: decision.description.clone(),
+            outcome: decision.selected_option.clone(),
+            options: decision.options.clone(),
+            decision_maker: decision.decision_maker.clone(),
+            votes: decision.votes.len(),
+        }
+    }
+}
+
+/// Work agreed in a session that was not completed before it ended
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionItem {
+    pub title: String,
+    pub assignee: Option<String>,
+    pub raised_by: String,
+    pub priority: Priority,
+    /// ID of the session message the item was raised in
+    pub message_id: String,
+}
+
+/// Structured summary of a coordination session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub session_id: String,
+    pub topic: String,
+    pub participants: Vec<String>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+    pub message_count: usize,
+    pub decisions: Vec<SummarizedDecision>,
+    pub action_items: Vec<ActionItem>,
+}
+
+impl SessionSummary {
+    /// Summarize a session. Participants include every agent that sent a
+    /// message, since agents leaving the session drop out of its participant
+    /// list. Task assignments and messages marked with [`ACTION_ITEM_KEY`]
+    /// become action items unless a later task completion names them in
+    /// [`COMPLETES_KEY`].
+    pub fn from_session(session: &CoordinationSession) -> Self {
+        let mut participants = session.participants.clone();
+        for message in &session.messages {
+            if message.sender_id != "system" && !participants.contains(&message.sender_id) {
+                participants.push(message.sender_id.clone());
+            }
+        }
+
+        let completed: HashSet<&str> = session
+            .messages
+            .iter()
+            .filter(|message| matches!(message.message_type, MessageType::TaskCompletion))
+            .filter_map(|message| message.metadata.get(COMPLETES_KEY))
+            .map(String::as_str)
+            .collect();
+        let action_items = session
+            .messages
+            .iter()
+            .filter(|message| !completed.contains(message.id.as_str()))
+            .filter_map(action_item)
+            .collect();
+
+        Self {
+            session_id: session.id.clone(),
+            topic: session.topic.clone(),
+            participants,
+            started_at: session.started_at,
+            ended_at: session.ended_at.unwrap_or_else(Utc::now),
+            message_count: session.messages.len(),
+            decisions: session.decisions.iter().map(Into::into).collect(),
+            action_items,
+        }
+    }
+
+    /// Markdown body of the knowledge entry
+    pub fn to_markdown(&self) -> String {
+        let minutes = (self.ended_at - self.started_at).num_minutes();
+        let mut out = format!(
+            "Coordination session `{}` on {}, {} minute(s), {} message(s).\n\n",
+            self.session_id,
+            self.started_at.format("%Y-%m-%d %H:%M UTC"),
+            minutes,
+            self.message_count
+        );
+
+        out.push_str("## Participants\n\n");
+        for participant in &self.participants {
+            out.push_str(&format!("- {}\n", participant));
+        }
+
+        out.push_str("\n## Decisions\n\n");
+        if self.decisions.is_empty() {
+            out.push_str("None recorded.\n");
+        }
+        for decision in &self.decisions {
+            match &decision.outcome {
+                Some(outcome) => out.push_str(&format!(
+                    "- **{}**: {} (by {}, {} vote(s))\n",
+                    decision.topic, outcome, decision.decision_maker, decision.votes
+                )),
+                None => out.push_str(&format!("- **{}**: undecided\n", decision.topic)),
+            }
+        }
+
+        out.push_str("\n## Action Items\n\n");
+        if self.action_items.is_empty() {
+            out.push_str("None outstanding.\n");
+        }
+        for item in &self.action_items {
+            match &item.assignee {
+                Some(assignee) => out.push_str(&format!("- {} ({})\n", item.title, assignee)),
+                None => out.push_str(&format!("- {}\n", item.title)),
+            }
+        }
+        out
+    }
+}
+
+fn action_item(message: &AgentMessage) -> Option<ActionItem> {
+    let marked = message.metadata.get(ACTION_ITEM_KEY);
+    if marked.is_none() && !matches!(message.message_type, MessageType::TaskAssignment) {
+        return None;
+    }
+    let title = marked
+        .filter(|title| !title.trim().is_empty())
+        .unwrap_or(&message.content)
+        .trim()
+        .to_string();
+    if title.is_empty() {
+        return None;
+    }
+
+    Some(ActionItem {
+        title,
+        assignee: message.metadata.get(ASSIGNEE_KEY).cloned(),
+        raised_by: message.sender_id.clone(),
+        priority: match message.priority {
+            MessagePriority::Low => Priority::Low,
+            MessagePriority::Normal => Priority::Medium,
+            MessagePriority::High => Priority::High,
+            MessagePriority::Critical | MessagePriority::Emergency => Priority::Critical,
+        },
+        message_id: message.id.clone(),
+    })
+}
+
+/// IDs of the entries written for one summary
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WrittenSummary {
+    /// `.rhema` directory of the scope written to
+    pub scope_path: PathBuf,
+    pub knowledge_id: String,
+    pub decision_ids: Vec<String>,
+    pub todo_ids: Vec<String>,
+}
+
+/// Writes session summaries into a scope's context as pending entries
+#[derive(Debug, Clone)]
+pub struct SessionSummaryWriter {
+    scope_path: PathBuf,
+    author: String,
+}
+
+impl SessionSummaryWriter {
+    /// Write into the scope whose `.rhema` directory is `scope_path`
+    pub fn new(scope_path: impl Into<PathBuf>) -> Self {
+        Self {
+            scope_path: scope_path.into(),
+            author: DEFAULT_AUTHOR.to_string(),
+        }
+    }
+
+    /// Name recorded as the submitter of the pending entries
+    pub fn with_author(mut self, author: impl Into<String>) -> Self {
+        self.author = author.into();
+        self
+    }
+
+    /// Write into the configured scope
+    pub fn write(&self, summary: &SessionSummary) -> RhemaResult<WrittenSummary> {
+        self.write_to(&self.scope_path, summary)
+    }
+
+    /// Write into the scope whose `.rhema` directory is `scope_path`
+    pub fn write_to(
+        &self,
+        scope_path: &Path,
+        summary: &SessionSummary,
+    ) -> RhemaResult<WrittenSummary> {
+        let source = format!("coordination-session:{}", summary.session_id);
+
+        let knowledge_file = get_or_create_knowledge_file(scope_path)?;
+        let mut knowledge: Knowledge = read_yaml_file(&knowledge_file)?;
+        let knowledge_id = Uuid::new_v4().to_string();
+        knowledge.entries.push(KnowledgeEntry {
+            id: knowledge_id.clone(),
+            title: format!("Session summary: {}", summary.topic),
+            content: summary.to_markdown(),
+            category: Some(SUMMARY_CATEGORY.to_string()),
+            tags: Some(vec![
+                "coordination-session".to_string(),
+                summary.session_id.clone(),
+            ]),
+            confidence: None,
+            created_at: Utc::now(),
+            updated_at: None,
+            source: Some(source.clone()),
+            custom: pending_fields(&self.author),
+        });
+        write_yaml_file(&knowledge_file, &knowledge)?;
+
+        let mut decision_ids = Vec::new();
+        let settled: Vec<&SummarizedDecision> = summary
+            .decisions
+            .iter()
+            .filter(|decision| decision.outcome.is_some())
+            .collect();
+        if !settled.is_empty() {
+            let decisions_file = get_or_create_decisions_file(scope_path)?;
+            let mut decisions: Decisions = read_yaml_file(&decisions_file)?;
+            for decision in settled {
+                let id = Uuid::new_v4().to_string();
+                let outcome = decision.outcome.clone().unwrap_or_default();
+                let alternatives: Vec<String> = decision
+                    .options
+                    .iter()
+                    .filter(|option| **option != outcome)
+                    .cloned()
+                    .collect();
+                decisions.decisions.push(DecisionEntry {
+                    id: id.clone(),
+                    title: format!("{}: {}", decision.topic, outcome),
+                    description: decision.description.clone(),
+                    status: DecisionStatus::Approved,
+                    context: Some(format!("Decided in {}", source)),
+                    alternatives: (!alternatives.is_empty()).then_some(alternatives),
+                    rationale: None,
+                    consequences: None,
+                    decided_at: summary.ended_at,
+                    review_date: None,
+                    decision_makers: Some(vec![decision.decision_maker.clone()]),
+                    implementing_commits: None,
+                    incidents: None,
+                    reversed_by: None,
+                    reverses: None,
+                    custom: pending_fields(&self.author),
+                });
+                decision_ids.push(id);
+            }
+            write_yaml_file(&decisions_file, &decisions)?;
+        }
+
+        let mut todo_ids = Vec::new();
+        if !summary.action_items.is_empty() {
+            let todos_file = get_or_create_todos_file(scope_path)?;
+            let mut todos: Todos = read_yaml_file(&todos_file)?;
+            for item in &summary.action_items {
+                let id = Uuid::new_v4().to_string();
+                todos.todos.push(TodoEntry {
+                    id: id.clone(),
+                    title: item.title.clone(),
+                    description: Some(format!(
+                        "Raised by {} in {} ({})",
+                        item.raised_by, source, summary.topic
+                    )),
+                    status: TodoStatus::Pending,
+                    priority: item.priority.clone(),
+                    assigned_to: item.assignee.clone(),
+                    due_date: None,
+                    created_at: Utc::now(),
+                    completed_at: None,
+                    outcome: None,
+                    related_knowledge: Some(vec![knowledge_id.clone()]),
+                    custom: pending_fields(&self.author),
+                });
+                todo_ids.push(id);
+            }
+            write_yaml_file(&todos_file, &todos)?;
+        }
+
+        Ok(WrittenSummary {
+            scope_path: scope_path.to_path_buf(),
+            knowledge_id,
+            decision_ids,
+            todo_ids,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::real_time_coordination::SessionStatus;
+    use rhema_core::review::{entry_state, ReviewState};
+    use std::collections::HashMap;
+
+    fn message(id: &str, sender: &str, message_type: MessageType, content: &str) -> AgentMessage {
+        AgentMessage {
+            id: id.to_string(),
+            message_type,
+            priority: MessagePriority::High,
+            sender_id: sender.to_string(),
+            recipient_ids: Vec::new(),
+            content: content.to_string(),
+            payload: None,
+            timestamp: Utc::now(),
+            requires_ack: false,
+            expires_at: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_summary_written_as_pending_entries() {
+        let mut assign = message("m1", "lead", MessageType::TaskAssignment, "Migrate auth");
+        assign
+            .metadata
+            .insert(ASSIGNEE_KEY.to_string(), "agent-b".to_string());
+        let finished = message("m2", "lead", MessageType::TaskAssignment, "Bump deps");
+        let mut done = message("m3", "agent-b", MessageType::TaskCompletion, "done");
+        done.metadata
+            .insert(COMPLETES_KEY.to_string(), "m2".to_string());
+        let chatter = message("m4", "agent-c", MessageType::SessionMessage, "sounds good");
+
+        let session = CoordinationSession {
+            id: "s1".to_string(),
+            topic: "auth rollout".to_string(),
+            participants: vec!["lead".to_string()],
+            status: SessionStatus::Completed,
+            started_at: Utc::now(),
+            ended_at: Some(Utc::now()),
+            messages: vec![assign, finished, done, chatter],
+            decisions: vec![SessionDecision {
+                id: "d1".to_string(),
+                topic: "token format".to_string(),
+                description: "Format of service tokens".to_string(),
+                options: vec!["jwt".to_string(), "opaque".to_string()],
+                selected_option: Some("opaque".to_string()),
+                votes: HashMap::new(),
+                timestamp: Utc::now(),
+                decision_maker: "lead".to_string(),
+            }],
+        };
+
+        let summary = SessionSummary::from_session(&session);
+        assert_eq!(summary.participants, vec!["lead", "agent-b", "agent-c"]);
+        assert_eq!(summary.action_items.len(), 1);
+        assert_eq!(summary.action_items[0].title, "Migrate auth");
+        assert_eq!(summary.action_items[0].assignee.as_deref(), Some("agent-b"));
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let written = SessionSummaryWriter::new(temp.path())
+            .with_author("session-bot")
+            .write(&summary)
+            .unwrap();
+        assert_eq!(written.decision_ids.len(), 1);
+        assert_eq!(written.todo_ids.len(), 1);
+
+        let todos: serde_yaml::Value = read_yaml_file(&temp.path().join("todos.yaml")).unwrap();
+        assert_eq!(entry_state(&todos["todos"][0]), Some(ReviewState::Pending));
+        let decisions: Decisions = read_yaml_file(&temp.path().join("decisions.yaml")).unwrap();
+        assert_eq!(decisions.decisions[0].title, "token format: opaque");
+        assert_eq!(
+            decisions.decisions[0].alternatives,
+            Some(vec!["jwt".to_string()])
+        );
+    }
+}
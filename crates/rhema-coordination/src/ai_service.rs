@@ -16,11 +16,12 @@
 
 use cached::TimedCache;
 use chrono::{DateTime, Utc};
+use rhema_core::ai_policy::{resolve_ai_policy, ModelTier};
 use rhema_core::{RhemaError, RhemaResult};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::instrument;
@@ -37,6 +38,7 @@ use crate::agent::real_time_coordination::{AgentInfo, AgentMessage};
 use crate::agent::state::{AgentManager, AgentState, PersistenceConfig};
 use crate::context_injection::{EnhancedContextInjector, LockFileContextRequirement, TaskType};
 use crate::coordination_integration::{CoordinationConfig, CoordinationIntegration};
+use crate::provider_health::LlmProviderProbe;
 
 // Re-export types from lock_context to avoid duplication
 pub use crate::agent::lock_context::{
@@ -45,6 +47,15 @@ pub use crate::agent::lock_context::{
     VersionConflict,
 };
 
+/// Default model for a scope AI policy model tier
+pub fn model_for_tier(tier: ModelTier) -> &'static str {
+    match tier {
+        ModelTier::Economy => "gpt-3.5-turbo",
+        ModelTier::Standard => "gpt-4",
+        ModelTier::Premium => "gpt-4-turbo",
+    }
+}
+
 /// AI Service configuration with lock file awareness and agent state management
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AIServiceConfig {
@@ -397,9 +408,12 @@ impl AIService {
 
     /// Process an AI request with lock file awareness
     #[instrument(skip(self, request))]
-    pub async fn process_request(&self, request: AIRequest) -> RhemaResult<AIResponse> {
+    pub async fn process_request(&self, mut request: AIRequest) -> RhemaResult<AIResponse> {
         let start_time = std::time::Instant::now();
 
+        // Enforce the AI policy of the scope the request operates in
+        self.apply_scope_policy(&mut request)?;
+
         // Validate lock file if required
         let lock_file_validation = if self.config.enable_lock_file_awareness
             && request
@@ -994,6 +1008,23 @@ impl AIService {
         // Cache clearing implementation
     }
 
+    /// Check a request against its scope's AI policy and fill in the
+    /// preferred model when the caller did not choose one
+    fn apply_scope_policy(&self, request: &mut AIRequest) -> RhemaResult<()> {
+        let Some(scope_path) = &request.scope_path else {
+            return Ok(());
+        };
+        let policy = resolve_ai_policy(Path::new(scope_path))?;
+
+        if let Some(task_type) = &request.task_type {
+            policy.check_operation(&task_type.operation_name())?;
+        }
+        if request.model.is_empty() {
+            request.model = model_for_tier(policy.model_tier).to_string();
+        }
+        Ok(())
+    }
+
     /// Update model information
     pub async fn update_model(&self, model: AIModel) {
         let mut models = self.models.write().await;
@@ -1036,6 +1067,16 @@ impl AIService {
         Ok(())
     }
 
+    /// Probe of this service's provider for a dependency health monitor
+    pub fn provider_probe(&self) -> RhemaResult<LlmProviderProbe> {
+        LlmProviderProbe::new(
+            "llm",
+            &self.config.base_url,
+            self.config.api_key.clone(),
+            std::time::Duration::from_secs(self.config.timeout_seconds),
+        )
+    }
+
     /// Get lock file context for AI operations
     pub async fn get_lock_file_context(&self) -> RhemaResult<Option<LockFileAIContext>> {
         if let Some(integration) = &self.lock_file_integration {
@@ -0,0 +1,843 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Chaos testing for the coordination layer.
+//!
+//! A [`ChaosInjector`] attached to a [`RealTimeCoordinationSystem`] drops and
+//! delays message deliveries, delays heartbeats, crashes agents and
+//! partitions links between nodes. Every decision comes from a seeded
+//! generator, so a run with the same seed and the same sequence of
+//! operations injects the same faults. Chaos is only enabled when
+//! [`ChaosConfig::enabled`] is set.
+//!
+//! [`FaultToleranceSuite`] runs scenarios under chaos and asserts that the
+//! behaviours promised by [`FaultToleranceConfig`] hold: dropped deliveries
+//! are retried, circuit breakers open for failing agents and recover after
+//! their timeout, silent agents are expired, and partitions isolate nodes.
+
+use chrono::Utc;
+use rhema_core::{RhemaError, RhemaResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::agent::real_time_coordination::{
+    AdvancedCoordinationConfig, AgentInfo, AgentMessage, AgentPerformanceMetrics, AgentStatus,
+    CoordinationConfig as SystemConfig, FaultToleranceConfig, MessagePriority, MessageType,
+    RealTimeCoordinationSystem,
+};
+use crate::loadtest::XorShift;
+
+/// A scheduled partition between groups of nodes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartitionSpec {
+    /// Nodes in different groups cannot reach each other
+    pub groups: Vec<Vec<String>>,
+    /// Delay after chaos is enabled before the partition starts
+    pub start_after_ms: u64,
+    /// How long the partition lasts
+    pub duration_ms: u64,
+}
+
+/// Chaos configuration. Nothing is injected unless `enabled` is set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChaosConfig {
+    pub enabled: bool,
+    /// Seed for every fault decision
+    pub seed: u64,
+    /// Probability that a delivery attempt is dropped
+    pub message_drop_rate: f64,
+    /// Probability that a delivery attempt is delayed
+    pub message_delay_rate: f64,
+    /// Upper bound of an injected message delay
+    pub max_message_delay_ms: u64,
+    /// Probability that a heartbeat is applied late
+    pub heartbeat_delay_rate: f64,
+    /// Delay applied to a late heartbeat
+    pub heartbeat_delay_ms: u64,
+    /// Probability that an agent crashes on each chaos tick
+    pub agent_crash_rate: f64,
+    /// Scheduled partitions between nodes
+    pub partitions: Vec<PartitionSpec>,
+    /// Agents faults are limited to (all agents when empty)
+    pub targets: Vec<String>,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            seed: 0,
+            message_drop_rate: 0.0,
+            message_delay_rate: 0.0,
+            max_message_delay_ms: 100,
+            heartbeat_delay_rate: 0.0,
+            heartbeat_delay_ms: 1000,
+            agent_crash_rate: 0.0,
+            partitions: Vec::new(),
+            targets: Vec::new(),
+        }
+    }
+}
+
+/// Kind of injected fault
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum FaultKind {
+    MessageDropped,
+    MessageDelayed { delay_ms: u64 },
+    HeartbeatDelayed { delay_ms: u64 },
+    AgentCrashed,
+    LinkPartitioned { to: String },
+}
+
+impl FaultKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            FaultKind::MessageDropped => "message_dropped",
+            FaultKind::MessageDelayed { .. } => "message_delayed",
+            FaultKind::HeartbeatDelayed { .. } => "heartbeat_delayed",
+            FaultKind::AgentCrashed => "agent_crashed",
+            FaultKind::LinkPartitioned { .. } => "link_partitioned",
+        }
+    }
+}
+
+/// A fault the injector applied
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FaultEvent {
+    /// Milliseconds since chaos was enabled
+    pub at_ms: u64,
+    #[serde(flatten)]
+    pub kind: FaultKind,
+    /// Agent or node the fault was applied to
+    pub target: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+}
+
+struct ActivePartition {
+    groups: Vec<HashSet<String>>,
+    start: Instant,
+    end: Instant,
+}
+
+/// Seeded fault source shared by the coordination layer
+pub struct ChaosInjector {
+    config: ChaosConfig,
+    rng: Mutex<XorShift>,
+    started: Instant,
+    partitions: Mutex<Vec<ActivePartition>>,
+    events: Mutex<Vec<FaultEvent>>,
+}
+
+impl ChaosInjector {
+    /// Create an injector; fails unless the configuration enables chaos
+    pub fn new(config: ChaosConfig) -> RhemaResult<Self> {
+        if !config.enabled {
+            return Err(RhemaError::ConfigError(
+                "Chaos mode is disabled; set `enabled: true` in the chaos configuration"
+                    .to_string(),
+            ));
+        }
+        for rate in [
+            config.message_drop_rate,
+            config.message_delay_rate,
+            config.heartbeat_delay_rate,
+            config.agent_crash_rate,
+        ] {
+            if !(0.0..=1.0).contains(&rate) {
+                return Err(RhemaError::ConfigError(format!(
+                    "Chaos fault rate {} is outside 0..=1",
+                    rate
+                )));
+            }
+        }
+
+        let started = Instant::now();
+        let partitions = config
+            .partitions
+            .iter()
+            .map(|spec| {
+                let start = started + Duration::from_millis(spec.start_after_ms);
+                ActivePartition {
+                    groups: spec
+                        .groups
+                        .iter()
+                        .map(|group| group.iter().cloned().collect())
+                        .collect(),
+                    start,
+                    end: start + Duration::from_millis(spec.duration_ms),
+                }
+            })
+            .collect();
+        Ok(Self {
+            rng: Mutex::new(XorShift::new(config.seed)),
+            config,
+            started,
+            partitions: Mutex::new(partitions),
+            events: Mutex::new(Vec::new()),
+        })
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.config.seed
+    }
+
+    pub fn config(&self) -> &ChaosConfig {
+        &self.config
+    }
+
+    fn targeted(&self, agent_id: &str) -> bool {
+        self.config.targets.is_empty() || self.config.targets.iter().any(|t| t == agent_id)
+    }
+
+    fn roll(&self, rate: f64) -> bool {
+        rate > 0.0 && self.rng.lock().unwrap().unit() < rate
+    }
+
+    fn record(&self, kind: FaultKind, target: &str, message_id: Option<&str>) {
+        self.events.lock().unwrap().push(FaultEvent {
+            at_ms: self.started.elapsed().as_millis() as u64,
+            kind,
+            target: target.to_string(),
+            message_id: message_id.map(str::to_string),
+        });
+    }
+
+    /// Whether a delivery attempt of `message` to `recipient` is dropped
+    pub fn drop_message(&self, message: &AgentMessage, recipient: &str) -> bool {
+        if !self.targeted(recipient) || !self.roll(self.config.message_drop_rate) {
+            return false;
+        }
+        self.record(FaultKind::MessageDropped, recipient, Some(&message.id));
+        true
+    }
+
+    /// Delay to apply before a delivery attempt of `message` to `recipient`
+    pub fn message_delay(&self, message: &AgentMessage, recipient: &str) -> Option<Duration> {
+        if !self.targeted(recipient) || !self.roll(self.config.message_delay_rate) {
+            return None;
+        }
+        let delay_ms = {
+            let mut rng = self.rng.lock().unwrap();
+            1 + rng.next() % self.config.max_message_delay_ms.max(1)
+        };
+        self.record(
+            FaultKind::MessageDelayed { delay_ms },
+            recipient,
+            Some(&message.id),
+        );
+        Some(Duration::from_millis(delay_ms))
+    }
+
+    /// Delay to apply before a heartbeat from `agent_id` takes effect
+    pub fn heartbeat_delay(&self, agent_id: &str) -> Option<Duration> {
+        if !self.targeted(agent_id) || !self.roll(self.config.heartbeat_delay_rate) {
+            return None;
+        }
+        let delay_ms = self.config.heartbeat_delay_ms;
+        self.record(FaultKind::HeartbeatDelayed { delay_ms }, agent_id, None);
+        Some(Duration::from_millis(delay_ms))
+    }
+
+    /// Whether `agent_id` crashes on this chaos tick
+    pub fn should_crash(&self, agent_id: &str) -> bool {
+        self.targeted(agent_id) && self.roll(self.config.agent_crash_rate)
+    }
+
+    pub(crate) fn record_crash(&self, agent_id: &str) {
+        self.record(FaultKind::AgentCrashed, agent_id, None);
+    }
+
+    /// Partition `groups` of nodes from each other for `duration`
+    pub fn partition(&self, groups: Vec<Vec<String>>, duration: Duration) {
+        let start = Instant::now();
+        self.partitions.lock().unwrap().push(ActivePartition {
+            groups: groups
+                .into_iter()
+                .map(|group| group.into_iter().collect())
+                .collect(),
+            start,
+            end: start + duration,
+        });
+    }
+
+    /// Whether node `from` can currently reach node `to`
+    pub fn link_allowed(&self, from: &str, to: &str) -> bool {
+        let now = Instant::now();
+        let blocked = self.partitions.lock().unwrap().iter().any(|partition| {
+            if now < partition.start || now >= partition.end {
+                return false;
+            }
+            let from_group = partition.groups.iter().position(|g| g.contains(from));
+            let to_group = partition.groups.iter().position(|g| g.contains(to));
+            matches!((from_group, to_group), (Some(a), Some(b)) if a != b)
+        });
+        if blocked {
+            self.record(
+                FaultKind::LinkPartitioned { to: to.to_string() },
+                from,
+                None,
+            );
+        }
+        !blocked
+    }
+
+    /// Every fault injected so far
+    pub fn events(&self) -> Vec<FaultEvent> {
+        self.events.lock().unwrap().clone()
+    }
+
+    /// Number of faults injected, by kind
+    pub fn fault_counts(&self) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for event in self.events.lock().unwrap().iter() {
+            *counts.entry(event.kind.name().to_string()).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+/// Outcome of one fault tolerance assertion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssertionStatus {
+    Passed,
+    Failed,
+    /// The scenario could not run within the suite's time budget
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssertionResult {
+    pub name: String,
+    pub status: AssertionStatus,
+    pub detail: String,
+}
+
+impl AssertionResult {
+    fn new(name: &str, passed: bool, detail: String) -> Self {
+        Self {
+            name: name.to_string(),
+            status: if passed {
+                AssertionStatus::Passed
+            } else {
+                AssertionStatus::Failed
+            },
+            detail,
+        }
+    }
+
+    fn skipped(name: &str, detail: String) -> Self {
+        Self {
+            name: name.to_string(),
+            status: AssertionStatus::Skipped,
+            detail,
+        }
+    }
+}
+
+/// Result of a fault tolerance suite run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChaosReport {
+    pub seed: u64,
+    pub assertions: Vec<AssertionResult>,
+    /// Faults injected across all scenarios, by kind
+    pub faults: BTreeMap<String, usize>,
+}
+
+impl ChaosReport {
+    pub fn passed(&self) -> bool {
+        self.assertions
+            .iter()
+            .all(|assertion| assertion.status != AssertionStatus::Failed)
+    }
+}
+
+/// Scenarios asserting that [`FaultToleranceConfig`] behaviours hold under chaos
+#[derive(Debug, Clone)]
+pub struct FaultToleranceSuite {
+    pub coordination: SystemConfig,
+    pub fault_tolerance: FaultToleranceConfig,
+    pub chaos: ChaosConfig,
+    /// Messages sent in the retry scenario
+    pub messages: usize,
+    /// Scenarios that need to wait longer than this are skipped
+    pub max_wait: Duration,
+}
+
+impl FaultToleranceSuite {
+    pub fn new(fault_tolerance: FaultToleranceConfig, chaos: ChaosConfig) -> Self {
+        Self {
+            coordination: SystemConfig::default(),
+            fault_tolerance,
+            chaos,
+            messages: 50,
+            max_wait: Duration::from_secs(5),
+        }
+    }
+
+    /// Run every scenario and collect the assertion results
+    pub async fn run(&self) -> RhemaResult<ChaosReport> {
+        let mut faults = BTreeMap::new();
+        let mut assertions = Vec::new();
+
+        let (retry, retry_events) = self.assert_retries().await?;
+        assertions.push(retry);
+        let (_, replay_events) = self.assert_retries().await?;
+        assertions.push(Self::assert_reproducible(&retry_events, &replay_events));
+        for (kind, count) in Self::count(&retry_events) {
+            *faults.entry(kind).or_insert(0) += count;
+        }
+
+        for (assertion, events) in [
+            self.assert_circuit_breaker().await?,
+            self.assert_heartbeat_expiry().await?,
+            self.assert_partitions()?,
+        ] {
+            assertions.push(assertion);
+            for (kind, count) in Self::count(&events) {
+                *faults.entry(kind).or_insert(0) += count;
+            }
+        }
+
+        Ok(ChaosReport {
+            seed: self.chaos.seed,
+            assertions,
+            faults,
+        })
+    }
+
+    fn count(events: &[FaultEvent]) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for event in events {
+            *counts.entry(event.kind.name().to_string()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    fn system(
+        &self,
+        fault_tolerance: FaultToleranceConfig,
+        chaos: ChaosConfig,
+    ) -> RhemaResult<(RealTimeCoordinationSystem, Arc<ChaosInjector>)> {
+        let mut system = RealTimeCoordinationSystem::with_advanced_config(
+            self.coordination.clone(),
+            AdvancedCoordinationConfig {
+                enable_fault_tolerance: true,
+                fault_tolerance_config: fault_tolerance,
+                ..Default::default()
+            },
+        );
+        let injector = system.enable_chaos(ChaosConfig {
+            enabled: true,
+            ..chaos
+        })?;
+        Ok((system, injector))
+    }
+
+    async fn register(system: &RealTimeCoordinationSystem, id: &str) -> RhemaResult<()> {
+        system
+            .register_agent(AgentInfo {
+                id: id.to_string(),
+                name: id.to_string(),
+                agent_type: "chaos".to_string(),
+                status: AgentStatus::Idle,
+                current_task_id: None,
+                assigned_scope: "chaos".to_string(),
+                capabilities: Vec::new(),
+                last_heartbeat: Utc::now(),
+                is_online: true,
+                performance_metrics: AgentPerformanceMetrics::default(),
+                liveness: Default::default(),
+            })
+            .await
+    }
+
+    fn message(sender: &str, recipient: &str) -> AgentMessage {
+        AgentMessage {
+            id: Uuid::new_v4().to_string(),
+            message_type: MessageType::Custom("chaos".to_string()),
+            priority: MessagePriority::Normal,
+            sender_id: sender.to_string(),
+            recipient_ids: vec![recipient.to_string()],
+            content: "chaos probe".to_string(),
+            payload: None,
+            timestamp: Utc::now(),
+            requires_ack: false,
+            expires_at: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    /// Dropped deliveries are retried up to `max_retry_attempts` times: no
+    /// message is lost unless every attempt was dropped
+    async fn assert_retries(&self) -> RhemaResult<(AssertionResult, Vec<FaultEvent>)> {
+        const NAME: &str = "retries_cover_dropped_deliveries";
+        // Keep the breaker closed so every message gets its full retry budget
+        let fault_tolerance = FaultToleranceConfig {
+            circuit_breaker_threshold: u32::MAX,
+            ..self.fault_tolerance.clone()
+        };
+        let (system, injector) = self.system(
+            fault_tolerance,
+            ChaosConfig {
+                agent_crash_rate: 0.0,
+                partitions: Vec::new(),
+                targets: vec!["receiver".to_string()],
+                ..self.chaos.clone()
+            },
+        )?;
+        Self::register(&system, "sender").await?;
+        Self::register(&system, "receiver").await?;
+        let mut stream = system.get_message_stream("receiver").await.ok_or_else(|| {
+            RhemaError::CoordinationError("No message stream for receiver".to_string())
+        })?;
+
+        let mut sent = Vec::new();
+        for _ in 0..self.messages {
+            let message = Self::message("sender", "receiver");
+            sent.push(message.id.clone());
+            system.send_message(message).await?;
+        }
+        let mut received = HashSet::new();
+        while let Ok(message) = stream.try_recv() {
+            received.insert(message.id);
+        }
+
+        let events = injector.events();
+        let mut drops: HashMap<&str, u32> = HashMap::new();
+        for event in &events {
+            if let (FaultKind::MessageDropped, Some(id)) = (&event.kind, &event.message_id) {
+                *drops.entry(id.as_str()).or_insert(0) += 1;
+            }
+        }
+        let attempts = self.fault_tolerance.max_retry_attempts + 1;
+        let mut violations = Vec::new();
+        for id in &sent {
+            let dropped = drops.get(id.as_str()).copied().unwrap_or(0);
+            let delivered = received.contains(id);
+            if delivered && dropped >= attempts {
+                violations.push(format!("{} delivered after {} drops", id, dropped));
+            }
+            if !delivered && dropped < attempts {
+                violations.push(format!(
+                    "{} lost after only {} of {} attempts were dropped",
+                    id, dropped, attempts
+                ));
+            }
+        }
+        let lost = sent.len() - received.len();
+        let detail = if violations.is_empty() {
+            format!(
+                "{} of {} messages delivered with {} drops injected; {} lost after {} dropped attempts",
+                received.len(),
+                sent.len(),
+                drops.values().sum::<u32>(),
+                lost,
+                attempts
+            )
+        } else {
+            violations.join("; ")
+        };
+        Ok((
+            AssertionResult::new(NAME, violations.is_empty(), detail),
+            events,
+        ))
+    }
+
+    /// The same seed and operations inject the same faults
+    fn assert_reproducible(first: &[FaultEvent], second: &[FaultEvent]) -> AssertionResult {
+        let sequence = |events: &[FaultEvent]| -> Vec<(FaultKind, String)> {
+            events
+                .iter()
+                .map(|event| (event.kind.clone(), event.target.clone()))
+                .collect()
+        };
+        let same = sequence(first) == sequence(second);
+        AssertionResult::new(
+            "faults_reproducible_from_seed",
+            same,
+            if same {
+                format!("{} faults replayed identically", first.len())
+            } else {
+                format!(
+                    "replay injected {} faults where the first run injected {}",
+                    second.len(),
+                    first.len()
+                )
+            },
+        )
+    }
+
+    /// Failed deliveries to a crashed agent open its circuit breaker after
+    /// `circuit_breaker_threshold` failures, and it half-opens after
+    /// `circuit_breaker_timeout_seconds`
+    async fn assert_circuit_breaker(&self) -> RhemaResult<(AssertionResult, Vec<FaultEvent>)> {
+        const NAME: &str = "circuit_breaker_opens_for_failing_agent";
+        let (system, injector) = self.system(
+            self.fault_tolerance.clone(),
+            ChaosConfig {
+                message_drop_rate: 0.0,
+                message_delay_rate: 0.0,
+                partitions: Vec::new(),
+                ..self.chaos.clone()
+            },
+        )?;
+        Self::register(&system, "sender").await?;
+        Self::register(&system, "worker").await?;
+        let _stream = system.get_message_stream("worker").await;
+        system.crash_agent("worker").await?;
+
+        let threshold = self.fault_tolerance.circuit_breaker_threshold;
+        let mut opened_after = None;
+        for attempt in 1..=threshold {
+            system
+                .send_message(Self::message("sender", "worker"))
+                .await?;
+            if !system.can_agent_execute("worker").await {
+                opened_after = Some(attempt);
+                break;
+            }
+        }
+
+        let result = match opened_after {
+            Some(attempt) if attempt < threshold => AssertionResult::new(
+                NAME,
+                false,
+                format!(
+                    "breaker opened after {} failures, before the threshold of {}",
+                    attempt, threshold
+                ),
+            ),
+            None => AssertionResult::new(
+                NAME,
+                false,
+                format!("breaker still closed after {} failed deliveries", threshold),
+            ),
+            Some(_) => {
+                let timeout =
+                    Duration::from_secs(self.fault_tolerance.circuit_breaker_timeout_seconds);
+                if timeout > self.max_wait {
+                    AssertionResult::new(
+                        NAME,
+                        true,
+                        format!(
+                            "opened after {} failures; half-open recovery not checked ({}s timeout exceeds the {}s budget)",
+                            threshold,
+                            timeout.as_secs(),
+                            self.max_wait.as_secs()
+                        ),
+                    )
+                } else {
+                    tokio::time::sleep(timeout + Duration::from_millis(100)).await;
+                    let recovered = system.can_agent_execute("worker").await;
+                    AssertionResult::new(
+                        NAME,
+                        recovered,
+                        format!(
+                            "opened after {} failures; {} after {}s",
+                            threshold,
+                            if recovered { "half-open" } else { "still open" },
+                            timeout.as_secs()
+                        ),
+                    )
+                }
+            }
+        };
+        Ok((result, injector.events()))
+    }
+
+    /// Agents that stop heartbeating are expired after `agent_timeout_seconds`
+    /// while agents whose heartbeats arrive (even late) are kept
+    async fn assert_heartbeat_expiry(&self) -> RhemaResult<(AssertionResult, Vec<FaultEvent>)> {
+        const NAME: &str = "silent_agents_expire";
+        let timeout = Duration::from_secs(self.coordination.agent_timeout_seconds);
+        let wait = timeout + Duration::from_millis(1100);
+        if wait > self.max_wait {
+            return Ok((
+                AssertionResult::skipped(
+                    NAME,
+                    format!(
+                        "agent timeout of {}s exceeds the {}s budget",
+                        timeout.as_secs(),
+                        self.max_wait.as_secs()
+                    ),
+                ),
+                Vec::new(),
+            ));
+        }
+
+        // Heartbeat delays must stay below the timeout for the live agent
+        let heartbeat_delay_ms = self
+            .chaos
+            .heartbeat_delay_ms
+            .min(timeout.as_millis() as u64 / 2);
+        let (system, injector) = self.system(
+            self.fault_tolerance.clone(),
+            ChaosConfig {
+                message_drop_rate: 0.0,
+                message_delay_rate: 0.0,
+                agent_crash_rate: 0.0,
+                heartbeat_delay_ms,
+                partitions: Vec::new(),
+                ..self.chaos.clone()
+            },
+        )?;
+        Self::register(&system, "live").await?;
+        Self::register(&system, "silent").await?;
+        system.crash_agent("silent").await?;
+
+        let started = Instant::now();
+        while started.elapsed() < wait {
+            system
+                .update_agent_status("live", AgentStatus::Idle)
+                .await?;
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+        tokio::time::sleep(Duration::from_millis(heartbeat_delay_ms)).await;
+        let expired = system.expire_timed_out_agents().await;
+
+        let passed = expired == vec!["silent".to_string()];
+        Ok((
+            AssertionResult::new(
+                NAME,
+                passed,
+                format!("expired after {}s: {:?}", wait.as_secs_f64(), expired),
+            ),
+            injector.events(),
+        ))
+    }
+
+    /// Nodes in different partition groups cannot reach each other until the
+    /// partition heals, while nodes in the same group can
+    fn assert_partitions(&self) -> RhemaResult<(AssertionResult, Vec<FaultEvent>)> {
+        const NAME: &str = "partitions_isolate_nodes";
+        let injector = ChaosInjector::new(ChaosConfig {
+            enabled: true,
+            partitions: Vec::new(),
+            ..self.chaos.clone()
+        })?;
+        let duration = Duration::from_millis(50);
+        injector.partition(
+            vec![
+                vec!["node-a".to_string(), "node-b".to_string()],
+                vec!["node-c".to_string()],
+            ],
+            duration,
+        );
+
+        let isolated = !injector.link_allowed("node-a", "node-c")
+            && !injector.link_allowed("node-c", "node-b");
+        let same_group = injector.link_allowed("node-a", "node-b");
+        std::thread::sleep(duration);
+        let healed = injector.link_allowed("node-a", "node-c");
+
+        Ok((
+            AssertionResult::new(
+                NAME,
+                isolated && same_group && healed,
+                format!(
+                    "cross-partition blocked: {}, same group reachable: {}, healed: {}",
+                    isolated, same_group, healed
+                ),
+            ),
+            injector.events(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chaos_requires_enabled_config() {
+        assert!(ChaosInjector::new(ChaosConfig::default()).is_err());
+        assert!(ChaosInjector::new(ChaosConfig {
+            enabled: true,
+            message_drop_rate: 1.5,
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_delivery_outside_chaos_mode_skips_circuit_breakers() {
+        let system = RealTimeCoordinationSystem::with_advanced_config(
+            SystemConfig::default(),
+            AdvancedCoordinationConfig {
+                enable_fault_tolerance: true,
+                fault_tolerance_config: FaultToleranceConfig {
+                    circuit_breaker_threshold: 1,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        );
+        FaultToleranceSuite::register(&system, "sender")
+            .await
+            .unwrap();
+        for _ in 0..3 {
+            system
+                .send_message(FaultToleranceSuite::message("sender", "ghost"))
+                .await
+                .unwrap();
+        }
+        assert!(system.can_agent_execute("ghost").await);
+    }
+
+    #[tokio::test]
+    async fn test_fault_tolerance_suite_passes() {
+        let mut suite = FaultToleranceSuite::new(
+            FaultToleranceConfig {
+                max_retry_attempts: 2,
+                retry_delay_ms: 1,
+                circuit_breaker_threshold: 3,
+                circuit_breaker_timeout_seconds: 1,
+                ..Default::default()
+            },
+            ChaosConfig {
+                enabled: true,
+                seed: 7,
+                message_drop_rate: 0.4,
+                message_delay_rate: 0.1,
+                max_message_delay_ms: 5,
+                heartbeat_delay_rate: 0.5,
+                heartbeat_delay_ms: 300,
+                ..Default::default()
+            },
+        );
+        suite.coordination.agent_timeout_seconds = 1;
+
+        let report = suite.run().await.unwrap();
+        for assertion in &report.assertions {
+            assert_eq!(
+                assertion.status,
+                AssertionStatus::Passed,
+                "{}: {}",
+                assertion.name,
+                assertion.detail
+            );
+        }
+        assert!(report.faults["message_dropped"] > 0);
+        assert!(report.faults["agent_crashed"] >= 2);
+    }
+}
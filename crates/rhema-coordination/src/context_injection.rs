@@ -1,3 +1,4 @@
+use rhema_core::prompt_guard::{PromptGuard, PromptGuardConfig};
 use rhema_core::schema::{PromptInjectionMethod, PromptPattern};
 use rhema_core::{RhemaError, RhemaResult};
 use serde::{Deserialize, Serialize};
@@ -26,6 +27,28 @@ pub enum TaskType {
     Custom(String),
 }
 
+impl TaskType {
+    /// Operation name used when checking scope AI policies
+    pub fn operation_name(&self) -> String {
+        match self {
+            TaskType::CodeReview => "code_review".to_string(),
+            TaskType::BugFix => "bug_fix".to_string(),
+            TaskType::FeatureDevelopment => "feature_development".to_string(),
+            TaskType::Testing => "testing".to_string(),
+            TaskType::Documentation => "documentation".to_string(),
+            TaskType::Refactoring => "refactoring".to_string(),
+            TaskType::SecurityReview => "security_review".to_string(),
+            TaskType::PerformanceOptimization => "performance_optimization".to_string(),
+            TaskType::DependencyUpdate => "dependency_update".to_string(),
+            TaskType::Deployment => "deployment".to_string(),
+            TaskType::LockFileManagement => "lock_file_management".to_string(),
+            TaskType::DependencyResolution => "dependency_resolution".to_string(),
+            TaskType::ConflictResolution => "conflict_resolution".to_string(),
+            TaskType::Custom(name) => format!("custom:{}", name),
+        }
+    }
+}
+
 /// Context injection rule based on task type
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContextInjectionRule {
@@ -116,6 +139,69 @@ pub struct ContextLearningMetrics {
     pub optimization_suggestions: Vec<String>,
 }
 
+/// Token usage of one `## ` section of assembled context
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SectionBudget {
+    /// Section heading, or `(preamble)` for text before the first heading
+    pub title: String,
+    /// Estimated tokens before the budget was applied
+    pub tokens: usize,
+    /// Estimated tokens kept in the final context
+    pub included_tokens: usize,
+    /// Relevance score used to rank the section for dropping
+    pub score: f64,
+}
+
+/// Why part of the context did not make it into the final prompt
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TruncationReason {
+    /// Whole section dropped because it ranked lowest while over budget
+    LowRelevance,
+    /// Section kept but cut at a line boundary to fit the remaining budget
+    PartiallyTruncated,
+}
+
+/// A section that was dropped or shortened to fit the token budget
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DroppedContextEntry {
+    /// Section heading
+    pub section: String,
+    /// Estimated tokens removed
+    pub tokens: usize,
+    /// Relevance score of the section
+    pub score: f64,
+    /// Why it was removed
+    pub reason: TruncationReason,
+}
+
+/// Token budget report for one context optimization pass
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ContextBudgetReport {
+    /// Configured token budget
+    pub max_tokens: usize,
+    /// Estimated tokens before the budget was applied
+    pub total_tokens: usize,
+    /// Estimated tokens in the final context
+    pub used_tokens: usize,
+    /// Per-section token usage, in document order
+    pub sections: Vec<SectionBudget>,
+    /// Sections dropped or shortened, in the order they were removed
+    pub dropped: Vec<DroppedContextEntry>,
+}
+
+impl ContextBudgetReport {
+    /// Whether anything was removed to fit the budget
+    pub fn is_truncated(&self) -> bool {
+        !self.dropped.is_empty()
+    }
+
+    /// Tokens over budget before truncation, zero when the context fit
+    pub fn overflow_tokens(&self) -> usize {
+        self.total_tokens.saturating_sub(self.max_tokens)
+    }
+}
+
 /// Context optimization configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContextOptimizationConfig {
@@ -131,6 +217,40 @@ pub struct ContextOptimizationConfig {
     pub enable_relevance_filtering: bool,
     /// Cache TTL in seconds
     pub cache_ttl_seconds: u64,
+    /// Fail instead of truncating when context exceeds `max_tokens`
+    #[serde(default)]
+    pub strict_budget: bool,
+}
+
+impl ContextOptimizationConfig {
+    /// Optimization settings for a scope AI policy context profile
+    pub fn for_profile(profile: &str) -> Option<Self> {
+        let standard = Self {
+            max_tokens: 4000,
+            min_relevance_score: 0.7,
+            enable_semantic_compression: true,
+            enable_structure_optimization: true,
+            enable_relevance_filtering: true,
+            cache_ttl_seconds: 3600, // 1 hour
+            strict_budget: false,
+        };
+        match profile {
+            "minimal" => Some(Self {
+                max_tokens: 1000,
+                min_relevance_score: 0.85,
+                ..standard
+            }),
+            "standard" => Some(standard),
+            "full" => Some(Self {
+                max_tokens: 16000,
+                min_relevance_score: 0.0,
+                enable_semantic_compression: false,
+                enable_relevance_filtering: false,
+                ..standard
+            }),
+            _ => None,
+        }
+    }
 }
 
 /// Enhanced context injector with task detection and lock file support
@@ -143,41 +263,43 @@ pub struct EnhancedContextInjector {
     learning_metrics: Arc<RwLock<Vec<ContextLearningMetrics>>>,
     optimization_config: ContextOptimizationConfig,
     cache_ttl: Duration,
+    last_budget_report: Arc<RwLock<Option<ContextBudgetReport>>>,
+    prompt_guard: Arc<PromptGuard>,
 }
 
 impl EnhancedContextInjector {
     /// Create a new enhanced context injector
     pub fn new(scope_path: PathBuf) -> Self {
-        let default_rules = Self::get_default_injection_rules();
-        let lock_file_path = scope_path.join("rhema.lock");
+        // The scope's AI policy selects the context profile
+        let profile = match rhema_core::ai_policy::resolve_ai_policy(&scope_path) {
+            Ok(policy) => policy.context_profile,
+            Err(e) => {
+                tracing::warn!("Failed to resolve AI policy for {:?}: {}", scope_path, e);
+                "standard".to_string()
+            }
+        };
+        let config = ContextOptimizationConfig::for_profile(&profile)
+            .or_else(|| ContextOptimizationConfig::for_profile("standard"))
+            .expect("standard context profile is always defined");
 
-        Self {
-            scope_path,
-            injection_rules: default_rules,
-            lock_file_path: if lock_file_path.exists() {
-                Some(lock_file_path)
-            } else {
-                None
-            },
-            // Initialize enhancement features
-            context_cache: Arc::new(RwLock::new(HashMap::new())),
-            learning_metrics: Arc::new(RwLock::new(Vec::new())),
-            optimization_config: ContextOptimizationConfig {
-                max_tokens: 4000,
-                min_relevance_score: 0.7,
-                enable_semantic_compression: true,
-                enable_structure_optimization: true,
-                enable_relevance_filtering: true,
-                cache_ttl_seconds: 3600, // 1 hour
-            },
-            cache_ttl: Duration::from_secs(3600),
-        }
+        Self::with_config(scope_path, config)
     }
 
     /// Create a new enhanced context injector with custom optimization config
     pub fn with_config(scope_path: PathBuf, config: ContextOptimizationConfig) -> Self {
         let default_rules = Self::get_default_injection_rules();
         let lock_file_path = scope_path.join("rhema.lock");
+        let prompt_guard = rhema_core::utils::find_repo_root_from(&scope_path)
+            .and_then(|repo_root| PromptGuard::open(&repo_root))
+            .or_else(|e| {
+                tracing::warn!(
+                    "Failed to open the prompt guard for {:?}: {}",
+                    scope_path,
+                    e
+                );
+                PromptGuard::new(PromptGuardConfig::default())
+            })
+            .expect("default prompt guard config is valid");
 
         Self {
             scope_path,
@@ -191,6 +313,8 @@ impl EnhancedContextInjector {
             learning_metrics: Arc::new(RwLock::new(Vec::new())),
             optimization_config: config.clone(),
             cache_ttl: Duration::from_secs(config.cache_ttl_seconds),
+            last_budget_report: Arc::new(RwLock::new(None)),
+            prompt_guard: Arc::new(prompt_guard),
         }
     }
 
@@ -278,6 +402,18 @@ impl EnhancedContextInjector {
 
     /// 2. Context Optimization - Optimize injected context for better AI consumption
     pub async fn optimize_context(&self, context: &str) -> RhemaResult<String> {
+        let (optimized, _) = self.optimize_context_with_report(context).await?;
+        Ok(optimized)
+    }
+
+    /// Optimize context and return the token budget report for the pass.
+    ///
+    /// In strict budget mode an over-budget context is an error instead of
+    /// being truncated.
+    pub async fn optimize_context_with_report(
+        &self,
+        context: &str,
+    ) -> RhemaResult<(String, ContextBudgetReport)> {
         let mut optimized = context.to_string();
 
         // Apply semantic compression if enabled
@@ -296,9 +432,15 @@ impl EnhancedContextInjector {
         }
 
         // Ensure token limit compliance
-        optimized = self.ensure_token_limit(&optimized)?;
+        let (optimized, report) = self.ensure_token_limit(&optimized)?;
+        *self.last_budget_report.write().await = Some(report.clone());
 
-        Ok(optimized)
+        Ok((optimized, report))
+    }
+
+    /// Budget report from the most recent optimization pass
+    pub async fn last_budget_report(&self) -> Option<ContextBudgetReport> {
+        self.last_budget_report.read().await.clone()
     }
 
     /// 3. Context Learning - Learn from context usage patterns to improve future injections
@@ -765,6 +907,20 @@ impl EnhancedContextInjector {
             let file_path = self.scope_path.join(file_name);
             if file_path.exists() {
                 if let Ok(content) = std::fs::read_to_string(&file_path) {
+                    // Entries still awaiting review are never injected
+                    let content = rhema_core::review::reviewed_content(&content);
+                    // Nor are entries that look like prompt injection
+                    let source = file_path.display().to_string();
+                    let (content, quarantined) =
+                        self.prompt_guard.screened_content(&source, &content);
+                    for record in quarantined {
+                        tracing::warn!(
+                            "Quarantined entry {} in {}: {}",
+                            record.entry_id,
+                            record.source,
+                            record.reason()
+                        );
+                    }
                     context.push_str(&format!("## {}\n\n{}\n\n", file_name, content));
                 }
             }
@@ -1010,31 +1166,141 @@ impl EnhancedContextInjector {
         Ok(relevant_lines.join("\n"))
     }
 
-    /// Ensure context stays within token limit
-    fn ensure_token_limit(&self, context: &str) -> RhemaResult<String> {
-        // Simple token estimation (rough approximation)
-        let estimated_tokens = context.split_whitespace().count() + context.lines().count();
+    /// Ensure context stays within token limit.
+    ///
+    /// Sections are dropped lowest relevance first (later sections first on
+    /// ties). If the last remaining section still does not fit it is cut at a
+    /// line boundary.
+    fn ensure_token_limit(&self, context: &str) -> RhemaResult<(String, ContextBudgetReport)> {
+        let max_tokens = self.optimization_config.max_tokens;
+        let sections = Self::split_sections(context);
+
+        let mut report = ContextBudgetReport {
+            max_tokens,
+            ..Default::default()
+        };
+        for (title, lines) in &sections {
+            let tokens = lines
+                .iter()
+                .map(|line| Self::estimate_line_tokens(line))
+                .sum();
+            report.total_tokens += tokens;
+            report.sections.push(SectionBudget {
+                title: title.clone(),
+                tokens,
+                included_tokens: tokens,
+                score: self.calculate_relevance_score(&lines.join("\n")),
+            });
+        }
+
+        if report.total_tokens <= max_tokens {
+            report.used_tokens = report.total_tokens;
+            return Ok((context.to_string(), report));
+        }
 
-        if estimated_tokens > self.optimization_config.max_tokens {
-            // Truncate context while preserving structure
-            let lines: Vec<&str> = context.lines().collect();
-            let mut truncated = Vec::new();
-            let mut token_count = 0;
+        if self.optimization_config.strict_budget {
+            return Err(RhemaError::ContextError(format!(
+                "Context needs ~{} tokens but the budget is {} ({} sections)",
+                report.total_tokens,
+                max_tokens,
+                report.sections.len()
+            )));
+        }
 
+        // Drop whole sections until the rest fits
+        let mut kept: Vec<bool> = vec![true; sections.len()];
+        let mut used = report.total_tokens;
+        while used > max_tokens && kept.iter().filter(|k| **k).count() > 1 {
+            let victim = (0..sections.len())
+                .filter(|i| kept[*i])
+                .min_by(|a, b| {
+                    let (sa, sb) = (report.sections[*a].score, report.sections[*b].score);
+                    sa.partial_cmp(&sb)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                        .then(b.cmp(a))
+                })
+                .expect("at least two sections are kept");
+            kept[victim] = false;
+            let section = &mut report.sections[victim];
+            used -= section.tokens;
+            section.included_tokens = 0;
+            report.dropped.push(DroppedContextEntry {
+                section: section.title.clone(),
+                tokens: section.tokens,
+                score: section.score,
+                reason: TruncationReason::LowRelevance,
+            });
+        }
+
+        let mut output = Vec::new();
+        for (index, (_, lines)) in sections.iter().enumerate() {
+            if !kept[index] {
+                continue;
+            }
+            if used <= max_tokens {
+                output.extend(lines.iter().copied());
+                continue;
+            }
+
+            // Single remaining section is still too large
+            let mut section_tokens = 0;
             for line in lines {
-                let line_tokens = line.split_whitespace().count() + 1;
-                if token_count + line_tokens > self.optimization_config.max_tokens {
-                    truncated.push("... (truncated)");
+                let line_tokens = Self::estimate_line_tokens(line);
+                if section_tokens + line_tokens > max_tokens {
+                    output.push("... (truncated)");
                     break;
                 }
-                truncated.push(line);
-                token_count += line_tokens;
+                output.push(*line);
+                section_tokens += line_tokens;
             }
+            let section = &mut report.sections[index];
+            report.dropped.push(DroppedContextEntry {
+                section: section.title.clone(),
+                tokens: section.tokens - section_tokens,
+                score: section.score,
+                reason: TruncationReason::PartiallyTruncated,
+            });
+            section.included_tokens = section_tokens;
+            used = section_tokens;
+        }
+        report.used_tokens = used;
+
+        tracing::warn!(
+            "Context exceeded token budget ({} > {}); removed {}",
+            report.total_tokens,
+            max_tokens,
+            report
+                .dropped
+                .iter()
+                .map(|d| format!(
+                    "'{}' ({} tokens, score {:.2})",
+                    d.section, d.tokens, d.score
+                ))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
 
-            Ok(truncated.join("\n"))
-        } else {
-            Ok(context.to_string())
+        Ok((output.join("\n"), report))
+    }
+
+    /// Split context into `## ` sections, keeping lines in document order
+    fn split_sections(context: &str) -> Vec<(String, Vec<&str>)> {
+        let mut sections: Vec<(String, Vec<&str>)> = Vec::new();
+        for line in context.lines() {
+            if let Some(title) = line.strip_prefix("## ") {
+                sections.push((title.trim().to_string(), vec![line]));
+            } else if let Some((_, lines)) = sections.last_mut() {
+                lines.push(line);
+            } else {
+                sections.push(("(preamble)".to_string(), vec![line]));
+            }
         }
+        sections
+    }
+
+    /// Rough token estimate for one line: words plus the line break
+    fn estimate_line_tokens(line: &str) -> usize {
+        line.split_whitespace().count() + 1
     }
 
     /// Validate context schema
@@ -1553,6 +1819,75 @@ mod tests {
         // Clean up
         std::fs::remove_dir_all(temp_dir).unwrap();
     }
+
+    fn budget_config(max_tokens: usize, strict_budget: bool) -> ContextOptimizationConfig {
+        ContextOptimizationConfig {
+            max_tokens,
+            min_relevance_score: 0.0,
+            enable_semantic_compression: false,
+            enable_structure_optimization: false,
+            enable_relevance_filtering: false,
+            cache_ttl_seconds: 60,
+            strict_budget,
+        }
+    }
+
+    const BUDGET_CONTEXT: &str = "## Code\nfn main() {}\nuse std::io;\n## Notes\nsome loose prose here\nmore prose that is not code\n## Tail\nfn tail() {}\nimpl Tail {}";
+
+    #[tokio::test]
+    async fn test_budget_report_drops_lowest_relevance_section() {
+        let injector =
+            EnhancedContextInjector::with_config(std::env::temp_dir(), budget_config(14, false));
+
+        let (optimized, report) = injector
+            .optimize_context_with_report(BUDGET_CONTEXT)
+            .await
+            .unwrap();
+
+        assert_eq!(report.total_tokens, 36);
+        assert_eq!(report.sections.len(), 3);
+        assert!(report.is_truncated());
+        assert_eq!(report.dropped[0].section, "Notes");
+        assert_eq!(report.dropped[0].reason, TruncationReason::LowRelevance);
+        assert!(report.used_tokens <= 14);
+        assert!(!optimized.contains("## Notes"));
+        assert!(optimized.contains("## Code"));
+        assert_eq!(injector.last_budget_report().await, Some(report));
+    }
+
+    #[tokio::test]
+    async fn test_budget_report_truncates_single_section() {
+        let injector =
+            EnhancedContextInjector::with_config(std::env::temp_dir(), budget_config(4, false));
+
+        let (optimized, report) = injector
+            .optimize_context_with_report("## Only\nfn a() {}\nfn b() {}")
+            .await
+            .unwrap();
+
+        assert!(optimized.ends_with("... (truncated)"));
+        assert_eq!(report.dropped.len(), 1);
+        assert_eq!(
+            report.dropped[0].reason,
+            TruncationReason::PartiallyTruncated
+        );
+        assert_eq!(report.sections[0].included_tokens, report.used_tokens);
+    }
+
+    #[tokio::test]
+    async fn test_strict_budget_fails_instead_of_truncating() {
+        let injector =
+            EnhancedContextInjector::with_config(std::env::temp_dir(), budget_config(14, true));
+
+        assert!(injector.optimize_context(BUDGET_CONTEXT).await.is_err());
+
+        let (optimized, report) = injector
+            .optimize_context_with_report("## Code\nfn main() {}")
+            .await
+            .unwrap();
+        assert_eq!(optimized, "## Code\nfn main() {}");
+        assert!(!report.is_truncated());
+    }
 }
 
 /// Example usage of the enhanced context injection system
@@ -1565,6 +1900,7 @@ pub async fn example_usage() -> RhemaResult<()> {
         enable_structure_optimization: true,
         enable_relevance_filtering: true,
         cache_ttl_seconds: 1800, // 30 minutes
+        strict_budget: false,
     };
 
     let scope_path = PathBuf::from(".");
@@ -499,6 +499,7 @@ mod tests {
             is_online: true,
             performance_metrics:
                 crate::agent::real_time_coordination::AgentPerformanceMetrics::default(),
+            liveness: Default::default(),
         };
 
         let result = integration.register_rhema_agent(&rhema_agent).await;
@@ -0,0 +1,314 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::locking::{DistributedLockBackend, LockLease};
+use rhema_core::{RhemaError, RhemaResult};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// File in the write root recording the highest fencing token that wrote to it
+pub const FENCE_FILE: &str = ".rhema-fence";
+
+/// File in the write root held exclusively while a commit checks the fence
+/// and writes
+pub const FENCE_LOCK_FILE: &str = ".rhema-fence.lock";
+
+/// Age after which a fence lock left behind by a crashed writer is broken
+const STALE_FENCE_LOCK: Duration = Duration::from_secs(30);
+
+/// Batches file writes under a lock lease and commits them only while the
+/// lease's fencing token is still current.
+///
+/// A commit creates [`FENCE_LOCK_FILE`] with `create_new`, so commits to the
+/// same root never interleave. While holding it, the commit checks that the
+/// lock backend has not issued a newer token for the key and that the fence
+/// file does not record a newer token, records its own token and writes the
+/// batch. Once a newer holder has committed, every commit of an older token
+/// is rejected, even one whose backend check passed before the lease was
+/// handed over. The guarantee covers writers sharing the root's filesystem.
+pub struct FencedBatchWriter {
+    backend: Arc<dyn DistributedLockBackend>,
+    lease: LockLease,
+    root: PathBuf,
+    pending: Vec<(PathBuf, Vec<u8>)>,
+}
+
+impl FencedBatchWriter {
+    pub fn new(backend: Arc<dyn DistributedLockBackend>, lease: LockLease, root: PathBuf) -> Self {
+        Self {
+            backend,
+            lease,
+            root,
+            pending: Vec::new(),
+        }
+    }
+
+    pub fn lease(&self) -> &LockLease {
+        &self.lease
+    }
+
+    /// Replace the lease after a renewal
+    pub fn set_lease(&mut self, lease: LockLease) {
+        self.lease = lease;
+    }
+
+    /// Queue a write. Relative paths are resolved against the write root and
+    /// paths outside it are rejected.
+    pub fn stage(
+        &mut self,
+        path: impl AsRef<Path>,
+        contents: impl Into<Vec<u8>>,
+    ) -> RhemaResult<()> {
+        let path = path.as_ref();
+        let path = if path.is_relative() {
+            self.root.join(path)
+        } else {
+            path.to_path_buf()
+        };
+        if !path.starts_with(&self.root) || path.components().any(|c| c.as_os_str() == "..") {
+            return Err(RhemaError::InvalidInput(format!(
+                "{} is outside the write root {}",
+                path.display(),
+                self.root.display()
+            )));
+        }
+
+        self.pending.push((path, contents.into()));
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Write all staged files, returning how many were written
+    pub async fn commit(&mut self) -> RhemaResult<usize> {
+        if self.pending.is_empty() {
+            return Ok(0);
+        }
+        let _lock = FenceLock::acquire(&self.root, &self.lease).await?;
+        self.check_fence().await?;
+
+        let written = self.pending.len();
+        for (path, contents) in self.pending.drain(..) {
+            write_atomic(&path, &contents)?;
+        }
+        Ok(written)
+    }
+
+    /// Drop all staged writes
+    pub fn discard(&mut self) {
+        self.pending.clear();
+    }
+
+    async fn check_fence(&self) -> RhemaResult<()> {
+        let token = self.lease.fencing_token;
+        if self.lease.is_expired() {
+            return Err(RhemaError::LockError(format!(
+                "Lease on {} (token {}) has expired",
+                self.lease.key, token
+            )));
+        }
+
+        let current = self.backend.current_token(&self.lease.key).await?;
+        if current != token {
+            return Err(RhemaError::LockError(format!(
+                "Lease on {} is stale: token {} superseded by {}",
+                self.lease.key, token, current
+            )));
+        }
+
+        let fence_path = self.root.join(FENCE_FILE);
+        let recorded = match std::fs::read_to_string(&fence_path) {
+            Ok(content) => content.trim().parse::<u64>().unwrap_or(0),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => 0,
+            Err(e) => return Err(e.into()),
+        };
+        if recorded > token {
+            return Err(RhemaError::LockError(format!(
+                "Write to {} rejected: token {} is older than recorded token {}",
+                self.root.display(),
+                token,
+                recorded
+            )));
+        }
+        if recorded < token {
+            write_atomic(&fence_path, token.to_string().as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+/// Exclusive hold on a write root, released on drop
+struct FenceLock {
+    path: PathBuf,
+}
+
+impl FenceLock {
+    /// Wait for the root's lock file, giving up once the lease has expired
+    async fn acquire(root: &Path, lease: &LockLease) -> RhemaResult<Self> {
+        std::fs::create_dir_all(root)?;
+        let path = root.join(FENCE_LOCK_FILE);
+        loop {
+            match std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(mut file) => {
+                    writeln!(file, "{} {}", lease.holder, lease.fencing_token)?;
+                    return Ok(Self { path });
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                    let stale = std::fs::metadata(&path)
+                        .and_then(|metadata| metadata.modified())
+                        .ok()
+                        .and_then(|modified| modified.elapsed().ok())
+                        .is_some_and(|age| age > STALE_FENCE_LOCK);
+                    if stale {
+                        let _ = std::fs::remove_file(&path);
+                        continue;
+                    }
+                    if lease.is_expired() {
+                        return Err(RhemaError::LockError(format!(
+                            "Lease on {} (token {}) expired while waiting for {}",
+                            lease.key,
+                            lease.fencing_token,
+                            path.display()
+                        )));
+                    }
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+}
+
+impl Drop for FenceLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Write via a temporary sibling and rename so readers never see partial files
+fn write_atomic(path: &Path, contents: &[u8]) -> RhemaResult<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let temp = path.with_file_name(format!(".{}.tmp-{}", file_name, std::process::id()));
+    std::fs::write(&temp, contents)?;
+    std::fs::rename(&temp, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributed::locking::InMemoryLockBackend;
+
+    #[tokio::test]
+    async fn test_stale_writer_is_fenced_off() -> RhemaResult<()> {
+        let temp = tempfile::TempDir::new()?;
+        let backend: Arc<dyn DistributedLockBackend> = Arc::new(InMemoryLockBackend::new());
+        let ttl = Duration::from_secs(30);
+
+        let old_lease = backend.acquire("scope", "daemon-1", ttl).await?.unwrap();
+        let mut old_writer = FencedBatchWriter::new(
+            backend.clone(),
+            old_lease.clone(),
+            temp.path().to_path_buf(),
+        );
+        old_writer.stage("todos.yaml", "todos: []\n")?;
+        assert_eq!(old_writer.commit().await?, 1);
+
+        // daemon-1 stalls, its lease is released and handed to daemon-2
+        backend.release(&old_lease).await?;
+        let new_lease = backend.acquire("scope", "daemon-2", ttl).await?.unwrap();
+        let mut new_writer =
+            FencedBatchWriter::new(backend.clone(), new_lease, temp.path().to_path_buf());
+        new_writer.stage("todos.yaml", "todos:\n  - id: a\n")?;
+        new_writer.commit().await?;
+
+        old_writer.stage("todos.yaml", "todos: []\n")?;
+        assert!(old_writer.commit().await.is_err());
+        assert_eq!(
+            std::fs::read_to_string(temp.path().join("todos.yaml"))?,
+            "todos:\n  - id: a\n"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_paths_outside_root_are_rejected() -> RhemaResult<()> {
+        let temp = tempfile::TempDir::new()?;
+        let backend: Arc<dyn DistributedLockBackend> = Arc::new(InMemoryLockBackend::new());
+        let lease = backend
+            .acquire("scope", "daemon-1", Duration::from_secs(30))
+            .await?
+            .unwrap();
+        let mut writer = FencedBatchWriter::new(backend, lease, temp.path().to_path_buf());
+
+        assert!(writer.stage("../escape.yaml", "x").is_err());
+        assert!(writer.stage("/etc/passwd", "x").is_err());
+        assert!(writer.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_commits_wait_for_the_fence_lock() -> RhemaResult<()> {
+        let temp = tempfile::TempDir::new()?;
+        let backend: Arc<dyn DistributedLockBackend> = Arc::new(InMemoryLockBackend::new());
+        let lease = backend
+            .acquire("scope", "daemon-1", Duration::from_millis(200))
+            .await?
+            .unwrap();
+        let lock_path = temp.path().join(FENCE_LOCK_FILE);
+        std::fs::write(&lock_path, "daemon-2 7\n")?;
+
+        // Another writer holds the root for longer than the lease lasts
+        let mut writer =
+            FencedBatchWriter::new(backend.clone(), lease, temp.path().to_path_buf());
+        writer.stage("todos.yaml", "todos: []\n")?;
+        assert!(writer.commit().await.is_err());
+        assert!(!temp.path().join("todos.yaml").exists());
+
+        // A lock left behind by a crashed writer is broken
+        let stale = std::time::SystemTime::now() - STALE_FENCE_LOCK * 2;
+        std::fs::File::options()
+            .write(true)
+            .open(&lock_path)?
+            .set_modified(stale)?;
+        let lease = backend
+            .acquire("scope", "daemon-1", Duration::from_secs(30))
+            .await?
+            .unwrap();
+        writer.set_lease(lease);
+        assert_eq!(writer.commit().await?, 1);
+        assert!(!lock_path.exists());
+        Ok(())
+    }
+}
@@ -0,0 +1,406 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Lock backends shared by every daemon and CI job working on a repository.
+//!
+//! Each successful acquisition is issued a fencing token that increases
+//! monotonically per key. Writers pass the token along with their writes so
+//! storage can reject a holder whose lease expired and was re-issued while it
+//! was paused (see [`FencedBatchWriter`](super::fenced_writer::FencedBatchWriter)).
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rhema_core::{RhemaError, RhemaResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// A held lock together with its fencing token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockLease {
+    pub key: String,
+    pub holder: String,
+    pub fencing_token: u64,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl LockLease {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now()
+    }
+}
+
+/// Backend providing mutually exclusive, expiring locks
+#[async_trait]
+pub trait DistributedLockBackend: Send + Sync {
+    /// Backend name for logging and diagnostics
+    fn name(&self) -> &'static str;
+
+    /// Try to acquire `key` for `holder`. Returns `None` when another holder
+    /// owns the lock. Re-acquiring a lock already held by `holder` extends it
+    /// and keeps its fencing token.
+    async fn acquire(
+        &self,
+        key: &str,
+        holder: &str,
+        ttl: Duration,
+    ) -> RhemaResult<Option<LockLease>>;
+
+    /// Extend a lease. Returns `None` when the lease has been lost.
+    async fn renew(&self, lease: &LockLease, ttl: Duration) -> RhemaResult<Option<LockLease>>;
+
+    /// Release a lease, returning whether it was still held
+    async fn release(&self, lease: &LockLease) -> RhemaResult<bool>;
+
+    /// Highest fencing token issued for `key` (0 if never locked)
+    async fn current_token(&self, key: &str) -> RhemaResult<u64>;
+}
+
+/// Lock backend configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LockBackendConfig {
+    /// Locks local to this process; suitable for a single daemon
+    #[default]
+    InMemory,
+    /// Locks stored in Redis and shared between processes and hosts
+    Redis {
+        url: String,
+        #[serde(default = "default_key_prefix")]
+        key_prefix: String,
+    },
+}
+
+fn default_key_prefix() -> String {
+    "rhema:lock".to_string()
+}
+
+impl LockBackendConfig {
+    /// Create the configured backend
+    pub async fn build(&self) -> RhemaResult<Arc<dyn DistributedLockBackend>> {
+        match self {
+            LockBackendConfig::InMemory => Ok(Arc::new(InMemoryLockBackend::new())),
+            LockBackendConfig::Redis { url, key_prefix } => Ok(Arc::new(
+                RedisLockBackend::connect(url, key_prefix.clone()).await?,
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct HeldLock {
+    holder: String,
+    fencing_token: u64,
+    expires_at: DateTime<Utc>,
+}
+
+/// In-process lock backend
+#[derive(Debug, Default)]
+pub struct InMemoryLockBackend {
+    state: Mutex<InMemoryState>,
+}
+
+#[derive(Debug, Default)]
+struct InMemoryState {
+    locks: HashMap<String, HeldLock>,
+    tokens: HashMap<String, u64>,
+}
+
+impl InMemoryLockBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, InMemoryState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[async_trait]
+impl DistributedLockBackend for InMemoryLockBackend {
+    fn name(&self) -> &'static str {
+        "in_memory"
+    }
+
+    async fn acquire(
+        &self,
+        key: &str,
+        holder: &str,
+        ttl: Duration,
+    ) -> RhemaResult<Option<LockLease>> {
+        let expires_at = expiry(ttl)?;
+        let mut state = self.state();
+        let now = Utc::now();
+
+        if let Some(held) = state.locks.get_mut(key) {
+            if held.expires_at > now {
+                if held.holder != holder {
+                    return Ok(None);
+                }
+                held.expires_at = expires_at;
+                return Ok(Some(LockLease {
+                    key: key.to_string(),
+                    holder: holder.to_string(),
+                    fencing_token: held.fencing_token,
+                    expires_at,
+                }));
+            }
+        }
+
+        let token = state.tokens.entry(key.to_string()).or_insert(0);
+        *token += 1;
+        let fencing_token = *token;
+        state.locks.insert(
+            key.to_string(),
+            HeldLock {
+                holder: holder.to_string(),
+                fencing_token,
+                expires_at,
+            },
+        );
+
+        Ok(Some(LockLease {
+            key: key.to_string(),
+            holder: holder.to_string(),
+            fencing_token,
+            expires_at,
+        }))
+    }
+
+    async fn renew(&self, lease: &LockLease, ttl: Duration) -> RhemaResult<Option<LockLease>> {
+        let expires_at = expiry(ttl)?;
+        let mut state = self.state();
+        match state.locks.get_mut(&lease.key) {
+            Some(held)
+                if held.fencing_token == lease.fencing_token && held.expires_at > Utc::now() =>
+            {
+                held.expires_at = expires_at;
+                Ok(Some(LockLease {
+                    expires_at,
+                    ..lease.clone()
+                }))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    async fn release(&self, lease: &LockLease) -> RhemaResult<bool> {
+        let mut state = self.state();
+        match state.locks.get(&lease.key) {
+            Some(held) if held.fencing_token == lease.fencing_token => {
+                state.locks.remove(&lease.key);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn current_token(&self, key: &str) -> RhemaResult<u64> {
+        Ok(self.state().tokens.get(key).copied().unwrap_or(0))
+    }
+}
+
+/// Acquire: re-entrant for the same holder, otherwise issue a new token
+const REDIS_ACQUIRE: &str = r#"
+local current = redis.call('GET', KEYS[1])
+if current then
+    local holder, token = string.match(current, '^(.*)|(%d+)$')
+    if holder ~= ARGV[1] then
+        return 0
+    end
+    redis.call('PEXPIRE', KEYS[1], ARGV[2])
+    return tonumber(token)
+end
+local token = redis.call('INCR', KEYS[2])
+redis.call('SET', KEYS[1], ARGV[1] .. '|' .. token, 'PX', ARGV[2])
+return token
+"#;
+
+/// Renew: only while the stored value still matches the lease
+const REDIS_RENEW: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('PEXPIRE', KEYS[1], ARGV[2])
+end
+return 0
+"#;
+
+/// Release: only while the stored value still matches the lease
+const REDIS_RELEASE: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('DEL', KEYS[1])
+end
+return 0
+"#;
+
+/// Lock backend stored in Redis.
+///
+/// Each key uses `<prefix>:<key>` for the lock (`holder|token`, with a TTL)
+/// and `<prefix>:<key>:fence` for the token counter, which never expires so
+/// tokens keep increasing across lease generations.
+pub struct RedisLockBackend {
+    connection: redis::aio::ConnectionManager,
+    key_prefix: String,
+}
+
+impl RedisLockBackend {
+    pub async fn connect(url: &str, key_prefix: String) -> RhemaResult<Self> {
+        let client = redis::Client::open(url)?;
+        let connection = redis::aio::ConnectionManager::new(client).await?;
+        Ok(Self {
+            connection,
+            key_prefix,
+        })
+    }
+
+    fn lock_key(&self, key: &str) -> String {
+        format!("{}:{}", self.key_prefix, key)
+    }
+
+    fn fence_key(&self, key: &str) -> String {
+        format!("{}:{}:fence", self.key_prefix, key)
+    }
+}
+
+#[async_trait]
+impl DistributedLockBackend for RedisLockBackend {
+    fn name(&self) -> &'static str {
+        "redis"
+    }
+
+    async fn acquire(
+        &self,
+        key: &str,
+        holder: &str,
+        ttl: Duration,
+    ) -> RhemaResult<Option<LockLease>> {
+        let expires_at = expiry(ttl)?;
+        let mut connection = self.connection.clone();
+        let token: u64 = redis::Script::new(REDIS_ACQUIRE)
+            .key(self.lock_key(key))
+            .key(self.fence_key(key))
+            .arg(holder)
+            .arg(ttl.as_millis() as u64)
+            .invoke_async(&mut connection)
+            .await?;
+
+        if token == 0 {
+            return Ok(None);
+        }
+        Ok(Some(LockLease {
+            key: key.to_string(),
+            holder: holder.to_string(),
+            fencing_token: token,
+            expires_at,
+        }))
+    }
+
+    async fn renew(&self, lease: &LockLease, ttl: Duration) -> RhemaResult<Option<LockLease>> {
+        let expires_at = expiry(ttl)?;
+        let mut connection = self.connection.clone();
+        let renewed: u64 = redis::Script::new(REDIS_RENEW)
+            .key(self.lock_key(&lease.key))
+            .arg(format!("{}|{}", lease.holder, lease.fencing_token))
+            .arg(ttl.as_millis() as u64)
+            .invoke_async(&mut connection)
+            .await?;
+
+        Ok((renewed == 1).then(|| LockLease {
+            expires_at,
+            ..lease.clone()
+        }))
+    }
+
+    async fn release(&self, lease: &LockLease) -> RhemaResult<bool> {
+        let mut connection = self.connection.clone();
+        let released: u64 = redis::Script::new(REDIS_RELEASE)
+            .key(self.lock_key(&lease.key))
+            .arg(format!("{}|{}", lease.holder, lease.fencing_token))
+            .invoke_async(&mut connection)
+            .await?;
+        Ok(released == 1)
+    }
+
+    async fn current_token(&self, key: &str) -> RhemaResult<u64> {
+        let mut connection = self.connection.clone();
+        let token: Option<u64> = redis::cmd("GET")
+            .arg(self.fence_key(key))
+            .query_async(&mut connection)
+            .await?;
+        Ok(token.unwrap_or(0))
+    }
+}
+
+fn expiry(ttl: Duration) -> RhemaResult<DateTime<Utc>> {
+    let ttl = chrono::Duration::from_std(ttl)
+        .map_err(|e| RhemaError::LockError(format!("Invalid lock TTL: {}", e)))?;
+    Ok(Utc::now() + ttl)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_in_memory_fencing_tokens_increase() -> RhemaResult<()> {
+        let backend = InMemoryLockBackend::new();
+        let ttl = Duration::from_secs(30);
+
+        let first = backend.acquire("scope-a", "daemon-1", ttl).await?.unwrap();
+        assert!(backend.acquire("scope-a", "daemon-2", ttl).await?.is_none());
+
+        // Re-entrant acquisition keeps the token
+        let again = backend.acquire("scope-a", "daemon-1", ttl).await?.unwrap();
+        assert_eq!(again.fencing_token, first.fencing_token);
+
+        assert!(backend.release(&first).await?);
+        let second = backend.acquire("scope-a", "daemon-2", ttl).await?.unwrap();
+        assert!(second.fencing_token > first.fencing_token);
+        assert_eq!(
+            backend.current_token("scope-a").await?,
+            second.fencing_token
+        );
+
+        // The stale lease can neither renew nor release the new one
+        assert!(backend.renew(&first, ttl).await?.is_none());
+        assert!(!backend.release(&first).await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_expired_lock_is_reissued() -> RhemaResult<()> {
+        let backend = InMemoryLockBackend::new();
+
+        let first = backend
+            .acquire("scope-a", "daemon-1", Duration::from_millis(1))
+            .await?
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        let second = backend
+            .acquire("scope-a", "daemon-2", Duration::from_secs(30))
+            .await?
+            .unwrap();
+        assert_eq!(second.fencing_token, first.fencing_token + 1);
+        assert!(backend
+            .renew(&first, Duration::from_secs(30))
+            .await?
+            .is_none());
+        Ok(())
+    }
+}
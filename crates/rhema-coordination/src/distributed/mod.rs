@@ -15,14 +15,20 @@
  */
 
 pub mod cluster_manager;
+pub mod fenced_writer;
 pub mod health_checker;
 pub mod load_balancer;
+pub mod locking;
 pub mod node_discovery;
 pub mod service_registry;
 
 pub use cluster_manager::ClusterManager;
+pub use fenced_writer::FencedBatchWriter;
 pub use health_checker::HealthChecker;
 pub use load_balancer::DistributedLoadBalancer;
+pub use locking::{
+    DistributedLockBackend, InMemoryLockBackend, LockBackendConfig, LockLease, RedisLockBackend,
+};
 pub use node_discovery::NodeDiscovery;
 pub use service_registry::ServiceRegistry;
 
@@ -31,6 +37,7 @@ use rhema_core::RhemaResult;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::net::SocketAddr;
+use std::sync::Arc;
 
 /// Distributed deployment configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,6 +54,9 @@ pub struct DistributedConfig {
     pub health_checking: HealthCheckingConfig,
     /// Service registry configuration
     pub service_registry: ServiceRegistryConfig,
+    /// Backend issuing locks and fencing tokens shared by every node
+    #[serde(default)]
+    pub lock_backend: LockBackendConfig,
 }
 
 /// Node configuration
@@ -277,6 +287,7 @@ impl Default for DistributedConfig {
                 registration_interval_seconds: 30,
                 deregistration_timeout_seconds: 60,
             },
+            lock_backend: LockBackendConfig::default(),
         }
     }
 }
@@ -389,6 +400,7 @@ pub struct DistributedManager {
     load_balancer: DistributedLoadBalancer,
     health_checker: HealthChecker,
     service_registry: ServiceRegistry,
+    lock_backend: Arc<dyn DistributedLockBackend>,
 }
 
 impl DistributedManager {
@@ -399,6 +411,7 @@ impl DistributedManager {
         let load_balancer = DistributedLoadBalancer::new(config.load_balancing.clone()).await?;
         let health_checker = HealthChecker::new(config.health_checking.clone()).await?;
         let service_registry = ServiceRegistry::new(config.service_registry.clone()).await?;
+        let lock_backend = config.lock_backend.build().await?;
 
         Ok(Self {
             config,
@@ -407,6 +420,7 @@ impl DistributedManager {
             load_balancer,
             health_checker,
             service_registry,
+            lock_backend,
         })
     }
 
@@ -434,6 +448,11 @@ impl DistributedManager {
         Ok(())
     }
 
+    /// Backend for locks and fencing tokens, as configured by `lock_backend`
+    pub fn lock_backend(&self) -> Arc<dyn DistributedLockBackend> {
+        self.lock_backend.clone()
+    }
+
     /// Get cluster health
     pub async fn get_cluster_health(&self) -> RhemaResult<ClusterHealth> {
         self.cluster_manager.get_health().await
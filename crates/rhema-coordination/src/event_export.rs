@@ -0,0 +1,593 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Bridge delivering the event outbox to external event buses.
+//!
+//! Each round reads the pending events of the [`EventOutbox`] and delivers
+//! every event to the sinks its type is routed to by the `event_export`
+//! config. Delivery progress per event and sink is kept in
+//! `.rhema/events/deliveries.json`; an event leaves the outbox once every
+//! sink accepted it. Failed deliveries are retried with exponential backoff
+//! and dead-lettered after `max_attempts`, until `retry_dead` is called.
+
+use async_nats::jetstream;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rhema_config::event_export::{EventExportConfig, EventSinkConfig};
+use rhema_core::events::{EventOutbox, ExportEvent};
+use rhema_core::{RhemaError, RhemaResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+use tracing::{info, warn};
+
+/// Delivery progress relative to the repository root
+pub const EVENT_DELIVERIES_FILE: &str = ".rhema/events/deliveries.json";
+
+/// Publishes events to one external system
+#[async_trait]
+pub trait EventSink: Send + Sync {
+    /// Succeeds only once the external system accepted the event
+    async fn deliver(&self, event: &ExportEvent) -> RhemaResult<()>;
+}
+
+/// Progress of one event towards one sink
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeliveryState {
+    pub attempts: u32,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delivered_at: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_attempt_at: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+
+    /// Gave up after `max_attempts` failures
+    #[serde(default)]
+    pub dead: bool,
+}
+
+/// Delivery states by event id and sink name
+type Deliveries = BTreeMap<String, BTreeMap<String, DeliveryState>>;
+
+/// Outcome of one delivery round
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct FlushReport {
+    pub delivered: usize,
+    pub failed: usize,
+    pub dead_lettered: usize,
+    /// Events still in the outbox
+    pub remaining: usize,
+}
+
+/// Undelivered events of one sink
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SinkBacklog {
+    pub pending: usize,
+    pub dead: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+/// Delivers exported events to the configured sinks
+pub struct EventExportBridge {
+    config: EventExportConfig,
+    outbox: EventOutbox,
+    deliveries_path: PathBuf,
+    sinks: HashMap<String, Arc<dyn EventSink>>,
+}
+
+impl EventExportBridge {
+    pub fn new(config: EventExportConfig, outbox: EventOutbox, deliveries_path: PathBuf) -> Self {
+        Self {
+            config,
+            outbox,
+            deliveries_path,
+            sinks: HashMap::new(),
+        }
+    }
+
+    /// Bridge for the repository at `repo_root`, with the configured sinks
+    pub fn open(repo_root: &Path) -> RhemaResult<Self> {
+        let config = EventExportConfig::load(repo_root)?;
+        let timeout = Duration::from_secs(config.timeout_secs);
+        let mut sinks: HashMap<String, Arc<dyn EventSink>> = HashMap::new();
+        for (name, sink) in &config.sinks {
+            sinks.insert(name.clone(), build_sink(sink, timeout)?);
+        }
+        let mut bridge = Self::new(
+            config,
+            EventOutbox::for_repository(repo_root),
+            repo_root.join(EVENT_DELIVERIES_FILE),
+        );
+        bridge.sinks = sinks;
+        Ok(bridge)
+    }
+
+    /// Deliver to `sink` under `name`, replacing any configured sink
+    pub fn with_sink(mut self, name: impl Into<String>, sink: Arc<dyn EventSink>) -> Self {
+        self.sinks.insert(name.into(), sink);
+        self
+    }
+
+    pub fn config(&self) -> &EventExportConfig {
+        &self.config
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Deliver every event that is due, once
+    pub async fn flush(&self) -> RhemaResult<FlushReport> {
+        let mut report = FlushReport::default();
+        let mut deliveries = self.load_deliveries()?;
+        let events = self.outbox.pending()?;
+        deliveries.retain(|id, _| events.iter().any(|event| &event.id == id));
+
+        for event in &events {
+            let sink_names = self.config.sinks_for(event.event_type);
+            for &sink_name in &sink_names {
+                let state = deliveries
+                    .entry(event.id.clone())
+                    .or_default()
+                    .entry(sink_name.to_string())
+                    .or_default();
+                if state.delivered_at.is_some()
+                    || state.dead
+                    || state.next_attempt_at.is_some_and(|at| at > Utc::now())
+                {
+                    continue;
+                }
+                let sink = self.sinks.get(sink_name).ok_or_else(|| {
+                    RhemaError::ConfigError(format!("No event sink named '{}'", sink_name))
+                })?;
+
+                match sink.deliver(event).await {
+                    Ok(()) => {
+                        state.delivered_at = Some(Utc::now());
+                        state.next_attempt_at = None;
+                        state.last_error = None;
+                        report.delivered += 1;
+                    }
+                    Err(e) => {
+                        state.attempts += 1;
+                        state.last_error = Some(e.to_string());
+                        if state.attempts >= self.config.max_attempts {
+                            state.dead = true;
+                            state.next_attempt_at = None;
+                            report.dead_lettered += 1;
+                            warn!(
+                                "Dead-lettered {} event {} for sink {}: {}",
+                                event.event_type, event.id, sink_name, e
+                            );
+                        } else {
+                            let backoff = self.config.backoff(state.attempts);
+                            state.next_attempt_at = Some(
+                                Utc::now()
+                                    + chrono::Duration::from_std(backoff)
+                                        .unwrap_or_else(|_| chrono::Duration::zero()),
+                            );
+                            report.failed += 1;
+                        }
+                    }
+                }
+                // Record progress per delivery so a crash repeats as few
+                // deliveries as possible
+                self.save_deliveries(&deliveries)?;
+            }
+
+            let delivered = sink_names.iter().all(|sink_name| {
+                deliveries
+                    .get(&event.id)
+                    .and_then(|states| states.get(*sink_name))
+                    .is_some_and(|state| state.delivered_at.is_some())
+            });
+            if delivered {
+                self.outbox.remove(event)?;
+                deliveries.remove(&event.id);
+            } else {
+                report.remaining += 1;
+            }
+        }
+
+        self.save_deliveries(&deliveries)?;
+        Ok(report)
+    }
+
+    /// Pending and dead-lettered events per sink
+    pub fn backlog(&self) -> RhemaResult<BTreeMap<String, SinkBacklog>> {
+        let deliveries = self.load_deliveries()?;
+        let mut backlog: BTreeMap<String, SinkBacklog> = self
+            .config
+            .sinks
+            .keys()
+            .map(|name| (name.clone(), SinkBacklog::default()))
+            .collect();
+        for event in self.outbox.pending()? {
+            for sink_name in self.config.sinks_for(event.event_type) {
+                let state = deliveries
+                    .get(&event.id)
+                    .and_then(|states| states.get(sink_name));
+                let entry = backlog.entry(sink_name.to_string()).or_default();
+                match state {
+                    Some(state) if state.delivered_at.is_some() => {}
+                    Some(state) if state.dead => entry.dead += 1,
+                    _ => entry.pending += 1,
+                }
+                if let Some(error) = state.and_then(|state| state.last_error.clone()) {
+                    entry.last_error = Some(error);
+                }
+            }
+        }
+        Ok(backlog)
+    }
+
+    /// Retry dead-lettered deliveries on the next round, returning how many
+    pub fn retry_dead(&self) -> RhemaResult<usize> {
+        let mut deliveries = self.load_deliveries()?;
+        let mut revived = 0;
+        for state in deliveries
+            .values_mut()
+            .flat_map(|states| states.values_mut())
+        {
+            if state.dead {
+                *state = DeliveryState::default();
+                revived += 1;
+            }
+        }
+        self.save_deliveries(&deliveries)?;
+        Ok(revived)
+    }
+
+    /// Deliver in the background every `flush_interval_secs` until the
+    /// handle is aborted
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let interval = Duration::from_secs(self.config.flush_interval_secs.max(1));
+        tokio::spawn(async move {
+            info!("Exporting events every {:?}", interval);
+            loop {
+                if let Err(e) = self.flush().await {
+                    warn!("Event export round failed: {}", e);
+                }
+                tokio::time::sleep(interval).await;
+            }
+        })
+    }
+
+    fn load_deliveries(&self) -> RhemaResult<Deliveries> {
+        if !self.deliveries_path.exists() {
+            return Ok(Deliveries::new());
+        }
+        let content = std::fs::read(&self.deliveries_path)?;
+        Ok(serde_json::from_slice(&content)?)
+    }
+
+    fn save_deliveries(&self, deliveries: &Deliveries) -> RhemaResult<()> {
+        if let Some(parent) = self.deliveries_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let temp = self.deliveries_path.with_extension("json.tmp");
+        std::fs::write(&temp, serde_json::to_vec_pretty(deliveries)?)?;
+        std::fs::rename(&temp, &self.deliveries_path)?;
+        Ok(())
+    }
+}
+
+fn build_sink(config: &EventSinkConfig, timeout: Duration) -> RhemaResult<Arc<dyn EventSink>> {
+    let client = || {
+        reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| RhemaError::ConfigError(format!("Failed to create HTTP client: {}", e)))
+    };
+    Ok(match config {
+        EventSinkConfig::Webhook {
+            url,
+            headers,
+            token_env,
+        } => Arc::new(WebhookSink {
+            client: client()?,
+            url: url.clone(),
+            headers: headers.clone(),
+            token: token_env
+                .as_deref()
+                .and_then(|name| std::env::var(name).ok()),
+        }),
+        EventSinkConfig::Nats {
+            url,
+            subject,
+            token_env,
+        } => Arc::new(NatsSink {
+            url: url.clone(),
+            subject: subject.clone(),
+            token: token_env
+                .as_deref()
+                .and_then(|name| std::env::var(name).ok()),
+            timeout,
+            jetstream: OnceCell::new(),
+        }),
+        EventSinkConfig::Kafka {
+            rest_proxy_url,
+            topic,
+        } => Arc::new(KafkaRestSink {
+            client: client()?,
+            base_url: rest_proxy_url.trim_end_matches('/').to_string(),
+            topic: topic.clone(),
+        }),
+    })
+}
+
+/// `{type}` in a subject or topic replaced by the event type
+fn expand(template: &str, event: &ExportEvent) -> String {
+    template.replace("{type}", event.event_type.name())
+}
+
+fn delivery_error(sink: &str, message: impl std::fmt::Display) -> RhemaError {
+    RhemaError::NetworkError(format!("{} delivery failed: {}", sink, message))
+}
+
+/// POSTs the envelope; any 2xx response counts as accepted
+pub struct WebhookSink {
+    client: reqwest::Client,
+    url: String,
+    headers: BTreeMap<String, String>,
+    token: Option<String>,
+}
+
+#[async_trait]
+impl EventSink for WebhookSink {
+    async fn deliver(&self, event: &ExportEvent) -> RhemaResult<()> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header("X-Rhema-Event", event.event_type.name())
+            .header("X-Rhema-Event-Id", &event.id)
+            .header("X-Rhema-Schema-Version", event.schema_version.to_string())
+            .json(event);
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| delivery_error("Webhook", e))?;
+        if !response.status().is_success() {
+            return Err(delivery_error("Webhook", response.status()));
+        }
+        Ok(())
+    }
+}
+
+/// Publishes to a JetStream stream over one long-lived connection, which the
+/// client re-establishes on its own. A delivery counts once the stream has
+/// acknowledged the message; the event id goes out as `Nats-Msg-Id`, so a
+/// retry after a lost acknowledgement is deduplicated by the stream. A
+/// stream must be configured to capture the subject.
+pub struct NatsSink {
+    url: String,
+    subject: String,
+    token: Option<String>,
+    timeout: Duration,
+    jetstream: OnceCell<jetstream::Context>,
+}
+
+impl NatsSink {
+    /// Connects on first use; a failed connect is retried on the next delivery
+    async fn jetstream(&self) -> RhemaResult<&jetstream::Context> {
+        self.jetstream
+            .get_or_try_init(|| async {
+                let mut options = async_nats::ConnectOptions::new()
+                    .name("rhema")
+                    .connection_timeout(self.timeout);
+                if let Some(token) = &self.token {
+                    options = options.token(token.clone());
+                }
+                let client = options
+                    .connect(self.url.as_str())
+                    .await
+                    .map_err(|e| delivery_error("NATS", e))?;
+                let mut context = jetstream::new(client);
+                context.set_timeout(self.timeout);
+                Ok(context)
+            })
+            .await
+    }
+
+    async fn publish(&self, event: &ExportEvent) -> RhemaResult<()> {
+        let payload = serde_json::to_vec(event)?;
+        let message = jetstream::context::Publish::build()
+            .payload(payload.into())
+            .message_id(&event.id);
+        self.jetstream()
+            .await?
+            .send_publish(expand(&self.subject, event), message)
+            .await
+            .map_err(|e| delivery_error("NATS", e))?
+            .await
+            .map_err(|e| delivery_error("NATS", e))?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EventSink for NatsSink {
+    async fn deliver(&self, event: &ExportEvent) -> RhemaResult<()> {
+        tokio::time::timeout(self.timeout, self.publish(event))
+            .await
+            .map_err(|_| delivery_error("NATS", "timed out"))?
+    }
+}
+
+/// Produces through a Kafka REST proxy (v2 API), keyed by event id
+pub struct KafkaRestSink {
+    client: reqwest::Client,
+    base_url: String,
+    topic: String,
+}
+
+#[async_trait]
+impl EventSink for KafkaRestSink {
+    async fn deliver(&self, event: &ExportEvent) -> RhemaResult<()> {
+        let url = format!("{}/topics/{}", self.base_url, expand(&self.topic, event));
+        let response = self
+            .client
+            .post(&url)
+            .header("Content-Type", "application/vnd.kafka.json.v2+json")
+            .body(serde_json::to_vec(
+                &serde_json::json!({ "records": [{ "key": event.id, "value": event }] }),
+            )?)
+            .send()
+            .await
+            .map_err(|e| delivery_error("Kafka", e))?;
+        if !response.status().is_success() {
+            return Err(delivery_error("Kafka", response.status()));
+        }
+
+        // The proxy reports per-record failures in the offsets
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| delivery_error("Kafka", e))?;
+        let failure = body["offsets"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|offset| !offset["error_code"].is_null());
+        match failure {
+            Some(offset) => Err(delivery_error("Kafka", &offset["error"])),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rhema_config::event_export::EventRoute;
+    use rhema_core::events::ExportEventType;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::TempDir;
+
+    /// Fails its first `failures` deliveries
+    struct FlakySink {
+        failures: usize,
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl EventSink for FlakySink {
+        async fn deliver(&self, _event: &ExportEvent) -> RhemaResult<()> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+                Err(RhemaError::NetworkError("unreachable".to_string()))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flush_retries_until_every_sink_accepted() {
+        let temp = TempDir::new().unwrap();
+        let config = EventExportConfig {
+            enabled: true,
+            sinks: [
+                ("hook", "https://hooks.example.com"),
+                ("audit", "https://audit.example.com"),
+            ]
+            .into_iter()
+            .map(|(name, url)| {
+                (
+                    name.to_string(),
+                    EventSinkConfig::Webhook {
+                        url: url.to_string(),
+                        headers: BTreeMap::new(),
+                        token_env: None,
+                    },
+                )
+            })
+            .collect(),
+            routes: vec![
+                EventRoute {
+                    events: vec!["*".to_string()],
+                    sinks: vec!["hook".to_string()],
+                },
+                EventRoute {
+                    events: vec!["approval_granted".to_string()],
+                    sinks: vec!["audit".to_string()],
+                },
+            ],
+            max_attempts: 2,
+            retry_backoff_secs: 0,
+            ..EventExportConfig::default()
+        };
+        let outbox = config.outbox(temp.path()).unwrap();
+        let hook = Arc::new(FlakySink {
+            failures: 1,
+            calls: AtomicUsize::new(0),
+        });
+        let audit = Arc::new(FlakySink {
+            failures: usize::MAX,
+            calls: AtomicUsize::new(0),
+        });
+        let bridge = EventExportBridge::new(
+            config,
+            outbox.clone(),
+            temp.path().join(EVENT_DELIVERIES_FILE),
+        )
+        .with_sink("hook", hook.clone())
+        .with_sink("audit", audit.clone());
+
+        let conflict = ExportEvent::new(
+            ExportEventType::ConflictDetected,
+            "rhema-coordination",
+            serde_json::json!({}),
+        );
+        outbox.publish(&conflict).unwrap();
+        let report = bridge.flush().await.unwrap();
+        assert_eq!((report.failed, report.remaining), (1, 1));
+
+        let report = bridge.flush().await.unwrap();
+        assert_eq!((report.delivered, report.remaining), (1, 0));
+        assert!(outbox.pending().unwrap().is_empty());
+
+        let approval = ExportEvent::new(
+            ExportEventType::ApprovalGranted,
+            "rhema-action",
+            serde_json::json!({}),
+        );
+        outbox.publish(&approval).unwrap();
+        bridge.flush().await.unwrap();
+        let report = bridge.flush().await.unwrap();
+        assert_eq!((report.dead_lettered, report.remaining), (1, 1));
+        let backlog = bridge.backlog().unwrap();
+        assert_eq!(backlog["audit"].dead, 1);
+        assert_eq!(backlog["hook"].pending, 0);
+        assert_eq!(hook.calls.load(Ordering::SeqCst), 3);
+
+        assert_eq!(bridge.retry_dead().unwrap(), 1);
+        assert_eq!(bridge.backlog().unwrap()["audit"].pending, 1);
+    }
+}
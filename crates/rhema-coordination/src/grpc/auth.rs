@@ -0,0 +1,116 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use rhema_core::agent_tokens::{AgentTokenClaims, AgentTokenStore, TokenAccess};
+use std::sync::Arc;
+use tonic::service::Interceptor;
+use tonic::{Request, Status};
+use tracing::warn;
+
+/// Interceptor requiring a scoped agent token in the `authorization`
+/// metadata. Verified claims are placed in the request extensions for
+/// services to check with [`require_scope`].
+#[derive(Clone)]
+pub struct AgentTokenInterceptor {
+    store: Arc<AgentTokenStore>,
+}
+
+impl AgentTokenInterceptor {
+    pub fn new(store: AgentTokenStore) -> Self {
+        Self {
+            store: Arc::new(store),
+        }
+    }
+}
+
+impl Interceptor for AgentTokenInterceptor {
+    fn call(&mut self, mut request: Request<()>) -> Result<Request<()>, Status> {
+        let token = request
+            .metadata()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| Status::unauthenticated("Missing agent token"))?;
+
+        let claims = self.store.verify(token, "grpc").map_err(|e| {
+            warn!("Rejected agent token: {}", e);
+            Status::unauthenticated(e.to_string())
+        })?;
+        request.extensions_mut().insert(claims);
+        Ok(request)
+    }
+}
+
+/// Check that the verified token of a request grants `access` to `scope`
+pub fn require_scope<T>(
+    request: &Request<T>,
+    access: TokenAccess,
+    scope: &str,
+) -> Result<(), Status> {
+    let claims = request
+        .extensions()
+        .get::<AgentTokenClaims>()
+        .ok_or_else(|| Status::unauthenticated("Missing agent token"))?;
+    check_claims(claims, access, scope)
+}
+
+/// Check that verified token claims grant `access` to `scope`
+pub fn check_claims(
+    claims: &AgentTokenClaims,
+    access: TokenAccess,
+    scope: &str,
+) -> Result<(), Status> {
+    if claims.allows(access, scope) {
+        Ok(())
+    } else {
+        Err(Status::permission_denied(format!(
+            "Token of {} does not grant {:?} access to scope {}",
+            claims.agent, access, scope
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_interceptor_checks_token_and_scope() {
+        let temp = TempDir::new().unwrap();
+        let store = AgentTokenStore::new(temp.path());
+        let issued = store
+            .issue(
+                "planner",
+                vec!["write:api".parse().unwrap()],
+                Duration::hours(1),
+            )
+            .unwrap();
+        let mut interceptor = AgentTokenInterceptor::new(store);
+
+        assert!(interceptor.call(Request::new(())).is_err());
+
+        let mut request = Request::new(());
+        request.metadata_mut().insert(
+            "authorization",
+            format!("Bearer {}", issued.token).parse().unwrap(),
+        );
+        let request = interceptor.call(request).unwrap();
+        assert!(require_scope(&request, TokenAccess::Write, "api").is_ok());
+        assert!(require_scope(&request, TokenAccess::Read, "docs").is_err());
+    }
+}
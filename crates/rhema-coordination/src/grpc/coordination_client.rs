@@ -548,6 +548,7 @@ impl GrpcCoordinationClient {
                 last_heartbeat: chrono::Utc::now(),
                 is_online: proto_agent.is_online,
                 performance_metrics: crate::agent::real_time_coordination::AgentPerformanceMetrics::default(),
+                liveness: Default::default(),
             };
 
             Ok(Some(agent_info))
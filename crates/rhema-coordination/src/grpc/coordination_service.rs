@@ -14,324 +14,708 @@
  * limitations under the License.
  */
 
-use std::sync::Arc;
-use tokio::sync::RwLock;
-// use futures::stream::{self, Stream, StreamExt};
-// use tokio_stream::wrappers::ReceiverStream;
+use prost_types::{Any, Timestamp};
+use rhema_core::agent_tokens::{AgentTokenClaims, TokenAccess};
+use std::pin::Pin;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_stream::{Stream, StreamExt};
+use tonic::{Request, Response, Status, Streaming};
+use tracing::warn;
 
-use crate::agent::real_time_coordination::RealTimeCoordinationSystem;
-
-// Temporarily comment out the generated protobuf code until we fix the dependencies
-/*
+use crate::agent::real_time_coordination::{
+    AgentInfo, AgentMessage, AgentStatus, CoordinationStats, MessagePriority, MessageType,
+    RealTimeCoordinationSystem,
+};
+use crate::grpc::auth::check_claims;
 use crate::grpc::coordination::{
-    AgentInfo as ProtoAgentInfo, AgentMessage as ProtoAgentMessage, AgentStatus as ProtoAgentStatus,
-    CoordinationSession as ProtoCoordinationSession, CoordinationStats as ProtoCoordinationStats,
-    CreateSessionRequest, CreateSessionResponse, GetAgentInfoRequest, GetAgentInfoResponse,
-    GetSessionInfoRequest, GetSessionInfoResponse, JoinSessionRequest, JoinSessionResponse,
+    real_time_coordination_service_server::RealTimeCoordinationService,
+    AgentInfo as ProtoAgentInfo, AgentMessage as ProtoAgentMessage,
+    AgentPerformanceMetrics as ProtoAgentPerformanceMetrics, AgentStatus as ProtoAgentStatus,
+    CoordinationStats as ProtoCoordinationStats, CreateSessionRequest, CreateSessionResponse,
+    GetAgentInfoRequest, GetAgentInfoResponse, GetAllAgentsRequest, GetAllAgentsResponse,
+    GetMessageHistoryRequest, GetMessageHistoryResponse, GetMessageStreamRequest, GetStatsRequest,
+    GetStatsResponse, HeartbeatRequest, HeartbeatResponse, JoinSessionRequest, JoinSessionResponse,
     LeaveSessionRequest, LeaveSessionResponse, MessagePriority as ProtoMessagePriority,
     MessageType as ProtoMessageType, RegisterAgentRequest, RegisterAgentResponse,
-    SendMessageRequest, SendMessageResponse, SendSessionMessageRequest, SendSessionMessageResponse, SessionStatus as ProtoSessionStatus,
-    UnregisterAgentRequest, UnregisterAgentResponse,
+    ReleaseResourceRequest, ReleaseResourceResponse, RequestResourceRequest,
+    RequestResourceResponse, SendMessageRequest, SendMessageResponse, SendSessionMessageRequest,
+    SendSessionMessageResponse, UnregisterAgentRequest, UnregisterAgentResponse,
+    UpdateAgentStatusRequest, UpdateAgentStatusResponse,
 };
-*/
 
-/// gRPC coordination service implementation
+/// Metadata key carrying message types the protocol has no variant for
+const MESSAGE_TYPE_KEY: &str = "message_type";
+
+/// Type URL of JSON message payloads
+const JSON_PAYLOAD_TYPE: &str = "application/json";
+
+type MessageStream = Pin<Box<dyn Stream<Item = Result<ProtoAgentMessage, Status>> + Send>>;
+
+/// gRPC coordination service implementation.
+///
+/// When the server requires agent tokens, every call is checked against the
+/// scope of the agents it acts on: changing an agent or sending on its
+/// behalf needs write access to its assigned scope, reading it needs read
+/// access, and calls spanning all agents need a grant on `*`.
+#[derive(Clone)]
 pub struct CoordinationService {
-    coordination_system: Arc<RwLock<RealTimeCoordinationSystem>>,
+    coordination_system: RealTimeCoordinationSystem,
+    require_tokens: bool,
 }
 
 impl CoordinationService {
     pub fn new(coordination_system: RealTimeCoordinationSystem) -> Self {
         Self {
-            coordination_system: Arc::new(RwLock::new(coordination_system)),
+            coordination_system,
+            require_tokens: false,
+        }
+    }
+
+    /// Reject calls that did not pass the agent token interceptor
+    pub fn requiring_tokens(mut self) -> Self {
+        self.require_tokens = true;
+        self
+    }
+
+    /// Claims of the caller, or `None` when tokens are not required
+    fn caller<T>(&self, request: &Request<T>) -> Result<Option<AgentTokenClaims>, Status> {
+        match request.extensions().get::<AgentTokenClaims>() {
+            Some(claims) => Ok(Some(claims.clone())),
+            None if self.require_tokens => Err(Status::unauthenticated("Missing agent token")),
+            None => Ok(None),
+        }
+    }
+
+    /// Check `access` to the scope assigned to `agent_id`
+    async fn authorize_agent(
+        &self,
+        caller: &Option<AgentTokenClaims>,
+        access: TokenAccess,
+        agent_id: &str,
+    ) -> Result<(), Status> {
+        let Some(claims) = caller else {
+            return Ok(());
+        };
+        let agent = self
+            .coordination_system
+            .get_agent_info(agent_id)
+            .await
+            .ok_or_else(|| Status::not_found(format!("Agent not found: {}", agent_id)))?;
+        check_claims(claims, access, &agent.assigned_scope)
+    }
+
+    /// Check access to every scope, for calls spanning all agents
+    fn authorize_all(caller: &Option<AgentTokenClaims>, access: TokenAccess) -> Result<(), Status> {
+        match caller {
+            Some(claims) => check_claims(claims, access, "*"),
+            None => Ok(()),
         }
     }
 }
 
-// Temporarily comment out the service implementation until we fix the dependencies
-/*
 #[tonic::async_trait]
-impl coordination_server::Coordination for CoordinationService {
+impl RealTimeCoordinationService for CoordinationService {
     async fn register_agent(
         &self,
         request: Request<RegisterAgentRequest>,
     ) -> Result<Response<RegisterAgentResponse>, Status> {
-        let proto_agent = request.into_inner().agent_info.ok_or_else(|| {
-            Status::invalid_argument("Agent info is required")
-        })?;
-
-        let agent_info = AgentInfo {
-            id: proto_agent.id,
-            name: proto_agent.name,
-            agent_type: proto_agent.agent_type,
-            status: match proto_agent.status() {
-                ProtoAgentStatus::AgentStatusIdle => AgentStatus::Idle,
-                ProtoAgentStatus::AgentStatusBusy => AgentStatus::Busy,
-                ProtoAgentStatus::AgentStatusWorking => AgentStatus::Working,
-                ProtoAgentStatus::AgentStatusBlocked => AgentStatus::Blocked,
-                ProtoAgentStatus::AgentStatusCollaborating => AgentStatus::Collaborating,
-                ProtoAgentStatus::AgentStatusOffline => AgentStatus::Offline,
-            },
-            current_task_id: proto_agent.current_task_id,
-            assigned_scope: proto_agent.assigned_scope,
-            capabilities: proto_agent.capabilities,
-            last_heartbeat: chrono::Utc::now(),
-            is_online: proto_agent.is_online,
-            performance_metrics: crate::agent::real_time_coordination::AgentPerformanceMetrics::default(),
-        };
-
-        match self.coordination_system.write().await.register_agent(agent_info).await {
-            Ok(_) => Ok(Response::new(RegisterAgentResponse {
-                success: true,
-                message: "Agent registered successfully".to_string(),
-            })),
-            Err(e) => Ok(Response::new(RegisterAgentResponse {
-                success: false,
-                message: format!("Failed to register agent: {}", e),
-            })),
+        let caller = self.caller(&request)?;
+        let agent_info = request
+            .into_inner()
+            .agent_info
+            .map(agent_from_proto)
+            .ok_or_else(|| Status::invalid_argument("Agent info is required"))?;
+        if let Some(claims) = &caller {
+            check_claims(claims, TokenAccess::Write, &agent_info.assigned_scope)?;
         }
+
+        Ok(Response::new(
+            match self.coordination_system.register_agent(agent_info).await {
+                Ok(()) => RegisterAgentResponse {
+                    success: true,
+                    message: "Agent registered successfully".to_string(),
+                },
+                Err(e) => RegisterAgentResponse {
+                    success: false,
+                    message: format!("Failed to register agent: {}", e),
+                },
+            },
+        ))
     }
 
     async fn unregister_agent(
         &self,
         request: Request<UnregisterAgentRequest>,
     ) -> Result<Response<UnregisterAgentResponse>, Status> {
+        let caller = self.caller(&request)?;
         let agent_id = request.into_inner().agent_id;
+        self.authorize_agent(&caller, TokenAccess::Write, &agent_id)
+            .await?;
 
-        match self.coordination_system.write().await.unregister_agent(&agent_id).await {
-            Ok(_) => Ok(Response::new(UnregisterAgentResponse {
-                success: true,
-                message: "Agent unregistered successfully".to_string(),
-            })),
-            Err(e) => Ok(Response::new(UnregisterAgentResponse {
-                success: false,
-                message: format!("Failed to unregister agent: {}", e),
-            })),
-        }
+        Ok(Response::new(
+            match self.coordination_system.unregister_agent(&agent_id).await {
+                Ok(()) => UnregisterAgentResponse {
+                    success: true,
+                    message: "Agent unregistered successfully".to_string(),
+                },
+                Err(e) => UnregisterAgentResponse {
+                    success: false,
+                    message: format!("Failed to unregister agent: {}", e),
+                },
+            },
+        ))
     }
 
-    async fn send_message(
+    async fn update_agent_status(
         &self,
-        request: Request<SendMessageRequest>,
-    ) -> Result<Response<SendMessageResponse>, Status> {
-        let proto_message = request.into_inner().message.ok_or_else(|| {
-            Status::invalid_argument("Message is required")
-        })?;
-
-        let message = AgentMessage {
-            id: proto_message.id,
-            message_type: match proto_message.message_type() {
-                ProtoMessageType::MessageTypeTaskAssignment => MessageType::TaskAssignment,
-                ProtoMessageType::MessageTypeTaskCompletion => MessageType::TaskCompletion,
-                ProtoMessageType::MessageTypeTaskBlocked => MessageType::TaskBlocked,
-                ProtoMessageType::MessageTypeResourceRequest => MessageType::ResourceRequest,
-                ProtoMessageType::MessageTypeResourceRelease => MessageType::ResourceRelease,
-                ProtoMessageType::MessageTypeConflictNotification => MessageType::ConflictNotification,
-                ProtoMessageType::MessageTypeCoordinationRequest => MessageType::CoordinationRequest,
-                ProtoMessageType::MessageTypeStatusUpdate => MessageType::StatusUpdate,
-                ProtoMessageType::MessageTypeKnowledgeShare => MessageType::KnowledgeShare,
-                ProtoMessageType::MessageTypeDecisionRequest => MessageType::DecisionRequest,
-                ProtoMessageType::MessageTypeDecisionResponse => MessageType::DecisionResponse,
-                ProtoMessageType::MessageTypeCustom => MessageType::Custom(proto_message.content.clone()),
-            },
-            priority: match proto_message.priority() {
-                ProtoMessagePriority::MessagePriorityLow => MessagePriority::Low,
-                ProtoMessagePriority::MessagePriorityNormal => MessagePriority::Normal,
-                ProtoMessagePriority::MessagePriorityHigh => MessagePriority::High,
-                ProtoMessagePriority::MessagePriorityCritical => MessagePriority::Critical,
-                ProtoMessagePriority::MessagePriorityEmergency => MessagePriority::Emergency,
-            },
-            sender_id: proto_message.sender_id,
-            recipient_ids: proto_message.recipient_ids,
-            content: proto_message.content,
-            payload: proto_message.payload.as_ref().map(|p| serde_json::from_str(&p.type_url).unwrap_or_default()),
-            timestamp: chrono::Utc::now(),
-            requires_ack: proto_message.requires_ack,
-            expires_at: proto_message.expires_at.as_ref().map(|dt| chrono::DateTime::from_timestamp(dt.seconds, dt.nanos as u32).unwrap_or_else(|| chrono::Utc::now())),
-            metadata: proto_message.metadata,
-        };
+        request: Request<UpdateAgentStatusRequest>,
+    ) -> Result<Response<UpdateAgentStatusResponse>, Status> {
+        let caller = self.caller(&request)?;
+        let request = request.into_inner();
+        self.authorize_agent(&caller, TokenAccess::Write, &request.agent_id)
+            .await?;
 
-        match self.coordination_system.write().await.send_message(message).await {
-            Ok(_) => Ok(Response::new(SendMessageResponse {
-                success: true,
-                message: "Message sent successfully".to_string(),
-            })),
-            Err(e) => Ok(Response::new(SendMessageResponse {
-                success: false,
-                message: format!("Failed to send message: {}", e),
-            })),
-        }
+        let status = status_from_proto(request.status())
+            .ok_or_else(|| Status::invalid_argument("Agent status is required"))?;
+        let result = self
+            .coordination_system
+            .update_agent_status(&request.agent_id, status)
+            .await;
+        Ok(Response::new(UpdateAgentStatusResponse {
+            success: result.is_ok(),
+            error_message: error_message(result),
+        }))
     }
 
     async fn get_agent_info(
         &self,
         request: Request<GetAgentInfoRequest>,
     ) -> Result<Response<GetAgentInfoResponse>, Status> {
+        let caller = self.caller(&request)?;
         let agent_id = request.into_inner().agent_id;
+        self.authorize_agent(&caller, TokenAccess::Read, &agent_id)
+            .await?;
+
+        let agent_info = self.coordination_system.get_agent_info(&agent_id).await;
+        Ok(Response::new(GetAgentInfoResponse {
+            success: agent_info.is_some(),
+            error_message: match agent_info {
+                Some(_) => String::new(),
+                None => format!("Agent not found: {}", agent_id),
+            },
+            agent_info: agent_info.map(agent_to_proto),
+        }))
+    }
+
+    async fn get_all_agents(
+        &self,
+        request: Request<GetAllAgentsRequest>,
+    ) -> Result<Response<GetAllAgentsResponse>, Status> {
+        let caller = self.caller(&request)?;
+
+        // Agents in scopes the caller cannot read are left out
+        let agents = self
+            .coordination_system
+            .get_all_agents()
+            .await
+            .into_iter()
+            .filter(|agent| {
+                caller
+                    .as_ref()
+                    .is_none_or(|claims| claims.allows(TokenAccess::Read, &agent.assigned_scope))
+            })
+            .map(agent_to_proto)
+            .collect();
+        Ok(Response::new(GetAllAgentsResponse { agents }))
+    }
+
+    async fn send_message(
+        &self,
+        request: Request<SendMessageRequest>,
+    ) -> Result<Response<SendMessageResponse>, Status> {
+        let caller = self.caller(&request)?;
+        let message = request
+            .into_inner()
+            .message
+            .map(message_from_proto)
+            .ok_or_else(|| Status::invalid_argument("Message is required"))?;
+        self.authorize_agent(&caller, TokenAccess::Write, &message.sender_id)
+            .await?;
+
+        let message_id = message.id.clone();
+        let result = self.coordination_system.send_message(message).await;
+        Ok(Response::new(SendMessageResponse {
+            success: result.is_ok(),
+            message_id,
+            error_message: error_message(result),
+        }))
+    }
 
-        match self.coordination_system.read().await.get_agent_info(&agent_id) {
-            Some(agent_info) => {
-                let proto_agent = ProtoAgentInfo {
-                    id: agent_info.id,
-                    name: agent_info.name,
-                    agent_type: agent_info.agent_type,
-                    status: match agent_info.status {
-                        AgentStatus::Idle => ProtoAgentStatus::AgentStatusIdle,
-                        AgentStatus::Busy => ProtoAgentStatus::AgentStatusBusy,
-                        AgentStatus::Working => ProtoAgentStatus::AgentStatusWorking,
-                        AgentStatus::Blocked => ProtoAgentStatus::AgentStatusBlocked,
-                        AgentStatus::Collaborating => ProtoAgentStatus::AgentStatusCollaborating,
-                        AgentStatus::Offline => ProtoAgentStatus::AgentStatusOffline,
-                    },
-                    current_task_id: agent_info.current_task_id,
-                    assigned_scope: agent_info.assigned_scope,
-                    capabilities: agent_info.capabilities,
-                    last_heartbeat: Some(prost_types::Timestamp::from(agent_info.last_heartbeat)),
-                    is_online: agent_info.is_online,
-                    performance_metrics: None,
-                    priority: 1,
-                    version: "1.0.0".to_string(),
-                    endpoint: None,
-                    metadata: std::collections::HashMap::new(),
-                    created_at: None,
-                    last_updated: None,
-                };
-
-                Ok(Response::new(GetAgentInfoResponse {
-                    agent_info: Some(proto_agent),
-                }))
+    type GetMessageStreamStream = MessageStream;
+
+    async fn get_message_stream(
+        &self,
+        request: Request<GetMessageStreamRequest>,
+    ) -> Result<Response<Self::GetMessageStreamStream>, Status> {
+        let caller = self.caller(&request)?;
+        let agent_id = request.into_inner().agent_id;
+        self.authorize_agent(&caller, TokenAccess::Read, &agent_id)
+            .await?;
+
+        let receiver = self
+            .coordination_system
+            .get_message_stream(&agent_id)
+            .await
+            .ok_or_else(|| Status::not_found(format!("Agent not found: {}", agent_id)))?;
+        let stream = ReceiverStream::new(receiver).map(|message| Ok(message_to_proto(message)));
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn get_message_history(
+        &self,
+        request: Request<GetMessageHistoryRequest>,
+    ) -> Result<Response<GetMessageHistoryResponse>, Status> {
+        let caller = self.caller(&request)?;
+        let request = request.into_inner();
+        match &request.agent_id {
+            Some(agent_id) => {
+                self.authorize_agent(&caller, TokenAccess::Read, agent_id)
+                    .await?
             }
-            None => Err(Status::not_found("Agent not found")),
+            None => Self::authorize_all(&caller, TokenAccess::Read)?,
         }
+
+        let history = self
+            .coordination_system
+            .get_message_history(usize::MAX)
+            .into_iter()
+            .filter(|message| {
+                request.agent_id.as_ref().is_none_or(|agent_id| {
+                    message.sender_id == *agent_id || message.recipient_ids.contains(agent_id)
+                })
+            });
+        let messages = match request.limit {
+            0 => history.map(message_to_proto).collect(),
+            limit => history.take(limit as usize).map(message_to_proto).collect(),
+        };
+        Ok(Response::new(GetMessageHistoryResponse { messages }))
     }
 
     async fn create_session(
         &self,
         request: Request<CreateSessionRequest>,
     ) -> Result<Response<CreateSessionResponse>, Status> {
-        let req = request.into_inner();
-        let topic = req.topic;
-        let participants = req.participants;
-
-        match self.coordination_system.write().await.create_session(topic, participants).await {
-            Ok(session_id) => Ok(Response::new(CreateSessionResponse {
-                session_id,
-                success: true,
-                message: "Session created successfully".to_string(),
-            })),
-            Err(e) => Ok(Response::new(CreateSessionResponse {
-                session_id: "".to_string(),
-                success: false,
-                message: format!("Failed to create session: {}", e),
-            })),
+        let caller = self.caller(&request)?;
+        let request = request.into_inner();
+        for participant in &request.participants {
+            self.authorize_agent(&caller, TokenAccess::Write, participant)
+                .await?;
         }
+
+        Ok(Response::new(
+            match self
+                .coordination_system
+                .create_session(request.topic, request.participants)
+                .await
+            {
+                Ok(session_id) => CreateSessionResponse {
+                    success: true,
+                    session_id,
+                    error_message: String::new(),
+                },
+                Err(e) => CreateSessionResponse {
+                    success: false,
+                    session_id: String::new(),
+                    error_message: e.to_string(),
+                },
+            },
+        ))
     }
 
     async fn join_session(
         &self,
         request: Request<JoinSessionRequest>,
     ) -> Result<Response<JoinSessionResponse>, Status> {
-        let req = request.into_inner();
-        let session_id = req.session_id;
-        let agent_id = req.agent_id;
-
-        match self.coordination_system.write().await.join_session(&session_id, &agent_id).await {
-            Ok(_) => Ok(Response::new(JoinSessionResponse {
-                success: true,
-                message: "Joined session successfully".to_string(),
-            })),
-            Err(e) => Ok(Response::new(JoinSessionResponse {
-                success: false,
-                message: format!("Failed to join session: {}", e),
-            })),
-        }
+        let caller = self.caller(&request)?;
+        let request = request.into_inner();
+        self.authorize_agent(&caller, TokenAccess::Write, &request.agent_id)
+            .await?;
+
+        let result = self
+            .coordination_system
+            .join_session(&request.session_id, &request.agent_id)
+            .await;
+        Ok(Response::new(JoinSessionResponse {
+            success: result.is_ok(),
+            error_message: error_message(result),
+        }))
     }
 
     async fn leave_session(
         &self,
         request: Request<LeaveSessionRequest>,
     ) -> Result<Response<LeaveSessionResponse>, Status> {
-        let req = request.into_inner();
-        let session_id = req.session_id;
-        let agent_id = req.agent_id;
-
-        match self.coordination_system.write().await.leave_session(&session_id, &agent_id).await {
-            Ok(_) => Ok(Response::new(LeaveSessionResponse {
-                success: true,
-                message: "Left session successfully".to_string(),
-            })),
-            Err(e) => Ok(Response::new(LeaveSessionResponse {
-                success: false,
-                message: format!("Failed to leave session: {}", e),
-            })),
-        }
+        let caller = self.caller(&request)?;
+        let request = request.into_inner();
+        self.authorize_agent(&caller, TokenAccess::Write, &request.agent_id)
+            .await?;
+
+        let result = self
+            .coordination_system
+            .leave_session(&request.session_id, &request.agent_id)
+            .await;
+        Ok(Response::new(LeaveSessionResponse {
+            success: result.is_ok(),
+            error_message: error_message(result),
+        }))
     }
 
     async fn send_session_message(
         &self,
         request: Request<SendSessionMessageRequest>,
     ) -> Result<Response<SendSessionMessageResponse>, Status> {
-        let proto_message = request.into_inner().message.ok_or_else(|| {
-            Status::invalid_argument("Message is required")
-        })?;
-
-        let message = AgentMessage {
-            id: proto_message.id,
-            message_type: match proto_message.message_type() {
-                ProtoMessageType::MessageTypeTaskAssignment => MessageType::TaskAssignment,
-                ProtoMessageType::MessageTypeTaskCompletion => MessageType::TaskCompletion,
-                ProtoMessageType::MessageTypeTaskBlocked => MessageType::TaskBlocked,
-                ProtoMessageType::MessageTypeResourceRequest => MessageType::ResourceRequest,
-                ProtoMessageType::MessageTypeResourceRelease => MessageType::ResourceRelease,
-                ProtoMessageType::MessageTypeConflictNotification => MessageType::ConflictNotification,
-                ProtoMessageType::MessageTypeCoordinationRequest => MessageType::CoordinationRequest,
-                ProtoMessageType::MessageTypeStatusUpdate => MessageType::StatusUpdate,
-                ProtoMessageType::MessageTypeKnowledgeShare => MessageType::KnowledgeShare,
-                ProtoMessageType::MessageTypeDecisionRequest => MessageType::DecisionRequest,
-                ProtoMessageType::MessageTypeDecisionResponse => MessageType::DecisionResponse,
-                ProtoMessageType::MessageTypeCustom => MessageType::Custom(proto_message.content.clone()),
-            },
-            priority: match proto_message.priority() {
-                ProtoMessagePriority::MessagePriorityLow => MessagePriority::Low,
-                ProtoMessagePriority::MessagePriorityNormal => MessagePriority::Normal,
-                ProtoMessagePriority::MessagePriorityHigh => MessagePriority::High,
-                ProtoMessagePriority::MessagePriorityCritical => MessagePriority::Critical,
-                ProtoMessagePriority::MessagePriorityEmergency => MessagePriority::Emergency,
+        let caller = self.caller(&request)?;
+        let request = request.into_inner();
+        let message = request
+            .message
+            .map(message_from_proto)
+            .ok_or_else(|| Status::invalid_argument("Message is required"))?;
+        self.authorize_agent(&caller, TokenAccess::Write, &message.sender_id)
+            .await?;
+
+        let result = self
+            .coordination_system
+            .send_session_message(&request.session_id, message)
+            .await;
+        Ok(Response::new(SendSessionMessageResponse {
+            success: result.is_ok(),
+            error_message: error_message(result),
+        }))
+    }
+
+    async fn request_resource(
+        &self,
+        request: Request<RequestResourceRequest>,
+    ) -> Result<Response<RequestResourceResponse>, Status> {
+        let caller = self.caller(&request)?;
+        let request = request.into_inner();
+        self.authorize_agent(&caller, TokenAccess::Write, &request.agent_id)
+            .await?;
+
+        Ok(Response::new(
+            match self
+                .coordination_system
+                .request_resource(&request.resource_id, &request.agent_id)
+                .await
+            {
+                Ok(acquired) => RequestResourceResponse {
+                    success: true,
+                    resource_acquired: acquired,
+                    error_message: String::new(),
+                },
+                Err(e) => RequestResourceResponse {
+                    success: false,
+                    resource_acquired: false,
+                    error_message: e.to_string(),
+                },
             },
-            sender_id: proto_message.sender_id,
-            recipient_ids: proto_message.recipient_ids,
-            content: proto_message.content,
-            payload: proto_message.payload.as_ref().map(|p| serde_json::from_str(&p.type_url).unwrap_or_default()),
-            timestamp: chrono::Utc::now(),
-            requires_ack: proto_message.requires_ack,
-            expires_at: proto_message.expires_at.as_ref().map(|dt| chrono::DateTime::from_timestamp(dt.seconds, dt.nanos as u32).unwrap_or_else(|| chrono::Utc::now())),
-            metadata: proto_message.metadata,
-        };
+        ))
+    }
 
-        let session_id = request.into_inner().session_id;
-
-        match self.coordination_system.write().await.send_session_message(&session_id, message).await {
-            Ok(_) => Ok(Response::new(SendSessionMessageResponse {
-                success: true,
-                message: "Session message sent successfully".to_string(),
-            })),
-            Err(e) => Ok(Response::new(SendSessionMessageResponse {
-                success: false,
-                message: format!("Failed to send session message: {}", e),
-            })),
-        }
+    async fn release_resource(
+        &self,
+        request: Request<ReleaseResourceRequest>,
+    ) -> Result<Response<ReleaseResourceResponse>, Status> {
+        let caller = self.caller(&request)?;
+        let request = request.into_inner();
+        self.authorize_agent(&caller, TokenAccess::Write, &request.agent_id)
+            .await?;
+
+        let result = self
+            .coordination_system
+            .release_resource(&request.resource_id, &request.agent_id)
+            .await;
+        Ok(Response::new(ReleaseResourceResponse {
+            success: result.is_ok(),
+            error_message: error_message(result),
+        }))
     }
 
-    async fn get_session_info(
+    async fn get_stats(
         &self,
-        request: Request<GetSessionInfoRequest>,
-    ) -> Result<Response<GetSessionInfoResponse>, Status> {
-        let session_id = request.into_inner().session_id;
+        request: Request<GetStatsRequest>,
+    ) -> Result<Response<GetStatsResponse>, Status> {
+        let caller = self.caller(&request)?;
+        Self::authorize_all(&caller, TokenAccess::Read)?;
 
-        // For now, return a placeholder response
-        Ok(Response::new(GetSessionInfoResponse {
-            session_info: None,
+        Ok(Response::new(GetStatsResponse {
+            stats: Some(stats_to_proto(self.coordination_system.get_stats())),
         }))
     }
+
+    async fn heartbeat(
+        &self,
+        request: Request<HeartbeatRequest>,
+    ) -> Result<Response<HeartbeatResponse>, Status> {
+        let caller = self.caller(&request)?;
+        let request = request.into_inner();
+        self.authorize_agent(&caller, TokenAccess::Write, &request.agent_id)
+            .await?;
+
+        if let Some(status) = status_from_proto(request.status()) {
+            if let Err(e) = self
+                .coordination_system
+                .update_agent_status(&request.agent_id, status)
+                .await
+            {
+                warn!("Failed to update status of {}: {}", request.agent_id, e);
+            }
+        }
+        // Pending messages are delivered on the agent's message stream
+        Ok(Response::new(HeartbeatResponse {
+            success: self
+                .coordination_system
+                .heartbeat(&request.agent_id)
+                .await
+                .is_ok(),
+            pending_messages: Vec::new(),
+        }))
+    }
+
+    type StreamUpdatesStream = MessageStream;
+
+    async fn stream_updates(
+        &self,
+        request: Request<Streaming<ProtoAgentMessage>>,
+    ) -> Result<Response<Self::StreamUpdatesStream>, Status> {
+        let caller = self.caller(&request)?;
+        let mut incoming = request.into_inner();
+
+        // Messages from the client are sent like `send_message` calls
+        let service = self.clone();
+        tokio::spawn(async move {
+            while let Some(Ok(message)) = incoming.next().await {
+                let message = message_from_proto(message);
+                if let Err(status) = service
+                    .authorize_agent(&caller, TokenAccess::Write, &message.sender_id)
+                    .await
+                {
+                    warn!(
+                        "Dropped streamed message {}: {}",
+                        message.id,
+                        status.message()
+                    );
+                    continue;
+                }
+                if let Err(e) = service.coordination_system.send_message(message).await {
+                    warn!("Failed to send streamed message: {}", e);
+                }
+            }
+        });
+
+        // Broadcasts are streamed back to the client
+        let mut broadcasts = self.coordination_system.get_broadcast_stream();
+        let (tx, rx) = mpsc::channel(100);
+        tokio::spawn(async move {
+            while let Ok(message) = broadcasts.recv().await {
+                if tx.send(Ok(message_to_proto(message))).await.is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+    }
+}
+
+fn error_message<T>(result: rhema_core::RhemaResult<T>) -> String {
+    result.err().map(|e| e.to_string()).unwrap_or_default()
+}
+
+fn timestamp(time: chrono::DateTime<chrono::Utc>) -> Timestamp {
+    Timestamp {
+        seconds: time.timestamp(),
+        nanos: time.timestamp_subsec_nanos() as i32,
+    }
+}
+
+fn datetime(timestamp: &Timestamp) -> Option<chrono::DateTime<chrono::Utc>> {
+    chrono::DateTime::from_timestamp(timestamp.seconds, timestamp.nanos.max(0) as u32)
+}
+
+fn status_from_proto(status: ProtoAgentStatus) -> Option<AgentStatus> {
+    match status {
+        ProtoAgentStatus::Unspecified => None,
+        ProtoAgentStatus::Idle => Some(AgentStatus::Idle),
+        ProtoAgentStatus::Busy => Some(AgentStatus::Busy),
+        ProtoAgentStatus::Working => Some(AgentStatus::Working),
+        ProtoAgentStatus::Blocked => Some(AgentStatus::Blocked),
+        ProtoAgentStatus::Collaborating => Some(AgentStatus::Collaborating),
+        ProtoAgentStatus::Offline => Some(AgentStatus::Offline),
+    }
+}
+
+fn status_to_proto(status: &AgentStatus) -> ProtoAgentStatus {
+    match status {
+        AgentStatus::Idle => ProtoAgentStatus::Idle,
+        AgentStatus::Busy => ProtoAgentStatus::Busy,
+        AgentStatus::Working => ProtoAgentStatus::Working,
+        AgentStatus::Blocked => ProtoAgentStatus::Blocked,
+        AgentStatus::Collaborating => ProtoAgentStatus::Collaborating,
+        AgentStatus::Offline | AgentStatus::Failed => ProtoAgentStatus::Offline,
+    }
+}
+
+fn agent_from_proto(agent: ProtoAgentInfo) -> AgentInfo {
+    AgentInfo {
+        status: status_from_proto(agent.status()).unwrap_or(AgentStatus::Idle),
+        id: agent.id,
+        name: agent.name,
+        agent_type: agent.agent_type,
+        current_task_id: agent.current_task_id,
+        assigned_scope: agent.assigned_scope,
+        capabilities: agent.capabilities,
+        last_heartbeat: chrono::Utc::now(),
+        is_online: agent.is_online,
+        performance_metrics: Default::default(),
+        liveness: Default::default(),
+    }
+}
+
+fn agent_to_proto(agent: AgentInfo) -> ProtoAgentInfo {
+    let metrics = &agent.performance_metrics;
+    ProtoAgentInfo {
+        status: status_to_proto(&agent.status) as i32,
+        last_heartbeat: Some(timestamp(agent.last_heartbeat)),
+        performance_metrics: Some(ProtoAgentPerformanceMetrics {
+            tasks_completed: metrics.tasks_completed as u32,
+            tasks_failed: metrics.tasks_failed as u32,
+            avg_completion_time_seconds: metrics.avg_completion_time_seconds,
+            success_rate: metrics.success_rate,
+            collaboration_score: metrics.collaboration_score,
+            avg_response_time_ms: metrics.avg_response_time_ms,
+        }),
+        id: agent.id,
+        name: agent.name,
+        agent_type: agent.agent_type,
+        current_task_id: agent.current_task_id,
+        assigned_scope: agent.assigned_scope,
+        capabilities: agent.capabilities,
+        is_online: agent.is_online,
+    }
+}
+
+fn message_type_from_proto(
+    message_type: ProtoMessageType,
+    metadata: &std::collections::HashMap<String, String>,
+) -> MessageType {
+    match message_type {
+        ProtoMessageType::TaskAssignment => MessageType::TaskAssignment,
+        ProtoMessageType::TaskCompletion => MessageType::TaskCompletion,
+        ProtoMessageType::TaskBlocked => MessageType::TaskBlocked,
+        ProtoMessageType::ResourceRequest => MessageType::ResourceRequest,
+        ProtoMessageType::ResourceRelease => MessageType::ResourceRelease,
+        ProtoMessageType::ConflictNotification => MessageType::ConflictNotification,
+        ProtoMessageType::CoordinationRequest => MessageType::CoordinationRequest,
+        ProtoMessageType::StatusUpdate => MessageType::StatusUpdate,
+        ProtoMessageType::KnowledgeShare => MessageType::KnowledgeShare,
+        ProtoMessageType::DecisionRequest => MessageType::DecisionRequest,
+        ProtoMessageType::DecisionResponse => MessageType::DecisionResponse,
+        ProtoMessageType::Unspecified | ProtoMessageType::Custom => {
+            MessageType::Custom(metadata.get(MESSAGE_TYPE_KEY).cloned().unwrap_or_default())
+        }
+    }
+}
+
+/// Protocol message type, and the name of types the protocol lacks
+fn message_type_to_proto(message_type: &MessageType) -> (ProtoMessageType, Option<String>) {
+    let proto = match message_type {
+        MessageType::TaskAssignment => ProtoMessageType::TaskAssignment,
+        MessageType::TaskCompletion => ProtoMessageType::TaskCompletion,
+        MessageType::TaskBlocked => ProtoMessageType::TaskBlocked,
+        MessageType::ResourceRequest => ProtoMessageType::ResourceRequest,
+        MessageType::ResourceRelease => ProtoMessageType::ResourceRelease,
+        MessageType::ConflictNotification => ProtoMessageType::ConflictNotification,
+        MessageType::CoordinationRequest => ProtoMessageType::CoordinationRequest,
+        MessageType::StatusUpdate => ProtoMessageType::StatusUpdate,
+        MessageType::KnowledgeShare => ProtoMessageType::KnowledgeShare,
+        MessageType::DecisionRequest => ProtoMessageType::DecisionRequest,
+        MessageType::DecisionResponse => ProtoMessageType::DecisionResponse,
+        MessageType::Custom(name) => return (ProtoMessageType::Custom, Some(name.clone())),
+        other => return (ProtoMessageType::Custom, Some(format!("{:?}", other))),
+    };
+    (proto, None)
+}
+
+fn message_from_proto(message: ProtoAgentMessage) -> AgentMessage {
+    AgentMessage {
+        message_type: message_type_from_proto(message.message_type(), &message.metadata),
+        priority: match message.priority() {
+            ProtoMessagePriority::Low => MessagePriority::Low,
+            ProtoMessagePriority::Unspecified | ProtoMessagePriority::Normal => {
+                MessagePriority::Normal
+            }
+            ProtoMessagePriority::High => MessagePriority::High,
+            ProtoMessagePriority::Critical => MessagePriority::Critical,
+            ProtoMessagePriority::Emergency => MessagePriority::Emergency,
+        },
+        payload: message
+            .payload
+            .as_ref()
+            .and_then(|payload| serde_json::from_slice(&payload.value).ok()),
+        timestamp: message
+            .timestamp
+            .as_ref()
+            .and_then(datetime)
+            .unwrap_or_else(chrono::Utc::now),
+        expires_at: message.expires_at.as_ref().and_then(datetime),
+        id: message.id,
+        sender_id: message.sender_id,
+        recipient_ids: message.recipient_ids,
+        content: message.content,
+        requires_ack: message.requires_ack,
+        metadata: message.metadata,
+    }
+}
+
+fn message_to_proto(message: AgentMessage) -> ProtoAgentMessage {
+    let (message_type, type_name) = message_type_to_proto(&message.message_type);
+    let mut metadata = message.metadata;
+    if let Some(name) = type_name {
+        metadata.insert(MESSAGE_TYPE_KEY.to_string(), name);
+    }
+    ProtoAgentMessage {
+        message_type: message_type as i32,
+        priority: match message.priority {
+            MessagePriority::Low => ProtoMessagePriority::Low,
+            MessagePriority::Normal => ProtoMessagePriority::Normal,
+            MessagePriority::High => ProtoMessagePriority::High,
+            MessagePriority::Critical => ProtoMessagePriority::Critical,
+            MessagePriority::Emergency => ProtoMessagePriority::Emergency,
+        } as i32,
+        payload: message.payload.map(|payload| Any {
+            type_url: JSON_PAYLOAD_TYPE.to_string(),
+            value: payload.to_string().into_bytes(),
+        }),
+        timestamp: Some(timestamp(message.timestamp)),
+        expires_at: message.expires_at.map(timestamp),
+        id: message.id,
+        sender_id: message.sender_id,
+        recipient_ids: message.recipient_ids,
+        content: message.content,
+        requires_ack: message.requires_ack,
+        metadata,
+    }
+}
+
+fn stats_to_proto(stats: CoordinationStats) -> ProtoCoordinationStats {
+    ProtoCoordinationStats {
+        total_messages: stats.total_messages as u32,
+        messages_delivered: stats.messages_delivered as u32,
+        messages_failed: stats.messages_failed as u32,
+        active_agents: stats.active_agents as u32,
+        active_sessions: stats.active_sessions as u32,
+        avg_response_time_ms: stats.avg_response_time_ms,
+        coordination_efficiency: stats.coordination_efficiency,
+    }
 }
-*/
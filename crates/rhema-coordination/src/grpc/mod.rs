@@ -14,21 +14,23 @@
  * limitations under the License.
  */
 
+// Handlers and interceptors return tonic::Status, which is large by design
+#![allow(clippy::result_large_err)]
+
+pub mod auth;
 pub mod coordination_client;
 pub mod coordination_service;
 pub mod server;
 
 // Re-export main types
+pub use auth::{check_claims, require_scope, AgentTokenInterceptor};
 pub use coordination_client::{GrpcClientConfig, GrpcCoordinationClient};
 pub use coordination_service::CoordinationService;
 pub use server::{GrpcCoordinationServer, GrpcServerConfig};
 
-// Temporarily comment out the protobuf module until we fix the dependencies
-/*
 pub mod coordination {
     tonic::include_proto!("rhema.coordination.v1");
 }
-*/
 
 /// Example usage of the gRPC coordination system
 pub fn example_usage() {
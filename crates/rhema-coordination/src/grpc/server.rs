@@ -14,18 +14,17 @@
  * limitations under the License.
  */
 
+use rhema_core::agent_tokens::AgentTokenStore;
 use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::Notify;
+use tonic::transport::Server;
 use tracing::info;
 
 use crate::agent::real_time_coordination::RealTimeCoordinationSystem;
-
-// Temporarily comment out the generated protobuf code until we fix the dependencies
-/*
-use crate::grpc::coordination::{
-    coordination_server::CoordinationServer,
-    health_server::HealthServer,
-};
-*/
+use crate::grpc::auth::AgentTokenInterceptor;
+use crate::grpc::coordination::real_time_coordination_service_server::RealTimeCoordinationServiceServer;
+use crate::grpc::coordination_service::CoordinationService;
 
 /// gRPC server configuration
 #[derive(Debug, Clone)]
@@ -61,6 +60,8 @@ impl Default for GrpcServerConfig {
 pub struct GrpcCoordinationServer {
     config: GrpcServerConfig,
     coordination_system: RealTimeCoordinationSystem,
+    agent_tokens: Option<AgentTokenStore>,
+    shutdown: Arc<Notify>,
 }
 
 impl GrpcCoordinationServer {
@@ -68,62 +69,54 @@ impl GrpcCoordinationServer {
         Self {
             config,
             coordination_system,
+            agent_tokens: None,
+            shutdown: Arc::new(Notify::new()),
         }
     }
 
+    /// Require scoped agent tokens on coordination calls
+    pub fn with_agent_tokens(mut self, store: AgentTokenStore) -> Self {
+        self.agent_tokens = Some(store);
+        self
+    }
+
+    fn token_interceptor(&self) -> Option<AgentTokenInterceptor> {
+        self.agent_tokens.clone().map(AgentTokenInterceptor::new)
+    }
+
+    /// Serve the coordination service until [`stop`](Self::stop) is called
     pub async fn start(&self) -> Result<(), Box<dyn std::error::Error>> {
         let addr = format!("{}:{}", self.config.host, self.config.port).parse::<SocketAddr>()?;
+        if self.config.enable_tls {
+            return Err("TLS is not supported by the coordination gRPC server".into());
+        }
 
         info!("Starting gRPC server on {}", addr);
-
-        // For now, just log that we would start the server
-        println!("Would start gRPC server on {}", addr);
-
-        // TODO: Implement actual server startup when dependencies are fixed
-        /*
         let service = CoordinationService::new(self.coordination_system.clone());
-        let health_service = HealthService::new();
-
         let mut server = Server::builder()
-            .max_concurrent_streams(self.config.max_concurrent_streams)
-            .max_concurrent_requests(self.config.max_concurrent_requests);
-
-        if self.config.enable_reflection {
-            #[cfg(feature = "reflection")]
-            {
-                let reflection_service = tonic_reflection::server::Builder::configure()
-                    .register_encoded_file_descriptor_set(
-                        crate::grpc::coordination::FILE_DESCRIPTOR_SET,
-                    )
-                    .build()
-                    .unwrap();
-                server = server.add_service(reflection_service);
-            }
-        }
+            .max_concurrent_streams(Some(self.config.max_concurrent_streams as u32))
+            .concurrency_limit_per_connection(self.config.max_concurrent_requests);
 
-        if self.config.enable_health {
-            server = server.add_service(HealthServer::new(health_service));
-        }
-
-        server = server.add_service(CoordinationServer::new(service));
-
-        if self.config.enable_tls {
-            let cert = std::fs::read_to_string(self.config.cert_path.as_ref().unwrap())?;
-            let key = std::fs::read_to_string(self.config.key_path.as_ref().unwrap())?;
-            let identity = tonic::transport::Identity::from_pem(cert, key);
-            let tls_acceptor = tonic::transport::server::TlsAcceptor::new(identity, addr).await?;
-            server.serve_with_incoming(tls_acceptor).await?;
-        } else {
-            server.serve(addr).await?;
-        }
-        */
+        let router = match self.token_interceptor() {
+            Some(interceptor) => {
+                server.add_service(RealTimeCoordinationServiceServer::with_interceptor(
+                    service.requiring_tokens(),
+                    interceptor,
+                ))
+            }
+            None => server.add_service(RealTimeCoordinationServiceServer::new(service)),
+        };
 
+        let shutdown = self.shutdown.clone();
+        router
+            .serve_with_shutdown(addr, async move { shutdown.notified().await })
+            .await?;
         Ok(())
     }
 
     pub async fn stop(&self) -> Result<(), Box<dyn std::error::Error>> {
         info!("Stopping gRPC server");
-        // TODO: Implement actual server shutdown when dependencies are fixed
+        self.shutdown.notify_one();
         Ok(())
     }
 }
@@ -165,3 +158,109 @@ impl tonic::health::server::Health for HealthService {
     }
 }
 */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grpc::coordination::{
+        real_time_coordination_service_client::RealTimeCoordinationServiceClient, AgentInfo,
+        GetAllAgentsRequest, RegisterAgentRequest,
+    };
+    use chrono::Duration;
+    use tempfile::TempDir;
+    use tonic::{Code, Request};
+
+    fn register(scope: &str) -> RegisterAgentRequest {
+        RegisterAgentRequest {
+            agent_info: Some(AgentInfo {
+                id: format!("{}-agent", scope),
+                name: format!("{} agent", scope),
+                agent_type: "planner".to_string(),
+                assigned_scope: scope.to_string(),
+                is_online: true,
+                ..Default::default()
+            }),
+        }
+    }
+
+    fn with_token<T>(message: T, token: &str) -> Request<T> {
+        let mut request = Request::new(message);
+        request.metadata_mut().insert(
+            "authorization",
+            format!("Bearer {}", token).parse().unwrap(),
+        );
+        request
+    }
+
+    #[tokio::test]
+    async fn test_served_service_requires_scoped_tokens() {
+        let temp = TempDir::new().unwrap();
+        let store = AgentTokenStore::new(temp.path());
+        let issued = store
+            .issue(
+                "planner",
+                vec!["write:api".parse().unwrap()],
+                Duration::hours(1),
+            )
+            .unwrap();
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let server = Arc::new(
+            GrpcCoordinationServer::new(
+                RealTimeCoordinationSystem::new(),
+                GrpcServerConfig {
+                    port,
+                    ..Default::default()
+                },
+            )
+            .with_agent_tokens(store),
+        );
+        let serving = tokio::spawn({
+            let server = server.clone();
+            async move { server.start().await.map_err(|e| e.to_string()) }
+        });
+
+        let endpoint = format!("http://127.0.0.1:{}", port);
+        let mut client = None;
+        for _ in 0..50 {
+            match RealTimeCoordinationServiceClient::connect(endpoint.clone()).await {
+                Ok(connected) => {
+                    client = Some(connected);
+                    break;
+                }
+                Err(_) => tokio::time::sleep(std::time::Duration::from_millis(50)).await,
+            }
+        }
+        let mut client = client.expect("server did not start");
+
+        let status = client.register_agent(register("api")).await.unwrap_err();
+        assert_eq!(status.code(), Code::Unauthenticated);
+
+        let status = client
+            .register_agent(with_token(register("docs"), &issued.token))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), Code::PermissionDenied);
+
+        let response = client
+            .register_agent(with_token(register("api"), &issued.token))
+            .await
+            .unwrap();
+        assert!(response.into_inner().success);
+
+        let agents = client
+            .get_all_agents(with_token(GetAllAgentsRequest {}, &issued.token))
+            .await
+            .unwrap()
+            .into_inner()
+            .agents;
+        assert_eq!(agents.len(), 1);
+        assert_eq!(agents[0].assigned_scope, "api");
+
+        server.stop().await.unwrap();
+        serving.await.unwrap().unwrap();
+    }
+}
@@ -17,21 +17,25 @@
 pub mod advanced_features;
 pub mod agent;
 pub mod ai_service;
+pub mod chaos;
 pub mod context_injection;
 pub mod coordination_integration;
 pub mod distributed;
+pub mod event_export;
 pub mod grpc;
+pub mod loadtest;
 pub mod persistence;
 pub mod production_config;
 pub mod production_integration;
+pub mod provider_health;
 
 // Re-export main components for easy access
 pub use advanced_features::{
     AdvancedFeaturesConfig, AdvancedFeaturesManager, PerformanceAlert, PerformanceMetric,
 };
 pub use agent::conflict_prevention::{
-    Conflict, ConflictPreventionSystem, ConflictResolution, ConflictSeverity, ConflictStatus,
-    ConflictType, ResolutionStrategy,
+    Conflict, ConflictAvoidance, ConflictPreventionSystem, ConflictResolution, ConflictSeverity,
+    ConflictStatus, ConflictType, PlannedWork, PredictedConflict, ResolutionStrategy,
 };
 pub use agent::constraint_system::{
     Constraint, ConstraintContext, ConstraintSeverity, ConstraintSystem, ConstraintType,
@@ -49,6 +53,7 @@ pub use agent::task_scoring::{
 };
 pub use coordination_integration::{CoordinationConfig, CoordinationIntegration, IntegrationStats};
 pub use distributed::{DistributedConfig, DistributedManager, NodeInfo, ServiceInfo};
+pub use event_export::{EventExportBridge, EventSink, FlushReport, SinkBacklog};
 pub use grpc::{
     GrpcClientConfig, GrpcCoordinationClient, GrpcCoordinationServer, GrpcServerConfig,
 };
@@ -0,0 +1,888 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Load testing harness for the coordination system.
+//!
+//! A stage spawns simulated agents that register with a [`LoadTarget`],
+//! form sessions and send direct and session messages at a fixed rate.
+//! Every delivery is timed end to end, so a stage reports throughput,
+//! latency percentiles and drop rates. A capacity sweep repeats stages with
+//! a growing number of agents (or message rate) until a [`SloConfig`]
+//! threshold is breached, and reports the last configuration that held.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use rhema_core::{RhemaError, RhemaResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio::time::MissedTickBehavior;
+use tracing::debug;
+use uuid::Uuid;
+
+use crate::agent::real_time_coordination::{
+    AgentInfo, AgentMessage, AgentPerformanceMetrics, AgentStatus,
+    CoordinationConfig as SystemConfig, MessagePriority, MessageType, RealTimeCoordinationSystem,
+};
+
+/// Metadata key carrying the send time (microseconds since stage start)
+const SENT_AT_KEY: &str = "loadtest_sent_us";
+
+/// Coordination endpoint driven by a load test
+#[async_trait]
+pub trait LoadTarget: Send + Sync {
+    /// Human-readable description of the target
+    fn name(&self) -> String;
+
+    /// Configuration limits of the target, included in capacity reports
+    fn limits(&self) -> BTreeMap<String, String> {
+        BTreeMap::new()
+    }
+
+    async fn register_agent(&self, agent: AgentInfo) -> RhemaResult<()>;
+
+    /// Stream of messages delivered to `agent_id`
+    async fn subscribe(&self, agent_id: &str) -> Option<mpsc::Receiver<AgentMessage>>;
+
+    async fn unregister_agent(&self, agent_id: &str) -> RhemaResult<()>;
+
+    async fn send_message(&self, message: AgentMessage) -> RhemaResult<()>;
+
+    async fn create_session(&self, topic: String, participants: Vec<String>)
+        -> RhemaResult<String>;
+
+    async fn send_session_message(
+        &self,
+        session_id: &str,
+        message: AgentMessage,
+    ) -> RhemaResult<()>;
+}
+
+/// Coordination system running in this process with the daemon's configuration
+pub struct InProcessTarget {
+    system: RealTimeCoordinationSystem,
+    config: SystemConfig,
+}
+
+impl InProcessTarget {
+    pub fn new(config: SystemConfig) -> Self {
+        Self {
+            system: RealTimeCoordinationSystem::with_config(config.clone()),
+            config,
+        }
+    }
+}
+
+#[async_trait]
+impl LoadTarget for InProcessTarget {
+    fn name(&self) -> String {
+        "in-process coordination system".to_string()
+    }
+
+    fn limits(&self) -> BTreeMap<String, String> {
+        BTreeMap::from([
+            (
+                "max_session_participants".to_string(),
+                self.config.max_session_participants.to_string(),
+            ),
+            (
+                "max_message_history".to_string(),
+                self.config.max_message_history.to_string(),
+            ),
+            (
+                "message_timeout_seconds".to_string(),
+                self.config.message_timeout_seconds.to_string(),
+            ),
+        ])
+    }
+
+    async fn register_agent(&self, agent: AgentInfo) -> RhemaResult<()> {
+        self.system.register_agent(agent).await
+    }
+
+    async fn subscribe(&self, agent_id: &str) -> Option<mpsc::Receiver<AgentMessage>> {
+        self.system.get_message_stream(agent_id).await
+    }
+
+    async fn unregister_agent(&self, agent_id: &str) -> RhemaResult<()> {
+        self.system.unregister_agent(agent_id).await
+    }
+
+    async fn send_message(&self, message: AgentMessage) -> RhemaResult<()> {
+        self.system.send_message(message).await
+    }
+
+    async fn create_session(
+        &self,
+        topic: String,
+        participants: Vec<String>,
+    ) -> RhemaResult<String> {
+        self.system.create_session(topic, participants).await
+    }
+
+    async fn send_session_message(
+        &self,
+        session_id: &str,
+        message: AgentMessage,
+    ) -> RhemaResult<()> {
+        self.system.send_session_message(session_id, message).await
+    }
+}
+
+/// Workload generated by one stage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkloadConfig {
+    /// Number of simulated agents
+    pub agents: usize,
+    /// Messages per second sent by each agent
+    pub rate_per_agent: f64,
+    /// How long agents send messages
+    pub duration_secs: f64,
+    /// Fraction of messages sent to a session instead of a single agent
+    pub session_ratio: f64,
+    /// Agents per session
+    pub session_size: usize,
+    /// Size of each message body
+    pub payload_bytes: usize,
+    /// Message bodies agents pick from, e.g. the entries of a generated
+    /// repository; bodies of `payload_bytes` filler when empty
+    #[serde(default)]
+    pub payloads: Vec<String>,
+    /// Deliveries arriving later than this count as dropped
+    pub delivery_timeout_ms: u64,
+    /// Seed for recipient and message-kind selection
+    pub seed: u64,
+}
+
+impl Default for WorkloadConfig {
+    fn default() -> Self {
+        Self {
+            agents: 10,
+            rate_per_agent: 5.0,
+            duration_secs: 10.0,
+            session_ratio: 0.3,
+            session_size: 4,
+            payload_bytes: 256,
+            payloads: Vec::new(),
+            delivery_timeout_ms: 1000,
+            seed: 42,
+        }
+    }
+}
+
+/// Service level objectives a stage must meet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SloConfig {
+    /// Maximum 99th percentile delivery latency
+    pub max_p99_latency_ms: f64,
+    /// Maximum fraction of deliveries lost or late
+    pub max_drop_rate: f64,
+    /// Maximum fraction of operations rejected by the target
+    pub max_error_rate: f64,
+    /// Minimum fraction of the offered message rate actually sent
+    pub min_throughput_ratio: f64,
+}
+
+impl Default for SloConfig {
+    fn default() -> Self {
+        Self {
+            max_p99_latency_ms: 50.0,
+            max_drop_rate: 0.001,
+            max_error_rate: 0.001,
+            min_throughput_ratio: 0.95,
+        }
+    }
+}
+
+/// Latency distribution in milliseconds
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LatencySummary {
+    pub samples: usize,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+impl LatencySummary {
+    /// Summarize latencies given in microseconds
+    pub fn from_micros(mut samples: Vec<u64>) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        samples.sort_unstable();
+        let percentile = |p: f64| {
+            let rank = ((p / 100.0) * samples.len() as f64).ceil() as usize;
+            samples[rank.clamp(1, samples.len()) - 1] as f64 / 1000.0
+        };
+        Self {
+            samples: samples.len(),
+            mean_ms: samples.iter().sum::<u64>() as f64 / samples.len() as f64 / 1000.0,
+            p50_ms: percentile(50.0),
+            p90_ms: percentile(90.0),
+            p99_ms: percentile(99.0),
+            max_ms: samples[samples.len() - 1] as f64 / 1000.0,
+        }
+    }
+}
+
+/// Measurements from one stage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StageResult {
+    pub agents: usize,
+    pub rate_per_agent: f64,
+    /// Messages per second the workload asked for
+    pub offered_rate: f64,
+    /// Messages per second actually sent
+    pub achieved_rate: f64,
+    /// Deliveries per second
+    pub delivery_throughput: f64,
+    pub messages_sent: u64,
+    pub send_errors: u64,
+    pub sessions_created: u64,
+    pub session_errors: u64,
+    pub expected_deliveries: u64,
+    pub delivered: u64,
+    /// Deliveries lost or later than the delivery timeout
+    pub dropped: u64,
+    pub drop_rate: f64,
+    pub error_rate: f64,
+    pub delivery_latency: LatencySummary,
+    /// Time for the target to accept a message
+    pub send_latency: LatencySummary,
+    /// SLOs this stage breached
+    pub violations: Vec<String>,
+    /// First error returned by the target, if any
+    pub first_error: Option<String>,
+}
+
+impl StageResult {
+    pub fn passed(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    fn check(&mut self, slo: &SloConfig) {
+        let mut violations = Vec::new();
+        if self.delivery_latency.p99_ms > slo.max_p99_latency_ms {
+            violations.push(format!(
+                "p99 latency {:.2}ms > {:.2}ms",
+                self.delivery_latency.p99_ms, slo.max_p99_latency_ms
+            ));
+        }
+        if self.drop_rate > slo.max_drop_rate {
+            violations.push(format!(
+                "drop rate {:.4} > {:.4}",
+                self.drop_rate, slo.max_drop_rate
+            ));
+        }
+        if self.error_rate > slo.max_error_rate {
+            violations.push(format!(
+                "error rate {:.4} > {:.4}",
+                self.error_rate, slo.max_error_rate
+            ));
+        }
+        let ratio = if self.offered_rate > 0.0 {
+            self.achieved_rate / self.offered_rate
+        } else {
+            1.0
+        };
+        if ratio < slo.min_throughput_ratio {
+            violations.push(format!(
+                "throughput {:.1}/s is {:.0}% of offered {:.1}/s (< {:.0}%)",
+                self.achieved_rate,
+                ratio * 100.0,
+                self.offered_rate,
+                slo.min_throughput_ratio * 100.0
+            ));
+        }
+        self.violations = violations;
+    }
+}
+
+#[derive(Default)]
+struct Counters {
+    sent: AtomicU64,
+    send_errors: AtomicU64,
+    sessions_created: AtomicU64,
+    session_errors: AtomicU64,
+    expected: AtomicU64,
+    delivered: AtomicU64,
+    late: AtomicU64,
+}
+
+/// Small deterministic generator so runs are reproducible without `rand`
+pub(crate) struct XorShift(u64);
+
+impl XorShift {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    pub(crate) fn unit(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    pub(crate) fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+fn record_error(first_error: &Mutex<Option<String>>, error: &RhemaError) {
+    let mut first = first_error.lock().unwrap();
+    if first.is_none() {
+        *first = Some(error.to_string());
+    }
+}
+
+fn simulated_agent(id: &str) -> AgentInfo {
+    AgentInfo {
+        id: id.to_string(),
+        name: id.to_string(),
+        agent_type: "loadtest".to_string(),
+        status: AgentStatus::Idle,
+        current_task_id: None,
+        assigned_scope: "loadtest".to_string(),
+        capabilities: Vec::new(),
+        last_heartbeat: Utc::now(),
+        is_online: true,
+        performance_metrics: AgentPerformanceMetrics::default(),
+        liveness: Default::default(),
+    }
+}
+
+/// Run one stage of `workload` against `target`
+pub async fn run_stage(
+    target: Arc<dyn LoadTarget>,
+    workload: &WorkloadConfig,
+    slo: &SloConfig,
+) -> RhemaResult<StageResult> {
+    if workload.agents < 2 {
+        return Err(RhemaError::InvalidInput(
+            "A load test needs at least 2 agents".to_string(),
+        ));
+    }
+    if workload.rate_per_agent <= 0.0 || workload.duration_secs <= 0.0 {
+        return Err(RhemaError::InvalidInput(
+            "Message rate and duration must be positive".to_string(),
+        ));
+    }
+
+    let run_id = Uuid::new_v4().simple().to_string()[..8].to_string();
+    let agent_ids: Vec<String> = (0..workload.agents)
+        .map(|i| format!("loadtest-{}-{}", run_id, i))
+        .collect();
+    let counters = Arc::new(Counters::default());
+    let delivery_latencies = Arc::new(Mutex::new(Vec::new()));
+    let send_latencies = Arc::new(Mutex::new(Vec::new()));
+    let first_error = Arc::new(Mutex::new(None));
+    let timeout_us = workload.delivery_timeout_ms * 1000;
+    let started = Instant::now();
+
+    // Register agents and start consuming their message streams
+    let mut receivers = Vec::new();
+    for id in &agent_ids {
+        target.register_agent(simulated_agent(id)).await?;
+        let Some(mut rx) = target.subscribe(id).await else {
+            return Err(RhemaError::CoordinationError(format!(
+                "Target did not provide a message stream for {}",
+                id
+            )));
+        };
+        let counters = counters.clone();
+        let latencies = delivery_latencies.clone();
+        receivers.push(tokio::spawn(async move {
+            while let Some(message) = rx.recv().await {
+                let Some(sent_us) = message
+                    .metadata
+                    .get(SENT_AT_KEY)
+                    .and_then(|sent| sent.parse::<u64>().ok())
+                else {
+                    continue;
+                };
+                let latency = (started.elapsed().as_micros() as u64).saturating_sub(sent_us);
+                if latency > timeout_us {
+                    counters.late.fetch_add(1, Ordering::Relaxed);
+                } else {
+                    counters.delivered.fetch_add(1, Ordering::Relaxed);
+                    latencies.lock().unwrap().push(latency);
+                }
+            }
+        }));
+    }
+
+    // Group agents into sessions
+    let mut sessions: HashMap<usize, (String, usize)> = HashMap::new();
+    if workload.session_ratio > 0.0 && workload.session_size >= 2 {
+        for (group_index, group) in agent_ids.chunks(workload.session_size).enumerate() {
+            if group.len() < 2 {
+                continue;
+            }
+            match target
+                .create_session(
+                    format!("loadtest-{}-{}", run_id, group_index),
+                    group.to_vec(),
+                )
+                .await
+            {
+                Ok(session_id) => {
+                    counters.sessions_created.fetch_add(1, Ordering::Relaxed);
+                    for offset in 0..group.len() {
+                        sessions.insert(
+                            group_index * workload.session_size + offset,
+                            (session_id.clone(), group.len()),
+                        );
+                    }
+                }
+                Err(e) => {
+                    counters.session_errors.fetch_add(1, Ordering::Relaxed);
+                    record_error(&first_error, &e);
+                }
+            }
+        }
+    }
+
+    // Send at a fixed rate; ticks missed while the target is saturated are
+    // skipped so they show up as lost throughput rather than bursts
+    let send_window = Duration::from_secs_f64(workload.duration_secs);
+    let send_started = Instant::now();
+    let interval = Duration::from_secs_f64(1.0 / workload.rate_per_agent);
+    let bodies = Arc::new(if workload.payloads.is_empty() {
+        vec!["x".repeat(workload.payload_bytes.max(1))]
+    } else {
+        workload.payloads.clone()
+    });
+    let mut senders = Vec::new();
+    for (index, id) in agent_ids.iter().enumerate() {
+        let target = target.clone();
+        let counters = counters.clone();
+        let send_latencies = send_latencies.clone();
+        let first_error = first_error.clone();
+        let agent_ids = agent_ids.clone();
+        let session = sessions.get(&index).cloned();
+        let id = id.clone();
+        let bodies = bodies.clone();
+        let session_ratio = workload.session_ratio;
+        let mut rng = XorShift::new(workload.seed ^ index as u64);
+
+        senders.push(tokio::spawn(async move {
+            // Stagger agents so they do not all send on the same tick
+            tokio::time::sleep(interval.mul_f64(rng.unit())).await;
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+            while send_started.elapsed() < send_window {
+                ticker.tick().await;
+                if send_started.elapsed() >= send_window {
+                    break;
+                }
+                let sent_at = started.elapsed();
+
+                let mut message = AgentMessage {
+                    id: Uuid::new_v4().to_string(),
+                    message_type: MessageType::Custom("loadtest".to_string()),
+                    priority: MessagePriority::Normal,
+                    sender_id: id.clone(),
+                    recipient_ids: Vec::new(),
+                    content: bodies[rng.below(bodies.len())].clone(),
+                    payload: None,
+                    timestamp: Utc::now(),
+                    requires_ack: false,
+                    expires_at: None,
+                    metadata: HashMap::from([(
+                        SENT_AT_KEY.to_string(),
+                        sent_at.as_micros().to_string(),
+                    )]),
+                };
+
+                let (result, deliveries) = match &session {
+                    Some((session_id, size)) if rng.unit() < session_ratio => (
+                        target.send_session_message(session_id, message).await,
+                        *size as u64,
+                    ),
+                    _ => {
+                        let mut recipient = rng.below(agent_ids.len() - 1);
+                        if recipient >= index {
+                            recipient += 1;
+                        }
+                        message.recipient_ids = vec![agent_ids[recipient].clone()];
+                        (target.send_message(message).await, 1)
+                    }
+                };
+                let send_latency = (started.elapsed() - sent_at).as_micros() as u64;
+
+                match result {
+                    Ok(()) => {
+                        counters.sent.fetch_add(1, Ordering::Relaxed);
+                        counters.expected.fetch_add(deliveries, Ordering::Relaxed);
+                        send_latencies.lock().unwrap().push(send_latency);
+                    }
+                    Err(e) => {
+                        counters.send_errors.fetch_add(1, Ordering::Relaxed);
+                        record_error(&first_error, &e);
+                    }
+                }
+            }
+        }));
+    }
+    for sender in senders {
+        sender
+            .await
+            .map_err(|e| RhemaError::CoordinationError(format!("Load agent failed: {}", e)))?;
+    }
+    let elapsed_send = send_started
+        .elapsed()
+        .as_secs_f64()
+        .min(workload.duration_secs);
+
+    // Wait for in-flight deliveries
+    let deadline = Instant::now() + Duration::from_millis(workload.delivery_timeout_ms);
+    while Instant::now() < deadline {
+        let received =
+            counters.delivered.load(Ordering::Relaxed) + counters.late.load(Ordering::Relaxed);
+        if received >= counters.expected.load(Ordering::Relaxed) {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    let elapsed_total = started.elapsed().as_secs_f64();
+
+    for id in &agent_ids {
+        if let Err(e) = target.unregister_agent(id).await {
+            debug!("Failed to unregister load agent {}: {}", id, e);
+        }
+    }
+    for receiver in receivers {
+        let _ = tokio::time::timeout(Duration::from_secs(1), receiver).await;
+    }
+
+    let sent = counters.sent.load(Ordering::Relaxed);
+    let send_errors = counters.send_errors.load(Ordering::Relaxed);
+    let sessions_created = counters.sessions_created.load(Ordering::Relaxed);
+    let session_errors = counters.session_errors.load(Ordering::Relaxed);
+    let expected = counters.expected.load(Ordering::Relaxed);
+    let delivered = counters.delivered.load(Ordering::Relaxed).min(expected);
+    let dropped = expected - delivered;
+    let operations = sent + send_errors + sessions_created + session_errors;
+    let offered_rate = workload.agents as f64 * workload.rate_per_agent;
+
+    let mut result = StageResult {
+        agents: workload.agents,
+        rate_per_agent: workload.rate_per_agent,
+        offered_rate,
+        achieved_rate: sent as f64 / elapsed_send,
+        delivery_throughput: delivered as f64 / elapsed_total,
+        messages_sent: sent,
+        send_errors,
+        sessions_created,
+        session_errors,
+        expected_deliveries: expected,
+        delivered,
+        dropped,
+        drop_rate: if expected == 0 {
+            0.0
+        } else {
+            dropped as f64 / expected as f64
+        },
+        error_rate: if operations == 0 {
+            0.0
+        } else {
+            (send_errors + session_errors) as f64 / operations as f64
+        },
+        delivery_latency: LatencySummary::from_micros(std::mem::take(
+            &mut *delivery_latencies.lock().unwrap(),
+        )),
+        send_latency: LatencySummary::from_micros(std::mem::take(
+            &mut *send_latencies.lock().unwrap(),
+        )),
+        violations: Vec::new(),
+        first_error: first_error.lock().unwrap().take(),
+    };
+    result.check(slo);
+    Ok(result)
+}
+
+/// Workload parameter grown between capacity stages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum SweepDimension {
+    /// Number of agents
+    Agents,
+    /// Messages per second per agent
+    Rate,
+}
+
+/// How a capacity run grows the workload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapacitySweep {
+    pub dimension: SweepDimension,
+    /// Largest value to try
+    pub max_value: f64,
+    /// Multiplier applied between stages
+    pub factor: f64,
+}
+
+/// Result of a capacity run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapacityReport {
+    pub target: String,
+    pub limits: BTreeMap<String, String>,
+    pub workload: WorkloadConfig,
+    pub slo: SloConfig,
+    pub sweep: CapacitySweep,
+    pub stages: Vec<StageResult>,
+    /// Largest stage that met every SLO
+    pub max_sustainable: Option<StageResult>,
+    /// First stage that breached an SLO
+    pub breaking_point: Option<StageResult>,
+}
+
+impl CapacityReport {
+    /// Render the report as Markdown
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("# Coordination capacity report\n\n");
+        out.push_str(&format!("- Target: {}\n", self.target));
+        for (limit, value) in &self.limits {
+            out.push_str(&format!("- `{}`: {}\n", limit, value));
+        }
+        out.push_str(&format!(
+            "- Workload: {:.0}s per stage, {:.0}% session messages, sessions of {}, {} byte payloads\n",
+            self.workload.duration_secs,
+            self.workload.session_ratio * 100.0,
+            self.workload.session_size,
+            self.workload.payload_bytes
+        ));
+        out.push_str(&format!(
+            "- SLOs: p99 ≤ {}ms, drop rate ≤ {}, error rate ≤ {}, throughput ≥ {:.0}% of offered\n\n",
+            self.slo.max_p99_latency_ms,
+            self.slo.max_drop_rate,
+            self.slo.max_error_rate,
+            self.slo.min_throughput_ratio * 100.0
+        ));
+
+        out.push_str("## Capacity\n\n");
+        match &self.max_sustainable {
+            Some(stage) => out.push_str(&format!(
+                "Sustained {} agents at {} msg/s each ({:.1} msg/s offered) within all SLOs.\n",
+                stage.agents, stage.rate_per_agent, stage.offered_rate
+            )),
+            None => out.push_str("No stage met every SLO.\n"),
+        }
+        match &self.breaking_point {
+            Some(stage) => out.push_str(&format!(
+                "SLOs broke at {} agents at {} msg/s each: {}.\n",
+                stage.agents,
+                stage.rate_per_agent,
+                stage.violations.join("; ")
+            )),
+            None => out.push_str(&format!(
+                "No SLO broke up to the sweep limit of {} {}.\n",
+                self.sweep.max_value,
+                match self.sweep.dimension {
+                    SweepDimension::Agents => "agents",
+                    SweepDimension::Rate => "msg/s per agent",
+                }
+            )),
+        }
+        if let Some(error) = self
+            .breaking_point
+            .as_ref()
+            .and_then(|stage| stage.first_error.as_ref())
+        {
+            out.push_str(&format!("First error from the target: `{}`\n", error));
+        }
+
+        out.push_str("\n## Stages\n\n");
+        out.push_str("| Agents | Rate/agent | Offered/s | Sent/s | Delivered/s | p50 ms | p99 ms | Max ms | Drop rate | Error rate | SLOs |\n");
+        out.push_str("|---|---|---|---|---|---|---|---|---|---|---|\n");
+        for stage in &self.stages {
+            out.push_str(&format!(
+                "| {} | {} | {:.1} | {:.1} | {:.1} | {:.2} | {:.2} | {:.2} | {:.4} | {:.4} | {} |\n",
+                stage.agents,
+                stage.rate_per_agent,
+                stage.offered_rate,
+                stage.achieved_rate,
+                stage.delivery_throughput,
+                stage.delivery_latency.p50_ms,
+                stage.delivery_latency.p99_ms,
+                stage.delivery_latency.max_ms,
+                stage.drop_rate,
+                stage.error_rate,
+                if stage.passed() {
+                    "✅".to_string()
+                } else {
+                    format!("❌ {}", stage.violations.join("; "))
+                }
+            ));
+        }
+        out
+    }
+}
+
+/// Run stages of growing load until an SLO breaks or the sweep limit is
+/// reached. `make_target` provides a fresh target for every stage.
+pub async fn run_capacity<F>(
+    mut make_target: F,
+    workload: &WorkloadConfig,
+    slo: &SloConfig,
+    sweep: &CapacitySweep,
+) -> RhemaResult<CapacityReport>
+where
+    F: FnMut() -> Arc<dyn LoadTarget>,
+{
+    if sweep.factor <= 1.0 {
+        return Err(RhemaError::InvalidInput(
+            "Capacity sweep factor must be greater than 1".to_string(),
+        ));
+    }
+
+    let first_target = make_target();
+    let mut report = CapacityReport {
+        target: first_target.name(),
+        limits: first_target.limits(),
+        workload: workload.clone(),
+        slo: slo.clone(),
+        sweep: sweep.clone(),
+        stages: Vec::new(),
+        max_sustainable: None,
+        breaking_point: None,
+    };
+
+    let mut stage_workload = workload.clone();
+    let mut target = Some(first_target);
+    loop {
+        let stage_target = target.take().unwrap_or_else(&mut make_target);
+        debug!(
+            "Load stage: {} agents at {} msg/s",
+            stage_workload.agents, stage_workload.rate_per_agent
+        );
+        let result = run_stage(stage_target, &stage_workload, slo).await?;
+        report.stages.push(result.clone());
+        if !result.passed() {
+            report.breaking_point = Some(result);
+            break;
+        }
+        report.max_sustainable = Some(result);
+
+        match sweep.dimension {
+            SweepDimension::Agents => {
+                let next = ((stage_workload.agents as f64 * sweep.factor).ceil() as usize)
+                    .max(stage_workload.agents + 1);
+                if next as f64 > sweep.max_value {
+                    break;
+                }
+                stage_workload.agents = next;
+            }
+            SweepDimension::Rate => {
+                let next = stage_workload.rate_per_agent * sweep.factor;
+                if next > sweep.max_value {
+                    break;
+                }
+                stage_workload.rate_per_agent = next;
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quick_workload(agents: usize) -> WorkloadConfig {
+        WorkloadConfig {
+            agents,
+            rate_per_agent: 20.0,
+            duration_secs: 0.5,
+            session_size: 3,
+            delivery_timeout_ms: 500,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_latency_percentiles() {
+        let summary = LatencySummary::from_micros((1..=100).map(|ms| ms * 1000).collect());
+        assert_eq!(summary.samples, 100);
+        assert_eq!(summary.p50_ms, 50.0);
+        assert_eq!(summary.p99_ms, 99.0);
+        assert_eq!(summary.max_ms, 100.0);
+    }
+
+    #[tokio::test]
+    async fn test_stage_delivers_direct_and_session_messages() {
+        let target: Arc<dyn LoadTarget> = Arc::new(InProcessTarget::new(SystemConfig::default()));
+        let result = run_stage(target, &quick_workload(6), &SloConfig::default())
+            .await
+            .unwrap();
+
+        assert_eq!(result.sessions_created, 2);
+        assert!(result.messages_sent > 0);
+        assert_eq!(result.send_errors, 0);
+        assert_eq!(result.delivered, result.expected_deliveries);
+        assert_eq!(result.drop_rate, 0.0);
+        assert!(result.expected_deliveries > result.messages_sent);
+        assert_eq!(result.delivery_latency.samples as u64, result.delivered);
+    }
+
+    #[tokio::test]
+    async fn test_capacity_reports_configuration_limit() {
+        // Sessions larger than the daemon allows are rejected, breaking the
+        // error-rate SLO once the agent count reaches the session size
+        let config = SystemConfig {
+            max_session_participants: 3,
+            ..Default::default()
+        };
+        let workload = WorkloadConfig {
+            session_size: 4,
+            ..quick_workload(2)
+        };
+        let report = run_capacity(
+            || Arc::new(InProcessTarget::new(config.clone())) as Arc<dyn LoadTarget>,
+            &workload,
+            &SloConfig::default(),
+            &CapacitySweep {
+                dimension: SweepDimension::Agents,
+                max_value: 8.0,
+                factor: 2.0,
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(report.max_sustainable.as_ref().unwrap().agents, 2);
+        let breaking = report.breaking_point.as_ref().unwrap();
+        assert_eq!(breaking.agents, 4);
+        assert_eq!(breaking.session_errors, 1);
+        assert!(breaking
+            .first_error
+            .as_deref()
+            .unwrap()
+            .contains("participants"));
+        assert!(report.to_markdown().contains("SLOs broke at 4 agents"));
+    }
+}
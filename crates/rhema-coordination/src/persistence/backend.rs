@@ -0,0 +1,1262 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Pluggable storage engines for coordination data.
+//!
+//! Every engine stores two kinds of data per [`Collection`]: keyed records
+//! (agent registry, sessions) and append-only logs with monotonically
+//! increasing sequence numbers (messages, consensus entries, action history).
+//! The `File` backend keeps JSON files, embedded deployments use sled or
+//! SQLite, and shared deployments point several daemons at one Postgres
+//! database.
+
+use super::{PersistenceConfig, StorageBackend};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rhema_core::{RhemaError, RhemaResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{postgres::PgPool, sqlite::SqlitePool, Row};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{info, warn};
+
+/// Latest schema version understood by this build
+pub const CURRENT_SCHEMA_VERSION: u32 = 2;
+
+/// Data sets persisted by coordination
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Collection {
+    /// Agent messages (log)
+    Messages,
+    /// Registered agents (records)
+    Agents,
+    /// Consensus entries (log)
+    ConsensusLog,
+    /// Executed actions (log)
+    ActionHistory,
+    /// Coordination sessions (records)
+    Sessions,
+    /// Agent states, system metrics and configuration snapshots (records)
+    State,
+    /// Consensus node states, logs and configurations (records)
+    ConsensusState,
+}
+
+impl Collection {
+    pub const ALL: [Collection; 7] = [
+        Collection::Messages,
+        Collection::Agents,
+        Collection::ConsensusLog,
+        Collection::ActionHistory,
+        Collection::Sessions,
+        Collection::State,
+        Collection::ConsensusState,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Collection::Messages => "messages",
+            Collection::Agents => "agents",
+            Collection::ConsensusLog => "consensus_log",
+            Collection::ActionHistory => "action_history",
+            Collection::Sessions => "sessions",
+            Collection::State => "state",
+            Collection::ConsensusState => "consensus_state",
+        }
+    }
+}
+
+/// A keyed record
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredRecord {
+    pub key: String,
+    pub value: Value,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// An entry of an append-only log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub sequence: u64,
+    pub value: Value,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Storage engine behind the persistence manager
+#[async_trait]
+pub trait StorageEngine: Send + Sync {
+    /// Engine name for logging and diagnostics
+    fn name(&self) -> &'static str;
+
+    /// Insert or replace a record
+    async fn put(&self, collection: Collection, key: &str, value: &Value) -> RhemaResult<()>;
+
+    async fn get(&self, collection: Collection, key: &str) -> RhemaResult<Option<Value>>;
+
+    /// Delete a record, returning whether it existed
+    async fn delete(&self, collection: Collection, key: &str) -> RhemaResult<bool>;
+
+    /// All records of a collection ordered by key
+    async fn list(&self, collection: Collection) -> RhemaResult<Vec<StoredRecord>>;
+
+    /// Append to a log, returning the assigned sequence number
+    async fn append(&self, collection: Collection, value: &Value) -> RhemaResult<u64>;
+
+    /// Write a log entry with a given sequence (used when migrating backends)
+    async fn restore_entry(&self, collection: Collection, entry: &LogEntry) -> RhemaResult<()>;
+
+    /// Log entries with a sequence greater than `after`, oldest first
+    async fn read_log(
+        &self,
+        collection: Collection,
+        after: u64,
+        limit: usize,
+    ) -> RhemaResult<Vec<LogEntry>>;
+
+    /// Schema version currently applied to the store
+    async fn schema_version(&self) -> RhemaResult<u32>;
+
+    /// Apply pending schema migrations, returning the resulting version
+    async fn migrate(&self) -> RhemaResult<u32>;
+}
+
+/// Open the engine selected by a persistence configuration and bring its
+/// schema up to date
+pub async fn open_engine(config: &PersistenceConfig) -> RhemaResult<Arc<dyn StorageEngine>> {
+    let storage_path = config
+        .storage_path
+        .clone()
+        .unwrap_or_else(|| "./data".into());
+
+    let engine: Arc<dyn StorageEngine> = match config.backend {
+        StorageBackend::Memory => Arc::new(MemoryEngine::new()),
+        StorageBackend::File => Arc::new(FileEngine::open(&storage_path.join("engine")).await?),
+        StorageBackend::Sled => Arc::new(SledEngine::open(&storage_path.join("engine"))?),
+        StorageBackend::Sqlite => {
+            let url = match &config.connection_string {
+                Some(url) => url.clone(),
+                None => {
+                    tokio::fs::create_dir_all(&storage_path).await?;
+                    format!(
+                        "sqlite://{}?mode=rwc",
+                        storage_path.join("coordination.db").display()
+                    )
+                }
+            };
+            Arc::new(SqlEngine::connect(&url).await?)
+        }
+        StorageBackend::Postgres => {
+            let url = config.connection_string.as_ref().ok_or_else(|| {
+                RhemaError::ConfigError(
+                    "Postgres persistence requires a connection_string".to_string(),
+                )
+            })?;
+            Arc::new(SqlEngine::connect(url).await?)
+        }
+        StorageBackend::Redis => {
+            return Err(RhemaError::ConfigError(
+                "Redis is not supported as a persistence backend".to_string(),
+            ))
+        }
+    };
+
+    let version = engine.migrate().await?;
+    info!(
+        "Opened {} storage engine at schema v{}",
+        engine.name(),
+        version
+    );
+    Ok(engine)
+}
+
+/// Open an engine from a location string: `memory`, `file:<path>`,
+/// `sled:<path>`, `sqlite://...` or `postgres://...`
+pub async fn open_engine_url(location: &str) -> RhemaResult<Arc<dyn StorageEngine>> {
+    let engine: Arc<dyn StorageEngine> = if location == "memory" {
+        Arc::new(MemoryEngine::new())
+    } else if let Some(path) = location.strip_prefix("file:") {
+        Arc::new(FileEngine::open(Path::new(path)).await?)
+    } else if let Some(path) = location.strip_prefix("sled:") {
+        Arc::new(SledEngine::open(Path::new(path))?)
+    } else if location.starts_with("sqlite:")
+        || location.starts_with("postgres://")
+        || location.starts_with("postgresql://")
+    {
+        Arc::new(SqlEngine::connect(location).await?)
+    } else {
+        return Err(RhemaError::InvalidInput(format!(
+            "Unknown storage location '{}', expected memory, file:<path>, sled:<path>, sqlite://... or postgres://...",
+            location
+        )));
+    };
+
+    engine.migrate().await?;
+    Ok(engine)
+}
+
+/// All records of a collection as a key to value map
+pub(crate) async fn load_records(
+    engine: &dyn StorageEngine,
+    collection: Collection,
+) -> RhemaResult<BTreeMap<String, Value>> {
+    Ok(engine
+        .list(collection)
+        .await?
+        .into_iter()
+        .map(|record| (record.key, record.value))
+        .collect())
+}
+
+/// Write changed records, deleting those whose value is `None`
+pub(crate) async fn apply_record_changes(
+    engine: &dyn StorageEngine,
+    collection: Collection,
+    changes: Vec<(String, Option<Value>)>,
+) -> RhemaResult<()> {
+    for (key, value) in changes {
+        match value {
+            Some(value) => engine.put(collection, &key, &value).await?,
+            None => {
+                engine.delete(collection, &key).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Make the records of a collection exactly `entries`
+pub(crate) async fn replace_records(
+    engine: &dyn StorageEngine,
+    collection: Collection,
+    entries: &BTreeMap<String, Value>,
+) -> RhemaResult<()> {
+    for record in engine.list(collection).await? {
+        if !entries.contains_key(&record.key) {
+            engine.delete(collection, &record.key).await?;
+        }
+    }
+    for (key, value) in entries {
+        engine.put(collection, key, value).await?;
+    }
+    Ok(())
+}
+
+/// Result of copying one engine's contents into another
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BackendMigrationReport {
+    pub records: usize,
+    pub log_entries: usize,
+    pub per_collection: BTreeMap<String, usize>,
+}
+
+/// Copy all records and logs from `source` into `target`, preserving keys
+/// and sequence numbers. Existing target records with the same key are
+/// overwritten, so the copy can be re-run after an interruption.
+pub async fn migrate_backend(
+    source: &dyn StorageEngine,
+    target: &dyn StorageEngine,
+) -> RhemaResult<BackendMigrationReport> {
+    const PAGE: usize = 500;
+
+    target.migrate().await?;
+    let mut report = BackendMigrationReport::default();
+
+    for collection in Collection::ALL {
+        let mut copied = 0;
+
+        for record in source.list(collection).await? {
+            target.put(collection, &record.key, &record.value).await?;
+            report.records += 1;
+            copied += 1;
+        }
+
+        let mut after = 0;
+        loop {
+            let page = source.read_log(collection, after, PAGE).await?;
+            let Some(last) = page.last() else {
+                break;
+            };
+            after = last.sequence;
+            for entry in &page {
+                target.restore_entry(collection, entry).await?;
+            }
+            report.log_entries += page.len();
+            copied += page.len();
+        }
+
+        report
+            .per_collection
+            .insert(collection.as_str().to_string(), copied);
+    }
+
+    info!(
+        "Migrated {} records and {} log entries from {} to {}",
+        report.records,
+        report.log_entries,
+        source.name(),
+        target.name()
+    );
+    Ok(report)
+}
+
+/// Engine keeping everything in memory (tests and ephemeral daemons)
+#[derive(Default)]
+pub struct MemoryEngine {
+    records: RwLock<HashMap<Collection, BTreeMap<String, StoredRecord>>>,
+    logs: RwLock<HashMap<Collection, BTreeMap<u64, LogEntry>>>,
+}
+
+impl MemoryEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl StorageEngine for MemoryEngine {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    async fn put(&self, collection: Collection, key: &str, value: &Value) -> RhemaResult<()> {
+        self.records
+            .write()
+            .await
+            .entry(collection)
+            .or_default()
+            .insert(
+                key.to_string(),
+                StoredRecord {
+                    key: key.to_string(),
+                    value: value.clone(),
+                    updated_at: Utc::now(),
+                },
+            );
+        Ok(())
+    }
+
+    async fn get(&self, collection: Collection, key: &str) -> RhemaResult<Option<Value>> {
+        Ok(self
+            .records
+            .read()
+            .await
+            .get(&collection)
+            .and_then(|records| records.get(key))
+            .map(|record| record.value.clone()))
+    }
+
+    async fn delete(&self, collection: Collection, key: &str) -> RhemaResult<bool> {
+        Ok(self
+            .records
+            .write()
+            .await
+            .get_mut(&collection)
+            .is_some_and(|records| records.remove(key).is_some()))
+    }
+
+    async fn list(&self, collection: Collection) -> RhemaResult<Vec<StoredRecord>> {
+        Ok(self
+            .records
+            .read()
+            .await
+            .get(&collection)
+            .map(|records| records.values().cloned().collect())
+            .unwrap_or_default())
+    }
+
+    async fn append(&self, collection: Collection, value: &Value) -> RhemaResult<u64> {
+        let mut logs = self.logs.write().await;
+        let log = logs.entry(collection).or_default();
+        let sequence = log.keys().next_back().map_or(1, |last| last + 1);
+        log.insert(
+            sequence,
+            LogEntry {
+                sequence,
+                value: value.clone(),
+                created_at: Utc::now(),
+            },
+        );
+        Ok(sequence)
+    }
+
+    async fn restore_entry(&self, collection: Collection, entry: &LogEntry) -> RhemaResult<()> {
+        self.logs
+            .write()
+            .await
+            .entry(collection)
+            .or_default()
+            .insert(entry.sequence, entry.clone());
+        Ok(())
+    }
+
+    async fn read_log(
+        &self,
+        collection: Collection,
+        after: u64,
+        limit: usize,
+    ) -> RhemaResult<Vec<LogEntry>> {
+        Ok(self
+            .logs
+            .read()
+            .await
+            .get(&collection)
+            .map(|log| {
+                log.range(after + 1..)
+                    .take(limit)
+                    .map(|(_, entry)| entry.clone())
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    async fn schema_version(&self) -> RhemaResult<u32> {
+        Ok(CURRENT_SCHEMA_VERSION)
+    }
+
+    async fn migrate(&self) -> RhemaResult<u32> {
+        Ok(CURRENT_SCHEMA_VERSION)
+    }
+}
+
+/// Engine of the `File` backend: one JSON file of records and one JSON lines
+/// log per collection, mirrored in memory. Files are written before the
+/// in-memory copy changes, so a failed write leaves both unchanged.
+pub struct FileEngine {
+    dir: PathBuf,
+    memory: MemoryEngine,
+    write_lock: Mutex<()>,
+}
+
+impl FileEngine {
+    pub async fn open(dir: &Path) -> RhemaResult<Self> {
+        tokio::fs::create_dir_all(dir).await?;
+        let engine = Self {
+            dir: dir.to_path_buf(),
+            memory: MemoryEngine::new(),
+            write_lock: Mutex::new(()),
+        };
+
+        for collection in Collection::ALL {
+            let records_path = engine.records_path(collection);
+            if records_path.exists() {
+                let content = tokio::fs::read_to_string(&records_path).await?;
+                let records: Vec<StoredRecord> = serde_json::from_str(&content)?;
+                engine.memory.records.write().await.insert(
+                    collection,
+                    records.into_iter().map(|r| (r.key.clone(), r)).collect(),
+                );
+            }
+
+            let log_path = engine.log_path(collection);
+            if log_path.exists() {
+                let content = tokio::fs::read_to_string(&log_path).await?;
+                let mut log = BTreeMap::new();
+                for line in content.lines().filter(|line| !line.trim().is_empty()) {
+                    match serde_json::from_str::<LogEntry>(line) {
+                        Ok(entry) => {
+                            log.insert(entry.sequence, entry);
+                        }
+                        // Only the last line can be torn by an interrupted append
+                        Err(e) => {
+                            warn!("Skipping unreadable entry in {}: {}", log_path.display(), e)
+                        }
+                    }
+                }
+                engine.memory.logs.write().await.insert(collection, log);
+            }
+        }
+        Ok(engine)
+    }
+
+    fn records_path(&self, collection: Collection) -> PathBuf {
+        self.dir.join(format!("{}.json", collection.as_str()))
+    }
+
+    fn log_path(&self, collection: Collection) -> PathBuf {
+        self.dir.join(format!("{}.jsonl", collection.as_str()))
+    }
+
+    async fn current_records(&self, collection: Collection) -> BTreeMap<String, StoredRecord> {
+        self.memory
+            .records
+            .read()
+            .await
+            .get(&collection)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Replace the records file of a collection, then the in-memory copy
+    async fn write_records(
+        &self,
+        collection: Collection,
+        records: BTreeMap<String, StoredRecord>,
+    ) -> RhemaResult<()> {
+        let path = self.records_path(collection);
+        let temp = path.with_extension("json.tmp");
+        let values: Vec<&StoredRecord> = records.values().collect();
+        tokio::fs::write(&temp, serde_json::to_vec_pretty(&values)?).await?;
+        tokio::fs::rename(&temp, &path).await?;
+        self.memory
+            .records
+            .write()
+            .await
+            .insert(collection, records);
+        Ok(())
+    }
+
+    /// Append an entry to the log file of a collection, then to memory
+    async fn write_entry(&self, collection: Collection, entry: &LogEntry) -> RhemaResult<()> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.log_path(collection))
+            .await?;
+        file.write_all(&line).await?;
+        file.sync_data().await?;
+        self.memory.restore_entry(collection, entry).await
+    }
+}
+
+#[async_trait]
+impl StorageEngine for FileEngine {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    async fn put(&self, collection: Collection, key: &str, value: &Value) -> RhemaResult<()> {
+        let _guard = self.write_lock.lock().await;
+        let mut records = self.current_records(collection).await;
+        records.insert(
+            key.to_string(),
+            StoredRecord {
+                key: key.to_string(),
+                value: value.clone(),
+                updated_at: Utc::now(),
+            },
+        );
+        self.write_records(collection, records).await
+    }
+
+    async fn get(&self, collection: Collection, key: &str) -> RhemaResult<Option<Value>> {
+        self.memory.get(collection, key).await
+    }
+
+    async fn delete(&self, collection: Collection, key: &str) -> RhemaResult<bool> {
+        let _guard = self.write_lock.lock().await;
+        let mut records = self.current_records(collection).await;
+        if records.remove(key).is_none() {
+            return Ok(false);
+        }
+        self.write_records(collection, records).await?;
+        Ok(true)
+    }
+
+    async fn list(&self, collection: Collection) -> RhemaResult<Vec<StoredRecord>> {
+        self.memory.list(collection).await
+    }
+
+    async fn append(&self, collection: Collection, value: &Value) -> RhemaResult<u64> {
+        let _guard = self.write_lock.lock().await;
+        let sequence = self
+            .memory
+            .logs
+            .read()
+            .await
+            .get(&collection)
+            .and_then(|log| log.keys().next_back().copied())
+            .map_or(1, |last| last + 1);
+        let entry = LogEntry {
+            sequence,
+            value: value.clone(),
+            created_at: Utc::now(),
+        };
+        self.write_entry(collection, &entry).await?;
+        Ok(sequence)
+    }
+
+    async fn restore_entry(&self, collection: Collection, entry: &LogEntry) -> RhemaResult<()> {
+        let _guard = self.write_lock.lock().await;
+        self.write_entry(collection, entry).await
+    }
+
+    async fn read_log(
+        &self,
+        collection: Collection,
+        after: u64,
+        limit: usize,
+    ) -> RhemaResult<Vec<LogEntry>> {
+        self.memory.read_log(collection, after, limit).await
+    }
+
+    /// JSON files carry no schema beyond the record and entry formats
+    async fn schema_version(&self) -> RhemaResult<u32> {
+        Ok(CURRENT_SCHEMA_VERSION)
+    }
+
+    async fn migrate(&self) -> RhemaResult<u32> {
+        Ok(CURRENT_SCHEMA_VERSION)
+    }
+}
+
+/// Embedded engine backed by sled, one tree per collection and kind
+pub struct SledEngine {
+    db: sled::Db,
+}
+
+impl SledEngine {
+    const META_TREE: &'static str = "meta";
+    const SCHEMA_KEY: &'static [u8] = b"schema_version";
+
+    pub fn open(path: &Path) -> RhemaResult<Self> {
+        let db = sled::open(path).map_err(sled_error)?;
+        Ok(Self { db })
+    }
+
+    fn records(&self, collection: Collection) -> RhemaResult<sled::Tree> {
+        self.db
+            .open_tree(format!("records:{}", collection.as_str()))
+            .map_err(sled_error)
+    }
+
+    fn log(&self, collection: Collection) -> RhemaResult<sled::Tree> {
+        self.db
+            .open_tree(format!("log:{}", collection.as_str()))
+            .map_err(sled_error)
+    }
+}
+
+fn sled_error(err: sled::Error) -> RhemaError {
+    RhemaError::IoError(std::io::Error::other(format!("sled: {}", err)))
+}
+
+#[async_trait]
+impl StorageEngine for SledEngine {
+    fn name(&self) -> &'static str {
+        "sled"
+    }
+
+    async fn put(&self, collection: Collection, key: &str, value: &Value) -> RhemaResult<()> {
+        let record = StoredRecord {
+            key: key.to_string(),
+            value: value.clone(),
+            updated_at: Utc::now(),
+        };
+        self.records(collection)?
+            .insert(key.as_bytes(), serde_json::to_vec(&record)?)
+            .map_err(sled_error)?;
+        self.db.flush_async().await.map_err(sled_error)?;
+        Ok(())
+    }
+
+    async fn get(&self, collection: Collection, key: &str) -> RhemaResult<Option<Value>> {
+        match self
+            .records(collection)?
+            .get(key.as_bytes())
+            .map_err(sled_error)?
+        {
+            Some(bytes) => Ok(Some(serde_json::from_slice::<StoredRecord>(&bytes)?.value)),
+            None => Ok(None),
+        }
+    }
+
+    async fn delete(&self, collection: Collection, key: &str) -> RhemaResult<bool> {
+        let removed = self
+            .records(collection)?
+            .remove(key.as_bytes())
+            .map_err(sled_error)?
+            .is_some();
+        self.db.flush_async().await.map_err(sled_error)?;
+        Ok(removed)
+    }
+
+    async fn list(&self, collection: Collection) -> RhemaResult<Vec<StoredRecord>> {
+        self.records(collection)?
+            .iter()
+            .values()
+            .map(|bytes| Ok(serde_json::from_slice(&bytes.map_err(sled_error)?)?))
+            .collect()
+    }
+
+    async fn append(&self, collection: Collection, value: &Value) -> RhemaResult<u64> {
+        let log = self.log(collection)?;
+        // Big-endian keys keep the tree ordered by sequence
+        let sequence = log
+            .last()
+            .map_err(sled_error)?
+            .map_or(1, |(key, _)| sequence_from_key(&key) + 1);
+        let entry = LogEntry {
+            sequence,
+            value: value.clone(),
+            created_at: Utc::now(),
+        };
+        let inserted = log
+            .compare_and_swap(
+                sequence.to_be_bytes(),
+                None as Option<&[u8]>,
+                Some(serde_json::to_vec(&entry)?),
+            )
+            .map_err(sled_error)?;
+        if inserted.is_err() {
+            return Err(RhemaError::CoordinationError(format!(
+                "Concurrent append to {} log",
+                collection.as_str()
+            )));
+        }
+        self.db.flush_async().await.map_err(sled_error)?;
+        Ok(sequence)
+    }
+
+    async fn restore_entry(&self, collection: Collection, entry: &LogEntry) -> RhemaResult<()> {
+        self.log(collection)?
+            .insert(entry.sequence.to_be_bytes(), serde_json::to_vec(entry)?)
+            .map_err(sled_error)?;
+        Ok(())
+    }
+
+    async fn read_log(
+        &self,
+        collection: Collection,
+        after: u64,
+        limit: usize,
+    ) -> RhemaResult<Vec<LogEntry>> {
+        self.log(collection)?
+            .range((after + 1).to_be_bytes()..)
+            .values()
+            .take(limit)
+            .map(|bytes| Ok(serde_json::from_slice(&bytes.map_err(sled_error)?)?))
+            .collect()
+    }
+
+    async fn schema_version(&self) -> RhemaResult<u32> {
+        let meta = self.db.open_tree(Self::META_TREE).map_err(sled_error)?;
+        Ok(meta
+            .get(Self::SCHEMA_KEY)
+            .map_err(sled_error)?
+            .and_then(|bytes| <[u8; 4]>::try_from(bytes.as_ref()).ok())
+            .map_or(0, u32::from_be_bytes))
+    }
+
+    async fn migrate(&self) -> RhemaResult<u32> {
+        // Trees are created lazily, so sled only needs to record the version
+        let version = self.schema_version().await?;
+        if version > CURRENT_SCHEMA_VERSION {
+            return Err(newer_schema_error(version));
+        }
+        if version < CURRENT_SCHEMA_VERSION {
+            self.db
+                .open_tree(Self::META_TREE)
+                .map_err(sled_error)?
+                .insert(
+                    Self::SCHEMA_KEY,
+                    CURRENT_SCHEMA_VERSION.to_be_bytes().to_vec(),
+                )
+                .map_err(sled_error)?;
+            self.db.flush_async().await.map_err(sled_error)?;
+        }
+        Ok(CURRENT_SCHEMA_VERSION)
+    }
+}
+
+fn sequence_from_key(key: &[u8]) -> u64 {
+    <[u8; 8]>::try_from(key).map_or(0, u64::from_be_bytes)
+}
+
+fn newer_schema_error(version: u32) -> RhemaError {
+    RhemaError::ConfigError(format!(
+        "Storage schema v{} is newer than supported v{}; upgrade rhema",
+        version, CURRENT_SCHEMA_VERSION
+    ))
+}
+
+/// A schema migration for the SQL engines
+struct SqlMigration {
+    version: u32,
+    description: &'static str,
+    statements: &'static [&'static str],
+}
+
+/// Statements are written in the SQL subset shared by SQLite and Postgres
+const SQL_MIGRATIONS: &[SqlMigration] = &[
+    SqlMigration {
+        version: 1,
+        description: "records and logs",
+        statements: &[
+            "CREATE TABLE IF NOT EXISTS rhema_records (
+                collection TEXT NOT NULL,
+                record_key TEXT NOT NULL,
+                value TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                PRIMARY KEY (collection, record_key)
+            )",
+            "CREATE TABLE IF NOT EXISTS rhema_logs (
+                collection TEXT NOT NULL,
+                sequence BIGINT NOT NULL,
+                value TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (collection, sequence)
+            )",
+        ],
+    },
+    SqlMigration {
+        version: 2,
+        description: "log time index",
+        statements: &[
+            "CREATE INDEX IF NOT EXISTS idx_rhema_logs_created_at ON rhema_logs (collection, created_at)",
+        ],
+    },
+];
+
+enum SqlPool {
+    Sqlite(SqlitePool),
+    Postgres(PgPool),
+}
+
+/// Run the same query builder against whichever pool is configured
+macro_rules! with_pool {
+    ($pool:expr, $p:ident => $body:expr) => {
+        match $pool {
+            SqlPool::Sqlite($p) => $body,
+            SqlPool::Postgres($p) => $body,
+        }
+    };
+}
+
+/// SQL engine for SQLite (embedded) and Postgres (shared deployments)
+pub struct SqlEngine {
+    pool: SqlPool,
+}
+
+impl SqlEngine {
+    pub async fn connect(url: &str) -> RhemaResult<Self> {
+        let pool = if url.starts_with("sqlite:") {
+            SqlPool::Sqlite(SqlitePool::connect(url).await.map_err(sql_error)?)
+        } else {
+            SqlPool::Postgres(PgPool::connect(url).await.map_err(sql_error)?)
+        };
+        Ok(Self { pool })
+    }
+
+    /// Rewrite `?` placeholders to `$n` for Postgres
+    fn sql(&self, query: &str) -> String {
+        match self.pool {
+            SqlPool::Sqlite(_) => query.to_string(),
+            SqlPool::Postgres(_) => {
+                let mut index = 0;
+                query
+                    .chars()
+                    .map(|c| {
+                        if c == '?' {
+                            index += 1;
+                            format!("${}", index)
+                        } else {
+                            c.to_string()
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+fn sql_error(err: sqlx::Error) -> RhemaError {
+    RhemaError::ExternalServiceError(format!("database: {}", err))
+}
+
+fn parse_timestamp(value: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|t| t.with_timezone(&Utc))
+        .unwrap_or_else(|_| Utc::now())
+}
+
+#[async_trait]
+impl StorageEngine for SqlEngine {
+    fn name(&self) -> &'static str {
+        match self.pool {
+            SqlPool::Sqlite(_) => "sqlite",
+            SqlPool::Postgres(_) => "postgres",
+        }
+    }
+
+    async fn put(&self, collection: Collection, key: &str, value: &Value) -> RhemaResult<()> {
+        let sql = self.sql(
+            "INSERT INTO rhema_records (collection, record_key, value, updated_at) VALUES (?, ?, ?, ?)
+             ON CONFLICT (collection, record_key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        );
+        let value = serde_json::to_string(value)?;
+        let now = Utc::now().to_rfc3339();
+        with_pool!(&self.pool, pool => sqlx::query(&sql)
+            .bind(collection.as_str())
+            .bind(key)
+            .bind(&value)
+            .bind(&now)
+            .execute(pool)
+            .await
+            .map(|_| ()))
+        .map_err(sql_error)
+    }
+
+    async fn get(&self, collection: Collection, key: &str) -> RhemaResult<Option<Value>> {
+        let sql =
+            self.sql("SELECT value FROM rhema_records WHERE collection = ? AND record_key = ?");
+        let value: Option<String> = with_pool!(&self.pool, pool => sqlx::query(&sql)
+            .bind(collection.as_str())
+            .bind(key)
+            .fetch_optional(pool)
+            .await
+            .map(|row| row.map(|row| row.get::<String, _>("value"))))
+        .map_err(sql_error)?;
+        Ok(value
+            .map(|v| serde_json::from_str::<Value>(&v))
+            .transpose()?)
+    }
+
+    async fn delete(&self, collection: Collection, key: &str) -> RhemaResult<bool> {
+        let sql = self.sql("DELETE FROM rhema_records WHERE collection = ? AND record_key = ?");
+        let affected = with_pool!(&self.pool, pool => sqlx::query(&sql)
+            .bind(collection.as_str())
+            .bind(key)
+            .execute(pool)
+            .await
+            .map(|result| result.rows_affected()))
+        .map_err(sql_error)?;
+        Ok(affected > 0)
+    }
+
+    async fn list(&self, collection: Collection) -> RhemaResult<Vec<StoredRecord>> {
+        let sql = self.sql(
+            "SELECT record_key, value, updated_at FROM rhema_records WHERE collection = ? ORDER BY record_key",
+        );
+        let rows: Vec<(String, String, String)> = with_pool!(&self.pool, pool => sqlx::query(&sql)
+            .bind(collection.as_str())
+            .fetch_all(pool)
+            .await
+            .map(|rows| rows
+                .iter()
+                .map(|row| (row.get("record_key"), row.get("value"), row.get("updated_at")))
+                .collect()))
+        .map_err(sql_error)?;
+
+        rows.into_iter()
+            .map(|(key, value, updated_at)| {
+                Ok(StoredRecord {
+                    key,
+                    value: serde_json::from_str(&value)?,
+                    updated_at: parse_timestamp(&updated_at),
+                })
+            })
+            .collect()
+    }
+
+    async fn append(&self, collection: Collection, value: &Value) -> RhemaResult<u64> {
+        let sql = self.sql(
+            "INSERT INTO rhema_logs (collection, sequence, value, created_at)
+             SELECT ?, COALESCE(MAX(sequence), 0) + 1, ?, ? FROM rhema_logs WHERE collection = ?
+             RETURNING sequence",
+        );
+        let value = serde_json::to_string(value)?;
+
+        // Concurrent writers may race for the same sequence; the primary key
+        // rejects the loser, which simply retries with the next one.
+        let mut last_error = None;
+        for _ in 0..5 {
+            let now = Utc::now().to_rfc3339();
+            let result = with_pool!(&self.pool, pool => sqlx::query(&sql)
+                .bind(collection.as_str())
+                .bind(&value)
+                .bind(&now)
+                .bind(collection.as_str())
+                .fetch_one(pool)
+                .await
+                .map(|row| row.get::<i64, _>("sequence")));
+            match result {
+                Ok(sequence) => return Ok(sequence as u64),
+                Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+                    last_error = Some(sqlx::Error::Database(e));
+                }
+                Err(e) => return Err(sql_error(e)),
+            }
+        }
+        Err(sql_error(last_error.expect("retry loop ran")))
+    }
+
+    async fn restore_entry(&self, collection: Collection, entry: &LogEntry) -> RhemaResult<()> {
+        let sql = self.sql(
+            "INSERT INTO rhema_logs (collection, sequence, value, created_at) VALUES (?, ?, ?, ?)
+             ON CONFLICT (collection, sequence) DO UPDATE SET value = excluded.value, created_at = excluded.created_at",
+        );
+        let value = serde_json::to_string(&entry.value)?;
+        let created_at = entry.created_at.to_rfc3339();
+        with_pool!(&self.pool, pool => sqlx::query(&sql)
+            .bind(collection.as_str())
+            .bind(entry.sequence as i64)
+            .bind(&value)
+            .bind(&created_at)
+            .execute(pool)
+            .await
+            .map(|_| ()))
+        .map_err(sql_error)
+    }
+
+    async fn read_log(
+        &self,
+        collection: Collection,
+        after: u64,
+        limit: usize,
+    ) -> RhemaResult<Vec<LogEntry>> {
+        let sql = self.sql(
+            "SELECT sequence, value, created_at FROM rhema_logs
+             WHERE collection = ? AND sequence > ? ORDER BY sequence LIMIT ?",
+        );
+        let rows: Vec<(i64, String, String)> = with_pool!(&self.pool, pool => sqlx::query(&sql)
+            .bind(collection.as_str())
+            .bind(after as i64)
+            .bind(limit.min(i64::MAX as usize) as i64)
+            .fetch_all(pool)
+            .await
+            .map(|rows| rows
+                .iter()
+                .map(|row| (row.get("sequence"), row.get("value"), row.get("created_at")))
+                .collect()))
+        .map_err(sql_error)?;
+
+        rows.into_iter()
+            .map(|(sequence, value, created_at)| {
+                Ok(LogEntry {
+                    sequence: sequence as u64,
+                    value: serde_json::from_str(&value)?,
+                    created_at: parse_timestamp(&created_at),
+                })
+            })
+            .collect()
+    }
+
+    async fn schema_version(&self) -> RhemaResult<u32> {
+        let create = "CREATE TABLE IF NOT EXISTS rhema_schema_migrations (
+            version BIGINT PRIMARY KEY,
+            description TEXT NOT NULL,
+            applied_at TEXT NOT NULL
+        )";
+        // Tables created by older builds declare `version INTEGER`, which
+        // Postgres returns as INT4
+        let query = "SELECT CAST(COALESCE(MAX(version), 0) AS BIGINT) AS version FROM rhema_schema_migrations";
+        let version: i64 = with_pool!(&self.pool, pool => {
+            match sqlx::query(create).execute(pool).await {
+                Ok(_) => sqlx::query(query)
+                    .fetch_one(pool)
+                    .await
+                    .map(|row| row.get::<i64, _>("version")),
+                Err(e) => Err(e),
+            }
+        })
+        .map_err(sql_error)?;
+        Ok(version as u32)
+    }
+
+    async fn migrate(&self) -> RhemaResult<u32> {
+        let current = self.schema_version().await?;
+        if current > CURRENT_SCHEMA_VERSION {
+            return Err(newer_schema_error(current));
+        }
+
+        let record = self.sql(
+            "INSERT INTO rhema_schema_migrations (version, description, applied_at) VALUES (?, ?, ?)",
+        );
+        for migration in SQL_MIGRATIONS.iter().filter(|m| m.version > current) {
+            let now = Utc::now().to_rfc3339();
+            with_pool!(&self.pool, pool => {
+                let mut tx = pool.begin().await.map_err(sql_error)?;
+                for statement in migration.statements {
+                    sqlx::query(statement)
+                        .execute(&mut *tx)
+                        .await
+                        .map_err(sql_error)?;
+                }
+                sqlx::query(&record)
+                    .bind(migration.version as i64)
+                    .bind(migration.description)
+                    .bind(&now)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(sql_error)?;
+                tx.commit().await.map_err(sql_error)?;
+            });
+            info!(
+                "Applied {} schema migration v{}: {}",
+                self.name(),
+                migration.version,
+                migration.description
+            );
+        }
+
+        Ok(CURRENT_SCHEMA_VERSION)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    async fn exercise(engine: &dyn StorageEngine) -> RhemaResult<()> {
+        engine.migrate().await?;
+        assert_eq!(engine.schema_version().await?, CURRENT_SCHEMA_VERSION);
+
+        engine
+            .put(Collection::Agents, "agent-1", &json!({"name": "planner"}))
+            .await?;
+        engine
+            .put(Collection::Agents, "agent-1", &json!({"name": "reviewer"}))
+            .await?;
+        assert_eq!(
+            engine.get(Collection::Agents, "agent-1").await?,
+            Some(json!({"name": "reviewer"}))
+        );
+        assert_eq!(engine.list(Collection::Agents).await?.len(), 1);
+
+        assert_eq!(engine.append(Collection::Messages, &json!("a")).await?, 1);
+        assert_eq!(engine.append(Collection::Messages, &json!("b")).await?, 2);
+        let tail = engine.read_log(Collection::Messages, 1, 10).await?;
+        assert_eq!(tail.len(), 1);
+        assert_eq!(tail[0].value, json!("b"));
+
+        assert!(engine.delete(Collection::Agents, "agent-1").await?);
+        assert!(engine.get(Collection::Agents, "agent-1").await?.is_none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_engines_share_semantics() -> RhemaResult<()> {
+        let temp = tempfile::TempDir::new()?;
+
+        exercise(&MemoryEngine::new()).await?;
+        exercise(&FileEngine::open(&temp.path().join("files")).await?).await?;
+        exercise(&SledEngine::open(&temp.path().join("sled"))?).await?;
+        let url = format!(
+            "sqlite://{}?mode=rwc",
+            temp.path().join("store.db").display()
+        );
+        exercise(&SqlEngine::connect(&url).await?).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_file_engine_reloads_from_disk() -> RhemaResult<()> {
+        let temp = tempfile::TempDir::new()?;
+        let dir = temp.path().join("engine");
+        {
+            let engine = FileEngine::open(&dir).await?;
+            engine
+                .put(Collection::Agents, "agent-1", &json!({"name": "planner"}))
+                .await?;
+            engine.append(Collection::Messages, &json!("a")).await?;
+            engine.append(Collection::Messages, &json!("b")).await?;
+        }
+        // An append torn by a crash leaves a partial last line
+        let mut log = std::fs::OpenOptions::new()
+            .append(true)
+            .open(dir.join("messages.jsonl"))?;
+        std::io::Write::write_all(&mut log, b"{\"sequence\":3,\"val")?;
+
+        let engine = FileEngine::open(&dir).await?;
+        assert_eq!(
+            engine.get(Collection::Agents, "agent-1").await?,
+            Some(json!({"name": "planner"}))
+        );
+        assert_eq!(engine.read_log(Collection::Messages, 0, 10).await?.len(), 2);
+        Ok(())
+    }
+
+    /// Runs against the database in `RHEMA_TEST_POSTGRES_URL` when it is set;
+    /// the rhema tables in that database are dropped first
+    #[tokio::test]
+    async fn test_postgres_engine() -> RhemaResult<()> {
+        let Ok(url) = std::env::var("RHEMA_TEST_POSTGRES_URL") else {
+            return Ok(());
+        };
+        let engine = SqlEngine::connect(&url).await?;
+        let SqlPool::Postgres(pool) = &engine.pool else {
+            panic!("RHEMA_TEST_POSTGRES_URL is not a Postgres URL");
+        };
+        sqlx::query("DROP TABLE IF EXISTS rhema_records, rhema_logs, rhema_schema_migrations")
+            .execute(pool)
+            .await
+            .map_err(sql_error)?;
+
+        exercise(&engine).await?;
+
+        // Migration tables created by older builds use an INT4 version column
+        sqlx::query("DROP TABLE rhema_schema_migrations")
+            .execute(pool)
+            .await
+            .map_err(sql_error)?;
+        sqlx::query(
+            "CREATE TABLE rhema_schema_migrations (
+                version INTEGER PRIMARY KEY,
+                description TEXT NOT NULL,
+                applied_at TEXT NOT NULL
+            )",
+        )
+        .execute(pool)
+        .await
+        .map_err(sql_error)?;
+        sqlx::query("INSERT INTO rhema_schema_migrations VALUES (1, 'records and logs', '')")
+            .execute(pool)
+            .await
+            .map_err(sql_error)?;
+        assert_eq!(engine.schema_version().await?, 1);
+        assert_eq!(engine.migrate().await?, CURRENT_SCHEMA_VERSION);
+        assert_eq!(engine.schema_version().await?, CURRENT_SCHEMA_VERSION);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_migrate_backend_preserves_sequences() -> RhemaResult<()> {
+        let temp = tempfile::TempDir::new()?;
+        let source = MemoryEngine::new();
+        source
+            .put(Collection::Sessions, "s1", &json!({"topic": "planning"}))
+            .await?;
+        for i in 0..3 {
+            source
+                .append(Collection::ActionHistory, &json!({ "step": i }))
+                .await?;
+        }
+
+        let target = SledEngine::open(&temp.path().join("target"))?;
+        let report = migrate_backend(&source, &target).await?;
+        assert_eq!(report.records, 1);
+        assert_eq!(report.log_entries, 3);
+
+        let log = target.read_log(Collection::ActionHistory, 0, 10).await?;
+        assert_eq!(
+            log.iter().map(|e| e.sequence).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(
+            target.append(Collection::ActionHistory, &json!({})).await?,
+            4
+        );
+        Ok(())
+    }
+}
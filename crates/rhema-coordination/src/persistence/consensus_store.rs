@@ -14,18 +14,22 @@
  * limitations under the License.
  */
 
-use super::{PersistenceConfig, StorageBackend, StoreStats};
+use super::backend::{load_records, replace_records};
+use super::{
+    open_engine, Collection, PersistenceConfig, StorageBackend, StorageEngine, StoreStats,
+};
 use crate::agent::real_time_coordination::{
     ConsensusAlgorithm, ConsensusConfig, ConsensusEntry, ConsensusState,
 };
 use chrono::{DateTime, Utc};
 use rhema_core::RhemaResult;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::info;
+use tracing::{info, warn};
 
 /// Consensus store for persisting consensus state
 pub struct ConsensusStore {
@@ -34,6 +38,8 @@ pub struct ConsensusStore {
     consensus_logs: Arc<RwLock<HashMap<String, Vec<StoredConsensusEntry>>>>,
     consensus_configs: Arc<RwLock<HashMap<String, ConsensusConfig>>>,
     file_path: Option<PathBuf>,
+    /// Storage engine holding the consensus data for database backends
+    engine: Option<Arc<dyn StorageEngine>>,
 }
 
 /// Stored consensus state with metadata
@@ -62,6 +68,28 @@ pub struct StoredConsensusEntry {
 impl ConsensusStore {
     /// Create a new consensus store
     pub async fn new(config: PersistenceConfig) -> RhemaResult<Self> {
+        let engine = if config.backend.stores_in_engine() {
+            Some(open_engine(&config).await?)
+        } else {
+            None
+        };
+        Self::open(config, engine).await
+    }
+
+    /// Create a consensus store sharing an open storage engine, which holds
+    /// the consensus data when the backend stores it in the engine
+    pub async fn with_engine(
+        config: PersistenceConfig,
+        engine: Arc<dyn StorageEngine>,
+    ) -> RhemaResult<Self> {
+        let engine = config.backend.stores_in_engine().then_some(engine);
+        Self::open(config, engine).await
+    }
+
+    async fn open(
+        config: PersistenceConfig,
+        engine: Option<Arc<dyn StorageEngine>>,
+    ) -> RhemaResult<Self> {
         let file_path = match &config.backend {
             StorageBackend::File => {
                 let path = config
@@ -86,6 +114,7 @@ impl ConsensusStore {
             consensus_logs: Arc::new(RwLock::new(HashMap::new())),
             consensus_configs: Arc::new(RwLock::new(HashMap::new())),
             file_path,
+            engine,
         };
 
         // Load existing data
@@ -289,6 +318,11 @@ impl ConsensusStore {
 
     /// Load data from storage
     async fn load(&mut self) -> RhemaResult<()> {
+        if let Some(engine) = &self.engine {
+            let entries = load_records(engine.as_ref(), Collection::ConsensusState).await?;
+            return self.restore(&entries).await;
+        }
+
         match &self.config.backend {
             StorageBackend::File => {
                 if let Some(path) = &self.file_path {
@@ -318,8 +352,59 @@ impl ConsensusStore {
         Ok(())
     }
 
+    /// Rebuild consensus states, logs and configurations from stored entries
+    async fn restore(&self, entries: &BTreeMap<String, Value>) -> RhemaResult<()> {
+        let mut states = HashMap::new();
+        let mut logs = HashMap::new();
+        let mut configs = HashMap::new();
+        for (key, value) in entries {
+            match key.split_once('/') {
+                Some(("state", node_id)) => {
+                    states.insert(node_id.to_string(), serde_json::from_value(value.clone())?);
+                }
+                Some(("log", node_id)) => {
+                    logs.insert(node_id.to_string(), serde_json::from_value(value.clone())?);
+                }
+                Some(("config", node_id)) => {
+                    configs.insert(node_id.to_string(), serde_json::from_value(value.clone())?);
+                }
+                _ => warn!("Ignoring unknown consensus store entry {}", key),
+            }
+        }
+
+        info!(
+            "Restored {} consensus states and {} consensus logs",
+            states.len(),
+            logs.len()
+        );
+        *self.consensus_states.write().await = states;
+        *self.consensus_logs.write().await = logs;
+        *self.consensus_configs.write().await = configs;
+        Ok(())
+    }
+
+    /// All consensus data as keyed entries
+    async fn entries(&self) -> RhemaResult<BTreeMap<String, Value>> {
+        let mut entries = BTreeMap::new();
+        for (node_id, state) in self.consensus_states.read().await.iter() {
+            entries.insert(format!("state/{}", node_id), serde_json::to_value(state)?);
+        }
+        for (node_id, log) in self.consensus_logs.read().await.iter() {
+            entries.insert(format!("log/{}", node_id), serde_json::to_value(log)?);
+        }
+        for (node_id, config) in self.consensus_configs.read().await.iter() {
+            entries.insert(format!("config/{}", node_id), serde_json::to_value(config)?);
+        }
+        Ok(entries)
+    }
+
     /// Save data to storage
     async fn save(&self) -> RhemaResult<()> {
+        if let Some(engine) = &self.engine {
+            let entries = self.entries().await?;
+            return replace_records(engine.as_ref(), Collection::ConsensusState, &entries).await;
+        }
+
         match &self.config.backend {
             StorageBackend::File => {
                 if let Some(path) = &self.file_path {
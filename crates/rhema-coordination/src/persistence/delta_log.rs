@@ -0,0 +1,586 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Delta-encoded persistence for frequently updated keyed state.
+//!
+//! Rewriting a whole store on every session or agent update costs IO in
+//! proportion to the store, not to the change. A [`DeltaLog`] instead appends
+//! one record per changed key to `<base>.deltas.jsonl`, holding only the
+//! fields that changed (and only the new items when an array grew). Every
+//! `snapshot_every` deltas, when the snapshot gets too old, or when the delta
+//! log outgrows the snapshot by `compact_ratio`, the full state is written to
+//! `<base>.snapshot.json` and the delta log is truncated.
+//!
+//! Loading reads the snapshot and replays the deltas after it. A torn final
+//! record from a crash mid-append is dropped and cut off the log.
+
+use chrono::{DateTime, Utc};
+use rhema_core::RhemaResult;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+use tracing::warn;
+
+/// When stores write deltas and when they fold them into a snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DeltaEncodingConfig {
+    /// Append deltas instead of rewriting the whole store on every update
+    pub enabled: bool,
+    /// Deltas appended before the next snapshot
+    pub snapshot_every: u64,
+    /// Snapshot once the last snapshot is this old and deltas are pending
+    pub max_snapshot_age_secs: Option<u64>,
+    /// Compact once the delta log is this many times larger than the snapshot
+    pub compact_ratio: f64,
+}
+
+impl Default for DeltaEncodingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            snapshot_every: 500,
+            max_snapshot_age_secs: Some(3600),
+            compact_ratio: 2.0,
+        }
+    }
+}
+
+/// Change to one JSON value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Patch {
+    Replace {
+        value: Value,
+    },
+    /// Patch the listed fields of an object and drop the removed ones
+    Object {
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        fields: BTreeMap<String, Patch>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        removed: Vec<String>,
+    },
+    /// Items added to the end of an array
+    Append {
+        items: Vec<Value>,
+    },
+}
+
+impl Patch {
+    /// Patch turning `old` into `new`, or `None` when they are equal
+    pub fn diff(old: &Value, new: &Value) -> Option<Self> {
+        if old == new {
+            return None;
+        }
+        match (old, new) {
+            (Value::Object(old), Value::Object(new)) => {
+                let fields: BTreeMap<String, Patch> = new
+                    .iter()
+                    .filter_map(|(key, value)| {
+                        let patch = match old.get(key) {
+                            Some(previous) => Self::diff(previous, value)?,
+                            None => Patch::Replace {
+                                value: value.clone(),
+                            },
+                        };
+                        Some((key.clone(), patch))
+                    })
+                    .collect();
+                let removed = old
+                    .keys()
+                    .filter(|key| !new.contains_key(*key))
+                    .cloned()
+                    .collect();
+                Some(Patch::Object { fields, removed })
+            }
+            (Value::Array(old), Value::Array(new))
+                if new.len() > old.len() && new[..old.len()] == old[..] =>
+            {
+                Some(Patch::Append {
+                    items: new[old.len()..].to_vec(),
+                })
+            }
+            _ => Some(Patch::Replace { value: new.clone() }),
+        }
+    }
+
+    /// Apply the patch to `value` in place
+    pub fn apply(&self, value: &mut Value) {
+        match self {
+            Patch::Replace { value: new } => *value = new.clone(),
+            Patch::Object { fields, removed } => {
+                if !value.is_object() {
+                    *value = Value::Object(Map::new());
+                }
+                let object = value.as_object_mut().expect("value was made an object");
+                for key in removed {
+                    object.remove(key);
+                }
+                for (key, patch) in fields {
+                    patch.apply(object.entry(key.clone()).or_insert(Value::Null));
+                }
+            }
+            Patch::Append { items } => match value {
+                Value::Array(existing) => existing.extend(items.iter().cloned()),
+                other => *other = Value::Array(items.clone()),
+            },
+        }
+    }
+}
+
+/// One appended change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DeltaRecord {
+    seq: u64,
+    key: String,
+    #[serde(flatten)]
+    change: Change,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+enum Change {
+    Patch { patch: Patch },
+    Remove,
+}
+
+/// Full state as of a sequence number
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Snapshot {
+    seq: u64,
+    taken_at: Option<DateTime<Utc>>,
+    entries: BTreeMap<String, Value>,
+}
+
+/// IO counters for a delta log
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeltaLogStats {
+    /// Sequence number of the last delta
+    pub seq: u64,
+    pub deltas_since_snapshot: u64,
+    pub snapshots_written: u64,
+    /// Bytes written to the delta log and snapshots
+    pub bytes_written: u64,
+    /// Bytes that rewriting the full state on every update would have written
+    pub full_rewrite_bytes: u64,
+}
+
+impl DeltaLogStats {
+    /// How many times fewer bytes were written than with full rewrites
+    pub fn write_reduction(&self) -> f64 {
+        if self.bytes_written == 0 {
+            return 1.0;
+        }
+        self.full_rewrite_bytes as f64 / self.bytes_written as f64
+    }
+}
+
+/// Keyed JSON state persisted as a snapshot plus a log of deltas
+pub struct DeltaLog {
+    snapshot_path: PathBuf,
+    deltas_path: PathBuf,
+    config: DeltaEncodingConfig,
+    entries: BTreeMap<String, Value>,
+    /// Serialized size of each entry, to price a full rewrite
+    entry_sizes: BTreeMap<String, u64>,
+    snapshot_taken_at: Option<DateTime<Utc>>,
+    snapshot_bytes: u64,
+    delta_bytes: u64,
+    stats: DeltaLogStats,
+}
+
+impl DeltaLog {
+    /// Open the log at `base`, replaying deltas over the latest snapshot
+    pub async fn open(base: &Path, config: DeltaEncodingConfig) -> RhemaResult<Self> {
+        let snapshot_path = with_suffix(base, "snapshot.json");
+        let deltas_path = with_suffix(base, "deltas.jsonl");
+
+        let snapshot: Snapshot = if snapshot_path.exists() {
+            serde_json::from_str(&tokio::fs::read_to_string(&snapshot_path).await?)?
+        } else {
+            Snapshot::default()
+        };
+        let snapshot_bytes = tokio::fs::metadata(&snapshot_path)
+            .await
+            .map(|m| m.len())
+            .unwrap_or(0);
+
+        let mut log = Self {
+            snapshot_path,
+            deltas_path,
+            config,
+            entry_sizes: BTreeMap::new(),
+            snapshot_taken_at: snapshot.taken_at,
+            snapshot_bytes,
+            delta_bytes: 0,
+            stats: DeltaLogStats {
+                seq: snapshot.seq,
+                ..Default::default()
+            },
+            entries: snapshot.entries,
+        };
+
+        if log.deltas_path.exists() {
+            let content = tokio::fs::read_to_string(&log.deltas_path).await?;
+            // Length of the readable prefix of the log
+            let mut good_len = 0;
+            for line in content.split_inclusive('\n') {
+                if line.trim().is_empty() {
+                    good_len += line.len();
+                    continue;
+                }
+                let record: DeltaRecord = match serde_json::from_str(line) {
+                    Ok(record) => record,
+                    Err(e) => {
+                        warn!(
+                            "Dropping unreadable delta in {}: {}",
+                            log.deltas_path.display(),
+                            e
+                        );
+                        break;
+                    }
+                };
+                good_len += line.len();
+                // Deltas already folded into the snapshot when a compaction
+                // was interrupted before truncating the log
+                if record.seq <= snapshot.seq {
+                    continue;
+                }
+                log.replay(record);
+            }
+            log.delta_bytes = log
+                .repair_deltas(&content[..good_len], content.len())
+                .await?;
+        }
+
+        log.entry_sizes = log
+            .entries
+            .iter()
+            .map(|(key, value)| (key.clone(), entry_size(key, value)))
+            .collect();
+        Ok(log)
+    }
+
+    /// Cut a torn record off the delta log so later appends start on a
+    /// line of their own, returning the repaired length
+    async fn repair_deltas(&self, good: &str, len: usize) -> RhemaResult<u64> {
+        let complete = good.is_empty() || good.ends_with('\n');
+        if good.len() == len && complete {
+            return Ok(len as u64);
+        }
+        let mut file = tokio::fs::OpenOptions::new()
+            .write(true)
+            .open(&self.deltas_path)
+            .await?;
+        file.set_len(good.len() as u64).await?;
+        let mut repaired = good.len() as u64;
+        if !complete {
+            // The last record parsed but its newline was never written
+            file.seek(std::io::SeekFrom::End(0)).await?;
+            file.write_all(b"\n").await?;
+            repaired += 1;
+        }
+        file.sync_data().await?;
+        Ok(repaired)
+    }
+
+    fn replay(&mut self, record: DeltaRecord) {
+        match record.change {
+            Change::Patch { patch } => {
+                patch.apply(self.entries.entry(record.key).or_insert(Value::Null));
+            }
+            Change::Remove => {
+                self.entries.remove(&record.key);
+            }
+        }
+        self.stats.seq = record.seq;
+        self.stats.deltas_since_snapshot += 1;
+    }
+
+    /// Current state
+    pub fn entries(&self) -> &BTreeMap<String, Value> {
+        &self.entries
+    }
+
+    pub fn stats(&self) -> &DeltaLogStats {
+        &self.stats
+    }
+
+    /// Persist a batch of changes; `None` removes the key. Unchanged values
+    /// write nothing, and the state only changes once the deltas are written.
+    pub async fn apply(&mut self, changes: Vec<(String, Option<Value>)>) -> RhemaResult<()> {
+        let mut batch = String::new();
+        let mut seq = self.stats.seq;
+        // Values as of the changes so far in this batch
+        let mut pending: BTreeMap<String, Option<Value>> = BTreeMap::new();
+        for (key, value) in changes {
+            let current = match pending.get(&key) {
+                Some(value) => value.as_ref(),
+                None => self.entries.get(&key),
+            };
+            let change = match (&value, current) {
+                (Some(new), Some(old)) => match Patch::diff(old, new) {
+                    Some(patch) => Change::Patch { patch },
+                    None => continue,
+                },
+                (Some(new), None) => Change::Patch {
+                    patch: Patch::Replace { value: new.clone() },
+                },
+                (None, Some(_)) => Change::Remove,
+                (None, None) => continue,
+            };
+
+            seq += 1;
+            let record = DeltaRecord {
+                seq,
+                key: key.clone(),
+                change,
+            };
+            batch.push_str(&serde_json::to_string(&record)?);
+            batch.push('\n');
+            pending.insert(key, value);
+        }
+        if batch.is_empty() {
+            return Ok(());
+        }
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.deltas_path)
+            .await?;
+        file.write_all(batch.as_bytes()).await?;
+        file.flush().await?;
+
+        self.stats.deltas_since_snapshot += seq - self.stats.seq;
+        self.stats.seq = seq;
+        for (key, value) in pending {
+            match value {
+                Some(value) => {
+                    self.entry_sizes
+                        .insert(key.clone(), entry_size(&key, &value));
+                    self.entries.insert(key, value);
+                }
+                None => {
+                    self.entry_sizes.remove(&key);
+                    self.entries.remove(&key);
+                }
+            }
+        }
+        self.stats.full_rewrite_bytes += self.entry_sizes.values().sum::<u64>();
+        self.delta_bytes += batch.len() as u64;
+        self.stats.bytes_written += batch.len() as u64;
+
+        if self.snapshot_due() {
+            self.compact().await?;
+        }
+        Ok(())
+    }
+
+    /// Persist `entries` as the whole state, removing keys not in it
+    pub async fn replace_all(&mut self, entries: BTreeMap<String, Value>) -> RhemaResult<()> {
+        let mut changes: Vec<(String, Option<Value>)> = self
+            .entries
+            .keys()
+            .filter(|key| !entries.contains_key(*key))
+            .map(|key| (key.clone(), None))
+            .collect();
+        changes.extend(entries.into_iter().map(|(key, value)| (key, Some(value))));
+        self.apply(changes).await
+    }
+
+    fn snapshot_due(&self) -> bool {
+        if self.stats.deltas_since_snapshot == 0 {
+            return false;
+        }
+        if self.stats.deltas_since_snapshot >= self.config.snapshot_every {
+            return true;
+        }
+        if self.snapshot_bytes > 0
+            && self.delta_bytes as f64 > self.snapshot_bytes as f64 * self.config.compact_ratio
+        {
+            return true;
+        }
+        match (self.config.max_snapshot_age_secs, self.snapshot_taken_at) {
+            (Some(max_age), Some(taken_at)) => {
+                (Utc::now() - taken_at).num_seconds() >= max_age as i64
+            }
+            _ => false,
+        }
+    }
+
+    /// Write the full state as a snapshot and truncate the delta log
+    pub async fn compact(&mut self) -> RhemaResult<()> {
+        let taken_at = Utc::now();
+        let snapshot = Snapshot {
+            seq: self.stats.seq,
+            taken_at: Some(taken_at),
+            entries: self.entries.clone(),
+        };
+        let data = serde_json::to_string(&snapshot)?;
+
+        if let Some(parent) = self.snapshot_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let temp = with_suffix(&self.snapshot_path, "tmp");
+        tokio::fs::write(&temp, &data).await?;
+        tokio::fs::rename(&temp, &self.snapshot_path).await?;
+        // A crash here leaves deltas the snapshot already covers; their
+        // sequence numbers make the next open skip them
+        tokio::fs::write(&self.deltas_path, b"").await?;
+
+        self.snapshot_taken_at = Some(taken_at);
+        self.snapshot_bytes = data.len() as u64;
+        self.delta_bytes = 0;
+        self.stats.deltas_since_snapshot = 0;
+        self.stats.snapshots_written += 1;
+        self.stats.bytes_written += data.len() as u64;
+        Ok(())
+    }
+}
+
+/// Whether a delta log or snapshot exists at `base`
+pub fn delta_log_exists(base: &Path) -> bool {
+    with_suffix(base, "snapshot.json").exists() || with_suffix(base, "deltas.jsonl").exists()
+}
+
+/// Delete the snapshot and delta log at `base`
+pub async fn remove_delta_log(base: &Path) -> RhemaResult<()> {
+    for path in [
+        with_suffix(base, "snapshot.json"),
+        with_suffix(base, "deltas.jsonl"),
+    ] {
+        if path.exists() {
+            tokio::fs::remove_file(path).await?;
+        }
+    }
+    Ok(())
+}
+
+fn with_suffix(base: &Path, suffix: &str) -> PathBuf {
+    let mut name = base.as_os_str().to_owned();
+    name.push(".");
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+fn entry_size(key: &str, value: &Value) -> u64 {
+    (key.len() + serde_json::to_string(value).map(|s| s.len()).unwrap_or(0)) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_patch_round_trip() {
+        let old = json!({"id": "s1", "status": "Active", "messages": [1, 2], "ended_at": null});
+        let new = json!({"id": "s1", "status": "Completed", "messages": [1, 2, 3]});
+
+        let patch = Patch::diff(&old, &new).unwrap();
+        let Patch::Object { fields, removed } = &patch else {
+            panic!("expected an object patch");
+        };
+        assert_eq!(removed, &vec!["ended_at".to_string()]);
+        assert_eq!(
+            fields["messages"],
+            Patch::Append {
+                items: vec![json!(3)]
+            }
+        );
+        assert!(!fields.contains_key("id"));
+
+        let mut value = old.clone();
+        patch.apply(&mut value);
+        assert_eq!(value, new);
+        assert!(Patch::diff(&new, &new).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_replays_deltas_and_compacts() -> RhemaResult<()> {
+        let temp = tempfile::TempDir::new()?;
+        let base = temp.path().join("sessions");
+        let config = DeltaEncodingConfig {
+            snapshot_every: 10,
+            max_snapshot_age_secs: None,
+            ..Default::default()
+        };
+
+        let mut log = DeltaLog::open(&base, config.clone()).await?;
+        let mut session = json!({"id": "s1", "topic": "x".repeat(2000), "messages": []});
+        for i in 0..25 {
+            session["messages"]
+                .as_array_mut()
+                .unwrap()
+                .push(json!({"n": i}));
+            log.apply(vec![
+                ("session/s1".to_string(), Some(session.clone())),
+                ("session/idle".to_string(), Some(json!({"id": "idle"}))),
+            ])
+            .await?;
+        }
+        log.apply(vec![("session/idle".to_string(), None)]).await?;
+
+        let stats = log.stats().clone();
+        assert_eq!(stats.seq, 27);
+        assert_eq!(stats.snapshots_written, 2);
+        assert!(stats.write_reduction() > 3.0, "{:?}", stats);
+
+        // A torn final record is dropped on open
+        let mut file = tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(with_suffix(&base, "deltas.jsonl"))
+            .await?;
+        file.write_all(b"{\"seq\": 28, \"key\": \"sess").await?;
+
+        let mut reopened = DeltaLog::open(&base, config.clone()).await?;
+        assert_eq!(reopened.entries().len(), 1);
+        assert_eq!(reopened.entries()["session/s1"], session);
+        assert_eq!(reopened.stats().seq, 27);
+
+        // and cut off, so the next delta survives another reopen
+        reopened
+            .apply(vec![("session/s2".to_string(), Some(json!({"id": "s2"})))])
+            .await?;
+        let reopened = DeltaLog::open(&base, config).await?;
+        assert_eq!(reopened.entries()["session/s2"], json!({"id": "s2"}));
+        assert_eq!(reopened.stats().seq, 28);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_failed_write_leaves_state_unchanged() -> RhemaResult<()> {
+        let temp = tempfile::TempDir::new()?;
+        let base = temp.path().join("sessions");
+        let mut log = DeltaLog::open(&base, DeltaEncodingConfig::default()).await?;
+        log.apply(vec![("session/s1".to_string(), Some(json!({"n": 1})))])
+            .await?;
+
+        // A directory in place of the delta log makes the append fail
+        tokio::fs::remove_file(with_suffix(&base, "deltas.jsonl")).await?;
+        tokio::fs::create_dir(with_suffix(&base, "deltas.jsonl")).await?;
+        assert!(log
+            .apply(vec![("session/s1".to_string(), Some(json!({"n": 2})))])
+            .await
+            .is_err());
+        assert_eq!(log.entries()["session/s1"], json!({"n": 1}));
+        assert_eq!(log.stats().seq, 1);
+        Ok(())
+    }
+}
@@ -14,17 +14,29 @@
  * limitations under the License.
  */
 
+pub mod backend;
 pub mod consensus_store;
+pub mod delta_log;
+pub mod session_inspector;
 pub mod session_store;
 pub mod state_manager;
 
+pub use backend::{
+    migrate_backend, open_engine, open_engine_url, BackendMigrationReport, Collection, LogEntry,
+    StorageEngine,
+};
 pub use consensus_store::ConsensusStore;
+pub use delta_log::{DeltaEncodingConfig, DeltaLog, DeltaLogStats, Patch};
+pub use session_inspector::{MessageFilter, SessionInspector, SessionSummary};
 pub use session_store::SessionStore;
 pub use state_manager::StateManager;
 
+use backend::MemoryEngine;
 use rhema_core::RhemaResult;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::warn;
 
 /// Persistence configuration for production environments
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,6 +69,9 @@ pub struct PersistenceConfig {
     pub cleanup_interval_hours: u64,
     /// Data retention days
     pub data_retention_days: u64,
+    /// Delta encoding for the file-backed session and state stores
+    #[serde(default)]
+    pub delta_encoding: DeltaEncodingConfig,
 }
 
 /// Storage backend types
@@ -64,16 +79,29 @@ pub struct PersistenceConfig {
 pub enum StorageBackend {
     /// File-based storage (JSON/YAML)
     File,
+    /// Embedded sled database
+    Sled,
     /// SQLite database
     Sqlite,
     /// PostgreSQL database
     Postgres,
-    /// Redis database
+    /// Redis database; not implemented, data is kept in memory
     Redis,
     /// In-memory storage (for testing)
     Memory,
 }
 
+impl StorageBackend {
+    /// Whether the session, consensus and state stores keep their data in
+    /// the storage engine rather than in files of their own
+    pub fn stores_in_engine(&self) -> bool {
+        matches!(
+            self,
+            StorageBackend::Sled | StorageBackend::Sqlite | StorageBackend::Postgres
+        )
+    }
+}
+
 impl Default for PersistenceConfig {
     fn default() -> Self {
         Self {
@@ -91,6 +119,7 @@ impl Default for PersistenceConfig {
             enable_cleanup: true,
             cleanup_interval_hours: 168, // 1 week
             data_retention_days: 90,
+            delta_encoding: DeltaEncodingConfig::default(),
         }
     }
 }
@@ -101,23 +130,70 @@ pub struct PersistenceManager {
     session_store: SessionStore,
     consensus_store: ConsensusStore,
     state_manager: StateManager,
+    engine: Arc<dyn StorageEngine>,
 }
 
 impl PersistenceManager {
     /// Create a new persistence manager
     pub async fn new(config: PersistenceConfig) -> RhemaResult<Self> {
-        let session_store = SessionStore::new(config.clone()).await?;
-        let consensus_store = ConsensusStore::new(config.clone()).await?;
-        let state_manager = StateManager::new(config.clone()).await?;
+        let engine: Arc<dyn StorageEngine> = if config.backend == StorageBackend::Redis {
+            warn!("Redis persistence is not implemented; coordination data is kept in memory");
+            Arc::new(MemoryEngine::new())
+        } else {
+            open_engine(&config).await?
+        };
+        let session_store = SessionStore::with_engine(config.clone(), engine.clone()).await?;
+        let consensus_store = ConsensusStore::with_engine(config.clone(), engine.clone()).await?;
+        let state_manager = StateManager::with_engine(config.clone(), engine.clone()).await?;
 
         Ok(Self {
             config,
             session_store,
             consensus_store,
             state_manager,
+            engine,
         })
     }
 
+    /// Storage engine holding message logs, the agent registry, consensus
+    /// logs and action history
+    pub fn engine(&self) -> Arc<dyn StorageEngine> {
+        self.engine.clone()
+    }
+
+    /// Append an agent message to the message log
+    pub async fn log_message<T: Serialize>(&self, message: &T) -> RhemaResult<u64> {
+        self.engine
+            .append(Collection::Messages, &serde_json::to_value(message)?)
+            .await
+    }
+
+    /// Register or update an agent in the registry
+    pub async fn register_agent<T: Serialize>(&self, agent_id: &str, info: &T) -> RhemaResult<()> {
+        self.engine
+            .put(Collection::Agents, agent_id, &serde_json::to_value(info)?)
+            .await
+    }
+
+    /// Remove an agent from the registry
+    pub async fn unregister_agent(&self, agent_id: &str) -> RhemaResult<bool> {
+        self.engine.delete(Collection::Agents, agent_id).await
+    }
+
+    /// Append an entry to the consensus log
+    pub async fn log_consensus_entry<T: Serialize>(&self, entry: &T) -> RhemaResult<u64> {
+        self.engine
+            .append(Collection::ConsensusLog, &serde_json::to_value(entry)?)
+            .await
+    }
+
+    /// Append an executed action to the action history
+    pub async fn record_action<T: Serialize>(&self, action: &T) -> RhemaResult<u64> {
+        self.engine
+            .append(Collection::ActionHistory, &serde_json::to_value(action)?)
+            .await
+    }
+
     /// Get session store reference
     pub fn session_store(&self) -> &SessionStore {
         &self.session_store
@@ -192,3 +268,52 @@ pub struct StoreStats {
     pub last_cleanup: Option<chrono::DateTime<chrono::Utc>>,
     pub validation_errors: usize,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_manager_stores_data_in_every_backend() -> RhemaResult<()> {
+        let temp = tempfile::TempDir::new()?;
+        for backend in [
+            StorageBackend::File,
+            StorageBackend::Sled,
+            StorageBackend::Sqlite,
+            StorageBackend::Redis,
+            StorageBackend::Memory,
+        ] {
+            let config = PersistenceConfig {
+                storage_path: Some(temp.path().join(format!("{:?}", backend))),
+                backend: backend.clone(),
+                ..PersistenceConfig::default()
+            };
+            {
+                let manager = PersistenceManager::new(config.clone()).await?;
+                manager
+                    .state_manager()
+                    .store_configuration(
+                        "limits".to_string(),
+                        serde_json::json!({"max_agents": 4}),
+                        "1".to_string(),
+                        None,
+                    )
+                    .await?;
+                assert_eq!(manager.log_message(&"hello").await?, 1);
+                if backend.stores_in_engine() {
+                    assert!(!manager.engine().list(Collection::State).await?.is_empty());
+                }
+            }
+
+            let reopened = PersistenceManager::new(config).await?;
+            let persisted = !matches!(backend, StorageBackend::Redis | StorageBackend::Memory);
+            assert_eq!(
+                reopened.state_manager().get_configuration("limits").await.is_some(),
+                persisted,
+                "{:?}",
+                backend
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,297 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::{PersistenceConfig, SessionStore, StorageBackend};
+use crate::agent::real_time_coordination::{
+    AgentMessage, CoordinationSession, MessagePriority, MessageType, SessionStatus,
+};
+use chrono::{DateTime, Utc};
+use rhema_core::{RhemaError, RhemaResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// Filter applied to messages when inspecting or tailing sessions
+#[derive(Debug, Clone, Default)]
+pub struct MessageFilter {
+    /// Only messages sent by or addressed to this agent
+    pub agent_id: Option<String>,
+    /// Only messages of these types (matched case-insensitively by name)
+    pub message_types: Vec<String>,
+    /// Only messages at or above this priority
+    pub min_priority: Option<MessagePriority>,
+}
+
+impl MessageFilter {
+    pub fn matches(&self, message: &AgentMessage) -> bool {
+        if let Some(agent_id) = &self.agent_id {
+            if &message.sender_id != agent_id && !message.recipient_ids.contains(agent_id) {
+                return false;
+            }
+        }
+
+        if !self.message_types.is_empty() {
+            let name = message_type_name(&message.message_type);
+            if !self
+                .message_types
+                .iter()
+                .any(|t| t.eq_ignore_ascii_case(&name))
+            {
+                return false;
+            }
+        }
+
+        match &self.min_priority {
+            Some(min) => message.priority >= *min,
+            None => true,
+        }
+    }
+}
+
+/// Display name of a message type; custom types use their own name
+pub fn message_type_name(message_type: &MessageType) -> String {
+    match message_type {
+        MessageType::Custom(name) => name.clone(),
+        other => format!("{:?}", other),
+    }
+}
+
+/// Condensed view of a session for listings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub id: String,
+    pub topic: String,
+    pub status: SessionStatus,
+    pub participants: Vec<String>,
+    pub message_count: usize,
+    pub decision_count: usize,
+    pub started_at: DateTime<Utc>,
+    pub last_activity: DateTime<Utc>,
+}
+
+impl From<&CoordinationSession> for SessionSummary {
+    fn from(session: &CoordinationSession) -> Self {
+        let last_activity = session
+            .messages
+            .iter()
+            .map(|m| m.timestamp)
+            .max()
+            .unwrap_or(session.started_at);
+
+        Self {
+            id: session.id.clone(),
+            topic: session.topic.clone(),
+            status: session.status.clone(),
+            participants: session.participants.clone(),
+            message_count: session.messages.len(),
+            decision_count: session.decisions.len(),
+            started_at: session.started_at,
+            last_activity,
+        }
+    }
+}
+
+/// A message together with the session it was posted to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionMessage {
+    pub session_id: String,
+    pub message: AgentMessage,
+}
+
+/// Read-only view over sessions persisted by a running coordination system.
+///
+/// The inspector reloads the session store on every call, so it observes
+/// updates written by the daemon without sharing its process.
+pub struct SessionInspector {
+    config: PersistenceConfig,
+    seen: HashSet<String>,
+}
+
+impl SessionInspector {
+    /// Create an inspector over the file store rooted at `storage_path`
+    pub fn new(storage_path: PathBuf) -> Self {
+        Self {
+            config: PersistenceConfig {
+                backend: StorageBackend::File,
+                storage_path: Some(storage_path),
+                enable_backups: false,
+                enable_cleanup: false,
+                ..PersistenceConfig::default()
+            },
+            seen: HashSet::new(),
+        }
+    }
+
+    /// Open the store in whichever format the daemon writes, so inspecting
+    /// never converts its files
+    async fn open_store(&self) -> RhemaResult<SessionStore> {
+        let mut config = self.config.clone();
+        if let Some(path) = &config.storage_path {
+            config.delta_encoding.enabled = !path.join("sessions").exists();
+        }
+        SessionStore::new(config).await
+    }
+
+    /// List sessions, most recently active first
+    pub async fn list_sessions(&self, active_only: bool) -> RhemaResult<Vec<SessionSummary>> {
+        let store = self.open_store().await?;
+        let mut summaries: Vec<SessionSummary> = store
+            .list_sessions()
+            .await
+            .iter()
+            .filter(|s| !active_only || s.status == SessionStatus::Active)
+            .map(SessionSummary::from)
+            .collect();
+        summaries.sort_by_key(|summary| std::cmp::Reverse(summary.last_activity));
+        Ok(summaries)
+    }
+
+    /// Load a single session
+    pub async fn get_session(&self, session_id: &str) -> RhemaResult<CoordinationSession> {
+        let store = self.open_store().await?;
+        store
+            .get_session(session_id)
+            .await
+            .ok_or_else(|| RhemaError::NotFound(format!("Session {} not found", session_id)))
+    }
+
+    /// Return messages not returned by a previous poll, oldest first.
+    ///
+    /// When `session_id` is `None` messages from all sessions are included.
+    pub async fn poll_messages(
+        &mut self,
+        session_id: Option<&str>,
+        filter: &MessageFilter,
+    ) -> RhemaResult<Vec<SessionMessage>> {
+        let sessions = match session_id {
+            Some(id) => vec![self.get_session(id).await?],
+            None => self.open_store().await?.list_sessions().await,
+        };
+
+        let mut messages = Vec::new();
+        for session in sessions {
+            for message in session.messages {
+                if self.seen.insert(message.id.clone()) && filter.matches(&message) {
+                    messages.push(SessionMessage {
+                        session_id: session.id.clone(),
+                        message,
+                    });
+                }
+            }
+        }
+        messages.sort_by_key(|m| m.message.timestamp);
+        Ok(messages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn message(sender: &str, message_type: MessageType, priority: MessagePriority) -> AgentMessage {
+        AgentMessage {
+            id: uuid::Uuid::new_v4().to_string(),
+            message_type,
+            priority,
+            sender_id: sender.to_string(),
+            recipient_ids: vec!["observer".to_string()],
+            content: "hello".to_string(),
+            payload: None,
+            timestamp: Utc::now(),
+            requires_ack: false,
+            expires_at: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_message_filter() {
+        let filter = MessageFilter {
+            agent_id: Some("agent-a".to_string()),
+            message_types: vec!["statusupdate".to_string()],
+            min_priority: Some(MessagePriority::High),
+        };
+
+        assert!(filter.matches(&message(
+            "agent-a",
+            MessageType::StatusUpdate,
+            MessagePriority::Critical
+        )));
+        assert!(!filter.matches(&message(
+            "agent-b",
+            MessageType::StatusUpdate,
+            MessagePriority::Critical
+        )));
+        assert!(!filter.matches(&message(
+            "agent-a",
+            MessageType::TaskAssignment,
+            MessagePriority::Critical
+        )));
+        assert!(!filter.matches(&message(
+            "agent-a",
+            MessageType::StatusUpdate,
+            MessagePriority::Normal
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_poll_returns_only_new_messages() -> RhemaResult<()> {
+        let temp = tempfile::TempDir::new()?;
+        let config = PersistenceConfig {
+            storage_path: Some(temp.path().to_path_buf()),
+            ..PersistenceConfig::default()
+        };
+        let store = SessionStore::new(config).await?;
+
+        let mut session = CoordinationSession {
+            id: "s1".to_string(),
+            topic: "planning".to_string(),
+            participants: vec!["agent-a".to_string()],
+            status: SessionStatus::Active,
+            started_at: Utc::now(),
+            ended_at: None,
+            messages: vec![message(
+                "agent-a",
+                MessageType::SessionMessage,
+                MessagePriority::Normal,
+            )],
+            decisions: Vec::new(),
+        };
+        store.store_session(session.clone()).await?;
+
+        let mut inspector = SessionInspector::new(temp.path().to_path_buf());
+        let filter = MessageFilter::default();
+        assert_eq!(inspector.poll_messages(Some("s1"), &filter).await?.len(), 1);
+        assert!(inspector
+            .poll_messages(Some("s1"), &filter)
+            .await?
+            .is_empty());
+
+        session.messages.push(message(
+            "agent-a",
+            MessageType::SessionMessage,
+            MessagePriority::High,
+        ));
+        store.update_session(session).await?;
+        assert_eq!(inspector.poll_messages(None, &filter).await?.len(), 1);
+
+        let sessions = inspector.list_sessions(true).await?;
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].message_count, 2);
+        Ok(())
+    }
+}
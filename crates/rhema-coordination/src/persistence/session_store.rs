@@ -14,16 +14,21 @@
  * limitations under the License.
  */
 
-use super::{PersistenceConfig, StorageBackend, StoreStats};
+use super::backend::{apply_record_changes, load_records, replace_records};
+use super::delta_log::{delta_log_exists, remove_delta_log, DeltaLog, DeltaLogStats};
+use super::{
+    open_engine, Collection, PersistenceConfig, StorageBackend, StorageEngine, StoreStats,
+};
 use crate::agent::real_time_coordination::{AdvancedSession, CoordinationSession, SessionStatus};
 use chrono::{DateTime, Utc};
 use rhema_core::RhemaResult;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use tracing::info;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{info, warn};
 
 /// Session store for persisting coordination sessions
 pub struct SessionStore {
@@ -31,6 +36,10 @@ pub struct SessionStore {
     sessions: Arc<RwLock<HashMap<String, StoredSession>>>,
     advanced_sessions: Arc<RwLock<HashMap<String, StoredAdvancedSession>>>,
     file_path: Option<PathBuf>,
+    /// Delta log replacing full rewrites of `file_path` when delta encoding is on
+    deltas: Option<Mutex<DeltaLog>>,
+    /// Storage engine holding the sessions for database backends
+    engine: Option<Arc<dyn StorageEngine>>,
 }
 
 /// Stored session with metadata
@@ -58,6 +67,28 @@ pub struct StoredAdvancedSession {
 impl SessionStore {
     /// Create a new session store
     pub async fn new(config: PersistenceConfig) -> RhemaResult<Self> {
+        let engine = if config.backend.stores_in_engine() {
+            Some(open_engine(&config).await?)
+        } else {
+            None
+        };
+        Self::open(config, engine).await
+    }
+
+    /// Create a session store sharing an open storage engine, which holds the
+    /// sessions when the backend stores them in the engine
+    pub async fn with_engine(
+        config: PersistenceConfig,
+        engine: Arc<dyn StorageEngine>,
+    ) -> RhemaResult<Self> {
+        let engine = config.backend.stores_in_engine().then_some(engine);
+        Self::open(config, engine).await
+    }
+
+    async fn open(
+        config: PersistenceConfig,
+        engine: Option<Arc<dyn StorageEngine>>,
+    ) -> RhemaResult<Self> {
         let file_path = match &config.backend {
             StorageBackend::File => {
                 let path = config
@@ -76,11 +107,20 @@ impl SessionStore {
             _ => None,
         };
 
+        let deltas = match &file_path {
+            Some(path) if config.delta_encoding.enabled => Some(Mutex::new(
+                DeltaLog::open(path, config.delta_encoding.clone()).await?,
+            )),
+            _ => None,
+        };
+
         let mut store = Self {
             config,
             sessions: Arc::new(RwLock::new(HashMap::new())),
             advanced_sessions: Arc::new(RwLock::new(HashMap::new())),
             file_path,
+            deltas,
+            engine,
         };
 
         // Load existing data
@@ -103,13 +143,14 @@ impl SessionStore {
             size_bytes,
         };
 
+        let key = session_key(&stored_session.session.id);
+        let value = serde_json::to_value(&stored_session)?;
         {
             let mut sessions = self.sessions.write().await;
             sessions.insert(stored_session.session.id.clone(), stored_session);
         }
 
-        self.save().await?;
-        Ok(())
+        self.persist(vec![(key, Some(value))]).await
     }
 
     /// Store an advanced session
@@ -126,13 +167,14 @@ impl SessionStore {
             size_bytes,
         };
 
+        let key = advanced_key(&stored_session.session.id);
+        let value = serde_json::to_value(&stored_session)?;
         {
             let mut sessions = self.advanced_sessions.write().await;
             sessions.insert(stored_session.session.id.clone(), stored_session);
         }
 
-        self.save().await?;
-        Ok(())
+        self.persist(vec![(key, Some(value))]).await
     }
 
     /// Retrieve a coordination session
@@ -166,21 +208,24 @@ impl SessionStore {
         let size_bytes = serde_json::to_string(&session)?.len() as u64;
         let now = Utc::now();
 
-        let mut sessions = self.sessions.write().await;
+        let change = {
+            let mut sessions = self.sessions.write().await;
 
-        if let Some(stored_session) = sessions.get_mut(&session.id) {
-            stored_session.session = session;
-            stored_session.updated_at = now;
-            stored_session.size_bytes = size_bytes;
-        } else {
-            return Err(rhema_core::RhemaError::NotFound(format!(
-                "Session {} not found",
-                session.id
-            )));
-        }
+            if let Some(stored_session) = sessions.get_mut(&session.id) {
+                let key = session_key(&session.id);
+                stored_session.session = session;
+                stored_session.updated_at = now;
+                stored_session.size_bytes = size_bytes;
+                (key, Some(serde_json::to_value(&*stored_session)?))
+            } else {
+                return Err(rhema_core::RhemaError::NotFound(format!(
+                    "Session {} not found",
+                    session.id
+                )));
+            }
+        };
 
-        self.save().await?;
-        Ok(())
+        self.persist(vec![change]).await
     }
 
     /// Update an advanced session
@@ -188,21 +233,24 @@ impl SessionStore {
         let size_bytes = serde_json::to_string(&session)?.len() as u64;
         let now = Utc::now();
 
-        let mut sessions = self.advanced_sessions.write().await;
+        let change = {
+            let mut sessions = self.advanced_sessions.write().await;
 
-        if let Some(stored_session) = sessions.get_mut(&session.id) {
-            stored_session.session = session;
-            stored_session.updated_at = now;
-            stored_session.size_bytes = size_bytes;
-        } else {
-            return Err(rhema_core::RhemaError::NotFound(format!(
-                "Advanced session {} not found",
-                session.id
-            )));
-        }
+            if let Some(stored_session) = sessions.get_mut(&session.id) {
+                let key = advanced_key(&session.id);
+                stored_session.session = session;
+                stored_session.updated_at = now;
+                stored_session.size_bytes = size_bytes;
+                (key, Some(serde_json::to_value(&*stored_session)?))
+            } else {
+                return Err(rhema_core::RhemaError::NotFound(format!(
+                    "Advanced session {} not found",
+                    session.id
+                )));
+            }
+        };
 
-        self.save().await?;
-        Ok(())
+        self.persist(vec![change]).await
     }
 
     /// Delete a session
@@ -217,8 +265,11 @@ impl SessionStore {
             advanced_sessions.remove(session_id);
         }
 
-        self.save().await?;
-        Ok(())
+        self.persist(vec![
+            (session_key(session_id), None),
+            (advanced_key(session_id), None),
+        ])
+        .await
     }
 
     /// List all sessions
@@ -255,9 +306,28 @@ impl SessionStore {
 
     /// Load data from storage
     async fn load(&mut self) -> RhemaResult<()> {
+        if let Some(engine) = &self.engine {
+            let entries = load_records(engine.as_ref(), Collection::Sessions).await?;
+            return self.restore(&entries).await;
+        }
+
         match &self.config.backend {
             StorageBackend::File => {
                 if let Some(path) = &self.file_path {
+                    if let Some(deltas) = &self.deltas {
+                        if delta_log_exists(path) {
+                            let entries = deltas.lock().await.entries().clone();
+                            return self.restore(&entries).await;
+                        }
+                    } else if !path.exists() && delta_log_exists(path) {
+                        // Delta encoding was turned off; fold the log back
+                        // into a full file
+                        let log = DeltaLog::open(path, self.config.delta_encoding.clone()).await?;
+                        self.restore(log.entries()).await?;
+                        self.save().await?;
+                        return remove_delta_log(path).await;
+                    }
+
                     if path.exists() {
                         let data = tokio::fs::read_to_string(path).await?;
                         let stored_data: StoredSessionData = serde_json::from_str(&data)?;
@@ -272,6 +342,15 @@ impl SessionStore {
                             "Loaded {} sessions and {} advanced sessions from storage",
                             session_count, advanced_session_count
                         );
+
+                        // Move the full file over to a snapshot
+                        if let Some(deltas) = &self.deltas {
+                            let entries = self.entries().await?;
+                            let mut log = deltas.lock().await;
+                            log.replace_all(entries).await?;
+                            log.compact().await?;
+                            tokio::fs::remove_file(path).await?;
+                        }
                     }
                 }
             }
@@ -283,8 +362,73 @@ impl SessionStore {
         Ok(())
     }
 
+    /// Rebuild the session maps from stored entries
+    async fn restore(&self, entries: &BTreeMap<String, Value>) -> RhemaResult<()> {
+        let mut sessions = HashMap::new();
+        let mut advanced_sessions = HashMap::new();
+        for (key, value) in entries {
+            match key.split_once('/') {
+                Some(("session", id)) => {
+                    sessions.insert(id.to_string(), serde_json::from_value(value.clone())?);
+                }
+                Some(("advanced", id)) => {
+                    advanced_sessions
+                        .insert(id.to_string(), serde_json::from_value(value.clone())?);
+                }
+                _ => warn!("Ignoring unknown session store entry {}", key),
+            }
+        }
+
+        info!(
+            "Restored {} sessions and {} advanced sessions",
+            sessions.len(),
+            advanced_sessions.len()
+        );
+        *self.sessions.write().await = sessions;
+        *self.advanced_sessions.write().await = advanced_sessions;
+        Ok(())
+    }
+
+    /// All sessions as keyed entries
+    async fn entries(&self) -> RhemaResult<BTreeMap<String, Value>> {
+        let mut entries = BTreeMap::new();
+        for (id, stored_session) in self.sessions.read().await.iter() {
+            entries.insert(session_key(id), serde_json::to_value(stored_session)?);
+        }
+        for (id, stored_session) in self.advanced_sessions.read().await.iter() {
+            entries.insert(advanced_key(id), serde_json::to_value(stored_session)?);
+        }
+        Ok(entries)
+    }
+
+    /// Persist changed entries: as engine records for database backends,
+    /// appending deltas when delta encoding is on and rewriting the whole
+    /// file otherwise
+    async fn persist(&self, changes: Vec<(String, Option<Value>)>) -> RhemaResult<()> {
+        if let Some(engine) = &self.engine {
+            return apply_record_changes(engine.as_ref(), Collection::Sessions, changes).await;
+        }
+        match &self.deltas {
+            Some(deltas) => deltas.lock().await.apply(changes).await,
+            None => self.save().await,
+        }
+    }
+
+    /// Write statistics of the delta log, when delta encoding is on
+    pub async fn delta_stats(&self) -> Option<DeltaLogStats> {
+        match &self.deltas {
+            Some(deltas) => Some(deltas.lock().await.stats().clone()),
+            None => None,
+        }
+    }
+
     /// Save data to storage
     async fn save(&self) -> RhemaResult<()> {
+        if let Some(engine) = &self.engine {
+            let entries = self.entries().await?;
+            return replace_records(engine.as_ref(), Collection::Sessions, &entries).await;
+        }
+
         match &self.config.backend {
             StorageBackend::File => {
                 if let Some(path) = &self.file_path {
@@ -349,7 +493,13 @@ impl SessionStore {
                     .retain(|_, stored_session| stored_session.updated_at > cutoff_date);
             }
 
-            self.save().await?;
+            match &self.deltas {
+                Some(deltas) => {
+                    let entries = self.entries().await?;
+                    deltas.lock().await.replace_all(entries).await?;
+                }
+                None => self.save().await?,
+            }
             info!("Session cleanup completed");
         }
         Ok(())
@@ -406,9 +556,126 @@ impl SessionStore {
     }
 }
 
+fn session_key(id: &str) -> String {
+    format!("session/{}", id)
+}
+
+fn advanced_key(id: &str) -> String {
+    format!("advanced/{}", id)
+}
+
 /// Stored session data structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct StoredSessionData {
     sessions: HashMap<String, StoredSession>,
     advanced_sessions: HashMap<String, StoredAdvancedSession>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent::real_time_coordination::{AgentMessage, MessagePriority, MessageType};
+    use crate::persistence::DeltaEncodingConfig;
+
+    fn message(sender: &str, n: usize) -> AgentMessage {
+        AgentMessage {
+            id: uuid::Uuid::new_v4().to_string(),
+            message_type: MessageType::SessionMessage,
+            priority: MessagePriority::Normal,
+            sender_id: sender.to_string(),
+            recipient_ids: vec!["agent-b".to_string()],
+            content: format!("progress update {}", n),
+            payload: None,
+            timestamp: Utc::now(),
+            requires_ack: false,
+            expires_at: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    /// Every session gains a message per update; returns the bytes a full
+    /// rewrite store wrote alongside the store itself
+    async fn run_load(config: PersistenceConfig) -> RhemaResult<(SessionStore, u64)> {
+        let store = SessionStore::new(config).await?;
+        let mut sessions: Vec<CoordinationSession> = (0..20)
+            .map(|i| CoordinationSession {
+                id: format!("s{}", i),
+                topic: "load".to_string(),
+                participants: vec!["agent-a".to_string(), "agent-b".to_string()],
+                status: SessionStatus::Active,
+                started_at: Utc::now(),
+                ended_at: None,
+                messages: Vec::new(),
+                decisions: Vec::new(),
+            })
+            .collect();
+        for session in &sessions {
+            store.store_session(session.clone()).await?;
+        }
+
+        let mut full_file_bytes = 0;
+        for round in 0..30 {
+            for session in sessions.iter_mut() {
+                session.messages.push(message("agent-a", round));
+                store.update_session(session.clone()).await?;
+                if let Some(path) = store.file_path.as_ref().filter(|p| p.exists()) {
+                    full_file_bytes += tokio::fs::metadata(path).await?.len();
+                }
+            }
+        }
+        Ok((store, full_file_bytes))
+    }
+
+    #[tokio::test]
+    async fn test_delta_encoding_reduces_write_amplification() -> RhemaResult<()> {
+        let full_dir = tempfile::TempDir::new()?;
+        let (_, full_rewrite_bytes) = run_load(PersistenceConfig {
+            storage_path: Some(full_dir.path().to_path_buf()),
+            delta_encoding: DeltaEncodingConfig {
+                enabled: false,
+                ..Default::default()
+            },
+            ..PersistenceConfig::default()
+        })
+        .await?;
+
+        let delta_dir = tempfile::TempDir::new()?;
+        let config = PersistenceConfig {
+            storage_path: Some(delta_dir.path().to_path_buf()),
+            ..PersistenceConfig::default()
+        };
+        let (store, _) = run_load(config.clone()).await?;
+        let stats = store.delta_stats().await.unwrap();
+        assert_eq!(stats.snapshots_written, 1);
+        assert!(
+            full_rewrite_bytes > stats.bytes_written * 10,
+            "full rewrites wrote {} bytes, deltas {}",
+            full_rewrite_bytes,
+            stats.bytes_written
+        );
+
+        let reopened = SessionStore::new(config).await?;
+        let sessions = reopened.list_sessions().await;
+        assert_eq!(sessions.len(), 20);
+        assert!(sessions.iter().all(|s| s.messages.len() == 30));
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_sessions_persist_in_storage_engine() -> RhemaResult<()> {
+        let temp = tempfile::TempDir::new()?;
+        let config = PersistenceConfig {
+            backend: StorageBackend::Sqlite,
+            storage_path: Some(temp.path().to_path_buf()),
+            ..PersistenceConfig::default()
+        };
+        let (store, _) = run_load(config.clone()).await?;
+        assert!(store.file_path.is_none());
+        store.delete_session("s0").await?;
+
+        let reopened = SessionStore::new(config).await?;
+        let sessions = reopened.list_sessions().await;
+        assert_eq!(sessions.len(), 19);
+        assert!(sessions.iter().all(|s| s.messages.len() == 30));
+        Ok(())
+    }
+}
@@ -14,16 +14,23 @@
  * limitations under the License.
  */
 
-use super::{PersistenceConfig, StorageBackend, StoreStats};
+use super::backend::{apply_record_changes, load_records, replace_records};
+use super::delta_log::{delta_log_exists, remove_delta_log, DeltaLog, DeltaLogStats};
+use super::{
+    open_engine, Collection, PersistenceConfig, StorageBackend, StorageEngine, StoreStats,
+};
 use crate::agent::real_time_coordination::{AgentInfo, AgentStatus};
 use chrono::{DateTime, Utc};
 use rhema_core::RhemaResult;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use tracing::info;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{info, warn};
+
+const METRICS_KEY: &str = "metrics";
 
 /// State manager for persisting general system state
 pub struct StateManager {
@@ -32,6 +39,10 @@ pub struct StateManager {
     system_metrics: Arc<RwLock<SystemMetrics>>,
     configuration_snapshots: Arc<RwLock<HashMap<String, StoredConfiguration>>>,
     file_path: Option<PathBuf>,
+    /// Delta log replacing full rewrites of `file_path` when delta encoding is on
+    deltas: Option<Mutex<DeltaLog>>,
+    /// Storage engine holding the state for database backends
+    engine: Option<Arc<dyn StorageEngine>>,
 }
 
 /// Stored agent state with metadata
@@ -87,6 +98,28 @@ pub struct StoredConfiguration {
 impl StateManager {
     /// Create a new state manager
     pub async fn new(config: PersistenceConfig) -> RhemaResult<Self> {
+        let engine = if config.backend.stores_in_engine() {
+            Some(open_engine(&config).await?)
+        } else {
+            None
+        };
+        Self::open(config, engine).await
+    }
+
+    /// Create a state manager sharing an open storage engine, which holds the
+    /// state when the backend stores it in the engine
+    pub async fn with_engine(
+        config: PersistenceConfig,
+        engine: Arc<dyn StorageEngine>,
+    ) -> RhemaResult<Self> {
+        let engine = config.backend.stores_in_engine().then_some(engine);
+        Self::open(config, engine).await
+    }
+
+    async fn open(
+        config: PersistenceConfig,
+        engine: Option<Arc<dyn StorageEngine>>,
+    ) -> RhemaResult<Self> {
         let file_path = match &config.backend {
             StorageBackend::File => {
                 let path = config
@@ -105,12 +138,21 @@ impl StateManager {
             _ => None,
         };
 
+        let deltas = match &file_path {
+            Some(path) if config.delta_encoding.enabled => Some(Mutex::new(
+                DeltaLog::open(path, config.delta_encoding.clone()).await?,
+            )),
+            _ => None,
+        };
+
         let mut manager = Self {
             config,
             agent_states: Arc::new(RwLock::new(HashMap::new())),
             system_metrics: Arc::new(RwLock::new(SystemMetrics::default())),
             configuration_snapshots: Arc::new(RwLock::new(HashMap::new())),
             file_path,
+            deltas,
+            engine,
         };
 
         // Load existing data
@@ -126,7 +168,7 @@ impl StateManager {
 
         let mut states = self.agent_states.write().await;
 
-        let _stored_state = if let Some(existing) = states.get_mut(&agent_info.id) {
+        let stored_state = if let Some(existing) = states.get_mut(&agent_info.id) {
             // Record state transition if status changed
             if existing.agent_info.status != agent_info.status {
                 let transition = StateTransition {
@@ -158,9 +200,11 @@ impl StateManager {
             states.insert(stored_state.agent_info.id.clone(), stored_state.clone());
             stored_state
         };
+        drop(states);
 
-        self.save().await?;
-        Ok(())
+        let key = agent_key(&stored_state.agent_info.id);
+        let value = serde_json::to_value(&stored_state)?;
+        self.persist(vec![(key, Some(value))]).await
     }
 
     /// Retrieve agent state
@@ -187,13 +231,14 @@ impl StateManager {
 
     /// Update system metrics
     pub async fn update_system_metrics(&self, metrics: SystemMetrics) -> RhemaResult<()> {
+        let value = serde_json::to_value(&metrics)?;
         {
             let mut system_metrics = self.system_metrics.write().await;
             *system_metrics = metrics;
         }
 
-        self.save().await?;
-        Ok(())
+        self.persist(vec![(METRICS_KEY.to_string(), Some(value))])
+            .await
     }
 
     /// Get system metrics
@@ -223,13 +268,14 @@ impl StateManager {
             size_bytes,
         };
 
+        let key = config_key(&name);
+        let value = serde_json::to_value(&stored_config)?;
         {
             let mut configs = self.configuration_snapshots.write().await;
             configs.insert(name, stored_config);
         }
 
-        self.save().await?;
-        Ok(())
+        self.persist(vec![(key, Some(value))]).await
     }
 
     /// Retrieve configuration snapshot
@@ -298,9 +344,28 @@ impl StateManager {
 
     /// Load data from storage
     async fn load(&mut self) -> RhemaResult<()> {
+        if let Some(engine) = &self.engine {
+            let entries = load_records(engine.as_ref(), Collection::State).await?;
+            return self.restore(&entries).await;
+        }
+
         match &self.config.backend {
             StorageBackend::File => {
                 if let Some(path) = &self.file_path {
+                    if let Some(deltas) = &self.deltas {
+                        if delta_log_exists(path) {
+                            let entries = deltas.lock().await.entries().clone();
+                            return self.restore(&entries).await;
+                        }
+                    } else if !path.exists() && delta_log_exists(path) {
+                        // Delta encoding was turned off; fold the log back
+                        // into a full file
+                        let log = DeltaLog::open(path, self.config.delta_encoding.clone()).await?;
+                        self.restore(log.entries()).await?;
+                        self.save().await?;
+                        return remove_delta_log(path).await;
+                    }
+
                     if path.exists() {
                         let data = tokio::fs::read_to_string(path).await?;
                         let stored_data: StoredStateData = serde_json::from_str(&data)?;
@@ -316,6 +381,15 @@ impl StateManager {
                             "Loaded {} agent states and {} configurations from storage",
                             agent_states_count, configurations_count
                         );
+
+                        // Move the full file over to a snapshot
+                        if let Some(deltas) = &self.deltas {
+                            let entries = self.entries().await?;
+                            let mut log = deltas.lock().await;
+                            log.replace_all(entries).await?;
+                            log.compact().await?;
+                            tokio::fs::remove_file(path).await?;
+                        }
                     }
                 }
             }
@@ -327,8 +401,81 @@ impl StateManager {
         Ok(())
     }
 
+    /// Rebuild agent states, metrics and configurations from stored entries
+    async fn restore(&self, entries: &BTreeMap<String, Value>) -> RhemaResult<()> {
+        let mut agent_states = HashMap::new();
+        let mut configurations = HashMap::new();
+        let mut system_metrics = SystemMetrics::default();
+        for (key, value) in entries {
+            match key.split_once('/') {
+                Some(("agent", id)) => {
+                    agent_states.insert(id.to_string(), serde_json::from_value(value.clone())?);
+                }
+                Some(("config", name)) => {
+                    configurations.insert(name.to_string(), serde_json::from_value(value.clone())?);
+                }
+                None if key == METRICS_KEY => {
+                    system_metrics = serde_json::from_value(value.clone())?;
+                }
+                _ => warn!("Ignoring unknown state entry {}", key),
+            }
+        }
+
+        info!(
+            "Restored {} agent states and {} configurations",
+            agent_states.len(),
+            configurations.len()
+        );
+        *self.agent_states.write().await = agent_states;
+        *self.system_metrics.write().await = system_metrics;
+        *self.configuration_snapshots.write().await = configurations;
+        Ok(())
+    }
+
+    /// All state as keyed entries
+    async fn entries(&self) -> RhemaResult<BTreeMap<String, Value>> {
+        let mut entries = BTreeMap::new();
+        for (id, stored_state) in self.agent_states.read().await.iter() {
+            entries.insert(agent_key(id), serde_json::to_value(stored_state)?);
+        }
+        for (name, stored_config) in self.configuration_snapshots.read().await.iter() {
+            entries.insert(config_key(name), serde_json::to_value(stored_config)?);
+        }
+        entries.insert(
+            METRICS_KEY.to_string(),
+            serde_json::to_value(&*self.system_metrics.read().await)?,
+        );
+        Ok(entries)
+    }
+
+    /// Persist changed entries: as engine records for database backends,
+    /// appending deltas when delta encoding is on and rewriting the whole
+    /// file otherwise
+    async fn persist(&self, changes: Vec<(String, Option<Value>)>) -> RhemaResult<()> {
+        if let Some(engine) = &self.engine {
+            return apply_record_changes(engine.as_ref(), Collection::State, changes).await;
+        }
+        match &self.deltas {
+            Some(deltas) => deltas.lock().await.apply(changes).await,
+            None => self.save().await,
+        }
+    }
+
+    /// Write statistics of the delta log, when delta encoding is on
+    pub async fn delta_stats(&self) -> Option<DeltaLogStats> {
+        match &self.deltas {
+            Some(deltas) => Some(deltas.lock().await.stats().clone()),
+            None => None,
+        }
+    }
+
     /// Save data to storage
     async fn save(&self) -> RhemaResult<()> {
+        if let Some(engine) = &self.engine {
+            let entries = self.entries().await?;
+            return replace_records(engine.as_ref(), Collection::State, &entries).await;
+        }
+
         match &self.config.backend {
             StorageBackend::File => {
                 if let Some(path) = &self.file_path {
@@ -396,7 +543,13 @@ impl StateManager {
                 configs.retain(|_, stored_config| stored_config.updated_at > cutoff_date);
             }
 
-            self.save().await?;
+            match &self.deltas {
+                Some(deltas) => {
+                    let entries = self.entries().await?;
+                    deltas.lock().await.replace_all(entries).await?;
+                }
+                None => self.save().await?,
+            }
             info!("State cleanup completed");
         }
         Ok(())
@@ -478,6 +631,14 @@ impl Default for SystemMetrics {
     }
 }
 
+fn agent_key(id: &str) -> String {
+    format!("agent/{}", id)
+}
+
+fn config_key(name: &str) -> String {
+    format!("config/{}", name)
+}
+
 /// Stored state data structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct StoredStateData {
@@ -0,0 +1,153 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Health probe for the LLM provider behind [`crate::ai_service::AIService`].
+//!
+//! The probe lists the provider's models, which costs no tokens, and reads
+//! the remaining quota from the rate limit headers of the response.
+
+use async_trait::async_trait;
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+use rhema_core::dependency_health::{DependencyKind, HealthProbe, LlmProviderTarget, ProbeOutcome};
+use rhema_core::{RhemaError, RhemaResult};
+use std::time::Duration;
+
+/// Headers carrying the remaining request or token quota, by provider
+const QUOTA_HEADERS: [&str; 4] = [
+    "x-ratelimit-remaining-requests",
+    "x-ratelimit-remaining-tokens",
+    "anthropic-ratelimit-requests-remaining",
+    "anthropic-ratelimit-tokens-remaining",
+];
+
+/// Lists the models of an OpenAI-compatible provider
+pub struct LlmProviderProbe {
+    name: String,
+    client: reqwest::Client,
+    base_url: String,
+    api_key: String,
+}
+
+impl LlmProviderProbe {
+    pub fn new(
+        name: impl Into<String>,
+        base_url: &str,
+        api_key: impl Into<String>,
+        timeout: Duration,
+    ) -> RhemaResult<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| RhemaError::ConfigError(format!("Failed to create HTTP client: {}", e)))?;
+        Ok(Self {
+            name: name.into(),
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.into(),
+        })
+    }
+
+    /// Probe the provider of a `dependency_health.llm_provider` entry
+    pub fn from_target(target: &LlmProviderTarget, timeout: Duration) -> RhemaResult<Self> {
+        let api_key = std::env::var(&target.api_key_env).unwrap_or_default();
+        Self::new(&target.name, &target.base_url, api_key, timeout)
+    }
+}
+
+#[async_trait]
+impl HealthProbe for LlmProviderProbe {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn kind(&self) -> DependencyKind {
+        DependencyKind::LlmProvider
+    }
+
+    async fn probe(&self) -> ProbeOutcome {
+        if self.api_key.is_empty() {
+            return ProbeOutcome::unhealthy("No API key configured");
+        }
+
+        let response = match self
+            .client
+            .get(format!("{}/v1/models", self.base_url))
+            .bearer_auth(&self.api_key)
+            .send()
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => return ProbeOutcome::unhealthy(format!("Provider unreachable: {}", e)),
+        };
+
+        let quota = remaining_quota(response.headers());
+        let outcome = match response.status() {
+            status if status.is_success() => ProbeOutcome::healthy(),
+            StatusCode::TOO_MANY_REQUESTS => {
+                return ProbeOutcome::unhealthy("Quota exhausted").with_quota(0)
+            }
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                ProbeOutcome::unhealthy("API key rejected")
+            }
+            status => ProbeOutcome::unhealthy(format!("Provider returned {}", status)),
+        };
+        match quota {
+            Some(remaining) => outcome.with_quota(remaining),
+            None => outcome,
+        }
+    }
+}
+
+/// Lowest remaining quota advertised in the response headers
+fn remaining_quota(headers: &HeaderMap) -> Option<u64> {
+    QUOTA_HEADERS
+        .iter()
+        .filter_map(|name| headers.get(*name))
+        .filter_map(|value| value.to_str().ok()?.trim().parse::<u64>().ok())
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+    use rhema_core::dependency_health::ProbeState;
+
+    #[test]
+    fn test_remaining_quota_takes_lowest_header() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(remaining_quota(&headers), None);
+
+        headers.insert(
+            "x-ratelimit-remaining-requests",
+            HeaderValue::from_static("499"),
+        );
+        headers.insert(
+            "x-ratelimit-remaining-tokens",
+            HeaderValue::from_static("12000"),
+        );
+        headers.insert("x-ratelimit-reset-tokens", HeaderValue::from_static("6ms"));
+        assert_eq!(remaining_quota(&headers), Some(499));
+    }
+
+    #[tokio::test]
+    async fn test_missing_key_is_unhealthy() {
+        let probe =
+            LlmProviderProbe::new("llm", "http://127.0.0.1:9", "", Duration::from_secs(1)).unwrap();
+        assert_eq!(probe.probe().await.state, ProbeState::Unhealthy);
+    }
+}
@@ -0,0 +1,225 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Scopes affected by a change.
+//!
+//! Maps the files changed in the working tree, or since a base revision, to
+//! the scopes owning them and to every scope depending on those, so checks
+//! can skip scopes a change cannot have broken.
+
+use crate::scope::Scope;
+use crate::{RhemaError, RhemaResult};
+use git2::{DiffOptions, Repository};
+use serde::Serialize;
+use std::collections::{BTreeSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Files changed relative to `base`, as absolute paths.
+///
+/// Without a base the diff is taken against `HEAD` and covers staged,
+/// unstaged and untracked files, which is what a pre-commit hook needs.
+pub fn changed_files(repo_root: &Path, base: Option<&str>) -> RhemaResult<Vec<PathBuf>> {
+    let repo = Repository::discover(repo_root)?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| RhemaError::GitRepoNotFound("Repository has no working tree".to_string()))?
+        .to_path_buf();
+
+    let tree = match base {
+        Some(revision) => Some(repo.revparse_single(revision)?.peel_to_tree()?),
+        None => repo.head().ok().and_then(|head| head.peel_to_tree().ok()),
+    };
+    let mut options = DiffOptions::new();
+    options.include_untracked(true).recurse_untracked_dirs(true);
+    let diff = repo.diff_tree_to_workdir_with_index(tree.as_ref(), Some(&mut options))?;
+
+    let mut files = BTreeSet::new();
+    for delta in diff.deltas() {
+        for path in [delta.old_file().path(), delta.new_file().path()]
+            .into_iter()
+            .flatten()
+        {
+            files.insert(workdir.join(path));
+        }
+    }
+    Ok(files.into_iter().collect())
+}
+
+/// Scopes a set of changed files can affect
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AffectedScopes {
+    /// Scopes owning at least one changed file
+    pub changed: Vec<String>,
+
+    /// Scopes depending on a changed scope, directly or transitively
+    pub dependents: Vec<String>,
+
+    /// Number of changed files
+    pub changed_files: usize,
+
+    /// Changed files outside every scope
+    pub unscoped_files: usize,
+
+    /// Number of scopes in the repository
+    pub total_scopes: usize,
+}
+
+impl AffectedScopes {
+    /// Attribute each changed file to its deepest scope and follow the scope
+    /// graph to the scopes depending on it. Dependencies are scope paths
+    /// relative to the repository root, as in `validate_scope_relationships`.
+    pub fn compute(scopes: &[Scope], repo_root: &Path, changed_files: &[PathBuf]) -> Self {
+        let dirs: Vec<PathBuf> = scopes
+            .iter()
+            .map(|scope| canonical(scope.path.parent().unwrap_or(&scope.path)))
+            .collect();
+
+        let mut owners = BTreeSet::new();
+        let mut unscoped_files = 0;
+        for file in changed_files {
+            let file = canonical(file);
+            let owner = dirs
+                .iter()
+                .enumerate()
+                .filter(|(_, dir)| file.starts_with(dir))
+                .max_by_key(|(_, dir)| dir.components().count())
+                .map(|(index, _)| index);
+            match owner {
+                Some(index) => {
+                    owners.insert(index);
+                }
+                None => unscoped_files += 1,
+            }
+        }
+
+        // dependents[i] lists the scopes declaring a dependency on scope i
+        let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); scopes.len()];
+        for (index, scope) in scopes.iter().enumerate() {
+            for dependency in scope.get_dependency_paths() {
+                let target = canonical(&repo_root.join(dependency.trim_end_matches("/.rhema")));
+                if let Some(dep) = dirs.iter().position(|dir| *dir == target) {
+                    dependents[dep].push(index);
+                }
+            }
+        }
+
+        let mut reached = owners.clone();
+        let mut queue: VecDeque<usize> = owners.iter().copied().collect();
+        while let Some(index) = queue.pop_front() {
+            for &dependent in &dependents[index] {
+                if reached.insert(dependent) {
+                    queue.push_back(dependent);
+                }
+            }
+        }
+
+        let name = |index: &usize| scopes[*index].definition.name.clone();
+        Self {
+            changed: owners.iter().map(name).collect(),
+            dependents: reached.difference(&owners).map(name).collect(),
+            changed_files: changed_files.len(),
+            unscoped_files,
+            total_scopes: scopes.len(),
+        }
+    }
+
+    pub fn contains(&self, scope: &str) -> bool {
+        self.changed
+            .iter()
+            .chain(&self.dependents)
+            .any(|name| name == scope)
+    }
+
+    /// Number of affected scopes
+    pub fn count(&self) -> usize {
+        self.changed.len() + self.dependents.len()
+    }
+
+    /// Time a full run would have spent on the skipped scopes, extrapolated
+    /// from `elapsed` spent on the affected ones
+    pub fn estimated_time_saved(&self, elapsed: Duration) -> Duration {
+        let affected = self.count();
+        if affected == 0 {
+            return Duration::ZERO;
+        }
+        let skipped = self.total_scopes.saturating_sub(affected);
+        elapsed.mul_f64(skipped as f64 / affected as f64)
+    }
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scope::discover_scopes;
+    use tempfile::TempDir;
+
+    fn write_scope(root: &Path, dir: &str, name: &str, dependency: Option<&str>) {
+        let rhema = root.join(dir).join(".rhema");
+        std::fs::create_dir_all(&rhema).unwrap();
+        let mut yaml = format!("name: {}\nscope_type: library\nversion: 1.0.0\n", name);
+        if let Some(dependency) = dependency {
+            yaml.push_str(&format!(
+                "dependencies:\n  - path: {}\n    dependency_type: required\n",
+                dependency
+            ));
+        }
+        std::fs::write(rhema.join("rhema.yaml"), yaml).unwrap();
+        std::fs::write(root.join(dir).join("lib.rs"), "").unwrap();
+    }
+
+    #[test]
+    fn test_changed_scope_and_dependents_are_affected() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write_scope(root, "crates/core", "core", None);
+        write_scope(root, "crates/api", "api", Some("crates/core"));
+        write_scope(root, "apps/web", "web", Some("crates/api"));
+        write_scope(root, "docs", "docs", None);
+
+        let repo = Repository::init(root).unwrap();
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("test", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])
+            .unwrap();
+
+        std::fs::write(root.join("crates/core/lib.rs"), "pub fn changed() {}").unwrap();
+        std::fs::write(root.join("NOTES.md"), "untracked").unwrap();
+
+        let files = changed_files(root, None).unwrap();
+        assert_eq!(files.len(), 2);
+
+        let scopes = discover_scopes(root).unwrap();
+        let affected = AffectedScopes::compute(&scopes, root, &files);
+        assert_eq!(affected.changed, vec!["core".to_string()]);
+        let mut dependents = affected.dependents.clone();
+        dependents.sort();
+        assert_eq!(dependents, vec!["api".to_string(), "web".to_string()]);
+        assert!(!affected.contains("docs"));
+        assert_eq!(affected.unscoped_files, 1);
+        let saved = affected.estimated_time_saved(Duration::from_millis(300));
+        assert!((saved.as_secs_f64() - 0.1).abs() < 1e-6);
+    }
+}
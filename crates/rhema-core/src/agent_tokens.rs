@@ -0,0 +1,570 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Scoped API tokens for agents.
+//!
+//! A token names an agent, the scopes it may read or write and an expiry,
+//! and is signed with a key kept in `.rhema/auth/`. The MCP and gRPC layers
+//! verify tokens against the same directory, which also holds the registry
+//! of issued tokens, the revocation list and an audit log of attempts to use
+//! expired or revoked tokens. Nothing in `.rhema/auth/` is committed.
+
+use crate::file_ops::{read_yaml_file, write_yaml_file};
+use crate::{RhemaError, RhemaResult};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine as _;
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Directory under `.rhema` holding keys, registries and the audit log
+pub const AUTH_DIR: &str = "auth";
+
+/// Prefix distinguishing agent tokens from API keys and JWTs
+pub const AGENT_TOKEN_PREFIX: &str = "rhema_at_";
+
+const SIGNING_KEY_FILE: &str = "signing.key";
+const ISSUED_FILE: &str = "tokens.yaml";
+const REVOKED_FILE: &str = "revoked.yaml";
+const AUDIT_FILE: &str = "audit.jsonl";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Whether a token looks like an agent token, before verifying it
+pub fn is_agent_token(token: &str) -> bool {
+    token.starts_with(AGENT_TOKEN_PREFIX)
+}
+
+/// Access level granted on a scope; write implies read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenAccess {
+    Read,
+    Write,
+}
+
+/// Access to one scope, written as `read:<scope>` or `write:<scope>`.
+/// The scope `*` stands for every scope.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TokenScope {
+    pub access: TokenAccess,
+    pub scope: String,
+}
+
+impl TokenScope {
+    /// Parse a comma-separated list such as `read:api,write:docs`
+    pub fn parse_list(list: &str) -> RhemaResult<Vec<Self>> {
+        list.split(',')
+            .map(str::trim)
+            .filter(|grant| !grant.is_empty())
+            .map(str::parse)
+            .collect()
+    }
+
+    pub fn allows(&self, access: TokenAccess, scope: &str) -> bool {
+        (self.scope == "*" || self.scope == scope)
+            && (self.access == TokenAccess::Write || access == TokenAccess::Read)
+    }
+}
+
+impl FromStr for TokenScope {
+    type Err = RhemaError;
+
+    fn from_str(grant: &str) -> RhemaResult<Self> {
+        let (access, scope) = grant.split_once(':').ok_or_else(|| {
+            RhemaError::InvalidInput(format!(
+                "Expected read:<scope> or write:<scope>, got '{}'",
+                grant
+            ))
+        })?;
+        let access = match access {
+            "read" => TokenAccess::Read,
+            "write" => TokenAccess::Write,
+            other => {
+                return Err(RhemaError::InvalidInput(format!(
+                    "Unknown access '{}' in '{}', expected read or write",
+                    other, grant
+                )))
+            }
+        };
+        if scope.is_empty() {
+            return Err(RhemaError::InvalidInput(format!(
+                "Missing scope in '{}'",
+                grant
+            )));
+        }
+        Ok(Self {
+            access,
+            scope: scope.to_string(),
+        })
+    }
+}
+
+impl TryFrom<String> for TokenScope {
+    type Error = RhemaError;
+
+    fn try_from(grant: String) -> RhemaResult<Self> {
+        grant.parse()
+    }
+}
+
+impl From<TokenScope> for String {
+    fn from(grant: TokenScope) -> Self {
+        grant.to_string()
+    }
+}
+
+impl fmt::Display for TokenScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let access = match self.access {
+            TokenAccess::Read => "read",
+            TokenAccess::Write => "write",
+        };
+        write!(f, "{}:{}", access, self.scope)
+    }
+}
+
+/// Signed content of an agent token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentTokenClaims {
+    pub id: String,
+    pub agent: String,
+    pub scopes: Vec<TokenScope>,
+    pub issued_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl AgentTokenClaims {
+    pub fn allows(&self, access: TokenAccess, scope: &str) -> bool {
+        self.scopes.iter().any(|grant| grant.allows(access, scope))
+    }
+
+    pub fn is_expired(&self) -> bool {
+        Utc::now() >= self.expires_at
+    }
+}
+
+/// A freshly issued token; the token string is not stored anywhere
+#[derive(Debug, Clone, Serialize)]
+pub struct IssuedToken {
+    pub token: String,
+    pub claims: AgentTokenClaims,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenStatus {
+    Active,
+    Expired,
+    Revoked,
+}
+
+impl fmt::Display for TokenStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenStatus::Active => write!(f, "active"),
+            TokenStatus::Expired => write!(f, "expired"),
+            TokenStatus::Revoked => write!(f, "revoked"),
+        }
+    }
+}
+
+/// An issued token with its current status
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenListing {
+    #[serde(flatten)]
+    pub claims: AgentTokenClaims,
+    pub status: TokenStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Revocation {
+    pub id: String,
+    pub agent: String,
+    pub revoked_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct IssuedTokens {
+    #[serde(default)]
+    tokens: Vec<AgentTokenClaims>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct RevocationList {
+    #[serde(default)]
+    revoked: Vec<Revocation>,
+}
+
+/// An attempt to use a token that is no longer valid
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenAuditEntry {
+    pub timestamp: DateTime<Utc>,
+    pub token_id: String,
+    pub agent: String,
+    pub status: TokenStatus,
+    /// Layer that received the token, such as `mcp` or `grpc`
+    pub surface: String,
+}
+
+/// Issues, verifies and revokes the agent tokens of a repository
+#[derive(Debug, Clone)]
+pub struct AgentTokenStore {
+    dir: PathBuf,
+}
+
+impl AgentTokenStore {
+    pub fn new(repo_root: &Path) -> Self {
+        Self {
+            dir: repo_root.join(".rhema").join(AUTH_DIR),
+        }
+    }
+
+    /// Issue a token for `agent` valid for `ttl`
+    pub fn issue(
+        &self,
+        agent: &str,
+        scopes: Vec<TokenScope>,
+        ttl: Duration,
+    ) -> RhemaResult<IssuedToken> {
+        if agent.trim().is_empty() {
+            return Err(RhemaError::InvalidInput(
+                "Agent id must not be empty".to_string(),
+            ));
+        }
+        if scopes.is_empty() {
+            return Err(RhemaError::InvalidInput(
+                "A token needs at least one scope grant".to_string(),
+            ));
+        }
+
+        let issued_at = Utc::now();
+        let claims = AgentTokenClaims {
+            id: uuid::Uuid::new_v4().to_string(),
+            agent: agent.trim().to_string(),
+            scopes,
+            issued_at,
+            expires_at: issued_at + ttl,
+        };
+        let payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&claims)?);
+        let signature = URL_SAFE_NO_PAD.encode(self.mac(&self.signing_key()?, &payload)?);
+
+        let mut issued: IssuedTokens = self.read(ISSUED_FILE)?;
+        issued.tokens.push(claims.clone());
+        self.write(ISSUED_FILE, &issued)?;
+
+        Ok(IssuedToken {
+            token: format!("{}{}.{}", AGENT_TOKEN_PREFIX, payload, signature),
+            claims,
+        })
+    }
+
+    /// Check the signature, revocation and expiry of a token presented to
+    /// `surface`. Uses of revoked or expired tokens are written to the audit log.
+    pub fn verify(&self, token: &str, surface: &str) -> RhemaResult<AgentTokenClaims> {
+        let invalid = || RhemaError::AuthenticationError("Invalid agent token".to_string());
+        let (payload, signature) = token
+            .strip_prefix(AGENT_TOKEN_PREFIX)
+            .and_then(|rest| rest.split_once('.'))
+            .ok_or_else(invalid)?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).map_err(|_| invalid())?;
+
+        let key_path = self.dir.join(SIGNING_KEY_FILE);
+        if !key_path.exists() {
+            return Err(invalid());
+        }
+        let mut mac = HmacSha256::new_from_slice(&self.load_key(&key_path)?)
+            .map_err(|e| RhemaError::SecurityError(e.to_string()))?;
+        mac.update(payload.as_bytes());
+        mac.verify_slice(&signature).map_err(|_| invalid())?;
+
+        let claims: AgentTokenClaims =
+            serde_json::from_slice(&URL_SAFE_NO_PAD.decode(payload).map_err(|_| invalid())?)?;
+
+        let revoked: RevocationList = self.read(REVOKED_FILE)?;
+        let status = if revoked.revoked.iter().any(|entry| entry.id == claims.id) {
+            TokenStatus::Revoked
+        } else if claims.is_expired() {
+            TokenStatus::Expired
+        } else {
+            return Ok(claims);
+        };
+
+        self.audit(&TokenAuditEntry {
+            timestamp: Utc::now(),
+            token_id: claims.id.clone(),
+            agent: claims.agent.clone(),
+            status,
+            surface: surface.to_string(),
+        })?;
+        Err(RhemaError::AuthenticationError(format!(
+            "Agent token {} of {} is {}",
+            claims.id, claims.agent, status
+        )))
+    }
+
+    /// Revoke one token by id
+    pub fn revoke(&self, id: &str, reason: Option<String>) -> RhemaResult<Revocation> {
+        let issued: IssuedTokens = self.read(ISSUED_FILE)?;
+        let claims = issued
+            .tokens
+            .iter()
+            .find(|claims| claims.id == id)
+            .ok_or_else(|| RhemaError::NotFound(format!("No agent token with id {}", id)))?;
+        Ok(self.revoke_all(&[claims], reason)?.remove(0))
+    }
+
+    /// Revoke every unrevoked token of an agent
+    pub fn revoke_agent(
+        &self,
+        agent: &str,
+        reason: Option<String>,
+    ) -> RhemaResult<Vec<Revocation>> {
+        let issued: IssuedTokens = self.read(ISSUED_FILE)?;
+        let tokens: Vec<&AgentTokenClaims> = issued
+            .tokens
+            .iter()
+            .filter(|claims| claims.agent == agent)
+            .collect();
+        if tokens.is_empty() {
+            return Err(RhemaError::NotFound(format!(
+                "No agent tokens issued to {}",
+                agent
+            )));
+        }
+        self.revoke_all(&tokens, reason)
+    }
+
+    /// Issued tokens, oldest first
+    pub fn list(&self) -> RhemaResult<Vec<TokenListing>> {
+        let issued: IssuedTokens = self.read(ISSUED_FILE)?;
+        let revoked: RevocationList = self.read(REVOKED_FILE)?;
+        Ok(issued
+            .tokens
+            .into_iter()
+            .map(|claims| {
+                let status = if revoked.revoked.iter().any(|entry| entry.id == claims.id) {
+                    TokenStatus::Revoked
+                } else if claims.is_expired() {
+                    TokenStatus::Expired
+                } else {
+                    TokenStatus::Active
+                };
+                TokenListing { claims, status }
+            })
+            .collect())
+    }
+
+    /// Recorded uses of expired and revoked tokens, oldest first
+    pub fn audit_log(&self) -> RhemaResult<Vec<TokenAuditEntry>> {
+        let path = self.dir.join(AUDIT_FILE);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        std::fs::read_to_string(path)?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| Ok(serde_json::from_str(line)?))
+            .collect()
+    }
+
+    fn revoke_all(
+        &self,
+        tokens: &[&AgentTokenClaims],
+        reason: Option<String>,
+    ) -> RhemaResult<Vec<Revocation>> {
+        let mut list: RevocationList = self.read(REVOKED_FILE)?;
+        let mut revoked = Vec::new();
+        for claims in tokens {
+            if let Some(existing) = list.revoked.iter().find(|entry| entry.id == claims.id) {
+                revoked.push(existing.clone());
+                continue;
+            }
+            let revocation = Revocation {
+                id: claims.id.clone(),
+                agent: claims.agent.clone(),
+                revoked_at: Utc::now(),
+                reason: reason.clone(),
+            };
+            list.revoked.push(revocation.clone());
+            revoked.push(revocation);
+        }
+        self.write(REVOKED_FILE, &list)?;
+        Ok(revoked)
+    }
+
+    fn mac(&self, key: &[u8], payload: &str) -> RhemaResult<Vec<u8>> {
+        let mut mac = HmacSha256::new_from_slice(key)
+            .map_err(|e| RhemaError::SecurityError(e.to_string()))?;
+        mac.update(payload.as_bytes());
+        Ok(mac.finalize().into_bytes().to_vec())
+    }
+
+    /// The signing key, generated on first use
+    fn signing_key(&self) -> RhemaResult<Vec<u8>> {
+        let path = self.dir.join(SIGNING_KEY_FILE);
+        if path.exists() {
+            return self.load_key(&path);
+        }
+
+        self.ensure_dir()?;
+        let mut key = vec![0u8; 32];
+        rand::thread_rng().fill_bytes(&mut key);
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        options
+            .open(&path)?
+            .write_all(URL_SAFE_NO_PAD.encode(&key).as_bytes())?;
+        Ok(key)
+    }
+
+    fn load_key(&self, path: &Path) -> RhemaResult<Vec<u8>> {
+        URL_SAFE_NO_PAD
+            .decode(std::fs::read_to_string(path)?.trim())
+            .map_err(|e| {
+                RhemaError::SecurityError(format!("Corrupt agent token signing key: {}", e))
+            })
+    }
+
+    fn audit(&self, entry: &TokenAuditEntry) -> RhemaResult<()> {
+        self.ensure_dir()?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.dir.join(AUDIT_FILE))?;
+        writeln!(file, "{}", serde_json::to_string(entry)?)?;
+        Ok(())
+    }
+
+    fn read<T: Default + serde::de::DeserializeOwned>(&self, file: &str) -> RhemaResult<T> {
+        let path = self.dir.join(file);
+        if path.exists() {
+            read_yaml_file(&path)
+        } else {
+            Ok(T::default())
+        }
+    }
+
+    fn write<T: Serialize>(&self, file: &str, value: &T) -> RhemaResult<()> {
+        self.ensure_dir()?;
+        write_yaml_file(&self.dir.join(file), value)
+    }
+
+    /// Create the directory, keeping its contents out of git
+    fn ensure_dir(&self) -> RhemaResult<()> {
+        if !self.dir.exists() {
+            std::fs::create_dir_all(&self.dir)?;
+            std::fs::write(self.dir.join(".gitignore"), "*\n")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse a lifetime such as `90m`, `24h`, `7d` or `2w`
+pub fn parse_ttl(ttl: &str) -> RhemaResult<Duration> {
+    let ttl = ttl.trim();
+    let invalid = || {
+        RhemaError::InvalidInput(format!(
+            "Invalid TTL '{}', expected a number followed by s, m, h, d or w",
+            ttl
+        ))
+    };
+    let split = ttl.len().checked_sub(1).ok_or_else(invalid)?;
+    let (amount, unit) = ttl.split_at(split);
+    let amount: i64 = amount.parse().map_err(|_| invalid())?;
+    if amount <= 0 {
+        return Err(invalid());
+    }
+    match unit {
+        "s" => Ok(Duration::seconds(amount)),
+        "m" => Ok(Duration::minutes(amount)),
+        "h" => Ok(Duration::hours(amount)),
+        "d" => Ok(Duration::days(amount)),
+        "w" => Ok(Duration::weeks(amount)),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_issue_and_verify_scoped_token() {
+        let temp = TempDir::new().unwrap();
+        let store = AgentTokenStore::new(temp.path());
+        let scopes = TokenScope::parse_list("read:api, write:docs").unwrap();
+        let issued = store
+            .issue("planner", scopes, parse_ttl("24h").unwrap())
+            .unwrap();
+
+        let claims = store.verify(&issued.token, "mcp").unwrap();
+        assert_eq!(claims, issued.claims);
+        assert!(claims.allows(TokenAccess::Read, "api"));
+        assert!(!claims.allows(TokenAccess::Write, "api"));
+        assert!(claims.allows(TokenAccess::Read, "docs"));
+        assert!(!claims.allows(TokenAccess::Read, "billing"));
+
+        let mut tampered = issued.token.clone();
+        tampered.insert(AGENT_TOKEN_PREFIX.len(), 'x');
+        assert!(store.verify(&tampered, "mcp").is_err());
+        assert!(TokenScope::parse_list("admin:api").is_err());
+        assert!(parse_ttl("0h").is_err());
+    }
+
+    #[test]
+    fn test_revoked_and_expired_tokens_are_audited() {
+        let temp = TempDir::new().unwrap();
+        let store = AgentTokenStore::new(temp.path());
+        let scopes = vec!["read:*".parse().unwrap()];
+        let expired = store
+            .issue("indexer", scopes.clone(), Duration::seconds(-1))
+            .unwrap();
+        let revoked = store.issue("indexer", scopes, Duration::hours(1)).unwrap();
+        store
+            .revoke(&revoked.claims.id, Some("rotated".into()))
+            .unwrap();
+
+        assert!(store.verify(&expired.token, "grpc").is_err());
+        assert!(store.verify(&revoked.token, "mcp").is_err());
+
+        let audit = store.audit_log().unwrap();
+        assert_eq!(audit.len(), 2);
+        assert_eq!(audit[0].status, TokenStatus::Expired);
+        assert_eq!(audit[0].surface, "grpc");
+        assert_eq!(audit[1].status, TokenStatus::Revoked);
+
+        let statuses: Vec<TokenStatus> = store.list().unwrap().iter().map(|t| t.status).collect();
+        assert_eq!(statuses, vec![TokenStatus::Expired, TokenStatus::Revoked]);
+        assert!(temp.path().join(".rhema/auth/.gitignore").exists());
+    }
+}
@@ -0,0 +1,373 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Per-scope policy for AI agents, declared as `ai_policy` in `rhema.yaml`.
+//!
+//! Policies inherit from enclosing scopes: a child may override the model
+//! tier and context profile, adds to the forbidden operations, and may only
+//! lower the autonomous safety ceiling unless it sets `inherit: false`.
+
+use crate::schema::RhemaScope;
+use crate::{RhemaError, RhemaResult};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Context injection profiles understood by the context injector
+pub const CONTEXT_PROFILES: &[&str] = &["minimal", "standard", "full"];
+
+/// Preferred model capability tier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelTier {
+    Economy,
+    Standard,
+    Premium,
+}
+
+/// Risk level up to which an agent may act without human approval
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutonomyLevel {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl fmt::Display for AutonomyLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            AutonomyLevel::Low => "low",
+            AutonomyLevel::Medium => "medium",
+            AutonomyLevel::High => "high",
+            AutonomyLevel::Critical => "critical",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// `ai_policy` block of a scope definition
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AiPolicy {
+    /// Preferred model tier for agents working in this scope
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_tier: Option<ModelTier>,
+
+    /// Highest safety level an agent may act on without approval
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_autonomous_safety: Option<AutonomyLevel>,
+
+    /// Operations agents must never perform (action types, task types, or
+    /// tool names; a trailing `*` matches by prefix)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub forbidden_operations: Vec<String>,
+
+    /// Context injection profile (see [`CONTEXT_PROFILES`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context_profile: Option<String>,
+
+    /// Whether to inherit the policy of enclosing scopes
+    #[serde(default = "default_inherit")]
+    pub inherit: bool,
+}
+
+fn default_inherit() -> bool {
+    true
+}
+
+impl AiPolicy {
+    pub fn validate(&self) -> RhemaResult<()> {
+        for operation in &self.forbidden_operations {
+            if operation.trim().is_empty() || operation.trim() == "*" {
+                return Err(RhemaError::ValidationError(format!(
+                    "Invalid forbidden operation '{}' in ai_policy",
+                    operation
+                )));
+            }
+        }
+
+        if let Some(profile) = &self.context_profile {
+            if !CONTEXT_PROFILES.contains(&profile.as_str()) {
+                return Err(RhemaError::ValidationError(format!(
+                    "Unknown ai_policy context_profile '{}', expected one of: {}",
+                    profile,
+                    CONTEXT_PROFILES.join(", ")
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Policy in force for a scope after applying inheritance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EffectiveAiPolicy {
+    pub model_tier: ModelTier,
+    pub max_autonomous_safety: AutonomyLevel,
+    pub forbidden_operations: BTreeSet<String>,
+    pub context_profile: String,
+    /// Scope directories that contributed, outermost first
+    pub sources: Vec<PathBuf>,
+}
+
+/// Without a scope policy agents are not restricted; a ceiling only applies
+/// once a scope declares `max_autonomous_safety`
+impl Default for EffectiveAiPolicy {
+    fn default() -> Self {
+        Self {
+            model_tier: ModelTier::Standard,
+            max_autonomous_safety: AutonomyLevel::Critical,
+            forbidden_operations: BTreeSet::new(),
+            context_profile: "standard".to_string(),
+            sources: Vec::new(),
+        }
+    }
+}
+
+impl EffectiveAiPolicy {
+    /// Layer a child scope's policy on top of this one
+    pub fn apply(&mut self, policy: &AiPolicy, source: &Path) {
+        if !policy.inherit {
+            *self = Self::default();
+        }
+
+        if let Some(tier) = policy.model_tier {
+            self.model_tier = tier;
+        }
+        if let Some(ceiling) = policy.max_autonomous_safety {
+            // Inherited ceilings can only be tightened
+            self.max_autonomous_safety = ceiling.min(self.max_autonomous_safety);
+        }
+        self.forbidden_operations.extend(
+            policy
+                .forbidden_operations
+                .iter()
+                .map(|op| op.trim().to_lowercase()),
+        );
+        if let Some(profile) = &policy.context_profile {
+            self.context_profile = profile.clone();
+        }
+        self.sources.push(source.to_path_buf());
+    }
+
+    /// Whether an agent may act at `level` without human approval
+    pub fn allows_autonomous(&self, level: AutonomyLevel) -> bool {
+        level <= self.max_autonomous_safety
+    }
+
+    /// Whether `operation` is forbidden in this scope
+    pub fn forbids(&self, operation: &str) -> bool {
+        let operation = operation.to_lowercase();
+        self.forbidden_operations
+            .iter()
+            .any(|rule| match rule.strip_suffix('*') {
+                Some(prefix) => operation.starts_with(prefix),
+                None => *rule == operation,
+            })
+    }
+
+    /// Error for a forbidden operation, if `operation` is forbidden
+    pub fn check_operation(&self, operation: &str) -> RhemaResult<()> {
+        if self.forbids(operation) {
+            return Err(RhemaError::AuthorizationError(format!(
+                "Operation '{}' is forbidden by the scope AI policy",
+                operation
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Resolve the effective policy for a file or directory by walking up to the
+/// repository root and layering every enclosing scope's `ai_policy`.
+pub fn resolve_ai_policy(path: &Path) -> RhemaResult<EffectiveAiPolicy> {
+    let mut start = if path.is_file() {
+        path.parent().unwrap_or(path)
+    } else {
+        path
+    };
+    if start.file_name().is_some_and(|n| n == ".rhema") {
+        start = start.parent().unwrap_or(start);
+    }
+
+    let mut chain = Vec::new();
+    for dir in start.ancestors() {
+        let scope_dir = dir.join(".rhema");
+        if let Some(policy) = load_scope_policy(&scope_dir)? {
+            chain.push((scope_dir, policy));
+        }
+        if dir.join(".git").exists() {
+            break;
+        }
+    }
+
+    let mut effective = EffectiveAiPolicy::default();
+    for (scope_dir, policy) in chain.iter().rev() {
+        effective.apply(policy, scope_dir);
+    }
+    Ok(effective)
+}
+
+/// Check that child policies do not try to raise an inherited safety ceiling
+pub fn validate_policy_inheritance(path: &Path) -> RhemaResult<()> {
+    let scope_dir = path.join(".rhema");
+    let Some(policy) = load_scope_policy(&scope_dir)? else {
+        return Ok(());
+    };
+    policy.validate()?;
+
+    let (Some(ceiling), true) = (policy.max_autonomous_safety, policy.inherit) else {
+        return Ok(());
+    };
+    let Some(parent) = path.parent() else {
+        return Ok(());
+    };
+    if path.join(".git").exists() {
+        return Ok(());
+    }
+
+    let inherited = resolve_ai_policy(parent)?;
+    if !inherited.sources.is_empty() && ceiling > inherited.max_autonomous_safety {
+        return Err(RhemaError::ValidationError(format!(
+            "ai_policy in {} raises max_autonomous_safety to {} above the inherited {}; set inherit: false to override",
+            scope_dir.display(),
+            ceiling,
+            inherited.max_autonomous_safety
+        )));
+    }
+    Ok(())
+}
+
+fn load_scope_policy(scope_dir: &Path) -> RhemaResult<Option<AiPolicy>> {
+    let file = scope_dir.join("rhema.yaml");
+    if !file.is_file() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&file)?;
+    let scope: RhemaScope =
+        serde_yaml::from_str(&content).map_err(|e| RhemaError::InvalidYaml {
+            file: file.display().to_string(),
+            message: e.to_string(),
+        })?;
+    Ok(scope.ai_policy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write_scope(dir: &Path, name: &str, policy: &str) {
+        let scope = dir.join(".rhema");
+        fs::create_dir_all(&scope).unwrap();
+        fs::write(
+            scope.join("rhema.yaml"),
+            format!(
+                "name: {}\nscope_type: service\nversion: \"1.0.0\"\n{}",
+                name, policy
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_policy_inheritance() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        write_scope(
+            root,
+            "root",
+            "ai_policy:\n  model_tier: premium\n  max_autonomous_safety: medium\n  forbidden_operations: [dependency]\n",
+        );
+        let child = root.join("services/api");
+        write_scope(
+            &child,
+            "api",
+            "ai_policy:\n  max_autonomous_safety: high\n  forbidden_operations: [\"custom:deploy*\"]\n  context_profile: minimal\n",
+        );
+
+        let policy = resolve_ai_policy(&child.join("src")).unwrap();
+        assert_eq!(policy.model_tier, ModelTier::Premium);
+        assert_eq!(policy.max_autonomous_safety, AutonomyLevel::Medium);
+        assert_eq!(policy.context_profile, "minimal");
+        assert!(policy.forbids("Dependency"));
+        assert!(policy.forbids("custom:deploy-prod"));
+        assert!(!policy.forbids("refactor"));
+        assert_eq!(policy.sources.len(), 2);
+
+        assert!(validate_policy_inheritance(&child).is_err());
+        assert!(validate_policy_inheritance(root).is_ok());
+    }
+
+    #[test]
+    fn test_policy_without_inheritance() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        write_scope(
+            root,
+            "root",
+            "ai_policy:\n  forbidden_operations: [dependency]\n",
+        );
+        let child = root.join("sandbox");
+        write_scope(
+            &child,
+            "sandbox",
+            "ai_policy:\n  inherit: false\n  max_autonomous_safety: critical\n",
+        );
+
+        let policy = resolve_ai_policy(&child).unwrap();
+        assert_eq!(policy.max_autonomous_safety, AutonomyLevel::Critical);
+        assert!(!policy.forbids("dependency"));
+        assert!(validate_policy_inheritance(&child).is_ok());
+    }
+
+    #[test]
+    fn test_no_ceiling_without_declared_policy() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        write_scope(root, "root", "");
+        let child = root.join("services/api");
+        write_scope(
+            &child,
+            "api",
+            "ai_policy:\n  forbidden_operations: [dependency]\n",
+        );
+
+        assert!(resolve_ai_policy(root)
+            .unwrap()
+            .allows_autonomous(AutonomyLevel::Critical));
+        let policy = resolve_ai_policy(&child).unwrap();
+        assert!(policy.allows_autonomous(AutonomyLevel::Critical));
+        assert!(policy.forbids("dependency"));
+    }
+
+    #[test]
+    fn test_unknown_context_profile_is_rejected() {
+        let policy = AiPolicy {
+            context_profile: Some("everything".to_string()),
+            inherit: true,
+            ..Default::default()
+        };
+        assert!(policy.validate().is_err());
+    }
+}
@@ -0,0 +1,386 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Consolidated cache statistics.
+//!
+//! The API query and scope caches, the MCP daemon cache and the tiers of the
+//! unified knowledge cache each describe themselves as a
+//! [`CacheTierReport`]: hits and misses, evictions broken down by
+//! [`EvictionCause`], memory held against the tier's limits and the keys
+//! serving the most hits. A [`CacheReport`] collects the tiers together with
+//! the [`TuningRecommendation`]s derived from them.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Keys listed per tier in a report
+pub const HOTTEST_KEYS: usize = 10;
+
+/// Distinct keys a [`CacheActivity`] counts hits for
+pub const MAX_TRACKED_KEYS: usize = 4096;
+
+/// Why an entry left a cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EvictionCause {
+    /// Pushed out to make room for new entries
+    Capacity,
+    /// Outlived its TTL
+    Expired,
+    /// Deleted, cleared or rejected on validation
+    Invalidated,
+}
+
+impl fmt::Display for EvictionCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvictionCause::Capacity => write!(f, "capacity"),
+            EvictionCause::Expired => write!(f, "expired"),
+            EvictionCause::Invalidated => write!(f, "invalidated"),
+        }
+    }
+}
+
+/// Evictions per cause
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EvictionCounts {
+    pub capacity: u64,
+    pub expired: u64,
+    pub invalidated: u64,
+}
+
+impl EvictionCounts {
+    pub fn record(&mut self, cause: EvictionCause, count: u64) {
+        match cause {
+            EvictionCause::Capacity => self.capacity += count,
+            EvictionCause::Expired => self.expired += count,
+            EvictionCause::Invalidated => self.invalidated += count,
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.capacity + self.expired + self.invalidated
+    }
+}
+
+/// Hits served by one key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyHits {
+    pub key: String,
+    pub hits: u64,
+}
+
+/// The `limit` keys with the most hits, ties broken by key
+pub fn hottest_keys<K: AsRef<str>>(
+    counts: impl IntoIterator<Item = (K, u64)>,
+    limit: usize,
+) -> Vec<KeyHits> {
+    let mut keys: Vec<KeyHits> = counts
+        .into_iter()
+        .filter(|(_, hits)| *hits > 0)
+        .map(|(key, hits)| KeyHits {
+            key: key.as_ref().to_string(),
+            hits,
+        })
+        .collect();
+    keys.sort_by(|a, b| b.hits.cmp(&a.hits).then_with(|| a.key.cmp(&b.key)));
+    keys.truncate(limit);
+    keys
+}
+
+/// Statistics of one cache tier
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheTierReport {
+    /// Component owning the cache: `api`, `mcp` or `knowledge`
+    pub source: String,
+    /// Tier within the component, e.g. `query` or `memory`
+    pub tier: String,
+    pub entries: usize,
+    pub max_entries: Option<usize>,
+    pub memory_bytes: u64,
+    pub max_memory_bytes: Option<u64>,
+    /// Lifetime of new entries, when the tier has one
+    pub ttl_secs: Option<u64>,
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: EvictionCounts,
+    pub hottest_keys: Vec<KeyHits>,
+}
+
+impl CacheTierReport {
+    pub fn new(source: impl Into<String>, tier: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            tier: tier.into(),
+            ..Self::default()
+        }
+    }
+
+    /// `source.tier`, as shown in reports and recommendations
+    pub fn name(&self) -> String {
+        format!("{}.{}", self.source, self.tier)
+    }
+
+    pub fn requests(&self) -> u64 {
+        self.hits + self.misses
+    }
+
+    pub fn hit_ratio(&self) -> f64 {
+        match self.requests() {
+            0 => 0.0,
+            requests => self.hits as f64 / requests as f64,
+        }
+    }
+
+    /// Fill of the tier against its tightest limit
+    pub fn utilization(&self) -> Option<f64> {
+        let by_entries = self
+            .max_entries
+            .filter(|max| *max > 0)
+            .map(|max| self.entries as f64 / max as f64);
+        let by_bytes = self
+            .max_memory_bytes
+            .filter(|max| *max > 0)
+            .map(|max| self.memory_bytes as f64 / max as f64);
+        match (by_entries, by_bytes) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// Share of the tier's hits served by its hottest keys
+    pub fn hot_key_share(&self) -> f64 {
+        if self.hits == 0 {
+            return 0.0;
+        }
+        let hot: u64 = self.hottest_keys.iter().map(|key| key.hits).sum();
+        (hot as f64 / self.hits as f64).min(1.0)
+    }
+}
+
+/// Hit, miss and eviction counters kept next to a cache that has none of
+/// its own
+#[derive(Debug, Clone, Default)]
+pub struct CacheActivity {
+    hits: u64,
+    misses: u64,
+    evictions: EvictionCounts,
+    key_hits: HashMap<String, u64>,
+}
+
+impl CacheActivity {
+    pub fn hit(&mut self, key: &str) {
+        self.hits += 1;
+        if let Some(hits) = self.key_hits.get_mut(key) {
+            *hits += 1;
+        } else if self.key_hits.len() < MAX_TRACKED_KEYS {
+            self.key_hits.insert(key.to_string(), 1);
+        }
+    }
+
+    pub fn miss(&mut self) {
+        self.misses += 1;
+    }
+
+    pub fn evict(&mut self, cause: EvictionCause, count: u64) {
+        self.evictions.record(cause, count);
+    }
+
+    /// Copy the counters into a tier report
+    pub fn fill(&self, report: &mut CacheTierReport) {
+        report.hits = self.hits;
+        report.misses = self.misses;
+        report.evictions = self.evictions;
+        report.hottest_keys = hottest_keys(
+            self.key_hits.iter().map(|(key, hits)| (key, *hits)),
+            HOTTEST_KEYS,
+        );
+    }
+}
+
+/// A limit of a tier that can be raised
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TierLimit {
+    Entries,
+    Bytes,
+}
+
+/// Configuration change recommended for a tier
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum TuningAction {
+    IncreaseTierSize {
+        limit: TierLimit,
+        current: u64,
+        suggested: u64,
+    },
+    LowerTtl {
+        current_secs: u64,
+        suggested_secs: u64,
+    },
+}
+
+impl fmt::Display for TuningAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TuningAction::IncreaseTierSize {
+                limit: TierLimit::Entries,
+                current,
+                suggested,
+            } => write!(
+                f,
+                "increase tier size from {} to {} entries",
+                current, suggested
+            ),
+            TuningAction::IncreaseTierSize {
+                limit: TierLimit::Bytes,
+                current,
+                suggested,
+            } => write!(
+                f,
+                "increase tier size from {} to {} bytes",
+                current, suggested
+            ),
+            TuningAction::LowerTtl {
+                current_secs,
+                suggested_secs,
+            } => write!(f, "lower TTL from {}s to {}s", current_secs, suggested_secs),
+        }
+    }
+}
+
+/// Tuning recommendation for one tier
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TuningRecommendation {
+    /// [`CacheTierReport::name`] of the tier
+    pub tier: String,
+    #[serde(flatten)]
+    pub action: TuningAction,
+    pub reason: String,
+}
+
+/// Statistics of every cache tier, with tuning recommendations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheReport {
+    pub generated_at: DateTime<Utc>,
+    pub tiers: Vec<CacheTierReport>,
+    pub recommendations: Vec<TuningRecommendation>,
+}
+
+impl CacheReport {
+    pub fn new(tiers: Vec<CacheTierReport>) -> Self {
+        Self {
+            generated_at: Utc::now(),
+            tiers,
+            recommendations: Vec::new(),
+        }
+    }
+
+    pub fn with_recommendations(mut self, recommendations: Vec<TuningRecommendation>) -> Self {
+        self.recommendations = recommendations;
+        self
+    }
+
+    /// Hit ratio over the requests of every tier
+    pub fn overall_hit_ratio(&self) -> f64 {
+        let hits: u64 = self.tiers.iter().map(|tier| tier.hits).sum();
+        let requests: u64 = self.tiers.iter().map(CacheTierReport::requests).sum();
+        match requests {
+            0 => 0.0,
+            requests => hits as f64 / requests as f64,
+        }
+    }
+
+    pub fn total_memory_bytes(&self) -> u64 {
+        self.tiers.iter().map(|tier| tier.memory_bytes).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_activity_fills_tier_report() {
+        let mut activity = CacheActivity::default();
+        for key in [
+            "query:todos",
+            "query:todos",
+            "query:decisions",
+            "query:todos",
+        ] {
+            activity.hit(key);
+        }
+        activity.miss();
+        activity.evict(EvictionCause::Invalidated, 2);
+
+        let mut report = CacheTierReport::new("api", "query");
+        report.entries = 3;
+        report.max_entries = Some(4);
+        activity.fill(&mut report);
+
+        assert_eq!(report.name(), "api.query");
+        assert_eq!(report.hit_ratio(), 0.8);
+        assert_eq!(report.utilization(), Some(0.75));
+        assert_eq!(report.evictions.total(), 2);
+        assert_eq!(
+            report.hottest_keys,
+            vec![
+                KeyHits {
+                    key: "query:todos".to_string(),
+                    hits: 3
+                },
+                KeyHits {
+                    key: "query:decisions".to_string(),
+                    hits: 1
+                },
+            ]
+        );
+        assert_eq!(report.hot_key_share(), 1.0);
+    }
+
+    #[test]
+    fn test_report_serialization() {
+        let mut tier = CacheTierReport::new("mcp", "memory");
+        tier.hits = 30;
+        tier.misses = 10;
+        let report = CacheReport::new(vec![tier, CacheTierReport::new("api", "scope")])
+            .with_recommendations(vec![TuningRecommendation {
+                tier: "mcp.memory".to_string(),
+                action: TuningAction::LowerTtl {
+                    current_secs: 3600,
+                    suggested_secs: 1800,
+                },
+                reason: "cold entries".to_string(),
+            }]);
+        assert_eq!(report.overall_hit_ratio(), 0.75);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["recommendations"][0]["action"], "lower_ttl");
+        assert_eq!(json["recommendations"][0]["suggested_secs"], 1800);
+        let parsed: CacheReport = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.recommendations, report.recommendations);
+        assert_eq!(
+            parsed.recommendations[0].action.to_string(),
+            "lower TTL from 3600s to 1800s"
+        );
+    }
+}
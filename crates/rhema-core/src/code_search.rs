@@ -0,0 +1,441 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Code search behind `rhema find`.
+//!
+//! The working tree is scanned like ripgrep does by default: hidden files,
+//! paths ignored by git and binary files are skipped, and a query without
+//! uppercase letters matches case-insensitively. Each hit is attributed to
+//! the scope owning the file and linked to the decisions and patterns that
+//! govern it, either because they mention the file or because one of a
+//! decision's implementing commits changed it.
+
+use crate::file_ops::read_yaml_file;
+use crate::schema::{Decisions, Patterns};
+use crate::{review, RhemaError, RhemaResult, Scope};
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Files larger than this are not searched
+const MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+/// Leading bytes checked for NUL to detect binary files
+const BINARY_SNIFF_BYTES: usize = 8192;
+
+/// Matching lines are cut to this many characters
+const MAX_LINE_CHARS: usize = 200;
+
+/// A matching line of a file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CodeLine {
+    /// 1-based line number
+    pub line: usize,
+    pub text: String,
+}
+
+/// Kind of context entry a code hit is linked to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkKind {
+    Decision,
+    Pattern,
+}
+
+/// Why a context entry governs a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkReason {
+    /// The entry mentions the file's path
+    Mentioned,
+    /// One of the decision's implementing commits changed the file
+    ImplementingCommit,
+}
+
+/// A decision or pattern governing a file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GoverningEntry {
+    pub kind: LinkKind,
+    pub id: String,
+    pub title: String,
+    pub scope: String,
+    pub reason: LinkReason,
+}
+
+/// A file matching the query
+#[derive(Debug, Clone, Serialize)]
+pub struct CodeHit {
+    /// Path relative to the repository root
+    pub path: PathBuf,
+    /// Name of the scope owning the file
+    pub scope: Option<String>,
+    pub match_count: usize,
+    pub lines: Vec<CodeLine>,
+    pub governing: Vec<GoverningEntry>,
+}
+
+/// Scans the working tree of a repository for a query
+pub struct CodeSearch {
+    repo_root: PathBuf,
+    pattern: Regex,
+    max_lines: usize,
+}
+
+impl CodeSearch {
+    /// Search for `query`, literally unless `is_regex` is set
+    pub fn new(repo_root: &Path, query: &str, is_regex: bool) -> RhemaResult<Self> {
+        let source = if is_regex {
+            query.to_string()
+        } else {
+            regex::escape(query)
+        };
+        let pattern = RegexBuilder::new(&source)
+            .case_insensitive(!query.chars().any(char::is_uppercase))
+            .build()
+            .map_err(|e| RhemaError::InvalidInput(format!("Invalid search pattern: {}", e)))?;
+        Ok(Self {
+            repo_root: repo_root.to_path_buf(),
+            pattern,
+            max_lines: 3,
+        })
+    }
+
+    /// Keep at most this many matching lines per file
+    pub fn with_max_lines(mut self, max_lines: usize) -> Self {
+        self.max_lines = max_lines;
+        self
+    }
+
+    /// Files with the most matching lines first, at most `limit` of them
+    pub fn search(&self, limit: usize) -> RhemaResult<Vec<CodeHit>> {
+        // git reports a canonical workdir, so walk from the canonical root
+        let root = self.repo_root.canonicalize()?;
+        let repo = git2::Repository::discover(&root).ok();
+        let workdir = repo
+            .as_ref()
+            .and_then(|repo| repo.workdir())
+            .map(Path::to_path_buf);
+        let ignored = |path: &Path| match (&repo, &workdir) {
+            (Some(repo), Some(workdir)) => path
+                .strip_prefix(workdir)
+                .ok()
+                .filter(|relative| !relative.as_os_str().is_empty())
+                .is_some_and(|relative| repo.is_path_ignored(relative).unwrap_or(false)),
+            _ => false,
+        };
+
+        let walker = WalkDir::new(&root).into_iter().filter_entry(|entry| {
+            entry.depth() == 0
+                || (!entry.file_name().to_string_lossy().starts_with('.') && !ignored(entry.path()))
+        });
+
+        let mut hits = Vec::new();
+        for entry in walker.filter_map(|entry| entry.ok()) {
+            if !entry.file_type().is_file()
+                || entry
+                    .metadata()
+                    .map_or(true, |meta| meta.len() > MAX_FILE_SIZE)
+            {
+                continue;
+            }
+            if let Some(hit) = self.search_file(&root, entry.path()) {
+                hits.push(hit);
+            }
+        }
+
+        hits.sort_by(|a, b| {
+            b.match_count
+                .cmp(&a.match_count)
+                .then_with(|| a.path.cmp(&b.path))
+        });
+        hits.truncate(limit);
+        Ok(hits)
+    }
+
+    fn search_file(&self, root: &Path, path: &Path) -> Option<CodeHit> {
+        let bytes = std::fs::read(path).ok()?;
+        if bytes.iter().take(BINARY_SNIFF_BYTES).any(|byte| *byte == 0) {
+            return None;
+        }
+        let text = String::from_utf8(bytes).ok()?;
+
+        let mut match_count = 0;
+        let mut lines = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            if !self.pattern.is_match(line) {
+                continue;
+            }
+            match_count += 1;
+            if lines.len() < self.max_lines {
+                lines.push(CodeLine {
+                    line: index + 1,
+                    text: line.trim().chars().take(MAX_LINE_CHARS).collect(),
+                });
+            }
+        }
+        if match_count == 0 {
+            return None;
+        }
+
+        Some(CodeHit {
+            path: path.strip_prefix(root).ok()?.to_path_buf(),
+            scope: None,
+            match_count,
+            lines,
+            governing: Vec::new(),
+        })
+    }
+}
+
+/// Decisions and patterns of one scope, with the text searched for mentions
+struct ScopeLinks {
+    name: String,
+    /// Directory governed by the scope, relative to the repository root
+    root: PathBuf,
+    entries: Vec<(GoverningEntry, String)>,
+}
+
+/// Attributes code hits to scopes and links them to governing entries
+pub struct CodeLinker {
+    scopes: Vec<ScopeLinks>,
+    commit_links: HashMap<PathBuf, Vec<GoverningEntry>>,
+}
+
+impl CodeLinker {
+    pub fn new(repo_root: &Path, scopes: &[Scope]) -> RhemaResult<Self> {
+        let repo = git2::Repository::discover(repo_root).ok();
+        let mut links = Vec::new();
+        let mut commit_links: HashMap<PathBuf, Vec<GoverningEntry>> = HashMap::new();
+
+        for scope in scopes {
+            let name = scope.definition.name.clone();
+            let root = scope
+                .path
+                .parent()
+                .and_then(|dir| dir.strip_prefix(repo_root).ok())
+                .map(Path::to_path_buf)
+                .unwrap_or_default();
+            let mut entries = Vec::new();
+
+            let decisions_file = scope.path.join("decisions.yaml");
+            if decisions_file.exists() {
+                let decisions: Decisions = read_yaml_file(&decisions_file)?;
+                for decision in decisions.decisions {
+                    if !review::is_visible_custom(&decision.custom) {
+                        continue;
+                    }
+                    let entry = GoverningEntry {
+                        kind: LinkKind::Decision,
+                        id: decision.id.clone(),
+                        title: decision.title.clone(),
+                        scope: name.clone(),
+                        reason: LinkReason::Mentioned,
+                    };
+                    if let Some(repo) = &repo {
+                        for commit in decision.implementing_commits.iter().flatten() {
+                            for path in changed_files(repo, commit) {
+                                commit_links.entry(path).or_default().push(GoverningEntry {
+                                    reason: LinkReason::ImplementingCommit,
+                                    ..entry.clone()
+                                });
+                            }
+                        }
+                    }
+                    let text = [
+                        Some(decision.description),
+                        decision.context,
+                        decision.rationale,
+                        decision.consequences.map(|items| items.join("\n")),
+                    ]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                    entries.push((entry, format!("{}\n{}", decision.title, text)));
+                }
+            }
+
+            let patterns_file = scope.path.join("patterns.yaml");
+            if patterns_file.exists() {
+                let patterns: Patterns = read_yaml_file(&patterns_file)?;
+                for pattern in patterns.patterns {
+                    if !review::is_visible_custom(&pattern.custom) {
+                        continue;
+                    }
+                    let text = format!(
+                        "{}\n{}\n{}",
+                        pattern.name,
+                        pattern.description,
+                        pattern.examples.unwrap_or_default().join("\n")
+                    );
+                    let entry = GoverningEntry {
+                        kind: LinkKind::Pattern,
+                        id: pattern.id,
+                        title: pattern.name,
+                        scope: name.clone(),
+                        reason: LinkReason::Mentioned,
+                    };
+                    entries.push((entry, text));
+                }
+            }
+
+            links.push(ScopeLinks {
+                name,
+                root,
+                entries,
+            });
+        }
+
+        // Deepest scopes first, so the first containing scope owns a file
+        links.sort_by_key(|scope| std::cmp::Reverse(scope.root.components().count()));
+        Ok(Self {
+            scopes: links,
+            commit_links,
+        })
+    }
+
+    /// Set the owning scope and governing entries of a hit
+    pub fn link(&self, hit: &mut CodeHit) {
+        let mut governing: Vec<GoverningEntry> = self
+            .commit_links
+            .get(&hit.path)
+            .cloned()
+            .unwrap_or_default();
+
+        let repo_path = slash_path(&hit.path);
+        for scope in self
+            .scopes
+            .iter()
+            .filter(|scope| hit.path.starts_with(&scope.root))
+        {
+            if hit.scope.is_none() {
+                hit.scope = Some(scope.name.clone());
+            }
+            let scope_path = hit
+                .path
+                .strip_prefix(&scope.root)
+                .map(slash_path)
+                .unwrap_or_default();
+            for (entry, text) in &scope.entries {
+                let mentioned = text.contains(&repo_path)
+                    || (!scope_path.is_empty() && text.contains(&scope_path));
+                if mentioned && !governing.iter().any(|known| known.id == entry.id) {
+                    governing.push(entry.clone());
+                }
+            }
+        }
+
+        hit.governing = governing;
+    }
+}
+
+/// Path with `/` separators, as written in context entries
+fn slash_path(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Files changed by a commit; empty if the commit is not in the repository
+fn changed_files(repo: &git2::Repository, commit: &str) -> Vec<PathBuf> {
+    let Some(commit) = repo
+        .revparse_single(commit)
+        .ok()
+        .and_then(|object| object.peel_to_commit().ok())
+    else {
+        return Vec::new();
+    };
+    let parent_tree = commit.parent(0).ok().and_then(|parent| parent.tree().ok());
+    let Some(diff) = commit.tree().ok().and_then(|tree| {
+        repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+            .ok()
+    }) else {
+        return Vec::new();
+    };
+    diff.deltas()
+        .filter_map(|delta| delta.new_file().path().map(Path::to_path_buf))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_ops::add_decision;
+    use crate::DecisionStatus;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_search_skips_ignored_files_and_links_decisions() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        git2::Repository::init(root).unwrap();
+        std::fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("target")).unwrap();
+        std::fs::write(
+            root.join("src/auth.rs"),
+            "fn verify_token() {}\n// token cache\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("target/auth.rs"), "fn verify_token() {}\n").unwrap();
+
+        let scope_dir = root.join(".rhema");
+        std::fs::create_dir_all(&scope_dir).unwrap();
+        std::fs::write(
+            scope_dir.join("rhema.yaml"),
+            "name: app\nscope_type: service\nversion: 1.0.0\n",
+        )
+        .unwrap();
+        let id = add_decision(
+            &scope_dir,
+            "Use JWT".into(),
+            "Tokens are verified in src/auth.rs".into(),
+            DecisionStatus::Approved,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Default::default(),
+        )
+        .unwrap();
+
+        let mut hits = CodeSearch::new(root, "TOKEN", false)
+            .unwrap()
+            .search(10)
+            .unwrap();
+        assert!(hits.is_empty());
+
+        hits = CodeSearch::new(root, "token", false)
+            .unwrap()
+            .search(10)
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].path, PathBuf::from("src/auth.rs"));
+        assert_eq!(hits[0].match_count, 2);
+
+        let scope = Scope::new(scope_dir).unwrap();
+        let linker = CodeLinker::new(root, &[scope]).unwrap();
+        linker.link(&mut hits[0]);
+        assert_eq!(hits[0].scope.as_deref(), Some("app"));
+        assert_eq!(hits[0].governing.len(), 1);
+        assert_eq!(hits[0].governing[0].id, id);
+        assert_eq!(hits[0].governing[0].reason, LinkReason::Mentioned);
+    }
+}
@@ -0,0 +1,380 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Decision outcome tracking: how decisions held up after they were made.
+//!
+//! A decision's quality score starts at 1.0 and is reduced by the incidents
+//! attributed to it and by being reversed. Reversals shortly after the
+//! decision cost more than ones after the decision has served for a year.
+
+use crate::{DecisionEntry, DecisionStatus, Priority, RhemaResult};
+use chrono::{DateTime, Datelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+/// Base penalty for a decision that was later reversed
+const REVERSAL_PENALTY: f64 = 0.4;
+
+/// Additional penalty for a reversal on the day of the decision, tapering to
+/// zero for reversals a year or more later
+const EARLY_REVERSAL_PENALTY: f64 = 0.2;
+
+/// Score penalty for an incident of the given severity
+fn incident_penalty(severity: &Priority) -> f64 {
+    match severity {
+        Priority::Low => 0.05,
+        Priority::Medium => 0.1,
+        Priority::High => 0.2,
+        Priority::Critical => 0.35,
+    }
+}
+
+/// Granularity of the decision quality trend
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OutcomePeriod {
+    Month,
+    #[default]
+    Quarter,
+    Year,
+}
+
+impl OutcomePeriod {
+    /// Label of the period containing `at`, sortable lexically
+    pub fn label(&self, at: DateTime<Utc>) -> String {
+        match self {
+            OutcomePeriod::Month => format!("{}-{:02}", at.year(), at.month()),
+            OutcomePeriod::Quarter => format!("{}-Q{}", at.year(), (at.month() - 1) / 3 + 1),
+            OutcomePeriod::Year => at.year().to_string(),
+        }
+    }
+}
+
+/// Outcome of a single decision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionOutcome {
+    pub id: String,
+    pub title: String,
+    pub status: DecisionStatus,
+    pub decided_at: DateTime<Utc>,
+
+    /// Whether the decision is implemented, by status or linked commits
+    pub implemented: bool,
+    pub commit_count: usize,
+    pub incident_count: usize,
+
+    /// Most severe incident attributed to the decision
+    pub worst_incident: Option<Priority>,
+    pub reversed_by: Option<String>,
+
+    /// Days between the decision and the decision reversing it
+    pub days_to_reversal: Option<i64>,
+
+    /// Quality score in [0, 1]
+    pub score: f64,
+}
+
+impl DecisionOutcome {
+    /// Score a decision; `decided_at` resolves the date of a reversing decision
+    pub fn score(decision: &DecisionEntry, decided_at: &HashMap<&str, DateTime<Utc>>) -> Self {
+        let incidents = decision.incidents.as_deref().unwrap_or_default();
+        let commit_count = decision
+            .implementing_commits
+            .as_ref()
+            .map_or(0, |commits| commits.len());
+
+        let mut penalty: f64 = incidents
+            .iter()
+            .map(|i| incident_penalty(&i.severity))
+            .sum();
+
+        let days_to_reversal = decision.reversed_by.as_deref().map(|reversing_id| {
+            decided_at.get(reversing_id).map_or(0, |reversed_at| {
+                (*reversed_at - decision.decided_at).num_days().max(0)
+            })
+        });
+        if let Some(days) = days_to_reversal {
+            let earliness = 1.0 - (days as f64 / 365.0).min(1.0);
+            penalty += REVERSAL_PENALTY + EARLY_REVERSAL_PENALTY * earliness;
+        }
+
+        Self {
+            id: decision.id.clone(),
+            title: decision.title.clone(),
+            status: decision.status.clone(),
+            decided_at: decision.decided_at,
+            implemented: decision.status == DecisionStatus::Implemented || commit_count > 0,
+            commit_count,
+            incident_count: incidents.len(),
+            worst_incident: incidents.iter().map(|i| i.severity.clone()).max_by(|a, b| {
+                incident_penalty(a)
+                    .partial_cmp(&incident_penalty(b))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            }),
+            reversed_by: decision.reversed_by.clone(),
+            days_to_reversal,
+            score: (1.0 - penalty).clamp(0.0, 1.0),
+        }
+    }
+}
+
+/// Aggregate decision quality for one period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeriodOutcome {
+    pub period: String,
+    pub decisions: usize,
+    pub mean_score: f64,
+    pub implemented: usize,
+    pub reversed: usize,
+    pub incidents: usize,
+}
+
+/// Decision quality over time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionOutcomeReport {
+    pub generated_at: DateTime<Utc>,
+    pub period: OutcomePeriod,
+
+    /// Scored decisions, worst first
+    pub decisions: Vec<DecisionOutcome>,
+
+    /// Per-period aggregates in chronological order
+    pub periods: Vec<PeriodOutcome>,
+
+    /// Decisions still proposed, under review or rejected, which are not scored
+    pub unscored: usize,
+    pub mean_score: f64,
+    pub implementation_rate: f64,
+    pub reversal_rate: f64,
+    pub total_incidents: usize,
+}
+
+impl DecisionOutcomeReport {
+    /// Score the decisions that were actually made and group them by period
+    pub fn build(decisions: &[DecisionEntry], period: OutcomePeriod) -> Self {
+        let decided_at: HashMap<&str, DateTime<Utc>> = decisions
+            .iter()
+            .map(|d| (d.id.as_str(), d.decided_at))
+            .collect();
+
+        let (made, pending): (Vec<&DecisionEntry>, Vec<&DecisionEntry>) =
+            decisions.iter().partition(|d| is_made(d));
+
+        let mut outcomes: Vec<DecisionOutcome> = made
+            .iter()
+            .map(|d| DecisionOutcome::score(d, &decided_at))
+            .collect();
+        outcomes.sort_by(|a, b| {
+            a.score
+                .partial_cmp(&b.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.decided_at.cmp(&b.decided_at))
+        });
+
+        let mut by_period: BTreeMap<String, Vec<&DecisionOutcome>> = BTreeMap::new();
+        for outcome in &outcomes {
+            by_period
+                .entry(period.label(outcome.decided_at))
+                .or_default()
+                .push(outcome);
+        }
+        let periods = by_period
+            .into_iter()
+            .map(|(label, items)| PeriodOutcome {
+                period: label,
+                decisions: items.len(),
+                mean_score: mean(items.iter().map(|o| o.score)),
+                implemented: items.iter().filter(|o| o.implemented).count(),
+                reversed: items.iter().filter(|o| o.reversed_by.is_some()).count(),
+                incidents: items.iter().map(|o| o.incident_count).sum(),
+            })
+            .collect();
+
+        let total = outcomes.len().max(1) as f64;
+        Self {
+            generated_at: Utc::now(),
+            period,
+            mean_score: mean(outcomes.iter().map(|o| o.score)),
+            implementation_rate: outcomes.iter().filter(|o| o.implemented).count() as f64 / total,
+            reversal_rate: outcomes.iter().filter(|o| o.reversed_by.is_some()).count() as f64
+                / total,
+            total_incidents: outcomes.iter().map(|o| o.incident_count).sum(),
+            unscored: pending.len(),
+            decisions: outcomes,
+            periods,
+        }
+    }
+
+    /// Change in mean score between the first and the latest period
+    pub fn trend(&self) -> Option<f64> {
+        match (self.periods.first(), self.periods.last()) {
+            (Some(first), Some(last)) if self.periods.len() > 1 => {
+                Some(last.mean_score - first.mean_score)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Whether a decision was taken, as opposed to still being discussed
+fn is_made(decision: &DecisionEntry) -> bool {
+    match decision.status {
+        DecisionStatus::Approved | DecisionStatus::Implemented | DecisionStatus::Deprecated => true,
+        DecisionStatus::Proposed | DecisionStatus::UnderReview | DecisionStatus::Rejected => {
+            decision.reversed_by.is_some()
+        }
+    }
+}
+
+fn mean(values: impl Iterator<Item = f64>) -> f64 {
+    let (sum, count) = values.fold((0.0, 0usize), |(sum, count), v| (sum + v, count + 1));
+    if count == 0 {
+        0.0
+    } else {
+        sum / count as f64
+    }
+}
+
+/// Find commits whose message mentions a decision ID, e.g. in a
+/// `Decision: <id>` trailer, walking at most `max_commits` back from HEAD.
+/// Returns commit SHAs keyed by decision ID, oldest first.
+pub fn discover_implementing_commits(
+    repo_path: &Path,
+    decisions: &[DecisionEntry],
+    max_commits: usize,
+) -> RhemaResult<HashMap<String, Vec<String>>> {
+    let repo = git2::Repository::discover(repo_path)?;
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push_head()?;
+    revwalk.set_sorting(git2::Sort::TIME)?;
+
+    let mut found: HashMap<String, Vec<String>> = HashMap::new();
+    for oid in revwalk.take(max_commits) {
+        let oid = oid?;
+        let commit = repo.find_commit(oid)?;
+        let Some(message) = commit.message() else {
+            continue;
+        };
+        for decision in decisions {
+            if message.contains(&decision.id) {
+                found
+                    .entry(decision.id.clone())
+                    .or_default()
+                    .push(oid.to_string());
+            }
+        }
+    }
+
+    for commits in found.values_mut() {
+        commits.reverse();
+    }
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DecisionIncident;
+    use chrono::{Duration, TimeZone};
+
+    fn decision(id: &str, status: DecisionStatus, decided_at: DateTime<Utc>) -> DecisionEntry {
+        DecisionEntry {
+            id: id.to_string(),
+            title: format!("Decision {}", id),
+            description: "desc".to_string(),
+            status,
+            context: None,
+            alternatives: None,
+            rationale: None,
+            consequences: None,
+            decided_at,
+            review_date: None,
+            decision_makers: None,
+            implementing_commits: None,
+            incidents: None,
+            reversed_by: None,
+            reverses: None,
+            custom: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_incidents_and_early_reversals_lower_scores() {
+        let start = Utc.with_ymd_and_hms(2025, 1, 10, 0, 0, 0).unwrap();
+
+        let mut clean = decision("clean", DecisionStatus::Implemented, start);
+        clean.implementing_commits = Some(vec!["abc123".to_string()]);
+
+        let mut incident = decision("incident", DecisionStatus::Implemented, start);
+        incident.incidents = Some(vec![DecisionIncident {
+            id: "INC-1".to_string(),
+            title: "Outage".to_string(),
+            severity: Priority::High,
+            occurred_at: start + Duration::days(20),
+            url: None,
+        }]);
+
+        let mut early = decision("early", DecisionStatus::Deprecated, start);
+        early.reversed_by = Some("early-fix".to_string());
+        let mut late = decision("late", DecisionStatus::Deprecated, start);
+        late.reversed_by = Some("late-fix".to_string());
+
+        let decisions = vec![
+            clean,
+            incident,
+            early,
+            late,
+            decision(
+                "early-fix",
+                DecisionStatus::Approved,
+                start + Duration::days(10),
+            ),
+            decision(
+                "late-fix",
+                DecisionStatus::Approved,
+                start + Duration::days(400),
+            ),
+            decision("pending", DecisionStatus::Proposed, start),
+        ];
+        let report = DecisionOutcomeReport::build(&decisions, OutcomePeriod::Quarter);
+        let score = |id: &str| report.decisions.iter().find(|o| o.id == id).unwrap().score;
+
+        assert_eq!(report.unscored, 1);
+        assert_eq!(score("clean"), 1.0);
+        assert!((score("incident") - 0.8).abs() < 1e-9);
+        assert!(score("early") < score("late"));
+        assert_eq!(report.decisions[0].id, "early");
+        assert_eq!(report.total_incidents, 1);
+        assert!((report.reversal_rate - 2.0 / 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_periods_are_chronological() {
+        let q1 = Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap();
+        let q4 = Utc.with_ymd_and_hms(2024, 11, 1, 0, 0, 0).unwrap();
+
+        let mut reversed = decision("a", DecisionStatus::Deprecated, q1);
+        reversed.reversed_by = Some("b".to_string());
+        let decisions = vec![reversed, decision("b", DecisionStatus::Implemented, q4)];
+
+        let report = DecisionOutcomeReport::build(&decisions, OutcomePeriod::Quarter);
+        let labels: Vec<&str> = report.periods.iter().map(|p| p.period.as_str()).collect();
+        assert_eq!(labels, vec!["2024-Q1", "2024-Q4"]);
+        assert!(report.trend().unwrap() > 0.0);
+        assert_eq!(OutcomePeriod::Month.label(q4), "2024-11");
+    }
+}
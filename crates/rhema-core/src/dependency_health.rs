@@ -0,0 +1,497 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Health of the external services behind the knowledge layer.
+//!
+//! A [`HealthProbe`] checks one embedding endpoint, vector store or LLM
+//! provider. [`DependencyHealthMonitor`] runs the probes with a timeout and
+//! caches each result for the probe's interval, so the daemon and agents can
+//! ask as often as they like and fall back when a dependency is down.
+
+use crate::events::{EventOutbox, ExportEvent, ExportEventType};
+use crate::policy;
+use crate::{RhemaError, RhemaResult};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+/// Section of `.rhema/repository.yaml` configuring dependency probes
+pub const DEPENDENCY_HEALTH_CONFIG_SECTION: &str = "dependency_health";
+
+/// Kind of external dependency
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyKind {
+    Embedding,
+    VectorStore,
+    LlmProvider,
+}
+
+impl fmt::Display for DependencyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DependencyKind::Embedding => "embedding",
+            DependencyKind::VectorStore => "vector store",
+            DependencyKind::LlmProvider => "llm provider",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Probe verdict, ordered from best to worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProbeState {
+    Healthy,
+    /// Usable, but slow or close to its quota
+    Degraded,
+    Unhealthy,
+}
+
+impl fmt::Display for ProbeState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ProbeState::Healthy => "healthy",
+            ProbeState::Degraded => "degraded",
+            ProbeState::Unhealthy => "unhealthy",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// What a probe found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProbeOutcome {
+    pub state: ProbeState,
+    pub message: Option<String>,
+
+    /// Requests or tokens left in the provider's current quota window
+    pub quota_remaining: Option<u64>,
+}
+
+impl ProbeOutcome {
+    pub fn healthy() -> Self {
+        Self {
+            state: ProbeState::Healthy,
+            message: None,
+            quota_remaining: None,
+        }
+    }
+
+    pub fn degraded(message: impl Into<String>) -> Self {
+        Self {
+            state: ProbeState::Degraded,
+            message: Some(message.into()),
+            quota_remaining: None,
+        }
+    }
+
+    pub fn unhealthy(message: impl Into<String>) -> Self {
+        Self {
+            state: ProbeState::Unhealthy,
+            message: Some(message.into()),
+            quota_remaining: None,
+        }
+    }
+
+    pub fn with_quota(mut self, remaining: u64) -> Self {
+        self.quota_remaining = Some(remaining);
+        self
+    }
+}
+
+/// Latest health of one dependency
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyHealth {
+    pub name: String,
+    pub kind: DependencyKind,
+    pub state: ProbeState,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+
+    /// How long the probe took
+    pub latency_ms: u64,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quota_remaining: Option<u64>,
+
+    pub checked_at: DateTime<Utc>,
+}
+
+/// Checks one external dependency
+#[async_trait]
+pub trait HealthProbe: Send + Sync {
+    /// Unique name, also used to override the probe interval
+    fn name(&self) -> &str;
+
+    fn kind(&self) -> DependencyKind;
+
+    async fn probe(&self) -> ProbeOutcome;
+}
+
+/// LLM provider probed by `rhema health --deps`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LlmProviderTarget {
+    #[serde(default = "default_llm_name")]
+    pub name: String,
+
+    /// API base URL, e.g. `https://api.openai.com`
+    pub base_url: String,
+
+    /// Environment variable holding the API key
+    #[serde(default = "default_api_key_env")]
+    pub api_key_env: String,
+}
+
+fn default_llm_name() -> String {
+    "llm".to_string()
+}
+
+fn default_api_key_env() -> String {
+    "OPENAI_API_KEY".to_string()
+}
+
+/// Dependency probe configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DependencyHealthConfig {
+    /// Seconds a probe result is reused before probing again
+    pub interval_secs: u64,
+
+    /// Probes running longer than this report unhealthy
+    pub timeout_secs: u64,
+
+    /// Healthy probes slower than this report degraded
+    pub degraded_latency_ms: u64,
+
+    /// Healthy probes with less quota left than this report degraded
+    pub min_quota: u64,
+
+    /// Interval overrides keyed by probe name
+    pub intervals: BTreeMap<String, u64>,
+
+    pub llm_provider: Option<LlmProviderTarget>,
+}
+
+impl Default for DependencyHealthConfig {
+    fn default() -> Self {
+        Self {
+            interval_secs: 60,
+            timeout_secs: 5,
+            degraded_latency_ms: 2000,
+            min_quota: 10,
+            intervals: BTreeMap::new(),
+            llm_provider: None,
+        }
+    }
+}
+
+impl DependencyHealthConfig {
+    /// Load the configuration from the repository config and policy bundle,
+    /// falling back to defaults
+    pub fn load(repo_root: &Path) -> RhemaResult<Self> {
+        let value = policy::repository_config(repo_root)?;
+        match value.get(DEPENDENCY_HEALTH_CONFIG_SECTION) {
+            Some(section) => serde_yaml::from_value(section.clone()).map_err(|e| {
+                RhemaError::ConfigError(format!(
+                    "Invalid {} section in the repository config: {}",
+                    DEPENDENCY_HEALTH_CONFIG_SECTION, e
+                ))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn interval_for(&self, probe: &str) -> Duration {
+        Duration::from_secs(*self.intervals.get(probe).unwrap_or(&self.interval_secs))
+    }
+}
+
+/// Runs dependency probes and caches their results
+pub struct DependencyHealthMonitor {
+    config: DependencyHealthConfig,
+    probes: Vec<Arc<dyn HealthProbe>>,
+    results: RwLock<HashMap<String, (Instant, DependencyHealth)>>,
+    event_outbox: Option<EventOutbox>,
+}
+
+impl DependencyHealthMonitor {
+    pub fn new(config: DependencyHealthConfig) -> Self {
+        Self {
+            config,
+            probes: Vec::new(),
+            results: RwLock::new(HashMap::new()),
+            event_outbox: None,
+        }
+    }
+
+    pub fn with_probe(mut self, probe: Arc<dyn HealthProbe>) -> Self {
+        self.probes.push(probe);
+        self
+    }
+
+    /// Export a `health_degraded` event whenever a dependency gets worse
+    pub fn with_event_outbox(mut self, outbox: EventOutbox) -> Self {
+        self.event_outbox = Some(outbox);
+        self
+    }
+
+    pub fn config(&self) -> &DependencyHealthConfig {
+        &self.config
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.probes.is_empty()
+    }
+
+    /// Last known results, without probing
+    pub async fn cached(&self) -> Vec<DependencyHealth> {
+        let mut results: Vec<DependencyHealth> = self
+            .results
+            .read()
+            .await
+            .values()
+            .map(|(_, health)| health.clone())
+            .collect();
+        results.sort_by(|a, b| a.name.cmp(&b.name));
+        results
+    }
+
+    /// Probe dependencies whose cached result is older than their interval
+    pub async fn check(&self) -> Vec<DependencyHealth> {
+        self.refresh(false).await;
+        self.cached().await
+    }
+
+    /// Probe every dependency, ignoring cached results
+    pub async fn check_now(&self) -> Vec<DependencyHealth> {
+        self.refresh(true).await;
+        self.cached().await
+    }
+
+    /// Worst cached state, or healthy when nothing was probed yet
+    pub async fn overall(&self) -> ProbeState {
+        self.results
+            .read()
+            .await
+            .values()
+            .map(|(_, health)| health.state)
+            .max()
+            .unwrap_or(ProbeState::Healthy)
+    }
+
+    /// Whether a dependency of this kind can be used.
+    ///
+    /// True when any probe of the kind is not unhealthy, or when none has
+    /// reported yet, so callers only fall back on evidence of an outage.
+    pub async fn is_available(&self, kind: DependencyKind) -> bool {
+        let results = self.results.read().await;
+        let mut states = results
+            .values()
+            .filter(|(_, health)| health.kind == kind)
+            .map(|(_, health)| health.state)
+            .peekable();
+        states.peek().is_none() || states.any(|state| state != ProbeState::Unhealthy)
+    }
+
+    /// Keep the cache fresh in the background until the handle is aborted
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let tick = self
+            .probes
+            .iter()
+            .map(|probe| self.config.interval_for(probe.name()))
+            .min()
+            .unwrap_or_else(|| Duration::from_secs(self.config.interval_secs))
+            .max(Duration::from_secs(1));
+        tokio::spawn(async move {
+            loop {
+                self.refresh(false).await;
+                tokio::time::sleep(tick).await;
+            }
+        })
+    }
+
+    async fn refresh(&self, force: bool) {
+        let due: Vec<Arc<dyn HealthProbe>> = {
+            let results = self.results.read().await;
+            self.probes
+                .iter()
+                .filter(|probe| match results.get(probe.name()) {
+                    Some((at, _)) if !force => {
+                        at.elapsed() >= self.config.interval_for(probe.name())
+                    }
+                    _ => true,
+                })
+                .cloned()
+                .collect()
+        };
+
+        let mut tasks = tokio::task::JoinSet::new();
+        for probe in due {
+            let config = self.config.clone();
+            tasks.spawn(async move { run_probe(probe.as_ref(), &config).await });
+        }
+        while let Some(joined) = tasks.join_next().await {
+            if let Ok(health) = joined {
+                let previous = self
+                    .results
+                    .write()
+                    .await
+                    .insert(health.name.clone(), (Instant::now(), health.clone()))
+                    .map(|(_, previous)| previous.state);
+                if health.state > previous.unwrap_or(ProbeState::Healthy) {
+                    self.export_degraded(&health, previous);
+                }
+            }
+        }
+    }
+
+    fn export_degraded(&self, health: &DependencyHealth, previous: Option<ProbeState>) {
+        let Some(outbox) = &self.event_outbox else {
+            return;
+        };
+        let mut data = serde_json::to_value(health).unwrap_or_default();
+        data["previous_state"] = serde_json::to_value(previous).unwrap_or_default();
+        let event = ExportEvent::new(ExportEventType::HealthDegraded, "rhema-core", data)
+            .with_subject(health.name.clone());
+        if let Err(e) = outbox.publish(&event) {
+            tracing::warn!("Failed to export health event for {}: {}", health.name, e);
+        }
+    }
+}
+
+async fn run_probe(probe: &dyn HealthProbe, config: &DependencyHealthConfig) -> DependencyHealth {
+    let started = Instant::now();
+    let timeout = Duration::from_secs(config.timeout_secs);
+    let mut outcome = match tokio::time::timeout(timeout, probe.probe()).await {
+        Ok(outcome) => outcome,
+        Err(_) => ProbeOutcome::unhealthy(format!("No response within {}s", config.timeout_secs)),
+    };
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    if outcome.state == ProbeState::Healthy {
+        if latency_ms > config.degraded_latency_ms {
+            outcome.state = ProbeState::Degraded;
+            outcome.message = Some(format!("Slow response ({}ms)", latency_ms));
+        } else if outcome
+            .quota_remaining
+            .is_some_and(|q| q < config.min_quota)
+        {
+            outcome.state = ProbeState::Degraded;
+            outcome.message = Some("Quota nearly exhausted".to_string());
+        }
+    }
+
+    DependencyHealth {
+        name: probe.name().to_string(),
+        kind: probe.kind(),
+        state: outcome.state,
+        message: outcome.message,
+        latency_ms,
+        quota_remaining: outcome.quota_remaining,
+        checked_at: Utc::now(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct FakeProbe {
+        name: &'static str,
+        outcome: ProbeOutcome,
+        delay: Duration,
+        calls: AtomicUsize,
+    }
+
+    impl FakeProbe {
+        fn new(name: &'static str, outcome: ProbeOutcome) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                outcome,
+                delay: Duration::ZERO,
+                calls: AtomicUsize::new(0),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl HealthProbe for FakeProbe {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn kind(&self) -> DependencyKind {
+            DependencyKind::Embedding
+        }
+
+        async fn probe(&self) -> ProbeOutcome {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            self.outcome.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_results_are_cached_per_interval() {
+        let probe = FakeProbe::new("embedding", ProbeOutcome::healthy());
+        let mut config = DependencyHealthConfig::default();
+        config.intervals.insert("embedding".to_string(), 3600);
+        let monitor = DependencyHealthMonitor::new(config).with_probe(probe.clone());
+
+        assert!(monitor.cached().await.is_empty());
+        monitor.check().await;
+        monitor.check().await;
+        assert_eq!(probe.calls.load(Ordering::SeqCst), 1);
+
+        let results = monitor.check_now().await;
+        assert_eq!(probe.calls.load(Ordering::SeqCst), 2);
+        assert_eq!(results[0].state, ProbeState::Healthy);
+    }
+
+    #[tokio::test]
+    async fn test_timeout_and_low_quota() {
+        let hung = Arc::new(FakeProbe {
+            name: "hung",
+            outcome: ProbeOutcome::healthy(),
+            delay: Duration::from_secs(30),
+            calls: AtomicUsize::new(0),
+        });
+        let config = DependencyHealthConfig {
+            timeout_secs: 0,
+            ..DependencyHealthConfig::default()
+        };
+        let monitor = DependencyHealthMonitor::new(config).with_probe(hung);
+        assert_eq!(monitor.check().await[0].state, ProbeState::Unhealthy);
+        assert!(!monitor.is_available(DependencyKind::Embedding).await);
+        assert!(monitor.is_available(DependencyKind::VectorStore).await);
+
+        let low = FakeProbe::new("llm", ProbeOutcome::healthy().with_quota(3));
+        let monitor =
+            DependencyHealthMonitor::new(DependencyHealthConfig::default()).with_probe(low);
+        let results = monitor.check().await;
+        assert_eq!(results[0].state, ProbeState::Degraded);
+        assert_eq!(monitor.overall().await, ProbeState::Degraded);
+    }
+}
@@ -0,0 +1,849 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Editor assistance for `.rhema/*.yaml` files: diagnostics, completions,
+//! hovers and code actions, independent of the protocol carrying them.
+//!
+//! A [`ContextIndex`] holds the IDs, tags and scopes of the repository and is
+//! updated from unsaved editor buffers. References are the fields listed in
+//! [`REFERENCE_FIELDS`]; positions are zero-based lines and UTF-16 columns,
+//! as editors count them.
+
+use crate::schema::{Conventions, Decisions, Knowledge, Patterns, RhemaScope, Todos};
+use crate::scope::discover_scopes;
+use crate::RhemaResult;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Diagnostic code of references to IDs or scopes that do not exist
+pub const DANGLING_REFERENCE: &str = "dangling-reference";
+
+/// Longest description shown in a hover
+const HOVER_SUMMARY_CHARS: usize = 400;
+
+/// Context file an editor buffer holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContextFileKind {
+    Scope,
+    Knowledge,
+    Todos,
+    Decisions,
+    Patterns,
+    Conventions,
+}
+
+impl ContextFileKind {
+    /// Kind of a file inside a `.rhema` directory, by name
+    pub fn of(path: &Path) -> Option<Self> {
+        let in_rhema_dir = path
+            .parent()
+            .and_then(|dir| dir.file_name())
+            .is_some_and(|name| name == ".rhema");
+        if !in_rhema_dir {
+            return None;
+        }
+        match path.file_name()?.to_str()? {
+            "rhema.yaml" | "scope.yaml" => Some(Self::Scope),
+            "knowledge.yaml" => Some(Self::Knowledge),
+            "todos.yaml" => Some(Self::Todos),
+            "decisions.yaml" => Some(Self::Decisions),
+            "patterns.yaml" => Some(Self::Patterns),
+            "conventions.yaml" => Some(Self::Conventions),
+            _ => None,
+        }
+    }
+
+    /// Key of the entry list, `None` for scope definitions
+    fn entries_key(&self) -> Option<&'static str> {
+        match self {
+            Self::Scope => None,
+            Self::Knowledge => Some("entries"),
+            Self::Todos => Some("todos"),
+            Self::Decisions => Some("decisions"),
+            Self::Patterns => Some("patterns"),
+            Self::Conventions => Some("conventions"),
+        }
+    }
+
+    fn target(&self) -> Option<RefTarget> {
+        match self {
+            Self::Knowledge => Some(RefTarget::Knowledge),
+            Self::Todos => Some(RefTarget::Todo),
+            Self::Decisions => Some(RefTarget::Decision),
+            Self::Patterns => Some(RefTarget::Pattern),
+            Self::Scope | Self::Conventions => None,
+        }
+    }
+
+    /// Schema error of the whole document, if it does not deserialize
+    fn schema_error(&self, text: &str) -> Option<serde_yaml::Error> {
+        let result = match self {
+            Self::Scope => serde_yaml::from_str::<RhemaScope>(text).map(drop),
+            Self::Knowledge => serde_yaml::from_str::<Knowledge>(text).map(drop),
+            Self::Todos => serde_yaml::from_str::<Todos>(text).map(drop),
+            Self::Decisions => serde_yaml::from_str::<Decisions>(text).map(drop),
+            Self::Patterns => serde_yaml::from_str::<Patterns>(text).map(drop),
+            Self::Conventions => serde_yaml::from_str::<Conventions>(text).map(drop),
+        };
+        result.err()
+    }
+}
+
+/// What a reference field points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RefTarget {
+    Knowledge,
+    Todo,
+    Decision,
+    Pattern,
+    Scope,
+}
+
+impl fmt::Display for RefTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            RefTarget::Knowledge => "knowledge entry",
+            RefTarget::Todo => "todo",
+            RefTarget::Decision => "decision",
+            RefTarget::Pattern => "pattern",
+            RefTarget::Scope => "scope",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Entry fields holding references, in every context file
+pub const REFERENCE_FIELDS: &[(&str, RefTarget)] = &[
+    ("related_knowledge", RefTarget::Knowledge),
+    ("related_todos", RefTarget::Todo),
+    ("blocked_by", RefTarget::Todo),
+    ("related_decisions", RefTarget::Decision),
+    ("reverses", RefTarget::Decision),
+    ("reversed_by", RefTarget::Decision),
+    ("related_patterns", RefTarget::Pattern),
+];
+
+fn reference_target(kind: ContextFileKind, field: &str) -> Option<RefTarget> {
+    // Only dependencies carry a `path` in scope definitions
+    if kind == ContextFileKind::Scope && field == "path" {
+        return Some(RefTarget::Scope);
+    }
+    REFERENCE_FIELDS
+        .iter()
+        .find(|(name, _)| *name == field)
+        .map(|(_, target)| *target)
+}
+
+/// Zero-based position range on one line, in UTF-16 code units
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineRange {
+    pub line: u32,
+    pub start: u32,
+    pub end: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticSeverity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EditorDiagnostic {
+    pub range: LineRange,
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompletionKind {
+    Reference,
+    Tag,
+    Scope,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EditorCompletion {
+    pub label: String,
+    pub kind: CompletionKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// Text inserted into a file, which is created first when `create` is set
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileInsert {
+    pub path: PathBuf,
+    pub create: bool,
+    pub line: u32,
+    pub character: u32,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EditorCodeAction {
+    pub title: String,
+    pub edits: Vec<FileInsert>,
+}
+
+/// An entry that references can point at
+#[derive(Debug, Clone)]
+struct IndexedEntry {
+    title: String,
+    /// Markdown shown on hover
+    summary: String,
+    file: PathBuf,
+}
+
+/// IDs, tags and scopes of a repository
+#[derive(Debug, Clone, Default)]
+pub struct ContextIndex {
+    repo_root: PathBuf,
+    entries: HashMap<(RefTarget, String), IndexedEntry>,
+    /// Tags with the files using them
+    tags: HashMap<String, BTreeSet<PathBuf>>,
+    /// Scope directories relative to the repository root, with their names
+    scopes: Vec<(String, String)>,
+}
+
+impl ContextIndex {
+    /// Index every context file of the repository's scopes
+    pub fn build(repo_root: &Path) -> RhemaResult<Self> {
+        let mut index = Self {
+            repo_root: repo_root.to_path_buf(),
+            ..Self::default()
+        };
+        for scope in discover_scopes(repo_root)? {
+            let dir = if scope.path.file_name().is_some_and(|name| name == ".rhema") {
+                scope.path.parent().unwrap_or(&scope.path)
+            } else {
+                &scope.path
+            };
+            let relative = dir
+                .strip_prefix(repo_root)
+                .unwrap_or(dir)
+                .to_string_lossy()
+                .to_string();
+            index.scopes.push((relative, scope.definition.name.clone()));
+
+            for path in scope.files.values() {
+                if let Ok(text) = std::fs::read_to_string(path) {
+                    index.update_document(path, &text);
+                }
+            }
+        }
+        index.scopes.sort();
+        Ok(index)
+    }
+
+    /// Re-index one file from its current (possibly unsaved) text
+    pub fn update_document(&mut self, path: &Path, text: &str) {
+        self.entries.retain(|_, entry| entry.file != path);
+        for files in self.tags.values_mut() {
+            files.remove(path);
+        }
+        self.tags.retain(|_, files| !files.is_empty());
+
+        let Some(kind) = ContextFileKind::of(path) else {
+            return;
+        };
+        let (Some(key), Ok(data)) = (kind.entries_key(), serde_yaml::from_str::<Value>(text))
+        else {
+            return;
+        };
+        let Some(entries) = data.get(key).and_then(|entries| entries.as_sequence()) else {
+            return;
+        };
+        for entry in entries {
+            for tag in entry
+                .get("tags")
+                .and_then(|tags| tags.as_sequence())
+                .into_iter()
+                .flatten()
+                .filter_map(|tag| tag.as_str())
+            {
+                self.tags
+                    .entry(tag.to_string())
+                    .or_default()
+                    .insert(path.to_path_buf());
+            }
+            let (Some(target), Some(id)) = (kind.target(), entry.get("id").and_then(scalar)) else {
+                continue;
+            };
+            let title = ["title", "name"]
+                .iter()
+                .find_map(|field| entry.get(*field).and_then(|v| v.as_str()))
+                .unwrap_or_default()
+                .to_string();
+            let summary = self.summarize(target, &id, &title, entry, path);
+            self.entries.insert(
+                (target, id),
+                IndexedEntry {
+                    title,
+                    summary,
+                    file: path.to_path_buf(),
+                },
+            );
+        }
+    }
+
+    fn summarize(
+        &self,
+        target: RefTarget,
+        id: &str,
+        title: &str,
+        entry: &Value,
+        path: &Path,
+    ) -> String {
+        let field = |name: &str| entry.get(name).and_then(scalar);
+        let mut summary = format!("**{}** · {}", id, title);
+        let state: Vec<String> = ["status", "priority", "category"]
+            .iter()
+            .filter_map(|name| field(name))
+            .collect();
+        if !state.is_empty() {
+            summary.push_str(&format!(" ({})", state.join(", ")));
+        }
+        let body = match target {
+            RefTarget::Knowledge => field("content"),
+            _ => field("description"),
+        };
+        if let Some(body) = body {
+            summary.push_str("\n\n");
+            summary.push_str(&truncate(&body, HOVER_SUMMARY_CHARS));
+        }
+        if let Some(rationale) = field("rationale") {
+            summary.push_str(&format!(
+                "\n\n*Rationale:* {}",
+                truncate(&rationale, HOVER_SUMMARY_CHARS)
+            ));
+        }
+        let file = path.strip_prefix(&self.repo_root).unwrap_or(path);
+        summary.push_str(&format!("\n\n{} in `{}`", target, file.display()));
+        summary
+    }
+
+    fn resolves(&self, target: RefTarget, value: &str) -> bool {
+        match target {
+            RefTarget::Scope => {
+                let path = self.repo_root.join(value);
+                if path.file_name().is_some_and(|name| name == ".rhema") {
+                    path.exists()
+                } else {
+                    path.join(".rhema").exists()
+                }
+            }
+            _ => self.entries.contains_key(&(target, value.to_string())),
+        }
+    }
+
+    fn candidates(&self, target: RefTarget) -> Vec<EditorCompletion> {
+        if target == RefTarget::Scope {
+            return self
+                .scopes
+                .iter()
+                .map(|(path, name)| EditorCompletion {
+                    label: path.clone(),
+                    kind: CompletionKind::Scope,
+                    detail: Some(name.clone()),
+                })
+                .collect();
+        }
+        let mut candidates: Vec<EditorCompletion> = self
+            .entries
+            .iter()
+            .filter(|((entry_target, _), _)| *entry_target == target)
+            .map(|((_, id), entry)| EditorCompletion {
+                label: id.clone(),
+                kind: CompletionKind::Reference,
+                detail: Some(entry.title.clone()),
+            })
+            .collect();
+        candidates.sort_by(|a, b| a.label.cmp(&b.label));
+        candidates
+    }
+
+    /// Problems in a context file: invalid YAML, schema errors, duplicate
+    /// IDs and dangling references
+    pub fn diagnostics(&self, path: &Path, text: &str) -> Vec<EditorDiagnostic> {
+        let Some(kind) = ContextFileKind::of(path) else {
+            return Vec::new();
+        };
+        if let Err(e) = serde_yaml::from_str::<Value>(text) {
+            return vec![error_at(text, &e, "Invalid YAML")];
+        }
+        let mut diagnostics = Vec::new();
+        if !text.trim().is_empty() {
+            if let Some(e) = kind.schema_error(text) {
+                diagnostics.push(error_at(text, &e, "Schema error"));
+            }
+        }
+
+        let mut seen = HashMap::new();
+        for site in value_sites(text, |field| (field == "id").then_some(())) {
+            if kind == ContextFileKind::Scope {
+                break;
+            }
+            if let Some(first) = seen.insert(site.value.clone(), site.range.line) {
+                diagnostics.push(EditorDiagnostic {
+                    range: site.range,
+                    severity: DiagnosticSeverity::Error,
+                    message: format!(
+                        "Duplicate id '{}', first used on line {}",
+                        site.value,
+                        first + 1
+                    ),
+                    code: None,
+                });
+            }
+        }
+
+        for site in reference_sites(kind, text) {
+            if !self.resolves(site.data, &site.value) {
+                diagnostics.push(EditorDiagnostic {
+                    range: site.range,
+                    severity: DiagnosticSeverity::Warning,
+                    message: format!("Unknown {} '{}'", site.data, site.value),
+                    code: Some(DANGLING_REFERENCE.to_string()),
+                });
+            }
+        }
+        diagnostics
+    }
+
+    /// Tags, IDs or scope paths valid at a position
+    pub fn completions(
+        &self,
+        path: &Path,
+        text: &str,
+        line: u32,
+        character: u32,
+    ) -> Vec<EditorCompletion> {
+        let Some(kind) = ContextFileKind::of(path) else {
+            return Vec::new();
+        };
+        let Some(field) = field_at(text, line, character) else {
+            return Vec::new();
+        };
+        if field == "tags" {
+            let mut tags: Vec<EditorCompletion> = self
+                .tags
+                .iter()
+                .map(|(tag, files)| EditorCompletion {
+                    label: tag.clone(),
+                    kind: CompletionKind::Tag,
+                    detail: Some(format!("used in {} file(s)", files.len())),
+                })
+                .collect();
+            tags.sort_by(|a, b| a.label.cmp(&b.label));
+            return tags;
+        }
+        match reference_target(kind, &field) {
+            Some(target) => self.candidates(target),
+            None => Vec::new(),
+        }
+    }
+
+    /// Markdown summary of the entry or scope referenced at a position
+    pub fn hover(&self, path: &Path, text: &str, line: u32, character: u32) -> Option<String> {
+        let kind = ContextFileKind::of(path)?;
+        let site = reference_sites(kind, text).into_iter().find(|site| {
+            site.range.line == line && site.range.start <= character && character <= site.range.end
+        })?;
+        match site.data {
+            RefTarget::Scope => {
+                let (dir, name) = self
+                    .scopes
+                    .iter()
+                    .find(|(dir, _)| site.value.trim_end_matches("/.rhema") == dir)?;
+                Some(format!("**{}** · scope at `{}`", name, dir))
+            }
+            target => {
+                let entry = self.entries.get(&(target, site.value.clone()));
+                Some(match entry {
+                    Some(entry) => entry.summary.clone(),
+                    None => format!("Unknown {} '{}'", target, site.value),
+                })
+            }
+        }
+    }
+
+    /// Fixes for dangling references on a line: creating referenced todos
+    pub fn code_actions(&self, path: &Path, text: &str, line: u32) -> Vec<EditorCodeAction> {
+        let Some(kind) = ContextFileKind::of(path) else {
+            return Vec::new();
+        };
+        let todos_path = path.with_file_name("todos.yaml");
+        let mut actions = Vec::new();
+        for site in reference_sites(kind, text) {
+            if site.range.line != line
+                || site.data != RefTarget::Todo
+                || self.resolves(RefTarget::Todo, &site.value)
+            {
+                continue;
+            }
+            let existing = if todos_path == path {
+                Some(text.to_string())
+            } else {
+                std::fs::read_to_string(&todos_path).ok()
+            };
+            actions.push(EditorCodeAction {
+                title: format!("Create todo '{}'", site.value),
+                edits: vec![new_todo_insert(
+                    &todos_path,
+                    existing.as_deref(),
+                    &site.value,
+                )],
+            });
+        }
+        actions
+    }
+}
+
+/// Insert appending a pending todo to `todos_path`
+fn new_todo_insert(todos_path: &Path, existing: Option<&str>, id: &str) -> FileInsert {
+    let Some(existing) = existing else {
+        return FileInsert {
+            path: todos_path.to_path_buf(),
+            create: true,
+            line: 0,
+            character: 0,
+            text: format!("todos:\n{}", todo_yaml(id, 2)),
+        };
+    };
+
+    // Match the indentation of existing entries
+    let indent = existing
+        .lines()
+        .find(|line| line.trim_start().starts_with("- "))
+        .map(|line| line.len() - line.trim_start().len())
+        .unwrap_or(2);
+    let line_count = existing.lines().count() as u32;
+    let (line, character, prefix) = match existing.lines().last() {
+        Some(last) if !existing.ends_with('\n') => {
+            (line_count - 1, utf16_len(last), "\n".to_string())
+        }
+        _ => (line_count, 0, String::new()),
+    };
+    FileInsert {
+        path: todos_path.to_path_buf(),
+        create: false,
+        line,
+        character,
+        text: format!("{}{}", prefix, todo_yaml(id, indent)),
+    }
+}
+
+fn todo_yaml(id: &str, indent: usize) -> String {
+    let pad = " ".repeat(indent);
+    format!(
+        "{pad}- id: {id}\n{pad}  title: {id}\n{pad}  status: pending\n{pad}  priority: medium\n{pad}  created_at: {}\n",
+        Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+    )
+}
+
+fn error_at(text: &str, error: &serde_yaml::Error, prefix: &str) -> EditorDiagnostic {
+    let line = error
+        .location()
+        .map(|location| location.line().saturating_sub(1))
+        .unwrap_or(0);
+    let end = text.lines().nth(line).map(utf16_len).unwrap_or(0);
+    EditorDiagnostic {
+        range: LineRange {
+            line: line as u32,
+            start: 0,
+            end,
+        },
+        severity: DiagnosticSeverity::Error,
+        message: format!("{}: {}", prefix, error),
+        code: None,
+    }
+}
+
+/// A scalar value of a field, with its position
+#[derive(Debug, Clone)]
+struct ValueSite<T> {
+    value: String,
+    range: LineRange,
+    data: T,
+}
+
+fn reference_sites(kind: ContextFileKind, text: &str) -> Vec<ValueSite<RefTarget>> {
+    value_sites(text, |field| reference_target(kind, field))
+}
+
+/// Scalar values of the fields `select` accepts, whether written inline,
+/// as a flow list or as a block list below the key
+fn value_sites<T: Copy>(text: &str, select: impl Fn(&str) -> Option<T>) -> Vec<ValueSite<T>> {
+    let mut sites = Vec::new();
+    // Field whose block list is being read, with the key's indentation
+    let mut block: Option<(T, usize)> = None;
+
+    for (n, raw) in text.lines().enumerate() {
+        let line = strip_comment(raw);
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let indent = line.len() - trimmed.len();
+
+        let mut rest = trimmed;
+        if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or((trimmed == "-").then_some(""))
+        {
+            if let Some((data, key_indent)) = block {
+                if indent >= key_indent {
+                    push_scalar(&mut sites, raw, n, item.trim(), data);
+                    continue;
+                }
+            }
+            rest = item.trim_start();
+        }
+        block = None;
+
+        let Some((key, value)) = rest.split_once(':') else {
+            continue;
+        };
+        let key = key.trim().trim_matches(|c| c == '"' || c == '\'');
+        let Some(data) = select(key) else {
+            continue;
+        };
+        let value = value.trim();
+        if value.is_empty() {
+            block = Some((data, rest.as_ptr() as usize - raw.as_ptr() as usize));
+        } else if let Some(list) = value.strip_prefix('[') {
+            for item in list.trim_end_matches(']').split(',') {
+                push_scalar(&mut sites, raw, n, item.trim(), data);
+            }
+        } else {
+            push_scalar(&mut sites, raw, n, value, data);
+        }
+    }
+    sites
+}
+
+/// Record `value`, a slice of `raw`, without its quotes
+fn push_scalar<T>(sites: &mut Vec<ValueSite<T>>, raw: &str, line: usize, value: &str, data: T) {
+    let value = value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value);
+    if value.is_empty() {
+        return;
+    }
+    let offset = value.as_ptr() as usize - raw.as_ptr() as usize;
+    let start = utf16_len(&raw[..offset]);
+    sites.push(ValueSite {
+        value: value.to_string(),
+        range: LineRange {
+            line: line as u32,
+            start,
+            end: start + utf16_len(value),
+        },
+        data,
+    });
+}
+
+/// Field whose value is being typed at a position
+fn field_at(text: &str, line: u32, character: u32) -> Option<String> {
+    let lines: Vec<&str> = text.lines().collect();
+    let current = lines.get(line as usize).copied().unwrap_or("");
+    let prefix = &current[..byte_offset(current, character)];
+    let trimmed = prefix.trim_start();
+    let indent = prefix.len() - trimmed.len();
+    let rest = trimmed.strip_prefix("- ").unwrap_or(trimmed);
+
+    if let Some((key, _)) = rest.split_once(':') {
+        return Some(key.trim().to_string());
+    }
+    if !trimmed.starts_with('-') {
+        return None;
+    }
+    // A block list item: the owning key is the nearest line above at the
+    // same or lower indentation that is not itself a list item
+    for above in lines[..line as usize].iter().rev() {
+        let above = strip_comment(above);
+        let above_trimmed = above.trim_start();
+        if above_trimmed.is_empty() {
+            continue;
+        }
+        let above_indent = above.len() - above_trimmed.len();
+        if above_trimmed.starts_with('-') && above_indent == indent {
+            continue;
+        }
+        if above_indent > indent {
+            continue;
+        }
+        let key_line = above_trimmed.strip_prefix("- ").unwrap_or(above_trimmed);
+        return match key_line.split_once(':') {
+            Some((key, value)) if value.trim().is_empty() => Some(key.trim().to_string()),
+            _ => None,
+        };
+    }
+    None
+}
+
+/// The line up to a trailing `#` comment outside quotes
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut previous = ' ';
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if c == q => quote = None,
+            (None, '#') if previous.is_whitespace() => return &line[..i],
+            _ => {}
+        }
+        previous = c;
+    }
+    line
+}
+
+fn scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    let text = text.trim();
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max_chars).collect();
+    format!("{}…", cut.trim_end())
+}
+
+fn utf16_len(text: &str) -> u32 {
+    text.encode_utf16().count() as u32
+}
+
+/// Byte offset of a UTF-16 column, clamped to the line
+fn byte_offset(line: &str, character: u32) -> usize {
+    let mut units = 0;
+    for (i, c) in line.char_indices() {
+        if units >= character as usize {
+            return i;
+        }
+        units += c.len_utf16();
+    }
+    line.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DECISIONS: &str = "decisions:
+  - id: D-1
+    title: Use Postgres
+    description: Relational storage for orders
+    status: approved
+    rationale: Team experience
+    decided_at: 2025-01-10T00:00:00Z
+";
+
+    fn index() -> (ContextIndex, PathBuf) {
+        let root = PathBuf::from("/repo");
+        let mut index = ContextIndex {
+            repo_root: root.clone(),
+            ..ContextIndex::default()
+        };
+        index.update_document(&root.join("api/.rhema/decisions.yaml"), DECISIONS);
+        (index, root.join("api/.rhema/knowledge.yaml"))
+    }
+
+    #[test]
+    fn test_diagnostics_and_hover_for_references() {
+        let (index, path) = index();
+        let text = "entries:
+  - id: K-1
+    title: Order storage
+    content: Orders live in Postgres
+    tags: [storage]
+    related_decisions:
+      - D-1
+      - D-9 # not decided yet
+    related_todos: [T-7]
+    created_at: 2025-01-10T00:00:00Z
+  - id: K-1
+    title: Duplicate
+    content: Again
+    created_at: 2025-01-10T00:00:00Z
+";
+        let diagnostics = index.diagnostics(&path, text);
+        let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Duplicate id 'K-1', first used on line 2",
+                "Unknown decision 'D-9'",
+                "Unknown todo 'T-7'",
+            ]
+        );
+        assert_eq!(
+            diagnostics[1].range,
+            LineRange {
+                line: 7,
+                start: 8,
+                end: 11
+            }
+        );
+
+        let hover = index.hover(&path, text, 6, 9).unwrap();
+        assert!(hover.starts_with("**D-1** · Use Postgres (approved)"));
+        assert!(hover.contains("*Rationale:* Team experience"));
+
+        let actions = index.code_actions(&path, text, 8);
+        assert_eq!(actions.len(), 1);
+        let insert = &actions[0].edits[0];
+        assert!(insert.create);
+        assert_eq!(insert.path, PathBuf::from("/repo/api/.rhema/todos.yaml"));
+        assert!(insert.text.starts_with("todos:\n  - id: T-7\n"));
+    }
+
+    #[test]
+    fn test_completions_for_block_and_flow_lists() {
+        let (index, path) = index();
+        let text = "entries:
+  - id: K-1
+    tags: [storage, ]
+    related_decisions:
+      -
+";
+        let ids = index.completions(&path, text, 4, 8);
+        assert_eq!(ids.len(), 1);
+        assert_eq!(ids[0].label, "D-1");
+        assert_eq!(ids[0].detail.as_deref(), Some("Use Postgres"));
+
+        assert!(index.completions(&path, text, 2, 19).is_empty());
+        assert!(index.completions(&path, text, 1, 10).is_empty());
+    }
+}
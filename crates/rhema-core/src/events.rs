@@ -0,0 +1,244 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Significant events bound for external event buses.
+//!
+//! Producers write each [`ExportEvent`] to a durable [`EventOutbox`], one
+//! file per event under `.rhema/events/outbox`, before returning. The export
+//! bridge in `rhema-coordination` delivers them and removes a file only once
+//! every routed sink accepted it, so delivery is at least once.
+
+use crate::{RhemaError, RhemaResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Version of the [`ExportEvent`] envelope; bumped on incompatible changes
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
+/// Outbox location relative to the repository root
+pub const EVENT_OUTBOX_DIR: &str = ".rhema/events/outbox";
+
+/// Kind of exported event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportEventType {
+    ActionExecuted,
+    ApprovalGranted,
+    ConflictDetected,
+    HealthDegraded,
+}
+
+impl ExportEventType {
+    pub const ALL: [ExportEventType; 4] = [
+        ExportEventType::ActionExecuted,
+        ExportEventType::ApprovalGranted,
+        ExportEventType::ConflictDetected,
+        ExportEventType::HealthDegraded,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExportEventType::ActionExecuted => "action_executed",
+            ExportEventType::ApprovalGranted => "approval_granted",
+            ExportEventType::ConflictDetected => "conflict_detected",
+            ExportEventType::HealthDegraded => "health_degraded",
+        }
+    }
+}
+
+impl fmt::Display for ExportEventType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ExportEventType {
+    type Err = RhemaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|event_type| event_type.name() == s)
+            .ok_or_else(|| RhemaError::InvalidInput(format!("Unknown event type: {}", s)))
+    }
+}
+
+/// Envelope published to every sink
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportEvent {
+    pub schema_version: u32,
+
+    /// Unique id, for consumers to drop redelivered events
+    pub id: String,
+
+    #[serde(rename = "type")]
+    pub event_type: ExportEventType,
+
+    /// Component that produced the event, e.g. `rhema-action`
+    pub source: String,
+
+    /// What the event is about, such as an intent or conflict id
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+
+    pub occurred_at: DateTime<Utc>,
+
+    /// Event-specific payload
+    pub data: Value,
+}
+
+impl ExportEvent {
+    pub fn new(event_type: ExportEventType, source: impl Into<String>, data: Value) -> Self {
+        Self {
+            schema_version: EVENT_SCHEMA_VERSION,
+            id: uuid::Uuid::new_v4().to_string(),
+            event_type,
+            source: source.into(),
+            subject: None,
+            occurred_at: Utc::now(),
+            data,
+        }
+    }
+
+    pub fn with_subject(mut self, subject: impl Into<String>) -> Self {
+        self.subject = Some(subject.into());
+        self
+    }
+
+    /// Outbox file name, ordering events by occurrence
+    fn file_name(&self) -> String {
+        format!(
+            "{:020}-{}.json",
+            self.occurred_at.timestamp_millis().max(0),
+            self.id
+        )
+    }
+}
+
+/// Durable queue of events awaiting export
+#[derive(Debug, Clone)]
+pub struct EventOutbox {
+    dir: PathBuf,
+}
+
+impl EventOutbox {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The outbox of the repository at `repo_root`
+    pub fn for_repository(repo_root: &Path) -> Self {
+        Self::new(repo_root.join(EVENT_OUTBOX_DIR))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Persist an event. The event is on disk when this returns, and other
+    /// processes never see a partially written file.
+    pub fn publish(&self, event: &ExportEvent) -> RhemaResult<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let json = serde_json::to_vec(event)?;
+        let temp = self.dir.join(format!(".{}.tmp", event.id));
+        let mut file = std::fs::File::create(&temp)?;
+        file.write_all(&json)?;
+        file.sync_all()?;
+        std::fs::rename(&temp, self.dir.join(event.file_name()))?;
+        Ok(())
+    }
+
+    /// Events not yet removed, oldest first
+    pub fn pending(&self) -> RhemaResult<Vec<ExportEvent>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(&self.dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension().is_some_and(|ext| ext == "json")
+                    && !path
+                        .file_name()
+                        .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+            })
+            .collect();
+        paths.sort();
+
+        let mut events = Vec::with_capacity(paths.len());
+        for path in paths {
+            let content = std::fs::read(&path)?;
+            match serde_json::from_slice(&content) {
+                Ok(event) => events.push(event),
+                Err(e) => tracing::warn!("Skipping unreadable event {}: {}", path.display(), e),
+            }
+        }
+        Ok(events)
+    }
+
+    /// Drop a delivered event
+    pub fn remove(&self, event: &ExportEvent) -> RhemaResult<()> {
+        match std::fs::remove_file(self.dir.join(event.file_name())) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_outbox_keeps_events_until_removed() {
+        let temp = TempDir::new().unwrap();
+        let outbox = EventOutbox::for_repository(temp.path());
+        assert!(outbox.pending().unwrap().is_empty());
+
+        let first = ExportEvent::new(
+            ExportEventType::ApprovalGranted,
+            "rhema-action",
+            json!({ "request_id": "r1" }),
+        )
+        .with_subject("intent-1");
+        let mut second = ExportEvent::new(ExportEventType::HealthDegraded, "rhema-core", json!({}));
+        second.occurred_at = first.occurred_at + chrono::Duration::seconds(1);
+        outbox.publish(&second).unwrap();
+        outbox.publish(&first).unwrap();
+
+        assert_eq!(
+            outbox.pending().unwrap(),
+            vec![first.clone(), second.clone()]
+        );
+        let envelope = serde_json::to_value(&first).unwrap();
+        assert_eq!(envelope["type"], "approval_granted");
+        assert_eq!(envelope["schema_version"], EVENT_SCHEMA_VERSION);
+
+        outbox.remove(&first).unwrap();
+        outbox.remove(&first).unwrap();
+        assert_eq!(outbox.pending().unwrap(), vec![second]);
+        assert_eq!(
+            "health_degraded".parse::<ExportEventType>().unwrap(),
+            ExportEventType::HealthDegraded
+        );
+    }
+}
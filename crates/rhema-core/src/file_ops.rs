@@ -14,9 +14,14 @@
  * limitations under the License.
  */
 
+use crate::lifecycle::{self, EntryType, LifecycleEvent};
+use crate::profiling::{self, Phase};
+use crate::review;
+use crate::templates::{self, EntryFields};
 use crate::{
-    Conventions, DecisionEntry, DecisionStatus, Decisions, Knowledge, KnowledgeEntry, PatternEntry,
-    PatternUsage, Patterns, Priority, RhemaError, RhemaResult, TodoEntry, TodoStatus, Todos,
+    Conventions, DecisionEntry, DecisionIncident, DecisionStatus, Decisions, Knowledge,
+    KnowledgeEntry, PatternEntry, PatternUsage, Patterns, Priority, RhemaError, RhemaResult,
+    TodoEntry, TodoStatus, Todos,
 };
 use chrono::Utc;
 use serde_yaml;
@@ -36,12 +41,18 @@ where
         )));
     }
 
-    let content = std::fs::read_to_string(file_path).map_err(|e| RhemaError::IoError(e))?;
+    let content = {
+        let _phase = profiling::phase(Phase::FileIo);
+        std::fs::read_to_string(file_path).map_err(RhemaError::IoError)?
+    };
 
-    let data: T = serde_yaml::from_str(&content).map_err(|e| RhemaError::InvalidYaml {
-        file: file_path.display().to_string(),
-        message: e.to_string(),
-    })?;
+    let data: T = {
+        let _phase = profiling::phase(Phase::Parse);
+        serde_yaml::from_str(&content).map_err(|e| RhemaError::InvalidYaml {
+            file: file_path.display().to_string(),
+            message: e.to_string(),
+        })?
+    };
 
     Ok(data)
 }
@@ -150,6 +161,7 @@ pub fn add_todo(
     priority: Priority,
     assignee: Option<String>,
     due_date: Option<String>,
+    fields: EntryFields,
 ) -> RhemaResult<String> {
     let todos_file = get_or_create_todos_file(scope_path)?;
     let mut todos: Todos = read_yaml_file(&todos_file)?;
@@ -184,11 +196,22 @@ pub fn add_todo(
         completed_at: None,
         outcome: None,
         related_knowledge: None,
-        custom: HashMap::new(),
+        custom: review::submission_fields(),
     };
+    let todo_entry =
+        templates::apply_entry_template(scope_path, EntryType::Todo, todo_entry, fields)?;
 
     todos.todos.push(todo_entry);
     write_yaml_file(&todos_file, &todos)?;
+    if let Some(entry) = todos.todos.last() {
+        lifecycle::emit(
+            scope_path,
+            LifecycleEvent::Create,
+            EntryType::Todo,
+            &id,
+            entry,
+        );
+    }
 
     Ok(id)
 }
@@ -204,6 +227,7 @@ pub fn list_todos(
     let todos: Todos = read_yaml_file(&todos_file)?;
 
     let mut filtered_todos = todos.todos;
+    filtered_todos.retain(|entry| review::is_visible_custom(&entry.custom));
 
     if let Some(status) = status_filter {
         filtered_todos.retain(|todo| todo.status == status);
@@ -234,8 +258,16 @@ pub fn complete_todo(scope_path: &Path, id: &str, outcome: Option<String>) -> Rh
     todo.status = TodoStatus::Completed;
     todo.completed_at = Some(Utc::now());
     todo.outcome = outcome;
+    let todo = todo.clone();
 
     write_yaml_file(&todos_file, &todos)?;
+    lifecycle::emit(
+        scope_path,
+        LifecycleEvent::Complete,
+        EntryType::Todo,
+        id,
+        &todo,
+    );
     Ok(())
 }
 
@@ -265,7 +297,11 @@ pub fn update_todo(
     if let Some(description) = description {
         todo.description = Some(description);
     }
+    let mut event = LifecycleEvent::Update;
     if let Some(status) = status {
+        if status == TodoStatus::Completed && todo.status != TodoStatus::Completed {
+            event = LifecycleEvent::Complete;
+        }
         todo.status = status;
     }
     if let Some(priority) = priority {
@@ -285,8 +321,10 @@ pub fn update_todo(
             .with_timezone(&Utc);
         todo.due_date = Some(due_date_parsed);
     }
+    let todo = todo.clone();
 
     write_yaml_file(&todos_file, &todos)?;
+    lifecycle::emit(scope_path, event, EntryType::Todo, id, &todo);
     Ok(())
 }
 
@@ -317,6 +355,7 @@ pub fn add_knowledge(
     confidence: Option<u8>,
     category: Option<String>,
     tags: Option<String>,
+    fields: EntryFields,
 ) -> RhemaResult<String> {
     let knowledge_file = get_or_create_knowledge_file(scope_path)?;
     let mut knowledge: Knowledge = read_yaml_file(&knowledge_file)?;
@@ -336,11 +375,22 @@ pub fn add_knowledge(
         created_at: now,
         updated_at: None,
         source: None,
-        custom: HashMap::new(),
+        custom: review::submission_fields(),
     };
+    let knowledge_entry =
+        templates::apply_entry_template(scope_path, EntryType::Knowledge, knowledge_entry, fields)?;
 
     knowledge.entries.push(knowledge_entry);
     write_yaml_file(&knowledge_file, &knowledge)?;
+    if let Some(entry) = knowledge.entries.last() {
+        lifecycle::emit(
+            scope_path,
+            LifecycleEvent::Create,
+            EntryType::Knowledge,
+            &id,
+            entry,
+        );
+    }
 
     Ok(id)
 }
@@ -356,6 +406,7 @@ pub fn list_knowledge(
     let knowledge: Knowledge = read_yaml_file(&knowledge_file)?;
 
     let mut filtered_entries = knowledge.entries;
+    filtered_entries.retain(|entry| review::is_visible_custom(&entry.custom));
 
     if let Some(category) = category_filter {
         filtered_entries.retain(|entry| entry.category.as_ref().map_or(false, |c| c == &category));
@@ -415,8 +466,16 @@ pub fn update_knowledge(
     }
 
     entry.updated_at = Some(Utc::now());
+    let entry = entry.clone();
 
     write_yaml_file(&knowledge_file, &knowledge)?;
+    lifecycle::emit(
+        scope_path,
+        LifecycleEvent::Update,
+        EntryType::Knowledge,
+        id,
+        &entry,
+    );
     Ok(())
 }
 
@@ -449,6 +508,7 @@ pub fn add_pattern(
     effectiveness: Option<u8>,
     examples: Option<String>,
     anti_patterns: Option<String>,
+    fields: EntryFields,
 ) -> RhemaResult<String> {
     let patterns_file = get_or_create_patterns_file(scope_path)?;
     let mut patterns: Patterns = read_yaml_file(&patterns_file)?;
@@ -472,11 +532,22 @@ pub fn add_pattern(
         related_patterns: None,
         created_at: now,
         updated_at: None,
-        custom: HashMap::new(),
+        custom: review::submission_fields(),
     };
+    let pattern_entry =
+        templates::apply_entry_template(scope_path, EntryType::Pattern, pattern_entry, fields)?;
 
     patterns.patterns.push(pattern_entry);
     write_yaml_file(&patterns_file, &patterns)?;
+    if let Some(entry) = patterns.patterns.last() {
+        lifecycle::emit(
+            scope_path,
+            LifecycleEvent::Create,
+            EntryType::Pattern,
+            &id,
+            entry,
+        );
+    }
 
     Ok(id)
 }
@@ -492,6 +563,7 @@ pub fn list_patterns(
     let patterns: Patterns = read_yaml_file(&patterns_file)?;
 
     let mut filtered_patterns = patterns.patterns;
+    filtered_patterns.retain(|entry| review::is_visible_custom(&entry.custom));
 
     if let Some(pattern_type) = pattern_type_filter {
         filtered_patterns.retain(|pattern| pattern.pattern_type == pattern_type);
@@ -558,8 +630,16 @@ pub fn update_pattern(
     }
 
     pattern.updated_at = Some(Utc::now());
+    let pattern = pattern.clone();
 
     write_yaml_file(&patterns_file, &patterns)?;
+    lifecycle::emit(
+        scope_path,
+        LifecycleEvent::Update,
+        EntryType::Pattern,
+        id,
+        &pattern,
+    );
     Ok(())
 }
 
@@ -593,6 +673,7 @@ pub fn add_decision(
     alternatives: Option<String>,
     rationale: Option<String>,
     consequences: Option<String>,
+    fields: EntryFields,
 ) -> RhemaResult<String> {
     let decisions_file = get_or_create_decisions_file(scope_path)?;
     let mut decisions: Decisions = read_yaml_file(&decisions_file)?;
@@ -618,11 +699,26 @@ pub fn add_decision(
         decided_at: now,
         review_date: None,
         decision_makers: makers_vec,
-        custom: HashMap::new(),
+        implementing_commits: None,
+        incidents: None,
+        reversed_by: None,
+        reverses: None,
+        custom: review::submission_fields(),
     };
+    let decision_entry =
+        templates::apply_entry_template(scope_path, EntryType::Decision, decision_entry, fields)?;
 
     decisions.decisions.push(decision_entry);
     write_yaml_file(&decisions_file, &decisions)?;
+    if let Some(entry) = decisions.decisions.last() {
+        lifecycle::emit(
+            scope_path,
+            LifecycleEvent::Create,
+            EntryType::Decision,
+            &id,
+            entry,
+        );
+    }
 
     Ok(id)
 }
@@ -637,6 +733,7 @@ pub fn list_decisions(
     let decisions: Decisions = read_yaml_file(&decisions_file)?;
 
     let mut filtered_decisions = decisions.decisions;
+    filtered_decisions.retain(|entry| review::is_visible_custom(&entry.custom));
 
     if let Some(status) = status_filter {
         filtered_decisions.retain(|decision| decision.status == status);
@@ -682,7 +779,11 @@ pub fn update_decision(
     if let Some(description) = description {
         decision.description = description;
     }
+    let mut event = LifecycleEvent::Update;
     if let Some(status) = status {
+        if status == DecisionStatus::Approved && decision.status != DecisionStatus::Approved {
+            event = LifecycleEvent::Complete;
+        }
         decision.status = status;
     }
     if let Some(context) = context {
@@ -710,6 +811,94 @@ pub fn update_decision(
                 .collect(),
         );
     }
+    let decision = decision.clone();
+
+    write_yaml_file(&decisions_file, &decisions)?;
+    lifecycle::emit(scope_path, event, EntryType::Decision, id, &decision);
+    Ok(())
+}
+
+/// Link a commit implementing a decision
+pub fn link_decision_commit(scope_path: &Path, id: &str, commit: &str) -> RhemaResult<()> {
+    let decisions_file = get_or_create_decisions_file(scope_path)?;
+    let mut decisions: Decisions = read_yaml_file(&decisions_file)?;
+
+    let decision = decisions
+        .decisions
+        .iter_mut()
+        .find(|d| d.id == id)
+        .ok_or_else(|| RhemaError::ConfigError(format!("Decision with ID {} not found", id)))?;
+
+    let commits = decision.implementing_commits.get_or_insert_with(Vec::new);
+    if !commits.iter().any(|c| c == commit) {
+        commits.push(commit.to_string());
+    }
+
+    write_yaml_file(&decisions_file, &decisions)?;
+    Ok(())
+}
+
+/// Attribute an incident to a decision
+pub fn link_decision_incident(
+    scope_path: &Path,
+    id: &str,
+    incident: DecisionIncident,
+) -> RhemaResult<()> {
+    let decisions_file = get_or_create_decisions_file(scope_path)?;
+    let mut decisions: Decisions = read_yaml_file(&decisions_file)?;
+
+    let decision = decisions
+        .decisions
+        .iter_mut()
+        .find(|d| d.id == id)
+        .ok_or_else(|| RhemaError::ConfigError(format!("Decision with ID {} not found", id)))?;
+
+    let incidents = decision.incidents.get_or_insert_with(Vec::new);
+    incidents.retain(|i| i.id != incident.id);
+    incidents.push(incident);
+
+    write_yaml_file(&decisions_file, &decisions)?;
+    Ok(())
+}
+
+/// Record that `reversing_id` reverses decision `id`, deprecating the original
+pub fn record_decision_reversal(
+    scope_path: &Path,
+    id: &str,
+    reversing_id: &str,
+) -> RhemaResult<()> {
+    if id == reversing_id {
+        return Err(RhemaError::ValidationError(format!(
+            "Decision {} cannot reverse itself",
+            id
+        )));
+    }
+
+    let decisions_file = get_or_create_decisions_file(scope_path)?;
+    let mut decisions: Decisions = read_yaml_file(&decisions_file)?;
+
+    if !decisions.decisions.iter().any(|d| d.id == reversing_id) {
+        return Err(RhemaError::ConfigError(format!(
+            "Decision with ID {} not found",
+            reversing_id
+        )));
+    }
+
+    let original = decisions
+        .decisions
+        .iter_mut()
+        .find(|d| d.id == id)
+        .ok_or_else(|| RhemaError::ConfigError(format!("Decision with ID {} not found", id)))?;
+    original.reversed_by = Some(reversing_id.to_string());
+    original.status = DecisionStatus::Deprecated;
+
+    if let Some(reversal) = decisions
+        .decisions
+        .iter_mut()
+        .find(|d| d.id == reversing_id)
+    {
+        reversal.reverses = Some(id.to_string());
+    }
 
     write_yaml_file(&decisions_file, &decisions)?;
     Ok(())
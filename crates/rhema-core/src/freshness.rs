@@ -0,0 +1,470 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Freshness SLAs, declared as `freshness` in `rhema.yaml`.
+//!
+//! An SLA names a kind of entry and how many days may pass before each entry
+//! must be looked at again, e.g. knowledge every 90 days and decisions every
+//! 365. An entry was last looked at when it was most recently created,
+//! updated, decided, approved in review or marked `reviewed_at`. Entries older
+//! than their SLA are breaches; [`create_review_todos`] turns them into
+//! review todos assigned to the scope's owners, which reach the configured
+//! lifecycle hooks like any other new todo.
+
+use crate::file_ops::{get_or_create_todos_file, read_yaml_file, write_yaml_file};
+use crate::lifecycle::{self, EntryType, LifecycleEvent};
+use crate::review::{self, ReviewRecord, REVIEW_FIELD};
+use crate::schema::{Priority, TodoEntry, TodoStatus, Todos};
+use crate::scope::Scope;
+use crate::{RhemaError, RhemaResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::collections::HashSet;
+use std::fmt;
+use uuid::Uuid;
+
+/// Todo field naming the entry a review todo was created for
+pub const FRESHNESS_REVIEW_FIELD: &str = "freshness_review";
+
+/// Entry fields recording when an entry was last looked at
+const TIMESTAMP_FIELDS: [&str; 4] = ["reviewed_at", "updated_at", "decided_at", "created_at"];
+
+/// Statuses of entries that are retired and need no review
+const RETIRED_STATUSES: [&str; 3] = ["deprecated", "rejected", "superseded"];
+
+/// Kind of entry an SLA applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FreshnessTarget {
+    Knowledge,
+    Decisions,
+    Patterns,
+    Conventions,
+}
+
+impl FreshnessTarget {
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            FreshnessTarget::Knowledge => "knowledge.yaml",
+            FreshnessTarget::Decisions => "decisions.yaml",
+            FreshnessTarget::Patterns => "patterns.yaml",
+            FreshnessTarget::Conventions => "conventions.yaml",
+        }
+    }
+
+    fn entry_noun(&self) -> &'static str {
+        match self {
+            FreshnessTarget::Knowledge => "knowledge entry",
+            FreshnessTarget::Decisions => "decision",
+            FreshnessTarget::Patterns => "pattern",
+            FreshnessTarget::Conventions => "convention",
+        }
+    }
+}
+
+impl fmt::Display for FreshnessTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            FreshnessTarget::Knowledge => "knowledge",
+            FreshnessTarget::Decisions => "decisions",
+            FreshnessTarget::Patterns => "patterns",
+            FreshnessTarget::Conventions => "conventions",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// How often entries of one kind must be reviewed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FreshnessSla {
+    pub entries: FreshnessTarget,
+
+    /// Days an entry may go without review
+    pub max_age_days: u32,
+}
+
+/// `freshness` block of a scope definition
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FreshnessPolicy {
+    /// Who reviews stale entries; review todos are spread across them in turn
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub owners: Vec<String>,
+
+    #[serde(default)]
+    pub slas: Vec<FreshnessSla>,
+}
+
+impl FreshnessPolicy {
+    pub fn validate(&self) -> RhemaResult<()> {
+        let mut seen = HashSet::new();
+        for sla in &self.slas {
+            if sla.max_age_days == 0 {
+                return Err(RhemaError::ValidationError(format!(
+                    "Freshness SLA for {} needs a max_age_days above 0",
+                    sla.entries
+                )));
+            }
+            if !seen.insert(sla.entries) {
+                return Err(RhemaError::ValidationError(format!(
+                    "Duplicate freshness SLA for {}",
+                    sla.entries
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An entry older than its SLA
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FreshnessBreach {
+    pub entry_id: String,
+    pub title: Option<String>,
+    /// `None` when the entry carries no timestamp at all
+    pub last_reviewed: Option<DateTime<Utc>>,
+    pub age_days: Option<i64>,
+}
+
+/// Compliance with one SLA
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlaCompliance {
+    pub entries: FreshnessTarget,
+    pub max_age_days: u32,
+    /// Live entries the SLA covers
+    pub total: usize,
+    pub fresh: usize,
+    /// Oldest first
+    pub breaches: Vec<FreshnessBreach>,
+}
+
+impl SlaCompliance {
+    /// Share of entries within the SLA (0.0-1.0)
+    pub fn compliance(&self) -> f64 {
+        if self.total == 0 {
+            return 1.0;
+        }
+        self.fresh as f64 / self.total as f64
+    }
+}
+
+/// Compliance of one scope
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScopeFreshness {
+    pub scope: String,
+    pub owners: Vec<String>,
+    pub slas: Vec<SlaCompliance>,
+}
+
+impl ScopeFreshness {
+    pub fn breach_count(&self) -> usize {
+        self.slas.iter().map(|sla| sla.breaches.len()).sum()
+    }
+}
+
+/// Compliance across scopes that declare SLAs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FreshnessReport {
+    pub generated_at: DateTime<Utc>,
+    pub scopes: Vec<ScopeFreshness>,
+}
+
+impl FreshnessReport {
+    pub fn breach_count(&self) -> usize {
+        self.scopes.iter().map(ScopeFreshness::breach_count).sum()
+    }
+
+    /// Share of covered entries within their SLA (0.0-1.0)
+    pub fn compliance(&self) -> f64 {
+        let slas = self.scopes.iter().flat_map(|scope| &scope.slas);
+        let (fresh, total) = slas.fold((0, 0), |(fresh, total), sla| {
+            (fresh + sla.fresh, total + sla.total)
+        });
+        if total == 0 {
+            return 1.0;
+        }
+        fresh as f64 / total as f64
+    }
+}
+
+/// Check every scope that declares SLAs
+pub fn check(scopes: &[Scope], now: DateTime<Utc>) -> RhemaResult<FreshnessReport> {
+    let mut checked = Vec::new();
+    for scope in scopes {
+        if let Some(freshness) = check_scope(scope, now)? {
+            checked.push(freshness);
+        }
+    }
+    checked.sort_by(|a, b| a.scope.cmp(&b.scope));
+    Ok(FreshnessReport {
+        generated_at: now,
+        scopes: checked,
+    })
+}
+
+/// Check one scope, or `None` when it declares no SLAs
+pub fn check_scope(scope: &Scope, now: DateTime<Utc>) -> RhemaResult<Option<ScopeFreshness>> {
+    let Some(policy) = scope.definition.freshness.as_ref() else {
+        return Ok(None);
+    };
+    if policy.slas.is_empty() {
+        return Ok(None);
+    }
+
+    let mut slas = Vec::new();
+    for sla in &policy.slas {
+        let path = scope.path.join(sla.entries.file_name());
+        let document: Value = if path.exists() {
+            read_yaml_file(&path)?
+        } else {
+            Value::Null
+        };
+
+        let mut compliance = SlaCompliance {
+            entries: sla.entries,
+            max_age_days: sla.max_age_days,
+            total: 0,
+            fresh: 0,
+            breaches: Vec::new(),
+        };
+        let live = document
+            .as_mapping()
+            .into_iter()
+            .flat_map(|map| map.values())
+            .filter_map(Value::as_sequence)
+            .flatten()
+            .filter(|entry| review::is_visible(entry) && !is_retired(entry));
+        for entry in live {
+            compliance.total += 1;
+            let reviewed_at = last_reviewed(entry);
+            let age_days = reviewed_at.map(|at| (now - at).num_days());
+            if age_days.is_some_and(|age| age <= sla.max_age_days as i64) {
+                compliance.fresh += 1;
+                continue;
+            }
+            compliance.breaches.push(FreshnessBreach {
+                entry_id: field(entry, "id").unwrap_or_default(),
+                title: field(entry, "title").or_else(|| field(entry, "name")),
+                last_reviewed: reviewed_at,
+                age_days,
+            });
+        }
+        compliance
+            .breaches
+            .sort_by_key(|breach| breach.last_reviewed);
+        slas.push(compliance);
+    }
+
+    Ok(Some(ScopeFreshness {
+        scope: scope.definition.name.clone(),
+        owners: policy.owners.clone(),
+        slas,
+    }))
+}
+
+/// Add a review todo for each breach that has no open one yet, assigned to
+/// the scope's owners in turn, or to `fallback_owner` when it declares none.
+/// Returns the ids of the created todos.
+pub fn create_review_todos(
+    scope: &Scope,
+    freshness: &ScopeFreshness,
+    fallback_owner: Option<&str>,
+) -> RhemaResult<Vec<String>> {
+    let todos_file = get_or_create_todos_file(&scope.path)?;
+    let mut todos: Todos = read_yaml_file(&todos_file)?;
+
+    let open_reviews: HashSet<String> = todos
+        .todos
+        .iter()
+        .filter(|todo| !matches!(todo.status, TodoStatus::Completed | TodoStatus::Cancelled))
+        .filter_map(|todo| todo.custom.get(FRESHNESS_REVIEW_FIELD))
+        .filter_map(|value| value.as_str().map(str::to_string))
+        .collect();
+
+    let owners: Vec<String> = if freshness.owners.is_empty() {
+        fallback_owner.map(str::to_string).into_iter().collect()
+    } else {
+        freshness.owners.clone()
+    };
+
+    let now = Utc::now();
+    let first_new = todos.todos.len();
+    for sla in &freshness.slas {
+        for breach in &sla.breaches {
+            if breach.entry_id.is_empty() || open_reviews.contains(&breach.entry_id) {
+                continue;
+            }
+            let created = todos.todos.len() - first_new;
+            let mut custom = review::submission_fields();
+            custom.insert(
+                FRESHNESS_REVIEW_FIELD.to_string(),
+                Value::String(breach.entry_id.clone()),
+            );
+            let last_reviewed = match breach.last_reviewed {
+                Some(at) => format!("last reviewed {}", at.format("%Y-%m-%d")),
+                None => "never reviewed".to_string(),
+            };
+            todos.todos.push(TodoEntry {
+                id: Uuid::new_v4().to_string(),
+                title: format!(
+                    "Review {} '{}'",
+                    sla.entries.entry_noun(),
+                    breach.title.as_deref().unwrap_or(&breach.entry_id)
+                ),
+                description: Some(format!(
+                    "The {} scope expects {} to be reviewed every {} days; {} ({}) was {}.",
+                    freshness.scope,
+                    sla.entries,
+                    sla.max_age_days,
+                    breach.entry_id,
+                    sla.entries.file_name(),
+                    last_reviewed
+                )),
+                status: TodoStatus::Pending,
+                priority: Priority::Medium,
+                assigned_to: (!owners.is_empty()).then(|| owners[created % owners.len()].clone()),
+                due_date: None,
+                created_at: now,
+                completed_at: None,
+                outcome: None,
+                related_knowledge: (sla.entries == FreshnessTarget::Knowledge)
+                    .then(|| vec![breach.entry_id.clone()]),
+                custom,
+            });
+        }
+    }
+
+    if todos.todos.len() == first_new {
+        return Ok(Vec::new());
+    }
+    write_yaml_file(&todos_file, &todos)?;
+
+    let created = &todos.todos[first_new..];
+    for todo in created {
+        lifecycle::emit(
+            &scope.path,
+            LifecycleEvent::Create,
+            EntryType::Todo,
+            &todo.id,
+            todo,
+        );
+    }
+    Ok(created.iter().map(|todo| todo.id.clone()).collect())
+}
+
+fn field(entry: &Value, name: &str) -> Option<String> {
+    entry.get(name).and_then(Value::as_str).map(str::to_string)
+}
+
+fn is_retired(entry: &Value) -> bool {
+    field(entry, "status")
+        .is_some_and(|status| RETIRED_STATUSES.contains(&status.to_lowercase().as_str()))
+}
+
+/// Most recent time anyone created, changed or reviewed the entry
+fn last_reviewed(entry: &Value) -> Option<DateTime<Utc>> {
+    let approved = entry
+        .get(REVIEW_FIELD)
+        .and_then(|record| serde_yaml::from_value::<ReviewRecord>(record.clone()).ok())
+        .and_then(|record| record.reviewed_at);
+    TIMESTAMP_FIELDS
+        .iter()
+        .filter_map(|name| field(entry, name))
+        .filter_map(|value| DateTime::parse_from_rfc3339(&value).ok())
+        .map(|at| at.with_timezone(&Utc))
+        .chain(approved)
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write_scope(dir: &std::path::Path) -> Scope {
+        fs::write(
+            dir.join("rhema.yaml"),
+            "name: docs\nscope_type: service\nversion: \"1.0.0\"\nfreshness:\n  owners: [alice, bob]\n  slas:\n    - entries: knowledge\n      max_age_days: 90\n    - entries: decisions\n      max_age_days: 365\n",
+        )
+        .unwrap();
+        let now = Utc::now();
+        let knowledge = format!(
+            "entries:\n  - id: k1\n    title: Caching\n    created_at: \"{}\"\n  - id: k2\n    title: Deploys\n    created_at: \"{}\"\n    updated_at: \"{}\"\n  - id: k3\n    title: Retries\n    created_at: \"{}\"\n",
+            (now - Duration::days(200)).to_rfc3339(),
+            (now - Duration::days(400)).to_rfc3339(),
+            (now - Duration::days(10)).to_rfc3339(),
+            (now - Duration::days(120)).to_rfc3339(),
+        );
+        fs::write(dir.join("knowledge.yaml"), knowledge).unwrap();
+        Scope::new(dir.to_path_buf()).unwrap()
+    }
+
+    #[test]
+    fn test_breaches_and_compliance() {
+        let temp = TempDir::new().unwrap();
+        let scope = write_scope(temp.path());
+
+        let report = check(std::slice::from_ref(&scope), Utc::now()).unwrap();
+        assert_eq!(report.breach_count(), 2);
+        let knowledge = &report.scopes[0].slas[0];
+        assert_eq!(knowledge.total, 3);
+        assert_eq!(knowledge.fresh, 1);
+        let breached: Vec<&str> = knowledge
+            .breaches
+            .iter()
+            .map(|breach| breach.entry_id.as_str())
+            .collect();
+        assert_eq!(breached, vec!["k1", "k3"]);
+        assert_eq!(report.scopes[0].slas[1].total, 0);
+        assert!((report.compliance() - 1.0 / 3.0).abs() < 1e-9);
+
+        let invalid = FreshnessPolicy {
+            owners: Vec::new(),
+            slas: vec![FreshnessSla {
+                entries: FreshnessTarget::Knowledge,
+                max_age_days: 0,
+            }],
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_review_todos_are_assigned_and_not_duplicated() {
+        let temp = TempDir::new().unwrap();
+        let scope = write_scope(temp.path());
+        let freshness = check_scope(&scope, Utc::now()).unwrap().unwrap();
+
+        let created = create_review_todos(&scope, &freshness, None).unwrap();
+        assert_eq!(created.len(), 2);
+        assert!(create_review_todos(&scope, &freshness, None)
+            .unwrap()
+            .is_empty());
+
+        let todos: Todos = read_yaml_file(&temp.path().join("todos.yaml")).unwrap();
+        let assignees: Vec<_> = todos
+            .todos
+            .iter()
+            .map(|todo| todo.assigned_to.clone().unwrap())
+            .collect();
+        assert_eq!(assignees, vec!["alice", "bob"]);
+        assert_eq!(
+            todos.todos[0].custom[FRESHNESS_REVIEW_FIELD],
+            Value::String("k1".to_string())
+        );
+    }
+}
@@ -0,0 +1,520 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Garbage collection of orphaned artifacts under `.rhema/`.
+//!
+//! Each [`ArtifactCollector`] owns one kind of artifact. It traces what the
+//! current context and actions still refer to and reports everything else as
+//! [`Garbage`]. [`GarbageCollector`] runs the collectors, totals the space
+//! that can be reclaimed and, unless it is a dry run, deletes the garbage.
+//! Nothing modified within the grace period is collected, so a collection
+//! cannot race a writer that has not linked its artifacts yet. [`GcJob`] runs
+//! collections on the [`JobQueue`].
+
+use crate::jobs::{Job, JobContext, JobHandler, JobQueue, JobSpec, JobStatus};
+use crate::policy;
+use crate::snapshot::SnapshotManager;
+use crate::{RhemaError, RhemaResult};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+/// Section of `.rhema/repository.yaml` configuring garbage collection
+pub const GC_CONFIG_SECTION: &str = "gc";
+
+/// Job kind running a garbage collection
+pub const GC_JOB: &str = "maintenance.gc";
+
+/// Cache directory, relative to the repository root
+pub const CACHE_DIR: &str = ".rhema/cache";
+
+/// `gc` section of the repository config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GcConfig {
+    /// Artifacts modified more recently than this are never collected
+    pub grace_period_secs: u64,
+
+    /// Cache files not modified for this many days are stale
+    pub cache_max_age_days: u64,
+
+    /// Superseded indexes and rollback backups are kept this many days
+    pub retention_days: u64,
+
+    /// Collect on the job queue every `interval_secs`
+    pub scheduled: bool,
+    pub interval_secs: u64,
+}
+
+impl Default for GcConfig {
+    fn default() -> Self {
+        Self {
+            grace_period_secs: 3_600,
+            cache_max_age_days: 30,
+            retention_days: 14,
+            scheduled: false,
+            interval_secs: 86_400,
+        }
+    }
+}
+
+impl GcConfig {
+    pub fn load(repo_root: &Path) -> RhemaResult<Self> {
+        let value = policy::repository_config(repo_root)?;
+        match value.get(GC_CONFIG_SECTION) {
+            Some(section) => serde_yaml::from_value(section.clone()).map_err(|e| {
+                RhemaError::ConfigError(format!(
+                    "Invalid {} section in the repository config: {}",
+                    GC_CONFIG_SECTION, e
+                ))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Whether `path` was last modified before the grace period
+    pub fn past_grace_period(&self, path: &Path) -> bool {
+        modified_before(path, Duration::from_secs(self.grace_period_secs))
+    }
+
+    /// Whether `path` was last modified before the retention period
+    pub fn past_retention(&self, path: &Path) -> bool {
+        modified_before(path, days(self.retention_days))
+    }
+}
+
+fn days(days: u64) -> Duration {
+    Duration::from_secs(days.saturating_mul(86_400))
+}
+
+/// Whether `path` exists and was last modified more than `age` ago
+pub fn modified_before(path: &Path, age: Duration) -> bool {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .is_some_and(|elapsed| elapsed > age)
+}
+
+/// Bytes taken by a file, or by everything under a directory
+pub fn path_size(path: &Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|metadata| metadata.is_file())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// An artifact nothing refers to any more
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Garbage {
+    /// Name of the collector that found it
+    pub collector: String,
+
+    /// Relative to the repository root when inside it, absolute otherwise
+    pub path: PathBuf,
+
+    /// Space freed by reclaiming it
+    pub bytes: u64,
+    pub reason: String,
+
+    /// Entries to drop from `path` instead of deleting it
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entries: Vec<String>,
+}
+
+impl Garbage {
+    /// A whole file or directory, sized from disk
+    pub fn path(collector: &str, repo_root: &Path, path: &Path, reason: impl Into<String>) -> Self {
+        Self {
+            collector: collector.to_string(),
+            path: path.strip_prefix(repo_root).unwrap_or(path).to_path_buf(),
+            bytes: path_size(path),
+            reason: reason.into(),
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn resolve(&self, repo_root: &Path) -> PathBuf {
+        repo_root.join(&self.path)
+    }
+}
+
+/// Finds and reclaims one kind of artifact
+pub trait ArtifactCollector: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Unreachable artifacts; must not change anything on disk
+    fn collect(&self, repo_root: &Path, config: &GcConfig) -> RhemaResult<Vec<Garbage>>;
+
+    /// Reclaim one artifact found by [`collect`](Self::collect), returning
+    /// the bytes freed. Deletes the file or directory by default
+    fn reclaim(&self, repo_root: &Path, garbage: &Garbage) -> RhemaResult<u64> {
+        remove_path(&garbage.resolve(repo_root))
+    }
+}
+
+/// Delete a file or directory, returning the bytes freed
+pub fn remove_path(path: &Path) -> RhemaResult<u64> {
+    let bytes = path_size(path);
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(path)?,
+        Ok(_) => fs::remove_file(path)?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e.into()),
+    }
+    Ok(bytes)
+}
+
+/// Outcome of a collection
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GcReport {
+    pub dry_run: bool,
+    pub garbage: Vec<Garbage>,
+    pub reclaimable_bytes: u64,
+    /// Zero on a dry run
+    pub reclaimed_bytes: u64,
+
+    /// Collectors and artifacts that failed; the rest of the collection
+    /// still runs
+    pub errors: Vec<String>,
+}
+
+/// Artifacts and bytes one collector found
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectorSummary {
+    pub artifacts: usize,
+    pub bytes: u64,
+}
+
+impl GcReport {
+    /// Totals per collector
+    pub fn summary(&self) -> BTreeMap<String, CollectorSummary> {
+        let mut summary: BTreeMap<String, CollectorSummary> = BTreeMap::new();
+        for garbage in &self.garbage {
+            let entry = summary.entry(garbage.collector.clone()).or_default();
+            entry.artifacts += 1;
+            entry.bytes += garbage.bytes;
+        }
+        summary
+    }
+}
+
+/// Runs artifact collectors over a repository
+pub struct GarbageCollector {
+    repo_root: PathBuf,
+    config: GcConfig,
+    collectors: Vec<Arc<dyn ArtifactCollector>>,
+}
+
+impl GarbageCollector {
+    /// Collector with the built-in snapshot object and cache collectors
+    pub fn new(repo_root: impl Into<PathBuf>, config: GcConfig) -> Self {
+        Self {
+            repo_root: repo_root.into(),
+            config,
+            collectors: vec![Arc::new(SnapshotObjectCollector), Arc::new(CacheCollector)],
+        }
+    }
+
+    /// Collector of a repository, configured from its repository config
+    pub fn open(repo_root: &Path) -> RhemaResult<Self> {
+        Ok(Self::new(repo_root, GcConfig::load(repo_root)?))
+    }
+
+    pub fn with_collector(mut self, collector: Arc<dyn ArtifactCollector>) -> Self {
+        self.collectors.push(collector);
+        self
+    }
+
+    pub fn config(&self) -> &GcConfig {
+        &self.config
+    }
+
+    /// Find unreachable artifacts and, unless `dry_run`, reclaim them
+    pub fn collect(&self, dry_run: bool) -> RhemaResult<GcReport> {
+        let mut report = GcReport {
+            dry_run,
+            ..Default::default()
+        };
+        for collector in &self.collectors {
+            let garbage = match collector.collect(&self.repo_root, &self.config) {
+                Ok(garbage) => garbage,
+                Err(e) => {
+                    report.errors.push(format!("{}: {}", collector.name(), e));
+                    continue;
+                }
+            };
+            for garbage in garbage {
+                if !inside_rhema_dir(&garbage.path) {
+                    report.errors.push(format!(
+                        "{}: refusing to collect {} outside a .rhema directory",
+                        collector.name(),
+                        garbage.path.display()
+                    ));
+                    continue;
+                }
+                report.reclaimable_bytes += garbage.bytes;
+                if !dry_run {
+                    match collector.reclaim(&self.repo_root, &garbage) {
+                        Ok(bytes) => report.reclaimed_bytes += bytes,
+                        Err(e) => report.errors.push(format!(
+                            "{}: failed to reclaim {}: {}",
+                            collector.name(),
+                            garbage.path.display(),
+                            e
+                        )),
+                    }
+                }
+                report.garbage.push(garbage);
+            }
+        }
+        Ok(report)
+    }
+}
+
+/// Garbage is only ever collected from `.rhema` directories
+fn inside_rhema_dir(path: &Path) -> bool {
+    path.components()
+        .any(|component| component == Component::Normal(".rhema".as_ref()))
+        && !path.components().any(|c| c == Component::ParentDir)
+}
+
+/// Snapshot contents no snapshot manifest refers to, e.g. after
+/// `rhema snapshot delete`
+pub struct SnapshotObjectCollector;
+
+impl SnapshotObjectCollector {
+    fn unreferenced(repo_root: &Path, config: &GcConfig) -> RhemaResult<Vec<PathBuf>> {
+        let manager = SnapshotManager::new(repo_root);
+        let objects_dir = manager.objects_dir();
+        if !objects_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        let referenced = manager.referenced_objects()?;
+        let mut unreferenced = Vec::new();
+        for entry in fs::read_dir(&objects_dir)? {
+            let path = entry?.path();
+            let hash = path.file_name().unwrap_or_default().to_string_lossy();
+            if !referenced.contains(hash.as_ref()) && config.past_grace_period(&path) {
+                unreferenced.push(path);
+            }
+        }
+        Ok(unreferenced)
+    }
+}
+
+impl ArtifactCollector for SnapshotObjectCollector {
+    fn name(&self) -> &str {
+        "snapshot_objects"
+    }
+
+    fn collect(&self, repo_root: &Path, config: &GcConfig) -> RhemaResult<Vec<Garbage>> {
+        Ok(Self::unreferenced(repo_root, config)?
+            .into_iter()
+            .map(|path| {
+                Garbage::path(
+                    self.name(),
+                    repo_root,
+                    &path,
+                    "not referenced by any snapshot",
+                )
+            })
+            .collect())
+    }
+
+    fn reclaim(&self, repo_root: &Path, garbage: &Garbage) -> RhemaResult<u64> {
+        // A snapshot taken since the scan may have linked the object again
+        let manager = SnapshotManager::new(repo_root);
+        let hash = garbage
+            .path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy();
+        if manager.referenced_objects()?.contains(hash.as_ref()) {
+            return Ok(0);
+        }
+        remove_path(&garbage.resolve(repo_root))
+    }
+}
+
+/// Files under `.rhema/cache` not modified for `cache_max_age_days`
+pub struct CacheCollector;
+
+impl ArtifactCollector for CacheCollector {
+    fn name(&self) -> &str {
+        "cache"
+    }
+
+    fn collect(&self, repo_root: &Path, config: &GcConfig) -> RhemaResult<Vec<Garbage>> {
+        let cache_dir = repo_root.join(CACHE_DIR);
+        let max_age = days(config.cache_max_age_days);
+        let reason = format!("not used for {} days", config.cache_max_age_days);
+        Ok(walkdir::WalkDir::new(&cache_dir)
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_type().is_file())
+            .filter(|entry| modified_before(entry.path(), max_age))
+            .map(|entry| Garbage::path(self.name(), repo_root, entry.path(), reason.clone()))
+            .collect())
+    }
+}
+
+/// Runs a collection on the job queue as [`GC_JOB`]. The payload may set
+/// `dry_run` to only report what would be reclaimed
+pub struct GcJob {
+    collector: Arc<GarbageCollector>,
+}
+
+impl GcJob {
+    pub fn new(collector: Arc<GarbageCollector>) -> Self {
+        Self { collector }
+    }
+}
+
+#[async_trait]
+impl JobHandler for GcJob {
+    async fn run(&self, job: &Job, _context: &JobContext) -> RhemaResult<serde_json::Value> {
+        let dry_run = job.payload["dry_run"].as_bool().unwrap_or(false);
+        let report = self.collector.collect(dry_run)?;
+        for error in &report.errors {
+            tracing::warn!("Garbage collection: {}", error);
+        }
+        Ok(serde_json::json!({
+            "dry_run": report.dry_run,
+            "reclaimable_bytes": report.reclaimable_bytes,
+            "reclaimed_bytes": report.reclaimed_bytes,
+            "collectors": report.summary(),
+            "errors": report.errors,
+        }))
+    }
+}
+
+/// Schedule garbage collections on the job queue, replacing any collection
+/// already queued. Returns the first collection job, or `None` when
+/// scheduling is off
+pub fn schedule_gc(queue: &JobQueue, config: &GcConfig) -> RhemaResult<Option<Job>> {
+    for job in queue.list(Some(JobStatus::Queued))? {
+        if job.kind == GC_JOB {
+            queue.cancel(&job.id)?;
+        }
+    }
+    if !config.scheduled {
+        return Ok(None);
+    }
+    let job = queue.enqueue(
+        JobSpec::new(GC_JOB, serde_json::json!({ "dry_run": false }))
+            .with_repeat_every(Duration::from_secs(config.interval_secs.max(3_600))),
+    )?;
+    Ok(Some(job))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn age(path: &Path, days_ago: u64) {
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(SystemTime::now() - days(days_ago))
+            .unwrap();
+    }
+
+    fn setup_repo(root: &Path) {
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::create_dir_all(root.join(".rhema")).unwrap();
+        fs::write(
+            root.join(".rhema").join("rhema.yaml"),
+            "name: app\nscope_type: service\nversion: 1.0.0\n",
+        )
+        .unwrap();
+        fs::write(root.join(".rhema").join("todos.yaml"), "todos: []\n").unwrap();
+    }
+
+    #[test]
+    fn test_collects_unreferenced_snapshot_objects_and_stale_caches() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        setup_repo(root);
+        let snapshots = SnapshotManager::new(root);
+        snapshots.create("kept", None, false).unwrap();
+        fs::write(root.join(".rhema").join("todos.yaml"), "todos: [] # v2\n").unwrap();
+        snapshots.create("dropped", None, false).unwrap();
+        snapshots.delete("dropped").unwrap();
+        for entry in fs::read_dir(snapshots.objects_dir()).unwrap() {
+            age(&entry.unwrap().path(), 1);
+        }
+
+        let cache_dir = root.join(CACHE_DIR);
+        fs::create_dir_all(&cache_dir).unwrap();
+        fs::write(cache_dir.join("stale.json"), "{}").unwrap();
+        fs::write(cache_dir.join("fresh.json"), "{}").unwrap();
+        age(&cache_dir.join("stale.json"), 45);
+
+        let gc = GarbageCollector::new(root, GcConfig::default());
+        let preview = gc.collect(true).unwrap();
+        assert!(preview.errors.is_empty(), "{:?}", preview.errors);
+        let summary = preview.summary();
+        assert_eq!(summary["snapshot_objects"].artifacts, 1);
+        assert_eq!(summary["cache"].artifacts, 1);
+        assert_eq!(preview.reclaimed_bytes, 0);
+        assert!(cache_dir.join("stale.json").exists());
+
+        let report = gc.collect(false).unwrap();
+        assert_eq!(report.reclaimed_bytes, report.reclaimable_bytes);
+        assert!(!cache_dir.join("stale.json").exists());
+        assert!(cache_dir.join("fresh.json").exists());
+        // `rhema.yaml` is shared; only the second `todos.yaml` went away
+        assert_eq!(fs::read_dir(snapshots.objects_dir()).unwrap().count(), 2);
+        snapshots.restore("kept", false).unwrap();
+        assert!(gc.collect(true).unwrap().garbage.is_empty());
+    }
+
+    #[test]
+    fn test_refuses_garbage_outside_rhema_directories() {
+        struct Stray;
+        impl ArtifactCollector for Stray {
+            fn name(&self) -> &str {
+                "stray"
+            }
+            fn collect(&self, repo_root: &Path, _config: &GcConfig) -> RhemaResult<Vec<Garbage>> {
+                Ok(vec![
+                    Garbage::path("stray", repo_root, &repo_root.join("src"), "test"),
+                    Garbage::path("stray", repo_root, &repo_root.join(".rhema/../src"), "test"),
+                ])
+            }
+        }
+
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        let report = GarbageCollector::new(dir.path(), GcConfig::default())
+            .with_collector(Arc::new(Stray))
+            .collect(false)
+            .unwrap();
+        assert!(report.garbage.is_empty());
+        assert_eq!(report.errors.len(), 2);
+        assert!(dir.path().join("src").exists());
+    }
+}
@@ -0,0 +1,445 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Offline help for dead ends: error codes with remediation docs, next steps
+//! derived from the repository, and usage tips revealed over time.
+//!
+//! Remediation docs are Markdown files under `help/`, compiled into the
+//! binary so `rhema explain <code>` works without network access. When a
+//! command ends without a result, such as no scopes being found or the
+//! daemon not answering, [`next_steps`] looks at the repository on disk to
+//! say what to do about it.
+//!
+//! Tips unlock as a user keeps running Rhema, one per run at most. The count
+//! of runs and the tips already shown are kept in
+//! `~/.config/rhema/tips.yaml` and never leave the machine; `RHEMA_NO_TIPS`
+//! turns tips off.
+
+use crate::file_ops::{read_yaml_file, write_yaml_file};
+use crate::scope::{discover_scopes, Scope};
+use crate::toolchain::detect_toolchains;
+use crate::{RhemaError, RhemaResult};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use walkdir::WalkDir;
+
+/// Environment variable turning usage tips off
+pub const NO_TIPS_ENV: &str = "RHEMA_NO_TIPS";
+
+/// Remediation docs by error code
+const EXPLANATIONS: &[(&str, &str)] = &[
+    ("RH001", include_str!("help/RH001.md")),
+    ("RH002", include_str!("help/RH002.md")),
+    ("RH003", include_str!("help/RH003.md")),
+    ("RH004", include_str!("help/RH004.md")),
+    ("RH005", include_str!("help/RH005.md")),
+    ("RH006", include_str!("help/RH006.md")),
+    ("RH007", include_str!("help/RH007.md")),
+    ("RH008", include_str!("help/RH008.md")),
+    ("RH009", include_str!("help/RH009.md")),
+    ("RH010", include_str!("help/RH010.md")),
+];
+
+/// Remediation doc for an error code, matched case-insensitively
+pub fn explain(code: &str) -> Option<&'static str> {
+    EXPLANATIONS
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(code.trim()))
+        .map(|(_, doc)| *doc)
+}
+
+/// Every error code with the title of its remediation doc
+pub fn error_codes() -> Vec<(&'static str, &'static str)> {
+    EXPLANATIONS
+        .iter()
+        .map(|(code, doc)| {
+            let heading = doc.lines().next().unwrap_or_default();
+            let title = heading
+                .trim_start_matches('#')
+                .trim()
+                .strip_prefix(code)
+                .map(|title| title.trim_start_matches(':').trim())
+                .unwrap_or(heading);
+            (*code, title)
+        })
+        .collect()
+}
+
+/// Code of the remediation doc covering an error, if there is one
+pub fn error_code(error: &RhemaError) -> Option<&'static str> {
+    let code = match error {
+        RhemaError::GitRepoNotFound(_) => "RH001",
+        RhemaError::ScopeNotFound(_) => "RH003",
+        RhemaError::InvalidYaml { .. } | RhemaError::YamlError(_) => "RH004",
+        RhemaError::InvalidQuery(_) => "RH005",
+        RhemaError::ConfigError(_) => "RH006",
+        RhemaError::NetworkError(_) | RhemaError::DaemonError(_) => "RH007",
+        RhemaError::Validation(_)
+        | RhemaError::SchemaValidation(_)
+        | RhemaError::ValidationError(_) => "RH008",
+        RhemaError::AuthenticationError(_) | RhemaError::AuthorizationError(_) => "RH009",
+        RhemaError::CircularDependency(_) => "RH010",
+        _ => return None,
+    };
+    Some(code)
+}
+
+/// A command that ended without a result
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeadEnd {
+    /// The repository has no loadable scopes
+    NoScopes,
+    /// A directory is not inside any scope
+    OutsideScopes(PathBuf),
+    /// Nothing answered as the daemon at a URL
+    DaemonUnreachable(String),
+}
+
+impl DeadEnd {
+    /// Code of the remediation doc covering this dead end
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NoScopes => "RH002",
+            Self::OutsideScopes(_) => "RH003",
+            Self::DaemonUnreachable(_) => "RH007",
+        }
+    }
+}
+
+/// What to do about a dead end, judged from the repository at `repo_root`
+pub fn next_steps(repo_root: &Path, dead_end: &DeadEnd) -> Vec<String> {
+    match dead_end {
+        DeadEnd::NoScopes => no_scope_steps(repo_root),
+        DeadEnd::OutsideScopes(dir) => {
+            let scopes = discover_scopes(repo_root).unwrap_or_default();
+            if scopes.is_empty() {
+                return no_scope_steps(repo_root);
+            }
+            let mut roots: Vec<String> = scopes
+                .iter()
+                .map(|scope| relative(repo_root, scope_dir(scope)))
+                .collect();
+            roots.sort();
+            let listed = roots.len().min(5);
+            let mut steps = vec![format!(
+                "Change into a scope directory: {}{}",
+                roots[..listed].join(", "),
+                if roots.len() > listed { ", …" } else { "" }
+            )];
+            steps.push(format!(
+                "Or make {} a scope with `rhema init`",
+                relative(repo_root, dir)
+            ));
+            steps
+        }
+        DeadEnd::DaemonUnreachable(url) => daemon_steps(repo_root, url),
+    }
+}
+
+fn no_scope_steps(repo_root: &Path) -> Vec<String> {
+    let mut steps = Vec::new();
+    for dir in rhema_dirs(repo_root) {
+        let shown = relative(repo_root, dir.parent().unwrap_or(&dir));
+        match Scope::find_scope_file(&dir) {
+            Ok(file) => {
+                if let Err(e) = Scope::new(dir.clone()) {
+                    steps.push(format!(
+                        "{} could not be loaded ({}); run `rhema validate`",
+                        relative(repo_root, &file),
+                        e
+                    ));
+                }
+            }
+            // The root `.rhema` may only hold repository settings
+            Err(_) if has_context_files(&dir) => steps.push(format!(
+                "{} has context files but no rhema.yaml; run `rhema init` there",
+                shown
+            )),
+            Err(_) => {}
+        }
+    }
+
+    let languages = detect_toolchains(repo_root)
+        .map(|detected| detected.languages)
+        .unwrap_or_default();
+    if languages.is_empty() {
+        steps.push("Create the first scope with `rhema init --wizard`".to_string());
+    } else {
+        steps.push(format!(
+            "Detected {}; propose scopes for it with `rhema init --auto-config`",
+            languages.into_iter().collect::<Vec<_>>().join(", ")
+        ));
+    }
+    steps
+}
+
+fn daemon_steps(repo_root: &Path, url: &str) -> Vec<String> {
+    let authority = url
+        .split("://")
+        .last()
+        .unwrap_or(url)
+        .split('/')
+        .next()
+        .unwrap_or_default();
+    let port = authority
+        .rsplit_once(':')
+        .map(|(_, port)| port.to_string())
+        .unwrap_or_else(|| {
+            if url.starts_with("https") {
+                "443"
+            } else {
+                "80"
+            }
+            .to_string()
+        });
+    let address = if authority.contains(':') {
+        authority.to_string()
+    } else {
+        format!("{}:{}", authority, port)
+    };
+
+    let listening = address
+        .to_socket_addrs()
+        .ok()
+        .and_then(|mut addrs| addrs.next())
+        .is_some_and(|addr| TcpStream::connect_timeout(&addr, Duration::from_millis(300)).is_ok());
+    if listening {
+        return vec![format!(
+            "Something is listening on {} but did not answer as the Rhema daemon; \
+             pass the daemon's address with --url",
+            address
+        )];
+    }
+
+    let config = ["rhema-mcp.yaml", "rhema-mcp.yml"]
+        .into_iter()
+        .find(|name| repo_root.join(name).is_file());
+    vec![format!(
+        "Nothing is listening on {}; start the daemon with `rhema-mcp-server --port {}{}`",
+        address,
+        port,
+        config
+            .map(|name| format!(" --config {}", name))
+            .unwrap_or_default()
+    )]
+}
+
+/// `.rhema` directories under `root`, outside build output and hidden dirs
+fn rhema_dirs(root: &Path) -> Vec<PathBuf> {
+    WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            entry.depth() == 0
+                || !entry.file_type().is_dir()
+                || name == ".rhema"
+                || !(name.starts_with('.') || name == "node_modules" || name == "target")
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_dir() && entry.file_name() == ".rhema")
+        .map(|entry| entry.into_path())
+        .collect()
+}
+
+fn has_context_files(dir: &Path) -> bool {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries.filter_map(|entry| entry.ok()).any(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                name.ends_with(".yaml") && name != "repository.yaml"
+            })
+        })
+        .unwrap_or(false)
+}
+
+fn scope_dir(scope: &Scope) -> &Path {
+    if scope.path.file_name().is_some_and(|name| name == ".rhema") {
+        scope.path.parent().unwrap_or(&scope.path)
+    } else {
+        &scope.path
+    }
+}
+
+fn relative(root: &Path, path: &Path) -> String {
+    match path.strip_prefix(root) {
+        Ok(relative) if relative.as_os_str().is_empty() => ".".to_string(),
+        Ok(relative) => relative.display().to_string(),
+        Err(_) => path.display().to_string(),
+    }
+}
+
+/// A usage tip, shown once a user has run Rhema `after_runs` times
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tip {
+    pub id: &'static str,
+    pub after_runs: u64,
+    pub text: &'static str,
+}
+
+/// Tips in the order they unlock, from first steps to advanced commands
+pub const TIPS: &[Tip] = &[
+    Tip {
+        id: "scopes",
+        after_runs: 1,
+        text: "`rhema scopes` lists the scopes of this repository",
+    },
+    Tip {
+        id: "query",
+        after_runs: 3,
+        text: "`rhema query \"todos WHERE status=pending\"` reads context across scopes",
+    },
+    Tip {
+        id: "explain",
+        after_runs: 5,
+        text: "errors show a code such as RH002; `rhema explain RH002` says how to fix it",
+    },
+    Tip {
+        id: "validate",
+        after_runs: 8,
+        text: "`rhema validate` checks every context file against its schema",
+    },
+    Tip {
+        id: "find",
+        after_runs: 13,
+        text: "`rhema find <text>` links code to the decisions and patterns about it",
+    },
+    Tip {
+        id: "doctor",
+        after_runs: 20,
+        text: "`rhema doctor` checks that the tools actions rely on are installed",
+    },
+];
+
+/// Runs counted and tips shown, kept on this machine only
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TipState {
+    pub runs: u64,
+    pub shown: BTreeSet<String>,
+}
+
+impl TipState {
+    /// Location of the state, `~/.config/rhema/tips.yaml`
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("rhema").join("tips.yaml"))
+    }
+
+    /// Load the state at `path`; a missing file is a first run
+    pub fn load(path: &Path) -> RhemaResult<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        read_yaml_file(path)
+    }
+
+    pub fn save(&self, path: &Path) -> RhemaResult<()> {
+        write_yaml_file(path, self)
+    }
+
+    /// Count a run and return the first unlocked tip not shown yet,
+    /// marking it shown
+    pub fn next_tip(&mut self) -> Option<&'static Tip> {
+        self.runs += 1;
+        let tip = TIPS
+            .iter()
+            .find(|tip| tip.after_runs <= self.runs && !self.shown.contains(tip.id))?;
+        self.shown.insert(tip.id.to_string());
+        Some(tip)
+    }
+}
+
+/// Whether tips are turned off with `RHEMA_NO_TIPS`
+pub fn tips_disabled() -> bool {
+    std::env::var(NO_TIPS_ENV).is_ok_and(|value| !value.is_empty() && value != "0")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_every_code_has_a_titled_doc() {
+        let codes = error_codes();
+        assert_eq!(codes.len(), EXPLANATIONS.len());
+        for (code, title) in &codes {
+            assert!(!title.is_empty() && !title.starts_with('#'), "{}", code);
+        }
+        assert_eq!(codes[1], ("RH002", "No scopes found"));
+        assert_eq!(explain("rh002"), explain("RH002"));
+        assert!(explain("RH999").is_none());
+        assert_eq!(
+            error_code(&RhemaError::ScopeNotFound("api".to_string())),
+            Some("RH003")
+        );
+        assert_eq!(error_code(&RhemaError::HookError("x".to_string())), None);
+    }
+
+    #[test]
+    fn test_next_steps_follow_repository_state() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join(".rhema")).unwrap();
+        fs::write(root.join(".rhema/repository.yaml"), "i18n:\n  locale: en\n").unwrap();
+        fs::create_dir_all(root.join("api/.rhema")).unwrap();
+        fs::write(root.join("api/.rhema/todos.yaml"), "todos: []\n").unwrap();
+        fs::write(root.join("Cargo.toml"), "[workspace]\n").unwrap();
+
+        let steps = next_steps(root, &DeadEnd::NoScopes);
+        assert_eq!(
+            steps,
+            vec![
+                "api has context files but no rhema.yaml; run `rhema init` there",
+                "Detected rust; propose scopes for it with `rhema init --auto-config`",
+            ]
+        );
+
+        fs::write(
+            root.join("api/.rhema/rhema.yaml"),
+            "name: api\nscope_type: service\nversion: \"1.0.0\"\n",
+        )
+        .unwrap();
+        let steps = next_steps(root, &DeadEnd::OutsideScopes(root.join("web")));
+        assert_eq!(
+            steps,
+            vec![
+                "Change into a scope directory: api",
+                "Or make web a scope with `rhema init`",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tips_unlock_progressively() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("rhema/tips.yaml");
+        let mut state = TipState::load(&path).unwrap();
+
+        assert_eq!(state.next_tip().map(|tip| tip.id), Some("scopes"));
+        assert_eq!(state.next_tip(), None);
+        assert_eq!(state.next_tip().map(|tip| tip.id), Some("query"));
+        state.save(&path).unwrap();
+
+        let mut reloaded = TipState::load(&path).unwrap();
+        assert_eq!(reloaded.runs, 3);
+        assert_eq!(reloaded.next_tip(), None);
+        assert_eq!(reloaded.next_tip().map(|tip| tip.id), Some("explain"));
+    }
+}
@@ -0,0 +1,522 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Localized text for CLI output and generated documents.
+//!
+//! Messages live in flat YAML catalogs keyed by dotted message ids, with
+//! `{name}` placeholders filled in at runtime:
+//!
+//! ```yaml
+//! readme.more: "…and {count} more"
+//! ```
+//!
+//! English, Japanese and German catalogs are built in. A repository adds
+//! locales, or overrides single messages, with `.rhema/locales/<locale>.yaml`;
+//! empty messages count as untranslated. Anything a catalog lacks falls back
+//! to English.
+//!
+//! CLI output follows `RHEMA_LOCALE`, then `i18n.locale` in the repository
+//! config, then `LC_ALL`, `LC_MESSAGES` and `LANG`. Generated documents only
+//! follow `i18n.docs_locale` or `i18n.locale`, so a README does not switch
+//! language with whoever regenerates it.
+
+use crate::policy::repository_config;
+use crate::{RhemaError, RhemaResult};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use walkdir::WalkDir;
+
+/// Section of `.rhema/repository.yaml` configuring localization
+pub const I18N_CONFIG_SECTION: &str = "i18n";
+
+/// Environment variable selecting the CLI locale
+pub const LOCALE_ENV: &str = "RHEMA_LOCALE";
+
+/// Locale every catalog translates from
+pub const SOURCE_LOCALE: &str = "en";
+
+/// Locales with a catalog compiled into Rhema
+pub const BUILTIN_LOCALES: [&str; 3] = ["en", "ja", "de"];
+
+/// Message id to message text
+pub type Catalog = BTreeMap<String, String>;
+
+fn builtin_source(locale: &str) -> Option<&'static str> {
+    match locale {
+        "en" => Some(include_str!("locales/en.yaml")),
+        "ja" => Some(include_str!("locales/ja.yaml")),
+        "de" => Some(include_str!("locales/de.yaml")),
+        _ => None,
+    }
+}
+
+/// Localization settings
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct I18nConfig {
+    /// Locale of CLI output, e.g. `ja` or `de-DE`
+    pub locale: Option<String>,
+
+    /// Locale of generated documents such as scope READMEs; defaults to
+    /// `locale`
+    pub docs_locale: Option<String>,
+}
+
+impl I18nConfig {
+    /// Load the configuration from the repository config and policy bundle,
+    /// falling back to defaults
+    pub fn load(repo_root: &Path) -> RhemaResult<Self> {
+        let value = repository_config(repo_root)?;
+        match value.get(I18N_CONFIG_SECTION) {
+            Some(section) => serde_yaml::from_value(section.clone()).map_err(|e| {
+                RhemaError::ConfigError(format!(
+                    "Invalid {} section in the repository config: {}",
+                    I18N_CONFIG_SECTION, e
+                ))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Locale for CLI output
+    pub fn cli_locale(&self) -> String {
+        let env = |name: &str| {
+            std::env::var(name)
+                .ok()
+                .and_then(|value| normalize_locale(&value))
+        };
+        env(LOCALE_ENV)
+            .or_else(|| self.locale.as_deref().and_then(normalize_locale))
+            .or_else(|| ["LC_ALL", "LC_MESSAGES", "LANG"].into_iter().find_map(env))
+            .unwrap_or_else(|| SOURCE_LOCALE.to_string())
+    }
+
+    /// Locale for generated documents
+    pub fn docs_locale(&self) -> String {
+        self.docs_locale
+            .as_deref()
+            .or(self.locale.as_deref())
+            .and_then(normalize_locale)
+            .unwrap_or_else(|| SOURCE_LOCALE.to_string())
+    }
+}
+
+/// Lowercase language tag of a locale name: `ja_JP.UTF-8` becomes `ja-jp`.
+/// The `C` and `POSIX` locales are English.
+pub fn normalize_locale(name: &str) -> Option<String> {
+    let tag = name
+        .split(['.', '@'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .replace('_', "-")
+        .to_lowercase();
+    match tag.as_str() {
+        "" => None,
+        "c" | "posix" => Some(SOURCE_LOCALE.to_string()),
+        _ => Some(tag),
+    }
+}
+
+/// Directory holding the repository's catalogs
+pub fn locales_dir(repo_root: &Path) -> PathBuf {
+    repo_root.join(".rhema").join("locales")
+}
+
+/// Catalog of one locale: the built-in messages, if any, overlaid with the
+/// repository's translated messages
+pub fn load_catalog(locale: &str, repo_root: Option<&Path>) -> RhemaResult<Catalog> {
+    let mut catalog = match builtin_source(locale) {
+        Some(source) => parse_catalog(source, &format!("built-in {} catalog", locale))?,
+        None => Catalog::new(),
+    };
+    if let Some(repo_root) = repo_root {
+        let path = locales_dir(repo_root).join(format!("{}.yaml", locale));
+        if path.exists() {
+            let source = std::fs::read_to_string(&path)?;
+            let overrides = parse_catalog(&source, &path.display().to_string())?;
+            catalog.extend(
+                overrides
+                    .into_iter()
+                    .filter(|(_, message)| !message.trim().is_empty()),
+            );
+        }
+    }
+    Ok(catalog)
+}
+
+fn parse_catalog(source: &str, file: &str) -> RhemaResult<Catalog> {
+    let catalog: Option<Catalog> =
+        serde_yaml::from_str(source).map_err(|e| RhemaError::InvalidYaml {
+            file: file.to_string(),
+            message: e.to_string(),
+        })?;
+    Ok(catalog.unwrap_or_default())
+}
+
+/// Locales with a built-in or repository catalog
+pub fn available_locales(repo_root: Option<&Path>) -> RhemaResult<Vec<String>> {
+    let mut locales: BTreeSet<String> = BUILTIN_LOCALES.iter().map(|l| l.to_string()).collect();
+    if let Some(repo_root) = repo_root {
+        let dir = locales_dir(repo_root);
+        if dir.is_dir() {
+            for entry in std::fs::read_dir(dir)? {
+                let path = entry?.path();
+                if path.extension().is_some_and(|ext| ext == "yaml") {
+                    if let Some(locale) = path.file_stem().and_then(|stem| stem.to_str()) {
+                        locales.insert(locale.to_string());
+                    }
+                }
+            }
+        }
+    }
+    Ok(locales.into_iter().collect())
+}
+
+/// Looks up messages in one locale, falling back to its base language and
+/// then to English
+#[derive(Debug, Clone)]
+pub struct Localizer {
+    locale: String,
+    /// Most specific catalog first
+    catalogs: Vec<Catalog>,
+}
+
+impl Default for Localizer {
+    /// English built-in messages
+    fn default() -> Self {
+        Self {
+            locale: SOURCE_LOCALE.to_string(),
+            catalogs: load_catalog(SOURCE_LOCALE, None).into_iter().collect(),
+        }
+    }
+}
+
+impl Localizer {
+    /// Localizer for `locale`, with the repository's catalogs when
+    /// `repo_root` is given
+    pub fn new(locale: &str, repo_root: Option<&Path>) -> RhemaResult<Self> {
+        let locale = normalize_locale(locale).unwrap_or_else(|| SOURCE_LOCALE.to_string());
+        let mut candidates = vec![locale.clone()];
+        if let Some((language, _)) = locale.split_once('-') {
+            candidates.push(language.to_string());
+        }
+        if !candidates
+            .iter()
+            .any(|candidate| candidate == SOURCE_LOCALE)
+        {
+            candidates.push(SOURCE_LOCALE.to_string());
+        }
+
+        let mut catalogs = Vec::new();
+        for candidate in &candidates {
+            let catalog = load_catalog(candidate, repo_root)?;
+            if !catalog.is_empty() {
+                catalogs.push(catalog);
+            }
+        }
+        Ok(Self { locale, catalogs })
+    }
+
+    /// Localizer for CLI output in the repository at `repo_root`
+    pub fn for_cli(repo_root: &Path) -> RhemaResult<Self> {
+        Self::new(&I18nConfig::load(repo_root)?.cli_locale(), Some(repo_root))
+    }
+
+    /// Localizer for documents generated in the repository at `repo_root`
+    pub fn for_documents(repo_root: &Path) -> RhemaResult<Self> {
+        Self::new(&I18nConfig::load(repo_root)?.docs_locale(), Some(repo_root))
+    }
+
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Message `id`, or the id itself when no catalog has it
+    pub fn text(&self, id: &str) -> String {
+        self.lookup(id).to_string()
+    }
+
+    /// Message `id` with its `{name}` placeholders filled in
+    pub fn text_with(&self, id: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+        let mut text = self.lookup(id).to_string();
+        for (name, value) in args {
+            text = text.replace(&format!("{{{}}}", name), &value.to_string());
+        }
+        text
+    }
+
+    fn lookup<'a>(&'a self, id: &'a str) -> &'a str {
+        self.catalogs
+            .iter()
+            .find_map(|catalog| catalog.get(id))
+            .map_or(id, String::as_str)
+    }
+}
+
+static CURRENT: OnceLock<Localizer> = OnceLock::new();
+
+/// Install the localizer for CLI output; only the first call takes effect
+pub fn init(localizer: Localizer) {
+    let _ = CURRENT.set(localizer);
+}
+
+/// Localizer installed with [`init`], English until then
+pub fn current() -> &'static Localizer {
+    CURRENT.get_or_init(Localizer::default)
+}
+
+/// Message `id` in the CLI locale
+pub fn text(id: &str) -> String {
+    current().text(id)
+}
+
+/// Message `id` in the CLI locale with its placeholders filled in
+pub fn text_with(id: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    current().text_with(id, args)
+}
+
+/// How complete a locale's catalog is compared with English
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct CatalogReport {
+    pub locale: String,
+    /// English messages
+    pub total: usize,
+    /// English messages the locale translates
+    pub translated: usize,
+    /// Messages shown in English for lack of a translation
+    pub missing: Vec<String>,
+    /// Translated messages English no longer has
+    pub obsolete: Vec<String>,
+    /// Translations whose placeholders differ from the English message
+    pub placeholder_mismatches: Vec<String>,
+    /// Message ids used in source code that English does not define
+    pub undefined: Vec<String>,
+}
+
+impl CatalogReport {
+    /// Share of English messages translated, from 0.0 to 1.0
+    pub fn coverage(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.translated as f64 / self.total as f64
+        }
+    }
+
+    /// Whether translators have nothing left to do
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+            && self.obsolete.is_empty()
+            && self.placeholder_mismatches.is_empty()
+            && self.undefined.is_empty()
+    }
+}
+
+/// Compare a locale's catalog with English. `used` holds message ids found in
+/// source code, see [`extract_message_ids`].
+pub fn check_catalog(
+    locale: &str,
+    repo_root: Option<&Path>,
+    used: &BTreeSet<String>,
+) -> RhemaResult<CatalogReport> {
+    let english = load_catalog(SOURCE_LOCALE, repo_root)?;
+    let translations = load_catalog(locale, repo_root)?;
+
+    let mut report = CatalogReport {
+        locale: locale.to_string(),
+        total: english.len(),
+        ..Default::default()
+    };
+    for (id, source) in &english {
+        match translations.get(id) {
+            Some(translation) => {
+                report.translated += 1;
+                if placeholders(translation) != placeholders(source) {
+                    report.placeholder_mismatches.push(id.clone());
+                }
+            }
+            None => report.missing.push(id.clone()),
+        }
+    }
+    report.obsolete = translations
+        .keys()
+        .filter(|id| !english.contains_key(*id))
+        .cloned()
+        .collect();
+    report.undefined = used
+        .iter()
+        .filter(|id| !english.contains_key(*id))
+        .cloned()
+        .collect();
+    Ok(report)
+}
+
+fn placeholders(message: &str) -> BTreeSet<&str> {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    let pattern = PATTERN.get_or_init(|| Regex::new(r"\{([a-z_][a-z0-9_]*)\}").unwrap());
+    pattern
+        .captures_iter(message)
+        .filter_map(|captures| captures.get(1))
+        .map(|name| name.as_str())
+        .collect()
+}
+
+/// Message ids passed as literals to `text` or `text_with` in the Rust
+/// sources under `dirs`
+pub fn extract_message_ids(dirs: &[PathBuf]) -> RhemaResult<BTreeSet<String>> {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    let pattern = PATTERN.get_or_init(|| {
+        Regex::new(r#"\btext(?:_with)?\(\s*"([A-Za-z0-9_-]+(?:\.[A-Za-z0-9_-]+)+)""#).unwrap()
+    });
+
+    let mut ids = BTreeSet::new();
+    for dir in dirs {
+        for entry in WalkDir::new(dir).into_iter().filter_map(Result::ok) {
+            let path = entry.path();
+            if !entry.file_type().is_file() || path.extension().is_none_or(|ext| ext != "rs") {
+                continue;
+            }
+            let source = std::fs::read_to_string(path)?;
+            ids.extend(
+                pattern
+                    .captures_iter(&source)
+                    .filter_map(|captures| captures.get(1))
+                    .map(|id| id.as_str().to_string()),
+            );
+        }
+    }
+    Ok(ids)
+}
+
+/// Catalog for translators of `locale`: every English message, each preceded
+/// by its English text as a comment, with the existing translation or an
+/// empty string to fill in
+pub fn translation_template(locale: &str, repo_root: Option<&Path>) -> RhemaResult<String> {
+    let english = load_catalog(SOURCE_LOCALE, repo_root)?;
+    let translations = load_catalog(locale, repo_root)?;
+
+    let mut template = format!(
+        "# Rhema messages for locale {}.\n\
+         # Empty messages are untranslated and shown in English.\n\
+         # Placeholders such as {{count}} are filled in at runtime and must be kept.\n",
+        locale
+    );
+    for (id, source) in &english {
+        let translation = translations.get(id).map(String::as_str).unwrap_or_default();
+        template.push_str(&format!(
+            "\n# {}: {}\n{}: {}\n",
+            SOURCE_LOCALE,
+            source.replace('\n', " "),
+            id,
+            serde_json::to_string(translation)?
+        ));
+    }
+    Ok(template)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_builtin_catalogs_translate_every_message() {
+        let none = BTreeSet::new();
+        for locale in BUILTIN_LOCALES {
+            let report = check_catalog(locale, None, &none).unwrap();
+            assert!(report.is_complete(), "{:?}", report);
+            assert_eq!(report.coverage(), 1.0);
+        }
+
+        let ja = Localizer::new("ja_JP.UTF-8", None).unwrap();
+        assert_eq!(ja.locale(), "ja-jp");
+        assert_eq!(ja.text_with("readme.more", &[("count", &3)]), "…ほか 3 件");
+        assert_eq!(
+            Localizer::default().text_with("readme.more", &[("count", &3)]),
+            "…and 3 more"
+        );
+        assert_eq!(ja.text("no.such.message"), "no.such.message");
+    }
+
+    #[test]
+    fn test_repository_catalogs_override_and_add_locales() {
+        let repo = TempDir::new().unwrap();
+        let dir = locales_dir(repo.path());
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("de.yaml"),
+            "readme.section.purpose: \"Ziel\"\nreadme.more: \"\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("fr.yaml"), "readme.section.purpose: \"Objet\"\n").unwrap();
+
+        let de = Localizer::new("de-AT", Some(repo.path())).unwrap();
+        assert_eq!(de.text("readme.section.purpose"), "Ziel");
+        assert_eq!(
+            de.text_with("readme.more", &[("count", &2)]),
+            "…und 2 weitere"
+        );
+
+        let fr = Localizer::new("fr", Some(repo.path())).unwrap();
+        assert_eq!(fr.text("readme.section.purpose"), "Objet");
+        assert_eq!(fr.text("readme.section.conventions"), "Conventions");
+        assert_eq!(
+            available_locales(Some(repo.path())).unwrap(),
+            vec!["de", "en", "fr", "ja"]
+        );
+
+        let used: BTreeSet<String> = ["readme.more", "readme.unknown"]
+            .iter()
+            .map(|id| id.to_string())
+            .collect();
+        let report = check_catalog("fr", Some(repo.path()), &used).unwrap();
+        assert_eq!(report.translated, 1);
+        assert!(report.missing.contains(&"readme.more".to_string()));
+        assert_eq!(report.undefined, vec!["readme.unknown"]);
+
+        let template = translation_template("fr", Some(repo.path())).unwrap();
+        assert!(template.contains("# en: Purpose\nreadme.section.purpose: \"Objet\"\n"));
+        assert!(template.contains("# en: …and {count} more\nreadme.more: \"\"\n"));
+        let parsed: Catalog = serde_yaml::from_str(&template).unwrap();
+        assert_eq!(parsed.len(), report.total);
+    }
+
+    #[test]
+    fn test_locale_selection() {
+        assert_eq!(
+            normalize_locale("de_DE.UTF-8@euro").as_deref(),
+            Some("de-de")
+        );
+        assert_eq!(normalize_locale("POSIX").as_deref(), Some("en"));
+        assert_eq!(normalize_locale(""), None);
+
+        let config = I18nConfig {
+            locale: Some("ja".to_string()),
+            docs_locale: None,
+        };
+        assert_eq!(config.docs_locale(), "ja");
+        let config = I18nConfig {
+            docs_locale: Some("de".to_string()),
+            ..config
+        };
+        assert_eq!(config.docs_locale(), "de");
+    }
+}
@@ -0,0 +1,540 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Backstage software catalog importer.
+//!
+//! Reads `catalog-info.yaml` descriptors. Each `Component` becomes a scope
+//! in the directory of its descriptor, with `dependsOn` components mapped to
+//! scope dependencies and ownership recorded as knowledge. `API` entities
+//! become knowledge in the scope of the component providing them. Other
+//! kinds have no Rhema equivalent and are listed in the mapping report.
+
+use super::{
+    display_path, normalize_scope_path, ImportOptions, ImportPlan, ImportSource, ImportedKnowledge,
+    ImportedScopeDefinition, Importer, Provenance,
+};
+use crate::{RhemaError, RhemaResult};
+use serde::Deserialize;
+use serde_yaml::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Directories never searched for descriptors
+const SKIPPED_DIRS: &[&str] = &[".git", ".rhema", "node_modules", "target"];
+
+/// Importer for Backstage `catalog-info.yaml` descriptors
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BackstageImporter;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Entity {
+    api_version: String,
+    kind: String,
+    metadata: Metadata,
+    #[serde(default)]
+    spec: Spec,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Metadata {
+    name: String,
+    #[serde(default)]
+    namespace: Option<String>,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    links: Vec<Link>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Link {
+    url: String,
+    #[serde(default)]
+    title: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Spec {
+    #[serde(default, rename = "type")]
+    entity_type: Option<String>,
+    #[serde(default)]
+    lifecycle: Option<String>,
+    #[serde(default)]
+    owner: Option<String>,
+    #[serde(default)]
+    system: Option<String>,
+    #[serde(default)]
+    depends_on: Vec<String>,
+    #[serde(default)]
+    provides_apis: Vec<String>,
+    #[serde(default)]
+    definition: Option<Value>,
+}
+
+/// An entity and the descriptor it was read from
+struct Descriptor {
+    entity: Entity,
+    path: PathBuf,
+}
+
+impl Entity {
+    fn namespace(&self) -> &str {
+        self.metadata.namespace.as_deref().unwrap_or("default")
+    }
+
+    /// Full entity reference, e.g. `component:default/payments`
+    fn entity_ref(&self) -> String {
+        format!(
+            "{}:{}/{}",
+            self.kind.to_lowercase(),
+            self.namespace(),
+            self.metadata.name
+        )
+    }
+
+    fn display_name(&self) -> &str {
+        self.metadata
+            .title
+            .as_deref()
+            .unwrap_or(&self.metadata.name)
+    }
+}
+
+/// Expand a possibly shortened reference to `kind:namespace/name`
+fn normalize_ref(reference: &str, default_kind: &str, default_namespace: &str) -> String {
+    let (kind, rest) = match reference.split_once(':') {
+        Some((kind, rest)) => (kind.to_lowercase(), rest),
+        None => (default_kind.to_string(), reference),
+    };
+    let (namespace, name) = rest.split_once('/').unwrap_or((default_namespace, rest));
+    format!("{}:{}/{}", kind, namespace, name)
+}
+
+impl BackstageImporter {
+    fn descriptor_files(input: &Path) -> Vec<PathBuf> {
+        if input.is_file() {
+            return vec![input.to_path_buf()];
+        }
+        let mut files: Vec<PathBuf> = WalkDir::new(input)
+            .into_iter()
+            .filter_entry(|entry| {
+                !(entry.file_type().is_dir()
+                    && SKIPPED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref()))
+            })
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .filter(|path| {
+                matches!(
+                    path.extension().and_then(|ext| ext.to_str()),
+                    Some("yaml") | Some("yml")
+                )
+            })
+            .collect();
+        files.sort();
+        files
+    }
+
+    /// Backstage entities in a descriptor; other YAML documents are ignored
+    fn read_entities(path: &Path) -> RhemaResult<Vec<Entity>> {
+        let content = std::fs::read_to_string(path)?;
+        let mut entities = Vec::new();
+        for document in serde_yaml::Deserializer::from_str(&content) {
+            let value = Value::deserialize(document)
+                .map_err(|e| RhemaError::ParseError(format!("{}: {}", path.display(), e)))?;
+            let is_entity = value
+                .get("apiVersion")
+                .and_then(Value::as_str)
+                .is_some_and(|api| api.starts_with("backstage.io/"));
+            if !is_entity {
+                continue;
+            }
+            let entity: Entity = serde_yaml::from_value(value).map_err(|e| {
+                RhemaError::ParseError(format!("{}: invalid entity: {}", path.display(), e))
+            })?;
+            entities.push(entity);
+        }
+        Ok(entities)
+    }
+
+    fn provenance(entity: &Entity, source_path: &str, options: &ImportOptions) -> Provenance {
+        Provenance {
+            system: ImportSource::Backstage,
+            source_id: entity.entity_ref(),
+            source_path: source_path.to_string(),
+            url: options.base_url.as_ref().map(|base| {
+                format!(
+                    "{}/catalog/{}/{}/{}",
+                    base.trim_end_matches('/'),
+                    entity.namespace(),
+                    entity.kind.to_lowercase(),
+                    entity.metadata.name
+                )
+            }),
+        }
+    }
+
+    fn ownership_content(entity: &Entity) -> Option<String> {
+        let mut lines = Vec::new();
+        if let Some(owner) = &entity.spec.owner {
+            lines.push(format!("Owner: {}", owner));
+        }
+        if let Some(lifecycle) = &entity.spec.lifecycle {
+            lines.push(format!("Lifecycle: {}", lifecycle));
+        }
+        if let Some(system) = &entity.spec.system {
+            lines.push(format!("System: {}", system));
+        }
+        if !entity.metadata.links.is_empty() {
+            lines.push("Links:".to_string());
+            for link in &entity.metadata.links {
+                lines.push(format!(
+                    "- {}{}",
+                    link.title
+                        .as_ref()
+                        .map(|title| format!("{}: ", title))
+                        .unwrap_or_default(),
+                    link.url
+                ));
+            }
+        }
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+
+    fn api_content(entity: &Entity) -> String {
+        let mut content = entity.metadata.description.clone().unwrap_or_default();
+        if let Some(api_type) = &entity.spec.entity_type {
+            if !content.is_empty() {
+                content.push_str("\n\n");
+            }
+            content.push_str(&format!("Type: {}", api_type));
+        }
+        let definition = match &entity.spec.definition {
+            Some(Value::String(definition)) => Some(definition.clone()),
+            Some(other) => serde_yaml::to_string(other).ok(),
+            None => None,
+        };
+        if let Some(definition) = definition {
+            content.push_str(&format!(
+                "\n\nDefinition:\n\n```\n{}\n```",
+                definition.trim()
+            ));
+        }
+        content
+    }
+}
+
+impl Importer for BackstageImporter {
+    fn source(&self) -> ImportSource {
+        ImportSource::Backstage
+    }
+
+    fn plan(&self, input: &Path, options: &ImportOptions) -> RhemaResult<ImportPlan> {
+        let root = if input.is_file() {
+            input.parent().unwrap_or(Path::new(".")).to_path_buf()
+        } else {
+            input.to_path_buf()
+        };
+
+        let mut descriptors = Vec::new();
+        for path in Self::descriptor_files(input) {
+            for entity in Self::read_entities(&path)? {
+                descriptors.push(Descriptor {
+                    entity,
+                    path: path.clone(),
+                });
+            }
+        }
+        if descriptors.is_empty() {
+            return Err(RhemaError::NotFound(format!(
+                "No Backstage entities found in {}",
+                input.display()
+            )));
+        }
+
+        // A component owns the directory of its descriptor, unless it shares
+        // the descriptor with other components
+        let mut components_per_file: HashMap<&Path, usize> = HashMap::new();
+        for descriptor in &descriptors {
+            if descriptor.entity.kind.eq_ignore_ascii_case("component") {
+                *components_per_file.entry(&descriptor.path).or_default() += 1;
+            }
+        }
+        let mut scope_paths: HashMap<String, String> = HashMap::new();
+        for descriptor in &descriptors {
+            if !descriptor.entity.kind.eq_ignore_ascii_case("component") {
+                continue;
+            }
+            let dir = descriptor
+                .path
+                .parent()
+                .and_then(|dir| dir.strip_prefix(&root).ok())
+                .map(|dir| dir.to_string_lossy().replace('\\', "/"))
+                .unwrap_or_default();
+            let mut path = format!("{}/{}", options.target_scope, dir);
+            if components_per_file[descriptor.path.as_path()] > 1 {
+                path = format!("{}/{}", path, descriptor.entity.metadata.name);
+            }
+            scope_paths.insert(descriptor.entity.entity_ref(), normalize_scope_path(&path)?);
+        }
+        let api_providers: HashMap<String, String> = descriptors
+            .iter()
+            .filter(|descriptor| descriptor.entity.kind.eq_ignore_ascii_case("component"))
+            .flat_map(|descriptor| {
+                let entity = &descriptor.entity;
+                entity.spec.provides_apis.iter().map(move |api| {
+                    (
+                        normalize_ref(api, "api", entity.namespace()),
+                        entity.entity_ref(),
+                    )
+                })
+            })
+            .collect();
+
+        let mut plan = ImportPlan::new(ImportSource::Backstage);
+        for descriptor in &descriptors {
+            let entity = &descriptor.entity;
+            let source_path = display_path(&descriptor.path, &root);
+            let provenance = Self::provenance(entity, &source_path, options);
+
+            if !entity.api_version.starts_with("backstage.io/v1") {
+                plan.report.unconverted(
+                    &source_path,
+                    &entity.kind,
+                    entity.display_name(),
+                    &format!("Unsupported apiVersion {}", entity.api_version),
+                );
+                continue;
+            }
+
+            match entity.kind.to_lowercase().as_str() {
+                "component" => {
+                    let scope_path = scope_paths[&entity.entity_ref()].clone();
+                    let mut dependencies = Vec::new();
+                    let mut unresolved = Vec::new();
+                    for dependency in &entity.spec.depends_on {
+                        let reference = normalize_ref(dependency, "component", entity.namespace());
+                        match scope_paths.get(&reference) {
+                            Some(path) => dependencies.push(path.clone()),
+                            None => unresolved.push(dependency.clone()),
+                        }
+                    }
+
+                    let scope = plan.scope_mut(&scope_path)?;
+                    scope.definition = Some(ImportedScopeDefinition {
+                        name: entity.metadata.name.clone(),
+                        scope_type: entity
+                            .spec
+                            .entity_type
+                            .clone()
+                            .unwrap_or_else(|| "service".to_string()),
+                        description: entity.metadata.description.clone(),
+                        dependencies,
+                        provenance: provenance.clone(),
+                    });
+                    if let Some(content) = Self::ownership_content(entity) {
+                        scope.knowledge.push(ImportedKnowledge {
+                            title: format!("{} ownership", entity.display_name()),
+                            content,
+                            category: Some("ownership".to_string()),
+                            tags: entity.metadata.tags.clone(),
+                            provenance,
+                        });
+                    }
+
+                    if unresolved.is_empty() {
+                        plan.report
+                            .converted(&source_path, &entity.kind, entity.display_name(), &scope_path);
+                    } else {
+                        plan.report.partial(
+                            &source_path,
+                            &entity.kind,
+                            entity.display_name(),
+                            &scope_path,
+                            &format!(
+                                "dependsOn entries without an imported component: {}",
+                                unresolved.join(", ")
+                            ),
+                        );
+                    }
+                }
+                "api" => {
+                    let scope_path = match api_providers
+                        .get(&entity.entity_ref())
+                        .and_then(|provider| scope_paths.get(provider))
+                    {
+                        Some(path) => path.clone(),
+                        None => normalize_scope_path(&options.target_scope)?,
+                    };
+                    plan.scope_mut(&scope_path)?.knowledge.push(ImportedKnowledge {
+                        title: format!("{} API", entity.display_name()),
+                        content: Self::api_content(entity),
+                        category: Some("api".to_string()),
+                        tags: entity.metadata.tags.clone(),
+                        provenance,
+                    });
+                    plan.report.converted(
+                        &source_path,
+                        &entity.kind,
+                        entity.display_name(),
+                        &format!("{}/knowledge.yaml", scope_path),
+                    );
+                }
+                "system" | "domain" => plan.report.unconverted(
+                    &source_path,
+                    &entity.kind,
+                    entity.display_name(),
+                    "Groupings have no scope equivalent; organise the member scopes by directory instead",
+                ),
+                "group" | "user" => plan.report.unconverted(
+                    &source_path,
+                    &entity.kind,
+                    entity.display_name(),
+                    "Organisation entities are not imported; owners are recorded on component knowledge",
+                ),
+                "resource" => plan.report.unconverted(
+                    &source_path,
+                    &entity.kind,
+                    entity.display_name(),
+                    "Resources are not scopes; document them as knowledge in the scopes that use them",
+                ),
+                "location" => plan.report.unconverted(
+                    &source_path,
+                    &entity.kind,
+                    entity.display_name(),
+                    "Remote locations are not fetched; import their targets directly",
+                ),
+                _ => plan.report.unconverted(
+                    &source_path,
+                    &entity.kind,
+                    entity.display_name(),
+                    "Unknown entity kind",
+                ),
+            }
+        }
+
+        Ok(plan)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CATALOG: &str = r#"
+apiVersion: backstage.io/v1alpha1
+kind: Component
+metadata:
+  name: payments
+  description: Payment processing service
+  tags: [java]
+spec:
+  type: service
+  lifecycle: production
+  owner: team-payments
+  dependsOn:
+    - component:ledger
+    - resource:payments-db
+  providesApis: [payments-api]
+---
+apiVersion: backstage.io/v1alpha1
+kind: API
+metadata:
+  name: payments-api
+spec:
+  type: openapi
+  definition: "openapi: 3.0.0"
+---
+apiVersion: backstage.io/v1alpha1
+kind: Group
+metadata:
+  name: team-payments
+"#;
+
+    #[test]
+    fn test_components_become_scopes_with_dependencies() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("services/payments")).unwrap();
+        std::fs::create_dir_all(dir.path().join("services/ledger")).unwrap();
+        std::fs::write(
+            dir.path().join("services/payments/catalog-info.yaml"),
+            CATALOG,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("services/ledger/catalog-info.yaml"),
+            "apiVersion: backstage.io/v1alpha1\nkind: Component\nmetadata:\n  name: ledger\nspec:\n  type: library\n",
+        )
+        .unwrap();
+
+        let plan = BackstageImporter
+            .plan(
+                dir.path(),
+                &ImportOptions {
+                    base_url: Some("https://backstage.example.com".to_string()),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        let payments = plan
+            .scopes
+            .iter()
+            .find(|scope| scope.path == "services/payments")
+            .unwrap();
+        let definition = payments.definition.as_ref().unwrap();
+        assert_eq!(definition.scope_type, "service");
+        assert_eq!(definition.dependencies, vec!["services/ledger".to_string()]);
+        assert_eq!(
+            definition.provenance.url.as_deref(),
+            Some("https://backstage.example.com/catalog/default/component/payments")
+        );
+        // Ownership and the provided API land in the component's scope
+        assert_eq!(payments.knowledge.len(), 2);
+        assert!(payments.knowledge[1].content.contains("openapi: 3.0.0"));
+
+        assert_eq!(plan.report.partial.len(), 1);
+        assert!(plan.report.partial[0]
+            .reason
+            .as_deref()
+            .unwrap()
+            .contains("resource:payments-db"));
+        assert_eq!(plan.report.unconverted.len(), 1);
+        assert_eq!(plan.report.unconverted[0].kind, "Group");
+    }
+
+    #[test]
+    fn test_component_names_cannot_escape_the_repository() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("catalog-info.yaml"),
+            "apiVersion: backstage.io/v1alpha1\nkind: Component\nmetadata:\n  name: app\n---\napiVersion: backstage.io/v1alpha1\nkind: Component\nmetadata:\n  name: \"../../outside\"\n",
+        )
+        .unwrap();
+
+        let error = BackstageImporter
+            .plan(dir.path(), &ImportOptions::default())
+            .unwrap_err();
+        assert!(error.to_string().contains("../../outside"));
+    }
+}
@@ -0,0 +1,416 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Confluence space export importer.
+//!
+//! Reads the HTML flavour of a space export. Pages whose title marks them as
+//! decision records (ADRs, pages built from the Decision template) become
+//! decisions; every other page becomes a knowledge entry with its body
+//! converted to Markdown. Macros, images and attachments cannot be carried
+//! over faithfully and are listed in the mapping report.
+
+use super::{
+    display_path, markdown_section, normalize_scope_path, parse_decision_status, ImportOptions,
+    ImportPlan, ImportSource, ImportedDecision, ImportedKnowledge, Importer, Provenance,
+};
+use crate::schema::DecisionStatus;
+use crate::{RhemaError, RhemaResult};
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use walkdir::WalkDir;
+
+/// Files and directories of the export that are navigation or styling
+const EXPORT_CHROME: &[&str] = &["index.html", "styles", "images"];
+
+/// Importer for Confluence HTML space exports
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ConfluenceImporter;
+
+fn pattern(cell: &'static OnceLock<Regex>, source: &str) -> &'static Regex {
+    cell.get_or_init(|| Regex::new(source).unwrap())
+}
+
+/// Page title without the `Space : ` prefix Confluence adds
+fn page_title(html: &str) -> Option<String> {
+    static TITLE: OnceLock<Regex> = OnceLock::new();
+    let title = pattern(&TITLE, r"(?is)<title>(.*?)</title>")
+        .captures(html)
+        .map(|captures| decode_entities(captures[1].trim()))?;
+    Some(
+        title
+            .split_once(" : ")
+            .map(|(_, page)| page.trim().to_string())
+            .unwrap_or(title),
+    )
+}
+
+/// HTML of the page body, without breadcrumbs, metadata and footer
+fn main_content(html: &str) -> &str {
+    let start = html
+        .find("id=\"main-content\"")
+        .and_then(|index| html[index..].find('>').map(|end| index + end + 1));
+    let Some(start) = start else {
+        return html;
+    };
+    let end = ["<div class=\"pageSection", "<div id=\"footer\"", "</body>"]
+        .iter()
+        .filter_map(|marker| html[start..].find(marker))
+        .min()
+        .map(|end| start + end)
+        .unwrap_or(html.len());
+    &html[start..end]
+}
+
+fn decode_entities(text: &str) -> String {
+    static NUMERIC: OnceLock<Regex> = OnceLock::new();
+    let text = pattern(&NUMERIC, r"&#(x?)([0-9a-fA-F]+);").replace_all(
+        text,
+        |captures: &regex::Captures| {
+            let radix = if captures[1].is_empty() { 10 } else { 16 };
+            u32::from_str_radix(&captures[2], radix)
+                .ok()
+                .and_then(char::from_u32)
+                .map(String::from)
+                .unwrap_or_default()
+        },
+    );
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Convert page body HTML into Markdown-ish text
+fn html_to_markdown(html: &str) -> String {
+    static BLOCKS: OnceLock<Regex> = OnceLock::new();
+    static HEADING: OnceLock<Regex> = OnceLock::new();
+    static LINK: OnceLock<Regex> = OnceLock::new();
+    static TAG: OnceLock<Regex> = OnceLock::new();
+    static BLANK_LINES: OnceLock<Regex> = OnceLock::new();
+
+    let text =
+        pattern(&BLOCKS, r"(?is)<(script|style)[^>]*>.*?</(script|style)>").replace_all(html, "");
+    let text = pattern(&HEADING, r"(?is)<h([1-6])[^>]*>(.*?)</h[1-6]>").replace_all(
+        &text,
+        |captures: &regex::Captures| {
+            let level: usize = captures[1].parse().unwrap_or(1);
+            format!("\n\n{} {}\n\n", "#".repeat(level), captures[2].trim())
+        },
+    );
+    let text = pattern(&LINK, r#"(?is)<a [^>]*href="([^"]+)"[^>]*>(.*?)</a>"#)
+        .replace_all(&text, "[$2]($1)");
+    let text = text
+        .replace("<li>", "\n- ")
+        .replace("<br>", "\n")
+        .replace("<br/>", "\n")
+        .replace("<br />", "\n")
+        .replace("</p>", "\n\n")
+        .replace("</tr>", "\n")
+        .replace("</th>", " | ")
+        .replace("</td>", " | ")
+        .replace("<pre>", "\n```\n")
+        .replace("</pre>", "\n```\n");
+    let text = pattern(&TAG, r"(?s)<[^>]+>").replace_all(&text, "");
+    let text = decode_entities(&text);
+    let text: Vec<&str> = text.lines().map(str::trim_end).collect();
+    pattern(&BLANK_LINES, r"\n{3,}")
+        .replace_all(&text.join("\n"), "\n\n")
+        .trim()
+        .to_string()
+}
+
+/// Features of a page that do not survive conversion
+fn lossy_features(body_html: &str) -> Vec<String> {
+    static MACRO: OnceLock<Regex> = OnceLock::new();
+    let mut features = Vec::new();
+    let macros = pattern(&MACRO, r#"data-macro-name="([^"]+)""#)
+        .captures_iter(body_html)
+        .map(|captures| captures[1].to_string())
+        .collect::<std::collections::BTreeSet<_>>();
+    if !macros.is_empty() {
+        features.push(format!(
+            "macros rendered as plain text: {}",
+            macros.into_iter().collect::<Vec<_>>().join(", ")
+        ));
+    }
+    let images = body_html.matches("<img").count();
+    if images > 0 {
+        features.push(format!("{} image(s) not imported", images));
+    }
+    features
+}
+
+fn is_decision_page(title: &str, body_html: &str) -> bool {
+    static TITLE: OnceLock<Regex> = OnceLock::new();
+    pattern(&TITLE, r"(?i)\b(adr|decision)s?\b").is_match(title)
+        || body_html.contains("data-macro-name=\"decision")
+        || body_html.contains("class=\"decision-list")
+}
+
+/// Value of a `Field | value` table row or `Field: value` line
+fn field(text: &str, names: &[&str]) -> Option<String> {
+    text.lines().find_map(|line| {
+        let line = line.trim().trim_start_matches('|').trim();
+        let (key, value) = line.split_once(" | ").or_else(|| line.split_once(':'))?;
+        names
+            .iter()
+            .any(|name| key.trim().eq_ignore_ascii_case(name))
+            .then(|| value.trim().trim_end_matches('|').trim().to_string())
+            .filter(|value| !value.is_empty())
+    })
+}
+
+impl ConfluenceImporter {
+    fn page_id(path: &Path) -> Option<String> {
+        static ID: OnceLock<Regex> = OnceLock::new();
+        let stem = path.file_stem()?.to_string_lossy();
+        pattern(&ID, r"(?:^|_)(\d+)$")
+            .captures(&stem)
+            .map(|captures| captures[1].to_string())
+    }
+
+    fn provenance(path: &Path, source_path: &str, options: &ImportOptions) -> Provenance {
+        let id = Self::page_id(path);
+        Provenance {
+            system: ImportSource::Confluence,
+            url: match (&options.base_url, &id) {
+                (Some(base), Some(id)) => Some(format!(
+                    "{}/pages/viewpage.action?pageId={}",
+                    base.trim_end_matches('/'),
+                    id
+                )),
+                _ => None,
+            },
+            source_id: id.unwrap_or_else(|| source_path.to_string()),
+            source_path: source_path.to_string(),
+        }
+    }
+}
+
+impl Importer for ConfluenceImporter {
+    fn source(&self) -> ImportSource {
+        ImportSource::Confluence
+    }
+
+    fn plan(&self, input: &Path, options: &ImportOptions) -> RhemaResult<ImportPlan> {
+        let root = if input.is_file() {
+            input.parent().unwrap_or(Path::new(".")).to_path_buf()
+        } else {
+            input.to_path_buf()
+        };
+        if root.join("entities.xml").exists() {
+            return Err(RhemaError::InvalidInput(format!(
+                "{} is an XML space export; export the space as HTML and import that instead",
+                input.display()
+            )));
+        }
+
+        let mut files: Vec<PathBuf> = WalkDir::new(input)
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() != 1
+                    || !EXPORT_CHROME.contains(&entry.file_name().to_string_lossy().as_ref())
+            })
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .collect();
+        files.sort();
+
+        let mut plan = ImportPlan::new(ImportSource::Confluence);
+        let scope_path = normalize_scope_path(&options.target_scope)?;
+        let mut pages = 0;
+        for path in &files {
+            let source_path = display_path(path, &root);
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let is_html = matches!(
+                path.extension().and_then(|ext| ext.to_str()),
+                Some("html") | Some("htm")
+            );
+            if !is_html {
+                plan.report.unconverted(
+                    &source_path,
+                    "attachment",
+                    &name,
+                    "Attachments are not imported; move the file into the repository and link it",
+                );
+                continue;
+            }
+            pages += 1;
+
+            let html = std::fs::read_to_string(path)?;
+            let title = page_title(&html).unwrap_or_else(|| name.clone());
+            let body_html = main_content(&html);
+            let body = html_to_markdown(body_html);
+            if body.is_empty() {
+                plan.report
+                    .unconverted(&source_path, "page", &title, "Page has no content");
+                continue;
+            }
+            let provenance = Self::provenance(path, &source_path, options);
+            let mut issues = lossy_features(body_html);
+
+            let target = if is_decision_page(&title, body_html) {
+                let status_text = field(&body, &["Status"]);
+                let status = status_text.as_deref().and_then(parse_decision_status);
+                if let (None, Some(text)) = (&status, &status_text) {
+                    issues.push(format!(
+                        "unrecognised status '{}' imported as proposed",
+                        text
+                    ));
+                }
+                let decision_makers = field(&body, &["Owner", "Driver", "Approver", "Deciders"])
+                    .map(|makers| {
+                        makers
+                            .split(',')
+                            .map(|maker| maker.trim().trim_start_matches('@').to_string())
+                            .filter(|maker| !maker.is_empty())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                plan.scope_mut(&scope_path)?
+                    .decisions
+                    .push(ImportedDecision {
+                        title: title.clone(),
+                        description: markdown_section(&body, &["Decision", "Outcome"])
+                            .unwrap_or_else(|| body.clone()),
+                        status: status.unwrap_or(DecisionStatus::Proposed),
+                        context: markdown_section(&body, &["Context", "Background"]),
+                        rationale: markdown_section(&body, &["Rationale", "Consequences"]),
+                        decision_makers,
+                        provenance,
+                    });
+                format!("{}/decisions.yaml", scope_path)
+            } else {
+                plan.scope_mut(&scope_path)?
+                    .knowledge
+                    .push(ImportedKnowledge {
+                        title: title.clone(),
+                        content: body,
+                        category: None,
+                        tags: Vec::new(),
+                        provenance,
+                    });
+                format!("{}/knowledge.yaml", scope_path)
+            };
+
+            if issues.is_empty() {
+                plan.report.converted(&source_path, "page", &title, &target);
+            } else {
+                plan.report
+                    .partial(&source_path, "page", &title, &target, &issues.join("; "));
+            }
+        }
+
+        if pages == 0 {
+            return Err(RhemaError::NotFound(format!(
+                "No Confluence pages found in {}",
+                input.display()
+            )));
+        }
+        Ok(plan)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DECISION_PAGE: &str = r#"<html><head><title>Platform : ADR-7 Adopt gRPC</title></head>
+<body><div id="breadcrumbs">Home</div>
+<div id="main-content" class="wiki-content group">
+<table><tr><th>Status</th><td>Accepted</td></tr><tr><th>Owner</th><td>@ada, @lin</td></tr></table>
+<h2>Context</h2><p>REST payloads are too large &amp; slow.</p>
+<h2>Decision</h2><p>Use gRPC between services.</p>
+<div class="confluence-information-macro" data-macro-name="info">Note</div>
+</div>
+<div class="pageSection group">Attachments</div>
+</body></html>"#;
+
+    #[test]
+    fn test_html_to_markdown() {
+        assert_eq!(
+            html_to_markdown("<h1>Setup</h1><p>Run <a href=\"https://x.io\">this</a>.</p><ul><li>one</li><li>two</li></ul>"),
+            "# Setup\n\nRun [this](https://x.io).\n\n- one\n- two"
+        );
+    }
+
+    #[test]
+    fn test_decision_pages_and_attachments() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(root.join("index.html"), "<title>Platform</title>").unwrap();
+        std::fs::write(root.join("ADR-7-Adopt-gRPC_98765.html"), DECISION_PAGE).unwrap();
+        std::fs::write(
+            root.join("Onboarding_12345.html"),
+            "<title>Platform : Onboarding</title><div id=\"main-content\"><p>Ask for access.</p></div>",
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("attachments/98765")).unwrap();
+        std::fs::write(root.join("attachments/98765/diagram.png"), [0u8; 4]).unwrap();
+
+        let plan = ConfluenceImporter
+            .plan(
+                root,
+                &ImportOptions {
+                    target_scope: ".".to_string(),
+                    base_url: Some("https://wiki.example.com/".to_string()),
+                },
+            )
+            .unwrap();
+
+        let scope = &plan.scopes[0];
+        let decision = &scope.decisions[0];
+        assert_eq!(decision.title, "ADR-7 Adopt gRPC");
+        assert_eq!(decision.status, DecisionStatus::Approved);
+        assert_eq!(decision.description, "Use gRPC between services.\n\nNote");
+        assert_eq!(
+            decision.context.as_deref(),
+            Some("REST payloads are too large & slow.")
+        );
+        assert_eq!(decision.decision_makers, vec!["ada", "lin"]);
+        assert_eq!(
+            decision.provenance.url.as_deref(),
+            Some("https://wiki.example.com/pages/viewpage.action?pageId=98765")
+        );
+        assert_eq!(scope.knowledge[0].content, "Ask for access.");
+
+        assert_eq!(plan.report.partial.len(), 1);
+        assert!(plan.report.partial[0]
+            .reason
+            .as_deref()
+            .unwrap()
+            .contains("info"));
+        assert_eq!(plan.report.unconverted.len(), 1);
+        assert_eq!(plan.report.unconverted[0].name, "diagram.png");
+    }
+
+    #[test]
+    fn test_xml_exports_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("entities.xml"), "<hibernate-generic/>").unwrap();
+        assert!(ConfluenceImporter
+            .plan(dir.path(), &ImportOptions::default())
+            .is_err());
+    }
+}
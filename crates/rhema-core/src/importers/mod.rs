@@ -0,0 +1,669 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Importers that migrate context from other systems into Rhema.
+//!
+//! An importer reads an export and produces an [`ImportPlan`]: the scopes,
+//! knowledge entries and decisions it could convert, each carrying a
+//! [`Provenance`] link back to the source, plus a [`MappingReport`] listing
+//! everything that needs manual attention. Plans are applied with
+//! [`apply_plan`], which skips entries imported by an earlier run.
+
+pub mod backstage;
+pub mod confluence;
+pub mod notion;
+
+pub use backstage::BackstageImporter;
+pub use confluence::ConfluenceImporter;
+pub use notion::NotionImporter;
+
+use crate::file_ops::{
+    get_or_create_decisions_file, get_or_create_knowledge_file, read_yaml_file, write_yaml_file,
+};
+use crate::schema::{
+    DecisionEntry, DecisionStatus, Decisions, Knowledge, KnowledgeEntry, RhemaScope,
+    ScopeDependency, CURRENT_SCHEMA_VERSION,
+};
+use crate::{RhemaError, RhemaResult};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+
+/// Key under which provenance is stored in an entry's custom fields
+pub const PROVENANCE_FIELD: &str = "provenance";
+
+/// System an import was read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportSource {
+    Backstage,
+    Notion,
+    Confluence,
+}
+
+impl fmt::Display for ImportSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportSource::Backstage => write!(f, "backstage"),
+            ImportSource::Notion => write!(f, "notion"),
+            ImportSource::Confluence => write!(f, "confluence"),
+        }
+    }
+}
+
+/// Link from an imported entry back to where it came from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Provenance {
+    pub system: ImportSource,
+
+    /// Stable identifier in the source system (entity ref, page id)
+    pub source_id: String,
+
+    /// File in the export the entry was read from
+    pub source_path: String,
+
+    /// URL of the original, when it can be derived
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+impl Provenance {
+    /// Value stored in an entry's `source` field
+    pub fn source_label(&self) -> String {
+        self.url
+            .clone()
+            .unwrap_or_else(|| format!("{}:{}", self.system, self.source_id))
+    }
+
+    fn to_custom(&self) -> HashMap<String, Value> {
+        let mut custom = HashMap::new();
+        if let Ok(value) = serde_yaml::to_value(self) {
+            custom.insert(PROVENANCE_FIELD.to_string(), value);
+        }
+        custom
+    }
+
+    fn from_custom(custom: &HashMap<String, Value>) -> Option<Self> {
+        custom
+            .get(PROVENANCE_FIELD)
+            .and_then(|value| serde_yaml::from_value(value.clone()).ok())
+    }
+}
+
+/// Options shared by all importers
+#[derive(Debug, Clone)]
+pub struct ImportOptions {
+    /// Directory (relative to the repository root) that receives entries not
+    /// tied to a more specific scope
+    pub target_scope: String,
+
+    /// Base URL of the source system, used to build provenance links
+    pub base_url: Option<String>,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        Self {
+            target_scope: ".".to_string(),
+            base_url: None,
+        }
+    }
+}
+
+/// Knowledge entry to be imported
+#[derive(Debug, Clone)]
+pub struct ImportedKnowledge {
+    pub title: String,
+    pub content: String,
+    pub category: Option<String>,
+    pub tags: Vec<String>,
+    pub provenance: Provenance,
+}
+
+/// Decision to be imported
+#[derive(Debug, Clone)]
+pub struct ImportedDecision {
+    pub title: String,
+    pub description: String,
+    pub status: DecisionStatus,
+    pub context: Option<String>,
+    pub rationale: Option<String>,
+    pub decision_makers: Vec<String>,
+    pub provenance: Provenance,
+}
+
+/// Scope definition to be imported
+#[derive(Debug, Clone)]
+pub struct ImportedScopeDefinition {
+    pub name: String,
+    pub scope_type: String,
+    pub description: Option<String>,
+    /// Repository-relative directories of scopes this one depends on
+    pub dependencies: Vec<String>,
+    pub provenance: Provenance,
+}
+
+/// Everything imported into one scope directory
+#[derive(Debug, Clone, Default)]
+pub struct ImportedScope {
+    /// Directory relative to the repository root
+    pub path: String,
+    /// Created when the scope does not exist yet
+    pub definition: Option<ImportedScopeDefinition>,
+    pub knowledge: Vec<ImportedKnowledge>,
+    pub decisions: Vec<ImportedDecision>,
+}
+
+/// How a source item was handled
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MappingItem {
+    /// File in the export
+    pub source_path: String,
+    /// What the item was in the source system (e.g. `Component`, `page`)
+    pub kind: String,
+    /// Name or title in the source system
+    pub name: String,
+    /// Where it ended up, for converted items
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// Why it was not converted (or was only partly converted)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+/// What an import converted and what needs manual follow-up
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MappingReport {
+    pub converted: Vec<MappingItem>,
+    /// Converted with information loss
+    pub partial: Vec<MappingItem>,
+    pub unconverted: Vec<MappingItem>,
+}
+
+impl MappingReport {
+    pub fn converted(&mut self, source_path: &str, kind: &str, name: &str, target: &str) {
+        self.converted
+            .push(Self::item(source_path, kind, name, Some(target), None));
+    }
+
+    pub fn partial(
+        &mut self,
+        source_path: &str,
+        kind: &str,
+        name: &str,
+        target: &str,
+        reason: &str,
+    ) {
+        self.partial.push(Self::item(
+            source_path,
+            kind,
+            name,
+            Some(target),
+            Some(reason),
+        ));
+    }
+
+    pub fn unconverted(&mut self, source_path: &str, kind: &str, name: &str, reason: &str) {
+        self.unconverted
+            .push(Self::item(source_path, kind, name, None, Some(reason)));
+    }
+
+    fn item(
+        source_path: &str,
+        kind: &str,
+        name: &str,
+        target: Option<&str>,
+        reason: Option<&str>,
+    ) -> MappingItem {
+        MappingItem {
+            source_path: source_path.to_string(),
+            kind: kind.to_string(),
+            name: name.to_string(),
+            target: target.map(str::to_string),
+            reason: reason.map(str::to_string),
+        }
+    }
+
+    /// Render the report as Markdown
+    pub fn to_markdown(&self, source: ImportSource) -> String {
+        let mut out = format!("# {} import mapping report\n\n", source);
+        out.push_str(&format!(
+            "- Converted: {}\n- Partially converted: {}\n- Not converted: {}\n",
+            self.converted.len(),
+            self.partial.len(),
+            self.unconverted.len()
+        ));
+
+        let sections = [
+            ("Not converted", &self.unconverted),
+            ("Partially converted", &self.partial),
+            ("Converted", &self.converted),
+        ];
+        for (heading, items) in sections {
+            if items.is_empty() {
+                continue;
+            }
+            out.push_str(&format!("\n## {}\n\n", heading));
+            out.push_str("| Source | Kind | Name | Target | Notes |\n");
+            out.push_str("|---|---|---|---|---|\n");
+            for item in items {
+                out.push_str(&format!(
+                    "| {} | {} | {} | {} | {} |\n",
+                    escape_cell(&item.source_path),
+                    escape_cell(&item.kind),
+                    escape_cell(&item.name),
+                    escape_cell(item.target.as_deref().unwrap_or("")),
+                    escape_cell(item.reason.as_deref().unwrap_or(""))
+                ));
+            }
+        }
+        out
+    }
+}
+
+fn escape_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Result of reading an export
+#[derive(Debug, Clone)]
+pub struct ImportPlan {
+    pub source: ImportSource,
+    pub scopes: Vec<ImportedScope>,
+    pub report: MappingReport,
+}
+
+impl ImportPlan {
+    pub fn new(source: ImportSource) -> Self {
+        Self {
+            source,
+            scopes: Vec::new(),
+            report: MappingReport::default(),
+        }
+    }
+
+    /// Scope entry for `path`, created on first use
+    pub fn scope_mut(&mut self, path: &str) -> RhemaResult<&mut ImportedScope> {
+        let path = normalize_scope_path(path)?;
+        let index = match self.scopes.iter().position(|scope| scope.path == path) {
+            Some(index) => index,
+            None => {
+                self.scopes.push(ImportedScope {
+                    path,
+                    ..Default::default()
+                });
+                self.scopes.len() - 1
+            }
+        };
+        Ok(&mut self.scopes[index])
+    }
+}
+
+/// Reads an export from another system
+pub trait Importer {
+    fn source(&self) -> ImportSource;
+
+    /// Convert the export at `input` (a file or directory) into a plan
+    fn plan(&self, input: &Path, options: &ImportOptions) -> RhemaResult<ImportPlan>;
+}
+
+/// Importer for a source system
+pub fn importer_for(source: ImportSource) -> Box<dyn Importer> {
+    match source {
+        ImportSource::Backstage => Box::new(BackstageImporter),
+        ImportSource::Notion => Box::new(NotionImporter),
+        ImportSource::Confluence => Box::new(ConfluenceImporter),
+    }
+}
+
+/// Counts of what applying a plan wrote
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportSummary {
+    pub scopes_created: usize,
+    pub knowledge_added: usize,
+    pub decisions_added: usize,
+    /// Entries skipped because an earlier import already brought them in
+    pub already_imported: usize,
+}
+
+/// Write a plan into the repository at `repo_root`.
+///
+/// Scope definitions are only written for directories without one, and
+/// entries whose provenance matches an existing entry are skipped, so
+/// re-running an import is safe. With `dry_run` nothing is written but the
+/// summary reflects what would be.
+pub fn apply_plan(
+    repo_root: &Path,
+    plan: &ImportPlan,
+    dry_run: bool,
+) -> RhemaResult<ImportSummary> {
+    let mut summary = ImportSummary::default();
+
+    for scope in &plan.scopes {
+        let scope_dir = repo_root
+            .join(normalize_scope_path(&scope.path)?)
+            .join(".rhema");
+        let scope_file = scope_dir.join("rhema.yaml");
+
+        if let Some(definition) = &scope.definition {
+            if !scope_file.exists() {
+                summary.scopes_created += 1;
+                if !dry_run {
+                    std::fs::create_dir_all(&scope_dir)?;
+                    write_yaml_file(&scope_file, &scope_definition(definition))?;
+                }
+            }
+        } else if !scope_file.exists()
+            && (!scope.knowledge.is_empty() || !scope.decisions.is_empty())
+        {
+            return Err(RhemaError::ScopeNotFound(format!(
+                "No scope at {}; create it with `rhema init` before importing",
+                scope.path
+            )));
+        }
+
+        if !scope.knowledge.is_empty() {
+            let existing = if scope_dir.join("knowledge.yaml").exists() {
+                let knowledge: Knowledge = read_yaml_file(&scope_dir.join("knowledge.yaml"))?;
+                knowledge
+                    .entries
+                    .iter()
+                    .filter_map(|entry| Provenance::from_custom(&entry.custom))
+                    .map(|provenance| (provenance.system, provenance.source_id))
+                    .collect()
+            } else {
+                HashSet::new()
+            };
+            let new: Vec<KnowledgeEntry> = scope
+                .knowledge
+                .iter()
+                .filter(|entry| {
+                    !existing
+                        .contains(&(entry.provenance.system, entry.provenance.source_id.clone()))
+                })
+                .map(knowledge_entry)
+                .collect();
+            summary.already_imported += scope.knowledge.len() - new.len();
+            summary.knowledge_added += new.len();
+
+            if !dry_run && !new.is_empty() {
+                let file = get_or_create_knowledge_file(&scope_dir)?;
+                let mut knowledge: Knowledge = read_yaml_file(&file)?;
+                knowledge.entries.extend(new);
+                write_yaml_file(&file, &knowledge)?;
+            }
+        }
+
+        if !scope.decisions.is_empty() {
+            let existing = if scope_dir.join("decisions.yaml").exists() {
+                let decisions: Decisions = read_yaml_file(&scope_dir.join("decisions.yaml"))?;
+                decisions
+                    .decisions
+                    .iter()
+                    .filter_map(|entry| Provenance::from_custom(&entry.custom))
+                    .map(|provenance| (provenance.system, provenance.source_id))
+                    .collect()
+            } else {
+                HashSet::new()
+            };
+            let new: Vec<DecisionEntry> = scope
+                .decisions
+                .iter()
+                .filter(|entry| {
+                    !existing
+                        .contains(&(entry.provenance.system, entry.provenance.source_id.clone()))
+                })
+                .map(decision_entry)
+                .collect();
+            summary.already_imported += scope.decisions.len() - new.len();
+            summary.decisions_added += new.len();
+
+            if !dry_run && !new.is_empty() {
+                let file = get_or_create_decisions_file(&scope_dir)?;
+                let mut decisions: Decisions = read_yaml_file(&file)?;
+                decisions.decisions.extend(new);
+                write_yaml_file(&file, &decisions)?;
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+fn scope_definition(definition: &ImportedScopeDefinition) -> RhemaScope {
+    let dependencies: Vec<ScopeDependency> = definition
+        .dependencies
+        .iter()
+        .map(|path| ScopeDependency {
+            path: path.clone(),
+            dependency_type: "required".to_string(),
+            version: None,
+        })
+        .collect();
+
+    RhemaScope {
+        name: definition.name.clone(),
+        scope_type: definition.scope_type.clone(),
+        description: definition.description.clone(),
+        version: "1.0.0".to_string(),
+        schema_version: Some(CURRENT_SCHEMA_VERSION.to_string()),
+        dependencies: (!dependencies.is_empty()).then_some(dependencies),
+        protocol_info: None,
+        ai_policy: None,
+        freshness: None,
+        toolchains: None,
+        runtime: None,
+        entry_templates: None,
+        custom: definition.provenance.to_custom(),
+    }
+}
+
+fn knowledge_entry(imported: &ImportedKnowledge) -> KnowledgeEntry {
+    KnowledgeEntry {
+        id: uuid::Uuid::new_v4().to_string(),
+        title: imported.title.clone(),
+        content: imported.content.clone(),
+        category: imported.category.clone(),
+        tags: (!imported.tags.is_empty()).then(|| imported.tags.clone()),
+        confidence: None,
+        created_at: chrono::Utc::now(),
+        updated_at: None,
+        source: Some(imported.provenance.source_label()),
+        custom: imported.provenance.to_custom(),
+    }
+}
+
+fn decision_entry(imported: &ImportedDecision) -> DecisionEntry {
+    DecisionEntry {
+        id: uuid::Uuid::new_v4().to_string(),
+        title: imported.title.clone(),
+        description: imported.description.clone(),
+        status: imported.status.clone(),
+        context: imported.context.clone(),
+        alternatives: None,
+        rationale: imported.rationale.clone(),
+        consequences: None,
+        decided_at: chrono::Utc::now(),
+        review_date: None,
+        decision_makers: (!imported.decision_makers.is_empty())
+            .then(|| imported.decision_makers.clone()),
+        implementing_commits: None,
+        incidents: None,
+        reversed_by: None,
+        reverses: None,
+        custom: imported.provenance.to_custom(),
+    }
+}
+
+/// Map a free-form status from another system onto a decision status
+pub fn parse_decision_status(status: &str) -> Option<DecisionStatus> {
+    match status.trim().to_lowercase().as_str() {
+        "proposed" | "draft" | "open" | "not started" => Some(DecisionStatus::Proposed),
+        "under review" | "in review" | "in progress" | "discussing" => {
+            Some(DecisionStatus::UnderReview)
+        }
+        "approved" | "accepted" | "decided" | "done" => Some(DecisionStatus::Approved),
+        "rejected" | "declined" => Some(DecisionStatus::Rejected),
+        "implemented" => Some(DecisionStatus::Implemented),
+        "deprecated" | "superseded" | "obsolete" => Some(DecisionStatus::Deprecated),
+        _ => None,
+    }
+}
+
+/// Body of the first Markdown section whose heading matches one of `names`
+/// (case-insensitive), up to the next heading of the same or higher level
+fn markdown_section(markdown: &str, names: &[&str]) -> Option<String> {
+    let mut section: Option<(usize, Vec<&str>)> = None;
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        let is_heading = level > 0 && trimmed[level..].starts_with(' ');
+
+        match &mut section {
+            Some((section_level, lines)) => {
+                if is_heading && level <= *section_level {
+                    break;
+                }
+                lines.push(line);
+            }
+            None if is_heading => {
+                let heading = trimmed[level..].trim().trim_end_matches(':');
+                if names.iter().any(|name| heading.eq_ignore_ascii_case(name)) {
+                    section = Some((level, Vec::new()));
+                }
+            }
+            None => {}
+        }
+    }
+
+    section
+        .map(|(_, lines)| lines.join("\n").trim().to_string())
+        .filter(|body| !body.is_empty())
+}
+
+/// Repository-relative scope path with `/` separators and no `./` prefix.
+/// Paths that could leave the repository (absolute, drive-prefixed or
+/// containing `..`) are rejected, as they often come from imported data.
+fn normalize_scope_path(path: &str) -> RhemaResult<String> {
+    let parts: Vec<&str> = path
+        .split(['/', '\\'])
+        .filter(|part| !part.is_empty() && *part != ".")
+        .collect();
+    if path.starts_with(['/', '\\']) || parts.iter().any(|part| *part == ".." || part.contains(':'))
+    {
+        return Err(RhemaError::InvalidInput(format!(
+            "Scope path '{}' must be relative and stay inside the repository",
+            path
+        )));
+    }
+    if parts.is_empty() {
+        Ok(".".to_string())
+    } else {
+        Ok(parts.join("/"))
+    }
+}
+
+/// Path of `path` relative to `root` for use in reports
+fn display_path(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root)
+        .ok()
+        .filter(|relative| !relative.as_os_str().is_empty())
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_plan_is_idempotent() {
+        let repo = tempfile::tempdir().unwrap();
+        let mut plan = ImportPlan::new(ImportSource::Notion);
+        let provenance = Provenance {
+            system: ImportSource::Notion,
+            source_id: "0123456789abcdef0123456789abcdef".to_string(),
+            source_path: "Runbook 0123456789abcdef0123456789abcdef.md".to_string(),
+            url: None,
+        };
+        let scope = plan.scope_mut("./docs/").unwrap();
+        scope.definition = Some(ImportedScopeDefinition {
+            name: "docs".to_string(),
+            scope_type: "documentation".to_string(),
+            description: None,
+            dependencies: vec![],
+            provenance: provenance.clone(),
+        });
+        scope.knowledge.push(ImportedKnowledge {
+            title: "Runbook".to_string(),
+            content: "Restart the worker".to_string(),
+            category: None,
+            tags: vec![],
+            provenance,
+        });
+        assert_eq!(plan.scopes[0].path, "docs");
+
+        let dry_run = apply_plan(repo.path(), &plan, true).unwrap();
+        assert_eq!(dry_run.knowledge_added, 1);
+        assert!(!repo.path().join("docs/.rhema").exists());
+
+        let first = apply_plan(repo.path(), &plan, false).unwrap();
+        assert_eq!(first.scopes_created, 1);
+        assert_eq!(first.knowledge_added, 1);
+
+        let second = apply_plan(repo.path(), &plan, false).unwrap();
+        assert_eq!(second.scopes_created, 0);
+        assert_eq!(second.knowledge_added, 0);
+        assert_eq!(second.already_imported, 1);
+
+        let knowledge: Knowledge =
+            read_yaml_file(&repo.path().join("docs/.rhema/knowledge.yaml")).unwrap();
+        assert_eq!(knowledge.entries.len(), 1);
+        assert_eq!(
+            knowledge.entries[0].source.as_deref(),
+            Some("notion:0123456789abcdef0123456789abcdef")
+        );
+    }
+
+    #[test]
+    fn test_scope_paths_stay_inside_the_repository() {
+        assert_eq!(
+            normalize_scope_path("./services\\api/").unwrap(),
+            "services/api"
+        );
+        for path in [
+            "../outside",
+            "docs/../../outside",
+            "/etc",
+            "\\\\server\\share",
+            "C:/temp",
+        ] {
+            assert!(normalize_scope_path(path).is_err(), "{} was accepted", path);
+        }
+
+        let repo = tempfile::tempdir().unwrap();
+        let mut plan = ImportPlan::new(ImportSource::Backstage);
+        plan.scopes.push(ImportedScope {
+            path: "../outside".to_string(),
+            ..Default::default()
+        });
+        assert!(apply_plan(repo.path(), &plan, false).is_err());
+        assert!(!repo.path().join("../outside").exists());
+    }
+}
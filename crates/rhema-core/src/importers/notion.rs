@@ -0,0 +1,638 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Notion "Markdown & CSV" export importer.
+//!
+//! Pages become knowledge entries in the target scope. Databases (exported
+//! as CSV with a sibling directory of row pages) become decisions when the
+//! database looks like a decision log, and knowledge entries otherwise.
+//! Attachments are left for manual migration.
+
+use super::{
+    display_path, markdown_section, normalize_scope_path, parse_decision_status, ImportOptions,
+    ImportPlan, ImportSource, ImportedDecision, ImportedKnowledge, Importer, Provenance,
+};
+use crate::schema::DecisionStatus;
+use crate::{RhemaError, RhemaResult};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use walkdir::WalkDir;
+
+const NOTION_URL: &str = "https://www.notion.so";
+
+/// Importer for Notion Markdown & CSV exports
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NotionImporter;
+
+fn id_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"^(.*?)\s+([0-9a-f]{32})(_all)?$").unwrap())
+}
+
+fn decision_log_pattern() -> &'static Regex {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    PATTERN.get_or_init(|| Regex::new(r"(?i)\b(decisions?|adrs?)\b").unwrap())
+}
+
+/// Title and Notion id from an exported file or directory name
+fn split_name(stem: &str) -> (String, Option<String>) {
+    match id_pattern().captures(stem) {
+        Some(captures) => (captures[1].to_string(), Some(captures[2].to_string())),
+        None => (stem.to_string(), None),
+    }
+}
+
+/// Parse CSV text (RFC 4180, as written by Notion) into rows of fields
+fn parse_csv(content: &str) -> Vec<Vec<String>> {
+    let content = content.trim_start_matches('\u{feff}');
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes => {
+                if chars.peek() == Some(&'"') {
+                    field.push('"');
+                    chars.next();
+                } else {
+                    in_quotes = false;
+                }
+            }
+            '"' if field.is_empty() => in_quotes = true,
+            ',' if !in_quotes => row.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes => {}
+            '\n' if !in_quotes => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows.retain(|row| row.iter().any(|field| !field.trim().is_empty()));
+    rows
+}
+
+/// An exported page split into title, property lines and body
+struct Page {
+    title: Option<String>,
+    properties: Vec<(String, String)>,
+    body: String,
+}
+
+impl Page {
+    fn parse(markdown: &str) -> Self {
+        let mut lines = markdown.lines().peekable();
+        while lines.peek().is_some_and(|line| line.trim().is_empty()) {
+            lines.next();
+        }
+        let title = lines
+            .peek()
+            .and_then(|line| line.strip_prefix("# "))
+            .map(|title| title.trim().to_string());
+        if title.is_some() {
+            lines.next();
+        }
+        while lines.peek().is_some_and(|line| line.trim().is_empty()) {
+            lines.next();
+        }
+
+        // Database rows start with a block of `Property: value` lines
+        let mut properties = Vec::new();
+        while let Some((key, value)) = lines.peek().and_then(|line| line.split_once(": ")) {
+            if key.is_empty() || key.len() > 40 || key.starts_with(['#', '-', '*', '>', '!', '[']) {
+                break;
+            }
+            properties.push((key.trim().to_string(), value.trim().to_string()));
+            lines.next();
+        }
+
+        Self {
+            title,
+            properties,
+            body: lines.collect::<Vec<_>>().join("\n").trim().to_string(),
+        }
+    }
+}
+
+/// Row page of a database: title, Notion id, parsed page and export path
+struct RowPage {
+    id: Option<String>,
+    page: Page,
+    path: PathBuf,
+}
+
+impl NotionImporter {
+    fn provenance(
+        id: Option<&str>,
+        fallback_id: String,
+        source_path: &str,
+        options: &ImportOptions,
+    ) -> Provenance {
+        let base = options.base_url.as_deref().unwrap_or(NOTION_URL);
+        Provenance {
+            system: ImportSource::Notion,
+            source_id: id.map(str::to_string).unwrap_or(fallback_id),
+            source_path: source_path.to_string(),
+            url: id.map(|id| format!("{}/{}", base.trim_end_matches('/'), id)),
+        }
+    }
+
+    /// Relative links to files inside the export, e.g. embedded images
+    fn embedded_files(body: &str) -> usize {
+        static PATTERN: OnceLock<Regex> = OnceLock::new();
+        let pattern = PATTERN.get_or_init(|| Regex::new(r"!?\[[^\]]*\]\(([^)]+)\)").unwrap());
+        pattern
+            .captures_iter(body)
+            .filter(|captures| {
+                let target = &captures[1];
+                !target.contains("://") && !target.ends_with(".md") && !target.ends_with(".csv")
+            })
+            .count()
+    }
+
+    fn database(
+        &self,
+        csv_path: &Path,
+        rows_dir: &Path,
+        root: &Path,
+        options: &ImportOptions,
+        plan: &mut ImportPlan,
+    ) -> RhemaResult<()> {
+        let source_path = display_path(csv_path, root);
+        let stem = csv_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let (name, database_id) = split_name(&stem);
+        let database_id = database_id.unwrap_or_else(|| name.to_lowercase().replace(' ', "-"));
+
+        let mut rows = parse_csv(&std::fs::read_to_string(csv_path)?).into_iter();
+        let Some(headers) = rows.next() else {
+            plan.report
+                .unconverted(&source_path, "database", &name, "Empty database export");
+            return Ok(());
+        };
+        let column = |names: &[&str]| {
+            headers
+                .iter()
+                .position(|header| names.iter().any(|name| header.eq_ignore_ascii_case(name)))
+        };
+
+        // Row pages are matched to CSV rows by title
+        let mut row_pages: HashMap<String, RowPage> = HashMap::new();
+        if rows_dir.is_dir() {
+            for entry in std::fs::read_dir(rows_dir)? {
+                let path = entry?.path();
+                if path.extension().and_then(|ext| ext.to_str()) != Some("md") {
+                    continue;
+                }
+                let stem = path
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                let (title, id) = split_name(&stem);
+                let page = Page::parse(&std::fs::read_to_string(&path)?);
+                let title = page.title.clone().unwrap_or(title);
+                row_pages.insert(title, RowPage { id, page, path });
+            }
+        }
+
+        let is_decision_log =
+            decision_log_pattern().is_match(&name) || column(&["Decision"]).is_some();
+        let status_column = column(&["Status"]);
+        let context_column = column(&["Context", "Background"]);
+        let rationale_column = column(&["Rationale", "Reasoning"]);
+        let makers_column = column(&["Decision makers", "Deciders", "Owner", "Owners"]);
+        let description_column = column(&["Decision", "Description", "Summary"]);
+        let tags_column = column(&["Tags"]);
+        let mapped: HashSet<usize> = if is_decision_log {
+            [
+                Some(0),
+                status_column,
+                context_column,
+                rationale_column,
+                makers_column,
+                description_column,
+            ]
+            .into_iter()
+            .flatten()
+            .collect()
+        } else {
+            [Some(0), tags_column].into_iter().flatten().collect()
+        };
+
+        let scope_path = normalize_scope_path(&options.target_scope)?;
+        let target = format!(
+            "{}/{}",
+            scope_path,
+            if is_decision_log {
+                "decisions.yaml"
+            } else {
+                "knowledge.yaml"
+            }
+        );
+        let cell = |row: &[String], index: Option<usize>| {
+            index
+                .and_then(|index| row.get(index))
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+
+        let mut imported = 0;
+        for row in rows {
+            let Some(title) = cell(&row, Some(0)) else {
+                plan.report.unconverted(
+                    &source_path,
+                    "database row",
+                    "(untitled)",
+                    "Row has no title",
+                );
+                continue;
+            };
+            let row_page = row_pages.remove(&title);
+            let row_source = row_page
+                .as_ref()
+                .map(|row_page| display_path(&row_page.path, root))
+                .unwrap_or_else(|| source_path.clone());
+            let provenance = Self::provenance(
+                row_page
+                    .as_ref()
+                    .and_then(|row_page| row_page.id.as_deref()),
+                format!("{}:{}", database_id, title),
+                &row_source,
+                options,
+            );
+            let body = row_page
+                .as_ref()
+                .map(|row_page| row_page.page.body.clone())
+                .filter(|body| !body.is_empty());
+
+            if is_decision_log {
+                let status_text = cell(&row, status_column);
+                let status = status_text.as_deref().and_then(parse_decision_status);
+                let description = cell(&row, description_column)
+                    .or_else(|| body.clone())
+                    .unwrap_or_else(|| title.clone());
+                let context = cell(&row, context_column).or_else(|| {
+                    body.as_deref()
+                        .and_then(|body| markdown_section(body, &["Context", "Background"]))
+                });
+                let rationale = cell(&row, rationale_column).or_else(|| {
+                    body.as_deref()
+                        .and_then(|body| markdown_section(body, &["Rationale", "Reasoning"]))
+                });
+                let decision_makers = cell(&row, makers_column)
+                    .map(|makers| {
+                        makers
+                            .split(',')
+                            .map(|maker| maker.trim().to_string())
+                            .filter(|maker| !maker.is_empty())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                plan.scope_mut(&scope_path)?
+                    .decisions
+                    .push(ImportedDecision {
+                        title: title.clone(),
+                        description,
+                        status: status.clone().unwrap_or(DecisionStatus::Proposed),
+                        context,
+                        rationale,
+                        decision_makers,
+                        provenance,
+                    });
+                match (status, status_text) {
+                    (None, Some(text)) => plan.report.partial(
+                        &row_source,
+                        "database row",
+                        &title,
+                        &target,
+                        &format!("Unrecognised status '{}' imported as proposed", text),
+                    ),
+                    _ => plan
+                        .report
+                        .converted(&row_source, "database row", &title, &target),
+                }
+            } else {
+                let content = body.unwrap_or_else(|| {
+                    headers
+                        .iter()
+                        .enumerate()
+                        .filter(|(index, _)| !mapped.contains(index))
+                        .filter_map(|(index, header)| {
+                            cell(&row, Some(index)).map(|value| format!("{}: {}", header, value))
+                        })
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                });
+                if content.is_empty() {
+                    plan.report.unconverted(
+                        &row_source,
+                        "database row",
+                        &title,
+                        "Row has no content besides its title",
+                    );
+                    continue;
+                }
+                let tags = cell(&row, tags_column)
+                    .map(|tags| {
+                        tags.split(',')
+                            .map(|tag| tag.trim().to_string())
+                            .filter(|tag| !tag.is_empty())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                plan.scope_mut(&scope_path)?
+                    .knowledge
+                    .push(ImportedKnowledge {
+                        title: title.clone(),
+                        content,
+                        category: Some(name.clone()),
+                        tags,
+                        provenance,
+                    });
+                plan.report
+                    .converted(&row_source, "database row", &title, &target);
+            }
+            imported += 1;
+        }
+
+        for (title, row_page) in row_pages {
+            plan.report.unconverted(
+                &display_path(&row_page.path, root),
+                "database row",
+                &title,
+                "Row page has no matching row in the database CSV",
+            );
+        }
+
+        let unmapped: Vec<&str> = headers
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| is_decision_log && !mapped.contains(index))
+            .map(|(_, header)| header.as_str())
+            .collect();
+        if !unmapped.is_empty() && imported > 0 {
+            plan.report.partial(
+                &source_path,
+                "database",
+                &name,
+                &target,
+                &format!("Columns without a decision field: {}", unmapped.join(", ")),
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Importer for NotionImporter {
+    fn source(&self) -> ImportSource {
+        ImportSource::Notion
+    }
+
+    fn plan(&self, input: &Path, options: &ImportOptions) -> RhemaResult<ImportPlan> {
+        let root = if input.is_file() {
+            input.parent().unwrap_or(Path::new(".")).to_path_buf()
+        } else {
+            input.to_path_buf()
+        };
+        let mut files: Vec<PathBuf> = WalkDir::new(input)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .filter(|path| {
+                !path
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+            })
+            .collect();
+        files.sort();
+        if files.is_empty() {
+            return Err(RhemaError::NotFound(format!(
+                "No Notion export found at {}",
+                input.display()
+            )));
+        }
+
+        // Newer exports write both `<db>.csv` (current view) and
+        // `<db>_all.csv` (every row); prefer the latter
+        let csv_files: HashSet<&PathBuf> = files
+            .iter()
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("csv"))
+            .collect();
+        let mut databases = Vec::new();
+        for csv_path in &csv_files {
+            let stem = csv_path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let base = stem.strip_suffix("_all").unwrap_or(&stem).to_string();
+            let all_variant = csv_path.with_file_name(format!("{}_all.csv", base));
+            if !stem.ends_with("_all") && csv_files.contains(&all_variant) {
+                continue;
+            }
+            databases.push(((*csv_path).clone(), csv_path.with_file_name(base)));
+        }
+        databases.sort();
+        let row_dirs: HashSet<&Path> = databases
+            .iter()
+            .map(|(_, rows_dir)| rows_dir.as_path())
+            .collect();
+
+        let mut plan = ImportPlan::new(ImportSource::Notion);
+        let scope_path = normalize_scope_path(&options.target_scope)?;
+        for (csv_path, rows_dir) in &databases {
+            self.database(csv_path, rows_dir, &root, options, &mut plan)?;
+        }
+
+        for path in &files {
+            let source_path = display_path(path, &root);
+            let extension = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .map(str::to_lowercase);
+            match extension.as_deref() {
+                Some("csv") => continue,
+                Some("md") if path.parent().is_some_and(|dir| row_dirs.contains(dir)) => continue,
+                Some("md") => {}
+                _ => {
+                    let name = path
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                    plan.report.unconverted(
+                        &source_path,
+                        "attachment",
+                        &name,
+                        "Attachments are not imported; move the file into the repository and link it",
+                    );
+                    continue;
+                }
+            }
+
+            let stem = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let (file_title, id) = split_name(&stem);
+            let page = Page::parse(&std::fs::read_to_string(path)?);
+            let title = page.title.clone().unwrap_or(file_title);
+            if page.body.is_empty() {
+                plan.report
+                    .unconverted(&source_path, "page", &title, "Page has no content");
+                continue;
+            }
+
+            // Nested pages are categorised by their top-level ancestor
+            let category = path.strip_prefix(&root).ok().and_then(|relative| {
+                let mut components = relative.components();
+                let first = components.next()?;
+                components.next()?;
+                Some(split_name(&first.as_os_str().to_string_lossy()).0)
+            });
+            let mut content = page.body.clone();
+            if !page.properties.is_empty() {
+                let properties: Vec<String> = page
+                    .properties
+                    .iter()
+                    .map(|(key, value)| format!("{}: {}", key, value))
+                    .collect();
+                content = format!("{}\n\n{}", properties.join("\n"), content);
+            }
+            let embedded = Self::embedded_files(&page.body);
+
+            plan.scope_mut(&scope_path)?
+                .knowledge
+                .push(ImportedKnowledge {
+                    title: title.clone(),
+                    content,
+                    category,
+                    tags: Vec::new(),
+                    provenance: Self::provenance(
+                        id.as_deref(),
+                        source_path.clone(),
+                        &source_path,
+                        options,
+                    ),
+                });
+            let target = format!("{}/knowledge.yaml", scope_path);
+            if embedded > 0 {
+                plan.report.partial(
+                    &source_path,
+                    "page",
+                    &title,
+                    &target,
+                    &format!(
+                        "{} embedded file(s) kept as links relative to the export",
+                        embedded
+                    ),
+                );
+            } else {
+                plan.report.converted(&source_path, "page", &title, &target);
+            }
+        }
+
+        Ok(plan)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv_handles_quotes_and_bom() {
+        let rows = parse_csv("\u{feff}Name,Notes\r\n\"Use \"\"Rust\"\"\",\"a, b\nc\"\r\n");
+        assert_eq!(
+            rows,
+            vec![
+                vec!["Name".to_string(), "Notes".to_string()],
+                vec!["Use \"Rust\"".to_string(), "a, b\nc".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pages_and_decision_databases() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(
+            root.join("Runbook 0123456789abcdef0123456789abcdef.md"),
+            "# Runbook\n\nRestart the worker.\n\n![diagram](Runbook/diagram.png)\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("Decision Log fedcba9876543210fedcba9876543210.csv"),
+            "\u{feff}Name,Status,Deciders,Priority\nUse Postgres,Accepted,\"Ada, Lin\",High\nDrop MySQL,Pending,,Low\n",
+        )
+        .unwrap();
+        let rows_dir = root.join("Decision Log fedcba9876543210fedcba9876543210");
+        std::fs::create_dir_all(&rows_dir).unwrap();
+        std::fs::write(
+            rows_dir.join("Use Postgres 11111111111111111111111111111111.md"),
+            "# Use Postgres\n\nStatus: Accepted\n\n## Context\n\nWe need JSON queries.\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("diagram.png"), [0u8; 4]).unwrap();
+
+        let plan = NotionImporter
+            .plan(
+                root,
+                &ImportOptions {
+                    target_scope: "docs".to_string(),
+                    base_url: None,
+                },
+            )
+            .unwrap();
+
+        let scope = &plan.scopes[0];
+        assert_eq!(scope.path, "docs");
+        assert_eq!(scope.decisions.len(), 2);
+        let postgres = &scope.decisions[0];
+        assert_eq!(postgres.status, DecisionStatus::Approved);
+        assert_eq!(postgres.context.as_deref(), Some("We need JSON queries."));
+        assert_eq!(postgres.decision_makers, vec!["Ada", "Lin"]);
+        assert_eq!(
+            postgres.provenance.url.as_deref(),
+            Some("https://www.notion.so/11111111111111111111111111111111")
+        );
+        assert_eq!(scope.decisions[1].status, DecisionStatus::Proposed);
+
+        assert_eq!(scope.knowledge.len(), 1);
+        assert_eq!(scope.knowledge[0].title, "Runbook");
+
+        let partial: Vec<&str> = plan
+            .report
+            .partial
+            .iter()
+            .map(|item| item.name.as_str())
+            .collect();
+        assert_eq!(partial, vec!["Drop MySQL", "Decision Log", "Runbook"]);
+        assert_eq!(plan.report.unconverted.len(), 1);
+        assert_eq!(plan.report.unconverted[0].kind, "attachment");
+    }
+}
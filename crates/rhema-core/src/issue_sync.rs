@@ -0,0 +1,1114 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Two-way sync of todos with GitHub Issues.
+//!
+//! A todo is linked to an issue by its `github_issue` field, written
+//! `owner/name#42`, or `#42` for the configured `issue_sync.repository`.
+//! Each sync compares both sides with the state they agreed on last time,
+//! kept in the ledger at `.rhema/sync/issues.yaml`. A side that moved since
+//! then wins; when both moved to different states, `issue_sync.conflict`
+//! decides, by default by recording a conflict to settle by hand. A link
+//! seen for the first time takes the issue's state.
+//!
+//! Issue comments are mirrored into the todo's `notes`, and notes written
+//! locally are posted as comments. The id of every comment that went either
+//! way is kept on its note and in the ledger, so nothing comes back around.
+//! Sync can be paused per scope, and [`IssueSyncJob`] runs it on the
+//! [`JobQueue`].
+
+use crate::file_ops::{read_yaml_file, write_yaml_file};
+use crate::jobs::{Job, JobContext, JobHandler, JobQueue, JobSpec, JobStatus};
+use crate::policy;
+use crate::scope::discover_scopes;
+use crate::sync::{transport_error, Resolution, SYNC_DIR};
+use crate::{RhemaError, RhemaResult};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Section of `.rhema/repository.yaml` configuring issue sync
+pub const ISSUE_SYNC_CONFIG_SECTION: &str = "issue_sync";
+
+/// Todo field linking it to an issue
+pub const ISSUE_FIELD: &str = "github_issue";
+
+/// Todo field holding notes mirrored to and from issue comments
+pub const NOTES_FIELD: &str = "notes";
+
+/// Job kind running an issue sync
+pub const ISSUE_SYNC_JOB: &str = "sync.issues";
+
+/// Environment variable read for the GitHub token unless configured otherwise
+pub const DEFAULT_GITHUB_TOKEN_ENV: &str = "GITHUB_TOKEN";
+
+/// Which side wins when a todo and its issue both changed state
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictRule {
+    /// Record a conflict and leave both sides alone until it is resolved
+    #[default]
+    Manual,
+    Local,
+    Remote,
+}
+
+/// Issue sync configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IssueSyncConfig {
+    /// GitHub API base URL; differs for GitHub Enterprise
+    pub api_url: String,
+
+    /// Repository (`owner/name`) of links written as `#42`
+    pub repository: Option<String>,
+
+    /// Environment variable holding the GitHub token
+    pub token_env: String,
+
+    /// Request timeout in seconds
+    pub timeout_secs: u64,
+
+    /// Copy issue comments into todo notes
+    pub mirror_comments: bool,
+
+    /// Post new todo notes as issue comments
+    pub post_notes: bool,
+
+    pub conflict: ConflictRule,
+
+    /// Sync on the job queue every `interval_secs`
+    pub scheduled: bool,
+    pub interval_secs: u64,
+}
+
+impl Default for IssueSyncConfig {
+    fn default() -> Self {
+        Self {
+            api_url: "https://api.github.com".to_string(),
+            repository: None,
+            token_env: DEFAULT_GITHUB_TOKEN_ENV.to_string(),
+            timeout_secs: 30,
+            mirror_comments: true,
+            post_notes: true,
+            conflict: ConflictRule::Manual,
+            scheduled: false,
+            interval_secs: 900,
+        }
+    }
+}
+
+impl IssueSyncConfig {
+    /// Load the configuration from the repository config and policy bundle,
+    /// falling back to defaults
+    pub fn load(repo_root: &Path) -> RhemaResult<Self> {
+        let value = policy::repository_config(repo_root)?;
+        match value.get(ISSUE_SYNC_CONFIG_SECTION) {
+            Some(section) => serde_yaml::from_value(section.clone()).map_err(|e| {
+                RhemaError::ConfigError(format!(
+                    "Invalid {} section in the repository config: {}",
+                    ISSUE_SYNC_CONFIG_SECTION, e
+                ))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+/// GitHub issue a todo is linked to
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IssueRef {
+    /// `owner/name`
+    pub repository: String,
+    pub number: u64,
+}
+
+impl IssueRef {
+    /// Parse `owner/name#42`, an issue URL, or `#42` / `42` in
+    /// `default_repository`
+    pub fn parse(link: &str, default_repository: Option<&str>) -> RhemaResult<Self> {
+        let invalid = || {
+            RhemaError::InvalidInput(format!(
+                "Invalid issue link '{}', expected owner/name#number",
+                link
+            ))
+        };
+        let link = link.trim();
+        let (repository, number) = if let Some(path) = link
+            .strip_prefix("https://")
+            .and_then(|rest| rest.split_once('/'))
+            .map(|(_, path)| path)
+        {
+            let mut parts = path.trim_end_matches('/').split('/');
+            match (parts.next(), parts.next(), parts.next(), parts.next()) {
+                (Some(owner), Some(name), Some("issues"), Some(number)) => {
+                    (format!("{}/{}", owner, name), number)
+                }
+                _ => return Err(invalid()),
+            }
+        } else {
+            match link.split_once('#') {
+                Some(("", number)) => (default_repository.ok_or_else(invalid)?.to_string(), number),
+                Some((repository, number)) => (repository.to_string(), number),
+                None => (default_repository.ok_or_else(invalid)?.to_string(), link),
+            }
+        };
+
+        let valid_repository = repository.split_once('/').is_some_and(|(owner, name)| {
+            !owner.is_empty() && !name.is_empty() && !name.contains('/')
+        });
+        match number.parse() {
+            Ok(number) if valid_repository => Ok(Self { repository, number }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl fmt::Display for IssueRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}#{}", self.repository, self.number)
+    }
+}
+
+/// Issue state as far as todos are concerned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueState {
+    Open,
+    /// Closed as completed
+    Completed,
+    /// Closed as not planned
+    NotPlanned,
+}
+
+impl IssueState {
+    /// State a todo status stands for
+    pub fn of_status(status: &str) -> Self {
+        match status {
+            "completed" => Self::Completed,
+            "cancelled" => Self::NotPlanned,
+            _ => Self::Open,
+        }
+    }
+
+    /// Todo status for this state, keeping `current` when it already agrees,
+    /// so an open issue leaves a todo in progress or blocked as it is
+    fn todo_status(self, current: &str) -> &str {
+        if Self::of_status(current) == self {
+            return current;
+        }
+        match self {
+            Self::Open => "pending",
+            Self::Completed => "completed",
+            Self::NotPlanned => "cancelled",
+        }
+    }
+}
+
+impl fmt::Display for IssueState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Open => write!(f, "open"),
+            Self::Completed => write!(f, "completed"),
+            Self::NotPlanned => write!(f, "not planned"),
+        }
+    }
+}
+
+/// Comment on an issue
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IssueComment {
+    pub id: u64,
+    pub author: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Note on a todo, stored under its `notes` field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TodoNote {
+    pub body: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+
+    #[serde(default = "Utc::now")]
+    pub created_at: DateTime<Utc>,
+
+    /// Issue comment the note was mirrored from or posted as
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment_id: Option<u64>,
+}
+
+/// Connection to the issue tracker.
+///
+/// Implementations report an unreachable tracker as
+/// [`RhemaError::NetworkError`] and a missing issue as
+/// [`RhemaError::NotFound`].
+#[async_trait]
+pub trait IssueTracker: Send + Sync {
+    async fn state(&self, issue: &IssueRef) -> RhemaResult<IssueState>;
+
+    async fn set_state(&self, issue: &IssueRef, state: IssueState) -> RhemaResult<()>;
+
+    async fn comments(&self, issue: &IssueRef) -> RhemaResult<Vec<IssueComment>>;
+
+    async fn post_comment(&self, issue: &IssueRef, body: &str) -> RhemaResult<IssueComment>;
+}
+
+/// [`IssueTracker`] for the GitHub REST API
+pub struct GitHubTracker {
+    client: reqwest::Client,
+    api_url: String,
+    token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GitHubIssue {
+    state: String,
+    #[serde(default)]
+    state_reason: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GitHubUser {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct GitHubComment {
+    id: u64,
+    #[serde(default)]
+    user: Option<GitHubUser>,
+    #[serde(default)]
+    body: String,
+    created_at: DateTime<Utc>,
+}
+
+impl From<GitHubComment> for IssueComment {
+    fn from(comment: GitHubComment) -> Self {
+        Self {
+            id: comment.id,
+            author: comment
+                .user
+                .map(|user| user.login)
+                .unwrap_or_else(|| "ghost".to_string()),
+            body: comment.body,
+            created_at: comment.created_at,
+        }
+    }
+}
+
+/// Comments fetched per page
+const COMMENTS_PER_PAGE: usize = 100;
+
+impl GitHubTracker {
+    pub fn new(config: &IssueSyncConfig) -> RhemaResult<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .user_agent("rhema")
+            .build()
+            .map_err(|e| RhemaError::ConfigError(e.to_string()))?;
+        Ok(Self {
+            client,
+            api_url: config.api_url.trim_end_matches('/').to_string(),
+            token: std::env::var(&config.token_env).ok(),
+        })
+    }
+
+    fn request(
+        &self,
+        method: reqwest::Method,
+        issue: &IssueRef,
+        path: &str,
+    ) -> reqwest::RequestBuilder {
+        let url = format!(
+            "{}/repos/{}/issues/{}{}",
+            self.api_url, issue.repository, issue.number, path
+        );
+        let request = self
+            .client
+            .request(method, url)
+            .header("Accept", "application/vnd.github+json");
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    async fn send<R: serde::de::DeserializeOwned>(
+        &self,
+        issue: &IssueRef,
+        request: reqwest::RequestBuilder,
+    ) -> RhemaResult<R> {
+        let response = request.send().await.map_err(transport_error)?;
+        let status = response.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+            return Err(RhemaError::AuthenticationError(format!(
+                "GitHub rejected the token for {} ({})",
+                issue, status
+            )));
+        }
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Err(RhemaError::NotFound(format!("GitHub issue {}", issue)));
+        }
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(RhemaError::ExternalServiceError(format!(
+                "GitHub returned {} for {}: {}",
+                status, issue, body
+            )));
+        }
+        response.json().await.map_err(transport_error)
+    }
+}
+
+#[async_trait]
+impl IssueTracker for GitHubTracker {
+    async fn state(&self, issue: &IssueRef) -> RhemaResult<IssueState> {
+        let found: GitHubIssue = self
+            .send(issue, self.request(reqwest::Method::GET, issue, ""))
+            .await?;
+        Ok(
+            match (found.state.as_str(), found.state_reason.as_deref()) {
+                ("open", _) => IssueState::Open,
+                (_, Some("not_planned")) => IssueState::NotPlanned,
+                _ => IssueState::Completed,
+            },
+        )
+    }
+
+    async fn set_state(&self, issue: &IssueRef, state: IssueState) -> RhemaResult<()> {
+        let fields = match state {
+            IssueState::Open => serde_json::json!({ "state": "open" }),
+            IssueState::Completed => {
+                serde_json::json!({ "state": "closed", "state_reason": "completed" })
+            }
+            IssueState::NotPlanned => {
+                serde_json::json!({ "state": "closed", "state_reason": "not_planned" })
+            }
+        };
+        let request = self
+            .request(reqwest::Method::PATCH, issue, "")
+            .json(&fields);
+        self.send::<serde_json::Value>(issue, request).await?;
+        Ok(())
+    }
+
+    async fn comments(&self, issue: &IssueRef) -> RhemaResult<Vec<IssueComment>> {
+        let mut comments = Vec::new();
+        for page in 1.. {
+            let request = self
+                .request(reqwest::Method::GET, issue, "/comments")
+                .query(&[("per_page", COMMENTS_PER_PAGE), ("page", page)]);
+            let batch: Vec<GitHubComment> = self.send(issue, request).await?;
+            let last = batch.len() < COMMENTS_PER_PAGE;
+            comments.extend(batch.into_iter().map(IssueComment::from));
+            if last {
+                break;
+            }
+        }
+        Ok(comments)
+    }
+
+    async fn post_comment(&self, issue: &IssueRef, body: &str) -> RhemaResult<IssueComment> {
+        let request = self
+            .request(reqwest::Method::POST, issue, "/comments")
+            .json(&serde_json::json!({ "body": body }));
+        let comment: GitHubComment = self.send(issue, request).await?;
+        Ok(comment.into())
+    }
+}
+
+/// What both sides of a link agreed on at the last sync
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LinkState {
+    pub issue: String,
+    pub state: IssueState,
+
+    /// Comments mirrored into notes or posted from them
+    #[serde(default)]
+    pub comments: BTreeSet<u64>,
+
+    pub synced_at: DateTime<Utc>,
+}
+
+/// A todo and its issue both changed state since the last sync
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatusConflict {
+    /// `scope/todo-id`
+    pub key: String,
+    pub issue: String,
+    pub local: IssueState,
+    pub remote: IssueState,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// Persistent issue sync bookkeeping
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IssueLedger {
+    /// Scopes whose todos are left alone
+    pub paused: BTreeSet<String>,
+
+    /// Link states keyed by `scope/todo-id`
+    pub links: BTreeMap<String, LinkState>,
+
+    pub conflicts: Vec<StatusConflict>,
+
+    pub last_sync: Option<DateTime<Utc>>,
+}
+
+/// Outcome of an issue sync
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct IssueSyncReport {
+    /// Linked todos checked
+    pub linked: usize,
+
+    /// Todos whose status followed their issue
+    pub todos_updated: usize,
+
+    /// Issues reopened or closed to follow their todo
+    pub issues_updated: usize,
+
+    /// Comments copied into notes
+    pub comments_mirrored: usize,
+
+    /// Notes posted as comments
+    pub notes_posted: usize,
+
+    /// New status conflicts
+    pub conflicts: usize,
+
+    /// Scopes skipped because their sync is paused
+    pub paused: usize,
+
+    /// Todos (`scope/todo-id`) whose link is malformed or names no issue
+    pub invalid_links: Vec<String>,
+
+    /// GitHub was unreachable; the remaining todos wait for the next sync
+    pub offline: bool,
+}
+
+/// Syncs linked todos with their issues through an [`IssueTracker`]
+pub struct IssueSync<T: IssueTracker> {
+    repo_root: PathBuf,
+    config: IssueSyncConfig,
+    tracker: T,
+    ledger: IssueLedger,
+}
+
+impl IssueSync<GitHubTracker> {
+    /// Sync configured in `.rhema/repository.yaml`
+    pub fn open(repo_root: &Path) -> RhemaResult<Self> {
+        let config = IssueSyncConfig::load(repo_root)?;
+        let tracker = GitHubTracker::new(&config)?;
+        Self::with_tracker(repo_root, config, tracker)
+    }
+}
+
+impl<T: IssueTracker> IssueSync<T> {
+    pub fn with_tracker(
+        repo_root: &Path,
+        config: IssueSyncConfig,
+        tracker: T,
+    ) -> RhemaResult<Self> {
+        let path = ledger_path(repo_root);
+        let ledger = if path.exists() {
+            read_yaml_file(&path)?
+        } else {
+            IssueLedger::default()
+        };
+        Ok(Self {
+            repo_root: repo_root.to_path_buf(),
+            config,
+            tracker,
+            ledger,
+        })
+    }
+
+    pub fn config(&self) -> &IssueSyncConfig {
+        &self.config
+    }
+
+    pub fn ledger(&self) -> &IssueLedger {
+        &self.ledger
+    }
+
+    /// Stop syncing the todos of a scope until it is resumed
+    pub fn pause(&mut self, scope: &str) -> RhemaResult<()> {
+        let exists = discover_scopes(&self.repo_root)?
+            .iter()
+            .any(|found| found.definition.name == scope);
+        if !exists {
+            return Err(RhemaError::ScopeNotFound(scope.to_string()));
+        }
+        self.ledger.paused.insert(scope.to_string());
+        self.save()
+    }
+
+    /// Sync a paused scope again; changes made meanwhile go out on the next
+    /// sync. Returns whether the scope was paused
+    pub fn resume(&mut self, scope: &str) -> RhemaResult<bool> {
+        let resumed = self.ledger.paused.remove(scope);
+        if resumed {
+            self.save()?;
+        }
+        Ok(resumed)
+    }
+
+    /// Settle a status conflict; the kept side goes out on the next sync
+    pub fn resolve(&mut self, key: &str, keep: Resolution) -> RhemaResult<()> {
+        let index = self
+            .ledger
+            .conflicts
+            .iter()
+            .position(|conflict| conflict.key == key)
+            .ok_or_else(|| RhemaError::NotFound(format!("Issue sync conflict for {}", key)))?;
+        let conflict = self.ledger.conflicts.remove(index);
+
+        // Agreeing on the discarded side makes the kept one the only change
+        if let Some(link) = self.ledger.links.get_mut(key) {
+            link.state = match keep {
+                Resolution::Local => conflict.remote,
+                Resolution::Remote => conflict.local,
+            };
+        }
+        self.save()
+    }
+
+    /// Sync every linked todo in scopes that are not paused
+    pub async fn sync(&mut self) -> RhemaResult<IssueSyncReport> {
+        let mut report = IssueSyncReport::default();
+        let mut failure = None;
+
+        'scopes: for scope in discover_scopes(&self.repo_root)? {
+            let name = scope.definition.name.clone();
+            if self.ledger.paused.contains(&name) {
+                report.paused += 1;
+                continue;
+            }
+            let path = scope.path.join("todos.yaml");
+            if !path.exists() {
+                continue;
+            }
+
+            let original: Value = read_yaml_file(&path)?;
+            let mut root = original.clone();
+            if let Some(Value::Sequence(todos)) = root.get_mut("todos") {
+                for todo in todos {
+                    let Value::Mapping(todo) = todo else {
+                        continue;
+                    };
+                    let Some(link) = todo.get(ISSUE_FIELD).and_then(link_text) else {
+                        continue;
+                    };
+                    let id = todo.get("id").and_then(Value::as_str).unwrap_or_default();
+                    let key = format!("{}/{}", name, id);
+                    let issue = match IssueRef::parse(&link, self.config.repository.as_deref()) {
+                        Ok(issue) => issue,
+                        Err(_) => {
+                            report.invalid_links.push(key);
+                            continue;
+                        }
+                    };
+
+                    report.linked += 1;
+                    match self.sync_todo(&key, todo, &issue, &mut report).await {
+                        Ok(()) => {}
+                        Err(RhemaError::NotFound(_)) => report.invalid_links.push(key),
+                        Err(RhemaError::NetworkError(_)) => {
+                            report.offline = true;
+                            break;
+                        }
+                        Err(e) => {
+                            failure = Some(e);
+                            break;
+                        }
+                    }
+                }
+            }
+
+            // Written even after a failure so posted comments stay recorded
+            if root != original {
+                write_yaml_file(&path, &root)?;
+            }
+            if report.offline || failure.is_some() {
+                break 'scopes;
+            }
+        }
+
+        if failure.is_none() && !report.offline {
+            self.ledger.last_sync = Some(Utc::now());
+        }
+        self.save()?;
+        match failure {
+            Some(e) => Err(e),
+            None => Ok(report),
+        }
+    }
+
+    async fn sync_todo(
+        &mut self,
+        key: &str,
+        todo: &mut Mapping,
+        issue: &IssueRef,
+        report: &mut IssueSyncReport,
+    ) -> RhemaResult<()> {
+        let remote = self.tracker.state(issue).await?;
+        let status = todo
+            .get("status")
+            .and_then(Value::as_str)
+            .unwrap_or("pending")
+            .to_string();
+        let local = IssueState::of_status(&status);
+
+        // A new link, or one moved to another issue, starts from the issue
+        let link = self
+            .ledger
+            .links
+            .get(key)
+            .filter(|link| link.issue == issue.to_string())
+            .cloned();
+        let base = link.as_ref().map_or(remote, |link| link.state);
+        let conflicted = self.ledger.conflicts.iter().any(|c| c.key == key);
+
+        let agreed = if conflicted {
+            base
+        } else {
+            match (local != base, remote != base) {
+                (false, false) => base,
+                (true, false) => self.push_state(issue, local, report).await?,
+                (false, true) => pull_state(todo, &status, remote, report),
+                (true, true) if local == remote => local,
+                (true, true) => match self.config.conflict {
+                    ConflictRule::Local => self.push_state(issue, local, report).await?,
+                    ConflictRule::Remote => pull_state(todo, &status, remote, report),
+                    ConflictRule::Manual => {
+                        self.ledger.conflicts.push(StatusConflict {
+                            key: key.to_string(),
+                            issue: issue.to_string(),
+                            local,
+                            remote,
+                            detected_at: Utc::now(),
+                        });
+                        report.conflicts += 1;
+                        base
+                    }
+                },
+            }
+        };
+
+        let mut seen = link.map(|link| link.comments).unwrap_or_default();
+        let result = self.sync_notes(todo, issue, &mut seen, report).await;
+        self.ledger.links.insert(
+            key.to_string(),
+            LinkState {
+                issue: issue.to_string(),
+                state: agreed,
+                comments: seen,
+                synced_at: Utc::now(),
+            },
+        );
+        result
+    }
+
+    async fn push_state(
+        &self,
+        issue: &IssueRef,
+        state: IssueState,
+        report: &mut IssueSyncReport,
+    ) -> RhemaResult<IssueState> {
+        self.tracker.set_state(issue, state).await?;
+        report.issues_updated += 1;
+        Ok(state)
+    }
+
+    /// Mirror new comments into notes and post new notes. Notes are written
+    /// back before an error is returned, so posted comments are not lost
+    async fn sync_notes(
+        &self,
+        todo: &mut Mapping,
+        issue: &IssueRef,
+        seen: &mut BTreeSet<u64>,
+        report: &mut IssueSyncReport,
+    ) -> RhemaResult<()> {
+        if !self.config.mirror_comments && !self.config.post_notes {
+            return Ok(());
+        }
+        let mut notes: Vec<TodoNote> = match todo.get(NOTES_FIELD) {
+            None | Some(Value::Null) => Vec::new(),
+            Some(value) => match serde_yaml::from_value(value.clone()) {
+                Ok(notes) => notes,
+                Err(e) => {
+                    tracing::warn!("Not syncing notes of {}: {}", issue, e);
+                    return Ok(());
+                }
+            },
+        };
+        let before = notes.len();
+        seen.extend(notes.iter().filter_map(|note| note.comment_id));
+
+        let mut result = Ok(());
+        if self.config.mirror_comments {
+            match self.tracker.comments(issue).await {
+                Ok(comments) => {
+                    for comment in comments {
+                        if seen.insert(comment.id) {
+                            notes.push(TodoNote {
+                                body: comment.body,
+                                author: Some(comment.author),
+                                created_at: comment.created_at,
+                                comment_id: Some(comment.id),
+                            });
+                            report.comments_mirrored += 1;
+                        }
+                    }
+                }
+                Err(e) => result = Err(e),
+            }
+        }
+        let mut posted = 0;
+        if self.config.post_notes && result.is_ok() {
+            for note in notes.iter_mut().filter(|note| note.comment_id.is_none()) {
+                match self.tracker.post_comment(issue, &note.body).await {
+                    Ok(comment) => {
+                        note.comment_id = Some(comment.id);
+                        seen.insert(comment.id);
+                        posted += 1;
+                    }
+                    Err(e) => {
+                        result = Err(e);
+                        break;
+                    }
+                }
+            }
+        }
+
+        report.notes_posted += posted;
+        if notes.len() != before || posted > 0 {
+            todo.insert(
+                Value::String(NOTES_FIELD.to_string()),
+                serde_yaml::to_value(&notes)?,
+            );
+        }
+        result
+    }
+
+    fn save(&self) -> RhemaResult<()> {
+        write_yaml_file(&ledger_path(&self.repo_root), &self.ledger)
+    }
+}
+
+/// Set a todo's status to follow its issue
+fn pull_state(
+    todo: &mut Mapping,
+    status: &str,
+    state: IssueState,
+    report: &mut IssueSyncReport,
+) -> IssueState {
+    let new_status = state.todo_status(status);
+    todo.insert(
+        Value::String("status".to_string()),
+        Value::String(new_status.to_string()),
+    );
+    let completed_at = Value::String("completed_at".to_string());
+    if state == IssueState::Open {
+        todo.remove(&completed_at);
+    } else if todo.get(&completed_at).is_none_or(Value::is_null) {
+        todo.insert(completed_at, Value::String(Utc::now().to_rfc3339()));
+    }
+    report.todos_updated += 1;
+    state
+}
+
+/// Link text of a `github_issue` field, which may be a bare number
+fn link_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(link) => Some(link.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+fn ledger_path(repo_root: &Path) -> PathBuf {
+    repo_root.join(SYNC_DIR).join("issues.yaml")
+}
+
+/// Runs an issue sync on the job queue as [`ISSUE_SYNC_JOB`]
+pub struct IssueSyncJob {
+    repo_root: PathBuf,
+}
+
+impl IssueSyncJob {
+    pub fn new(repo_root: &Path) -> Self {
+        Self {
+            repo_root: repo_root.to_path_buf(),
+        }
+    }
+}
+
+#[async_trait]
+impl JobHandler for IssueSyncJob {
+    async fn run(&self, _job: &Job, _context: &JobContext) -> RhemaResult<serde_json::Value> {
+        let report = IssueSync::open(&self.repo_root)?.sync().await?;
+        if report.offline {
+            return Err(RhemaError::NetworkError(
+                "GitHub unreachable; issue sync will retry".to_string(),
+            ));
+        }
+        Ok(serde_json::to_value(&report)?)
+    }
+}
+
+/// Schedule issue syncs on the job queue, replacing any sync already queued.
+/// Returns the first sync job, or `None` when scheduling is off
+pub fn schedule_issue_sync(queue: &JobQueue, config: &IssueSyncConfig) -> RhemaResult<Option<Job>> {
+    for job in queue.list(Some(JobStatus::Queued))? {
+        if job.kind == ISSUE_SYNC_JOB {
+            queue.cancel(&job.id)?;
+        }
+    }
+    if !config.scheduled {
+        return Ok(None);
+    }
+    let job = queue.enqueue(
+        JobSpec::new(ISSUE_SYNC_JOB, serde_json::json!({}))
+            .with_repeat_every(Duration::from_secs(config.interval_secs.max(300))),
+    )?;
+    Ok(Some(job))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    /// In-memory tracker holding issues of `acme/app` by number
+    #[derive(Default)]
+    struct MemoryTracker {
+        issues: Mutex<BTreeMap<u64, (IssueState, Vec<IssueComment>)>>,
+    }
+
+    impl MemoryTracker {
+        fn with_issue(number: u64, state: IssueState) -> Self {
+            let tracker = Self::default();
+            tracker
+                .issues
+                .lock()
+                .unwrap()
+                .insert(number, (state, Vec::new()));
+            tracker
+        }
+
+        fn set(&self, number: u64, state: IssueState) {
+            self.issues.lock().unwrap().get_mut(&number).unwrap().0 = state;
+        }
+
+        fn state_of(&self, number: u64) -> IssueState {
+            self.issues.lock().unwrap()[&number].0
+        }
+
+        fn comment(&self, number: u64, author: &str, body: &str) {
+            let mut issues = self.issues.lock().unwrap();
+            let id = issues.values().map(|(_, c)| c.len() as u64).sum::<u64>() + 1;
+            issues.get_mut(&number).unwrap().1.push(IssueComment {
+                id,
+                author: author.to_string(),
+                body: body.to_string(),
+                created_at: Utc::now(),
+            });
+        }
+    }
+
+    #[async_trait]
+    impl IssueTracker for &MemoryTracker {
+        async fn state(&self, issue: &IssueRef) -> RhemaResult<IssueState> {
+            let issues = self.issues.lock().unwrap();
+            let (state, _) = issues
+                .get(&issue.number)
+                .ok_or_else(|| RhemaError::NotFound(issue.to_string()))?;
+            Ok(*state)
+        }
+
+        async fn set_state(&self, issue: &IssueRef, state: IssueState) -> RhemaResult<()> {
+            self.issues
+                .lock()
+                .unwrap()
+                .get_mut(&issue.number)
+                .unwrap()
+                .0 = state;
+            Ok(())
+        }
+
+        async fn comments(&self, issue: &IssueRef) -> RhemaResult<Vec<IssueComment>> {
+            Ok(self.issues.lock().unwrap()[&issue.number].1.clone())
+        }
+
+        async fn post_comment(&self, issue: &IssueRef, body: &str) -> RhemaResult<IssueComment> {
+            self.comment(issue.number, "rhema-bot", body);
+            Ok(self.issues.lock().unwrap()[&issue.number]
+                .1
+                .last()
+                .unwrap()
+                .clone())
+        }
+    }
+
+    fn setup_repo(todos: &str) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        let scope = dir.path().join("app/.rhema");
+        std::fs::create_dir_all(&scope).unwrap();
+        std::fs::write(
+            scope.join("rhema.yaml"),
+            "name: app\nscope_type: service\nversion: 1.0.0\n",
+        )
+        .unwrap();
+        std::fs::write(scope.join("todos.yaml"), todos).unwrap();
+        dir
+    }
+
+    fn todos_path(dir: &TempDir) -> PathBuf {
+        dir.path().join("app/.rhema/todos.yaml")
+    }
+
+    fn config() -> IssueSyncConfig {
+        IssueSyncConfig {
+            repository: Some("acme/app".to_string()),
+            ..IssueSyncConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_issue_links() {
+        let parse = |link: &str| IssueRef::parse(link, Some("acme/app")).map(|i| i.to_string());
+        assert_eq!(parse("acme/web#7").unwrap(), "acme/web#7");
+        assert_eq!(parse("#42").unwrap(), "acme/app#42");
+        assert_eq!(parse("42").unwrap(), "acme/app#42");
+        assert_eq!(
+            parse("https://github.com/acme/web/issues/9").unwrap(),
+            "acme/web#9"
+        );
+        assert!(parse("acme#x").is_err());
+        assert!(IssueRef::parse("#42", None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_status_changes_propagate_both_ways() {
+        let tracker = MemoryTracker::with_issue(1, IssueState::Open);
+        let repo = setup_repo(
+            "todos:\n- id: t-1\n  title: Ship it\n  status: in_progress\n  github_issue: '#1'\n",
+        );
+        let mut sync = IssueSync::with_tracker(repo.path(), config(), &tracker).unwrap();
+
+        // In progress agrees with an open issue
+        let report = sync.sync().await.unwrap();
+        assert_eq!(
+            (report.linked, report.todos_updated, report.issues_updated),
+            (1, 0, 0)
+        );
+
+        // Closing the issue completes the todo
+        tracker.set(1, IssueState::Completed);
+        assert_eq!(sync.sync().await.unwrap().todos_updated, 1);
+        let todos = std::fs::read_to_string(todos_path(&repo)).unwrap();
+        assert!(todos.contains("status: completed") && todos.contains("completed_at"));
+
+        // Reopening the todo reopens the issue
+        std::fs::write(
+            todos_path(&repo),
+            todos.replace("status: completed", "status: pending"),
+        )
+        .unwrap();
+        assert_eq!(sync.sync().await.unwrap().issues_updated, 1);
+        assert_eq!(tracker.state_of(1), IssueState::Open);
+
+        // Paused scopes are left alone
+        sync.pause("app").unwrap();
+        tracker.set(1, IssueState::NotPlanned);
+        let report = sync.sync().await.unwrap();
+        assert_eq!((report.paused, report.todos_updated), (1, 0));
+        assert!(sync.resume("app").unwrap());
+        assert_eq!(sync.sync().await.unwrap().todos_updated, 1);
+        let todos = std::fs::read_to_string(todos_path(&repo)).unwrap();
+        assert!(todos.contains("status: cancelled"));
+    }
+
+    #[tokio::test]
+    async fn test_comments_mirror_without_loops() {
+        let tracker = MemoryTracker::with_issue(1, IssueState::Open);
+        tracker.comment(1, "octocat", "Seen in production too");
+        let repo = setup_repo(
+            "todos:\n- id: t-1\n  title: Fix crash\n  status: pending\n  github_issue: 1\n  notes:\n  - body: Reproduced locally\n    author: dev\n",
+        );
+        let mut sync = IssueSync::with_tracker(repo.path(), config(), &tracker).unwrap();
+
+        let report = sync.sync().await.unwrap();
+        assert_eq!((report.comments_mirrored, report.notes_posted), (1, 1));
+        assert_eq!(tracker.issues.lock().unwrap()[&1].1.len(), 2);
+
+        // The posted note is not mirrored back, and nothing is posted twice
+        let report = sync.sync().await.unwrap();
+        assert_eq!((report.comments_mirrored, report.notes_posted), (0, 0));
+        let todos: Value = read_yaml_file(&todos_path(&repo)).unwrap();
+        let notes: Vec<TodoNote> =
+            serde_yaml::from_value(todos["todos"][0][NOTES_FIELD].clone()).unwrap();
+        assert_eq!(notes.len(), 2);
+        assert!(notes.iter().all(|note| note.comment_id.is_some()));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_status_changes_conflict_until_resolved() {
+        let tracker = MemoryTracker::with_issue(1, IssueState::Open);
+        let repo = setup_repo(
+            "todos:\n- id: t-1\n  title: Migrate\n  status: pending\n  github_issue: '#1'\n",
+        );
+        let mut sync = IssueSync::with_tracker(repo.path(), config(), &tracker).unwrap();
+        sync.sync().await.unwrap();
+
+        let todos = std::fs::read_to_string(todos_path(&repo)).unwrap();
+        std::fs::write(
+            todos_path(&repo),
+            todos.replace("status: pending", "status: completed"),
+        )
+        .unwrap();
+        tracker.set(1, IssueState::NotPlanned);
+
+        assert_eq!(sync.sync().await.unwrap().conflicts, 1);
+        // Both sides stay as they are while the conflict is open
+        let report = sync.sync().await.unwrap();
+        assert_eq!(
+            (
+                report.conflicts,
+                report.todos_updated,
+                report.issues_updated
+            ),
+            (0, 0, 0)
+        );
+        assert_eq!(tracker.state_of(1), IssueState::NotPlanned);
+
+        sync.resolve("app/t-1", Resolution::Local).unwrap();
+        assert_eq!(sync.sync().await.unwrap().issues_updated, 1);
+        assert_eq!(tracker.state_of(1), IssueState::Completed);
+        assert!(sync.ledger().conflicts.is_empty());
+    }
+}
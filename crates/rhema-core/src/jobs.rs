@@ -0,0 +1,829 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Embedded queue for background jobs.
+//!
+//! Jobs are JSON files under `.rhema/jobs`, so they survive restarts and can
+//! be listed, cancelled and retried from another process. A worker claims due
+//! jobs in priority order, runs each on the [`JobHandler`] registered for its
+//! kind with at most `max_concurrency` running at once, and retries failures
+//! with exponential backoff until the job runs out of attempts. A running job
+//! holds a lease its worker keeps renewing; when a worker dies the lease lapses
+//! and the job is queued again.
+
+use crate::policy;
+use crate::{RhemaError, RhemaResult};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Section of `.rhema/repository.yaml` configuring the job queue
+pub const JOBS_CONFIG_SECTION: &str = "jobs";
+
+/// Directory holding one file per job, relative to the repository root
+pub const JOBS_DIR: &str = ".rhema/jobs";
+
+/// How urgently a job should run, ordered from least to most urgent
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    clap::ValueEnum,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum JobPriority {
+    Low,
+    #[default]
+    Normal,
+    High,
+    Critical,
+}
+
+impl fmt::Display for JobPriority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            JobPriority::Low => "low",
+            JobPriority::Normal => "normal",
+            JobPriority::High => "high",
+            JobPriority::Critical => "critical",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    clap::ValueEnum,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    /// Waiting for its `run_after` time and a free worker slot
+    Queued,
+    Running,
+    Succeeded,
+    /// Out of attempts
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    pub fn is_finished(&self) -> bool {
+        matches!(
+            self,
+            JobStatus::Succeeded | JobStatus::Failed | JobStatus::Cancelled
+        )
+    }
+}
+
+impl fmt::Display for JobStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            JobStatus::Queued => "queued",
+            JobStatus::Running => "running",
+            JobStatus::Succeeded => "succeeded",
+            JobStatus::Failed => "failed",
+            JobStatus::Cancelled => "cancelled",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Persisted state of one job
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Job {
+    pub id: String,
+
+    /// Selects the handler that runs the job
+    pub kind: String,
+    pub payload: serde_json::Value,
+    pub priority: JobPriority,
+    pub status: JobStatus,
+
+    /// Attempts started so far
+    pub attempts: u32,
+    pub max_attempts: u32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+
+    /// The job is not started before this time
+    pub run_after: DateTime<Utc>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+
+    /// Claim held by the worker running the job
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lease_expires_at: Option<DateTime<Utc>>,
+
+    /// Enqueue the job again this long after each success
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repeat_every_secs: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+}
+
+/// Job to enqueue
+#[derive(Debug, Clone)]
+pub struct JobSpec {
+    pub kind: String,
+    pub payload: serde_json::Value,
+    pub priority: JobPriority,
+
+    /// Falls back to the queue's `max_attempts`
+    pub max_attempts: Option<u32>,
+    pub run_after: Option<DateTime<Utc>>,
+    pub repeat_every_secs: Option<u64>,
+}
+
+impl JobSpec {
+    pub fn new(kind: impl Into<String>, payload: serde_json::Value) -> Self {
+        Self {
+            kind: kind.into(),
+            payload,
+            priority: JobPriority::Normal,
+            max_attempts: None,
+            run_after: None,
+            repeat_every_secs: None,
+        }
+    }
+
+    pub fn with_priority(mut self, priority: JobPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = Some(max_attempts.max(1));
+        self
+    }
+
+    pub fn with_run_after(mut self, run_after: DateTime<Utc>) -> Self {
+        self.run_after = Some(run_after);
+        self
+    }
+
+    pub fn with_repeat_every(mut self, interval: Duration) -> Self {
+        self.repeat_every_secs = Some(interval.as_secs().max(1));
+        self
+    }
+}
+
+/// What a handler knows about the attempt it is running
+#[derive(Debug, Clone)]
+pub struct JobContext {
+    job_id: String,
+    attempt: u32,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl JobContext {
+    pub fn job_id(&self) -> &str {
+        &self.job_id
+    }
+
+    /// 1 for the first attempt
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    /// Long-running handlers should check this between steps and stop early
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// Runs jobs of one kind
+#[async_trait]
+pub trait JobHandler: Send + Sync {
+    /// Returns the job's result, or an error to retry it
+    async fn run(&self, job: &Job, context: &JobContext) -> RhemaResult<serde_json::Value>;
+}
+
+/// `jobs` section of the repository config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JobQueueConfig {
+    /// Jobs one worker runs at once
+    pub max_concurrency: usize,
+    pub max_attempts: u32,
+
+    /// Delay before the first retry, doubled for every further one
+    pub backoff_ms: u64,
+    pub max_backoff_ms: u64,
+    pub poll_interval_ms: u64,
+    pub lease_secs: u64,
+
+    /// Finished jobs older than this are pruned
+    pub retention_days: i64,
+}
+
+impl Default for JobQueueConfig {
+    fn default() -> Self {
+        Self {
+            max_concurrency: 2,
+            max_attempts: 3,
+            backoff_ms: 1_000,
+            max_backoff_ms: 300_000,
+            poll_interval_ms: 1_000,
+            lease_secs: 60,
+            retention_days: 7,
+        }
+    }
+}
+
+impl JobQueueConfig {
+    pub fn load(repo_root: &Path) -> RhemaResult<Self> {
+        let value = policy::repository_config(repo_root)?;
+        match value.get(JOBS_CONFIG_SECTION) {
+            Some(section) => serde_yaml::from_value(section.clone()).map_err(|e| {
+                RhemaError::ConfigError(format!(
+                    "Invalid {} section in the repository config: {}",
+                    JOBS_CONFIG_SECTION, e
+                ))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Delay before retrying a job that failed its `attempts`th attempt
+    pub fn backoff(&self, attempts: u32) -> chrono::Duration {
+        let factor = 1u64 << attempts.saturating_sub(1).min(32);
+        let ms = self
+            .backoff_ms
+            .saturating_mul(factor)
+            .min(self.max_backoff_ms);
+        chrono::Duration::milliseconds(ms as i64)
+    }
+
+    fn lease(&self) -> chrono::Duration {
+        chrono::Duration::seconds(self.lease_secs.max(1) as i64)
+    }
+}
+
+/// Persistent, priority-ordered job queue
+pub struct JobQueue {
+    dir: PathBuf,
+    config: JobQueueConfig,
+    handlers: HashMap<String, Arc<dyn JobHandler>>,
+
+    /// Cancellation flags of the jobs this queue is running
+    running: Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+impl JobQueue {
+    pub fn new(dir: impl Into<PathBuf>, config: JobQueueConfig) -> RhemaResult<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            config,
+            handlers: HashMap::new(),
+            running: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Queue of a repository, configured from its repository config
+    pub fn open(repo_root: &Path) -> RhemaResult<Self> {
+        Self::new(repo_root.join(JOBS_DIR), JobQueueConfig::load(repo_root)?)
+    }
+
+    pub fn with_handler(mut self, kind: impl Into<String>, handler: Arc<dyn JobHandler>) -> Self {
+        self.handlers.insert(kind.into(), handler);
+        self
+    }
+
+    pub fn config(&self) -> &JobQueueConfig {
+        &self.config
+    }
+
+    pub fn handles(&self, kind: &str) -> bool {
+        self.handlers.contains_key(kind)
+    }
+
+    pub fn enqueue(&self, spec: JobSpec) -> RhemaResult<Job> {
+        let now = Utc::now();
+        let job = Job {
+            id: uuid::Uuid::new_v4().to_string(),
+            kind: spec.kind,
+            payload: spec.payload,
+            priority: spec.priority,
+            status: JobStatus::Queued,
+            attempts: 0,
+            max_attempts: spec.max_attempts.unwrap_or(self.config.max_attempts).max(1),
+            created_at: now,
+            updated_at: now,
+            run_after: spec.run_after.unwrap_or(now),
+            finished_at: None,
+            lease_expires_at: None,
+            repeat_every_secs: spec.repeat_every_secs,
+            last_error: None,
+            result: None,
+        };
+        self.write(&job)?;
+        Ok(job)
+    }
+
+    pub fn get(&self, id: &str) -> RhemaResult<Job> {
+        let path = self.path(id);
+        if !path.exists() {
+            return Err(RhemaError::NotFound(format!("Job {}", id)));
+        }
+        let content = fs::read_to_string(&path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Jobs with the given status, or all jobs, most urgent first
+    pub fn list(&self, status: Option<JobStatus>) -> RhemaResult<Vec<Job>> {
+        let mut jobs = self.all()?;
+        if let Some(status) = status {
+            jobs.retain(|job| job.status == status);
+        }
+        jobs.sort_by(|a, b| {
+            a.status
+                .cmp(&b.status)
+                .then(b.priority.cmp(&a.priority))
+                .then(a.run_after.cmp(&b.run_after))
+                .then(a.created_at.cmp(&b.created_at))
+        });
+        Ok(jobs)
+    }
+
+    /// Number of jobs in each status
+    pub fn counts(&self) -> RhemaResult<BTreeMap<JobStatus, usize>> {
+        let mut counts = BTreeMap::new();
+        for job in self.all()? {
+            *counts.entry(job.status).or_insert(0) += 1;
+        }
+        Ok(counts)
+    }
+
+    /// Cancel a queued or running job. A running handler sees the
+    /// cancellation through [`JobContext::is_cancelled`]
+    pub fn cancel(&self, id: &str) -> RhemaResult<Job> {
+        let mut job = self.get(id)?;
+        if job.status.is_finished() {
+            return Err(RhemaError::InvalidInput(format!(
+                "Job {} already {}",
+                id, job.status
+            )));
+        }
+        let now = Utc::now();
+        job.status = JobStatus::Cancelled;
+        job.updated_at = now;
+        job.finished_at = Some(now);
+        job.lease_expires_at = None;
+        self.write(&job)?;
+        if let Some(flag) = self.running.lock().unwrap().get(id) {
+            flag.store(true, Ordering::SeqCst);
+        }
+        Ok(job)
+    }
+
+    /// Queue a failed or cancelled job again with a fresh set of attempts
+    pub fn retry(&self, id: &str) -> RhemaResult<Job> {
+        let mut job = self.get(id)?;
+        if !matches!(job.status, JobStatus::Failed | JobStatus::Cancelled) {
+            return Err(RhemaError::InvalidInput(format!(
+                "Job {} is {}; only failed or cancelled jobs can be retried",
+                id, job.status
+            )));
+        }
+        let now = Utc::now();
+        job.status = JobStatus::Queued;
+        job.attempts = 0;
+        job.run_after = now;
+        job.updated_at = now;
+        job.finished_at = None;
+        self.write(&job)?;
+        let _ = fs::remove_file(self.claim_path(id));
+        Ok(job)
+    }
+
+    /// Delete finished jobs past the retention period
+    pub fn prune(&self) -> RhemaResult<usize> {
+        let cutoff = Utc::now() - chrono::Duration::days(self.config.retention_days.max(0));
+        let mut pruned = 0;
+        for job in self.all()? {
+            if job.status.is_finished() && job.finished_at.unwrap_or(job.updated_at) < cutoff {
+                fs::remove_file(self.path(&job.id))?;
+                let _ = fs::remove_file(self.claim_path(&job.id));
+                pruned += 1;
+            }
+        }
+        Ok(pruned)
+    }
+
+    /// Claim the jobs that are due and run them to completion, returning
+    /// their updated records
+    pub async fn run_due(self: &Arc<Self>) -> RhemaResult<Vec<Job>> {
+        let claimed = self.claim(self.config.max_concurrency.max(1))?;
+        let mut tasks = tokio::task::JoinSet::new();
+        for job in claimed {
+            let queue = Arc::clone(self);
+            tasks.spawn(async move { queue.execute(job).await });
+        }
+        let mut finished = Vec::new();
+        let mut error = None;
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok(Ok(job)) => finished.push(job),
+                Ok(Err(e)) => {
+                    error.get_or_insert(e);
+                }
+                Err(_) => {}
+            }
+        }
+        match error {
+            Some(e) => Err(e),
+            None => Ok(finished),
+        }
+    }
+
+    /// Run a worker in the background until the handle is aborted
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let tick = Duration::from_millis(self.config.poll_interval_ms.max(10));
+        tokio::spawn(async move {
+            let mut tasks = tokio::task::JoinSet::new();
+            let mut last_prune = Utc::now();
+            loop {
+                while tasks.try_join_next().is_some() {}
+                self.renew_leases();
+                let free = self
+                    .config
+                    .max_concurrency
+                    .max(1)
+                    .saturating_sub(tasks.len());
+                if free > 0 {
+                    match self.claim(free) {
+                        Ok(claimed) => {
+                            for job in claimed {
+                                let queue = Arc::clone(&self);
+                                tasks.spawn(async move { queue.execute(job).await });
+                            }
+                        }
+                        Err(e) => tracing::warn!("Failed to claim jobs: {}", e),
+                    }
+                }
+                if Utc::now() - last_prune > chrono::Duration::hours(1) {
+                    if let Err(e) = self.prune() {
+                        tracing::warn!("Failed to prune finished jobs: {}", e);
+                    }
+                    last_prune = Utc::now();
+                }
+                tokio::time::sleep(tick).await;
+            }
+        })
+    }
+
+    /// Queue jobs whose worker lost its lease, then claim up to `limit` due
+    /// jobs this queue has handlers for
+    fn claim(&self, limit: usize) -> RhemaResult<Vec<Job>> {
+        let now = Utc::now();
+        let mut due = Vec::new();
+        for mut job in self.all()? {
+            if job.status == JobStatus::Running
+                && job.lease_expires_at.is_none_or(|lease| lease < now)
+                && !self.running.lock().unwrap().contains_key(&job.id)
+            {
+                self.fail_attempt(&mut job, "worker stopped before the job finished", now);
+                self.write(&job)?;
+                let _ = fs::remove_file(self.claim_path(&job.id));
+            }
+            if job.status == JobStatus::Queued
+                && job.run_after <= now
+                && self.handlers.contains_key(&job.kind)
+            {
+                due.push(job);
+            }
+        }
+        due.sort_by(|a, b| {
+            b.priority
+                .cmp(&a.priority)
+                .then(a.run_after.cmp(&b.run_after))
+                .then(a.created_at.cmp(&b.created_at))
+        });
+
+        let mut claimed = Vec::new();
+        for job in due {
+            if claimed.len() >= limit {
+                break;
+            }
+            let claim = fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(self.claim_path(&job.id));
+            match claim {
+                Ok(_) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e.into()),
+            }
+            // Another worker may have finished the job between the scan and the claim
+            let mut job = self.get(&job.id)?;
+            if job.status != JobStatus::Queued {
+                let _ = fs::remove_file(self.claim_path(&job.id));
+                continue;
+            }
+            job.status = JobStatus::Running;
+            job.attempts += 1;
+            job.updated_at = now;
+            job.lease_expires_at = Some(now + self.config.lease());
+            self.write(&job)?;
+            self.running
+                .lock()
+                .unwrap()
+                .insert(job.id.clone(), Arc::new(AtomicBool::new(false)));
+            claimed.push(job);
+        }
+        Ok(claimed)
+    }
+
+    async fn execute(self: Arc<Self>, job: Job) -> RhemaResult<Job> {
+        let cancelled = self
+            .running
+            .lock()
+            .unwrap()
+            .get(&job.id)
+            .cloned()
+            .unwrap_or_default();
+        let context = JobContext {
+            job_id: job.id.clone(),
+            attempt: job.attempts,
+            cancelled: Arc::clone(&cancelled),
+        };
+        let outcome = match self.handlers.get(&job.kind).cloned() {
+            // Run on its own task so a panicking handler fails the attempt
+            // instead of leaving the job claimed
+            Some(handler) => {
+                let running = job.clone();
+                tokio::spawn(async move { handler.run(&running, &context).await })
+                    .await
+                    .unwrap_or_else(|e| {
+                        Err(RhemaError::InvalidInput(format!("Job panicked: {}", e)))
+                    })
+            }
+            None => Err(RhemaError::ConfigError(format!(
+                "No handler for job kind {}",
+                job.kind
+            ))),
+        };
+
+        let finished = self.finish(job, outcome, &cancelled)?;
+        if finished.status == JobStatus::Succeeded {
+            if let Some(every) = finished.repeat_every_secs {
+                let mut next = JobSpec::new(finished.kind.clone(), finished.payload.clone())
+                    .with_priority(finished.priority)
+                    .with_max_attempts(finished.max_attempts)
+                    .with_run_after(finished.updated_at + chrono::Duration::seconds(every as i64));
+                next.repeat_every_secs = Some(every);
+                self.enqueue(next)?;
+            }
+        }
+        Ok(finished)
+    }
+
+    /// Record the outcome of an attempt and release the claim
+    fn finish(
+        &self,
+        job: Job,
+        outcome: RhemaResult<serde_json::Value>,
+        cancelled: &AtomicBool,
+    ) -> RhemaResult<Job> {
+        // Hold the running set so a lease renewal cannot overwrite the result
+        let mut running = self.running.lock().unwrap();
+        let mut finished = self.get(&job.id).unwrap_or(job);
+        let now = Utc::now();
+        if finished.status == JobStatus::Cancelled || cancelled.load(Ordering::SeqCst) {
+            finished.status = JobStatus::Cancelled;
+            finished.finished_at.get_or_insert(now);
+        } else {
+            match outcome {
+                Ok(result) => {
+                    finished.status = JobStatus::Succeeded;
+                    finished.result = Some(result);
+                    finished.last_error = None;
+                    finished.finished_at = Some(now);
+                }
+                Err(e) => self.fail_attempt(&mut finished, &e.to_string(), now),
+            }
+        }
+        finished.updated_at = now;
+        finished.lease_expires_at = None;
+        let written = self.write(&finished);
+        running.remove(&finished.id);
+        let _ = fs::remove_file(self.claim_path(&finished.id));
+        written.map(|_| finished)
+    }
+
+    /// Schedule a retry after backoff, or fail the job once it is out of
+    /// attempts
+    fn fail_attempt(&self, job: &mut Job, error: &str, now: DateTime<Utc>) {
+        job.last_error = Some(error.to_string());
+        job.updated_at = now;
+        job.lease_expires_at = None;
+        if job.attempts >= job.max_attempts {
+            job.status = JobStatus::Failed;
+            job.finished_at = Some(now);
+        } else {
+            job.status = JobStatus::Queued;
+            job.run_after = now + self.config.backoff(job.attempts);
+        }
+    }
+
+    /// Extend the leases of running jobs and pick up cancellations made by
+    /// other processes
+    fn renew_leases(&self) {
+        let running = self.running.lock().unwrap();
+        let lease = Utc::now() + self.config.lease();
+        for (id, cancelled) in running.iter() {
+            let Ok(mut job) = self.get(id) else {
+                continue;
+            };
+            if job.status == JobStatus::Cancelled {
+                cancelled.store(true, Ordering::SeqCst);
+            } else if job.status == JobStatus::Running {
+                job.lease_expires_at = Some(lease);
+                if let Err(e) = self.write(&job) {
+                    tracing::warn!("Failed to renew the lease of job {}: {}", id, e);
+                }
+            }
+        }
+    }
+
+    fn all(&self) -> RhemaResult<Vec<Job>> {
+        let mut jobs = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            match fs::read_to_string(&path)
+                .map_err(RhemaError::from)
+                .and_then(|content| Ok(serde_json::from_str::<Job>(&content)?))
+            {
+                Ok(job) => jobs.push(job),
+                Err(e) => tracing::warn!("Skipping unreadable job {}: {}", path.display(), e),
+            }
+        }
+        Ok(jobs)
+    }
+
+    /// Write through a temporary file so readers never see a partial job
+    fn write(&self, job: &Job) -> RhemaResult<()> {
+        let path = self.path(&job.id);
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string_pretty(job)?)?;
+        fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    fn path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", id))
+    }
+
+    fn claim_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{}.claim", id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::atomic::AtomicU32;
+    use tempfile::TempDir;
+
+    struct Recorder {
+        order: Mutex<Vec<String>>,
+        failures_left: AtomicU32,
+    }
+
+    #[async_trait]
+    impl JobHandler for Recorder {
+        async fn run(&self, job: &Job, _context: &JobContext) -> RhemaResult<serde_json::Value> {
+            self.order
+                .lock()
+                .unwrap()
+                .push(job.payload["name"].to_string());
+            if self.failures_left.load(Ordering::SeqCst) > 0 {
+                self.failures_left.fetch_sub(1, Ordering::SeqCst);
+                return Err(RhemaError::InvalidInput("flaky".to_string()));
+            }
+            Ok(json!({ "ok": true }))
+        }
+    }
+
+    fn queue(dir: &Path, failures: u32) -> (Arc<JobQueue>, Arc<Recorder>) {
+        let recorder = Arc::new(Recorder {
+            order: Mutex::new(Vec::new()),
+            failures_left: AtomicU32::new(failures),
+        });
+        let config = JobQueueConfig {
+            max_concurrency: 1,
+            backoff_ms: 0,
+            ..Default::default()
+        };
+        let queue = JobQueue::new(dir, config)
+            .unwrap()
+            .with_handler("test", recorder.clone());
+        (Arc::new(queue), recorder)
+    }
+
+    #[tokio::test]
+    async fn runs_by_priority_and_retries_with_backoff() {
+        let dir = TempDir::new().unwrap();
+        let (queue, recorder) = queue(dir.path(), 1);
+        queue
+            .enqueue(JobSpec::new("test", json!({ "name": "low" })).with_priority(JobPriority::Low))
+            .unwrap();
+        let high = queue
+            .enqueue(
+                JobSpec::new("test", json!({ "name": "high" })).with_priority(JobPriority::High),
+            )
+            .unwrap();
+
+        let first = queue.run_due().await.unwrap();
+        assert_eq!(first[0].id, high.id);
+        assert_eq!(first[0].status, JobStatus::Queued);
+        assert_eq!(first[0].last_error.as_deref(), Some("Invalid input: flaky"));
+
+        queue.run_due().await.unwrap();
+        queue.run_due().await.unwrap();
+        let high = queue.get(&high.id).unwrap();
+        assert_eq!(high.status, JobStatus::Succeeded);
+        assert_eq!(high.attempts, 2);
+        assert_eq!(
+            *recorder.order.lock().unwrap(),
+            vec!["\"high\"", "\"high\"", "\"low\""]
+        );
+        assert_eq!(queue.counts().unwrap()[&JobStatus::Succeeded], 2);
+    }
+
+    #[tokio::test]
+    async fn cancels_retries_and_recovers_expired_leases() {
+        let dir = TempDir::new().unwrap();
+        let (queue, recorder) = queue(dir.path(), 0);
+        let job = queue
+            .enqueue(JobSpec::new("test", json!({ "name": "a" })))
+            .unwrap();
+
+        assert_eq!(queue.cancel(&job.id).unwrap().status, JobStatus::Cancelled);
+        assert!(queue.cancel(&job.id).is_err());
+        assert!(queue.run_due().await.unwrap().is_empty());
+        assert_eq!(queue.retry(&job.id).unwrap().status, JobStatus::Queued);
+
+        // A worker that died mid-run leaves the job running with a stale lease
+        let mut stale = queue.get(&job.id).unwrap();
+        stale.status = JobStatus::Running;
+        stale.attempts = 1;
+        stale.lease_expires_at = Some(Utc::now() - chrono::Duration::seconds(1));
+        queue.write(&stale).unwrap();
+        fs::write(queue.claim_path(&job.id), "").unwrap();
+
+        let finished = queue.run_due().await.unwrap();
+        assert_eq!(finished.len(), 1);
+        assert_eq!(finished[0].status, JobStatus::Succeeded);
+        assert_eq!(finished[0].attempts, 2);
+        assert_eq!(recorder.order.lock().unwrap().len(), 1);
+    }
+}
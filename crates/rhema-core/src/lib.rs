@@ -1,17 +1,71 @@
+pub mod affected;
+pub mod agent_tokens;
+pub mod ai_policy;
+pub mod cache_report;
+pub mod code_search;
+pub mod decision_outcomes;
+pub mod dependency_health;
+pub mod editor;
 pub mod error;
+pub mod events;
 pub mod file_ops;
+pub mod freshness;
+pub mod gc;
+pub mod help;
+pub mod i18n;
+pub mod importers;
+pub mod issue_sync;
+pub mod jobs;
+pub mod lifecycle;
 pub mod lock;
+pub mod lockfiles;
+pub mod merge;
+pub mod ownership;
+pub mod policy;
+pub mod profiling;
+pub mod prompt_guard;
+pub mod readme_sync;
+pub mod review;
+pub mod roots;
 pub mod schema;
 pub mod scope;
 pub mod scope_loader;
+pub mod snapshot;
+pub mod sync;
+pub mod templates;
+pub mod tool_runtime;
+pub mod toolchain;
 pub mod utils;
 
+pub use ai_policy::{resolve_ai_policy, AiPolicy, EffectiveAiPolicy};
+pub use cache_report::{
+    CacheActivity, CacheReport, CacheTierReport, EvictionCause, EvictionCounts, KeyHits, TierLimit,
+    TuningAction, TuningRecommendation,
+};
+pub use decision_outcomes::{DecisionOutcome, DecisionOutcomeReport, OutcomePeriod};
+pub use editor::{
+    ContextFileKind, ContextIndex, EditorCodeAction, EditorCompletion, EditorDiagnostic,
+};
 pub use error::{RhemaError, RhemaResult};
+pub use events::{EventOutbox, ExportEvent, ExportEventType};
+pub use freshness::{FreshnessPolicy, FreshnessReport, FreshnessSla, FreshnessTarget};
+pub use gc::{ArtifactCollector, Garbage, GarbageCollector, GcConfig, GcReport};
+pub use i18n::{I18nConfig, Localizer};
+pub use jobs::{Job, JobHandler, JobPriority, JobQueue, JobQueueConfig, JobSpec, JobStatus};
 pub use lock::*;
+pub use prompt_guard::{PromptGuard, PromptGuardConfig, QuarantineRecord};
+pub use readme_sync::{ReadmeSection, ReadmeStatus, ReadmeSync, ReadmeSyncConfig};
 pub use schema::*;
+pub use review::{ReviewQueue, ReviewState};
+pub use roots::{RepositoryRoot, RepositoryRoots, RepositoryRootsConfig, RootKind};
 pub use scope::*;
 pub use scope_loader::{
     ConfigPluginConfig, PackageBoundary, PackageManager, PluginError, PluginMetadata,
     PluginRegistry, RegistryError, ScopeContext, ScopeLoaderError, ScopeLoaderPlugin,
     ScopeLoaderService, ScopeSuggestion, ScopeType,
 };
+pub use snapshot::{SnapshotDiff, SnapshotManager, SnapshotManifest};
+pub use sync::{SyncConfig, SyncEngine, SyncReport};
+pub use templates::{EntryFields, EntryTemplate, EntryTemplates};
+pub use tool_runtime::{ResolvedRuntime, RuntimeSpec, ToolRuntimePolicy};
+pub use toolchain::{detect_toolchains, record_toolchains, resolve_toolchains, ScopeToolchains};
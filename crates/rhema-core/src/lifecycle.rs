@@ -0,0 +1,511 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Hooks fired when context entries are created, updated or completed.
+//!
+//! Hooks are declared in the `lifecycle_hooks` section of the repository
+//! config, each filtered by event, entry type and scope, and either POST the
+//! change to a webhook or run a command with the change on stdin. The write
+//! functions in [`crate::file_ops`] report changes to the process-wide
+//! [`LifecycleHooks`], which delivers them in the background with retries;
+//! callers await [`LifecycleHooks::flush`] before exiting.
+
+use crate::policy;
+use crate::scope::Scope;
+use crate::{RhemaError, RhemaResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::task::JoinHandle;
+
+/// Repository config section holding [`LifecycleHooksConfig`]
+pub const LIFECYCLE_HOOKS_SECTION: &str = "lifecycle_hooks";
+
+/// What happened to an entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LifecycleEvent {
+    Create,
+    Update,
+    /// A todo was completed or a decision approved
+    Complete,
+}
+
+impl fmt::Display for LifecycleEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LifecycleEvent::Create => write!(f, "create"),
+            LifecycleEvent::Update => write!(f, "update"),
+            LifecycleEvent::Complete => write!(f, "complete"),
+        }
+    }
+}
+
+/// Kind of context entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryType {
+    Todo,
+    Knowledge,
+    Pattern,
+    Decision,
+}
+
+impl fmt::Display for EntryType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntryType::Todo => write!(f, "todo"),
+            EntryType::Knowledge => write!(f, "knowledge"),
+            EntryType::Pattern => write!(f, "pattern"),
+            EntryType::Decision => write!(f, "decision"),
+        }
+    }
+}
+
+/// A change delivered to hooks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntryChange {
+    pub event: LifecycleEvent,
+    pub entry_type: EntryType,
+    /// Scope name from `rhema.yaml`
+    pub scope: String,
+    pub entry_id: String,
+    /// The entry as written
+    pub entry: serde_json::Value,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// What a hook does with a change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HookAction {
+    /// POST the change as JSON
+    Webhook {
+        url: String,
+        #[serde(default)]
+        headers: BTreeMap<String, String>,
+    },
+    /// Run a command from the repository root with the change as JSON on
+    /// stdin and `RHEMA_HOOK_*` variables describing it
+    Command {
+        command: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+}
+
+/// One configured hook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LifecycleHook {
+    pub name: String,
+
+    /// Events to fire on; empty means all
+    #[serde(default)]
+    pub events: Vec<LifecycleEvent>,
+
+    /// Entry types to fire for; empty means all
+    #[serde(default)]
+    pub entry_types: Vec<EntryType>,
+
+    /// Scope names to fire for, a trailing `*` matching a prefix; empty
+    /// means all
+    #[serde(default)]
+    pub scopes: Vec<String>,
+
+    pub action: HookAction,
+
+    /// Overrides the section-wide attempt limit
+    #[serde(default)]
+    pub max_attempts: Option<u32>,
+}
+
+impl LifecycleHook {
+    pub fn matches(&self, change: &EntryChange) -> bool {
+        (self.events.is_empty() || self.events.contains(&change.event))
+            && (self.entry_types.is_empty() || self.entry_types.contains(&change.entry_type))
+            && (self.scopes.is_empty()
+                || self
+                    .scopes
+                    .iter()
+                    .any(|pattern| match pattern.strip_suffix('*') {
+                        Some(prefix) => change.scope.starts_with(prefix),
+                        None => *pattern == change.scope,
+                    }))
+    }
+}
+
+/// Hook configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LifecycleHooksConfig {
+    pub hooks: Vec<LifecycleHook>,
+
+    /// Deliveries per change before giving up, including the first
+    pub max_attempts: u32,
+
+    /// Wait before the first retry, doubled for each further retry
+    pub backoff_ms: u64,
+
+    /// Limit on one webhook request or command run
+    pub timeout_secs: u64,
+}
+
+impl Default for LifecycleHooksConfig {
+    fn default() -> Self {
+        Self {
+            hooks: Vec::new(),
+            max_attempts: 3,
+            backoff_ms: 500,
+            timeout_secs: 10,
+        }
+    }
+}
+
+impl LifecycleHooksConfig {
+    /// Load the configuration from the repository config and policy bundle,
+    /// falling back to defaults
+    pub fn load(repo_root: &Path) -> RhemaResult<Self> {
+        let value = policy::repository_config(repo_root)?;
+        match value.get(LIFECYCLE_HOOKS_SECTION) {
+            Some(section) => serde_yaml::from_value(section.clone()).map_err(|e| {
+                RhemaError::ConfigError(format!(
+                    "Invalid {} section in the repository config: {}",
+                    LIFECYCLE_HOOKS_SECTION, e
+                ))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+/// Result of delivering one change to one hook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HookOutcome {
+    pub hook: String,
+    pub event: LifecycleEvent,
+    pub entry_id: String,
+    pub attempts: u32,
+    /// Last error when every attempt failed
+    pub error: Option<String>,
+}
+
+impl HookOutcome {
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Delivers entry changes to the configured hooks
+pub struct LifecycleHooks {
+    repo_root: PathBuf,
+    config: LifecycleHooksConfig,
+    client: reqwest::Client,
+    pending: Mutex<Vec<JoinHandle<HookOutcome>>>,
+}
+
+impl LifecycleHooks {
+    pub fn new(repo_root: &Path, config: LifecycleHooksConfig) -> RhemaResult<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .map_err(|e| RhemaError::ConfigError(e.to_string()))?;
+        Ok(Self {
+            repo_root: repo_root.to_path_buf(),
+            config,
+            client,
+            pending: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Hooks of the repository at `repo_root`
+    pub fn load(repo_root: &Path) -> RhemaResult<Self> {
+        Self::new(repo_root, LifecycleHooksConfig::load(repo_root)?)
+    }
+
+    pub fn config(&self) -> &LifecycleHooksConfig {
+        &self.config
+    }
+
+    /// Start delivering `change` to every matching hook in the background
+    pub fn dispatch(self: &Arc<Self>, change: EntryChange) {
+        let change = Arc::new(change);
+        let runtime = background_runtime();
+        let mut pending = self.pending.lock().unwrap();
+        for hook in self
+            .config
+            .hooks
+            .iter()
+            .filter(|hook| hook.matches(&change))
+        {
+            let hooks = Arc::clone(self);
+            let hook = hook.clone();
+            let change = Arc::clone(&change);
+            pending.push(runtime.spawn(async move { hooks.deliver(&hook, &change).await }));
+        }
+    }
+
+    /// Wait for every delivery started so far
+    pub async fn flush(&self) -> Vec<HookOutcome> {
+        let handles = std::mem::take(&mut *self.pending.lock().unwrap());
+        let mut outcomes = Vec::with_capacity(handles.len());
+        for handle in handles {
+            if let Ok(outcome) = handle.await {
+                outcomes.push(outcome);
+            }
+        }
+        outcomes
+    }
+
+    /// Run `hook` until it succeeds or runs out of attempts
+    async fn deliver(&self, hook: &LifecycleHook, change: &EntryChange) -> HookOutcome {
+        let max_attempts = hook.max_attempts.unwrap_or(self.config.max_attempts).max(1);
+        let mut backoff = Duration::from_millis(self.config.backoff_ms);
+        let mut outcome = HookOutcome {
+            hook: hook.name.clone(),
+            event: change.event,
+            entry_id: change.entry_id.clone(),
+            attempts: 0,
+            error: None,
+        };
+        while outcome.attempts < max_attempts {
+            if outcome.attempts > 0 {
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+            outcome.attempts += 1;
+            match self.run(&hook.action, change).await {
+                Ok(()) => {
+                    outcome.error = None;
+                    break;
+                }
+                Err(e) => outcome.error = Some(e.to_string()),
+            }
+        }
+        outcome
+    }
+
+    async fn run(&self, action: &HookAction, change: &EntryChange) -> RhemaResult<()> {
+        match action {
+            HookAction::Webhook { url, headers } => {
+                let mut request = self.client.post(url).json(change);
+                for (name, value) in headers {
+                    request = request.header(name, value);
+                }
+                let response = request.send().await?;
+                if !response.status().is_success() {
+                    return Err(RhemaError::ExternalServiceError(format!(
+                        "Webhook {} returned {}",
+                        url,
+                        response.status()
+                    )));
+                }
+                Ok(())
+            }
+            HookAction::Command { command, args } => {
+                let payload = serde_json::to_vec(change)?;
+                let mut child = tokio::process::Command::new(command)
+                    .args(args)
+                    .current_dir(&self.repo_root)
+                    .env("RHEMA_HOOK_EVENT", change.event.to_string())
+                    .env("RHEMA_HOOK_ENTRY_TYPE", change.entry_type.to_string())
+                    .env("RHEMA_HOOK_ENTRY_ID", &change.entry_id)
+                    .env("RHEMA_HOOK_SCOPE", &change.scope)
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::piped())
+                    .kill_on_drop(true)
+                    .spawn()?;
+                if let Some(mut stdin) = child.stdin.take() {
+                    // Commands that ignore stdin may exit before reading it
+                    let _ = stdin.write_all(&payload).await;
+                }
+                let output = tokio::time::timeout(
+                    Duration::from_secs(self.config.timeout_secs),
+                    child.wait_with_output(),
+                )
+                .await
+                .map_err(|_| {
+                    RhemaError::SystemError(format!(
+                        "{} timed out after {}s",
+                        command, self.config.timeout_secs
+                    ))
+                })??;
+                if !output.status.success() {
+                    return Err(RhemaError::SystemError(format!(
+                        "{} exited with {}: {}",
+                        command,
+                        output.status,
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    )));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+static HOOKS: OnceLock<Arc<LifecycleHooks>> = OnceLock::new();
+
+/// Install the process-wide hooks. Only the first call has any effect.
+pub fn install(hooks: Arc<LifecycleHooks>) -> Arc<LifecycleHooks> {
+    HOOKS.get_or_init(|| hooks).clone()
+}
+
+/// The installed hooks, if any
+pub fn active() -> Option<&'static Arc<LifecycleHooks>> {
+    HOOKS.get()
+}
+
+/// Report a change made through the write API to the installed hooks
+pub fn emit<T: Serialize>(
+    scope_path: &Path,
+    event: LifecycleEvent,
+    entry_type: EntryType,
+    entry_id: &str,
+    entry: &T,
+) {
+    let Some(hooks) = active() else {
+        return;
+    };
+    if hooks.config.hooks.is_empty() {
+        return;
+    }
+    let scope = Scope::new(scope_path.to_path_buf())
+        .map(|scope| scope.definition.name)
+        .unwrap_or_else(|_| scope_path.display().to_string());
+    hooks.dispatch(EntryChange {
+        event,
+        entry_type,
+        scope,
+        entry_id: entry_id.to_string(),
+        entry: serde_json::to_value(entry).unwrap_or(serde_json::Value::Null),
+        occurred_at: Utc::now(),
+    });
+}
+
+/// Runtime of the calling task, or a shared one for synchronous callers
+fn background_runtime() -> tokio::runtime::Handle {
+    static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+    tokio::runtime::Handle::try_current().unwrap_or_else(|_| {
+        RUNTIME
+            .get_or_init(|| {
+                tokio::runtime::Builder::new_multi_thread()
+                    .worker_threads(1)
+                    .thread_name("rhema-lifecycle-hooks")
+                    .enable_all()
+                    .build()
+                    .expect("failed to start the lifecycle hook runtime")
+            })
+            .handle()
+            .clone()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn change(event: LifecycleEvent, entry_type: EntryType, scope: &str) -> EntryChange {
+        EntryChange {
+            event,
+            entry_type,
+            scope: scope.to_string(),
+            entry_id: "todo-1".to_string(),
+            entry: serde_json::json!({ "id": "todo-1" }),
+            occurred_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_hooks_filter_by_event_type_and_scope() {
+        let config: LifecycleHooksConfig = serde_yaml::from_str(
+            r#"
+hooks:
+  - name: notify
+    events: [complete]
+    entry_types: [todo]
+    scopes: ["payments-*"]
+    action:
+      type: webhook
+      url: https://hooks.example.com/rhema
+"#,
+        )
+        .unwrap();
+        let hook = &config.hooks[0];
+        assert_eq!(config.max_attempts, 3);
+
+        assert!(hook.matches(&change(
+            LifecycleEvent::Complete,
+            EntryType::Todo,
+            "payments-api"
+        )));
+        assert!(!hook.matches(&change(
+            LifecycleEvent::Update,
+            EntryType::Todo,
+            "payments-api"
+        )));
+        assert!(!hook.matches(&change(
+            LifecycleEvent::Complete,
+            EntryType::Decision,
+            "payments-api"
+        )));
+        assert!(!hook.matches(&change(
+            LifecycleEvent::Complete,
+            EntryType::Todo,
+            "billing"
+        )));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_failed_deliveries_are_retried() {
+        let temp = TempDir::new().unwrap();
+        // Fails on the first run, succeeds once the marker exists
+        let script = "test -f attempted && cat > delivered || { touch attempted; exit 1; }";
+        let config = LifecycleHooksConfig {
+            hooks: vec![LifecycleHook {
+                name: "index".to_string(),
+                events: Vec::new(),
+                entry_types: Vec::new(),
+                scopes: Vec::new(),
+                action: HookAction::Command {
+                    command: "sh".to_string(),
+                    args: vec!["-c".to_string(), script.to_string()],
+                },
+                max_attempts: None,
+            }],
+            backoff_ms: 10,
+            ..Default::default()
+        };
+        let hooks = Arc::new(LifecycleHooks::new(temp.path(), config).unwrap());
+
+        hooks.dispatch(change(LifecycleEvent::Create, EntryType::Knowledge, "docs"));
+        let outcomes = hooks.flush().await;
+
+        assert_eq!(outcomes.len(), 1);
+        assert!(outcomes[0].succeeded());
+        assert_eq!(outcomes[0].attempts, 2);
+        let delivered = std::fs::read_to_string(temp.path().join("delivered")).unwrap();
+        assert!(delivered.contains("\"entry_type\":\"knowledge\""));
+    }
+}
@@ -0,0 +1,1051 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Language package lockfiles: `Cargo.lock`, `package-lock.json` and `poetry.lock`.
+//!
+//! Every lockfile is read into the same shape, a list of locked packages with
+//! the names of their dependencies plus the project packages the graph starts
+//! from. Lockfiles without a project entry (Poetry, npm v1) get a synthetic
+//! [`ROOT_PACKAGE`] depending on every package nothing else depends on. On
+//! top of that this module reports staleness against the sibling manifest,
+//! diffs lockfiles between git revisions and explains why a package is in the
+//! tree.
+
+use crate::{RhemaError, RhemaResult};
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Name of the synthetic project package of lockfiles that have none
+pub const ROOT_PACKAGE: &str = "<root>";
+
+/// Directories never searched for lockfiles
+const SKIPPED_DIRS: &[&str] = &[
+    ".git",
+    "node_modules",
+    "target",
+    ".venv",
+    "venv",
+    "__pycache__",
+];
+
+/// Supported lockfile formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LockfileKind {
+    Cargo,
+    Npm,
+    Poetry,
+}
+
+impl LockfileKind {
+    pub const ALL: [LockfileKind; 3] =
+        [LockfileKind::Cargo, LockfileKind::Npm, LockfileKind::Poetry];
+
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            LockfileKind::Cargo => "Cargo.lock",
+            LockfileKind::Npm => "package-lock.json",
+            LockfileKind::Poetry => "poetry.lock",
+        }
+    }
+
+    /// Manifest the lockfile is generated from, in the same directory
+    pub fn manifest_name(&self) -> &'static str {
+        match self {
+            LockfileKind::Cargo => "Cargo.toml",
+            LockfileKind::Npm => "package.json",
+            LockfileKind::Poetry => "pyproject.toml",
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let file_name = path.file_name()?.to_str()?;
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.file_name() == file_name)
+    }
+
+    /// Canonical package name; Python names compare case- and separator-insensitively
+    pub fn normalize(&self, name: &str) -> String {
+        match self {
+            LockfileKind::Poetry => name.to_lowercase().replace(['_', '.'], "-"),
+            _ => name.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for LockfileKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.file_name())
+    }
+}
+
+/// One resolved package
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LockedPackage {
+    pub name: String,
+    pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Names of the packages this one depends on
+    pub dependencies: Vec<String>,
+    /// Only needed for development
+    pub dev: bool,
+}
+
+/// A parsed lockfile
+#[derive(Debug, Clone, Serialize)]
+pub struct Lockfile {
+    pub kind: LockfileKind,
+    /// Path relative to the repository root
+    pub path: PathBuf,
+    pub packages: Vec<LockedPackage>,
+    /// Project packages the dependency graph starts from
+    pub roots: Vec<String>,
+    /// Dependency specs the lockfile was resolved against, when it records them
+    #[serde(skip)]
+    pub locked_specs: BTreeMap<String, String>,
+}
+
+impl Lockfile {
+    /// Parse lockfile content; `path` is only used for reporting
+    pub fn parse(kind: LockfileKind, path: impl Into<PathBuf>, content: &str) -> RhemaResult<Self> {
+        let path = path.into();
+        let parse_error = |e: &dyn std::fmt::Display| {
+            RhemaError::ParseError(format!("{}: {}", path.display(), e))
+        };
+        let mut lockfile = Lockfile {
+            kind,
+            path: path.clone(),
+            packages: Vec::new(),
+            roots: Vec::new(),
+            locked_specs: BTreeMap::new(),
+        };
+
+        match kind {
+            LockfileKind::Cargo => {
+                let table: toml::Table = toml::from_str(content).map_err(|e| parse_error(&e))?;
+                lockfile.parse_cargo(&table);
+            }
+            LockfileKind::Npm => {
+                let value: serde_json::Value =
+                    serde_json::from_str(content).map_err(|e| parse_error(&e))?;
+                lockfile.parse_npm(&value);
+            }
+            LockfileKind::Poetry => {
+                let table: toml::Table = toml::from_str(content).map_err(|e| parse_error(&e))?;
+                lockfile.parse_poetry(&table);
+            }
+        }
+
+        if lockfile.roots.is_empty() {
+            lockfile.add_synthetic_root();
+        }
+        Ok(lockfile)
+    }
+
+    /// Read and parse a lockfile below the repository root
+    pub fn load(repo_root: &Path, path: &Path) -> RhemaResult<Self> {
+        let kind = LockfileKind::from_path(path).ok_or_else(|| {
+            RhemaError::InvalidInput(format!("Not a supported lockfile: {}", path.display()))
+        })?;
+        let content = std::fs::read_to_string(repo_root.join(path))?;
+        Self::parse(kind, path, &content)
+    }
+
+    fn parse_cargo(&mut self, table: &toml::Table) {
+        let entries = table.get("package").and_then(|p| p.as_array());
+        for entry in entries.into_iter().flatten() {
+            let (Some(name), Some(version)) = (
+                entry.get("name").and_then(|v| v.as_str()),
+                entry.get("version").and_then(|v| v.as_str()),
+            ) else {
+                continue;
+            };
+            let source = entry
+                .get("source")
+                .and_then(|v| v.as_str())
+                .map(str::to_string);
+            // Entries read `name`, `name version` or `name version (source)`
+            let dependencies = entry
+                .get("dependencies")
+                .and_then(|d| d.as_array())
+                .into_iter()
+                .flatten()
+                .filter_map(|d| d.as_str()?.split_whitespace().next())
+                .map(str::to_string)
+                .collect();
+
+            // Crates without a source are the workspace's own
+            if source.is_none() {
+                self.roots.push(name.to_string());
+            }
+            self.packages.push(LockedPackage {
+                name: name.to_string(),
+                version: version.to_string(),
+                source,
+                dependencies,
+                dev: false,
+            });
+        }
+    }
+
+    fn parse_npm(&mut self, value: &serde_json::Value) {
+        let keys = |entry: &serde_json::Value, sections: &[&str]| -> Vec<String> {
+            sections
+                .iter()
+                .filter_map(|section| entry.get(*section)?.as_object())
+                .flat_map(|deps| deps.keys().cloned())
+                .collect()
+        };
+
+        if let Some(packages) = value.get("packages").and_then(|p| p.as_object()) {
+            // lockfileVersion 2 and 3
+            for (key, entry) in packages {
+                if key.is_empty() {
+                    let name = entry
+                        .get("name")
+                        .and_then(|n| n.as_str())
+                        .unwrap_or(ROOT_PACKAGE)
+                        .to_string();
+                    for section in ["dependencies", "devDependencies", "optionalDependencies"] {
+                        let specs = entry.get(section).and_then(|d| d.as_object());
+                        for (dep, spec) in specs.into_iter().flatten() {
+                            self.locked_specs
+                                .insert(dep.clone(), spec.as_str().unwrap_or_default().to_string());
+                        }
+                    }
+                    self.roots.push(name.clone());
+                    self.packages.push(LockedPackage {
+                        name,
+                        version: entry
+                            .get("version")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or_default()
+                            .to_string(),
+                        source: None,
+                        dependencies: keys(
+                            entry,
+                            &["dependencies", "devDependencies", "optionalDependencies"],
+                        ),
+                        dev: false,
+                    });
+                    continue;
+                }
+
+                // Workspace folders and links are not installed packages
+                let Some(index) = key.rfind("node_modules/") else {
+                    continue;
+                };
+                let Some(version) = entry.get("version").and_then(|v| v.as_str()) else {
+                    continue;
+                };
+                let name = entry
+                    .get("name")
+                    .and_then(|n| n.as_str())
+                    .unwrap_or(&key[index + "node_modules/".len()..]);
+                self.packages.push(LockedPackage {
+                    name: name.to_string(),
+                    version: version.to_string(),
+                    source: entry
+                        .get("resolved")
+                        .and_then(|r| r.as_str())
+                        .map(str::to_string),
+                    dependencies: keys(entry, &["dependencies", "optionalDependencies"]),
+                    dev: entry.get("dev").and_then(|d| d.as_bool()).unwrap_or(false),
+                });
+            }
+        } else if let Some(dependencies) = value.get("dependencies").and_then(|d| d.as_object()) {
+            // lockfileVersion 1 nests dependencies instead of listing paths
+            let mut pending: Vec<&serde_json::Map<String, serde_json::Value>> = vec![dependencies];
+            while let Some(level) = pending.pop() {
+                for (name, entry) in level {
+                    let Some(version) = entry.get("version").and_then(|v| v.as_str()) else {
+                        continue;
+                    };
+                    self.packages.push(LockedPackage {
+                        name: name.clone(),
+                        version: version.to_string(),
+                        source: entry
+                            .get("resolved")
+                            .and_then(|r| r.as_str())
+                            .map(str::to_string),
+                        dependencies: keys(entry, &["requires"]),
+                        dev: entry.get("dev").and_then(|d| d.as_bool()).unwrap_or(false),
+                    });
+                    if let Some(nested) = entry.get("dependencies").and_then(|d| d.as_object()) {
+                        pending.push(nested);
+                    }
+                }
+            }
+        }
+    }
+
+    fn parse_poetry(&mut self, table: &toml::Table) {
+        let entries = table.get("package").and_then(|p| p.as_array());
+        for entry in entries.into_iter().flatten() {
+            let (Some(name), Some(version)) = (
+                entry.get("name").and_then(|v| v.as_str()),
+                entry.get("version").and_then(|v| v.as_str()),
+            ) else {
+                continue;
+            };
+            let dependencies = entry
+                .get("dependencies")
+                .and_then(|d| d.as_table())
+                .into_iter()
+                .flat_map(|deps| deps.keys())
+                .map(|dep| self.kind.normalize(dep))
+                .collect();
+            let source = entry
+                .get("source")
+                .and_then(|s| s.get("url"))
+                .and_then(|u| u.as_str())
+                .map(str::to_string);
+            self.packages.push(LockedPackage {
+                name: self.kind.normalize(name),
+                version: version.to_string(),
+                source,
+                dependencies,
+                // Poetry before 1.5 recorded the dependency group as a category
+                dev: entry.get("category").and_then(|c| c.as_str()) == Some("dev"),
+            });
+        }
+    }
+
+    /// Root the graph at a project package depending on every package nothing else depends on
+    fn add_synthetic_root(&mut self) {
+        let required: BTreeSet<&str> = self
+            .packages
+            .iter()
+            .flat_map(|package| package.dependencies.iter().map(String::as_str))
+            .collect();
+        let mut top_level: Vec<String> = self
+            .packages
+            .iter()
+            .map(|package| package.name.clone())
+            .filter(|name| !required.contains(name.as_str()))
+            .collect();
+        top_level.sort();
+        top_level.dedup();
+
+        self.roots.push(ROOT_PACKAGE.to_string());
+        self.packages.push(LockedPackage {
+            name: ROOT_PACKAGE.to_string(),
+            version: String::new(),
+            source: None,
+            dependencies: top_level,
+            dev: false,
+        });
+    }
+
+    /// Installed packages, leaving out the project packages
+    pub fn dependencies(&self) -> impl Iterator<Item = &LockedPackage> {
+        self.packages
+            .iter()
+            .filter(|package| !self.roots.contains(&package.name))
+    }
+
+    /// Every locked entry with this name
+    pub fn find(&self, name: &str) -> Vec<&LockedPackage> {
+        let name = self.kind.normalize(name);
+        self.packages
+            .iter()
+            .filter(|package| package.name == name)
+            .collect()
+    }
+
+    fn dependency_map(&self) -> HashMap<&str, BTreeSet<&str>> {
+        let mut map: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+        for package in &self.packages {
+            map.entry(package.name.as_str())
+                .or_default()
+                .extend(package.dependencies.iter().map(String::as_str));
+        }
+        map
+    }
+
+    /// Why a package is in the tree: who depends on it and the shortest path
+    /// to it from each project package
+    pub fn explain(&self, name: &str, max_paths: usize) -> Option<PackageExplanation> {
+        let entries = self.find(name);
+        if entries.is_empty() {
+            return None;
+        }
+        let name = entries[0].name.clone();
+        let graph = self.dependency_map();
+
+        let mut dependents: Vec<String> = graph
+            .iter()
+            .filter(|(_, deps)| deps.contains(name.as_str()))
+            .map(|(dependent, _)| dependent.to_string())
+            .collect();
+        dependents.sort();
+        dependents.dedup();
+        let direct = dependents.iter().any(|d| self.roots.contains(d));
+
+        let mut paths: Vec<Vec<String>> = self
+            .roots
+            .iter()
+            .filter_map(|root| shortest_path(&graph, root, &name))
+            .collect();
+        paths.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+        paths.dedup();
+        paths.truncate(max_paths);
+
+        let mut versions: Vec<String> = entries.iter().map(|p| p.version.clone()).collect();
+        versions.sort();
+        versions.dedup();
+
+        Some(PackageExplanation {
+            lockfile: self.path.clone(),
+            package: name,
+            versions,
+            direct,
+            dev_only: entries.iter().all(|p| p.dev),
+            dependents,
+            paths,
+        })
+    }
+}
+
+/// Breadth-first search over package names
+fn shortest_path(
+    graph: &HashMap<&str, BTreeSet<&str>>,
+    from: &str,
+    to: &str,
+) -> Option<Vec<String>> {
+    let mut parents: HashMap<&str, &str> = HashMap::new();
+    let mut queue = VecDeque::from([from]);
+    let mut seen = BTreeSet::from([from]);
+    while let Some(current) = queue.pop_front() {
+        if current == to {
+            let mut path = vec![current.to_string()];
+            let mut at = current;
+            while let Some(&parent) = parents.get(at) {
+                path.push(parent.to_string());
+                at = parent;
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for &next in graph.get(current).into_iter().flatten() {
+            if seen.insert(next) {
+                parents.insert(next, current);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Answer to "why is this package here?"
+#[derive(Debug, Clone, Serialize)]
+pub struct PackageExplanation {
+    pub lockfile: PathBuf,
+    pub package: String,
+    pub versions: Vec<String>,
+    /// Declared by a project package
+    pub direct: bool,
+    pub dev_only: bool,
+    /// Packages depending on it directly
+    pub dependents: Vec<String>,
+    /// Dependency chains from project packages, shortest first
+    pub paths: Vec<Vec<String>>,
+}
+
+/// Lockfiles below the repository root, relative to it
+pub fn discover_lockfiles(repo_root: &Path) -> Vec<PathBuf> {
+    let mut found: Vec<PathBuf> = WalkDir::new(repo_root)
+        .into_iter()
+        .filter_entry(|entry| entry.depth() == 0 || !is_skipped_dir(entry))
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| LockfileKind::from_path(entry.path()).is_some())
+        .filter_map(|entry| {
+            entry
+                .path()
+                .strip_prefix(repo_root)
+                .ok()
+                .map(Path::to_path_buf)
+        })
+        .collect();
+    found.sort();
+    found
+}
+
+fn is_skipped_dir(entry: &walkdir::DirEntry) -> bool {
+    entry.file_type().is_dir()
+        && SKIPPED_DIRS
+            .iter()
+            .any(|skipped| entry.file_name() == *skipped)
+}
+
+/// How a lockfile relates to its manifest
+#[derive(Debug, Clone, Serialize)]
+pub struct LockfileStatus {
+    pub path: PathBuf,
+    pub kind: LockfileKind,
+    pub packages: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manifest: Option<PathBuf>,
+    /// Why the lockfile no longer matches the manifest; empty when up to date
+    pub stale: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl LockfileStatus {
+    pub fn is_stale(&self) -> bool {
+        !self.stale.is_empty()
+    }
+}
+
+/// Status of one lockfile; parse problems are reported instead of failing
+pub fn lockfile_status(repo_root: &Path, path: &Path) -> LockfileStatus {
+    let kind = LockfileKind::from_path(path).unwrap_or(LockfileKind::Cargo);
+    let mut status = LockfileStatus {
+        path: path.to_path_buf(),
+        kind,
+        packages: 0,
+        manifest: None,
+        stale: Vec::new(),
+        error: None,
+    };
+
+    let lockfile = match Lockfile::load(repo_root, path) {
+        Ok(lockfile) => lockfile,
+        Err(e) => {
+            status.error = Some(e.to_string());
+            return status;
+        }
+    };
+    status.packages = lockfile.dependencies().count();
+
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let manifest = dir.join(kind.manifest_name());
+    if !repo_root.join(&manifest).is_file() {
+        status
+            .stale
+            .push(format!("no {} next to the lockfile", kind.manifest_name()));
+        return status;
+    }
+    status.manifest = Some(manifest);
+
+    let declared = match declared_dependencies(kind, &repo_root.join(dir)) {
+        Ok(declared) => declared,
+        Err(e) => {
+            status.error = Some(e.to_string());
+            return status;
+        }
+    };
+    let locked: BTreeSet<&str> = lockfile.packages.iter().map(|p| p.name.as_str()).collect();
+    let missing: Vec<&str> = declared
+        .keys()
+        .map(String::as_str)
+        .filter(|name| !locked.contains(name))
+        .collect();
+    if !missing.is_empty() {
+        status
+            .stale
+            .push(format!("declared but not locked: {}", missing.join(", ")));
+    }
+
+    if !lockfile.locked_specs.is_empty() {
+        let changed: Vec<String> = declared
+            .iter()
+            .filter_map(|(name, spec)| {
+                let locked = lockfile.locked_specs.get(name)?;
+                (locked != spec).then(|| format!("{} ({} → {})", name, locked, spec))
+            })
+            .collect();
+        if !changed.is_empty() {
+            status
+                .stale
+                .push(format!("requirement changed: {}", changed.join(", ")));
+        }
+        let dropped: Vec<&str> = lockfile
+            .locked_specs
+            .keys()
+            .map(String::as_str)
+            .filter(|name| !declared.contains_key(*name))
+            .collect();
+        if !dropped.is_empty() {
+            status.stale.push(format!(
+                "locked but no longer declared: {}",
+                dropped.join(", ")
+            ));
+        }
+    }
+
+    status
+}
+
+/// Status of every lockfile in the repository
+pub fn lockfile_statuses(repo_root: &Path) -> Vec<LockfileStatus> {
+    discover_lockfiles(repo_root)
+        .iter()
+        .map(|path| lockfile_status(repo_root, path))
+        .collect()
+}
+
+/// Dependencies declared by the manifests a lockfile covers, with their specs
+pub fn declared_dependencies(
+    kind: LockfileKind,
+    dir: &Path,
+) -> RhemaResult<BTreeMap<String, String>> {
+    let mut declared = BTreeMap::new();
+    match kind {
+        LockfileKind::Cargo => {
+            for manifest in cargo_manifests(dir) {
+                let table = read_toml(&manifest)?;
+                collect_cargo_tables(&table, &mut declared);
+                if let Some(workspace) = table.get("workspace") {
+                    collect_cargo_table(workspace.get("dependencies"), &mut declared);
+                }
+            }
+        }
+        LockfileKind::Npm => {
+            let path = dir.join(kind.manifest_name());
+            let manifest: serde_json::Value =
+                serde_json::from_str(&std::fs::read_to_string(&path)?)
+                    .map_err(|e| RhemaError::ParseError(format!("{}: {}", path.display(), e)))?;
+            for section in ["dependencies", "devDependencies", "optionalDependencies"] {
+                let deps = manifest.get(section).and_then(|d| d.as_object());
+                for (name, spec) in deps.into_iter().flatten() {
+                    declared.insert(name.clone(), spec.as_str().unwrap_or_default().to_string());
+                }
+            }
+        }
+        LockfileKind::Poetry => {
+            let table = read_toml(&dir.join(kind.manifest_name()))?;
+            let poetry = table.get("tool").and_then(|t| t.get("poetry"));
+            let mut sections: Vec<Option<&toml::Value>> = vec![
+                poetry.and_then(|p| p.get("dependencies")),
+                poetry.and_then(|p| p.get("dev-dependencies")),
+            ];
+            let groups = poetry
+                .and_then(|p| p.get("group"))
+                .and_then(|g| g.as_table());
+            sections.extend(
+                groups
+                    .into_iter()
+                    .flat_map(|groups| groups.values())
+                    .map(|group| group.get("dependencies")),
+            );
+            for (name, spec) in sections
+                .into_iter()
+                .flatten()
+                .filter_map(|section| section.as_table())
+                .flatten()
+            {
+                if name != "python" {
+                    declared.insert(kind.normalize(name), toml_spec(spec));
+                }
+            }
+
+            let requirements = table
+                .get("project")
+                .and_then(|p| p.get("dependencies"))
+                .and_then(|d| d.as_array());
+            for requirement in requirements
+                .into_iter()
+                .flatten()
+                .filter_map(|r| r.as_str())
+            {
+                let name: String = requirement
+                    .chars()
+                    .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+                    .collect();
+                if !name.is_empty() {
+                    let spec = requirement[name.len()..].trim().to_string();
+                    declared.insert(kind.normalize(&name), spec);
+                }
+            }
+        }
+    }
+    Ok(declared)
+}
+
+/// Every Cargo manifest sharing the lockfile in `dir`
+fn cargo_manifests(dir: &Path) -> Vec<PathBuf> {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_entry(|entry| {
+            if entry.depth() == 0 || !entry.file_type().is_dir() {
+                return true;
+            }
+            // A nested lockfile belongs to a separate workspace
+            !is_skipped_dir(entry) && !entry.path().join("Cargo.lock").exists()
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name() == "Cargo.toml")
+        .map(|entry| entry.into_path())
+        .collect()
+}
+
+fn read_toml(path: &Path) -> RhemaResult<toml::Table> {
+    toml::from_str(&std::fs::read_to_string(path)?)
+        .map_err(|e| RhemaError::ParseError(format!("{}: {}", path.display(), e)))
+}
+
+fn collect_cargo_tables(table: &toml::Table, declared: &mut BTreeMap<String, String>) {
+    for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+        collect_cargo_table(table.get(section), declared);
+    }
+    let targets = table.get("target").and_then(|t| t.as_table());
+    for target in targets.into_iter().flat_map(|targets| targets.values()) {
+        for section in ["dependencies", "dev-dependencies", "build-dependencies"] {
+            collect_cargo_table(target.get(section), declared);
+        }
+    }
+}
+
+fn collect_cargo_table(section: Option<&toml::Value>, declared: &mut BTreeMap<String, String>) {
+    let Some(deps) = section.and_then(|s| s.as_table()) else {
+        return;
+    };
+    for (key, spec) in deps {
+        // `alias = { package = "real-name" }` locks the real name
+        let name = spec.get("package").and_then(|p| p.as_str()).unwrap_or(key);
+        declared.insert(name.to_string(), toml_spec(spec));
+    }
+}
+
+fn toml_spec(spec: &toml::Value) -> String {
+    match spec {
+        toml::Value::String(version) => version.clone(),
+        other => other
+            .get("version")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string(),
+    }
+}
+
+/// Kind of change to a package between two lockfiles
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Upgraded,
+    Downgraded,
+    /// Several versions changed, or the versions don't compare
+    Changed,
+}
+
+/// A package whose locked versions changed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PackageChange {
+    pub name: String,
+    pub change: ChangeKind,
+    pub from: Vec<String>,
+    pub to: Vec<String>,
+}
+
+impl std::fmt::Display for PackageChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.change {
+            ChangeKind::Added => write!(f, "+ {} {}", self.name, self.to.join(", ")),
+            ChangeKind::Removed => write!(f, "- {} {}", self.name, self.from.join(", ")),
+            _ => write!(
+                f,
+                "~ {} {} → {}",
+                self.name,
+                self.from.join(", "),
+                self.to.join(", ")
+            ),
+        }
+    }
+}
+
+/// Changes to one lockfile between two revisions
+#[derive(Debug, Clone, Serialize)]
+pub struct LockfileDiff {
+    pub path: PathBuf,
+    pub kind: LockfileKind,
+    pub changes: Vec<PackageChange>,
+}
+
+/// Package changes from `old` to `new`; a missing side counts as empty
+pub fn diff_lockfiles(old: Option<&Lockfile>, new: Option<&Lockfile>) -> Vec<PackageChange> {
+    let versions = |lockfile: Option<&Lockfile>| -> BTreeMap<String, BTreeSet<String>> {
+        let mut versions: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for package in lockfile.into_iter().flat_map(|l| l.dependencies()) {
+            versions
+                .entry(package.name.clone())
+                .or_default()
+                .insert(package.version.clone());
+        }
+        versions
+    };
+    let before = versions(old);
+    let after = versions(new);
+
+    let names: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let from: Vec<String> = before.get(name).into_iter().flatten().cloned().collect();
+            let to: Vec<String> = after.get(name).into_iter().flatten().cloned().collect();
+            let change = match (from.as_slice(), to.as_slice()) {
+                (a, b) if a == b => return None,
+                ([], _) => ChangeKind::Added,
+                (_, []) => ChangeKind::Removed,
+                ([a], [b]) => match compare_versions(a, b) {
+                    Some(Ordering::Less) => ChangeKind::Upgraded,
+                    Some(Ordering::Greater) => ChangeKind::Downgraded,
+                    _ => ChangeKind::Changed,
+                },
+                _ => ChangeKind::Changed,
+            };
+            Some(PackageChange {
+                name: name.clone(),
+                change,
+                from,
+                to,
+            })
+        })
+        .collect()
+}
+
+/// Compare dotted numeric versions, ignoring pre-release and build suffixes
+fn compare_versions(a: &str, b: &str) -> Option<Ordering> {
+    fn components(version: &str) -> Option<Vec<u64>> {
+        version
+            .trim_start_matches('v')
+            .split(['-', '+'])
+            .next()?
+            .split('.')
+            .map(|part| part.parse().ok())
+            .collect()
+    }
+    Some(components(a)?.cmp(&components(b)?))
+}
+
+/// Lockfile changes from revision `from` to revision `to`, or to the working tree
+pub fn diff_revisions(
+    repo_root: &Path,
+    from: &str,
+    to: Option<&str>,
+) -> RhemaResult<Vec<LockfileDiff>> {
+    let repo = git2::Repository::discover(repo_root)?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| {
+            RhemaError::GitRepoNotFound("bare repositories have no lockfiles".to_string())
+        })?
+        .to_path_buf();
+
+    let before = lockfiles_at_revision(&repo, from)?;
+    let after = match to {
+        Some(rev) => lockfiles_at_revision(&repo, rev)?,
+        None => discover_lockfiles(&workdir)
+            .into_iter()
+            .filter_map(|path| {
+                let content = std::fs::read_to_string(workdir.join(&path)).ok()?;
+                Some((path, content))
+            })
+            .collect(),
+    };
+
+    let paths: BTreeSet<&PathBuf> = before.keys().chain(after.keys()).collect();
+    let mut diffs = Vec::new();
+    for path in paths {
+        let Some(kind) = LockfileKind::from_path(path) else {
+            continue;
+        };
+        let parse = |content: Option<&String>| -> RhemaResult<Option<Lockfile>> {
+            content
+                .map(|content| Lockfile::parse(kind, path.clone(), content))
+                .transpose()
+        };
+        let old = parse(before.get(path))?;
+        let new = parse(after.get(path))?;
+        let changes = diff_lockfiles(old.as_ref(), new.as_ref());
+        if !changes.is_empty() {
+            diffs.push(LockfileDiff {
+                path: path.clone(),
+                kind,
+                changes,
+            });
+        }
+    }
+    Ok(diffs)
+}
+
+/// Content of every lockfile committed at a revision, keyed by path
+fn lockfiles_at_revision(
+    repo: &git2::Repository,
+    rev: &str,
+) -> RhemaResult<BTreeMap<PathBuf, String>> {
+    let tree = repo.revparse_single(rev)?.peel_to_tree()?;
+    let mut blobs = Vec::new();
+    tree.walk(git2::TreeWalkMode::PreOrder, |dir, entry| {
+        let Some(name) = entry.name() else {
+            return git2::TreeWalkResult::Ok;
+        };
+        if entry.kind() == Some(git2::ObjectType::Tree) && SKIPPED_DIRS.contains(&name) {
+            return git2::TreeWalkResult::Skip;
+        }
+        let path = Path::new(dir).join(name);
+        if entry.kind() == Some(git2::ObjectType::Blob) && LockfileKind::from_path(&path).is_some()
+        {
+            blobs.push((path, entry.id()));
+        }
+        git2::TreeWalkResult::Ok
+    })?;
+
+    let mut lockfiles = BTreeMap::new();
+    for (path, id) in blobs {
+        let blob = repo.find_blob(id)?;
+        lockfiles.insert(path, String::from_utf8_lossy(blob.content()).into_owned());
+    }
+    Ok(lockfiles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CARGO_LOCK: &str = r#"
+version = 3
+
+[[package]]
+name = "app"
+version = "0.1.0"
+dependencies = ["serde", "tokio 1.36.0"]
+
+[[package]]
+name = "serde"
+version = "1.0.197"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+dependencies = ["serde_derive"]
+
+[[package]]
+name = "serde_derive"
+version = "1.0.197"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "tokio"
+version = "1.36.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"#;
+
+    #[test]
+    fn test_cargo_lock_explain() {
+        let lockfile = Lockfile::parse(LockfileKind::Cargo, "Cargo.lock", CARGO_LOCK).unwrap();
+        assert_eq!(lockfile.roots, vec!["app"]);
+        assert_eq!(lockfile.dependencies().count(), 3);
+
+        let why = lockfile.explain("serde_derive", 5).unwrap();
+        assert!(!why.direct);
+        assert_eq!(why.dependents, vec!["serde"]);
+        assert_eq!(why.paths, vec![vec!["app", "serde", "serde_derive"]]);
+        assert!(lockfile.explain("rand", 5).is_none());
+    }
+
+    #[test]
+    fn test_npm_and_poetry_parsing() {
+        let npm = r#"{
+            "name": "web",
+            "lockfileVersion": 3,
+            "packages": {
+                "": { "name": "web", "dependencies": { "react": "^18.2.0" }, "devDependencies": { "jest": "^29.0.0" } },
+                "node_modules/react": { "version": "18.2.0", "dependencies": { "loose-envify": "^1.1.0" } },
+                "node_modules/loose-envify": { "version": "1.4.0" },
+                "node_modules/jest": { "version": "29.7.0", "dev": true }
+            }
+        }"#;
+        let lockfile = Lockfile::parse(LockfileKind::Npm, "package-lock.json", npm).unwrap();
+        assert_eq!(lockfile.roots, vec!["web"]);
+        assert_eq!(lockfile.locked_specs["react"], "^18.2.0");
+        let why = lockfile.explain("jest", 5).unwrap();
+        assert!(why.direct && why.dev_only);
+
+        let poetry = r#"
+[[package]]
+name = "requests"
+version = "2.31.0"
+
+[package.dependencies]
+charset_normalizer = ">=2,<4"
+
+[[package]]
+name = "charset-normalizer"
+version = "3.3.2"
+"#;
+        let lockfile = Lockfile::parse(LockfileKind::Poetry, "poetry.lock", poetry).unwrap();
+        assert_eq!(lockfile.roots, vec![ROOT_PACKAGE]);
+        let why = lockfile.explain("Charset_Normalizer", 5).unwrap();
+        assert_eq!(
+            why.paths,
+            vec![vec![ROOT_PACKAGE, "requests", "charset-normalizer"]]
+        );
+    }
+
+    #[test]
+    fn test_diff_classifies_changes() {
+        let old = Lockfile::parse(LockfileKind::Cargo, "Cargo.lock", CARGO_LOCK).unwrap();
+        let new_content = CARGO_LOCK.replace("1.36.0", "1.37.0").replace(
+            "[[package]]\nname = \"serde_derive\"",
+            "[[package]]\nname = \"rand\"",
+        );
+        let new = Lockfile::parse(LockfileKind::Cargo, "Cargo.lock", &new_content).unwrap();
+
+        let changes = diff_lockfiles(Some(&old), Some(&new));
+        let summary: Vec<(String, ChangeKind)> =
+            changes.iter().map(|c| (c.name.clone(), c.change)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("rand".to_string(), ChangeKind::Added),
+                ("serde_derive".to_string(), ChangeKind::Removed),
+                ("tokio".to_string(), ChangeKind::Upgraded),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_status_reports_undeclared_and_changed_requirements() {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{ "dependencies": { "react": "^18.3.0", "lodash": "^4.17.0" } }"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("package-lock.json"),
+            r#"{ "lockfileVersion": 3, "packages": {
+                "": { "dependencies": { "react": "^18.2.0" } },
+                "node_modules/react": { "version": "18.2.0" } } }"#,
+        )
+        .unwrap();
+
+        let statuses = lockfile_statuses(dir.path());
+        assert_eq!(statuses.len(), 1);
+        let status = &statuses[0];
+        assert!(status.is_stale());
+        assert_eq!(status.packages, 1);
+        assert!(status.stale[0].contains("lodash"));
+        assert!(status.stale[1].contains("react (^18.2.0 → ^18.3.0)"));
+    }
+}
@@ -0,0 +1,577 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Entry-level resolution of merge conflicts in context files.
+//!
+//! A context file left conflicted by a merge is merged again entry by entry
+//! from the base, ours and theirs versions in the git index. Entries changed
+//! on one side only merge cleanly; an entry changed differently on both sides
+//! becomes an [`EntryConflict`] to resolve by taking one side, merging
+//! fields, or editing. Each resolved entry records how and why in its
+//! [`RESOLUTION_FIELD`], and the file is validated against its schema before
+//! it is written and marked resolved in the index.
+
+use crate::review::{REVIEWED_FILES, REVIEW_FIELD, REVIEW_STATE_FIELD};
+use crate::schema::{Conventions, Decisions, Knowledge, Patterns, Todos, Validatable};
+use crate::{RhemaError, RhemaResult};
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Entry field holding the [`ResolutionRecord`] of a resolved conflict
+pub const RESOLUTION_FIELD: &str = "merge_resolution";
+
+/// Side of a merge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    Ours,
+    Theirs,
+}
+
+/// How a conflict was resolved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResolutionStrategy {
+    TakeOurs,
+    TakeTheirs,
+    MergeFields,
+    Edit,
+}
+
+impl fmt::Display for ResolutionStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ResolutionStrategy::TakeOurs => "take ours",
+            ResolutionStrategy::TakeTheirs => "take theirs",
+            ResolutionStrategy::MergeFields => "merge fields",
+            ResolutionStrategy::Edit => "edit",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Provenance of a resolved conflict, stored on the entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolutionRecord {
+    pub resolved_by: String,
+    pub resolved_at: DateTime<Utc>,
+    pub strategy: ResolutionStrategy,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rationale: Option<String>,
+}
+
+/// An entry changed differently on both sides of a merge
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntryConflict {
+    /// Top-level key of the file, e.g. `todos`
+    pub collection: String,
+
+    /// Entry id; `None` when the whole top-level value conflicts
+    pub id: Option<String>,
+
+    pub base: Option<Value>,
+    /// `None` when the side deleted the entry
+    pub ours: Option<Value>,
+    pub theirs: Option<Value>,
+
+    pub resolution: Option<ResolutionRecord>,
+}
+
+impl EntryConflict {
+    /// Display name, `collection/id`
+    pub fn label(&self) -> String {
+        match &self.id {
+            Some(id) => format!("{}/{}", self.collection, id),
+            None => self.collection.clone(),
+        }
+    }
+
+    pub fn side(&self, side: Side) -> Option<&Value> {
+        match side {
+            Side::Ours => self.ours.as_ref(),
+            Side::Theirs => self.theirs.as_ref(),
+        }
+    }
+
+    /// Fields both sides changed to different values
+    pub fn conflicting_fields(&self) -> Vec<String> {
+        self.fields(false)
+            .into_iter()
+            .filter(|field| {
+                let (base, ours, theirs) = self.field_versions(field);
+                merge_value(base, ours, theirs).is_err()
+            })
+            .collect()
+    }
+
+    /// Merge field by field: fields changed on one side take that side, and
+    /// fields changed on both take the side `pick` names for them. `None`
+    /// when one side deleted the entry, leaving no fields to merge.
+    pub fn merge_fields(&self, pick: impl Fn(&str) -> Side) -> Option<Value> {
+        self.ours.as_ref()?;
+        self.theirs.as_ref()?;
+
+        let mut merged = Mapping::new();
+        for field in self.fields(true) {
+            let (base, ours, theirs) = self.field_versions(&field);
+            let value = match merge_value(base, ours, theirs) {
+                Ok(value) => value,
+                // Review state and provenance stay as on our side
+                Err(()) if is_bookkeeping(&field) => ours,
+                Err(()) => match pick(&field) {
+                    Side::Ours => ours,
+                    Side::Theirs => theirs,
+                },
+            };
+            if let Some(value) = value {
+                merged.insert(Value::String(field), value.clone());
+            }
+        }
+        Some(Value::Mapping(merged))
+    }
+
+    /// Field names across all versions, in ours-then-theirs order
+    fn fields(&self, bookkeeping: bool) -> Vec<String> {
+        let mut seen = BTreeSet::new();
+        let mut fields = Vec::new();
+        for version in [&self.ours, &self.theirs, &self.base] {
+            let Some(map) = version.as_ref().and_then(Value::as_mapping) else {
+                continue;
+            };
+            for key in map.keys().filter_map(Value::as_str) {
+                if (bookkeeping || !is_bookkeeping(key)) && seen.insert(key.to_string()) {
+                    fields.push(key.to_string());
+                }
+            }
+        }
+        fields
+    }
+
+    fn field_versions(&self, field: &str) -> (Option<&Value>, Option<&Value>, Option<&Value>) {
+        (
+            field_of(&self.base, field),
+            field_of(&self.ours, field),
+            field_of(&self.theirs, field),
+        )
+    }
+}
+
+fn field_of<'a>(version: &'a Option<Value>, field: &str) -> Option<&'a Value> {
+    version.as_ref().and_then(|value| value.get(field))
+}
+
+fn is_bookkeeping(field: &str) -> bool {
+    matches!(field, REVIEW_STATE_FIELD | REVIEW_FIELD | RESOLUTION_FIELD)
+}
+
+/// A conflicted context file, merged entry by entry
+#[derive(Debug, Clone)]
+pub struct FileMerge {
+    /// Path relative to the repository root
+    pub path: PathBuf,
+    pub conflicts: Vec<EntryConflict>,
+
+    /// Cleanly merged document; conflicting entries hold the ours version
+    /// until resolved
+    document: Value,
+}
+
+impl FileMerge {
+    /// Merge the three versions of a context file
+    pub fn from_versions(
+        path: impl Into<PathBuf>,
+        base: Option<&str>,
+        ours: &str,
+        theirs: &str,
+    ) -> RhemaResult<Self> {
+        let path = path.into();
+        let parse = |content: &str| -> RhemaResult<Value> {
+            serde_yaml::from_str(content).map_err(|e| RhemaError::InvalidYaml {
+                file: path.display().to_string(),
+                message: e.to_string(),
+            })
+        };
+        let base = base.map(parse).transpose()?;
+        let (document, conflicts) = merge_documents(base.as_ref(), &parse(ours)?, &parse(theirs)?);
+        Ok(Self {
+            path,
+            conflicts,
+            document,
+        })
+    }
+
+    pub fn is_resolved(&self) -> bool {
+        self.conflicts
+            .iter()
+            .all(|conflict| conflict.resolution.is_some())
+    }
+
+    /// Settle conflict `index` with `value` (`None` deletes the entry),
+    /// recording `record` on the entry
+    pub fn resolve(
+        &mut self,
+        index: usize,
+        value: Option<Value>,
+        record: ResolutionRecord,
+    ) -> RhemaResult<()> {
+        let conflict = self.conflicts.get(index).ok_or_else(|| {
+            RhemaError::NotFound(format!("No conflict #{} in {}", index, self.path.display()))
+        })?;
+        let value = match (value, &conflict.id) {
+            (Some(Value::Mapping(mut fields)), Some(_)) => {
+                fields.insert(
+                    Value::String(RESOLUTION_FIELD.to_string()),
+                    serde_yaml::to_value(&record)?,
+                );
+                Some(Value::Mapping(fields))
+            }
+            (value, _) => value,
+        };
+        let (collection, id) = (conflict.collection.clone(), conflict.id.clone());
+        place(&mut self.document, &collection, id.as_deref(), value);
+        self.conflicts[index].resolution = Some(record);
+        Ok(())
+    }
+
+    /// The document as it would be written
+    pub fn document(&self) -> &Value {
+        &self.document
+    }
+
+    /// Check the document against the schema of its file
+    pub fn validate(&self) -> RhemaResult<()> {
+        let file_name = self
+            .path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        let file = self.path.display().to_string();
+        match file_name {
+            "todos.yaml" => validate_as::<Todos>(&self.document, &file),
+            "knowledge.yaml" => validate_as::<Knowledge>(&self.document, &file),
+            "decisions.yaml" => validate_as::<Decisions>(&self.document, &file),
+            "patterns.yaml" => validate_as::<Patterns>(&self.document, &file),
+            "conventions.yaml" => validate_as::<Conventions>(&self.document, &file),
+            _ => Ok(()),
+        }
+    }
+
+    /// Validate and write the resolved file, then mark it resolved in the
+    /// git index
+    pub fn write(&self, repo_root: &Path) -> RhemaResult<()> {
+        if !self.is_resolved() {
+            return Err(RhemaError::ValidationError(format!(
+                "{} still has unresolved conflicts",
+                self.path.display()
+            )));
+        }
+        self.validate()?;
+        std::fs::write(
+            repo_root.join(&self.path),
+            serde_yaml::to_string(&self.document)?,
+        )?;
+
+        let repo = git2::Repository::open(repo_root)?;
+        let mut index = repo.index()?;
+        index.add_path(&self.path)?;
+        index.write()?;
+        Ok(())
+    }
+}
+
+/// Context files the git index holds conflicts for, merged entry by entry
+pub fn conflicted_files(repo_root: &Path) -> RhemaResult<Vec<FileMerge>> {
+    let repo = git2::Repository::open(repo_root)?;
+    let index = repo.index()?;
+    let blob = |entry: &Option<git2::IndexEntry>| -> RhemaResult<Option<String>> {
+        match entry {
+            Some(entry) => {
+                let blob = repo.find_blob(entry.id)?;
+                Ok(Some(String::from_utf8_lossy(blob.content()).into_owned()))
+            }
+            None => Ok(None),
+        }
+    };
+
+    let mut files = Vec::new();
+    for conflict in index.conflicts()? {
+        let conflict = conflict?;
+        let Some(entry) = conflict.our.as_ref().or(conflict.their.as_ref()) else {
+            continue;
+        };
+        let path = PathBuf::from(String::from_utf8_lossy(&entry.path).into_owned());
+        let is_context = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| REVIEWED_FILES.contains(&name));
+        if !is_context {
+            continue;
+        }
+        let (Some(ours), Some(theirs)) = (blob(&conflict.our)?, blob(&conflict.their)?) else {
+            tracing::warn!(
+                "{} was deleted on one side of the merge; resolve it with git",
+                path.display()
+            );
+            continue;
+        };
+        let base = blob(&conflict.ancestor)?;
+        files.push(FileMerge::from_versions(
+            path,
+            base.as_deref(),
+            &ours,
+            &theirs,
+        )?);
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// Three-way merge of context documents. Top-level lists of entries with
+/// an `id` merge entry by entry; other top-level values merge as a whole.
+pub fn merge_documents(
+    base: Option<&Value>,
+    ours: &Value,
+    theirs: &Value,
+) -> (Value, Vec<EntryConflict>) {
+    let empty = Mapping::new();
+    let base_map = base.and_then(Value::as_mapping).unwrap_or(&empty);
+    let ours_map = ours.as_mapping().unwrap_or(&empty);
+    let theirs_map = theirs.as_mapping().unwrap_or(&empty);
+
+    let mut merged = Mapping::new();
+    let mut conflicts = Vec::new();
+    for key in ordered_keys(ours_map.keys(), theirs_map.keys()) {
+        let collection = key.as_str().map(str::to_string).unwrap_or_default();
+        let (b, o, t) = (base_map.get(&key), ours_map.get(&key), theirs_map.get(&key));
+
+        if let (Some(o_entries), Some(t_entries)) = (entry_list(o), entry_list(t)) {
+            let b_entries = entry_list(b).unwrap_or_default();
+            let ids = ordered_keys(
+                o_entries.iter().map(|e| &e.0),
+                t_entries.iter().map(|e| &e.0),
+            );
+            let mut entries = Vec::new();
+            for id in ids {
+                let (be, oe, te) = (
+                    find(&b_entries, &id),
+                    find(&o_entries, &id),
+                    find(&t_entries, &id),
+                );
+                match merge_value(be, oe, te) {
+                    Ok(value) => entries.extend(value.cloned()),
+                    Err(()) => {
+                        entries.extend(oe.cloned());
+                        conflicts.push(EntryConflict {
+                            collection: collection.clone(),
+                            id: Some(id),
+                            base: be.cloned(),
+                            ours: oe.cloned(),
+                            theirs: te.cloned(),
+                            resolution: None,
+                        });
+                    }
+                }
+            }
+            merged.insert(key, Value::Sequence(entries));
+            continue;
+        }
+
+        match merge_value(b, o, t) {
+            Ok(Some(value)) => {
+                merged.insert(key, value.clone());
+            }
+            Ok(None) => {}
+            Err(()) => {
+                if let Some(value) = o {
+                    merged.insert(key.clone(), value.clone());
+                }
+                conflicts.push(EntryConflict {
+                    collection,
+                    id: None,
+                    base: b.cloned(),
+                    ours: o.cloned(),
+                    theirs: t.cloned(),
+                    resolution: None,
+                });
+            }
+        }
+    }
+    (Value::Mapping(merged), conflicts)
+}
+
+/// Three-way merge of one value; `Err` when both sides changed it differently
+fn merge_value<'a>(
+    base: Option<&'a Value>,
+    ours: Option<&'a Value>,
+    theirs: Option<&'a Value>,
+) -> Result<Option<&'a Value>, ()> {
+    if ours == theirs || theirs == base {
+        Ok(ours)
+    } else if ours == base {
+        Ok(theirs)
+    } else {
+        Err(())
+    }
+}
+
+fn find<'a>(entries: &'a [(String, Value)], id: &str) -> Option<&'a Value> {
+    entries
+        .iter()
+        .find(|entry| entry.0 == id)
+        .map(|entry| &entry.1)
+}
+
+/// `(id, entry)` pairs when every item of the value is a mapping with an id
+fn entry_list(value: Option<&Value>) -> Option<Vec<(String, Value)>> {
+    value?
+        .as_sequence()?
+        .iter()
+        .map(|entry| {
+            let id = entry.get("id").and_then(Value::as_str)?;
+            Some((id.to_string(), entry.clone()))
+        })
+        .collect()
+}
+
+/// Keys of `first` in order, then those only in `second`
+fn ordered_keys<'a, K: Clone + PartialEq + 'a>(
+    first: impl Iterator<Item = &'a K>,
+    second: impl Iterator<Item = &'a K>,
+) -> Vec<K> {
+    let mut keys: Vec<K> = first.cloned().collect();
+    for key in second {
+        if !keys.contains(key) {
+            keys.push(key.clone());
+        }
+    }
+    keys
+}
+
+/// Put a resolved value in the document, replacing the placeholder
+fn place(document: &mut Value, collection: &str, id: Option<&str>, value: Option<Value>) {
+    let Some(map) = document.as_mapping_mut() else {
+        return;
+    };
+    let key = Value::String(collection.to_string());
+    let Some(id) = id else {
+        match value {
+            Some(value) => map.insert(key, value),
+            None => map.remove(&key),
+        };
+        return;
+    };
+    let Some(entries) = map.get_mut(&key).and_then(Value::as_sequence_mut) else {
+        return;
+    };
+    let position = entries
+        .iter()
+        .position(|entry| entry.get("id").and_then(Value::as_str) == Some(id));
+    match (position, value) {
+        (Some(index), Some(value)) => entries[index] = value,
+        (Some(index), None) => {
+            entries.remove(index);
+        }
+        (None, Some(value)) => entries.push(value),
+        (None, None) => {}
+    }
+}
+
+fn validate_as<T: DeserializeOwned + Validatable>(document: &Value, file: &str) -> RhemaResult<()> {
+    let typed: T =
+        serde_yaml::from_value(document.clone()).map_err(|e| RhemaError::InvalidYaml {
+            file: file.to_string(),
+            message: e.to_string(),
+        })?;
+    typed.validate()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = "todos:
+  - id: t1
+    title: Add rate limiting
+    status: pending
+    priority: medium
+    created_at: 2025-01-10T00:00:00Z
+  - id: t2
+    title: Remove legacy endpoint
+    status: pending
+    priority: low
+    created_at: 2025-01-10T00:00:00Z
+";
+
+    #[test]
+    fn test_entries_merge_cleanly_unless_both_sides_changed_them() {
+        let ours = BASE
+            .replace(
+                "status: pending\n    priority: medium",
+                "status: in_progress\n    priority: high",
+            )
+            .replace(
+                "title: Remove legacy endpoint",
+                "title: Remove the v1 endpoint",
+            );
+        let theirs = format!(
+            "{}  - id: t3\n    title: Document limits\n    status: pending\n    priority: low\n    created_at: 2025-01-11T00:00:00Z\n",
+            BASE.replace("priority: medium", "priority: critical")
+        );
+
+        let mut merge =
+            FileMerge::from_versions(".rhema/todos.yaml", Some(BASE), &ours, &theirs).unwrap();
+        assert_eq!(merge.conflicts.len(), 1);
+        let conflict = merge.conflicts[0].clone();
+        assert_eq!(conflict.label(), "todos/t1");
+        assert_eq!(conflict.conflicting_fields(), vec!["priority"]);
+
+        let merged = conflict.merge_fields(|_| Side::Theirs).unwrap();
+        assert_eq!(merged["status"], "in_progress");
+        assert_eq!(merged["priority"], "critical");
+
+        assert!(merge.write(Path::new("/nonexistent")).is_err());
+        let record = ResolutionRecord {
+            resolved_by: "dev@example.com".to_string(),
+            resolved_at: Utc::now(),
+            strategy: ResolutionStrategy::MergeFields,
+            rationale: Some("Release blocker after the incident".to_string()),
+        };
+        merge.resolve(0, Some(merged), record).unwrap();
+        assert!(merge.is_resolved());
+        merge.validate().unwrap();
+
+        let todos = merge.document()["todos"].as_sequence().unwrap();
+        let ids: Vec<&str> = todos.iter().map(|t| t["id"].as_str().unwrap()).collect();
+        assert_eq!(ids, vec!["t1", "t2", "t3"]);
+        assert_eq!(todos[1]["title"], "Remove the v1 endpoint");
+        assert_eq!(todos[0][RESOLUTION_FIELD]["strategy"], "merge_fields");
+
+        let mut broken = merge.clone();
+        place(
+            &mut broken.document,
+            "todos",
+            Some("t2"),
+            Some(Value::String("x".into())),
+        );
+        assert!(broken.validate().is_err());
+    }
+}
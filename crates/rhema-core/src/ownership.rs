@@ -0,0 +1,431 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Who maintains each scope's context.
+//!
+//! A maintainer's weight in a scope is the number of context file lines git
+//! blame attributes to them, plus [`REVIEWED_ENTRY_WEIGHT`] for each
+//! agent-written entry they approved. Agents and importers are counted
+//! separately, as they cannot keep the context up to date on their own. The
+//! bus factor is the smallest number of maintainers holding more than half
+//! of a scope's weight.
+
+use crate::importers::PROVENANCE_FIELD;
+use crate::review::{ReviewRecord, REVIEW_FIELD};
+use crate::scope::Scope;
+use crate::RhemaResult;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use git2::{BlameOptions, Repository, Sort};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+/// Weight of an approved entry, roughly the lines of a typical entry
+pub const REVIEWED_ENTRY_WEIGHT: usize = 8;
+
+/// Analyzer settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OwnershipConfig {
+    /// Context untouched for this long is stale, and maintainers without a
+    /// commit in this long no longer count as owners
+    pub stale_days: i64,
+}
+
+impl Default for OwnershipConfig {
+    fn default() -> Self {
+        Self { stale_days: 180 }
+    }
+}
+
+/// Someone maintaining a scope's context
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Maintainer {
+    pub name: String,
+    pub email: String,
+    /// Context file lines last changed by this maintainer
+    pub lines: usize,
+    /// Agent-written entries this maintainer approved
+    pub reviewed_entries: usize,
+    /// Share of the scope's weight (0.0-1.0)
+    pub share: f64,
+    pub last_change: Option<DateTime<Utc>>,
+    /// Committed anywhere in the repository within the stale window
+    pub active: bool,
+}
+
+impl Maintainer {
+    fn weight(&self) -> usize {
+        self.lines + self.reviewed_entries * REVIEWED_ENTRY_WEIGHT
+    }
+}
+
+/// Ownership of one scope's context
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScopeOwnership {
+    pub scope: String,
+    /// Scope directory relative to the repository root
+    pub path: String,
+    /// Sorted by share, largest first
+    pub maintainers: Vec<Maintainer>,
+    pub context_lines: usize,
+    /// Lines not yet committed
+    pub uncommitted_lines: usize,
+    /// Entries written by agents and not yet approved by anyone
+    pub agent_entries: usize,
+    /// Entries imported from other systems
+    pub imported_entries: usize,
+    pub bus_factor: usize,
+    pub last_change: Option<DateTime<Utc>>,
+    /// No context change within the stale window
+    pub stale: bool,
+    /// No maintainer has committed within the stale window
+    pub unowned: bool,
+}
+
+/// Ownership across the repository
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OwnershipReport {
+    pub generated_at: DateTime<Utc>,
+    pub stale_days: i64,
+    pub scopes: Vec<ScopeOwnership>,
+    /// Scopes a single maintainer holds the majority of
+    pub single_maintainer: Vec<String>,
+    /// Scopes that are both stale and unowned
+    pub unowned_stale: Vec<String>,
+}
+
+impl OwnershipReport {
+    /// Maintainers in order of their total weight across scopes, for the
+    /// columns of a heatmap
+    pub fn top_maintainers(&self, limit: usize) -> Vec<String> {
+        let mut totals: HashMap<&str, usize> = HashMap::new();
+        for scope in &self.scopes {
+            for maintainer in &scope.maintainers {
+                *totals.entry(&maintainer.email).or_default() += maintainer.weight();
+            }
+        }
+        let mut ranked: Vec<(&str, usize)> = totals.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        ranked
+            .into_iter()
+            .take(limit)
+            .map(|(email, _)| email.to_string())
+            .collect()
+    }
+}
+
+/// Analyze the ownership of every scope's context files
+pub fn analyze(
+    repo_root: &Path,
+    scopes: &[Scope],
+    config: &OwnershipConfig,
+) -> RhemaResult<OwnershipReport> {
+    let repo = Repository::discover(repo_root)?;
+    let workdir = repo.workdir().unwrap_or(repo_root).to_path_buf();
+    let workdir = workdir.canonicalize().unwrap_or(workdir);
+    let now = Utc::now();
+    let cutoff = now - Duration::days(config.stale_days);
+    let active = active_authors(&repo, cutoff);
+
+    let mut report = OwnershipReport {
+        generated_at: now,
+        stale_days: config.stale_days,
+        scopes: Vec::new(),
+        single_maintainer: Vec::new(),
+        unowned_stale: Vec::new(),
+    };
+
+    for scope in scopes {
+        let mut maintainers: BTreeMap<String, Maintainer> = BTreeMap::new();
+        let mut ownership = ScopeOwnership {
+            scope: scope.definition.name.clone(),
+            path: relative(&workdir, scope.path.parent().unwrap_or(&scope.path)),
+            maintainers: Vec::new(),
+            context_lines: 0,
+            uncommitted_lines: 0,
+            agent_entries: 0,
+            imported_entries: 0,
+            bus_factor: 0,
+            last_change: None,
+            stale: false,
+            unowned: false,
+        };
+
+        let mut files: Vec<&Path> = scope.files.values().map(|p| p.as_path()).collect();
+        files.sort();
+        for file in files {
+            let Ok(content) = std::fs::read_to_string(file) else {
+                continue;
+            };
+            let total = content.lines().count();
+            ownership.context_lines += total;
+
+            let blamed = blame(&repo, &workdir, file, &mut maintainers)?;
+            ownership.uncommitted_lines += total.saturating_sub(blamed);
+
+            if let Ok(data) = serde_yaml::from_str::<Value>(&content) {
+                credit_entries(&data, &mut maintainers, &mut ownership);
+            }
+        }
+
+        let total_weight: usize = maintainers.values().map(Maintainer::weight).sum();
+        let mut maintainers: Vec<Maintainer> = maintainers.into_values().collect();
+        for maintainer in &mut maintainers {
+            maintainer.share = if total_weight == 0 {
+                0.0
+            } else {
+                maintainer.weight() as f64 / total_weight as f64
+            };
+            maintainer.active = active.contains(&maintainer.email);
+        }
+        maintainers.sort_by(|a, b| {
+            b.weight()
+                .cmp(&a.weight())
+                .then_with(|| a.email.cmp(&b.email))
+        });
+
+        ownership.bus_factor = bus_factor(&maintainers, total_weight);
+        ownership.last_change = maintainers.iter().filter_map(|m| m.last_change).max();
+        ownership.stale = ownership.last_change.is_none_or(|at| at < cutoff);
+        ownership.unowned = !maintainers.iter().any(|m| m.active);
+        ownership.maintainers = maintainers;
+
+        if ownership.bus_factor == 1 {
+            report.single_maintainer.push(ownership.scope.clone());
+        }
+        if ownership.stale && ownership.unowned {
+            report.unowned_stale.push(ownership.scope.clone());
+        }
+        report.scopes.push(ownership);
+    }
+
+    Ok(report)
+}
+
+/// Attribute the committed lines of `file` to their authors, returning how
+/// many lines were attributed
+fn blame(
+    repo: &Repository,
+    workdir: &Path,
+    file: &Path,
+    maintainers: &mut BTreeMap<String, Maintainer>,
+) -> RhemaResult<usize> {
+    let file = file.canonicalize().unwrap_or_else(|_| file.to_path_buf());
+    let Ok(relative) = file.strip_prefix(workdir) else {
+        return Ok(0);
+    };
+    // Files never committed have no history to blame
+    let Ok(blame) = repo.blame_file(relative, Some(&mut BlameOptions::new())) else {
+        return Ok(0);
+    };
+
+    let mut attributed = 0;
+    for hunk in blame.iter() {
+        let signature = hunk.final_signature();
+        let email = signature.email().unwrap_or("unknown").to_lowercase();
+        let name = signature.name().unwrap_or(&email).to_string();
+        let when = Utc.timestamp_opt(signature.when().seconds(), 0).single();
+        let lines = hunk.lines_in_hunk();
+
+        let maintainer = maintainer(maintainers, &email, &name);
+        maintainer.lines += lines;
+        maintainer.last_change = maintainer.last_change.max(when);
+        attributed += lines;
+    }
+    Ok(attributed)
+}
+
+/// Credit approved agent entries to their reviewers and count entries no
+/// person has vouched for
+fn credit_entries(
+    data: &Value,
+    maintainers: &mut BTreeMap<String, Maintainer>,
+    ownership: &mut ScopeOwnership,
+) {
+    let Value::Mapping(mapping) = data else {
+        return;
+    };
+    for entries in mapping.values() {
+        let Value::Sequence(entries) = entries else {
+            continue;
+        };
+        for entry in entries {
+            if entry.get(PROVENANCE_FIELD).is_some() {
+                ownership.imported_entries += 1;
+                continue;
+            }
+            let Some(record) = entry
+                .get(REVIEW_FIELD)
+                .and_then(|v| serde_yaml::from_value::<ReviewRecord>(v.clone()).ok())
+            else {
+                continue;
+            };
+            match record.reviewed_by {
+                Some(reviewer) => {
+                    let email = reviewer.to_lowercase();
+                    let maintainer = maintainer(maintainers, &email, &reviewer);
+                    maintainer.reviewed_entries += 1;
+                    maintainer.last_change = maintainer.last_change.max(record.reviewed_at);
+                }
+                None => ownership.agent_entries += 1,
+            }
+        }
+    }
+}
+
+fn maintainer<'a>(
+    maintainers: &'a mut BTreeMap<String, Maintainer>,
+    email: &str,
+    name: &str,
+) -> &'a mut Maintainer {
+    maintainers
+        .entry(email.to_string())
+        .or_insert_with(|| Maintainer {
+            name: name.to_string(),
+            email: email.to_string(),
+            lines: 0,
+            reviewed_entries: 0,
+            share: 0.0,
+            last_change: None,
+            active: false,
+        })
+}
+
+/// Smallest number of maintainers, largest first, holding more than half of
+/// the weight
+fn bus_factor(maintainers: &[Maintainer], total_weight: usize) -> usize {
+    let mut held = 0;
+    for (index, maintainer) in maintainers.iter().enumerate() {
+        held += maintainer.weight();
+        if held * 2 > total_weight {
+            return index + 1;
+        }
+    }
+    0
+}
+
+/// Emails of everyone who authored a commit since `cutoff`
+fn active_authors(repo: &Repository, cutoff: DateTime<Utc>) -> HashSet<String> {
+    let mut authors = HashSet::new();
+    let Ok(mut revwalk) = repo.revwalk() else {
+        return authors;
+    };
+    if revwalk.push_head().is_err() || revwalk.set_sorting(Sort::TIME).is_err() {
+        return authors;
+    }
+    for oid in revwalk.flatten() {
+        let Ok(commit) = repo.find_commit(oid) else {
+            continue;
+        };
+        if commit.time().seconds() < cutoff.timestamp() {
+            break;
+        }
+        let author = commit.author();
+        if let Some(email) = author.email() {
+            authors.insert(email.to_lowercase());
+        }
+    }
+    authors
+}
+
+fn relative(root: &Path, path: &Path) -> String {
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    match path.strip_prefix(root) {
+        Ok(relative) if relative.as_os_str().is_empty() => ".".to_string(),
+        Ok(relative) => relative.display().to_string(),
+        Err(_) => path.display().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scope::discover_scopes;
+    use tempfile::TempDir;
+
+    fn commit(repo: &Repository, author: &str, message: &str) {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now(author, &format!("{}@example.com", author)).unwrap();
+        let parents: Vec<git2::Commit> = repo
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_commit().ok())
+            .into_iter()
+            .collect();
+        let parents: Vec<&git2::Commit> = parents.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_single_maintainer_and_shared_scopes() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        for (dir, name) in [("core", "core"), ("api", "api")] {
+            let rhema = root.join(dir).join(".rhema");
+            std::fs::create_dir_all(&rhema).unwrap();
+            std::fs::write(
+                rhema.join("rhema.yaml"),
+                format!("name: {}\nscope_type: library\nversion: 1.0.0\n", name),
+            )
+            .unwrap();
+        }
+        let repo = Repository::init(root).unwrap();
+        commit(&repo, "alice", "init");
+
+        std::fs::write(
+            root.join("api/.rhema/todos.yaml"),
+            "todos:\n  - id: t1\n    title: Retry uploads\n",
+        )
+        .unwrap();
+        commit(&repo, "bob", "api todos");
+        std::fs::write(
+            root.join("api/.rhema/knowledge.yaml"),
+            "entries:\n  - id: k1\n    title: Upload limits\n    review_state: pending\n    review:\n      submitted_by: agent-7\n      submitted_at: 2025-01-01T00:00:00Z\n",
+        )
+        .unwrap();
+
+        let scopes = discover_scopes(root).unwrap();
+        let report = analyze(root, &scopes, &OwnershipConfig::default()).unwrap();
+
+        let core = report.scopes.iter().find(|s| s.scope == "core").unwrap();
+        assert_eq!(core.bus_factor, 1);
+        assert_eq!(core.maintainers[0].email, "alice@example.com");
+        assert!(!core.stale && !core.unowned);
+
+        let api = report.scopes.iter().find(|s| s.scope == "api").unwrap();
+        assert_eq!(api.maintainers.len(), 2);
+        assert_eq!(api.bus_factor, 2);
+        assert_eq!(api.agent_entries, 1);
+        assert!(api.uncommitted_lines > 0);
+        assert_eq!(report.single_maintainer, vec!["core".to_string()]);
+        assert!(report.unowned_stale.is_empty());
+    }
+}
@@ -0,0 +1,487 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Organization policy bundles distributed through git.
+//!
+//! An organization keeps its required validation rules, constraints,
+//! workflow templates and security policies in a git repository whose
+//! `policy.yaml` uses the same sections as `.rhema/repository.yaml`. The
+//! bundle is named under `policy_bundle` in the global config, and each
+//! repository pins the commit it uses in `.rhema/policy.lock`.
+//!
+//! [`repository_config`] merges the pinned bundle below the repository's own
+//! config: repository values win, except that lists are combined, so a
+//! repository can add to the bundle's rules but not drop them.
+
+use crate::file_ops::{read_yaml_file, write_yaml_file};
+use crate::{RhemaError, RhemaResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Global config section naming the bundle
+pub const POLICY_BUNDLE_SECTION: &str = "policy_bundle";
+
+/// File inside the bundle holding the policy sections
+pub const BUNDLE_POLICY_FILE: &str = "policy.yaml";
+
+/// File under `.rhema` recording the pinned commit
+pub const POLICY_PIN_FILE: &str = "policy.lock";
+
+/// Refs mirrored from the bundle repository
+const FETCH_REFSPECS: [&str; 2] = ["+refs/heads/*:refs/heads/*", "+refs/tags/*:refs/tags/*"];
+
+fn default_reference() -> String {
+    "main".to_string()
+}
+
+/// Where the bundle lives, from the `policy_bundle` global config section
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyBundleSource {
+    /// Git URL or local path of the bundle repository
+    pub url: String,
+
+    /// Branch, tag or commit that `rhema policy update` moves the pin to
+    #[serde(rename = "ref", default = "default_reference")]
+    pub reference: String,
+
+    /// Directory inside the bundle holding `policy.yaml`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+impl PolicyBundleSource {
+    /// Source named in `~/.config/rhema/global.yaml`, if any
+    pub fn from_global_config() -> RhemaResult<Option<Self>> {
+        match dirs::config_dir() {
+            Some(dir) => Self::from_config_file(&dir.join("rhema").join("global.yaml")),
+            None => Ok(None),
+        }
+    }
+
+    /// Source named under `policy_bundle` in a config file, if any
+    pub fn from_config_file(path: &Path) -> RhemaResult<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let value: Value = read_yaml_file(path)?;
+        match value.get(POLICY_BUNDLE_SECTION) {
+            Some(section) => serde_yaml::from_value(section.clone())
+                .map(Some)
+                .map_err(|e| {
+                    RhemaError::ConfigError(format!(
+                        "Invalid {} section in {}: {}",
+                        POLICY_BUNDLE_SECTION,
+                        path.display(),
+                        e
+                    ))
+                }),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Bundle commit a repository uses
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyPin {
+    pub url: String,
+
+    #[serde(rename = "ref")]
+    pub reference: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    pub commit: String,
+    pub pinned_at: DateTime<Utc>,
+}
+
+/// Bundle commit between the old and new pin
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ChangelogEntry {
+    pub commit: String,
+    pub summary: String,
+    pub author: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SectionChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// Top-level policy section that differs between the old and new pin
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SectionChange {
+    pub section: String,
+    pub kind: SectionChangeKind,
+}
+
+/// Result of moving the pin
+#[derive(Debug, Clone, Serialize)]
+pub struct PolicyUpdate {
+    /// Previously pinned commit
+    pub previous: Option<String>,
+    pub pin: PolicyPin,
+
+    /// Bundle commits picked up by the update, newest first
+    pub commits: Vec<ChangelogEntry>,
+    pub sections: Vec<SectionChange>,
+}
+
+impl PolicyUpdate {
+    pub fn is_unchanged(&self) -> bool {
+        self.previous.as_deref() == Some(self.pin.commit.as_str())
+    }
+}
+
+/// Fetches, pins and reads the policy bundle of one repository
+pub struct PolicyManager {
+    repo_root: PathBuf,
+    cache_dir: PathBuf,
+}
+
+impl PolicyManager {
+    /// Bundles are mirrored under the user cache directory
+    pub fn new(repo_root: &Path) -> Self {
+        let cache_dir = dirs::cache_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("rhema")
+            .join("policy");
+        Self {
+            repo_root: repo_root.to_path_buf(),
+            cache_dir,
+        }
+    }
+
+    pub fn with_cache_dir(mut self, cache_dir: impl Into<PathBuf>) -> Self {
+        self.cache_dir = cache_dir.into();
+        self
+    }
+
+    fn pin_path(&self) -> PathBuf {
+        self.repo_root.join(".rhema").join(POLICY_PIN_FILE)
+    }
+
+    /// Commit pinned by this repository, if any
+    pub fn pin(&self) -> RhemaResult<Option<PolicyPin>> {
+        let path = self.pin_path();
+        if !path.exists() {
+            return Ok(None);
+        }
+        read_yaml_file(&path).map(Some)
+    }
+
+    /// Policy sections of the pinned commit, or null when nothing is pinned.
+    /// The bundle is fetched when the commit is not mirrored yet.
+    pub fn bundle_config(&self) -> RhemaResult<Value> {
+        let Some(pin) = self.pin()? else {
+            return Ok(Value::Null);
+        };
+        let mirror = self.mirror(&pin.url)?;
+        let oid = git2::Oid::from_str(&pin.commit)?;
+        if mirror.find_commit(oid).is_err() {
+            fetch(&mirror, &pin.url)?;
+        }
+        let commit = mirror.find_commit(oid).map_err(|_| {
+            RhemaError::NotFound(format!(
+                "Pinned policy commit {} not found in {}",
+                pin.commit, pin.url
+            ))
+        })?;
+        read_policy(&mirror, &commit, pin.path.as_deref())
+    }
+
+    /// Fetch the bundle and pin the commit `source.reference` points to
+    pub fn update(&self, source: &PolicyBundleSource) -> RhemaResult<PolicyUpdate> {
+        let mirror = self.mirror(&source.url)?;
+        fetch(&mirror, &source.url)?;
+        let head = mirror
+            .revparse_single(&source.reference)
+            .and_then(|object| object.peel_to_commit())
+            .map_err(|_| {
+                RhemaError::NotFound(format!(
+                    "Reference '{}' not found in {}",
+                    source.reference, source.url
+                ))
+            })?;
+        let new_policy = read_policy(&mirror, &head, source.path.as_deref())?;
+
+        let previous = self.pin()?;
+        let previous_commit = previous
+            .as_ref()
+            .and_then(|pin| git2::Oid::from_str(&pin.commit).ok())
+            .and_then(|oid| mirror.find_commit(oid).ok());
+
+        let mut revwalk = mirror.revwalk()?;
+        revwalk.push(head.id())?;
+        if let Some(old) = &previous_commit {
+            revwalk.hide(old.id())?;
+        }
+        let mut commits = Vec::new();
+        for oid in revwalk {
+            let commit = mirror.find_commit(oid?)?;
+            commits.push(ChangelogEntry {
+                commit: commit.id().to_string()[..8].to_string(),
+                summary: commit.summary().unwrap_or_default().to_string(),
+                author: commit.author().name().unwrap_or_default().to_string(),
+            });
+            // Without a common base only the new head is meaningful
+            if previous_commit.is_none() {
+                break;
+            }
+        }
+
+        let old_policy = match (&previous_commit, &previous) {
+            (Some(commit), Some(pin)) => read_policy(&mirror, commit, pin.path.as_deref())?,
+            _ => Value::Null,
+        };
+
+        let pin = PolicyPin {
+            url: source.url.clone(),
+            reference: source.reference.clone(),
+            path: source.path.clone(),
+            commit: head.id().to_string(),
+            pinned_at: Utc::now(),
+        };
+        let update = PolicyUpdate {
+            previous: previous.map(|pin| pin.commit),
+            pin,
+            commits,
+            sections: section_changes(&old_policy, &new_policy),
+        };
+        if !update.is_unchanged() {
+            write_yaml_file(&self.pin_path(), &update.pin)?;
+        }
+        Ok(update)
+    }
+
+    /// Bare mirror of `url` in the cache directory
+    fn mirror(&self, url: &str) -> RhemaResult<git2::Repository> {
+        let digest = format!("{:x}", Sha256::digest(url.as_bytes()));
+        let path = self.cache_dir.join(&digest[..16]);
+        if path.exists() {
+            Ok(git2::Repository::open_bare(&path)?)
+        } else {
+            std::fs::create_dir_all(&path)?;
+            Ok(git2::Repository::init_bare(&path)?)
+        }
+    }
+}
+
+fn fetch(mirror: &git2::Repository, url: &str) -> RhemaResult<()> {
+    let mut remote = mirror.remote_anonymous(url)?;
+    remote.fetch(&FETCH_REFSPECS, None, None).map_err(|e| {
+        RhemaError::NetworkError(format!("Failed to fetch policy bundle {}: {}", url, e))
+    })
+}
+
+fn read_policy(
+    repo: &git2::Repository,
+    commit: &git2::Commit,
+    dir: Option<&str>,
+) -> RhemaResult<Value> {
+    let path = match dir {
+        Some(dir) => Path::new(dir).join(BUNDLE_POLICY_FILE),
+        None => PathBuf::from(BUNDLE_POLICY_FILE),
+    };
+    let entry = commit.tree()?.get_path(&path).map_err(|_| {
+        RhemaError::NotFound(format!(
+            "Policy bundle commit {} has no {}",
+            commit.id(),
+            path.display()
+        ))
+    })?;
+    let blob = repo.find_blob(entry.id())?;
+    let content = String::from_utf8_lossy(blob.content());
+    serde_yaml::from_str(&content).map_err(|e| RhemaError::InvalidYaml {
+        file: format!("{}@{}", path.display(), commit.id()),
+        message: e.to_string(),
+    })
+}
+
+fn section_changes(old: &Value, new: &Value) -> Vec<SectionChange> {
+    let empty = serde_yaml::Mapping::new();
+    let old = old.as_mapping().unwrap_or(&empty);
+    let new = new.as_mapping().unwrap_or(&empty);
+    let name = |key: &Value| key.as_str().unwrap_or_default().to_string();
+
+    let mut changes = Vec::new();
+    for (key, value) in new {
+        let kind = match old.get(key) {
+            None => SectionChangeKind::Added,
+            Some(previous) if previous != value => SectionChangeKind::Changed,
+            Some(_) => continue,
+        };
+        changes.push(SectionChange {
+            section: name(key),
+            kind,
+        });
+    }
+    for key in old.keys().filter(|key| !new.contains_key(*key)) {
+        changes.push(SectionChange {
+            section: name(key),
+            kind: SectionChangeKind::Removed,
+        });
+    }
+    changes
+}
+
+/// Merge `config` over `bundle`: mappings merge key by key, lists keep the
+/// bundle's items followed by new ones, anything else is taken from `config`
+pub fn merge_below(bundle: Value, config: Value) -> Value {
+    match (bundle, config) {
+        (Value::Mapping(mut merged), Value::Mapping(overrides)) => {
+            for (key, value) in overrides {
+                let value = match merged.remove(&key) {
+                    Some(base) => merge_below(base, value),
+                    None => value,
+                };
+                merged.insert(key, value);
+            }
+            Value::Mapping(merged)
+        }
+        (Value::Sequence(mut merged), Value::Sequence(additions)) => {
+            for item in additions {
+                if !merged.contains(&item) {
+                    merged.push(item);
+                }
+            }
+            Value::Sequence(merged)
+        }
+        (bundle, Value::Null) => bundle,
+        (_, config) => config,
+    }
+}
+
+/// `.rhema/repository.yaml` merged over the pinned policy bundle, or null
+/// when neither exists
+pub fn repository_config(repo_root: &Path) -> RhemaResult<Value> {
+    let path = repo_root.join(".rhema").join("repository.yaml");
+    let config = if path.exists() {
+        read_yaml_file(&path)?
+    } else {
+        Value::Null
+    };
+    let bundle = PolicyManager::new(repo_root).bundle_config()?;
+    Ok(merge_below(bundle, config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn commit_policy(repo: &git2::Repository, content: &str, message: &str) {
+        let workdir = repo.workdir().unwrap();
+        std::fs::write(workdir.join(BUNDLE_POLICY_FILE), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(BUNDLE_POLICY_FILE)).unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = git2::Signature::now("Platform Team", "platform@example.com").unwrap();
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_merge_keeps_bundle_list_items() {
+        let bundle: Value = serde_yaml::from_str(
+            "dependency_guard:\n  mode: block\n  allowlist: [serde]\nsync:\n  timeout_secs: 10\n",
+        )
+        .unwrap();
+        let config: Value =
+            serde_yaml::from_str("dependency_guard:\n  mode: warn\n  allowlist: [tokio, serde]\n")
+                .unwrap();
+
+        let merged = merge_below(bundle, config);
+        assert_eq!(merged["dependency_guard"]["mode"].as_str(), Some("warn"));
+        let allowlist: Vec<_> = merged["dependency_guard"]["allowlist"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .map(|item| item.as_str().unwrap())
+            .collect();
+        assert_eq!(allowlist, ["serde", "tokio"]);
+        assert_eq!(merged["sync"]["timeout_secs"].as_u64(), Some(10));
+    }
+
+    #[test]
+    fn test_update_pins_commit_and_reports_changelog() {
+        let bundle_dir = TempDir::new().unwrap();
+        let bundle = git2::Repository::init(bundle_dir.path()).unwrap();
+        commit_policy(&bundle, "sync:\n  timeout_secs: 10\n", "Initial policy");
+
+        let repo = TempDir::new().unwrap();
+        std::fs::create_dir_all(repo.path().join(".rhema")).unwrap();
+        std::fs::write(
+            repo.path().join(".rhema/repository.yaml"),
+            "sync:\n  server: https://knowledge.example.com\n",
+        )
+        .unwrap();
+        let cache = TempDir::new().unwrap();
+        let manager = PolicyManager::new(repo.path()).with_cache_dir(cache.path());
+        let branch = bundle.head().unwrap().shorthand().unwrap().to_string();
+        let source = PolicyBundleSource {
+            url: bundle_dir.path().display().to_string(),
+            reference: branch,
+            path: None,
+        };
+
+        let first = manager.update(&source).unwrap();
+        assert_eq!(first.previous, None);
+        assert_eq!(first.commits.len(), 1);
+        assert_eq!(manager.pin().unwrap().unwrap().commit, first.pin.commit);
+
+        commit_policy(
+            &bundle,
+            "sync:\n  timeout_secs: 20\ndependency_guard:\n  mode: block\n",
+            "Block unreviewed dependencies",
+        );
+        let second = manager.update(&source).unwrap();
+        assert_eq!(second.previous.as_deref(), Some(first.pin.commit.as_str()));
+        assert_eq!(second.commits.len(), 1);
+        assert_eq!(second.commits[0].summary, "Block unreviewed dependencies");
+        assert!(second.sections.contains(&SectionChange {
+            section: "dependency_guard".to_string(),
+            kind: SectionChangeKind::Added,
+        }));
+        assert!(manager.update(&source).unwrap().is_unchanged());
+
+        let merged = merge_below(
+            manager.bundle_config().unwrap(),
+            read_yaml_file(&repo.path().join(".rhema/repository.yaml")).unwrap(),
+        );
+        assert_eq!(merged["sync"]["timeout_secs"].as_u64(), Some(20));
+        assert_eq!(
+            merged["sync"]["server"].as_str(),
+            Some("https://knowledge.example.com")
+        );
+        assert_eq!(merged["dependency_guard"]["mode"].as_str(), Some("block"));
+    }
+}
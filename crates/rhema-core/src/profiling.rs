@@ -0,0 +1,382 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Opt-in phase profiling for diagnosing slow commands.
+//!
+//! Library code marks major phases with [`phase`]. Nothing is recorded until a
+//! [`Profiler`] is installed for the process, so the guards cost a single
+//! atomic load otherwise. The recorded spans can be summarized per phase or
+//! written as a Chrome trace (`chrome://tracing`, Perfetto, speedscope).
+
+use crate::{RhemaError, RhemaResult};
+use serde::Serialize;
+use serde_json::json;
+use std::cell::RefCell;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Major phases of a command
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    ScopeDiscovery,
+    FileIo,
+    Parse,
+    Query,
+    Render,
+}
+
+impl Phase {
+    pub const ALL: [Phase; 5] = [
+        Phase::ScopeDiscovery,
+        Phase::FileIo,
+        Phase::Parse,
+        Phase::Query,
+        Phase::Render,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Phase::ScopeDiscovery => "scope_discovery",
+            Phase::FileIo => "file_io",
+            Phase::Parse => "parse",
+            Phase::Query => "query",
+            Phase::Render => "render",
+        }
+    }
+}
+
+/// One completed phase span
+#[derive(Debug, Clone)]
+struct SpanRecord {
+    phase: Phase,
+    thread: u64,
+    start: Duration,
+    duration: Duration,
+    /// Duration minus time spent in nested phases on the same thread
+    self_time: Duration,
+}
+
+static PROFILER: OnceLock<Arc<Profiler>> = OnceLock::new();
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static NEXT_THREAD_ID: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    static THREAD_ID: u64 = NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed);
+    /// Child time accumulated by each open phase on this thread
+    static OPEN_PHASES: RefCell<Vec<Duration>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Install the process-wide profiler. Only the first call has any effect.
+pub fn install(profiler: Arc<Profiler>) -> Arc<Profiler> {
+    let installed = PROFILER.get_or_init(|| profiler).clone();
+    ENABLED.store(true, Ordering::Release);
+    installed
+}
+
+/// The installed profiler, if profiling is enabled
+pub fn active() -> Option<&'static Arc<Profiler>> {
+    if ENABLED.load(Ordering::Acquire) {
+        PROFILER.get()
+    } else {
+        None
+    }
+}
+
+/// Start timing a phase; the span ends when the guard is dropped
+pub fn phase(phase: Phase) -> PhaseGuard {
+    let started = active().map(|_| {
+        OPEN_PHASES.with(|open| open.borrow_mut().push(Duration::ZERO));
+        Instant::now()
+    });
+    PhaseGuard { phase, started }
+}
+
+/// Guard returned by [`phase`]
+#[must_use = "the phase ends when the guard is dropped"]
+pub struct PhaseGuard {
+    phase: Phase,
+    started: Option<Instant>,
+}
+
+impl Drop for PhaseGuard {
+    fn drop(&mut self) {
+        let (Some(started), Some(profiler)) = (self.started, PROFILER.get()) else {
+            return;
+        };
+        let duration = started.elapsed();
+        let child_time = OPEN_PHASES.with(|open| {
+            let mut open = open.borrow_mut();
+            let child_time = open.pop().unwrap_or_default();
+            if let Some(parent) = open.last_mut() {
+                *parent += duration;
+            }
+            child_time
+        });
+        profiler.record(SpanRecord {
+            phase: self.phase,
+            thread: THREAD_ID.with(|id| *id),
+            start: started.saturating_duration_since(profiler.started),
+            duration,
+            self_time: duration.saturating_sub(child_time),
+        });
+    }
+}
+
+/// Collects phase spans and memory samples for one command run
+pub struct Profiler {
+    started: Instant,
+    spans: Mutex<Vec<SpanRecord>>,
+    memory_samples: Mutex<Vec<(Duration, u64)>>,
+    sampling: AtomicBool,
+    sampler: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            spans: Mutex::new(Vec::new()),
+            memory_samples: Mutex::new(Vec::new()),
+            sampling: AtomicBool::new(false),
+            sampler: Mutex::new(None),
+        }
+    }
+
+    fn record(&self, span: SpanRecord) {
+        if let Ok(mut spans) = self.spans.lock() {
+            spans.push(span);
+        }
+    }
+
+    /// Sample resident memory on a background thread until [`Profiler::finish`].
+    /// Does nothing on platforms where RSS cannot be read.
+    pub fn start_memory_sampler(self: &Arc<Self>, interval: Duration) {
+        if current_rss_bytes().is_none() || self.sampling.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let profiler = Arc::clone(self);
+        let handle = std::thread::spawn(move || {
+            while profiler.sampling.load(Ordering::Acquire) {
+                if let (Some(rss), Ok(mut samples)) =
+                    (current_rss_bytes(), profiler.memory_samples.lock())
+                {
+                    samples.push((profiler.started.elapsed(), rss));
+                }
+                std::thread::sleep(interval);
+            }
+        });
+        if let Ok(mut sampler) = self.sampler.lock() {
+            *sampler = Some(handle);
+        }
+    }
+
+    /// Stop sampling and summarize the run
+    pub fn finish(&self, command: &str) -> ProfileReport {
+        self.sampling.store(false, Ordering::Release);
+        if let Some(handle) = self.sampler.lock().ok().and_then(|mut s| s.take()) {
+            let _ = handle.join();
+        }
+        if let (Some(rss), Ok(mut samples)) = (current_rss_bytes(), self.memory_samples.lock()) {
+            samples.push((self.started.elapsed(), rss));
+        }
+
+        let spans = self.spans.lock().map(|s| s.clone()).unwrap_or_default();
+        let phases = Phase::ALL
+            .iter()
+            .filter_map(|phase| {
+                let matching: Vec<&SpanRecord> =
+                    spans.iter().filter(|s| s.phase == *phase).collect();
+                if matching.is_empty() {
+                    return None;
+                }
+                Some(PhaseSummary {
+                    phase: *phase,
+                    calls: matching.len(),
+                    total_ms: millis(matching.iter().map(|s| s.duration).sum()),
+                    self_ms: millis(matching.iter().map(|s| s.self_time).sum()),
+                })
+            })
+            .collect();
+
+        let sampled_peak = self
+            .memory_samples
+            .lock()
+            .ok()
+            .and_then(|samples| samples.iter().map(|(_, rss)| *rss).max());
+
+        ProfileReport {
+            command: command.to_string(),
+            total_ms: millis(self.started.elapsed()),
+            phases,
+            peak_memory_bytes: peak_rss_bytes().or(sampled_peak),
+        }
+    }
+
+    /// Write the spans and memory samples in Chrome trace event format
+    pub fn write_chrome_trace(&self, report: &ProfileReport, path: &Path) -> RhemaResult<()> {
+        let pid = std::process::id();
+        let mut events = vec![json!({
+            "name": report.command,
+            "cat": "command",
+            "ph": "X",
+            "ts": 0,
+            "dur": (report.total_ms * 1000.0) as u64,
+            "pid": pid,
+            "tid": 0,
+        })];
+
+        let spans = self.spans.lock().map(|s| s.clone()).unwrap_or_default();
+        events.extend(spans.iter().map(|span| {
+            json!({
+                "name": span.phase.name(),
+                "cat": "phase",
+                "ph": "X",
+                "ts": span.start.as_micros() as u64,
+                "dur": span.duration.as_micros() as u64,
+                "pid": pid,
+                "tid": span.thread,
+                "args": {"self_us": span.self_time.as_micros() as u64},
+            })
+        }));
+
+        let samples = self
+            .memory_samples
+            .lock()
+            .map(|s| s.clone())
+            .unwrap_or_default();
+        events.extend(samples.iter().map(|(at, rss)| {
+            json!({
+                "name": "memory",
+                "ph": "C",
+                "ts": at.as_micros() as u64,
+                "pid": pid,
+                "args": {"rss_bytes": rss},
+            })
+        }));
+
+        let trace = json!({
+            "traceEvents": events,
+            "displayTimeUnit": "ms",
+            "otherData": {"command": report.command},
+        });
+        let file = std::fs::File::create(path).map_err(RhemaError::IoError)?;
+        serde_json::to_writer(std::io::BufWriter::new(file), &trace)?;
+        Ok(())
+    }
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Time spent in one phase across all calls
+#[derive(Debug, Clone, Serialize)]
+pub struct PhaseSummary {
+    pub phase: Phase,
+    pub calls: usize,
+    /// Wall time including nested phases
+    pub total_ms: f64,
+    /// Wall time excluding nested phases on the same thread
+    pub self_ms: f64,
+}
+
+/// Summary of one profiled command
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileReport {
+    pub command: String,
+    pub total_ms: f64,
+    pub phases: Vec<PhaseSummary>,
+    pub peak_memory_bytes: Option<u64>,
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Read a `kB` field from `/proc/self/status`
+fn proc_status_bytes(field: &str) -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix(field))
+        .and_then(|rest| {
+            rest.trim()
+                .trim_end_matches("kB")
+                .trim()
+                .parse::<u64>()
+                .ok()
+        })
+        .map(|kb| kb * 1024)
+}
+
+/// Current resident set size, where the platform exposes it
+pub fn current_rss_bytes() -> Option<u64> {
+    proc_status_bytes("VmRSS:")
+}
+
+/// Peak resident set size recorded by the kernel, where available
+pub fn peak_rss_bytes() -> Option<u64> {
+    proc_status_bytes("VmHWM:")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phases_are_recorded_with_self_time() {
+        let profiler = install(Arc::new(Profiler::new()));
+        {
+            let _query = phase(Phase::Query);
+            let _io = phase(Phase::FileIo);
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        let report = profiler.finish("query");
+        let query = report
+            .phases
+            .iter()
+            .find(|p| p.phase == Phase::Query)
+            .unwrap();
+        let io = report
+            .phases
+            .iter()
+            .find(|p| p.phase == Phase::FileIo)
+            .unwrap();
+        assert!(io.total_ms >= 5.0);
+        assert!(query.total_ms >= io.total_ms);
+        assert!(query.self_ms < io.total_ms);
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("trace.json");
+        profiler.write_chrome_trace(&report, &path).unwrap();
+        let trace: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        let names: Vec<&str> = trace["traceEvents"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|e| e["name"].as_str())
+            .collect();
+        assert!(names.contains(&"query") && names.contains(&"file_io"));
+    }
+}
@@ -0,0 +1,809 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Scope README sections generated from context and kept in sync with it.
+//!
+//! Each generated section sits between stable markers recording the hash of
+//! what was written:
+//!
+//! ```markdown
+//! <!-- rhema:begin active-work hash=3f9a0c12e4b7 -->
+//! ## Active Work
+//! ...
+//! <!-- rhema:end active-work -->
+//! ```
+//!
+//! Text outside the markers is never touched. A section whose text no longer
+//! matches its recorded hash was edited by hand; it is reported as drifted
+//! and only regenerated when forced. Sections are written in the repository's
+//! documents locale, see [`crate::i18n`].
+
+use crate::i18n::Localizer;
+use crate::jobs::{Job, JobContext, JobHandler, JobQueue, JobSpec, JobStatus};
+use crate::policy::repository_config;
+use crate::review::reviewed_content;
+use crate::schema::{
+    Conventions, DecisionStatus, Decisions, Priority, RhemaScope, TodoStatus, Todos,
+};
+use crate::scope::{discover_scopes, Scope};
+use crate::{RhemaError, RhemaResult};
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Section of `.rhema/repository.yaml` configuring README sync
+pub const README_SYNC_CONFIG_SECTION: &str = "readme_sync";
+
+/// Job kind regenerating READMEs on the job queue
+pub const README_SYNC_JOB: &str = "readme.sync";
+
+const MARKER_PREFIX: &str = "<!-- rhema:";
+
+/// Generated README section
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReadmeSection {
+    Purpose,
+    KeyDecisions,
+    ActiveWork,
+    Conventions,
+}
+
+impl ReadmeSection {
+    pub const ALL: [ReadmeSection; 4] = [
+        ReadmeSection::Purpose,
+        ReadmeSection::KeyDecisions,
+        ReadmeSection::ActiveWork,
+        ReadmeSection::Conventions,
+    ];
+
+    /// Name used in the markers
+    pub fn marker(&self) -> &'static str {
+        match self {
+            ReadmeSection::Purpose => "purpose",
+            ReadmeSection::KeyDecisions => "key-decisions",
+            ReadmeSection::ActiveWork => "active-work",
+            ReadmeSection::Conventions => "conventions",
+        }
+    }
+
+    /// Message id of the section heading
+    pub fn heading_id(&self) -> &'static str {
+        match self {
+            ReadmeSection::Purpose => "readme.section.purpose",
+            ReadmeSection::KeyDecisions => "readme.section.key_decisions",
+            ReadmeSection::ActiveWork => "readme.section.active_work",
+            ReadmeSection::Conventions => "readme.section.conventions",
+        }
+    }
+
+    /// Message id of the text shown when the section has no entries
+    pub fn empty_id(&self) -> &'static str {
+        match self {
+            ReadmeSection::Purpose => "readme.empty.purpose",
+            ReadmeSection::KeyDecisions => "readme.empty.key_decisions",
+            ReadmeSection::ActiveWork => "readme.empty.active_work",
+            ReadmeSection::Conventions => "readme.empty.conventions",
+        }
+    }
+}
+
+impl fmt::Display for ReadmeSection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.marker())
+    }
+}
+
+/// README sync configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReadmeSyncConfig {
+    /// README file name inside each scope directory
+    pub file_name: String,
+
+    /// Sections to generate, in the order new sections are appended
+    pub sections: Vec<ReadmeSection>,
+
+    pub max_decisions: usize,
+
+    pub max_todos: usize,
+
+    /// Regenerate READMEs on the job queue
+    pub scheduled: bool,
+
+    /// Seconds between scheduled syncs
+    pub interval_secs: u64,
+}
+
+impl Default for ReadmeSyncConfig {
+    fn default() -> Self {
+        Self {
+            file_name: "README.md".to_string(),
+            sections: ReadmeSection::ALL.to_vec(),
+            max_decisions: 10,
+            max_todos: 10,
+            scheduled: false,
+            interval_secs: 900,
+        }
+    }
+}
+
+impl ReadmeSyncConfig {
+    /// Load the configuration from the repository config and policy bundle,
+    /// falling back to defaults
+    pub fn load(repo_root: &Path) -> RhemaResult<Self> {
+        let value = repository_config(repo_root)?;
+        match value.get(README_SYNC_CONFIG_SECTION) {
+            Some(section) => serde_yaml::from_value(section.clone()).map_err(|e| {
+                RhemaError::ConfigError(format!(
+                    "Invalid {} section in the repository config: {}",
+                    README_SYNC_CONFIG_SECTION, e
+                ))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+/// How a README section compares to the context
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SectionState {
+    UpToDate,
+    /// Context changed since the section was generated
+    Stale,
+    Missing,
+    /// Edited by hand since it was generated
+    Drifted,
+}
+
+impl fmt::Display for SectionState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SectionState::UpToDate => "up to date",
+            SectionState::Stale => "stale",
+            SectionState::Missing => "missing",
+            SectionState::Drifted => "drifted",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SectionStatus {
+    pub section: ReadmeSection,
+    pub state: SectionState,
+}
+
+/// Sync state of one scope's README
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadmeStatus {
+    pub scope: String,
+    pub path: PathBuf,
+
+    /// State of each section before syncing
+    pub sections: Vec<SectionStatus>,
+
+    /// Sections written by the sync
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub updated: Vec<ReadmeSection>,
+}
+
+impl ReadmeStatus {
+    pub fn drifted(&self) -> Vec<ReadmeSection> {
+        self.sections_in(SectionState::Drifted)
+    }
+
+    /// Whether any section is missing, stale or drifted
+    pub fn out_of_sync(&self) -> bool {
+        self.sections
+            .iter()
+            .any(|status| status.state != SectionState::UpToDate)
+    }
+
+    fn sections_in(&self, state: SectionState) -> Vec<ReadmeSection> {
+        self.sections
+            .iter()
+            .filter(|status| status.state == state)
+            .map(|status| status.section)
+            .collect()
+    }
+}
+
+/// Generated section found in a README
+struct Block {
+    /// Byte range of the whole block, markers included
+    start: usize,
+    end: usize,
+    body: String,
+    hash: Option<String>,
+}
+
+/// Generates scope README sections and keeps them in sync with context
+pub struct ReadmeSync {
+    repo_root: PathBuf,
+    config: ReadmeSyncConfig,
+    localizer: Localizer,
+}
+
+impl ReadmeSync {
+    /// Sync engine writing English sections
+    pub fn new(repo_root: impl Into<PathBuf>, config: ReadmeSyncConfig) -> Self {
+        Self {
+            repo_root: repo_root.into(),
+            config,
+            localizer: Localizer::default(),
+        }
+    }
+
+    /// Write sections in the language of `localizer`
+    pub fn with_localizer(mut self, localizer: Localizer) -> Self {
+        self.localizer = localizer;
+        self
+    }
+
+    /// Sync engine configured by the repository at `repo_root`
+    pub fn open(repo_root: &Path) -> RhemaResult<Self> {
+        Ok(Self::new(repo_root, ReadmeSyncConfig::load(repo_root)?)
+            .with_localizer(Localizer::for_documents(repo_root)?))
+    }
+
+    pub fn config(&self) -> &ReadmeSyncConfig {
+        &self.config
+    }
+
+    /// README of a scope, next to its `.rhema` directory
+    pub fn readme_path(&self, scope: &Scope) -> PathBuf {
+        let dir = if scope.path.file_name().is_some_and(|name| name == ".rhema") {
+            scope.path.parent().unwrap_or(&scope.path)
+        } else {
+            scope.path.as_path()
+        };
+        dir.join(&self.config.file_name)
+    }
+
+    /// Markdown of every configured section, from the scope's reviewed context
+    pub fn render(&self, scope: &Scope) -> RhemaResult<Vec<(ReadmeSection, String)>> {
+        self.config
+            .sections
+            .iter()
+            .map(|section| Ok((*section, self.render_section(scope, *section)?)))
+            .collect()
+    }
+
+    /// Compare a scope's README with its context without writing
+    pub fn status(&self, scope: &Scope) -> RhemaResult<ReadmeStatus> {
+        self.status_at(scope, self.readme_path(scope))
+    }
+
+    fn status_at(&self, scope: &Scope, path: PathBuf) -> RhemaResult<ReadmeStatus> {
+        let text = read_optional(&path)?;
+        let rendered = self.render(scope)?;
+        Ok(ReadmeStatus {
+            scope: scope.definition.name.clone(),
+            path,
+            sections: rendered
+                .iter()
+                .map(|(section, body)| SectionStatus {
+                    section: *section,
+                    state: section_state(text.as_deref(), *section, body),
+                })
+                .collect(),
+            updated: Vec::new(),
+        })
+    }
+
+    /// Regenerate missing and stale sections of a scope's README. Drifted
+    /// sections are kept unless `force` is set.
+    pub fn sync(&self, scope: &Scope, force: bool) -> RhemaResult<ReadmeStatus> {
+        self.sync_at(scope, self.readme_path(scope), force)
+    }
+
+    /// Like [`ReadmeSync::sync`], for a README at another path
+    pub fn sync_at(&self, scope: &Scope, path: PathBuf, force: bool) -> RhemaResult<ReadmeStatus> {
+        let mut status = self.status_at(scope, path)?;
+        let original = read_optional(&status.path)?;
+        let mut text = original
+            .clone()
+            .unwrap_or_else(|| format!("# {}\n", scope.definition.name));
+
+        for (section, body) in self.render(scope)? {
+            let state = status
+                .sections
+                .iter()
+                .find(|status| status.section == section)
+                .map(|status| status.state)
+                .unwrap_or(SectionState::Missing);
+            let block = render_block(section, &body);
+            match state {
+                SectionState::UpToDate => continue,
+                SectionState::Drifted if !force => continue,
+                SectionState::Missing => {
+                    if !text.ends_with("\n\n") {
+                        text.push_str(if text.ends_with('\n') { "\n" } else { "\n\n" });
+                    }
+                    text.push_str(&block);
+                    text.push('\n');
+                }
+                SectionState::Stale | SectionState::Drifted => {
+                    if let Some(existing) = find_block(&text, section) {
+                        text.replace_range(existing.start..existing.end, &block);
+                    }
+                }
+            }
+            status.updated.push(section);
+        }
+
+        if original.as_deref() != Some(text.as_str()) {
+            std::fs::write(&status.path, text)?;
+        }
+        Ok(status)
+    }
+
+    /// Status of every scope in the repository
+    pub fn status_all(&self) -> RhemaResult<Vec<ReadmeStatus>> {
+        self.scopes()?
+            .iter()
+            .map(|scope| self.status(scope))
+            .collect()
+    }
+
+    /// Sync every scope in the repository
+    pub fn sync_all(&self, force: bool) -> RhemaResult<Vec<ReadmeStatus>> {
+        self.scopes()?
+            .iter()
+            .map(|scope| self.sync(scope, force))
+            .collect()
+    }
+
+    fn scopes(&self) -> RhemaResult<Vec<Scope>> {
+        let mut scopes = discover_scopes(&self.repo_root)?;
+        scopes.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(scopes)
+    }
+
+    fn render_section(&self, scope: &Scope, section: ReadmeSection) -> RhemaResult<String> {
+        let l10n = &self.localizer;
+        let mut md = format!("## {}\n\n", l10n.text(section.heading_id()));
+        let lines = match section {
+            ReadmeSection::Purpose => purpose(l10n, &scope.definition),
+            ReadmeSection::KeyDecisions => {
+                let decisions: Option<Decisions> = load_context(scope, "decisions.yaml")?;
+                self.key_decisions(decisions)
+            }
+            ReadmeSection::ActiveWork => {
+                let todos: Option<Todos> = load_context(scope, "todos.yaml")?;
+                self.active_work(todos)
+            }
+            ReadmeSection::Conventions => {
+                let conventions: Option<Conventions> = load_context(scope, "conventions.yaml")?;
+                conventions
+                    .map(|conventions| conventions.conventions)
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|convention| {
+                        format!(
+                            "- **{}** ({}): {}",
+                            convention.name,
+                            label(&convention.enforcement),
+                            first_line(&convention.description)
+                        )
+                    })
+                    .collect()
+            }
+        };
+        if lines.is_empty() {
+            md.push_str(&format!("_{}_\n", l10n.text(section.empty_id())));
+        } else {
+            md.push_str(&lines.join("\n"));
+            md.push('\n');
+        }
+        Ok(md)
+    }
+
+    fn key_decisions(&self, decisions: Option<Decisions>) -> Vec<String> {
+        let mut decisions: Vec<_> = decisions
+            .map(|decisions| decisions.decisions)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|decision| {
+                matches!(
+                    decision.status,
+                    DecisionStatus::Approved | DecisionStatus::Implemented
+                )
+            })
+            .collect();
+        decisions.sort_by_key(|decision| std::cmp::Reverse(decision.decided_at));
+        let mut lines: Vec<String> = decisions
+            .iter()
+            .take(self.config.max_decisions)
+            .map(|decision| {
+                format!(
+                    "- **{}** (`{}`, {} {}): {}",
+                    decision.title,
+                    decision.id,
+                    label(&decision.status),
+                    decision.decided_at.format("%Y-%m-%d"),
+                    first_line(
+                        decision
+                            .rationale
+                            .as_deref()
+                            .unwrap_or(&decision.description)
+                    )
+                )
+            })
+            .collect();
+        more(
+            &self.localizer,
+            &mut lines,
+            decisions.len(),
+            self.config.max_decisions,
+        );
+        lines
+    }
+
+    fn active_work(&self, todos: Option<Todos>) -> Vec<String> {
+        let mut todos: Vec<_> = todos
+            .map(|todos| todos.todos)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|todo| {
+                matches!(
+                    todo.status,
+                    TodoStatus::Pending | TodoStatus::InProgress | TodoStatus::Blocked
+                )
+            })
+            .collect();
+        todos.sort_by(|a, b| {
+            priority_rank(&b.priority)
+                .cmp(&priority_rank(&a.priority))
+                .then(a.created_at.cmp(&b.created_at))
+        });
+        let mut lines: Vec<String> = todos
+            .iter()
+            .take(self.config.max_todos)
+            .map(|todo| {
+                format!(
+                    "- [ ] **{}** (`{}`, {}, {})",
+                    todo.title,
+                    todo.id,
+                    label(&todo.status).replace('_', " "),
+                    self.localizer.text_with(
+                        "readme.todo.priority",
+                        &[("priority", &label(&todo.priority))]
+                    )
+                )
+            })
+            .collect();
+        more(
+            &self.localizer,
+            &mut lines,
+            todos.len(),
+            self.config.max_todos,
+        );
+        lines
+    }
+}
+
+fn purpose(l10n: &Localizer, scope: &RhemaScope) -> Vec<String> {
+    let mut lines = vec![scope.description.clone().unwrap_or_else(|| {
+        l10n.text_with(
+            "readme.purpose.default",
+            &[("name", &scope.name), ("scope_type", &scope.scope_type)],
+        )
+    })];
+    lines.push(String::new());
+    lines.push(l10n.text_with(
+        "readme.purpose.summary",
+        &[
+            ("name", &scope.name),
+            ("scope_type", &scope.scope_type),
+            ("version", &scope.version),
+        ],
+    ));
+    let dependencies = scope.dependencies.as_deref().unwrap_or_default();
+    if !dependencies.is_empty() {
+        let paths: Vec<String> = dependencies
+            .iter()
+            .map(|dependency| format!("`{}`", dependency.path))
+            .collect();
+        lines.push(String::new());
+        lines.push(l10n.text_with("readme.purpose.depends_on", &[("paths", &paths.join(", "))]));
+    }
+    lines
+}
+
+fn load_context<T: DeserializeOwned>(scope: &Scope, file_name: &str) -> RhemaResult<Option<T>> {
+    let Some(path) = scope.files.get(file_name) else {
+        return Ok(None);
+    };
+    let content = std::fs::read_to_string(path)?;
+    serde_yaml::from_str(&reviewed_content(&content))
+        .map(Some)
+        .map_err(|e| RhemaError::InvalidYaml {
+            file: path.display().to_string(),
+            message: e.to_string(),
+        })
+}
+
+fn read_optional(path: &Path) -> RhemaResult<Option<String>> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => Ok(Some(text)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn section_state(text: Option<&str>, section: ReadmeSection, rendered: &str) -> SectionState {
+    let Some(block) = text.and_then(|text| find_block(text, section)) else {
+        return SectionState::Missing;
+    };
+    if block.hash.as_deref() != Some(hash(&block.body).as_str()) {
+        SectionState::Drifted
+    } else if block.body.trim() != rendered.trim() {
+        SectionState::Stale
+    } else {
+        SectionState::UpToDate
+    }
+}
+
+fn render_block(section: ReadmeSection, body: &str) -> String {
+    format!(
+        "{}begin {} hash={} -->\n{}\n{}end {} -->",
+        MARKER_PREFIX,
+        section.marker(),
+        hash(body),
+        body.trim(),
+        MARKER_PREFIX,
+        section.marker()
+    )
+}
+
+fn find_block(text: &str, section: ReadmeSection) -> Option<Block> {
+    let begin = format!("{}begin {}", MARKER_PREFIX, section.marker());
+    let end_marker = format!("{}end {} -->", MARKER_PREFIX, section.marker());
+
+    let start = text
+        .match_indices(&begin)
+        .map(|(i, _)| i)
+        .find(|&i| text[i + begin.len()..].starts_with([' ', '-']))?;
+    let begin_line_end = text[start..].find('\n').map(|i| start + i + 1)?;
+    let attributes = text[start + begin.len()..begin_line_end]
+        .trim()
+        .trim_end_matches("-->")
+        .trim();
+    let hash = attributes
+        .split_whitespace()
+        .find_map(|attribute| attribute.strip_prefix("hash="))
+        .map(str::to_string);
+
+    let body_end = text[begin_line_end..].find(&end_marker)? + begin_line_end;
+    Some(Block {
+        start,
+        end: body_end + end_marker.len(),
+        body: text[begin_line_end..body_end].to_string(),
+        hash,
+    })
+}
+
+fn hash(body: &str) -> String {
+    let digest = Sha256::digest(body.trim().as_bytes());
+    digest[..6].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn first_line(text: &str) -> &str {
+    text.trim().lines().next().unwrap_or_default()
+}
+
+/// Lowercase serde name of an enum value
+fn label<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn priority_rank(priority: &Priority) -> u8 {
+    match priority {
+        Priority::Low => 0,
+        Priority::Medium => 1,
+        Priority::High => 2,
+        Priority::Critical => 3,
+    }
+}
+
+fn more(l10n: &Localizer, lines: &mut Vec<String>, total: usize, shown: usize) {
+    if total > shown {
+        let count = total - shown;
+        lines.push(format!(
+            "- {}",
+            l10n.text_with("readme.more", &[("count", &count)])
+        ));
+    }
+}
+
+/// Regenerates stale README sections of every scope; runs as
+/// [`README_SYNC_JOB`] on the job queue
+pub struct ReadmeSyncJob {
+    repo_root: PathBuf,
+}
+
+impl ReadmeSyncJob {
+    pub fn new(repo_root: impl Into<PathBuf>) -> Self {
+        Self {
+            repo_root: repo_root.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl JobHandler for ReadmeSyncJob {
+    async fn run(&self, _job: &Job, _context: &JobContext) -> RhemaResult<serde_json::Value> {
+        let statuses = ReadmeSync::open(&self.repo_root)?.sync_all(false)?;
+        let updated: Vec<&ReadmeStatus> = statuses
+            .iter()
+            .filter(|status| !status.updated.is_empty())
+            .collect();
+        let drifted: Vec<&ReadmeStatus> = statuses
+            .iter()
+            .filter(|status| !status.drifted().is_empty())
+            .collect();
+        for status in &drifted {
+            tracing::warn!(
+                "README of scope {} has hand-edited generated sections: {:?}",
+                status.scope,
+                status.drifted()
+            );
+        }
+        Ok(serde_json::json!({
+            "updated": updated.iter().map(|status| &status.path).collect::<Vec<_>>(),
+            "drifted": drifted.iter().map(|status| &status.path).collect::<Vec<_>>(),
+        }))
+    }
+}
+
+/// Schedule README syncs on the job queue, replacing any sync already
+/// queued. Returns the first sync job, or `None` when scheduling is off
+pub fn schedule_readme_sync(
+    queue: &JobQueue,
+    config: &ReadmeSyncConfig,
+) -> RhemaResult<Option<Job>> {
+    for job in queue.list(Some(JobStatus::Queued))? {
+        if job.kind == README_SYNC_JOB {
+            queue.cancel(&job.id)?;
+        }
+    }
+    if !config.scheduled {
+        return Ok(None);
+    }
+    let job = queue.enqueue(
+        JobSpec::new(README_SYNC_JOB, serde_json::Value::Null)
+            .with_repeat_every(Duration::from_secs(config.interval_secs.max(60))),
+    )?;
+    Ok(Some(job))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const TODOS: &str = "todos:
+  - id: T-1
+    title: Add rate limiting
+    status: in_progress
+    priority: high
+    created_at: 2025-01-10T00:00:00Z
+  - id: T-2
+    title: Old cleanup
+    status: completed
+    priority: low
+    created_at: 2025-01-09T00:00:00Z
+";
+
+    #[test]
+    fn test_sync_regenerates_stale_sections_and_reports_drift() {
+        let temp = TempDir::new().unwrap();
+        let rhema = temp.path().join("api/.rhema");
+        std::fs::create_dir_all(&rhema).unwrap();
+        std::fs::write(
+            rhema.join("rhema.yaml"),
+            "name: api\nscope_type: service\nversion: 1.0.0\ndescription: Public HTTP API\n",
+        )
+        .unwrap();
+        std::fs::write(rhema.join("todos.yaml"), TODOS).unwrap();
+        let readme = temp.path().join("api/README.md");
+        std::fs::write(&readme, "# API\n\nHand-written intro.\n").unwrap();
+
+        let config = ReadmeSyncConfig {
+            sections: vec![ReadmeSection::Purpose, ReadmeSection::ActiveWork],
+            ..ReadmeSyncConfig::default()
+        };
+        let sync = ReadmeSync::new(temp.path(), config);
+        let scope = Scope::new(rhema.clone()).unwrap();
+
+        let status = sync.sync(&scope, false).unwrap();
+        assert_eq!(
+            status.updated,
+            vec![ReadmeSection::Purpose, ReadmeSection::ActiveWork]
+        );
+        let text = std::fs::read_to_string(&readme).unwrap();
+        assert!(text.starts_with("# API\n\nHand-written intro.\n\n<!-- rhema:begin purpose hash="));
+        assert!(text.contains("- [ ] **Add rate limiting** (`T-1`, in progress, high priority)"));
+        assert!(!text.contains("Old cleanup"));
+        assert!(!sync.status(&scope).unwrap().out_of_sync());
+
+        // Context changes make the section stale, hand edits make it drift
+        std::fs::write(
+            rhema.join("todos.yaml"),
+            TODOS.replace("in_progress", "blocked"),
+        )
+        .unwrap();
+        std::fs::write(
+            &readme,
+            text.replace("Public HTTP API", "Public API, edited"),
+        )
+        .unwrap();
+        let scope = Scope::new(rhema.clone()).unwrap();
+        let status = sync.sync(&scope, false).unwrap();
+        assert_eq!(status.drifted(), vec![ReadmeSection::Purpose]);
+        assert_eq!(status.updated, vec![ReadmeSection::ActiveWork]);
+        let text = std::fs::read_to_string(&readme).unwrap();
+        assert!(text.contains("Public API, edited"));
+        assert!(text.contains("(`T-1`, blocked, high priority)"));
+
+        sync.sync(&scope, true).unwrap();
+        let text = std::fs::read_to_string(&readme).unwrap();
+        assert!(!text.contains("edited"));
+        assert!(text.contains("Hand-written intro."));
+        assert!(!sync.status(&scope).unwrap().out_of_sync());
+    }
+
+    #[test]
+    fn test_sections_follow_the_documents_locale() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join(".rhema")).unwrap();
+        std::fs::write(
+            temp.path().join(".rhema/repository.yaml"),
+            "i18n:\n  locale: de\n  docs_locale: ja\nreadme_sync:\n  sections: [purpose, active-work, conventions]\n",
+        )
+        .unwrap();
+        let rhema = temp.path().join("api/.rhema");
+        std::fs::create_dir_all(&rhema).unwrap();
+        std::fs::write(
+            rhema.join("rhema.yaml"),
+            "name: api\nscope_type: service\nversion: 1.0.0\n",
+        )
+        .unwrap();
+        std::fs::write(rhema.join("todos.yaml"), TODOS).unwrap();
+
+        let sync = ReadmeSync::open(temp.path()).unwrap();
+        let scope = Scope::new(rhema).unwrap();
+        let rendered: Vec<String> = sync
+            .render(&scope)
+            .unwrap()
+            .into_iter()
+            .map(|(_, md)| md)
+            .collect();
+        assert!(rendered[0].starts_with("## 目的\n\n`api` service スコープ。\n"));
+        assert!(rendered[1].contains("(`T-1`, in progress, 優先度 high)"));
+        assert_eq!(rendered[2], "## 規約\n\n_規約は記録されていません。_\n");
+    }
+}
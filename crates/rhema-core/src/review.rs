@@ -0,0 +1,600 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Review workflow for context entries written by agents.
+//!
+//! When [`AGENT_ENV`] is set, entries added through [`crate::file_ops`] are
+//! stored with `review_state: pending` and a [`ReviewRecord`] naming the
+//! agent. Pending and rejected entries stay in their files but are hidden
+//! from queries and context injection until a reviewer approves them through
+//! a [`ReviewQueue`], which records the reviewer in the entry's `review`
+//! field.
+
+use crate::file_ops::{read_yaml_file, write_yaml_file};
+use crate::scope::discover_scopes;
+use crate::{RhemaError, RhemaResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming the agent on whose behalf entries are written
+pub const AGENT_ENV: &str = "RHEMA_AGENT_ID";
+
+/// Entry field holding the [`ReviewState`]
+pub const REVIEW_STATE_FIELD: &str = "review_state";
+
+/// Entry field holding the [`ReviewRecord`]
+pub const REVIEW_FIELD: &str = "review";
+
+/// Context files whose entries can await review
+pub(crate) const REVIEWED_FILES: [&str; 5] = [
+    "todos.yaml",
+    "knowledge.yaml",
+    "decisions.yaml",
+    "patterns.yaml",
+    "conventions.yaml",
+];
+
+/// Where an entry stands in the review workflow
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReviewState {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl fmt::Display for ReviewState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ReviewState::Pending => "pending",
+            ReviewState::Approved => "approved",
+            ReviewState::Rejected => "rejected",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Who wrote an entry and who reviewed it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReviewRecord {
+    pub submitted_by: String,
+    pub submitted_at: DateTime<Utc>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reviewed_by: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reviewed_at: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+
+    /// Whether the reviewer changed the entry before approving it
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub edited: bool,
+}
+
+/// Custom fields marking a new entry as submitted by `agent`
+pub fn pending_fields(agent: &str) -> HashMap<String, Value> {
+    let record = ReviewRecord {
+        submitted_by: agent.to_string(),
+        submitted_at: Utc::now(),
+        reviewed_by: None,
+        reviewed_at: None,
+        note: None,
+        edited: false,
+    };
+    let mut fields = HashMap::new();
+    if let (Ok(state), Ok(record)) = (
+        serde_yaml::to_value(ReviewState::Pending),
+        serde_yaml::to_value(&record),
+    ) {
+        fields.insert(REVIEW_STATE_FIELD.to_string(), state);
+        fields.insert(REVIEW_FIELD.to_string(), record);
+    }
+    fields
+}
+
+/// Custom fields for a new entry: pending when [`AGENT_ENV`] is set, none otherwise
+pub fn submission_fields() -> HashMap<String, Value> {
+    match std::env::var(AGENT_ENV) {
+        Ok(agent) if !agent.trim().is_empty() => pending_fields(agent.trim()),
+        _ => HashMap::new(),
+    }
+}
+
+/// Review state of an entry, `None` for entries that never needed review
+pub fn entry_state(entry: &Value) -> Option<ReviewState> {
+    entry
+        .get(REVIEW_STATE_FIELD)
+        .and_then(|state| serde_yaml::from_value(state.clone()).ok())
+}
+
+/// Whether an entry may be shown by default
+pub fn is_visible(entry: &Value) -> bool {
+    matches!(entry_state(entry), None | Some(ReviewState::Approved))
+}
+
+/// [`is_visible`] for the custom fields of a typed entry
+pub fn is_visible_custom(custom: &HashMap<String, Value>) -> bool {
+    let state = custom
+        .get(REVIEW_STATE_FIELD)
+        .and_then(|state| serde_yaml::from_value(state.clone()).ok());
+    matches!(state, None | Some(ReviewState::Approved))
+}
+
+/// Drop pending and rejected entries from a parsed context file (or one of
+/// its entry lists), returning how many were hidden
+pub fn hide_unreviewed(data: &mut Value) -> usize {
+    match data {
+        Value::Sequence(entries) => {
+            let before = entries.len();
+            entries.retain(is_visible);
+            before - entries.len()
+        }
+        Value::Mapping(map) => map
+            .iter_mut()
+            .filter(|(_, value)| value.is_sequence())
+            .map(|(_, value)| hide_unreviewed(value))
+            .sum(),
+        _ => 0,
+    }
+}
+
+/// Context file content with unreviewed entries removed; content without any
+/// is returned unchanged
+pub fn reviewed_content(content: &str) -> String {
+    if !content.contains(REVIEW_STATE_FIELD) {
+        return content.to_string();
+    }
+    let Ok(mut data) = serde_yaml::from_str::<Value>(content) else {
+        return content.to_string();
+    };
+    if hide_unreviewed(&mut data) == 0 {
+        return content.to_string();
+    }
+    serde_yaml::to_string(&data).unwrap_or_else(|_| content.to_string())
+}
+
+/// Identity recorded for reviews made from this checkout: the git user,
+/// falling back to the login name
+pub fn local_reviewer(repo_root: &Path) -> String {
+    let config = git2::Repository::open(repo_root)
+        .and_then(|repo| repo.config())
+        .or_else(|_| git2::Config::open_default());
+    if let Ok(config) = config {
+        for key in ["user.email", "user.name"] {
+            if let Ok(value) = config.get_string(key) {
+                if !value.trim().is_empty() {
+                    return value;
+                }
+            }
+        }
+    }
+    std::env::var("USER").unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// One changed field between two versions of an entry
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<Value>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Value>,
+}
+
+/// Field-level diff of an entry, ignoring the review fields. A missing
+/// `before` shows every field as added.
+pub fn diff_entries(before: Option<&Value>, after: &Value) -> Vec<FieldChange> {
+    let empty = Mapping::new();
+    let old = before.and_then(Value::as_mapping).unwrap_or(&empty);
+    let new = after.as_mapping().unwrap_or(&empty);
+    let is_review_field =
+        |key: &Value| matches!(key.as_str(), Some(REVIEW_STATE_FIELD) | Some(REVIEW_FIELD));
+
+    let mut changes = Vec::new();
+    for (key, value) in new.iter().filter(|(key, _)| !is_review_field(key)) {
+        if old.get(key) != Some(value) {
+            changes.push(FieldChange {
+                field: field_name(key),
+                before: old.get(key).cloned(),
+                after: Some(value.clone()),
+            });
+        }
+    }
+    for (key, value) in old.iter().filter(|(key, _)| !is_review_field(key)) {
+        if !new.contains_key(key) {
+            changes.push(FieldChange {
+                field: field_name(key),
+                before: Some(value.clone()),
+                after: None,
+            });
+        }
+    }
+    changes
+}
+
+fn field_name(key: &Value) -> String {
+    match key.as_str() {
+        Some(name) => name.to_string(),
+        None => serde_yaml::to_string(key)
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+    }
+}
+
+/// An entry awaiting (or refused) review
+#[derive(Debug, Clone, Serialize)]
+pub struct ReviewItem {
+    pub id: String,
+
+    /// Scope directory relative to the repository root
+    pub scope: String,
+    pub file: String,
+    pub state: ReviewState,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub review: Option<ReviewRecord>,
+    pub entry: Value,
+}
+
+impl ReviewItem {
+    /// Changes the entry would make once approved
+    pub fn changes(&self) -> Vec<FieldChange> {
+        diff_entries(None, &self.entry)
+    }
+}
+
+/// Reviews entries across every scope of a repository
+pub struct ReviewQueue {
+    repo_root: PathBuf,
+}
+
+/// Position of an entry inside a context file
+struct Located {
+    path: PathBuf,
+    scope: String,
+    file: String,
+    document: Value,
+    collection: Value,
+    index: usize,
+}
+
+impl ReviewQueue {
+    pub fn new(repo_root: &Path) -> Self {
+        Self {
+            repo_root: repo_root.to_path_buf(),
+        }
+    }
+
+    /// Entries in `state`, ordered by scope, file and position
+    pub fn list(&self, state: ReviewState) -> RhemaResult<Vec<ReviewItem>> {
+        let mut items = Vec::new();
+        for (path, scope, file) in self.context_files()? {
+            let document: Value = read_yaml_file(&path)?;
+            for entries in entry_lists(&document) {
+                for entry in entries {
+                    if entry_state(entry) == Some(state) {
+                        items.push(review_item(&scope, &file, entry)?);
+                    }
+                }
+            }
+        }
+        Ok(items)
+    }
+
+    /// An entry that went through review, in any state
+    pub fn get(&self, id: &str) -> RhemaResult<ReviewItem> {
+        let located = self.locate(id)?;
+        review_item(&located.scope, &located.file, located.entry())
+    }
+
+    /// Make an entry visible, recording `reviewer`
+    pub fn approve(
+        &self,
+        id: &str,
+        reviewer: &str,
+        note: Option<String>,
+    ) -> RhemaResult<ReviewItem> {
+        self.decide(id, reviewer, note, ReviewState::Approved, None)
+            .map(|(item, _)| item)
+    }
+
+    /// Keep an entry hidden, recording `reviewer`
+    pub fn reject(
+        &self,
+        id: &str,
+        reviewer: &str,
+        note: Option<String>,
+    ) -> RhemaResult<ReviewItem> {
+        self.decide(id, reviewer, note, ReviewState::Rejected, None)
+            .map(|(item, _)| item)
+    }
+
+    /// Apply `changes` to an entry and approve it, returning the fields the
+    /// reviewer changed
+    pub fn edit(
+        &self,
+        id: &str,
+        reviewer: &str,
+        changes: Mapping,
+        note: Option<String>,
+    ) -> RhemaResult<(ReviewItem, Vec<FieldChange>)> {
+        for key in changes.keys() {
+            if matches!(
+                key.as_str(),
+                Some("id") | Some(REVIEW_STATE_FIELD) | Some(REVIEW_FIELD)
+            ) {
+                return Err(RhemaError::InvalidInput(format!(
+                    "Field '{}' cannot be edited during review",
+                    field_name(key)
+                )));
+            }
+        }
+        self.decide(id, reviewer, note, ReviewState::Approved, Some(changes))
+    }
+
+    fn decide(
+        &self,
+        id: &str,
+        reviewer: &str,
+        note: Option<String>,
+        state: ReviewState,
+        changes: Option<Mapping>,
+    ) -> RhemaResult<(ReviewItem, Vec<FieldChange>)> {
+        let mut located = self.locate(id)?;
+        let current = located.entry().clone();
+        if entry_state(&current) == Some(ReviewState::Approved) {
+            return Err(RhemaError::InvalidInput(format!(
+                "Entry '{}' is already approved",
+                id
+            )));
+        }
+
+        let mut record: ReviewRecord = match current.get(REVIEW_FIELD) {
+            Some(record) => {
+                serde_yaml::from_value(record.clone()).map_err(|e| RhemaError::InvalidYaml {
+                    file: located.path.display().to_string(),
+                    message: format!("Invalid review record of '{}': {}", id, e),
+                })?
+            }
+            None => ReviewRecord {
+                submitted_by: "unknown".to_string(),
+                submitted_at: Utc::now(),
+                reviewed_by: None,
+                reviewed_at: None,
+                note: None,
+                edited: false,
+            },
+        };
+        record.reviewed_by = Some(reviewer.to_string());
+        record.reviewed_at = Some(Utc::now());
+        record.note = note;
+
+        let mut updated = current.clone();
+        let Some(fields) = updated.as_mapping_mut() else {
+            return Err(RhemaError::InvalidInput(format!(
+                "Entry '{}' is not a mapping",
+                id
+            )));
+        };
+        if let Some(changes) = changes {
+            record.edited = !changes.is_empty();
+            for (key, value) in changes {
+                fields.insert(key, value);
+            }
+        }
+        fields.insert(
+            Value::String(REVIEW_STATE_FIELD.to_string()),
+            serde_yaml::to_value(state)?,
+        );
+        fields.insert(
+            Value::String(REVIEW_FIELD.to_string()),
+            serde_yaml::to_value(&record)?,
+        );
+
+        let edits = diff_entries(Some(&current), &updated);
+        located.replace(updated);
+        write_yaml_file(&located.path, &located.document)?;
+        let item = review_item(&located.scope, &located.file, located.entry())?;
+        Ok((item, edits))
+    }
+
+    fn locate(&self, id: &str) -> RhemaResult<Located> {
+        for (path, scope, file) in self.context_files()? {
+            let document: Value = read_yaml_file(&path)?;
+            let Some(map) = document.as_mapping() else {
+                continue;
+            };
+            for (collection, entries) in map {
+                let Some(entries) = entries.as_sequence() else {
+                    continue;
+                };
+                let found = entries.iter().position(|entry| {
+                    entry.get("id").and_then(Value::as_str) == Some(id)
+                        && entry_state(entry).is_some()
+                });
+                if let Some(index) = found {
+                    return Ok(Located {
+                        collection: collection.clone(),
+                        path,
+                        scope,
+                        file,
+                        document,
+                        index,
+                    });
+                }
+            }
+        }
+        Err(RhemaError::NotFound(format!(
+            "No reviewable entry with id '{}'",
+            id
+        )))
+    }
+
+    /// Reviewable context files as (path, scope, file name)
+    fn context_files(&self) -> RhemaResult<Vec<(PathBuf, String, String)>> {
+        let mut scopes = discover_scopes(&self.repo_root)?;
+        scopes.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let mut files = Vec::new();
+        for scope in &scopes {
+            let relative = scope.relative_path(&self.repo_root)?;
+            for name in REVIEWED_FILES {
+                let path = scope.path.join(name);
+                if path.exists() {
+                    files.push((path, relative.clone(), name.to_string()));
+                }
+            }
+        }
+        Ok(files)
+    }
+}
+
+impl Located {
+    fn entry(&self) -> &Value {
+        &self.document[&self.collection][self.index]
+    }
+
+    fn replace(&mut self, entry: Value) {
+        if let Some(entries) = self
+            .document
+            .get_mut(&self.collection)
+            .and_then(Value::as_sequence_mut)
+        {
+            entries[self.index] = entry;
+        }
+    }
+}
+
+fn entry_lists(document: &Value) -> impl Iterator<Item = &Vec<Value>> {
+    document
+        .as_mapping()
+        .into_iter()
+        .flat_map(|map| map.values())
+        .filter_map(Value::as_sequence)
+}
+
+fn review_item(scope: &str, file: &str, entry: &Value) -> RhemaResult<ReviewItem> {
+    let state = entry_state(entry).ok_or_else(|| {
+        RhemaError::InvalidInput(format!("Entry in {}/{} has no review state", scope, file))
+    })?;
+    Ok(ReviewItem {
+        id: entry
+            .get("id")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        scope: scope.to_string(),
+        file: file.to_string(),
+        state,
+        review: entry
+            .get(REVIEW_FIELD)
+            .and_then(|record| serde_yaml::from_value(record.clone()).ok()),
+        entry: entry.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_ops::add_todo;
+    use crate::schema::{Priority, Todos};
+    use tempfile::TempDir;
+
+    fn repo_with_pending_todo() -> (TempDir, String) {
+        let temp = TempDir::new().unwrap();
+        let scope = temp.path().join(".rhema");
+        std::fs::create_dir_all(&scope).unwrap();
+        std::fs::write(
+            scope.join("rhema.yaml"),
+            "name: app\nscope_type: service\nversion: 1.0.0\n",
+        )
+        .unwrap();
+
+        add_todo(
+            &scope,
+            "Human todo".into(),
+            None,
+            Priority::Low,
+            None,
+            None,
+            Default::default(),
+        )
+        .unwrap();
+        let path = scope.join("todos.yaml");
+        let mut todos: Todos = read_yaml_file(&path).unwrap();
+        let mut agent_todo = todos.todos[0].clone();
+        agent_todo.id = uuid::Uuid::new_v4().to_string();
+        agent_todo.title = "Agent todo".into();
+        agent_todo.custom = pending_fields("agent:planner");
+        let id = agent_todo.id.clone();
+        todos.todos.push(agent_todo);
+        write_yaml_file(&path, &todos).unwrap();
+        (temp, id)
+    }
+
+    #[test]
+    fn test_unreviewed_entries_are_hidden() {
+        let (temp, _) = repo_with_pending_todo();
+        let content = std::fs::read_to_string(temp.path().join(".rhema/todos.yaml")).unwrap();
+
+        let mut data: Value = serde_yaml::from_str(&content).unwrap();
+        assert_eq!(hide_unreviewed(&mut data), 1);
+        assert_eq!(data["todos"].as_sequence().unwrap().len(), 1);
+        assert!(!reviewed_content(&content).contains("Agent todo"));
+        assert_eq!(reviewed_content("todos: []\n"), "todos: []\n");
+    }
+
+    #[test]
+    fn test_approve_records_reviewer_and_edits() {
+        let (temp, id) = repo_with_pending_todo();
+        let queue = ReviewQueue::new(temp.path());
+
+        let pending = queue.list(ReviewState::Pending).unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id, id);
+        assert!(pending[0]
+            .changes()
+            .iter()
+            .any(|change| change.field == "title"));
+
+        let mut changes = Mapping::new();
+        changes.insert("title".into(), "Reviewed todo".into());
+        let (item, edits) = queue
+            .edit(&id, "alice@example.com", changes, Some("Clarified".into()))
+            .unwrap();
+        assert_eq!(item.state, ReviewState::Approved);
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].field, "title");
+
+        let review = item.review.unwrap();
+        assert_eq!(review.submitted_by, "agent:planner");
+        assert_eq!(review.reviewed_by.as_deref(), Some("alice@example.com"));
+        assert!(review.edited);
+
+        assert!(queue.list(ReviewState::Pending).unwrap().is_empty());
+        assert!(queue.approve(&id, "bob", None).is_err());
+    }
+}
@@ -0,0 +1,325 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Repositories nested inside the main repository.
+//!
+//! A directory with its own `.git` (a directory for nested clones, a file
+//! for submodules) is a separate repository root. Scope discovery stops at
+//! these boundaries unless the `repository_roots` section of the repository
+//! config turns descending on, optionally limited to some roots. Every path
+//! under the main repository resolves to the innermost root containing it,
+//! which is what git operations and query provenance are attributed to.
+
+use crate::policy;
+use crate::{RhemaError, RhemaResult};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Repository config section holding [`RepositoryRootsConfig`]
+pub const REPOSITORY_ROOTS_SECTION: &str = "repository_roots";
+
+/// Relative path reported for the main repository
+pub const PRIMARY_ROOT: &str = ".";
+
+/// Which nested repositories take part in discovery
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RepositoryRootsConfig {
+    /// Discover scopes inside submodules and nested repositories
+    pub descend: bool,
+
+    /// Only descend into these roots, relative to the main repository;
+    /// empty means every nested root
+    pub include: Vec<String>,
+
+    /// Never descend into these roots
+    pub exclude: Vec<String>,
+}
+
+impl RepositoryRootsConfig {
+    /// Load the configuration from the repository config and policy bundle,
+    /// falling back to defaults
+    pub fn load(repo_root: &Path) -> RhemaResult<Self> {
+        let value = policy::repository_config(repo_root)?;
+        match value.get(REPOSITORY_ROOTS_SECTION) {
+            Some(section) => serde_yaml::from_value(section.clone()).map_err(|e| {
+                RhemaError::ConfigError(format!(
+                    "Invalid {} section in the repository config: {}",
+                    REPOSITORY_ROOTS_SECTION, e
+                ))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Whether discovery should enter the nested root at `relative`
+    pub fn descends_into(&self, relative: &str) -> bool {
+        self.descend
+            && (self.include.is_empty() || self.include.iter().any(|r| same_root(r, relative)))
+            && !self.exclude.iter().any(|r| same_root(r, relative))
+    }
+
+    /// Whether a walk of `repo_root` should enter the directory `dir`
+    pub fn enters(&self, repo_root: &Path, dir: &Path) -> bool {
+        dir == repo_root
+            || !is_repository_boundary(dir)
+            || self.descends_into(&relative_to(repo_root, dir))
+    }
+}
+
+/// How a root relates to the main repository
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RootKind {
+    Primary,
+    /// Checked out by git as a submodule, with a `.git` file pointing into
+    /// the parent's git directory
+    Submodule,
+    /// A clone that git itself does not know about
+    Nested,
+}
+
+impl fmt::Display for RootKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RootKind::Primary => write!(f, "primary"),
+            RootKind::Submodule => write!(f, "submodule"),
+            RootKind::Nested => write!(f, "nested"),
+        }
+    }
+}
+
+/// One git repository under the main repository
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepositoryRoot {
+    /// Working directory of the repository
+    pub path: PathBuf,
+
+    /// Path relative to the main repository, [`PRIMARY_ROOT`] for itself
+    pub relative: String,
+
+    pub kind: RootKind,
+}
+
+impl RepositoryRoot {
+    /// The root whose working directory is `dir`
+    pub fn at(repo_root: &Path, dir: &Path) -> Self {
+        let kind = if dir == repo_root {
+            RootKind::Primary
+        } else if dir.join(".git").is_file() {
+            RootKind::Submodule
+        } else {
+            RootKind::Nested
+        };
+        Self {
+            path: dir.to_path_buf(),
+            relative: if dir == repo_root {
+                PRIMARY_ROOT.to_string()
+            } else {
+                relative_to(repo_root, dir)
+            },
+            kind,
+        }
+    }
+
+    /// Innermost root containing `path`, judged from the filesystem alone
+    pub fn containing(repo_root: &Path, path: &Path) -> Self {
+        let dir = path
+            .ancestors()
+            .take_while(|ancestor| ancestor.starts_with(repo_root))
+            .find(|ancestor| *ancestor == repo_root || is_repository_boundary(ancestor))
+            .unwrap_or(repo_root);
+        Self::at(repo_root, dir)
+    }
+
+    pub fn is_primary(&self) -> bool {
+        self.kind == RootKind::Primary
+    }
+}
+
+/// The main repository and the nested roots discovery descends into
+#[derive(Debug, Clone)]
+pub struct RepositoryRoots {
+    roots: Vec<RepositoryRoot>,
+}
+
+impl RepositoryRoots {
+    /// Roots of the repository at `repo_root` as configured
+    pub fn load(repo_root: &Path) -> RhemaResult<Self> {
+        Ok(Self::discover(
+            repo_root,
+            &RepositoryRootsConfig::load(repo_root)?,
+        ))
+    }
+
+    /// Walk `repo_root` for nested repositories, keeping those `config`
+    /// descends into. Roots inside skipped roots are skipped too.
+    pub fn discover(repo_root: &Path, config: &RepositoryRootsConfig) -> Self {
+        let mut roots = vec![RepositoryRoot::at(repo_root, repo_root)];
+
+        let mut walker = WalkDir::new(repo_root).follow_links(true).into_iter();
+        while let Some(entry) = walker.next() {
+            let Ok(entry) = entry else { continue };
+            let path = entry.path();
+            if entry.depth() == 0 || !entry.file_type().is_dir() {
+                continue;
+            }
+            if path.file_name().and_then(|s| s.to_str()) == Some(".git") {
+                walker.skip_current_dir();
+                continue;
+            }
+            if !is_repository_boundary(path) {
+                continue;
+            }
+            if !config.enters(repo_root, path) {
+                walker.skip_current_dir();
+                continue;
+            }
+            roots.push(RepositoryRoot::at(repo_root, path));
+        }
+
+        Self { roots }
+    }
+
+    /// Only the main repository
+    pub fn primary_only(repo_root: &Path) -> Self {
+        Self::discover(repo_root, &RepositoryRootsConfig::default())
+    }
+
+    pub fn roots(&self) -> &[RepositoryRoot] {
+        &self.roots
+    }
+
+    pub fn primary(&self) -> &RepositoryRoot {
+        &self.roots[0]
+    }
+
+    /// Innermost root containing `path`, or `None` for paths outside the
+    /// main repository
+    pub fn root_for_path(&self, path: &Path) -> Option<&RepositoryRoot> {
+        self.roots
+            .iter()
+            .filter(|root| path.starts_with(&root.path))
+            .max_by_key(|root| root.path.components().count())
+    }
+
+    /// Whether `dir` lies in a nested root discovery does not descend into
+    pub fn is_excluded(&self, dir: &Path) -> bool {
+        let Some(root) = self.root_for_path(dir) else {
+            return true;
+        };
+        dir.ancestors()
+            .take_while(|ancestor| *ancestor != root.path)
+            .any(is_repository_boundary)
+    }
+}
+
+/// Whether `dir` is the working directory of its own repository
+pub fn is_repository_boundary(dir: &Path) -> bool {
+    dir.join(".git").exists()
+}
+
+fn relative_to(repo_root: &Path, path: &Path) -> String {
+    path.strip_prefix(repo_root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+fn same_root(configured: &str, relative: &str) -> bool {
+    configured.trim_matches('/') == relative
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn layout() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        for dir in [
+            ".git",
+            "vendor/lib",
+            "tools/cli/.git",
+            "tools/cli/inner/.git",
+        ] {
+            std::fs::create_dir_all(temp.path().join(dir)).unwrap();
+        }
+        // Submodule checkouts point at the parent's git dir with a file
+        std::fs::write(
+            temp.path().join("vendor/lib/.git"),
+            "gitdir: ../../.git/modules/lib\n",
+        )
+        .unwrap();
+        temp
+    }
+
+    #[test]
+    fn test_roots_follow_configuration() {
+        let temp = layout();
+
+        let primary = RepositoryRoots::primary_only(temp.path());
+        assert_eq!(primary.roots().len(), 1);
+        assert!(primary.is_excluded(&temp.path().join("vendor/lib/src")));
+        assert!(!primary.is_excluded(&temp.path().join("src")));
+
+        let config = RepositoryRootsConfig {
+            descend: true,
+            exclude: vec!["tools/cli/inner".to_string()],
+            ..Default::default()
+        };
+        let roots = RepositoryRoots::discover(temp.path(), &config);
+        let mut relative: Vec<_> = roots.roots().iter().map(|r| r.relative.as_str()).collect();
+        relative.sort();
+        assert_eq!(relative, vec![".", "tools/cli", "vendor/lib"]);
+        assert!(roots.is_excluded(&temp.path().join("tools/cli/inner/src")));
+
+        let only_vendor = RepositoryRootsConfig {
+            descend: true,
+            include: vec!["vendor/lib/".to_string()],
+            ..Default::default()
+        };
+        let roots = RepositoryRoots::discover(temp.path(), &only_vendor);
+        assert_eq!(roots.roots().len(), 2);
+        assert!(roots.is_excluded(&temp.path().join("tools/cli")));
+    }
+
+    #[test]
+    fn test_root_for_path_picks_innermost() {
+        let temp = layout();
+        let config = RepositoryRootsConfig {
+            descend: true,
+            ..Default::default()
+        };
+        let roots = RepositoryRoots::discover(temp.path(), &config);
+
+        let root = roots
+            .root_for_path(&temp.path().join("tools/cli/inner/.rhema"))
+            .unwrap();
+        assert_eq!(root.relative, "tools/cli/inner");
+        assert_eq!(root.kind, RootKind::Nested);
+        let root = RepositoryRoot::containing(temp.path(), &temp.path().join("vendor/lib/.rhema"));
+        assert_eq!(root.relative, "vendor/lib");
+        assert_eq!(root.kind, RootKind::Submodule);
+        assert!(roots
+            .root_for_path(&temp.path().join("docs/.rhema"))
+            .unwrap()
+            .is_primary());
+        assert!(roots.root_for_path(Path::new("/elsewhere")).is_none());
+    }
+}
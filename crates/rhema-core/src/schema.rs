@@ -47,6 +47,26 @@ pub struct RhemaScope {
     /// Protocol information for AI context bootstrapping
     pub protocol_info: Option<ProtocolInfo>,
 
+    /// Policy for AI agents operating within this scope
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ai_policy: Option<crate::ai_policy::AiPolicy>,
+
+    /// How often this scope's entries must be reviewed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub freshness: Option<crate::freshness::FreshnessPolicy>,
+
+    /// Languages, frameworks and toolchains found by the detection pass
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub toolchains: Option<crate::toolchain::ScopeToolchains>,
+
+    /// Environment, working directory and services for the tools actions run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<crate::tool_runtime::RuntimeSpec>,
+
+    /// Fields this scope's entries must carry, replacing the repository's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry_templates: Option<crate::templates::EntryTemplates>,
+
     /// Custom fields for extensibility
     #[serde(flatten)]
     pub custom: HashMap<String, Value>,
@@ -349,11 +369,47 @@ pub struct DecisionEntry {
     /// Decision makers
     pub decision_makers: Option<Vec<String>>,
 
+    /// Commits implementing the decision
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub implementing_commits: Option<Vec<String>>,
+
+    /// Incidents attributed to the decision
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub incidents: Option<Vec<DecisionIncident>>,
+
+    /// ID of the decision that reversed this one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reversed_by: Option<String>,
+
+    /// ID of the decision this one reverses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reverses: Option<String>,
+
     /// Custom fields
     #[serde(flatten)]
     pub custom: HashMap<String, Value>,
 }
 
+/// Incident attributed to a decision after it was made
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DecisionIncident {
+    /// Incident identifier in the tracking system
+    pub id: String,
+
+    /// Short incident summary
+    pub title: String,
+
+    /// Incident severity
+    pub severity: Priority,
+
+    /// When the incident occurred
+    pub occurred_at: DateTime<Utc>,
+
+    /// Link to the postmortem or incident record
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
 /// Decision status
 #[derive(Debug, Clone, Serialize, Deserialize, clap::ValueEnum, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -909,9 +965,9 @@ impl PromptVersion {
     ) {
         let now = Utc::now();
 
-        // Add current version to history
+        // Record the new version in history
         self.history.push(VersionEntry {
-            version: self.current.clone(),
+            version: new_version.to_string(),
             template: template.to_string(),
             description: description.to_string(),
             timestamp: now,
@@ -1020,6 +1076,16 @@ impl UsageAnalytics {
 
     /// Record a new usage of this prompt
     pub fn record_usage(&mut self, successful: bool, feedback: Option<String>) {
+        self.record_version_usage(None, successful, feedback);
+    }
+
+    /// Record a new usage of a specific prompt version
+    pub fn record_version_usage(
+        &mut self,
+        version: Option<&str>,
+        successful: bool,
+        feedback: Option<String>,
+    ) {
         self.total_uses += 1;
         if successful {
             self.successful_uses += 1;
@@ -1031,10 +1097,19 @@ impl UsageAnalytics {
                 timestamp: Utc::now(),
                 successful,
                 feedback: feedback_text,
+                version: version.map(str::to_string),
             });
         }
     }
 
+    /// Get feedback recorded against a specific prompt version
+    pub fn version_feedback(&self, version: &str) -> Vec<&FeedbackEntry> {
+        self.feedback_history
+            .iter()
+            .filter(|entry| entry.version.as_deref() == Some(version))
+            .collect()
+    }
+
     /// Create new usage analytics
     pub fn new() -> Self {
         Self {
@@ -1060,6 +1135,9 @@ pub struct FeedbackEntry {
     pub successful: bool,
     /// User feedback text
     pub feedback: String,
+    /// Prompt version the feedback applies to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1078,6 +1156,9 @@ pub struct Prompts {
 impl PromptPattern {
     /// Create a new prompt pattern
     pub fn new(id: &str, name: &str, template: &str, injection: PromptInjectionMethod) -> Self {
+        let mut version = PromptVersion::new("1.0.0");
+        version.history[0].template = template.to_string();
+
         Self {
             id: id.to_string(),
             name: name.to_string(),
@@ -1085,7 +1166,7 @@ impl PromptPattern {
             template: template.to_string(),
             injection,
             usage_analytics: UsageAnalytics::new(),
-            version: PromptVersion::new("1.0.0"),
+            version,
             tags: None,
             context_rules: None,
             variables: None,
@@ -1165,6 +1246,27 @@ impl PromptPattern {
         result
     }
 
+    /// Get the template of a specific version. The current version always
+    /// resolves to the live template.
+    pub fn template_at(&self, version: &str) -> Option<&str> {
+        if version == self.version.current {
+            return Some(&self.template);
+        }
+        self.version
+            .get_version(version)
+            .map(|entry| entry.template.as_str())
+            .filter(|template| !template.is_empty())
+    }
+
+    /// Get a copy of this pattern with the template of a specific version
+    pub fn at_version(&self, version: &str) -> Option<PromptPattern> {
+        let template = self.template_at(version)?.to_string();
+        let mut pattern = self.clone();
+        pattern.template = template;
+        pattern.version.current = version.to_string();
+        Some(pattern)
+    }
+
     /// Set the base template to extend from
     pub fn set_extends(&mut self, base_template: &str) {
         self.extends = Some(base_template.to_string());
@@ -2083,6 +2185,14 @@ impl Validatable for RhemaScope {
             info.validate()?;
         }
 
+        if let Some(policy) = &self.ai_policy {
+            policy.validate()?;
+        }
+
+        if let Some(freshness) = &self.freshness {
+            freshness.validate()?;
+        }
+
         Ok(())
     }
 
@@ -2445,6 +2555,18 @@ impl Validatable for Decisions {
             }
         }
 
+        // Validate reversal links
+        for decision in &self.decisions {
+            if decision.reversed_by.as_deref() == Some(decision.id.as_str())
+                || decision.reverses.as_deref() == Some(decision.id.as_str())
+            {
+                return Err(crate::RhemaError::ValidationError(format!(
+                    "Decision {} cannot reverse itself",
+                    decision.id
+                )));
+            }
+        }
+
         // Validate review dates
         for decision in &self.decisions {
             if let Some(review_date) = decision.review_date {
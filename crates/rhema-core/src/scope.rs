@@ -14,6 +14,8 @@
  * limitations under the License.
  */
 
+use crate::profiling::{self, Phase};
+use crate::roots::RepositoryRootsConfig;
 use crate::{schema::Validatable, RhemaError, RhemaScope};
 use serde_yaml;
 use std::collections::HashMap;
@@ -75,13 +77,18 @@ impl Scope {
     pub fn new(path: PathBuf) -> Result<Self, RhemaError> {
         let rhema_file = Self::find_scope_file(&path)?;
 
-        let content = std::fs::read_to_string(&rhema_file).map_err(|e| RhemaError::IoError(e))?;
+        let content = {
+            let _phase = profiling::phase(Phase::FileIo);
+            std::fs::read_to_string(&rhema_file).map_err(RhemaError::IoError)?
+        };
 
-        let definition: RhemaScope =
+        let definition: RhemaScope = {
+            let _phase = profiling::phase(Phase::Parse);
             serde_yaml::from_str(&content).map_err(|e| RhemaError::InvalidYaml {
                 file: rhema_file.display().to_string(),
                 message: e.to_string(),
-            })?;
+            })?
+        };
 
         // Validate the scope definition
         definition.validate()?;
@@ -145,11 +152,15 @@ impl Scope {
 
 /// Discover all scopes in a repository
 pub fn discover_scopes(repo_root: &Path) -> Result<Vec<Scope>, RhemaError> {
+    let _phase = profiling::phase(Phase::ScopeDiscovery);
+    let roots = RepositoryRootsConfig::load(repo_root)?;
     let mut scopes = Vec::new();
 
+    // Submodules and nested repositories are only entered when configured
     for entry in WalkDir::new(repo_root)
         .follow_links(true)
         .into_iter()
+        .filter_entry(|e| !e.file_type().is_dir() || roots.enters(repo_root, e.path()))
         .filter_map(|e| e.ok())
     {
         let path = entry.path();
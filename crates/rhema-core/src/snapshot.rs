@@ -0,0 +1,563 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::{
+    file_ops::{read_yaml_file, write_yaml_file},
+    scope::discover_scopes,
+    RhemaError, RhemaResult,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// Directory (relative to the repository root) where snapshots are stored
+pub const SNAPSHOTS_DIR: &str = ".rhema/snapshots";
+
+/// Prefix used for git tags created alongside snapshots
+pub const SNAPSHOT_TAG_PREFIX: &str = "rhema-snapshot/";
+
+/// Manifest describing a named snapshot of all context files
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    /// Snapshot name
+    pub name: String,
+
+    /// Optional human readable description
+    pub description: Option<String>,
+
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+
+    /// Commit that HEAD pointed at when the snapshot was taken
+    pub git_commit: Option<String>,
+
+    /// Git tag created for this snapshot, if any
+    pub git_tag: Option<String>,
+
+    /// Context files keyed by path relative to the repository root
+    pub files: BTreeMap<String, SnapshotFile>,
+}
+
+/// A single context file recorded in a snapshot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotFile {
+    /// SHA-256 of the file content
+    pub hash: String,
+
+    /// File size in bytes
+    pub size: u64,
+}
+
+/// Kind of change between two snapshot states
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+/// A change to an individual context entry (todo, decision, ...)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryChange {
+    /// File the entry lives in, relative to the repository root
+    pub file: String,
+
+    /// Collection key within the file (e.g. `todos`, `entries`)
+    pub collection: String,
+
+    /// Entry identifier
+    pub id: String,
+
+    /// Kind of change
+    pub kind: ChangeKind,
+}
+
+/// A change to a whole context file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileChange {
+    /// File path relative to the repository root
+    pub file: String,
+
+    /// Kind of change
+    pub kind: ChangeKind,
+}
+
+/// Differences between two snapshot states
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SnapshotDiff {
+    /// File-level changes
+    pub files: Vec<FileChange>,
+
+    /// Entry-level changes inside changed files
+    pub entries: Vec<EntryChange>,
+}
+
+impl SnapshotDiff {
+    /// Whether the two states are identical
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+/// Outcome of restoring a snapshot
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RestoreReport {
+    /// Files written back from the snapshot
+    pub restored: Vec<String>,
+
+    /// Files removed because they did not exist in the snapshot
+    pub removed: Vec<String>,
+
+    /// Files that exist only in the working tree and were left untouched
+    pub kept: Vec<String>,
+}
+
+/// Creates, compares and restores named context snapshots.
+///
+/// File contents are stored content-addressed under `.rhema/snapshots/objects`
+/// so identical files are shared between snapshots.
+pub struct SnapshotManager {
+    repo_root: PathBuf,
+    snapshots_dir: PathBuf,
+}
+
+impl SnapshotManager {
+    /// Create a snapshot manager for a repository
+    pub fn new(repo_root: &Path) -> Self {
+        Self {
+            repo_root: repo_root.to_path_buf(),
+            snapshots_dir: repo_root.join(SNAPSHOTS_DIR),
+        }
+    }
+
+    /// Record a snapshot of all context files, optionally tagging HEAD
+    pub fn create(
+        &self,
+        name: &str,
+        description: Option<String>,
+        create_tag: bool,
+    ) -> RhemaResult<SnapshotManifest> {
+        validate_snapshot_name(name)?;
+
+        let manifest_path = self.manifest_path(name);
+        if manifest_path.exists() {
+            return Err(RhemaError::InvalidInput(format!(
+                "Snapshot '{}' already exists",
+                name
+            )));
+        }
+
+        let mut files = BTreeMap::new();
+        for (relative, content) in self.working_contents()? {
+            let hash = content_hash(&content);
+            self.store_object(&hash, &content)?;
+            files.insert(
+                relative,
+                SnapshotFile {
+                    hash,
+                    size: content.len() as u64,
+                },
+            );
+        }
+
+        let repo = git2::Repository::open(&self.repo_root).ok();
+        let head_commit = repo
+            .as_ref()
+            .and_then(|r| r.head().ok())
+            .and_then(|h| h.peel_to_commit().ok());
+
+        let git_tag = match (create_tag, &repo, &head_commit) {
+            (true, Some(repo), Some(commit)) => {
+                let tag_name = format!("{}{}", SNAPSHOT_TAG_PREFIX, name);
+                repo.tag_lightweight(&tag_name, commit.as_object(), false)?;
+                Some(tag_name)
+            }
+            (true, _, _) => {
+                return Err(RhemaError::GitError(git2::Error::from_str(
+                    "Cannot tag snapshot: repository has no HEAD commit",
+                )))
+            }
+            _ => None,
+        };
+
+        let manifest = SnapshotManifest {
+            name: name.to_string(),
+            description,
+            created_at: Utc::now(),
+            git_commit: head_commit.map(|c| c.id().to_string()),
+            git_tag,
+            files,
+        };
+
+        write_yaml_file(&manifest_path, &manifest)?;
+        Ok(manifest)
+    }
+
+    /// List all snapshots ordered by creation time
+    pub fn list(&self) -> RhemaResult<Vec<SnapshotManifest>> {
+        let mut manifests = Vec::new();
+        if !self.snapshots_dir.exists() {
+            return Ok(manifests);
+        }
+
+        for entry in std::fs::read_dir(&self.snapshots_dir)? {
+            let path = entry?.path();
+            if path.is_file() && path.extension().and_then(|s| s.to_str()) == Some("yaml") {
+                manifests.push(read_yaml_file::<SnapshotManifest>(&path)?);
+            }
+        }
+
+        manifests.sort_by_key(|manifest| manifest.created_at);
+        Ok(manifests)
+    }
+
+    /// Load a snapshot manifest by name
+    pub fn load(&self, name: &str) -> RhemaResult<SnapshotManifest> {
+        validate_snapshot_name(name)?;
+        let path = self.manifest_path(name);
+        if !path.exists() {
+            return Err(RhemaError::NotFound(format!("Snapshot '{}'", name)));
+        }
+        read_yaml_file(&path)
+    }
+
+    /// Delete a snapshot manifest (and its git tag, if one was created)
+    pub fn delete(&self, name: &str) -> RhemaResult<()> {
+        let manifest = self.load(name)?;
+        if let Some(tag) = &manifest.git_tag {
+            if let Ok(repo) = git2::Repository::open(&self.repo_root) {
+                // The tag may already have been removed by hand
+                let _ = repo.tag_delete(tag);
+            }
+        }
+        std::fs::remove_file(self.manifest_path(name))?;
+        Ok(())
+    }
+
+    /// Compare two snapshots
+    pub fn diff(&self, from: &str, to: &str) -> RhemaResult<SnapshotDiff> {
+        let from = self.snapshot_contents(&self.load(from)?)?;
+        let to = self.snapshot_contents(&self.load(to)?)?;
+        Ok(diff_contents(&from, &to))
+    }
+
+    /// Compare a snapshot against the current working tree
+    pub fn diff_working(&self, name: &str) -> RhemaResult<SnapshotDiff> {
+        let from = self.snapshot_contents(&self.load(name)?)?;
+        let to = self.working_contents()?;
+        Ok(diff_contents(&from, &to))
+    }
+
+    /// Preview the changes a restore would make to the working tree
+    pub fn restore_preview(&self, name: &str) -> RhemaResult<SnapshotDiff> {
+        let from = self.working_contents()?;
+        let to = self.snapshot_contents(&self.load(name)?)?;
+        Ok(diff_contents(&from, &to))
+    }
+
+    /// Restore context files from a snapshot.
+    ///
+    /// Files created after the snapshot are only removed when `prune` is set.
+    pub fn restore(&self, name: &str, prune: bool) -> RhemaResult<RestoreReport> {
+        let snapshot = self.snapshot_contents(&self.load(name)?)?;
+        let working = self.working_contents()?;
+        let mut report = RestoreReport::default();
+
+        for (relative, content) in &snapshot {
+            if working.get(relative) != Some(content) {
+                let path = self.repo_root.join(relative);
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&path, content)?;
+                report.restored.push(relative.clone());
+            }
+        }
+
+        for relative in working.keys().filter(|k| !snapshot.contains_key(*k)) {
+            if prune {
+                std::fs::remove_file(self.repo_root.join(relative))?;
+                report.removed.push(relative.clone());
+            } else {
+                report.kept.push(relative.clone());
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Directory holding the content-addressed file contents
+    pub fn objects_dir(&self) -> PathBuf {
+        self.snapshots_dir.join("objects")
+    }
+
+    /// Hashes of the objects some snapshot still refers to
+    pub fn referenced_objects(&self) -> RhemaResult<BTreeSet<String>> {
+        Ok(self
+            .list()?
+            .into_iter()
+            .flat_map(|manifest| manifest.files.into_values().map(|file| file.hash))
+            .collect())
+    }
+
+    fn manifest_path(&self, name: &str) -> PathBuf {
+        self.snapshots_dir.join(format!("{}.yaml", name))
+    }
+
+    fn object_path(&self, hash: &str) -> PathBuf {
+        self.objects_dir().join(hash)
+    }
+
+    fn store_object(&self, hash: &str, content: &str) -> RhemaResult<()> {
+        let path = self.object_path(hash);
+        if !path.exists() {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, content)?;
+        }
+        Ok(())
+    }
+
+    /// Read the contents of every file recorded in a snapshot
+    fn snapshot_contents(
+        &self,
+        manifest: &SnapshotManifest,
+    ) -> RhemaResult<BTreeMap<String, String>> {
+        let mut contents = BTreeMap::new();
+        for (relative, file) in &manifest.files {
+            let path = self.object_path(&file.hash);
+            let content = std::fs::read_to_string(&path).map_err(|_| {
+                RhemaError::FileNotFound(format!(
+                    "Snapshot '{}' is missing object {} for {}",
+                    manifest.name, file.hash, relative
+                ))
+            })?;
+            contents.insert(relative.clone(), content);
+        }
+        Ok(contents)
+    }
+
+    /// Read the contents of every context file in the working tree
+    fn working_contents(&self) -> RhemaResult<BTreeMap<String, String>> {
+        let mut contents = BTreeMap::new();
+        for scope in discover_scopes(&self.repo_root)? {
+            for path in scope.files.values() {
+                let relative = path
+                    .strip_prefix(&self.repo_root)?
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                contents.insert(relative, std::fs::read_to_string(path)?);
+            }
+        }
+        Ok(contents)
+    }
+}
+
+/// Compute the SHA-256 hex digest of file content
+pub fn content_hash(content: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(content.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+fn validate_snapshot_name(name: &str) -> RhemaResult<()> {
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(RhemaError::InvalidInput(format!(
+            "Invalid snapshot name '{}': use letters, digits, '-', '_' or '.'",
+            name
+        )))
+    }
+}
+
+/// Compute file- and entry-level differences between two sets of file contents
+fn diff_contents(from: &BTreeMap<String, String>, to: &BTreeMap<String, String>) -> SnapshotDiff {
+    let mut diff = SnapshotDiff::default();
+
+    for (file, old) in from {
+        match to.get(file) {
+            None => {
+                diff.files.push(FileChange {
+                    file: file.clone(),
+                    kind: ChangeKind::Removed,
+                });
+                diff.entries.extend(entry_changes(file, Some(old), None));
+            }
+            Some(new) if new != old => {
+                diff.files.push(FileChange {
+                    file: file.clone(),
+                    kind: ChangeKind::Modified,
+                });
+                diff.entries
+                    .extend(entry_changes(file, Some(old), Some(new)));
+            }
+            Some(_) => {}
+        }
+    }
+
+    for (file, new) in to.iter().filter(|(f, _)| !from.contains_key(*f)) {
+        diff.files.push(FileChange {
+            file: file.clone(),
+            kind: ChangeKind::Added,
+        });
+        diff.entries.extend(entry_changes(file, None, Some(new)));
+    }
+
+    diff
+}
+
+/// Compare the id-keyed collections (todos, entries, decisions, ...) of a file
+fn entry_changes(file: &str, old: Option<&str>, new: Option<&str>) -> Vec<EntryChange> {
+    let old = old.map(collect_entries).unwrap_or_default();
+    let new = new.map(collect_entries).unwrap_or_default();
+    let mut changes = Vec::new();
+
+    for ((collection, id), old_value) in &old {
+        let kind = match new.get(&(collection.clone(), id.clone())) {
+            None => Some(ChangeKind::Removed),
+            Some(new_value) if new_value != old_value => Some(ChangeKind::Modified),
+            Some(_) => None,
+        };
+        if let Some(kind) = kind {
+            changes.push(EntryChange {
+                file: file.to_string(),
+                collection: collection.clone(),
+                id: id.clone(),
+                kind,
+            });
+        }
+    }
+
+    for (collection, id) in new.keys().filter(|k| !old.contains_key(*k)) {
+        changes.push(EntryChange {
+            file: file.to_string(),
+            collection: collection.clone(),
+            id: id.clone(),
+            kind: ChangeKind::Added,
+        });
+    }
+
+    changes
+}
+
+/// Index every top-level sequence of mappings that carry an `id` field
+fn collect_entries(content: &str) -> BTreeMap<(String, String), Value> {
+    let mut entries = BTreeMap::new();
+    let Ok(Value::Mapping(root)) = serde_yaml::from_str::<Value>(content) else {
+        return entries;
+    };
+
+    for (key, value) in root {
+        let (Some(collection), Value::Sequence(items)) = (key.as_str(), value) else {
+            continue;
+        };
+        for item in items {
+            if let Some(id) = item.get("id").and_then(|v| v.as_str()) {
+                entries.insert((collection.to_string(), id.to_string()), item.clone());
+            }
+        }
+    }
+
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn setup_repo(root: &Path) {
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        let scope = root.join("service/.rhema");
+        std::fs::create_dir_all(&scope).unwrap();
+        std::fs::write(
+            scope.join("rhema.yaml"),
+            "name: service\nscope_type: service\nversion: 1.0.0\n",
+        )
+        .unwrap();
+        std::fs::write(
+            scope.join("todos.yaml"),
+            "todos:\n- id: a\n  title: First\n- id: b\n  title: Second\n",
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_entry_level_diff() {
+        let old = "todos:\n- id: a\n  title: First\n- id: b\n  title: Second\n";
+        let new = "todos:\n- id: a\n  title: Changed\n- id: c\n  title: Third\n";
+        let mut from = BTreeMap::new();
+        from.insert("todos.yaml".to_string(), old.to_string());
+        let mut to = BTreeMap::new();
+        to.insert("todos.yaml".to_string(), new.to_string());
+
+        let diff = diff_contents(&from, &to);
+        assert_eq!(diff.files.len(), 1);
+        assert_eq!(diff.files[0].kind, ChangeKind::Modified);
+
+        let kinds: Vec<(String, ChangeKind)> = diff
+            .entries
+            .iter()
+            .map(|e| (e.id.clone(), e.kind))
+            .collect();
+        assert!(kinds.contains(&("a".to_string(), ChangeKind::Modified)));
+        assert!(kinds.contains(&("b".to_string(), ChangeKind::Removed)));
+        assert!(kinds.contains(&("c".to_string(), ChangeKind::Added)));
+    }
+
+    #[test]
+    fn test_create_and_restore_snapshot() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        setup_repo(root);
+
+        let manager = SnapshotManager::new(root);
+        let manifest = manager.create("baseline", None, false).unwrap();
+        assert!(manifest.files.contains_key("service/.rhema/todos.yaml"));
+
+        let todos = root.join("service/.rhema/todos.yaml");
+        std::fs::write(&todos, "todos:\n- id: a\n  title: First\n").unwrap();
+
+        let preview = manager.restore_preview("baseline").unwrap();
+        assert_eq!(preview.entries.len(), 1);
+        assert_eq!(preview.entries[0].id, "b");
+        assert_eq!(preview.entries[0].kind, ChangeKind::Added);
+
+        let report = manager.restore("baseline", false).unwrap();
+        assert_eq!(
+            report.restored,
+            vec!["service/.rhema/todos.yaml".to_string()]
+        );
+        assert!(manager.diff_working("baseline").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_rejects_invalid_names() {
+        assert!(validate_snapshot_name("release-1.0").is_ok());
+        assert!(validate_snapshot_name("../escape").is_err());
+        assert!(validate_snapshot_name("").is_err());
+    }
+}
@@ -0,0 +1,1104 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Two-way sync of context entries with a central knowledge service.
+//!
+//! An entry is any item with an `id` in a top-level list of a scope file
+//! (todos, decisions, knowledge entries, ...). Each entry carries a version
+//! vector with one counter per replica. Local edits bump this replica's
+//! counter and wait in `.rhema/sync/queue.yaml` until the service accepts
+//! them, so changes made offline go out on the next sync. An incoming entry
+//! whose vector is concurrent with the local one is never applied; both
+//! sides are kept in `.rhema/sync/conflicts.yaml` until resolved.
+
+use crate::file_ops::{read_yaml_file, write_yaml_file};
+use crate::policy;
+use crate::scope::discover_scopes;
+use crate::snapshot::content_hash;
+use crate::{RhemaError, RhemaResult};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Section of `.rhema/repository.yaml` configuring sync
+pub const SYNC_CONFIG_SECTION: &str = "sync";
+
+/// Directory (relative to the repository root) holding sync state
+pub const SYNC_DIR: &str = ".rhema/sync";
+
+/// Environment variable read for the service token unless configured otherwise
+pub const DEFAULT_TOKEN_ENV: &str = "RHEMA_SYNC_TOKEN";
+
+/// Scope definition files, which are never synced
+const DEFINITION_FILES: [&str; 2] = ["rhema.yaml", "scope.yaml"];
+
+/// Which way entries of a scope flow
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncDirection {
+    #[default]
+    Both,
+    Push,
+    Pull,
+    None,
+}
+
+impl SyncDirection {
+    pub fn pushes(self) -> bool {
+        matches!(self, SyncDirection::Both | SyncDirection::Push)
+    }
+
+    pub fn pulls(self) -> bool {
+        matches!(self, SyncDirection::Both | SyncDirection::Pull)
+    }
+}
+
+/// Selective sync rule for one or more scopes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncRule {
+    /// Scope name; a trailing `*` matches by prefix
+    pub scope: String,
+
+    #[serde(default)]
+    pub direction: SyncDirection,
+
+    /// Collections to sync (`todos`, `decisions`, ...); empty syncs all
+    #[serde(default)]
+    pub collections: Vec<String>,
+}
+
+impl SyncRule {
+    fn matches(&self, scope: &str) -> bool {
+        match self.scope.strip_suffix('*') {
+            Some(prefix) => scope.starts_with(prefix),
+            None => self.scope == scope,
+        }
+    }
+}
+
+/// Sync configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyncConfig {
+    /// Base URL of the knowledge service
+    pub server: Option<String>,
+
+    /// Environment variable holding the bearer token
+    pub token_env: String,
+
+    /// Request timeout in seconds
+    pub timeout_secs: u64,
+
+    /// Direction for scopes no rule matches
+    pub default_direction: SyncDirection,
+
+    /// Per-scope rules; the first matching rule wins
+    pub rules: Vec<SyncRule>,
+}
+
+impl Default for SyncConfig {
+    fn default() -> Self {
+        Self {
+            server: None,
+            token_env: DEFAULT_TOKEN_ENV.to_string(),
+            timeout_secs: 30,
+            default_direction: SyncDirection::Both,
+            rules: Vec::new(),
+        }
+    }
+}
+
+impl SyncConfig {
+    /// Load the configuration from the repository config and policy bundle,
+    /// falling back to defaults
+    pub fn load(repo_root: &Path) -> RhemaResult<Self> {
+        let value = policy::repository_config(repo_root)?;
+        match value.get(SYNC_CONFIG_SECTION) {
+            Some(section) => serde_yaml::from_value(section.clone()).map_err(|e| {
+                RhemaError::ConfigError(format!(
+                    "Invalid {} section in the repository config: {}",
+                    SYNC_CONFIG_SECTION, e
+                ))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Whether the entry may flow in the given direction
+    pub fn allows(&self, key: &EntryKey, push: bool) -> bool {
+        let (direction, collections) = match self.rules.iter().find(|r| r.matches(&key.scope)) {
+            Some(rule) => (rule.direction, rule.collections.as_slice()),
+            None => (self.default_direction, &[][..]),
+        };
+        let allowed = if push {
+            direction.pushes()
+        } else {
+            direction.pulls()
+        };
+        allowed && (collections.is_empty() || collections.contains(&key.collection))
+    }
+}
+
+/// Identity of a synced entry, written `scope/file#collection/id`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct EntryKey {
+    pub scope: String,
+    pub file: String,
+    pub collection: String,
+    pub id: String,
+}
+
+impl EntryKey {
+    /// Reject file names that would escape the scope directory
+    fn validate(&self) -> RhemaResult<()> {
+        let valid_file = self.file.ends_with(".yaml")
+            && !self.file.contains(['/', '\\'])
+            && !self.file.starts_with('.')
+            && !DEFINITION_FILES.contains(&self.file.as_str());
+        if valid_file && !self.id.is_empty() && !self.collection.is_empty() {
+            Ok(())
+        } else {
+            Err(RhemaError::InvalidInput(format!(
+                "Invalid sync entry key '{}'",
+                self
+            )))
+        }
+    }
+}
+
+impl fmt::Display for EntryKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{}#{}/{}",
+            self.scope, self.file, self.collection, self.id
+        )
+    }
+}
+
+impl FromStr for EntryKey {
+    type Err = RhemaError;
+
+    fn from_str(s: &str) -> RhemaResult<Self> {
+        let invalid = || {
+            RhemaError::InvalidInput(format!(
+                "Invalid entry key '{}', expected scope/file#collection/id",
+                s
+            ))
+        };
+        let (location, entry) = s.split_once('#').ok_or_else(invalid)?;
+        let (scope, file) = location.rsplit_once('/').ok_or_else(invalid)?;
+        let (collection, id) = entry.split_once('/').ok_or_else(invalid)?;
+        Ok(Self {
+            scope: scope.to_string(),
+            file: file.to_string(),
+            collection: collection.to_string(),
+            id: id.to_string(),
+        })
+    }
+}
+
+/// How two version vectors relate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Causality {
+    Equal,
+    /// Every change in this vector is also in the other one
+    Before,
+    /// This vector includes every change of the other one and more
+    After,
+    /// Each side has changes the other has not seen
+    Concurrent,
+}
+
+/// Per-replica edit counters of an entry
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct VersionVector(BTreeMap<String, u64>);
+
+impl VersionVector {
+    pub fn get(&self, replica: &str) -> u64 {
+        self.0.get(replica).copied().unwrap_or(0)
+    }
+
+    pub fn increment(&mut self, replica: &str) {
+        *self.0.entry(replica.to_string()).or_insert(0) += 1;
+    }
+
+    /// Take the highest counter of each replica
+    pub fn merge(&mut self, other: &VersionVector) {
+        for (replica, &count) in &other.0 {
+            let entry = self.0.entry(replica.clone()).or_insert(0);
+            *entry = (*entry).max(count);
+        }
+    }
+
+    /// How this vector relates to `other`
+    pub fn compare(&self, other: &VersionVector) -> Causality {
+        let replicas: BTreeSet<&String> = self.0.keys().chain(other.0.keys()).collect();
+        let (mut ahead, mut behind) = (false, false);
+        for replica in replicas {
+            let (ours, theirs) = (self.get(replica), other.get(replica));
+            ahead |= ours > theirs;
+            behind |= ours < theirs;
+        }
+        match (ahead, behind) {
+            (false, false) => Causality::Equal,
+            (false, true) => Causality::Before,
+            (true, false) => Causality::After,
+            (true, true) => Causality::Concurrent,
+        }
+    }
+}
+
+impl fmt::Display for VersionVector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counters: Vec<String> = self
+            .0
+            .iter()
+            .map(|(replica, count)| format!("{}:{}", short_replica(replica), count))
+            .collect();
+        write!(f, "[{}]", counters.join(" "))
+    }
+}
+
+fn short_replica(replica: &str) -> &str {
+    replica.get(..8).unwrap_or(replica)
+}
+
+/// One entry as exchanged with the service
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncEntry {
+    pub key: EntryKey,
+    pub version: VersionVector,
+
+    /// Entry content; `None` records a deletion
+    #[serde(default)]
+    pub value: Option<serde_json::Value>,
+
+    /// Replica that made this version
+    pub updated_by: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SyncEntry {
+    pub fn is_deleted(&self) -> bool {
+        self.value.is_none()
+    }
+}
+
+/// Last known local version of an entry
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EntryState {
+    pub version: VersionVector,
+
+    /// Content hash; `None` once the entry is deleted
+    #[serde(default)]
+    pub hash: Option<String>,
+}
+
+/// Persistent sync bookkeeping of this replica
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyncState {
+    /// Identifies this checkout in version vectors
+    pub replica_id: String,
+
+    /// Service cursor of the last pull
+    pub cursor: Option<String>,
+
+    pub last_sync: Option<DateTime<Utc>>,
+
+    /// Entry states keyed by `scope/file#collection/id`
+    pub entries: BTreeMap<String, EntryState>,
+}
+
+/// Local and remote versions of an entry changed concurrently
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncConflict {
+    pub key: EntryKey,
+    pub local: SyncEntry,
+    pub remote: SyncEntry,
+    pub detected_at: DateTime<Utc>,
+}
+
+/// Which side of a conflict to keep
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    Local,
+    Remote,
+}
+
+/// Outcome of a sync operation
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct SyncReport {
+    /// Local changes added to the queue
+    pub queued: usize,
+
+    /// Queued changes accepted by the service
+    pub pushed: usize,
+
+    /// Remote changes applied locally
+    pub pulled: usize,
+
+    /// New conflicts
+    pub conflicts: usize,
+
+    /// Remote entries for scopes that do not exist in this repository
+    pub skipped: usize,
+
+    /// The service was unreachable; local changes stay queued
+    pub offline: bool,
+}
+
+impl SyncReport {
+    fn absorb(&mut self, other: SyncReport) {
+        self.queued += other.queued;
+        self.pushed += other.pushed;
+        self.pulled += other.pulled;
+        self.conflicts += other.conflicts;
+        self.skipped += other.skipped;
+        self.offline |= other.offline;
+    }
+}
+
+/// Service response to a push
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PushResponse {
+    /// Entries the service stored
+    #[serde(default)]
+    pub accepted: Vec<EntryKey>,
+
+    /// The service's copy of entries rejected as concurrent
+    #[serde(default)]
+    pub conflicts: Vec<SyncEntry>,
+}
+
+/// Service response to a pull
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PullResponse {
+    /// Entries changed since the cursor
+    #[serde(default)]
+    pub entries: Vec<SyncEntry>,
+
+    /// Cursor to pass to the next pull
+    pub cursor: Option<String>,
+}
+
+/// Connection to the central knowledge service.
+///
+/// Implementations report an unreachable service as
+/// [`RhemaError::NetworkError`]; the engine then keeps changes queued.
+#[async_trait]
+pub trait SyncTransport: Send + Sync {
+    async fn push(&self, replica_id: &str, entries: &[SyncEntry]) -> RhemaResult<PushResponse>;
+
+    async fn pull(&self, cursor: Option<&str>) -> RhemaResult<PullResponse>;
+}
+
+/// [`SyncTransport`] for the service's HTTP API:
+/// `POST {server}/v1/sync/push` and `GET {server}/v1/sync/pull?cursor=...`
+pub struct HttpTransport {
+    client: reqwest::Client,
+    base_url: String,
+    token: Option<String>,
+}
+
+#[derive(Serialize)]
+struct PushRequest<'a> {
+    replica_id: &'a str,
+    entries: &'a [SyncEntry],
+}
+
+impl HttpTransport {
+    pub fn new(config: &SyncConfig) -> RhemaResult<Self> {
+        let server = config.server.as_deref().ok_or_else(|| {
+            RhemaError::ConfigError(format!(
+                "No sync server configured; set {}.server in .rhema/repository.yaml",
+                SYNC_CONFIG_SECTION
+            ))
+        })?;
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .map_err(|e| RhemaError::ConfigError(e.to_string()))?;
+        Ok(Self {
+            client,
+            base_url: server.trim_end_matches('/').to_string(),
+            token: std::env::var(&config.token_env).ok(),
+        })
+    }
+
+    async fn send<R: serde::de::DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> RhemaResult<R> {
+        let request = match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        };
+        let response = request.send().await.map_err(transport_error)?;
+        let status = response.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+            return Err(RhemaError::AuthenticationError(format!(
+                "Sync service rejected the token ({})",
+                status
+            )));
+        }
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(RhemaError::ExternalServiceError(format!(
+                "Sync service returned {}: {}",
+                status, body
+            )));
+        }
+        response.json().await.map_err(transport_error)
+    }
+}
+
+/// Connection failures mean offline; anything else is a service error
+pub(crate) fn transport_error(err: reqwest::Error) -> RhemaError {
+    if err.is_connect() || err.is_timeout() {
+        RhemaError::NetworkError(err.to_string())
+    } else {
+        RhemaError::ExternalServiceError(err.to_string())
+    }
+}
+
+#[async_trait]
+impl SyncTransport for HttpTransport {
+    async fn push(&self, replica_id: &str, entries: &[SyncEntry]) -> RhemaResult<PushResponse> {
+        let url = format!("{}/v1/sync/push", self.base_url);
+        self.send(self.client.post(url).json(&PushRequest {
+            replica_id,
+            entries,
+        }))
+        .await
+    }
+
+    async fn pull(&self, cursor: Option<&str>) -> RhemaResult<PullResponse> {
+        let url = format!("{}/v1/sync/pull", self.base_url);
+        let mut request = self.client.get(url);
+        if let Some(cursor) = cursor {
+            request = request.query(&[("cursor", cursor)]);
+        }
+        self.send(request).await
+    }
+}
+
+/// Pushes and pulls context entries through a [`SyncTransport`]
+pub struct SyncEngine<T: SyncTransport> {
+    repo_root: PathBuf,
+    config: SyncConfig,
+    transport: T,
+    state: SyncState,
+    queue: Vec<SyncEntry>,
+    conflicts: Vec<SyncConflict>,
+}
+
+impl SyncEngine<HttpTransport> {
+    /// Engine for the service configured in `.rhema/repository.yaml`
+    pub fn open(repo_root: &Path) -> RhemaResult<Self> {
+        let config = SyncConfig::load(repo_root)?;
+        let transport = HttpTransport::new(&config)?;
+        Self::with_transport(repo_root, config, transport)
+    }
+}
+
+impl<T: SyncTransport> SyncEngine<T> {
+    pub fn with_transport(repo_root: &Path, config: SyncConfig, transport: T) -> RhemaResult<Self> {
+        let dir = repo_root.join(SYNC_DIR);
+        let mut state: SyncState = read_or_default(&dir.join("state.yaml"))?;
+        if state.replica_id.is_empty() {
+            state.replica_id = uuid::Uuid::new_v4().to_string();
+        }
+        Ok(Self {
+            repo_root: repo_root.to_path_buf(),
+            config,
+            transport,
+            state,
+            queue: read_or_default(&dir.join("queue.yaml"))?,
+            conflicts: read_or_default(&dir.join("conflicts.yaml"))?,
+        })
+    }
+
+    pub fn config(&self) -> &SyncConfig {
+        &self.config
+    }
+
+    pub fn state(&self) -> &SyncState {
+        &self.state
+    }
+
+    /// Local changes not yet accepted by the service
+    pub fn queue(&self) -> &[SyncEntry] {
+        &self.queue
+    }
+
+    pub fn conflicts(&self) -> &[SyncConflict] {
+        &self.conflicts
+    }
+
+    /// Queue every local edit, addition and deletion since the last scan
+    pub fn queue_local_changes(&mut self) -> RhemaResult<usize> {
+        let local = local_entries(&self.repo_root)?;
+        let mut changes: Vec<(EntryKey, Option<serde_json::Value>)> = Vec::new();
+
+        for (key, value) in &local {
+            let hash = value_hash(value);
+            let known = self.state.entries.get(&key.to_string());
+            if known.and_then(|s| s.hash.as_ref()) != Some(&hash) {
+                changes.push((key.clone(), Some(value.clone())));
+            }
+        }
+        for (key, entry) in &self.state.entries {
+            let Ok(key) = key.parse::<EntryKey>() else {
+                continue;
+            };
+            if entry.hash.is_some() && !local.contains_key(&key) {
+                changes.push((key, None));
+            }
+        }
+
+        let mut queued = 0;
+        for (key, value) in changes {
+            // Conflicted entries wait for `resolve`
+            if self.config.allows(&key, true) && !self.is_conflicted(&key) {
+                self.record_local(key, value);
+                queued += 1;
+            }
+        }
+        if queued > 0 {
+            self.save()?;
+        }
+        Ok(queued)
+    }
+
+    /// Queue local changes and send the queue to the service
+    pub async fn push(&mut self) -> RhemaResult<SyncReport> {
+        let mut report = SyncReport {
+            queued: self.queue_local_changes()?,
+            ..SyncReport::default()
+        };
+        if self.queue.is_empty() {
+            return Ok(report);
+        }
+
+        let response = match self
+            .transport
+            .push(&self.state.replica_id, &self.queue)
+            .await
+        {
+            Ok(response) => response,
+            Err(RhemaError::NetworkError(_)) => {
+                report.offline = true;
+                return Ok(report);
+            }
+            Err(e) => return Err(e),
+        };
+
+        let accepted: BTreeSet<&EntryKey> = response.accepted.iter().collect();
+        let before = self.queue.len();
+        self.queue.retain(|entry| !accepted.contains(&entry.key));
+        report.pushed = before - self.queue.len();
+
+        for remote in response.conflicts {
+            if let Some(index) = self.queue.iter().position(|e| e.key == remote.key) {
+                let local = self.queue.remove(index);
+                self.add_conflict(local, remote);
+                report.conflicts += 1;
+            }
+        }
+
+        self.state.last_sync = Some(Utc::now());
+        self.save()?;
+        Ok(report)
+    }
+
+    /// Apply remote changes since the last pull
+    pub async fn pull(&mut self) -> RhemaResult<SyncReport> {
+        let mut report = SyncReport {
+            queued: self.queue_local_changes()?,
+            ..SyncReport::default()
+        };
+
+        let response = match self.transport.pull(self.state.cursor.as_deref()).await {
+            Ok(response) => response,
+            Err(RhemaError::NetworkError(_)) => {
+                report.offline = true;
+                return Ok(report);
+            }
+            Err(e) => return Err(e),
+        };
+
+        let scopes: BTreeSet<String> = discover_scopes(&self.repo_root)?
+            .into_iter()
+            .map(|scope| scope.definition.name)
+            .collect();
+
+        for remote in response.entries {
+            if remote.key.validate().is_err() || !self.config.allows(&remote.key, false) {
+                continue;
+            }
+            if !scopes.contains(&remote.key.scope) {
+                report.skipped += 1;
+                continue;
+            }
+
+            let key = remote.key.to_string();
+            let local_version = self
+                .state
+                .entries
+                .get(&key)
+                .map(|s| s.version.clone())
+                .unwrap_or_default();
+            match remote.version.compare(&local_version) {
+                Causality::Equal | Causality::Before => {}
+                Causality::After => {
+                    if let Some(conflict) = self.conflicts.iter_mut().find(|c| c.key == remote.key)
+                    {
+                        // Still unresolved; keep the newest remote side
+                        conflict.remote = remote;
+                        continue;
+                    }
+                    self.apply_remote(&remote)?;
+                    self.queue.retain(|e| e.key != remote.key);
+                    report.pulled += 1;
+                }
+                Causality::Concurrent => {
+                    let local = match self.queue.iter().position(|e| e.key == remote.key) {
+                        Some(index) => self.queue.remove(index),
+                        None => self.local_entry(&remote.key)?,
+                    };
+                    self.add_conflict(local, remote);
+                    report.conflicts += 1;
+                }
+            }
+        }
+
+        if response.cursor.is_some() {
+            self.state.cursor = response.cursor;
+        }
+        self.state.last_sync = Some(Utc::now());
+        self.save()?;
+        Ok(report)
+    }
+
+    /// Pull remote changes, then push local ones
+    pub async fn sync(&mut self) -> RhemaResult<SyncReport> {
+        let mut report = self.pull().await?;
+        if report.offline {
+            return Ok(report);
+        }
+        report.absorb(self.push().await?);
+        Ok(report)
+    }
+
+    /// Settle a conflict; keeping the local side queues it as a new version
+    pub fn resolve(&mut self, key: &EntryKey, keep: Resolution) -> RhemaResult<()> {
+        let index = self
+            .conflicts
+            .iter()
+            .position(|c| &c.key == key)
+            .ok_or_else(|| RhemaError::NotFound(format!("Sync conflict for {}", key)))?;
+        let conflict = self.conflicts.remove(index);
+
+        match keep {
+            Resolution::Remote => self.apply_remote(&conflict.remote)?,
+            Resolution::Local => {
+                let state = self.state.entries.entry(key.to_string()).or_default();
+                state.version.merge(&conflict.remote.version);
+                write_entry(&self.repo_root, key, conflict.local.value.as_ref())?;
+                self.record_local(key.clone(), conflict.local.value);
+            }
+        }
+        self.save()
+    }
+
+    fn is_conflicted(&self, key: &EntryKey) -> bool {
+        self.conflicts.iter().any(|c| &c.key == key)
+    }
+
+    fn add_conflict(&mut self, local: SyncEntry, remote: SyncEntry) {
+        self.conflicts.retain(|c| c.key != remote.key);
+        self.conflicts.push(SyncConflict {
+            key: remote.key.clone(),
+            local,
+            remote,
+            detected_at: Utc::now(),
+        });
+    }
+
+    /// Bump this replica's counter for a local change and queue it
+    fn record_local(&mut self, key: EntryKey, value: Option<serde_json::Value>) {
+        let state = self.state.entries.entry(key.to_string()).or_default();
+        state.version.increment(&self.state.replica_id);
+        state.hash = value.as_ref().map(value_hash);
+
+        let entry = SyncEntry {
+            key,
+            version: state.version.clone(),
+            value,
+            updated_by: self.state.replica_id.clone(),
+            updated_at: Utc::now(),
+        };
+        self.queue.retain(|e| e.key != entry.key);
+        self.queue.push(entry);
+    }
+
+    /// The current local side of an entry
+    fn local_entry(&self, key: &EntryKey) -> RhemaResult<SyncEntry> {
+        let state = self
+            .state
+            .entries
+            .get(&key.to_string())
+            .cloned()
+            .unwrap_or_default();
+        Ok(SyncEntry {
+            key: key.clone(),
+            version: state.version,
+            value: read_entry(&self.repo_root, key)?,
+            updated_by: self.state.replica_id.clone(),
+            updated_at: Utc::now(),
+        })
+    }
+
+    fn apply_remote(&mut self, remote: &SyncEntry) -> RhemaResult<()> {
+        write_entry(&self.repo_root, &remote.key, remote.value.as_ref())?;
+        self.state.entries.insert(
+            remote.key.to_string(),
+            EntryState {
+                version: remote.version.clone(),
+                hash: remote.value.as_ref().map(value_hash),
+            },
+        );
+        Ok(())
+    }
+
+    fn save(&self) -> RhemaResult<()> {
+        let dir = self.repo_root.join(SYNC_DIR);
+        write_yaml_file(&dir.join("state.yaml"), &self.state)?;
+        write_yaml_file(&dir.join("queue.yaml"), &self.queue)?;
+        write_yaml_file(&dir.join("conflicts.yaml"), &self.conflicts)
+    }
+}
+
+fn read_or_default<D: serde::de::DeserializeOwned + Default>(path: &Path) -> RhemaResult<D> {
+    if path.exists() {
+        read_yaml_file(path)
+    } else {
+        Ok(D::default())
+    }
+}
+
+fn value_hash(value: &serde_json::Value) -> String {
+    content_hash(&value.to_string())
+}
+
+/// Every id-keyed entry in every scope file of the repository
+fn local_entries(repo_root: &Path) -> RhemaResult<BTreeMap<EntryKey, serde_json::Value>> {
+    let mut entries = BTreeMap::new();
+    for scope in discover_scopes(repo_root)? {
+        for (file, path) in &scope.files {
+            if DEFINITION_FILES.contains(&file.as_str()) {
+                continue;
+            }
+            let Ok(serde_yaml::Value::Mapping(root)) = read_yaml_file(path) else {
+                continue;
+            };
+            for (collection, items) in root {
+                let (Some(collection), serde_yaml::Value::Sequence(items)) =
+                    (collection.as_str(), items)
+                else {
+                    continue;
+                };
+                for item in items {
+                    let Some(id) = item.get("id").and_then(|v| v.as_str()) else {
+                        continue;
+                    };
+                    let key = EntryKey {
+                        scope: scope.definition.name.clone(),
+                        file: file.clone(),
+                        collection: collection.to_string(),
+                        id: id.to_string(),
+                    };
+                    entries.insert(key, serde_json::to_value(&item)?);
+                }
+            }
+        }
+    }
+    Ok(entries)
+}
+
+/// Path of the file holding an entry
+fn entry_path(repo_root: &Path, key: &EntryKey) -> RhemaResult<PathBuf> {
+    key.validate()?;
+    let scope = discover_scopes(repo_root)?
+        .into_iter()
+        .find(|scope| scope.definition.name == key.scope)
+        .ok_or_else(|| RhemaError::ScopeNotFound(key.scope.clone()))?;
+    Ok(scope.path.join(&key.file))
+}
+
+fn read_entry(repo_root: &Path, key: &EntryKey) -> RhemaResult<Option<serde_json::Value>> {
+    let path = entry_path(repo_root, key)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let root: serde_yaml::Value = read_yaml_file(&path)?;
+    let item = root
+        .get(&key.collection)
+        .and_then(|items| items.as_sequence())
+        .and_then(|items| {
+            items
+                .iter()
+                .find(|item| item.get("id").and_then(|v| v.as_str()) == Some(key.id.as_str()))
+        });
+    Ok(match item {
+        Some(item) => Some(serde_json::to_value(item)?),
+        None => None,
+    })
+}
+
+/// Replace, add or (for `None`) remove an entry in its scope file
+fn write_entry(
+    repo_root: &Path,
+    key: &EntryKey,
+    value: Option<&serde_json::Value>,
+) -> RhemaResult<()> {
+    let path = entry_path(repo_root, key)?;
+    let mut root = if path.exists() {
+        read_yaml_file(&path)?
+    } else {
+        serde_yaml::Value::Mapping(serde_yaml::Mapping::new())
+    };
+    let serde_yaml::Value::Mapping(mapping) = &mut root else {
+        return Err(RhemaError::InvalidYaml {
+            file: path.display().to_string(),
+            message: "expected a mapping at the top level".to_string(),
+        });
+    };
+
+    let collection = serde_yaml::Value::String(key.collection.clone());
+    let items = mapping
+        .entry(collection)
+        .or_insert_with(|| serde_yaml::Value::Sequence(Vec::new()));
+    let serde_yaml::Value::Sequence(items) = items else {
+        return Err(RhemaError::InvalidYaml {
+            file: path.display().to_string(),
+            message: format!("'{}' is not a list", key.collection),
+        });
+    };
+
+    let position = items
+        .iter()
+        .position(|item| item.get("id").and_then(|v| v.as_str()) == Some(key.id.as_str()));
+    match (value, position) {
+        (Some(value), Some(index)) => items[index] = serde_yaml::to_value(value)?,
+        (Some(value), None) => items.push(serde_yaml::to_value(value)?),
+        (None, Some(index)) => {
+            items.remove(index);
+        }
+        (None, None) => return Ok(()),
+    }
+    write_yaml_file(&path, &root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    /// In-memory service that rejects pushes not descending from its copy
+    #[derive(Default)]
+    struct MemoryService {
+        entries: Mutex<Vec<SyncEntry>>,
+        offline: bool,
+    }
+
+    #[async_trait]
+    impl SyncTransport for &MemoryService {
+        async fn push(&self, _replica: &str, entries: &[SyncEntry]) -> RhemaResult<PushResponse> {
+            if self.offline {
+                return Err(RhemaError::NetworkError("connection refused".to_string()));
+            }
+            let mut stored = self.entries.lock().unwrap();
+            let mut response = PushResponse::default();
+            for entry in entries {
+                match stored.iter().position(|e| e.key == entry.key) {
+                    Some(i) if entry.version.compare(&stored[i].version) != Causality::After => {
+                        response.conflicts.push(stored[i].clone());
+                    }
+                    Some(i) => stored[i] = entry.clone(),
+                    None => stored.push(entry.clone()),
+                }
+                if !response.conflicts.iter().any(|c| c.key == entry.key) {
+                    response.accepted.push(entry.key.clone());
+                }
+            }
+            Ok(response)
+        }
+
+        async fn pull(&self, _cursor: Option<&str>) -> RhemaResult<PullResponse> {
+            if self.offline {
+                return Err(RhemaError::NetworkError("connection refused".to_string()));
+            }
+            Ok(PullResponse {
+                entries: self.entries.lock().unwrap().clone(),
+                cursor: None,
+            })
+        }
+    }
+
+    fn setup_repo(todos: &str) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        let scope = dir.path().join("service/.rhema");
+        std::fs::create_dir_all(&scope).unwrap();
+        std::fs::write(
+            scope.join("rhema.yaml"),
+            "name: service\nscope_type: service\nversion: 1.0.0\n",
+        )
+        .unwrap();
+        std::fs::write(scope.join("todos.yaml"), todos).unwrap();
+        dir
+    }
+
+    fn todos(dir: &TempDir) -> String {
+        std::fs::read_to_string(dir.path().join("service/.rhema/todos.yaml")).unwrap()
+    }
+
+    #[test]
+    fn test_version_vector_causality() {
+        let mut a = VersionVector::default();
+        a.increment("a");
+        let mut b = a.clone();
+        b.increment("b");
+        assert_eq!(a.compare(&b), Causality::Before);
+        assert_eq!(b.compare(&a), Causality::After);
+
+        a.increment("a");
+        assert_eq!(a.compare(&b), Causality::Concurrent);
+        a.merge(&b);
+        assert_eq!(a.compare(&b), Causality::After);
+
+        let key: EntryKey = "service/todos.yaml#todos/t-1".parse().unwrap();
+        assert_eq!(key.to_string(), "service/todos.yaml#todos/t-1");
+    }
+
+    #[tokio::test]
+    async fn test_push_and_pull_between_replicas() {
+        let service = MemoryService::default();
+        let alice = setup_repo("todos:\n- id: t-1\n  title: First\n");
+        let bob = setup_repo("todos: []\n");
+
+        let mut engine =
+            SyncEngine::with_transport(alice.path(), SyncConfig::default(), &service).unwrap();
+        let report = engine.push().await.unwrap();
+        assert_eq!((report.queued, report.pushed), (1, 1));
+        assert!(engine.queue().is_empty());
+
+        let mut engine =
+            SyncEngine::with_transport(bob.path(), SyncConfig::default(), &service).unwrap();
+        assert_eq!(engine.pull().await.unwrap().pulled, 1);
+        assert!(todos(&bob).contains("First"));
+
+        // Pulling again is a no-op
+        assert_eq!(engine.sync().await.unwrap(), SyncReport::default());
+    }
+
+    #[tokio::test]
+    async fn test_offline_changes_stay_queued() {
+        let offline = MemoryService {
+            offline: true,
+            ..MemoryService::default()
+        };
+        let repo = setup_repo("todos:\n- id: t-1\n  title: First\n");
+
+        let mut engine =
+            SyncEngine::with_transport(repo.path(), SyncConfig::default(), &offline).unwrap();
+        let report = engine.push().await.unwrap();
+        assert!(report.offline);
+        assert_eq!(engine.queue().len(), 1);
+
+        // The queue survives a restart and goes out once the service is back
+        let online = MemoryService::default();
+        let mut engine =
+            SyncEngine::with_transport(repo.path(), SyncConfig::default(), &online).unwrap();
+        assert_eq!(engine.queue().len(), 1);
+        assert_eq!(engine.push().await.unwrap().pushed, 1);
+        assert_eq!(online.entries.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_edits_conflict_until_resolved() {
+        let service = MemoryService::default();
+        let alice = setup_repo("todos:\n- id: t-1\n  title: First\n");
+        let bob = setup_repo("todos: []\n");
+
+        let mut a =
+            SyncEngine::with_transport(alice.path(), SyncConfig::default(), &service).unwrap();
+        a.push().await.unwrap();
+        let mut b =
+            SyncEngine::with_transport(bob.path(), SyncConfig::default(), &service).unwrap();
+        b.pull().await.unwrap();
+
+        let file = |dir: &TempDir| dir.path().join("service/.rhema/todos.yaml");
+        std::fs::write(file(&alice), "todos:\n- id: t-1\n  title: Alice\n").unwrap();
+        std::fs::write(file(&bob), "todos:\n- id: t-1\n  title: Bob\n").unwrap();
+        a.push().await.unwrap();
+
+        let report = b.sync().await.unwrap();
+        assert_eq!((report.conflicts, report.pulled), (1, 0));
+        assert!(todos(&bob).contains("Bob"));
+        assert!(b.queue().is_empty());
+
+        let key = b.conflicts()[0].key.clone();
+        b.resolve(&key, Resolution::Local).unwrap();
+        assert_eq!(b.push().await.unwrap().pushed, 1);
+
+        assert_eq!(a.pull().await.unwrap().pulled, 1);
+        assert!(todos(&alice).contains("Bob"));
+    }
+
+    #[test]
+    fn test_rules_select_scopes_and_collections() {
+        let config = SyncConfig {
+            default_direction: SyncDirection::None,
+            rules: vec![SyncRule {
+                scope: "serv*".to_string(),
+                direction: SyncDirection::Push,
+                collections: vec!["decisions".to_string()],
+            }],
+            ..SyncConfig::default()
+        };
+        let key = |scope: &str, collection: &str| EntryKey {
+            scope: scope.to_string(),
+            file: "x.yaml".to_string(),
+            collection: collection.to_string(),
+            id: "1".to_string(),
+        };
+
+        assert!(config.allows(&key("service", "decisions"), true));
+        assert!(!config.allows(&key("service", "decisions"), false));
+        assert!(!config.allows(&key("service", "todos"), true));
+        assert!(!config.allows(&key("web", "decisions"), true));
+    }
+}
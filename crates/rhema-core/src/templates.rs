@@ -0,0 +1,379 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Entry templates: the fields each kind of context entry must carry.
+//!
+//! Templates are declared globally in the `entry_templates` section of the
+//! repository config and per scope as `entry_templates` in `rhema.yaml`. A
+//! scope's template for a kind of entry replaces the global one. New entries
+//! missing a required field are rejected by [`crate::file_ops`] with the
+//! field's hint, and interactive flows pre-fill prompts from the scaffold.
+//! Fields outside the entry schema, such as `evidence`, are stored as custom
+//! fields on the entry.
+
+use crate::lifecycle::EntryType;
+use crate::policy;
+use crate::schema::RhemaScope;
+use crate::{RhemaError, RhemaResult};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Repository config section holding the global templates
+pub const ENTRY_TEMPLATES_SECTION: &str = "entry_templates";
+
+/// Extra fields set on an entry when it is written, by name
+pub type EntryFields = BTreeMap<String, Value>;
+
+/// Fields one kind of entry must carry
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EntryTemplate {
+    /// Fields that must be present and non-empty
+    pub required: Vec<String>,
+
+    /// What to put in a field, shown in prompts and errors
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub hints: BTreeMap<String, String>,
+
+    /// Text interactive flows pre-fill a field with
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub scaffold: BTreeMap<String, String>,
+}
+
+impl EntryTemplate {
+    /// Required fields that are absent or empty in `entry`
+    pub fn missing_fields(&self, entry: &Value) -> Vec<&str> {
+        self.required
+            .iter()
+            .filter(|field| entry.get(field.as_str()).is_none_or(is_empty))
+            .map(String::as_str)
+            .collect()
+    }
+
+    pub fn hint(&self, field: &str) -> Option<&str> {
+        self.hints.get(field).map(String::as_str)
+    }
+
+    pub fn scaffold(&self, field: &str) -> Option<&str> {
+        self.scaffold.get(field).map(String::as_str)
+    }
+}
+
+/// `entry_templates` block of a scope definition or the repository config
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EntryTemplates {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub todo: Option<EntryTemplate>,
+
+    /// Template for insights, the CLI's name for knowledge entries
+    #[serde(alias = "insight", skip_serializing_if = "Option::is_none")]
+    pub knowledge: Option<EntryTemplate>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<EntryTemplate>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decision: Option<EntryTemplate>,
+}
+
+impl EntryTemplates {
+    pub fn get(&self, entry_type: EntryType) -> Option<&EntryTemplate> {
+        match entry_type {
+            EntryType::Todo => self.todo.as_ref(),
+            EntryType::Knowledge => self.knowledge.as_ref(),
+            EntryType::Pattern => self.pattern.as_ref(),
+            EntryType::Decision => self.decision.as_ref(),
+        }
+    }
+
+    /// These templates with any missing kind of entry taken from `global`
+    pub fn or_global(self, global: EntryTemplates) -> EntryTemplates {
+        EntryTemplates {
+            todo: self.todo.or(global.todo),
+            knowledge: self.knowledge.or(global.knowledge),
+            pattern: self.pattern.or(global.pattern),
+            decision: self.decision.or(global.decision),
+        }
+    }
+
+    /// Load the global templates from the repository config and policy bundle
+    pub fn load(repo_root: &Path) -> RhemaResult<Self> {
+        let value = policy::repository_config(repo_root)?;
+        match value.get(ENTRY_TEMPLATES_SECTION) {
+            Some(section) => serde_yaml::from_value(section.clone()).map_err(|e| {
+                RhemaError::ConfigError(format!(
+                    "Invalid {} section in the repository config: {}",
+                    ENTRY_TEMPLATES_SECTION, e
+                ))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+/// Templates in effect for entries written under `path`: those of the
+/// nearest scope, falling back to the repository's
+pub fn resolve_entry_templates(path: &Path) -> RhemaResult<EntryTemplates> {
+    let mut scope_templates = None;
+    let mut repo_root = None;
+    for dir in path.ancestors() {
+        if scope_templates.is_none() {
+            scope_templates = load_scope_templates(dir)?;
+        }
+        if dir.join(".git").exists() {
+            repo_root = Some(dir);
+            break;
+        }
+    }
+
+    let global = match repo_root {
+        Some(root) => EntryTemplates::load(root)?,
+        None => EntryTemplates::default(),
+    };
+    Ok(scope_templates.unwrap_or_default().or_global(global))
+}
+
+/// Set `fields` on `entry` and check it against the template for its kind,
+/// returning the entry as it should be written
+pub fn apply_entry_template<T>(
+    scope_path: &Path,
+    entry_type: EntryType,
+    entry: T,
+    fields: EntryFields,
+) -> RhemaResult<T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    let templates = resolve_entry_templates(scope_path)?;
+    let template = templates.get(entry_type);
+    if template.is_none() && fields.is_empty() {
+        return Ok(entry);
+    }
+
+    let mut value = serde_yaml::to_value(&entry)?;
+    if let Value::Mapping(mapping) = &mut value {
+        for (name, field) in fields {
+            mapping.insert(Value::String(name), field);
+        }
+    }
+
+    if let Some(template) = template {
+        let missing = template.missing_fields(&value);
+        if !missing.is_empty() {
+            let fields = missing
+                .iter()
+                .map(|field| match template.hint(field) {
+                    Some(hint) => format!("  - {}: {}", field, hint),
+                    None => format!("  - {}", field),
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            return Err(RhemaError::ValidationError(format!(
+                "The {} template for this scope requires fields that are missing or empty:\n{}",
+                entry_type, fields
+            )));
+        }
+    }
+
+    serde_yaml::from_value(value).map_err(|e| {
+        RhemaError::ValidationError(format!("Invalid field for a {}: {}", entry_type, e))
+    })
+}
+
+/// Parse a `name=value` field; the value is read as YAML so lists and
+/// numbers keep their type, anything else is a string
+pub fn parse_entry_field(field: &str) -> RhemaResult<(String, Value)> {
+    let (name, value) = field.split_once('=').ok_or_else(|| {
+        RhemaError::InvalidInput(format!("Expected a field as name=value, got '{}'", field))
+    })?;
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(RhemaError::InvalidInput(format!(
+            "Missing field name in '{}'",
+            field
+        )));
+    }
+    let value = match serde_yaml::from_str::<Value>(value) {
+        Ok(parsed @ (Value::Sequence(_) | Value::Number(_) | Value::Bool(_))) => parsed,
+        _ => Value::String(value.trim().to_string()),
+    };
+    Ok((name.to_string(), value))
+}
+
+/// Parse repeated `name=value` fields, later ones winning
+pub fn parse_entry_fields(fields: &[String]) -> RhemaResult<EntryFields> {
+    fields
+        .iter()
+        .map(|field| parse_entry_field(field))
+        .collect()
+}
+
+/// Templates declared by the scope in `dir`, if it is one
+fn load_scope_templates(dir: &Path) -> RhemaResult<Option<EntryTemplates>> {
+    for file in [
+        dir.join("rhema.yaml"),
+        dir.join(".rhema").join("rhema.yaml"),
+    ] {
+        if !file.is_file() {
+            continue;
+        }
+        let content = std::fs::read_to_string(&file)?;
+        let scope: RhemaScope =
+            serde_yaml::from_str(&content).map_err(|e| RhemaError::InvalidYaml {
+                file: file.display().to_string(),
+                message: e.to_string(),
+            })?;
+        return Ok(Some(scope.entry_templates.unwrap_or_default()));
+    }
+    Ok(None)
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(s) => s.trim().is_empty(),
+        Value::Sequence(items) => items.is_empty(),
+        Value::Mapping(map) => map.is_empty(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_ops::{add_decision, add_knowledge, read_yaml_file};
+    use crate::schema::{DecisionStatus, Knowledge};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write_scope(dir: &Path, templates: &str) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(
+            dir.join("rhema.yaml"),
+            format!(
+                "name: api\nscope_type: service\nversion: \"1.0.0\"\n{}",
+                templates
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_scope_templates_override_repository_templates() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir(temp.path().join(".git")).unwrap();
+        fs::create_dir(temp.path().join(".rhema")).unwrap();
+        fs::write(
+            temp.path().join(".rhema").join("repository.yaml"),
+            "entry_templates:\n  todo:\n    required: [assigned_to]\n  decision:\n    required: [rationale]\n",
+        )
+        .unwrap();
+        let scope = temp.path().join("services").join("api");
+        write_scope(
+            &scope,
+            "entry_templates:\n  decision:\n    required: [rationale, review_date]\n",
+        );
+
+        let templates = resolve_entry_templates(&scope).unwrap();
+        assert_eq!(
+            templates.get(EntryType::Decision).unwrap().required,
+            vec!["rationale", "review_date"]
+        );
+        assert_eq!(
+            templates.get(EntryType::Todo).unwrap().required,
+            vec!["assigned_to"]
+        );
+        assert!(templates.get(EntryType::Pattern).is_none());
+    }
+
+    #[test]
+    fn test_missing_required_fields_are_rejected_with_hints() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir(temp.path().join(".git")).unwrap();
+        write_scope(
+            temp.path(),
+            "entry_templates:\n  decision:\n    required: [rationale, alternatives, review_date]\n    hints:\n      review_date: When to revisit the decision\n",
+        );
+
+        let error = add_decision(
+            temp.path(),
+            "Use Postgres".into(),
+            "Primary store".into(),
+            DecisionStatus::Approved,
+            None,
+            None,
+            Some("MySQL".into()),
+            None,
+            None,
+            EntryFields::new(),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(error.contains("rationale"));
+        assert!(error.contains("review_date: When to revisit the decision"));
+        assert!(!error.contains("alternatives"));
+
+        let mut fields = EntryFields::new();
+        fields.insert(
+            "review_date".into(),
+            Value::String("2027-01-01T00:00:00Z".into()),
+        );
+        add_decision(
+            temp.path(),
+            "Use Postgres".into(),
+            "Primary store".into(),
+            DecisionStatus::Approved,
+            None,
+            None,
+            Some("MySQL".into()),
+            Some("Team knows it".into()),
+            None,
+            fields,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_custom_fields_satisfy_insight_templates() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir(temp.path().join(".git")).unwrap();
+        write_scope(
+            temp.path(),
+            "entry_templates:\n  insight:\n    required: [evidence]\n",
+        );
+
+        let fields =
+            parse_entry_fields(&["evidence=[https://ci/run/1, PR-42]".to_string()]).unwrap();
+        add_knowledge(
+            temp.path(),
+            "Flaky cache".into(),
+            "Cache misses under load".into(),
+            None,
+            None,
+            None,
+            fields,
+        )
+        .unwrap();
+
+        let knowledge: Knowledge = read_yaml_file(&temp.path().join("knowledge.yaml")).unwrap();
+        let evidence = knowledge.entries[0].custom.get("evidence").unwrap();
+        assert_eq!(evidence.as_sequence().unwrap().len(), 2);
+    }
+}
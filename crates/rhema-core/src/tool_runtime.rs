@@ -0,0 +1,422 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Environment variables, working directory and services for the external
+//! tools an action runs, declared as `runtime` in `rhema.yaml` or in an
+//! intent's metadata.
+//!
+//! Integration tests often need configuration such as `DATABASE_URL`. A
+//! [`RuntimeSpec`] names the variables to inject, where their values come
+//! from, the directory tools run in and the services that must be up. Values
+//! are either literal or secrets read from the environment of the process
+//! running the action or from a file, so they never have to be committed.
+//! Only variables on the repository's `tool_runtime.allowed_env` allowlist
+//! may be set, and variables that change which programs or libraries get
+//! loaded are refused outright. [`RuntimeSpec::resolve`] checks all of that
+//! and returns the [`ResolvedRuntime`] the action layer injects into every
+//! process a tool spawns.
+
+use crate::file_ops::read_yaml_file;
+use crate::policy;
+use crate::schema::RhemaScope;
+use crate::{RhemaError, RhemaResult};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+/// Section of `.rhema/repository.yaml` holding the runtime allowlist
+pub const TOOL_RUNTIME_CONFIG_SECTION: &str = "tool_runtime";
+
+/// Variables never injected, allowlisted or not
+const DENIED_ENV: &[&str] = &[
+    "PATH",
+    "HOME",
+    "SHELL",
+    "IFS",
+    "BASH_ENV",
+    "ENV",
+    "LD_PRELOAD",
+    "LD_LIBRARY_PATH",
+    "LD_AUDIT",
+    "NODE_OPTIONS",
+];
+
+/// Prefixes of variables never injected
+const DENIED_ENV_PREFIXES: &[&str] = &["DYLD_"];
+
+/// Where the value of a variable comes from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EnvValue {
+    /// Used as written
+    Literal(String),
+
+    /// Secret taken from the environment of the process running the action
+    FromEnv { from_env: String },
+
+    /// Secret read from a file, relative to the declaring scope or repository;
+    /// trailing newlines are dropped
+    FromFile { from_file: String },
+}
+
+/// A service tools need, e.g. the database integration tests connect to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServiceRequirement {
+    pub name: String,
+
+    /// `host:port` that must accept TCP connections
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+
+    /// Variables that must be set for tools to reach the service
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env: Vec<String>,
+}
+
+/// `runtime` block of a scope definition or of intent metadata
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RuntimeSpec {
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, EnvValue>,
+
+    /// Directory tools run in when they do not pick their own
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<String>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub services: Vec<ServiceRequirement>,
+}
+
+/// `tool_runtime` section of the repository config
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolRuntimePolicy {
+    /// Variables scopes and intents may set; `TEST_*` allows a prefix
+    pub allowed_env: Vec<String>,
+
+    /// How long to wait for a required service to accept a connection
+    pub service_timeout_ms: u64,
+}
+
+impl Default for ToolRuntimePolicy {
+    fn default() -> Self {
+        Self {
+            allowed_env: Vec::new(),
+            service_timeout_ms: 2_000,
+        }
+    }
+}
+
+impl ToolRuntimePolicy {
+    pub fn load(repo_root: &Path) -> RhemaResult<Self> {
+        let value = policy::repository_config(repo_root)?;
+        match value.get(TOOL_RUNTIME_CONFIG_SECTION) {
+            Some(section) => serde_yaml::from_value(section.clone()).map_err(|e| {
+                RhemaError::ConfigError(format!(
+                    "Invalid {} section in the repository config: {}",
+                    TOOL_RUNTIME_CONFIG_SECTION, e
+                ))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Whether scopes and intents may set `name`
+    pub fn allows(&self, name: &str) -> bool {
+        if DENIED_ENV.contains(&name)
+            || DENIED_ENV_PREFIXES
+                .iter()
+                .any(|prefix| name.starts_with(prefix))
+        {
+            return false;
+        }
+        self.allowed_env
+            .iter()
+            .any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => name.starts_with(prefix),
+                None => pattern == name,
+            })
+    }
+}
+
+/// A runtime with every value resolved, ready to inject into processes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResolvedRuntime {
+    pub env: BTreeMap<String, String>,
+    pub working_dir: Option<PathBuf>,
+
+    /// Values that came from secrets, to be kept out of tool output
+    pub secrets: Vec<String>,
+}
+
+impl RuntimeSpec {
+    pub fn is_empty(&self) -> bool {
+        self.env.is_empty() && self.working_dir.is_none() && self.services.is_empty()
+    }
+
+    /// Layer `other` over this spec: its variables and working directory win,
+    /// and its services are added to these
+    pub fn merge(&mut self, other: RuntimeSpec) {
+        self.env.extend(other.env);
+        if other.working_dir.is_some() {
+            self.working_dir = other.working_dir;
+        }
+        for service in other.services {
+            self.services
+                .retain(|existing| existing.name != service.name);
+            self.services.push(service);
+        }
+    }
+
+    /// Make relative file paths absolute against `base`, so specs declared
+    /// in different places can be merged
+    pub fn rooted_at(mut self, base: &Path) -> RhemaResult<Self> {
+        if let Some(dir) = &self.working_dir {
+            self.working_dir = Some(within(base, dir)?.to_string_lossy().to_string());
+        }
+        for value in self.env.values_mut() {
+            if let EnvValue::FromFile { from_file } = value {
+                *from_file = within(base, from_file)?.to_string_lossy().to_string();
+            }
+        }
+        Ok(self)
+    }
+
+    /// Check every variable against the allowlist and resolve the values and
+    /// working directory, relative to `base`
+    pub fn resolve(&self, policy: &ToolRuntimePolicy, base: &Path) -> RhemaResult<ResolvedRuntime> {
+        let refused: Vec<&str> = self
+            .env
+            .keys()
+            .filter(|name| !policy.allows(name))
+            .map(String::as_str)
+            .collect();
+        if !refused.is_empty() {
+            return Err(RhemaError::SecurityError(format!(
+                "Tool runtime may not set {}; allow variables in {}.allowed_env",
+                refused.join(", "),
+                TOOL_RUNTIME_CONFIG_SECTION
+            )));
+        }
+
+        let mut resolved = ResolvedRuntime::default();
+        for (name, value) in &self.env {
+            let value = match value {
+                EnvValue::Literal(value) => value.clone(),
+                EnvValue::FromEnv { from_env } => {
+                    let secret = std::env::var(from_env).map_err(|_| {
+                        RhemaError::ConfigError(format!(
+                            "{} reads {} from the environment, which is not set",
+                            name, from_env
+                        ))
+                    })?;
+                    resolved.secrets.push(secret.clone());
+                    secret
+                }
+                EnvValue::FromFile { from_file } => {
+                    let path = within(base, from_file)?;
+                    let secret = std::fs::read_to_string(&path)
+                        .map_err(|e| {
+                            RhemaError::ConfigError(format!(
+                                "{} reads {}, which cannot be read: {}",
+                                name,
+                                path.display(),
+                                e
+                            ))
+                        })?
+                        .trim_end_matches(['\r', '\n'])
+                        .to_string();
+                    resolved.secrets.push(secret.clone());
+                    secret
+                }
+            };
+            resolved.env.insert(name.clone(), value);
+        }
+        resolved.secrets.retain(|secret| !secret.is_empty());
+
+        if let Some(dir) = &self.working_dir {
+            let dir = within(base, dir)?;
+            if !dir.is_dir() {
+                return Err(RhemaError::ConfigError(format!(
+                    "Tool working directory {} does not exist",
+                    dir.display()
+                )));
+            }
+            resolved.working_dir = Some(dir);
+        }
+        Ok(resolved)
+    }
+
+    /// Required services that are not reachable with `runtime`, one message each
+    pub fn unavailable_services(
+        &self,
+        runtime: &ResolvedRuntime,
+        policy: &ToolRuntimePolicy,
+    ) -> Vec<String> {
+        let timeout = Duration::from_millis(policy.service_timeout_ms);
+        let mut unavailable = Vec::new();
+        for service in &self.services {
+            let missing: Vec<&str> = service
+                .env
+                .iter()
+                .filter(|name| {
+                    !runtime.env.contains_key(name.as_str()) && std::env::var_os(name).is_none()
+                })
+                .map(String::as_str)
+                .collect();
+            if !missing.is_empty() {
+                unavailable.push(format!(
+                    "Service {} needs {} to be set",
+                    service.name,
+                    missing.join(", ")
+                ));
+            }
+            if let Some(address) = &service.address {
+                if !accepts_connections(address, timeout) {
+                    unavailable.push(format!(
+                        "Service {} is not reachable at {}",
+                        service.name, address
+                    ));
+                }
+            }
+        }
+        unavailable
+    }
+}
+
+/// `runtime` of the scope enclosing `path`, with relative paths resolved
+/// against the scope's directory. `None` when no scope covers the path.
+pub fn scope_runtime(path: &Path) -> RhemaResult<Option<RuntimeSpec>> {
+    let path = if path.is_relative() {
+        std::env::current_dir()?.join(path)
+    } else {
+        path.to_path_buf()
+    };
+    for dir in path.ancestors() {
+        let scope_file = dir.join(".rhema").join("rhema.yaml");
+        if scope_file.is_file() {
+            let definition: RhemaScope = read_yaml_file(&scope_file)?;
+            return definition
+                .runtime
+                .map(|runtime| runtime.rooted_at(dir))
+                .transpose();
+        }
+        if dir.join(".git").exists() {
+            break;
+        }
+    }
+    Ok(None)
+}
+
+/// `path` joined to `base`; relative paths may not leave `base`
+fn within(base: &Path, path: &str) -> RhemaResult<PathBuf> {
+    let path = Path::new(path);
+    if path.is_absolute() {
+        return Ok(path.to_path_buf());
+    }
+    if path
+        .components()
+        .any(|component| matches!(component, Component::ParentDir))
+    {
+        return Err(RhemaError::SecurityError(format!(
+            "Tool runtime path {} leaves {}",
+            path.display(),
+            base.display()
+        )));
+    }
+    Ok(base.join(path))
+}
+
+fn accepts_connections(address: &str, timeout: Duration) -> bool {
+    address
+        .to_socket_addrs()
+        .map(|mut addrs| addrs.any(|addr| TcpStream::connect_timeout(&addr, timeout).is_ok()))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use tempfile::TempDir;
+
+    fn policy(allowed: &[&str]) -> ToolRuntimePolicy {
+        ToolRuntimePolicy {
+            allowed_env: allowed.iter().map(|name| name.to_string()).collect(),
+            ..ToolRuntimePolicy::default()
+        }
+    }
+
+    #[test]
+    fn test_resolve_checks_allowlist_and_reads_secrets() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("secrets")).unwrap();
+        std::fs::write(dir.path().join("secrets/db_password"), "hunter2\n").unwrap();
+
+        let spec: RuntimeSpec = serde_yaml::from_str(
+            "env:\n  DATABASE_URL: postgres://localhost/test\n  TEST_DB_PASSWORD:\n    from_file: secrets/db_password\nworking_dir: secrets\n",
+        )
+        .unwrap();
+        let resolved = spec
+            .resolve(&policy(&["DATABASE_URL", "TEST_*"]), dir.path())
+            .unwrap();
+        assert_eq!(resolved.env["DATABASE_URL"], "postgres://localhost/test");
+        assert_eq!(resolved.env["TEST_DB_PASSWORD"], "hunter2");
+        assert_eq!(resolved.secrets, vec!["hunter2"]);
+        assert_eq!(resolved.working_dir, Some(dir.path().join("secrets")));
+
+        assert!(spec.resolve(&policy(&["TEST_*"]), dir.path()).is_err());
+        let path: RuntimeSpec = serde_yaml::from_str("env:\n  PATH: /tmp/evil\n").unwrap();
+        assert!(path.resolve(&policy(&["*"]), dir.path()).is_err());
+        let escape: RuntimeSpec = serde_yaml::from_str("working_dir: ../elsewhere\n").unwrap();
+        assert!(escape.resolve(&policy(&[]), dir.path()).is_err());
+    }
+
+    #[test]
+    fn test_merge_and_unavailable_services() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+
+        let mut spec: RuntimeSpec = serde_yaml::from_str(&format!(
+            "env:\n  DATABASE_URL: postgres://scope\nservices:\n  - name: postgres\n    address: \"{}\"\n    env: [DATABASE_URL]\n",
+            address
+        ))
+        .unwrap();
+        spec.merge(
+            serde_yaml::from_str(
+                "env:\n  DATABASE_URL: postgres://intent\nservices:\n  - name: redis\n    env: [RHEMA_TEST_UNSET_REDIS_URL]\n",
+            )
+            .unwrap(),
+        );
+        assert_eq!(
+            spec.env["DATABASE_URL"],
+            EnvValue::Literal("postgres://intent".to_string())
+        );
+
+        let policy = policy(&["DATABASE_URL"]);
+        let resolved = spec.resolve(&policy, Path::new("/")).unwrap();
+        assert_eq!(
+            spec.unavailable_services(&resolved, &policy),
+            vec!["Service redis needs RHEMA_TEST_UNSET_REDIS_URL to be set"]
+        );
+
+        drop(listener);
+        assert_eq!(spec.unavailable_services(&resolved, &policy).len(), 2);
+    }
+}
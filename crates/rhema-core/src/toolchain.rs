@@ -0,0 +1,498 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Languages, frameworks and toolchains of a scope, recorded as `toolchains`
+//! in `rhema.yaml`.
+//!
+//! [`detect_toolchains`] walks the code a scope covers, stopping at nested
+//! scopes, hidden directories and build output. Languages come from file
+//! extensions; toolchains and frameworks from manifests (`Cargo.toml`,
+//! `package.json`, `go.mod`, `pyproject.toml`, ...) and tool configuration
+//! files such as `jest.config.js` or `pytest.ini`. [`record_toolchains`]
+//! stores the result in the scope definition, and [`resolve_toolchains`]
+//! reads it back for any path so the action pipeline can pick the tools that
+//! apply to an intent.
+
+use crate::file_ops::{read_yaml_file, write_yaml_file};
+use crate::schema::RhemaScope;
+use crate::scope::Scope;
+use crate::{RhemaError, RhemaResult};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::path::Path;
+use walkdir::WalkDir;
+
+/// Directories never searched, besides hidden ones and nested scopes
+const SKIPPED_DIRS: &[&str] = &[
+    "target",
+    "node_modules",
+    "vendor",
+    "dist",
+    "build",
+    "venv",
+    "__pycache__",
+];
+
+/// Files looked at per scope before detection stops
+const MAX_FILES: usize = 20_000;
+
+/// Languages by file extension
+const EXTENSION_LANGUAGES: &[(&str, &str)] = &[
+    ("rs", "rust"),
+    ("go", "go"),
+    ("py", "python"),
+    ("ts", "typescript"),
+    ("tsx", "typescript"),
+    ("mts", "typescript"),
+    ("js", "javascript"),
+    ("jsx", "javascript"),
+    ("mjs", "javascript"),
+    ("cjs", "javascript"),
+    ("java", "java"),
+    ("kt", "kotlin"),
+    ("rb", "ruby"),
+    ("php", "php"),
+    ("cs", "csharp"),
+    ("swift", "swift"),
+    ("c", "c"),
+    ("cpp", "cpp"),
+    ("cc", "cpp"),
+    ("sh", "shell"),
+    ("bash", "shell"),
+    ("tf", "terraform"),
+    ("dockerfile", "dockerfile"),
+];
+
+/// Frameworks by npm or Python package name
+const PACKAGE_FRAMEWORKS: &[(&str, &str)] = &[
+    ("jest", "jest"),
+    ("mocha", "mocha"),
+    ("vitest", "vitest"),
+    ("react", "react"),
+    ("vue", "vue"),
+    ("svelte", "svelte"),
+    ("next", "nextjs"),
+    ("@angular/core", "angular"),
+    ("express", "express"),
+    ("eslint", "eslint"),
+    ("prettier", "prettier"),
+    ("pytest", "pytest"),
+    ("django", "django"),
+    ("flask", "flask"),
+    ("fastapi", "fastapi"),
+];
+
+/// Frameworks by configuration file name prefix
+const CONFIG_FRAMEWORKS: &[(&str, &str)] = &[
+    ("jest.config.", "jest"),
+    (".mocharc", "mocha"),
+    ("vitest.config.", "vitest"),
+    (".eslintrc", "eslint"),
+    ("eslint.config.", "eslint"),
+    (".prettierrc", "prettier"),
+    ("prettier.config.", "prettier"),
+    ("pytest.ini", "pytest"),
+    ("conftest.py", "pytest"),
+];
+
+/// `toolchains` block of a scope definition
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScopeToolchains {
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    pub languages: BTreeSet<String>,
+
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    pub frameworks: BTreeSet<String>,
+
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    pub toolchains: BTreeSet<String>,
+
+    /// When the detection pass last ran
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detected_at: Option<DateTime<Utc>>,
+}
+
+impl ScopeToolchains {
+    pub fn is_empty(&self) -> bool {
+        self.languages.is_empty() && self.frameworks.is_empty() && self.toolchains.is_empty()
+    }
+
+    /// Add everything detected in `other`
+    pub fn merge(&mut self, other: &ScopeToolchains) {
+        self.languages.extend(other.languages.iter().cloned());
+        self.frameworks.extend(other.frameworks.iter().cloned());
+        self.toolchains.extend(other.toolchains.iter().cloned());
+    }
+
+    fn language(&mut self, language: &str) {
+        self.languages.insert(language.to_string());
+    }
+
+    fn framework(&mut self, framework: &str) {
+        self.frameworks.insert(framework.to_string());
+    }
+
+    fn toolchain(&mut self, toolchain: &str) {
+        self.toolchains.insert(toolchain.to_string());
+    }
+}
+
+/// Detect the languages, frameworks and toolchains of the code under `root`
+pub fn detect_toolchains(root: &Path) -> RhemaResult<ScopeToolchains> {
+    if !root.is_dir() {
+        return Err(RhemaError::FileNotFound(format!(
+            "Cannot detect toolchains of {}: not a directory",
+            root.display()
+        )));
+    }
+
+    let mut detected = ScopeToolchains::default();
+    let walker = WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|entry| entry.depth() == 0 || !is_skipped_dir(entry))
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .take(MAX_FILES);
+
+    for entry in walker {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy();
+
+        if let Some(extension) = path.extension().and_then(|e| e.to_str()) {
+            if let Some((_, language)) = EXTENSION_LANGUAGES
+                .iter()
+                .find(|(known, _)| known.eq_ignore_ascii_case(extension))
+            {
+                detected.language(language);
+            }
+        }
+        if let Some((_, framework)) = CONFIG_FRAMEWORKS
+            .iter()
+            .find(|(prefix, _)| name.starts_with(prefix))
+        {
+            detected.framework(framework);
+        }
+        detect_manifest(path, &name, &mut detected);
+    }
+
+    detected.detected_at = Some(Utc::now());
+    Ok(detected)
+}
+
+/// Detect the toolchains of a scope and store them in its definition
+pub fn record_toolchains(scope: &Scope) -> RhemaResult<ScopeToolchains> {
+    let detected = detect_toolchains(&scope_root(&scope.path))?;
+
+    let file = Scope::find_scope_file(&scope.path)?;
+    let mut definition: RhemaScope = read_yaml_file(&file)?;
+    definition.toolchains = Some(detected.clone());
+    write_yaml_file(&file, &definition)?;
+    Ok(detected)
+}
+
+/// Toolchains for a file or directory: those recorded by the nearest
+/// enclosing scope, otherwise detected from that scope's directory, or from
+/// the nearest directory with a manifest when no scope covers the path
+pub fn resolve_toolchains(path: &Path) -> RhemaResult<ScopeToolchains> {
+    let path = if path.is_relative() {
+        std::env::current_dir()?.join(path)
+    } else {
+        path.to_path_buf()
+    };
+    let start = if path.is_file() {
+        path.parent().unwrap_or(&path)
+    } else {
+        &path
+    };
+    let start = scope_root(start);
+
+    let mut manifest_dir = None;
+    for dir in start.ancestors() {
+        let scope_file = dir.join(".rhema").join("rhema.yaml");
+        if scope_file.is_file() {
+            let definition: RhemaScope = read_yaml_file(&scope_file)?;
+            return match definition.toolchains {
+                Some(recorded) if !recorded.is_empty() => Ok(recorded),
+                _ => detect_toolchains(dir),
+            };
+        }
+        if manifest_dir.is_none() && has_manifest(dir) {
+            manifest_dir = Some(dir);
+        }
+        if dir.join(".git").exists() {
+            break;
+        }
+    }
+
+    match manifest_dir {
+        Some(dir) => detect_toolchains(dir),
+        None if start.is_dir() => detect_toolchains(&start),
+        None => Ok(ScopeToolchains::default()),
+    }
+}
+
+/// Directory of the code a scope covers, for a path that may be its `.rhema`
+fn scope_root(path: &Path) -> std::path::PathBuf {
+    if path.file_name().is_some_and(|name| name == ".rhema") {
+        path.parent().unwrap_or(path).to_path_buf()
+    } else {
+        path.to_path_buf()
+    }
+}
+
+fn is_skipped_dir(entry: &walkdir::DirEntry) -> bool {
+    if !entry.file_type().is_dir() {
+        return false;
+    }
+    let name = entry.file_name().to_string_lossy();
+    name.starts_with('.')
+        || SKIPPED_DIRS.contains(&name.as_ref())
+        || entry.path().join(".rhema").is_dir()
+}
+
+fn has_manifest(dir: &Path) -> bool {
+    [
+        "Cargo.toml",
+        "package.json",
+        "go.mod",
+        "pyproject.toml",
+        "requirements.txt",
+        "pom.xml",
+        "build.gradle",
+        "build.gradle.kts",
+    ]
+    .iter()
+    .any(|manifest| dir.join(manifest).is_file())
+}
+
+fn detect_manifest(path: &Path, name: &str, detected: &mut ScopeToolchains) {
+    match name {
+        "Cargo.toml" => {
+            detected.toolchain("cargo");
+            detected.language("rust");
+        }
+        "go.mod" | "go.work" => {
+            detected.toolchain("go");
+            detected.language("go");
+        }
+        "package.json" => {
+            detected.toolchain(node_package_manager(path));
+            detected.language("javascript");
+            if let Some(manifest) = read_json(path) {
+                for section in ["dependencies", "devDependencies", "peerDependencies"] {
+                    let Some(deps) = manifest.get(section).and_then(|d| d.as_object()) else {
+                        continue;
+                    };
+                    for dependency in deps.keys() {
+                        if dependency == "typescript" {
+                            detected.language("typescript");
+                        }
+                        package_framework(dependency, detected);
+                    }
+                }
+            }
+        }
+        "tsconfig.json" => detected.language("typescript"),
+        "pyproject.toml" => {
+            detected.language("python");
+            let content = std::fs::read_to_string(path).unwrap_or_default();
+            match content.parse::<toml::Table>() {
+                Ok(manifest) => {
+                    let poetry = manifest
+                        .get("tool")
+                        .and_then(|tool| tool.get("poetry"))
+                        .is_some();
+                    detected.toolchain(if poetry { "poetry" } else { "pip" });
+                    for requirement in python_requirements(&manifest) {
+                        package_framework(&requirement, detected);
+                    }
+                }
+                Err(_) => detected.toolchain("pip"),
+            }
+        }
+        "setup.py" | "setup.cfg" | "Pipfile" => {
+            detected.toolchain("pip");
+            detected.language("python");
+        }
+        "pom.xml" => {
+            detected.toolchain("maven");
+            detected.language("java");
+        }
+        "build.gradle" | "build.gradle.kts" => detected.toolchain("gradle"),
+        "Dockerfile" | "Containerfile" => detected.language("dockerfile"),
+        _ if name.starts_with("Dockerfile.") => detected.language("dockerfile"),
+        _ if name.starts_with("requirements") && name.ends_with(".txt") => {
+            detected.toolchain("pip");
+            detected.language("python");
+            let content = std::fs::read_to_string(path).unwrap_or_default();
+            for line in content.lines() {
+                package_framework(&requirement_name(line), detected);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `pnpm`, `yarn` or `npm`, by the lockfile next to a `package.json`
+fn node_package_manager(package_json: &Path) -> &'static str {
+    let dir = package_json.parent().unwrap_or(Path::new("."));
+    if dir.join("pnpm-lock.yaml").is_file() {
+        "pnpm"
+    } else if dir.join("yarn.lock").is_file() {
+        "yarn"
+    } else {
+        "npm"
+    }
+}
+
+fn package_framework(package: &str, detected: &mut ScopeToolchains) {
+    if let Some((_, framework)) = PACKAGE_FRAMEWORKS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(package))
+    {
+        detected.framework(framework);
+    }
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+}
+
+/// Package names required by a `pyproject.toml`, from PEP 621 and Poetry tables
+fn python_requirements(manifest: &toml::Table) -> Vec<String> {
+    let mut names = Vec::new();
+    let project = manifest.get("project");
+    let specs = project
+        .and_then(|p| p.get("dependencies"))
+        .and_then(|d| d.as_array())
+        .into_iter()
+        .flatten()
+        .chain(
+            project
+                .and_then(|p| p.get("optional-dependencies"))
+                .and_then(|d| d.as_table())
+                .into_iter()
+                .flat_map(|groups| groups.values())
+                .filter_map(|group| group.as_array())
+                .flatten(),
+        );
+    names.extend(specs.filter_map(|spec| spec.as_str()).map(requirement_name));
+
+    if let Some(poetry) = manifest.get("tool").and_then(|tool| tool.get("poetry")) {
+        let tables = ["dependencies", "dev-dependencies"]
+            .iter()
+            .filter_map(|key| poetry.get(key))
+            .chain(
+                poetry
+                    .get("group")
+                    .and_then(|groups| groups.as_table())
+                    .into_iter()
+                    .flat_map(|groups| groups.values())
+                    .filter_map(|group| group.get("dependencies")),
+            );
+        for table in tables.filter_map(|table| table.as_table()) {
+            names.extend(table.keys().cloned());
+        }
+    }
+    names
+}
+
+/// Package name of a requirement specifier such as `pytest>=7.0`
+fn requirement_name(spec: &str) -> String {
+    spec.trim()
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detects_manifests_extensions_and_frameworks() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join("web/src")).unwrap();
+        fs::write(
+            root.join("web/package.json"),
+            r#"{"devDependencies": {"jest": "^29", "typescript": "^5"}}"#,
+        )
+        .unwrap();
+        fs::write(root.join("web/yarn.lock"), "").unwrap();
+        fs::write(root.join("web/src/app.tsx"), "").unwrap();
+        fs::write(root.join("web/Dockerfile"), "FROM node:20\n").unwrap();
+        fs::create_dir_all(root.join("api")).unwrap();
+        fs::write(
+            root.join("api/pyproject.toml"),
+            "[project]\nname = \"api\"\ndependencies = [\"fastapi>=0.100\"]\n\n[project.optional-dependencies]\ntest = [\"pytest>=7\"]\n",
+        )
+        .unwrap();
+        // Build output and nested scopes are not part of the scope
+        fs::create_dir_all(root.join("web/node_modules/mocha")).unwrap();
+        fs::write(root.join("web/node_modules/mocha/package.json"), "{}").unwrap();
+        fs::create_dir_all(root.join("tools/.rhema")).unwrap();
+        fs::write(
+            root.join("tools/Cargo.toml"),
+            "[package]\nname = \"tools\"\n",
+        )
+        .unwrap();
+
+        let detected = detect_toolchains(root).unwrap();
+        let set = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+        assert_eq!(
+            detected.languages,
+            set(&["dockerfile", "javascript", "python", "typescript"])
+        );
+        assert_eq!(detected.frameworks, set(&["fastapi", "jest", "pytest"]));
+        assert_eq!(detected.toolchains, set(&["pip", "yarn"]));
+        assert!(detected.detected_at.is_some());
+    }
+
+    #[test]
+    fn test_recorded_toolchains_are_resolved_for_paths() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::create_dir_all(root.join("svc/.rhema")).unwrap();
+        fs::create_dir_all(root.join("svc/src")).unwrap();
+        fs::write(
+            root.join("svc/.rhema/rhema.yaml"),
+            "name: svc\nscope_type: service\nversion: \"1.0.0\"\n",
+        )
+        .unwrap();
+        fs::write(root.join("svc/go.mod"), "module example.com/svc\n").unwrap();
+        fs::write(root.join("svc/src/main.go"), "package main\n").unwrap();
+
+        // Detected on the fly until recorded
+        let resolved = resolve_toolchains(&root.join("svc/src/main.go")).unwrap();
+        assert!(resolved.toolchains.contains("go"));
+
+        let scope = Scope::new(root.join("svc/.rhema")).unwrap();
+        let recorded = record_toolchains(&scope).unwrap();
+        let reloaded = Scope::new(root.join("svc/.rhema")).unwrap();
+        assert_eq!(reloaded.definition.toolchains.as_ref(), Some(&recorded));
+
+        fs::remove_file(root.join("svc/go.mod")).unwrap();
+        let resolved = resolve_toolchains(&root.join("svc/src")).unwrap();
+        assert_eq!(resolved.languages, recorded.languages);
+        assert!(resolved.toolchains.contains("go"));
+    }
+}
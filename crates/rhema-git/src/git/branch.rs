@@ -16,6 +16,7 @@
 
 use chrono::{DateTime, Utc};
 use git2::{BranchType, Repository};
+use rhema_core::roots::is_repository_boundary;
 use rhema_core::{RhemaError, RhemaResult};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -477,13 +478,15 @@ impl BranchContextManager {
     /// Discover context files in current branch
     pub fn discover_context_files(&self) -> RhemaResult<Vec<PathBuf>> {
         let mut context_files = Vec::new();
-        let repo_path = self.repo.path().parent().ok_or_else(|| {
+        let repo_path = self.repo.workdir().ok_or_else(|| {
             RhemaError::GitError(git2::Error::from_str("Invalid repository path"))
         })?;
 
-        // Walk through repository to find context files
+        // Walk through repository to find context files, leaving submodules
+        // and nested repositories to their own manager
         for entry in walkdir::WalkDir::new(repo_path)
             .into_iter()
+            .filter_entry(|e| e.depth() == 0 || !is_repository_boundary(e.path()))
             .filter_map(|e| e.ok())
         {
             let path = entry.path();
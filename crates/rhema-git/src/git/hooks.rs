@@ -14,6 +14,7 @@
  * limitations under the License.
  */
 
+use crate::git::branch::{BranchContextManager, ValidationStatus};
 use crate::git::monitoring::GitMonitoringManager;
 use git2::Repository;
 use rhema_core::RhemaResult;
@@ -711,7 +712,7 @@ impl HookManager {
 
     /// Enhanced hook installation with advanced features
     pub fn install_hooks(&self) -> RhemaResult<()> {
-        let hooks_dir = self.repo.path().join(".git").join("hooks");
+        let hooks_dir = self.repo.path().join("hooks");
 
         if !hooks_dir.exists() {
             fs::create_dir_all(&hooks_dir)?;
@@ -816,7 +817,7 @@ impl HookManager {
 
     /// Verify hook integrity
     fn verify_hook_integrity(&self) -> RhemaResult<()> {
-        let hooks_dir = self.repo.path().join(".git").join("hooks");
+        let hooks_dir = self.repo.path().join("hooks");
 
         for hook_type in &[HookType::PreCommit, HookType::PostCommit, HookType::PrePush] {
             let hook_file = hooks_dir.join(hook_type.filename());
@@ -1114,12 +1115,7 @@ echo "Rhema pre-rebase validation completed successfully"
         }
 
         // Validate hook script exists
-        let hook_path = self
-            .repo
-            .path()
-            .join(".git")
-            .join("hooks")
-            .join(hook_type.filename());
+        let hook_path = self.repo.path().join("hooks").join(hook_type.filename());
         if !hook_path.exists() {
             warnings.push(format!(
                 "Hook script {} does not exist",
@@ -1229,7 +1225,7 @@ echo "Rhema pre-rebase validation completed successfully"
         }
 
         // Check for loose objects
-        let loose_objects_dir = self.repo.path().join(".git").join("objects");
+        let loose_objects_dir = self.repo.path().join("objects");
         if let Ok(entries) = std::fs::read_dir(loose_objects_dir) {
             let loose_count = entries.filter_map(|e| e.ok()).count();
             if loose_count > 1000 {
@@ -1527,7 +1523,7 @@ echo "Rhema pre-rebase validation completed successfully"
     fn execute_post_checkout(
         &self,
         messages: &mut Vec<String>,
-        _errors: &mut Vec<String>,
+        errors: &mut Vec<String>,
         _warnings: &mut Vec<String>,
     ) -> RhemaResult<()> {
         if let Some(config) = &self.config.hook_specific.post_checkout {
@@ -1538,7 +1534,12 @@ echo "Rhema pre-rebase validation completed successfully"
 
             if config.validate_branch_context {
                 messages.push("Validating branch context...".to_string());
-                // TODO: Implement branch validation
+                // Hooks run per repository, so this only covers the checked-out root
+                let workdir = self.repo.workdir().unwrap_or_else(|| self.repo.path());
+                let mut branches = BranchContextManager::new(Repository::open(workdir)?);
+                if let ValidationStatus::Invalid(issues) = branches.validate_branch_context()? {
+                    errors.extend(issues);
+                }
             }
 
             if config.update_environment {
@@ -5,6 +5,7 @@ pub mod feature_automation;
 pub mod history;
 pub mod hooks;
 pub mod monitoring;
+pub mod roots;
 pub mod security;
 pub mod version_management;
 pub mod workflow;
@@ -21,6 +22,9 @@ pub use version_management::{
     VersionManagementResult, VersionManager,
 };
 
+// Export multi-root dispatch types
+pub use roots::{MultiRootGit, RootValidation};
+
 // Export automation types
 pub use automation::{
     default_automation_config, AutomationConfig, GitAutomationManager, TaskResult, TaskStatus,
@@ -0,0 +1,168 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Git operations across the main repository and its nested roots.
+//!
+//! Every root configured under `repository_roots` is a repository of its own,
+//! with its own hooks directory and branches. Paths are routed to the root
+//! that owns them, hooks are installed into each root, and branch contexts
+//! are validated root by root.
+
+use crate::git::branch::{BranchContextManager, ValidationStatus};
+use crate::git::hooks::{HookConfig, HookManager};
+use git2::Repository;
+use rhema_core::roots::{RepositoryRoot, RepositoryRoots};
+use rhema_core::{RhemaError, RhemaResult};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Branch context validation of one root
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RootValidation {
+    pub root: RepositoryRoot,
+    pub branch: String,
+    pub status: ValidationStatus,
+}
+
+/// Dispatches git operations to the repository owning each path
+pub struct MultiRootGit {
+    repo_root: PathBuf,
+    roots: RepositoryRoots,
+}
+
+impl MultiRootGit {
+    /// Roots of the repository at `repo_root` as configured
+    pub fn open(repo_root: &Path) -> RhemaResult<Self> {
+        Ok(Self::with_roots(
+            repo_root,
+            RepositoryRoots::load(repo_root)?,
+        ))
+    }
+
+    pub fn with_roots(repo_root: &Path, roots: RepositoryRoots) -> Self {
+        Self {
+            repo_root: repo_root.to_path_buf(),
+            roots,
+        }
+    }
+
+    pub fn roots(&self) -> &[RepositoryRoot] {
+        self.roots.roots()
+    }
+
+    /// Root owning `path`; relative paths are taken from the main repository
+    pub fn root_for_path(&self, path: &Path) -> RhemaResult<&RepositoryRoot> {
+        let path = self.repo_root.join(path);
+        if self.roots.is_excluded(&path) {
+            return Err(RhemaError::ConfigError(format!(
+                "{} is in a repository not listed under repository_roots",
+                path.display()
+            )));
+        }
+        self.roots.root_for_path(&path).ok_or_else(|| {
+            RhemaError::ConfigError(format!("{} is outside the repository", path.display()))
+        })
+    }
+
+    /// Open the repository owning `path`
+    pub fn repository_for(&self, path: &Path) -> RhemaResult<(&RepositoryRoot, Repository)> {
+        let root = self.root_for_path(path)?;
+        Ok((root, open(root)?))
+    }
+
+    /// Install Rhema hooks into every root, returning the roots done
+    pub fn install_hooks(&self, config: &HookConfig) -> RhemaResult<Vec<&RepositoryRoot>> {
+        let mut installed = Vec::new();
+        for root in self.roots() {
+            HookManager::new(open(root)?, config.clone(), None).install_hooks()?;
+            installed.push(root);
+        }
+        Ok(installed)
+    }
+
+    /// Validate the branch context of every root on its own checked-out branch
+    pub fn validate_branch_contexts(&self) -> RhemaResult<Vec<RootValidation>> {
+        self.roots()
+            .iter()
+            .map(|root| {
+                let mut branches = BranchContextManager::new(open(root)?);
+                Ok(RootValidation {
+                    root: root.clone(),
+                    branch: branches.get_current_branch()?,
+                    status: branches.validate_branch_context()?,
+                })
+            })
+            .collect()
+    }
+}
+
+fn open(root: &RepositoryRoot) -> RhemaResult<Repository> {
+    Repository::open(&root.path).map_err(|e| {
+        RhemaError::ConfigError(format!(
+            "Failed to open repository root {}: {}",
+            root.relative, e
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rhema_core::roots::{RepositoryRootsConfig, RootKind};
+    use tempfile::TempDir;
+
+    fn init_with_commit(path: &Path) -> Repository {
+        let repo = Repository::init(path).unwrap();
+        let signature = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        repo.commit(Some("HEAD"), &signature, &signature, "init", &tree, &[])
+            .unwrap();
+        drop(tree);
+        repo
+    }
+
+    #[test]
+    fn test_paths_dispatch_to_owning_repository() {
+        let temp = TempDir::new().unwrap();
+        init_with_commit(temp.path());
+        init_with_commit(&temp.path().join("vendor/lib"));
+        init_with_commit(&temp.path().join("tools/skipped"));
+
+        let config = RepositoryRootsConfig {
+            descend: true,
+            exclude: vec!["tools/skipped".to_string()],
+            ..Default::default()
+        };
+        let git =
+            MultiRootGit::with_roots(temp.path(), RepositoryRoots::discover(temp.path(), &config));
+
+        let (root, repo) = git
+            .repository_for(Path::new("vendor/lib/src/main.rs"))
+            .unwrap();
+        assert_eq!(root.relative, "vendor/lib");
+        assert_eq!(root.kind, RootKind::Nested);
+        assert!(repo.path().starts_with(temp.path().join("vendor/lib")));
+        assert!(git.root_for_path(Path::new("docs")).unwrap().is_primary());
+        assert!(git.root_for_path(Path::new("tools/skipped/src")).is_err());
+
+        let validations = git.validate_branch_contexts().unwrap();
+        assert_eq!(validations.len(), 2);
+        assert!(validations
+            .iter()
+            .all(|v| v.status == ValidationStatus::Valid));
+    }
+}
@@ -71,7 +71,8 @@ pub struct GitHooksManager {
 impl GitHooksManager {
     /// Create a new Git hooks manager
     pub fn new(repo_path: &Path) -> RhemaResult<Self> {
-        let hooks_dir = repo_path.join(".git").join("hooks");
+        // Submodules keep their hooks in the parent's git directory
+        let hooks_dir = crate::utils::get_repo(repo_path)?.path().join("hooks");
 
         if !hooks_dir.exists() {
             return Err(RhemaError::ConfigError(
@@ -0,0 +1,739 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use rhema_core::file_ops::{read_yaml_file, write_yaml_file};
+use rhema_core::{Knowledge, KnowledgeEntry, PatternEntry, Patterns};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use tracing::debug;
+
+use crate::embedding::EmbeddingManager;
+use crate::types::{KnowledgeError, KnowledgeResult};
+
+/// Custom field holding suggestions that still await confirmation
+pub const PENDING_TAGS_FIELD: &str = "auto_tag";
+
+/// Custom field holding a pattern's tags
+pub const PATTERN_TAGS_FIELD: &str = "tags";
+
+/// Default location of the taxonomy file inside a scope
+pub const TAXONOMY_FILE: &str = "taxonomy.yaml";
+
+/// A category in the configurable taxonomy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaxonomyCategory {
+    /// Category name
+    pub name: String,
+
+    /// Description used to embed the category
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Tags commonly associated with this category
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Taxonomy the auto-tagger may suggest from
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Taxonomy {
+    /// Known categories
+    #[serde(default)]
+    pub categories: Vec<TaxonomyCategory>,
+
+    /// Only suggest categories that appear in the taxonomy
+    #[serde(default)]
+    pub restrict_to_taxonomy: bool,
+}
+
+impl Taxonomy {
+    /// Load a taxonomy file, returning an empty taxonomy when it does not exist
+    pub fn load(path: &Path) -> KnowledgeResult<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)?;
+        serde_yaml::from_str(&content).map_err(|e| {
+            KnowledgeError::ConfigurationError(format!(
+                "Invalid taxonomy {}: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+}
+
+/// Auto-tagger configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoTagConfig {
+    /// Embedding model to use (defaults to the manager's default model)
+    pub model: Option<String>,
+
+    /// Number of most similar tagged entries that vote on suggestions
+    pub neighbours: usize,
+
+    /// Minimum similarity for a neighbour or taxonomy category to count
+    pub min_similarity: f32,
+
+    /// Minimum normalized vote share for a tag to be suggested
+    pub min_tag_confidence: f32,
+
+    /// Maximum number of tags suggested per entry
+    pub max_tags: usize,
+}
+
+impl Default for AutoTagConfig {
+    fn default() -> Self {
+        Self {
+            model: None,
+            neighbours: 5,
+            min_similarity: 0.3,
+            min_tag_confidence: 0.3,
+            max_tags: 5,
+        }
+    }
+}
+
+/// Kind of context entry being tagged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TaggableKind {
+    Knowledge,
+    Pattern,
+}
+
+/// Entry view the auto-tagger works on
+#[derive(Debug, Clone)]
+pub struct TaggableEntry {
+    pub id: String,
+    pub kind: TaggableKind,
+    pub text: String,
+    pub category: Option<String>,
+    pub tags: Vec<String>,
+}
+
+impl TaggableEntry {
+    pub fn is_tagged(&self) -> bool {
+        match self.kind {
+            TaggableKind::Knowledge => self.category.is_some() || !self.tags.is_empty(),
+            // Every pattern has its type as category, so only tags count
+            TaggableKind::Pattern => !self.tags.is_empty(),
+        }
+    }
+}
+
+impl From<&KnowledgeEntry> for TaggableEntry {
+    fn from(entry: &KnowledgeEntry) -> Self {
+        Self {
+            id: entry.id.clone(),
+            kind: TaggableKind::Knowledge,
+            text: format!("{}\n{}", entry.title, entry.content),
+            category: entry.category.clone(),
+            tags: entry.tags.clone().unwrap_or_default(),
+        }
+    }
+}
+
+impl From<&PatternEntry> for TaggableEntry {
+    fn from(entry: &PatternEntry) -> Self {
+        // Patterns have no first-class tags, so they live in custom fields
+        let tags = entry
+            .custom
+            .get(PATTERN_TAGS_FIELD)
+            .and_then(|v| serde_yaml::from_value::<Vec<String>>(v.clone()).ok())
+            .unwrap_or_default();
+        Self {
+            id: entry.id.clone(),
+            kind: TaggableKind::Pattern,
+            text: format!("{}\n{}", entry.name, entry.description),
+            category: Some(entry.pattern_type.clone()),
+            tags,
+        }
+    }
+}
+
+/// Suggested category and tags for an entry
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TagSuggestion {
+    /// Suggested category
+    pub category: Option<String>,
+
+    /// Suggested tags, most confident first
+    pub tags: Vec<String>,
+
+    /// Confidence of the category suggestion (0.0 - 1.0)
+    pub confidence: f32,
+
+    /// Whether the suggestion still needs to be confirmed
+    pub pending: bool,
+}
+
+impl TagSuggestion {
+    pub fn is_empty(&self) -> bool {
+        self.category.is_none() && self.tags.is_empty()
+    }
+
+    /// Serialize the suggestion into an entry's custom fields
+    pub fn to_value(&self) -> Value {
+        serde_yaml::to_value(self).unwrap_or(Value::Null)
+    }
+}
+
+struct TaggedExample {
+    embedding: Vec<f32>,
+    category: Option<String>,
+    tags: Vec<String>,
+}
+
+/// Suggests categories and tags from embedding similarity to already tagged
+/// entries and to the taxonomy
+pub struct AutoTagger {
+    embedding_manager: Arc<EmbeddingManager>,
+    taxonomy: Taxonomy,
+    config: AutoTagConfig,
+    examples: Vec<TaggedExample>,
+    category_embeddings: Vec<(String, Vec<f32>)>,
+}
+
+impl AutoTagger {
+    /// Create an auto-tagger, embedding the taxonomy categories up front
+    pub async fn new(
+        embedding_manager: Arc<EmbeddingManager>,
+        taxonomy: Taxonomy,
+        config: AutoTagConfig,
+    ) -> KnowledgeResult<Self> {
+        let mut category_embeddings = Vec::with_capacity(taxonomy.categories.len());
+        for category in &taxonomy.categories {
+            let text = format!(
+                "{} {} {}",
+                category.name,
+                category.description.as_deref().unwrap_or_default(),
+                category.tags.join(" ")
+            );
+            let embedding = embedding_manager
+                .embed(&text, config.model.as_deref())
+                .await?;
+            category_embeddings.push((category.name.clone(), embedding));
+        }
+
+        Ok(Self {
+            embedding_manager,
+            taxonomy,
+            config,
+            examples: Vec::new(),
+            category_embeddings,
+        })
+    }
+
+    /// Learn from entries that already carry a category or tags
+    pub async fn index_examples(&mut self, entries: &[TaggableEntry]) -> KnowledgeResult<usize> {
+        let tagged: Vec<&TaggableEntry> = entries.iter().filter(|e| e.is_tagged()).collect();
+        for entry in &tagged {
+            let embedding = self.embed(&entry.text).await?;
+            self.examples.push(TaggedExample {
+                embedding,
+                category: entry.category.clone(),
+                tags: entry.tags.clone(),
+            });
+        }
+        debug!("Auto-tagger indexed {} tagged examples", tagged.len());
+        Ok(tagged.len())
+    }
+
+    /// Suggest a category and tags for an entry
+    pub async fn suggest(&self, entry: &TaggableEntry) -> KnowledgeResult<TagSuggestion> {
+        let embedding = self.embed(&entry.text).await?;
+        let model = self.config.model.as_deref();
+
+        let mut neighbours = Vec::with_capacity(self.examples.len());
+        for example in &self.examples {
+            let score = self
+                .embedding_manager
+                .similarity(&embedding, &example.embedding, model)
+                .await?;
+            if score >= self.config.min_similarity {
+                neighbours.push((score, example));
+            }
+        }
+        neighbours.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        neighbours.truncate(self.config.neighbours);
+
+        let mut category_votes: HashMap<String, f32> = HashMap::new();
+        let mut tag_votes: HashMap<String, f32> = HashMap::new();
+        let mut total_weight = 0.0;
+
+        for (score, example) in &neighbours {
+            total_weight += score;
+            if let Some(category) = &example.category {
+                *category_votes.entry(category.clone()).or_default() += score;
+            }
+            for tag in &example.tags {
+                *tag_votes.entry(tag.clone()).or_default() += score;
+            }
+        }
+
+        for (name, category_embedding) in &self.category_embeddings {
+            let score = self
+                .embedding_manager
+                .similarity(&embedding, category_embedding, model)
+                .await?;
+            if score >= self.config.min_similarity {
+                total_weight += score;
+                *category_votes.entry(name.clone()).or_default() += score;
+            }
+        }
+
+        if self.taxonomy.restrict_to_taxonomy {
+            category_votes
+                .retain(|name, _| self.taxonomy.categories.iter().any(|c| &c.name == name));
+        }
+
+        let (category, confidence) = match best(&category_votes) {
+            Some((name, votes)) if total_weight > 0.0 => (Some(name), votes / total_weight),
+            _ => (None, 0.0),
+        };
+
+        if let Some(category) = category
+            .as_ref()
+            .and_then(|name| self.taxonomy.categories.iter().find(|c| &c.name == name))
+        {
+            for tag in &category.tags {
+                *tag_votes.entry(tag.clone()).or_default() += confidence * total_weight;
+            }
+        }
+
+        let mut tags: Vec<(String, f32)> = tag_votes
+            .into_iter()
+            .filter(|(tag, _)| !entry.tags.contains(tag))
+            .filter(|(_, votes)| {
+                total_weight > 0.0 && votes / total_weight >= self.config.min_tag_confidence
+            })
+            .collect();
+        tags.sort_by(|a, b| {
+            b.1.partial_cmp(&a.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.0.cmp(&b.0))
+        });
+        tags.truncate(self.config.max_tags);
+
+        Ok(TagSuggestion {
+            category: category.filter(|c| entry.category.as_ref() != Some(c)),
+            tags: tags.into_iter().map(|(tag, _)| tag).collect(),
+            confidence,
+            pending: true,
+        })
+    }
+
+    async fn embed(&self, text: &str) -> KnowledgeResult<Vec<f32>> {
+        self.embedding_manager
+            .embed(text, self.config.model.as_deref())
+            .await
+    }
+}
+
+/// Store a suggestion on a knowledge entry as pending
+pub fn mark_pending(entry: &mut KnowledgeEntry, suggestion: &TagSuggestion) {
+    entry
+        .custom
+        .insert(PENDING_TAGS_FIELD.to_string(), suggestion.to_value());
+}
+
+/// Read a pending suggestion from a knowledge entry
+pub fn pending_suggestion(entry: &KnowledgeEntry) -> Option<TagSuggestion> {
+    pending_in(&entry.custom)
+}
+
+/// Apply a suggestion to a knowledge entry, clearing any pending marker
+pub fn apply_suggestion(entry: &mut KnowledgeEntry, suggestion: &TagSuggestion) {
+    if entry.category.is_none() {
+        entry.category = suggestion.category.clone();
+    }
+    let tags = entry.tags.get_or_insert_with(Vec::new);
+    for tag in &suggestion.tags {
+        if !tags.contains(tag) {
+            tags.push(tag.clone());
+        }
+    }
+    entry.custom.remove(PENDING_TAGS_FIELD);
+}
+
+/// Store a suggestion on a pattern as pending
+pub fn mark_pattern_pending(pattern: &mut PatternEntry, suggestion: &TagSuggestion) {
+    pattern
+        .custom
+        .insert(PENDING_TAGS_FIELD.to_string(), suggestion.to_value());
+}
+
+/// Read a pending suggestion from a pattern
+pub fn pending_pattern_suggestion(pattern: &PatternEntry) -> Option<TagSuggestion> {
+    pending_in(&pattern.custom)
+}
+
+/// Add a suggestion's tags to a pattern, clearing any pending marker. The
+/// pattern type stays its category.
+pub fn apply_pattern_suggestion(pattern: &mut PatternEntry, suggestion: &TagSuggestion) {
+    let mut tags = TaggableEntry::from(&*pattern).tags;
+    for tag in &suggestion.tags {
+        if !tags.contains(tag) {
+            tags.push(tag.clone());
+        }
+    }
+    pattern.custom.insert(
+        PATTERN_TAGS_FIELD.to_string(),
+        serde_yaml::to_value(tags).unwrap_or(Value::Null),
+    );
+    pattern.custom.remove(PENDING_TAGS_FIELD);
+}
+
+fn pending_in(custom: &HashMap<String, Value>) -> Option<TagSuggestion> {
+    custom
+        .get(PENDING_TAGS_FIELD)
+        .and_then(|v| serde_yaml::from_value::<TagSuggestion>(v.clone()).ok())
+        .filter(|s| s.pending)
+}
+
+/// How suggestions produced by [`auto_tag_scope`] are handled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutoTagMode {
+    /// Store suggestions on the entries for later confirmation
+    Pending,
+    /// Apply suggestions to the entries immediately
+    Apply,
+    /// Apply suggestions previously stored as pending
+    AcceptPending,
+    /// Compute suggestions without writing anything
+    DryRun,
+}
+
+/// Suggest tags for the untagged knowledge entries (insights included) and
+/// patterns of a scope, or for the given entry ids, learning from the scope's
+/// tagged knowledge and patterns. Patterns keep their type as category, so
+/// only tags are suggested for them.
+pub async fn auto_tag_scope(
+    scope_path: &Path,
+    embedding_manager: Arc<EmbeddingManager>,
+    config: AutoTagConfig,
+    mode: AutoTagMode,
+    only_ids: Option<&[String]>,
+) -> rhema_core::RhemaResult<Vec<(String, TagSuggestion)>> {
+    let knowledge_file = scope_path.join("knowledge.yaml");
+    let patterns_file = scope_path.join("patterns.yaml");
+    let mut knowledge: Option<Knowledge> = read_if_exists(&knowledge_file)?;
+    let mut patterns: Option<Patterns> = read_if_exists(&patterns_file)?;
+    let entries = knowledge
+        .as_mut()
+        .map(|k| k.entries.as_mut_slice())
+        .unwrap_or_default();
+    let pattern_entries = patterns
+        .as_mut()
+        .map(|p| p.patterns.as_mut_slice())
+        .unwrap_or_default();
+    let mut knowledge_results = Vec::new();
+    let mut pattern_results = Vec::new();
+
+    if mode == AutoTagMode::AcceptPending {
+        for entry in entries.iter_mut() {
+            if let Some(suggestion) = pending_suggestion(entry) {
+                apply_suggestion(entry, &suggestion);
+                knowledge_results.push((entry.id.clone(), suggestion));
+            }
+        }
+        for pattern in pattern_entries.iter_mut() {
+            if let Some(suggestion) = pending_pattern_suggestion(pattern) {
+                apply_pattern_suggestion(pattern, &suggestion);
+                pattern_results.push((pattern.id.clone(), suggestion));
+            }
+        }
+    } else {
+        let taxonomy = Taxonomy::load(&scope_path.join(TAXONOMY_FILE))?;
+        let mut tagger = AutoTagger::new(embedding_manager, taxonomy, config).await?;
+
+        let examples: Vec<TaggableEntry> = entries
+            .iter()
+            .map(TaggableEntry::from)
+            .chain(pattern_entries.iter().map(TaggableEntry::from))
+            .collect();
+        tagger.index_examples(&examples).await?;
+
+        let selected = |entry: &TaggableEntry| match only_ids {
+            Some(ids) => ids.contains(&entry.id),
+            None => !entry.is_tagged(),
+        };
+
+        for entry in entries.iter_mut() {
+            let taggable = TaggableEntry::from(&*entry);
+            if !selected(&taggable) {
+                continue;
+            }
+
+            let suggestion = tagger.suggest(&taggable).await?;
+            if suggestion.is_empty() {
+                continue;
+            }
+            match mode {
+                AutoTagMode::Pending => mark_pending(entry, &suggestion),
+                AutoTagMode::Apply => apply_suggestion(entry, &suggestion),
+                _ => {}
+            }
+            knowledge_results.push((entry.id.clone(), suggestion));
+        }
+
+        for pattern in pattern_entries.iter_mut() {
+            let taggable = TaggableEntry::from(&*pattern);
+            if !selected(&taggable) {
+                continue;
+            }
+
+            let suggestion = TagSuggestion {
+                category: None,
+                ..tagger.suggest(&taggable).await?
+            };
+            if suggestion.is_empty() {
+                continue;
+            }
+            match mode {
+                AutoTagMode::Pending => mark_pattern_pending(pattern, &suggestion),
+                AutoTagMode::Apply => apply_pattern_suggestion(pattern, &suggestion),
+                _ => {}
+            }
+            pattern_results.push((pattern.id.clone(), suggestion));
+        }
+    }
+
+    if mode != AutoTagMode::DryRun {
+        if let Some(knowledge) = knowledge.filter(|_| !knowledge_results.is_empty()) {
+            write_yaml_file(&knowledge_file, &knowledge)?;
+        }
+        if let Some(patterns) = patterns.filter(|_| !pattern_results.is_empty()) {
+            write_yaml_file(&patterns_file, &patterns)?;
+        }
+    }
+
+    knowledge_results.extend(pattern_results);
+    Ok(knowledge_results)
+}
+
+fn read_if_exists<T: DeserializeOwned>(path: &Path) -> rhema_core::RhemaResult<Option<T>> {
+    if path.exists() {
+        read_yaml_file(path).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+fn best(votes: &HashMap<String, f32>) -> Option<(String, f32)> {
+    votes
+        .iter()
+        .max_by(|a, b| {
+            a.1.partial_cmp(b.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| b.0.cmp(a.0))
+        })
+        .map(|(name, votes)| (name.clone(), *votes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedding::{
+        EmbeddingDevice, EmbeddingModel, EmbeddingModelInfo, EmbeddingModelType,
+    };
+    use async_trait::async_trait;
+
+    /// Bag-of-words model so that similar texts get similar embeddings
+    struct WordModel;
+
+    const VOCABULARY: &[&str] = &["cache", "redis", "query", "sql", "deploy", "kubernetes"];
+
+    #[async_trait]
+    impl EmbeddingModel for WordModel {
+        async fn embed(&self, text: &str) -> KnowledgeResult<Vec<f32>> {
+            let text = text.to_lowercase();
+            Ok(VOCABULARY
+                .iter()
+                .map(|w| if text.contains(w) { 1.0 } else { 0.0 })
+                .collect())
+        }
+
+        async fn embed_batch(&self, texts: &[String]) -> KnowledgeResult<Vec<Vec<f32>>> {
+            let mut out = Vec::new();
+            for text in texts {
+                out.push(self.embed(text).await?);
+            }
+            Ok(out)
+        }
+
+        async fn similarity(&self, a: &[f32], b: &[f32]) -> KnowledgeResult<f32> {
+            let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+            let na: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+            let nb: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+            Ok(if na == 0.0 || nb == 0.0 {
+                0.0
+            } else {
+                dot / (na * nb)
+            })
+        }
+
+        async fn dimension(&self) -> usize {
+            VOCABULARY.len()
+        }
+
+        async fn model_info(&self) -> EmbeddingModelInfo {
+            EmbeddingModelInfo {
+                name: "words".to_string(),
+                version: "1".to_string(),
+                dimension: VOCABULARY.len(),
+                max_length: 512,
+                model_type: EmbeddingModelType::Custom("words".to_string()),
+                device: EmbeddingDevice::CPU,
+            }
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    fn entry(id: &str, text: &str, category: Option<&str>, tags: &[&str]) -> TaggableEntry {
+        TaggableEntry {
+            id: id.to_string(),
+            kind: TaggableKind::Knowledge,
+            text: text.to_string(),
+            category: category.map(|c| c.to_string()),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    async fn tagger(taxonomy: Taxonomy) -> AutoTagger {
+        let manager = Arc::new(EmbeddingManager::new_dummy());
+        manager
+            .add_model("words".to_string(), Arc::new(WordModel))
+            .await;
+        let config = AutoTagConfig {
+            model: Some("words".to_string()),
+            ..Default::default()
+        };
+        AutoTagger::new(manager, taxonomy, config).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_suggests_from_similar_entries() {
+        let mut tagger = tagger(Taxonomy::default()).await;
+        tagger
+            .index_examples(&[
+                entry("1", "Redis cache eviction", Some("performance"), &["cache"]),
+                entry(
+                    "2",
+                    "Kubernetes deploy rollout",
+                    Some("operations"),
+                    &["deploy"],
+                ),
+            ])
+            .await
+            .unwrap();
+
+        let suggestion = tagger
+            .suggest(&entry("3", "Warm the redis cache on start", None, &[]))
+            .await
+            .unwrap();
+        assert_eq!(suggestion.category.as_deref(), Some("performance"));
+        assert_eq!(suggestion.tags, vec!["cache".to_string()]);
+        assert!(suggestion.pending);
+    }
+
+    #[tokio::test]
+    async fn test_taxonomy_restricts_categories() {
+        let taxonomy = Taxonomy {
+            categories: vec![TaxonomyCategory {
+                name: "data".to_string(),
+                description: Some("sql query tuning".to_string()),
+                tags: vec!["sql".to_string()],
+            }],
+            restrict_to_taxonomy: true,
+        };
+        let mut tagger = tagger(taxonomy).await;
+        tagger
+            .index_examples(&[entry("1", "Slow sql query", Some("misc"), &[])])
+            .await
+            .unwrap();
+
+        let suggestion = tagger
+            .suggest(&entry("2", "Index the sql query", None, &[]))
+            .await
+            .unwrap();
+        assert_eq!(suggestion.category.as_deref(), Some("data"));
+        assert!(suggestion.tags.contains(&"sql".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_auto_tag_scope_tags_patterns_and_dry_run_writes_nothing() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let patterns_file = temp.path().join("patterns.yaml");
+        let patterns = r#"patterns:
+- id: p1
+  name: Redis cache aside
+  description: Read through the redis cache
+  pattern_type: performance
+  usage: recommended
+  created_at: 2025-01-01T00:00:00Z
+  tags: [cache]
+- id: p2
+  name: Warm the cache
+  description: Fill the redis cache on start
+  pattern_type: performance
+  usage: optional
+  created_at: 2025-01-01T00:00:00Z
+"#;
+        std::fs::write(&patterns_file, patterns).unwrap();
+
+        let manager = Arc::new(EmbeddingManager::new_dummy());
+        manager
+            .add_model("words".to_string(), Arc::new(WordModel))
+            .await;
+        let config = AutoTagConfig {
+            model: Some("words".to_string()),
+            ..Default::default()
+        };
+
+        let suggestions = auto_tag_scope(
+            temp.path(),
+            manager.clone(),
+            config.clone(),
+            AutoTagMode::DryRun,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].0, "p2");
+        assert_eq!(suggestions[0].1.tags, vec!["cache".to_string()]);
+        assert_eq!(suggestions[0].1.category, None);
+        assert!(!temp.path().join("knowledge.yaml").exists());
+        assert_eq!(std::fs::read_to_string(&patterns_file).unwrap(), patterns);
+
+        auto_tag_scope(temp.path(), manager, config, AutoTagMode::Apply, None)
+            .await
+            .unwrap();
+        let patterns: Patterns = read_yaml_file(&patterns_file).unwrap();
+        assert_eq!(
+            TaggableEntry::from(&patterns.patterns[1]).tags,
+            vec!["cache".to_string()]
+        );
+    }
+}
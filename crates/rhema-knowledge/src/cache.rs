@@ -30,6 +30,10 @@ use crate::types::{
     SemanticCacheEntry, UnifiedCacheResult,
 };
 use crate::vector::VectorStoreWrapper;
+use rhema_core::cache_report::{
+    hottest_keys, CacheReport, CacheTierReport, EvictionCause, EvictionCounts, TierLimit,
+    TuningAction, TuningRecommendation, HOTTEST_KEYS,
+};
 
 /// Error types for cache operations
 #[derive(Error, Debug)]
@@ -105,6 +109,7 @@ pub struct CacheStats {
     pub eviction_count: u64,
     pub memory_usage_bytes: u64,
     pub semantic_hit_count: u64,
+    pub evictions: EvictionCounts,
     #[serde(skip)]
     pub last_updated: Instant,
 }
@@ -122,6 +127,8 @@ impl<'de> serde::Deserialize<'de> for CacheStats {
             pub eviction_count: u64,
             pub memory_usage_bytes: u64,
             pub semantic_hit_count: u64,
+            #[serde(default)]
+            pub evictions: EvictionCounts,
         }
 
         let helper = CacheStatsHelper::deserialize(deserializer)?;
@@ -132,6 +139,7 @@ impl<'de> serde::Deserialize<'de> for CacheStats {
             eviction_count: helper.eviction_count,
             memory_usage_bytes: helper.memory_usage_bytes,
             semantic_hit_count: helper.semantic_hit_count,
+            evictions: helper.evictions,
             last_updated: Instant::now(),
         })
     }
@@ -448,6 +456,7 @@ impl SemanticMemoryCache {
                 eviction_count: 0,
                 memory_usage_bytes: 0,
                 semantic_hit_count: 0,
+                evictions: EvictionCounts::default(),
                 last_updated: Instant::now(),
             })),
         }
@@ -475,6 +484,7 @@ impl SemanticMemoryCache {
                 eviction_count: 0,
                 memory_usage_bytes: 0,
                 semantic_hit_count: 0,
+                evictions: EvictionCounts::default(),
                 last_updated: Instant::now(),
             })),
         }
@@ -548,6 +558,15 @@ impl SemanticMemoryCache {
     }
 
     pub async fn delete(&self, key: &str) -> KnowledgeResult<()> {
+        self.remove(key, EvictionCause::Invalidated).await
+    }
+
+    /// Remove an entry that outlived its TTL
+    pub async fn expire(&self, key: &str) -> KnowledgeResult<()> {
+        self.remove(key, EvictionCause::Expired).await
+    }
+
+    async fn remove(&self, key: &str, cause: EvictionCause) -> KnowledgeResult<()> {
         if let Some(entry) = self.entries.remove(key) {
             // Remove from semantic index
             if self.config.enable_semantic_indexing {
@@ -556,9 +575,9 @@ impl SemanticMemoryCache {
             }
 
             // Update stats
-            self.update_stats_delete(entry.1.data.len()).await;
+            self.update_stats_delete(entry.1.data.len(), cause).await;
 
-            debug!("Deleted entry from memory cache: {}", key);
+            debug!("Deleted entry from memory cache: {} ({})", key, cause);
         }
         Ok(())
     }
@@ -700,16 +719,18 @@ impl SemanticMemoryCache {
         stats.last_updated = Instant::now();
     }
 
-    async fn update_stats_delete(&self, size_bytes: usize) {
+    async fn update_stats_delete(&self, size_bytes: usize, cause: EvictionCause) {
         let mut stats = self.stats.write().await;
         stats.total_entries = stats.total_entries.saturating_sub(1);
         stats.memory_usage_bytes = stats.memory_usage_bytes.saturating_sub(size_bytes as u64);
+        stats.evictions.record(cause, 1);
         stats.last_updated = Instant::now();
     }
 
     async fn update_stats_eviction(&self, size_bytes: usize) {
         let mut stats = self.stats.write().await;
         stats.eviction_count += 1;
+        stats.evictions.record(EvictionCause::Capacity, 1);
         stats.total_entries = stats.total_entries.saturating_sub(1);
         stats.memory_usage_bytes = stats.memory_usage_bytes.saturating_sub(size_bytes as u64);
         stats.last_updated = Instant::now();
@@ -811,6 +832,7 @@ impl SemanticDiskCache {
                 eviction_count: 0,
                 memory_usage_bytes: 0,
                 semantic_hit_count: 0,
+                evictions: EvictionCounts::default(),
                 last_updated: Instant::now(),
             })),
         }
@@ -880,6 +902,7 @@ impl SemanticDiskCache {
                 eviction_count: 0,
                 memory_usage_bytes: 0,
                 semantic_hit_count: 0,
+                evictions: EvictionCounts::default(),
                 last_updated: Instant::now(),
             })),
         })
@@ -973,6 +996,15 @@ impl SemanticDiskCache {
     }
 
     pub async fn delete(&self, key: &str) -> KnowledgeResult<()> {
+        self.remove(key, EvictionCause::Invalidated).await
+    }
+
+    /// Remove an entry that outlived its TTL
+    pub async fn expire(&self, key: &str) -> KnowledgeResult<()> {
+        self.remove(key, EvictionCause::Expired).await
+    }
+
+    async fn remove(&self, key: &str, cause: EvictionCause) -> KnowledgeResult<()> {
         let file_path = self.cache_dir.join(format!("{}.cache", key));
 
         if file_path.exists() {
@@ -988,7 +1020,7 @@ impl SemanticDiskCache {
             self.remove_from_index(key).await;
 
             // Update stats
-            self.update_stats_delete(0).await; // Size unknown after deletion
+            self.update_stats_delete(0, cause).await; // Size unknown after deletion
         }
 
         debug!("Deleted entry from disk cache: {}", key);
@@ -1060,9 +1092,10 @@ impl SemanticDiskCache {
         stats.last_updated = Instant::now();
     }
 
-    async fn update_stats_delete(&self, _size_bytes: usize) {
+    async fn update_stats_delete(&self, _size_bytes: usize, cause: EvictionCause) {
         let mut stats = self.stats.write().await;
         stats.total_entries = stats.total_entries.saturating_sub(1);
+        stats.evictions.record(cause, 1);
         stats.last_updated = Instant::now();
     }
 
@@ -1258,6 +1291,57 @@ impl UnifiedCacheManager {
         }
     }
 
+    /// Statistics of each enabled tier for the consolidated cache report
+    ///
+    /// Entries carry their own TTL, so neither tier reports one.
+    pub async fn tier_reports(&self) -> Vec<CacheTierReport> {
+        let mut reports = Vec::new();
+
+        if self.config.enable_memory_cache {
+            let stats = self.memory_cache.stats().await;
+            let limits = &self.config.memory_cache_config;
+            let mut report = CacheTierReport::new("knowledge", "memory");
+            report.entries = self.memory_cache.entries.len();
+            report.max_entries = Some(limits.max_entries);
+            report.memory_bytes = stats.memory_usage_bytes;
+            report.max_memory_bytes = Some(limits.max_size_mb as u64 * 1024 * 1024);
+            report.hits = stats.hit_count;
+            report.misses = stats.miss_count;
+            report.evictions = stats.evictions;
+            report.hottest_keys = hottest_keys(
+                self.memory_cache
+                    .entries
+                    .iter()
+                    .map(|entry| (entry.key().clone(), entry.value().metadata.access_count)),
+                HOTTEST_KEYS,
+            );
+            reports.push(report);
+        }
+
+        if self.config.enable_disk_cache {
+            let stats = self.disk_cache.stats().await;
+            let index = self.disk_cache.index.read().await;
+            let mut report = CacheTierReport::new("knowledge", "disk");
+            report.entries = index.access_patterns.len();
+            report.memory_bytes = stats.memory_usage_bytes;
+            report.max_memory_bytes =
+                Some(self.config.disk_cache_config.max_size_gb as u64 * 1024 * 1024 * 1024);
+            report.hits = stats.hit_count;
+            report.misses = stats.miss_count;
+            report.evictions = stats.evictions;
+            report.hottest_keys = hottest_keys(
+                index
+                    .access_patterns
+                    .iter()
+                    .map(|(key, pattern)| (key, pattern.access_count)),
+                HOTTEST_KEYS,
+            );
+            reports.push(report);
+        }
+
+        reports
+    }
+
     /// Get cache from memory first, then disk
     pub async fn get(&self, key: &str) -> KnowledgeResult<Option<UnifiedCacheResult>> {
         // Try memory cache first
@@ -1458,6 +1542,19 @@ impl UnifiedCacheManager {
         Ok(())
     }
 
+    /// Remove an entry that outlived its TTL from both memory and disk
+    pub async fn expire(&self, key: &str) -> KnowledgeResult<()> {
+        if self.config.enable_memory_cache {
+            self.memory_cache.expire(key).await?;
+        }
+
+        if self.config.enable_disk_cache {
+            self.disk_cache.expire(key).await?;
+        }
+
+        Ok(())
+    }
+
     /// Persist cache to disk for recovery across restarts
     pub async fn persist_cache(&self) -> KnowledgeResult<()> {
         if !self.config.enable_disk_cache {
@@ -1973,6 +2070,40 @@ pub struct CacheOptimizerConfig {
     pub optimization_interval_minutes: u64,
     pub performance_threshold: f64,
     pub max_optimization_actions: usize,
+    #[serde(default)]
+    pub tuning: CacheTuningThresholds,
+}
+
+/// Thresholds behind [`CacheOptimizer::recommend_tuning`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheTuningThresholds {
+    /// Tiers with fewer requests are too quiet to judge
+    pub min_requests: u64,
+    /// Hit ratio below which a tier losing entries to capacity is too small
+    pub target_hit_ratio: f64,
+    /// Capacity evictions per request above which a tier is too small
+    pub capacity_eviction_ratio: f64,
+    /// Fill at which a tier counts as full
+    pub full_utilization: f64,
+    /// Share of hits served by the hottest keys at which the rest of a full
+    /// tier counts as cold
+    pub hot_key_share: f64,
+    /// Factor applied to a tier's limit when recommending a larger one
+    pub growth_factor: f64,
+}
+
+impl Default for CacheTuningThresholds {
+    fn default() -> Self {
+        Self {
+            min_requests: 100,
+            target_hit_ratio: 0.8,
+            capacity_eviction_ratio: 0.1,
+            full_utilization: 0.9,
+            hot_key_share: 0.8,
+            growth_factor: 1.5,
+        }
+    }
 }
 
 /// Optimization action
@@ -2005,6 +2136,7 @@ impl Default for CacheOptimizerConfig {
             optimization_interval_minutes: 30,
             performance_threshold: 0.8,
             max_optimization_actions: 10,
+            tuning: CacheTuningThresholds::default(),
         }
     }
 }
@@ -2227,7 +2359,7 @@ impl CacheOptimizer {
         // Remove expired entries
         let expired_count = expired_keys.len();
         for key in expired_keys {
-            cache_manager.delete(&key).await?;
+            cache_manager.expire(&key).await?;
         }
 
         info!("Cleaned up {} expired cache entries", expired_count);
@@ -2289,6 +2421,99 @@ impl CacheOptimizer {
 
         recommendations
     }
+
+    /// Consolidated report of the given tiers with their tuning recommendations
+    pub fn report(&self, tiers: Vec<CacheTierReport>) -> CacheReport {
+        let recommendations = self.recommend_tuning(&tiers);
+        CacheReport::new(tiers).with_recommendations(recommendations)
+    }
+
+    /// Recommend size and TTL changes per tier.
+    ///
+    /// A tier that misses often while evicting for capacity is too small for
+    /// its working set, so its tightest limit should grow. A full tier whose
+    /// hits come almost entirely from a few hot keys, and whose entries are
+    /// pushed out rather than expiring, is holding cold entries, so a shorter
+    /// TTL frees the space instead.
+    pub fn recommend_tuning(&self, tiers: &[CacheTierReport]) -> Vec<TuningRecommendation> {
+        let thresholds = &self.config.tuning;
+        let mut recommendations = Vec::new();
+
+        for tier in tiers {
+            if tier.requests() < thresholds.min_requests {
+                continue;
+            }
+
+            let capacity_ratio = tier.evictions.capacity as f64 / tier.requests() as f64;
+            if capacity_ratio > thresholds.capacity_eviction_ratio
+                && tier.hit_ratio() < thresholds.target_hit_ratio
+            {
+                if let Some((limit, current)) = tightest_limit(tier) {
+                    let suggested = (current as f64 * thresholds.growth_factor).ceil() as u64;
+                    recommendations.push(TuningRecommendation {
+                        tier: tier.name(),
+                        action: TuningAction::IncreaseTierSize {
+                            limit,
+                            current,
+                            suggested: suggested.max(current + 1),
+                        },
+                        reason: format!(
+                            "hit ratio is {:.0}% and {} entries were evicted for capacity in {} requests",
+                            tier.hit_ratio() * 100.0,
+                            tier.evictions.capacity,
+                            tier.requests()
+                        ),
+                    });
+                }
+            }
+
+            let Some(ttl) = tier.ttl_secs.filter(|ttl| *ttl > 1) else {
+                continue;
+            };
+            let full = tier.utilization().unwrap_or(0.0) >= thresholds.full_utilization;
+            if full
+                && tier.entries > tier.hottest_keys.len()
+                && tier.hot_key_share() >= thresholds.hot_key_share
+                && tier.evictions.expired < tier.evictions.capacity
+            {
+                recommendations.push(TuningRecommendation {
+                    tier: tier.name(),
+                    action: TuningAction::LowerTtl {
+                        current_secs: ttl,
+                        suggested_secs: ttl / 2,
+                    },
+                    reason: format!(
+                        "tier is {:.0}% full while {} keys serve {:.0}% of hits, and entries are evicted before they expire",
+                        tier.utilization().unwrap_or(0.0) * 100.0,
+                        tier.hottest_keys.len(),
+                        tier.hot_key_share() * 100.0
+                    ),
+                });
+            }
+        }
+
+        recommendations
+    }
+}
+
+/// The limit a tier is closest to, with its current value
+fn tightest_limit(tier: &CacheTierReport) -> Option<(TierLimit, u64)> {
+    let entries = tier.max_entries.filter(|max| *max > 0).map(|max| {
+        (
+            tier.entries as f64 / max as f64,
+            TierLimit::Entries,
+            max as u64,
+        )
+    });
+    let bytes = tier
+        .max_memory_bytes
+        .filter(|max| *max > 0)
+        .map(|max| (tier.memory_bytes as f64 / max as f64, TierLimit::Bytes, max));
+    let tightest = match (entries, bytes) {
+        (Some(entries), Some(bytes)) if bytes.0 > entries.0 => Some(bytes),
+        (entries, bytes) => entries.or(bytes),
+    };
+    tightest.map(|(_, limit, current)| (limit, current))
 }
 
 /// Cache validator for data integrity and consistency
@@ -2742,3 +2967,78 @@ pub struct CacheTierBreakdown {
     pub memory_hit_rate: f64,
     pub disk_hit_rate: f64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rhema_core::cache_report::KeyHits;
+
+    fn tier(name: &str, hits: u64, misses: u64) -> CacheTierReport {
+        let mut tier = CacheTierReport::new("mcp", name);
+        tier.hits = hits;
+        tier.misses = misses;
+        tier
+    }
+
+    #[test]
+    fn test_recommend_tuning() {
+        let optimizer = CacheOptimizer::new(CacheOptimizerConfig::default());
+
+        // Misses often and keeps evicting for room: grow the entry limit
+        let mut small = tier("small", 400, 600);
+        small.entries = 100;
+        small.max_entries = Some(100);
+        small.memory_bytes = 1024;
+        small.max_memory_bytes = Some(1024 * 1024);
+        small.evictions.capacity = 300;
+
+        // Full, but hits come from two keys and nothing expires: lower the TTL
+        let mut cold = tier("cold", 950, 50);
+        cold.entries = 500;
+        cold.max_entries = Some(500);
+        cold.ttl_secs = Some(3600);
+        cold.evictions.capacity = 20;
+        cold.hottest_keys = vec![
+            KeyHits {
+                key: "scope:api".to_string(),
+                hits: 700,
+            },
+            KeyHits {
+                key: "scope:web".to_string(),
+                hits: 200,
+            },
+        ];
+
+        // Too few requests to judge
+        let mut quiet = small.clone();
+        quiet.tier = "quiet".to_string();
+        quiet.hits = 4;
+        quiet.misses = 6;
+
+        let report = optimizer.report(vec![small, cold, quiet]);
+        assert_eq!(
+            report.recommendations,
+            vec![
+                TuningRecommendation {
+                    tier: "mcp.small".to_string(),
+                    action: TuningAction::IncreaseTierSize {
+                        limit: TierLimit::Entries,
+                        current: 100,
+                        suggested: 150,
+                    },
+                    reason: "hit ratio is 40% and 300 entries were evicted for capacity in 1000 requests"
+                        .to_string(),
+                },
+                TuningRecommendation {
+                    tier: "mcp.cold".to_string(),
+                    action: TuningAction::LowerTtl {
+                        current_secs: 3600,
+                        suggested_secs: 1800,
+                    },
+                    reason: "tier is 100% full while 2 keys serve 95% of hits, and entries are evicted before they expire"
+                        .to_string(),
+                },
+            ]
+        );
+    }
+}
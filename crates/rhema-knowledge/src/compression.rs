@@ -0,0 +1,594 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Model-aware compression of context before it is injected into prompts.
+//!
+//! A [`CompressionProfile`] selected in [`crate::types::RAGConfig`] names the
+//! model family that will consume the context. Token counts use that
+//! family's tokenizer rules, and lossy summarization only applies to content
+//! types with a threshold in the profile. Every other entry is compacted
+//! losslessly.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+use crate::types::ContentType;
+
+/// Family of models whose tokenizer is used for counting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ModelFamily {
+    /// OpenAI GPT models (cl100k/o200k byte-level BPE)
+    Gpt,
+    /// Anthropic Claude models
+    Claude,
+    /// Meta Llama models (SentencePiece BPE)
+    Llama,
+    /// Roughly four characters per token
+    #[default]
+    Generic,
+}
+
+impl std::fmt::Display for ModelFamily {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModelFamily::Gpt => write!(f, "gpt"),
+            ModelFamily::Claude => write!(f, "claude"),
+            ModelFamily::Llama => write!(f, "llama"),
+            ModelFamily::Generic => write!(f, "generic"),
+        }
+    }
+}
+
+/// How a tokenizer splits text, used to count tokens without loading its vocabulary
+struct TokenizerRules {
+    /// Letters per token in a word
+    letters_per_token: usize,
+    /// Digits merged into one token
+    digits_per_token: usize,
+    /// Punctuation characters merged into one token
+    punctuation_per_token: usize,
+    /// Whether a run of spaces is a single token
+    merges_spaces: bool,
+    /// Tokens per CJK or other non-Latin character
+    tokens_per_wide_char: usize,
+}
+
+impl ModelFamily {
+    fn rules(self) -> TokenizerRules {
+        match self {
+            ModelFamily::Gpt => TokenizerRules {
+                letters_per_token: 6,
+                digits_per_token: 3,
+                punctuation_per_token: 2,
+                merges_spaces: true,
+                tokens_per_wide_char: 1,
+            },
+            ModelFamily::Claude => TokenizerRules {
+                letters_per_token: 5,
+                digits_per_token: 3,
+                punctuation_per_token: 2,
+                merges_spaces: true,
+                tokens_per_wide_char: 1,
+            },
+            // SentencePiece splits digits individually and falls back to bytes
+            ModelFamily::Llama => TokenizerRules {
+                letters_per_token: 4,
+                digits_per_token: 1,
+                punctuation_per_token: 1,
+                merges_spaces: false,
+                tokens_per_wide_char: 2,
+            },
+            ModelFamily::Generic => TokenizerRules {
+                letters_per_token: 4,
+                digits_per_token: 2,
+                punctuation_per_token: 1,
+                merges_spaces: true,
+                tokens_per_wide_char: 1,
+            },
+        }
+    }
+
+    /// Count the tokens this family's tokenizer produces for `text`
+    pub fn count_tokens(self, text: &str) -> usize {
+        #[derive(PartialEq, Clone, Copy)]
+        enum Class {
+            Letter,
+            Digit,
+            Space,
+            Newline,
+            Punctuation,
+            Wide,
+        }
+        fn classify(c: char) -> Class {
+            if c == '\n' {
+                Class::Newline
+            } else if c.is_whitespace() {
+                Class::Space
+            } else if c.is_ascii_digit() {
+                Class::Digit
+            } else if c.is_ascii_alphabetic() || (c.is_alphabetic() && (c as u32) < 0x2E80) {
+                Class::Letter
+            } else if c.is_ascii() {
+                Class::Punctuation
+            } else {
+                Class::Wide
+            }
+        }
+
+        let rules = self.rules();
+        let mut tokens = 0;
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            let class = classify(c);
+            let mut run: usize = 1;
+            while chars.peek().map(|&next| classify(next)) == Some(class) && class != Class::Wide {
+                chars.next();
+                run += 1;
+            }
+            tokens += match class {
+                Class::Letter => run.div_ceil(rules.letters_per_token),
+                Class::Digit => run.div_ceil(rules.digits_per_token),
+                Class::Punctuation => run.div_ceil(rules.punctuation_per_token),
+                Class::Wide => rules.tokens_per_wide_char,
+                Class::Newline => {
+                    if rules.merges_spaces {
+                        1
+                    } else {
+                        run
+                    }
+                }
+                // A single space before a word is part of the word's token
+                Class::Space => {
+                    let before_word =
+                        chars.peek().map(|&next| classify(next)) == Some(Class::Letter);
+                    match (run, before_word, rules.merges_spaces) {
+                        (1, true, _) => 0,
+                        (_, _, true) => 1,
+                        (_, true, false) => run - 1,
+                        (_, false, false) => run,
+                    }
+                }
+            };
+        }
+        tokens
+    }
+}
+
+/// When content of a type is summarized lossily
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SummarizationThreshold {
+    /// Content with more tokens than this is summarized
+    pub min_tokens: usize,
+    /// Fraction of the tokens kept by the summary
+    pub target_ratio: f32,
+}
+
+/// Compression settings for the model family that consumes injected context
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompressionProfile {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub model_family: ModelFamily,
+    /// Lossy summarization thresholds; content types not listed are only
+    /// compacted losslessly. Defaults to the model family's thresholds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summarization: Option<HashMap<ContentType, SummarizationThreshold>>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl Default for CompressionProfile {
+    /// Compression is off unless a profile is configured
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ..Self::for_model(ModelFamily::Generic)
+        }
+    }
+}
+
+impl CompressionProfile {
+    pub fn for_model(model_family: ModelFamily) -> Self {
+        Self {
+            enabled: true,
+            model_family,
+            summarization: None,
+        }
+    }
+
+    /// Summarization threshold for a content type, if it may be summarized
+    pub fn threshold(&self, content_type: &ContentType) -> Option<SummarizationThreshold> {
+        match &self.summarization {
+            Some(thresholds) => thresholds.get(content_type).cloned(),
+            None => Self::family_threshold(self.model_family, content_type),
+        }
+    }
+
+    /// Thresholds tuned to a model family's context window. Smaller windows
+    /// summarize earlier and harder; code and configuration are never
+    /// summarized.
+    fn family_threshold(
+        model_family: ModelFamily,
+        content_type: &ContentType,
+    ) -> Option<SummarizationThreshold> {
+        let (prose_tokens, prose_ratio) = match model_family {
+            ModelFamily::Claude => (3000, 0.6),
+            ModelFamily::Gpt => (1500, 0.5),
+            ModelFamily::Llama => (800, 0.4),
+            ModelFamily::Generic => (1200, 0.5),
+        };
+        let (scale, ratio) = match content_type {
+            ContentType::Documentation
+            | ContentType::Knowledge
+            | ContentType::Insight
+            | ContentType::Unknown => (1, prose_ratio),
+            // Decisions and patterns lose more when cut, so keep more of them
+            ContentType::Decision | ContentType::Pattern => (2, prose_ratio + 0.2),
+            ContentType::Code | ContentType::Configuration | ContentType::Todo => return None,
+        };
+        Some(SummarizationThreshold {
+            min_tokens: prose_tokens * scale,
+            target_ratio: ratio,
+        })
+    }
+}
+
+/// Result of compressing one piece of content
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompressedContent {
+    pub content: String,
+    pub content_type: ContentType,
+    pub original_tokens: usize,
+    pub compressed_tokens: usize,
+    /// Whether sentences were dropped by summarization
+    pub lossy: bool,
+}
+
+impl CompressedContent {
+    pub fn tokens_saved(&self) -> usize {
+        self.original_tokens.saturating_sub(self.compressed_tokens)
+    }
+}
+
+/// Token savings for one injected entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InjectionItemCompression {
+    pub id: String,
+    pub content_type: ContentType,
+    pub original_tokens: usize,
+    pub compressed_tokens: usize,
+    pub lossy: bool,
+}
+
+/// Measured token savings for one context injection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InjectionCompressionReport {
+    pub model_family: ModelFamily,
+    pub items: Vec<InjectionItemCompression>,
+    pub original_tokens: usize,
+    pub compressed_tokens: usize,
+}
+
+impl InjectionCompressionReport {
+    pub fn new(model_family: ModelFamily) -> Self {
+        Self {
+            model_family,
+            items: Vec::new(),
+            original_tokens: 0,
+            compressed_tokens: 0,
+        }
+    }
+
+    pub fn record(&mut self, id: &str, compressed: &CompressedContent) {
+        self.original_tokens += compressed.original_tokens;
+        self.compressed_tokens += compressed.compressed_tokens;
+        self.items.push(InjectionItemCompression {
+            id: id.to_string(),
+            content_type: compressed.content_type.clone(),
+            original_tokens: compressed.original_tokens,
+            compressed_tokens: compressed.compressed_tokens,
+            lossy: compressed.lossy,
+        });
+    }
+
+    pub fn tokens_saved(&self) -> usize {
+        self.original_tokens.saturating_sub(self.compressed_tokens)
+    }
+
+    /// Fraction of the original tokens saved
+    pub fn savings_ratio(&self) -> f32 {
+        if self.original_tokens == 0 {
+            0.0
+        } else {
+            self.tokens_saved() as f32 / self.original_tokens as f32
+        }
+    }
+}
+
+/// Compresses context according to a [`CompressionProfile`]
+#[derive(Debug, Clone)]
+pub struct ContextCompressor {
+    profile: CompressionProfile,
+}
+
+impl ContextCompressor {
+    pub fn new(profile: CompressionProfile) -> Self {
+        Self { profile }
+    }
+
+    pub fn profile(&self) -> &CompressionProfile {
+        &self.profile
+    }
+
+    pub fn count_tokens(&self, text: &str) -> usize {
+        self.profile.model_family.count_tokens(text)
+    }
+
+    /// Compact `content` and, past the content type's threshold, summarize it
+    /// toward the target ratio, keeping sentences that match `query`
+    pub fn compress(
+        &self,
+        content: &str,
+        content_type: &ContentType,
+        query: Option<&str>,
+    ) -> CompressedContent {
+        let original_tokens = self.count_tokens(content);
+        if !self.profile.enabled {
+            return CompressedContent {
+                content: content.to_string(),
+                content_type: content_type.clone(),
+                original_tokens,
+                compressed_tokens: original_tokens,
+                lossy: false,
+            };
+        }
+
+        let preserve_indentation =
+            matches!(content_type, ContentType::Code | ContentType::Configuration);
+        let mut compacted = compact(content, preserve_indentation);
+        let mut lossy = false;
+
+        if let Some(threshold) = self.profile.threshold(content_type) {
+            let compacted_tokens = self.count_tokens(&compacted);
+            if compacted_tokens > threshold.min_tokens {
+                let budget = (original_tokens as f32 * threshold.target_ratio) as usize;
+                if budget < compacted_tokens {
+                    compacted = self.summarize(&compacted, budget, query);
+                    lossy = true;
+                }
+            }
+        }
+
+        let compressed_tokens = self.count_tokens(&compacted);
+        CompressedContent {
+            content: compacted,
+            content_type: content_type.clone(),
+            original_tokens,
+            compressed_tokens,
+            lossy,
+        }
+    }
+
+    /// Extractive summary within `budget` tokens. Headings are always kept;
+    /// sentences are ranked by query overlap and position in their paragraph.
+    fn summarize(&self, content: &str, budget: usize, query: Option<&str>) -> String {
+        let query_terms: HashSet<String> = query
+            .unwrap_or_default()
+            .split_whitespace()
+            .map(|term| term.to_lowercase())
+            .filter(|term| term.len() > 2)
+            .collect();
+
+        struct Unit<'a> {
+            paragraph: usize,
+            text: &'a str,
+            tokens: usize,
+            score: f32,
+            heading: bool,
+        }
+        let mut units = Vec::new();
+        for (paragraph, block) in content.split("\n\n").enumerate() {
+            for (position, sentence) in split_sentences(block).into_iter().enumerate() {
+                let heading = sentence.starts_with('#');
+                let words: Vec<String> = sentence
+                    .split_whitespace()
+                    .map(|word| {
+                        word.trim_matches(|c: char| !c.is_alphanumeric())
+                            .to_lowercase()
+                    })
+                    .collect();
+                let matches = words
+                    .iter()
+                    .filter(|word| query_terms.contains(*word))
+                    .count();
+                let score = 2.0 * matches as f32
+                    + if position == 0 { 1.0 } else { 0.0 }
+                    + if paragraph == 0 { 0.5 } else { 0.0 }
+                    - 0.01 * position as f32;
+                units.push(Unit {
+                    paragraph,
+                    text: sentence,
+                    tokens: self.count_tokens(sentence),
+                    score,
+                    heading,
+                });
+            }
+        }
+
+        let mut keep = vec![false; units.len()];
+        let mut used = 0;
+        for (index, unit) in units.iter().enumerate() {
+            if unit.heading {
+                keep[index] = true;
+                used += unit.tokens;
+            }
+        }
+        let mut ranked: Vec<usize> = (0..units.len()).filter(|&i| !units[i].heading).collect();
+        ranked.sort_by(|&a, &b| units[b].score.total_cmp(&units[a].score).then(a.cmp(&b)));
+        for index in ranked {
+            if used + units[index].tokens <= budget {
+                keep[index] = true;
+                used += units[index].tokens;
+            }
+        }
+
+        let mut paragraphs: Vec<Vec<&str>> = Vec::new();
+        let mut current = usize::MAX;
+        for (unit, kept) in units.iter().zip(keep) {
+            if !kept {
+                continue;
+            }
+            if unit.paragraph != current {
+                paragraphs.push(Vec::new());
+                current = unit.paragraph;
+            }
+            paragraphs.last_mut().unwrap().push(unit.text);
+        }
+        paragraphs
+            .into_iter()
+            .map(|sentences| sentences.join(" "))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+/// Lossless compaction: trailing whitespace, repeated blank lines, repeated
+/// paragraphs and (outside code) runs of spaces are removed
+fn compact(content: &str, preserve_indentation: bool) -> String {
+    let mut seen_paragraphs = HashSet::new();
+    let mut paragraphs = Vec::new();
+
+    for block in content.split("\n\n") {
+        let lines: Vec<String> = block
+            .lines()
+            .map(|line| {
+                let line = line.trim_end();
+                if preserve_indentation {
+                    line.to_string()
+                } else {
+                    line.split_whitespace().collect::<Vec<_>>().join(" ")
+                }
+            })
+            .filter(|line| !line.is_empty())
+            .collect();
+        if lines.is_empty() {
+            continue;
+        }
+        let paragraph = lines.join("\n");
+        if seen_paragraphs.insert(paragraph.clone()) {
+            paragraphs.push(paragraph);
+        }
+    }
+    paragraphs.join("\n\n")
+}
+
+/// Split a paragraph into sentences and heading lines
+fn split_sentences(block: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    for line in block.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with('#') || line.starts_with("- ") || line.starts_with("* ") {
+            sentences.push(line);
+            continue;
+        }
+        let mut start = 0;
+        let bytes = line.as_bytes();
+        for (i, &byte) in bytes.iter().enumerate() {
+            let ends_sentence = matches!(byte, b'.' | b'!' | b'?')
+                && !matches!(bytes.get(i + 1), Some(next) if *next != b' ');
+            if ends_sentence {
+                let sentence = line[start..=i].trim();
+                if !sentence.is_empty() {
+                    sentences.push(sentence);
+                }
+                start = i + 1;
+            }
+        }
+        let rest = line[start..].trim();
+        if !rest.is_empty() {
+            sentences.push(rest);
+        }
+    }
+    sentences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_counts_follow_tokenizer_rules() {
+        let text = "The cache stores 1234567 entries";
+        let gpt = ModelFamily::Gpt.count_tokens(text);
+        let llama = ModelFamily::Llama.count_tokens(text);
+        // Llama splits every digit, GPT groups three
+        assert!(llama > gpt, "llama {} gpt {}", llama, gpt);
+        assert_eq!(ModelFamily::Gpt.count_tokens(""), 0);
+        assert!(ModelFamily::Claude.count_tokens("数据缓存") >= 4);
+    }
+
+    #[test]
+    fn test_code_is_only_compacted() {
+        let compressor = ContextCompressor::new(CompressionProfile::for_model(ModelFamily::Llama));
+        let code =
+            "fn main() {   \n    let x = 1;\n\n\n\n    println!(\"{}\", x);\n}\n".repeat(200);
+        let result = compressor.compress(&code, &ContentType::Code, None);
+        assert!(!result.lossy);
+        assert!(result.content.contains("    let x = 1;"));
+        assert!(result.tokens_saved() > 0);
+    }
+
+    #[test]
+    fn test_prose_past_threshold_is_summarized() {
+        let profile = CompressionProfile::for_model(ModelFamily::Llama);
+        let compressor = ContextCompressor::new(profile);
+        let mut doc = String::from("# Caching\n\n");
+        for i in 0..200 {
+            doc.push_str(&format!(
+                "Paragraph {} explains general background material. It adds filler detail number {}.\n\n",
+                i, i
+            ));
+        }
+        doc.push_str("Eviction uses an adaptive policy. Eviction thresholds are tuned per tier.\n");
+
+        let result =
+            compressor.compress(&doc, &ContentType::Documentation, Some("eviction policy"));
+        assert!(result.lossy);
+        assert!(result.compressed_tokens < result.original_tokens / 2);
+        assert!(result.content.starts_with("# Caching"));
+        assert!(result.content.contains("Eviction uses an adaptive policy."));
+
+        let mut report = InjectionCompressionReport::new(ModelFamily::Llama);
+        report.record("doc", &result);
+        assert_eq!(report.tokens_saved(), result.tokens_saved());
+        assert!(report.savings_ratio() > 0.5);
+    }
+
+    #[test]
+    fn test_disabled_profile_is_passthrough() {
+        let compressor = ContextCompressor::new(CompressionProfile::default());
+        let result = compressor.compress("a  b\n\n\n\nc", &ContentType::Knowledge, None);
+        assert_eq!(result.content, "a  b\n\n\n\nc");
+        assert_eq!(result.tokens_saved(), 0);
+    }
+}
@@ -43,6 +43,9 @@ pub enum EmbeddingError {
 
     #[error("Dimension mismatch: expected {expected}, got {actual}")]
     DimensionMismatch { expected: usize, actual: usize },
+
+    #[error("Rate limited by embedding provider")]
+    RateLimited { retry_after_ms: Option<u64> },
 }
 
 /// Embedding model configuration
@@ -86,6 +89,11 @@ pub trait EmbeddingModel: Send + Sync {
     async fn dimension(&self) -> usize;
     async fn model_info(&self) -> EmbeddingModelInfo;
 
+    /// Most texts the provider accepts in one `embed_batch` call
+    fn max_batch_size(&self) -> Option<usize> {
+        None
+    }
+
     /// Get type information for downcasting
     fn as_any(&self) -> &dyn std::any::Any;
 }
@@ -228,6 +236,10 @@ impl EmbeddingModel for SimpleHashEmbeddingModel {
         }
     }
 
+    fn max_batch_size(&self) -> Option<usize> {
+        Some(self.config.batch_size.max(1))
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -0,0 +1,650 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use chrono::{DateTime, Utc};
+use rhema_monitoring::MonitoringService;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+use crate::embedding::{EmbeddingError, EmbeddingManager};
+use crate::types::{KnowledgeError, KnowledgeResult};
+
+/// Batch pipeline configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingBatchConfig {
+    /// Upper bound on texts per provider call; the model's own limit wins if lower
+    pub max_batch_size: usize,
+
+    /// Provider request budget; `None` sends batches back to back
+    pub requests_per_minute: Option<u32>,
+
+    /// Retries per batch after rate limiting or transient failures
+    pub max_retries: u32,
+
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+
+    /// Where progress is checkpointed so an interrupted run can resume
+    pub checkpoint_path: Option<PathBuf>,
+
+    /// Write the checkpoint after this many batches
+    pub checkpoint_every: usize,
+}
+
+impl Default for EmbeddingBatchConfig {
+    fn default() -> Self {
+        Self {
+            max_batch_size: 64,
+            requests_per_minute: Some(300),
+            max_retries: 5,
+            initial_backoff_ms: 500,
+            max_backoff_ms: 60_000,
+            checkpoint_path: None,
+            checkpoint_every: 1,
+        }
+    }
+}
+
+/// A text to embed, keyed by a caller-chosen stable id
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingInput {
+    pub id: String,
+    pub text: String,
+}
+
+impl EmbeddingInput {
+    pub fn new(id: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            text: text.into(),
+        }
+    }
+}
+
+/// Running totals for a batch job
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EmbeddingProgress {
+    pub total: usize,
+    pub completed: usize,
+    /// Inputs restored from a checkpoint instead of being re-embedded
+    pub resumed: usize,
+    pub batches: usize,
+    pub rate_limited: usize,
+    /// Request rate the limiter is currently pacing to
+    pub requests_per_minute: Option<f64>,
+}
+
+impl EmbeddingProgress {
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.completed as f64 / self.total as f64
+        }
+    }
+
+    /// Estimated tokens sent to the provider for `inputs`, leaving out
+    /// inputs restored from a checkpoint
+    pub fn embedded_tokens(&self, inputs: &[EmbeddingInput]) -> u64 {
+        if self.total == 0 {
+            return 0;
+        }
+        let embedded = self.completed.saturating_sub(self.resumed) as f64 / self.total as f64;
+        (crate::index_migration::estimate_tokens(inputs) as f64 * embedded).round() as u64
+    }
+}
+
+/// What just happened in a batch job
+#[derive(Debug, Clone)]
+pub enum EmbeddingProgressEvent {
+    Started,
+    BatchCompleted { texts: usize, duration: Duration },
+    RateLimited { retry_in: Duration },
+    Finished,
+}
+
+/// Receives progress updates from a batch job
+pub trait EmbeddingProgressObserver: Send + Sync {
+    fn on_progress(&self, event: &EmbeddingProgressEvent, progress: &EmbeddingProgress);
+}
+
+impl EmbeddingProgressObserver for MonitoringService {
+    fn on_progress(&self, event: &EmbeddingProgressEvent, progress: &EmbeddingProgress) {
+        match event {
+            EmbeddingProgressEvent::BatchCompleted { texts, duration } => {
+                self.record_embedding_batch(*texts, *duration)
+            }
+            EmbeddingProgressEvent::RateLimited { .. } => self.record_embedding_rate_limited(),
+            EmbeddingProgressEvent::Started | EmbeddingProgressEvent::Finished => {}
+        }
+        self.set_embedding_progress(progress.completed, progress.total);
+    }
+}
+
+/// Result of a batch job, with embeddings in input order
+#[derive(Debug, Clone)]
+pub struct EmbeddingBatchReport {
+    pub model: String,
+    pub embeddings: Vec<(String, Vec<f32>)>,
+    pub progress: EmbeddingProgress,
+    pub elapsed: Duration,
+}
+
+/// Embeddings completed so far, persisted between runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingCheckpoint {
+    pub model: String,
+    pub updated_at: DateTime<Utc>,
+    pub completed: HashMap<String, CheckpointedEmbedding>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointedEmbedding {
+    /// Hash of the embedded text; a changed text is embedded again on resume
+    pub content_hash: String,
+    pub embedding: Vec<f32>,
+}
+
+impl EmbeddingCheckpoint {
+    fn new(model: &str) -> Self {
+        Self {
+            model: model.to_string(),
+            updated_at: Utc::now(),
+            completed: HashMap::new(),
+        }
+    }
+
+    /// Load a checkpoint, ignoring missing or unreadable files
+    pub fn load(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        match serde_json::from_str(&content) {
+            Ok(checkpoint) => Some(checkpoint),
+            Err(e) => {
+                warn!(
+                    "Ignoring unreadable embedding checkpoint {}: {}",
+                    path.display(),
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    pub fn save(&mut self, path: &Path) -> KnowledgeResult<()> {
+        self.updated_at = Utc::now();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string(self)
+            .map_err(|e| KnowledgeError::InvalidData(format!("Invalid checkpoint: {}", e)))?;
+        let temp = path.with_extension("tmp");
+        std::fs::write(&temp, content)?;
+        std::fs::rename(&temp, path)?;
+        Ok(())
+    }
+
+    fn lookup(&self, input: &EmbeddingInput) -> Option<&Vec<f32>> {
+        self.completed
+            .get(&input.id)
+            .filter(|entry| entry.content_hash == content_hash(&input.text))
+            .map(|entry| &entry.embedding)
+    }
+}
+
+fn content_hash(text: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(text.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Paces requests to a per-minute budget, slowing down when the provider
+/// pushes back and easing back towards the budget after sustained success.
+struct AdaptiveRateLimiter {
+    base_interval: Duration,
+    interval: Duration,
+    max_interval: Duration,
+    next_slot: Instant,
+    successes: u32,
+}
+
+/// Consecutive successful batches before the limiter speeds back up
+const RECOVERY_STREAK: u32 = 5;
+
+impl AdaptiveRateLimiter {
+    fn new(requests_per_minute: Option<u32>, max_backoff: Duration) -> Self {
+        let base_interval = match requests_per_minute {
+            Some(rpm) if rpm > 0 => Duration::from_secs(60) / rpm,
+            _ => Duration::ZERO,
+        };
+        Self {
+            base_interval,
+            interval: base_interval,
+            max_interval: max_backoff.max(base_interval),
+            next_slot: Instant::now(),
+            successes: 0,
+        }
+    }
+
+    async fn acquire(&mut self) {
+        let now = Instant::now();
+        if self.next_slot > now {
+            tokio::time::sleep(self.next_slot - now).await;
+        }
+        self.next_slot = Instant::now() + self.interval;
+    }
+
+    fn on_success(&mut self) {
+        self.successes += 1;
+        if self.successes >= RECOVERY_STREAK && self.interval > self.base_interval {
+            self.interval = (self.interval * 3 / 4).max(self.base_interval);
+            self.successes = 0;
+        }
+    }
+
+    fn on_rate_limited(&mut self, wait: Duration) {
+        self.successes = 0;
+        self.interval = (self.interval * 2)
+            .max(Duration::from_millis(100))
+            .min(self.max_interval);
+        self.next_slot = Instant::now() + wait.max(self.interval);
+    }
+
+    fn requests_per_minute(&self) -> Option<f64> {
+        if self.interval.is_zero() {
+            None
+        } else {
+            Some(60.0 / self.interval.as_secs_f64())
+        }
+    }
+}
+
+/// How long to wait before retrying, or `None` if the error is not retryable
+fn retry_delay(
+    error: &KnowledgeError,
+    attempt: u32,
+    config: &EmbeddingBatchConfig,
+) -> Option<Duration> {
+    let backoff = config
+        .initial_backoff_ms
+        .saturating_mul(1u64 << attempt.min(16))
+        .min(config.max_backoff_ms);
+    match error {
+        KnowledgeError::EmbeddingError(EmbeddingError::RateLimited { retry_after_ms }) => Some(
+            Duration::from_millis(retry_after_ms.unwrap_or(backoff).min(config.max_backoff_ms)),
+        ),
+        KnowledgeError::NetworkError(_) | KnowledgeError::TimeoutError(_) => {
+            Some(Duration::from_millis(backoff))
+        }
+        _ => None,
+    }
+}
+
+impl EmbeddingManager {
+    /// Embed a large set of texts in provider-sized batches.
+    ///
+    /// Requests are paced to `config.requests_per_minute`, rate-limited and
+    /// transient failures are retried with backoff, and completed embeddings
+    /// are checkpointed to `config.checkpoint_path` so a rerun after an
+    /// interruption only embeds what is left. The checkpoint is removed once
+    /// the job completes.
+    pub async fn embed_batched(
+        &self,
+        inputs: &[EmbeddingInput],
+        model_name: Option<&str>,
+        config: &EmbeddingBatchConfig,
+        observer: Option<&dyn EmbeddingProgressObserver>,
+    ) -> KnowledgeResult<EmbeddingBatchReport> {
+        let started = Instant::now();
+        let model = self.get_model(model_name).await?;
+        let model_id = model.model_info().await.name;
+        let batch_size = model
+            .max_batch_size()
+            .map_or(config.max_batch_size, |limit| {
+                limit.min(config.max_batch_size)
+            })
+            .max(1);
+
+        let mut checkpoint = config
+            .checkpoint_path
+            .as_deref()
+            .and_then(EmbeddingCheckpoint::load)
+            .filter(|checkpoint| {
+                let usable = checkpoint.model == model_id;
+                if !usable {
+                    info!(
+                        "Discarding embedding checkpoint for model {} (now using {})",
+                        checkpoint.model, model_id
+                    );
+                }
+                usable
+            })
+            .unwrap_or_else(|| EmbeddingCheckpoint::new(&model_id));
+
+        let mut results: Vec<Option<Vec<f32>>> = inputs
+            .iter()
+            .map(|input| checkpoint.lookup(input).cloned())
+            .collect();
+        let pending: Vec<usize> = (0..inputs.len())
+            .filter(|&index| results[index].is_none())
+            .collect();
+
+        let mut progress = EmbeddingProgress {
+            total: inputs.len(),
+            completed: inputs.len() - pending.len(),
+            resumed: inputs.len() - pending.len(),
+            ..Default::default()
+        };
+        let mut limiter = AdaptiveRateLimiter::new(
+            config.requests_per_minute,
+            Duration::from_millis(config.max_backoff_ms),
+        );
+        progress.requests_per_minute = limiter.requests_per_minute();
+        notify(observer, &EmbeddingProgressEvent::Started, &progress);
+        if progress.resumed > 0 {
+            info!(
+                "Resuming embedding job: {}/{} inputs already embedded",
+                progress.resumed, progress.total
+            );
+        }
+
+        for (batch_number, batch) in pending.chunks(batch_size).enumerate() {
+            let texts: Vec<String> = batch.iter().map(|&i| inputs[i].text.clone()).collect();
+
+            let mut attempt = 0;
+            let (embeddings, duration) = loop {
+                limiter.acquire().await;
+                let call_started = Instant::now();
+                match model.embed_batch(&texts).await {
+                    Ok(embeddings) => break (embeddings, call_started.elapsed()),
+                    Err(e) => {
+                        let Some(wait) = retry_delay(&e, attempt, config) else {
+                            self.save_checkpoint(&mut checkpoint, config)?;
+                            return Err(e);
+                        };
+                        if attempt >= config.max_retries {
+                            self.save_checkpoint(&mut checkpoint, config)?;
+                            return Err(EmbeddingError::BatchProcessingError(format!(
+                                "Giving up after {} retries: {}",
+                                attempt, e
+                            ))
+                            .into());
+                        }
+                        attempt += 1;
+                        if matches!(
+                            e,
+                            KnowledgeError::EmbeddingError(EmbeddingError::RateLimited { .. })
+                        ) {
+                            progress.rate_limited += 1;
+                            limiter.on_rate_limited(wait);
+                        } else {
+                            tokio::time::sleep(wait).await;
+                        }
+                        progress.requests_per_minute = limiter.requests_per_minute();
+                        debug!("Retrying embedding batch in {:?}: {}", wait, e);
+                        notify(
+                            observer,
+                            &EmbeddingProgressEvent::RateLimited { retry_in: wait },
+                            &progress,
+                        );
+                    }
+                }
+            };
+            limiter.on_success();
+
+            if embeddings.len() != texts.len() {
+                return Err(EmbeddingError::BatchProcessingError(format!(
+                    "Provider returned {} embeddings for {} texts",
+                    embeddings.len(),
+                    texts.len()
+                ))
+                .into());
+            }
+
+            for (&index, embedding) in batch.iter().zip(embeddings) {
+                checkpoint.completed.insert(
+                    inputs[index].id.clone(),
+                    CheckpointedEmbedding {
+                        content_hash: content_hash(&inputs[index].text),
+                        embedding: embedding.clone(),
+                    },
+                );
+                results[index] = Some(embedding);
+            }
+
+            progress.batches += 1;
+            progress.completed += batch.len();
+            progress.requests_per_minute = limiter.requests_per_minute();
+            notify(
+                observer,
+                &EmbeddingProgressEvent::BatchCompleted {
+                    texts: batch.len(),
+                    duration,
+                },
+                &progress,
+            );
+
+            if (batch_number + 1) % config.checkpoint_every.max(1) == 0 {
+                self.save_checkpoint(&mut checkpoint, config)?;
+            }
+        }
+
+        if let Some(path) = &config.checkpoint_path {
+            if path.exists() {
+                std::fs::remove_file(path)?;
+            }
+        }
+        notify(observer, &EmbeddingProgressEvent::Finished, &progress);
+
+        let embeddings = inputs
+            .iter()
+            .zip(results)
+            .map(|(input, embedding)| (input.id.clone(), embedding.unwrap_or_default()))
+            .collect();
+
+        Ok(EmbeddingBatchReport {
+            model: model_id,
+            embeddings,
+            progress,
+            elapsed: started.elapsed(),
+        })
+    }
+
+    fn save_checkpoint(
+        &self,
+        checkpoint: &mut EmbeddingCheckpoint,
+        config: &EmbeddingBatchConfig,
+    ) -> KnowledgeResult<()> {
+        match &config.checkpoint_path {
+            Some(path) => checkpoint.save(path),
+            None => Ok(()),
+        }
+    }
+}
+
+fn notify(
+    observer: Option<&dyn EmbeddingProgressObserver>,
+    event: &EmbeddingProgressEvent,
+    progress: &EmbeddingProgress,
+) {
+    if let Some(observer) = observer {
+        observer.on_progress(event, progress);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedding::{
+        EmbeddingDevice, EmbeddingModel, EmbeddingModelInfo, EmbeddingModelType,
+    };
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    /// Provider that caps batch size, rate limits every third call and can be
+    /// told to fail after a number of successful batches
+    struct FlakyModel {
+        calls: AtomicUsize,
+        fail_after: Option<usize>,
+        batch_sizes: Mutex<Vec<usize>>,
+    }
+
+    impl FlakyModel {
+        fn new(fail_after: Option<usize>) -> Self {
+            Self {
+                calls: AtomicUsize::new(0),
+                fail_after,
+                batch_sizes: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl EmbeddingModel for FlakyModel {
+        async fn embed(&self, text: &str) -> KnowledgeResult<Vec<f32>> {
+            Ok(vec![text.len() as f32])
+        }
+
+        async fn embed_batch(&self, texts: &[String]) -> KnowledgeResult<Vec<Vec<f32>>> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if call.is_multiple_of(3) {
+                return Err(EmbeddingError::RateLimited {
+                    retry_after_ms: Some(1),
+                }
+                .into());
+            }
+            let succeeded = self.batch_sizes.lock().unwrap().len();
+            if self.fail_after.is_some_and(|limit| succeeded >= limit) {
+                return Err(KnowledgeError::ConfigurationError("provider down".into()));
+            }
+            self.batch_sizes.lock().unwrap().push(texts.len());
+            Ok(texts.iter().map(|t| vec![t.len() as f32]).collect())
+        }
+
+        async fn similarity(&self, _a: &[f32], _b: &[f32]) -> KnowledgeResult<f32> {
+            Ok(0.0)
+        }
+
+        async fn dimension(&self) -> usize {
+            1
+        }
+
+        async fn model_info(&self) -> EmbeddingModelInfo {
+            EmbeddingModelInfo {
+                name: "flaky".to_string(),
+                version: "1".to_string(),
+                dimension: 1,
+                max_length: 512,
+                model_type: EmbeddingModelType::Custom("flaky".to_string()),
+                device: EmbeddingDevice::CPU,
+            }
+        }
+
+        fn max_batch_size(&self) -> Option<usize> {
+            Some(4)
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    struct Recorder(Mutex<Vec<usize>>);
+
+    impl EmbeddingProgressObserver for Recorder {
+        fn on_progress(&self, _event: &EmbeddingProgressEvent, progress: &EmbeddingProgress) {
+            self.0.lock().unwrap().push(progress.completed);
+        }
+    }
+
+    fn inputs(count: usize) -> Vec<EmbeddingInput> {
+        (0..count)
+            .map(|i| EmbeddingInput::new(format!("doc-{}", i), "x".repeat(i + 1)))
+            .collect()
+    }
+
+    fn config(checkpoint: Option<PathBuf>) -> EmbeddingBatchConfig {
+        EmbeddingBatchConfig {
+            max_batch_size: 10,
+            requests_per_minute: None,
+            initial_backoff_ms: 1,
+            checkpoint_path: checkpoint,
+            ..Default::default()
+        }
+    }
+
+    async fn manager_with(model: Arc<FlakyModel>) -> EmbeddingManager {
+        let manager = EmbeddingManager::new_dummy();
+        manager.add_model("flaky".to_string(), model).await;
+        manager
+    }
+
+    #[tokio::test]
+    async fn test_batches_respect_provider_limit_and_retry_rate_limits() {
+        let model = Arc::new(FlakyModel::new(None));
+        let manager = manager_with(model.clone()).await;
+        let recorder = Recorder(Mutex::new(Vec::new()));
+
+        let report = manager
+            .embed_batched(&inputs(10), Some("flaky"), &config(None), Some(&recorder))
+            .await
+            .unwrap();
+
+        assert_eq!(*model.batch_sizes.lock().unwrap(), vec![4, 4, 2]);
+        assert_eq!(report.progress.batches, 3);
+        assert_eq!(report.progress.rate_limited, 1);
+        assert_eq!(report.embeddings[9], ("doc-9".to_string(), vec![10.0]));
+        assert_eq!(recorder.0.lock().unwrap().last(), Some(&10));
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_job_resumes_from_checkpoint() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let checkpoint = temp.path().join("embeddings.checkpoint.json");
+        let inputs = inputs(10);
+
+        let failing = manager_with(Arc::new(FlakyModel::new(Some(1)))).await;
+        let mut cfg = config(Some(checkpoint.clone()));
+        cfg.max_retries = 0;
+        assert!(failing
+            .embed_batched(&inputs, Some("flaky"), &cfg, None)
+            .await
+            .is_err());
+        let saved = EmbeddingCheckpoint::load(&checkpoint).unwrap();
+        assert_eq!(saved.completed.len(), 4);
+
+        let model = Arc::new(FlakyModel::new(None));
+        let manager = manager_with(model.clone()).await;
+        let report = manager
+            .embed_batched(
+                &inputs,
+                Some("flaky"),
+                &config(Some(checkpoint.clone())),
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(report.progress.resumed, 4);
+        assert_eq!(*model.batch_sizes.lock().unwrap(), vec![4, 2]);
+        assert_eq!(report.embeddings.len(), 10);
+        assert!(!checkpoint.exists());
+    }
+}
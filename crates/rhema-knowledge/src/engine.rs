@@ -34,8 +34,10 @@ use crate::types::{
 
 use super::{
     cache::{SemanticCacheConfig, SemanticDiskCache, SemanticDiskConfig, SemanticMemoryCache},
+    compression::ContextCompressor,
     embedding::EmbeddingManager,
     search::SemanticSearchEngine,
+    summary_cache::{content_hash, SummaryCacheStats},
     synthesis::KnowledgeSynthesizer,
     vector::VectorStoreFactory,
 };
@@ -1279,6 +1281,12 @@ impl UnifiedKnowledgeEngine {
 
         // Generate semantic embedding and index
         if let Some(content) = self.extract_content(data).await {
+            // Summaries built from the previous content of this entry are now stale
+            self.knowledge_synthesizer
+                .summary_cache()
+                .invalidate_source(key, Some(&content_hash(&content)))
+                .await;
+
             let embedding = self.embedding_manager.embed(&content, None).await?;
 
             // Store in vector store
@@ -1396,6 +1404,16 @@ impl UnifiedKnowledgeEngine {
         self.metrics.read().await.clone()
     }
 
+    /// Hit rate and staleness of cached syntheses
+    pub async fn summary_cache_stats(&self) -> SummaryCacheStats {
+        self.knowledge_synthesizer.summary_cache().stats().await
+    }
+
+    /// Compressor for injected context, using the RAG compression profile
+    pub fn context_compressor(&self) -> ContextCompressor {
+        ContextCompressor::new(self.config.rag.compression.clone())
+    }
+
     // Private helper methods
 
     async fn get_direct(&self, key: &str) -> KnowledgeResult<Option<UnifiedCacheResult>> {
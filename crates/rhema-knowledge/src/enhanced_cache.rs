@@ -319,6 +319,7 @@ impl Default for EnhancedCacheMetrics {
                 eviction_count: 0,
                 memory_usage_bytes: 0,
                 semantic_hit_count: 0,
+                evictions: Default::default(),
                 last_updated: Instant::now(),
             },
             disk_cache_stats: crate::cache::CacheStats {
@@ -328,6 +329,7 @@ impl Default for EnhancedCacheMetrics {
                 eviction_count: 0,
                 memory_usage_bytes: 0,
                 semantic_hit_count: 0,
+                evictions: Default::default(),
                 last_updated: Instant::now(),
             },
             semantic_index_stats: SemanticIndexStats {
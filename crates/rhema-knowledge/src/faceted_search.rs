@@ -0,0 +1,743 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Faceted search over the context entries of a repository.
+//!
+//! Knowledge, todo, decision and pattern entries are embedded into an
+//! in-memory vector store and queried through [`SemanticSearchEngine`].
+//! Results can be narrowed by scope, content type, tag and date range,
+//! expanded with "more like this", and come with highlighted snippets.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rhema_core::file_ops::read_yaml_file;
+use rhema_core::{Decisions, Knowledge, Patterns, RhemaError, RhemaResult, Todos};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc;
+use tracing::debug;
+
+use crate::embedding::EmbeddingManager;
+use crate::index_migration::read_for_model;
+use crate::search::SemanticSearchEngine;
+use crate::types::{ContentType, DistanceMetric, SearchResultMetadata, SemanticSearchConfig};
+use crate::vector::{InMemoryVectorStore, VectorStore};
+
+/// A searchable context entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchDocument {
+    /// `<scope>/<kind>:<id>`, unique across the repository
+    pub key: String,
+    pub id: String,
+    pub scope: String,
+    pub content_type: ContentType,
+    pub title: String,
+    pub body: String,
+    pub tags: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl SearchDocument {
+    fn new(scope: &str, content_type: ContentType, id: &str, title: &str, body: &str) -> Self {
+        Self {
+            key: format!("{}/{}:{}", scope, content_type, id),
+            id: id.to_string(),
+            scope: scope.to_string(),
+            content_type,
+            title: title.to_string(),
+            body: body.to_string(),
+            tags: Vec::new(),
+            created_at: Utc::now(),
+        }
+    }
+
+    fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    fn created(mut self, at: DateTime<Utc>) -> Self {
+        self.created_at = at;
+        self
+    }
+
+    /// Text that is embedded and highlighted
+    pub fn text(&self) -> String {
+        format!("{}\n{}", self.title, self.body)
+    }
+
+    /// Key used by `rhema knowledge index`
+    fn index_key(&self) -> String {
+        format!("{}:{}", self.content_type, self.id)
+    }
+}
+
+/// Tags stored as a custom `tags` list on entries without a native tag field
+fn custom_tags(custom: &HashMap<String, Value>) -> Vec<String> {
+    custom
+        .get("tags")
+        .and_then(Value::as_sequence)
+        .map(|tags| {
+            tags.iter()
+                .filter_map(|t| t.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Load the searchable entries of one scope
+pub fn load_scope_documents(scope: &str, scope_path: &Path) -> RhemaResult<Vec<SearchDocument>> {
+    let mut documents = Vec::new();
+
+    let knowledge_file = scope_path.join("knowledge.yaml");
+    if knowledge_file.exists() {
+        let knowledge: Knowledge = read_yaml_file(&knowledge_file)?;
+        documents.extend(knowledge.entries.iter().map(|entry| {
+            SearchDocument::new(
+                scope,
+                ContentType::Knowledge,
+                &entry.id,
+                &entry.title,
+                &entry.content,
+            )
+            .with_tags(entry.tags.clone().unwrap_or_default())
+            .created(entry.created_at)
+        }));
+    }
+
+    let todos_file = scope_path.join("todos.yaml");
+    if todos_file.exists() {
+        let todos: Todos = read_yaml_file(&todos_file)?;
+        documents.extend(todos.todos.iter().map(|entry| {
+            SearchDocument::new(
+                scope,
+                ContentType::Todo,
+                &entry.id,
+                &entry.title,
+                entry.description.as_deref().unwrap_or_default(),
+            )
+            .with_tags(custom_tags(&entry.custom))
+            .created(entry.created_at)
+        }));
+    }
+
+    let decisions_file = scope_path.join("decisions.yaml");
+    if decisions_file.exists() {
+        let decisions: Decisions = read_yaml_file(&decisions_file)?;
+        documents.extend(decisions.decisions.iter().map(|entry| {
+            SearchDocument::new(
+                scope,
+                ContentType::Decision,
+                &entry.id,
+                &entry.title,
+                &entry.description,
+            )
+            .with_tags(custom_tags(&entry.custom))
+            .created(entry.decided_at)
+        }));
+    }
+
+    let patterns_file = scope_path.join("patterns.yaml");
+    if patterns_file.exists() {
+        let patterns: Patterns = read_yaml_file(&patterns_file)?;
+        documents.extend(patterns.patterns.iter().map(|entry| {
+            SearchDocument::new(
+                scope,
+                ContentType::Pattern,
+                &entry.id,
+                &entry.name,
+                &entry.description,
+            )
+            .with_tags(custom_tags(&entry.custom))
+            .created(entry.created_at)
+        }));
+    }
+
+    Ok(documents)
+}
+
+/// Filters applied to search results
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SearchFacets {
+    /// Any of these scopes
+    pub scopes: Vec<String>,
+    /// Any of these content types
+    pub content_types: Vec<ContentType>,
+    /// All of these tags
+    pub tags: Vec<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl SearchFacets {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn matches(&self, document: &SearchDocument) -> bool {
+        (self.scopes.is_empty() || self.scopes.contains(&document.scope))
+            && (self.content_types.is_empty()
+                || self.content_types.contains(&document.content_type))
+            && self
+                .tags
+                .iter()
+                .all(|tag| document.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+            && !matches!(self.since, Some(since) if document.created_at < since)
+            && !matches!(self.until, Some(until) if document.created_at > until)
+    }
+
+    /// Apply a refinement such as `scope:api`, `type:decision`, `tag:perf`,
+    /// `since:2025-01-01` or `until:2025-06-30`. A leading `-` removes the
+    /// facet (`-tag:perf`, or `-tag` for all tags).
+    pub fn refine(&mut self, refinement: &str) -> RhemaResult<()> {
+        let (remove, refinement) = match refinement.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, refinement),
+        };
+        let (facet, value) = refinement
+            .split_once(':')
+            .map(|(f, v)| (f, Some(v.trim())))
+            .unwrap_or((refinement, None));
+        let value = value.filter(|v| !v.is_empty());
+
+        if !remove && value.is_none() {
+            return Err(RhemaError::InvalidInput(format!(
+                "Facet '{}' needs a value, e.g. {}:VALUE",
+                facet, facet
+            )));
+        }
+
+        match facet {
+            "scope" => toggle(&mut self.scopes, value.map(str::to_string), remove),
+            "type" => {
+                let value = value.map(parse_content_type).transpose()?;
+                toggle(&mut self.content_types, value, remove)
+            }
+            "tag" => toggle(&mut self.tags, value.map(str::to_string), remove),
+            "since" => {
+                self.since = if remove {
+                    None
+                } else {
+                    value.map(parse_date).transpose()?
+                }
+            }
+            "until" => {
+                self.until = if remove {
+                    None
+                } else {
+                    value
+                        .map(parse_date)
+                        .transpose()?
+                        .map(|at| at + chrono::Duration::days(1) - chrono::Duration::seconds(1))
+                }
+            }
+            other => {
+                return Err(RhemaError::InvalidInput(format!(
+                    "Unknown facet '{}'; use scope, type, tag, since or until",
+                    other
+                )))
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for SearchFacets {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts: Vec<String> = Vec::new();
+        parts.extend(self.scopes.iter().map(|s| format!("scope:{}", s)));
+        parts.extend(self.content_types.iter().map(|t| format!("type:{}", t)));
+        parts.extend(self.tags.iter().map(|t| format!("tag:{}", t)));
+        if let Some(since) = self.since {
+            parts.push(format!("since:{}", since.format("%Y-%m-%d")));
+        }
+        if let Some(until) = self.until {
+            parts.push(format!("until:{}", until.format("%Y-%m-%d")));
+        }
+        if parts.is_empty() {
+            write!(f, "none")
+        } else {
+            write!(f, "{}", parts.join(" "))
+        }
+    }
+}
+
+fn toggle<T: PartialEq>(values: &mut Vec<T>, value: Option<T>, remove: bool) {
+    match (value, remove) {
+        (Some(value), true) => values.retain(|v| *v != value),
+        (None, true) => values.clear(),
+        (Some(value), false) if !values.contains(&value) => values.push(value),
+        _ => {}
+    }
+}
+
+/// Parse a content type facet value
+pub fn parse_content_type(value: &str) -> RhemaResult<ContentType> {
+    match value.to_ascii_lowercase().trim_end_matches('s') {
+        "knowledge" => Ok(ContentType::Knowledge),
+        "todo" => Ok(ContentType::Todo),
+        "decision" => Ok(ContentType::Decision),
+        "pattern" => Ok(ContentType::Pattern),
+        "insight" => Ok(ContentType::Insight),
+        _ => Err(RhemaError::InvalidInput(format!(
+            "Unknown content type '{}'; use knowledge, todo, decision or pattern",
+            value
+        ))),
+    }
+}
+
+/// Parse `YYYY-MM-DD` or an RFC 3339 timestamp
+pub fn parse_date(value: &str) -> RhemaResult<DateTime<Utc>> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Ok(at.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|at| at.and_utc())
+        .ok_or_else(|| {
+            RhemaError::InvalidInput(format!(
+                "Invalid date '{}'; use YYYY-MM-DD or RFC 3339",
+                value
+            ))
+        })
+}
+
+/// Search settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnowledgeSearchConfig {
+    /// Results scoring below this are dropped
+    pub min_score: f32,
+    /// Share of the score given to exact query-term matches
+    pub keyword_weight: f32,
+    /// Length of the highlighted snippet
+    pub snippet_chars: usize,
+}
+
+impl Default for KnowledgeSearchConfig {
+    fn default() -> Self {
+        Self {
+            min_score: 0.2,
+            keyword_weight: 0.3,
+            snippet_chars: 160,
+        }
+    }
+}
+
+/// A ranked result with its highlighted snippet
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    pub document: SearchDocument,
+    pub score: f32,
+    pub snippet: String,
+    /// Byte ranges of query terms within `snippet`
+    pub highlights: Vec<Range<usize>>,
+}
+
+/// Result counts per facet value, for showing how to narrow a search
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FacetCounts {
+    pub scopes: BTreeMap<String, usize>,
+    pub content_types: BTreeMap<String, usize>,
+    pub tags: BTreeMap<String, usize>,
+}
+
+impl FacetCounts {
+    pub fn from_hits(hits: &[SearchHit]) -> Self {
+        let mut counts = Self::default();
+        for hit in hits {
+            *counts.scopes.entry(hit.document.scope.clone()).or_default() += 1;
+            *counts
+                .content_types
+                .entry(hit.document.content_type.to_string())
+                .or_default() += 1;
+            for tag in &hit.document.tags {
+                *counts.tags.entry(tag.clone()).or_default() += 1;
+            }
+        }
+        counts
+    }
+}
+
+/// Semantic search index over a repository's context entries
+pub struct KnowledgeSearch {
+    documents: HashMap<String, SearchDocument>,
+    store: Arc<InMemoryVectorStore>,
+    engine: SemanticSearchEngine,
+    config: KnowledgeSearchConfig,
+}
+
+impl KnowledgeSearch {
+    /// Embed the entries of the given scopes (`(name, path)` pairs)
+    pub async fn build(
+        scopes: &[(String, std::path::PathBuf)],
+        embedding_manager: Arc<EmbeddingManager>,
+        config: KnowledgeSearchConfig,
+    ) -> RhemaResult<Self> {
+        let model = embedding_manager.get_model(None).await?;
+        let model_name = model.model_info().await.name;
+        let dimension = model.dimension().await;
+        let store = Arc::new(InMemoryVectorStore::new(
+            "knowledge_search".to_string(),
+            dimension,
+            DistanceMetric::Cosine,
+        ));
+
+        let mut documents = HashMap::new();
+        for (scope, scope_path) in scopes {
+            let precomputed = read_for_model(scope_path, &model_name, dimension);
+            for document in load_scope_documents(scope, scope_path)? {
+                let text = document.text();
+                let embedding = match precomputed.get(&document.index_key()) {
+                    Some(embedding) => embedding.clone(),
+                    None => embedding_manager.embed(&text, None).await?,
+                };
+                let metadata = SearchResultMetadata {
+                    source_type: document.content_type.clone(),
+                    scope_path: Some(scope.clone()),
+                    created_at: document.created_at,
+                    last_modified: document.created_at,
+                    size_bytes: text.len() as u64,
+                    chunk_id: None,
+                };
+                store
+                    .insert(&document.key, &embedding, Some(text), Some(metadata))
+                    .await?;
+                documents.insert(document.key.clone(), document);
+            }
+        }
+        debug!("Indexed {} entries for search", documents.len());
+
+        // Scores are thresholded after keyword blending and faceting
+        let engine = SemanticSearchEngine::new(
+            embedding_manager,
+            store.clone() as Arc<dyn VectorStore>,
+            SemanticSearchConfig {
+                similarity_threshold: -1.0,
+                ..SemanticSearchConfig::default()
+            },
+        )
+        .await?;
+
+        Ok(Self {
+            documents,
+            store,
+            engine,
+            config,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Find a document by key, `<kind>:<id>` or bare ID, if unambiguous
+    pub fn resolve(&self, id: &str) -> Option<&SearchDocument> {
+        if let Some(document) = self.documents.get(id) {
+            return Some(document);
+        }
+        let mut matches = self
+            .documents
+            .values()
+            .filter(|d| d.index_key() == id || d.id == id);
+        match (matches.next(), matches.next()) {
+            (Some(document), None) => Some(document),
+            _ => None,
+        }
+    }
+
+    /// Rank entries matching the facets against a query. An empty query
+    /// lists matching entries, newest first.
+    pub async fn search(
+        &self,
+        query: &str,
+        facets: &SearchFacets,
+        limit: usize,
+    ) -> RhemaResult<Vec<SearchHit>> {
+        let terms = query_terms(query);
+        if terms.is_empty() {
+            let mut documents: Vec<&SearchDocument> = self
+                .documents
+                .values()
+                .filter(|d| facets.matches(d))
+                .collect();
+            documents.sort_by_key(|d| std::cmp::Reverse(d.created_at));
+            return Ok(documents
+                .into_iter()
+                .take(limit)
+                .map(|d| self.hit(d, 1.0, &[]))
+                .collect());
+        }
+
+        let results = self
+            .engine
+            .search_semantic(query, self.documents.len().max(1))
+            .await?;
+
+        let weight = self.config.keyword_weight.clamp(0.0, 1.0);
+        let mut hits: Vec<SearchHit> = results
+            .into_iter()
+            .filter_map(|result| {
+                let document = self.documents.get(&result.cache_key)?;
+                if !facets.matches(document) {
+                    return None;
+                }
+                let score = (1.0 - weight) * result.relevance_score
+                    + weight * keyword_score(document, &terms);
+                (score >= self.config.min_score).then(|| self.hit(document, score, &terms))
+            })
+            .collect();
+        sort_hits(&mut hits);
+        hits.truncate(limit);
+        Ok(hits)
+    }
+
+    /// Entries closest to an existing entry's embedding
+    pub async fn more_like_this(
+        &self,
+        id: &str,
+        facets: &SearchFacets,
+        limit: usize,
+    ) -> RhemaResult<Vec<SearchHit>> {
+        let source = self
+            .resolve(id)
+            .ok_or_else(|| RhemaError::NotFound(format!("No unique entry matches '{}'", id)))?;
+        let record = self
+            .store
+            .get(&source.key)
+            .await?
+            .ok_or_else(|| RhemaError::NotFound(source.key.clone()))?;
+
+        let terms = query_terms(&source.title);
+        let mut hits: Vec<SearchHit> = self
+            .store
+            .search(&record.embedding, self.documents.len())
+            .await?
+            .into_iter()
+            .filter(|result| result.id != source.key && result.score >= self.config.min_score)
+            .filter_map(|result| {
+                let document = self.documents.get(&result.id)?;
+                facets
+                    .matches(document)
+                    .then(|| self.hit(document, result.score, &terms))
+            })
+            .collect();
+        sort_hits(&mut hits);
+        hits.truncate(limit);
+        Ok(hits)
+    }
+
+    fn hit(&self, document: &SearchDocument, score: f32, terms: &[String]) -> SearchHit {
+        let (snippet, highlights) = highlight(&document.text(), terms, self.config.snippet_chars);
+        SearchHit {
+            document: document.clone(),
+            score,
+            snippet,
+            highlights,
+        }
+    }
+}
+
+fn sort_hits(hits: &mut [SearchHit]) {
+    hits.sort_by(|a, b| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.document.key.cmp(&b.document.key))
+    });
+}
+
+fn query_terms(query: &str) -> Vec<String> {
+    query
+        .split(|c: char| !c.is_alphanumeric() && c != '_' && c != '-')
+        .filter(|term| term.len() >= 2)
+        .map(str::to_ascii_lowercase)
+        .collect()
+}
+
+/// Fraction of query terms that occur in the document
+fn keyword_score(document: &SearchDocument, terms: &[String]) -> f32 {
+    if terms.is_empty() {
+        return 0.0;
+    }
+    let text = document.text().to_ascii_lowercase();
+    let found = terms
+        .iter()
+        .filter(|term| text.contains(term.as_str()))
+        .count();
+    found as f32 / terms.len() as f32
+}
+
+/// A window of `text` around the first term match, with the byte ranges of
+/// every term occurrence inside it
+pub fn highlight(text: &str, terms: &[String], max_chars: usize) -> (String, Vec<Range<usize>>) {
+    // ASCII lowercasing keeps byte offsets aligned with the original text
+    let flat: String = text
+        .chars()
+        .map(|c| if c.is_whitespace() { ' ' } else { c })
+        .collect();
+    let lower = flat.to_ascii_lowercase();
+
+    let first = terms.iter().filter_map(|t| lower.find(t.as_str())).min();
+    let start = floor_char_boundary(
+        &flat,
+        first.map_or(0, |at| at.saturating_sub(max_chars / 4)),
+    );
+    let end = floor_char_boundary(&flat, (start + max_chars).min(flat.len()));
+    let window = &lower[start..end];
+
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for term in terms {
+        let mut from = 0;
+        while let Some(at) = window[from..].find(term.as_str()) {
+            ranges.push(from + at..from + at + term.len());
+            from += at + term.len();
+        }
+    }
+    ranges.sort_by_key(|r| r.start);
+    let mut merged: Vec<Range<usize>> = Vec::new();
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+
+    (flat[start..end].trim_end().to_string(), merged)
+}
+
+fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_scope(dir: &Path) {
+        std::fs::write(
+            dir.join("knowledge.yaml"),
+            r#"entries:
+  - id: k1
+    title: Connection pool sizing
+    content: Keep the database connection pool below the server limit.
+    tags: [database, performance]
+    created_at: 2025-02-01T00:00:00Z
+  - id: k2
+    title: Frontend theming
+    content: Colors come from the design tokens package.
+    tags: [ui]
+    created_at: 2025-05-01T00:00:00Z
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("decisions.yaml"),
+            r#"decisions:
+  - id: d1
+    title: Use PgBouncer for database pooling
+    description: Pool connections in front of the database.
+    status: approved
+    decided_at: 2025-03-01T00:00:00Z
+"#,
+        )
+        .unwrap();
+    }
+
+    async fn index(dir: &Path) -> KnowledgeSearch {
+        KnowledgeSearch::build(
+            &[("api".to_string(), dir.to_path_buf())],
+            Arc::new(EmbeddingManager::new_dummy()),
+            // Rank on term matches only, so the order does not depend on the
+            // hash-based test embeddings
+            KnowledgeSearchConfig {
+                min_score: 0.0,
+                keyword_weight: 1.0,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_facets_narrow_results() {
+        let dir = TempDir::new().unwrap();
+        write_scope(dir.path());
+        let search = index(dir.path()).await;
+        assert_eq!(search.len(), 3);
+
+        let hits = search
+            .search("database pool", &SearchFacets::default(), 10)
+            .await
+            .unwrap();
+        assert!(hits.len() >= 2);
+        assert!(hits[0].document.text().to_lowercase().contains("database"));
+        assert!(!hits[0].highlights.is_empty());
+
+        let mut facets = SearchFacets::default();
+        facets.refine("type:decision").unwrap();
+        let hits = search.search("database pool", &facets, 10).await.unwrap();
+        assert!(hits
+            .iter()
+            .all(|h| h.document.content_type == ContentType::Decision));
+
+        facets.refine("-type").unwrap();
+        facets.refine("tag:ui").unwrap();
+        facets.refine("since:2025-04-01").unwrap();
+        let hits = search.search("", &facets, 10).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].document.id, "k2");
+
+        let similar = search
+            .more_like_this("knowledge:k1", &SearchFacets::default(), 5)
+            .await
+            .unwrap();
+        assert!(similar.iter().all(|h| h.document.id != "k1"));
+    }
+
+    #[test]
+    fn test_highlight_marks_terms_in_snippet() {
+        let (snippet, ranges) = highlight(
+            "Keep the database\nconnection pool small",
+            &["pool".to_string(), "database".to_string()],
+            80,
+        );
+        assert_eq!(snippet, "Keep the database connection pool small");
+        let marked: Vec<&str> = ranges.iter().map(|r| &snippet[r.clone()]).collect();
+        assert_eq!(marked, vec!["database", "pool"]);
+    }
+
+    #[test]
+    fn test_refine_rejects_unknown_facets() {
+        let mut facets = SearchFacets::default();
+        assert!(facets.refine("owner:me").is_err());
+        assert!(facets.refine("scope").is_err());
+        assert!(facets.refine("since:yesterday").is_err());
+        facets.refine("scope:api").unwrap();
+        assert_eq!(facets.to_string(), "scope:api");
+    }
+}
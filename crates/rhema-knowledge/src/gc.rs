@@ -0,0 +1,200 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Garbage collection of embedding indexes for [`rhema_core::gc`].
+//!
+//! An index keeps the vectors of entries that were deleted after it was
+//! built, and migrations and interrupted runs leave staged indexes,
+//! checkpoints and the superseded index behind.
+
+use rhema_core::gc::{remove_path, ArtifactCollector, Garbage, GcConfig};
+use rhema_core::scope::discover_scopes;
+use rhema_core::RhemaResult;
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::index_migration::{
+    scope_index_inputs, EmbeddingIndex, EMBEDDINGS_CHECKPOINT_FILE, EMBEDDINGS_FILE,
+    JSON_BYTES_PER_VALUE, MIGRATION_CHECKPOINT_FILE, MIGRATION_STATE_FILE,
+    PREVIOUS_EMBEDDINGS_FILE, STAGED_EMBEDDINGS_FILE,
+};
+
+/// Collects orphaned vectors and leftover index files of every scope
+pub struct EmbeddingIndexCollector;
+
+impl EmbeddingIndexCollector {
+    /// Keys in the scope's active index whose entry no longer exists
+    fn orphaned_keys(scope_path: &Path) -> RhemaResult<Option<(EmbeddingIndex, Vec<String>)>> {
+        let Some(index) = EmbeddingIndex::load(&scope_path.join(EMBEDDINGS_FILE))? else {
+            return Ok(None);
+        };
+        let reachable: HashSet<String> = scope_index_inputs(scope_path)?
+            .into_iter()
+            .map(|input| input.id)
+            .collect();
+        let orphaned = index
+            .embeddings
+            .keys()
+            .filter(|key| !reachable.contains(*key))
+            .cloned()
+            .collect();
+        Ok(Some((index, orphaned)))
+    }
+}
+
+impl ArtifactCollector for EmbeddingIndexCollector {
+    fn name(&self) -> &str {
+        "embeddings"
+    }
+
+    fn collect(&self, repo_root: &Path, config: &GcConfig) -> RhemaResult<Vec<Garbage>> {
+        let mut garbage = Vec::new();
+        for scope in discover_scopes(repo_root)? {
+            let scope_path = &scope.path;
+
+            let active = scope_path.join(EMBEDDINGS_FILE);
+            if config.past_grace_period(&active) {
+                if let Some((index, orphaned)) = Self::orphaned_keys(scope_path)? {
+                    if !orphaned.is_empty() {
+                        let mut vectors =
+                            Garbage::path(self.name(), repo_root, &active, String::new());
+                        vectors.bytes =
+                            (orphaned.len() * index.dimension * JSON_BYTES_PER_VALUE) as u64;
+                        vectors.reason = format!("{} vectors of deleted entries", orphaned.len());
+                        vectors.entries = orphaned;
+                        garbage.push(vectors);
+                    }
+                }
+            }
+
+            // Without a migration state the staged index cannot be switched in
+            if !scope_path.join(MIGRATION_STATE_FILE).exists() {
+                for file in [STAGED_EMBEDDINGS_FILE, MIGRATION_CHECKPOINT_FILE] {
+                    let path = scope_path.join(file);
+                    if config.past_grace_period(&path) {
+                        garbage.push(Garbage::path(
+                            self.name(),
+                            repo_root,
+                            &path,
+                            "left behind by an abandoned migration",
+                        ));
+                    }
+                }
+            }
+
+            for (file, reason) in [
+                (
+                    PREVIOUS_EMBEDDINGS_FILE,
+                    "superseded by a completed migration",
+                ),
+                (
+                    EMBEDDINGS_CHECKPOINT_FILE,
+                    "checkpoint of an interrupted index run",
+                ),
+            ] {
+                let path = scope_path.join(file);
+                if config.past_retention(&path) {
+                    garbage.push(Garbage::path(self.name(), repo_root, &path, reason));
+                }
+            }
+        }
+        Ok(garbage)
+    }
+
+    fn reclaim(&self, repo_root: &Path, garbage: &Garbage) -> RhemaResult<u64> {
+        if garbage.entries.is_empty() {
+            return remove_path(&garbage.resolve(repo_root));
+        }
+
+        // Entries may have been added back since the scan
+        let path = garbage.resolve(repo_root);
+        let Some(scope_path) = path.parent() else {
+            return Ok(0);
+        };
+        let Some((mut index, orphaned)) = Self::orphaned_keys(scope_path)? else {
+            return Ok(0);
+        };
+        let orphaned: HashSet<&String> = orphaned.iter().collect();
+        let before = index.embeddings.len();
+        index
+            .embeddings
+            .retain(|key, _| !(orphaned.contains(key) && garbage.entries.contains(key)));
+        let removed = before - index.embeddings.len();
+        if removed > 0 {
+            index.save(&path)?;
+        }
+        Ok((removed * index.dimension * JSON_BYTES_PER_VALUE) as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rhema_core::gc::GarbageCollector;
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_drops_vectors_of_deleted_entries_and_abandoned_staged_indexes() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+        let scope = root.join(".rhema");
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::create_dir_all(&scope).unwrap();
+        std::fs::write(
+            scope.join("rhema.yaml"),
+            "name: app\nscope_type: service\nversion: 1.0.0\n",
+        )
+        .unwrap();
+        std::fs::write(
+            scope.join("knowledge.yaml"),
+            "entries:\n  - id: kept\n    title: Kept\n    content: Still here\n    confidence: 8\n    created_at: 2025-01-10T00:00:00Z\n",
+        )
+        .unwrap();
+
+        let embeddings = BTreeMap::from([
+            ("knowledge:kept".to_string(), vec![0.1, 0.2]),
+            ("knowledge:deleted".to_string(), vec![0.3, 0.4]),
+        ]);
+        EmbeddingIndex::new("model", embeddings.clone())
+            .save(&scope.join(EMBEDDINGS_FILE))
+            .unwrap();
+        EmbeddingIndex::new("other", embeddings)
+            .save(&scope.join(STAGED_EMBEDDINGS_FILE))
+            .unwrap();
+
+        let config = GcConfig {
+            grace_period_secs: 0,
+            ..Default::default()
+        };
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        let gc =
+            GarbageCollector::new(root, config).with_collector(Arc::new(EmbeddingIndexCollector));
+        let report = gc.collect(false).unwrap();
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert_eq!(report.summary()["embeddings"].artifacts, 2);
+
+        let index = EmbeddingIndex::load(&scope.join(EMBEDDINGS_FILE))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            index.embeddings.keys().collect::<Vec<_>>(),
+            vec!["knowledge:kept"]
+        );
+        assert!(!scope.join(STAGED_EMBEDDINGS_FILE).exists());
+    }
+}
@@ -0,0 +1,213 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Health probes for the embedding model and vector store, for use with
+//! [`rhema_core::dependency_health::DependencyHealthMonitor`].
+
+use async_trait::async_trait;
+use rhema_config::event_export::EventExportConfig;
+use rhema_coordination::provider_health::LlmProviderProbe;
+use rhema_core::dependency_health::{
+    DependencyHealthConfig, DependencyHealthMonitor, DependencyKind, HealthProbe, ProbeOutcome,
+    DEPENDENCY_HEALTH_CONFIG_SECTION,
+};
+use rhema_core::policy::repository_config;
+use rhema_core::{RhemaError, RhemaResult};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::embedding::{EmbeddingError, EmbeddingManager, EmbeddingManagerConfig};
+use crate::types::{KnowledgeError, VectorStoreConfig};
+use crate::vector::{VectorStore, VectorStoreFactory};
+
+/// Text embedded by the embedding probe
+const PROBE_TEXT: &str = "rhema health probe";
+
+/// Embeds a short text with one model of an [`EmbeddingManager`]
+pub struct EmbeddingProbe {
+    name: String,
+    manager: Arc<EmbeddingManager>,
+    model: Option<String>,
+}
+
+impl EmbeddingProbe {
+    /// Probe the manager's default model
+    pub fn new(name: impl Into<String>, manager: Arc<EmbeddingManager>) -> Self {
+        Self {
+            name: name.into(),
+            manager,
+            model: None,
+        }
+    }
+
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = Some(model.into());
+        self
+    }
+}
+
+#[async_trait]
+impl HealthProbe for EmbeddingProbe {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn kind(&self) -> DependencyKind {
+        DependencyKind::Embedding
+    }
+
+    async fn probe(&self) -> ProbeOutcome {
+        match self.manager.embed(PROBE_TEXT, self.model.as_deref()).await {
+            Ok(embedding) if embedding.is_empty() => {
+                ProbeOutcome::unhealthy("Model returned an empty embedding")
+            }
+            Ok(_) => ProbeOutcome::healthy(),
+            Err(KnowledgeError::EmbeddingError(EmbeddingError::RateLimited { .. })) => {
+                ProbeOutcome::degraded("Rate limited by embedding provider").with_quota(0)
+            }
+            Err(e) => ProbeOutcome::unhealthy(e.to_string()),
+        }
+    }
+}
+
+/// Reads the collection info of a [`VectorStore`]
+pub struct VectorStoreProbe {
+    name: String,
+    store: Arc<dyn VectorStore>,
+    expected_dimension: Option<usize>,
+}
+
+impl VectorStoreProbe {
+    pub fn new(name: impl Into<String>, store: Arc<dyn VectorStore>) -> Self {
+        Self {
+            name: name.into(),
+            store,
+            expected_dimension: None,
+        }
+    }
+
+    /// Report unhealthy when the collection dimension differs, e.g. after
+    /// switching embedding models without reindexing
+    pub fn with_expected_dimension(mut self, dimension: usize) -> Self {
+        self.expected_dimension = Some(dimension);
+        self
+    }
+}
+
+#[async_trait]
+impl HealthProbe for VectorStoreProbe {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn kind(&self) -> DependencyKind {
+        DependencyKind::VectorStore
+    }
+
+    async fn probe(&self) -> ProbeOutcome {
+        match self.store.collection_info().await {
+            Ok(info) => match self.expected_dimension {
+                Some(expected) if expected != info.dimension => ProbeOutcome::unhealthy(format!(
+                    "Collection '{}' has dimension {}, expected {}",
+                    info.name, info.dimension, expected
+                )),
+                _ => ProbeOutcome::healthy(),
+            },
+            Err(e) => ProbeOutcome::unhealthy(e.to_string()),
+        }
+    }
+}
+
+/// Monitor for the dependencies configured in `.rhema/repository.yaml`.
+///
+/// Always probes the default embedding model, and the vector store and LLM
+/// provider when `dependency_health.vector_store` and
+/// `dependency_health.llm_provider` are set. Degradations are exported as
+/// `health_degraded` events when `event_export` routes any.
+pub async fn configured_monitor(repo_root: &Path) -> RhemaResult<DependencyHealthMonitor> {
+    let config = DependencyHealthConfig::load(repo_root)?;
+    let timeout = Duration::from_secs(config.timeout_secs);
+    let llm_provider = config.llm_provider.clone();
+
+    let manager = EmbeddingManager::new(EmbeddingManagerConfig::default()).await?;
+    let mut monitor = DependencyHealthMonitor::new(config).with_probe(Arc::new(
+        EmbeddingProbe::new("embedding", Arc::new(manager)),
+    ));
+
+    if let Some(store_config) = vector_store_config(repo_root)? {
+        let dimension = store_config.dimension;
+        let store = VectorStoreFactory::create(store_config).await?;
+        monitor = monitor.with_probe(Arc::new(
+            VectorStoreProbe::new("vector_store", Arc::new(store))
+                .with_expected_dimension(dimension),
+        ));
+    }
+    if let Some(target) = llm_provider {
+        monitor = monitor.with_probe(Arc::new(LlmProviderProbe::from_target(&target, timeout)?));
+    }
+    if let Some(outbox) = EventExportConfig::load(repo_root)?.outbox(repo_root) {
+        monitor = monitor.with_event_outbox(outbox);
+    }
+    Ok(monitor)
+}
+
+pub(crate) fn vector_store_config(repo_root: &Path) -> RhemaResult<Option<VectorStoreConfig>> {
+    let value = repository_config(repo_root)?;
+    match value
+        .get(DEPENDENCY_HEALTH_CONFIG_SECTION)
+        .and_then(|section| section.get("vector_store"))
+    {
+        Some(store) => serde_yaml::from_value(store.clone())
+            .map(Some)
+            .map_err(|e| {
+                RhemaError::ConfigError(format!(
+                    "Invalid {}.vector_store in the repository config: {}",
+                    DEPENDENCY_HEALTH_CONFIG_SECTION, e
+                ))
+            }),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DistanceMetric;
+    use crate::vector::MockVectorStore;
+    use rhema_core::dependency_health::ProbeState;
+
+    #[tokio::test]
+    async fn test_probes_report_healthy_and_dimension_mismatch() {
+        let embedding = EmbeddingProbe::new("embedding", Arc::new(EmbeddingManager::new_dummy()));
+        assert_eq!(embedding.probe().await.state, ProbeState::Healthy);
+
+        let missing = EmbeddingProbe::new("embedding", Arc::new(EmbeddingManager::new_dummy()))
+            .with_model("not-loaded");
+        assert_eq!(missing.probe().await.state, ProbeState::Unhealthy);
+
+        let store: Arc<dyn VectorStore> = Arc::new(MockVectorStore::new(
+            "knowledge".to_string(),
+            384,
+            DistanceMetric::Cosine,
+        ));
+        let vector = VectorStoreProbe::new("vectors", store.clone());
+        assert_eq!(vector.probe().await.state, ProbeState::Healthy);
+
+        let mismatched = VectorStoreProbe::new("vectors", store).with_expected_dimension(768);
+        assert_eq!(mismatched.probe().await.state, ProbeState::Unhealthy);
+    }
+}
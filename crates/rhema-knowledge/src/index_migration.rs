@@ -0,0 +1,732 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Migrating a scope's embedding index to another model.
+//!
+//! An index built with one model is useless to queries embedded with
+//! another, and a model with a different dimension cannot read it at all.
+//! A migration re-embeds every entry into a staged index next to the active
+//! one. While it runs, readers keep using whichever index matches their
+//! model ([`read_for_model`]), so search is served throughout. Once parity
+//! checks pass, the staged index replaces the active one in a single rename.
+
+use chrono::{DateTime, Utc};
+use rhema_core::file_ops::read_yaml_file;
+use rhema_core::{Decisions, Knowledge, Patterns, RhemaResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::info;
+
+use crate::embedding::{EmbeddingManager, EmbeddingModelInfo};
+use crate::embedding_batch::{EmbeddingBatchConfig, EmbeddingInput, EmbeddingProgressObserver};
+use crate::quota::KnowledgeQuotas;
+use crate::types::{KnowledgeError, KnowledgeResult};
+
+/// Active index written by `rhema knowledge index`, relative to the scope directory
+pub const EMBEDDINGS_FILE: &str = "embeddings.json";
+
+/// Index being built by a migration
+pub const STAGED_EMBEDDINGS_FILE: &str = "embeddings.next.json";
+
+/// Active index as it was before the last switch
+pub const PREVIOUS_EMBEDDINGS_FILE: &str = "embeddings.previous.json";
+
+/// Progress of an interrupted `rhema knowledge index` run
+pub const EMBEDDINGS_CHECKPOINT_FILE: &str = ".embeddings.checkpoint.json";
+
+/// State of an unfinished migration
+pub(crate) const MIGRATION_STATE_FILE: &str = ".embeddings.migration.json";
+
+/// Progress of an interrupted migration's re-embedding
+pub(crate) const MIGRATION_CHECKPOINT_FILE: &str = ".embeddings.next.checkpoint.json";
+
+/// Rough characters per token, for cost estimates
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Rough bytes per vector component in the JSON index
+pub(crate) const JSON_BYTES_PER_VALUE: usize = 12;
+
+/// Rough number of tokens the provider bills for embedding `inputs`
+pub(crate) fn estimate_tokens(inputs: &[EmbeddingInput]) -> usize {
+    inputs
+        .iter()
+        .map(|input| input.text.len().div_ceil(CHARS_PER_TOKEN))
+        .sum()
+}
+
+/// Knowledge, pattern and decision texts in the scope, keyed by `<kind>:<id>`
+pub fn scope_index_inputs(scope_path: &Path) -> RhemaResult<Vec<EmbeddingInput>> {
+    let mut inputs = Vec::new();
+
+    let knowledge_file = scope_path.join("knowledge.yaml");
+    if knowledge_file.exists() {
+        let knowledge: Knowledge = read_yaml_file(&knowledge_file)?;
+        for entry in &knowledge.entries {
+            inputs.push(EmbeddingInput::new(
+                format!("knowledge:{}", entry.id),
+                format!("{}\n{}", entry.title, entry.content),
+            ));
+        }
+    }
+
+    let patterns_file = scope_path.join("patterns.yaml");
+    if patterns_file.exists() {
+        let patterns: Patterns = read_yaml_file(&patterns_file)?;
+        for entry in &patterns.patterns {
+            inputs.push(EmbeddingInput::new(
+                format!("pattern:{}", entry.id),
+                format!("{}\n{}", entry.name, entry.description),
+            ));
+        }
+    }
+
+    let decisions_file = scope_path.join("decisions.yaml");
+    if decisions_file.exists() {
+        let decisions: Decisions = read_yaml_file(&decisions_file)?;
+        for entry in &decisions.decisions {
+            inputs.push(EmbeddingInput::new(
+                format!("decision:{}", entry.id),
+                format!("{}\n{}", entry.title, entry.description),
+            ));
+        }
+    }
+
+    Ok(inputs)
+}
+
+/// Embeddings of a scope's entries, keyed by `<kind>:<id>`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingIndex {
+    pub model: String,
+
+    /// Zero in indexes written before the dimension was recorded
+    #[serde(default)]
+    pub dimension: usize,
+
+    pub generated_at: DateTime<Utc>,
+    pub embeddings: BTreeMap<String, Vec<f32>>,
+}
+
+impl EmbeddingIndex {
+    pub fn new(model: impl Into<String>, embeddings: BTreeMap<String, Vec<f32>>) -> Self {
+        let dimension = embeddings.values().next().map_or(0, Vec::len);
+        Self {
+            model: model.into(),
+            dimension,
+            generated_at: Utc::now(),
+            embeddings,
+        }
+    }
+
+    /// Load an index, `None` if the file does not exist
+    pub fn load(path: &Path) -> KnowledgeResult<Option<Self>> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let mut index: Self = serde_json::from_str(&content).map_err(|e| {
+            KnowledgeError::InvalidData(format!("Invalid index {}: {}", path.display(), e))
+        })?;
+        if index.dimension == 0 {
+            index.dimension = index.embeddings.values().next().map_or(0, Vec::len);
+        }
+        Ok(Some(index))
+    }
+
+    /// Write the index through a temporary file so readers never see it half written
+    pub fn save(&self, path: &Path) -> KnowledgeResult<()> {
+        let content = serde_json::to_string(self)
+            .map_err(|e| KnowledgeError::InvalidData(format!("Invalid index: {}", e)))?;
+        let temp = path.with_extension("tmp");
+        std::fs::write(&temp, content)?;
+        std::fs::rename(&temp, path)?;
+        Ok(())
+    }
+
+    /// How this index differs from what `model` produces, if at all
+    pub fn mismatch(&self, model: &EmbeddingModelInfo) -> Option<IndexMismatch> {
+        (self.model != model.name || self.dimension != model.dimension).then(|| IndexMismatch {
+            indexed_model: self.model.clone(),
+            indexed_dimension: self.dimension,
+            current_model: model.name.clone(),
+            current_dimension: model.dimension,
+        })
+    }
+}
+
+/// The model an index was built with differs from the configured one
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexMismatch {
+    pub indexed_model: String,
+    pub indexed_dimension: usize,
+    pub current_model: String,
+    pub current_dimension: usize,
+}
+
+impl IndexMismatch {
+    /// Whether vectors from the index cannot even be compared with new ones
+    pub fn dimension_changed(&self) -> bool {
+        self.indexed_dimension != self.current_dimension
+    }
+}
+
+/// Embeddings usable with `model`: from the active index if it was built with
+/// that model, otherwise from a migration's staged index if that one was.
+/// Vectors of the wrong dimension are dropped.
+pub fn read_for_model(
+    scope_path: &Path,
+    model: &str,
+    dimension: usize,
+) -> HashMap<String, Vec<f32>> {
+    [EMBEDDINGS_FILE, STAGED_EMBEDDINGS_FILE]
+        .iter()
+        .filter_map(|file| EmbeddingIndex::load(&scope_path.join(file)).ok().flatten())
+        .find(|index| index.model == model)
+        .map(|index| {
+            index
+                .embeddings
+                .into_iter()
+                .filter(|(_, embedding)| embedding.len() == dimension)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Expected size and cost of re-embedding a scope
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReindexPlan {
+    /// Entries in the active index, `None` if the scope has none
+    pub indexed_entries: Option<usize>,
+    pub mismatch: Option<IndexMismatch>,
+    pub target_model: String,
+    pub target_dimension: usize,
+    pub entries: usize,
+    pub estimated_tokens: usize,
+    pub batches: usize,
+    /// Time the request budget alone imposes; provider latency comes on top
+    pub estimated_duration: Duration,
+    pub estimated_cost_usd: Option<f64>,
+    /// Size of the staged index on disk, roughly
+    pub estimated_index_bytes: u64,
+}
+
+impl ReindexPlan {
+    /// Whether the active index already matches the target model
+    pub fn is_current(&self) -> bool {
+        self.indexed_entries.is_some() && self.mismatch.is_none()
+    }
+}
+
+/// Whether a staged index is fit to replace the active one
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ParityReport {
+    pub expected: usize,
+    pub embedded: usize,
+    /// Entries without an embedding in the staged index
+    pub missing: Vec<String>,
+    /// Entries whose vector has the wrong dimension
+    pub wrong_dimension: Vec<String>,
+    /// Entries whose vector is all zeros or not finite
+    pub degenerate: Vec<String>,
+    /// Mean overlap of nearest neighbours between the active and staged
+    /// index over a sample of entries, when both cover enough entries
+    pub neighbour_overlap: Option<f64>,
+    pub min_neighbour_overlap: f64,
+}
+
+impl ParityReport {
+    pub fn passed(&self) -> bool {
+        self.missing.is_empty()
+            && self.wrong_dimension.is_empty()
+            && self.degenerate.is_empty()
+            && self
+                .neighbour_overlap
+                .is_none_or(|overlap| overlap >= self.min_neighbour_overlap)
+    }
+}
+
+/// Where a migration stands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationPhase {
+    Reindexing,
+    /// Re-embedding finished but parity checks failed
+    ParityFailed,
+    /// Parity checks passed; the staged index can be switched in
+    Ready,
+}
+
+/// Unfinished migration, persisted next to the index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationState {
+    pub from_model: Option<String>,
+    pub to_model: String,
+    pub phase: MigrationPhase,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parity: Option<ParityReport>,
+}
+
+/// Migration settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IndexMigrationConfig {
+    /// Model to migrate to; the embedding manager's default if `None`
+    pub target_model: Option<String>,
+    pub batch: EmbeddingBatchConfig,
+    /// Provider price, for the cost estimate
+    pub cost_per_million_tokens: Option<f64>,
+    /// Neighbours compared per sampled entry in the parity check
+    pub neighbours: usize,
+    /// Entries sampled for the neighbour comparison
+    pub sample_size: usize,
+    /// Lowest acceptable mean neighbour overlap (0.0-1.0)
+    pub min_neighbour_overlap: f64,
+}
+
+impl Default for IndexMigrationConfig {
+    fn default() -> Self {
+        Self {
+            target_model: None,
+            batch: EmbeddingBatchConfig::default(),
+            cost_per_million_tokens: None,
+            neighbours: 5,
+            sample_size: 50,
+            min_neighbour_overlap: 0.2,
+        }
+    }
+}
+
+/// Moves one scope's index to another embedding model
+#[derive(Clone)]
+pub struct IndexMigration {
+    scope_path: PathBuf,
+    manager: Arc<EmbeddingManager>,
+    config: IndexMigrationConfig,
+    quotas: Option<KnowledgeQuotas>,
+}
+
+impl IndexMigration {
+    /// Migrate the index of the scope whose `.rhema` directory is `scope_path`
+    pub fn new(
+        scope_path: impl Into<PathBuf>,
+        manager: Arc<EmbeddingManager>,
+        config: IndexMigrationConfig,
+    ) -> Self {
+        Self {
+            scope_path: scope_path.into(),
+            manager,
+            config,
+            quotas: None,
+        }
+    }
+
+    /// Refuse to re-embed past the index size and embedding spend quotas
+    pub fn with_quotas(mut self, quotas: KnowledgeQuotas) -> Self {
+        self.quotas = Some(quotas);
+        self
+    }
+
+    pub fn active_path(&self) -> PathBuf {
+        self.scope_path.join(EMBEDDINGS_FILE)
+    }
+
+    pub fn staged_path(&self) -> PathBuf {
+        self.scope_path.join(STAGED_EMBEDDINGS_FILE)
+    }
+
+    fn state_path(&self) -> PathBuf {
+        self.scope_path.join(MIGRATION_STATE_FILE)
+    }
+
+    /// State of an unfinished migration, if any
+    pub fn state(&self) -> KnowledgeResult<Option<MigrationState>> {
+        let content = match std::fs::read_to_string(self.state_path()) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|e| KnowledgeError::InvalidData(format!("Invalid migration state: {}", e)))
+    }
+
+    fn save_state(&self, state: &mut MigrationState) -> KnowledgeResult<()> {
+        state.updated_at = Utc::now();
+        let content = serde_json::to_string_pretty(state)
+            .map_err(|e| KnowledgeError::InvalidData(format!("Invalid migration state: {}", e)))?;
+        std::fs::write(self.state_path(), content)?;
+        Ok(())
+    }
+
+    async fn target_model(&self) -> KnowledgeResult<EmbeddingModelInfo> {
+        let model = self
+            .manager
+            .get_model(self.config.target_model.as_deref())
+            .await?;
+        Ok(model.model_info().await)
+    }
+
+    /// Compare the active index with the target model and estimate what
+    /// re-embedding `inputs` would take
+    pub async fn plan(&self, inputs: &[EmbeddingInput]) -> KnowledgeResult<ReindexPlan> {
+        let target = self.target_model().await?;
+        let active = EmbeddingIndex::load(&self.active_path())?;
+        let mismatch = active.as_ref().and_then(|index| index.mismatch(&target));
+
+        let model = self
+            .manager
+            .get_model(self.config.target_model.as_deref())
+            .await?;
+        let batch_size = model
+            .max_batch_size()
+            .map_or(self.config.batch.max_batch_size, |limit| {
+                limit.min(self.config.batch.max_batch_size)
+            })
+            .max(1);
+        let batches = inputs.len().div_ceil(batch_size);
+        let estimated_tokens = estimate_tokens(inputs);
+        let estimated_duration = match self.config.batch.requests_per_minute {
+            Some(rpm) if rpm > 0 => Duration::from_secs(60) / rpm * batches as u32,
+            _ => Duration::ZERO,
+        };
+
+        Ok(ReindexPlan {
+            indexed_entries: active.map(|index| index.embeddings.len()),
+            mismatch,
+            target_model: target.name,
+            target_dimension: target.dimension,
+            entries: inputs.len(),
+            estimated_tokens,
+            batches,
+            estimated_duration,
+            estimated_cost_usd: self
+                .config
+                .cost_per_million_tokens
+                .map(|price| estimated_tokens as f64 / 1_000_000.0 * price),
+            estimated_index_bytes: (inputs.len() * target.dimension * JSON_BYTES_PER_VALUE) as u64,
+        })
+    }
+
+    /// Re-embed `inputs` with the target model into the staged index and run
+    /// the parity checks. The active index is left untouched, and an
+    /// interrupted run resumes from its checkpoint.
+    pub async fn reindex(
+        &self,
+        inputs: &[EmbeddingInput],
+        observer: Option<&dyn EmbeddingProgressObserver>,
+    ) -> KnowledgeResult<ParityReport> {
+        let target = self.target_model().await?;
+        let active = EmbeddingIndex::load(&self.active_path())?;
+        if let Some(quotas) = &self.quotas {
+            quotas.check_embedding(&self.scope_path, inputs, Some(target.dimension))?;
+        }
+
+        let mut state = match self.state()? {
+            Some(state) if state.to_model == target.name => state,
+            _ => MigrationState {
+                from_model: active.as_ref().map(|index| index.model.clone()),
+                to_model: target.name.clone(),
+                phase: MigrationPhase::Reindexing,
+                started_at: Utc::now(),
+                updated_at: Utc::now(),
+                parity: None,
+            },
+        };
+        state.phase = MigrationPhase::Reindexing;
+        state.parity = None;
+        self.save_state(&mut state)?;
+
+        let batch_config = EmbeddingBatchConfig {
+            checkpoint_path: Some(self.scope_path.join(MIGRATION_CHECKPOINT_FILE)),
+            ..self.config.batch.clone()
+        };
+        let report = self
+            .manager
+            .embed_batched(
+                inputs,
+                self.config.target_model.as_deref(),
+                &batch_config,
+                observer,
+            )
+            .await?;
+        if let Some(quotas) = &self.quotas {
+            quotas.record_embedding(&self.scope_path, report.progress.embedded_tokens(inputs))?;
+        }
+        let staged = EmbeddingIndex {
+            model: report.model,
+            dimension: target.dimension,
+            generated_at: Utc::now(),
+            embeddings: report.embeddings.into_iter().collect(),
+        };
+        staged.save(&self.staged_path())?;
+
+        let parity = self.check_parity(inputs, active.as_ref(), &staged);
+        state.phase = if parity.passed() {
+            MigrationPhase::Ready
+        } else {
+            MigrationPhase::ParityFailed
+        };
+        state.parity = Some(parity.clone());
+        self.save_state(&mut state)?;
+        info!(
+            "Staged {} embeddings from {} in {}: parity {}",
+            staged.embeddings.len(),
+            staged.model,
+            self.scope_path.display(),
+            if parity.passed() { "passed" } else { "failed" }
+        );
+        Ok(parity)
+    }
+
+    /// Run [`Self::reindex`] on a background task
+    pub fn spawn(
+        self,
+        inputs: Vec<EmbeddingInput>,
+    ) -> tokio::task::JoinHandle<KnowledgeResult<ParityReport>> {
+        tokio::spawn(async move { self.reindex(&inputs, None).await })
+    }
+
+    /// Compare the staged index with the inputs it must cover and with the
+    /// neighbourhoods of the active index
+    pub fn check_parity(
+        &self,
+        inputs: &[EmbeddingInput],
+        active: Option<&EmbeddingIndex>,
+        staged: &EmbeddingIndex,
+    ) -> ParityReport {
+        let mut report = ParityReport {
+            expected: inputs.len(),
+            min_neighbour_overlap: self.config.min_neighbour_overlap,
+            ..Default::default()
+        };
+        for input in inputs {
+            match staged.embeddings.get(&input.id) {
+                None => report.missing.push(input.id.clone()),
+                Some(embedding) if embedding.len() != staged.dimension => {
+                    report.wrong_dimension.push(input.id.clone())
+                }
+                Some(embedding)
+                    if embedding.iter().all(|x| *x == 0.0)
+                        || embedding.iter().any(|x| !x.is_finite()) =>
+                {
+                    report.degenerate.push(input.id.clone())
+                }
+                Some(_) => report.embedded += 1,
+            }
+        }
+
+        if let Some(active) = active {
+            report.neighbour_overlap = neighbour_overlap(
+                active,
+                staged,
+                self.config.neighbours,
+                self.config.sample_size,
+            );
+        }
+        report
+    }
+
+    /// Replace the active index with the staged one. The previous index is
+    /// kept as [`PREVIOUS_EMBEDDINGS_FILE`].
+    pub fn switch(&self) -> KnowledgeResult<EmbeddingIndex> {
+        let state = self.state()?.ok_or_else(|| {
+            KnowledgeError::ConfigurationError("No migration in progress".to_string())
+        })?;
+        if state.phase != MigrationPhase::Ready {
+            return Err(KnowledgeError::ConfigurationError(format!(
+                "Migration to {} has not passed its parity checks",
+                state.to_model
+            )));
+        }
+        let staged = EmbeddingIndex::load(&self.staged_path())?.ok_or_else(|| {
+            KnowledgeError::ConfigurationError("Staged index is missing".to_string())
+        })?;
+
+        let active = self.active_path();
+        if active.exists() {
+            std::fs::copy(&active, self.scope_path.join(PREVIOUS_EMBEDDINGS_FILE))?;
+        }
+        // A rename over the active file swaps it for readers in one step
+        std::fs::rename(self.staged_path(), &active)?;
+        std::fs::remove_file(self.state_path())?;
+        info!(
+            "Switched {} from {} to {}",
+            active.display(),
+            state.from_model.as_deref().unwrap_or("no index"),
+            staged.model
+        );
+        Ok(staged)
+    }
+
+    /// Drop the staged index and the migration state, keeping the active index
+    pub fn abort(&self) -> KnowledgeResult<()> {
+        for path in [
+            self.staged_path(),
+            self.state_path(),
+            self.scope_path.join(MIGRATION_CHECKPOINT_FILE),
+        ] {
+            if path.exists() {
+                std::fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Mean Jaccard overlap of each sampled entry's nearest neighbours in the two
+/// indexes. `None` when they share too few entries to compare.
+fn neighbour_overlap(
+    active: &EmbeddingIndex,
+    staged: &EmbeddingIndex,
+    neighbours: usize,
+    sample_size: usize,
+) -> Option<f64> {
+    let shared: Vec<&String> = active
+        .embeddings
+        .keys()
+        .filter(|id| staged.embeddings.contains_key(*id))
+        .collect();
+    let neighbours = neighbours.min(shared.len().saturating_sub(1));
+    if neighbours == 0 || sample_size == 0 {
+        return None;
+    }
+
+    let step = shared.len().div_ceil(sample_size).max(1);
+    let sample: Vec<&String> = shared.iter().step_by(step).copied().collect();
+    let total: f64 = sample
+        .iter()
+        .map(|id| {
+            let before = nearest(&active.embeddings, &shared, id, neighbours);
+            let after = nearest(&staged.embeddings, &shared, id, neighbours);
+            let common = before.intersection(&after).count();
+            common as f64 / (before.len() + after.len() - common).max(1) as f64
+        })
+        .sum();
+    Some(total / sample.len() as f64)
+}
+
+fn nearest<'a>(
+    embeddings: &BTreeMap<String, Vec<f32>>,
+    candidates: &[&'a String],
+    id: &str,
+    limit: usize,
+) -> HashSet<&'a String> {
+    let query = &embeddings[id];
+    let mut scored: Vec<(f32, &'a String)> = candidates
+        .iter()
+        .filter(|candidate| candidate.as_str() != id)
+        .map(|candidate| (cosine(query, &embeddings[candidate.as_str()]), *candidate))
+        .collect();
+    scored.sort_by(|a, b| {
+        b.0.partial_cmp(&a.0)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.1.cmp(b.1))
+    });
+    scored.into_iter().take(limit).map(|(_, id)| id).collect()
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedding::{
+        EmbeddingDevice, EmbeddingModelConfig, EmbeddingModelType, SimpleHashEmbeddingModel,
+    };
+
+    #[tokio::test]
+    async fn test_migration_stages_checks_and_switches() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let manager = Arc::new(EmbeddingManager::new_dummy());
+        manager
+            .add_model(
+                "hash-128".to_string(),
+                Arc::new(SimpleHashEmbeddingModel::new(EmbeddingModelConfig {
+                    model_name: "hash-128".to_string(),
+                    model_type: EmbeddingModelType::SimpleHash,
+                    max_length: 512,
+                    dimension: 128,
+                    device: EmbeddingDevice::CPU,
+                    batch_size: 2,
+                    enable_caching: false,
+                    cache_size: 0,
+                })),
+            )
+            .await;
+
+        let inputs: Vec<EmbeddingInput> = (0..5)
+            .map(|i| EmbeddingInput::new(format!("knowledge:{}", i), format!("entry {}", i)))
+            .collect();
+        let texts: Vec<String> = inputs.iter().map(|input| input.text.clone()).collect();
+        let old = manager.embed_batch(&texts, None).await.unwrap();
+        EmbeddingIndex::new(
+            "simple-hash",
+            inputs.iter().map(|i| i.id.clone()).zip(old).collect(),
+        )
+        .save(&temp.path().join(EMBEDDINGS_FILE))
+        .unwrap();
+
+        let migration = IndexMigration::new(
+            temp.path(),
+            Arc::clone(&manager),
+            IndexMigrationConfig {
+                target_model: Some("hash-128".to_string()),
+                batch: EmbeddingBatchConfig {
+                    requests_per_minute: Some(60),
+                    ..Default::default()
+                },
+                cost_per_million_tokens: Some(0.1),
+                min_neighbour_overlap: 0.0,
+                ..Default::default()
+            },
+        );
+        let plan = migration.plan(&inputs).await.unwrap();
+        assert!(plan.mismatch.as_ref().unwrap().dimension_changed());
+        assert_eq!(plan.batches, 3);
+        assert_eq!(plan.estimated_duration, Duration::from_secs(3));
+
+        let parity = migration.reindex(&inputs, None).await.unwrap();
+        assert!(parity.passed());
+        assert_eq!(parity.embedded, 5);
+        assert!(parity.neighbour_overlap.is_some());
+
+        // Dual read: each model is served from the index built with it
+        assert_eq!(read_for_model(temp.path(), "simple-hash", 384).len(), 5);
+        assert_eq!(read_for_model(temp.path(), "hash-128", 128).len(), 5);
+
+        let switched = migration.switch().unwrap();
+        assert_eq!(switched.model, "hash-128");
+        assert!(migration.state().unwrap().is_none());
+        assert!(temp.path().join(PREVIOUS_EMBEDDINGS_FILE).exists());
+        assert!(read_for_model(temp.path(), "simple-hash", 384).is_empty());
+        assert!(migration.switch().is_err());
+    }
+}
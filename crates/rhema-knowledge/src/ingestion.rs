@@ -0,0 +1,544 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use chrono::Utc;
+use rhema_core::file_ops::{get_or_create_knowledge_file, read_yaml_file, write_yaml_file};
+use rhema_core::{Knowledge, KnowledgeEntry, RhemaResult};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+use walkdir::WalkDir;
+
+use crate::quota::KnowledgeQuotas;
+
+/// Configuration for ingesting existing documentation into knowledge entries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestionConfig {
+    /// Directories (relative to the repository root) scanned for Markdown files
+    pub doc_dirs: Vec<String>,
+
+    /// Whether README files anywhere in the repository are ingested
+    pub include_readmes: bool,
+
+    /// Source file extensions scanned for tagged comments
+    pub source_extensions: Vec<String>,
+
+    /// Markers that tag a comment as knowledge (e.g. `// RHEMA: ...`)
+    pub comment_markers: Vec<String>,
+
+    /// Directories that are never scanned
+    pub exclude_dirs: Vec<String>,
+
+    /// Minimum section body length for a Markdown section to be proposed
+    pub min_section_chars: usize,
+
+    /// Word-overlap similarity above which a proposal counts as a duplicate
+    pub duplicate_threshold: f32,
+}
+
+impl Default for IngestionConfig {
+    fn default() -> Self {
+        Self {
+            doc_dirs: vec!["docs".to_string()],
+            include_readmes: true,
+            source_extensions: ["rs", "ts", "tsx", "js", "py", "go", "java", "sh"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            comment_markers: vec!["RHEMA:".to_string(), "@rhema".to_string()],
+            exclude_dirs: ["target", "node_modules", ".git", "dist", "build"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            min_section_chars: 40,
+            duplicate_threshold: 0.8,
+        }
+    }
+}
+
+/// Where a proposed knowledge entry came from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IngestionSourceKind {
+    Markdown,
+    CodeComment,
+}
+
+/// Link back to the origin of a proposal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceLink {
+    /// Path relative to the repository root
+    pub path: String,
+
+    /// 1-based line number
+    pub line: usize,
+
+    /// Kind of source
+    pub kind: IngestionSourceKind,
+}
+
+impl std::fmt::Display for SourceLink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}#L{}", self.path, self.line)
+    }
+}
+
+/// A knowledge entry proposed by ingestion, awaiting review
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnowledgeProposal {
+    /// The entry that will be written if accepted
+    pub entry: KnowledgeEntry,
+
+    /// Origin of the proposal
+    pub source: SourceLink,
+
+    /// Id of an existing entry this proposal duplicates, if any
+    pub duplicate_of: Option<String>,
+}
+
+/// Review decision for a proposal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReviewDecision {
+    Accept,
+    Reject,
+}
+
+/// Scans documentation and tagged code comments and turns them into
+/// knowledge entry proposals
+pub struct KnowledgeIngestor {
+    repo_root: PathBuf,
+    config: IngestionConfig,
+}
+
+impl KnowledgeIngestor {
+    pub fn new(repo_root: &Path, config: IngestionConfig) -> Self {
+        Self {
+            repo_root: repo_root.to_path_buf(),
+            config,
+        }
+    }
+
+    /// Collect proposals from all configured sources, marking duplicates of
+    /// `existing` entries and of each other
+    pub fn scan(&self, existing: &[KnowledgeEntry]) -> RhemaResult<Vec<KnowledgeProposal>> {
+        let mut proposals = Vec::new();
+
+        for path in self.markdown_files() {
+            let content = std::fs::read_to_string(&path)?;
+            let relative = self.relative(&path);
+            proposals.extend(self.parse_markdown(&relative, &content));
+        }
+
+        for path in self.source_files() {
+            // Binary or non UTF-8 files are not interesting here
+            let Ok(content) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            let relative = self.relative(&path);
+            proposals.extend(self.parse_comments(&relative, &content));
+        }
+
+        debug!("Ingestion produced {} proposals", proposals.len());
+        self.mark_duplicates(&mut proposals, existing);
+        Ok(proposals)
+    }
+
+    /// Split a Markdown document into one proposal per heading section
+    pub fn parse_markdown(&self, path: &str, content: &str) -> Vec<KnowledgeProposal> {
+        let mut proposals = Vec::new();
+        let mut current: Option<(String, usize, Vec<&str>)> = None;
+        let mut in_fence = false;
+
+        for (index, line) in content.lines().enumerate() {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_fence = !in_fence;
+            }
+
+            let heading = if in_fence {
+                None
+            } else {
+                parse_heading(trimmed)
+            };
+            match heading {
+                Some(title) => {
+                    if let Some(section) = current.take() {
+                        proposals.extend(self.section_proposal(path, section));
+                    }
+                    current = Some((title, index + 1, Vec::new()));
+                }
+                None => {
+                    if let Some((_, _, body)) = current.as_mut() {
+                        body.push(line);
+                    }
+                }
+            }
+        }
+
+        if let Some(section) = current.take() {
+            proposals.extend(self.section_proposal(path, section));
+        }
+
+        proposals
+    }
+
+    /// Extract proposals from comments tagged with one of the configured markers.
+    ///
+    /// The text after the marker becomes the title; directly following comment
+    /// lines become the content.
+    pub fn parse_comments(&self, path: &str, content: &str) -> Vec<KnowledgeProposal> {
+        let lines: Vec<&str> = content.lines().collect();
+        let mut proposals = Vec::new();
+        let mut index = 0;
+
+        while index < lines.len() {
+            let Some(text) = strip_comment(lines[index]) else {
+                index += 1;
+                continue;
+            };
+            let Some(title) = self.strip_marker(text) else {
+                index += 1;
+                continue;
+            };
+
+            let start = index;
+            let mut body = Vec::new();
+            index += 1;
+            while index < lines.len() {
+                match strip_comment(lines[index]) {
+                    Some(text) if self.strip_marker(text).is_none() => {
+                        body.push(text.to_string());
+                        index += 1;
+                    }
+                    _ => break,
+                }
+            }
+
+            let content = body.join("\n").trim().to_string();
+            let content = if content.is_empty() {
+                title.clone()
+            } else {
+                content
+            };
+            let source = SourceLink {
+                path: path.to_string(),
+                line: start + 1,
+                kind: IngestionSourceKind::CodeComment,
+            };
+            proposals.push(new_proposal(title, content, source, "code-comment"));
+        }
+
+        proposals
+    }
+
+    /// Flag proposals that duplicate existing entries or earlier proposals.
+    /// Matching titles only count within one document, since headings such as
+    /// "Usage" recur across documents; elsewhere the content has to match.
+    pub fn mark_duplicates(
+        &self,
+        proposals: &mut [KnowledgeProposal],
+        existing: &[KnowledgeEntry],
+    ) {
+        // Id, normalized title, content and document of every entry so far
+        let mut seen: Vec<(String, String, String, Option<String>)> = existing
+            .iter()
+            .map(|e| {
+                let path = e
+                    .source
+                    .as_deref()
+                    .and_then(|source| source.split('#').next())
+                    .map(str::to_string);
+                (e.id.clone(), normalize(&e.title), e.content.clone(), path)
+            })
+            .collect();
+        let existing_sources: HashMap<&str, &str> = existing
+            .iter()
+            .filter_map(|e| e.source.as_deref().map(|s| (s, e.id.as_str())))
+            .collect();
+
+        for proposal in proposals.iter_mut() {
+            let source = proposal.source.to_string();
+            let title = normalize(&proposal.entry.title);
+
+            proposal.duplicate_of = existing_sources
+                .get(source.as_str())
+                .map(|id| id.to_string())
+                .or_else(|| {
+                    seen.iter()
+                        .find(|(_, seen_title, seen_content, seen_path)| {
+                            (*seen_title == title
+                                && seen_path.as_deref() == Some(proposal.source.path.as_str()))
+                                || similarity(seen_content, &proposal.entry.content)
+                                    >= self.config.duplicate_threshold
+                        })
+                        .map(|(id, _, _, _)| id.clone())
+                });
+
+            if proposal.duplicate_of.is_none() {
+                seen.push((
+                    proposal.entry.id.clone(),
+                    title,
+                    proposal.entry.content.clone(),
+                    Some(proposal.source.path.clone()),
+                ));
+            }
+        }
+    }
+
+    /// Write accepted proposals into a scope's knowledge file, returning the
+    /// number of entries added. Nothing is written if the entries would
+    /// exceed an entry quota.
+    pub fn apply(
+        scope_path: &Path,
+        proposals: &[KnowledgeProposal],
+        decisions: &[ReviewDecision],
+        quotas: Option<&KnowledgeQuotas>,
+    ) -> RhemaResult<usize> {
+        let knowledge_file = get_or_create_knowledge_file(scope_path)?;
+        let mut knowledge: Knowledge = read_yaml_file(&knowledge_file)?;
+
+        let accepted: Vec<KnowledgeEntry> = proposals
+            .iter()
+            .zip(decisions)
+            .filter(|(_, decision)| **decision == ReviewDecision::Accept)
+            .map(|(proposal, _)| proposal.entry.clone())
+            .collect();
+        let added = accepted.len();
+        if let Some(quotas) = quotas {
+            quotas.check_entries(scope_path, added)?;
+        }
+
+        if added > 0 {
+            knowledge.entries.extend(accepted);
+            write_yaml_file(&knowledge_file, &knowledge)?;
+        }
+
+        Ok(added)
+    }
+
+    fn section_proposal(
+        &self,
+        path: &str,
+        (title, line, body): (String, usize, Vec<&str>),
+    ) -> Option<KnowledgeProposal> {
+        let content = body.join("\n").trim().to_string();
+        if content.chars().count() < self.config.min_section_chars {
+            return None;
+        }
+        let source = SourceLink {
+            path: path.to_string(),
+            line,
+            kind: IngestionSourceKind::Markdown,
+        };
+        Some(new_proposal(title, content, source, "documentation"))
+    }
+
+    fn strip_marker(&self, text: &str) -> Option<String> {
+        self.config.comment_markers.iter().find_map(|marker| {
+            text.strip_prefix(marker.as_str())
+                .map(|rest| rest.trim().to_string())
+                .filter(|rest| !rest.is_empty())
+        })
+    }
+
+    fn markdown_files(&self) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = self
+            .config
+            .doc_dirs
+            .iter()
+            .flat_map(|dir| self.walk(&self.repo_root.join(dir)))
+            .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("md"))
+            .collect();
+
+        if self.config.include_readmes {
+            files.extend(self.walk(&self.repo_root).into_iter().filter(|p| {
+                p.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.eq_ignore_ascii_case("readme.md"))
+            }));
+        }
+
+        let mut unique = HashSet::new();
+        files.retain(|p| unique.insert(p.clone()));
+        files
+    }
+
+    fn source_files(&self) -> Vec<PathBuf> {
+        self.walk(&self.repo_root)
+            .into_iter()
+            .filter(|p| {
+                p.extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| self.config.source_extensions.iter().any(|s| s == e))
+            })
+            .collect()
+    }
+
+    fn walk(&self, root: &Path) -> Vec<PathBuf> {
+        WalkDir::new(root)
+            .into_iter()
+            .filter_entry(|e| {
+                e.file_name()
+                    .to_str()
+                    .is_none_or(|name| !self.config.exclude_dirs.iter().any(|d| d == name))
+            })
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .map(|e| e.into_path())
+            .collect()
+    }
+
+    fn relative(&self, path: &Path) -> String {
+        path.strip_prefix(&self.repo_root)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/")
+    }
+}
+
+fn new_proposal(
+    title: String,
+    content: String,
+    source: SourceLink,
+    category: &str,
+) -> KnowledgeProposal {
+    let entry = KnowledgeEntry {
+        id: uuid::Uuid::new_v4().to_string(),
+        title,
+        content,
+        category: Some(category.to_string()),
+        tags: Some(vec!["ingested".to_string()]),
+        confidence: None,
+        created_at: Utc::now(),
+        updated_at: None,
+        source: Some(source.to_string()),
+        custom: HashMap::new(),
+    };
+    KnowledgeProposal {
+        entry,
+        source,
+        duplicate_of: None,
+    }
+}
+
+fn parse_heading(line: &str) -> Option<String> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let rest = &line[level..];
+    if !rest.starts_with(' ') {
+        return None;
+    }
+    let title = rest.trim().trim_end_matches('#').trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// Return the text of a line comment, or `None` for non-comment lines
+fn strip_comment(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    ["///", "//!", "//", "/**", "/*", "*", "#", "--"]
+        .iter()
+        .find_map(|prefix| trimmed.strip_prefix(prefix))
+        .map(|rest| rest.trim_end_matches("*/").trim())
+}
+
+fn normalize(text: &str) -> String {
+    text.to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Jaccard similarity of the word sets of two texts
+fn similarity(a: &str, b: &str) -> f32 {
+    let a: HashSet<String> = a.split_whitespace().map(|w| w.to_lowercase()).collect();
+    let b: HashSet<String> = b.split_whitespace().map(|w| w.to_lowercase()).collect();
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f32 / a.union(&b).count() as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ingestor() -> KnowledgeIngestor {
+        KnowledgeIngestor::new(
+            Path::new("/repo"),
+            IngestionConfig {
+                min_section_chars: 5,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_parse_markdown_sections() {
+        let doc = "# Intro\nShort intro text.\n\n```sh\n# not a heading\n```\n## Caching\nWe cache query results.\n";
+        let proposals = ingestor().parse_markdown("docs/guide.md", doc);
+
+        assert_eq!(proposals.len(), 2);
+        assert_eq!(proposals[0].entry.title, "Intro");
+        assert!(proposals[0].entry.content.contains("# not a heading"));
+        assert_eq!(proposals[1].entry.title, "Caching");
+        assert_eq!(proposals[1].source.to_string(), "docs/guide.md#L7");
+    }
+
+    #[test]
+    fn test_parse_tagged_comments() {
+        let source =
+            "fn main() {}\n// RHEMA: Retries are capped\n// at three attempts.\nfn other() {}\n";
+        let proposals = ingestor().parse_comments("src/main.rs", source);
+
+        assert_eq!(proposals.len(), 1);
+        assert_eq!(proposals[0].entry.title, "Retries are capped");
+        assert_eq!(proposals[0].entry.content, "at three attempts.");
+        assert_eq!(proposals[0].source.line, 2);
+    }
+
+    #[test]
+    fn test_mark_duplicates() {
+        let ingestor = ingestor();
+        let mut proposals = ingestor.parse_comments(
+            "src/lib.rs",
+            "// RHEMA: Caching\n// We cache query results\n// RHEMA: Other\n// unrelated text here\n",
+        );
+        let existing = vec![proposals[0].entry.clone()];
+        let existing_id = existing[0].id.clone();
+
+        ingestor.mark_duplicates(&mut proposals, &existing);
+        assert_eq!(proposals[0].duplicate_of, Some(existing_id));
+        assert_eq!(proposals[1].duplicate_of, None);
+
+        // A common heading repeats across documents without being a duplicate
+        let mut proposals = ingestor.parse_markdown(
+            "docs/cli.md",
+            "## Usage\nRun the binary with a scope path.\n## Usage\nPass --json for output.\n",
+        );
+        proposals.extend(ingestor.parse_markdown(
+            "docs/server.md",
+            "## Usage\nStart the daemon before querying.\n",
+        ));
+        ingestor.mark_duplicates(&mut proposals, &[]);
+        assert_eq!(
+            proposals[1].duplicate_of,
+            Some(proposals[0].entry.id.clone())
+        );
+        assert_eq!(proposals[2].duplicate_of, None);
+    }
+}
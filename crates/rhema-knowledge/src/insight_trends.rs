@@ -0,0 +1,702 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use chrono::{DateTime, Datelike, Utc};
+use rhema_core::file_ops::read_yaml_file;
+use rhema_core::{Knowledge, KnowledgeEntry};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::Arc;
+use tracing::debug;
+
+use crate::embedding::EmbeddingManager;
+use crate::types::KnowledgeResult;
+
+/// Words ignored when deriving a cluster label from insight titles
+const STOPWORDS: &[&str] = &[
+    "about", "after", "again", "also", "because", "been", "before", "being", "between", "could",
+    "does", "doesn", "from", "have", "into", "just", "more", "most", "only", "other", "over",
+    "should", "some", "than", "that", "their", "them", "then", "there", "these", "they", "this",
+    "those", "through", "very", "were", "what", "when", "where", "which", "while", "with", "would",
+    "your",
+];
+
+/// Insight clustering configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InsightTrendConfig {
+    /// Embedding model to use (defaults to the manager's default model)
+    pub model: Option<String>,
+
+    /// Minimum time-adjusted similarity for an insight to join a cluster
+    pub similarity_threshold: f32,
+
+    /// Similarity lost when an insight is `time_scale_days` or more away from
+    /// the cluster's most recent insight
+    pub time_weight: f32,
+
+    /// Gap at which the full time penalty applies
+    pub time_scale_days: f32,
+
+    /// Clusters smaller than this are not reported as themes
+    pub min_cluster_size: usize,
+
+    /// Relative quarter-over-quarter increase for a theme to count as growing
+    pub growth_threshold: f32,
+}
+
+impl Default for InsightTrendConfig {
+    fn default() -> Self {
+        Self {
+            model: None,
+            similarity_threshold: 0.6,
+            time_weight: 0.15,
+            time_scale_days: 180.0,
+            min_cluster_size: 2,
+            growth_threshold: 0.5,
+        }
+    }
+}
+
+/// Insight as seen by the trend analysis
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InsightRecord {
+    pub id: String,
+    pub scope: String,
+    pub title: String,
+    pub text: String,
+    pub tags: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl InsightRecord {
+    pub fn from_entry(scope: &str, entry: &KnowledgeEntry) -> Self {
+        Self {
+            id: entry.id.clone(),
+            scope: scope.to_string(),
+            title: entry.title.clone(),
+            text: format!("{}\n{}", entry.title, entry.content),
+            tags: entry.tags.clone().unwrap_or_default(),
+            created_at: entry.created_at,
+        }
+    }
+}
+
+/// Load the insights (knowledge entries) of a scope
+pub fn load_scope_insights(
+    scope: &str,
+    scope_path: &Path,
+) -> rhema_core::RhemaResult<Vec<InsightRecord>> {
+    let knowledge_file = scope_path.join("knowledge.yaml");
+    if !knowledge_file.exists() {
+        return Ok(Vec::new());
+    }
+    let knowledge: Knowledge = read_yaml_file(&knowledge_file)?;
+    Ok(knowledge
+        .entries
+        .iter()
+        .map(|entry| InsightRecord::from_entry(scope, entry))
+        .collect())
+}
+
+/// Quarter label (`2025-Q3`) of a timestamp
+pub fn quarter_of(at: DateTime<Utc>) -> String {
+    format!("{}-Q{}", at.year(), (at.month() - 1) / 3 + 1)
+}
+
+/// How a theme developed over the analysed quarters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrendDirection {
+    /// Only seen in the latest quarter
+    Emerging,
+    /// Clearly more insights in the latest quarter than the one before
+    Growing,
+    /// Keeps coming back across three or more quarters
+    Recurring,
+    /// Present throughout without a clear change
+    Stable,
+    /// Nothing new in the last two quarters
+    Fading,
+}
+
+impl TrendDirection {
+    fn headline(&self, label: &str) -> String {
+        match self {
+            TrendDirection::Emerging => format!("emerging theme around {}", label),
+            TrendDirection::Growing => format!("growing pain points around {}", label),
+            TrendDirection::Recurring => format!("recurring {}", label),
+            TrendDirection::Stable => format!("steady discussion of {}", label),
+            TrendDirection::Fading => format!("fading concerns about {}", label),
+        }
+    }
+}
+
+/// A theme: insights that are semantically close and close in time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InsightCluster {
+    pub id: usize,
+
+    /// Short label derived from shared tags or title keywords
+    pub label: String,
+
+    /// Human readable trend summary, e.g. "growing pain points around build times"
+    pub headline: String,
+    pub direction: TrendDirection,
+    pub insight_ids: Vec<String>,
+
+    /// Insight count per quarter, chronologically
+    pub quarters: BTreeMap<String, usize>,
+
+    /// Insight count per scope
+    pub scopes: BTreeMap<String, usize>,
+    pub first_seen: DateTime<Utc>,
+    pub last_seen: DateTime<Utc>,
+
+    /// Mean similarity of members to the cluster centroid
+    pub cohesion: f32,
+}
+
+impl InsightCluster {
+    pub fn size(&self) -> usize {
+        self.insight_ids.len()
+    }
+}
+
+/// Themes across the analysed insights
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InsightTrendReport {
+    pub generated_at: DateTime<Utc>,
+    pub insight_count: usize,
+
+    /// Every quarter between the first and the latest insight
+    pub quarters: Vec<String>,
+
+    /// Themes, largest first
+    pub clusters: Vec<InsightCluster>,
+
+    /// Insights that did not end up in a reported theme
+    pub unclustered: usize,
+}
+
+impl InsightTrendReport {
+    /// Themes with insights in the given quarter, most active first
+    pub fn for_quarter(&self, quarter: &str) -> Vec<(&InsightCluster, usize)> {
+        let mut themes: Vec<_> = self
+            .clusters
+            .iter()
+            .filter_map(|c| c.quarters.get(quarter).map(|count| (c, *count)))
+            .collect();
+        themes.sort_by_key(|theme| std::cmp::Reverse(theme.1));
+        themes
+    }
+
+    /// Themes with insights in the given scope, most active first
+    pub fn for_scope(&self, scope: &str) -> Vec<(&InsightCluster, usize)> {
+        let mut themes: Vec<_> = self
+            .clusters
+            .iter()
+            .filter_map(|c| c.scopes.get(scope).map(|count| (c, *count)))
+            .collect();
+        themes.sort_by_key(|theme| std::cmp::Reverse(theme.1));
+        themes
+    }
+
+    /// Scopes that contributed to at least one theme
+    pub fn scopes(&self) -> Vec<&str> {
+        let mut scopes: Vec<&str> = self
+            .clusters
+            .iter()
+            .flat_map(|c| c.scopes.keys().map(String::as_str))
+            .collect();
+        scopes.sort_unstable();
+        scopes.dedup();
+        scopes
+    }
+
+    /// Render the report as a Markdown page
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::from("# Insight Trends\n\n");
+        md.push_str(&format!(
+            "{} insights in {} themes ({} unclustered), generated {}.\n\n",
+            self.insight_count,
+            self.clusters.len(),
+            self.unclustered,
+            self.generated_at.format("%Y-%m-%d")
+        ));
+
+        if self.clusters.is_empty() {
+            md.push_str("No recurring themes yet.\n");
+            return md;
+        }
+
+        md.push_str("## Themes\n\n");
+        md.push_str("| Theme | Trend | Insights | First seen | Last seen |\n");
+        md.push_str("|---|---|---|---|---|\n");
+        for cluster in &self.clusters {
+            md.push_str(&format!(
+                "| {} | {:?} | {} | {} | {} |\n",
+                cluster.headline,
+                cluster.direction,
+                cluster.size(),
+                quarter_of(cluster.first_seen),
+                quarter_of(cluster.last_seen)
+            ));
+        }
+
+        md.push_str("\n## By Quarter\n\n");
+        for quarter in self.quarters.iter().rev() {
+            let themes = self.for_quarter(quarter);
+            if themes.is_empty() {
+                continue;
+            }
+            md.push_str(&format!("### {}\n\n", quarter));
+            for (cluster, count) in themes {
+                md.push_str(&format!("- {} ({})\n", cluster.headline, count));
+            }
+            md.push('\n');
+        }
+
+        md.push_str("## By Scope\n\n");
+        for scope in self.scopes() {
+            md.push_str(&format!("### {}\n\n", scope));
+            for (cluster, count) in self.for_scope(scope) {
+                md.push_str(&format!("- {} ({})\n", cluster.headline, count));
+            }
+            md.push('\n');
+        }
+
+        md
+    }
+}
+
+struct WorkingCluster {
+    members: Vec<usize>,
+    centroid: Vec<f32>,
+    last_seen: DateTime<Utc>,
+}
+
+/// Clusters insights by embedding similarity and time and derives trends
+pub struct InsightTrendAnalyzer {
+    embedding_manager: Arc<EmbeddingManager>,
+    config: InsightTrendConfig,
+}
+
+impl InsightTrendAnalyzer {
+    pub fn new(embedding_manager: Arc<EmbeddingManager>, config: InsightTrendConfig) -> Self {
+        Self {
+            embedding_manager,
+            config,
+        }
+    }
+
+    /// Cluster the insights and build the trend report
+    pub async fn analyze(&self, insights: &[InsightRecord]) -> KnowledgeResult<InsightTrendReport> {
+        let model = self.config.model.as_deref();
+
+        let mut order: Vec<usize> = (0..insights.len()).collect();
+        order.sort_by_key(|&i| insights[i].created_at);
+
+        let mut embeddings = Vec::with_capacity(insights.len());
+        for insight in insights {
+            embeddings.push(self.embedding_manager.embed(&insight.text, model).await?);
+        }
+
+        // Single pass in chronological order: join the closest cluster when
+        // its time-adjusted similarity clears the threshold
+        let mut clusters: Vec<WorkingCluster> = Vec::new();
+        for &i in &order {
+            let mut best: Option<(usize, f32)> = None;
+            for (c, cluster) in clusters.iter().enumerate() {
+                let similarity = self
+                    .embedding_manager
+                    .similarity(&embeddings[i], &cluster.centroid, model)
+                    .await?;
+                let gap_days = (insights[i].created_at - cluster.last_seen)
+                    .num_days()
+                    .unsigned_abs() as f32;
+                let score = similarity
+                    - self.config.time_weight * (gap_days / self.config.time_scale_days).min(1.0);
+                if score >= self.config.similarity_threshold
+                    && !matches!(best, Some((_, best_score)) if score <= best_score)
+                {
+                    best = Some((c, score));
+                }
+            }
+
+            match best {
+                Some((c, _)) => {
+                    let cluster = &mut clusters[c];
+                    cluster.members.push(i);
+                    let n = cluster.members.len() as f32;
+                    for (value, x) in cluster.centroid.iter_mut().zip(&embeddings[i]) {
+                        *value += (x - *value) / n;
+                    }
+                    cluster.last_seen = cluster.last_seen.max(insights[i].created_at);
+                }
+                None => clusters.push(WorkingCluster {
+                    members: vec![i],
+                    centroid: embeddings[i].clone(),
+                    last_seen: insights[i].created_at,
+                }),
+            }
+        }
+
+        let quarters = quarter_range(insights);
+        let mut reported = Vec::new();
+        let mut unclustered = 0;
+        for cluster in clusters {
+            if cluster.members.len() < self.config.min_cluster_size {
+                unclustered += cluster.members.len();
+                continue;
+            }
+
+            let mut cohesion = 0.0;
+            for &i in &cluster.members {
+                cohesion += self
+                    .embedding_manager
+                    .similarity(&embeddings[i], &cluster.centroid, model)
+                    .await?;
+            }
+            cohesion /= cluster.members.len() as f32;
+
+            let members: Vec<&InsightRecord> =
+                cluster.members.iter().map(|&i| &insights[i]).collect();
+            reported.push(self.describe(reported.len(), &members, &quarters, cohesion));
+        }
+
+        reported.sort_by(|a, b| {
+            b.size()
+                .cmp(&a.size())
+                .then_with(|| b.last_seen.cmp(&a.last_seen))
+        });
+        for (id, cluster) in reported.iter_mut().enumerate() {
+            cluster.id = id;
+        }
+        debug!(
+            "Clustered {} insights into {} themes",
+            insights.len(),
+            reported.len()
+        );
+
+        Ok(InsightTrendReport {
+            generated_at: Utc::now(),
+            insight_count: insights.len(),
+            quarters,
+            clusters: reported,
+            unclustered,
+        })
+    }
+
+    fn describe(
+        &self,
+        id: usize,
+        members: &[&InsightRecord],
+        quarters: &[String],
+        cohesion: f32,
+    ) -> InsightCluster {
+        let mut per_quarter = BTreeMap::new();
+        let mut per_scope = BTreeMap::new();
+        for insight in members {
+            *per_quarter
+                .entry(quarter_of(insight.created_at))
+                .or_insert(0) += 1;
+            *per_scope.entry(insight.scope.clone()).or_insert(0) += 1;
+        }
+
+        let direction = self.direction(&per_quarter, quarters);
+        let label = cluster_label(members);
+        InsightCluster {
+            id,
+            headline: direction.headline(&label),
+            label,
+            direction,
+            insight_ids: members.iter().map(|i| i.id.clone()).collect(),
+            quarters: per_quarter,
+            scopes: per_scope,
+            first_seen: members
+                .iter()
+                .map(|i| i.created_at)
+                .min()
+                .unwrap_or_default(),
+            last_seen: members
+                .iter()
+                .map(|i| i.created_at)
+                .max()
+                .unwrap_or_default(),
+            cohesion,
+        }
+    }
+
+    fn direction(
+        &self,
+        per_quarter: &BTreeMap<String, usize>,
+        quarters: &[String],
+    ) -> TrendDirection {
+        let count = |offset: usize| -> usize {
+            quarters
+                .len()
+                .checked_sub(offset + 1)
+                .and_then(|i| per_quarter.get(&quarters[i]))
+                .copied()
+                .unwrap_or(0)
+        };
+        let (latest, previous) = (count(0), count(1));
+
+        if latest == 0 && previous == 0 {
+            TrendDirection::Fading
+        } else if latest > 0 && per_quarter.len() == 1 && quarters.len() > 1 {
+            TrendDirection::Emerging
+        } else if latest >= 2
+            && latest as f32 > previous as f32 * (1.0 + self.config.growth_threshold)
+        {
+            TrendDirection::Growing
+        } else if per_quarter.len() >= 3 {
+            TrendDirection::Recurring
+        } else {
+            TrendDirection::Stable
+        }
+    }
+}
+
+/// Every quarter from the earliest to the latest insight
+fn quarter_range(insights: &[InsightRecord]) -> Vec<String> {
+    let (Some(first), Some(last)) = (
+        insights.iter().map(|i| i.created_at).min(),
+        insights.iter().map(|i| i.created_at).max(),
+    ) else {
+        return Vec::new();
+    };
+
+    let mut quarters = Vec::new();
+    let (mut year, mut quarter) = (first.year(), (first.month() - 1) / 3);
+    let end = (last.year(), (last.month() - 1) / 3);
+    while (year, quarter) <= end {
+        quarters.push(format!("{}-Q{}", year, quarter + 1));
+        quarter += 1;
+        if quarter == 4 {
+            quarter = 0;
+            year += 1;
+        }
+    }
+    quarters
+}
+
+/// Label a cluster by the tags most of its insights share, falling back to
+/// the most frequent title keywords
+fn cluster_label(members: &[&InsightRecord]) -> String {
+    let mut tag_counts: HashMap<String, usize> = HashMap::new();
+    for insight in members {
+        for tag in &insight.tags {
+            *tag_counts.entry(tag.to_lowercase()).or_insert(0) += 1;
+        }
+    }
+    let shared = top_terms(tag_counts, members.len().div_ceil(2));
+    if !shared.is_empty() {
+        return shared.join(" and ");
+    }
+
+    let mut word_counts: HashMap<String, usize> = HashMap::new();
+    for insight in members {
+        let mut seen = Vec::new();
+        for word in insight
+            .title
+            .split(|c: char| !c.is_alphanumeric())
+            .map(str::to_lowercase)
+            .filter(|w| w.len() > 3 && !STOPWORDS.contains(&w.as_str()))
+        {
+            if !seen.contains(&word) {
+                seen.push(word);
+            }
+        }
+        for word in seen {
+            *word_counts.entry(word).or_insert(0) += 1;
+        }
+    }
+    let keywords = top_terms(word_counts, 2);
+    if keywords.is_empty() {
+        members
+            .first()
+            .map(|i| i.title.to_lowercase())
+            .unwrap_or_default()
+    } else {
+        keywords.join(" ")
+    }
+}
+
+/// Up to two most frequent terms occurring at least `min_count` times
+fn top_terms(counts: HashMap<String, usize>, min_count: usize) -> Vec<String> {
+    let mut terms: Vec<(String, usize)> = counts
+        .into_iter()
+        .filter(|(_, count)| *count >= min_count.max(1))
+        .collect();
+    terms.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    terms.into_iter().take(2).map(|(term, _)| term).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::embedding::{
+        EmbeddingDevice, EmbeddingModel, EmbeddingModelInfo, EmbeddingModelType,
+    };
+    use async_trait::async_trait;
+    use chrono::TimeZone;
+
+    /// Bag-of-words model so that insights on the same topic embed alike
+    struct WordModel;
+
+    const VOCABULARY: &[&str] = &["build", "slow", "compile", "auth", "token", "login"];
+
+    #[async_trait]
+    impl EmbeddingModel for WordModel {
+        async fn embed(&self, text: &str) -> KnowledgeResult<Vec<f32>> {
+            let text = text.to_lowercase();
+            Ok(VOCABULARY
+                .iter()
+                .map(|w| if text.contains(w) { 1.0 } else { 0.0 })
+                .collect())
+        }
+
+        async fn embed_batch(&self, texts: &[String]) -> KnowledgeResult<Vec<Vec<f32>>> {
+            let mut out = Vec::new();
+            for text in texts {
+                out.push(self.embed(text).await?);
+            }
+            Ok(out)
+        }
+
+        async fn similarity(&self, a: &[f32], b: &[f32]) -> KnowledgeResult<f32> {
+            let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+            let na: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+            let nb: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+            Ok(if na == 0.0 || nb == 0.0 {
+                0.0
+            } else {
+                dot / (na * nb)
+            })
+        }
+
+        async fn dimension(&self) -> usize {
+            VOCABULARY.len()
+        }
+
+        async fn model_info(&self) -> EmbeddingModelInfo {
+            EmbeddingModelInfo {
+                name: "words".to_string(),
+                version: "1".to_string(),
+                dimension: VOCABULARY.len(),
+                max_length: 512,
+                model_type: EmbeddingModelType::Custom("words".to_string()),
+                device: EmbeddingDevice::CPU,
+            }
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    async fn analyzer() -> InsightTrendAnalyzer {
+        let manager = EmbeddingManager::new_dummy();
+        manager
+            .add_model("words".to_string(), Arc::new(WordModel))
+            .await;
+        InsightTrendAnalyzer::new(
+            Arc::new(manager),
+            InsightTrendConfig {
+                model: Some("words".to_string()),
+                ..Default::default()
+            },
+        )
+    }
+
+    fn insight(id: &str, scope: &str, title: &str, tags: &[&str], month: u32) -> InsightRecord {
+        InsightRecord {
+            id: id.to_string(),
+            scope: scope.to_string(),
+            title: title.to_string(),
+            text: title.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            created_at: Utc.with_ymd_and_hms(2025, month, 15, 0, 0, 0).unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_clusters_by_topic_and_detects_growth() {
+        let insights = vec![
+            insight(
+                "b1",
+                "api",
+                "Slow build after dependency bump",
+                &["build-times"],
+                2,
+            ),
+            insight("a1", "web", "Auth token refresh confusion", &[], 1),
+            insight("a2", "api", "Auth login token expiry confusion", &[], 4),
+            insight("a3", "web", "Auth login token scopes unclear", &[], 7),
+            insight("b2", "api", "Build slow on CI", &["build-times"], 8),
+            insight(
+                "b3",
+                "web",
+                "Compile and build slow again",
+                &["build-times"],
+                8,
+            ),
+            insight(
+                "b4",
+                "api",
+                "Slow build caching broken",
+                &["build-times", "ci"],
+                9,
+            ),
+        ];
+
+        let report = analyzer().await.analyze(&insights).await.unwrap();
+        assert_eq!(report.clusters.len(), 2);
+        assert_eq!(report.unclustered, 0);
+        assert_eq!(report.quarters, vec!["2025-Q1", "2025-Q2", "2025-Q3"]);
+
+        let build = &report.clusters[0];
+        assert_eq!(build.size(), 4);
+        assert_eq!(build.label, "build-times");
+        assert_eq!(build.direction, TrendDirection::Growing);
+        assert_eq!(build.headline, "growing pain points around build-times");
+        assert_eq!(build.scopes.get("api"), Some(&3));
+
+        let auth = &report.clusters[1];
+        assert_eq!(auth.direction, TrendDirection::Recurring);
+        assert_eq!(auth.label, "auth token");
+        assert_eq!(report.for_scope("web").len(), 2);
+        assert_eq!(report.for_quarter("2025-Q1").len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_markdown_lists_themes_per_quarter_and_scope() {
+        let insights = vec![
+            insight("a1", "web", "Auth token refresh confusion", &[], 1),
+            insight("a2", "api", "Auth login token expiry confusion", &[], 2),
+            insight("x", "api", "Unrelated note", &[], 2),
+        ];
+        let report = analyzer().await.analyze(&insights).await.unwrap();
+        assert_eq!(report.unclustered, 1);
+
+        let markdown = report.to_markdown();
+        assert!(markdown.contains("## By Quarter"));
+        assert!(markdown.contains("### 2025-Q1"));
+        assert!(markdown.contains("### web"));
+    }
+}
@@ -2,9 +2,11 @@ use anyhow::Result;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+use crate::compression::InjectionCompressionReport;
 use crate::embedding::EmbeddingManager;
 use crate::engine::UnifiedKnowledgeEngine;
 use crate::search::SemanticSearchEngine;
+use crate::summary_cache::{SourceFingerprint, SummaryCache, SummaryCacheStats};
 use crate::types::KnowledgeError;
 use crate::vector::VectorStoreWrapper;
 
@@ -24,6 +26,7 @@ pub struct AIIntegration {
     vector_store: Arc<VectorStoreWrapper>,
     metrics: Arc<RwLock<AIIntegrationMetrics>>,
     ai_client: reqwest::Client,
+    answer_cache: SummaryCache<Option<String>>,
 }
 
 impl AIIntegration {
@@ -47,6 +50,7 @@ impl AIIntegration {
             vector_store,
             metrics: Arc::new(RwLock::new(AIIntegrationMetrics::default())),
             ai_client,
+            answer_cache: SummaryCache::default(),
         })
     }
 
@@ -64,13 +68,28 @@ impl AIIntegration {
             .await?;
 
         // Apply AI enhancements
-        let enhanced_results = self
+        let mut enhanced_results = self
             .apply_ai_enhancements(&request, &search_results)
             .await?;
 
         // Generate synthesized content if requested
+        // Answers are cached against the content of the entries they were built from
         let synthesized_content = if request.enable_synthesis {
-            self.generate_synthesized_content(&enhanced_results).await?
+            let sources = SourceFingerprint::from_results(&search_results);
+            let cache_key = SummaryCache::<Option<String>>::request_key(
+                "rag_answer",
+                &[&request.query, &request.max_results.to_string()],
+            );
+            match self.answer_cache.get(&cache_key, &sources).await {
+                Some(cached) => cached,
+                None => {
+                    let answer = self.generate_synthesized_content(&enhanced_results).await?;
+                    self.answer_cache
+                        .insert(&cache_key, &sources, answer.clone())
+                        .await;
+                    answer
+                }
+            }
         } else {
             None
         };
@@ -83,6 +102,24 @@ impl AIIntegration {
         // Calculate confidence score
         let confidence_score = self.calculate_confidence_score(&enhanced_results);
 
+        // Compress results for injection, measuring the tokens saved
+        let compressor = self.knowledge_engine.context_compressor();
+        let compression = if compressor.profile().enabled {
+            let mut report = InjectionCompressionReport::new(compressor.profile().model_family);
+            for result in &mut enhanced_results {
+                let compressed = compressor.compress(
+                    &result.content,
+                    &result.content_type,
+                    Some(&request.query),
+                );
+                report.record(&result.id, &compressed);
+                result.content = compressed.content;
+            }
+            Some(report)
+        } else {
+            None
+        };
+
         // Update metrics
         let processing_time = start_time.elapsed().as_millis() as u64;
         self.update_metrics(processing_time, enhanced_results.len())
@@ -96,6 +133,7 @@ impl AIIntegration {
             processing_time_ms: processing_time,
             ai_enhancements: vec![], // TODO: Track enhancements
             suggestions,
+            compression,
             created_at: chrono::Utc::now(),
         })
     }
@@ -129,7 +167,7 @@ impl AIIntegration {
                 content: result.content.clone(),
                 relevance_score: result.relevance_score,
                 ai_enhanced_score,
-                content_type: crate::types::ContentType::Knowledge,
+                content_type: result.metadata.source_type.clone(),
                 metadata: Some(result.metadata.clone()),
                 ai_insights: insights,
                 related_concepts,
@@ -381,6 +419,11 @@ impl AIIntegration {
         self.metrics.read().await.clone()
     }
 
+    /// Hit rate and staleness of cached RAG answers
+    pub async fn answer_cache_stats(&self) -> SummaryCacheStats {
+        self.answer_cache.stats().await
+    }
+
     /// Optimize knowledge base using AI
     pub async fn optimize_knowledge_base(&self) -> Result<(), KnowledgeError> {
         // TODO: Implement knowledge base optimization
@@ -1,6 +1,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::compression::InjectionCompressionReport;
 use crate::types::{ContentType, SearchResultMetadata};
 
 /// AI-enhanced knowledge request
@@ -29,6 +30,9 @@ pub struct AIKnowledgeResponse {
     pub processing_time_ms: u64,
     pub ai_enhancements: Vec<AIEnhancement>,
     pub suggestions: Vec<KnowledgeSuggestion>,
+    /// Token savings from compressing the results for injection
+    #[serde(default)]
+    pub compression: Option<InjectionCompressionReport>,
     pub created_at: DateTime<Utc>,
 }
 
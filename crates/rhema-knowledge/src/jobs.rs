@@ -0,0 +1,132 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Background indexing on the [`rhema_core::jobs::JobQueue`], so large
+//! reindexes survive restarts and retry when the embedding endpoint or vector
+//! store is briefly unavailable.
+
+use async_trait::async_trait;
+use rhema_core::jobs::{Job, JobContext, JobHandler, JobSpec};
+use rhema_core::{RhemaError, RhemaResult};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+
+use crate::embedding::{EmbeddingManager, EmbeddingManagerConfig};
+use crate::health::vector_store_config;
+use crate::indexing::{IndexingConfig, SemanticIndexer};
+use crate::vector::VectorStoreFactory;
+
+/// Job kind indexing files into the configured vector store
+pub const INDEX_FILES_JOB: &str = "knowledge.index_files";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexFilesPayload {
+    files: Vec<PathBuf>,
+
+    #[serde(default)]
+    scope_path: Option<String>,
+}
+
+/// Indexes the files named in a job's payload, resolving relative paths
+/// against the repository root
+pub struct IndexFilesJob {
+    repo_root: PathBuf,
+
+    /// Built on the first job from the repository's vector store config
+    indexer: OnceCell<Arc<SemanticIndexer>>,
+}
+
+impl IndexFilesJob {
+    pub fn new(repo_root: impl Into<PathBuf>) -> Self {
+        Self {
+            repo_root: repo_root.into(),
+            indexer: OnceCell::new(),
+        }
+    }
+
+    pub fn with_indexer(mut self, indexer: Arc<SemanticIndexer>) -> Self {
+        self.indexer = OnceCell::from(indexer);
+        self
+    }
+
+    pub fn spec(files: &[PathBuf], scope_path: Option<&str>) -> JobSpec {
+        let payload = IndexFilesPayload {
+            files: files.to_vec(),
+            scope_path: scope_path.map(str::to_string),
+        };
+        JobSpec::new(
+            INDEX_FILES_JOB,
+            serde_json::to_value(payload).unwrap_or_default(),
+        )
+    }
+
+    async fn indexer(&self) -> RhemaResult<&Arc<SemanticIndexer>> {
+        self.indexer
+            .get_or_try_init(|| build_indexer(&self.repo_root))
+            .await
+    }
+}
+
+async fn build_indexer(repo_root: &Path) -> RhemaResult<Arc<SemanticIndexer>> {
+    let store_config = vector_store_config(repo_root)?.ok_or_else(|| {
+        RhemaError::ConfigError(
+            "No vector store configured under dependency_health.vector_store".to_string(),
+        )
+    })?;
+    let manager = EmbeddingManager::new(EmbeddingManagerConfig::default()).await?;
+    let store = VectorStoreFactory::create(store_config).await?;
+    let indexer = SemanticIndexer::new(Arc::new(manager), store, IndexingConfig::default()).await?;
+    Ok(Arc::new(indexer))
+}
+
+#[async_trait]
+impl JobHandler for IndexFilesJob {
+    async fn run(&self, job: &Job, context: &JobContext) -> RhemaResult<serde_json::Value> {
+        let payload: IndexFilesPayload = serde_json::from_value(job.payload.clone())?;
+        let indexer = self.indexer().await?;
+
+        let mut indexed = serde_json::Map::new();
+        let mut failed = serde_json::Map::new();
+        for file in &payload.files {
+            if context.is_cancelled() {
+                break;
+            }
+            let path = self.repo_root.join(file);
+            match indexer
+                .index_file(&path, payload.scope_path.as_deref())
+                .await
+            {
+                Ok(chunks) => {
+                    indexed.insert(file.display().to_string(), chunks.len().into());
+                }
+                Err(e) => {
+                    failed.insert(file.display().to_string(), e.to_string().into());
+                }
+            }
+        }
+
+        // Nothing indexed usually means the dependencies are down, so retry
+        if indexed.is_empty() && !failed.is_empty() {
+            return Err(RhemaError::KnowledgeError(format!(
+                "Failed to index {} file(s)",
+                failed.len()
+            )));
+        }
+        Ok(serde_json::json!({ "indexed": indexed, "failed": failed }))
+    }
+}
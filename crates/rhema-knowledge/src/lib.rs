@@ -14,14 +14,26 @@
  * limitations under the License.
  */
 
+pub mod auto_tag;
 pub mod cache;
+pub mod compression;
 pub mod embedding;
+pub mod embedding_batch;
 pub mod engine;
+pub mod faceted_search;
+pub mod gc;
+pub mod health;
+pub mod index_migration;
 pub mod indexing;
+pub mod ingestion;
+pub mod jobs;
+pub mod insight_trends;
 pub mod integration;
 pub mod proactive;
+pub mod quota;
 pub mod search;
 pub mod storage;
+pub mod summary_cache;
 pub mod synthesis;
 pub mod temporal;
 pub mod types;
@@ -30,8 +42,15 @@ pub mod vector;
 // Re-export main types for convenience
 // Cache module exports
 pub use cache::{
-    AdaptiveEvictionPolicy, CacheMetrics, CacheMonitor, CacheOptimizer, CachePerformanceReport,
-    CacheValidator, UnifiedCacheManager, UnifiedCacheStats,
+    AdaptiveEvictionPolicy, CacheMetrics, CacheMonitor, CacheOptimizer, CacheOptimizerConfig,
+    CachePerformanceReport, CacheTuningThresholds, CacheValidator, UnifiedCacheConfig,
+    UnifiedCacheManager, UnifiedCacheStats,
+};
+
+// Compression module exports
+pub use compression::{
+    CompressedContent, CompressionProfile, ContextCompressor, InjectionCompressionReport,
+    ModelFamily, SummarizationThreshold,
 };
 
 // Engine module exports
@@ -54,6 +73,48 @@ pub use types::{
     WorkflowType,
 };
 
+// Auto-tagging module exports
+pub use auto_tag::{
+    AutoTagConfig, AutoTagMode, AutoTagger, TagSuggestion, Taxonomy, TaxonomyCategory,
+};
+
+// Ingestion module exports
+pub use ingestion::{
+    IngestionConfig, KnowledgeIngestor, KnowledgeProposal, ReviewDecision, SourceLink,
+};
+
+// Insight trend exports
+pub use insight_trends::{
+    InsightCluster, InsightRecord, InsightTrendAnalyzer, InsightTrendConfig, InsightTrendReport,
+    TrendDirection,
+};
+
+// Embedding batch pipeline exports
+pub use embedding_batch::{
+    EmbeddingBatchConfig, EmbeddingBatchReport, EmbeddingCheckpoint, EmbeddingInput,
+    EmbeddingProgress, EmbeddingProgressEvent, EmbeddingProgressObserver,
+};
+
+// Embedding index migration exports
+pub use index_migration::{
+    EmbeddingIndex, IndexMigration, IndexMigrationConfig, IndexMismatch, MigrationPhase,
+    ParityReport, ReindexPlan,
+};
+
+// Garbage collection exports
+pub use gc::EmbeddingIndexCollector;
+
+// Dependency health probe exports
+pub use health::{configured_monitor, EmbeddingProbe, VectorStoreProbe};
+
+// Background job exports
+pub use jobs::{IndexFilesJob, INDEX_FILES_JOB};
+// Quota exports
+pub use quota::{
+    KnowledgeQuotaConfig, KnowledgeQuotas, KnowledgeUsage, KnowledgeUsageReport, QuotaKind,
+    QuotaLimits, QuotaViolation,
+};
+
 // Search module exports
 pub use search::SemanticSearchEngine;
 
@@ -68,6 +129,11 @@ pub use proactive::{
 // Synthesis module exports
 pub use synthesis::KnowledgeSynthesizer;
 
+// Summary cache exports
+pub use summary_cache::{
+    content_hash, SourceFingerprint, SummaryCache, SummaryCacheConfig, SummaryCacheStats,
+};
+
 // Performance module exports - not yet implemented
 // pub use performance::{
 //     PerformanceMonitor, PerformanceConfig, PerformanceMetrics, ResourceUsage,
@@ -0,0 +1,549 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Per-scope and global quotas on knowledge storage and embedding spend.
+//!
+//! Limits come from the `knowledge_quotas` section of
+//! `.rhema/repository.yaml`. Ingestion checks the entry count before writing,
+//! and indexing checks the projected index size and the month's embedding
+//! spend before calling the provider. Spend is estimated from the embedded
+//! text and recorded in a ledger under `.rhema/knowledge`.
+
+use chrono::Utc;
+use rhema_core::file_ops::read_yaml_file;
+use rhema_core::policy::repository_config;
+use rhema_core::scope::{discover_scopes, Scope};
+use rhema_core::{Knowledge, RhemaError, RhemaResult};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::embedding_batch::EmbeddingInput;
+use crate::index_migration::{
+    estimate_tokens, EMBEDDINGS_FILE, JSON_BYTES_PER_VALUE, STAGED_EMBEDDINGS_FILE,
+};
+use crate::types::{KnowledgeError, KnowledgeResult};
+
+/// Section of `.rhema/repository.yaml` configuring knowledge quotas
+pub const KNOWLEDGE_QUOTAS_CONFIG_SECTION: &str = "knowledge_quotas";
+
+/// Embedding spend ledger, relative to the repository root
+pub const EMBEDDING_SPEND_FILE: &str = ".rhema/knowledge/embedding_spend.json";
+
+/// Limits for one scope or for the whole repository; unset limits are off
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuotaLimits {
+    pub max_entries: Option<usize>,
+
+    /// Size of the embedding index on disk, staged migrations included
+    pub max_index_bytes: Option<u64>,
+
+    /// Estimated embedding provider spend per calendar month (UTC)
+    pub max_monthly_embedding_usd: Option<f64>,
+}
+
+/// Knowledge quota configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KnowledgeQuotaConfig {
+    /// Limits on the totals across all scopes
+    pub global: QuotaLimits,
+
+    /// Limits per scope, by scope name
+    pub scopes: BTreeMap<String, QuotaLimits>,
+
+    /// Provider price used to estimate spend
+    pub cost_per_million_tokens: f64,
+}
+
+impl Default for KnowledgeQuotaConfig {
+    fn default() -> Self {
+        Self {
+            global: QuotaLimits::default(),
+            scopes: BTreeMap::new(),
+            cost_per_million_tokens: 0.02,
+        }
+    }
+}
+
+impl KnowledgeQuotaConfig {
+    /// Load the configuration from the repository config and policy bundle,
+    /// falling back to defaults
+    pub fn load(repo_root: &Path) -> RhemaResult<Self> {
+        let value = repository_config(repo_root)?;
+        match value.get(KNOWLEDGE_QUOTAS_CONFIG_SECTION) {
+            Some(section) => serde_yaml::from_value(section.clone()).map_err(|e| {
+                RhemaError::ConfigError(format!(
+                    "Invalid {} section in the repository config: {}",
+                    KNOWLEDGE_QUOTAS_CONFIG_SECTION, e
+                ))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+/// What a quota limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaKind {
+    Entries,
+    IndexSize,
+    EmbeddingSpend,
+}
+
+impl QuotaKind {
+    /// Ways to get back under the quota
+    pub fn suggestions(&self) -> &'static [&'static str] {
+        match self {
+            QuotaKind::Entries => &[
+                "archive entries that are no longer relevant",
+                "dedupe overlapping entries into one",
+                "raise max_entries in the knowledge_quotas config",
+            ],
+            QuotaKind::IndexSize => &[
+                "compress the index by migrating to a smaller embedding model (`rhema knowledge migrate --model ...`)",
+                "dedupe overlapping entries before re-indexing",
+                "archive entries so they drop out of the index",
+            ],
+            QuotaKind::EmbeddingSpend => &[
+                "dedupe overlapping entries so fewer texts are embedded",
+                "resume interrupted runs instead of passing --restart",
+                "wait for next month's budget or raise max_monthly_embedding_usd",
+            ],
+        }
+    }
+
+    fn format(&self, value: f64) -> String {
+        match self {
+            QuotaKind::Entries => format!("{} entries", value),
+            QuotaKind::IndexSize => format!("{:.1} MB", value / 1_000_000.0),
+            QuotaKind::EmbeddingSpend => format!("${:.2}", value),
+        }
+    }
+}
+
+impl fmt::Display for QuotaKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            QuotaKind::Entries => "entry",
+            QuotaKind::IndexSize => "index size",
+            QuotaKind::EmbeddingSpend => "monthly embedding spend",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A limit that is, or would be, exceeded
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuotaViolation {
+    /// Scope whose limit is exceeded, `None` for the global limit
+    pub scope: Option<String>,
+    pub kind: QuotaKind,
+    pub used: f64,
+    pub limit: f64,
+}
+
+impl fmt::Display for QuotaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let owner = match &self.scope {
+            Some(scope) => format!("scope {}", scope),
+            None => "the repository".to_string(),
+        };
+        write!(
+            f,
+            "{} quota of {} exceeded: {} of {}; {}",
+            self.kind,
+            owner,
+            self.kind.format(self.used),
+            self.kind.format(self.limit),
+            self.kind.suggestions().join(", or ")
+        )
+    }
+}
+
+impl From<QuotaViolation> for KnowledgeError {
+    fn from(violation: QuotaViolation) -> Self {
+        KnowledgeError::QuotaExceeded(violation.to_string())
+    }
+}
+
+/// Knowledge usage of one scope, or totals across scopes
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct KnowledgeUsage {
+    pub entries: usize,
+    pub index_bytes: u64,
+    /// Tokens embedded this month
+    pub embedding_tokens: u64,
+    pub embedding_usd: f64,
+}
+
+impl KnowledgeUsage {
+    fn add(&mut self, other: &KnowledgeUsage) {
+        self.entries += other.entries;
+        self.index_bytes += other.index_bytes;
+        self.embedding_tokens += other.embedding_tokens;
+        self.embedding_usd += other.embedding_usd;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScopeUsage {
+    pub scope: String,
+    pub path: PathBuf,
+    #[serde(flatten)]
+    pub usage: KnowledgeUsage,
+    pub limits: QuotaLimits,
+}
+
+/// Usage of every scope against its quotas
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KnowledgeUsageReport {
+    /// Month the spend figures cover, `YYYY-MM`
+    pub month: String,
+    pub scopes: Vec<ScopeUsage>,
+    pub total: KnowledgeUsage,
+    pub global_limits: QuotaLimits,
+    pub violations: Vec<QuotaViolation>,
+}
+
+/// Embedding spend per month and scope
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SpendLedger {
+    months: BTreeMap<String, BTreeMap<String, SpendRecord>>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+struct SpendRecord {
+    tokens: u64,
+    usd: f64,
+}
+
+/// Enforces knowledge quotas for a repository
+#[derive(Debug, Clone)]
+pub struct KnowledgeQuotas {
+    repo_root: PathBuf,
+    config: KnowledgeQuotaConfig,
+}
+
+impl KnowledgeQuotas {
+    pub fn new(repo_root: impl Into<PathBuf>, config: KnowledgeQuotaConfig) -> Self {
+        Self {
+            repo_root: repo_root.into(),
+            config,
+        }
+    }
+
+    /// Quotas configured by the repository at `repo_root`
+    pub fn open(repo_root: &Path) -> RhemaResult<Self> {
+        Ok(Self::new(repo_root, KnowledgeQuotaConfig::load(repo_root)?))
+    }
+
+    pub fn config(&self) -> &KnowledgeQuotaConfig {
+        &self.config
+    }
+
+    /// Usage of every scope this month, with the quotas already exceeded
+    pub fn usage(&self) -> RhemaResult<KnowledgeUsageReport> {
+        let ledger = self.load_ledger()?;
+        let month = current_month();
+        let mut scopes = Vec::new();
+        let mut total = KnowledgeUsage::default();
+        let mut violations = Vec::new();
+
+        let mut discovered = discover_scopes(&self.repo_root)?;
+        discovered.sort_by(|a, b| a.path.cmp(&b.path));
+        for scope in &discovered {
+            let name = &scope.definition.name;
+            let usage = scope_usage(&scope.path, ledger.record(&month, name))?;
+            let limits = self.limits_for(name);
+            violations.extend(exceeded(Some(name), &limits, &usage));
+            total.add(&usage);
+            scopes.push(ScopeUsage {
+                scope: name.clone(),
+                path: scope.path.clone(),
+                usage,
+                limits,
+            });
+        }
+        violations.extend(exceeded(None, &self.config.global, &total));
+
+        Ok(KnowledgeUsageReport {
+            month,
+            scopes,
+            total,
+            global_limits: self.config.global.clone(),
+            violations,
+        })
+    }
+
+    /// Fail if adding `additional` entries to the scope at `scope_path`
+    /// would exceed an entry quota
+    pub fn check_entries(&self, scope_path: &Path, additional: usize) -> KnowledgeResult<()> {
+        if additional == 0 {
+            return Ok(());
+        }
+        self.check(scope_path, |usage| usage.entries += additional)
+    }
+
+    /// Fail if embedding `inputs` into the scope at `scope_path` would
+    /// exceed this month's spend quota or, when the model `dimension` is
+    /// known, an index size quota
+    pub fn check_embedding(
+        &self,
+        scope_path: &Path,
+        inputs: &[EmbeddingInput],
+        dimension: Option<usize>,
+    ) -> KnowledgeResult<()> {
+        let tokens = estimate_tokens(inputs) as u64;
+        let usd = self.cost(tokens);
+        self.check(scope_path, |usage| {
+            usage.embedding_tokens += tokens;
+            usage.embedding_usd += usd;
+            if let Some(dimension) = dimension {
+                usage.index_bytes = usage
+                    .index_bytes
+                    .max((inputs.len() * dimension * JSON_BYTES_PER_VALUE) as u64);
+            }
+        })
+    }
+
+    /// Add embedded `tokens` to the scope's spend for this month
+    pub fn record_embedding(&self, scope_path: &Path, tokens: u64) -> KnowledgeResult<()> {
+        if tokens == 0 {
+            return Ok(());
+        }
+        let name = scope_name(scope_path)?;
+        let mut ledger = self.load_ledger()?;
+        let record = ledger
+            .months
+            .entry(current_month())
+            .or_default()
+            .entry(name)
+            .or_default();
+        record.tokens += tokens;
+        record.usd += self.cost(tokens);
+
+        let path = self.repo_root.join(EMBEDDING_SPEND_FILE);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_vec_pretty(&ledger)?)?;
+        Ok(())
+    }
+
+    /// Check the scope and global quotas against usage changed by `grow`.
+    /// Only limits the change pushes past are reported, so shrinking an
+    /// over-quota scope is never blocked.
+    fn check(&self, scope_path: &Path, grow: impl Fn(&mut KnowledgeUsage)) -> KnowledgeResult<()> {
+        let name = scope_name(scope_path)?;
+        let report = self
+            .usage()
+            .map_err(|e| KnowledgeError::InvalidData(e.to_string()))?;
+        let current = report
+            .scopes
+            .iter()
+            .find(|scope| scope.scope == name)
+            .map(|scope| scope.usage.clone())
+            .unwrap_or_default();
+        let mut projected = current.clone();
+        grow(&mut projected);
+
+        let mut total = report.total;
+        total.entries = total.entries - current.entries + projected.entries;
+        total.index_bytes = total.index_bytes - current.index_bytes + projected.index_bytes;
+        total.embedding_tokens += projected.embedding_tokens - current.embedding_tokens;
+        total.embedding_usd += projected.embedding_usd - current.embedding_usd;
+
+        let grew = |kind: QuotaKind| match kind {
+            QuotaKind::Entries => projected.entries > current.entries,
+            QuotaKind::IndexSize => projected.index_bytes > current.index_bytes,
+            QuotaKind::EmbeddingSpend => projected.embedding_usd > current.embedding_usd,
+        };
+        let limits = self.limits_for(&name);
+        exceeded(Some(&name), &limits, &projected)
+            .into_iter()
+            .chain(exceeded(None, &self.config.global, &total))
+            .find(|violation| grew(violation.kind))
+            .map_or(Ok(()), |violation| Err(violation.into()))
+    }
+
+    fn limits_for(&self, scope: &str) -> QuotaLimits {
+        self.config.scopes.get(scope).cloned().unwrap_or_default()
+    }
+
+    fn cost(&self, tokens: u64) -> f64 {
+        tokens as f64 / 1_000_000.0 * self.config.cost_per_million_tokens
+    }
+
+    fn load_ledger(&self) -> KnowledgeResult<SpendLedger> {
+        match std::fs::read(self.repo_root.join(EMBEDDING_SPEND_FILE)) {
+            Ok(content) => Ok(serde_json::from_slice(&content)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(SpendLedger::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+impl SpendLedger {
+    fn record(&self, month: &str, scope: &str) -> SpendRecord {
+        self.months
+            .get(month)
+            .and_then(|scopes| scopes.get(scope))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+fn current_month() -> String {
+    Utc::now().format("%Y-%m").to_string()
+}
+
+fn scope_name(scope_path: &Path) -> KnowledgeResult<String> {
+    Scope::new(scope_path.to_path_buf())
+        .map(|scope| scope.definition.name)
+        .map_err(|e| KnowledgeError::InvalidData(e.to_string()))
+}
+
+fn scope_usage(scope_path: &Path, spend: SpendRecord) -> RhemaResult<KnowledgeUsage> {
+    let knowledge_file = scope_path.join("knowledge.yaml");
+    let entries = if knowledge_file.exists() {
+        read_yaml_file::<Knowledge>(&knowledge_file)?.entries.len()
+    } else {
+        0
+    };
+    let index_bytes = [EMBEDDINGS_FILE, STAGED_EMBEDDINGS_FILE]
+        .iter()
+        .filter_map(|file| std::fs::metadata(scope_path.join(file)).ok())
+        .map(|metadata| metadata.len())
+        .sum();
+    Ok(KnowledgeUsage {
+        entries,
+        index_bytes,
+        embedding_tokens: spend.tokens,
+        embedding_usd: spend.usd,
+    })
+}
+
+fn exceeded(
+    scope: Option<&str>,
+    limits: &QuotaLimits,
+    usage: &KnowledgeUsage,
+) -> Vec<QuotaViolation> {
+    [
+        (
+            QuotaKind::Entries,
+            usage.entries as f64,
+            limits.max_entries.map(|limit| limit as f64),
+        ),
+        (
+            QuotaKind::IndexSize,
+            usage.index_bytes as f64,
+            limits.max_index_bytes.map(|limit| limit as f64),
+        ),
+        (
+            QuotaKind::EmbeddingSpend,
+            usage.embedding_usd,
+            limits.max_monthly_embedding_usd,
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(kind, used, limit)| {
+        let limit = limit?;
+        (used > limit).then(|| QuotaViolation {
+            scope: scope.map(str::to_string),
+            kind,
+            used,
+            limit,
+        })
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn scope(root: &Path, name: &str, entries: usize) -> PathBuf {
+        let path = root.join(name).join(".rhema");
+        std::fs::create_dir_all(&path).unwrap();
+        std::fs::write(
+            path.join("rhema.yaml"),
+            format!("name: {}\nscope_type: library\nversion: 1.0.0\n", name),
+        )
+        .unwrap();
+        let mut knowledge = String::from("entries:\n");
+        for i in 0..entries {
+            knowledge.push_str(&format!(
+                "  - id: k{i}\n    title: Entry {i}\n    content: Text {i}\n    confidence: 5\n    created_at: 2025-01-01T00:00:00Z\n"
+            ));
+        }
+        std::fs::write(path.join("knowledge.yaml"), knowledge).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_quotas_block_growth_past_scope_and_global_limits() {
+        let temp = TempDir::new().unwrap();
+        let api = scope(temp.path(), "api", 3);
+        let web = scope(temp.path(), "web", 4);
+
+        let mut config = KnowledgeQuotaConfig {
+            global: QuotaLimits {
+                max_entries: Some(10),
+                max_monthly_embedding_usd: Some(1.0),
+                ..QuotaLimits::default()
+            },
+            cost_per_million_tokens: 1_000.0,
+            ..KnowledgeQuotaConfig::default()
+        };
+        config.scopes.insert(
+            "api".to_string(),
+            QuotaLimits {
+                max_entries: Some(4),
+                ..QuotaLimits::default()
+            },
+        );
+        let quotas = KnowledgeQuotas::new(temp.path(), config);
+
+        quotas.check_entries(&api, 1).unwrap();
+        let error = quotas.check_entries(&api, 2).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("entry quota of scope api exceeded"));
+        assert!(error.to_string().contains("archive"));
+        quotas.check_entries(&web, 3).unwrap();
+        assert!(quotas.check_entries(&web, 4).is_err());
+
+        // 2000 characters ≈ 500 tokens ≈ $0.50 at this price
+        let inputs = vec![EmbeddingInput::new("knowledge:k0", "x".repeat(2000))];
+        quotas.check_embedding(&api, &inputs, None).unwrap();
+        quotas.record_embedding(&api, 600).unwrap();
+        let error = quotas.check_embedding(&web, &inputs, None).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("monthly embedding spend quota of the repository"));
+
+        let report = quotas.usage().unwrap();
+        assert_eq!(report.total.entries, 7);
+        assert_eq!(report.scopes[0].scope, "api");
+        assert_eq!(report.scopes[0].usage.embedding_tokens, 600);
+        assert!(report.violations.is_empty());
+    }
+}
@@ -0,0 +1,402 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Cache for synthesized summaries and RAG answers.
+//!
+//! Each entry records the content hash of every source it was built from.
+//! A lookup only hits when the current sources hash to exactly the same
+//! values, so a summary is never served once any of its sources has changed,
+//! been removed, or been joined by a new source. Sources can also be
+//! invalidated eagerly when their content is re-indexed.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use tokio::sync::RwLock;
+use tracing::debug;
+
+use crate::types::SemanticResult;
+
+/// Hash of a source entry's content
+pub fn content_hash(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
+/// A source entry and the hash of the content a summary was built from
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SourceFingerprint {
+    pub source_key: String,
+    pub content_hash: String,
+}
+
+impl SourceFingerprint {
+    pub fn new(source_key: &str, content: &str) -> Self {
+        Self {
+            source_key: source_key.to_string(),
+            content_hash: content_hash(content),
+        }
+    }
+
+    /// Fingerprints of the search results a summary is built from
+    pub fn from_results(results: &[SemanticResult]) -> Vec<Self> {
+        results
+            .iter()
+            .map(|result| Self::new(&result.cache_key, &result.content))
+            .collect()
+    }
+}
+
+/// Summary cache configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SummaryCacheConfig {
+    pub enabled: bool,
+    /// Maximum number of cached summaries; the least recently used is evicted
+    pub max_entries: usize,
+    /// Optional upper bound on entry age, independent of source changes
+    pub max_age_secs: Option<u64>,
+}
+
+impl Default for SummaryCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_entries: 512,
+            max_age_secs: None,
+        }
+    }
+}
+
+/// Hit rate and staleness metrics
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SummaryCacheStats {
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+    /// Lookups that found an entry built from outdated sources
+    pub stale_lookups: u64,
+    /// Entries dropped because a source was re-indexed with new content
+    pub source_invalidations: u64,
+    /// Entries dropped for exceeding `max_age_secs`
+    pub expirations: u64,
+    pub evictions: u64,
+    /// Age of the oldest cached entry
+    pub oldest_entry_age_secs: Option<i64>,
+    /// Mean age of the entries served on hits
+    pub average_served_age_secs: f64,
+}
+
+impl SummaryCacheStats {
+    /// Fraction of lookups served from the cache
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+
+    /// Fraction of lookups that found an outdated entry
+    pub fn staleness_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.stale_lookups as f64 / lookups as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SummaryEntry<T> {
+    value: T,
+    /// Source key → content hash
+    sources: BTreeMap<String, String>,
+    created_at: DateTime<Utc>,
+    last_used: DateTime<Utc>,
+}
+
+#[derive(Debug)]
+struct SummaryCacheState<T> {
+    entries: HashMap<String, SummaryEntry<T>>,
+    /// Source key → cache keys built from it
+    dependents: HashMap<String, HashSet<String>>,
+    stats: SummaryCacheStats,
+    served_age_total_secs: f64,
+}
+
+/// Summary cache keyed by request and validated against source content hashes
+#[derive(Debug)]
+pub struct SummaryCache<T> {
+    config: SummaryCacheConfig,
+    state: RwLock<SummaryCacheState<T>>,
+}
+
+impl<T: Clone> SummaryCache<T> {
+    pub fn new(config: SummaryCacheConfig) -> Self {
+        Self {
+            config,
+            state: RwLock::new(SummaryCacheState {
+                entries: HashMap::new(),
+                dependents: HashMap::new(),
+                stats: SummaryCacheStats::default(),
+                served_age_total_secs: 0.0,
+            }),
+        }
+    }
+
+    /// Cache key for a request, e.g. `request_key("synthesis", &[topic, scope])`
+    pub fn request_key(kind: &str, parts: &[&str]) -> String {
+        let mut hasher = Sha256::new();
+        for part in parts {
+            hasher.update(part.trim().to_lowercase().as_bytes());
+            hasher.update([0]);
+        }
+        format!("{}:{:x}", kind, hasher.finalize())
+    }
+
+    /// Cached value for `key` if it was built from exactly `sources`
+    pub async fn get(&self, key: &str, sources: &[SourceFingerprint]) -> Option<T> {
+        if !self.config.enabled {
+            return None;
+        }
+
+        let now = Utc::now();
+        let mut guard = self.state.write().await;
+        let state = &mut *guard;
+        let Some(entry) = state.entries.get_mut(key) else {
+            state.stats.misses += 1;
+            return None;
+        };
+
+        let expired = self
+            .config
+            .max_age_secs
+            .is_some_and(|max_age| (now - entry.created_at).num_seconds() > max_age as i64);
+        let current: BTreeMap<String, String> = sources
+            .iter()
+            .map(|source| (source.source_key.clone(), source.content_hash.clone()))
+            .collect();
+
+        if expired || entry.sources != current {
+            if expired {
+                state.stats.expirations += 1;
+            } else {
+                state.stats.stale_lookups += 1;
+            }
+            state.stats.misses += 1;
+            debug!("Summary cache entry {} is stale", key);
+            Self::remove(state, key);
+            return None;
+        }
+
+        entry.last_used = now;
+        state.stats.hits += 1;
+        state.served_age_total_secs += (now - entry.created_at).num_milliseconds() as f64 / 1000.0;
+        Some(entry.value.clone())
+    }
+
+    /// Cache `value` for `key`, recording the sources it was built from
+    pub async fn insert(&self, key: &str, sources: &[SourceFingerprint], value: T) {
+        if !self.config.enabled || self.config.max_entries == 0 {
+            return;
+        }
+
+        let now = Utc::now();
+        let mut state = self.state.write().await;
+        Self::remove(&mut state, key);
+
+        while state.entries.len() >= self.config.max_entries {
+            let Some(oldest) = state
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            Self::remove(&mut state, &oldest);
+            state.stats.evictions += 1;
+        }
+
+        for source in sources {
+            state
+                .dependents
+                .entry(source.source_key.clone())
+                .or_default()
+                .insert(key.to_string());
+        }
+        state.entries.insert(
+            key.to_string(),
+            SummaryEntry {
+                value,
+                sources: sources
+                    .iter()
+                    .map(|source| (source.source_key.clone(), source.content_hash.clone()))
+                    .collect(),
+                created_at: now,
+                last_used: now,
+            },
+        );
+    }
+
+    /// Drop every entry built from `source_key` whose recorded hash differs
+    /// from `current_hash` (all of them when the source was removed).
+    /// Returns the number of entries invalidated.
+    pub async fn invalidate_source(&self, source_key: &str, current_hash: Option<&str>) -> usize {
+        let mut state = self.state.write().await;
+        let Some(keys) = state.dependents.get(source_key).cloned() else {
+            return 0;
+        };
+
+        let stale: Vec<String> = keys
+            .into_iter()
+            .filter(|key| {
+                state.entries.get(key).is_some_and(|entry| {
+                    entry.sources.get(source_key).map(String::as_str) != current_hash
+                })
+            })
+            .collect();
+        for key in &stale {
+            Self::remove(&mut state, key);
+        }
+        state.stats.source_invalidations += stale.len() as u64;
+        if !stale.is_empty() {
+            debug!(
+                "Invalidated {} cached summaries after {} changed",
+                stale.len(),
+                source_key
+            );
+        }
+        stale.len()
+    }
+
+    /// Remove every entry
+    pub async fn clear(&self) {
+        let mut state = self.state.write().await;
+        state.entries.clear();
+        state.dependents.clear();
+    }
+
+    /// Hit rate and staleness metrics
+    pub async fn stats(&self) -> SummaryCacheStats {
+        let state = self.state.read().await;
+        let now = Utc::now();
+        let mut stats = state.stats.clone();
+        stats.entries = state.entries.len();
+        stats.oldest_entry_age_secs = state
+            .entries
+            .values()
+            .map(|entry| (now - entry.created_at).num_seconds())
+            .max();
+        stats.average_served_age_secs = if stats.hits == 0 {
+            0.0
+        } else {
+            state.served_age_total_secs / stats.hits as f64
+        };
+        stats
+    }
+
+    fn remove(state: &mut SummaryCacheState<T>, key: &str) {
+        let Some(entry) = state.entries.remove(key) else {
+            return;
+        };
+        for source_key in entry.sources.keys() {
+            if let Some(dependents) = state.dependents.get_mut(source_key) {
+                dependents.remove(key);
+                if dependents.is_empty() {
+                    state.dependents.remove(source_key);
+                }
+            }
+        }
+    }
+}
+
+impl<T: Clone> Default for SummaryCache<T> {
+    fn default() -> Self {
+        Self::new(SummaryCacheConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_entries_invalidate_when_sources_change() {
+        let cache: SummaryCache<String> = SummaryCache::default();
+        let key = SummaryCache::<String>::request_key("synthesis", &["Caching", "api"]);
+        let sources = vec![
+            SourceFingerprint::new("knowledge:redis", "Use Redis for sessions"),
+            SourceFingerprint::new("decisions:ttl", "Sessions expire after 1h"),
+        ];
+
+        assert!(cache.get(&key, &sources).await.is_none());
+        cache.insert(&key, &sources, "summary".to_string()).await;
+        assert_eq!(cache.get(&key, &sources).await.as_deref(), Some("summary"));
+
+        // Edited source content is a stale lookup
+        let edited = vec![
+            sources[0].clone(),
+            SourceFingerprint::new("decisions:ttl", "Sessions expire after 2h"),
+        ];
+        assert!(cache.get(&key, &edited).await.is_none());
+
+        // Re-indexing a source eagerly drops summaries built from old content
+        cache.insert(&key, &sources, "summary".to_string()).await;
+        assert_eq!(
+            cache
+                .invalidate_source("knowledge:redis", Some(&sources[0].content_hash))
+                .await,
+            0
+        );
+        assert_eq!(
+            cache
+                .invalidate_source("knowledge:redis", Some(&content_hash("Use Memcached")))
+                .await,
+            1
+        );
+
+        let stats = cache.stats().await;
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 2);
+        assert_eq!(stats.stale_lookups, 1);
+        assert_eq!(stats.source_invalidations, 1);
+        assert_eq!(stats.entries, 0);
+        assert!((stats.hit_rate() - 1.0 / 3.0).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_least_recently_used_entry_is_evicted() {
+        let cache: SummaryCache<u32> = SummaryCache::new(SummaryCacheConfig {
+            max_entries: 2,
+            ..Default::default()
+        });
+        let sources = vec![SourceFingerprint::new("todos:1", "Ship it")];
+
+        cache.insert("a", &sources, 1).await;
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        cache.insert("b", &sources, 2).await;
+        tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        assert_eq!(cache.get("a", &sources).await, Some(1));
+        cache.insert("c", &sources, 3).await;
+
+        assert_eq!(cache.get("b", &sources).await, None);
+        assert_eq!(cache.get("a", &sources).await, Some(1));
+        assert_eq!(cache.stats().await.evictions, 1);
+    }
+}
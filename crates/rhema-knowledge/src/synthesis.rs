@@ -26,7 +26,12 @@ use crate::types::{
 };
 use chrono::Datelike;
 
-use super::{embedding::EmbeddingManager, search::SemanticSearchEngine, vector::VectorStore};
+use super::{
+    embedding::EmbeddingManager,
+    search::SemanticSearchEngine,
+    summary_cache::{SourceFingerprint, SummaryCache},
+    vector::VectorStore,
+};
 
 /// Error types for knowledge synthesis
 #[derive(Error, Debug)]
@@ -56,6 +61,7 @@ pub struct KnowledgeSynthesizer {
     vector_store: Arc<dyn VectorStore>,
     search_engine: Arc<SemanticSearchEngine>,
     config: SynthesisConfig,
+    summary_cache: Arc<SummaryCache<KnowledgeSynthesis>>,
 }
 
 /// Synthesis configuration
@@ -99,6 +105,7 @@ impl KnowledgeSynthesizer {
             )),
             search_engine: Arc::new(SemanticSearchEngine::new_dummy()),
             config: SynthesisConfig::default(),
+            summary_cache: Arc::new(SummaryCache::default()),
         }
     }
 
@@ -127,6 +134,7 @@ impl KnowledgeSynthesizer {
             vector_store,
             search_engine,
             config: SynthesisConfig::default(),
+            summary_cache: Arc::new(SummaryCache::default()),
         })
     }
 
@@ -159,6 +167,18 @@ impl KnowledgeSynthesizer {
             .into());
         }
 
+        // Reuse an earlier synthesis if it was built from the same source content
+        let sources = SourceFingerprint::from_results(&search_results);
+        let methods = format!("{:?}", self.config.synthesis_methods);
+        let cache_key = SummaryCache::<KnowledgeSynthesis>::request_key(
+            "synthesis",
+            &[topic, scope_path.unwrap_or(""), &methods],
+        );
+        if let Some(cached) = self.summary_cache.get(&cache_key, &sources).await {
+            debug!("Serving cached synthesis for topic: {}", topic);
+            return Ok(cached);
+        }
+
         // Group results by synthesis method
         let mut synthesis_results = Vec::new();
 
@@ -198,9 +218,17 @@ impl KnowledgeSynthesizer {
             topic, synthesis.confidence_score
         );
 
+        self.summary_cache
+            .insert(&cache_key, &sources, synthesis.clone())
+            .await;
         Ok(synthesis)
     }
 
+    /// Cache of syntheses keyed on the content hashes of their sources
+    pub fn summary_cache(&self) -> Arc<SummaryCache<KnowledgeSynthesis>> {
+        self.summary_cache.clone()
+    }
+
     /// Synthesize using a specific method
     async fn synthesize_with_method(
         &self,
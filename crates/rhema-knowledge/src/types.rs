@@ -63,6 +63,9 @@ pub enum KnowledgeError {
     #[error("Network error: {0}")]
     NetworkError(String),
 
+    #[error("Quota exceeded: {0}")]
+    QuotaExceeded(String),
+
     #[error("Engine error: {0}")]
     EngineError(#[from] crate::engine::EngineError),
 
@@ -416,6 +419,7 @@ impl Default for UnifiedEngineConfig {
                     pinecone_index_name: None,
                 },
                 semantic_search: SemanticSearchConfig::default(),
+                compression: crate::compression::CompressionProfile::default(),
             },
             cache: CacheConfig {
                 storage: StorageConfig {
@@ -475,6 +479,9 @@ pub struct RAGConfig {
     pub overlap_size: usize,
     pub vector_store: VectorStoreConfig,
     pub semantic_search: SemanticSearchConfig,
+    /// Compression applied to context before injection
+    #[serde(default)]
+    pub compression: crate::compression::CompressionProfile,
 }
 
 /// Vector store configuration
@@ -370,6 +370,165 @@ impl VectorStore for MockVectorStore {
     }
 }
 
+/// Brute-force vector store kept in process memory, for local indexes that
+/// are rebuilt per command
+pub struct InMemoryVectorStore {
+    collection_name: String,
+    dimension: usize,
+    distance_metric: DistanceMetric,
+    vectors: tokio::sync::RwLock<HashMap<String, VectorRecord>>,
+}
+
+impl InMemoryVectorStore {
+    pub fn new(collection_name: String, dimension: usize, distance_metric: DistanceMetric) -> Self {
+        Self {
+            collection_name,
+            dimension,
+            distance_metric,
+            vectors: tokio::sync::RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Store a vector along with its content and search metadata
+    pub async fn insert(
+        &self,
+        id: &str,
+        embedding: &[f32],
+        content: Option<String>,
+        metadata: Option<SearchResultMetadata>,
+    ) -> KnowledgeResult<()> {
+        if embedding.len() != self.dimension {
+            return Err(VectorError::DimensionMismatch {
+                expected: self.dimension,
+                actual: embedding.len(),
+            }
+            .into());
+        }
+        self.vectors.write().await.insert(
+            id.to_string(),
+            VectorRecord {
+                id: id.to_string(),
+                embedding: embedding.to_vec(),
+                content,
+                metadata,
+                created_at: Utc::now(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Higher is more similar for every metric
+    fn score(&self, a: &[f32], b: &[f32]) -> f32 {
+        match self.distance_metric {
+            DistanceMetric::Cosine => {
+                let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+                let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+                let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+                if norm_a == 0.0 || norm_b == 0.0 {
+                    0.0
+                } else {
+                    dot / (norm_a * norm_b)
+                }
+            }
+            DistanceMetric::DotProduct => a.iter().zip(b).map(|(x, y)| x * y).sum(),
+            DistanceMetric::Euclidean => {
+                let distance = a
+                    .iter()
+                    .zip(b)
+                    .map(|(x, y)| (x - y).powi(2))
+                    .sum::<f32>()
+                    .sqrt();
+                1.0 / (1.0 + distance)
+            }
+            DistanceMetric::Manhattan => {
+                let distance: f32 = a.iter().zip(b).map(|(x, y)| (x - y).abs()).sum();
+                1.0 / (1.0 + distance)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl VectorStore for InMemoryVectorStore {
+    async fn store(
+        &self,
+        id: &str,
+        embedding: &[f32],
+        metadata: Option<SearchResultMetadata>,
+    ) -> KnowledgeResult<()> {
+        self.insert(id, embedding, None, metadata).await
+    }
+
+    async fn store_with_metadata(
+        &self,
+        id: &str,
+        embedding: &[f32],
+        content: &str,
+        _metadata: Option<CacheEntryMetadata>,
+    ) -> KnowledgeResult<()> {
+        self.insert(id, embedding, Some(content.to_string()), None)
+            .await
+    }
+
+    async fn search(
+        &self,
+        query_embedding: &[f32],
+        limit: usize,
+    ) -> KnowledgeResult<Vec<VectorSearchResult>> {
+        if query_embedding.len() != self.dimension {
+            return Err(VectorError::DimensionMismatch {
+                expected: self.dimension,
+                actual: query_embedding.len(),
+            }
+            .into());
+        }
+        let vectors = self.vectors.read().await;
+        let mut results: Vec<VectorSearchResult> = vectors
+            .values()
+            .map(|record| VectorSearchResult {
+                id: record.id.clone(),
+                score: self.score(query_embedding, &record.embedding),
+                embedding: record.embedding.clone(),
+                content: record.content.clone(),
+                metadata: record.metadata.clone(),
+            })
+            .collect();
+        results.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        results.truncate(limit);
+        Ok(results)
+    }
+
+    async fn delete(&self, id: &str) -> KnowledgeResult<()> {
+        self.vectors.write().await.remove(id);
+        Ok(())
+    }
+
+    async fn get(&self, id: &str) -> KnowledgeResult<Option<VectorRecord>> {
+        Ok(self.vectors.read().await.get(id).cloned())
+    }
+
+    async fn collection_info(&self) -> KnowledgeResult<VectorCollectionInfo> {
+        let vector_count = self.vectors.read().await.len();
+        Ok(VectorCollectionInfo {
+            name: self.collection_name.clone(),
+            vector_count,
+            dimension: self.dimension,
+            distance_metric: self.distance_metric.clone(),
+            size_bytes: (vector_count * self.dimension * std::mem::size_of::<f32>()) as u64,
+        })
+    }
+
+    async fn clear(&self) -> KnowledgeResult<()> {
+        self.vectors.write().await.clear();
+        Ok(())
+    }
+}
+
 /// Qdrant vector store configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QdrantConfig {
@@ -28,6 +28,10 @@ use crate::types::{
     ContextSize, LocomoError, PerformanceMetrics, QualityMetrics,
 };
 use rhema_core::RhemaResult;
+use rhema_testkit::{Distribution, RepoSpec, SyntheticRepo};
+
+/// Seed of the synthetic repository test contexts are rendered from
+const SYNTHETIC_REPO_SEED: u64 = 42;
 
 /// LOCOMO benchmark engine
 pub struct LocomoBenchmarkEngine {
@@ -441,15 +445,17 @@ impl LocomoBenchmarkEngine {
     }
 
     async fn generate_test_context(&self, size: &ContextSize) -> RhemaResult<Context> {
-        let content_size = match size {
-            ContextSize::Small => 5000,        // ~5KB
-            ContextSize::Medium => 50000,      // ~50KB
-            ContextSize::Large => 500000,      // ~500KB
-            ContextSize::VeryLarge => 2000000, // ~2MB
-            ContextSize::MultiScope => 100000, // ~100KB
+        let (content_size, scopes) = match size {
+            ContextSize::Small => (5000, 1),         // ~5KB
+            ContextSize::Medium => (50000, 2),       // ~50KB
+            ContextSize::Large => (500000, 4),       // ~500KB
+            ContextSize::VeryLarge => (2000000, 8),  // ~2MB
+            ContextSize::MultiScope => (100000, 12), // ~100KB
         };
 
-        let content = self.generate_synthetic_content(content_size).await?;
+        let content = self
+            .generate_synthetic_content(content_size, scopes)
+            .await?;
 
         Ok(Context {
             id: uuid::Uuid::new_v4().to_string(),
@@ -470,45 +476,36 @@ impl LocomoBenchmarkEngine {
         })
     }
 
-    async fn generate_synthetic_content(&self, size: usize) -> RhemaResult<String> {
-        // Generate synthetic content for benchmarking
-        let words = vec![
-            "context",
-            "management",
-            "optimization",
-            "benchmark",
-            "performance",
-            "quality",
-            "assessment",
-            "retrieval",
-            "compression",
-            "persistence",
-            "integration",
-            "evolution",
-            "tracking",
-            "analysis",
-            "metrics",
-            "framework",
-            "system",
-            "architecture",
-            "design",
-            "implementation",
-        ];
-
-        let mut content = String::new();
-        let mut current_size = 0;
-
-        while current_size < size {
-            let word = words[current_size % words.len()];
-            content.push_str(word);
-            content.push(' ');
-            current_size += word.len() + 1;
-
-            if current_size % 100 == 0 {
-                content.push('\n');
+    /// Scope files of a seeded synthetic repository, so every run measures
+    /// the same realistic context
+    async fn generate_synthetic_content(&self, size: usize, scopes: usize) -> RhemaResult<String> {
+        // An entry renders to roughly 800 bytes; generate a little more
+        // context than requested and cut it to size
+        let per_kind = size / (scopes * 1000) + 1;
+        let mut spec = RepoSpec::default()
+            .with_seed(SYNTHETIC_REPO_SEED)
+            .with_scopes(scopes);
+        spec.entries.todos = Distribution::fixed(per_kind);
+        spec.entries.knowledge = Distribution::fixed(per_kind);
+        spec.entries.content_words = Distribution::range(40, 120);
+        let repo = SyntheticRepo::generate(&spec)?;
+
+        let mut content = String::with_capacity(size);
+        'scopes: for scope in &repo.scopes {
+            for (file, body) in scope.files()? {
+                content.push_str(&format!("# {}/{}\n", scope.rhema_dir().display(), file));
+                content.push_str(&body);
+                if content.len() >= size {
+                    break 'scopes;
+                }
             }
         }
 
+        let mut end = size.min(content.len());
+        while !content.is_char_boundary(end) {
+            end -= 1;
+        }
+        content.truncate(end);
         Ok(content)
     }
 
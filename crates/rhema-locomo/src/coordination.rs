@@ -0,0 +1,198 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Multi-agent coordination metrics for LOCOMO reports.
+//!
+//! Converts the counters kept by the real-time coordination system
+//! (`CoordinationStats` and each agent's `AgentPerformanceMetrics`) into
+//! efficiency ratios that are reported next to the context-quality metrics.
+
+use rhema_coordination::agent::real_time_coordination::{
+    AgentPerformanceMetrics, CoordinationStats,
+};
+use rhema_coordination::RealTimeCoordinationSystem;
+use serde::{Deserialize, Serialize};
+
+/// Conflicts per task attempt above which a report recommends action
+const CONFLICT_RATE_THRESHOLD: f64 = 0.1;
+
+/// Reworked tasks per task attempt above which a report recommends action
+const REWORK_RATE_THRESHOLD: f64 = 0.15;
+
+/// Messages per completed task above which a report recommends action
+const MESSAGES_PER_TASK_THRESHOLD: f64 = 20.0;
+
+/// Coordination efficiency of a multi-agent workflow
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CoordinationMetrics {
+    pub active_agents: usize,
+    pub total_messages: usize,
+    pub messages_failed: usize,
+    pub tasks_completed: usize,
+    pub tasks_failed: usize,
+    pub conflicts: usize,
+    pub reworked_tasks: usize,
+    pub messages_per_completed_task: f64,
+    /// Conflicts per task attempt
+    pub conflict_rate: f64,
+    /// Share of task attempts that had to be redone
+    pub rework_rate: f64,
+    pub coordination_efficiency: f64,
+    pub avg_response_time_ms: f64,
+}
+
+impl CoordinationMetrics {
+    /// Combine system-wide statistics with the metrics of each agent.
+    ///
+    /// Failed task attempts count as rework until a more precise figure is
+    /// supplied with [`with_reworked_tasks`](Self::with_reworked_tasks).
+    pub fn from_stats(stats: &CoordinationStats, agents: &[AgentPerformanceMetrics]) -> Self {
+        let tasks_completed = agents.iter().map(|agent| agent.tasks_completed).sum();
+        let tasks_failed = agents.iter().map(|agent| agent.tasks_failed).sum();
+
+        let mut metrics = Self {
+            active_agents: stats.active_agents,
+            total_messages: stats.total_messages,
+            messages_failed: stats.messages_failed,
+            tasks_completed,
+            tasks_failed,
+            conflicts: 0,
+            reworked_tasks: tasks_failed,
+            messages_per_completed_task: 0.0,
+            conflict_rate: 0.0,
+            rework_rate: 0.0,
+            coordination_efficiency: stats.coordination_efficiency,
+            avg_response_time_ms: stats.avg_response_time_ms,
+        };
+        metrics.update_rates();
+        metrics
+    }
+
+    /// Snapshot the statistics of a running coordination system
+    pub async fn collect(system: &RealTimeCoordinationSystem) -> Self {
+        let agents: Vec<AgentPerformanceMetrics> = system
+            .get_all_agents()
+            .await
+            .into_iter()
+            .map(|agent| agent.performance_metrics)
+            .collect();
+        Self::from_stats(&system.get_stats(), &agents)
+    }
+
+    /// Set the number of conflicts detected between agents
+    pub fn with_conflicts(mut self, conflicts: usize) -> Self {
+        self.conflicts = conflicts;
+        self.update_rates();
+        self
+    }
+
+    /// Set the number of tasks that had to be redone
+    pub fn with_reworked_tasks(mut self, reworked_tasks: usize) -> Self {
+        self.reworked_tasks = reworked_tasks;
+        self.update_rates();
+        self
+    }
+
+    /// Score between 0.0 and 1.0, higher is better
+    pub fn efficiency_score(&self) -> f64 {
+        let conflict_score = 1.0 - self.conflict_rate.min(1.0);
+        let rework_score = 1.0 - self.rework_rate;
+        let messaging_score =
+            1.0 - (self.messages_per_completed_task / (2.0 * MESSAGES_PER_TASK_THRESHOLD)).min(1.0);
+        (self.coordination_efficiency.clamp(0.0, 1.0)
+            + conflict_score
+            + rework_score
+            + messaging_score)
+            / 4.0
+    }
+
+    /// Recommendations for the coordination figures that exceed their thresholds
+    pub fn recommendations(&self) -> Vec<String> {
+        let mut recommendations = Vec::new();
+        if self.conflict_rate > CONFLICT_RATE_THRESHOLD {
+            recommendations.push(format!(
+                "Agents conflict on {:.0}% of tasks - tighten file leases or partition work by scope",
+                self.conflict_rate * 100.0
+            ));
+        }
+        if self.rework_rate > REWORK_RATE_THRESHOLD {
+            recommendations.push(format!(
+                "{:.0}% of tasks are redone - improve the context handed to agents before they start",
+                self.rework_rate * 100.0
+            ));
+        }
+        if self.messages_per_completed_task > MESSAGES_PER_TASK_THRESHOLD {
+            recommendations.push(format!(
+                "Agents exchange {:.1} messages per completed task - reduce coordination chatter",
+                self.messages_per_completed_task
+            ));
+        }
+        recommendations
+    }
+
+    fn update_rates(&mut self) {
+        let attempts = self.tasks_completed + self.tasks_failed;
+        self.messages_per_completed_task = ratio(self.total_messages, self.tasks_completed);
+        self.conflict_rate = ratio(self.conflicts, attempts);
+        self.rework_rate = ratio(self.reworked_tasks, attempts).min(1.0);
+    }
+}
+
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent(tasks_completed: usize, tasks_failed: usize) -> AgentPerformanceMetrics {
+        AgentPerformanceMetrics {
+            tasks_completed,
+            tasks_failed,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_rates_from_coordination_stats() {
+        let stats = CoordinationStats {
+            total_messages: 120,
+            messages_delivered: 118,
+            messages_failed: 2,
+            active_agents: 2,
+            active_sessions: 1,
+            avg_response_time_ms: 40.0,
+            coordination_efficiency: 0.9,
+        };
+        let metrics = CoordinationMetrics::from_stats(&stats, &[agent(6, 1), agent(4, 1)]);
+
+        assert_eq!(metrics.tasks_completed, 10);
+        assert_eq!(metrics.messages_per_completed_task, 12.0);
+        assert_eq!(metrics.rework_rate, 2.0 / 12.0);
+        assert_eq!(metrics.conflict_rate, 0.0);
+
+        let metrics = metrics.with_conflicts(3).with_reworked_tasks(1);
+        assert_eq!(metrics.conflict_rate, 0.25);
+        assert_eq!(metrics.rework_rate, 1.0 / 12.0);
+        assert_eq!(metrics.recommendations().len(), 1);
+        assert!(metrics.efficiency_score() > 0.0 && metrics.efficiency_score() < 1.0);
+    }
+}
@@ -15,8 +15,10 @@
  */
 
 pub mod benchmark_engine;
+pub mod coordination;
 pub mod metrics;
 pub mod optimization;
+pub mod prompt_experiment;
 pub mod quality_assessor;
 pub mod reporting;
 pub mod types;
@@ -24,6 +26,7 @@ pub mod validation;
 
 // Re-export main types for convenience
 pub use benchmark_engine::{LocomoBenchmarkEngine, LocomoBenchmarkResult, LocomoBenchmarkSuite};
+pub use coordination::CoordinationMetrics;
 pub use types::{BenchmarkConfig, BenchmarkScenario};
 
 pub use quality_assessor::{
@@ -50,6 +53,11 @@ pub use optimization::{
     OptimizationResult,
 };
 
+pub use prompt_experiment::{
+    ExperimentReport, ExperimentTask, PromptExperiment, PromptExperimentConfig, PromptTaskExecutor,
+    TaskExecution,
+};
+
 pub use reporting::{
     Alert, ChartData, DashboardData, DashboardGenerator, DetailedMetrics, LocomoReport,
     LocomoReportingSystem, ReportSummary, ReportType, TrendAnalysis, TrendAnalyzer, TrendDirection,
@@ -0,0 +1,767 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tracing::{debug, info};
+
+use crate::quality_assessor::ContextQualityAssessor;
+use crate::types::{ContentType, Context, ContextMetadata, LocomoError};
+use rhema_core::schema::PromptPattern;
+use rhema_core::RhemaResult;
+
+/// A task run against both prompt versions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExperimentTask {
+    pub id: String,
+    /// What the agent is asked to do; also the relevance query for scoring
+    pub query: String,
+    /// Context substituted for `{{CONTEXT}}` in the prompt template
+    pub context: String,
+}
+
+/// What an agent produced for one task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskExecution {
+    pub response: String,
+    /// Whether the agent considered the task completed
+    pub successful: bool,
+    /// Agent feedback on the prompt
+    pub feedback: Option<String>,
+    /// Agent rating of the prompt (0.0-1.0)
+    pub rating: Option<f64>,
+}
+
+/// Executes a task with a rendered prompt
+#[async_trait]
+pub trait PromptTaskExecutor: Send + Sync {
+    async fn execute(&self, prompt: &str, task: &ExperimentTask) -> RhemaResult<TaskExecution>;
+}
+
+/// Configuration for an A/B prompt experiment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptExperimentConfig {
+    /// Baseline version
+    pub version_a: String,
+    /// Candidate version
+    pub version_b: String,
+    /// Times each task runs per version
+    pub repetitions: usize,
+    /// Confidence required to declare a winner (e.g. 0.95)
+    pub confidence_level: f64,
+}
+
+impl Default for PromptExperimentConfig {
+    fn default() -> Self {
+        Self {
+            version_a: String::new(),
+            version_b: String::new(),
+            repetitions: 3,
+            confidence_level: 0.95,
+        }
+    }
+}
+
+/// One execution of a task with one prompt version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExperimentRun {
+    pub task_id: String,
+    pub version: String,
+    pub quality_score: f64,
+    pub relevance_score: f64,
+    pub ai_consumption_score: f64,
+    pub successful: bool,
+    pub feedback: Option<String>,
+    pub rating: Option<f64>,
+    pub latency: Duration,
+}
+
+/// Aggregated results for one prompt version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionSummary {
+    pub version: String,
+    pub runs: usize,
+    pub mean_quality: f64,
+    pub quality_std_dev: f64,
+    pub mean_relevance: f64,
+    pub mean_ai_consumption: f64,
+    pub success_rate: f64,
+    /// Mean agent rating, if any agent rated the prompt
+    pub mean_rating: Option<f64>,
+    pub mean_latency: Duration,
+    pub feedback: Vec<String>,
+}
+
+/// Metric that decided the experiment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecidingMetric {
+    /// LOCOMO overall quality, compared with Welch's t-test
+    Quality,
+    /// Task success rate, compared with a two-proportion z-test
+    SuccessRate,
+}
+
+/// Result of a significance test between the two versions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignificanceTest {
+    /// Difference of version B over version A
+    pub difference: f64,
+    pub statistic: f64,
+    /// Two-sided p-value
+    pub p_value: f64,
+}
+
+/// Result of an A/B prompt experiment
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExperimentReport {
+    pub pattern_id: String,
+    pub version_a: VersionSummary,
+    pub version_b: VersionSummary,
+    pub quality_test: SignificanceTest,
+    pub success_test: SignificanceTest,
+    pub confidence_level: f64,
+    /// Winning version, if the difference is significant at `confidence_level`
+    pub winner: Option<String>,
+    pub deciding_metric: Option<DecidingMetric>,
+    /// Confidence that the winner is better (1 - p)
+    pub confidence: f64,
+    pub runs: Vec<ExperimentRun>,
+}
+
+impl ExperimentReport {
+    /// Record every run in the pattern's usage analytics against its version
+    pub fn apply_to_pattern(&self, pattern: &mut PromptPattern) {
+        for run in &self.runs {
+            pattern.usage_analytics.record_version_usage(
+                Some(&run.version),
+                run.successful,
+                run.feedback.clone(),
+            );
+        }
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# Prompt experiment: {}\n\n", self.pattern_id);
+        match &self.winner {
+            Some(winner) => out.push_str(&format!(
+                "**Winner: {}** ({} with {:.1}% confidence)\n\n",
+                winner,
+                match self.deciding_metric {
+                    Some(DecidingMetric::SuccessRate) => "success rate",
+                    _ => "LOCOMO quality",
+                },
+                self.confidence * 100.0
+            )),
+            None => out.push_str(&format!(
+                "**No winner** at {:.0}% confidence\n\n",
+                self.confidence_level * 100.0
+            )),
+        }
+
+        out.push_str("| Metric | ");
+        out.push_str(&self.version_a.version);
+        out.push_str(" | ");
+        out.push_str(&self.version_b.version);
+        out.push_str(" |\n|---|---|---|\n");
+        let rows = [
+            (
+                "Runs",
+                self.version_a.runs.to_string(),
+                self.version_b.runs.to_string(),
+            ),
+            (
+                "Quality",
+                format!(
+                    "{:.3} ± {:.3}",
+                    self.version_a.mean_quality, self.version_a.quality_std_dev
+                ),
+                format!(
+                    "{:.3} ± {:.3}",
+                    self.version_b.mean_quality, self.version_b.quality_std_dev
+                ),
+            ),
+            (
+                "Relevance",
+                format!("{:.3}", self.version_a.mean_relevance),
+                format!("{:.3}", self.version_b.mean_relevance),
+            ),
+            (
+                "AI consumption",
+                format!("{:.3}", self.version_a.mean_ai_consumption),
+                format!("{:.3}", self.version_b.mean_ai_consumption),
+            ),
+            (
+                "Success rate",
+                format!("{:.1}%", self.version_a.success_rate * 100.0),
+                format!("{:.1}%", self.version_b.success_rate * 100.0),
+            ),
+            (
+                "Agent rating",
+                format_rating(self.version_a.mean_rating),
+                format_rating(self.version_b.mean_rating),
+            ),
+            (
+                "Latency",
+                format!("{:?}", self.version_a.mean_latency),
+                format!("{:?}", self.version_b.mean_latency),
+            ),
+        ];
+        for (metric, a, b) in rows {
+            out.push_str(&format!("| {} | {} | {} |\n", metric, a, b));
+        }
+
+        out.push_str(&format!(
+            "\nQuality: Δ {:+.3}, t = {:.3}, p = {:.4}\n",
+            self.quality_test.difference, self.quality_test.statistic, self.quality_test.p_value
+        ));
+        out.push_str(&format!(
+            "Success rate: Δ {:+.3}, z = {:.3}, p = {:.4}\n",
+            self.success_test.difference, self.success_test.statistic, self.success_test.p_value
+        ));
+
+        for summary in [&self.version_a, &self.version_b] {
+            if !summary.feedback.is_empty() {
+                out.push_str(&format!("\n## Feedback on {}\n\n", summary.version));
+                for feedback in &summary.feedback {
+                    out.push_str(&format!("- {}\n", feedback));
+                }
+            }
+        }
+        out
+    }
+}
+
+fn format_rating(rating: Option<f64>) -> String {
+    rating.map_or_else(|| "-".to_string(), |rating| format!("{:.2}", rating))
+}
+
+/// Runs tasks with two versions of a prompt pattern and compares them
+pub struct PromptExperiment {
+    pattern: PromptPattern,
+    config: PromptExperimentConfig,
+    tasks: Vec<ExperimentTask>,
+}
+
+impl PromptExperiment {
+    pub fn new(
+        pattern: PromptPattern,
+        config: PromptExperimentConfig,
+        tasks: Vec<ExperimentTask>,
+    ) -> RhemaResult<Self> {
+        for version in [&config.version_a, &config.version_b] {
+            if pattern.template_at(version).is_none() {
+                return Err(LocomoError::ConfigurationError(format!(
+                    "Prompt '{}' has no template for version {}",
+                    pattern.id, version
+                ))
+                .into());
+            }
+        }
+        if config.version_a == config.version_b {
+            return Err(LocomoError::ConfigurationError(
+                "An experiment needs two different prompt versions".to_string(),
+            )
+            .into());
+        }
+        if tasks.is_empty() || config.repetitions == 0 {
+            return Err(LocomoError::ConfigurationError(
+                "An experiment needs at least one task and one repetition".to_string(),
+            )
+            .into());
+        }
+        if !(0.5..1.0).contains(&config.confidence_level) {
+            return Err(LocomoError::ConfigurationError(format!(
+                "Confidence level {} must be in [0.5, 1)",
+                config.confidence_level
+            ))
+            .into());
+        }
+
+        Ok(Self {
+            pattern,
+            config,
+            tasks,
+        })
+    }
+
+    /// Execute every task with both versions, alternating which goes first
+    /// so ordering effects do not favour either version
+    pub async fn run(
+        &self,
+        executor: &dyn PromptTaskExecutor,
+        assessor: &ContextQualityAssessor,
+    ) -> RhemaResult<ExperimentReport> {
+        let versions = [&self.config.version_a, &self.config.version_b];
+        let patterns = [
+            self.pattern.at_version(versions[0]),
+            self.pattern.at_version(versions[1]),
+        ];
+        let mut runs = Vec::new();
+
+        info!(
+            "Running prompt experiment on '{}': {} vs {} over {} tasks",
+            self.pattern.id,
+            versions[0],
+            versions[1],
+            self.tasks.len()
+        );
+        for repetition in 0..self.config.repetitions {
+            for (index, task) in self.tasks.iter().enumerate() {
+                let first = (repetition + index) % 2;
+                for slot in [first, 1 - first] {
+                    let pattern = patterns[slot].as_ref().expect("validated in new");
+                    runs.push(self.run_task(pattern, task, executor, assessor).await?);
+                }
+            }
+        }
+
+        Ok(self.report(runs))
+    }
+
+    async fn run_task(
+        &self,
+        pattern: &PromptPattern,
+        task: &ExperimentTask,
+        executor: &dyn PromptTaskExecutor,
+        assessor: &ContextQualityAssessor,
+    ) -> RhemaResult<ExperimentRun> {
+        let prompt = pattern.substitute_variables(&task.context);
+        let started = Instant::now();
+        let execution = executor.execute(&prompt, task).await?;
+        let latency = started.elapsed();
+
+        let now = Utc::now();
+        let response = Context {
+            id: format!("{}:{}:{}", pattern.id, pattern.version.current, task.id),
+            size_bytes: execution.response.len(),
+            content: execution.response,
+            scope_path: None,
+            content_type: ContentType::Knowledge,
+            semantic_tags: Vec::new(),
+            metadata: ContextMetadata {
+                created_at: now,
+                last_modified: now,
+                version: pattern.version.current.clone(),
+                author: None,
+                tags: Vec::new(),
+                dependencies: Vec::new(),
+                complexity_score: 0.0,
+            },
+        };
+        let quality = assessor
+            .assess_context_quality(&response, Some(&task.query))
+            .await?;
+        debug!(
+            "Task {} with version {}: quality {:.3}, successful {}",
+            task.id, pattern.version.current, quality.overall_score, execution.successful
+        );
+
+        Ok(ExperimentRun {
+            task_id: task.id.clone(),
+            version: pattern.version.current.clone(),
+            quality_score: quality.overall_score,
+            relevance_score: quality.relevance_score,
+            ai_consumption_score: quality.ai_consumption_score,
+            successful: execution.successful,
+            feedback: execution.feedback,
+            rating: execution.rating,
+            latency,
+        })
+    }
+
+    fn report(&self, runs: Vec<ExperimentRun>) -> ExperimentReport {
+        let version_a = summarize(&self.config.version_a, &runs);
+        let version_b = summarize(&self.config.version_b, &runs);
+        let quality = |version: &str| -> Vec<f64> {
+            runs.iter()
+                .filter(|run| run.version == version)
+                .map(|run| run.quality_score)
+                .collect()
+        };
+        let quality_test = welch_t_test(
+            &quality(&self.config.version_a),
+            &quality(&self.config.version_b),
+        );
+        let success_test = two_proportion_z_test(
+            version_a.success_rate,
+            version_a.runs,
+            version_b.success_rate,
+            version_b.runs,
+        );
+
+        // Quality is the primary metric; success rate breaks a quality tie
+        let alpha = 1.0 - self.config.confidence_level;
+        let decision = [
+            (DecidingMetric::Quality, &quality_test),
+            (DecidingMetric::SuccessRate, &success_test),
+        ]
+        .into_iter()
+        .find(|(_, test)| test.p_value < alpha && test.difference != 0.0);
+        let (winner, deciding_metric, confidence) = match decision {
+            Some((metric, test)) => {
+                let winner = if test.difference > 0.0 {
+                    &self.config.version_b
+                } else {
+                    &self.config.version_a
+                };
+                (Some(winner.clone()), Some(metric), 1.0 - test.p_value)
+            }
+            None => (None, None, 1.0 - quality_test.p_value),
+        };
+
+        ExperimentReport {
+            pattern_id: self.pattern.id.clone(),
+            version_a,
+            version_b,
+            quality_test,
+            success_test,
+            confidence_level: self.config.confidence_level,
+            winner,
+            deciding_metric,
+            confidence,
+            runs,
+        }
+    }
+}
+
+fn summarize(version: &str, runs: &[ExperimentRun]) -> VersionSummary {
+    let runs: Vec<&ExperimentRun> = runs.iter().filter(|run| run.version == version).collect();
+    let count = runs.len();
+    let mean_of = |value: fn(&ExperimentRun) -> f64| -> f64 {
+        if count == 0 {
+            0.0
+        } else {
+            runs.iter().map(|run| value(run)).sum::<f64>() / count as f64
+        }
+    };
+    let quality: Vec<f64> = runs.iter().map(|run| run.quality_score).collect();
+    let ratings: Vec<f64> = runs.iter().filter_map(|run| run.rating).collect();
+
+    VersionSummary {
+        version: version.to_string(),
+        runs: count,
+        mean_quality: mean(&quality),
+        quality_std_dev: variance(&quality).sqrt(),
+        mean_relevance: mean_of(|run| run.relevance_score),
+        mean_ai_consumption: mean_of(|run| run.ai_consumption_score),
+        success_rate: mean_of(|run| if run.successful { 1.0 } else { 0.0 }),
+        mean_rating: (!ratings.is_empty()).then(|| mean(&ratings)),
+        mean_latency: if count == 0 {
+            Duration::ZERO
+        } else {
+            runs.iter().map(|run| run.latency).sum::<Duration>() / count as u32
+        },
+        feedback: runs.iter().filter_map(|run| run.feedback.clone()).collect(),
+    }
+}
+
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+/// Sample variance
+fn variance(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let mean = mean(values);
+    values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (values.len() - 1) as f64
+}
+
+/// Welch's t-test for a difference in means (B - A)
+fn welch_t_test(a: &[f64], b: &[f64]) -> SignificanceTest {
+    let difference = mean(b) - mean(a);
+    if a.len() < 2 || b.len() < 2 {
+        return SignificanceTest {
+            difference,
+            statistic: 0.0,
+            p_value: 1.0,
+        };
+    }
+
+    let se_a = variance(a) / a.len() as f64;
+    let se_b = variance(b) / b.len() as f64;
+    let se = (se_a + se_b).sqrt();
+    if se == 0.0 {
+        // Both samples are constant: the difference is certain or absent
+        let p_value = if difference == 0.0 { 1.0 } else { 0.0 };
+        return SignificanceTest {
+            difference,
+            statistic: if difference == 0.0 {
+                0.0
+            } else {
+                f64::INFINITY
+            },
+            p_value,
+        };
+    }
+
+    let t = difference / se;
+    let df = (se_a + se_b).powi(2)
+        / (se_a.powi(2) / (a.len() - 1) as f64 + se_b.powi(2) / (b.len() - 1) as f64);
+    SignificanceTest {
+        difference,
+        statistic: t,
+        p_value: regularized_incomplete_beta(df / (df + t * t), df / 2.0, 0.5),
+    }
+}
+
+/// Two-proportion z-test for a difference in rates (B - A)
+fn two_proportion_z_test(p_a: f64, n_a: usize, p_b: f64, n_b: usize) -> SignificanceTest {
+    let difference = p_b - p_a;
+    if n_a == 0 || n_b == 0 {
+        return SignificanceTest {
+            difference,
+            statistic: 0.0,
+            p_value: 1.0,
+        };
+    }
+
+    let pooled = (p_a * n_a as f64 + p_b * n_b as f64) / (n_a + n_b) as f64;
+    let se = (pooled * (1.0 - pooled) * (1.0 / n_a as f64 + 1.0 / n_b as f64)).sqrt();
+    if se == 0.0 {
+        return SignificanceTest {
+            difference,
+            statistic: 0.0,
+            p_value: 1.0,
+        };
+    }
+
+    let z = difference / se;
+    SignificanceTest {
+        difference,
+        statistic: z,
+        p_value: erfc(z.abs() / std::f64::consts::SQRT_2),
+    }
+}
+
+/// Complementary error function (Numerical Recipes `erfcc`, |error| < 1.2e-7)
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -z * z - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let result = t * poly.exp();
+    if x >= 0.0 {
+        result
+    } else {
+        2.0 - result
+    }
+}
+
+fn ln_gamma(x: f64) -> f64 {
+    // Lanczos approximation (g = 7, n = 9)
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let mut sum = COEFFICIENTS[0];
+    for (i, coefficient) in COEFFICIENTS.iter().enumerate().skip(1) {
+        sum += coefficient / (x + i as f64);
+    }
+    let t = x + 7.5;
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// Regularized incomplete beta function I_x(a, b)
+fn regularized_incomplete_beta(x: f64, a: f64, b: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_continued_fraction(x, a, b) / a
+    } else {
+        1.0 - front * beta_continued_fraction(1.0 - x, b, a) / b
+    }
+}
+
+/// Continued fraction for the incomplete beta function (modified Lentz)
+fn beta_continued_fraction(x: f64, a: f64, b: f64) -> f64 {
+    const EPSILON: f64 = 1e-12;
+    const TINY: f64 = 1e-300;
+
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut result = d;
+
+    for m in 1..=200 {
+        let m = m as f64;
+        let numerator = m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m));
+        d = 1.0 + numerator * d;
+        d = if d.abs() < TINY { TINY } else { d };
+        c = 1.0 + numerator / c;
+        c = if c.abs() < TINY { TINY } else { c };
+        d = 1.0 / d;
+        result *= d * c;
+
+        let numerator = -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0));
+        d = 1.0 + numerator * d;
+        d = if d.abs() < TINY { TINY } else { d };
+        c = 1.0 + numerator / c;
+        c = if c.abs() < TINY { TINY } else { c };
+        d = 1.0 / d;
+        let delta = d * c;
+        result *= delta;
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rhema_core::schema::PromptInjectionMethod;
+
+    /// Answers with the task query when the prompt asks for detail
+    struct KeywordExecutor;
+
+    #[async_trait]
+    impl PromptTaskExecutor for KeywordExecutor {
+        async fn execute(&self, prompt: &str, task: &ExperimentTask) -> RhemaResult<TaskExecution> {
+            let detailed = prompt.contains("step by step");
+            Ok(TaskExecution {
+                response: if detailed {
+                    format!(
+                        "## {}\n\nThis explains {} because the context requires it. {}",
+                        task.query, task.query, task.context
+                    )
+                } else {
+                    "ok".to_string()
+                },
+                successful: detailed,
+                feedback: (!detailed).then(|| "Prompt was too terse".to_string()),
+                rating: Some(if detailed { 0.9 } else { 0.4 }),
+            })
+        }
+    }
+
+    fn pattern() -> PromptPattern {
+        let mut pattern = PromptPattern::new(
+            "review",
+            "Code review",
+            "Review this: {{CONTEXT}}",
+            PromptInjectionMethod::TemplateVariable,
+        );
+        let v2 = "Review this step by step: {{CONTEXT}}";
+        pattern
+            .version
+            .create_version("2.0.0", v2, "More guidance", Vec::new(), None);
+        pattern.template = v2.to_string();
+        pattern
+    }
+
+    #[test]
+    fn test_statistics() {
+        // t = 2.0 with 10 degrees of freedom has a two-sided p of about 0.0734
+        let p = regularized_incomplete_beta(10.0 / 14.0, 5.0, 0.5);
+        assert!((p - 0.0734).abs() < 1e-3, "p = {}", p);
+        assert!((erfc(1.959_964 / std::f64::consts::SQRT_2) - 0.05).abs() < 1e-4);
+
+        let same = welch_t_test(&[0.5, 0.6, 0.7], &[0.5, 0.6, 0.7]);
+        assert!((same.p_value - 1.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_experiment_picks_better_version() {
+        let pattern = pattern();
+        let tasks = (0..5)
+            .map(|i| ExperimentTask {
+                id: format!("task-{}", i),
+                query: format!("error handling in module {}", i),
+                context: format!("fn module_{}() -> Result<(), Error>", i),
+            })
+            .collect();
+        let experiment = PromptExperiment::new(
+            pattern.clone(),
+            PromptExperimentConfig {
+                version_a: "1.0.0".to_string(),
+                version_b: "2.0.0".to_string(),
+                repetitions: 2,
+                confidence_level: 0.95,
+            },
+            tasks,
+        )
+        .unwrap();
+
+        let report = experiment
+            .run(&KeywordExecutor, &ContextQualityAssessor::new_dummy())
+            .await
+            .unwrap();
+        assert_eq!(report.version_a.runs, 10);
+        assert_eq!(report.version_b.success_rate, 1.0);
+        assert_eq!(report.winner.as_deref(), Some("2.0.0"));
+        assert!(report.confidence >= 0.95);
+
+        let mut updated = pattern;
+        report.apply_to_pattern(&mut updated);
+        assert_eq!(updated.usage_analytics.total_uses, 20);
+        assert_eq!(updated.usage_analytics.version_feedback("1.0.0").len(), 10);
+    }
+
+    #[test]
+    fn test_experiment_requires_known_versions() {
+        let result = PromptExperiment::new(
+            pattern(),
+            PromptExperimentConfig {
+                version_a: "1.0.0".to_string(),
+                version_b: "9.9.9".to_string(),
+                ..Default::default()
+            },
+            vec![ExperimentTask {
+                id: "t".to_string(),
+                query: "q".to_string(),
+                context: "c".to_string(),
+            }],
+        );
+        assert!(result.is_err());
+    }
+}
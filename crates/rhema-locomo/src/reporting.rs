@@ -22,6 +22,7 @@ use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 
 use crate::benchmark_engine::{BenchmarkSummary, LocomoBenchmarkResult};
+use crate::coordination::CoordinationMetrics;
 use crate::metrics::{LocomoMetrics, LocomoMetricsCollector};
 use crate::optimization::{OptimizationAction, OptimizationResult};
 use crate::types::{BenchmarkType, Context, LocomoError};
@@ -34,6 +35,7 @@ pub struct LocomoReportingSystem {
     report_history: Arc<RwLock<Vec<LocomoReport>>>,
     dashboard_generator: Arc<DashboardGenerator>,
     trend_analyzer: Arc<TrendAnalyzer>,
+    coordination_metrics: Arc<RwLock<Option<CoordinationMetrics>>>,
 }
 
 /// LOCOMO report
@@ -50,6 +52,9 @@ pub struct LocomoReport {
     pub performance_score: f64,
     pub quality_score: f64,
     pub optimization_score: f64,
+    /// Multi-agent efficiency, present once coordination metrics are recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coordination_score: Option<f64>,
 }
 
 /// Report type
@@ -87,6 +92,8 @@ pub struct DetailedMetrics {
     pub ai_optimization: AIOptimizationMetrics,
     pub quality_assessment: QualityAssessmentMetrics,
     pub validation_metrics: ValidationMetrics,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coordination: Option<CoordinationMetrics>,
 }
 
 /// Context retrieval metrics
@@ -226,9 +233,15 @@ impl LocomoReportingSystem {
             report_history: Arc::new(RwLock::new(Vec::new())),
             dashboard_generator: Arc::new(DashboardGenerator::new(Default::default())),
             trend_analyzer: Arc::new(TrendAnalyzer::new(Default::default())),
+            coordination_metrics: Arc::new(RwLock::new(None)),
         }
     }
 
+    /// Record the latest coordination metrics so reports cover multi-agent efficiency
+    pub async fn record_coordination_metrics(&self, metrics: CoordinationMetrics) {
+        *self.coordination_metrics.write().await = Some(metrics);
+    }
+
     /// Generate comprehensive LOCOMO report
     pub async fn generate_comprehensive_report(&self, days: u64) -> RhemaResult<LocomoReport> {
         info!(
@@ -252,6 +265,10 @@ impl LocomoReportingSystem {
         let performance_score = self.calculate_performance_score(&detailed_metrics).await?;
         let quality_score = self.calculate_quality_score(&detailed_metrics).await?;
         let optimization_score = self.calculate_optimization_score(&detailed_metrics).await?;
+        let coordination_score = detailed_metrics
+            .coordination
+            .as_ref()
+            .map(CoordinationMetrics::efficiency_score);
 
         // Generate summary
         let summary = self.generate_report_summary(&detailed_metrics).await?;
@@ -273,6 +290,7 @@ impl LocomoReportingSystem {
             performance_score,
             quality_score,
             optimization_score,
+            coordination_score,
         };
 
         // Store report
@@ -298,6 +316,10 @@ impl LocomoReportingSystem {
         let performance_score = self.calculate_performance_score(&detailed_metrics).await?;
         let quality_score = self.calculate_quality_score(&detailed_metrics).await?;
         let optimization_score = self.calculate_optimization_score(&detailed_metrics).await?;
+        let coordination_score = detailed_metrics
+            .coordination
+            .as_ref()
+            .map(CoordinationMetrics::efficiency_score);
 
         // Generate summary
         let summary = self.generate_benchmark_summary(benchmark_result).await?;
@@ -330,6 +352,7 @@ impl LocomoReportingSystem {
             performance_score,
             quality_score,
             optimization_score,
+            coordination_score,
         };
 
         // Store report
@@ -362,6 +385,10 @@ impl LocomoReportingSystem {
         let performance_score = self.calculate_performance_score(&detailed_metrics).await?;
         let quality_score = self.calculate_quality_score(&detailed_metrics).await?;
         let optimization_score = self.calculate_optimization_score(&detailed_metrics).await?;
+        let coordination_score = detailed_metrics
+            .coordination
+            .as_ref()
+            .map(CoordinationMetrics::efficiency_score);
 
         // Generate summary
         let summary = self.generate_trend_summary(&trends).await?;
@@ -381,6 +408,7 @@ impl LocomoReportingSystem {
             performance_score,
             quality_score,
             optimization_score,
+            coordination_score,
         };
 
         // Store report
@@ -508,6 +536,7 @@ impl LocomoReportingSystem {
             ai_optimization,
             quality_assessment,
             validation_metrics,
+            coordination: self.coordination_metrics.read().await.clone(),
         })
     }
 
@@ -636,6 +665,11 @@ impl LocomoReportingSystem {
                 .push("Implement more aggressive token optimization strategies".to_string());
         }
 
+        // Coordination recommendations
+        if let Some(coordination) = &metrics.coordination {
+            recommendations.extend(coordination.recommendations());
+        }
+
         // Trend-based recommendations
         match trends.performance_trend {
             TrendDirection::Declining => {
@@ -919,4 +953,35 @@ mod tests {
         let dashboard_data = reporting_system.generate_dashboard_data().await.unwrap();
         assert!(!dashboard_data.alerts.is_empty() || dashboard_data.alerts.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_report_includes_coordination_metrics() {
+        let metrics_collector = Arc::new(LocomoMetricsCollector::new().unwrap());
+        let reporting_system = LocomoReportingSystem::new(metrics_collector);
+
+        let report = reporting_system.generate_trend_report(1).await.unwrap();
+        assert!(report.coordination_score.is_none());
+        assert!(report.detailed_metrics.coordination.is_none());
+
+        let stats = rhema_coordination::agent::real_time_coordination::CoordinationStats {
+            total_messages: 200,
+            messages_delivered: 200,
+            messages_failed: 0,
+            active_agents: 3,
+            active_sessions: 1,
+            avg_response_time_ms: 25.0,
+            coordination_efficiency: 0.8,
+        };
+        let coordination = CoordinationMetrics::from_stats(&stats, &[]).with_conflicts(4);
+        reporting_system
+            .record_coordination_metrics(coordination.clone())
+            .await;
+
+        let report = reporting_system
+            .generate_comprehensive_report(1)
+            .await
+            .unwrap();
+        assert_eq!(report.detailed_metrics.coordination, Some(coordination));
+        assert!(report.coordination_score.is_some());
+    }
 }
@@ -17,6 +17,7 @@
 use aes_gcm::{Aes256Gcm, Key};
 use chrono::{DateTime, Utc};
 use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use rhema_core::agent_tokens::{is_agent_token, AgentTokenStore, TokenAccess, TokenScope};
 use rhema_core::{RhemaError, RhemaResult};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -100,6 +101,7 @@ pub enum AuditEventType {
     RateLimitViolation,
     SessionManagement,
     TokenManagement,
+    QueryExecution,
 }
 
 /// Audit result
@@ -167,6 +169,35 @@ pub struct AuthManager {
     jwt_encoding_key: Option<EncodingKey>,
     jwt_decoding_key: Option<DecodingKey>,
     security_monitor: Arc<SecurityMonitor>,
+    agent_tokens: Option<Arc<AgentTokenStore>>,
+}
+
+/// Endpoint permissions implied by holding any agent token scope grant
+const SCOPED_READ_PERMISSIONS: &[&str] = &[
+    "scopes:read",
+    "knowledge:read",
+    "todos:read",
+    "decisions:read",
+    "patterns:read",
+    "validation:read",
+];
+
+/// Endpoint permissions implied by holding a `write:<scope>` grant
+const SCOPED_WRITE_PERMISSIONS: &[&str] = &[
+    "scopes:write",
+    "knowledge:write",
+    "todos:write",
+    "decisions:write",
+    "patterns:write",
+];
+
+/// Scope grants of an authenticated agent token
+fn scope_grants(auth_result: &AuthResult) -> Vec<TokenScope> {
+    auth_result
+        .permissions
+        .iter()
+        .filter_map(|permission| permission.parse().ok())
+        .collect()
 }
 
 /// Security monitoring and alerting
@@ -197,6 +228,8 @@ pub enum SecurityEventType {
     TokenCompromise,
     BruteForceAttempt,
     UnauthorizedAccess,
+    /// A context entry was quarantined for prompt-injection content
+    PromptInjection,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -273,9 +306,16 @@ impl AuthManager {
                 config.security.max_failed_attempts,
                 Duration::from_secs(config.security.lockout_duration_seconds),
             )),
+            agent_tokens: None,
         })
     }
 
+    /// Accept scoped agent tokens issued by `rhema auth issue`
+    pub fn with_agent_tokens(mut self, store: AgentTokenStore) -> Self {
+        self.agent_tokens = Some(Arc::new(store));
+        self
+    }
+
     /// Authenticate a request with enhanced security
     pub async fn authenticate(
         &self,
@@ -609,6 +649,10 @@ impl AuthManager {
         token: &str,
         client_info: Option<ClientInfo>,
     ) -> RhemaResult<AuthResult> {
+        if is_agent_token(token) {
+            return Ok(self.authenticate_agent_token(token, client_info).await);
+        }
+
         // Validate JWT format
         if !self.validate_jwt_format(token) {
             return Ok(AuthResult {
@@ -677,6 +721,60 @@ impl AuthManager {
         }
     }
 
+    /// Authenticate a scoped agent token. Its grants become the permissions
+    /// of the result, in the same `read:<scope>` form they were issued with.
+    async fn authenticate_agent_token(
+        &self,
+        token: &str,
+        client_info: Option<ClientInfo>,
+    ) -> AuthResult {
+        let verified = match &self.agent_tokens {
+            Some(store) => store.verify(token, "mcp"),
+            None => Err(RhemaError::AuthenticationError(
+                "Agent tokens are not enabled".to_string(),
+            )),
+        };
+
+        match verified {
+            Ok(claims) => AuthResult {
+                authenticated: true,
+                user_id: Some(claims.agent.clone()),
+                permissions: claims
+                    .scopes
+                    .iter()
+                    .map(|grant| grant.to_string())
+                    .collect(),
+                token_id: Some(claims.id),
+                error: None,
+                session_id: None,
+            },
+            Err(e) => {
+                self.audit_logger
+                    .log(
+                        AuditEventType::SecurityViolation,
+                        "Rejected agent token",
+                        AuditResult::Denied,
+                        None,
+                        client_info.as_ref().and_then(|c| c.ip_address.clone()),
+                        client_info.as_ref().and_then(|c| c.user_agent.clone()),
+                        None,
+                        None,
+                        HashMap::new(),
+                    )
+                    .await;
+
+                AuthResult {
+                    authenticated: false,
+                    user_id: None,
+                    permissions: Vec::new(),
+                    token_id: None,
+                    error: Some(e.to_string()),
+                    session_id: None,
+                }
+            }
+        }
+    }
+
     /// Validate JWT format
     fn validate_jwt_format(&self, token: &str) -> bool {
         let parts: Vec<&str> = token.split('.').collect();
@@ -1064,8 +1162,33 @@ impl AuthManager {
             return false;
         }
 
-        auth_result.permissions.contains(&"*".to_string())
+        if auth_result.permissions.contains(&"*".to_string())
             || auth_result.permissions.contains(&permission.to_string())
+        {
+            return true;
+        }
+
+        // Agent tokens carry scope grants rather than endpoint permissions;
+        // any grant admits the per-scope read endpoints and a write grant
+        // the per-scope write endpoints, which then check the scope itself
+        // with `has_scope_access`.
+        scope_grants(auth_result).iter().any(|grant| {
+            SCOPED_READ_PERMISSIONS.contains(&permission)
+                || (grant.access == TokenAccess::Write
+                    && SCOPED_WRITE_PERMISSIONS.contains(&permission))
+        })
+    }
+
+    /// Check whether the caller may access `scope`. Callers without agent
+    /// token grants are governed by their permissions alone.
+    pub fn has_scope_access(
+        &self,
+        auth_result: &AuthResult,
+        scope: &str,
+        access: TokenAccess,
+    ) -> bool {
+        let grants = scope_grants(auth_result);
+        grants.is_empty() || grants.iter().any(|grant| grant.allows(access, scope))
     }
 
     /// Validate origin for CORS
@@ -1557,6 +1680,12 @@ impl AuthManager {
         &self.security_monitor
     }
 
+    /// Shared handle to the security monitor, for components outside
+    /// authentication that report security events
+    pub fn security_monitor_handle(&self) -> Arc<SecurityMonitor> {
+        self.security_monitor.clone()
+    }
+
     /// Get a reference to the audit logger (for testing)
     pub fn audit_logger(&self) -> &AuditLogger {
         &self.audit_logger
@@ -1814,6 +1943,58 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_agent_token_grants_map_to_permissions() -> RhemaResult<()> {
+        let config = AuthConfig {
+            enabled: true,
+            api_key: None,
+            jwt_secret: None,
+            allowed_origins: vec![],
+            rate_limiting: RateLimitConfig::default(),
+            audit_logging: crate::mcp::AuditLoggingConfig::default(),
+            security: crate::mcp::SecurityConfig::default(),
+        };
+        let temp = tempfile::TempDir::new()?;
+        let store = AgentTokenStore::new(temp.path());
+        let writer = store.issue(
+            "writer",
+            vec!["write:api".parse()?],
+            chrono::Duration::hours(1),
+        )?;
+        let reader = store.issue(
+            "reader",
+            vec!["read:api".parse()?],
+            chrono::Duration::hours(1),
+        )?;
+        let auth_manager = AuthManager::new(&config)?.with_agent_tokens(store);
+
+        let result = auth_manager
+            .authenticate(Some(&format!("Bearer {}", writer.token)), None)
+            .await?;
+        assert!(result.authenticated);
+        assert!(auth_manager.has_permission(&result, "knowledge:read").await);
+        assert!(
+            auth_manager
+                .has_permission(&result, "knowledge:write")
+                .await
+        );
+        assert!(!auth_manager.has_permission(&result, "stats:read").await);
+        assert!(auth_manager.has_scope_access(&result, "api", TokenAccess::Write));
+        assert!(!auth_manager.has_scope_access(&result, "docs", TokenAccess::Read));
+
+        let result = auth_manager
+            .authenticate(Some(&format!("Bearer {}", reader.token)), None)
+            .await?;
+        assert!(auth_manager.has_permission(&result, "knowledge:read").await);
+        assert!(
+            !auth_manager
+                .has_permission(&result, "knowledge:write")
+                .await
+        );
+        assert!(!auth_manager.has_scope_access(&result, "api", TokenAccess::Write));
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_cors_validation() -> RhemaResult<()> {
         let config = AuthConfig {
@@ -26,6 +26,10 @@ use tokio::sync::RwLock;
 use tokio::time::interval;
 use tracing::{debug, error, info};
 
+use crate::request_trace;
+use rhema_core::cache_report::{
+    hottest_keys, CacheTierReport, EvictionCause, EvictionCounts, KeyHits, HOTTEST_KEYS,
+};
 use rhema_core::{RhemaError, RhemaResult};
 
 /// Cache entry with metadata
@@ -124,6 +128,7 @@ pub struct CacheStats {
     pub hit_rate: f64,
     pub memory_usage_bytes: u64,
     pub eviction_count: u64,
+    pub evictions: EvictionCounts,
 }
 
 /// Enhanced cache statistics with additional metrics
@@ -137,6 +142,10 @@ pub struct CacheStatistics {
     pub eviction_count: u64,
     pub average_entry_size: u64,
     pub compression_ratio: f64,
+    #[serde(default)]
+    pub evictions: EvictionCounts,
+    #[serde(default)]
+    pub hottest_keys: Vec<KeyHits>,
 }
 
 /// Cache eviction policies
@@ -707,6 +716,7 @@ impl CacheManager {
             hit_rate: 0.0,
             memory_usage_bytes: 0,
             eviction_count: 0,
+            evictions: EvictionCounts::default(),
         }));
 
         let performance_metrics = Arc::new(RwLock::new(PerformanceMetrics {
@@ -1207,6 +1217,18 @@ impl CacheManager {
     /// Get a value from cache with enhanced features
     pub async fn get(&self, key: &str) -> RhemaResult<Option<Value>> {
         let start_time = Instant::now();
+        let result = self.lookup(key).await;
+        let outcome = match &result {
+            Ok(Some(_)) => "hit".to_string(),
+            Ok(None) => "miss".to_string(),
+            Err(e) => format!("error: {}", e),
+        };
+        request_trace::record("cache", "get", key, start_time.elapsed(), &outcome);
+        result
+    }
+
+    async fn lookup(&self, key: &str) -> RhemaResult<Option<Value>> {
+        let start_time = Instant::now();
 
         // Track access pattern
         self.track_access_pattern(key).await;
@@ -1215,7 +1237,9 @@ impl CacheManager {
         if self.config.memory_enabled {
             if let Some(mut entry) = self.memory_cache.get_mut(key) {
                 if entry.is_expired() {
+                    drop(entry);
                     self.memory_cache.remove(key);
+                    self.record_evictions(EvictionCause::Expired, 1).await;
                     self.update_stats_miss().await;
                     return Ok(None);
                 }
@@ -1223,7 +1247,9 @@ impl CacheManager {
                 // Validate entry if enabled
                 if self.config.validation.validate_on_read {
                     if !self.validate_entry(&entry).await? {
+                        drop(entry);
                         self.memory_cache.remove(key);
+                        self.record_evictions(EvictionCause::Invalidated, 1).await;
                         self.update_stats_miss().await;
                         return Ok(None);
                     }
@@ -1270,6 +1296,10 @@ impl CacheManager {
 
     /// Set a value in cache with enhanced features
     pub async fn set(&self, key: &str, value: Value) -> RhemaResult<()> {
+        request_trace::traced("cache", "set", key, self.store(key, value)).await
+    }
+
+    async fn store(&self, key: &str, value: Value) -> RhemaResult<()> {
         let start_time = Instant::now();
 
         // Compress value if enabled
@@ -1311,8 +1341,8 @@ impl CacheManager {
     /// Delete a value from cache
     pub async fn delete(&self, key: &str) -> RhemaResult<()> {
         // Delete from memory cache
-        if self.config.memory_enabled {
-            self.memory_cache.remove(key);
+        if self.config.memory_enabled && self.memory_cache.remove(key).is_some() {
+            self.record_evictions(EvictionCause::Invalidated, 1).await;
         }
 
         // Delete from Redis cache
@@ -1329,7 +1359,10 @@ impl CacheManager {
     pub async fn clear(&self) -> RhemaResult<()> {
         // Clear memory cache
         if self.config.memory_enabled {
+            let cleared = self.memory_cache.len() as u64;
             self.memory_cache.clear();
+            self.record_evictions(EvictionCause::Invalidated, cleared)
+                .await;
         }
 
         // Clear Redis cache
@@ -1381,9 +1414,36 @@ impl CacheManager {
                 0
             },
             compression_ratio: self.calculate_compression_ratio().await,
+            evictions: stats.evictions,
+            hottest_keys: self.hottest_keys(),
         }
     }
 
+    /// Statistics of the memory tier for the consolidated cache report
+    pub async fn tier_report(&self) -> CacheTierReport {
+        let stats = self.stats().await;
+        let mut report = CacheTierReport::new("mcp", "memory");
+        report.entries = self.memory_cache.len();
+        // Capacity evictions compare the entry count against `max_size`
+        report.max_entries = Some(self.config.max_size);
+        report.memory_bytes = self.memory_usage().await;
+        report.ttl_secs = Some(self.config.ttl_seconds);
+        report.hits = stats.hit_count;
+        report.misses = stats.miss_count;
+        report.evictions = stats.evictions;
+        report.hottest_keys = self.hottest_keys();
+        report
+    }
+
+    fn hottest_keys(&self) -> Vec<KeyHits> {
+        hottest_keys(
+            self.memory_cache
+                .iter()
+                .map(|entry| (entry.key().clone(), entry.value().access_count)),
+            HOTTEST_KEYS,
+        )
+    }
+
     /// Evict expired entries
     pub async fn evict_expired(&self) -> RhemaResult<usize> {
         if !self.config.memory_enabled {
@@ -1407,6 +1467,9 @@ impl CacheManager {
         // Update stats
         let mut stats = self.stats.write().await;
         stats.eviction_count += evicted as u64;
+        stats
+            .evictions
+            .record(EvictionCause::Expired, evicted as u64);
         stats.total_entries = self.memory_cache.len();
 
         Ok(evicted)
@@ -2083,10 +2146,19 @@ impl CacheManager {
         metrics.throughput_requests_per_second = 1.0 / (response_time.as_secs_f64() + 0.001);
     }
 
-    /// Update eviction stats
+    /// Update eviction stats after making room for new entries
     async fn update_eviction_stats(&self, evicted: usize) {
         let mut stats = self.stats.write().await;
         stats.eviction_count += evicted as u64;
+        stats
+            .evictions
+            .record(EvictionCause::Capacity, evicted as u64);
+    }
+
+    /// Count entries dropped for expiring or being invalidated
+    async fn record_evictions(&self, cause: EvictionCause, count: u64) {
+        let mut stats = self.stats.write().await;
+        stats.evictions.record(cause, count);
     }
 
     pub async fn get_performance_metrics(&self) -> PerformanceMetrics {
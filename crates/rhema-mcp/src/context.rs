@@ -14,11 +14,18 @@
  * limitations under the License.
  */
 
+use crate::auth::{SecurityEventType, SecurityMonitor, SecuritySeverity};
 use crate::cache::CompressionAlgorithm;
+use crate::request_trace;
+use crate::resource_revisions::{
+    resource_etag, ChangedResources, ResourceRevision, ResourceRevisionLog,
+};
 use chrono::Timelike;
 use chrono::Utc;
+use rhema_core::prompt_guard::{PromptGuard, QuarantineRecord, ThreatLevel};
 use rhema_core::{schema::*, scope::Scope, RhemaError, RhemaLock, RhemaResult};
-use rhema_query::QueryResult;
+use rhema_query::{QueryExecutionConfig, QueryResult};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -397,6 +404,14 @@ pub struct ContextProvider {
     version_config: ContextVersionConfig,
     compression_config: ContextCompressionConfig,
     encryption_config: ContextEncryptionConfig,
+    query_config: QueryExecutionConfig,
+
+    // Content revisions backing ETags and change tokens
+    resource_revisions: Arc<RwLock<ResourceRevisionLog>>,
+
+    // Prompt-injection screening of served entries
+    prompt_guard: Arc<PromptGuard>,
+    security_monitor: Option<Arc<SecurityMonitor>>,
 
     // Background tasks
     sync_task: Option<tokio::task::JoinHandle<()>>,
@@ -424,6 +439,10 @@ impl Clone for ContextProvider {
             version_config: self.version_config.clone(),
             compression_config: self.compression_config.clone(),
             encryption_config: self.encryption_config.clone(),
+            query_config: self.query_config.clone(),
+            resource_revisions: self.resource_revisions.clone(),
+            prompt_guard: self.prompt_guard.clone(),
+            security_monitor: self.security_monitor.clone(),
             sync_task: None,    // JoinHandle cannot be cloned
             backup_task: None,  // JoinHandle cannot be cloned
             cleanup_task: None, // JoinHandle cannot be cloned
@@ -434,6 +453,10 @@ impl Clone for ContextProvider {
 impl ContextProvider {
     /// Create a new context provider
     pub fn new(repo_root: PathBuf) -> RhemaResult<Self> {
+        let prompt_guard = Arc::new(PromptGuard::open(&repo_root)?);
+        // The daemon answers the same hot queries over and over, so read
+        // context files through the memory-mapped path
+        let query_config = QueryExecutionConfig::load(&repo_root)?.with_mapped_reads(true);
         Ok(Self {
             repo_root,
             scopes: Arc::new(RwLock::new(Vec::new())),
@@ -460,6 +483,12 @@ impl ContextProvider {
             version_config: ContextVersionConfig::default(),
             compression_config: ContextCompressionConfig::default(),
             encryption_config: ContextEncryptionConfig::default(),
+            query_config,
+
+            resource_revisions: Arc::new(RwLock::new(ResourceRevisionLog::new())),
+
+            prompt_guard,
+            security_monitor: None,
 
             // Background tasks
             sync_task: None,
@@ -468,11 +497,22 @@ impl ContextProvider {
         })
     }
 
+    /// Record quarantined entries as security events on `monitor`
+    pub fn with_security_monitor(mut self, monitor: Arc<SecurityMonitor>) -> Self {
+        self.security_monitor = Some(monitor);
+        self
+    }
+
     /// Get the repository root path
     pub fn repo_root(&self) -> &std::path::Path {
         &self.repo_root
     }
 
+    /// Prompt-injection screen applied to served entries
+    pub fn prompt_guard(&self) -> &PromptGuard {
+        &self.prompt_guard
+    }
+
     /// List all available resources
     pub async fn list_resources(&self) -> RhemaResult<Vec<serde_json::Value>> {
         let scopes = self.get_scopes().await?;
@@ -531,6 +571,73 @@ impl ContextProvider {
 
     /// Get a specific resource by URI
     pub async fn get_resource(&self, uri: &str) -> RhemaResult<serde_json::Value> {
+        request_trace::traced(
+            "context_provider",
+            "get_resource",
+            uri,
+            self.load_resource(uri),
+        )
+        .await
+    }
+
+    /// Get a resource together with its content revision (ETag and last-modified time)
+    pub async fn get_resource_with_revision(
+        &self,
+        uri: &str,
+    ) -> RhemaResult<(serde_json::Value, ResourceRevision)> {
+        let resource = self.get_resource(uri).await?;
+        let revision = self
+            .resource_revisions
+            .write()
+            .await
+            .observe(uri, &resource);
+        Ok((resource, revision))
+    }
+
+    /// Get the resources added, modified or removed since a change token.
+    ///
+    /// Without a usable token every resource is returned and `full_sync` is set.
+    pub async fn changed_since(&self, token: Option<&str>) -> RhemaResult<ChangedResources> {
+        let resources: Vec<(String, serde_json::Value)> = self
+            .list_resources()
+            .await?
+            .into_iter()
+            .filter_map(|resource| {
+                let uri = resource.get("uri")?.as_str()?.to_string();
+                Some((uri, resource))
+            })
+            .collect();
+
+        let mut revisions = self.resource_revisions.write().await;
+        revisions.observe_all(&resources);
+
+        let since = token.and_then(|token| revisions.parse_token(token));
+        let (changed, removed) = match since {
+            Some(sequence) => revisions.changes_after(sequence),
+            None => (Vec::new(), Vec::new()),
+        };
+
+        let resources = resources
+            .into_iter()
+            .filter(|(uri, _)| since.is_none() || changed.contains(uri))
+            .map(|(_, mut resource)| {
+                let etag = resource_etag(&resource);
+                if let Some(object) = resource.as_object_mut() {
+                    object.insert("etag".to_string(), serde_json::Value::String(etag));
+                }
+                resource
+            })
+            .collect();
+
+        Ok(ChangedResources {
+            token: revisions.token(),
+            full_sync: since.is_none(),
+            resources,
+            removed,
+        })
+    }
+
+    async fn load_resource(&self, uri: &str) -> RhemaResult<serde_json::Value> {
         if uri.starts_with("scope://") {
             let scope_path = uri.strip_prefix("scope://").unwrap();
             if let Some(scope) = self.get_scope(scope_path).await? {
@@ -604,6 +711,7 @@ impl ContextProvider {
     /// Reload all context data
     pub async fn reload(&self) -> RhemaResult<()> {
         tracing::info!("Reloading context data");
+        self.prompt_guard.reload()?;
         self.initialize().await
     }
 
@@ -624,8 +732,8 @@ impl ContextProvider {
 
     /// Get knowledge for a scope
     pub async fn get_knowledge(&self, scope_path: &str) -> RhemaResult<Option<Knowledge>> {
-        let knowledge = self.knowledge_cache.read().await;
-        Ok(knowledge.get(scope_path).cloned())
+        let knowledge = self.knowledge_cache.read().await.get(scope_path).cloned();
+        self.screened(scope_path, "knowledge.yaml", knowledge).await
     }
 
     /// Get knowledge for a scope (for MCP compatibility)
@@ -639,26 +747,78 @@ impl ContextProvider {
 
     /// Get todos for a scope
     pub async fn get_todos(&self, scope_path: &str) -> RhemaResult<Option<Todos>> {
-        let todos = self.todos_cache.read().await;
-        Ok(todos.get(scope_path).cloned())
+        let todos = self.todos_cache.read().await.get(scope_path).cloned();
+        self.screened(scope_path, "todos.yaml", todos).await
     }
 
     /// Get decisions for a scope
     pub async fn get_decisions(&self, scope_path: &str) -> RhemaResult<Option<Decisions>> {
-        let decisions = self.decisions_cache.read().await;
-        Ok(decisions.get(scope_path).cloned())
+        let decisions = self.decisions_cache.read().await.get(scope_path).cloned();
+        self.screened(scope_path, "decisions.yaml", decisions).await
     }
 
     /// Get patterns for a scope
     pub async fn get_patterns(&self, scope_path: &str) -> RhemaResult<Option<Patterns>> {
-        let patterns = self.patterns_cache.read().await;
-        Ok(patterns.get(scope_path).cloned())
+        let patterns = self.patterns_cache.read().await.get(scope_path).cloned();
+        self.screened(scope_path, "patterns.yaml", patterns).await
     }
 
     /// Get conventions for a scope
     pub async fn get_conventions(&self, scope_path: &str) -> RhemaResult<Option<Conventions>> {
-        let conventions = self.conventions_cache.read().await;
-        Ok(conventions.get(scope_path).cloned())
+        let conventions = self.conventions_cache.read().await.get(scope_path).cloned();
+        self.screened(scope_path, "conventions.yaml", conventions)
+            .await
+    }
+
+    /// Entries of a scope's context file without quarantined ones. Entries
+    /// quarantined for the first time are reported as security events
+    async fn screened<T: Serialize + DeserializeOwned>(
+        &self,
+        scope_path: &str,
+        file: &str,
+        data: Option<T>,
+    ) -> RhemaResult<Option<T>> {
+        let Some(data) = data else {
+            return Ok(None);
+        };
+        if !self.prompt_guard.config().enabled {
+            return Ok(Some(data));
+        }
+        let mut value = serde_json::to_value(&data)?;
+        let source = format!("{}/{}", scope_path, file);
+        for record in self.prompt_guard.screen(&source, &mut value) {
+            self.report_quarantine(&record).await;
+        }
+        Ok(Some(serde_json::from_value(value)?))
+    }
+
+    async fn report_quarantine(&self, record: &QuarantineRecord) {
+        let details = format!(
+            "Quarantined entry {} in {} ({}): {}",
+            record.entry_id,
+            record.source,
+            record.fingerprint,
+            record.reason()
+        );
+        match &self.security_monitor {
+            Some(monitor) => {
+                let severity = match record.level() {
+                    ThreatLevel::Low => SecuritySeverity::Low,
+                    ThreatLevel::Medium => SecuritySeverity::Medium,
+                    ThreatLevel::High => SecuritySeverity::High,
+                };
+                monitor
+                    .record_security_event(
+                        SecurityEventType::PromptInjection,
+                        None,
+                        None,
+                        details,
+                        severity,
+                    )
+                    .await;
+            }
+            None => tracing::warn!("{}", details),
+        }
     }
 
     /// Get lock file information for AI agent context
@@ -923,8 +1083,27 @@ impl ContextProvider {
 
     /// Execute a query with lock file context
     pub async fn execute_query(&self, query: &str) -> RhemaResult<serde_json::Value> {
-        let result = rhema_query::query::execute_query(&self.repo_root, query)?;
-        Ok(serde_json::to_value(result)?)
+        request_trace::traced("query_engine", "execute_query", query, async {
+            let result = self.run_query(query)?;
+            Ok(serde_json::to_value(result)?)
+        })
+        .await
+    }
+
+    /// Run a query in a span carrying the current request ID, so the query
+    /// engine's own tracing output is correlated with the request
+    fn run_query(&self, query: &str) -> RhemaResult<serde_yaml::Value> {
+        let span = tracing::info_span!(
+            "query_engine",
+            request_id = %request_trace::current_request_id().unwrap_or_default()
+        );
+        span.in_scope(|| {
+            rhema_query::query::execute_query_with_config(
+                &self.repo_root,
+                query,
+                &self.query_config,
+            )
+        })
     }
 
     /// Execute a query with statistics including lock file info
@@ -932,7 +1111,10 @@ impl ContextProvider {
         &self,
         query: &str,
     ) -> RhemaResult<(Value, HashMap<String, Value>)> {
-        let result = rhema_query::query::execute_query(&self.repo_root, query)?;
+        let result = request_trace::traced("query_engine", "execute_query", query, async {
+            self.run_query(query)
+        })
+        .await?;
         let mut stats = HashMap::new();
 
         // Add lock file statistics
@@ -0,0 +1,688 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! GraphQL schema over scopes, context entries, scope relations and daemon
+//! health, served at `/graphql` when the `graphql` feature is enabled.
+//!
+//! Dashboards can fetch a scope, its dependencies, their entries and so on in
+//! one request instead of walking the REST endpoints. Scopes are discovered
+//! once per request and context files are read lazily, only for the scopes
+//! whose `entries` field is selected.
+
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, Enum, InputObject, Json, Object, Schema,
+    SimpleObject,
+};
+use chrono::{DateTime, FixedOffset};
+use rhema_core::scope::{discover_scopes, Scope};
+use rhema_core::{RhemaError, RhemaResult};
+use serde_json::Value;
+use std::path::{Component, Path};
+use std::sync::Arc;
+
+use crate::mcp::HealthStatus;
+
+/// Maximum nesting depth of a query, so relation cycles cannot recurse forever
+pub const MAX_QUERY_DEPTH: usize = 12;
+
+/// Maximum complexity (selected fields) of a query
+pub const MAX_QUERY_COMPLEXITY: usize = 1000;
+
+/// Maximum number of entries returned by one `entries` field
+pub const MAX_ENTRY_LIMIT: usize = 1000;
+
+/// GraphQL schema type served by the HTTP server
+pub type RhemaSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Build the read-only schema with depth and complexity limits applied
+pub fn build_schema() -> RhemaSchema {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY)
+        .finish()
+}
+
+/// Kind of context entry, matching the scope's context files
+#[derive(Enum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    Knowledge,
+    Todos,
+    Decisions,
+    Patterns,
+    Conventions,
+}
+
+impl EntryKind {
+    pub const ALL: [EntryKind; 5] = [
+        EntryKind::Knowledge,
+        EntryKind::Todos,
+        EntryKind::Decisions,
+        EntryKind::Patterns,
+        EntryKind::Conventions,
+    ];
+
+    /// Context file holding entries of this kind
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            EntryKind::Knowledge => "knowledge.yaml",
+            EntryKind::Todos => "todos.yaml",
+            EntryKind::Decisions => "decisions.yaml",
+            EntryKind::Patterns => "patterns.yaml",
+            EntryKind::Conventions => "conventions.yaml",
+        }
+    }
+
+    /// Top-level key of the entry list in the context file
+    fn list_key(&self) -> &'static str {
+        match self {
+            EntryKind::Knowledge => "entries",
+            EntryKind::Todos => "todos",
+            EntryKind::Decisions => "decisions",
+            EntryKind::Patterns => "patterns",
+            EntryKind::Conventions => "conventions",
+        }
+    }
+
+    /// Permission required to read entries of this kind
+    pub fn permission(&self) -> &'static str {
+        match self {
+            EntryKind::Knowledge => "knowledge:read",
+            EntryKind::Todos => "todos:read",
+            EntryKind::Decisions => "decisions:read",
+            EntryKind::Patterns => "patterns:read",
+            EntryKind::Conventions => "conventions:read",
+        }
+    }
+}
+
+/// What the authenticated caller may read, attached to each request
+#[derive(Debug, Clone, Default)]
+pub struct GraphqlAccess {
+    /// Entry kinds the caller has read permission for
+    pub entry_kinds: Vec<EntryKind>,
+    /// Daemon health, present only when the caller may read stats
+    pub health: Option<HealthStatus>,
+}
+
+/// Scopes discovered for one request
+pub struct RepositorySnapshot {
+    scopes: Vec<ScopeNode>,
+}
+
+impl RepositorySnapshot {
+    /// Discover all scopes under the repository root
+    pub fn load(repo_root: &Path) -> RhemaResult<Self> {
+        let mut scopes: Vec<ScopeNode> = discover_scopes(repo_root)?
+            .into_iter()
+            .map(|scope| ScopeNode::new(repo_root, scope))
+            .collect();
+        scopes.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(Self { scopes })
+    }
+
+    fn find(&self, path: &str) -> Option<&ScopeNode> {
+        let path = normalize(Path::new(path));
+        self.scopes.iter().find(|s| s.path == path)
+    }
+
+    /// Resolve a dependency declared by `from` to a known scope. Paths
+    /// starting with `.` are tried relative to the declaring scope first,
+    /// others relative to the repository root first; the scope name is the
+    /// last resort.
+    fn resolve(&self, from: &ScopeNode, dependency: &str) -> Option<&ScopeNode> {
+        let from_scope = Path::new(&from.path)
+            .join(dependency)
+            .to_string_lossy()
+            .to_string();
+        let (first, second) = if dependency.starts_with('.') {
+            (from_scope.as_str(), dependency)
+        } else {
+            (dependency, from_scope.as_str())
+        };
+        self.find(first).or_else(|| self.find(second)).or_else(|| {
+            self.scopes
+                .iter()
+                .find(|s| s.scope.definition.name == dependency)
+        })
+    }
+}
+
+/// Repository-relative scope path with `.` and `..` folded, `.` for the root
+fn normalize(path: &Path) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_string_lossy().to_string()),
+            Component::ParentDir => {
+                parts.pop();
+            }
+            _ => {}
+        }
+    }
+    if parts.is_empty() {
+        ".".to_string()
+    } else {
+        parts.join("/")
+    }
+}
+
+/// A scope with its definition, relations and entries
+#[derive(Clone)]
+pub struct ScopeNode {
+    path: String,
+    scope: Arc<Scope>,
+}
+
+impl ScopeNode {
+    fn new(repo_root: &Path, scope: Scope) -> Self {
+        let dir = scope.path.parent().unwrap_or(&scope.path);
+        let path = normalize(dir.strip_prefix(repo_root).unwrap_or(dir));
+        Self {
+            path,
+            scope: Arc::new(scope),
+        }
+    }
+
+    fn dependency_paths(&self) -> Vec<(String, String, Option<String>)> {
+        self.scope
+            .definition
+            .dependencies
+            .iter()
+            .flatten()
+            .map(|d| (d.path.clone(), d.dependency_type.clone(), d.version.clone()))
+            .collect()
+    }
+
+    /// Read entries of the given kinds from this scope's context files
+    fn load_entries(&self, kinds: &[EntryKind]) -> RhemaResult<Vec<ContextEntry>> {
+        let mut entries = Vec::new();
+        for kind in kinds {
+            let Some(file) = self.scope.get_file(kind.file_name()) else {
+                continue;
+            };
+            let content = std::fs::read_to_string(file)?;
+            let document: Value =
+                serde_yaml::from_str(&content).map_err(|e| RhemaError::InvalidYaml {
+                    file: file.display().to_string(),
+                    message: e.to_string(),
+                })?;
+            if let Some(items) = document.get(kind.list_key()).and_then(|v| v.as_array()) {
+                entries.extend(
+                    items
+                        .iter()
+                        .map(|item| ContextEntry::new(&self.path, *kind, item.clone())),
+                );
+            }
+        }
+        Ok(entries)
+    }
+}
+
+#[Object]
+impl ScopeNode {
+    /// Scope directory relative to the repository root
+    async fn path(&self) -> &str {
+        &self.path
+    }
+
+    async fn name(&self) -> &str {
+        &self.scope.definition.name
+    }
+
+    async fn scope_type(&self) -> &str {
+        &self.scope.definition.scope_type
+    }
+
+    async fn description(&self) -> Option<&str> {
+        self.scope.definition.description.as_deref()
+    }
+
+    async fn version(&self) -> &str {
+        &self.scope.definition.version
+    }
+
+    /// Scopes this scope depends on
+    async fn dependencies(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<ScopeRelation>> {
+        let snapshot = ctx.data::<Arc<RepositorySnapshot>>()?;
+        Ok(self
+            .dependency_paths()
+            .into_iter()
+            .map(|(path, dependency_type, version)| ScopeRelation {
+                scope: snapshot.resolve(self, &path).cloned(),
+                path,
+                dependency_type,
+                version,
+            })
+            .collect())
+    }
+
+    /// Scopes that depend on this scope
+    async fn dependents(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<ScopeRelation>> {
+        let snapshot = ctx.data::<Arc<RepositorySnapshot>>()?;
+        let mut relations = Vec::new();
+        for other in &snapshot.scopes {
+            for (path, dependency_type, version) in other.dependency_paths() {
+                if snapshot
+                    .resolve(other, &path)
+                    .is_some_and(|s| s.path == self.path)
+                {
+                    relations.push(ScopeRelation {
+                        path: other.path.clone(),
+                        dependency_type,
+                        version,
+                        scope: Some(other.clone()),
+                    });
+                }
+            }
+        }
+        Ok(relations)
+    }
+
+    /// Context entries in this scope
+    async fn entries(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<EntryFilter>,
+    ) -> async_graphql::Result<Vec<ContextEntry>> {
+        let filter = filter.unwrap_or_default();
+        let kinds = filter.allowed_kinds(ctx.data::<GraphqlAccess>()?)?;
+        let entries = self.load_entries(&kinds)?;
+        filter.apply(entries)
+    }
+}
+
+/// A dependency edge between two scopes
+#[derive(SimpleObject, Clone)]
+pub struct ScopeRelation {
+    /// Path as declared in the dependency, or the dependent's path
+    pub path: String,
+    pub dependency_type: String,
+    pub version: Option<String>,
+    /// The related scope, when it exists in this repository
+    pub scope: Option<ScopeNode>,
+}
+
+/// A knowledge, todo, decision, pattern or convention entry
+#[derive(SimpleObject, Clone)]
+pub struct ContextEntry {
+    pub id: Option<String>,
+    pub kind: EntryKind,
+    /// Path of the owning scope
+    pub scope: String,
+    /// `title`, or `name` for patterns and conventions
+    pub title: Option<String>,
+    pub status: Option<String>,
+    pub tags: Vec<String>,
+    pub created_at: Option<String>,
+    /// The full entry as stored in the context file
+    pub data: Json<Value>,
+}
+
+impl ContextEntry {
+    fn new(scope: &str, kind: EntryKind, data: Value) -> Self {
+        let text = |key: &str| data.get(key).and_then(|v| v.as_str()).map(str::to_string);
+        let tags = data
+            .get("tags")
+            .and_then(|v| v.as_array())
+            .map(|tags| {
+                tags.iter()
+                    .filter_map(|t| t.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            id: text("id"),
+            kind,
+            scope: scope.to_string(),
+            title: text("title").or_else(|| text("name")),
+            status: text("status"),
+            tags,
+            created_at: text("created_at"),
+            data: Json(data),
+        }
+    }
+
+    fn created(&self) -> Option<DateTime<FixedOffset>> {
+        self.created_at
+            .as_deref()
+            .and_then(|c| DateTime::parse_from_rfc3339(c).ok())
+    }
+}
+
+/// Filter for `entries` fields
+#[derive(InputObject, Default)]
+pub struct EntryFilter {
+    /// Only these kinds; defaults to every kind the caller may read
+    pub kinds: Option<Vec<EntryKind>>,
+    /// Exact status match, case-insensitive
+    pub status: Option<String>,
+    /// Entries carrying this tag
+    pub tag: Option<String>,
+    /// Case-insensitive substring of the serialized entry
+    pub text: Option<String>,
+    /// RFC 3339 timestamps bounding `created_at`
+    pub created_after: Option<String>,
+    pub created_before: Option<String>,
+    /// Maximum entries returned, capped at `MAX_ENTRY_LIMIT`
+    pub limit: Option<usize>,
+}
+
+impl EntryFilter {
+    fn allowed_kinds(&self, access: &GraphqlAccess) -> async_graphql::Result<Vec<EntryKind>> {
+        match &self.kinds {
+            Some(kinds) => {
+                if let Some(denied) = kinds.iter().find(|k| !access.entry_kinds.contains(k)) {
+                    return Err(format!("Insufficient permissions: {}", denied.permission()).into());
+                }
+                Ok(kinds.clone())
+            }
+            None => Ok(access.entry_kinds.clone()),
+        }
+    }
+
+    fn apply(&self, entries: Vec<ContextEntry>) -> async_graphql::Result<Vec<ContextEntry>> {
+        let bound = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(DateTime::parse_from_rfc3339)
+                .transpose()
+                .map_err(|e| async_graphql::Error::new(format!("Invalid timestamp: {}", e)))
+        };
+        let after = bound(&self.created_after)?;
+        let before = bound(&self.created_before)?;
+        let text = self.text.as_ref().map(|t| t.to_lowercase());
+        let limit = self.limit.unwrap_or(MAX_ENTRY_LIMIT).min(MAX_ENTRY_LIMIT);
+
+        Ok(entries
+            .into_iter()
+            .filter(|e| match &self.status {
+                Some(status) => e
+                    .status
+                    .as_deref()
+                    .is_some_and(|s| s.eq_ignore_ascii_case(status)),
+                None => true,
+            })
+            .filter(|e| match &self.tag {
+                Some(tag) => e.tags.contains(tag),
+                None => true,
+            })
+            .filter(|e| match &text {
+                Some(text) => e.data.0.to_string().to_lowercase().contains(text),
+                None => true,
+            })
+            .filter(|e| {
+                if after.is_none() && before.is_none() {
+                    return true;
+                }
+                match e.created() {
+                    Some(created) => {
+                        !matches!(after, Some(a) if created < a)
+                            && !matches!(before, Some(b) if created >= b)
+                    }
+                    None => false,
+                }
+            })
+            .take(limit)
+            .collect())
+    }
+}
+
+/// Filter for the top-level `scopes` field
+#[derive(InputObject, Default)]
+pub struct ScopeFilter {
+    pub scope_type: Option<String>,
+    /// Case-insensitive substring of the scope name
+    pub name_contains: Option<String>,
+    /// Scopes with a dependency resolving to this path
+    pub depends_on: Option<String>,
+}
+
+/// Daemon health as exposed over GraphQL
+#[derive(SimpleObject)]
+pub struct HealthNode {
+    pub status: String,
+    pub uptime_seconds: u64,
+    pub connections: usize,
+    pub request_count: u64,
+    pub error_count: u64,
+    pub error_rate: f64,
+    pub cache_hit_rate: f64,
+    pub restart_count: u32,
+    pub scope_count: usize,
+}
+
+/// Root query type
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// All scopes, optionally filtered
+    async fn scopes(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<ScopeFilter>,
+    ) -> async_graphql::Result<Vec<ScopeNode>> {
+        let snapshot = ctx.data::<Arc<RepositorySnapshot>>()?;
+        let filter = filter.unwrap_or_default();
+        let name = filter.name_contains.as_ref().map(|n| n.to_lowercase());
+        let target = filter.depends_on.as_deref().and_then(|p| snapshot.find(p));
+
+        Ok(snapshot
+            .scopes
+            .iter()
+            .filter(|s| match &filter.scope_type {
+                Some(scope_type) => &s.scope.definition.scope_type == scope_type,
+                None => true,
+            })
+            .filter(|s| match &name {
+                Some(name) => s.scope.definition.name.to_lowercase().contains(name),
+                None => true,
+            })
+            .filter(|s| match (&filter.depends_on, target) {
+                (None, _) => true,
+                (Some(_), None) => false,
+                (Some(_), Some(target)) => s.dependency_paths().iter().any(|(path, _, _)| {
+                    snapshot
+                        .resolve(s, path)
+                        .is_some_and(|d| d.path == target.path)
+                }),
+            })
+            .cloned()
+            .collect())
+    }
+
+    /// A single scope by its repository-relative path
+    async fn scope(
+        &self,
+        ctx: &Context<'_>,
+        path: String,
+    ) -> async_graphql::Result<Option<ScopeNode>> {
+        let snapshot = ctx.data::<Arc<RepositorySnapshot>>()?;
+        Ok(snapshot.find(&path).cloned())
+    }
+
+    /// Entries across all scopes
+    async fn entries(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<EntryFilter>,
+    ) -> async_graphql::Result<Vec<ContextEntry>> {
+        let snapshot = ctx.data::<Arc<RepositorySnapshot>>()?;
+        let filter = filter.unwrap_or_default();
+        let kinds = filter.allowed_kinds(ctx.data::<GraphqlAccess>()?)?;
+        let mut entries = Vec::new();
+        for scope in &snapshot.scopes {
+            entries.extend(scope.load_entries(&kinds)?);
+        }
+        filter.apply(entries)
+    }
+
+    /// Daemon health; requires `stats:read`
+    async fn health(&self, ctx: &Context<'_>) -> async_graphql::Result<HealthNode> {
+        let snapshot = ctx.data::<Arc<RepositorySnapshot>>()?;
+        let health = ctx
+            .data::<GraphqlAccess>()?
+            .health
+            .as_ref()
+            .ok_or("Insufficient permissions: stats:read")?;
+        Ok(HealthNode {
+            status: health.status.clone(),
+            uptime_seconds: health.uptime,
+            connections: health.connections,
+            request_count: health.request_count,
+            error_count: health.error_count,
+            error_rate: health.error_rate,
+            cache_hit_rate: health.cache_hit_rate,
+            restart_count: health.restart_count,
+            scope_count: snapshot.scopes.len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn scope(root: &Path, dir: &str, name: &str, dependencies: &str, todos: &str) {
+        let rhema = root.join(dir).join(".rhema");
+        fs::create_dir_all(&rhema).unwrap();
+        fs::write(
+            rhema.join("rhema.yaml"),
+            format!(
+                "name: {}\nscope_type: service\nversion: 1.0.0\n{}",
+                name, dependencies
+            ),
+        )
+        .unwrap();
+        fs::write(rhema.join("todos.yaml"), format!("todos:\n{}", todos)).unwrap();
+    }
+
+    fn repo() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        scope(
+            temp.path(),
+            "shared",
+            "shared",
+            "",
+            "  - id: s1\n    title: Bump serde\n    status: pending\n    tags: [deps]\n",
+        );
+        scope(
+            temp.path(),
+            "api",
+            "api",
+            "dependencies:\n  - path: ../shared\n    dependency_type: required\n",
+            "  - id: a1\n    title: Add auth\n    status: pending\n  - id: a2\n    title: Fix login\n    status: completed\n",
+        );
+        temp
+    }
+
+    async fn run(temp: &TempDir, access: GraphqlAccess, query: &str) -> Value {
+        let snapshot = Arc::new(RepositorySnapshot::load(temp.path()).unwrap());
+        let response = build_schema()
+            .execute(
+                async_graphql::Request::new(query)
+                    .data(snapshot)
+                    .data(access),
+            )
+            .await;
+        serde_json::to_value(response).unwrap()
+    }
+
+    fn full_access() -> GraphqlAccess {
+        GraphqlAccess {
+            entry_kinds: EntryKind::ALL.to_vec(),
+            health: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_nested_relations_and_entry_filters() {
+        let temp = repo();
+        let result = run(
+            &temp,
+            full_access(),
+            r#"{ scope(path: "api") {
+                    name
+                    entries(filter: {status: "pending"}) { id }
+                    dependencies { dependencyType scope { path entries(filter: {tag: "deps"}) { id } dependents { path } } }
+                } }"#,
+        )
+        .await;
+
+        assert!(result.get("errors").is_none(), "{}", result);
+        let api = &result["data"]["scope"];
+        assert_eq!(api["name"], "api");
+        assert_eq!(api["entries"], serde_json::json!([{"id": "a1"}]));
+        let shared = &api["dependencies"][0]["scope"];
+        assert_eq!(shared["path"], "shared");
+        assert_eq!(shared["entries"][0]["id"], "s1");
+        assert_eq!(shared["dependents"][0]["path"], "api");
+    }
+
+    #[tokio::test]
+    async fn test_scope_filter_depends_on() {
+        let temp = repo();
+        let result = run(
+            &temp,
+            full_access(),
+            r#"{ scopes(filter: {dependsOn: "shared"}) { path } }"#,
+        )
+        .await;
+        assert_eq!(
+            result["data"]["scopes"],
+            serde_json::json!([{"path": "api"}])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_permissions_are_enforced() {
+        let temp = repo();
+        let access = GraphqlAccess {
+            entry_kinds: vec![EntryKind::Knowledge],
+            health: None,
+        };
+
+        let denied = run(
+            &temp,
+            access.clone(),
+            r#"{ entries(filter: {kinds: [TODOS]}) { id } }"#,
+        )
+        .await;
+        assert!(denied["errors"][0]["message"]
+            .as_str()
+            .unwrap()
+            .contains("todos:read"));
+
+        // Without explicit kinds, unreadable kinds are skipped
+        let skipped = run(&temp, access.clone(), r#"{ entries { id } }"#).await;
+        assert_eq!(skipped["data"]["entries"], serde_json::json!([]));
+
+        let health = run(&temp, access, r#"{ health { status } }"#).await;
+        assert!(health["errors"][0]["message"]
+            .as_str()
+            .unwrap()
+            .contains("stats:read"));
+    }
+
+    #[test]
+    fn test_normalize_scope_paths() {
+        assert_eq!(normalize(Path::new("")), ".");
+        assert_eq!(normalize(Path::new("./api/")), "api");
+        assert_eq!(normalize(Path::new("api/../shared")), "shared");
+    }
+}
@@ -17,10 +17,12 @@
 use axum::{
     extract::{Path, Query, State, WebSocketUpgrade},
     http::{
-        header::{AUTHORIZATION, CONTENT_TYPE},
+        header::{
+            AUTHORIZATION, CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED,
+        },
         HeaderMap, Method, StatusCode,
     },
-    response::{IntoResponse, Json},
+    response::{IntoResponse, Json, Response},
     routing::{get, post},
     Router,
 };
@@ -45,6 +47,11 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use tokio::sync::Semaphore;
 
 use crate::mcp::{ClientType, McpConfig, McpDaemon};
+use crate::query_guard::{ANONYMOUS_IDENTITY, QUERY_TOOL_NAME};
+use crate::review_tool::{self, REVIEW_TOOL_NAME};
+use crate::request_trace::{self, current_request_id, trace_store};
+use crate::shutdown;
+use rhema_core::agent_tokens::TokenAccess;
 use rhema_core::{RhemaError, RhemaResult};
 
 /// Performance metrics for monitoring
@@ -271,6 +278,8 @@ pub struct HttpServer {
     string_cache: Arc<StringCache>,
     response_cache: Arc<DashMap<String, (Value, Instant)>>,
     rate_limit_cache: Arc<DashMap<String, (u32, Instant)>>,
+    #[cfg(feature = "graphql")]
+    graphql_schema: crate::graphql::RhemaSchema,
 }
 
 /// Query parameters for resource listing
@@ -338,6 +347,25 @@ pub struct SearchSuggestionsRequest {
 #[derive(Debug, Deserialize)]
 pub struct GetResourceParams {
     uri: String,
+    /// ETag from a previous read; a match returns `not_modified` instead of the content
+    #[serde(default)]
+    if_none_match: Option<String>,
+}
+
+/// Resource changes parameters
+#[derive(Debug, Default, Deserialize)]
+pub struct ResourceChangesParams {
+    /// Change token returned by the previous call
+    #[serde(default)]
+    since: Option<String>,
+}
+
+/// Tool call parameters
+#[derive(Debug, Deserialize)]
+pub struct ToolCallParams {
+    name: String,
+    #[serde(default)]
+    arguments: Value,
 }
 
 /// Execute query parameters
@@ -388,6 +416,7 @@ pub struct HealthResponse {
     error_count: u64,
     error_rate: f64,
     restart_count: u32,
+    dependencies: Vec<rhema_core::dependency_health::DependencyHealth>,
 }
 
 /// Info response
@@ -512,6 +541,8 @@ impl HttpServer {
             string_cache: Arc::new(StringCache::new()),
             response_cache: Arc::new(DashMap::new()),
             rate_limit_cache: Arc::new(DashMap::new()),
+            #[cfg(feature = "graphql")]
+            graphql_schema: crate::graphql::build_schema(),
         }
     }
 
@@ -588,7 +619,12 @@ impl HttpServer {
 
         info!("HTTP server listening on {}", http_addr);
 
-        axum::serve(listener, app).await?;
+        // Stop accepting connections once the daemon enters drain mode; axum then
+        // waits for open connections to finish their in-flight requests
+        let drain = self.daemon.drain_controller();
+        axum::serve(listener, app)
+            .with_graceful_shutdown(async move { drain.shutdown_requested().await })
+            .await?;
 
         Ok(())
     }
@@ -617,9 +653,8 @@ impl HttpServer {
     /// Stop the HTTP server
     pub async fn stop(&mut self) -> RhemaResult<()> {
         info!("Stopping HTTP server");
-        // Note: axum doesn't provide a direct stop method, but the server will stop
-        // when the future is dropped. In a real implementation, you might want to
-        // use a shutdown signal or graceful shutdown mechanism.
+        // Entering drain mode resolves the listener's graceful shutdown signal
+        self.daemon.drain_controller().begin_drain();
         Ok(())
     }
 
@@ -628,11 +663,12 @@ impl HttpServer {
         let cors = self.create_cors_layer();
         let security_headers = self.create_security_headers_layer();
 
-        Router::new()
+        let router = Router::new()
             .route("/health", get(Self::health_handler))
             .route("/info", get(Self::info_handler))
             .route("/rpc", post(Self::rpc_handler))
             .route("/resources", get(Self::resources_list_handler))
+            .route("/resources/changes", get(Self::resource_changes_handler))
             .route("/resources/:uri", get(Self::resource_handler))
             .route("/query", post(Self::query_handler))
             .route("/search", post(Self::search_handler))
@@ -677,9 +713,22 @@ impl HttpServer {
                 "/validation/dependencies",
                 get(Self::validate_dependencies_handler),
             )
+            // Request tracing
+            .route("/traces", get(Self::traces_handler))
+            .route("/traces/:request_id", get(Self::trace_handler));
+
+        #[cfg(feature = "graphql")]
+        let router = router.route("/graphql", post(Self::graphql_handler));
+
+        router
             .layer(cors)
             .layer(security_headers)
             .layer(TraceLayer::new_for_http())
+            .layer(axum::middleware::from_fn_with_state(
+                self.daemon.drain_controller(),
+                shutdown::http_middleware,
+            ))
+            .layer(axum::middleware::from_fn(request_trace::http_middleware))
             .layer(CompressionLayer::new())
             .with_state(Arc::new(self.clone()))
     }
@@ -827,8 +876,8 @@ impl HttpServer {
         server.daemon.increment_request_count().await;
 
         // Use zero-copy strings for common values
-        let status = server.string_cache.get_or_insert("healthy");
         let health = server.daemon.health().await;
+        let status = server.string_cache.get_or_insert(&health.status);
 
         let response = HealthResponse {
             status: status.to_string(),
@@ -840,6 +889,7 @@ impl HttpServer {
             error_count: health.error_count,
             error_rate: health.error_rate,
             restart_count: health.restart_count,
+            dependencies: health.dependencies,
         };
 
         let response_value = serde_json::to_value(response).unwrap_or_default();
@@ -910,6 +960,7 @@ impl HttpServer {
         let supported_methods = vec![
             "resources/list".to_string(),
             "resources/read".to_string(),
+            "resources/changes".to_string(),
             "query/execute".to_string(),
             "system/health".to_string(),
         ];
@@ -969,7 +1020,8 @@ impl HttpServer {
         // Increment request count
         server.daemon.increment_request_count().await;
 
-        let response = match HttpServer::handle_rpc_method(&server, &request).await {
+        let identity = Self::caller_identity(&auth_result, client_id);
+        let response = match HttpServer::handle_rpc_method(&server, &request, &identity).await {
             Ok(result) => JsonRpcResponse {
                 jsonrpc: "2.0".to_string(),
                 id: request.id,
@@ -985,7 +1037,8 @@ impl HttpServer {
                     error: Some(JsonRpcError {
                         code: -32603,
                         message: e.to_string(),
-                        data: None,
+                        data: current_request_id()
+                            .map(|request_id| serde_json::json!({ "request_id": request_id })),
                     }),
                 }
             }
@@ -1113,22 +1166,101 @@ impl HttpServer {
         }
 
         // Get resource from context provider
-        let resource = match server
+        let (resource, revision) = match server
             .daemon
             .get_context_provider()
-            .get_resource(&uri)
+            .get_resource_with_revision(&uri)
             .await
         {
             Ok(resource) => resource,
             Err(_) => return (StatusCode::NOT_FOUND, "Resource not found").into_response(),
         };
 
+        let validators = [
+            (ETAG, revision.etag.clone()),
+            (LAST_MODIFIED, revision.last_modified_header()),
+        ];
+
+        // Honor conditional requests so polling clients skip unchanged payloads
+        let if_none_match = headers.get(IF_NONE_MATCH).and_then(|h| h.to_str().ok());
+        let if_modified_since = headers.get(IF_MODIFIED_SINCE).and_then(|h| h.to_str().ok());
+        if revision.is_not_modified(if_none_match, if_modified_since) {
+            return (StatusCode::NOT_MODIFIED, validators).into_response();
+        }
+
         let response = serde_json::json!({
             "resource": resource,
-            "uri": uri
+            "uri": uri,
+            "etag": revision.etag
         });
 
-        (StatusCode::OK, Json(response)).into_response()
+        (StatusCode::OK, validators, Json(response)).into_response()
+    }
+
+    /// Resources added, modified or removed since a change token (`?since=`)
+    async fn resource_changes_handler(
+        State(server): State<Arc<Self>>,
+        headers: HeaderMap,
+        Query(params): Query<HashMap<String, String>>,
+    ) -> impl IntoResponse {
+        let client_id = Self::get_client_id(&headers);
+        let client_info = Self::extract_client_info(&headers);
+
+        // Check rate limiting
+        if let Some(ref client_id) = client_id {
+            if !server
+                .daemon
+                .get_auth_manager()
+                .check_rate_limit(client_id, "http")
+                .await
+            {
+                return (StatusCode::TOO_MANY_REQUESTS, "Rate limit exceeded").into_response();
+            }
+        }
+
+        // Authenticate request
+        let auth_result = match server
+            .daemon
+            .get_auth_manager()
+            .authenticate(
+                headers.get("authorization").and_then(|h| h.to_str().ok()),
+                client_info,
+            )
+            .await
+        {
+            Ok(result) => result,
+            Err(_) => {
+                return (StatusCode::INTERNAL_SERVER_ERROR, "Authentication error").into_response();
+            }
+        };
+
+        if !auth_result.authenticated {
+            return (StatusCode::UNAUTHORIZED, "Authentication required").into_response();
+        }
+
+        // Check permissions
+        if !server
+            .daemon
+            .get_auth_manager()
+            .has_permission(&auth_result, "resources:read")
+            .await
+        {
+            return (StatusCode::FORBIDDEN, "Insufficient permissions").into_response();
+        }
+
+        match server
+            .daemon
+            .get_context_provider()
+            .changed_since(params.get("since").map(String::as_str))
+            .await
+        {
+            Ok(changes) => (StatusCode::OK, Json(changes)).into_response(),
+            Err(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to compute resource changes",
+            )
+                .into_response(),
+        }
     }
 
     /// Query handler
@@ -1936,6 +2068,86 @@ impl HttpServer {
         (StatusCode::OK, Json(scopes)).into_response()
     }
 
+    /// GraphQL handler over scopes, entries, relations and health
+    #[cfg(feature = "graphql")]
+    async fn graphql_handler(
+        State(server): State<Arc<Self>>,
+        headers: HeaderMap,
+        Json(request): Json<async_graphql::Request>,
+    ) -> impl IntoResponse {
+        use crate::graphql::{EntryKind, GraphqlAccess, RepositorySnapshot};
+
+        let client_id = Self::get_client_id(&headers);
+        let client_info = Self::extract_client_info(&headers);
+        let auth_manager = server.daemon.get_auth_manager();
+
+        // Check rate limiting
+        if let Some(ref client_id) = client_id {
+            if !auth_manager.check_rate_limit(client_id, "http").await {
+                return (StatusCode::TOO_MANY_REQUESTS, "Rate limit exceeded").into_response();
+            }
+        }
+
+        // Authenticate request
+        let auth_result = match auth_manager
+            .authenticate(
+                headers.get("authorization").and_then(|h| h.to_str().ok()),
+                client_info,
+            )
+            .await
+        {
+            Ok(result) => result,
+            Err(_) => {
+                return (StatusCode::INTERNAL_SERVER_ERROR, "Authentication error").into_response();
+            }
+        };
+
+        if !auth_result.authenticated {
+            return (StatusCode::UNAUTHORIZED, "Authentication required").into_response();
+        }
+
+        if !auth_manager
+            .has_permission(&auth_result, "scopes:read")
+            .await
+        {
+            return (StatusCode::FORBIDDEN, "Insufficient permissions").into_response();
+        }
+
+        // Field-level permissions are resolved once and checked by the resolvers
+        let mut access = GraphqlAccess::default();
+        for kind in EntryKind::ALL {
+            if auth_manager
+                .has_permission(&auth_result, kind.permission())
+                .await
+            {
+                access.entry_kinds.push(kind);
+            }
+        }
+        if auth_manager
+            .has_permission(&auth_result, "stats:read")
+            .await
+        {
+            access.health = Some(server.daemon.health().await);
+        }
+
+        let repo_root = server.daemon.get_context_provider().repo_root();
+        let snapshot = match RepositorySnapshot::load(repo_root) {
+            Ok(snapshot) => Arc::new(snapshot),
+            Err(e) => {
+                error!("Failed to discover scopes for GraphQL request: {}", e);
+                return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to get scopes").into_response();
+            }
+        };
+
+        server.daemon.increment_request_count().await;
+        let response = server
+            .graphql_schema
+            .execute(request.data(snapshot).data(access))
+            .await;
+
+        (StatusCode::OK, Json(response)).into_response()
+    }
+
     /// Scope handler
     async fn scope_handler(
         State(server): State<Arc<Self>>,
@@ -1987,6 +2199,14 @@ impl HttpServer {
             return (StatusCode::FORBIDDEN, "Insufficient permissions").into_response();
         }
 
+        if !server.can_access_scope(&auth_result, &scope_id).await {
+            return (
+                StatusCode::FORBIDDEN,
+                "Token does not grant access to this scope",
+            )
+                .into_response();
+        }
+
         // Get scope from context provider
         let scope = match server
             .daemon
@@ -2052,6 +2272,14 @@ impl HttpServer {
             return (StatusCode::FORBIDDEN, "Insufficient permissions").into_response();
         }
 
+        if !server.can_access_scope(&auth_result, &scope_id).await {
+            return (
+                StatusCode::FORBIDDEN,
+                "Token does not grant access to this scope",
+            )
+                .into_response();
+        }
+
         // Get knowledge from context provider
         let knowledge = match server
             .daemon
@@ -2121,6 +2349,14 @@ impl HttpServer {
             return (StatusCode::FORBIDDEN, "Insufficient permissions").into_response();
         }
 
+        if !server.can_access_scope(&auth_result, &scope_id).await {
+            return (
+                StatusCode::FORBIDDEN,
+                "Token does not grant access to this scope",
+            )
+                .into_response();
+        }
+
         // Get todos from context provider
         let todos = match server
             .daemon
@@ -2189,6 +2425,14 @@ impl HttpServer {
             return (StatusCode::FORBIDDEN, "Insufficient permissions").into_response();
         }
 
+        if !server.can_access_scope(&auth_result, &scope_id).await {
+            return (
+                StatusCode::FORBIDDEN,
+                "Token does not grant access to this scope",
+            )
+                .into_response();
+        }
+
         // Get decisions from context provider
         let decisions = match server
             .daemon
@@ -2258,6 +2502,14 @@ impl HttpServer {
             return (StatusCode::FORBIDDEN, "Insufficient permissions").into_response();
         }
 
+        if !server.can_access_scope(&auth_result, &scope_id).await {
+            return (
+                StatusCode::FORBIDDEN,
+                "Token does not grant access to this scope",
+            )
+                .into_response();
+        }
+
         // Get patterns from context provider
         let patterns = match server
             .daemon
@@ -2456,11 +2708,16 @@ impl HttpServer {
                 .await;
         }
 
-        ws.on_upgrade(|socket| Self::handle_websocket(server, socket))
+        let identity = Self::caller_identity(&auth_result, client_id);
+        ws.on_upgrade(move |socket| Self::handle_websocket(server, socket, identity))
     }
 
     /// Handle WebSocket connection
-    async fn handle_websocket(server: Arc<Self>, mut socket: axum::extract::ws::WebSocket) {
+    async fn handle_websocket(
+        server: Arc<Self>,
+        mut socket: axum::extract::ws::WebSocket,
+        identity: String,
+    ) {
         info!("WebSocket connection established");
 
         while let Some(msg) = socket.recv().await {
@@ -2468,7 +2725,16 @@ impl HttpServer {
                 Ok(axum::extract::ws::Message::Text(text)) => {
                     // Parse JSON-RPC message
                     if let Ok(request) = serde_json::from_str::<JsonRpcRequest>(&text) {
-                        match HttpServer::handle_rpc_method(&server, &request).await {
+                        let request_id = request_trace::new_request_id();
+                        let outcome = request_trace::run_request(
+                            request_id.clone(),
+                            "websocket",
+                            &request.method,
+                            "/ws",
+                            HttpServer::handle_rpc_method(&server, &request, &identity),
+                        )
+                        .await;
+                        match outcome {
                             Ok(result) => {
                                 let response = JsonRpcResponse {
                                     jsonrpc: "2.0".to_string(),
@@ -2487,7 +2753,7 @@ impl HttpServer {
                                 let error = JsonRpcError {
                                     code: -1,
                                     message: e.to_string(),
-                                    data: None,
+                                    data: Some(serde_json::json!({ "request_id": request_id })),
                                 };
 
                                 let response = JsonRpcResponse {
@@ -2530,6 +2796,34 @@ impl HttpServer {
             .map(|s| s.to_string())
     }
 
+    /// Identity recorded for a caller: the authenticated user when there is
+    /// one, otherwise the client ID from the request headers
+    fn caller_identity(auth_result: &crate::auth::AuthResult, client_id: Option<String>) -> String {
+        auth_result
+            .user_id
+            .clone()
+            .or(client_id)
+            .unwrap_or_else(|| ANONYMOUS_IDENTITY.to_string())
+    }
+
+    /// Check an agent token's scope grants against a scope path, matching
+    /// grants by scope name
+    async fn can_access_scope(
+        &self,
+        auth_result: &crate::auth::AuthResult,
+        scope_id: &str,
+    ) -> bool {
+        let scope_name = match self.daemon.get_context_provider().get_scope(scope_id).await {
+            Ok(Some(scope)) => scope.definition.name,
+            _ => scope_id.to_string(),
+        };
+        self.daemon.get_auth_manager().has_scope_access(
+            auth_result,
+            &scope_name,
+            TokenAccess::Read,
+        )
+    }
+
     /// Extract client information from headers
     fn extract_client_info(headers: &HeaderMap) -> Option<crate::auth::ClientInfo> {
         let ip_address = headers
@@ -2557,7 +2851,11 @@ impl HttpServer {
     }
 
     /// Handle RPC method calls with performance optimization
-    async fn handle_rpc_method(server: &Arc<Self>, request: &JsonRpcRequest) -> RhemaResult<Value> {
+    async fn handle_rpc_method(
+        server: &Arc<Self>,
+        request: &JsonRpcRequest,
+        identity: &str,
+    ) -> RhemaResult<Value> {
         let start_time = Instant::now();
 
         let result = match request.method.as_str() {
@@ -2575,12 +2873,35 @@ impl HttpServer {
                     .as_ref()
                     .ok_or_else(|| RhemaError::InvalidInput("Missing params".to_string()))?;
                 let params: GetResourceParams = serde_json::from_value(params.clone())?;
-                let resource = server
+                let (mut resource, revision) = server
                     .daemon
                     .get_context_provider()
-                    .get_resource(&params.uri)
+                    .get_resource_with_revision(&params.uri)
                     .await?;
-                Ok(serde_json::to_value(resource)?)
+                if revision.is_not_modified(params.if_none_match.as_deref(), None) {
+                    Ok(serde_json::json!({
+                        "uri": params.uri,
+                        "etag": revision.etag,
+                        "not_modified": true
+                    }))
+                } else {
+                    if let Some(object) = resource.as_object_mut() {
+                        object.insert("etag".to_string(), Value::String(revision.etag));
+                    }
+                    Ok(resource)
+                }
+            }
+            "resources/changes" => {
+                let params: ResourceChangesParams = match request.params.as_ref() {
+                    Some(params) => serde_json::from_value(params.clone())?,
+                    None => ResourceChangesParams::default(),
+                };
+                let changes = server
+                    .daemon
+                    .get_context_provider()
+                    .changed_since(params.since.as_deref())
+                    .await?;
+                Ok(serde_json::to_value(changes)?)
             }
             "query/execute" => {
                 let params = request
@@ -2601,6 +2922,38 @@ impl HttpServer {
                     "execution_time_ms": execution_time.as_millis()
                 }))
             }
+            "tools/call" => {
+                let params = request
+                    .params
+                    .as_ref()
+                    .ok_or_else(|| RhemaError::InvalidInput("Missing params".to_string()))?;
+                let params: ToolCallParams = serde_json::from_value(params.clone())?;
+                if params.name == REVIEW_TOOL_NAME {
+                    let repo_root = server.daemon.get_context_provider().repo_root();
+                    return review_tool::execute(repo_root, identity, &params.arguments);
+                }
+                if params.name != QUERY_TOOL_NAME {
+                    return Err(RhemaError::InvalidInput(format!(
+                        "Unknown tool: {}",
+                        params.name
+                    )));
+                }
+                let query = params.arguments["query"].as_str().ok_or_else(|| {
+                    RhemaError::InvalidInput("Missing query parameter".to_string())
+                })?;
+                let max_rows = params.arguments["max_rows"].as_u64().map(|n| n as usize);
+                let result = server
+                    .daemon
+                    .get_query_guard()
+                    .execute(
+                        server.daemon.get_context_provider(),
+                        identity,
+                        query,
+                        max_rows,
+                    )
+                    .await?;
+                Ok(serde_json::to_value(result)?)
+            }
             _ => Err(RhemaError::InvalidInput(format!(
                 "Unknown method: {}",
                 request.method
@@ -2682,6 +3035,72 @@ impl HttpServer {
         }
     }
 
+    /// Authenticate a request for trace lookups
+    async fn authorize_trace_read(server: &Arc<Self>, headers: &HeaderMap) -> Option<Response> {
+        let client_info = Self::extract_client_info(headers);
+        let auth_result = match server
+            .daemon
+            .get_auth_manager()
+            .authenticate(
+                headers.get("authorization").and_then(|h| h.to_str().ok()),
+                client_info,
+            )
+            .await
+        {
+            Ok(result) => result,
+            Err(_) => {
+                return Some((StatusCode::UNAUTHORIZED, "Authentication failed").into_response())
+            }
+        };
+
+        if !server
+            .daemon
+            .get_auth_manager()
+            .has_permission(&auth_result, "stats:read")
+            .await
+        {
+            return Some((StatusCode::FORBIDDEN, "Insufficient permissions").into_response());
+        }
+        None
+    }
+
+    /// Reconstruct the path of a single request by correlation ID (or unique prefix)
+    async fn trace_handler(
+        State(server): State<Arc<Self>>,
+        headers: HeaderMap,
+        Path(request_id): Path<String>,
+    ) -> impl IntoResponse {
+        if let Some(denied) = Self::authorize_trace_read(&server, &headers).await {
+            return denied;
+        }
+
+        match trace_store().get(&request_id) {
+            Some(trace) => (StatusCode::OK, Json(trace)).into_response(),
+            None => (
+                StatusCode::NOT_FOUND,
+                format!("No trace recorded for request {}", request_id),
+            )
+                .into_response(),
+        }
+    }
+
+    /// List the most recent request traces
+    async fn traces_handler(
+        State(server): State<Arc<Self>>,
+        headers: HeaderMap,
+        Query(params): Query<HashMap<String, String>>,
+    ) -> impl IntoResponse {
+        if let Some(denied) = Self::authorize_trace_read(&server, &headers).await {
+            return denied;
+        }
+
+        let limit = params
+            .get("limit")
+            .and_then(|limit| limit.parse().ok())
+            .unwrap_or(50);
+        (StatusCode::OK, Json(trace_store().recent(limit))).into_response()
+    }
+
     // ============================================================================
     // VALIDATION ENDPOINT HANDLERS
     // ============================================================================
@@ -2774,6 +3193,14 @@ impl HttpServer {
             return (StatusCode::FORBIDDEN, "Insufficient permissions").into_response();
         }
 
+        if !server.can_access_scope(&auth_result, &scope_id).await {
+            return (
+                StatusCode::FORBIDDEN,
+                "Token does not grant access to this scope",
+            )
+                .into_response();
+        }
+
         // Validate the specific scope
         match server
             .daemon
@@ -3034,6 +3461,8 @@ impl Clone for HttpServer {
             string_cache: self.string_cache.clone(),
             response_cache: self.response_cache.clone(),
             rate_limit_cache: self.rate_limit_cache.clone(),
+            #[cfg(feature = "graphql")]
+            graphql_schema: self.graphql_schema.clone(),
         }
     }
 }
@@ -17,10 +17,19 @@
 pub mod auth;
 pub mod cache;
 pub mod context;
+#[cfg(feature = "graphql")]
+pub mod graphql;
 pub mod http_server;
 pub mod mcp;
+pub mod mcp_client;
 pub mod official_sdk;
+pub mod query_guard;
+pub mod request_trace;
+pub mod resource_revisions;
+pub mod resource_templates;
+pub mod review_tool;
 pub mod sdk;
+pub mod shutdown;
 pub mod watcher;
 
 // Re-export configuration types
@@ -36,14 +45,28 @@ pub use auth::{
 };
 pub use cache::{CacheManager, CacheStatistics};
 pub use context::ContextProvider;
+#[cfg(feature = "graphql")]
+pub use graphql::{build_schema, EntryKind, GraphqlAccess, RepositorySnapshot, RhemaSchema};
 pub use http_server::{
     ConnectionGuard, ConnectionPool, ConnectionPoolStats, EnhancedConnectionGuard,
     EnhancedConnectionPool, HttpServer, PerformanceMetrics, StringCache,
 };
+pub use mcp_client::{
+    ExternalMcpClient, ExternalMcpClients, ExternalResource, ExternalServerConfig, ExternalTool,
+    ExternalTransport, McpClientsConfig, MCP_CLIENTS_CONFIG_SECTION,
+};
 pub use official_sdk::{OfficialRhemaMcpServer, MCP_VERSION, SUPPORTED_VERSIONS};
+pub use query_guard::{GuardedQueryResult, QueryGuard, QueryGuardConfig, QUERY_TOOL_NAME};
+pub use request_trace::{
+    current_request_id, trace_store, RequestTrace, RequestTraceStore, TraceEvent, REQUEST_ID_HEADER,
+};
+pub use resource_revisions::{ChangedResources, ResourceRevision, ResourceRevisionLog};
+pub use resource_templates::{ResourceTemplate, ResourceTemplateRegistry, TemplateParameter};
+pub use review_tool::REVIEW_TOOL_NAME;
 pub use sdk::{
     ContextProviderExt, Prompt, PromptSegment, Resource, RhemaMcpServer, Tool, ToolResult,
 };
+pub use shutdown::{DrainController, DrainReport};
 pub use watcher::{FileWatcher, WatcherConfig as FileWatcherConfig};
 
 /// Main MCP service that coordinates all components
@@ -89,7 +112,8 @@ impl RhemaMcpService {
                 Arc::new(self.daemon.get_auth_manager().clone()),
                 self.daemon.config(),
             )
-            .await?;
+            .await?
+            .with_query_guard(self.daemon.get_query_guard());
 
             self.official_sdk_server = Some(official_sdk_server);
 
@@ -121,6 +145,35 @@ impl RhemaMcpService {
         Ok(())
     }
 
+    /// Drain in-flight work and stop the service.
+    ///
+    /// Unlike [`Self::stop`], this finishes running requests (up to
+    /// `deadline`), delivers pending watcher events and flushes the cache
+    /// before anything is torn down.
+    pub async fn drain(
+        &mut self,
+        deadline: std::time::Duration,
+    ) -> rhema_core::RhemaResult<DrainReport> {
+        let report = self.daemon.drain(deadline).await?;
+
+        if let Some(ref mut server) = self.official_sdk_server {
+            server.stop().await?;
+        }
+
+        self.http_server = None;
+        self.official_sdk_server = None;
+
+        Ok(report)
+    }
+
+    /// Wait for SIGTERM or Ctrl-C, then drain using the configured
+    /// graceful shutdown timeout
+    pub async fn run_until_shutdown(&mut self) -> rhema_core::RhemaResult<DrainReport> {
+        shutdown::termination_signal().await;
+        let deadline = self.daemon.shutdown_timeout();
+        self.drain(deadline).await
+    }
+
     /// Get the daemon instance
     pub fn daemon(&self) -> &McpDaemon {
         &self.daemon
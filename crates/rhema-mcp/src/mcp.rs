@@ -25,13 +25,18 @@ use crate::cache::{
 use crate::context::ContextProvider;
 use crate::http_server::HttpServer;
 use crate::official_sdk::OfficialRhemaMcpServer;
+use crate::query_guard::{QueryGuard, QueryGuardConfig};
 use crate::sdk::{
     ContextProviderExt, Prompt as SdkPrompt, Resource as SdkResource, RhemaMcpServer,
     Tool as SdkTool, ToolResult as SdkToolResult,
 };
+use crate::shutdown::{DrainController, DrainReport};
 use crate::watcher::FileWatcher;
 
+use rhema_core::agent_tokens::AgentTokenStore;
+use rhema_core::dependency_health::{DependencyHealth, DependencyHealthMonitor, ProbeState};
 use rhema_core::RhemaResult;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -41,7 +46,7 @@ use tokio::sync::RwLock;
 use tracing::{error, info, warn};
 
 /// MCP Daemon configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct McpConfig {
     /// Daemon host address
     pub host: String,
@@ -75,10 +80,14 @@ pub struct McpConfig {
 
     /// Maximum concurrent connections
     pub max_connections: Option<usize>,
+
+    /// Guardrails for the `rhema.query` tool
+    #[serde(default)]
+    pub query_guard: QueryGuardConfig,
 }
 
 /// Authentication configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AuthConfig {
     /// Enable authentication
     pub enabled: bool,
@@ -103,7 +112,7 @@ pub struct AuthConfig {
 }
 
 /// Audit logging configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct AuditLoggingConfig {
     /// Enable audit logging
     pub enabled: bool,
@@ -135,7 +144,7 @@ impl Default for AuditLoggingConfig {
 }
 
 /// Security configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SecurityConfig {
     /// Enable brute force protection
     pub brute_force_protection: bool,
@@ -163,7 +172,7 @@ pub struct SecurityConfig {
 }
 
 /// Rate limiting configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct RateLimitConfig {
     /// Requests per minute for HTTP API
     pub http_requests_per_minute: u32,
@@ -176,7 +185,7 @@ pub struct RateLimitConfig {
 }
 
 /// File system watcher configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WatcherConfig {
     /// Enable file system watching
     pub enabled: bool,
@@ -198,7 +207,7 @@ pub struct WatcherConfig {
 }
 
 /// Cache configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct CacheConfig {
     /// Enable in-memory caching
     pub memory_enabled: bool,
@@ -220,7 +229,7 @@ pub struct CacheConfig {
 }
 
 /// Logging configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LoggingConfig {
     /// Log level
     pub level: String,
@@ -236,7 +245,7 @@ pub struct LoggingConfig {
 }
 
 /// Startup configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct StartupConfig {
     /// Graceful shutdown timeout in seconds
     pub graceful_shutdown_timeout: u64,
@@ -268,6 +277,7 @@ impl Default for McpConfig {
             logging: LoggingConfig::default(),
             use_official_sdk: true,
             startup: StartupConfig::default(),
+            query_guard: QueryGuardConfig::default(),
         }
     }
 }
@@ -367,9 +377,11 @@ pub struct McpDaemon {
     cache_manager: Arc<CacheManager>,
     file_watcher: Arc<FileWatcher>,
     auth_manager: Arc<AuthManager>,
+    query_guard: Arc<QueryGuard>,
     connections: Arc<RwLock<HashMap<String, ClientConnection>>>,
     official_sdk_server: Option<OfficialRhemaMcpServer>,
     http_server: Option<HttpServer>,
+    drain: Arc<DrainController>,
     // Daemon state tracking
     start_time: Instant,
     uptime: Arc<RwLock<Duration>>,
@@ -379,12 +391,21 @@ pub struct McpDaemon {
     request_count: Arc<RwLock<u64>>,
     error_count: Arc<RwLock<u64>>,
     last_health_check: Arc<RwLock<Instant>>,
+    // Embedding, vector store and LLM provider probes
+    dependency_monitor: Option<Arc<DependencyHealthMonitor>>,
+    dependency_refresh: Arc<RwLock<Option<tokio::task::JoinHandle<()>>>>,
 }
 
 impl McpDaemon {
     /// Create a new MCP daemon instance
     pub async fn new(config: McpConfig, repo_root: PathBuf) -> RhemaResult<Self> {
-        let context_provider = Arc::new(ContextProvider::new(repo_root.clone())?);
+        let agent_tokens = AgentTokenStore::new(&repo_root);
+        let auth_manager =
+            Arc::new(AuthManager::new(&config.auth)?.with_agent_tokens(agent_tokens));
+        let context_provider = Arc::new(
+            ContextProvider::new(repo_root.clone())?
+                .with_security_monitor(auth_manager.security_monitor_handle()),
+        );
 
         // Convert config types
         let cache_config = CacheManagerConfig {
@@ -419,7 +440,8 @@ impl McpDaemon {
 
         let cache_manager = Arc::new(CacheManager::new(&cache_config).await?);
         let file_watcher = Arc::new(FileWatcher::new(&watcher_config, repo_root).await?);
-        let auth_manager = Arc::new(AuthManager::new(&config.auth)?);
+        let query_guard =
+            Arc::new(QueryGuard::new(config.query_guard.clone()).with_audit(auth_manager.clone()));
         let connections = Arc::new(RwLock::new(HashMap::new()));
 
         // Initialize official SDK server if enabled
@@ -432,7 +454,8 @@ impl McpDaemon {
                     auth_manager.clone(),
                     &config,
                 )
-                .await?,
+                .await?
+                .with_query_guard(query_guard.clone()),
             )
         } else {
             None
@@ -444,9 +467,11 @@ impl McpDaemon {
             cache_manager,
             file_watcher,
             auth_manager,
+            query_guard,
             connections,
             official_sdk_server,
             http_server: None, // Will be initialized in start()
+            drain: Arc::new(DrainController::new()),
             start_time: Instant::now(),
             uptime: Arc::new(RwLock::new(Duration::ZERO)),
             is_running: Arc::new(RwLock::new(false)),
@@ -454,9 +479,21 @@ impl McpDaemon {
             request_count: Arc::new(RwLock::new(0)),
             error_count: Arc::new(RwLock::new(0)),
             last_health_check: Arc::new(RwLock::new(Instant::now())),
+            dependency_monitor: None,
+            dependency_refresh: Arc::new(RwLock::new(None)),
         })
     }
 
+    /// Probe external dependencies while running and report them in [`HealthStatus`]
+    pub fn with_dependency_monitor(mut self, monitor: Arc<DependencyHealthMonitor>) -> Self {
+        self.dependency_monitor = Some(monitor);
+        self
+    }
+
+    pub fn dependency_monitor(&self) -> Option<&Arc<DependencyHealthMonitor>> {
+        self.dependency_monitor.as_ref()
+    }
+
     /// Start the MCP daemon
     pub async fn start(&mut self) -> RhemaResult<()> {
         info!(
@@ -464,6 +501,9 @@ impl McpDaemon {
             self.config.host, self.config.port
         );
 
+        // Accept requests again if the daemon was previously drained
+        self.drain.reset();
+
         // Mark daemon as running
         *self.is_running.write().await = true;
 
@@ -481,6 +521,14 @@ impl McpDaemon {
         // Start health check monitoring
         self.start_health_monitoring().await;
 
+        // Keep dependency probe results fresh in the background
+        if let Some(monitor) = &self.dependency_monitor {
+            let refresh = monitor.clone().spawn();
+            if let Some(previous) = self.dependency_refresh.write().await.replace(refresh) {
+                previous.abort();
+            }
+        }
+
         // Start HTTP server
         self.start_http_server().await?;
 
@@ -495,6 +543,10 @@ impl McpDaemon {
         // Mark daemon as not running
         *self.is_running.write().await = false;
 
+        if let Some(refresh) = self.dependency_refresh.write().await.take() {
+            refresh.abort();
+        }
+
         // Stop file watcher
         self.file_watcher.stop().await?;
 
@@ -518,6 +570,78 @@ impl McpDaemon {
         Ok(())
     }
 
+    /// Drain the daemon and stop it.
+    ///
+    /// New requests are refused immediately, in-flight requests get until
+    /// `deadline` to finish, then pending watcher events are delivered, the
+    /// cache is flushed and a final statistics snapshot is recorded before
+    /// the daemon stops.
+    pub async fn drain(&mut self, deadline: Duration) -> RhemaResult<DrainReport> {
+        let started = Instant::now();
+        let started_at = chrono::Utc::now();
+        let in_flight_at_start = self.drain.in_flight();
+        info!(
+            "Draining MCP daemon ({} requests in flight, deadline {:?})",
+            in_flight_at_start, deadline
+        );
+
+        self.drain.begin_drain();
+        let abandoned_requests = if self.drain.wait_idle(deadline).await {
+            0
+        } else {
+            let remaining = self.drain.in_flight();
+            warn!(
+                "Drain deadline passed with {} requests still in flight",
+                remaining
+            );
+            remaining
+        };
+
+        // Debounced events always get at least one debounce window to land,
+        // even when in-flight requests used up the deadline
+        let watcher_budget = deadline
+            .saturating_sub(started.elapsed())
+            .max(Duration::from_millis(self.config.watcher.debounce_ms));
+        let (flushed_watcher_events, dropped_watcher_events) =
+            self.file_watcher.drain(watcher_budget).await;
+        if dropped_watcher_events > 0 {
+            warn!(
+                "Dropped {} pending file watcher events during shutdown",
+                dropped_watcher_events
+            );
+        }
+
+        let cache_flushed = match self.cache_manager.save_persisted_cache().await {
+            Ok(()) => true,
+            Err(e) => {
+                error!("Failed to flush cache during shutdown: {}", e);
+                false
+            }
+        };
+
+        let statistics = self.get_statistics().await;
+        info!(
+            "Final daemon statistics: {}",
+            serde_json::to_string(&statistics).unwrap_or_default()
+        );
+
+        self.stop().await?;
+
+        let report = DrainReport {
+            started_at,
+            duration_ms: started.elapsed().as_millis() as u64,
+            deadline_ms: deadline.as_millis() as u64,
+            in_flight_at_start,
+            abandoned_requests,
+            flushed_watcher_events,
+            dropped_watcher_events,
+            cache_flushed,
+            statistics,
+        };
+        info!("MCP daemon drained in {}ms", report.duration_ms);
+        Ok(report)
+    }
+
     /// Restart the MCP daemon
     pub async fn restart(&mut self) -> RhemaResult<()> {
         info!("Restarting MCP daemon");
@@ -558,12 +682,19 @@ impl McpDaemon {
         let request_count = *self.request_count.read().await;
         let error_count = *self.error_count.read().await;
         let is_running = *self.is_running.read().await;
+        let dependencies = match &self.dependency_monitor {
+            Some(monitor) => monitor.cached().await,
+            None => Vec::new(),
+        };
+        let degraded = dependencies
+            .iter()
+            .any(|dependency| dependency.state != ProbeState::Healthy);
 
         HealthStatus {
-            status: if is_running {
-                "healthy".to_string()
-            } else {
-                "stopped".to_string()
+            status: match (is_running, degraded) {
+                (false, _) => "stopped".to_string(),
+                (true, true) => "degraded".to_string(),
+                (true, false) => "healthy".to_string(),
             },
             uptime: uptime.as_secs(),
             connections,
@@ -577,6 +708,7 @@ impl McpDaemon {
                 0.0
             },
             restart_count: *self.restart_count.read().await,
+            dependencies,
         }
     }
 
@@ -707,6 +839,16 @@ impl McpDaemon {
         &self.config
     }
 
+    /// Get the drain controller shared by the daemon's transports
+    pub fn drain_controller(&self) -> Arc<DrainController> {
+        self.drain.clone()
+    }
+
+    /// Configured graceful shutdown deadline
+    pub fn shutdown_timeout(&self) -> Duration {
+        Duration::from_secs(self.config.startup.graceful_shutdown_timeout)
+    }
+
     /// Get a reference to the context provider
     pub fn get_context_provider(&self) -> &ContextProvider {
         &self.context_provider
@@ -727,6 +869,11 @@ impl McpDaemon {
         &self.auth_manager
     }
 
+    /// Get the guardrails applied to AI client queries
+    pub fn get_query_guard(&self) -> Arc<QueryGuard> {
+        self.query_guard.clone()
+    }
+
     /// Get memory usage statistics
     pub async fn get_memory_usage(&self) -> MemoryUsage {
         let mut used = 0u64;
@@ -772,6 +919,7 @@ impl McpDaemon {
         let uptime = self.get_uptime().await;
         let connection_count = self.get_connection_count().await;
         let cache_stats = self.cache_manager.get_statistics().await;
+        let cache_tier = self.cache_manager.tier_report().await;
 
         DaemonStatistics {
             uptime: uptime.as_secs(),
@@ -781,6 +929,7 @@ impl McpDaemon {
             error_rate: health.error_rate,
             cache_hit_rate: health.cache_hit_rate,
             cache_stats,
+            cache_tier,
             restart_count: health.restart_count,
             memory_usage: health.memory_usage,
         }
@@ -799,6 +948,9 @@ pub struct HealthStatus {
     pub error_count: u64,
     pub error_rate: f64,
     pub restart_count: u32,
+    /// Latest probe results for embedding, vector store and LLM provider dependencies
+    #[serde(default)]
+    pub dependencies: Vec<DependencyHealth>,
 }
 
 /// Memory usage information
@@ -820,6 +972,9 @@ pub struct DaemonStatistics {
     pub error_rate: f64,
     pub cache_hit_rate: f64,
     pub cache_stats: crate::cache::CacheStatistics,
+    /// The daemon cache as a tier of the consolidated cache report
+    #[serde(default)]
+    pub cache_tier: rhema_core::cache_report::CacheTierReport,
     pub restart_count: u32,
     pub memory_usage: MemoryUsage,
 }
@@ -0,0 +1,961 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Client mode for consuming other MCP servers.
+//!
+//! Servers listed in the `mcp_clients` section of `.rhema/repository.yaml`
+//! are connected over stdio (a spawned process speaking newline-delimited
+//! JSON-RPC) or streamable HTTP. Their tools and resources are namespaced by
+//! server name so they cannot collide with each other or with Rhema's own:
+//! tool `search` on server `jira` becomes `jira.search`, and resource
+//! `issue://ABC-1` becomes `jira:issue://ABC-1`.
+//!
+//! Tool and resource listings and resource reads are cached for the server's
+//! TTL. Tool calls have side effects, so only tools named in the server's
+//! `cache_tools` list are cached, keyed by their arguments.
+
+use async_trait::async_trait;
+use rhema_core::{RhemaError, RhemaResult};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+
+use crate::official_sdk::MCP_VERSION;
+
+/// Section of `.rhema/repository.yaml` listing external MCP servers
+pub const MCP_CLIENTS_CONFIG_SECTION: &str = "mcp_clients";
+
+/// Separates the server name from a tool name
+pub const TOOL_NAMESPACE_SEPARATOR: char = '.';
+
+/// Separates the server name from a resource URI
+pub const RESOURCE_NAMESPACE_SEPARATOR: char = ':';
+
+/// External MCP server settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct McpClientsConfig {
+    pub servers: Vec<ExternalServerConfig>,
+
+    /// Seconds a cached response stays valid unless the server sets its own
+    pub default_ttl_secs: u64,
+
+    /// Seconds to wait for any single request
+    pub request_timeout_secs: u64,
+
+    /// Oldest responses are evicted beyond this
+    pub max_cache_entries: usize,
+}
+
+impl Default for McpClientsConfig {
+    fn default() -> Self {
+        Self {
+            servers: Vec::new(),
+            default_ttl_secs: 300,
+            request_timeout_secs: 30,
+            max_cache_entries: 1024,
+        }
+    }
+}
+
+impl McpClientsConfig {
+    /// Load the configuration from the repository config and policy bundle,
+    /// falling back to defaults
+    pub fn load(repo_root: &Path) -> RhemaResult<Self> {
+        let config = match rhema_core::policy::repository_config(repo_root)?
+            .get(MCP_CLIENTS_CONFIG_SECTION)
+        {
+            Some(section) => serde_yaml::from_value::<Self>(section.clone()).map_err(|e| {
+                RhemaError::ConfigError(format!(
+                    "Invalid {} section in the repository config: {}",
+                    MCP_CLIENTS_CONFIG_SECTION, e
+                ))
+            })?,
+            None => Self::default(),
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Server names must be unique and must not contain a namespace separator
+    pub fn validate(&self) -> RhemaResult<()> {
+        let mut seen = Vec::new();
+        for server in &self.servers {
+            let valid = !server.name.is_empty()
+                && server
+                    .name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            if !valid {
+                return Err(RhemaError::ConfigError(format!(
+                    "Invalid MCP server name '{}': use letters, digits, '-' and '_'",
+                    server.name
+                )));
+            }
+            if seen.contains(&&server.name) {
+                return Err(RhemaError::ConfigError(format!(
+                    "MCP server '{}' is configured twice",
+                    server.name
+                )));
+            }
+            seen.push(&server.name);
+        }
+        Ok(())
+    }
+
+    fn ttl_for(&self, server: &ExternalServerConfig) -> Duration {
+        Duration::from_secs(server.ttl_secs.unwrap_or(self.default_ttl_secs))
+    }
+}
+
+/// One external MCP server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalServerConfig {
+    /// Namespace for the server's tools and resources
+    pub name: String,
+
+    #[serde(flatten)]
+    pub transport: ExternalTransport,
+
+    /// Overrides `default_ttl_secs` for this server
+    #[serde(default)]
+    pub ttl_secs: Option<u64>,
+
+    /// Tools whose results may be cached, by their name on the server
+    #[serde(default)]
+    pub cache_tools: Vec<String>,
+
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// How to reach an external server. `${VAR}` in environment values and
+/// headers is replaced from the environment, so secrets stay out of the
+/// repository config.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "transport", rename_all = "snake_case")]
+pub enum ExternalTransport {
+    Stdio {
+        command: String,
+        #[serde(default)]
+        args: Vec<String>,
+        #[serde(default)]
+        env: BTreeMap<String, String>,
+    },
+    Http {
+        url: String,
+        #[serde(default)]
+        headers: BTreeMap<String, String>,
+    },
+}
+
+/// A tool offered by an external server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalTool {
+    /// `server.tool`
+    pub name: String,
+    pub server: String,
+    /// Name on the server
+    pub tool: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub input_schema: Value,
+}
+
+/// A resource offered by an external server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalResource {
+    /// `server:uri`
+    pub uri: String,
+    pub server: String,
+    /// URI on the server
+    pub resource_uri: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+}
+
+/// Hit and miss counts of the response cache
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ResponseCacheStats {
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+/// `server.tool` for a tool on `server`
+pub fn namespaced_tool(server: &str, tool: &str) -> String {
+    format!("{}{}{}", server, TOOL_NAMESPACE_SEPARATOR, tool)
+}
+
+/// `server:uri` for a resource on `server`
+pub fn namespaced_resource(server: &str, uri: &str) -> String {
+    format!("{}{}{}", server, RESOURCE_NAMESPACE_SEPARATOR, uri)
+}
+
+/// Connections to every enabled external server, with a shared response cache
+pub struct ExternalMcpClients {
+    config: McpClientsConfig,
+    clients: BTreeMap<String, ExternalMcpClient>,
+    failures: BTreeMap<String, String>,
+    cache: ResponseCache,
+}
+
+impl ExternalMcpClients {
+    /// Connect to the servers configured for the repository
+    pub async fn open(repo_root: &Path) -> RhemaResult<Self> {
+        Self::connect(McpClientsConfig::load(repo_root)?).await
+    }
+
+    /// Connect to every enabled server. A server that cannot be reached is
+    /// recorded in [`Self::failures`] instead of failing the others.
+    pub async fn connect(config: McpClientsConfig) -> RhemaResult<Self> {
+        config.validate()?;
+        let timeout = Duration::from_secs(config.request_timeout_secs.max(1));
+        let mut clients = BTreeMap::new();
+        let mut failures = BTreeMap::new();
+        for server in config.servers.iter().filter(|server| server.enabled) {
+            match ExternalMcpClient::connect(server.clone(), timeout).await {
+                Ok(client) => {
+                    tracing::info!("Connected to external MCP server {}", server.name);
+                    clients.insert(server.name.clone(), client);
+                }
+                Err(e) => {
+                    tracing::warn!("Could not connect to MCP server {}: {}", server.name, e);
+                    failures.insert(server.name.clone(), e.to_string());
+                }
+            }
+        }
+        Ok(Self {
+            cache: ResponseCache::new(config.max_cache_entries),
+            config,
+            clients,
+            failures,
+        })
+    }
+
+    pub fn config(&self) -> &McpClientsConfig {
+        &self.config
+    }
+
+    /// Names of the connected servers
+    pub fn servers(&self) -> Vec<&str> {
+        self.clients.keys().map(String::as_str).collect()
+    }
+
+    /// Servers that could not be connected, with the reason
+    pub fn failures(&self) -> &BTreeMap<String, String> {
+        &self.failures
+    }
+
+    /// Namespaced tools of every connected server
+    pub async fn tools(&self) -> RhemaResult<Vec<ExternalTool>> {
+        let mut tools = Vec::new();
+        for client in self.clients.values() {
+            let listing = self
+                .cached(client, "tools/list".to_string(), || client.list_tools())
+                .await?;
+            for tool in listing.as_array().into_iter().flatten() {
+                let Some(name) = tool.get("name").and_then(Value::as_str) else {
+                    continue;
+                };
+                tools.push(ExternalTool {
+                    name: namespaced_tool(client.name(), name),
+                    server: client.name().to_string(),
+                    tool: name.to_string(),
+                    description: tool
+                        .get("description")
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                    input_schema: tool
+                        .get("inputSchema")
+                        .cloned()
+                        .unwrap_or_else(|| json!({"type": "object"})),
+                });
+            }
+        }
+        Ok(tools)
+    }
+
+    /// Namespaced resources of every connected server
+    pub async fn resources(&self) -> RhemaResult<Vec<ExternalResource>> {
+        let mut resources = Vec::new();
+        for client in self.clients.values() {
+            let listing = self
+                .cached(client, "resources/list".to_string(), || {
+                    client.list_resources()
+                })
+                .await?;
+            for resource in listing.as_array().into_iter().flatten() {
+                let Some(uri) = resource.get("uri").and_then(Value::as_str) else {
+                    continue;
+                };
+                let text = |field: &str| {
+                    resource
+                        .get(field)
+                        .and_then(Value::as_str)
+                        .map(str::to_string)
+                };
+                resources.push(ExternalResource {
+                    uri: namespaced_resource(client.name(), uri),
+                    server: client.name().to_string(),
+                    resource_uri: uri.to_string(),
+                    name: text("name").unwrap_or_else(|| uri.to_string()),
+                    description: text("description"),
+                    mime_type: text("mimeType"),
+                });
+            }
+        }
+        Ok(resources)
+    }
+
+    /// Call a tool by its namespaced name. Tool-level failures come back as a
+    /// result with `isError` set, as the server reported them, and are never
+    /// cached.
+    pub async fn call_tool(&self, name: &str, arguments: Value) -> RhemaResult<Value> {
+        let (client, tool) = self.resolve(name, TOOL_NAMESPACE_SEPARATOR)?;
+        let params = json!({"name": tool, "arguments": arguments});
+        if !client.config.cache_tools.iter().any(|t| t == tool) {
+            return client.request("tools/call", params).await;
+        }
+        let key = format!("tools/call\0{}\0{}", tool, arguments);
+        self.cached(client, key, || client.request("tools/call", params.clone()))
+            .await
+    }
+
+    /// Read a resource by its namespaced URI
+    pub async fn read_resource(&self, uri: &str) -> RhemaResult<Value> {
+        let (client, resource_uri) = self.resolve(uri, RESOURCE_NAMESPACE_SEPARATOR)?;
+        let key = format!("resources/read\0{}", resource_uri);
+        self.cached(client, key, || {
+            client.request("resources/read", json!({"uri": resource_uri}))
+        })
+        .await
+    }
+
+    /// Drop cached responses of one server, or of all servers
+    pub fn invalidate(&self, server: Option<&str>) {
+        self.cache.invalidate(server);
+    }
+
+    pub fn cache_stats(&self) -> ResponseCacheStats {
+        self.cache.stats()
+    }
+
+    /// Shut down stdio server processes
+    pub async fn close(self) {
+        for client in self.clients.into_values() {
+            client.close().await;
+        }
+    }
+
+    fn resolve<'a>(
+        &'a self,
+        name: &'a str,
+        separator: char,
+    ) -> RhemaResult<(&'a ExternalMcpClient, &'a str)> {
+        let (server, rest) = name.split_once(separator).ok_or_else(|| {
+            RhemaError::InvalidInput(format!(
+                "'{}' is not namespaced with an MCP server name",
+                name
+            ))
+        })?;
+        match self.clients.get(server) {
+            Some(client) => Ok((client, rest)),
+            None => match self.failures.get(server) {
+                Some(reason) => Err(RhemaError::ServiceUnavailable(format!(
+                    "MCP server '{}' is not connected: {}",
+                    server, reason
+                ))),
+                None => Err(RhemaError::NotFound(format!(
+                    "No MCP server named '{}'",
+                    server
+                ))),
+            },
+        }
+    }
+
+    async fn cached<F, Fut>(
+        &self,
+        client: &ExternalMcpClient,
+        key: String,
+        fetch: F,
+    ) -> RhemaResult<Value>
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = RhemaResult<Value>>,
+    {
+        let key = format!("{}\0{}", client.name(), key);
+        if let Some(value) = self.cache.get(&key) {
+            return Ok(value);
+        }
+        let value = fetch().await?;
+        let is_error = value.get("isError").and_then(Value::as_bool) == Some(true);
+        if !is_error {
+            let ttl = self.config.ttl_for(&client.config);
+            self.cache.insert(key, value.clone(), ttl);
+        }
+        Ok(value)
+    }
+}
+
+/// An initialized session with one external server
+pub struct ExternalMcpClient {
+    config: ExternalServerConfig,
+    transport: Box<dyn Transport>,
+    /// `serverInfo` and `capabilities` from the initialize response
+    server: Value,
+}
+
+impl ExternalMcpClient {
+    /// Open the transport and run the initialize handshake
+    pub async fn connect(config: ExternalServerConfig, timeout: Duration) -> RhemaResult<Self> {
+        let transport: Box<dyn Transport> = match &config.transport {
+            ExternalTransport::Stdio { command, args, env } => {
+                Box::new(StdioTransport::spawn(command, args, env, timeout)?)
+            }
+            ExternalTransport::Http { url, headers } => {
+                Box::new(HttpTransport::new(url, headers, timeout)?)
+            }
+        };
+        let server = transport
+            .request(
+                "initialize",
+                json!({
+                    "protocolVersion": MCP_VERSION,
+                    "capabilities": {},
+                    "clientInfo": {
+                        "name": "rhema",
+                        "version": env!("CARGO_PKG_VERSION"),
+                    },
+                }),
+            )
+            .await?;
+        transport
+            .notify("notifications/initialized", json!({}))
+            .await?;
+        Ok(Self {
+            config,
+            transport,
+            server,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    /// Initialize response: protocol version, server info and capabilities
+    pub fn server_info(&self) -> &Value {
+        &self.server
+    }
+
+    /// Every tool, following pagination cursors
+    pub async fn list_tools(&self) -> RhemaResult<Value> {
+        self.list("tools/list", "tools").await
+    }
+
+    /// Every resource, following pagination cursors. Servers without the
+    /// resources capability have none.
+    pub async fn list_resources(&self) -> RhemaResult<Value> {
+        if self.server.pointer("/capabilities/resources").is_none() {
+            return Ok(json!([]));
+        }
+        self.list("resources/list", "resources").await
+    }
+
+    pub async fn request(&self, method: &str, params: Value) -> RhemaResult<Value> {
+        self.transport.request(method, params).await
+    }
+
+    pub async fn close(&self) {
+        self.transport.close().await;
+    }
+
+    async fn list(&self, method: &str, field: &str) -> RhemaResult<Value> {
+        let mut items = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let params = match &cursor {
+                Some(cursor) => json!({"cursor": cursor}),
+                None => json!({}),
+            };
+            let page = self.transport.request(method, params).await?;
+            if let Some(Value::Array(page_items)) = page.get(field) {
+                items.extend(page_items.iter().cloned());
+            }
+            cursor = page
+                .get("nextCursor")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            if cursor.is_none() {
+                return Ok(Value::Array(items));
+            }
+        }
+    }
+}
+
+/// JSON-RPC over some connection to a server
+#[async_trait]
+trait Transport: Send + Sync {
+    /// Send a request and return the `result` of its response
+    async fn request(&self, method: &str, params: Value) -> RhemaResult<Value>;
+
+    async fn notify(&self, method: &str, params: Value) -> RhemaResult<()>;
+
+    async fn close(&self);
+}
+
+/// A spawned server process speaking newline-delimited JSON-RPC
+struct StdioTransport {
+    child: tokio::sync::Mutex<Child>,
+    io: tokio::sync::Mutex<(ChildStdin, BufReader<ChildStdout>)>,
+    next_id: AtomicU64,
+    timeout: Duration,
+}
+
+impl StdioTransport {
+    fn spawn(
+        command: &str,
+        args: &[String],
+        env: &BTreeMap<String, String>,
+        timeout: Duration,
+    ) -> RhemaResult<Self> {
+        let mut child = Command::new(command)
+            .args(args)
+            .envs(env.iter().map(|(key, value)| (key, expand_env(value))))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                RhemaError::McpError(format!("Failed to start MCP server '{}': {}", command, e))
+            })?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = child.stdout.take().expect("stdout is piped");
+        Ok(Self {
+            child: tokio::sync::Mutex::new(child),
+            io: tokio::sync::Mutex::new((stdin, BufReader::new(stdout))),
+            next_id: AtomicU64::new(1),
+            timeout,
+        })
+    }
+
+    async fn write(stdin: &mut ChildStdin, message: &Value) -> RhemaResult<()> {
+        let mut line = serde_json::to_vec(message)?;
+        line.push(b'\n');
+        stdin.write_all(&line).await?;
+        stdin.flush().await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Transport for StdioTransport {
+    async fn request(&self, method: &str, params: Value) -> RhemaResult<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let mut io = self.io.lock().await;
+        let (stdin, stdout) = &mut *io;
+        let exchange = async {
+            Self::write(
+                stdin,
+                &json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params}),
+            )
+            .await?;
+            let mut line = String::new();
+            loop {
+                line.clear();
+                if stdout.read_line(&mut line).await? == 0 {
+                    return Err(RhemaError::McpError(
+                        "MCP server closed its output".to_string(),
+                    ));
+                }
+                let Ok(message) = serde_json::from_str::<Value>(line.trim()) else {
+                    continue;
+                };
+                // Requests from the server, such as ping, are answered inline
+                if let (Some(request_id), Some(request_method)) = (
+                    message.get("id"),
+                    message.get("method").and_then(Value::as_str),
+                ) {
+                    Self::write(stdin, &server_request_reply(request_id, request_method)).await?;
+                    continue;
+                }
+                if message.get("id").and_then(Value::as_u64) == Some(id) {
+                    return rpc_result(message);
+                }
+            }
+        };
+        tokio::time::timeout(self.timeout, exchange)
+            .await
+            .map_err(|_| timed_out(method, self.timeout))?
+    }
+
+    async fn notify(&self, method: &str, params: Value) -> RhemaResult<()> {
+        let mut io = self.io.lock().await;
+        Self::write(
+            &mut io.0,
+            &json!({"jsonrpc": "2.0", "method": method, "params": params}),
+        )
+        .await
+    }
+
+    async fn close(&self) {
+        let _ = self.child.lock().await.kill().await;
+    }
+}
+
+/// Streamable HTTP: each request is a POST answered with JSON or an SSE stream
+struct HttpTransport {
+    client: reqwest::Client,
+    url: String,
+    headers: BTreeMap<String, String>,
+    session: Mutex<Option<String>>,
+    next_id: AtomicU64,
+    timeout: Duration,
+}
+
+impl HttpTransport {
+    fn new(url: &str, headers: &BTreeMap<String, String>, timeout: Duration) -> RhemaResult<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .build()
+            .map_err(|e| RhemaError::McpError(format!("Failed to build HTTP client: {}", e)))?;
+        Ok(Self {
+            client,
+            url: url.to_string(),
+            headers: headers
+                .iter()
+                .map(|(key, value)| (key.clone(), expand_env(value)))
+                .collect(),
+            session: Mutex::new(None),
+            next_id: AtomicU64::new(1),
+            timeout,
+        })
+    }
+
+    async fn post(&self, message: &Value) -> RhemaResult<reqwest::Response> {
+        let mut request = self
+            .client
+            .post(&self.url)
+            .header("Accept", "application/json, text/event-stream")
+            .header("MCP-Protocol-Version", MCP_VERSION)
+            .json(message);
+        for (key, value) in &self.headers {
+            request = request.header(key, value);
+        }
+        let session = self.session.lock().unwrap().clone();
+        if let Some(session) = session {
+            request = request.header("Mcp-Session-Id", session);
+        }
+        let response = request.send().await.map_err(|e| {
+            if e.is_timeout() {
+                timed_out(message["method"].as_str().unwrap_or_default(), self.timeout)
+            } else {
+                RhemaError::NetworkError(format!("MCP request to {} failed: {}", self.url, e))
+            }
+        })?;
+        if !response.status().is_success() {
+            return Err(RhemaError::McpError(format!(
+                "MCP server at {} answered {}",
+                self.url,
+                response.status()
+            )));
+        }
+        if let Some(session) = response
+            .headers()
+            .get("Mcp-Session-Id")
+            .and_then(|value| value.to_str().ok())
+        {
+            *self.session.lock().unwrap() = Some(session.to_string());
+        }
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl Transport for HttpTransport {
+    async fn request(&self, method: &str, params: Value) -> RhemaResult<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let response = self
+            .post(&json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params}))
+            .await?;
+        let is_stream = response
+            .headers()
+            .get("Content-Type")
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("text/event-stream"));
+        let body = response
+            .text()
+            .await
+            .map_err(|e| RhemaError::NetworkError(e.to_string()))?;
+
+        let messages: Vec<Value> = if is_stream {
+            body.lines()
+                .filter_map(|line| line.strip_prefix("data:"))
+                .filter_map(|data| serde_json::from_str(data.trim()).ok())
+                .collect()
+        } else {
+            vec![serde_json::from_str(&body)?]
+        };
+        messages
+            .into_iter()
+            .find(|message| message.get("id").and_then(Value::as_u64) == Some(id))
+            .map(rpc_result)
+            .unwrap_or_else(|| {
+                Err(RhemaError::McpError(format!(
+                    "MCP server did not answer {}",
+                    method
+                )))
+            })
+    }
+
+    async fn notify(&self, method: &str, params: Value) -> RhemaResult<()> {
+        self.post(&json!({"jsonrpc": "2.0", "method": method, "params": params}))
+            .await?;
+        Ok(())
+    }
+
+    async fn close(&self) {
+        let session = self.session.lock().unwrap().take();
+        if let Some(session) = session {
+            let _ = self
+                .client
+                .delete(&self.url)
+                .header("Mcp-Session-Id", session)
+                .send()
+                .await;
+        }
+    }
+}
+
+/// `result` of a JSON-RPC response, or its `error` as an McpError
+fn rpc_result(mut message: Value) -> RhemaResult<Value> {
+    if let Some(error) = message.get("error") {
+        return Err(RhemaError::McpError(format!(
+            "{} (code {})",
+            error["message"].as_str().unwrap_or("MCP request failed"),
+            error["code"]
+        )));
+    }
+    Ok(message
+        .get_mut("result")
+        .map(Value::take)
+        .unwrap_or(Value::Null))
+}
+
+/// We offer no client capabilities, so only ping is answered successfully
+fn server_request_reply(id: &Value, method: &str) -> Value {
+    if method == "ping" {
+        json!({"jsonrpc": "2.0", "id": id, "result": {}})
+    } else {
+        json!({"jsonrpc": "2.0", "id": id,
+               "error": {"code": -32601, "message": format!("Method not found: {}", method)}})
+    }
+}
+
+fn timed_out(method: &str, timeout: Duration) -> RhemaError {
+    RhemaError::McpError(format!(
+        "MCP request {} timed out after {}s",
+        method,
+        timeout.as_secs()
+    ))
+}
+
+/// Replace `${VAR}` with the environment variable's value
+fn expand_env(value: &str) -> String {
+    let mut expanded = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&std::env::var(&rest[start + 2..start + end]).unwrap_or_default());
+        rest = &rest[start + end + 1..];
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+struct CachedResponse {
+    value: Value,
+    expires_at: Instant,
+    /// Insertion order, for evicting the oldest entry
+    sequence: u64,
+}
+
+/// Responses keyed by `server\0request`, each with its own expiry
+struct ResponseCache {
+    entries: Mutex<HashMap<String, CachedResponse>>,
+    max_entries: usize,
+    sequence: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ResponseCache {
+    fn new(max_entries: usize) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            max_entries,
+            sequence: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<Value> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(key) {
+            Some(entry) if entry.expires_at > Instant::now() => {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                Some(entry.value.clone())
+            }
+            Some(_) => {
+                entries.remove(key);
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+            None => {
+                self.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    fn insert(&self, key: String, value: Value, ttl: Duration) {
+        if ttl.is_zero() || self.max_entries == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        let now = Instant::now();
+        entries.retain(|_, entry| entry.expires_at > now);
+        while entries.len() >= self.max_entries {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.sequence)
+                .map(|(key, _)| key.clone());
+            match oldest {
+                Some(oldest) => entries.remove(&oldest),
+                None => break,
+            };
+        }
+        entries.insert(
+            key,
+            CachedResponse {
+                value,
+                expires_at: now + ttl,
+                sequence: self.sequence.fetch_add(1, Ordering::Relaxed),
+            },
+        );
+    }
+
+    fn invalidate(&self, server: Option<&str>) {
+        let mut entries = self.entries.lock().unwrap();
+        match server {
+            Some(server) => {
+                let prefix = format!("{}\0", server);
+                entries.retain(|key, _| !key.starts_with(&prefix));
+            }
+            None => entries.clear(),
+        }
+    }
+
+    fn stats(&self) -> ResponseCacheStats {
+        ResponseCacheStats {
+            entries: self.entries.lock().unwrap().len(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_parses_transports_and_rejects_bad_names() {
+        let config: McpClientsConfig = serde_yaml::from_str(
+            r#"
+servers:
+  - name: jira
+    transport: http
+    url: https://mcp.example.com/mcp
+    headers:
+      Authorization: "Bearer ${JIRA_TOKEN}"
+    ttl_secs: 60
+  - name: docs
+    transport: stdio
+    command: docs-mcp
+    args: [--readonly]
+    cache_tools: [search]
+"#,
+        )
+        .unwrap();
+        assert!(config.validate().is_ok());
+        assert!(matches!(
+            config.servers[0].transport,
+            ExternalTransport::Http { .. }
+        ));
+        assert_eq!(config.ttl_for(&config.servers[0]), Duration::from_secs(60));
+        assert_eq!(config.ttl_for(&config.servers[1]), Duration::from_secs(300));
+
+        let mut bad = config.clone();
+        bad.servers[1].name = "docs.v2".to_string();
+        assert!(bad.validate().is_err());
+        bad.servers[1].name = "jira".to_string();
+        assert!(bad.validate().is_err());
+
+        assert_eq!(namespaced_tool("jira", "search"), "jira.search");
+        assert_eq!(
+            namespaced_resource("jira", "issue://ABC-1"),
+            "jira:issue://ABC-1"
+        );
+    }
+
+    #[test]
+    fn test_response_cache_expires_and_invalidates_per_server() {
+        let cache = ResponseCache::new(2);
+        cache.insert("jira\0a".to_string(), json!(1), Duration::from_secs(60));
+        cache.insert("docs\0b".to_string(), json!(2), Duration::ZERO);
+        assert_eq!(cache.get("jira\0a"), Some(json!(1)));
+        assert_eq!(cache.get("docs\0b"), None);
+
+        cache.insert("docs\0c".to_string(), json!(3), Duration::from_secs(60));
+        cache.insert("docs\0d".to_string(), json!(4), Duration::from_secs(60));
+        assert_eq!(cache.get("jira\0a"), None, "oldest entry is evicted");
+
+        cache.invalidate(Some("docs"));
+        assert_eq!(cache.stats().entries, 0);
+        assert_eq!(cache.stats().hits, 1);
+    }
+}
@@ -24,6 +24,10 @@ use tracing::{info, warn};
 
 use super::{AuthManager, CacheManager, ContextProvider, FileWatcher};
 use crate::mcp::McpConfig;
+use crate::query_guard::{QueryGuard, ANONYMOUS_IDENTITY, QUERY_TOOL_NAME};
+use crate::request_trace;
+use crate::resource_templates::{ResourceTemplate, ResourceTemplateRegistry};
+use crate::review_tool::{self, REVIEW_TOOL_NAME};
 
 /// Official MCP Protocol versions supported by Rhema
 pub const MCP_VERSION: &str = "2025-06-18";
@@ -76,9 +80,12 @@ pub struct OfficialRhemaMcpServer {
     cache_manager: Arc<CacheManager>,
     file_watcher: Arc<FileWatcher>,
     auth_manager: Arc<AuthManager>,
+    query_guard: Arc<QueryGuard>,
     resources: Arc<RwLock<HashMap<String, Resource>>>,
     tools: Arc<RwLock<HashMap<String, Tool>>>,
     prompts: Arc<RwLock<HashMap<String, Prompt>>>,
+    resource_templates: Arc<ResourceTemplateRegistry>,
+    template_invalidation: Arc<RwLock<Option<tokio::task::JoinHandle<()>>>>,
     start_time: std::time::Instant,
 }
 
@@ -89,24 +96,39 @@ impl OfficialRhemaMcpServer {
         cache_manager: Arc<CacheManager>,
         file_watcher: Arc<FileWatcher>,
         auth_manager: Arc<AuthManager>,
-        _config: &McpConfig,
+        config: &McpConfig,
     ) -> RhemaResult<Self> {
+        let query_guard =
+            Arc::new(QueryGuard::new(config.query_guard.clone()).with_audit(auth_manager.clone()));
         let resources = Arc::new(RwLock::new(HashMap::new()));
         let tools = Arc::new(RwLock::new(HashMap::new()));
         let prompts = Arc::new(RwLock::new(HashMap::new()));
+        let resource_templates = Arc::new(ResourceTemplateRegistry::new(
+            std::time::Duration::from_secs(config.cache.ttl_seconds),
+        ));
 
         Ok(Self {
             context_provider,
             cache_manager,
             file_watcher,
             auth_manager,
+            query_guard,
             resources,
             tools,
             prompts,
+            resource_templates,
+            template_invalidation: Arc::new(RwLock::new(None)),
             start_time: std::time::Instant::now(),
         })
     }
 
+    /// Share a query guard with other transports so rate limits apply per
+    /// client rather than per transport
+    pub fn with_query_guard(mut self, query_guard: Arc<QueryGuard>) -> Self {
+        self.query_guard = query_guard;
+        self
+    }
+
     /// Start the MCP server
     pub async fn start(&mut self, _config: &McpConfig) -> RhemaResult<()> {
         info!("Starting Rhema MCP server with official protocol");
@@ -116,25 +138,90 @@ impl OfficialRhemaMcpServer {
         self.initialize_tools().await?;
         self.initialize_prompts().await?;
 
+        // Cached template views are stale once a context file changes
+        let mut events = self.file_watcher.subscribe().await;
+        let templates = self.resource_templates.clone();
+        *self.template_invalidation.write().await = Some(tokio::spawn(async move {
+            while events.recv().await.is_some() {
+                templates.invalidate().await;
+            }
+        }));
+
         info!("Rhema MCP server started successfully");
         Ok(())
     }
 
     /// Stop the MCP server
     pub async fn stop(&mut self) -> RhemaResult<()> {
+        if let Some(task) = self.template_invalidation.write().await.take() {
+            task.abort();
+        }
         info!("Rhema MCP server stopped");
         Ok(())
     }
 
-    /// Handle tool calls
+    /// Handle tool calls from an unauthenticated client
     pub async fn handle_tool_call(
         &self,
         name: String,
         arguments: Value,
+    ) -> RhemaResult<ToolResult> {
+        self.handle_tool_call_as(ANONYMOUS_IDENTITY, name, arguments)
+            .await
+    }
+
+    /// Handle tool calls on behalf of an authenticated identity
+    pub async fn handle_tool_call_as(
+        &self,
+        identity: &str,
+        name: String,
+        arguments: Value,
+    ) -> RhemaResult<ToolResult> {
+        let request_id =
+            request_trace::current_request_id().unwrap_or_else(request_trace::new_request_id);
+        let target = name.clone();
+        request_trace::run_request(
+            request_id,
+            "mcp",
+            "tools/call",
+            &target,
+            self.dispatch_tool_call(identity, name, arguments),
+        )
+        .await
+    }
+
+    async fn dispatch_tool_call(
+        &self,
+        identity: &str,
+        name: String,
+        arguments: Value,
     ) -> RhemaResult<ToolResult> {
         info!("Executing tool: {}", name);
 
         match name.as_str() {
+            QUERY_TOOL_NAME => {
+                let query = arguments["query"].as_str().ok_or_else(|| {
+                    rhema_core::RhemaError::InvalidInput("Missing query parameter".to_string())
+                })?;
+                let max_rows = arguments["max_rows"].as_u64().map(|n| n as usize);
+
+                let result = self
+                    .query_guard
+                    .execute(&self.context_provider, identity, query, max_rows)
+                    .await?;
+
+                Ok(ToolResult::Text {
+                    text: serde_json::to_string(&result)?,
+                })
+            }
+            REVIEW_TOOL_NAME => {
+                let result =
+                    review_tool::execute(self.context_provider.repo_root(), identity, &arguments)?;
+
+                Ok(ToolResult::Text {
+                    text: serde_json::to_string(&result)?,
+                })
+            }
             "rhema_query" => {
                 let query = arguments["query"].as_str().ok_or_else(|| {
                     rhema_core::RhemaError::InvalidInput("Missing query parameter".to_string())
@@ -221,6 +308,25 @@ impl OfficialRhemaMcpServer {
         resources_guard.values().cloned().collect()
     }
 
+    /// Get all resource templates, as listed by `resources/templates/list`
+    pub fn get_resource_templates(&self) -> Vec<ResourceTemplate> {
+        self.resource_templates.templates()
+    }
+
+    /// Read a resource, expanding resource templates such as
+    /// `rhema://todos?priority=high`
+    pub async fn read_resource(&self, uri: &str) -> RhemaResult<Value> {
+        if let Some(resource) = self.resources.read().await.get(uri) {
+            return Ok(resource.content.clone());
+        }
+        self.resource_templates
+            .read(uri, &self.context_provider)
+            .await?
+            .ok_or_else(|| {
+                rhema_core::RhemaError::InvalidInput(format!("Unknown resource: {}", uri))
+            })
+    }
+
     /// Get all prompts
     pub async fn get_prompts(&self) -> Vec<Prompt> {
         let prompts_guard = self.prompts.read().await;
@@ -272,6 +378,40 @@ impl OfficialRhemaMcpServer {
     async fn initialize_tools(&self) -> RhemaResult<()> {
         let mut tools_guard = self.tools.write().await;
 
+        // Add guarded query tool for AI clients
+        let limits = self.query_guard.config();
+        tools_guard.insert(
+            QUERY_TOOL_NAME.to_string(),
+            Tool {
+                name: QUERY_TOOL_NAME.to_string(),
+                description: Some(format!(
+                    "Run a read-only CQL query against Rhema context. Returns at most {} rows \
+                     (about {} tokens); larger results are truncated and summarized. \
+                     Limited to {} queries per minute.",
+                    limits.max_rows, limits.max_tokens, limits.queries_per_minute
+                )),
+                input_schema: QueryGuard::input_schema(),
+                output_schema: None,
+                title: Some("Query Rhema context".to_string()),
+            },
+        );
+
+        // Add review tool for human-in-the-loop clients
+        tools_guard.insert(
+            REVIEW_TOOL_NAME.to_string(),
+            Tool {
+                name: REVIEW_TOOL_NAME.to_string(),
+                description: Some(
+                    "List context entries written by agents that await review, and approve, \
+                     reject or edit them. Pending entries are hidden from queries until approved."
+                        .to_string(),
+                ),
+                input_schema: review_tool::input_schema(),
+                output_schema: None,
+                title: Some("Review agent-written context".to_string()),
+            },
+        );
+
         // Add Rhema query tool
         tools_guard.insert(
             "rhema_query".to_string(),
@@ -441,6 +581,7 @@ impl OfficialRhemaMcpServer {
             error_count: 0,   // TODO: Track error count
             error_rate: 0.0,
             restart_count: 0,
+            dependencies: Vec::new(),
         }
     }
 
@@ -0,0 +1,586 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Guardrails for CQL queries issued by AI clients.
+//!
+//! The `rhema.query` tool runs every statement through [`QueryGuard`] before it
+//! reaches the query engine:
+//!
+//! 1. Only single, read-only statements are accepted; `INSERT`, `UPDATE` and
+//!    `DELETE` are rejected.
+//! 2. Each client identity gets a sliding one-minute query budget.
+//! 3. Results are capped by row count and by estimated tokens. When a result
+//!    exceeds either cap, the rows that fit are returned together with a
+//!    structural summary of the full result.
+//! 4. Queries are linted against the loaded scopes. Lint errors, such as
+//!    deprecated syntax that would not run as written, reject the query with
+//!    the suggested rewrite; warnings are returned alongside the rows.
+//! 5. Every query, accepted or not, is written to the audit log with its text
+//!    and the requesting identity.
+
+use rhema_core::scope::Scope;
+use rhema_core::{RhemaError, RhemaResult};
+use rhema_query::lint::{LintFinding, LintSeverity, QueryLinter};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::auth::{AuditEventType, AuditResult, AuthManager};
+use crate::context::ContextProvider;
+use crate::request_trace;
+
+/// Name under which the guarded query tool is exposed to MCP clients
+pub const QUERY_TOOL_NAME: &str = "rhema.query";
+
+/// Identity recorded for callers that did not authenticate
+pub const ANONYMOUS_IDENTITY: &str = "anonymous";
+
+/// Rough characters-per-token ratio used to estimate result size
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Sliding window for per-client rate limits
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Number of distinct values listed per field in a result summary
+const SUMMARY_TOP_VALUES: usize = 5;
+
+/// Guardrail settings for the `rhema.query` tool
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct QueryGuardConfig {
+    /// Queries each client may run per minute
+    pub queries_per_minute: u32,
+
+    /// Maximum rows returned to the client
+    pub max_rows: usize,
+
+    /// Maximum estimated tokens in the returned rows
+    pub max_tokens: usize,
+
+    /// Maximum length of the query text in bytes
+    pub max_query_length: usize,
+}
+
+impl Default for QueryGuardConfig {
+    fn default() -> Self {
+        Self {
+            queries_per_minute: 30,
+            max_rows: 200,
+            max_tokens: 8000,
+            max_query_length: 4096,
+        }
+    }
+}
+
+/// Summary of a result that was too large to return in full
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ResultSummary {
+    pub total_rows: usize,
+    pub estimated_tokens: usize,
+    /// Row counts per scope, when rows carry a `scope` field
+    pub rows_per_scope: BTreeMap<String, usize>,
+    /// How many rows carry each top-level field
+    pub field_counts: BTreeMap<String, usize>,
+    /// Most common values of short string fields such as `status` or `priority`
+    pub top_values: BTreeMap<String, Vec<(String, usize)>>,
+}
+
+/// Query result after guardrails have been applied
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GuardedQueryResult {
+    pub query: String,
+    pub rows: Vec<Value>,
+    pub total_rows: usize,
+    pub estimated_tokens: usize,
+    pub truncated: bool,
+    /// Present when `truncated` is set
+    pub summary: Option<ResultSummary>,
+    pub execution_time_ms: u128,
+    /// Lint warnings about the query; the rows are returned regardless
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub lint: Vec<LintFinding>,
+}
+
+/// Enforces read-only access, rate limits and result caps for AI queries
+pub struct QueryGuard {
+    config: QueryGuardConfig,
+    auth_manager: Option<Arc<AuthManager>>,
+    windows: Mutex<HashMap<String, VecDeque<Instant>>>,
+}
+
+impl QueryGuard {
+    pub fn new(config: QueryGuardConfig) -> Self {
+        Self {
+            config,
+            auth_manager: None,
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Write audit entries through the auth manager's audit logger
+    pub fn with_audit(mut self, auth_manager: Arc<AuthManager>) -> Self {
+        self.auth_manager = Some(auth_manager);
+        self
+    }
+
+    pub fn config(&self) -> &QueryGuardConfig {
+        &self.config
+    }
+
+    /// JSON Schema of the tool's arguments
+    pub fn input_schema() -> Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "Read-only CQL query, e.g. \"todos WHERE status='pending'\""
+                },
+                "max_rows": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Return at most this many rows (cannot exceed the server limit)"
+                }
+            },
+            "required": ["query"]
+        })
+    }
+
+    /// Reject anything other than a single read-only statement
+    pub fn check_statement(&self, query: &str) -> RhemaResult<()> {
+        let trimmed = query.trim();
+        if trimmed.is_empty() {
+            return Err(RhemaError::InvalidQuery("Query is empty".to_string()));
+        }
+        if trimmed.len() > self.config.max_query_length {
+            return Err(RhemaError::InvalidQuery(format!(
+                "Query is {} bytes, limit is {}",
+                trimmed.len(),
+                self.config.max_query_length
+            )));
+        }
+        let statement = trimmed.trim_end_matches(';');
+        if statement.contains(';') {
+            return Err(RhemaError::InvalidQuery(
+                "Only a single statement is allowed".to_string(),
+            ));
+        }
+        if rhema_query::is_mutation(statement) {
+            return Err(RhemaError::AuthorizationError(
+                "rhema.query is read-only; INSERT, UPDATE and DELETE are not allowed".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Record a query against the client's budget, failing once it is spent
+    pub fn check_rate(&self, identity: &str) -> RhemaResult<()> {
+        self.check_rate_at(identity, Instant::now())
+    }
+
+    fn check_rate_at(&self, identity: &str, now: Instant) -> RhemaResult<()> {
+        let mut windows = self.windows.lock().unwrap();
+        let window = windows.entry(identity.to_string()).or_default();
+        while window
+            .front()
+            .is_some_and(|&time| now.duration_since(time) >= RATE_WINDOW)
+        {
+            window.pop_front();
+        }
+
+        if window.len() >= self.config.queries_per_minute as usize {
+            let retry_after = window
+                .front()
+                .map(|&oldest| RATE_WINDOW.saturating_sub(now.duration_since(oldest)))
+                .unwrap_or(RATE_WINDOW);
+            return Err(RhemaError::RateLimitError(format!(
+                "Query rate limit of {} per minute exceeded, retry in {}s",
+                self.config.queries_per_minute,
+                retry_after.as_secs().max(1)
+            )));
+        }
+
+        window.push_back(now);
+        Ok(())
+    }
+
+    /// Apply row and token caps to a query result.
+    ///
+    /// `max_rows` lets a client ask for fewer rows than the configured limit,
+    /// never more.
+    pub fn shape_result(
+        &self,
+        query: &str,
+        result: Value,
+        max_rows: Option<usize>,
+    ) -> GuardedQueryResult {
+        let rows = match result {
+            Value::Array(rows) => rows,
+            Value::Null => Vec::new(),
+            other => vec![other],
+        };
+        let total_rows = rows.len();
+        let total_tokens = estimate_tokens(&Value::Array(rows.clone()));
+        let row_cap = max_rows
+            .unwrap_or(self.config.max_rows)
+            .min(self.config.max_rows);
+
+        let mut kept = Vec::new();
+        let mut kept_tokens = 0;
+        for row in &rows {
+            if kept.len() >= row_cap {
+                break;
+            }
+            let row_tokens = estimate_tokens(row);
+            if kept_tokens + row_tokens > self.config.max_tokens {
+                break;
+            }
+            kept_tokens += row_tokens;
+            kept.push(row.clone());
+        }
+
+        let truncated = kept.len() < total_rows;
+        let summary = truncated.then(|| summarize_rows(&rows, total_tokens));
+
+        GuardedQueryResult {
+            query: query.to_string(),
+            rows: kept,
+            total_rows,
+            estimated_tokens: kept_tokens,
+            truncated,
+            summary,
+            execution_time_ms: 0,
+            lint: Vec::new(),
+        }
+    }
+
+    /// Lint `query` against `scopes`, failing on findings that mean the query
+    /// would not run as written. Results larger than the row cap count as
+    /// large, since they would be truncated.
+    pub fn check_lint(
+        &self,
+        query: &str,
+        scopes: &[Scope],
+        repo_root: &Path,
+    ) -> RhemaResult<Vec<LintFinding>> {
+        let statement = query.trim().trim_end_matches(';');
+        let report = QueryLinter::new()
+            .with_large_result_rows(self.config.max_rows)
+            .lint(statement, scopes, repo_root);
+        if !report.has_errors() {
+            return Ok(report.findings);
+        }
+
+        let errors: Vec<String> = report
+            .findings
+            .iter()
+            .filter(|f| f.severity == LintSeverity::Error)
+            .map(|f| match &f.suggestion {
+                Some(suggestion) => format!("{} (try: {})", f.message, suggestion),
+                None => f.message.clone(),
+            })
+            .collect();
+        Err(RhemaError::InvalidQuery(errors.join("; ")))
+    }
+
+    /// Run a query on behalf of `identity` with every guardrail applied
+    pub async fn execute(
+        &self,
+        context_provider: &ContextProvider,
+        identity: &str,
+        query: &str,
+        max_rows: Option<usize>,
+    ) -> RhemaResult<GuardedQueryResult> {
+        let start = Instant::now();
+        let scopes = context_provider.get_scopes().await?;
+        let mut lint = Vec::new();
+        let checked = self
+            .check_statement(query)
+            .and_then(|_| self.check_rate(identity))
+            .and_then(|_| {
+                lint = self.check_lint(query, &scopes, context_provider.repo_root())?;
+                Ok(())
+            });
+        if let Err(e) = checked {
+            let result = match e {
+                RhemaError::RateLimitError(_) => AuditResult::RateLimited,
+                _ => AuditResult::Denied,
+            };
+            self.audit(identity, query, result, None, Some(&e)).await;
+            return Err(e);
+        }
+
+        match context_provider.execute_query(query).await {
+            Ok(value) => {
+                let mut shaped = self.shape_result(query, value, max_rows);
+                shaped.execution_time_ms = start.elapsed().as_millis();
+                shaped.lint = lint;
+                self.audit(identity, query, AuditResult::Success, Some(&shaped), None)
+                    .await;
+                Ok(shaped)
+            }
+            Err(e) => {
+                self.audit(identity, query, AuditResult::Failure, None, Some(&e))
+                    .await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn audit(
+        &self,
+        identity: &str,
+        query: &str,
+        result: AuditResult,
+        shaped: Option<&GuardedQueryResult>,
+        error: Option<&RhemaError>,
+    ) {
+        let Some(auth_manager) = &self.auth_manager else {
+            return;
+        };
+
+        let mut details = HashMap::new();
+        details.insert("query".to_string(), Value::String(query.to_string()));
+        if let Some(request_id) = request_trace::current_request_id() {
+            details.insert("request_id".to_string(), Value::String(request_id));
+        }
+        if let Some(shaped) = shaped {
+            details.insert("rows_returned".to_string(), shaped.rows.len().into());
+            details.insert("total_rows".to_string(), shaped.total_rows.into());
+            details.insert("truncated".to_string(), shaped.truncated.into());
+        }
+        if let Some(error) = error {
+            details.insert("error".to_string(), Value::String(error.to_string()));
+        }
+
+        auth_manager
+            .audit_logger()
+            .log(
+                AuditEventType::QueryExecution,
+                QUERY_TOOL_NAME,
+                result,
+                Some(identity.to_string()),
+                None,
+                None,
+                Some(QUERY_TOOL_NAME.to_string()),
+                None,
+                details,
+            )
+            .await;
+    }
+}
+
+/// Estimate the tokens a value occupies once serialized
+pub fn estimate_tokens(value: &Value) -> usize {
+    let chars = serde_json::to_string(value)
+        .map(|s| s.chars().count())
+        .unwrap_or(0);
+    chars.div_ceil(CHARS_PER_TOKEN)
+}
+
+/// Build a structural summary of the full row set
+fn summarize_rows(rows: &[Value], estimated_tokens: usize) -> ResultSummary {
+    let mut rows_per_scope = BTreeMap::new();
+    let mut field_counts = BTreeMap::new();
+    let mut value_counts: BTreeMap<String, HashMap<String, usize>> = BTreeMap::new();
+
+    for row in rows {
+        let Some(object) = row.as_object() else {
+            continue;
+        };
+        if let Some(scope) = object.get("scope").and_then(Value::as_str) {
+            *rows_per_scope.entry(scope.to_string()).or_insert(0) += 1;
+        }
+        // Query results with several scopes wrap each entry in `data`
+        let fields = object
+            .get("data")
+            .and_then(Value::as_object)
+            .unwrap_or(object);
+        for (field, value) in fields {
+            *field_counts.entry(field.clone()).or_insert(0) += 1;
+            if let Some(text) = value.as_str().filter(|s| s.len() <= 32) {
+                *value_counts
+                    .entry(field.clone())
+                    .or_default()
+                    .entry(text.to_string())
+                    .or_insert(0) += 1;
+            }
+        }
+    }
+
+    // Only fields that repeat values are worth listing, e.g. status but not id
+    let top_values = value_counts
+        .into_iter()
+        .filter(|(_, counts)| counts.len() <= rows.len() / 2)
+        .map(|(field, counts)| {
+            let mut counts: Vec<_> = counts.into_iter().collect();
+            counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            counts.truncate(SUMMARY_TOP_VALUES);
+            (field, counts)
+        })
+        .collect();
+
+    ResultSummary {
+        total_rows: rows.len(),
+        estimated_tokens,
+        rows_per_scope,
+        field_counts,
+        top_values,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn guard(config: QueryGuardConfig) -> QueryGuard {
+        QueryGuard::new(config)
+    }
+
+    #[test]
+    fn test_rejects_mutations_and_multiple_statements() {
+        let guard = guard(QueryGuardConfig::default());
+        assert!(guard
+            .check_statement("todos WHERE status='pending'")
+            .is_ok());
+        assert!(guard.check_statement("todos;").is_ok());
+        assert!(matches!(
+            guard.check_statement("DELETE FROM todos WHERE id='1'"),
+            Err(RhemaError::AuthorizationError(_))
+        ));
+        assert!(guard
+            .check_statement("  update todos SET status='done'")
+            .is_err());
+        assert!(guard.check_statement("todos; DELETE FROM todos").is_err());
+        assert!(guard.check_statement("   ").is_err());
+        assert!(guard.check_statement(&"x".repeat(5000)).is_err());
+    }
+
+    #[test]
+    fn test_rate_limit_is_per_client() {
+        let guard = guard(QueryGuardConfig {
+            queries_per_minute: 2,
+            ..Default::default()
+        });
+        let start = Instant::now();
+        assert!(guard.check_rate_at("agent-a", start).is_ok());
+        assert!(guard.check_rate_at("agent-a", start).is_ok());
+        assert!(matches!(
+            guard.check_rate_at("agent-a", start),
+            Err(RhemaError::RateLimitError(_))
+        ));
+        assert!(guard.check_rate_at("agent-b", start).is_ok());
+        // The window slides
+        assert!(guard
+            .check_rate_at("agent-a", start + Duration::from_secs(61))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_caps_rows_and_summarizes() {
+        let guard = guard(QueryGuardConfig {
+            max_rows: 3,
+            ..Default::default()
+        });
+        let rows: Vec<Value> = (0..10)
+            .map(|i| {
+                json!({
+                    "scope": if i % 2 == 0 { "api" } else { "web" },
+                    "data": {"id": format!("todo-{}", i), "status": "pending"}
+                })
+            })
+            .collect();
+
+        let shaped = guard.shape_result("todos", Value::Array(rows), None);
+        assert_eq!(shaped.rows.len(), 3);
+        assert_eq!(shaped.total_rows, 10);
+        assert!(shaped.truncated);
+        let summary = shaped.summary.unwrap();
+        assert_eq!(summary.rows_per_scope["api"], 5);
+        assert_eq!(summary.field_counts["id"], 10);
+        assert_eq!(
+            summary.top_values["status"],
+            vec![("pending".to_string(), 10)]
+        );
+        assert!(!summary.top_values.contains_key("id"));
+
+        // Clients may ask for fewer rows, not more
+        let shaped = guard.shape_result("todos", json!([1, 2, 3, 4]), Some(1));
+        assert_eq!(shaped.rows, vec![json!(1)]);
+        let shaped = guard.shape_result("todos", json!([1, 2, 3, 4]), Some(50));
+        assert_eq!(shaped.rows.len(), 3);
+    }
+
+    #[test]
+    fn test_caps_tokens() {
+        let guard = guard(QueryGuardConfig {
+            max_tokens: 30,
+            ..Default::default()
+        });
+        let row = json!({"title": "a".repeat(200)});
+        let shaped = guard.shape_result("knowledge", json!([row.clone(), row]), None);
+        assert!(shaped.rows.is_empty());
+        assert!(shaped.truncated);
+        assert!(shaped.summary.unwrap().estimated_tokens > 30);
+
+        let shaped = guard.shape_result("knowledge", json!({"title": "short"}), None);
+        assert_eq!(shaped.total_rows, 1);
+        assert!(!shaped.truncated);
+        assert!(shaped.summary.is_none());
+    }
+
+    #[test]
+    fn test_lint_errors_reject_and_warnings_pass() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let rhema = temp.path().join(".rhema");
+        std::fs::create_dir_all(&rhema).unwrap();
+        std::fs::write(
+            rhema.join("rhema.yaml"),
+            "name: api\nscope_type: service\nversion: \"1.0.0\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            rhema.join("todos.yaml"),
+            "todos:\n  - id: a\n    status: pending\n  - id: b\n    status: done\n",
+        )
+        .unwrap();
+        let scopes = vec![Scope::new(rhema).unwrap()];
+        let guard = guard(QueryGuardConfig {
+            max_rows: 2,
+            ..Default::default()
+        });
+
+        let err = guard
+            .check_lint("todos where status = 'pending'", &scopes, temp.path())
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("try: todos WHERE status = 'pending'"));
+
+        let findings = guard.check_lint("todos;", &scopes, temp.path()).unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, LintSeverity::Warning);
+        assert!(guard
+            .check_lint("todos WHERE status = 'pending'", &scopes, temp.path())
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,426 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Request-level correlation IDs for the MCP daemon.
+//!
+//! Every MCP/HTTP request gets a correlation ID, either taken from the
+//! `x-request-id` header or generated. The ID lives in a task-local for the
+//! duration of the request, so subsystems (context provider, cache, query
+//! engine) can attach events to the request without threading it through every
+//! signature. Completed requests are kept in a bounded in-memory store that
+//! backs `GET /traces/:id` and `rhema daemon trace <id>`.
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{header::CONTENT_LENGTH, header::CONTENT_TYPE, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::future::Future;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tracing::Instrument;
+
+/// Header carrying the correlation ID on requests and responses
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Number of completed requests kept for lookup
+pub const DEFAULT_TRACE_CAPACITY: usize = 1000;
+
+/// Largest error body rewritten to carry the request ID
+const MAX_ERROR_BODY_BYTES: usize = 64 * 1024;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// Generate a new correlation ID
+pub fn new_request_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
+/// Correlation ID of the request being handled by the current task
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
+/// Run `future` as part of the request `request_id`
+pub async fn with_request_id<F: Future>(request_id: String, future: F) -> F::Output {
+    REQUEST_ID.scope(request_id, future).await
+}
+
+/// Accept client-supplied IDs only if they are short and printable
+fn is_valid_request_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= 128
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// One step a request took through a subsystem
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceEvent {
+    /// Milliseconds since the request started
+    pub offset_ms: u64,
+    pub subsystem: String,
+    pub operation: String,
+    pub detail: String,
+    pub duration_ms: u64,
+    pub outcome: String,
+}
+
+/// Everything recorded for one request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestTrace {
+    pub request_id: String,
+    /// `http`, `websocket` or `mcp`
+    pub transport: String,
+    pub method: String,
+    pub target: String,
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: Option<u64>,
+    pub status: Option<String>,
+    pub events: Vec<TraceEvent>,
+}
+
+struct ActiveTrace {
+    started: Instant,
+    trace: RequestTrace,
+}
+
+/// Bounded store of request traces, oldest evicted first
+pub struct RequestTraceStore {
+    traces: DashMap<String, ActiveTrace>,
+    order: Mutex<VecDeque<String>>,
+    capacity: usize,
+}
+
+impl RequestTraceStore {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            traces: DashMap::new(),
+            order: Mutex::new(VecDeque::new()),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Start tracing a request
+    pub fn begin(&self, request_id: &str, transport: &str, method: &str, target: &str) {
+        let trace = RequestTrace {
+            request_id: request_id.to_string(),
+            transport: transport.to_string(),
+            method: method.to_string(),
+            target: target.to_string(),
+            started_at: Utc::now(),
+            duration_ms: None,
+            status: None,
+            events: Vec::new(),
+        };
+        let replaced = self
+            .traces
+            .insert(
+                request_id.to_string(),
+                ActiveTrace {
+                    started: Instant::now(),
+                    trace,
+                },
+            )
+            .is_some();
+
+        let mut order = self.order.lock().unwrap_or_else(|e| e.into_inner());
+        if replaced {
+            order.retain(|id| id != request_id);
+        }
+        order.push_back(request_id.to_string());
+        while order.len() > self.capacity {
+            if let Some(evicted) = order.pop_front() {
+                self.traces.remove(&evicted);
+            }
+        }
+    }
+
+    /// Record a subsystem event for a request
+    pub fn record(
+        &self,
+        request_id: &str,
+        subsystem: &str,
+        operation: &str,
+        detail: &str,
+        duration: Duration,
+        outcome: &str,
+    ) {
+        if let Some(mut active) = self.traces.get_mut(request_id) {
+            let offset = active.started.elapsed().saturating_sub(duration);
+            active.trace.events.push(TraceEvent {
+                offset_ms: offset.as_millis() as u64,
+                subsystem: subsystem.to_string(),
+                operation: operation.to_string(),
+                detail: detail.to_string(),
+                duration_ms: duration.as_millis() as u64,
+                outcome: outcome.to_string(),
+            });
+        }
+    }
+
+    /// Mark a request as finished
+    pub fn finish(&self, request_id: &str, status: impl Into<String>) {
+        if let Some(mut active) = self.traces.get_mut(request_id) {
+            active.trace.duration_ms = Some(active.started.elapsed().as_millis() as u64);
+            active.trace.status = Some(status.into());
+        }
+    }
+
+    /// Look up a request by its full ID or a unique prefix of it
+    pub fn get(&self, request_id: &str) -> Option<RequestTrace> {
+        if let Some(active) = self.traces.get(request_id) {
+            return Some(active.trace.clone());
+        }
+        let mut matches = self
+            .traces
+            .iter()
+            .filter(|entry| entry.key().starts_with(request_id));
+        match (matches.next(), matches.next()) {
+            (Some(only), None) => Some(only.trace.clone()),
+            _ => None,
+        }
+    }
+
+    /// Most recent requests, newest first
+    pub fn recent(&self, limit: usize) -> Vec<RequestTrace> {
+        let order = self.order.lock().unwrap_or_else(|e| e.into_inner());
+        order
+            .iter()
+            .rev()
+            .filter_map(|id| self.traces.get(id).map(|active| active.trace.clone()))
+            .take(limit)
+            .collect()
+    }
+}
+
+/// Process-wide trace store shared by the HTTP server and the subsystems
+pub fn trace_store() -> &'static RequestTraceStore {
+    static STORE: OnceLock<RequestTraceStore> = OnceLock::new();
+    STORE.get_or_init(|| RequestTraceStore::new(DEFAULT_TRACE_CAPACITY))
+}
+
+/// Record a subsystem event against the current request, if there is one
+pub fn record(subsystem: &str, operation: &str, detail: &str, duration: Duration, outcome: &str) {
+    if let Some(request_id) = current_request_id() {
+        tracing::debug!(
+            request_id = %request_id,
+            subsystem,
+            operation,
+            outcome,
+            "{} {} took {:?}",
+            subsystem,
+            operation,
+            duration
+        );
+        trace_store().record(&request_id, subsystem, operation, detail, duration, outcome);
+    }
+}
+
+/// Run a subsystem operation in a span tagged with the current request ID and
+/// record its duration and outcome
+pub async fn traced<T, E, F>(
+    subsystem: &str,
+    operation: &str,
+    detail: &str,
+    future: F,
+) -> Result<T, E>
+where
+    E: Display,
+    F: Future<Output = Result<T, E>>,
+{
+    let span = tracing::debug_span!(
+        "subsystem",
+        request_id = %current_request_id().unwrap_or_default(),
+        subsystem,
+        operation
+    );
+    let started = Instant::now();
+    let result = future.instrument(span).await;
+    let outcome = match &result {
+        Ok(_) => "ok".to_string(),
+        Err(e) => format!("error: {}", e),
+    };
+    record(subsystem, operation, detail, started.elapsed(), &outcome);
+    result
+}
+
+/// Run a whole request under a correlation ID: opens the trace, the request
+/// span and the task-local scope, and closes the trace when done
+pub async fn run_request<T, E, F>(
+    request_id: String,
+    transport: &str,
+    method: &str,
+    target: &str,
+    future: F,
+) -> Result<T, E>
+where
+    E: Display,
+    F: Future<Output = Result<T, E>>,
+{
+    trace_store().begin(&request_id, transport, method, target);
+    let span = tracing::info_span!(
+        "mcp_request",
+        request_id = %request_id,
+        transport,
+        method,
+        target
+    );
+    let result = with_request_id(request_id.clone(), future)
+        .instrument(span)
+        .await;
+    let status = match &result {
+        Ok(_) => "ok".to_string(),
+        Err(e) => format!("error: {}", e),
+    };
+    trace_store().finish(&request_id, status);
+    result
+}
+
+/// Axum middleware assigning a correlation ID to every HTTP request.
+///
+/// The ID is echoed in the `x-request-id` response header and added to error
+/// bodies, so a client can quote it when reporting a failure.
+pub async fn http_middleware(request: Request, next: Next) -> Response {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| is_valid_request_id(id))
+        .map(str::to_string)
+        .unwrap_or_else(new_request_id);
+    let method = request.method().to_string();
+    let target = request.uri().path().to_string();
+
+    trace_store().begin(&request_id, "http", &method, &target);
+    let span = tracing::info_span!(
+        "mcp_request",
+        request_id = %request_id,
+        transport = "http",
+        method = %method,
+        target = %target
+    );
+    let response = with_request_id(request_id.clone(), next.run(request))
+        .instrument(span)
+        .await;
+    trace_store().finish(&request_id, response.status().as_u16().to_string());
+
+    let mut response = if response.status().is_client_error() || response.status().is_server_error()
+    {
+        attach_request_id_to_error(response, &request_id).await
+    } else {
+        response
+    };
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+/// Turn an error body into JSON carrying the request ID
+async fn attach_request_id_to_error(response: Response, request_id: &str) -> Response {
+    let (mut parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, MAX_ERROR_BODY_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(_) => return Response::from_parts(parts, Body::empty()),
+    };
+
+    let payload = match serde_json::from_slice::<serde_json::Value>(&bytes) {
+        Ok(serde_json::Value::Object(mut object)) => {
+            object.insert("request_id".to_string(), request_id.into());
+            serde_json::Value::Object(object)
+        }
+        _ => serde_json::json!({
+            "error": String::from_utf8_lossy(&bytes),
+            "request_id": request_id,
+        }),
+    };
+
+    parts
+        .headers
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    parts.headers.remove(CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(payload.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_subsystem_events_attach_to_current_request() {
+        let request_id = new_request_id();
+        let result: Result<u32, String> = run_request(
+            request_id.clone(),
+            "mcp",
+            "tools/call",
+            "rhema_query",
+            async {
+                assert_eq!(current_request_id().as_deref(), Some(request_id.as_str()));
+                let cached: Result<(), String> =
+                    traced("cache", "get", "scopes", async { Ok(()) }).await;
+                cached?;
+                traced("query_engine", "execute_query", "SELECT", async {
+                    Err::<u32, _>("parse error".to_string())
+                })
+                .await
+            },
+        )
+        .await;
+        assert!(result.is_err());
+
+        let trace = trace_store().get(&request_id[..12]).unwrap();
+        assert_eq!(trace.request_id, request_id);
+        assert_eq!(trace.status.as_deref(), Some("error: parse error"));
+        let steps: Vec<_> = trace
+            .events
+            .iter()
+            .map(|e| (e.subsystem.as_str(), e.outcome.as_str()))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![("cache", "ok"), ("query_engine", "error: parse error")]
+        );
+
+        // Outside a request nothing is recorded
+        assert!(current_request_id().is_none());
+        record("cache", "get", "orphan", Duration::ZERO, "ok");
+    }
+
+    #[test]
+    fn test_store_evicts_oldest_requests() {
+        let store = RequestTraceStore::new(2);
+        for id in ["a1", "b2", "c3"] {
+            store.begin(id, "http", "GET", "/scopes");
+            store.finish(id, "200");
+        }
+
+        assert!(store.get("a1").is_none());
+        let recent: Vec<_> = store.recent(10).into_iter().map(|t| t.request_id).collect();
+        assert_eq!(recent, vec!["c3".to_string(), "b2".to_string()]);
+        assert!(!is_valid_request_id("bad id\n"));
+    }
+}
@@ -0,0 +1,304 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Content-hash revisions for context resources.
+//!
+//! Every resource served by the context provider gets a strong ETag derived
+//! from its serialized content and a `Last-Modified` timestamp recording when
+//! that content was first observed. The revision log also hands out opaque
+//! change tokens so that polling clients can ask for only the resources that
+//! changed since their last sync.
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// Format used for `Last-Modified` / `If-Modified-Since` headers (RFC 7231 IMF-fixdate)
+const HTTP_DATE_FORMAT: &str = "%a, %d %b %Y %H:%M:%S GMT";
+
+/// Compute a strong ETag for a resource from its serialized content
+pub fn resource_etag(resource: &Value) -> String {
+    let bytes = serde_json::to_vec(resource).unwrap_or_default();
+    let digest = Sha256::digest(&bytes);
+    format!("\"{}\"", hex::encode(&digest[..16]))
+}
+
+/// Format a timestamp as an HTTP date
+pub fn http_date(timestamp: &DateTime<Utc>) -> String {
+    timestamp.format(HTTP_DATE_FORMAT).to_string()
+}
+
+/// Parse an HTTP date, returning `None` for anything malformed
+pub fn parse_http_date(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(value.trim())
+        .ok()
+        .map(|date| date.with_timezone(&Utc))
+}
+
+/// Whether an `If-None-Match` header value matches the given ETag.
+///
+/// Uses the weak comparison required for `If-None-Match`, so `W/"abc"` matches
+/// `"abc"`.
+pub fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    let etag = etag.trim_start_matches("W/");
+    if_none_match
+        .split(',')
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == etag)
+}
+
+/// Recorded revision of a single resource
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ResourceRevision {
+    pub etag: String,
+    pub last_modified: DateTime<Utc>,
+    /// Sequence number of the sync pass in which this content was first seen
+    pub revision: u64,
+}
+
+impl ResourceRevision {
+    /// Evaluate conditional request headers against this revision.
+    ///
+    /// `If-None-Match` takes precedence; `If-Modified-Since` is only consulted
+    /// when no entity tag was supplied, as specified by RFC 7232.
+    pub fn is_not_modified(
+        &self,
+        if_none_match: Option<&str>,
+        if_modified_since: Option<&str>,
+    ) -> bool {
+        if let Some(if_none_match) = if_none_match {
+            return etag_matches(if_none_match, &self.etag);
+        }
+
+        match if_modified_since.and_then(parse_http_date) {
+            // HTTP dates have second precision, so compare at that granularity
+            Some(since) => self.last_modified.timestamp() <= since.timestamp(),
+            None => false,
+        }
+    }
+
+    /// `Last-Modified` header value for this revision
+    pub fn last_modified_header(&self) -> String {
+        http_date(&self.last_modified)
+    }
+}
+
+/// Resources that changed since a client's change token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangedResources {
+    /// Token to pass on the next call
+    pub token: String,
+    /// True when the supplied token was missing, malformed or from another
+    /// daemon instance and every resource is returned
+    pub full_sync: bool,
+    /// Added or modified resources, each carrying its `etag`
+    pub resources: Vec<Value>,
+    /// URIs of resources removed since the token was issued
+    pub removed: Vec<String>,
+}
+
+/// Log of resource revisions used to answer conditional and delta requests
+#[derive(Debug, Clone)]
+pub struct ResourceRevisionLog {
+    /// Identifies this log so tokens issued by a previous daemon are rejected
+    epoch: String,
+    sequence: u64,
+    entries: HashMap<String, ResourceRevision>,
+    /// Removed URIs and the sequence number at which they disappeared
+    tombstones: HashMap<String, u64>,
+}
+
+impl Default for ResourceRevisionLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResourceRevisionLog {
+    pub fn new() -> Self {
+        Self {
+            epoch: uuid::Uuid::new_v4().simple().to_string()[..12].to_string(),
+            sequence: 0,
+            entries: HashMap::new(),
+            tombstones: HashMap::new(),
+        }
+    }
+
+    /// Current change token
+    pub fn token(&self) -> String {
+        format!("{}.{}", self.epoch, self.sequence)
+    }
+
+    /// Record the current content of a single resource and return its revision
+    pub fn observe(&mut self, uri: &str, resource: &Value) -> ResourceRevision {
+        let etag = resource_etag(resource);
+        if let Some(existing) = self.entries.get(uri) {
+            if existing.etag == etag {
+                return existing.clone();
+            }
+        }
+
+        self.sequence += 1;
+        let revision = ResourceRevision {
+            etag,
+            last_modified: Self::now(),
+            revision: self.sequence,
+        };
+        self.tombstones.remove(uri);
+        self.entries.insert(uri.to_string(), revision.clone());
+        revision
+    }
+
+    /// Record a complete listing of resources, detecting additions,
+    /// modifications and removals in a single sync pass
+    pub fn observe_all(&mut self, resources: &[(String, Value)]) {
+        let next = self.sequence + 1;
+        let now = Self::now();
+        let mut changed = false;
+
+        for (uri, resource) in resources {
+            let etag = resource_etag(resource);
+            if self.entries.get(uri).map(|entry| &entry.etag) == Some(&etag) {
+                continue;
+            }
+            changed = true;
+            self.tombstones.remove(uri);
+            self.entries.insert(
+                uri.clone(),
+                ResourceRevision {
+                    etag,
+                    last_modified: now,
+                    revision: next,
+                },
+            );
+        }
+
+        let removed: Vec<String> = self
+            .entries
+            .keys()
+            .filter(|uri| !resources.iter().any(|(current, _)| current == *uri))
+            .cloned()
+            .collect();
+        for uri in removed {
+            changed = true;
+            self.entries.remove(&uri);
+            self.tombstones.insert(uri, next);
+        }
+
+        if changed {
+            self.sequence = next;
+        }
+    }
+
+    /// Revision of a resource, if it has been observed
+    pub fn get(&self, uri: &str) -> Option<&ResourceRevision> {
+        self.entries.get(uri)
+    }
+
+    /// Sequence number encoded in `token`, or `None` if the token cannot be
+    /// used for a delta (malformed, or issued by a different log)
+    pub fn parse_token(&self, token: &str) -> Option<u64> {
+        let (epoch, sequence) = token.split_once('.')?;
+        if epoch != self.epoch {
+            return None;
+        }
+        sequence
+            .parse()
+            .ok()
+            .filter(|sequence| *sequence <= self.sequence)
+    }
+
+    /// URIs changed and removed after the given sequence number
+    pub fn changes_after(&self, sequence: u64) -> (Vec<String>, Vec<String>) {
+        let mut changed: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|(_, revision)| revision.revision > sequence)
+            .map(|(uri, _)| uri.clone())
+            .collect();
+        let mut removed: Vec<String> = self
+            .tombstones
+            .iter()
+            .filter(|(_, removed_at)| **removed_at > sequence)
+            .map(|(uri, _)| uri.clone())
+            .collect();
+        changed.sort();
+        removed.sort();
+        (changed, removed)
+    }
+
+    /// HTTP dates only carry whole seconds, so store timestamps the same way
+    /// to keep `If-Modified-Since` round-trips exact
+    fn now() -> DateTime<Utc> {
+        Utc.timestamp_opt(Utc::now().timestamp(), 0)
+            .single()
+            .unwrap_or_else(Utc::now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_conditional_headers() {
+        let mut log = ResourceRevisionLog::new();
+        let revision = log.observe("todos://api", &json!({"todos": []}));
+
+        assert!(revision.is_not_modified(Some(&revision.etag), None));
+        assert!(revision.is_not_modified(Some(&format!("W/{}", revision.etag)), None));
+        assert!(revision.is_not_modified(Some("\"other\", *"), None));
+        assert!(!revision.is_not_modified(Some("\"other\""), None));
+
+        let header = revision.last_modified_header();
+        assert!(revision.is_not_modified(None, Some(&header)));
+        // If-None-Match wins over If-Modified-Since
+        assert!(!revision.is_not_modified(Some("\"other\""), Some(&header)));
+        assert!(!revision.is_not_modified(None, Some("Thu, 01 Jan 1970 00:00:00 GMT")));
+        assert!(!revision.is_not_modified(None, Some("yesterday")));
+
+        // Unchanged content keeps its revision
+        assert_eq!(log.observe("todos://api", &json!({"todos": []})), revision);
+    }
+
+    #[test]
+    fn test_changes_since_token() {
+        let mut log = ResourceRevisionLog::new();
+        log.observe_all(&[
+            ("scope://api".to_string(), json!({"v": 1})),
+            ("todos://api".to_string(), json!({"v": 1})),
+        ]);
+        let token = log.token();
+        let since = log.parse_token(&token).unwrap();
+        assert_eq!(log.changes_after(since), (vec![], vec![]));
+
+        log.observe_all(&[
+            ("scope://api".to_string(), json!({"v": 2})),
+            ("knowledge://api".to_string(), json!({"v": 1})),
+        ]);
+        let (changed, removed) = log.changes_after(since);
+        assert_eq!(changed, vec!["knowledge://api", "scope://api"]);
+        assert_eq!(removed, vec!["todos://api"]);
+
+        // Tokens from another daemon instance or from the future force a full sync
+        assert!(log.parse_token("abc.1").is_none());
+        assert!(log.parse_token(&format!("{}.99", log.epoch)).is_none());
+        assert!(log.parse_token("garbage").is_none());
+    }
+}
@@ -0,0 +1,563 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! MCP resource templates: parameterized views over context such as
+//! `rhema://scope/{name}/summary` or `rhema://todos{?priority,status,scope}`.
+//!
+//! Templates use the RFC 6570 forms MCP clients expand: `{var}` fills one path
+//! segment and `{?a,b}` lists optional query parameters. Every parameter of a
+//! read is validated against its template before the view is rendered, and
+//! rendered views are cached per parameter combination until the context
+//! changes or the cache TTL runs out.
+
+use rhema_core::{RhemaError, RhemaResult, Scope};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+use crate::context::ContextProvider;
+
+const TODO_PRIORITIES: &[&str] = &["low", "medium", "high", "critical"];
+const TODO_STATUSES: &[&str] = &[
+    "pending",
+    "in_progress",
+    "blocked",
+    "completed",
+    "cancelled",
+];
+const DECISION_STATUSES: &[&str] = &[
+    "proposed",
+    "under_review",
+    "approved",
+    "rejected",
+    "implemented",
+    "deprecated",
+];
+
+/// One parameter of a resource template
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateParameter {
+    pub name: String,
+    pub description: String,
+    pub required: bool,
+
+    /// Accepted values; empty when any non-empty value is accepted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_values: Vec<String>,
+}
+
+impl TemplateParameter {
+    fn required(name: &str, description: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            required: true,
+            allowed_values: Vec::new(),
+        }
+    }
+
+    fn optional(name: &str, description: &str, allowed_values: &[&str]) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            required: false,
+            allowed_values: allowed_values.iter().map(|v| v.to_string()).collect(),
+        }
+    }
+}
+
+/// MCP resource template as listed by `resources/templates/list`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceTemplate {
+    pub uri_template: String,
+    pub name: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub mime_type: Option<String>,
+    pub parameters: Vec<TemplateParameter>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TemplateView {
+    ScopeSummary,
+    ScopeKnowledge,
+    Todos,
+    Decisions,
+}
+
+/// Template parameter values keyed by name
+type Params = BTreeMap<String, String>;
+
+/// A template matching a URI, with its view and validated parameters
+type Resolved<'a> = (&'a ResourceTemplate, TemplateView, Params);
+
+/// Built-in resource templates with a per-parameter view cache
+pub struct ResourceTemplateRegistry {
+    templates: Vec<(ResourceTemplate, TemplateView)>,
+    cache: RwLock<HashMap<String, (Instant, Value)>>,
+    ttl: Duration,
+}
+
+impl ResourceTemplateRegistry {
+    pub fn new(ttl: Duration) -> Self {
+        let template =
+            |uri_template: &str,
+             name: &str,
+             title: &str,
+             description: &str,
+             parameters: Vec<TemplateParameter>| ResourceTemplate {
+                uri_template: uri_template.to_string(),
+                name: name.to_string(),
+                title: Some(title.to_string()),
+                description: Some(description.to_string()),
+                mime_type: Some("application/json".to_string()),
+                parameters,
+            };
+        let scope_param =
+            |description: &str| TemplateParameter::optional("scope", description, &[]);
+
+        let templates = vec![
+            (
+                template(
+                    "rhema://scope/{name}/summary",
+                    "scope_summary",
+                    "Scope summary",
+                    "Type, version, dependencies and entry counts of one scope",
+                    vec![TemplateParameter::required("name", "Scope name or path")],
+                ),
+                TemplateView::ScopeSummary,
+            ),
+            (
+                template(
+                    "rhema://scope/{name}/knowledge{?category}",
+                    "scope_knowledge",
+                    "Scope knowledge",
+                    "Knowledge entries of one scope, optionally limited to a category",
+                    vec![
+                        TemplateParameter::required("name", "Scope name or path"),
+                        TemplateParameter::optional("category", "Knowledge category", &[]),
+                    ],
+                ),
+                TemplateView::ScopeKnowledge,
+            ),
+            (
+                template(
+                    "rhema://todos{?priority,status,scope}",
+                    "todos",
+                    "Todos",
+                    "Todos across all scopes, filtered by priority, status or scope",
+                    vec![
+                        TemplateParameter::optional("priority", "Todo priority", TODO_PRIORITIES),
+                        TemplateParameter::optional("status", "Todo status", TODO_STATUSES),
+                        scope_param("Only todos of this scope"),
+                    ],
+                ),
+                TemplateView::Todos,
+            ),
+            (
+                template(
+                    "rhema://decisions{?status,scope}",
+                    "decisions",
+                    "Decisions",
+                    "Decisions across all scopes, filtered by status or scope",
+                    vec![
+                        TemplateParameter::optional("status", "Decision status", DECISION_STATUSES),
+                        scope_param("Only decisions of this scope"),
+                    ],
+                ),
+                TemplateView::Decisions,
+            ),
+        ];
+
+        Self {
+            templates,
+            cache: RwLock::new(HashMap::new()),
+            ttl,
+        }
+    }
+
+    pub fn templates(&self) -> Vec<ResourceTemplate> {
+        self.templates
+            .iter()
+            .map(|(template, _)| template.clone())
+            .collect()
+    }
+
+    /// Render the view a URI names, or `None` when no template matches it
+    pub async fn read(&self, uri: &str, provider: &ContextProvider) -> RhemaResult<Option<Value>> {
+        let Some((template, view, params)) = self.resolve(uri)? else {
+            return Ok(None);
+        };
+
+        let key = cache_key(template, &params);
+        if let Some((cached_at, value)) = self.cache.read().await.get(&key) {
+            if cached_at.elapsed() < self.ttl {
+                return Ok(Some(value.clone()));
+            }
+        }
+
+        let value = render(view, &params, provider).await?;
+        self.cache
+            .write()
+            .await
+            .insert(key, (Instant::now(), value.clone()));
+        Ok(Some(value))
+    }
+
+    /// Drop every cached view, for example after context files changed
+    pub async fn invalidate(&self) {
+        self.cache.write().await.clear();
+    }
+
+    /// Match a URI against the templates and validate its parameters
+    fn resolve(&self, uri: &str) -> RhemaResult<Option<Resolved<'_>>> {
+        for (template, view) in &self.templates {
+            if let Some((path_params, query)) = match_uri(&template.uri_template, uri) {
+                let params = validate(template, path_params, query)?;
+                return Ok(Some((template, *view, params)));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Split a URI into path variables and raw query pairs if its path matches
+/// the template
+fn match_uri(uri_template: &str, uri: &str) -> Option<(Params, Vec<(String, String)>)> {
+    let path_template = match uri_template.find("{?") {
+        Some(start) => &uri_template[..start],
+        None => uri_template,
+    };
+    let (path, query) = uri.split_once('?').unwrap_or((uri, ""));
+
+    let expected: Vec<&str> = path_template.split('/').collect();
+    let actual: Vec<&str> = path.split('/').collect();
+    if expected.len() != actual.len() {
+        return None;
+    }
+    let mut path_params = BTreeMap::new();
+    for (expected, actual) in expected.iter().zip(&actual) {
+        match expected
+            .strip_prefix('{')
+            .and_then(|rest| rest.strip_suffix('}'))
+        {
+            Some(name) if !actual.is_empty() => {
+                path_params.insert(name.to_string(), percent_decode(actual));
+            }
+            Some(_) => return None,
+            None if expected == actual => {}
+            None => return None,
+        }
+    }
+
+    let query = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(name), percent_decode(value))
+        })
+        .collect();
+    Some((path_params, query))
+}
+
+fn validate(
+    template: &ResourceTemplate,
+    mut params: BTreeMap<String, String>,
+    query: Vec<(String, String)>,
+) -> RhemaResult<BTreeMap<String, String>> {
+    let invalid = |message: String| {
+        RhemaError::InvalidInput(format!("{} for {}", message, template.uri_template))
+    };
+
+    for (name, value) in query {
+        if !template.parameters.iter().any(|p| p.name == name) {
+            let known: Vec<&str> = template
+                .parameters
+                .iter()
+                .map(|p| p.name.as_str())
+                .collect();
+            return Err(invalid(format!(
+                "Unknown parameter {} (expected one of {})",
+                name,
+                known.join(", ")
+            )));
+        }
+        if params.insert(name.clone(), value).is_some() {
+            return Err(invalid(format!("Parameter {} given more than once", name)));
+        }
+    }
+
+    for parameter in &template.parameters {
+        match params.get(&parameter.name) {
+            None if parameter.required => {
+                return Err(invalid(format!("Missing parameter {}", parameter.name)));
+            }
+            None => {}
+            Some(value) if value.is_empty() => {
+                return Err(invalid(format!("Empty parameter {}", parameter.name)));
+            }
+            Some(value)
+                if !parameter.allowed_values.is_empty()
+                    && !parameter.allowed_values.contains(value) =>
+            {
+                return Err(invalid(format!(
+                    "Invalid {} {} (expected one of {})",
+                    parameter.name,
+                    value,
+                    parameter.allowed_values.join(", ")
+                )));
+            }
+            Some(_) => {}
+        }
+    }
+    Ok(params)
+}
+
+/// Decode `%XX` escapes and `+`, leaving malformed escapes as they are
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+                match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 3;
+                        continue;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            b'+' => decoded.push(b' '),
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn cache_key(template: &ResourceTemplate, params: &BTreeMap<String, String>) -> String {
+    let params: Vec<String> = params
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect();
+    format!("{}|{}", template.uri_template, params.join("&"))
+}
+
+async fn render(
+    view: TemplateView,
+    params: &BTreeMap<String, String>,
+    provider: &ContextProvider,
+) -> RhemaResult<Value> {
+    let param = |name: &str| params.get(name).map(String::as_str);
+
+    match view {
+        TemplateView::ScopeSummary => {
+            let scope = find_scope(provider, param("name").unwrap_or_default()).await?;
+            let key = scope.path.to_string_lossy().to_string();
+            let knowledge = provider.get_knowledge(&key).await?;
+            let todos = provider.get_todos(&key).await?;
+            let decisions = provider.get_decisions(&key).await?;
+            let patterns = provider.get_patterns(&key).await?;
+            let conventions = provider.get_conventions(&key).await?;
+            let todos = todos.map(|t| t.todos).unwrap_or_default();
+            let open_todos = todos
+                .iter()
+                .filter(|todo| {
+                    !matches!(
+                        variant_name(&todo.status).as_str(),
+                        "completed" | "cancelled"
+                    )
+                })
+                .count();
+
+            Ok(serde_json::json!({
+                "name": scope.definition.name,
+                "path": key,
+                "type": scope.definition.scope_type,
+                "description": scope.definition.description,
+                "version": scope.definition.version,
+                "dependencies": scope.definition.dependencies,
+                "counts": {
+                    "knowledge": knowledge.map_or(0, |k| k.entries.len()),
+                    "todos": todos.len(),
+                    "open_todos": open_todos,
+                    "decisions": decisions.map_or(0, |d| d.decisions.len()),
+                    "patterns": patterns.map_or(0, |p| p.patterns.len()),
+                    "conventions": conventions.map_or(0, |c| c.conventions.len()),
+                }
+            }))
+        }
+        TemplateView::ScopeKnowledge => {
+            let scope = find_scope(provider, param("name").unwrap_or_default()).await?;
+            let key = scope.path.to_string_lossy().to_string();
+            let category = param("category");
+            let entries: Vec<_> = provider
+                .get_knowledge(&key)
+                .await?
+                .map(|k| k.entries)
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|entry| match category {
+                    Some(category) => entry
+                        .category
+                        .as_deref()
+                        .is_some_and(|c| c.eq_ignore_ascii_case(category)),
+                    None => true,
+                })
+                .collect();
+            Ok(serde_json::json!({
+                "scope": scope.definition.name,
+                "category": category,
+                "entries": entries,
+            }))
+        }
+        TemplateView::Todos => {
+            let mut matches = Vec::new();
+            for scope in scopes(provider, param("scope")).await? {
+                let key = scope.path.to_string_lossy().to_string();
+                let Some(todos) = provider.get_todos(&key).await? else {
+                    continue;
+                };
+                for todo in todos.todos {
+                    if param("priority").is_some_and(|p| p != variant_name(&todo.priority))
+                        || param("status").is_some_and(|s| s != variant_name(&todo.status))
+                    {
+                        continue;
+                    }
+                    matches.push(with_scope(&scope, serde_json::to_value(&todo)?));
+                }
+            }
+            Ok(serde_json::json!({ "todos": matches }))
+        }
+        TemplateView::Decisions => {
+            let mut matches = Vec::new();
+            for scope in scopes(provider, param("scope")).await? {
+                let key = scope.path.to_string_lossy().to_string();
+                let Some(decisions) = provider.get_decisions(&key).await? else {
+                    continue;
+                };
+                for decision in decisions.decisions {
+                    if param("status").is_some_and(|s| s != variant_name(&decision.status)) {
+                        continue;
+                    }
+                    matches.push(with_scope(&scope, serde_json::to_value(&decision)?));
+                }
+            }
+            Ok(serde_json::json!({ "decisions": matches }))
+        }
+    }
+}
+
+async fn find_scope(provider: &ContextProvider, name: &str) -> RhemaResult<Scope> {
+    provider
+        .get_scopes()
+        .await?
+        .into_iter()
+        .find(|scope| scope.definition.name == name || scope.path.to_string_lossy() == name)
+        .ok_or_else(|| RhemaError::ScopeNotFound(name.to_string()))
+}
+
+/// All scopes, or the one named
+async fn scopes(provider: &ContextProvider, name: Option<&str>) -> RhemaResult<Vec<Scope>> {
+    match name {
+        Some(name) => Ok(vec![find_scope(provider, name).await?]),
+        None => provider.get_scopes().await,
+    }
+}
+
+fn with_scope(scope: &Scope, mut entry: Value) -> Value {
+    if let Some(object) = entry.as_object_mut() {
+        object.insert(
+            "scope".to_string(),
+            Value::String(scope.definition.name.clone()),
+        );
+    }
+    entry
+}
+
+/// Serialized name of a unit enum variant
+fn variant_name<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn matches_and_validates_template_parameters() {
+        let registry = ResourceTemplateRegistry::new(Duration::from_secs(60));
+
+        let (template, view, params) = registry
+            .resolve("rhema://scope/api%20gateway/summary")
+            .unwrap()
+            .unwrap();
+        assert_eq!(template.name, "scope_summary");
+        assert_eq!(view, TemplateView::ScopeSummary);
+        assert_eq!(params["name"], "api gateway");
+
+        let (_, view, params) = registry
+            .resolve("rhema://todos?priority=high&scope=core")
+            .unwrap()
+            .unwrap();
+        assert_eq!(view, TemplateView::Todos);
+        assert_eq!(params.len(), 2);
+
+        assert!(registry.resolve("rhema://todos?priority=urgent").is_err());
+        assert!(registry.resolve("rhema://todos?owner=me").is_err());
+        assert!(registry
+            .resolve("rhema://scope//summary")
+            .unwrap()
+            .is_none());
+        assert!(registry
+            .resolve("rhema://context/schema")
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn caches_views_per_parameter_combination() {
+        let temp_dir = TempDir::new().unwrap();
+        let provider = ContextProvider::new(temp_dir.path().to_path_buf()).unwrap();
+        let registry = ResourceTemplateRegistry::new(Duration::from_secs(60));
+
+        for uri in [
+            "rhema://todos?priority=high&status=pending",
+            "rhema://todos?status=pending&priority=high",
+            "rhema://todos?priority=low",
+        ] {
+            let view = registry.read(uri, &provider).await.unwrap().unwrap();
+            assert_eq!(view["todos"], serde_json::json!([]));
+        }
+        assert_eq!(registry.cache.read().await.len(), 2);
+
+        registry.invalidate().await;
+        assert!(registry.cache.read().await.is_empty());
+    }
+}
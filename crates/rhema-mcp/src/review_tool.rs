@@ -0,0 +1,136 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! The `rhema.review` tool, which lets human-in-the-loop clients list
+//! entries written by agents and approve, reject or edit them.
+//!
+//! Decisions are recorded under the authenticated identity. Unauthenticated
+//! clients (such as local stdio sessions) must name the reviewer explicitly.
+
+use rhema_core::review::{ReviewQueue, ReviewState};
+use rhema_core::{RhemaError, RhemaResult};
+use serde_json::Value;
+use std::path::Path;
+
+use crate::query_guard::ANONYMOUS_IDENTITY;
+
+/// Name under which the review tool is exposed to MCP clients
+pub const REVIEW_TOOL_NAME: &str = "rhema.review";
+
+/// JSON schema of the tool arguments
+pub fn input_schema() -> Value {
+    serde_json::json!({
+        "type": "object",
+        "properties": {
+            "action": {
+                "type": "string",
+                "enum": ["list", "approve", "reject", "edit"],
+                "description": "List entries awaiting review, or decide on one"
+            },
+            "state": {
+                "type": "string",
+                "enum": ["pending", "rejected"],
+                "description": "Entries to list (default: pending)"
+            },
+            "id": {
+                "type": "string",
+                "description": "Entry id, required for approve, reject and edit"
+            },
+            "changes": {
+                "type": "object",
+                "description": "Fields to overwrite before approving (edit only)"
+            },
+            "note": {
+                "type": "string",
+                "description": "Reason recorded with the decision"
+            },
+            "reviewer": {
+                "type": "string",
+                "description": "Reviewer identity, required when the client is not authenticated"
+            }
+        },
+        "required": ["action"]
+    })
+}
+
+/// Run one review action on behalf of `identity`
+pub fn execute(repo_root: &Path, identity: &str, arguments: &Value) -> RhemaResult<Value> {
+    let queue = ReviewQueue::new(repo_root);
+    let action = arguments["action"]
+        .as_str()
+        .ok_or_else(|| RhemaError::InvalidInput("Missing action parameter".to_string()))?;
+
+    if action == "list" {
+        let state = match arguments["state"].as_str() {
+            None | Some("pending") => ReviewState::Pending,
+            Some("rejected") => ReviewState::Rejected,
+            Some(other) => {
+                return Err(RhemaError::InvalidInput(format!(
+                    "Unknown review state: {}",
+                    other
+                )))
+            }
+        };
+        let items: Vec<Value> = queue
+            .list(state)?
+            .into_iter()
+            .map(|item| {
+                let changes = item.changes();
+                serde_json::json!({ "item": item, "changes": changes })
+            })
+            .collect();
+        return Ok(serde_json::json!({ "entries": items }));
+    }
+
+    let id = arguments["id"]
+        .as_str()
+        .ok_or_else(|| RhemaError::InvalidInput("Missing id parameter".to_string()))?;
+    let reviewer = reviewer(identity, arguments)?;
+    let note = arguments["note"].as_str().map(str::to_string);
+
+    match action {
+        "approve" => Ok(serde_json::to_value(queue.approve(id, &reviewer, note)?)?),
+        "reject" => Ok(serde_json::to_value(queue.reject(id, &reviewer, note)?)?),
+        "edit" => {
+            let changes = match &arguments["changes"] {
+                Value::Object(_) => serde_json::from_value(arguments["changes"].clone())?,
+                _ => {
+                    return Err(RhemaError::InvalidInput(
+                        "Missing changes parameter".to_string(),
+                    ))
+                }
+            };
+            let (item, edits) = queue.edit(id, &reviewer, changes, note)?;
+            Ok(serde_json::json!({ "item": item, "changes": edits }))
+        }
+        other => Err(RhemaError::InvalidInput(format!(
+            "Unknown review action: {}",
+            other
+        ))),
+    }
+}
+
+fn reviewer(identity: &str, arguments: &Value) -> RhemaResult<String> {
+    if identity != ANONYMOUS_IDENTITY {
+        return Ok(identity.to_string());
+    }
+    match arguments["reviewer"].as_str().map(str::trim) {
+        Some(reviewer) if !reviewer.is_empty() => Ok(reviewer.to_string()),
+        _ => Err(RhemaError::InvalidInput(
+            "Unauthenticated clients must pass a reviewer".to_string(),
+        )),
+    }
+}
@@ -0,0 +1,250 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Drain mode and graceful shutdown.
+//!
+//! Shutdown happens in a fixed order:
+//!
+//! 1. Enter drain mode: listeners stop accepting connections and requests
+//!    arriving on open connections are rejected with `503`.
+//! 2. Wait for in-flight requests to finish, up to the deadline.
+//! 3. Stop the file watcher and deliver the events it has already debounced.
+//! 4. Flush the cache to its persistence store.
+//! 5. Record a final statistics snapshot and stop the daemon.
+
+use axum::{
+    extract::{Request, State},
+    http::{
+        header::{CONNECTION, RETRY_AFTER},
+        StatusCode,
+    },
+    middleware::Next,
+    response::{IntoResponse, Json, Response},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{watch, Notify};
+
+use crate::mcp::DaemonStatistics;
+
+/// Tracks drain state and in-flight requests for the daemon's transports
+#[derive(Debug)]
+pub struct DrainController {
+    draining: AtomicBool,
+    in_flight: AtomicUsize,
+    idle: Notify,
+    shutdown: watch::Sender<bool>,
+}
+
+impl Default for DrainController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DrainController {
+    pub fn new() -> Self {
+        let (shutdown, _) = watch::channel(false);
+        Self {
+            draining: AtomicBool::new(false),
+            in_flight: AtomicUsize::new(0),
+            idle: Notify::new(),
+            shutdown,
+        }
+    }
+
+    /// Whether the daemon is draining
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    /// Number of requests currently being served
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Enter drain mode; listeners waiting on [`Self::shutdown_requested`] stop accepting
+    pub fn begin_drain(&self) {
+        if !self.draining.swap(true, Ordering::SeqCst) {
+            self.shutdown.send_replace(true);
+        }
+    }
+
+    /// Leave drain mode so a restarted daemon accepts requests again
+    pub fn reset(&self) {
+        self.draining.store(false, Ordering::SeqCst);
+        self.shutdown.send_replace(false);
+    }
+
+    /// Register an in-flight request, or `None` when draining
+    pub fn track(self: &Arc<Self>) -> Option<InFlightGuard> {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        // Check after incrementing so `wait_idle` can never miss a request that
+        // slipped in while drain mode was being entered
+        if self.is_draining() {
+            self.release();
+            return None;
+        }
+        Some(InFlightGuard {
+            controller: self.clone(),
+        })
+    }
+
+    /// Resolves once drain mode has been entered
+    pub async fn shutdown_requested(&self) {
+        let mut receiver = self.shutdown.subscribe();
+        let _ = receiver.wait_for(|draining| *draining).await;
+    }
+
+    /// Wait until no requests are in flight, returning `false` if the
+    /// deadline passed first
+    pub async fn wait_idle(&self, deadline: Duration) -> bool {
+        tokio::time::timeout(deadline, async {
+            loop {
+                let notified = self.idle.notified();
+                if self.in_flight() == 0 {
+                    return;
+                }
+                notified.await;
+            }
+        })
+        .await
+        .is_ok()
+    }
+
+    fn release(&self) {
+        if self.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.idle.notify_waiters();
+        }
+    }
+}
+
+/// Marks a request as in flight until dropped
+#[derive(Debug)]
+pub struct InFlightGuard {
+    controller: Arc<DrainController>,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.controller.release();
+    }
+}
+
+/// Outcome of draining the service
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DrainReport {
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    pub deadline_ms: u64,
+    /// Requests still running when the drain started
+    pub in_flight_at_start: usize,
+    /// Requests still running when the deadline expired
+    pub abandoned_requests: usize,
+    /// Debounced watcher events delivered before the watcher stopped
+    pub flushed_watcher_events: usize,
+    /// Watcher events that could not be delivered before the deadline
+    pub dropped_watcher_events: usize,
+    pub cache_flushed: bool,
+    /// Final statistics snapshot taken before the daemon stopped
+    pub statistics: DaemonStatistics,
+}
+
+/// HTTP middleware rejecting new requests once the daemon is draining
+pub async fn http_middleware(
+    State(drain): State<Arc<DrainController>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(_guard) = drain.track() else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            [(CONNECTION, "close"), (RETRY_AFTER, "1")],
+            Json(serde_json::json!({
+                "error": "Service is shutting down",
+                "draining": true
+            })),
+        )
+            .into_response();
+    };
+
+    next.run(request).await
+}
+
+/// Resolves when the process receives SIGTERM or Ctrl-C
+pub async fn termination_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for Ctrl-C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => tracing::info!("Received Ctrl-C"),
+        _ = terminate => tracing::info!("Received SIGTERM"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_drain_waits_for_in_flight_requests() {
+        let drain = Arc::new(DrainController::new());
+        let guard = drain.track().unwrap();
+        assert_eq!(drain.in_flight(), 1);
+
+        drain.begin_drain();
+        assert!(drain.track().is_none());
+        assert_eq!(drain.in_flight(), 1);
+        assert!(!drain.wait_idle(Duration::from_millis(20)).await);
+
+        let waiter = {
+            let drain = drain.clone();
+            tokio::spawn(async move { drain.wait_idle(Duration::from_secs(5)).await })
+        };
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        drop(guard);
+        assert!(waiter.await.unwrap());
+
+        // Listeners subscribing after the drain started resolve immediately
+        drain.shutdown_requested().await;
+
+        drain.reset();
+        assert!(drain.track().is_some());
+    }
+}
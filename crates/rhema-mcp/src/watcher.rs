@@ -16,6 +16,7 @@
 
 use notify::{Event, EventKind, RecursiveMode, Watcher};
 use rhema_core::{RhemaError, RhemaResult};
+use rhema_query::subscription::QuerySubscriptionManager;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -96,7 +97,7 @@ pub struct WatcherStats {
 pub struct FileWatcher {
     config: WatcherConfig,
     repo_root: PathBuf,
-    watcher: Option<notify::RecommendedWatcher>,
+    watcher: Arc<std::sync::Mutex<Option<notify::RecommendedWatcher>>>,
     event_sender: mpsc::Sender<FileEvent>,
     #[allow(dead_code)]
     event_receiver: Arc<RwLock<mpsc::Receiver<FileEvent>>>,
@@ -111,7 +112,7 @@ impl Clone for FileWatcher {
         Self {
             config: self.config.clone(),
             repo_root: self.repo_root.clone(),
-            watcher: None, // Watcher cannot be cloned
+            watcher: self.watcher.clone(),
             event_sender: self.event_sender.clone(),
             event_receiver: self.event_receiver.clone(),
             subscribers: self.subscribers.clone(),
@@ -150,7 +151,7 @@ impl FileWatcher {
         Ok(Self {
             config: watcher_config,
             repo_root,
-            watcher: None,
+            watcher: Arc::new(std::sync::Mutex::new(None)),
             event_sender,
             event_receiver: Arc::new(RwLock::new(event_receiver)),
             subscribers,
@@ -199,9 +200,10 @@ impl FileWatcher {
             }
         }
 
-        // Store the watcher
-        let _watcher_guard = self.watcher.as_ref().map(|_| ()).unwrap_or(());
-        let _ = _watcher_guard;
+        // Keep the watcher alive for as long as this file watcher runs
+        if let Ok(mut guard) = self.watcher.lock() {
+            *guard = Some(watcher);
+        }
 
         // Start event processing
         self.start_event_processor_with_results(rx).await?;
@@ -217,6 +219,10 @@ impl FileWatcher {
     pub async fn stop(&self) -> RhemaResult<()> {
         tracing::info!("Stopping file watcher");
 
+        if let Ok(mut guard) = self.watcher.lock() {
+            guard.take();
+        }
+
         // Clear debounce timers
         let mut timers = self.debounce_timers.write().await;
         for (_, handle) in timers.drain() {
@@ -241,6 +247,47 @@ impl FileWatcher {
         Ok(())
     }
 
+    /// Stop receiving file system events and deliver the ones already being
+    /// debounced, waiting at most `timeout`.
+    ///
+    /// Returns the number of events delivered and the number dropped because
+    /// the timeout expired. Call before [`Self::stop`], which discards pending
+    /// events.
+    pub async fn drain(&self, timeout: Duration) -> (usize, usize) {
+        if let Ok(mut guard) = self.watcher.lock() {
+            guard.take();
+        }
+
+        let deadline = tokio::time::Instant::now() + timeout;
+        let pending: Vec<_> = self.debounce_timers.write().await.drain().collect();
+
+        let mut flushed = 0;
+        let mut dropped = 0;
+        for (path, mut handle) in pending {
+            if handle.is_finished() {
+                continue;
+            }
+            match tokio::time::timeout_at(deadline, &mut handle).await {
+                Ok(Ok(())) => flushed += 1,
+                Ok(Err(e)) => {
+                    tracing::warn!("Debounced event for {:?} failed: {}", path, e);
+                    dropped += 1;
+                }
+                Err(_) => {
+                    handle.abort();
+                    dropped += 1;
+                }
+            }
+        }
+
+        tracing::info!(
+            "File watcher drained ({} events delivered, {} dropped)",
+            flushed,
+            dropped
+        );
+        (flushed, dropped)
+    }
+
     /// Subscribe to file events
     pub async fn subscribe(&self) -> mpsc::Receiver<FileEvent> {
         let (tx, rx) = mpsc::channel(100);
@@ -253,6 +300,31 @@ impl FileWatcher {
         rx
     }
 
+    /// Forward file events to a query subscription manager so standing
+    /// queries are re-evaluated whenever the files they read change
+    pub async fn forward_to_subscriptions(
+        &self,
+        manager: Arc<QuerySubscriptionManager>,
+    ) -> tokio::task::JoinHandle<()> {
+        let mut events = self.subscribe().await;
+        tokio::spawn(async move {
+            while let Some(event) = events.recv().await {
+                if event.path.as_os_str().is_empty() {
+                    continue;
+                }
+                let manager = manager.clone();
+                let paths = vec![event.path];
+                let delivered =
+                    tokio::task::spawn_blocking(move || manager.on_files_changed(&paths)).await;
+                if let Ok(count) = delivered {
+                    if count > 0 {
+                        tracing::debug!("Delivered {} query subscription deltas", count);
+                    }
+                }
+            }
+        })
+    }
+
     /// Get watcher statistics
     pub async fn stats(&self) -> WatcherStats {
         let mut stats = self.stats.write().await;
@@ -0,0 +1,95 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Periodic performance reports on the [`rhema_core::jobs::JobQueue`].
+//! Each report is kept as the result of the job that generated it.
+
+use async_trait::async_trait;
+use chrono::Utc;
+use rhema_core::jobs::{Job, JobContext, JobHandler, JobSpec};
+use rhema_core::RhemaResult;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::performance::{PerformanceMonitor, ReportPeriod};
+
+/// Job kind generating a performance report
+pub const PERFORMANCE_REPORT_JOB: &str = "monitoring.performance_report";
+
+/// Generates a report over the last `hours` hours of the payload, 24 by default
+pub struct PerformanceReportJob {
+    monitor: Arc<PerformanceMonitor>,
+}
+
+impl PerformanceReportJob {
+    pub fn new(monitor: Arc<PerformanceMonitor>) -> Self {
+        Self { monitor }
+    }
+
+    /// Report over the last `hours`, repeated every `hours`
+    pub fn spec(hours: u64) -> JobSpec {
+        let hours = hours.max(1);
+        JobSpec::new(
+            PERFORMANCE_REPORT_JOB,
+            serde_json::json!({ "hours": hours }),
+        )
+        .with_repeat_every(Duration::from_secs(hours * 3600))
+    }
+}
+
+#[async_trait]
+impl JobHandler for PerformanceReportJob {
+    async fn run(&self, job: &Job, _context: &JobContext) -> RhemaResult<serde_json::Value> {
+        let hours = job.payload["hours"].as_u64().unwrap_or(24);
+        let end = Utc::now();
+        let period = ReportPeriod {
+            start: end - chrono::Duration::hours(hours as i64),
+            end,
+            duration_seconds: hours * 3600,
+        };
+        let report = self.monitor.generate_performance_report(period).await?;
+        Ok(serde_json::to_value(&report)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rhema_core::jobs::{JobQueue, JobStatus};
+
+    #[tokio::test]
+    async fn test_queued_report_runs() -> RhemaResult<()> {
+        let temp = tempfile::TempDir::new()?;
+        let monitor = PerformanceMonitor::new(PerformanceMonitor::default_config())?;
+        let queue = Arc::new(JobQueue::open(temp.path())?.with_handler(
+            PERFORMANCE_REPORT_JOB,
+            Arc::new(PerformanceReportJob::new(Arc::new(monitor))),
+        ));
+
+        let job = queue.enqueue(PerformanceReportJob::spec(6))?;
+        let finished = queue.run_due().await?;
+        assert_eq!(finished.len(), 1);
+        assert_eq!(finished[0].status, JobStatus::Succeeded);
+        let result = finished[0].result.as_ref().unwrap();
+        assert_eq!(result["period"]["duration_seconds"], 6 * 3600);
+
+        // The report repeats on the queue
+        let next = queue.list(Some(JobStatus::Queued))?;
+        assert_eq!(next.len(), 1);
+        assert_ne!(next[0].id, job.id);
+        Ok(())
+    }
+}
@@ -1,7 +1,11 @@
 pub mod dashboard;
+pub mod jobs;
 pub mod locomo_integration;
+pub mod logging;
 pub mod monitoring;
 pub mod performance;
 
+pub use jobs::{PerformanceReportJob, PERFORMANCE_REPORT_JOB};
+pub use logging::{init_logging, logging_handle, LogFormat, LoggingConfig, LoggingHandle};
 pub use monitoring::*;
 pub use performance::*;
@@ -0,0 +1,465 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Central logging configuration for Rhema binaries.
+//!
+//! Binaries call [`init_logging`] once at startup instead of building their
+//! own `tracing_subscriber`. The configuration supports a default level with
+//! per-module overrides, pretty/compact/JSON output, and sampling of
+//! high-volume targets such as the coordination layer. Levels can be changed
+//! at runtime through the returned [`LoggingHandle`], which the monitoring
+//! server exposes at `/admin/log-levels`.
+
+use rhema_core::{RhemaError, RhemaResult};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
+use tracing::level_filters::LevelFilter;
+use tracing::{Level, Metadata, Subscriber};
+use tracing_subscriber::layer::{Context, Filter, Layered, SubscriberExt};
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, EnvFilter, Layer, Registry};
+
+/// Environment variable holding level directives, e.g. `info,rhema_mcp=debug`
+pub const LOG_ENV: &str = "RHEMA_LOG";
+
+/// Environment variable selecting the output format (`pretty`, `compact` or `json`)
+pub const LOG_FORMAT_ENV: &str = "RHEMA_LOG_FORMAT";
+
+/// Log output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Pretty,
+    Compact,
+    /// One JSON object per line, for log ingestion pipelines
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = RhemaError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "pretty" | "text" => Ok(Self::Pretty),
+            "compact" => Ok(Self::Compact),
+            "json" => Ok(Self::Json),
+            other => Err(RhemaError::ConfigError(format!(
+                "Unknown log format '{}' (expected pretty, compact or json)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Keep one in `rate` info/debug/trace events from a target.
+///
+/// Warnings and errors are never sampled.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SamplingRule {
+    /// Target prefix, e.g. `rhema_coordination` (matches its submodules too)
+    pub target: String,
+
+    /// Keep one event out of every `rate`
+    pub rate: u64,
+}
+
+/// Logging configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    /// Default level for every module
+    pub level: String,
+
+    /// Per-module level overrides, keyed by target (e.g. `rhema_mcp::watcher`)
+    pub modules: BTreeMap<String, String>,
+
+    /// Output format
+    pub format: LogFormat,
+
+    /// Include the event target in output
+    pub with_target: bool,
+
+    /// Use ANSI colours (ignored for JSON output)
+    pub ansi: bool,
+
+    /// Sampling rules for high-volume targets
+    pub sampling: Vec<SamplingRule>,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+            modules: BTreeMap::new(),
+            format: LogFormat::Pretty,
+            with_target: true,
+            ansi: true,
+            sampling: Vec::new(),
+        }
+    }
+}
+
+impl LoggingConfig {
+    /// Apply `RHEMA_LOG` and `RHEMA_LOG_FORMAT` on top of this configuration
+    pub fn with_env(mut self) -> RhemaResult<Self> {
+        if let Ok(directives) = std::env::var(LOG_ENV) {
+            self.apply_directives(&directives)?;
+        }
+        if let Ok(format) = std::env::var(LOG_FORMAT_ENV) {
+            self.format = format.parse()?;
+        }
+        Ok(self)
+    }
+
+    /// Apply comma-separated directives: a bare level sets the default, and
+    /// `module=level` sets an override
+    pub fn apply_directives(&mut self, directives: &str) -> RhemaResult<()> {
+        let mut levels = self.levels();
+        for directive in directives.split(',').map(str::trim) {
+            match directive.split_once('=') {
+                Some((module, level)) => {
+                    levels.set(Some(module.trim()), level.trim())?;
+                }
+                None if !directive.is_empty() => levels.set(None, directive)?,
+                None => {}
+            }
+        }
+        self.level = levels.level;
+        self.modules = levels.modules;
+        Ok(())
+    }
+
+    /// Current default level and overrides
+    pub fn levels(&self) -> LogLevels {
+        LogLevels {
+            level: self.level.clone(),
+            modules: self.modules.clone(),
+        }
+    }
+}
+
+/// Default level and per-module overrides in effect
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogLevels {
+    pub level: String,
+    #[serde(default)]
+    pub modules: BTreeMap<String, String>,
+}
+
+impl LogLevels {
+    /// Set the default level (`module = None`) or a module override
+    pub fn set(&mut self, module: Option<&str>, level: &str) -> RhemaResult<()> {
+        let level = parse_level(level)?;
+        match module {
+            Some(module) => {
+                if module.is_empty()
+                    || !module
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':' || c == '-')
+                {
+                    return Err(RhemaError::ConfigError(format!(
+                        "Invalid log module '{}'",
+                        module
+                    )));
+                }
+                self.modules.insert(module.to_string(), level);
+            }
+            None => self.level = level,
+        }
+        Ok(())
+    }
+
+    /// Build the equivalent `EnvFilter`
+    pub fn env_filter(&self) -> RhemaResult<EnvFilter> {
+        let directives = std::iter::once(self.level.clone())
+            .chain(
+                self.modules
+                    .iter()
+                    .map(|(module, level)| format!("{}={}", module, level)),
+            )
+            .collect::<Vec<_>>()
+            .join(",");
+        EnvFilter::try_new(&directives).map_err(|e| {
+            RhemaError::ConfigError(format!("Invalid log directives '{}': {}", directives, e))
+        })
+    }
+}
+
+fn parse_level(level: &str) -> RhemaResult<String> {
+    LevelFilter::from_str(level.trim())
+        .map(|filter| filter.to_string().to_lowercase())
+        .map_err(|_| {
+            RhemaError::ConfigError(format!(
+                "Invalid log level '{}' (expected off, error, warn, info, debug or trace)",
+                level
+            ))
+        })
+}
+
+/// Number of events a sampling rule has seen and dropped
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SamplingStats {
+    pub target: String,
+    pub rate: u64,
+    pub seen: u64,
+    pub dropped: u64,
+}
+
+#[derive(Debug)]
+struct SamplingState {
+    rule: SamplingRule,
+    seen: AtomicU64,
+    dropped: AtomicU64,
+}
+
+/// Per-layer filter that thins out events from high-volume targets
+#[derive(Debug, Clone)]
+pub struct SamplingFilter {
+    rules: Arc<Vec<SamplingState>>,
+}
+
+impl SamplingFilter {
+    pub fn new(rules: &[SamplingRule]) -> Self {
+        let mut rules: Vec<SamplingState> = rules
+            .iter()
+            .filter(|rule| rule.rate > 1)
+            .map(|rule| SamplingState {
+                rule: rule.clone(),
+                seen: AtomicU64::new(0),
+                dropped: AtomicU64::new(0),
+            })
+            .collect();
+        // Most specific target wins
+        rules.sort_by_key(|r| std::cmp::Reverse(r.rule.target.len()));
+        Self {
+            rules: Arc::new(rules),
+        }
+    }
+
+    /// Whether an event with this metadata should be written
+    pub fn sample(&self, metadata: &Metadata<'_>) -> bool {
+        if *metadata.level() <= Level::WARN {
+            return true;
+        }
+        let target = metadata.target();
+        let Some(state) = self.rules.iter().find(|state| {
+            let prefix = state.rule.target.as_str();
+            target == prefix
+                || target
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.starts_with("::"))
+        }) else {
+            return true;
+        };
+
+        let keep = state.seen.fetch_add(1, Ordering::Relaxed) % state.rule.rate == 0;
+        if !keep {
+            state.dropped.fetch_add(1, Ordering::Relaxed);
+        }
+        keep
+    }
+
+    /// Counters for every sampling rule
+    pub fn stats(&self) -> Vec<SamplingStats> {
+        self.rules
+            .iter()
+            .map(|state| SamplingStats {
+                target: state.rule.target.clone(),
+                rate: state.rule.rate,
+                seen: state.seen.load(Ordering::Relaxed),
+                dropped: state.dropped.load(Ordering::Relaxed),
+            })
+            .collect()
+    }
+}
+
+impl<S: Subscriber> Filter<S> for SamplingFilter {
+    fn enabled(&self, _metadata: &Metadata<'_>, _cx: &Context<'_, S>) -> bool {
+        true
+    }
+
+    fn event_enabled(&self, event: &tracing::Event<'_>, _cx: &Context<'_, S>) -> bool {
+        self.sample(event.metadata())
+    }
+}
+
+type BaseSubscriber = Layered<reload::Layer<EnvFilter, Registry>, Registry>;
+
+/// Handle for changing log levels after initialization
+#[derive(Debug)]
+pub struct LoggingHandle {
+    filter: reload::Handle<EnvFilter, Registry>,
+    levels: RwLock<LogLevels>,
+    initial: LogLevels,
+    sampling: SamplingFilter,
+}
+
+impl LoggingHandle {
+    /// Levels currently in effect
+    pub fn levels(&self) -> LogLevels {
+        self.levels
+            .read()
+            .map(|levels| levels.clone())
+            .unwrap_or_else(|e| e.into_inner().clone())
+    }
+
+    /// Change the default level (`module = None`) or a module override
+    pub fn set_level(&self, module: Option<&str>, level: &str) -> RhemaResult<LogLevels> {
+        let mut updated = self.levels();
+        updated.set(module, level)?;
+        self.apply(updated)
+    }
+
+    /// Remove a module override so it falls back to the default level
+    pub fn clear_level(&self, module: &str) -> RhemaResult<LogLevels> {
+        let mut updated = self.levels();
+        updated.modules.remove(module);
+        self.apply(updated)
+    }
+
+    /// Restore the levels the process started with
+    pub fn reset(&self) -> RhemaResult<LogLevels> {
+        self.apply(self.initial.clone())
+    }
+
+    /// Sampling counters
+    pub fn sampling_stats(&self) -> Vec<SamplingStats> {
+        self.sampling.stats()
+    }
+
+    fn apply(&self, levels: LogLevels) -> RhemaResult<LogLevels> {
+        let filter = levels.env_filter()?;
+        self.filter
+            .reload(filter)
+            .map_err(|e| RhemaError::ConfigError(format!("Failed to update log levels: {}", e)))?;
+        tracing::info!(level = %levels.level, modules = ?levels.modules, "Log levels updated");
+        match self.levels.write() {
+            Ok(mut current) => *current = levels.clone(),
+            Err(e) => *e.into_inner() = levels.clone(),
+        }
+        Ok(levels)
+    }
+}
+
+static LOGGING: OnceLock<LoggingHandle> = OnceLock::new();
+
+/// Install the global subscriber described by `config`.
+///
+/// Fails if a global subscriber has already been installed.
+pub fn init_logging(config: &LoggingConfig) -> RhemaResult<&'static LoggingHandle> {
+    let levels = config.levels();
+    let (filter_layer, filter) = reload::Layer::new(levels.env_filter()?);
+    let sampling = SamplingFilter::new(&config.sampling);
+
+    let fmt_layer: Box<dyn Layer<BaseSubscriber> + Send + Sync> = match config.format {
+        LogFormat::Json => fmt::layer()
+            .json()
+            .with_current_span(true)
+            .with_span_list(false)
+            .with_target(config.with_target)
+            .boxed(),
+        LogFormat::Compact => fmt::layer()
+            .compact()
+            .with_ansi(config.ansi)
+            .with_target(config.with_target)
+            .boxed(),
+        LogFormat::Pretty => fmt::layer()
+            .with_ansi(config.ansi)
+            .with_target(config.with_target)
+            .boxed(),
+    };
+
+    tracing_subscriber::registry()
+        .with(filter_layer)
+        .with(fmt_layer.with_filter(sampling.clone()))
+        .try_init()
+        .map_err(|e| RhemaError::ConfigError(format!("Failed to initialize logging: {}", e)))?;
+
+    Ok(LOGGING.get_or_init(|| LoggingHandle {
+        filter,
+        levels: RwLock::new(levels.clone()),
+        initial: levels,
+        sampling,
+    }))
+}
+
+/// Handle of the subscriber installed by [`init_logging`], if any
+pub fn logging_handle() -> Option<&'static LoggingHandle> {
+    LOGGING.get()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directives_and_env_filter() {
+        let mut config = LoggingConfig::default();
+        config
+            .apply_directives("warn, rhema_mcp=DEBUG,rhema_coordination::agent=trace")
+            .unwrap();
+        assert_eq!(config.level, "warn");
+        assert_eq!(config.modules["rhema_mcp"], "debug");
+        assert_eq!(config.modules["rhema_coordination::agent"], "trace");
+        assert!(config.levels().env_filter().is_ok());
+
+        assert!(config.apply_directives("rhema_mcp=loud").is_err());
+        assert!(config.apply_directives("bad module=info").is_err());
+        assert_eq!("JSON".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        assert!("xml".parse::<LogFormat>().is_err());
+    }
+
+    /// Counts the events that reach it
+    #[derive(Clone, Default)]
+    struct EventCounter(Arc<AtomicU64>);
+
+    impl<S: Subscriber> Layer<S> for EventCounter {
+        fn on_event(&self, _event: &tracing::Event<'_>, _cx: Context<'_, S>) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn test_sampling_keeps_one_in_rate() {
+        let filter = SamplingFilter::new(&[SamplingRule {
+            target: "rhema_coordination".to_string(),
+            rate: 4,
+        }]);
+        let counter = EventCounter::default();
+        let subscriber =
+            tracing_subscriber::registry().with(counter.clone().with_filter(filter.clone()));
+
+        tracing::subscriber::with_default(subscriber, || {
+            for _ in 0..8 {
+                tracing::info!(target: "rhema_coordination::agent", "heartbeat");
+            }
+            for _ in 0..3 {
+                tracing::warn!(target: "rhema_coordination::agent", "slow heartbeat");
+            }
+            tracing::info!(target: "rhema_coordination_extra", "unrelated");
+        });
+
+        // 2 of 8 sampled infos, every warning, and the unmatched target
+        assert_eq!(counter.0.load(Ordering::Relaxed), 6);
+        let stats = filter.stats();
+        assert_eq!(stats[0].seen, 8);
+        assert_eq!(stats[0].dropped, 6);
+    }
+}
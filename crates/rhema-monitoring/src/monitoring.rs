@@ -65,6 +65,13 @@ pub struct Metrics {
     pub performance_monitoring_enabled: Gauge,
     pub performance_reports_generated: Counter,
     pub performance_alerts_triggered: Counter,
+
+    // Embedding pipeline metrics
+    pub embedding_texts_total: Counter,
+    pub embedding_batches_total: Counter,
+    pub embedding_batch_duration: Histogram,
+    pub embedding_rate_limited_total: Counter,
+    pub embedding_progress_ratio: Gauge,
 }
 
 /// Health status
@@ -186,6 +193,32 @@ impl MonitoringService {
                 "Total number of performance alerts triggered",
             )
             .unwrap(),
+
+            embedding_texts_total: Counter::new(
+                "rhema_embedding_texts_total",
+                "Total number of texts embedded by batch pipelines",
+            )
+            .unwrap(),
+            embedding_batches_total: Counter::new(
+                "rhema_embedding_batches_total",
+                "Total number of embedding batches sent to providers",
+            )
+            .unwrap(),
+            embedding_batch_duration: Histogram::with_opts(HistogramOpts::new(
+                "rhema_embedding_batch_duration_seconds",
+                "Embedding batch duration in seconds",
+            ))
+            .unwrap(),
+            embedding_rate_limited_total: Counter::new(
+                "rhema_embedding_rate_limited_total",
+                "Total number of embedding requests rejected by provider rate limits",
+            )
+            .unwrap(),
+            embedding_progress_ratio: Gauge::new(
+                "rhema_embedding_progress_ratio",
+                "Fraction of the current embedding job that has completed",
+            )
+            .unwrap(),
         });
 
         // Register metrics
@@ -249,6 +282,21 @@ impl MonitoringService {
         registry
             .register(Box::new(metrics.performance_alerts_triggered.clone()))
             .unwrap();
+        registry
+            .register(Box::new(metrics.embedding_texts_total.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(metrics.embedding_batches_total.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(metrics.embedding_batch_duration.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(metrics.embedding_rate_limited_total.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(metrics.embedding_progress_ratio.clone()))
+            .unwrap();
 
         let health_status = Arc::new(RwLock::new(HealthStatus {
             status: "healthy".to_string(),
@@ -299,6 +347,12 @@ impl MonitoringService {
                 .route("/health", web::get().to(health_handler))
                 .route("/ready", web::get().to(ready_handler))
                 .route("/live", web::get().to(live_handler))
+                .route("/admin/log-levels", web::get().to(log_levels_handler))
+                .route("/admin/log-levels", web::put().to(set_log_level_handler))
+                .route(
+                    "/admin/log-levels/{module}",
+                    web::delete().to(clear_log_level_handler),
+                )
         })
         .bind(addr)?
         .run()
@@ -364,6 +418,30 @@ impl MonitoringService {
         }
     }
 
+    /// Record a completed embedding batch
+    pub fn record_embedding_batch(&self, texts: usize, duration: Duration) {
+        self.metrics.embedding_batches_total.inc();
+        self.metrics.embedding_texts_total.inc_by(texts as f64);
+        self.metrics
+            .embedding_batch_duration
+            .observe(duration.as_secs_f64());
+    }
+
+    /// Record an embedding request rejected by a provider rate limit
+    pub fn record_embedding_rate_limited(&self) {
+        self.metrics.embedding_rate_limited_total.inc();
+    }
+
+    /// Update how far the current embedding job has progressed
+    pub fn set_embedding_progress(&self, completed: usize, total: usize) {
+        let ratio = if total == 0 {
+            1.0
+        } else {
+            completed as f64 / total as f64
+        };
+        self.metrics.embedding_progress_ratio.set(ratio);
+    }
+
     /// Update system metrics
     pub fn update_system_metrics(&self, memory_bytes: u64, cpu_percent: f64, connections: u64) {
         self.metrics.memory_usage_bytes.set(memory_bytes as f64);
@@ -540,6 +618,57 @@ async fn live_handler() -> HttpResponse {
     }))
 }
 
+/// Request body for changing a log level
+#[derive(Debug, serde::Deserialize)]
+struct SetLogLevelRequest {
+    /// Module to override; omit to change the default level
+    module: Option<String>,
+    level: String,
+}
+
+/// Current log levels and sampling counters
+async fn log_levels_handler() -> HttpResponse {
+    match crate::logging::logging_handle() {
+        Some(handle) => HttpResponse::Ok().json(serde_json::json!({
+            "levels": handle.levels(),
+            "sampling": handle.sampling_stats()
+        })),
+        None => logging_not_initialized(),
+    }
+}
+
+/// Change the default level or a module override
+async fn set_log_level_handler(request: web::Json<SetLogLevelRequest>) -> HttpResponse {
+    let Some(handle) = crate::logging::logging_handle() else {
+        return logging_not_initialized();
+    };
+    match handle.set_level(request.module.as_deref(), &request.level) {
+        Ok(levels) => HttpResponse::Ok().json(levels),
+        Err(e) => HttpResponse::BadRequest().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+/// Remove a module override
+async fn clear_log_level_handler(module: web::Path<String>) -> HttpResponse {
+    let Some(handle) = crate::logging::logging_handle() else {
+        return logging_not_initialized();
+    };
+    match handle.clear_level(&module) {
+        Ok(levels) => HttpResponse::Ok().json(levels),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
+fn logging_not_initialized() -> HttpResponse {
+    HttpResponse::ServiceUnavailable().json(serde_json::json!({
+        "error": "Logging was not initialized through rhema_monitoring::init_logging"
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -566,4 +695,19 @@ mod tests {
         assert!(service.metrics().file_operations_total.get() == 1.0);
         assert!(service.metrics().file_errors_total.get() == 1.0);
     }
+
+    #[tokio::test]
+    async fn test_embedding_metrics_recording() {
+        let service = MonitoringService::new().unwrap();
+
+        service.record_embedding_batch(32, Duration::from_millis(120));
+        service.record_embedding_batch(8, Duration::from_millis(40));
+        service.record_embedding_rate_limited();
+        service.set_embedding_progress(40, 160);
+
+        assert!(service.metrics().embedding_batches_total.get() == 2.0);
+        assert!(service.metrics().embedding_texts_total.get() == 40.0);
+        assert!(service.metrics().embedding_rate_limited_total.get() == 1.0);
+        assert!(service.metrics().embedding_progress_ratio.get() == 0.25);
+    }
 }
@@ -0,0 +1,121 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Compares the standard read path with the memory-mapped one on a selective
+//! filter over large todo lists. Allocations per query are counted by a
+//! wrapping global allocator and printed before the latency runs.
+//!
+//! Run with `cargo bench -p rhema-query --bench mapped_query`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rhema_query::query::{execute_parsed_query_with_config, parse_cql_query, QueryExecutionConfig};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tempfile::TempDir;
+
+const SCOPE_COUNTS: [usize; 2] = [50, 200];
+const TODOS_PER_SCOPE: usize = 500;
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn build_repo(scope_count: usize) -> TempDir {
+    let temp = TempDir::new().expect("temp dir");
+    for i in 0..scope_count {
+        let scope = temp.path().join(format!("services/svc-{:04}/.rhema", i));
+        fs::create_dir_all(&scope).expect("scope dir");
+        fs::write(
+            scope.join("rhema.yaml"),
+            format!(
+                "name: svc-{:04}\nscope_type: service\nversion: \"1.0.0\"\n",
+                i
+            ),
+        )
+        .expect("scope file");
+        let mut yaml = String::from("todos:\n");
+        for n in 0..TODOS_PER_SCOPE {
+            // One todo in fifty is blocked
+            let status = if n % 50 == 0 { "blocked" } else { "completed" };
+            yaml.push_str(&format!(
+                "  - id: T-{i}-{n}\n    title: Task {n} in scope {i}\n    status: {status}\n    priority: {}\n    tags: [backend, q{}]\n",
+                n % 5,
+                n % 4
+            ));
+        }
+        fs::write(scope.join("todos.yaml"), yaml).expect("todos file");
+    }
+    temp
+}
+
+fn bench_mapped_query(c: &mut Criterion) {
+    let query = parse_cql_query("todos.todos WHERE status='blocked'").expect("valid query");
+    let modes = [
+        ("standard", QueryExecutionConfig::sequential()),
+        (
+            "mapped",
+            QueryExecutionConfig::sequential().with_mapped_reads(true),
+        ),
+    ];
+
+    let mut group = c.benchmark_group("mapped_query");
+    group.sample_size(20);
+
+    for scope_count in SCOPE_COUNTS {
+        let repo = build_repo(scope_count);
+        let scopes = rhema_core::scope::discover_scopes(repo.path()).expect("scopes");
+        assert_eq!(scopes.len(), scope_count);
+
+        for (name, config) in &modes {
+            let before = ALLOCATIONS.load(Ordering::Relaxed);
+            let results = execute_parsed_query_with_config(&query, &scopes, repo.path(), config)
+                .expect("query succeeds");
+            let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+            assert_eq!(results.len(), scope_count);
+            println!(
+                "mapped_query/{}/{}: {} allocations per query",
+                name, scope_count, allocations
+            );
+
+            group.bench_with_input(BenchmarkId::new(*name, scope_count), config, |b, config| {
+                b.iter(|| {
+                    execute_parsed_query_with_config(&query, &scopes, repo.path(), config)
+                        .expect("query succeeds")
+                })
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_mapped_query);
+criterion_main!(benches);
@@ -0,0 +1,95 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Compares sequential and parallel multi-scope query execution.
+//!
+//! Run with `cargo bench -p rhema-query --bench parallel_query`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rhema_query::query::{execute_parsed_query_with_config, parse_cql_query, QueryExecutionConfig};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const SCOPE_COUNTS: [usize; 2] = [100, 250];
+const TODOS_PER_SCOPE: usize = 200;
+
+fn build_repo(scope_count: usize) -> TempDir {
+    let temp = TempDir::new().expect("temp dir");
+    for i in 0..scope_count {
+        let scope = temp.path().join(format!("services/svc-{:04}/.rhema", i));
+        fs::create_dir_all(&scope).expect("scope dir");
+        fs::write(
+            scope.join("rhema.yaml"),
+            format!(
+                "name: svc-{:04}\nscope_type: service\nversion: \"1.0.0\"\n",
+                i
+            ),
+        )
+        .expect("scope file");
+        fs::write(scope.join("todos.yaml"), todos_yaml(i)).expect("todos file");
+    }
+    temp
+}
+
+fn todos_yaml(scope: usize) -> String {
+    let mut yaml = String::from("todos:\n");
+    for n in 0..TODOS_PER_SCOPE {
+        let status = if n % 3 == 0 { "pending" } else { "completed" };
+        yaml.push_str(&format!(
+            "  - id: T-{scope}-{n}\n    title: Task {n} in scope {scope}\n    status: {status}\n    priority: {}\n",
+            n % 5
+        ));
+    }
+    yaml
+}
+
+fn bench_multi_scope_query(c: &mut Criterion) {
+    let query = parse_cql_query("todos.todos WHERE status='pending' ORDER BY priority DESC")
+        .expect("valid query");
+    let workers = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4);
+
+    let mut group = c.benchmark_group("multi_scope_query");
+    group.sample_size(20);
+
+    for scope_count in SCOPE_COUNTS {
+        let repo = build_repo(scope_count);
+        let root: &Path = repo.path();
+        let scopes = rhema_core::scope::discover_scopes(root).expect("scopes");
+        assert_eq!(scopes.len(), scope_count);
+
+        let configs = [
+            ("sequential", QueryExecutionConfig::sequential()),
+            ("workers-2", QueryExecutionConfig::with_workers(2)),
+            ("workers-all", QueryExecutionConfig::with_workers(workers)),
+        ];
+        for (name, config) in configs {
+            group.bench_with_input(BenchmarkId::new(name, scope_count), &config, |b, config| {
+                b.iter(|| {
+                    execute_parsed_query_with_config(&query, &scopes, root, config)
+                        .expect("query succeeds")
+                })
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_multi_scope_query);
+criterion_main!(benches);
@@ -0,0 +1,58 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Scope discovery, parse, filter and aggregate on synthetic repositories of
+//! 10, 100 and 1000 scopes. `rhema perf check-regression` gates the same
+//! operations against a stored baseline.
+//!
+//! Run with `cargo bench -p rhema-query --bench query_engine`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rhema_query::benchmark::{build_synthetic_repo, PerfConfig, QueryOperation};
+use tempfile::TempDir;
+
+fn bench_query_engine(c: &mut Criterion) {
+    let config = PerfConfig::default();
+    let mut group = c.benchmark_group("query_engine");
+    group.sample_size(20);
+
+    for &scope_count in &config.scope_counts {
+        let repo = TempDir::new().expect("temp dir");
+        build_synthetic_repo(repo.path(), scope_count, config.todos_per_scope)
+            .expect("synthetic repo");
+        let scopes = rhema_core::scope::discover_scopes(repo.path()).expect("scopes");
+        assert_eq!(scopes.len(), scope_count);
+
+        for operation in QueryOperation::ALL {
+            group.bench_with_input(
+                BenchmarkId::new(operation.name(), scope_count),
+                &scopes,
+                |b, scopes| {
+                    b.iter(|| {
+                        operation
+                            .run(repo.path(), scopes)
+                            .expect("operation succeeds")
+                    })
+                },
+            );
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_query_engine);
+criterion_main!(benches);
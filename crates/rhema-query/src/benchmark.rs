@@ -0,0 +1,457 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Query engine benchmarks on synthetic repositories.
+//!
+//! The criterion suite in `benches/query_engine.rs` and
+//! `rhema perf check-regression` run the same operations; the latter stores
+//! p95 latencies as a baseline and fails when a later run regresses beyond
+//! the configured thresholds.
+
+use chrono::{DateTime, Utc};
+use rhema_core::policy;
+use rhema_core::scope::{discover_scopes, Scope};
+use rhema_core::{RhemaError, RhemaResult};
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::query::{execute_parsed_query_with_config, parse_cql_query, QueryExecutionConfig};
+
+/// Repository config section holding [`PerfConfig`]
+pub const PERF_CONFIG_SECTION: &str = "perf";
+
+/// Default baseline location, relative to the repository root
+pub const QUERY_BASELINE_FILE: &str = ".rhema/perf/query-baseline.json";
+
+/// Query filtered by the `filter` and `aggregate` operations
+pub const BENCHMARK_QUERY: &str = "todos.todos WHERE status='pending' ORDER BY priority DESC";
+
+/// Query engine operation measured by the benchmarks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryOperation {
+    /// Walk the repository for scopes
+    Discovery,
+    /// Parse the benchmark query
+    Parse,
+    /// Run the benchmark query over discovered scopes
+    Filter,
+    /// Run the benchmark query and count the matches by priority
+    Aggregate,
+}
+
+impl QueryOperation {
+    pub const ALL: [QueryOperation; 4] = [
+        QueryOperation::Discovery,
+        QueryOperation::Parse,
+        QueryOperation::Filter,
+        QueryOperation::Aggregate,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            QueryOperation::Discovery => "discovery",
+            QueryOperation::Parse => "parse",
+            QueryOperation::Filter => "filter",
+            QueryOperation::Aggregate => "aggregate",
+        }
+    }
+
+    /// Run the operation once against a synthetic repository
+    pub fn run(&self, repo_root: &Path, scopes: &[Scope]) -> RhemaResult<usize> {
+        match self {
+            QueryOperation::Discovery => Ok(discover_scopes(repo_root)?.len()),
+            QueryOperation::Parse => Ok(parse_cql_query(BENCHMARK_QUERY)?.conditions.len()),
+            QueryOperation::Filter => {
+                let query = parse_cql_query(BENCHMARK_QUERY)?;
+                let config = QueryExecutionConfig::default();
+                Ok(execute_parsed_query_with_config(&query, scopes, repo_root, &config)?.len())
+            }
+            QueryOperation::Aggregate => {
+                let query = parse_cql_query(BENCHMARK_QUERY)?;
+                let config = QueryExecutionConfig::default();
+                let results = execute_parsed_query_with_config(&query, scopes, repo_root, &config)?;
+                let mut by_priority: HashMap<String, usize> = HashMap::new();
+                for result in &results {
+                    let items = match &result.data {
+                        Value::Sequence(items) => items.as_slice(),
+                        item => std::slice::from_ref(item),
+                    };
+                    for item in items {
+                        let priority = item
+                            .get("priority")
+                            .map(|p| serde_yaml::to_string(p).unwrap_or_default())
+                            .unwrap_or_default();
+                        *by_priority.entry(priority).or_default() += 1;
+                    }
+                }
+                Ok(by_priority.len())
+            }
+        }
+    }
+}
+
+impl fmt::Display for QueryOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Write a repository of `scope_count` service scopes with
+/// `todos_per_scope` todos each under `root`
+pub fn build_synthetic_repo(
+    root: &Path,
+    scope_count: usize,
+    todos_per_scope: usize,
+) -> RhemaResult<()> {
+    for i in 0..scope_count {
+        let scope = root.join(format!("services/svc-{:04}/.rhema", i));
+        fs::create_dir_all(&scope)?;
+        fs::write(
+            scope.join("rhema.yaml"),
+            format!(
+                "name: svc-{:04}\nscope_type: service\nversion: \"1.0.0\"\n",
+                i
+            ),
+        )?;
+
+        let mut todos = String::from("todos:\n");
+        for n in 0..todos_per_scope {
+            let status = if n % 3 == 0 { "pending" } else { "completed" };
+            todos.push_str(&format!(
+                "  - id: T-{i}-{n}\n    title: Task {n} in scope {i}\n    status: {status}\n    priority: {}\n",
+                n % 5
+            ));
+        }
+        fs::write(scope.join("todos.yaml"), todos)?;
+    }
+    Ok(())
+}
+
+/// Thresholds a run must stay within relative to the baseline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RegressionThresholds {
+    /// Largest allowed p95 increase, in percent of the baseline
+    pub max_p95_regression_percent: f64,
+    /// Increases smaller than this are noise and never fail the check
+    pub min_p95_delta_us: u64,
+    /// Per-operation overrides of `max_p95_regression_percent`
+    pub operations: HashMap<QueryOperation, f64>,
+}
+
+impl Default for RegressionThresholds {
+    fn default() -> Self {
+        Self {
+            max_p95_regression_percent: 20.0,
+            min_p95_delta_us: 500,
+            operations: HashMap::new(),
+        }
+    }
+}
+
+impl RegressionThresholds {
+    pub fn max_regression_percent(&self, operation: QueryOperation) -> f64 {
+        self.operations
+            .get(&operation)
+            .copied()
+            .unwrap_or(self.max_p95_regression_percent)
+    }
+}
+
+/// `perf` section of the repository config
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PerfConfig {
+    /// Synthetic repository sizes to measure
+    pub scope_counts: Vec<usize>,
+    /// Todos written to each synthetic scope
+    pub todos_per_scope: usize,
+    /// Timed runs per operation and size, after one warm-up run
+    pub iterations: usize,
+    pub thresholds: RegressionThresholds,
+}
+
+impl Default for PerfConfig {
+    fn default() -> Self {
+        Self {
+            scope_counts: vec![10, 100, 1000],
+            todos_per_scope: 20,
+            iterations: 30,
+            thresholds: RegressionThresholds::default(),
+        }
+    }
+}
+
+impl PerfConfig {
+    pub fn load(repo_root: &Path) -> RhemaResult<Self> {
+        let value = policy::repository_config(repo_root)?;
+        match value.get(PERF_CONFIG_SECTION) {
+            Some(section) => serde_yaml::from_value(section.clone()).map_err(|e| {
+                RhemaError::ConfigError(format!(
+                    "Invalid {} section in the repository config: {}",
+                    PERF_CONFIG_SECTION, e
+                ))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+/// Latencies of one operation on one repository size, in microseconds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationLatency {
+    pub operation: QueryOperation,
+    pub scope_count: usize,
+    pub iterations: usize,
+    pub p50_us: u64,
+    pub p95_us: u64,
+    pub max_us: u64,
+}
+
+impl OperationLatency {
+    fn from_samples(
+        operation: QueryOperation,
+        scope_count: usize,
+        mut samples: Vec<Duration>,
+    ) -> Self {
+        samples.sort();
+        let percentile = |p: f64| {
+            let rank = ((samples.len() as f64 * p).ceil() as usize).clamp(1, samples.len());
+            samples[rank - 1].as_micros() as u64
+        };
+        Self {
+            operation,
+            scope_count,
+            iterations: samples.len(),
+            p50_us: percentile(0.50),
+            p95_us: percentile(0.95),
+            max_us: percentile(1.0),
+        }
+    }
+}
+
+/// Results of one benchmark run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkReport {
+    pub recorded_at: DateTime<Utc>,
+    pub results: Vec<OperationLatency>,
+}
+
+impl BenchmarkReport {
+    pub fn load(path: &Path) -> RhemaResult<Self> {
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    pub fn save(&self, path: &Path) -> RhemaResult<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, operation: QueryOperation, scope_count: usize) -> Option<&OperationLatency> {
+        self.results
+            .iter()
+            .find(|r| r.operation == operation && r.scope_count == scope_count)
+    }
+}
+
+/// Measure every operation on every configured repository size, building
+/// the synthetic repositories under `work_dir`
+pub fn run_benchmarks(work_dir: &Path, config: &PerfConfig) -> RhemaResult<BenchmarkReport> {
+    let iterations = config.iterations.max(1);
+    let mut results = Vec::new();
+
+    for &scope_count in &config.scope_counts {
+        let root = work_dir.join(format!("repo-{}", scope_count));
+        if root.exists() {
+            fs::remove_dir_all(&root)?;
+        }
+        build_synthetic_repo(&root, scope_count, config.todos_per_scope)?;
+        let scopes = discover_scopes(&root)?;
+
+        for operation in QueryOperation::ALL {
+            operation.run(&root, &scopes)?;
+            let mut samples = Vec::with_capacity(iterations);
+            for _ in 0..iterations {
+                let started = Instant::now();
+                operation.run(&root, &scopes)?;
+                samples.push(started.elapsed());
+            }
+            results.push(OperationLatency::from_samples(
+                operation,
+                scope_count,
+                samples,
+            ));
+        }
+        fs::remove_dir_all(&root)?;
+    }
+
+    Ok(BenchmarkReport {
+        recorded_at: Utc::now(),
+        results,
+    })
+}
+
+/// p95 of one operation compared against the baseline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyComparison {
+    pub operation: QueryOperation,
+    pub scope_count: usize,
+    pub baseline_p95_us: u64,
+    pub current_p95_us: u64,
+    pub change_percent: f64,
+    pub allowed_percent: f64,
+    pub regressed: bool,
+}
+
+/// Outcome of comparing a run against the baseline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegressionCheck {
+    pub comparisons: Vec<LatencyComparison>,
+    /// Measurements without a baseline entry, as `operation/scope_count`
+    pub missing_baseline: Vec<String>,
+}
+
+impl RegressionCheck {
+    pub fn regressions(&self) -> impl Iterator<Item = &LatencyComparison> {
+        self.comparisons.iter().filter(|c| c.regressed)
+    }
+
+    pub fn passed(&self) -> bool {
+        self.regressions().next().is_none()
+    }
+}
+
+pub fn check_regression(
+    baseline: &BenchmarkReport,
+    current: &BenchmarkReport,
+    thresholds: &RegressionThresholds,
+) -> RegressionCheck {
+    let mut comparisons = Vec::new();
+    let mut missing_baseline = Vec::new();
+    let mut ordered: BTreeMap<(usize, QueryOperation), &OperationLatency> = BTreeMap::new();
+    for result in &current.results {
+        ordered.insert((result.scope_count, result.operation), result);
+    }
+
+    for ((scope_count, operation), result) in ordered {
+        let Some(base) = baseline.get(operation, scope_count) else {
+            missing_baseline.push(format!("{}/{}", operation, scope_count));
+            continue;
+        };
+        let delta = result.p95_us as f64 - base.p95_us as f64;
+        let change_percent = if base.p95_us == 0 {
+            0.0
+        } else {
+            delta / base.p95_us as f64 * 100.0
+        };
+        let allowed_percent = thresholds.max_regression_percent(operation);
+        comparisons.push(LatencyComparison {
+            operation,
+            scope_count,
+            baseline_p95_us: base.p95_us,
+            current_p95_us: result.p95_us,
+            change_percent,
+            allowed_percent,
+            regressed: change_percent > allowed_percent
+                && delta > thresholds.min_p95_delta_us as f64,
+        });
+    }
+
+    RegressionCheck {
+        comparisons,
+        missing_baseline,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn report(p95_us: &[(QueryOperation, u64)]) -> BenchmarkReport {
+        BenchmarkReport {
+            recorded_at: Utc::now(),
+            results: p95_us
+                .iter()
+                .map(|&(operation, p95_us)| OperationLatency {
+                    operation,
+                    scope_count: 10,
+                    iterations: 1,
+                    p50_us: p95_us,
+                    p95_us,
+                    max_us: p95_us,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_check_regression_applies_thresholds() {
+        let baseline = report(&[
+            (QueryOperation::Parse, 100),
+            (QueryOperation::Filter, 10_000),
+            (QueryOperation::Aggregate, 10_000),
+        ]);
+        let current = report(&[
+            // Doubled, but well under the noise floor
+            (QueryOperation::Parse, 200),
+            (QueryOperation::Filter, 15_000),
+            (QueryOperation::Aggregate, 11_000),
+            (QueryOperation::Discovery, 5_000),
+        ]);
+        let mut thresholds = RegressionThresholds::default();
+        thresholds.operations.insert(QueryOperation::Aggregate, 5.0);
+
+        let check = check_regression(&baseline, &current, &thresholds);
+        let regressed: Vec<QueryOperation> = check.regressions().map(|c| c.operation).collect();
+        assert_eq!(
+            regressed,
+            vec![QueryOperation::Filter, QueryOperation::Aggregate]
+        );
+        assert_eq!(check.missing_baseline, vec!["discovery/10".to_string()]);
+        assert!(!check.passed());
+    }
+
+    #[test]
+    fn test_run_benchmarks_on_synthetic_repo() {
+        let temp = TempDir::new().unwrap();
+        let config = PerfConfig {
+            scope_counts: vec![3],
+            todos_per_scope: 6,
+            iterations: 2,
+            ..Default::default()
+        };
+        let report = run_benchmarks(temp.path(), &config).unwrap();
+        assert_eq!(report.results.len(), QueryOperation::ALL.len());
+        let filter = report.get(QueryOperation::Filter, 3).unwrap();
+        assert_eq!(filter.iterations, 2);
+        assert!(filter.p50_us <= filter.p95_us);
+
+        let path = temp.path().join("baseline.json");
+        report.save(&path).unwrap();
+        assert_eq!(BenchmarkReport::load(&path).unwrap().results.len(), 4);
+    }
+}
@@ -1,9 +1,19 @@
+pub mod benchmark;
+pub mod lint;
 pub mod locomo_queries;
+pub mod mapped;
+pub mod mutation;
 pub mod query;
 pub mod repo_analysis;
 pub mod search;
+pub mod subscription;
 
+pub use benchmark::*;
+pub use lint::*;
 pub use locomo_queries::*;
+pub use mapped::*;
+pub use mutation::*;
 pub use query::*;
 pub use repo_analysis::*;
 pub use search::*;
+pub use subscription::*;
@@ -0,0 +1,701 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Static checks for CQL queries before they run.
+//!
+//! The linter rewrites deprecated syntax first, so a query written in an old
+//! form is still checked for everything else against its modern equivalent.
+//! The remaining rules look at the scopes the query would read: queries with
+//! no filter or limit over every scope, filters that cannot narrow a large
+//! collection, and fields that are neither in the entry schema nor in the
+//! data, with a suggestion when a known field is a likely typo.
+
+use crate::query::{extract_yaml_path, parse_cql_query, resolve_target_scopes, CqlQuery, Operator};
+use regex::Regex;
+use rhema_core::scope::Scope;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::Path;
+
+/// Estimated row count from which an unfiltered query is reported
+pub const DEFAULT_LARGE_RESULT_ROWS: usize = 1000;
+
+/// Fields of the entries in each standard context file
+const SCHEMA_FIELDS: &[(&str, &[&str])] = &[
+    (
+        "todos",
+        &[
+            "id",
+            "title",
+            "description",
+            "status",
+            "priority",
+            "assigned_to",
+            "due_date",
+            "created_at",
+            "completed_at",
+            "outcome",
+            "related_knowledge",
+        ],
+    ),
+    (
+        "knowledge",
+        &[
+            "id",
+            "title",
+            "content",
+            "category",
+            "tags",
+            "confidence",
+            "created_at",
+            "updated_at",
+            "source",
+        ],
+    ),
+    (
+        "decisions",
+        &[
+            "id",
+            "title",
+            "description",
+            "status",
+            "context",
+            "alternatives",
+            "rationale",
+            "consequences",
+            "decided_at",
+            "review_date",
+            "decision_makers",
+            "implementing_commits",
+            "incidents",
+            "reversed_by",
+            "reverses",
+        ],
+    ),
+    (
+        "patterns",
+        &[
+            "id",
+            "name",
+            "description",
+            "pattern_type",
+            "usage",
+            "effectiveness",
+            "examples",
+            "anti_patterns",
+            "related_patterns",
+            "created_at",
+            "updated_at",
+        ],
+    ),
+    (
+        "conventions",
+        &[
+            "id",
+            "name",
+            "description",
+            "convention_type",
+            "enforcement",
+            "examples",
+            "tools",
+            "created_at",
+            "updated_at",
+        ],
+    ),
+];
+
+/// Verbs of the old natural-language query form, e.g. `find todos where ...`
+const LEGACY_VERBS: &[&str] = &["find", "get", "show", "list"];
+
+/// Largest edit distance at which an unknown field is taken for a typo
+const MAX_TYPO_DISTANCE: usize = 2;
+
+/// How serious a finding is; errors mean the query will not run as written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintSeverity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for LintSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LintSeverity::Info => write!(f, "info"),
+            LintSeverity::Warning => write!(f, "warning"),
+            LintSeverity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// The check that produced a finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LintRule {
+    /// The query does not parse
+    Syntax,
+    /// Syntax that is no longer accepted or does not mean what it looks like
+    DeprecatedSyntax,
+    /// No WHERE and no LIMIT over every scope
+    UnboundedQuery,
+    /// Likely to return more rows than anyone will read
+    LargeResult,
+    /// A field that is neither in the schema nor in the data
+    UnknownField,
+}
+
+impl fmt::Display for LintRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LintRule::Syntax => write!(f, "syntax"),
+            LintRule::DeprecatedSyntax => write!(f, "deprecated-syntax"),
+            LintRule::UnboundedQuery => write!(f, "unbounded-query"),
+            LintRule::LargeResult => write!(f, "large-result"),
+            LintRule::UnknownField => write!(f, "unknown-field"),
+        }
+    }
+}
+
+/// One problem found in a query
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LintFinding {
+    pub rule: LintRule,
+    pub severity: LintSeverity,
+    pub message: String,
+
+    /// The query rewritten to address this finding, when there is one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+impl LintFinding {
+    fn new(rule: LintRule, severity: LintSeverity, message: String) -> Self {
+        Self {
+            rule,
+            severity,
+            message,
+            suggestion: None,
+        }
+    }
+
+    fn suggest(mut self, query: impl Into<String>) -> Self {
+        self.suggestion = Some(query.into());
+        self
+    }
+}
+
+impl fmt::Display for LintFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]: {}", self.severity, self.rule, self.message)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, "\n  try: {}", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+/// Findings for one query
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LintReport {
+    pub query: String,
+    pub findings: Vec<LintFinding>,
+
+    /// Rows the query could return, counted from the scopes it reads
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_rows: Option<usize>,
+}
+
+impl LintReport {
+    pub fn has_errors(&self) -> bool {
+        self.findings
+            .iter()
+            .any(|f| f.severity == LintSeverity::Error)
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+}
+
+/// Checks CQL queries against the scopes they would read
+#[derive(Debug, Clone)]
+pub struct QueryLinter {
+    large_result_rows: usize,
+}
+
+impl Default for QueryLinter {
+    fn default() -> Self {
+        Self {
+            large_result_rows: DEFAULT_LARGE_RESULT_ROWS,
+        }
+    }
+}
+
+impl QueryLinter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report unfiltered queries from this many estimated rows
+    pub fn with_large_result_rows(mut self, rows: usize) -> Self {
+        self.large_result_rows = rows;
+        self
+    }
+
+    /// Lint `query` as it would run over `scopes`
+    pub fn lint(&self, query: &str, scopes: &[Scope], repo_root: &Path) -> LintReport {
+        let (modern, mut findings) = modernize(query.trim());
+        let mut report = LintReport {
+            query: query.to_string(),
+            ..Default::default()
+        };
+
+        let parsed = match parse_cql_query(&modern) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                findings.push(LintFinding::new(
+                    LintRule::Syntax,
+                    LintSeverity::Error,
+                    e.to_string(),
+                ));
+                report.findings = findings;
+                return report;
+            }
+        };
+
+        let collection = Collection::read(&parsed, scopes, repo_root);
+        report.estimated_rows = collection.as_ref().map(|c| c.rows);
+        if let Some(collection) = &collection {
+            findings.extend(self.check_size(&parsed, collection));
+            findings.extend(check_fields(&parsed, collection));
+        }
+
+        report.findings = findings;
+        report
+    }
+
+    fn check_size(&self, parsed: &CqlQuery, collection: &Collection) -> Option<LintFinding> {
+        if parsed.limit.is_some() {
+            return None;
+        }
+        // Negative conditions keep almost everything, so they are no filter
+        let unfiltered = parsed.conditions.iter().all(|c| {
+            matches!(
+                c.operator,
+                Operator::NotEquals
+                    | Operator::NotIn
+                    | Operator::NotLike
+                    | Operator::NotContains
+                    | Operator::IsNotNull
+            )
+        });
+        if !unfiltered {
+            return None;
+        }
+
+        if collection.rows >= self.large_result_rows {
+            let what = if parsed.conditions.is_empty() {
+                "no WHERE filter"
+            } else {
+                "only exclusion filters"
+            };
+            let finding = LintFinding::new(
+                LintRule::LargeResult,
+                LintSeverity::Warning,
+                format!(
+                    "Query has {} and can return about {} rows from {} scope(s); filter it or add a LIMIT",
+                    what, collection.rows, collection.scopes
+                ),
+            );
+            return Some(if parsed.offset.is_none() {
+                finding.suggest(format!("{} LIMIT {}", parsed.query, self.large_result_rows))
+            } else {
+                finding
+            });
+        }
+
+        if parsed.conditions.is_empty() && collection.scopes > 1 {
+            return Some(LintFinding::new(
+                LintRule::UnboundedQuery,
+                LintSeverity::Info,
+                format!(
+                    "Query reads all {} scopes with {} without WHERE or LIMIT",
+                    collection.scopes, parsed.target
+                ),
+            ));
+        }
+        None
+    }
+}
+
+/// Lint `query` with the default thresholds
+pub fn lint_query(query: &str, scopes: &[Scope], repo_root: &Path) -> LintReport {
+    QueryLinter::default().lint(query, scopes, repo_root)
+}
+
+/// What the query's target looks like across the scopes it reads
+struct Collection {
+    scopes: usize,
+    rows: usize,
+    fields: BTreeSet<String>,
+}
+
+impl Collection {
+    /// `None` when the target cannot be resolved statically
+    fn read(parsed: &CqlQuery, scopes: &[Scope], repo_root: &Path) -> Option<Self> {
+        if parsed.target.contains('*') {
+            return None;
+        }
+        let targets = resolve_target_scopes(&parsed.target, scopes, repo_root).ok()?;
+
+        let mut fields: BTreeSet<String> = SCHEMA_FIELDS
+            .iter()
+            .find(|(file, _)| *file == parsed.target)
+            .map(|(_, fields)| fields.iter().map(|f| f.to_string()).collect())
+            .unwrap_or_default();
+        let mut collection_scopes = 0;
+        let mut rows = 0;
+        for scope in targets {
+            let Some(file) = scope.get_file(&format!("{}.yaml", parsed.target)) else {
+                continue;
+            };
+            let Some(data) = std::fs::read_to_string(file)
+                .ok()
+                .and_then(|content| serde_yaml::from_str::<Value>(&content).ok())
+            else {
+                continue;
+            };
+            let data = match &parsed.yaml_path {
+                Some(path) => match extract_yaml_path(&data, path) {
+                    Ok(data) => data,
+                    Err(_) => continue,
+                },
+                None => data,
+            };
+
+            collection_scopes += 1;
+            match &data {
+                Value::Sequence(items) => {
+                    rows += items.len();
+                    for item in items {
+                        fields.extend(keys_of(item));
+                    }
+                }
+                other => {
+                    rows += 1;
+                    fields.extend(keys_of(other));
+                }
+            }
+        }
+
+        Some(Self {
+            scopes: collection_scopes,
+            rows,
+            fields,
+        })
+    }
+}
+
+fn keys_of(value: &Value) -> impl Iterator<Item = String> + '_ {
+    value
+        .as_mapping()
+        .into_iter()
+        .flat_map(|map| map.keys().filter_map(|k| k.as_str().map(str::to_string)))
+}
+
+/// Flag WHERE and ORDER BY fields the collection does not have
+fn check_fields(parsed: &CqlQuery, collection: &Collection) -> Vec<LintFinding> {
+    if collection.fields.is_empty() {
+        return Vec::new();
+    }
+    let used: BTreeSet<&str> = parsed
+        .conditions
+        .iter()
+        .map(|c| c.field.as_str())
+        .chain(parsed.order_by.iter().flatten().map(|o| o.field.as_str()))
+        .collect();
+
+    used.into_iter()
+        .filter_map(|field| {
+            let head = field.split('.').next().unwrap_or(field);
+            if collection.fields.contains(head) {
+                return None;
+            }
+            let closest = collection
+                .fields
+                .iter()
+                .map(|known| (edit_distance(head, known), known))
+                .filter(|(distance, _)| *distance <= MAX_TYPO_DISTANCE && *distance < head.len())
+                .min();
+            Some(match closest {
+                Some((_, known)) => LintFinding::new(
+                    LintRule::UnknownField,
+                    LintSeverity::Warning,
+                    format!(
+                        "{} has no field '{}'; did you mean '{}'?",
+                        parsed.target, head, known
+                    ),
+                )
+                .suggest(replace_word(&parsed.query, head, known)),
+                None => LintFinding::new(
+                    LintRule::UnknownField,
+                    LintSeverity::Warning,
+                    format!(
+                        "{} has no field '{}'; conditions on it never match",
+                        parsed.target, head
+                    ),
+                ),
+            })
+        })
+        .collect()
+}
+
+/// Rewrite deprecated forms, returning the modern query and a finding for
+/// every rewrite
+fn modernize(query: &str) -> (String, Vec<LintFinding>) {
+    let mut findings = Vec::new();
+    let mut query = query.to_string();
+
+    let legacy = Regex::new(r"(?i)^(\w+)\s+(?:all\s+)?(\S+)(.*)$").unwrap();
+    if let Some(captures) = legacy.captures(&query) {
+        let verb = captures[1].to_lowercase();
+        if LEGACY_VERBS.contains(&verb.as_str()) {
+            let rewritten = format!("{}{}", &captures[2], &captures[3]);
+            findings.push(
+                LintFinding::new(
+                    LintRule::DeprecatedSyntax,
+                    LintSeverity::Error,
+                    format!(
+                        "The '{} <target>' form is no longer supported; start with the target",
+                        verb
+                    ),
+                )
+                .suggest(rewritten.clone()),
+            );
+            query = rewritten;
+        }
+    }
+
+    let keywords = Regex::new(r"(?i)\s(where|order\s+by|limit|offset)\s").unwrap();
+    let lowercase: Vec<_> = keywords
+        .captures_iter(&mask_quoted(&query))
+        .filter_map(|c| c.get(1))
+        .filter(|m| m.as_str() != m.as_str().to_uppercase())
+        .map(|m| m.range())
+        .collect();
+    if !lowercase.is_empty() {
+        for range in lowercase.iter().rev() {
+            let upper = query[range.clone()].to_uppercase();
+            query.replace_range(range.clone(), &upper);
+        }
+        findings.push(
+            LintFinding::new(
+                LintRule::DeprecatedSyntax,
+                LintSeverity::Error,
+                "Clause keywords must be upper case (WHERE, ORDER BY, LIMIT, OFFSET)".to_string(),
+            )
+            .suggest(query.clone()),
+        );
+    }
+
+    // `a <> b` parses as `a < "> b"`, which is never what was meant
+    let not_equal = Regex::new(r"<>").unwrap();
+    let ranges: Vec<_> = not_equal
+        .find_iter(&mask_quoted(&query))
+        .map(|m| m.range())
+        .collect();
+    if !ranges.is_empty() {
+        for range in ranges.into_iter().rev() {
+            query.replace_range(range, "!=");
+        }
+        findings.push(
+            LintFinding::new(
+                LintRule::DeprecatedSyntax,
+                LintSeverity::Error,
+                "'<>' is not an operator and is read as '<'; use '!='".to_string(),
+            )
+            .suggest(query.clone()),
+        );
+    }
+
+    let null_comparison = Regex::new(r"(?i)\s(!=|=)\s*null\b").unwrap();
+    let comparisons: Vec<_> = null_comparison
+        .captures_iter(&mask_quoted(&query))
+        .map(|c| (c.get(0).unwrap().range(), c[1].to_string()))
+        .collect();
+    if !comparisons.is_empty() {
+        for (range, operator) in comparisons.into_iter().rev() {
+            let replacement = if operator == "!=" {
+                " IS NOT NULL"
+            } else {
+                " IS NULL"
+            };
+            query.replace_range(range, replacement);
+        }
+        findings.push(
+            LintFinding::new(
+                LintRule::DeprecatedSyntax,
+                LintSeverity::Warning,
+                "Comparing with '= null' is deprecated; use IS NULL or IS NOT NULL".to_string(),
+            )
+            .suggest(query.clone()),
+        );
+    }
+
+    (query, findings)
+}
+
+/// `query` with the contents of quoted values blanked out, byte for byte, so
+/// keyword matches never land inside a value
+fn mask_quoted(query: &str) -> String {
+    let mut quote = None;
+    let masked: Vec<u8> = query
+        .bytes()
+        .map(|b| match quote {
+            Some(q) if b == q => {
+                quote = None;
+                b
+            }
+            Some(_) => b'_',
+            None => {
+                if b == b'\'' || b == b'"' {
+                    quote = Some(b);
+                }
+                b
+            }
+        })
+        .collect();
+    String::from_utf8(masked).unwrap_or_else(|_| query.to_string())
+}
+
+fn replace_word(query: &str, word: &str, replacement: &str) -> String {
+    let pattern = format!(r"\b{}\b", regex::escape(word));
+    Regex::new(&pattern)
+        .map(|re| re.replace_all(query, replacement).into_owned())
+        .unwrap_or_else(|_| query.to_string())
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn scope_with_todos(root: &Path, name: &str, count: usize) -> Scope {
+        let rhema = root.join(name).join(".rhema");
+        std::fs::create_dir_all(&rhema).unwrap();
+        std::fs::write(
+            rhema.join("rhema.yaml"),
+            format!("name: {}\nscope_type: service\nversion: \"1.0.0\"\n", name),
+        )
+        .unwrap();
+        let todos: String = (0..count)
+            .map(|i| {
+                format!(
+                    "  - id: t{}\n    title: Todo {}\n    status: pending\n",
+                    i, i
+                )
+            })
+            .collect();
+        std::fs::write(rhema.join("todos.yaml"), format!("todos:\n{}", todos)).unwrap();
+        Scope::new(rhema).unwrap()
+    }
+
+    #[test]
+    fn test_deprecated_syntax_is_rewritten_and_checked() {
+        let temp = TempDir::new().unwrap();
+        let scopes = vec![scope_with_todos(temp.path(), "api", 2)];
+
+        let report = lint_query(
+            "find todos where stauts = 'where pending'",
+            &scopes,
+            temp.path(),
+        );
+        let rules: Vec<_> = report.findings.iter().map(|f| f.rule).collect();
+        assert_eq!(
+            rules,
+            vec![
+                LintRule::DeprecatedSyntax,
+                LintRule::DeprecatedSyntax,
+                LintRule::UnknownField
+            ]
+        );
+        assert!(report.has_errors());
+        assert_eq!(
+            report.findings[1].suggestion.as_deref(),
+            Some("todos WHERE stauts = 'where pending'")
+        );
+        assert_eq!(
+            report.findings[2].suggestion.as_deref(),
+            Some("todos WHERE status = 'where pending'")
+        );
+
+        let report = lint_query("todos WHERE owner <> null", &scopes, temp.path());
+        assert_eq!(
+            report.findings[1].suggestion.as_deref(),
+            Some("todos WHERE owner IS NOT NULL")
+        );
+    }
+
+    #[test]
+    fn test_size_rules_follow_estimated_rows() {
+        let temp = TempDir::new().unwrap();
+        let scopes = vec![
+            scope_with_todos(temp.path(), "api", 3),
+            scope_with_todos(temp.path(), "web", 3),
+        ];
+        let linter = QueryLinter::new().with_large_result_rows(5);
+
+        let report = linter.lint("todos", &scopes, temp.path());
+        assert_eq!(report.estimated_rows, Some(6));
+        assert_eq!(report.findings[0].rule, LintRule::LargeResult);
+        assert_eq!(
+            report.findings[0].suggestion.as_deref(),
+            Some("todos LIMIT 5")
+        );
+
+        let report = linter.lint("todos WHERE status != 'done'", &scopes, temp.path());
+        assert_eq!(report.findings[0].rule, LintRule::LargeResult);
+
+        let report = QueryLinter::new().lint("todos", &scopes, temp.path());
+        assert_eq!(report.findings[0].rule, LintRule::UnboundedQuery);
+        assert!(!report.has_errors());
+
+        assert!(linter
+            .lint(
+                "todos WHERE status = 'pending' LIMIT 2",
+                &scopes,
+                temp.path()
+            )
+            .is_clean());
+    }
+}
@@ -0,0 +1,396 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Memory-mapped read path for hot queries.
+//!
+//! Daemons answering thousands of queries a minute spend most of their time
+//! deserializing whole context files only to drop most entries again. With
+//! [`QueryExecutionConfig::mapped_reads`](crate::query::QueryExecutionConfig)
+//! set, a scope's target file is memory-mapped instead of read into a buffer,
+//! and simple filters (`=`, `!=`, `IN` and `NOT IN` on string, boolean or null
+//! values, joined by `AND`) are evaluated by scanning the mapped text: entries
+//! of the targeted list are delimited by indentation and their filter fields
+//! compared as borrowed slices, so only matching entries are deserialized.
+//!
+//! The scanner gives up whenever it cannot vouch for the answer the full
+//! parser would give (anchors, tags, flow collections, quoted keys, scalars
+//! that may resolve to numbers, entries awaiting review, missing fields), and
+//! the query falls back to parsing the mapped text with `serde_yaml`. A file
+//! that is malformed outside the scanned list is not detected on the fast
+//! path; `rhema validate` reports it.
+
+use crate::query::{Condition, ConditionValue, CqlQuery, LogicalOperator, Operator};
+use memmap2::Mmap;
+use rhema_core::review::REVIEW_STATE_FIELD;
+use rhema_core::RhemaError;
+use serde_yaml::Value;
+use std::fs::File;
+use std::path::Path;
+
+/// A context file mapped into memory
+pub struct MappedFile {
+    /// `None` for empty files, which cannot be mapped
+    map: Option<Mmap>,
+}
+
+impl MappedFile {
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        let file = File::open(path)?;
+        if file.metadata()?.len() == 0 {
+            return Ok(Self { map: None });
+        }
+        // Context files are replaced by rename rather than rewritten in
+        // place, so a live mapping keeps seeing the version it opened
+        let map = unsafe { Mmap::map(&file)? };
+        Ok(Self { map: Some(map) })
+    }
+
+    /// The file content, borrowed from the mapping
+    pub fn as_str(&self) -> std::io::Result<&str> {
+        let bytes = self.map.as_deref().unwrap_or_default();
+        std::str::from_utf8(bytes)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))
+    }
+}
+
+/// Entries of the queried list that pass the query's conditions, or `None`
+/// when the query or the file needs the full parser
+pub fn scan_matches<'a>(content: &'a str, query: &CqlQuery) -> Option<Vec<&'a str>> {
+    let list = query.yaml_path.as_deref()?;
+    if !is_simple_key(list) || !is_simple_filter(&query.conditions) {
+        return None;
+    }
+    // Unreviewed entries are hidden by the parser-based path only
+    if content.contains(REVIEW_STATE_FIELD) || !content.lines().all(is_plain_line) {
+        return None;
+    }
+
+    let mut matches = Vec::new();
+    for entry in list_entries(content, list)? {
+        let mut keep = true;
+        for condition in &query.conditions {
+            let value = resolve_scalar(entry_field(entry, &condition.field)?)?;
+            keep &= condition_holds(&value, condition);
+        }
+        if keep {
+            matches.push(entry);
+        }
+    }
+    Some(matches)
+}
+
+/// Deserialize matched entries into the sequence the full path would produce
+pub fn parse_matches(matches: &[&str], file_path: &Path) -> Result<Value, RhemaError> {
+    let mut yaml = String::with_capacity(matches.iter().map(|entry| entry.len() + 1).sum());
+    for entry in matches {
+        yaml.push_str(entry);
+        if !entry.ends_with('\n') {
+            yaml.push('\n');
+        }
+    }
+    if yaml.is_empty() {
+        return Ok(Value::Sequence(Vec::new()));
+    }
+    serde_yaml::from_str(&yaml).map_err(|e| RhemaError::InvalidYaml {
+        file: file_path.display().to_string(),
+        message: e.to_string(),
+    })
+}
+
+fn is_simple_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn is_simple_filter(conditions: &[Condition]) -> bool {
+    let simple_value = |value: &ConditionValue| {
+        matches!(
+            value,
+            ConditionValue::String(_) | ConditionValue::Boolean(_) | ConditionValue::Null
+        )
+    };
+    !conditions.is_empty()
+        && conditions.iter().all(|condition| {
+            is_simple_key(&condition.field)
+                && condition.logical_op == LogicalOperator::And
+                && match (&condition.operator, &condition.value) {
+                    (Operator::Equals | Operator::NotEquals, value) => simple_value(value),
+                    (Operator::In | Operator::NotIn, ConditionValue::Array(items)) => {
+                        items.iter().all(simple_value)
+                    }
+                    _ => false,
+                }
+        })
+}
+
+/// Reject lines using YAML features the scanner does not model: tabs,
+/// documents and directives, complex keys, anchors, aliases and tags
+fn is_plain_line(line: &str) -> bool {
+    if line.contains('\t') || line.starts_with("---") || line.starts_with("...") {
+        return false;
+    }
+    if line.starts_with('%') {
+        return false;
+    }
+    let mut rest = line.trim_start();
+    while let Some(item) = rest
+        .strip_prefix('-')
+        .filter(|r| r.is_empty() || r.starts_with(' '))
+    {
+        rest = item.trim_start();
+    }
+    if rest.starts_with('?') {
+        return false;
+    }
+    let value = rest
+        .split_once(": ")
+        .map_or(rest, |(_, value)| value)
+        .trim_start();
+    !value.starts_with(['&', '*', '!'])
+}
+
+fn indent(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+/// Blank and comment lines never delimit entries
+fn is_content(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    !trimmed.is_empty() && !trimmed.starts_with('#')
+}
+
+/// Key of a `key:` or `key: value` line, and its value
+fn split_key(line: &str) -> Option<(&str, &str)> {
+    let (key, value) = line.split_once(':')?;
+    (value.is_empty() || value.starts_with(' ')).then(|| (key, value.trim()))
+}
+
+/// Source slices of the entries of a top-level block sequence, each starting
+/// at its `-` line; `None` unless `list` is defined exactly once as one
+fn list_entries<'a>(content: &'a str, list: &str) -> Option<Vec<&'a str>> {
+    let mut offset = 0;
+    let mut lines = Vec::new();
+    for line in content.split_inclusive('\n') {
+        lines.push((offset, line.trim_end_matches(['\n', '\r'])));
+        offset += line.len();
+    }
+
+    let mut heads = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, line))| indent(line) == 0 && is_content(line))
+        .filter(|(_, (_, line))| split_key(line).is_some_and(|(key, _)| key == list));
+    let (head, (_, head_line)) = heads.next()?;
+    if heads.next().is_some() {
+        return None;
+    }
+    // Comments after the key are fine; `[]`, `~` or anything else is not a block list
+    let (_, inline) = split_key(head_line)?;
+    if !(inline.is_empty() || inline.starts_with('#')) {
+        return None;
+    }
+
+    let mut starts = Vec::new();
+    let mut end = content.len();
+    let mut dash_indent = None;
+    for &(offset, line) in &lines[head + 1..] {
+        if !is_content(line) {
+            continue;
+        }
+        let depth = indent(line);
+        let is_item = &line[depth..] == "-" || line[depth..].starts_with("- ");
+        match dash_indent {
+            None if is_item => dash_indent = Some(depth),
+            None => return None,
+            Some(dash) if depth == dash && is_item => {}
+            Some(dash) if depth > dash => continue,
+            Some(_) if depth == 0 => {
+                end = offset;
+                break;
+            }
+            Some(_) => return None,
+        }
+        starts.push(offset);
+    }
+    // A key with nothing under it holds null, not an empty list
+    dash_indent?;
+
+    Some(
+        starts
+            .iter()
+            .enumerate()
+            .map(|(i, &start)| &content[start..starts.get(i + 1).copied().unwrap_or(end)])
+            .collect(),
+    )
+}
+
+/// Raw value of a field in a block-mapping entry; `None` when the field is
+/// missing, repeated, or the entry is not a block mapping
+fn entry_field<'a>(entry: &'a str, field: &str) -> Option<&'a str> {
+    let mut lines = entry.lines().filter(|line| is_content(line));
+    let first = lines.next()?;
+    let after_dash = &first[indent(first) + 1..];
+    let inline = after_dash.trim_start();
+
+    // Keys sit where the first key after `- ` starts, or on the next line
+    let (key_indent, mut candidates): (usize, Vec<&str>) = if inline.is_empty() {
+        let next = lines.next()?;
+        (indent(next), vec![&next[indent(next)..]])
+    } else {
+        (first.len() - inline.len(), vec![inline])
+    };
+    candidates.extend(
+        lines
+            .filter(|line| indent(line) == key_indent)
+            .map(|line| &line[key_indent..]),
+    );
+
+    let mut values = candidates
+        .iter()
+        .filter_map(|line| split_key(line))
+        .filter(|(key, _)| *key == field)
+        .map(|(_, value)| value);
+    let value = values.next()?;
+    values.next().is_none().then_some(value)
+}
+
+/// A scalar as the parser would resolve it
+#[derive(Debug, PartialEq)]
+enum Scalar<'a> {
+    Null,
+    Bool(bool),
+    Str(&'a str),
+}
+
+/// Resolve a raw field value; `None` for block or flow values, escapes, and
+/// plain scalars that may be numbers
+fn resolve_scalar(raw: &str) -> Option<Scalar<'_>> {
+    let raw = match raw.find(" #") {
+        Some(comment) => raw[..comment].trim_end(),
+        None => raw,
+    };
+    if raw.is_empty() {
+        return None;
+    }
+
+    for quote in ['"', '\''] {
+        if let Some(rest) = raw.strip_prefix(quote) {
+            let inner = rest.strip_suffix(quote)?;
+            return (!inner.contains([quote, '\\'])).then_some(Scalar::Str(inner));
+        }
+    }
+
+    if raw.starts_with(|c: char| c.is_ascii_digit() || "+-.&*!|>{}[]@`%?,#".contains(c))
+        || raw.contains(": ")
+        || raw.ends_with(':')
+    {
+        return None;
+    }
+    Some(match raw {
+        "~" | "null" | "Null" | "NULL" => Scalar::Null,
+        "true" | "True" | "TRUE" => Scalar::Bool(true),
+        "false" | "False" | "FALSE" => Scalar::Bool(false),
+        _ => Scalar::Str(raw),
+    })
+}
+
+fn scalar_equals(value: &Scalar, expected: &ConditionValue) -> bool {
+    match (value, expected) {
+        (Scalar::Null, ConditionValue::Null) => true,
+        (Scalar::Bool(a), ConditionValue::Boolean(b)) => a == b,
+        (Scalar::Str(a), ConditionValue::String(b)) => a == b,
+        _ => false,
+    }
+}
+
+fn condition_holds(value: &Scalar, condition: &Condition) -> bool {
+    let any = |items: &[ConditionValue]| items.iter().any(|item| scalar_equals(value, item));
+    match (&condition.operator, &condition.value) {
+        (Operator::Equals, expected) => scalar_equals(value, expected),
+        (Operator::NotEquals, expected) => !scalar_equals(value, expected),
+        (Operator::In, ConditionValue::Array(items)) => any(items),
+        (Operator::NotIn, ConditionValue::Array(items)) => !any(items),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::parse_cql_query;
+
+    const TODOS: &str = "\
+# Team todos
+todos:
+  - id: T-1
+    title: \"Ship: the release\"
+    status: pending
+    tags: [a, b]
+  - id: T-2
+    status: 'completed'  # done
+    notes: |
+      - not an entry
+      status: not a field
+  - id: T-3
+    status: pending
+owner: platform
+";
+
+    #[test]
+    fn test_scan_matches_the_full_parser() {
+        let query = parse_cql_query("todos.todos WHERE status='pending'").unwrap();
+        let matches = scan_matches(TODOS, &query).unwrap();
+        let scanned = parse_matches(&matches, Path::new("todos.yaml")).unwrap();
+
+        let parsed: Value = serde_yaml::from_str(TODOS).unwrap();
+        let expected: Vec<Value> = parsed["todos"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .filter(|todo| todo["status"] == "pending")
+            .cloned()
+            .collect();
+        assert_eq!(scanned, Value::Sequence(expected));
+
+        let query = parse_cql_query("todos WHERE status IN (completed, blocked)").unwrap();
+        let matches = scan_matches(TODOS, &query).unwrap();
+        assert_eq!(matches.len(), 1);
+        assert!(matches[0].contains("T-2"));
+    }
+
+    #[test]
+    fn test_scan_falls_back_when_unsure() {
+        let query = parse_cql_query("todos.todos WHERE status='pending'").unwrap();
+        for content in [
+            "todos:\n  - &first\n    status: pending\n",
+            "todos:\n  - id: T-1\n    status: pending\n    review_state: pending\n",
+            "todos:\n  - id: T-1\n",
+            "todos:\n  - {id: T-1, status: pending}\n",
+            "todos:\n  - id: T-1\n    status: 1.0\n",
+            "todos: []\n",
+            "todos:\n  - id: T-1\n    status: pending\ntodos:\n  - id: T-2\n",
+        ] {
+            assert!(scan_matches(content, &query).is_none(), "{}", content);
+        }
+
+        let numeric = parse_cql_query("todos.todos WHERE priority=3").unwrap();
+        assert!(scan_matches(TODOS, &numeric).is_none());
+        let or = parse_cql_query("todos.todos WHERE status='pending' OR status='blocked'").unwrap();
+        assert!(scan_matches(TODOS, &or).is_none());
+    }
+}
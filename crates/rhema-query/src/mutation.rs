@@ -0,0 +1,684 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! CQL mutation statements.
+//!
+//! ```text
+//! INSERT INTO todos (title, status) VALUES ('Write docs', 'pending')
+//! UPDATE todos SET status='completed', outcome='Shipped' WHERE id='T-12'
+//! DELETE FROM todos WHERE status='cancelled'
+//! ```
+//!
+//! UPDATE and DELETE without WHERE change every row of every permitted
+//! scope, so they are refused unless [`MutationOptions::all_rows`] is set.
+//!
+//! Mutations are always planned first. A [`MutationPlan`] holds the rows that
+//! would change and the rewritten files, which are validated against the
+//! scope schema before anything is written. [`apply_mutation`] refuses to
+//! write if a file changed since it was planned.
+
+use crate::query::{matches_conditions, parse_enhanced_conditions, Condition};
+use regex::Regex;
+use rhema_core::file_ops::write_yaml_file;
+use rhema_core::schema::Validatable;
+use rhema_core::{
+    scope::Scope, Conventions, Decisions, Knowledge, Patterns, RhemaError, RhemaResult, Todos,
+};
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+static INSERT_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?is)^INSERT\s+INTO\s+(\S+)\s*\((.+?)\)\s*VALUES\s*\((.+)\)$").unwrap()
+});
+static UPDATE_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?is)^UPDATE\s+(\S+)\s+SET\s+(.+?)(?:\s+WHERE\s+(.+))?$").unwrap()
+});
+static DELETE_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)^DELETE\s+FROM\s+(\S+)(?:\s+WHERE\s+(.+))?$").unwrap());
+
+/// Default number of rows a mutation may touch without confirmation
+pub const DEFAULT_MAX_ROWS: usize = 10;
+
+/// Kind of mutation statement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MutationKind {
+    Insert,
+    Update,
+    Delete,
+}
+
+/// Parsed mutation statement
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CqlMutation {
+    pub statement: String,
+    pub kind: MutationKind,
+    /// Target file (without `.yaml`)
+    pub target: String,
+    /// Sequence within the file holding the rows
+    pub yaml_path: String,
+    /// Field assignments for INSERT and UPDATE
+    pub assignments: Vec<(String, Value)>,
+    /// WHERE conditions for UPDATE and DELETE
+    pub conditions: Vec<Condition>,
+}
+
+/// Guardrails applied when planning a mutation
+#[derive(Debug, Clone)]
+pub struct MutationOptions {
+    /// Scopes the mutation may touch (relative scope directories); empty
+    /// means any scope
+    pub allowed_scopes: Vec<String>,
+    /// Rows that may change before `confirmed` is required
+    pub max_rows: usize,
+    /// Whether the caller confirmed a mutation above `max_rows`
+    pub confirmed: bool,
+    /// Whether UPDATE and DELETE may omit WHERE and change every row
+    pub all_rows: bool,
+}
+
+impl Default for MutationOptions {
+    fn default() -> Self {
+        Self {
+            allowed_scopes: Vec::new(),
+            max_rows: DEFAULT_MAX_ROWS,
+            confirmed: false,
+            all_rows: false,
+        }
+    }
+}
+
+/// A single row affected by a mutation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RowChange {
+    pub scope: String,
+    pub file: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+/// Rewritten contents of one file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlannedFile {
+    pub path: PathBuf,
+    #[serde(skip)]
+    original: String,
+    pub contents: Value,
+}
+
+/// Changes a mutation would make, validated but not yet written
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MutationPlan {
+    pub mutation: CqlMutation,
+    pub changes: Vec<RowChange>,
+    pub files: Vec<PlannedFile>,
+}
+
+impl MutationPlan {
+    pub fn affected_rows(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Human-readable preview of the changes, one block per row
+    pub fn render_preview(&self) -> String {
+        let mut out = String::new();
+        for change in &self.changes {
+            let (marker, label) = match (&change.before, &change.after) {
+                (None, Some(_)) => ('+', "insert"),
+                (Some(_), None) => ('-', "delete"),
+                _ => ('~', "update"),
+            };
+            out.push_str(&format!(
+                "{} {} {}/{}\n",
+                marker, label, change.scope, change.file
+            ));
+            if let Some(before) = &change.before {
+                push_yaml(&mut out, '-', before);
+            }
+            if let Some(after) = &change.after {
+                push_yaml(&mut out, '+', after);
+            }
+        }
+        out
+    }
+}
+
+fn push_yaml(out: &mut String, marker: char, value: &Value) {
+    let yaml = serde_yaml::to_string(value).unwrap_or_default();
+    for line in yaml.lines().filter(|l| *l != "---") {
+        out.push_str(&format!("    {} {}\n", marker, line));
+    }
+}
+
+/// Whether a statement is a mutation rather than a query
+pub fn is_mutation(statement: &str) -> bool {
+    let upper = statement.trim_start().to_uppercase();
+    ["INSERT ", "UPDATE ", "DELETE "]
+        .iter()
+        .any(|kw| upper.starts_with(kw))
+}
+
+/// Parse an INSERT, UPDATE or DELETE statement
+pub fn parse_cql_mutation(statement: &str) -> RhemaResult<CqlMutation> {
+    let statement = statement.trim();
+    let invalid = || RhemaError::InvalidQuery(format!("Invalid mutation syntax: {}", statement));
+
+    let (kind, target, assignments, where_clause) =
+        if let Some(c) = INSERT_PATTERN.captures(statement) {
+            let fields = split_list(&c[2]);
+            let values = split_list(&c[3]);
+            if fields.len() != values.len() {
+                return Err(RhemaError::InvalidQuery(format!(
+                    "INSERT lists {} fields but {} values",
+                    fields.len(),
+                    values.len()
+                )));
+            }
+            let assignments = fields
+                .into_iter()
+                .zip(values.iter().map(|v| parse_literal(v)))
+                .collect();
+            (MutationKind::Insert, c[1].to_string(), assignments, None)
+        } else if let Some(c) = UPDATE_PATTERN.captures(statement) {
+            let assignments = split_list(&c[2])
+                .iter()
+                .map(|assignment| {
+                    let (field, value) = assignment.split_once('=').ok_or_else(invalid)?;
+                    Ok((field.trim().to_string(), parse_literal(value)))
+                })
+                .collect::<RhemaResult<Vec<_>>>()?;
+            let where_clause = c.get(3).map(|m| m.as_str().to_string());
+            (
+                MutationKind::Update,
+                c[1].to_string(),
+                assignments,
+                where_clause,
+            )
+        } else if let Some(c) = DELETE_PATTERN.captures(statement) {
+            let where_clause = c.get(2).map(|m| m.as_str().to_string());
+            (
+                MutationKind::Delete,
+                c[1].to_string(),
+                Vec::new(),
+                where_clause,
+            )
+        } else {
+            return Err(invalid());
+        };
+
+    if assignments.iter().any(|(field, _)| field.is_empty()) {
+        return Err(invalid());
+    }
+
+    let (target, yaml_path) = match target.split_once('.') {
+        Some((file, path)) => (file.to_string(), path.to_string()),
+        None => (target.clone(), target),
+    };
+    let conditions = match where_clause {
+        Some(clause) => parse_enhanced_conditions(&clause)?,
+        None => Vec::new(),
+    };
+
+    Ok(CqlMutation {
+        statement: statement.to_string(),
+        kind,
+        target,
+        yaml_path,
+        assignments,
+        conditions,
+    })
+}
+
+/// Compute and validate the changes a mutation would make without writing
+pub fn plan_mutation(
+    repo_root: &Path,
+    statement: &str,
+    options: &MutationOptions,
+) -> RhemaResult<MutationPlan> {
+    let mutation = parse_cql_mutation(statement)?;
+    let file_name = format!("{}.yaml", mutation.target);
+    if mutation.kind != MutationKind::Insert && mutation.conditions.is_empty() && !options.all_rows
+    {
+        let keyword = if mutation.kind == MutationKind::Update {
+            "UPDATE"
+        } else {
+            "DELETE"
+        };
+        return Err(RhemaError::SafetyViolation(format!(
+            "{} without WHERE changes every row of {}; add a WHERE clause or allow all rows",
+            keyword, file_name
+        )));
+    }
+    let scopes = rhema_core::scope::discover_scopes(repo_root)?;
+
+    let mut candidates = Vec::new();
+    for scope in &scopes {
+        let Some(path) = scope.get_file(&file_name) else {
+            continue;
+        };
+        let name = scope_name(scope, repo_root)?;
+        if options.allowed_scopes.is_empty()
+            || options
+                .allowed_scopes
+                .iter()
+                .any(|allowed| normalize_scope(allowed) == name)
+        {
+            candidates.push((name, path.clone()));
+        }
+    }
+
+    if candidates.is_empty() {
+        return Err(RhemaError::ScopeNotFound(format!(
+            "No permitted scope contains {}",
+            file_name
+        )));
+    }
+    if mutation.kind == MutationKind::Insert && candidates.len() > 1 {
+        return Err(RhemaError::InvalidInput(format!(
+            "INSERT into {} matches {} scopes; restrict it to one scope",
+            file_name,
+            candidates.len()
+        )));
+    }
+
+    let mut plan = MutationPlan {
+        mutation,
+        changes: Vec::new(),
+        files: Vec::new(),
+    };
+    for (scope, path) in candidates {
+        plan_file(&mut plan, &scope, &path)?;
+    }
+
+    if plan.affected_rows() > options.max_rows && !options.confirmed {
+        return Err(RhemaError::SafetyViolation(format!(
+            "Mutation affects {} rows, more than the limit of {}; confirm to proceed",
+            plan.affected_rows(),
+            options.max_rows
+        )));
+    }
+
+    Ok(plan)
+}
+
+/// Write a planned mutation through the validated write path
+pub fn apply_mutation(plan: &MutationPlan) -> RhemaResult<usize> {
+    for file in &plan.files {
+        let current = std::fs::read_to_string(&file.path)?;
+        if current != file.original {
+            return Err(RhemaError::ContextConflict(format!(
+                "{} changed since the mutation was planned",
+                file.path.display()
+            )));
+        }
+    }
+    for file in &plan.files {
+        write_yaml_file(&file.path, &file.contents)?;
+    }
+    Ok(plan.affected_rows())
+}
+
+fn plan_file(plan: &mut MutationPlan, scope: &str, path: &Path) -> RhemaResult<()> {
+    let mutation = &plan.mutation;
+    let original = std::fs::read_to_string(path)?;
+    let mut document: Value =
+        serde_yaml::from_str(&original).map_err(|e| RhemaError::InvalidYaml {
+            file: path.display().to_string(),
+            message: e.to_string(),
+        })?;
+
+    let rows = rows_mut(&mut document, &mutation.yaml_path, path)?;
+    let file = format!("{}.yaml", mutation.target);
+    let mut changes = Vec::new();
+
+    match mutation.kind {
+        MutationKind::Insert => {
+            let mut row = Mapping::new();
+            for (field, value) in &mutation.assignments {
+                row.insert(Value::String(field.clone()), value.clone());
+            }
+            fill_insert_defaults(&mut row);
+            let row = Value::Mapping(row);
+            rows.push(row.clone());
+            changes.push(RowChange {
+                scope: scope.to_string(),
+                file: file.clone(),
+                before: None,
+                after: Some(row),
+            });
+        }
+        MutationKind::Update => {
+            for row in rows.iter_mut() {
+                if !matches_conditions(row, &mutation.conditions)? {
+                    continue;
+                }
+                let before = row.clone();
+                let Value::Mapping(map) = row else {
+                    continue;
+                };
+                for (field, value) in &mutation.assignments {
+                    map.insert(Value::String(field.clone()), value.clone());
+                }
+                if *row != before {
+                    changes.push(RowChange {
+                        scope: scope.to_string(),
+                        file: file.clone(),
+                        before: Some(before),
+                        after: Some(row.clone()),
+                    });
+                }
+            }
+        }
+        MutationKind::Delete => {
+            let mut kept = Vec::with_capacity(rows.len());
+            for row in rows.drain(..) {
+                if matches_conditions(&row, &mutation.conditions)? {
+                    changes.push(RowChange {
+                        scope: scope.to_string(),
+                        file: file.clone(),
+                        before: Some(row),
+                        after: None,
+                    });
+                } else {
+                    kept.push(row);
+                }
+            }
+            *rows = kept;
+        }
+    }
+
+    if changes.is_empty() {
+        return Ok(());
+    }
+    validate_document(&mutation.target, &document, path)?;
+
+    plan.changes.extend(changes);
+    plan.files.push(PlannedFile {
+        path: path.to_path_buf(),
+        original,
+        contents: document,
+    });
+    Ok(())
+}
+
+/// The sequence of rows at `yaml_path`, created if the key is missing
+fn rows_mut<'a>(
+    document: &'a mut Value,
+    yaml_path: &str,
+    path: &Path,
+) -> RhemaResult<&'a mut Vec<Value>> {
+    let not_a_list = || {
+        RhemaError::InvalidQuery(format!(
+            "{} in {} is not a list of entries",
+            yaml_path,
+            path.display()
+        ))
+    };
+
+    let mut current = document;
+    for segment in yaml_path.split('.') {
+        let Value::Mapping(map) = current else {
+            return Err(not_a_list());
+        };
+        current = map
+            .entry(Value::String(segment.to_string()))
+            .or_insert_with(|| Value::Sequence(Vec::new()));
+    }
+    if current.is_null() {
+        *current = Value::Sequence(Vec::new());
+    }
+    match current {
+        Value::Sequence(rows) => Ok(rows),
+        _ => Err(not_a_list()),
+    }
+}
+
+/// Entries of the built-in context files need an id and creation time
+fn fill_insert_defaults(row: &mut Mapping) {
+    let id = Value::String("id".to_string());
+    if !row.contains_key(&id) {
+        row.insert(id, Value::String(uuid::Uuid::new_v4().to_string()));
+    }
+    let created_at = Value::String("created_at".to_string());
+    if !row.contains_key(&created_at) {
+        row.insert(created_at, Value::String(chrono::Utc::now().to_rfc3339()));
+    }
+}
+
+/// Check the rewritten document against the schema of known context files
+fn validate_document(target: &str, document: &Value, path: &Path) -> RhemaResult<()> {
+    fn check<T: serde::de::DeserializeOwned + Validatable>(
+        document: &Value,
+        path: &Path,
+    ) -> RhemaResult<()> {
+        let typed: T = serde_yaml::from_value(document.clone()).map_err(|e| {
+            RhemaError::SchemaValidation(format!(
+                "Mutation would make {} invalid: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        typed.validate()
+    }
+
+    match target {
+        "todos" => check::<Todos>(document, path),
+        "knowledge" => check::<Knowledge>(document, path),
+        "decisions" => check::<Decisions>(document, path),
+        "patterns" => check::<Patterns>(document, path),
+        "conventions" => check::<Conventions>(document, path),
+        _ => Ok(()),
+    }
+}
+
+/// Scope directory relative to the repository root (`.` for the root scope)
+fn scope_name(scope: &Scope, repo_root: &Path) -> RhemaResult<String> {
+    Ok(normalize_scope(&scope.relative_path(repo_root)?))
+}
+
+fn normalize_scope(scope: &str) -> String {
+    let scope = scope.trim().trim_start_matches("./").trim_end_matches('/');
+    let scope = scope
+        .strip_suffix(".rhema")
+        .unwrap_or(scope)
+        .trim_end_matches('/');
+    if scope.is_empty() {
+        ".".to_string()
+    } else {
+        scope.to_string()
+    }
+}
+
+/// Split a comma-separated list, ignoring commas inside quotes
+fn split_list(list: &str) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+    for c in list.chars() {
+        match (c, quote) {
+            ('\'' | '"', None) => quote = Some(c),
+            (q, Some(open)) if q == open => quote = None,
+            (',', None) => {
+                items.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    if !current.trim().is_empty() {
+        items.push(current.trim().to_string());
+    }
+    items
+}
+
+/// Parse a literal from a SET or VALUES list
+fn parse_literal(literal: &str) -> Value {
+    let literal = literal.trim();
+    if literal.len() >= 2
+        && ((literal.starts_with('\'') && literal.ends_with('\''))
+            || (literal.starts_with('"') && literal.ends_with('"')))
+    {
+        return Value::String(literal[1..literal.len() - 1].to_string());
+    }
+    match literal.to_lowercase().as_str() {
+        "true" => return Value::Bool(true),
+        "false" => return Value::Bool(false),
+        "null" => return Value::Null,
+        _ => {}
+    }
+    if let Ok(int) = literal.parse::<i64>() {
+        return Value::Number(int.into());
+    }
+    if let Ok(float) = literal.parse::<f64>() {
+        return Value::Number(float.into());
+    }
+    Value::String(literal.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const TODOS: &str = r#"todos:
+  - id: T-1
+    title: First
+    status: pending
+    priority: medium
+    created_at: "2025-01-01T00:00:00Z"
+  - id: T-2
+    title: Second
+    status: pending
+    priority: low
+    created_at: "2025-01-02T00:00:00Z"
+"#;
+
+    fn setup() -> (TempDir, PathBuf) {
+        let temp = TempDir::new().unwrap();
+        let scope = temp.path().join(".rhema");
+        fs::create_dir_all(&scope).unwrap();
+        fs::write(
+            scope.join("rhema.yaml"),
+            "name: test\nscope_type: service\nversion: \"1.0.0\"\n",
+        )
+        .unwrap();
+        let todos = scope.join("todos.yaml");
+        fs::write(&todos, TODOS).unwrap();
+        (temp, todos)
+    }
+
+    #[test]
+    fn test_parse_statements() {
+        let update = parse_cql_mutation(
+            "UPDATE todos SET status='completed', outcome='Done, finally' WHERE id='T-12'",
+        )
+        .unwrap();
+        assert_eq!(update.kind, MutationKind::Update);
+        assert_eq!(update.yaml_path, "todos");
+        assert_eq!(update.assignments.len(), 2);
+        assert_eq!(
+            update.assignments[1].1,
+            Value::String("Done, finally".to_string())
+        );
+        assert_eq!(update.conditions.len(), 1);
+
+        let insert =
+            parse_cql_mutation("insert into todos (title, status) values ('New', 'pending')")
+                .unwrap();
+        assert_eq!(insert.kind, MutationKind::Insert);
+        assert!(parse_cql_mutation("INSERT INTO todos (a, b) VALUES (1)").is_err());
+        assert!(is_mutation("DELETE FROM todos WHERE id='x'"));
+        assert!(!is_mutation("todos WHERE status='pending'"));
+    }
+
+    #[test]
+    fn test_dry_run_then_apply_update() {
+        let (temp, todos) = setup();
+        let plan = plan_mutation(
+            temp.path(),
+            "UPDATE todos SET status='completed', completed_at='2025-01-03T00:00:00Z' WHERE id='T-1'",
+            &MutationOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(plan.affected_rows(), 1);
+        assert!(plan.render_preview().contains("+ status: completed"));
+        // Planning alone does not write
+        assert_eq!(fs::read_to_string(&todos).unwrap(), TODOS);
+
+        apply_mutation(&plan).unwrap();
+        let written: Todos = serde_yaml::from_str(&fs::read_to_string(&todos).unwrap()).unwrap();
+        assert_eq!(written.todos[0].status, rhema_core::TodoStatus::Completed);
+    }
+
+    #[test]
+    fn test_guardrails() {
+        let (temp, todos) = setup();
+        // Without WHERE every row changes, which must be asked for
+        assert!(plan_mutation(temp.path(), "DELETE FROM todos", &Default::default()).is_err());
+        assert!(plan_mutation(
+            temp.path(),
+            "UPDATE todos SET status='completed'",
+            &Default::default()
+        )
+        .is_err());
+
+        let options = MutationOptions {
+            max_rows: 1,
+            all_rows: true,
+            ..Default::default()
+        };
+        assert!(plan_mutation(temp.path(), "DELETE FROM todos", &options).is_err());
+        let confirmed = MutationOptions {
+            confirmed: true,
+            ..options
+        };
+        assert_eq!(
+            plan_mutation(temp.path(), "DELETE FROM todos", &confirmed)
+                .unwrap()
+                .affected_rows(),
+            2
+        );
+
+        let restricted = MutationOptions {
+            allowed_scopes: vec!["services/api".to_string()],
+            all_rows: true,
+            ..Default::default()
+        };
+        assert!(plan_mutation(temp.path(), "DELETE FROM todos", &restricted).is_err());
+
+        // Invalid values are rejected by schema validation
+        assert!(plan_mutation(
+            temp.path(),
+            "UPDATE todos SET status='bogus' WHERE id='T-1'",
+            &MutationOptions::default()
+        )
+        .is_err());
+
+        // Concurrent edits invalidate a plan
+        let plan = plan_mutation(
+            temp.path(),
+            "DELETE FROM todos WHERE id='T-2'",
+            &MutationOptions::default(),
+        )
+        .unwrap();
+        fs::write(&todos, TODOS.replace("First", "Edited")).unwrap();
+        assert!(apply_mutation(&plan).is_err());
+    }
+}
@@ -14,14 +14,20 @@
  * limitations under the License.
  */
 
+use crate::mapped::{self, MappedFile};
 use crate::search::{SearchEngine, SearchFilter, SearchOptions, SearchType};
 use chrono::{DateTime, NaiveDateTime, Utc};
+use rayon::prelude::*;
 use regex::Regex;
+use rhema_core::profiling::{self, Phase};
+use rhema_core::review;
+use rhema_core::roots::{RepositoryRoot, PRIMARY_ROOT};
 use rhema_core::{scope::Scope, RhemaError};
 use serde::{Deserialize, Serialize};
 use serde_yaml::Value;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex};
 
 /// Provenance information for query execution
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,6 +47,10 @@ pub struct QueryProvenance {
     /// Scopes that were searched
     pub scopes_searched: Vec<String>,
 
+    /// Repository roots the searched scopes live in, `.` for the main one
+    #[serde(default)]
+    pub roots_searched: Vec<String>,
+
     /// Files that were accessed
     pub files_accessed: Vec<String>,
 
@@ -335,6 +345,12 @@ pub enum OrderDirection {
     Desc,
 }
 
+/// Result metadata key naming the repository root the scope lives in
+pub const ROOT_METADATA_KEY: &str = "root";
+
+/// Result metadata key holding the kind of that root
+pub const ROOT_KIND_METADATA_KEY: &str = "root_kind";
+
 /// Query result with enhanced metadata and provenance
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct QueryResult {
@@ -353,20 +369,114 @@ pub struct QueryResult {
     pub metadata: HashMap<String, Value>,
 }
 
-/// Execute a CQL query
+/// Section of `.rhema/repository.yaml` configuring query execution
+pub const QUERY_CONFIG_SECTION: &str = "query";
+
+/// Worker pools by thread count, built on first use and shared by all queries
+static WORKER_POOLS: LazyLock<Mutex<HashMap<usize, Arc<rayon::ThreadPool>>>> =
+    LazyLock::new(Default::default);
+
+/// Worker pool settings for evaluating a query across scopes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QueryExecutionConfig {
+    /// Dedicated worker threads for per-scope evaluation; `None` uses rayon's global pool
+    pub worker_threads: Option<usize>,
+
+    /// Queries touching fewer scopes than this run on the calling thread
+    pub parallel_threshold: usize,
+
+    /// Memory-map target files and scan them for simple filters before parsing;
+    /// see [`crate::mapped`]
+    pub mapped_reads: bool,
+}
+
+impl Default for QueryExecutionConfig {
+    fn default() -> Self {
+        Self {
+            worker_threads: None,
+            parallel_threshold: 8,
+            mapped_reads: false,
+        }
+    }
+}
+
+impl QueryExecutionConfig {
+    /// Load the `query` section of the repository config, falling back to
+    /// defaults
+    pub fn load(repo_root: &Path) -> Result<Self, RhemaError> {
+        let value = rhema_core::policy::repository_config(repo_root)?;
+        match value.get(QUERY_CONFIG_SECTION) {
+            Some(section) => serde_yaml::from_value(section.clone()).map_err(|e| {
+                RhemaError::ConfigError(format!(
+                    "Invalid {} section in the repository config: {}",
+                    QUERY_CONFIG_SECTION, e
+                ))
+            }),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Evaluate every scope on the calling thread
+    pub fn sequential() -> Self {
+        Self {
+            worker_threads: Some(1),
+            parallel_threshold: usize::MAX,
+            mapped_reads: false,
+        }
+    }
+
+    /// Use a dedicated pool of `threads` workers
+    pub fn with_workers(threads: usize) -> Self {
+        Self::default().with_worker_threads(threads)
+    }
+
+    /// Replace the worker count, keeping the other settings
+    pub fn with_worker_threads(mut self, threads: usize) -> Self {
+        self.worker_threads = Some(threads.max(1));
+        self
+    }
+
+    /// Enable or disable the memory-mapped read path
+    pub fn with_mapped_reads(mut self, enabled: bool) -> Self {
+        self.mapped_reads = enabled;
+        self
+    }
+
+    fn runs_sequentially(&self, scope_count: usize) -> bool {
+        scope_count < self.parallel_threshold || self.worker_threads == Some(1)
+    }
+}
+
+/// Execute a CQL query with the repository's `query` configuration.
+///
+/// Results from several scopes are ordered by scope path.
 pub fn execute_query(repo_root: &Path, query: &str) -> Result<Value, RhemaError> {
+    execute_query_with_config(repo_root, query, &QueryExecutionConfig::load(repo_root)?)
+}
+
+/// Execute a CQL query with an explicit execution configuration. Results
+/// from several scopes are ordered by scope path.
+pub fn execute_query_with_config(
+    repo_root: &Path,
+    query: &str,
+    config: &QueryExecutionConfig,
+) -> Result<Value, RhemaError> {
     let parsed_query = parse_cql_query(query)?;
     let scopes = rhema_core::scope::discover_scopes(repo_root)?;
 
-    let results = execute_parsed_query(&parsed_query, &scopes, repo_root)?;
+    let results = execute_parsed_query_with_config(&parsed_query, &scopes, repo_root, config)?;
 
     // Convert results to a single Value
     if results.len() == 1 {
         Ok(results[0].data.clone())
     } else {
         let mut result_array = Vec::new();
-        for result in results {
+        for mut result in results {
             let mut result_obj = HashMap::new();
+            if let Some(root) = nested_root(&mut result) {
+                result_obj.insert(ROOT_METADATA_KEY.to_string(), root);
+            }
             result_obj.insert("scope".to_string(), Value::String(result.scope));
             result_obj.insert("file".to_string(), Value::String(result.file));
             result_obj.insert("path".to_string(), Value::String(result.path));
@@ -379,6 +489,31 @@ pub fn execute_query(repo_root: &Path, query: &str) -> Result<Value, RhemaError>
     }
 }
 
+/// Execute a parsed CQL query with the repository's `query` configuration and
+/// return the per-scope results, ordered by scope path, without flattening
+pub fn execute_query_results(
+    repo_root: &Path,
+    query: &CqlQuery,
+) -> Result<Vec<QueryResult>, RhemaError> {
+    let scopes = rhema_core::scope::discover_scopes(repo_root)?;
+    execute_parsed_query_with_config(
+        query,
+        &scopes,
+        repo_root,
+        &QueryExecutionConfig::load(repo_root)?,
+    )
+}
+
+/// Execute a parsed CQL query using an explicit worker pool configuration
+pub fn execute_query_results_with_config(
+    repo_root: &Path,
+    query: &CqlQuery,
+    config: &QueryExecutionConfig,
+) -> Result<Vec<QueryResult>, RhemaError> {
+    let scopes = rhema_core::scope::discover_scopes(repo_root)?;
+    execute_parsed_query_with_config(query, &scopes, repo_root, config)
+}
+
 /// Execute a CQL query with full provenance tracking
 pub fn execute_query_with_provenance(
     repo_root: &Path,
@@ -398,14 +533,15 @@ pub fn execute_query_with_provenance(
     let scope_duration = scope_start.elapsed().as_millis() as u64;
 
     // Execute query with provenance tracking
-    let results =
+    let mut results =
         execute_parsed_query_with_provenance(&parsed_query, &scopes, repo_root, &executed_at)?;
+    attribute_roots(&mut results, repo_root);
 
     // Calculate total execution time
     let total_duration = start_time.elapsed().as_millis() as u64;
 
     // Build provenance information
-    let provenance = build_query_provenance(
+    let mut provenance = build_query_provenance(
         query,
         &parsed_query,
         executed_at,
@@ -415,14 +551,18 @@ pub fn execute_query_with_provenance(
         parse_duration,
         scope_duration,
     )?;
+    provenance.roots_searched = roots_of(&scopes, repo_root);
 
     // Convert results to a single Value (same as original)
     let result_value = if results.len() == 1 {
         results[0].data.clone()
     } else {
         let mut result_array = Vec::new();
-        for result in results {
+        for mut result in results {
             let mut result_obj = HashMap::new();
+            if let Some(root) = nested_root(&mut result) {
+                result_obj.insert(ROOT_METADATA_KEY.to_string(), root);
+            }
             result_obj.insert("scope".to_string(), Value::String(result.scope));
             result_obj.insert("file".to_string(), Value::String(result.file));
             result_obj.insert("path".to_string(), Value::String(result.path));
@@ -506,7 +646,7 @@ fn parse_target(target: &str) -> Result<(String, Option<String>), RhemaError> {
 }
 
 /// Parse enhanced WHERE conditions with logical operators
-fn parse_enhanced_conditions(where_clause: &str) -> Result<Vec<Condition>, RhemaError> {
+pub(crate) fn parse_enhanced_conditions(where_clause: &str) -> Result<Vec<Condition>, RhemaError> {
     let mut conditions = Vec::new();
     let mut current_conditions = Vec::new();
 
@@ -733,7 +873,10 @@ fn parse_order_by(order_by_clause: &str) -> Result<Option<Vec<OrderBy>>, RhemaEr
 }
 
 /// Check if a value matches the given conditions
-fn matches_conditions(value: &Value, conditions: &[Condition]) -> Result<bool, RhemaError> {
+pub(crate) fn matches_conditions(
+    value: &Value,
+    conditions: &[Condition],
+) -> Result<bool, RhemaError> {
     if conditions.is_empty() {
         return Ok(true);
     }
@@ -888,57 +1031,194 @@ fn execute_parsed_query(
     scopes: &[Scope],
     repo_root: &Path,
 ) -> Result<Vec<QueryResult>, RhemaError> {
-    let mut results = Vec::new();
+    execute_parsed_query_with_config(query, scopes, repo_root, &QueryExecutionConfig::default())
+}
+
+/// Execute a parsed query, spreading per-scope work across the configured worker pool
+pub fn execute_parsed_query_with_config(
+    query: &CqlQuery,
+    scopes: &[Scope],
+    repo_root: &Path,
+    config: &QueryExecutionConfig,
+) -> Result<Vec<QueryResult>, RhemaError> {
+    let _phase = profiling::phase(Phase::Query);
 
     // Determine which scopes to query
     let target_scopes = resolve_target_scopes(&query.target, scopes, repo_root)?;
 
-    for scope in target_scopes {
-        if let Some(file_path) = scope.get_file(&format!("{}.yaml", query.target)) {
-            let content = std::fs::read_to_string(file_path).map_err(|e| RhemaError::IoError(e))?;
+    let mut results = run_per_scope(target_scopes, config, |scope| {
+        query_scope(query, scope, repo_root, config)
+    })?;
+    attribute_roots(&mut results, repo_root);
+    Ok(results)
+}
 
-            let yaml_data: Value =
-                serde_yaml::from_str(&content).map_err(|e| RhemaError::InvalidYaml {
-                    file: file_path.display().to_string(),
-                    message: e.to_string(),
-                })?;
+/// Record the repository root each result's scope lives in
+fn attribute_roots(results: &mut [QueryResult], repo_root: &Path) {
+    for result in results {
+        let root = RepositoryRoot::containing(repo_root, &repo_root.join(&result.scope));
+        result
+            .metadata
+            .insert(ROOT_METADATA_KEY.to_string(), Value::String(root.relative));
+        result.metadata.insert(
+            ROOT_KIND_METADATA_KEY.to_string(),
+            Value::String(root.kind.to_string()),
+        );
+    }
+}
 
-            // Apply YAML path if specified
-            let mut filtered_data = if let Some(ref yaml_path) = query.yaml_path {
-                extract_yaml_path(&yaml_data, yaml_path)?
-            } else {
-                yaml_data
-            };
+/// The root of a result from a submodule or nested repository, for flattened output
+fn nested_root(result: &mut QueryResult) -> Option<Value> {
+    result
+        .metadata
+        .remove(ROOT_METADATA_KEY)
+        .filter(|root| root.as_str() != Some(PRIMARY_ROOT))
+}
 
-            // Apply WHERE conditions
-            filtered_data = apply_conditions(&filtered_data, &query.conditions)?;
+/// Distinct roots of the given scopes, main repository first
+fn roots_of(scopes: &[Scope], repo_root: &Path) -> Vec<String> {
+    let mut roots: Vec<String> = scopes
+        .iter()
+        .map(|scope| RepositoryRoot::containing(repo_root, &scope.path).relative)
+        .collect();
+    roots.sort_by(|a, b| (a != PRIMARY_ROOT, a).cmp(&(b != PRIMARY_ROOT, b)));
+    roots.dedup();
+    roots
+}
 
-            // Apply ORDER BY if specified
-            if let Some(ref order_by) = query.order_by {
-                filtered_data = apply_order_by(&filtered_data, order_by)?;
-            }
+/// Load one scope's target file and apply the query to it
+fn query_scope(
+    query: &CqlQuery,
+    scope: &Scope,
+    repo_root: &Path,
+    config: &QueryExecutionConfig,
+) -> Result<Option<QueryResult>, RhemaError> {
+    let Some(file_path) = scope.get_file(&format!("{}.yaml", query.target)) else {
+        return Ok(None);
+    };
 
-            // Apply LIMIT and OFFSET
-            filtered_data = apply_limit_offset(&filtered_data, query.limit, query.offset)?;
+    let mut filtered_data = if config.mapped_reads {
+        let file = {
+            let _phase = profiling::phase(Phase::FileIo);
+            MappedFile::open(file_path).map_err(RhemaError::IoError)?
+        };
+        let content = file.as_str().map_err(RhemaError::IoError)?;
+        let scanned = {
+            let _phase = profiling::phase(Phase::Parse);
+            mapped::scan_matches(content, query)
+                .map(|matches| mapped::parse_matches(&matches, file_path))
+                .transpose()?
+        };
+        match scanned {
+            Some(data) => data,
+            None => apply_conditions(&target_data(content, file_path, query)?, &query.conditions)?,
+        }
+    } else {
+        let content = {
+            let _phase = profiling::phase(Phase::FileIo);
+            std::fs::read_to_string(file_path).map_err(RhemaError::IoError)?
+        };
+        apply_conditions(&target_data(&content, file_path, query)?, &query.conditions)?
+    };
 
-            if !filtered_data.is_null() {
-                let scope_rel_path = scope.relative_path(repo_root)?;
-                results.push(QueryResult {
-                    scope: scope_rel_path,
-                    file: format!("{}.yaml", query.target),
-                    data: filtered_data,
-                    path: query.yaml_path.clone().unwrap_or_default(),
-                    field_provenance: HashMap::new(),
-                    query_provenance: None,
-                    metadata: HashMap::new(),
-                });
+    // Apply ORDER BY if specified
+    if let Some(ref order_by) = query.order_by {
+        filtered_data = apply_order_by(&filtered_data, order_by)?;
+    }
+
+    // Apply LIMIT and OFFSET
+    filtered_data = apply_limit_offset(&filtered_data, query.limit, query.offset)?;
+
+    if filtered_data.is_null() {
+        return Ok(None);
+    }
+
+    Ok(Some(QueryResult {
+        scope: scope.relative_path(repo_root)?,
+        file: format!("{}.yaml", query.target),
+        data: filtered_data,
+        path: query.yaml_path.clone().unwrap_or_default(),
+        field_provenance: HashMap::new(),
+        query_provenance: None,
+        metadata: HashMap::new(),
+    }))
+}
+
+/// Parse a target file and select the query's YAML path from it
+fn target_data(content: &str, file_path: &Path, query: &CqlQuery) -> Result<Value, RhemaError> {
+    let mut yaml_data: Value = {
+        let _phase = profiling::phase(Phase::Parse);
+        serde_yaml::from_str(content).map_err(|e| RhemaError::InvalidYaml {
+            file: file_path.display().to_string(),
+            message: e.to_string(),
+        })?
+    };
+    // Entries awaiting review are only listed by `rhema review`
+    review::hide_unreviewed(&mut yaml_data);
+
+    // Apply YAML path if specified
+    match query.yaml_path {
+        Some(ref yaml_path) => extract_yaml_path(&yaml_data, yaml_path),
+        None => Ok(yaml_data),
+    }
+}
+
+/// Run `per_scope` over every scope and merge the results ordered by scope path.
+///
+/// Small scope sets run on the calling thread. Larger ones are evaluated in
+/// parallel; results are collected by index, so output order (and which error
+/// is reported when several scopes fail) matches sequential execution.
+fn run_per_scope<F>(
+    mut scopes: Vec<&Scope>,
+    config: &QueryExecutionConfig,
+    per_scope: F,
+) -> Result<Vec<QueryResult>, RhemaError>
+where
+    F: Fn(&Scope) -> Result<Option<QueryResult>, RhemaError> + Sync,
+{
+    // Directory walk order is filesystem dependent, so fix the order up front
+    scopes.sort_by(|a, b| a.path.cmp(&b.path));
+
+    let outcomes: Vec<Result<Option<QueryResult>, RhemaError>> =
+        if config.runs_sequentially(scopes.len()) {
+            scopes.iter().map(|scope| per_scope(scope)).collect()
+        } else {
+            let run = || scopes.par_iter().map(|scope| per_scope(scope)).collect();
+            match config.worker_threads {
+                Some(threads) => worker_pool(threads)?.install(run),
+                None => run(),
             }
+        };
+
+    let mut results = Vec::new();
+    for outcome in outcomes {
+        if let Some(result) = outcome? {
+            results.push(result);
         }
     }
-
     Ok(results)
 }
 
+/// The shared pool of `threads` workers, started by the first query using it
+fn worker_pool(threads: usize) -> Result<Arc<rayon::ThreadPool>, RhemaError> {
+    let mut pools = WORKER_POOLS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(pool) = pools.get(&threads) {
+        return Ok(pool.clone());
+    }
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .thread_name(|i| format!("rhema-query-{}", i))
+        .build()
+        .map(Arc::new)
+        .map_err(|e| {
+            RhemaError::ConfigError(format!("Failed to start query worker pool: {}", e))
+        })?;
+    pools.insert(threads, pool.clone());
+    Ok(pool)
+}
+
 /// Execute a parsed query with full provenance tracking
 fn execute_parsed_query_with_provenance(
     query: &CqlQuery,
@@ -946,108 +1226,107 @@ fn execute_parsed_query_with_provenance(
     repo_root: &Path,
     executed_at: &DateTime<Utc>,
 ) -> Result<Vec<QueryResult>, RhemaError> {
-    let mut results = Vec::new();
-
     // Determine which scopes to query
     let target_scopes = resolve_target_scopes(&query.target, scopes, repo_root)?;
 
-    for scope in target_scopes {
-        if let Some(file_path) = scope.get_file(&format!("{}.yaml", query.target)) {
-            let content = std::fs::read_to_string(file_path).map_err(|e| RhemaError::IoError(e))?;
-
-            let yaml_data: Value =
-                serde_yaml::from_str(&content).map_err(|e| RhemaError::InvalidYaml {
-                    file: file_path.display().to_string(),
-                    message: e.to_string(),
-                })?;
-
-            // Track field-level provenance
-            let mut field_provenance = HashMap::new();
-
-            // Apply YAML path if specified
-            let mut filtered_data = if let Some(ref yaml_path) = query.yaml_path {
-                let path_data = extract_yaml_path(&yaml_data, yaml_path)?;
-                // Track field provenance for YAML path extraction
-                track_field_provenance(
-                    &mut field_provenance,
-                    &yaml_data,
-                    &path_data,
-                    Some(yaml_path),
-                    scope,
-                    &query.target,
-                    executed_at,
-                )?;
-                path_data
-            } else {
-                // Track field provenance for all fields
-                track_field_provenance(
-                    &mut field_provenance,
-                    &yaml_data,
-                    &yaml_data,
-                    None,
-                    scope,
-                    &query.target,
-                    executed_at,
-                )?;
-                yaml_data
-            };
-
-            // Apply WHERE conditions with provenance tracking
-            let before_conditions = filtered_data.clone();
-            filtered_data = apply_conditions(&filtered_data, &query.conditions)?;
-            track_condition_provenance(
+    run_per_scope(target_scopes, &QueryExecutionConfig::default(), |scope| {
+        let Some(file_path) = scope.get_file(&format!("{}.yaml", query.target)) else {
+            return Ok(None);
+        };
+        let content = std::fs::read_to_string(file_path).map_err(RhemaError::IoError)?;
+
+        let mut yaml_data: Value =
+            serde_yaml::from_str(&content).map_err(|e| RhemaError::InvalidYaml {
+                file: file_path.display().to_string(),
+                message: e.to_string(),
+            })?;
+        review::hide_unreviewed(&mut yaml_data);
+
+        // Track field-level provenance
+        let mut field_provenance = HashMap::new();
+
+        // Apply YAML path if specified
+        let mut filtered_data = if let Some(ref yaml_path) = query.yaml_path {
+            let path_data = extract_yaml_path(&yaml_data, yaml_path)?;
+            // Track field provenance for YAML path extraction
+            track_field_provenance(
                 &mut field_provenance,
-                &before_conditions,
-                &filtered_data,
-                &query.conditions,
+                &yaml_data,
+                &path_data,
+                Some(yaml_path),
+                scope,
+                &query.target,
                 executed_at,
             )?;
+            path_data
+        } else {
+            // Track field provenance for all fields
+            track_field_provenance(
+                &mut field_provenance,
+                &yaml_data,
+                &yaml_data,
+                None,
+                scope,
+                &query.target,
+                executed_at,
+            )?;
+            yaml_data
+        };
 
-            // Apply ORDER BY if specified
-            if let Some(ref order_by) = query.order_by {
-                let before_ordering = filtered_data.clone();
-                filtered_data = apply_order_by(&filtered_data, order_by)?;
-                track_ordering_provenance(
-                    &mut field_provenance,
-                    &before_ordering,
-                    &filtered_data,
-                    order_by,
-                    executed_at,
-                )?;
-            }
-
-            // Apply LIMIT and OFFSET
-            let before_limit = filtered_data.clone();
-            filtered_data = apply_limit_offset(&filtered_data, query.limit, query.offset)?;
-            track_limit_provenance(
+        // Apply WHERE conditions with provenance tracking
+        let before_conditions = filtered_data.clone();
+        filtered_data = apply_conditions(&filtered_data, &query.conditions)?;
+        track_condition_provenance(
+            &mut field_provenance,
+            &before_conditions,
+            &filtered_data,
+            &query.conditions,
+            executed_at,
+        )?;
+
+        // Apply ORDER BY if specified
+        if let Some(ref order_by) = query.order_by {
+            let before_ordering = filtered_data.clone();
+            filtered_data = apply_order_by(&filtered_data, order_by)?;
+            track_ordering_provenance(
                 &mut field_provenance,
-                &before_limit,
+                &before_ordering,
                 &filtered_data,
-                query.limit,
-                query.offset,
+                order_by,
                 executed_at,
             )?;
+        }
 
-            if !filtered_data.is_null() {
-                let scope_rel_path = scope.relative_path(repo_root)?;
-                results.push(QueryResult {
-                    scope: scope_rel_path,
-                    file: format!("{}.yaml", query.target),
-                    data: filtered_data,
-                    path: query.yaml_path.clone().unwrap_or_default(),
-                    field_provenance,
-                    query_provenance: None,
-                    metadata: HashMap::new(),
-                });
-            }
+        // Apply LIMIT and OFFSET
+        let before_limit = filtered_data.clone();
+        filtered_data = apply_limit_offset(&filtered_data, query.limit, query.offset)?;
+        track_limit_provenance(
+            &mut field_provenance,
+            &before_limit,
+            &filtered_data,
+            query.limit,
+            query.offset,
+            executed_at,
+        )?;
+
+        if filtered_data.is_null() {
+            return Ok(None);
         }
-    }
 
-    Ok(results)
+        Ok(Some(QueryResult {
+            scope: scope.relative_path(repo_root)?,
+            file: format!("{}.yaml", query.target),
+            data: filtered_data,
+            path: query.yaml_path.clone().unwrap_or_default(),
+            field_provenance,
+            query_provenance: None,
+            metadata: HashMap::new(),
+        }))
+    })
 }
 
 /// Resolve target scopes based on query target
-fn resolve_target_scopes<'a>(
+pub(crate) fn resolve_target_scopes<'a>(
     target: &str,
     scopes: &'a [Scope],
     _repo_root: &Path,
@@ -1993,6 +2272,7 @@ fn build_query_provenance(
         executed_at,
         execution_time_ms: total_duration,
         scopes_searched: scopes.iter().map(|s| s.definition.name.clone()).collect(),
+        roots_searched: Vec::new(),
         files_accessed: results.iter().map(|r| r.file.clone()).collect(),
         execution_steps,
         applied_filters,
@@ -2000,3 +2280,153 @@ fn build_query_provenance(
         errors: None,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn repo_with_scopes(count: usize) -> TempDir {
+        let temp = TempDir::new().unwrap();
+        for i in 0..count {
+            let scope = temp.path().join(format!("svc-{:03}", i)).join(".rhema");
+            fs::create_dir_all(&scope).unwrap();
+            fs::write(
+                scope.join("rhema.yaml"),
+                format!(
+                    "name: svc-{:03}\nscope_type: service\nversion: \"1.0.0\"\n",
+                    i
+                ),
+            )
+            .unwrap();
+            fs::write(
+                scope.join("todos.yaml"),
+                format!(
+                    "todos:\n  - id: T-{i}-a\n    status: pending\n  - id: T-{i}-b\n    status: completed\n"
+                ),
+            )
+            .unwrap();
+        }
+        temp
+    }
+
+    #[test]
+    fn test_parallel_results_match_sequential_order() {
+        let temp = repo_with_scopes(24);
+        let query = parse_cql_query("todos.todos WHERE status='pending'").unwrap();
+
+        let sequential = execute_query_results_with_config(
+            temp.path(),
+            &query,
+            &QueryExecutionConfig::sequential(),
+        )
+        .unwrap();
+        let parallel = execute_query_results_with_config(
+            temp.path(),
+            &query,
+            &QueryExecutionConfig::with_workers(4),
+        )
+        .unwrap();
+
+        assert_eq!(sequential.len(), 24);
+        let scopes =
+            |results: &[QueryResult]| results.iter().map(|r| r.scope.clone()).collect::<Vec<_>>();
+        assert_eq!(scopes(&sequential), scopes(&parallel));
+        let mut sorted = scopes(&parallel);
+        sorted.sort();
+        assert_eq!(scopes(&parallel), sorted);
+        for (a, b) in sequential.iter().zip(&parallel) {
+            assert_eq!(a.data, b.data);
+        }
+    }
+
+    #[test]
+    fn test_parallel_reports_first_failing_scope() {
+        let temp = repo_with_scopes(12);
+        for i in [3, 9] {
+            fs::write(
+                temp.path()
+                    .join(format!("svc-{:03}", i))
+                    .join(".rhema")
+                    .join("todos.yaml"),
+                "todos: [unclosed",
+            )
+            .unwrap();
+        }
+        let query = parse_cql_query("todos").unwrap();
+
+        let err = execute_query_results_with_config(
+            temp.path(),
+            &query,
+            &QueryExecutionConfig::with_workers(4),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("svc-003"));
+    }
+
+    #[test]
+    fn test_worker_count_comes_from_repository_config() {
+        let temp = repo_with_scopes(1);
+        fs::create_dir_all(temp.path().join(".rhema")).unwrap();
+        fs::write(
+            temp.path().join(".rhema/repository.yaml"),
+            "query:\n  worker_threads: 3\n",
+        )
+        .unwrap();
+
+        let config = QueryExecutionConfig::load(temp.path()).unwrap();
+        assert_eq!(config.worker_threads, Some(3));
+        assert_eq!(
+            config.parallel_threshold,
+            QueryExecutionConfig::default().parallel_threshold
+        );
+
+        // Queries with the same worker count share one pool
+        assert!(Arc::ptr_eq(
+            &worker_pool(3).unwrap(),
+            &worker_pool(3).unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_unreviewed_entries_are_not_returned() {
+        let temp = repo_with_scopes(1);
+        fs::write(
+            temp.path().join("svc-000/.rhema/todos.yaml"),
+            "todos:\n  - id: T-a\n    status: pending\n  - id: T-b\n    status: pending\n    review_state: pending\n  - id: T-c\n    status: pending\n    review_state: approved\n",
+        )
+        .unwrap();
+
+        let result = execute_query(temp.path(), "todos.todos WHERE status='pending'").unwrap();
+        let ids: Vec<_> = result
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .map(|todo| todo["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(ids, ["T-a", "T-c"]);
+    }
+
+    #[test]
+    fn test_mapped_reads_match_standard_reads() {
+        let temp = repo_with_scopes(3);
+        // Anchors send this scope through the parser fallback
+        fs::write(
+            temp.path().join("svc-001/.rhema/todos.yaml"),
+            "todos:\n  - &first\n    id: T-x\n    status: pending\n",
+        )
+        .unwrap();
+
+        let mapped = QueryExecutionConfig::default().with_mapped_reads(true);
+        for query in [
+            "todos.todos WHERE status='pending'",
+            "todos.todos WHERE status!='pending' ORDER BY id DESC",
+            "todos",
+        ] {
+            let standard = execute_query(temp.path(), query).unwrap();
+            let scanned = execute_query_with_config(temp.path(), query, &mapped).unwrap();
+            assert_eq!(standard, scanned, "{}", query);
+        }
+    }
+}
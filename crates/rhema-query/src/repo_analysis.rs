@@ -413,6 +413,11 @@ impl RepoAnalysis {
             },
             custom: self.custom_fields.clone(),
             protocol_info: None,
+            ai_policy: None,
+            freshness: None,
+            toolchains: None,
+            runtime: None,
+            entry_templates: None,
         }
     }
 
@@ -0,0 +1,381 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Standing CQL queries that push incremental result updates.
+//!
+//! A subscriber registers a query once and receives a [`QueryDelta`] whenever
+//! a change to the files it reads alters its results. The manager keeps the
+//! last result set per subscription, so only subscriptions whose target file
+//! changed are re-run and only the differences are delivered.
+
+use crate::query::{execute_query_results, parse_cql_query, CqlQuery, QueryResult};
+use rhema_core::RhemaError;
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::mpsc;
+
+/// Files whose changes can alter which scopes a query sees
+const SCOPE_DEFINITION_FILES: &[&str] = &["rhema.yaml", "scope.yaml"];
+
+/// A single result item tracked by a subscription
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeltaItem {
+    /// Stable key: `<scope>/<file>#<entry id or index>`
+    pub key: String,
+    pub scope: String,
+    pub file: String,
+    pub value: Value,
+}
+
+/// Incremental change to a subscription's result set
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QueryDelta {
+    pub subscription_id: String,
+    pub added: Vec<DeltaItem>,
+    pub removed: Vec<DeltaItem>,
+    /// Items whose key is unchanged but whose value differs (new value)
+    pub changed: Vec<DeltaItem>,
+}
+
+impl QueryDelta {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Callback invoked with each non-empty delta
+pub type DeltaCallback = Arc<dyn Fn(&QueryDelta) + Send + Sync>;
+
+enum DeltaSink {
+    Channel(mpsc::UnboundedSender<QueryDelta>),
+    Callback(DeltaCallback),
+}
+
+impl DeltaSink {
+    /// Deliver a delta, returning false when the receiving side has gone away
+    fn deliver(&self, delta: &QueryDelta) -> bool {
+        match self {
+            DeltaSink::Channel(tx) => tx.send(delta.clone()).is_ok(),
+            DeltaSink::Callback(callback) => {
+                callback(delta);
+                true
+            }
+        }
+    }
+}
+
+struct Subscription {
+    query: CqlQuery,
+    sink: DeltaSink,
+    results: BTreeMap<String, DeltaItem>,
+}
+
+/// Registry of standing queries for one repository
+pub struct QuerySubscriptionManager {
+    repo_root: PathBuf,
+    subscriptions: Mutex<HashMap<String, Subscription>>,
+    next_id: AtomicU64,
+}
+
+impl QuerySubscriptionManager {
+    pub fn new(repo_root: impl Into<PathBuf>) -> Self {
+        Self {
+            repo_root: repo_root.into(),
+            subscriptions: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    pub fn repo_root(&self) -> &Path {
+        &self.repo_root
+    }
+
+    /// Register a query whose deltas are delivered over a channel.
+    ///
+    /// The first message on the channel contains the full initial result set
+    /// as `added` items (it is omitted when the query matches nothing).
+    pub fn subscribe(
+        &self,
+        query: &str,
+    ) -> Result<(String, mpsc::UnboundedReceiver<QueryDelta>), RhemaError> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let id = self.register(query, DeltaSink::Channel(tx))?;
+        Ok((id, rx))
+    }
+
+    /// Register a query whose deltas are passed to `callback`.
+    ///
+    /// The callback is invoked immediately with the initial result set.
+    pub fn subscribe_with_callback(
+        &self,
+        query: &str,
+        callback: DeltaCallback,
+    ) -> Result<String, RhemaError> {
+        self.register(query, DeltaSink::Callback(callback))
+    }
+
+    /// Remove a subscription, returning whether it existed
+    pub fn unsubscribe(&self, id: &str) -> bool {
+        self.lock().remove(id).is_some()
+    }
+
+    /// Number of active subscriptions
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Re-run every subscription affected by the given file changes and
+    /// deliver the resulting deltas. Returns the number of deltas delivered.
+    pub fn on_files_changed(&self, paths: &[PathBuf]) -> usize {
+        let affected: Vec<String> = self
+            .lock()
+            .iter()
+            .filter(|(_, sub)| paths.iter().any(|p| affects(&sub.query, p)))
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        affected.iter().filter(|id| self.refresh(id)).count()
+    }
+
+    /// Re-run a single subscription and deliver its delta if non-empty.
+    /// Returns true when a delta was delivered.
+    pub fn refresh(&self, id: &str) -> bool {
+        let query = match self.lock().get(id) {
+            Some(sub) => sub.query.clone(),
+            None => return false,
+        };
+
+        // Files are often observed mid-write; keep the previous results and
+        // wait for the next change rather than reporting a spurious removal.
+        let current = match execute_query_results(&self.repo_root, &query) {
+            Ok(results) => index_results(&results),
+            Err(e) => {
+                tracing::warn!("Subscription {} query failed: {}", id, e);
+                return false;
+            }
+        };
+
+        let mut subscriptions = self.lock();
+        let Some(sub) = subscriptions.get_mut(id) else {
+            return false;
+        };
+        let delta = compute_delta(id, &sub.results, &current);
+        sub.results = current;
+        if delta.is_empty() {
+            return false;
+        }
+
+        if let DeltaSink::Callback(callback) = &sub.sink {
+            // Release the lock before running user code so callbacks may
+            // (un)subscribe without deadlocking.
+            let callback = callback.clone();
+            drop(subscriptions);
+            callback(&delta);
+            return true;
+        }
+
+        if sub.sink.deliver(&delta) {
+            true
+        } else {
+            tracing::debug!("Subscription {} receiver dropped, removing", id);
+            subscriptions.remove(id);
+            false
+        }
+    }
+
+    fn register(&self, query: &str, sink: DeltaSink) -> Result<String, RhemaError> {
+        let parsed = parse_cql_query(query)?;
+        let results = index_results(&execute_query_results(&self.repo_root, &parsed)?);
+        let id = format!("sub-{}", self.next_id.fetch_add(1, Ordering::Relaxed));
+
+        let initial = compute_delta(&id, &BTreeMap::new(), &results);
+        if !initial.is_empty() {
+            sink.deliver(&initial);
+        }
+
+        self.lock().insert(
+            id.clone(),
+            Subscription {
+                query: parsed,
+                sink,
+                results,
+            },
+        );
+        Ok(id)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Subscription>> {
+        self.subscriptions
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Whether a change to `path` can alter the results of `query`
+fn affects(query: &CqlQuery, path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    name == format!("{}.yaml", query.target) || SCOPE_DEFINITION_FILES.contains(&name)
+}
+
+/// Flatten query results into keyed items. Sequences of mappings are keyed by
+/// their `id` field when present so reordering does not appear as changes.
+fn index_results(results: &[QueryResult]) -> BTreeMap<String, DeltaItem> {
+    let mut items = BTreeMap::new();
+    for result in results {
+        let prefix = format!("{}/{}", result.scope, result.file);
+        let mut insert = |suffix: String, value: &Value| {
+            let key = format!("{}#{}", prefix, suffix);
+            items.insert(
+                key.clone(),
+                DeltaItem {
+                    key,
+                    scope: result.scope.clone(),
+                    file: result.file.clone(),
+                    value: value.clone(),
+                },
+            );
+        };
+
+        match &result.data {
+            Value::Sequence(values) => {
+                for (index, value) in values.iter().enumerate() {
+                    let suffix = value
+                        .get("id")
+                        .and_then(Value::as_str)
+                        .map(str::to_string)
+                        .unwrap_or_else(|| index.to_string());
+                    insert(suffix, value);
+                }
+            }
+            value => insert(result.path.clone(), value),
+        }
+    }
+    items
+}
+
+fn compute_delta(
+    id: &str,
+    previous: &BTreeMap<String, DeltaItem>,
+    current: &BTreeMap<String, DeltaItem>,
+) -> QueryDelta {
+    let mut delta = QueryDelta {
+        subscription_id: id.to_string(),
+        ..Default::default()
+    };
+
+    for (key, item) in current {
+        match previous.get(key) {
+            None => delta.added.push(item.clone()),
+            Some(old) if old.value != item.value => delta.changed.push(item.clone()),
+            Some(_) => {}
+        }
+    }
+    for (key, item) in previous {
+        if !current.contains_key(key) {
+            delta.removed.push(item.clone());
+        }
+    }
+    delta
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write_scope(root: &Path, todos: &str) -> PathBuf {
+        let scope = root.join(".rhema");
+        fs::create_dir_all(&scope).unwrap();
+        fs::write(
+            scope.join("rhema.yaml"),
+            "name: test\nscope_type: service\nversion: \"1.0.0\"\n",
+        )
+        .unwrap();
+        let todos_path = scope.join("todos.yaml");
+        fs::write(&todos_path, todos).unwrap();
+        todos_path
+    }
+
+    #[test]
+    fn test_subscription_delivers_initial_and_incremental_deltas() {
+        let temp = TempDir::new().unwrap();
+        let todos_path = write_scope(
+            temp.path(),
+            "todos:\n  - id: a\n    title: First\n  - id: b\n    title: Second\n",
+        );
+
+        let manager = QuerySubscriptionManager::new(temp.path());
+        let (id, mut rx) = manager.subscribe("todos.todos").unwrap();
+
+        let initial = rx.try_recv().unwrap();
+        assert_eq!(initial.subscription_id, id);
+        assert_eq!(initial.added.len(), 2);
+
+        fs::write(
+            &todos_path,
+            "todos:\n  - id: b\n    title: Second (edited)\n  - id: c\n    title: Third\n",
+        )
+        .unwrap();
+        assert_eq!(manager.on_files_changed(std::slice::from_ref(&todos_path)), 1);
+
+        let delta = rx.try_recv().unwrap();
+        assert_eq!(delta.added.len(), 1);
+        assert!(delta.added[0].key.ends_with("#c"));
+        assert_eq!(delta.removed.len(), 1);
+        assert!(delta.removed[0].key.ends_with("#a"));
+        assert_eq!(delta.changed.len(), 1);
+        assert!(delta.changed[0].key.ends_with("#b"));
+    }
+
+    #[test]
+    fn test_unrelated_changes_are_ignored() {
+        let temp = TempDir::new().unwrap();
+        write_scope(temp.path(), "todos:\n  - id: a\n    title: First\n");
+
+        let manager = QuerySubscriptionManager::new(temp.path());
+        let (_id, mut rx) = manager.subscribe("todos.todos").unwrap();
+        rx.try_recv().unwrap();
+
+        let other = temp.path().join(".rhema").join("knowledge.yaml");
+        assert_eq!(manager.on_files_changed(&[other]), 0);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_dropped_receiver_removes_subscription() {
+        let temp = TempDir::new().unwrap();
+        let todos_path = write_scope(temp.path(), "todos:\n  - id: a\n    title: First\n");
+
+        let manager = QuerySubscriptionManager::new(temp.path());
+        let (_id, rx) = manager.subscribe("todos.todos").unwrap();
+        drop(rx);
+
+        fs::write(&todos_path, "todos: []\n").unwrap();
+        manager.on_files_changed(&[todos_path]);
+        assert!(manager.is_empty());
+    }
+}
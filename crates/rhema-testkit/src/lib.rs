@@ -0,0 +1,137 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Seeded synthetic Rhema repositories.
+//!
+//! A declarative [`RepoSpec`] describes the scopes of a repository, the
+//! distribution of todos, knowledge, decisions, patterns and conventions
+//! across them, and the git history that added them. The generator turns it
+//! into a [`SyntheticRepo`] held in memory, which benchmarks and load tests
+//! render directly, or into a [`TestRepo`] written to a temporary git
+//! repository for integration tests. The same spec and seed always produce
+//! the same repository, byte for byte.
+
+pub mod model;
+pub mod repo;
+pub mod rng;
+pub mod spec;
+mod words;
+
+pub use model::{EntryKind, SyntheticCommit, SyntheticRepo, SyntheticScope};
+pub use repo::TestRepo;
+pub use rng::SeededRng;
+pub use spec::{
+    AuthorSpec, Distribution, EntrySpec, HistorySpec, RepoSpec, ScopeLayout, ScopeSpec,
+};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rhema_core::scope::discover_scopes;
+
+    fn rendered(repo: &SyntheticRepo) -> Vec<String> {
+        repo.scopes
+            .iter()
+            .flat_map(|scope| scope.files().unwrap())
+            .map(|(name, content)| format!("{}\n{}", name, content))
+            .collect()
+    }
+
+    #[test]
+    fn test_same_seed_generates_same_repository() {
+        let spec = RepoSpec::from_yaml(
+            "seed: 7\nscopes:\n  count: 6\n  layout: nested\n  dependency_probability: 0.5\nentries:\n  todos: { min: 0, max: 12, skew: 2.0 }\n",
+        )
+        .unwrap();
+        let first = SyntheticRepo::generate(&spec).unwrap();
+        let second = SyntheticRepo::generate(&spec).unwrap();
+        assert_eq!(rendered(&first), rendered(&second));
+        assert_ne!(
+            rendered(&first),
+            rendered(&SyntheticRepo::generate(&spec.clone().with_seed(8)).unwrap())
+        );
+
+        assert_eq!(first.scopes.len(), 6);
+        for (index, scope) in first.scopes.iter().enumerate() {
+            assert!(scope.todos.len() <= 12);
+            assert!((1..=5).contains(&scope.knowledge.len()));
+            assert!(scope
+                .path
+                .starts_with(format!("{}s", scope.definition.scope_type)));
+            // Dependencies only point at earlier scopes
+            for dependency in scope.definition.dependencies.iter().flatten() {
+                let target = first
+                    .scopes
+                    .iter()
+                    .position(|other| other.path.to_string_lossy() == dependency.path)
+                    .unwrap();
+                assert!(target < index);
+            }
+        }
+
+        let added: usize = first.commits.iter().map(|c| c.added.len()).sum();
+        assert_eq!(added, first.total_entries());
+        assert!(first.commits.len() <= spec.history.commits);
+
+        // Growing the repository keeps the existing entries' text
+        let grown = SyntheticRepo::generate(&spec.clone().with_scopes(8).with_commits(9)).unwrap();
+        let titles = |repo: &SyntheticRepo| -> Vec<String> {
+            repo.scopes[..6]
+                .iter()
+                .flat_map(|scope| scope.todos.iter().map(|todo| todo.title.clone()))
+                .collect()
+        };
+        assert_eq!(titles(&grown), titles(&first));
+
+        assert!(RepoSpec::from_yaml("scopes:\n  count: 0\n").is_err());
+        assert!(RepoSpec::from_yaml("entries:\n  todos: { min: 5, max: 2 }\n").is_err());
+    }
+
+    #[test]
+    fn test_written_repository_replays_history() {
+        let spec = RepoSpec::default().with_scopes(4).with_commits(6);
+        let test_repo = TestRepo::generate(&spec).unwrap();
+        let repo = test_repo.repo();
+
+        let scopes = discover_scopes(test_repo.path()).unwrap();
+        assert_eq!(scopes.len(), 4);
+
+        let git = git2::Repository::open(test_repo.path()).unwrap();
+        let mut walk = git.revwalk().unwrap();
+        walk.push_head().unwrap();
+        let mut log: Vec<git2::Oid> = walk.map(Result::unwrap).collect();
+        log.reverse();
+        assert_eq!(log.len(), repo.commits.len());
+        assert_eq!(
+            log.iter().map(|id| id.to_string()).collect::<Vec<_>>(),
+            test_repo.commits()
+        );
+
+        for (id, planned) in log.iter().zip(&repo.commits) {
+            let commit = git.find_commit(*id).unwrap();
+            assert_eq!(commit.author().name(), Some(planned.author.name.as_str()));
+            assert_eq!(commit.time().seconds(), planned.timestamp.timestamp());
+        }
+
+        // The last commit leaves every scope with all of its entries
+        for scope in &repo.scopes {
+            let dir = test_repo.path().join(scope.rhema_dir());
+            for (file, content) in scope.files().unwrap() {
+                assert_eq!(std::fs::read_to_string(dir.join(file)).unwrap(), content);
+            }
+        }
+    }
+}
@@ -0,0 +1,559 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! In-memory synthetic repositories.
+//!
+//! [`SyntheticRepo::generate`] turns a [`RepoSpec`] into scopes, entries and
+//! a planned commit history without touching the filesystem. Benchmarks and
+//! load tests that only need realistic context render the scopes directly;
+//! integration tests write the repository out with
+//! [`SyntheticRepo::write_to`](crate::SyntheticRepo::write_to).
+//!
+//! The scope layout, the entry counts of each scope, every single entry and
+//! the history draw from their own streams of the seed, so adding scopes or
+//! commits leaves the titles and text of existing entries unchanged.
+
+use chrono::{DateTime, Duration, Utc};
+use rhema_core::schema::{
+    ConventionEntry, Conventions, DecisionEntry, DecisionStatus, Decisions, EnforcementLevel,
+    Knowledge, KnowledgeEntry, PatternEntry, PatternUsage, Patterns, Priority, RhemaScope,
+    ScopeDependency, TodoEntry, TodoStatus, Todos, CURRENT_SCHEMA_VERSION,
+};
+use rhema_core::RhemaResult;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
+
+use crate::rng::SeededRng;
+use crate::spec::{AuthorSpec, Distribution, EntrySpec, RepoSpec, ScopeLayout};
+use crate::words;
+
+const LAYOUT_STREAM: u64 = 0;
+const HISTORY_STREAM: u64 = 1;
+const SCOPE_STREAMS: u64 = 1 << 32;
+
+/// Stream of the entry counts of a scope, or of one of its entries
+fn scope_stream(scope: usize, entry: Option<(EntryKind, usize)>) -> u64 {
+    let entry = entry.map_or(0, |(kind, number)| {
+        ((kind as u64 + 1) << 20) + number as u64
+    });
+    SCOPE_STREAMS + ((scope as u64) << 24) + entry
+}
+
+const CATEGORIES: &[&str] = &[
+    "architecture",
+    "performance",
+    "security",
+    "testing",
+    "operations",
+];
+const TAGS: &[&str] = &[
+    "api",
+    "cache",
+    "ci",
+    "database",
+    "docs",
+    "observability",
+    "reliability",
+    "tooling",
+];
+const PATTERN_TYPES: &[&str] = &["architectural", "design", "testing", "operational"];
+const CONVENTION_TYPES: &[&str] = &["naming", "style", "testing", "documentation"];
+const TOOLS: &[&str] = &["rustfmt", "clippy", "eslint", "pre-commit"];
+
+/// Kind of context entry, one file per kind in a scope
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryKind {
+    Todo,
+    Knowledge,
+    Decision,
+    Pattern,
+    Convention,
+}
+
+impl EntryKind {
+    pub const ALL: [EntryKind; 5] = [
+        EntryKind::Todo,
+        EntryKind::Knowledge,
+        EntryKind::Decision,
+        EntryKind::Pattern,
+        EntryKind::Convention,
+    ];
+
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            EntryKind::Todo => "todos.yaml",
+            EntryKind::Knowledge => "knowledge.yaml",
+            EntryKind::Decision => "decisions.yaml",
+            EntryKind::Pattern => "patterns.yaml",
+            EntryKind::Convention => "conventions.yaml",
+        }
+    }
+
+    fn id_prefix(&self) -> &'static str {
+        match self {
+            EntryKind::Todo => "todo",
+            EntryKind::Knowledge => "knowledge",
+            EntryKind::Decision => "decision",
+            EntryKind::Pattern => "pattern",
+            EntryKind::Convention => "convention",
+        }
+    }
+
+    fn distribution(&self, entries: &EntrySpec) -> Distribution {
+        match self {
+            EntryKind::Todo => entries.todos,
+            EntryKind::Knowledge => entries.knowledge,
+            EntryKind::Decision => entries.decisions,
+            EntryKind::Pattern => entries.patterns,
+            EntryKind::Convention => entries.conventions,
+        }
+    }
+}
+
+/// A generated scope with its entries
+#[derive(Debug, Clone)]
+pub struct SyntheticScope {
+    /// Scope directory relative to the repository root, empty for a root scope
+    pub path: PathBuf,
+    pub definition: RhemaScope,
+    pub todos: Vec<TodoEntry>,
+    pub knowledge: Vec<KnowledgeEntry>,
+    pub decisions: Vec<DecisionEntry>,
+    pub patterns: Vec<PatternEntry>,
+    pub conventions: Vec<ConventionEntry>,
+}
+
+impl SyntheticScope {
+    pub fn name(&self) -> &str {
+        &self.definition.name
+    }
+
+    /// `.rhema` directory relative to the repository root
+    pub fn rhema_dir(&self) -> PathBuf {
+        self.path.join(".rhema")
+    }
+
+    pub fn entries(&self, kind: EntryKind) -> usize {
+        match kind {
+            EntryKind::Todo => self.todos.len(),
+            EntryKind::Knowledge => self.knowledge.len(),
+            EntryKind::Decision => self.decisions.len(),
+            EntryKind::Pattern => self.patterns.len(),
+            EntryKind::Convention => self.conventions.len(),
+        }
+    }
+
+    pub fn total_entries(&self) -> usize {
+        EntryKind::ALL.iter().map(|kind| self.entries(*kind)).sum()
+    }
+
+    /// Files of the scope's `.rhema` directory with their content; kinds
+    /// without entries have no file
+    pub fn files(&self) -> RhemaResult<Vec<(&'static str, String)>> {
+        self.files_with(&EntryKind::ALL.map(|kind| self.entries(kind)))
+    }
+
+    /// Files holding only the first `counts[kind]` entries of each kind
+    pub(crate) fn files_with(
+        &self,
+        counts: &[usize; 5],
+    ) -> RhemaResult<Vec<(&'static str, String)>> {
+        let mut files = vec![("rhema.yaml", serde_yaml::to_string(&self.definition)?)];
+        for kind in EntryKind::ALL {
+            let count = counts[kind as usize];
+            if count == 0 {
+                continue;
+            }
+            let content = match kind {
+                EntryKind::Todo => serde_yaml::to_string(&Todos {
+                    todos: self.todos[..count].to_vec(),
+                    custom: HashMap::new(),
+                })?,
+                EntryKind::Knowledge => serde_yaml::to_string(&Knowledge {
+                    entries: self.knowledge[..count].to_vec(),
+                    categories: None,
+                    custom: HashMap::new(),
+                })?,
+                EntryKind::Decision => serde_yaml::to_string(&Decisions {
+                    decisions: self.decisions[..count].to_vec(),
+                    custom: HashMap::new(),
+                })?,
+                EntryKind::Pattern => serde_yaml::to_string(&Patterns {
+                    patterns: self.patterns[..count].to_vec(),
+                    custom: HashMap::new(),
+                })?,
+                EntryKind::Convention => serde_yaml::to_string(&Conventions {
+                    conventions: self.conventions[..count].to_vec(),
+                    custom: HashMap::new(),
+                })?,
+            };
+            files.push((kind.file_name(), content));
+        }
+        Ok(files)
+    }
+
+    /// Every entry as a YAML document of its own, in file order
+    pub fn entry_documents(&self) -> RhemaResult<Vec<String>> {
+        let mut documents = Vec::with_capacity(self.total_entries());
+        for todo in &self.todos {
+            documents.push(serde_yaml::to_string(todo)?);
+        }
+        for entry in &self.knowledge {
+            documents.push(serde_yaml::to_string(entry)?);
+        }
+        for decision in &self.decisions {
+            documents.push(serde_yaml::to_string(decision)?);
+        }
+        for pattern in &self.patterns {
+            documents.push(serde_yaml::to_string(pattern)?);
+        }
+        for convention in &self.conventions {
+            documents.push(serde_yaml::to_string(convention)?);
+        }
+        Ok(documents)
+    }
+}
+
+/// A commit of the generated history
+#[derive(Debug, Clone)]
+pub struct SyntheticCommit {
+    pub author: AuthorSpec,
+    pub timestamp: DateTime<Utc>,
+    pub message: String,
+    /// Entries the commit adds, as scope index and kind
+    pub added: Vec<(usize, EntryKind)>,
+}
+
+/// Scopes, entries and history generated from a [`RepoSpec`]
+#[derive(Debug, Clone)]
+pub struct SyntheticRepo {
+    pub spec: RepoSpec,
+    pub scopes: Vec<SyntheticScope>,
+    /// Oldest first; the first commit also adds every scope definition
+    pub commits: Vec<SyntheticCommit>,
+}
+
+impl SyntheticRepo {
+    pub fn generate(spec: &RepoSpec) -> RhemaResult<Self> {
+        spec.validate()?;
+        let mut scopes = generate_scopes(spec);
+
+        let mut slots = Vec::new();
+        for index in 0..scopes.len() {
+            let mut rng = SeededRng::derive(spec.seed, scope_stream(index, None));
+            for kind in EntryKind::ALL {
+                let count = kind.distribution(&spec.entries).sample(&mut rng);
+                slots.extend(std::iter::repeat_n((index, kind), count));
+            }
+        }
+
+        // Spread the entries over the commits; each entry is created with
+        // the timestamp of the commit that adds it
+        let mut history = SeededRng::derive(spec.seed, HISTORY_STREAM);
+        history.shuffle(&mut slots);
+        let commit_count = spec.history.commits.min(slots.len()).max(1);
+        let weights: Vec<u32> = spec.history.authors.iter().map(|a| a.weight).collect();
+        let mut commits = Vec::with_capacity(commit_count);
+        for index in 0..commit_count {
+            let added = slots
+                [index * slots.len() / commit_count..(index + 1) * slots.len() / commit_count]
+                .to_vec();
+            let author = spec.history.authors[history.weighted(&weights)].clone();
+            let timestamp = spec.history.start
+                + Duration::hours(index as i64 * spec.history.interval_hours as i64);
+            for (scope, kind) in &added {
+                add_entry(&mut scopes[*scope], *scope, *kind, spec, &author, timestamp);
+            }
+            commits.push(SyntheticCommit {
+                message: commit_message(index, &scopes, &added),
+                author,
+                timestamp,
+                added,
+            });
+        }
+
+        Ok(Self {
+            spec: spec.clone(),
+            scopes,
+            commits,
+        })
+    }
+
+    pub fn scope(&self, name: &str) -> Option<&SyntheticScope> {
+        self.scopes.iter().find(|scope| scope.name() == name)
+    }
+
+    pub fn total_entries(&self) -> usize {
+        self.scopes.iter().map(SyntheticScope::total_entries).sum()
+    }
+
+    /// Every entry of every scope as a YAML document of its own
+    pub fn entry_documents(&self) -> RhemaResult<Vec<String>> {
+        let mut documents = Vec::with_capacity(self.total_entries());
+        for scope in &self.scopes {
+            documents.extend(scope.entry_documents()?);
+        }
+        Ok(documents)
+    }
+}
+
+fn generate_scopes(spec: &RepoSpec) -> Vec<SyntheticScope> {
+    let mut rng = SeededRng::derive(spec.seed, LAYOUT_STREAM);
+    let (types, weights): (Vec<&String>, Vec<u32>) = spec
+        .scopes
+        .types
+        .iter()
+        .map(|(name, weight)| (name, *weight))
+        .unzip();
+
+    let mut scopes: Vec<SyntheticScope> = Vec::with_capacity(spec.scopes.count);
+    for index in 0..spec.scopes.count {
+        let scope_type = types[rng.weighted(&weights)].clone();
+        let name = format!("{}-{}", scope_type, index + 1);
+        let path = match spec.scopes.layout {
+            _ if index == 0 && spec.scopes.root => PathBuf::new(),
+            ScopeLayout::Flat => PathBuf::from(&name),
+            ScopeLayout::Nested => PathBuf::from(format!("{}s", scope_type)).join(&name),
+        };
+
+        // Only earlier scopes are candidates, which keeps the graph acyclic
+        let mut dependencies = Vec::new();
+        for earlier in &scopes {
+            if dependencies.len() < spec.scopes.max_dependencies
+                && rng.chance(spec.scopes.dependency_probability)
+            {
+                dependencies.push(ScopeDependency {
+                    path: dependency_path(&earlier.path),
+                    dependency_type: if rng.chance(0.75) {
+                        "required"
+                    } else {
+                        "optional"
+                    }
+                    .to_string(),
+                    version: None,
+                });
+            }
+        }
+
+        scopes.push(SyntheticScope {
+            path,
+            definition: RhemaScope {
+                description: Some(words::text(&mut rng, 12)),
+                name,
+                scope_type,
+                version: "1.0.0".to_string(),
+                schema_version: Some(CURRENT_SCHEMA_VERSION.to_string()),
+                dependencies: (!dependencies.is_empty()).then_some(dependencies),
+                protocol_info: None,
+                ai_policy: None,
+                freshness: None,
+                toolchains: None,
+                runtime: None,
+                entry_templates: None,
+                custom: HashMap::new(),
+            },
+            todos: Vec::new(),
+            knowledge: Vec::new(),
+            decisions: Vec::new(),
+            patterns: Vec::new(),
+            conventions: Vec::new(),
+        });
+    }
+    scopes
+}
+
+fn dependency_path(path: &std::path::Path) -> String {
+    if path.as_os_str().is_empty() {
+        ".".to_string()
+    } else {
+        path.to_string_lossy().replace('\\', "/")
+    }
+}
+
+fn weighted<T: Clone>(rng: &mut SeededRng, options: &[(T, u32)]) -> T {
+    let weights: Vec<u32> = options.iter().map(|(_, weight)| *weight).collect();
+    options[rng.weighted(&weights)].0.clone()
+}
+
+fn tags(rng: &mut SeededRng) -> Vec<String> {
+    let mut tags = TAGS.to_vec();
+    rng.shuffle(&mut tags);
+    let count = rng.between(1, 3);
+    tags[..count].iter().map(|tag| tag.to_string()).collect()
+}
+
+fn add_entry(
+    scope: &mut SyntheticScope,
+    scope_index: usize,
+    kind: EntryKind,
+    spec: &RepoSpec,
+    author: &AuthorSpec,
+    timestamp: DateTime<Utc>,
+) {
+    let number = scope.entries(kind) + 1;
+    let rng = &mut SeededRng::derive(spec.seed, scope_stream(scope_index, Some((kind, number))));
+    let id = format!("{}-{}-{:03}", kind.id_prefix(), scope.name(), number);
+    let title_words = rng.between(3, 6);
+    let title = words::title(rng, title_words);
+    let content_words = spec.entries.content_words.sample(rng).max(1);
+    let description = words::text(rng, content_words);
+
+    match kind {
+        EntryKind::Todo => {
+            let status = weighted(
+                rng,
+                &[
+                    (TodoStatus::Pending, 4),
+                    (TodoStatus::InProgress, 2),
+                    (TodoStatus::Blocked, 1),
+                    (TodoStatus::Completed, 3),
+                    (TodoStatus::Cancelled, 1),
+                ],
+            );
+            let completed = status == TodoStatus::Completed;
+            let completed_at = timestamp + Duration::hours(rng.between(1, 72) as i64);
+            scope.todos.push(TodoEntry {
+                id,
+                title,
+                description: Some(description),
+                status,
+                priority: weighted(
+                    rng,
+                    &[
+                        (Priority::Low, 2),
+                        (Priority::Medium, 4),
+                        (Priority::High, 3),
+                        (Priority::Critical, 1),
+                    ],
+                ),
+                assigned_to: rng.chance(0.6).then(|| author.name.clone()),
+                due_date: rng
+                    .chance(0.3)
+                    .then(|| timestamp + Duration::days(rng.between(7, 60) as i64)),
+                created_at: timestamp,
+                completed_at: completed.then_some(completed_at),
+                outcome: completed.then(|| words::text(rng, 8)),
+                related_knowledge: None,
+                custom: HashMap::new(),
+            });
+        }
+        EntryKind::Knowledge => scope.knowledge.push(KnowledgeEntry {
+            id,
+            title,
+            content: description,
+            category: Some(rng.pick(CATEGORIES).to_string()),
+            tags: Some(tags(rng)),
+            confidence: Some(rng.between(3, 10) as u8),
+            created_at: timestamp,
+            updated_at: None,
+            source: Some(author.email.clone()),
+            custom: HashMap::new(),
+        }),
+        EntryKind::Decision => {
+            let alternatives = rng.between(1, 3);
+            scope.decisions.push(DecisionEntry {
+                id,
+                title,
+                description,
+                status: weighted(
+                    rng,
+                    &[
+                        (DecisionStatus::Proposed, 2),
+                        (DecisionStatus::UnderReview, 1),
+                        (DecisionStatus::Approved, 3),
+                        (DecisionStatus::Implemented, 3),
+                        (DecisionStatus::Rejected, 1),
+                        (DecisionStatus::Deprecated, 1),
+                    ],
+                ),
+                context: Some(words::text(rng, 10)),
+                alternatives: Some((0..alternatives).map(|_| words::title(rng, 4)).collect()),
+                rationale: Some(words::text(rng, 12)),
+                consequences: None,
+                decided_at: timestamp,
+                review_date: None,
+                decision_makers: Some(vec![author.name.clone()]),
+                implementing_commits: None,
+                incidents: None,
+                reversed_by: None,
+                reverses: None,
+                custom: HashMap::new(),
+            });
+        }
+        EntryKind::Pattern => scope.patterns.push(PatternEntry {
+            id,
+            name: title,
+            description,
+            pattern_type: rng.pick(PATTERN_TYPES).to_string(),
+            usage: weighted(
+                rng,
+                &[
+                    (PatternUsage::Required, 1),
+                    (PatternUsage::Recommended, 3),
+                    (PatternUsage::Optional, 2),
+                    (PatternUsage::Deprecated, 1),
+                ],
+            ),
+            effectiveness: Some(rng.between(3, 10) as u8),
+            examples: Some(vec![words::text(rng, 8)]),
+            anti_patterns: None,
+            related_patterns: None,
+            created_at: timestamp,
+            updated_at: None,
+            custom: HashMap::new(),
+        }),
+        EntryKind::Convention => scope.conventions.push(ConventionEntry {
+            id,
+            name: title,
+            description,
+            convention_type: rng.pick(CONVENTION_TYPES).to_string(),
+            enforcement: weighted(
+                rng,
+                &[
+                    (EnforcementLevel::Required, 1),
+                    (EnforcementLevel::Recommended, 3),
+                    (EnforcementLevel::Optional, 2),
+                ],
+            ),
+            examples: Some(vec![words::text(rng, 8)]),
+            tools: Some(vec![rng.pick(TOOLS).to_string()]),
+            created_at: timestamp,
+            updated_at: None,
+            custom: HashMap::new(),
+        }),
+    }
+}
+
+fn commit_message(index: usize, scopes: &[SyntheticScope], added: &[(usize, EntryKind)]) -> String {
+    let entries = match added.len() {
+        1 => "1 entry".to_string(),
+        count => format!("{} entries", count),
+    };
+    if index == 0 {
+        return format!("Add {} scopes with {}", scopes.len(), entries);
+    }
+    let names: BTreeSet<&str> = added
+        .iter()
+        .map(|(scope, _)| scopes[*scope].name())
+        .collect();
+    format!(
+        "Add {} to {}",
+        entries,
+        names.into_iter().collect::<Vec<_>>().join(", ")
+    )
+}
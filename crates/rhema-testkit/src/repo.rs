@@ -0,0 +1,121 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Synthetic repositories on disk.
+//!
+//! [`SyntheticRepo::write_to`] replays the planned history into a git
+//! repository: every commit rewrites the scope files it touches with the
+//! entries added so far and is committed under its author and timestamp, so
+//! blame, ownership and history-based features see a realistic log.
+
+use git2::{IndexAddOption, Repository, Signature, Time};
+use rhema_core::RhemaResult;
+use std::collections::BTreeSet;
+use std::path::Path;
+use tempfile::TempDir;
+
+use crate::model::SyntheticRepo;
+use crate::spec::RepoSpec;
+
+impl SyntheticRepo {
+    /// Write the repository into `root`, which should be empty, and commit
+    /// its history; returns the commit ids oldest first
+    pub fn write_to(&self, root: &Path) -> RhemaResult<Vec<String>> {
+        let repo = Repository::init(root)?;
+        let mut written = vec![[0usize; 5]; self.scopes.len()];
+        let mut parent = None;
+        let mut ids = Vec::with_capacity(self.commits.len());
+
+        for (index, commit) in self.commits.iter().enumerate() {
+            let mut touched: BTreeSet<usize> = commit.added.iter().map(|(s, _)| *s).collect();
+            if index == 0 {
+                touched.extend(0..self.scopes.len());
+            }
+            for (scope, kind) in &commit.added {
+                written[*scope][*kind as usize] += 1;
+            }
+            for scope_index in touched {
+                let scope = &self.scopes[scope_index];
+                let dir = root.join(scope.rhema_dir());
+                std::fs::create_dir_all(&dir)?;
+                for (file, content) in scope.files_with(&written[scope_index])? {
+                    std::fs::write(dir.join(file), content)?;
+                }
+            }
+
+            let mut git_index = repo.index()?;
+            git_index.add_all(["*"], IndexAddOption::DEFAULT, None)?;
+            git_index.write()?;
+            let tree = repo.find_tree(git_index.write_tree()?)?;
+            let signature = Signature::new(
+                &commit.author.name,
+                &commit.author.email,
+                &Time::new(commit.timestamp.timestamp(), 0),
+            )?;
+            let parents = match parent {
+                Some(id) => vec![repo.find_commit(id)?],
+                None => Vec::new(),
+            };
+            let parents: Vec<&git2::Commit> = parents.iter().collect();
+            let id = repo.commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                &commit.message,
+                &tree,
+                &parents,
+            )?;
+            parent = Some(id);
+            ids.push(id.to_string());
+        }
+        Ok(ids)
+    }
+}
+
+/// A synthetic repository written to a temporary directory, removed on drop
+pub struct TestRepo {
+    dir: TempDir,
+    repo: SyntheticRepo,
+    commits: Vec<String>,
+}
+
+impl TestRepo {
+    pub fn generate(spec: &RepoSpec) -> RhemaResult<Self> {
+        let repo = SyntheticRepo::generate(spec)?;
+        let dir = TempDir::new()?;
+        let commits = repo.write_to(dir.path())?;
+        Ok(Self { dir, repo, commits })
+    }
+
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    pub fn repo(&self) -> &SyntheticRepo {
+        &self.repo
+    }
+
+    /// Commit ids, oldest first
+    pub fn commits(&self) -> &[String] {
+        &self.commits
+    }
+
+    /// The directory and the generated model, for fixtures that keep the
+    /// directory alive themselves
+    pub fn into_parts(self) -> (TempDir, SyntheticRepo) {
+        (self.dir, self.repo)
+    }
+}
@@ -0,0 +1,302 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Declarative repository specifications.
+//!
+//! A [`RepoSpec`] says how many scopes a synthetic repository has and how
+//! they are laid out, how many entries of each kind a scope holds, and how
+//! the history that added them is spread over commits and authors. Every
+//! field has a default, so a YAML spec only lists what it changes:
+//!
+//! ```yaml
+//! seed: 7
+//! scopes:
+//!   count: 12
+//!   layout: nested
+//! entries:
+//!   todos: { min: 0, max: 40, skew: 2.0 }
+//! history:
+//!   commits: 30
+//! ```
+
+use chrono::{DateTime, TimeZone, Utc};
+use rhema_core::{RhemaError, RhemaResult};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::rng::SeededRng;
+
+/// Description of a synthetic repository
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RepoSpec {
+    /// Seed of every random choice; a spec and seed always give the same repository
+    pub seed: u64,
+    pub scopes: ScopeSpec,
+    pub entries: EntrySpec,
+    pub history: HistorySpec,
+}
+
+impl Default for RepoSpec {
+    fn default() -> Self {
+        Self {
+            seed: 42,
+            scopes: ScopeSpec::default(),
+            entries: EntrySpec::default(),
+            history: HistorySpec::default(),
+        }
+    }
+}
+
+impl RepoSpec {
+    pub fn from_yaml(yaml: &str) -> RhemaResult<Self> {
+        let spec: Self = serde_yaml::from_str(yaml)?;
+        spec.validate()?;
+        Ok(spec)
+    }
+
+    pub fn from_file(path: &Path) -> RhemaResult<Self> {
+        Self::from_yaml(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_scopes(mut self, count: usize) -> Self {
+        self.scopes.count = count;
+        self
+    }
+
+    pub fn with_commits(mut self, commits: usize) -> Self {
+        self.history.commits = commits;
+        self
+    }
+
+    pub fn validate(&self) -> RhemaResult<()> {
+        if self.scopes.count == 0 {
+            return Err(invalid("scopes.count must be at least 1"));
+        }
+        if !self.scopes.types.values().any(|weight| *weight > 0) {
+            return Err(invalid("scopes.types needs a type with a positive weight"));
+        }
+        if !(0.0..=1.0).contains(&self.scopes.dependency_probability) {
+            return Err(invalid(
+                "scopes.dependency_probability must be within 0..=1",
+            ));
+        }
+        for (name, distribution) in [
+            ("todos", &self.entries.todos),
+            ("knowledge", &self.entries.knowledge),
+            ("decisions", &self.entries.decisions),
+            ("patterns", &self.entries.patterns),
+            ("conventions", &self.entries.conventions),
+            ("content_words", &self.entries.content_words),
+        ] {
+            distribution
+                .validate()
+                .map_err(|reason| invalid(&format!("entries.{}: {}", name, reason)))?;
+        }
+        if self.history.commits == 0 {
+            return Err(invalid("history.commits must be at least 1"));
+        }
+        if self.history.authors.is_empty() {
+            return Err(invalid("history.authors must not be empty"));
+        }
+        Ok(())
+    }
+}
+
+fn invalid(reason: &str) -> RhemaError {
+    RhemaError::InvalidInput(format!("Invalid repository spec: {}", reason))
+}
+
+/// Where scope directories are placed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScopeLayout {
+    /// `<name>/.rhema`
+    #[default]
+    Flat,
+    /// `<type>s/<name>/.rhema`, e.g. `services/service-3/.rhema`
+    Nested,
+}
+
+/// Number, placement and wiring of scopes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScopeSpec {
+    pub count: usize,
+    pub layout: ScopeLayout,
+    /// Put the first scope at the repository root
+    pub root: bool,
+    /// Scope types with their relative weights
+    pub types: BTreeMap<String, u32>,
+    /// Chance that a scope depends on each scope generated before it
+    pub dependency_probability: f64,
+    pub max_dependencies: usize,
+}
+
+impl Default for ScopeSpec {
+    fn default() -> Self {
+        Self {
+            count: 3,
+            layout: ScopeLayout::Flat,
+            root: false,
+            types: BTreeMap::from([
+                ("service".to_string(), 3),
+                ("library".to_string(), 2),
+                ("app".to_string(), 1),
+            ]),
+            dependency_probability: 0.3,
+            max_dependencies: 3,
+        }
+    }
+}
+
+/// Entries per scope, drawn independently for each scope and kind
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EntrySpec {
+    pub todos: Distribution,
+    pub knowledge: Distribution,
+    pub decisions: Distribution,
+    pub patterns: Distribution,
+    pub conventions: Distribution,
+    /// Length of descriptions and knowledge content in words
+    pub content_words: Distribution,
+}
+
+impl Default for EntrySpec {
+    fn default() -> Self {
+        Self {
+            todos: Distribution::range(2, 8),
+            knowledge: Distribution::range(1, 5),
+            decisions: Distribution::range(0, 3),
+            patterns: Distribution::range(0, 2),
+            conventions: Distribution::range(0, 2),
+            content_words: Distribution::range(10, 40),
+        }
+    }
+}
+
+/// Count drawn from `min..=max`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Distribution {
+    pub min: usize,
+    pub max: usize,
+    /// 0 draws uniformly; larger values pile draws up near `min`, so a few
+    /// scopes end up holding most of the entries
+    pub skew: f64,
+}
+
+impl Default for Distribution {
+    fn default() -> Self {
+        Self::fixed(0)
+    }
+}
+
+impl Distribution {
+    pub fn fixed(count: usize) -> Self {
+        Self::range(count, count)
+    }
+
+    pub fn range(min: usize, max: usize) -> Self {
+        Self {
+            min,
+            max,
+            skew: 0.0,
+        }
+    }
+
+    pub fn with_skew(mut self, skew: f64) -> Self {
+        self.skew = skew;
+        self
+    }
+
+    pub fn sample(&self, rng: &mut SeededRng) -> usize {
+        if self.max <= self.min {
+            return self.min;
+        }
+        let span = (self.max - self.min + 1) as f64;
+        let offset = (rng.unit().powf(1.0 + self.skew) * span) as usize;
+        (self.min + offset).min(self.max)
+    }
+
+    fn validate(&self) -> Result<(), &'static str> {
+        if self.min > self.max {
+            return Err("min must not exceed max");
+        }
+        if self.skew.is_nan() || self.skew < 0.0 {
+            return Err("skew must not be negative");
+        }
+        Ok(())
+    }
+}
+
+/// Commits replaying the creation of the entries
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HistorySpec {
+    /// Commits to spread the entries over; capped at one per entry
+    pub commits: usize,
+    pub authors: Vec<AuthorSpec>,
+    /// Time of the first commit
+    pub start: DateTime<Utc>,
+    /// Time between consecutive commits
+    pub interval_hours: u64,
+}
+
+impl Default for HistorySpec {
+    fn default() -> Self {
+        Self {
+            commits: 5,
+            authors: vec![
+                AuthorSpec::new("Ada Lovelace", "ada@example.com"),
+                AuthorSpec::new("Grace Hopper", "grace@example.com"),
+                AuthorSpec::new("Alan Turing", "alan@example.com"),
+            ],
+            start: Utc.with_ymd_and_hms(2025, 1, 1, 9, 0, 0).unwrap(),
+            interval_hours: 24,
+        }
+    }
+}
+
+/// Commit author and how often they commit relative to the others
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthorSpec {
+    pub name: String,
+    pub email: String,
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+}
+
+impl AuthorSpec {
+    pub fn new(name: impl Into<String>, email: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            email: email.into(),
+            weight: default_weight(),
+        }
+    }
+}
+
+fn default_weight() -> u32 {
+    1
+}
@@ -0,0 +1,167 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Vocabulary for synthetic titles and prose.
+
+use crate::rng::SeededRng;
+
+const WORDS: &[&str] = &[
+    "cache",
+    "scope",
+    "context",
+    "query",
+    "index",
+    "schema",
+    "session",
+    "agent",
+    "token",
+    "request",
+    "response",
+    "latency",
+    "retry",
+    "timeout",
+    "queue",
+    "worker",
+    "batch",
+    "stream",
+    "snapshot",
+    "migration",
+    "config",
+    "policy",
+    "service",
+    "client",
+    "server",
+    "daemon",
+    "lock",
+    "conflict",
+    "merge",
+    "branch",
+    "commit",
+    "release",
+    "build",
+    "deploy",
+    "metric",
+    "trace",
+    "log",
+    "alert",
+    "budget",
+    "quota",
+    "entry",
+    "pattern",
+    "decision",
+    "convention",
+    "knowledge",
+    "dependency",
+    "boundary",
+    "contract",
+    "payload",
+    "handler",
+    "pipeline",
+    "storage",
+    "backend",
+    "replica",
+    "shard",
+    "endpoint",
+    "module",
+    "interface",
+    "adapter",
+];
+
+const VERBS: &[&str] = &[
+    "add",
+    "refactor",
+    "document",
+    "validate",
+    "cache",
+    "split",
+    "merge",
+    "migrate",
+    "retry",
+    "measure",
+    "reduce",
+    "isolate",
+    "expose",
+    "deprecate",
+    "simplify",
+    "harden",
+    "profile",
+];
+
+const ADJECTIVES: &[&str] = &[
+    "stale",
+    "shared",
+    "slow",
+    "flaky",
+    "nested",
+    "async",
+    "bounded",
+    "legacy",
+    "optional",
+    "partial",
+    "cross-scope",
+    "idempotent",
+    "versioned",
+    "cold",
+    "hot",
+    "incremental",
+];
+
+/// Title of about `words` words, starting with a capitalized verb
+pub(crate) fn title(rng: &mut SeededRng, words: usize) -> String {
+    let mut parts = vec![capitalize(rng.pick(VERBS))];
+    for i in 1..words.max(2) {
+        let word = if i % 2 == 1 && rng.chance(0.4) {
+            rng.pick(ADJECTIVES)
+        } else {
+            rng.pick(WORDS)
+        };
+        parts.push(word.to_string());
+    }
+    parts.join(" ")
+}
+
+/// Prose of exactly `words` words, in sentences of 6 to 14 words
+pub(crate) fn text(rng: &mut SeededRng, words: usize) -> String {
+    let mut sentences = Vec::new();
+    let mut remaining = words.max(1);
+    while remaining > 0 {
+        let length = rng.between(6, 14).min(remaining);
+        remaining -= length;
+        let mut sentence: Vec<String> = Vec::with_capacity(length);
+        for i in 0..length {
+            let word = match rng.below(5) {
+                0 => rng.pick(VERBS),
+                1 => rng.pick(ADJECTIVES),
+                _ => rng.pick(WORDS),
+            };
+            sentence.push(if i == 0 {
+                capitalize(word)
+            } else {
+                word.to_string()
+            });
+        }
+        sentences.push(format!("{}.", sentence.join(" ")));
+    }
+    sentences.join(" ")
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
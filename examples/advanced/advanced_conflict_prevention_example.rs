@@ -139,6 +139,7 @@ async fn register_agents(
             last_heartbeat: Utc::now(),
             is_online: true,
             performance_metrics: rhema_coordination::agent::real_time_coordination::AgentPerformanceMetrics::default(),
+            liveness: Default::default(),
         },
         AgentInfo {
             id: "test-runner".to_string(),
@@ -151,6 +152,7 @@ async fn register_agents(
             last_heartbeat: Utc::now(),
             is_online: true,
             performance_metrics: rhema_coordination::agent::real_time_coordination::AgentPerformanceMetrics::default(),
+            liveness: Default::default(),
         },
         AgentInfo {
             id: "deployment-manager".to_string(),
@@ -163,6 +165,7 @@ async fn register_agents(
             last_heartbeat: Utc::now(),
             is_online: true,
             performance_metrics: rhema_coordination::agent::real_time_coordination::AgentPerformanceMetrics::default(),
+            liveness: Default::default(),
         },
     ];
 
@@ -259,6 +259,7 @@ fn create_production_config() -> rhema_core::RhemaResult<ProductionConfig> {
             enable_cleanup: true,
             cleanup_interval_hours: 168, // 1 week
             data_retention_days: 90,
+            delta_encoding: Default::default(),
         },
         distributed: Some(DistributedConfig {
             node: rhema_coordination::distributed::NodeConfig {
@@ -31,7 +31,7 @@ use rhema_knowledge::{
     UnifiedEngineConfig, RAGConfig, VectorStoreConfig, VectorStoreType,
     DistanceMetric, CacheConfig, StorageConfig, MemoryConfig, DiskConfig,
     NetworkConfig, LifecycleConfig, PerformanceConfig, ProactiveConfig,
-    MonitoringConfig, SemanticSearchConfig,
+    MonitoringConfig, SemanticSearchConfig, CompressionProfile, ModelFamily,
     
     // Types
     ContentType, KnowledgeResult, SearchResultMetadata, CacheEntryMetadata,
@@ -291,6 +291,7 @@ impl ProductionKnowledgeIntegration {
                     hybrid_search_enabled: true,
                     reranking_enabled: true,
                 },
+                compression: CompressionProfile::for_model(ModelFamily::Claude),
             },
             cache: Self::create_cache_config(),
             proactive: ProactiveConfig {
@@ -437,6 +438,7 @@ impl ProductionKnowledgeIntegration {
                 processing_time_ms: 0,
                 ai_enhancements: vec![],
                 suggestions: vec![],
+                compression: None,
                 created_at: Utc::now(),
             })
         }
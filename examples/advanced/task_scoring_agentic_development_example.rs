@@ -326,6 +326,7 @@ async fn demo_real_time_coordination(service: &mut AgenticDevelopmentService) ->
                 collaboration_score: 0.8,
                 avg_response_time_ms: 120.0,
             },
+            liveness: Default::default(),
         },
         AgentInfo {
             id: "agent-backend".to_string(),
@@ -345,6 +346,7 @@ async fn demo_real_time_coordination(service: &mut AgenticDevelopmentService) ->
                 collaboration_score: 0.7,
                 avg_response_time_ms: 180.0,
             },
+            liveness: Default::default(),
         },
         AgentInfo {
             id: "agent-security".to_string(),
@@ -364,6 +366,7 @@ async fn demo_real_time_coordination(service: &mut AgenticDevelopmentService) ->
                 collaboration_score: 0.9,
                 avg_response_time_ms: 90.0,
             },
+            liveness: Default::default(),
         },
     ];
 
@@ -60,6 +60,7 @@ impl ExampleAgent {
             last_heartbeat: chrono::Utc::now(),
             is_online: true,
             performance_metrics: Default::default(),
+            liveness: Default::default(),
         };
 
         client.register_agent(agent_info.clone()).await?;
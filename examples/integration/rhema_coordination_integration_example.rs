@@ -85,6 +85,7 @@ async fn basic_coordination_integration() -> rhema_core::RhemaResult<()> {
         last_heartbeat: chrono::Utc::now(),
         is_online: true,
         performance_metrics: AgentPerformanceMetrics::default(),
+        liveness: Default::default(),
     };
 
     let agent2 = AgentInfo {
@@ -98,6 +99,7 @@ async fn basic_coordination_integration() -> rhema_core::RhemaResult<()> {
         last_heartbeat: chrono::Utc::now(),
         is_online: true,
         performance_metrics: AgentPerformanceMetrics::default(),
+        liveness: Default::default(),
     };
 
     // Register agents
@@ -245,6 +247,7 @@ async fn advanced_coordination_integration() -> rhema_core::RhemaResult<()> {
             last_heartbeat: chrono::Utc::now(),
             is_online: true,
             performance_metrics: AgentPerformanceMetrics::default(),
+            liveness: Default::default(),
         };
         rhema.register_agent(agent).await?;
     }
@@ -363,6 +366,7 @@ async fn multi_agent_coordination_workflow() -> rhema_core::RhemaResult<()> {
             last_heartbeat: chrono::Utc::now(),
             is_online: true,
             performance_metrics: AgentPerformanceMetrics::default(),
+            liveness: Default::default(),
         };
         rhema.register_agent(agent).await?;
     }
@@ -497,6 +501,7 @@ async fn coordination_with_external_integration() -> rhema_core::RhemaResult<()>
         last_heartbeat: chrono::Utc::now(),
         is_online: true,
         performance_metrics: AgentPerformanceMetrics::default(),
+        liveness: Default::default(),
     };
 
     let internal_agent = AgentInfo {
@@ -510,6 +515,7 @@ async fn coordination_with_external_integration() -> rhema_core::RhemaResult<()>
         last_heartbeat: chrono::Utc::now(),
         is_online: true,
         performance_metrics: AgentPerformanceMetrics::default(),
+        liveness: Default::default(),
     };
 
     // Register agents
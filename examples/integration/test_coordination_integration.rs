@@ -168,6 +168,7 @@ async fn test_agent_registration_and_messaging() -> RhemaResult<()> {
             collaboration_score: 0.0,
             avg_response_time_ms: 0.0,
         },
+        liveness: Default::default(),
     };
 
     service.register_agent_with_coordination(agent_info).await?;
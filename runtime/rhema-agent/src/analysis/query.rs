@@ -185,6 +185,12 @@ pub fn run_with_provenance(rhema: &Rhema, query: &str) -> RhemaResult<()> {
         "📁 Scopes Searched: {}",
         provenance.scopes_searched.join(", ")
     );
+    if provenance.roots_searched.len() > 1 {
+        println!(
+            "🗂️  Repository Roots: {}",
+            provenance.roots_searched.join(", ")
+        );
+    }
     println!(
         "📄 Files Accessed: {}",
         provenance.files_accessed.join(", ")
@@ -47,6 +47,7 @@ pub fn run(rhema: &Rhema, subcommand: &DecisionSubcommands) -> RhemaResult<()> {
             alternatives,
             rationale,
             consequences,
+            fields,
         } => record_decision(
             scope,
             title,
@@ -57,6 +58,7 @@ pub fn run(rhema: &Rhema, subcommand: &DecisionSubcommands) -> RhemaResult<()> {
             alternatives,
             rationale,
             consequences,
+            fields,
         ),
         DecisionSubcommands::List { status, maker } => list_decisions(scope, status, maker),
         DecisionSubcommands::Update {
@@ -95,6 +97,7 @@ fn record_decision(
     alternatives: &Option<String>,
     rationale: &Option<String>,
     consequences: &Option<String>,
+    fields: &[String],
 ) -> RhemaResult<()> {
     let id = file_ops::add_decision(
         &scope.path,
@@ -106,6 +109,7 @@ fn record_decision(
         alternatives.clone(),
         rationale.clone(),
         consequences.clone(),
+        rhema_core::templates::parse_entry_fields(fields)?,
     )?;
 
     println!("🎯 Decision recorded successfully with ID: {}", id.green());
@@ -16,6 +16,8 @@
 
 use crate::{Rhema, RhemaResult, RhemaScope};
 use colored::*;
+use rhema_core::readme_sync::ReadmeSync;
+use rhema_core::scope::Scope;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
@@ -40,7 +42,7 @@ pub fn run(
     for scope in scopes {
         generate_scope_readme(
             rhema,
-            &scope.definition,
+            &scope,
             output_file,
             template,
             include_context,
@@ -303,7 +305,7 @@ struct ContextSection {
 /// Generate README for a specific scope
 fn generate_scope_readme(
     rhema: &Rhema,
-    scope: &RhemaScope,
+    scope: &Scope,
     output_file: Option<&str>,
     template: Option<&str>,
     include_context: bool,
@@ -315,7 +317,7 @@ fn generate_scope_readme(
     // Create README content
     let content = create_readme_content(
         rhema,
-        scope,
+        &scope.definition,
         template_type,
         include_context,
         seo_optimized,
@@ -335,7 +337,15 @@ fn generate_scope_readme(
     // Write to file
     fs::write(&output_path, readme_content)?;
 
-    println!("  Generated README for scope: {}", scope.name.yellow());
+    // Context sections are kept up to date by `rhema readme sync` afterwards
+    if include_context {
+        ReadmeSync::open(rhema.repo_root())?.sync_at(scope, output_path.clone(), true)?;
+    }
+
+    println!(
+        "  Generated README for scope: {}",
+        scope.definition.name.yellow()
+    );
     println!("  Output: {}", output_path.display().to_string().yellow());
 
     Ok(())
@@ -44,7 +44,8 @@ pub fn run(rhema: &Rhema, subcommand: &InsightSubcommands) -> RhemaResult<()> {
             confidence,
             category,
             tags,
-        } => record_insight(scope, title, content, confidence, category, tags),
+            fields,
+        } => record_insight(scope, title, content, confidence, category, tags, fields),
         InsightSubcommands::List {
             category,
             tag,
@@ -69,6 +70,7 @@ fn record_insight(
     confidence: &Option<u8>,
     category: &Option<String>,
     tags: &Option<String>,
+    fields: &[String],
 ) -> RhemaResult<()> {
     let id = file_ops::add_knowledge(
         &scope.path,
@@ -77,6 +79,7 @@ fn record_insight(
         confidence.clone(),
         category.clone(),
         tags.clone(),
+        rhema_core::templates::parse_entry_fields(fields)?,
     )?;
 
     println!("💡 Insight recorded successfully with ID: {}", id.green());
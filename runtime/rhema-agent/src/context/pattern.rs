@@ -46,6 +46,7 @@ pub fn run(rhema: &Rhema, subcommand: &PatternSubcommands) -> RhemaResult<()> {
             effectiveness,
             examples,
             anti_patterns,
+            fields,
         } => add_pattern(
             scope,
             name,
@@ -55,6 +56,7 @@ pub fn run(rhema: &Rhema, subcommand: &PatternSubcommands) -> RhemaResult<()> {
             effectiveness,
             examples,
             anti_patterns,
+            fields,
         ),
         PatternSubcommands::List {
             pattern_type,
@@ -94,6 +96,7 @@ fn add_pattern(
     effectiveness: &Option<u8>,
     examples: &Option<String>,
     anti_patterns: &Option<String>,
+    fields: &[String],
 ) -> RhemaResult<()> {
     let id = file_ops::add_pattern(
         &scope.path,
@@ -104,6 +107,7 @@ fn add_pattern(
         effectiveness.clone(),
         examples.clone(),
         anti_patterns.clone(),
+        rhema_core::templates::parse_entry_fields(fields)?,
     )?;
 
     println!("🔄 Pattern added successfully with ID: {}", id.green());
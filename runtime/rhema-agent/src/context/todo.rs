@@ -45,7 +45,16 @@ pub fn run(rhema: &Rhema, subcommand: &TodoSubcommands) -> RhemaResult<()> {
             priority,
             assignee,
             due_date,
-        } => add_todo(scope, title, description, priority, assignee, due_date),
+            fields,
+        } => add_todo(
+            scope,
+            title,
+            description,
+            priority,
+            assignee,
+            due_date,
+            fields,
+        ),
         TodoSubcommands::List {
             status,
             priority,
@@ -81,6 +90,7 @@ fn add_todo(
     priority: &Priority,
     assignee: &Option<String>,
     due_date: &Option<String>,
+    fields: &[String],
 ) -> RhemaResult<()> {
     let id = file_ops::add_todo(
         &scope.path,
@@ -89,6 +99,7 @@ fn add_todo(
         priority.clone(),
         assignee.clone(),
         due_date.clone(),
+        rhema_core::templates::parse_entry_fields(fields)?,
     )?;
 
     println!("✅ Todo added successfully with ID: {}", id.green());
@@ -41,6 +41,10 @@ pub enum TodoSubcommands {
         /// Due date (ISO format)
         #[arg(long, value_name = "DATE")]
         due_date: Option<String>,
+
+        /// Field required by an entry template, as NAME=VALUE (repeatable)
+        #[arg(long = "field", value_name = "NAME=VALUE")]
+        fields: Vec<String>,
     },
 
     /// List todos
@@ -131,6 +135,10 @@ pub enum InsightSubcommands {
         /// Tags (comma-separated)
         #[arg(long, value_name = "TAGS")]
         tags: Option<String>,
+
+        /// Field required by an entry template, as NAME=VALUE (repeatable)
+        #[arg(long = "field", value_name = "NAME=VALUE")]
+        fields: Vec<String>,
     },
 
     /// List insights
@@ -214,6 +222,10 @@ pub enum PatternSubcommands {
         /// Anti-patterns to avoid (comma-separated)
         #[arg(long, value_name = "ANTI_PATTERNS")]
         anti_patterns: Option<String>,
+
+        /// Field required by an entry template, as NAME=VALUE (repeatable)
+        #[arg(long = "field", value_name = "NAME=VALUE")]
+        fields: Vec<String>,
     },
 
     /// List patterns
@@ -309,6 +321,10 @@ pub enum DecisionSubcommands {
         /// Consequences (comma-separated)
         #[arg(long, value_name = "CONSEQUENCES")]
         consequences: Option<String>,
+
+        /// Field required by an entry template, as NAME=VALUE (repeatable)
+        #[arg(long = "field", value_name = "NAME=VALUE")]
+        fields: Vec<String>,
     },
 
     /// List decisions
@@ -119,6 +119,11 @@ pub fn run(
         schema_version: Some(crate::CURRENT_SCHEMA_VERSION.to_string()),
         dependencies: None,
         protocol_info: Some(protocol_info),
+        ai_policy: None,
+        freshness: None,
+        toolchains: None,
+        runtime: None,
+        entry_templates: None,
         custom: custom_fields,
     };
 
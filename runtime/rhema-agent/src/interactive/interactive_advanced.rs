@@ -17,6 +17,7 @@
 use crate::commands::*;
 use crate::{Rhema, RhemaError, RhemaResult};
 use colored::*;
+use rhema_query::lint::{lint_query, LintSeverity};
 use rustyline::error::ReadlineError;
 use rustyline::Editor;
 use std::collections::HashMap;
@@ -1222,11 +1223,13 @@ impl AdvancedInteractiveSession {
             .to_string();
 
         let mut stats = false;
+        let mut lint = true;
         let mut format = "yaml".to_string();
 
         while let Some(arg) = parser.next() {
             match arg {
                 "--stats" => stats = true,
+                "--no-lint" => lint = false,
                 "--format" => {
                     format = parser
                         .next()
@@ -1244,6 +1247,10 @@ impl AdvancedInteractiveSession {
             }
         }
 
+        if lint && !self.query_passes_lint(&query)? {
+            return Ok(());
+        }
+
         if stats {
             crate::query::run_with_stats(&self.rhema, &query)
         } else if format != "yaml" {
@@ -1253,6 +1260,24 @@ impl AdvancedInteractiveSession {
         }
     }
 
+    /// Print lint findings for a query, returning whether it should run
+    fn query_passes_lint(&self, query: &str) -> RhemaResult<bool> {
+        let scopes = self.rhema.discover_scopes()?;
+        let report = lint_query(query, &scopes, self.rhema.repo_root());
+        for finding in &report.findings {
+            let line = finding.to_string();
+            match finding.severity {
+                LintSeverity::Error => println!("{}", line.red()),
+                LintSeverity::Warning => println!("{}", line.yellow()),
+                LintSeverity::Info => println!("{}", line.dimmed()),
+            }
+        }
+        if report.has_errors() {
+            println!("{}", "Query not run; fix the errors above or pass --no-lint".red());
+        }
+        Ok(!report.has_errors())
+    }
+
     fn handle_search_enhanced(&mut self, parser: &mut InteractiveCommandParser) -> RhemaResult<()> {
         let term = parser
             .next()
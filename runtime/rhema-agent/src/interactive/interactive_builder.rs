@@ -16,6 +16,8 @@
 
 use crate::{Rhema, RhemaError, RhemaResult};
 use colored::*;
+use rhema_core::lifecycle::EntryType;
+use rhema_core::templates::resolve_entry_templates;
 use std::io::{self, Write};
 
 /// Interactive command builder for complex operations
@@ -51,20 +53,26 @@ impl InteractiveBuilder {
         // Build command
         let mut command = format!("todo add \"{}\"", title);
 
-        if let Some(desc) = description {
+        if let Some(desc) = &description {
             command.push_str(&format!(" --description \"{}\"", desc));
         }
 
         command.push_str(&format!(" --priority {}", priority));
 
-        if let Some(assignee) = assignee {
+        if let Some(assignee) = &assignee {
             command.push_str(&format!(" --assignee \"{}\"", assignee));
         }
 
-        if let Some(due_date) = due_date {
+        if let Some(due_date) = &due_date {
             command.push_str(&format!(" --due-date \"{}\"", due_date));
         }
 
+        let mut provided = vec!["title", "priority"];
+        provided.extend(description.is_some().then_some("description"));
+        provided.extend(assignee.is_some().then_some("assigned_to"));
+        provided.extend(due_date.is_some().then_some("due_date"));
+        self.prompt_template_fields(EntryType::Todo, &provided, &mut command)?;
+
         println!("\n{}", "Generated command:".bold().green());
         println!("{}", command.cyan());
 
@@ -97,14 +105,19 @@ impl InteractiveBuilder {
             title, content, confidence
         );
 
-        if let Some(category) = category {
+        if let Some(category) = &category {
             command.push_str(&format!(" --category \"{}\"", category));
         }
 
-        if let Some(tags) = tags {
+        if let Some(tags) = &tags {
             command.push_str(&format!(" --tags \"{}\"", tags));
         }
 
+        let mut provided = vec!["title", "content", "confidence"];
+        provided.extend(category.is_some().then_some("category"));
+        provided.extend(tags.is_some().then_some("tags"));
+        self.prompt_template_fields(EntryType::Knowledge, &provided, &mut command)?;
+
         println!("\n{}", "Generated command:".bold().green());
         println!("{}", command.cyan());
 
@@ -137,14 +150,19 @@ impl InteractiveBuilder {
             name, usage, effectiveness
         );
 
-        if let Some(desc) = description {
+        if let Some(desc) = &description {
             command.push_str(&format!(" --description \"{}\"", desc));
         }
 
-        if let Some(pattern_type) = pattern_type {
+        if let Some(pattern_type) = &pattern_type {
             command.push_str(&format!(" --type \"{}\"", pattern_type));
         }
 
+        let mut provided = vec!["name", "usage", "effectiveness"];
+        provided.extend(description.is_some().then_some("description"));
+        provided.extend(pattern_type.is_some().then_some("pattern_type"));
+        self.prompt_template_fields(EntryType::Pattern, &provided, &mut command)?;
+
         println!("\n{}", "Generated command:".bold().green());
         println!("{}", command.cyan());
 
@@ -174,18 +192,24 @@ impl InteractiveBuilder {
         // Build command
         let mut command = format!("decision record \"{}\" --status {}", title, status);
 
-        if let Some(desc) = description {
+        if let Some(desc) = &description {
             command.push_str(&format!(" --description \"{}\"", desc));
         }
 
-        if let Some(maker) = maker {
+        if let Some(maker) = &maker {
             command.push_str(&format!(" --maker \"{}\"", maker));
         }
 
-        if let Some(rationale) = rationale {
+        if let Some(rationale) = &rationale {
             command.push_str(&format!(" --rationale \"{}\"", rationale));
         }
 
+        let mut provided = vec!["title", "status"];
+        provided.extend(description.is_some().then_some("description"));
+        provided.extend(maker.is_some().then_some("decision_makers"));
+        provided.extend(rationale.is_some().then_some("rationale"));
+        self.prompt_template_fields(EntryType::Decision, &provided, &mut command)?;
+
         println!("\n{}", "Generated command:".bold().green());
         println!("{}", command.cyan());
 
@@ -306,6 +330,55 @@ impl InteractiveBuilder {
         }
     }
 
+    /// Prompt for the fields the scope's entry template requires that were
+    /// not given yet, pre-filled from its scaffold, and add them as `--field`
+    fn prompt_template_fields(
+        &self,
+        entry_type: EntryType,
+        provided: &[&str],
+        command: &mut String,
+    ) -> RhemaResult<()> {
+        let templates = resolve_entry_templates(&std::env::current_dir()?)?;
+        let Some(template) = templates.get(entry_type) else {
+            return Ok(());
+        };
+        let missing: Vec<&String> = template
+            .required
+            .iter()
+            .filter(|field| !provided.contains(&field.as_str()))
+            .collect();
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        println!(
+            "\n{}",
+            format!("Required by the {} template:", entry_type).bold()
+        );
+        let mut editor = rustyline::DefaultEditor::new()?;
+        for field in missing {
+            if let Some(hint) = template.hint(field) {
+                println!("{}", hint.dimmed());
+            }
+            let prompt = format!("{}: ", field);
+            let initial = template.scaffold(field).unwrap_or("");
+            let value = editor.readline_with_initial(&prompt, (initial, ""))?;
+            let value = value.trim();
+            if value.is_empty() {
+                return Err(RhemaError::InvalidCommand(format!(
+                    "{} is required by the {} template",
+                    field, entry_type
+                )));
+            }
+            command.push_str(&format!(
+                " --field \"{}={}\"",
+                field,
+                value.replace('"', "\\\"")
+            ));
+        }
+        Ok(())
+    }
+
     /// Prompt for user input
     fn prompt_input(&self, prompt: &str) -> RhemaResult<String> {
         print!("{}: ", prompt.cyan());
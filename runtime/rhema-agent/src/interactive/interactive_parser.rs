@@ -114,6 +114,7 @@ impl InteractiveCommandParser {
                 let mut due_date = None;
                 let mut description = None;
 
+                let mut fields = Vec::new();
                 while let Some(arg) = self.next() {
                     match arg {
                         "--priority" | "-p" => {
@@ -168,6 +169,17 @@ impl InteractiveCommandParser {
                                     .to_string(),
                             );
                         }
+                        "--field" => {
+                            fields.push(
+                                self.next()
+                                    .ok_or_else(|| {
+                                        RhemaError::InvalidCommand(
+                                            "--field requires a value".to_string(),
+                                        )
+                                    })?
+                                    .to_string(),
+                            );
+                        }
                         _ => {
                             return Err(RhemaError::InvalidCommand(format!(
                                 "Unknown argument: {}",
@@ -183,6 +195,7 @@ impl InteractiveCommandParser {
                     priority,
                     assignee,
                     due_date,
+                    fields,
                 })
             }
             "list" | "ls" => {
@@ -419,6 +432,7 @@ impl InteractiveCommandParser {
                 let mut category = None;
                 let mut tags = None;
 
+                let mut fields = Vec::new();
                 while let Some(arg) = self.next() {
                     match arg {
                         "--content" | "-c" => {
@@ -466,6 +480,17 @@ impl InteractiveCommandParser {
                                     .to_string(),
                             );
                         }
+                        "--field" => {
+                            fields.push(
+                                self.next()
+                                    .ok_or_else(|| {
+                                        RhemaError::InvalidCommand(
+                                            "--field requires a value".to_string(),
+                                        )
+                                    })?
+                                    .to_string(),
+                            );
+                        }
                         _ => {
                             return Err(RhemaError::InvalidCommand(format!(
                                 "Unknown argument: {}",
@@ -481,6 +506,7 @@ impl InteractiveCommandParser {
                     confidence,
                     category,
                     tags,
+                    fields,
                 })
             }
             "list" | "ls" => {
@@ -566,6 +592,7 @@ impl InteractiveCommandParser {
                 let mut usage = None;
                 let mut effectiveness = None;
 
+                let mut fields = Vec::new();
                 while let Some(arg) = self.next() {
                     match arg {
                         "--description" | "-desc" => {
@@ -618,6 +645,17 @@ impl InteractiveCommandParser {
                                 )
                             })?);
                         }
+                        "--field" => {
+                            fields.push(
+                                self.next()
+                                    .ok_or_else(|| {
+                                        RhemaError::InvalidCommand(
+                                            "--field requires a value".to_string(),
+                                        )
+                                    })?
+                                    .to_string(),
+                            );
+                        }
                         _ => {
                             return Err(RhemaError::InvalidCommand(format!(
                                 "Unknown argument: {}",
@@ -635,6 +673,7 @@ impl InteractiveCommandParser {
                     effectiveness,
                     examples: None,
                     anti_patterns: None,
+                    fields,
                 })
             }
             "list" | "ls" => {
@@ -725,6 +764,7 @@ impl InteractiveCommandParser {
                 let mut maker = None;
                 let mut rationale = None;
 
+                let mut fields = Vec::new();
                 while let Some(arg) = self.next() {
                     match arg {
                         "--description" | "-desc" => {
@@ -777,6 +817,17 @@ impl InteractiveCommandParser {
                                     .to_string(),
                             );
                         }
+                        "--field" => {
+                            fields.push(
+                                self.next()
+                                    .ok_or_else(|| {
+                                        RhemaError::InvalidCommand(
+                                            "--field requires a value".to_string(),
+                                        )
+                                    })?
+                                    .to_string(),
+                            );
+                        }
                         _ => {
                             return Err(RhemaError::InvalidCommand(format!(
                                 "Unknown argument: {}",
@@ -795,6 +846,7 @@ impl InteractiveCommandParser {
                     context: None,
                     alternatives: None,
                     consequences: None,
+                    fields,
                 })
             }
             "list" | "ls" => {
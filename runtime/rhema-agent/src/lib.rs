@@ -4,7 +4,7 @@
 // Re-export types from other crates for convenience
 pub use rhema_config::{Config, GlobalConfig, RepositoryConfig};
 pub use rhema_coordination::context_injection::{
-    ContextInjectionRule, EnhancedContextInjector, TaskType,
+    ContextBudgetReport, ContextInjectionRule, EnhancedContextInjector, TaskType,
 };
 pub use rhema_core::{
     file_ops, schema::*, scope, JsonSchema, RhemaError, RhemaResult, SchemaMigratable, Validatable,
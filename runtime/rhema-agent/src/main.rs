@@ -816,6 +816,9 @@ async fn handle_git_commands(subcommand: GitSubcommands) -> RhemaResult<()> {
 async fn main() -> RhemaResult<()> {
     let cli = Cli::parse();
 
+    // Commands still use the unversioned API; keep its deprecation warnings
+    // out of command output
+    rhema_api::compat::set_deprecation_warnings(false);
     let rhema = Rhema::new()?;
 
     match &cli.command {
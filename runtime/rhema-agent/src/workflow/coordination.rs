@@ -419,6 +419,7 @@ impl CoordinationManager {
                 collaboration_score: 0.0,
                 avg_response_time_ms: 0.0,
             },
+            liveness: Default::default(),
         };
 
         let agent_id = agent_info.id.clone();
@@ -18,13 +18,13 @@ use crate::{RhemaError, RhemaResult};
 use rhema_mcp::{
     McpConfig, McpDaemon,
     mcp::{AuthConfig, WatcherConfig, CacheConfig, LoggingConfig, RateLimitConfig, StartupConfig},
-    DaemonStatistics,
+    shutdown::termination_signal,
+    DaemonStatistics, QueryGuardConfig,
 };
 use clap::Args;
 use std::path::PathBuf;
 use std::process;
 use std::fs;
-use tokio::signal;
 use tokio::time::{sleep, Duration};
 use tracing::{error, info, warn};
 
@@ -273,17 +273,31 @@ async fn start_daemon(
     }
 
     // Create daemon
-    let mut daemon = McpDaemon::new(config, repo_root).await?;
+    let mut daemon = McpDaemon::new(config, repo_root.clone()).await?;
 
-    // Set up signal handlers
+    // Probe embedding, vector store and LLM provider dependencies for /health
+    match rhema_knowledge::configured_monitor(&repo_root).await {
+        Ok(monitor) => daemon = daemon.with_dependency_monitor(std::sync::Arc::new(monitor)),
+        Err(e) => warn!("Dependency health checks disabled: {}", e),
+    }
+
+    // Deliver exported events to the configured event buses
+    match rhema_coordination::EventExportBridge::open(&repo_root) {
+        Ok(bridge) if bridge.is_enabled() => {
+            std::sync::Arc::new(bridge).spawn();
+        }
+        Ok(_) => {}
+        Err(e) => warn!("Event export disabled: {}", e),
+    }
+
+    // Drain on SIGTERM/Ctrl-C so in-flight requests and watcher events are not lost
     let mut daemon_clone = daemon.clone();
     tokio::spawn(async move {
-        if let Err(e) = signal::ctrl_c().await {
-            error!("Failed to listen for Ctrl+C: {}", e);
-        }
+        termination_signal().await;
         info!("Received shutdown signal");
-        if let Err(e) = daemon_clone.stop().await {
-            error!("Failed to stop daemon: {}", e);
+        let deadline = daemon_clone.shutdown_timeout();
+        if let Err(e) = daemon_clone.drain(deadline).await {
+            error!("Failed to drain daemon: {}", e);
         }
     });
 
@@ -729,6 +743,13 @@ use_official_sdk: true
 
 # Maximum concurrent connections
 max_connections: null
+
+# Guardrails for the rhema.query tool used by AI clients
+query_guard:
+  queries_per_minute: 30            # Queries per client per minute
+  max_rows: 200                     # Rows returned before truncating
+  max_tokens: 8000                  # Estimated tokens returned before truncating
+  max_query_length: 4096            # Longest accepted query text in bytes
 "#
         .to_string()
     } else {
@@ -798,6 +819,7 @@ fn create_config_from_args(
         use_official_sdk: true,
         startup: StartupConfig::default(),
         max_connections: None,
+        query_guard: QueryGuardConfig::default(),
     }
 }
 
@@ -262,6 +262,9 @@ async fn run_report_command(_rhema: &crate::Rhema, args: &ReportArgs) -> RhemaRe
     println!("  Performance Score: {:.2}", report.performance_score);
     println!("  Quality Score: {:.2}", report.quality_score);
     println!("  Optimization Score: {:.2}", report.optimization_score);
+    if let Some(coordination_score) = report.coordination_score {
+        println!("  Coordination Score: {:.2}", coordination_score);
+    }
     println!("  Overall Grade: {}", report.summary.overall_grade);
     
     if let Some(output_file) = &args.output_file {
@@ -494,7 +497,7 @@ fn generate_markdown_report(report: &rhema_locomo::LocomoReport) -> RhemaResult<
 - **Successful Benchmarks:** {}
 - **Failed Benchmarks:** {}
 - **Overall Grade:** {}
-"#,
+{}"#,
         report.timestamp.format("%Y-%m-%d %H:%M:%S"),
         report.report_type,
         report.performance_score,
@@ -504,7 +507,14 @@ fn generate_markdown_report(report: &rhema_locomo::LocomoReport) -> RhemaResult<
         report.summary.total_benchmarks,
         report.summary.successful_benchmarks,
         report.summary.failed_benchmarks,
-        report.summary.overall_grade
+        report.summary.overall_grade,
+        report.detailed_metrics.coordination.as_ref().map(|coordination| format!(
+            "\n## Coordination\n\n- **Active Agents:** {}\n- **Messages per Completed Task:** {:.1}\n- **Conflict Rate:** {:.2}\n- **Rework Rate:** {:.2}\n",
+            coordination.active_agents,
+            coordination.messages_per_completed_task,
+            coordination.conflict_rate,
+            coordination.rework_rate
+        )).unwrap_or_default()
     );
     
     Ok(markdown)
@@ -0,0 +1,195 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CliContext;
+use clap::Subcommand;
+use colored::*;
+use rhema_api::RhemaResult;
+use rhema_core::agent_tokens::{parse_ttl, AgentTokenStore, TokenScope, TokenStatus};
+use rhema_core::RhemaError;
+
+#[derive(Subcommand)]
+pub enum AuthSubcommands {
+    /// Issue a signed token granting an agent access to scopes
+    Issue {
+        /// Agent the token is issued to
+        #[arg(long, value_name = "ID")]
+        agent: String,
+
+        /// Comma-separated grants such as read:api,write:docs; `*` matches every scope
+        #[arg(long, value_name = "GRANTS")]
+        scopes: String,
+
+        /// Lifetime such as 90m, 24h or 7d
+        #[arg(long, default_value = "24h")]
+        ttl: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List issued tokens and whether they are active, expired or revoked
+    List {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Revoke a token, or every token of an agent
+    Revoke {
+        /// Token id to revoke
+        #[arg(value_name = "ID", required_unless_present = "agent")]
+        id: Option<String>,
+
+        /// Revoke every token issued to this agent
+        #[arg(long, conflicts_with = "id")]
+        agent: Option<String>,
+
+        /// Reason recorded in the revocation list
+        #[arg(long)]
+        reason: Option<String>,
+    },
+
+    /// Show attempts to use expired or revoked tokens
+    Audit {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+pub fn handle_auth(context: &CliContext, subcommand: &AuthSubcommands) -> RhemaResult<()> {
+    let store = AgentTokenStore::new(context.rhema.repo_root());
+
+    match subcommand {
+        AuthSubcommands::Issue {
+            agent,
+            scopes,
+            ttl,
+            json,
+        } => {
+            let grants = context.handle_error(TokenScope::parse_list(scopes))?;
+            if grants.is_empty() {
+                return context.handle_error(Err(RhemaError::InvalidInput(
+                    "--scopes needs at least one grant such as read:<scope>".to_string(),
+                )));
+            }
+            let known: Vec<String> = context
+                .handle_error(context.rhema.discover_scopes())?
+                .into_iter()
+                .map(|scope| scope.definition.name)
+                .collect();
+            for grant in &grants {
+                if grant.scope != "*" && !known.contains(&grant.scope) {
+                    context.display_warning(&format!("No scope named '{}'", grant.scope))?;
+                }
+            }
+
+            let ttl = context.handle_error(parse_ttl(ttl))?;
+            let issued = context.handle_error(store.issue(agent, grants, ttl))?;
+
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&issued)?);
+                return Ok(());
+            }
+            let grants: Vec<String> = issued.claims.scopes.iter().map(|g| g.to_string()).collect();
+            println!(
+                "🔑 Issued token {} to {}",
+                issued.claims.id,
+                issued.claims.agent.bold()
+            );
+            println!("   Grants:  {}", grants.join(", "));
+            println!("   Expires: {}", issued.claims.expires_at.to_rfc3339());
+            println!("\n{}", issued.token);
+            println!("\n{}", "The token is not stored; copy it now.".dimmed());
+        }
+
+        AuthSubcommands::List { json } => {
+            let tokens = context.handle_error(store.list())?;
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&tokens)?);
+                return Ok(());
+            }
+            if tokens.is_empty() {
+                println!("📭 No agent tokens issued");
+                return Ok(());
+            }
+            for token in &tokens {
+                let status = match token.status {
+                    TokenStatus::Active => token.status.to_string().green(),
+                    TokenStatus::Expired => token.status.to_string().yellow(),
+                    TokenStatus::Revoked => token.status.to_string().red(),
+                };
+                let grants: Vec<String> =
+                    token.claims.scopes.iter().map(|g| g.to_string()).collect();
+                println!(
+                    "🔑 {} {} [{}] {}",
+                    token.claims.id,
+                    token.claims.agent.bold(),
+                    status,
+                    format!(
+                        "{} · expires {}",
+                        grants.join(", "),
+                        token.claims.expires_at.to_rfc3339()
+                    )
+                    .dimmed()
+                );
+            }
+        }
+
+        AuthSubcommands::Revoke { id, agent, reason } => {
+            let revoked = match (id, agent) {
+                (Some(id), _) => vec![context.handle_error(store.revoke(id, reason.clone()))?],
+                (None, Some(agent)) => {
+                    context.handle_error(store.revoke_agent(agent, reason.clone()))?
+                }
+                (None, None) => unreachable!("clap requires an id or --agent"),
+            };
+            for revocation in &revoked {
+                println!(
+                    "🚫 Revoked {} of {}",
+                    revocation.id,
+                    revocation.agent.bold()
+                );
+            }
+        }
+
+        AuthSubcommands::Audit { json } => {
+            let entries = context.handle_error(store.audit_log())?;
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&entries)?);
+                return Ok(());
+            }
+            if entries.is_empty() {
+                println!("✅ No expired or revoked tokens have been used");
+                return Ok(());
+            }
+            for entry in &entries {
+                println!(
+                    "⚠️  {} {} used {} token {} via {}",
+                    entry.timestamp.to_rfc3339().dimmed(),
+                    entry.agent.bold(),
+                    entry.status,
+                    entry.token_id,
+                    entry.surface
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -16,8 +16,29 @@
 
 use crate::CliContext;
 use clap::Subcommand;
-use rhema_api::RhemaResult;
-use rhema_coordination::agent::real_time_coordination::{AgentStatus, MessagePriority};
+use colored::*;
+use rhema_api::{RhemaError, RhemaResult};
+use rhema_coordination::agent::real_time_coordination::CoordinationConfig as SystemConfig;
+use rhema_coordination::agent::real_time_coordination::{
+    AgentStatus, FaultToleranceConfig, MessagePriority,
+};
+use rhema_coordination::chaos::{AssertionStatus, ChaosConfig, FaultToleranceSuite};
+use rhema_coordination::loadtest::{
+    run_capacity, CapacitySweep, InProcessTarget, LoadTarget, SloConfig, SweepDimension,
+    WorkloadConfig,
+};
+use rhema_coordination::persistence::backend::CURRENT_SCHEMA_VERSION;
+use rhema_coordination::persistence::session_inspector::{
+    message_type_name, SessionMessage, SessionSummary,
+};
+use rhema_coordination::persistence::{
+    migrate_backend, open_engine_url, Collection, MessageFilter, PersistenceConfig,
+    SessionInspector,
+};
+use rhema_testkit::{RepoSpec, SyntheticRepo};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 
 #[derive(Subcommand)]
 pub enum AgentSubcommands {
@@ -271,6 +292,66 @@ pub enum SystemSubcommands {
     },
 }
 
+/// Output format for session inspection commands
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum InspectOutput {
+    Text,
+    Json,
+}
+
+#[derive(Subcommand)]
+pub enum SessionsSubcommands {
+    /// List persisted coordination sessions
+    List {
+        /// Show only active sessions
+        #[arg(long)]
+        active: bool,
+
+        /// Only sessions this agent participates in
+        #[arg(long, value_name = "AGENT_ID")]
+        agent: Option<String>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        output: InspectOutput,
+    },
+
+    /// Show a session with its participants, decisions and messages
+    Show {
+        /// Session ID
+        #[arg(value_name = "SESSION_ID")]
+        session_id: String,
+
+        /// Number of most recent messages to show
+        #[arg(long, default_value = "20")]
+        limit: usize,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        output: InspectOutput,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum StorageSubcommands {
+    /// Show the engine and schema version of a storage location
+    Status {
+        /// Storage location: memory, file:<path>, sled:<path>, sqlite://... or postgres://...
+        #[arg(value_name = "LOCATION")]
+        location: String,
+    },
+
+    /// Copy all coordination data from one storage backend to another
+    Migrate {
+        /// Source location: file:<path>, sled:<path>, sqlite://... or postgres://...
+        #[arg(long, value_name = "LOCATION")]
+        from: String,
+
+        /// Target location: file:<path>, sled:<path>, sqlite://... or postgres://...
+        #[arg(long, value_name = "LOCATION")]
+        to: String,
+    },
+}
 #[derive(Subcommand)]
 pub enum CoordinationSubcommands {
     /// Agent management
@@ -290,9 +371,191 @@ pub enum CoordinationSubcommands {
         #[command(subcommand)]
         subcommand: SystemSubcommands,
     },
+
+    /// Inspect sessions persisted by the coordination daemon
+    Sessions {
+        /// Session store directory (defaults to the daemon's `data` directory)
+        #[arg(long, value_name = "DIR", global = true)]
+        store: Option<PathBuf>,
+
+        #[command(subcommand)]
+        subcommand: SessionsSubcommands,
+    },
+
+    /// Stream session messages as they arrive
+    Tail {
+        /// Only messages from this session (defaults to all sessions)
+        #[arg(long, value_name = "SESSION_ID")]
+        session: Option<String>,
+
+        /// Only messages sent by or addressed to this agent
+        #[arg(long, value_name = "AGENT_ID")]
+        agent: Option<String>,
+
+        /// Only messages of this type (repeatable)
+        #[arg(long = "type", value_name = "TYPE")]
+        message_types: Vec<String>,
+
+        /// Only messages at or above this priority
+        #[arg(long, value_enum)]
+        priority: Option<MessagePriority>,
+
+        /// Print existing messages before following new ones
+        #[arg(long)]
+        from_start: bool,
+
+        /// Poll interval in milliseconds
+        #[arg(long, default_value = "500")]
+        interval_ms: u64,
+
+        /// Session store directory (defaults to the daemon's `data` directory)
+        #[arg(long, value_name = "DIR")]
+        store: Option<PathBuf>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        output: InspectOutput,
+    },
+
+    /// Manage persistence storage backends and schema migrations
+    Storage {
+        #[command(subcommand)]
+        subcommand: StorageSubcommands,
+    },
+
+    /// Load test the coordination system and report capacity
+    Loadtest {
+        /// Simulated agents in the first stage
+        #[arg(long, default_value = "10")]
+        agents: usize,
+
+        /// Messages per second sent by each agent
+        #[arg(long, default_value = "5")]
+        rate: f64,
+
+        /// Seconds each stage sends messages
+        #[arg(long, default_value = "10")]
+        duration: f64,
+
+        /// Fraction of messages sent to sessions instead of single agents
+        #[arg(long, default_value = "0.3")]
+        session_ratio: f64,
+
+        /// Agents per session
+        #[arg(long, default_value = "4")]
+        session_size: usize,
+
+        /// Message body size in bytes
+        #[arg(long, default_value = "256")]
+        payload_bytes: usize,
+
+        /// Testkit repository spec (YAML) whose generated entries are sent as
+        /// message bodies instead of filler
+        #[arg(long, value_name = "SPEC")]
+        fixture: Option<PathBuf>,
+
+        /// Deliveries later than this many milliseconds count as dropped
+        #[arg(long, default_value = "1000")]
+        delivery_timeout_ms: u64,
+
+        /// Workload parameter grown between stages
+        #[arg(long, value_enum, default_value = "agents")]
+        sweep: SweepDimension,
+
+        /// Largest agent count (or rate) to try; runs a single stage if omitted
+        #[arg(long, value_name = "N")]
+        max: Option<f64>,
+
+        /// Multiplier applied to the swept parameter between stages
+        #[arg(long, default_value = "2")]
+        factor: f64,
+
+        /// SLO: maximum p99 delivery latency in milliseconds
+        #[arg(long, default_value = "50")]
+        slo_p99_ms: f64,
+
+        /// SLO: maximum fraction of deliveries dropped
+        #[arg(long, default_value = "0.001")]
+        slo_drop_rate: f64,
+
+        /// SLO: maximum fraction of operations rejected
+        #[arg(long, default_value = "0.001")]
+        slo_error_rate: f64,
+
+        /// SLO: minimum fraction of the offered rate actually sent
+        #[arg(long, default_value = "0.95")]
+        slo_min_throughput: f64,
+
+        /// Daemon limit: maximum session participants
+        #[arg(long)]
+        max_session_participants: Option<usize>,
+
+        /// Daemon limit: maximum message history size
+        #[arg(long)]
+        max_message_history: Option<usize>,
+
+        /// Write the capacity report to a file (Markdown, or JSON for .json)
+        #[arg(long, value_name = "FILE")]
+        report: Option<PathBuf>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        output: InspectOutput,
+    },
+
+    /// Inject faults with a seeded chaos run and check fault tolerance holds
+    Chaos {
+        /// Seed for fault injection; the same seed replays the same faults
+        #[arg(long, default_value = "0")]
+        seed: u64,
+
+        /// Probability that a delivery attempt is dropped
+        #[arg(long, default_value = "0.3")]
+        drop_rate: f64,
+
+        /// Probability that a delivery attempt is delayed
+        #[arg(long, default_value = "0.1")]
+        delay_rate: f64,
+
+        /// Probability that a heartbeat arrives late
+        #[arg(long, default_value = "0.3")]
+        heartbeat_delay_rate: f64,
+
+        /// Messages sent in the retry scenario
+        #[arg(long, default_value = "100")]
+        messages: usize,
+
+        /// Fault tolerance: retries per delivery
+        #[arg(long, default_value = "3")]
+        max_retry_attempts: u32,
+
+        /// Fault tolerance: delay between retries in milliseconds
+        #[arg(long, default_value = "10")]
+        retry_delay_ms: u64,
+
+        /// Fault tolerance: failures before a circuit breaker opens
+        #[arg(long, default_value = "5")]
+        breaker_threshold: u32,
+
+        /// Fault tolerance: seconds before an open breaker half-opens
+        #[arg(long, default_value = "1")]
+        breaker_timeout: u64,
+
+        /// Seconds without a heartbeat before an agent expires
+        #[arg(long, default_value = "1")]
+        agent_timeout: u64,
+
+        /// Skip scenarios that would wait longer than this many seconds
+        #[arg(long, default_value = "5")]
+        max_wait: u64,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        output: InspectOutput,
+    },
 }
 
-pub fn handle_coordination(
+pub async fn handle_coordination(
     context: &CliContext,
     subcommand: &CoordinationSubcommands,
 ) -> RhemaResult<()> {
@@ -300,7 +563,487 @@ pub fn handle_coordination(
         CoordinationSubcommands::Agent { subcommand } => handle_agent(context, subcommand),
         CoordinationSubcommands::Session { subcommand } => handle_session(context, subcommand),
         CoordinationSubcommands::System { subcommand } => handle_system(context, subcommand),
+        CoordinationSubcommands::Sessions { store, subcommand } => {
+            let inspector = SessionInspector::new(store_path(context, store.as_ref()));
+            handle_sessions(context, &inspector, subcommand).await
+        }
+        CoordinationSubcommands::Tail {
+            session,
+            agent,
+            message_types,
+            priority,
+            from_start,
+            interval_ms,
+            store,
+            output,
+        } => {
+            let mut inspector = SessionInspector::new(store_path(context, store.as_ref()));
+            let filter = MessageFilter {
+                agent_id: agent.clone(),
+                message_types: message_types.clone(),
+                min_priority: priority.clone(),
+            };
+            handle_tail(
+                context,
+                &mut inspector,
+                session.as_deref(),
+                &filter,
+                *from_start,
+                Duration::from_millis(*interval_ms),
+                *output,
+            )
+            .await
+        }
+        CoordinationSubcommands::Storage { subcommand } => {
+            handle_storage(context, subcommand).await
+        }
+        CoordinationSubcommands::Loadtest {
+            agents,
+            rate,
+            duration,
+            session_ratio,
+            session_size,
+            payload_bytes,
+            fixture,
+            delivery_timeout_ms,
+            sweep,
+            max,
+            factor,
+            slo_p99_ms,
+            slo_drop_rate,
+            slo_error_rate,
+            slo_min_throughput,
+            max_session_participants,
+            max_message_history,
+            report,
+            output,
+        } => {
+            let payloads = match fixture {
+                Some(path) => {
+                    SyntheticRepo::generate(&RepoSpec::from_file(path)?)?.entry_documents()?
+                }
+                None => Vec::new(),
+            };
+            let workload = WorkloadConfig {
+                agents: *agents,
+                rate_per_agent: *rate,
+                duration_secs: *duration,
+                session_ratio: *session_ratio,
+                session_size: *session_size,
+                payload_bytes: *payload_bytes,
+                payloads,
+                delivery_timeout_ms: *delivery_timeout_ms,
+                ..Default::default()
+            };
+            let slo = SloConfig {
+                max_p99_latency_ms: *slo_p99_ms,
+                max_drop_rate: *slo_drop_rate,
+                max_error_rate: *slo_error_rate,
+                min_throughput_ratio: *slo_min_throughput,
+            };
+            let start = match sweep {
+                SweepDimension::Agents => *agents as f64,
+                SweepDimension::Rate => *rate,
+            };
+            let sweep = CapacitySweep {
+                dimension: *sweep,
+                max_value: max.unwrap_or(start),
+                factor: *factor,
+            };
+            let mut config = SystemConfig::default();
+            if let Some(limit) = max_session_participants {
+                config.max_session_participants = *limit;
+            }
+            if let Some(limit) = max_message_history {
+                config.max_message_history = *limit;
+            }
+            handle_loadtest(
+                context,
+                config,
+                &workload,
+                &slo,
+                &sweep,
+                report.as_ref(),
+                *output,
+            )
+            .await
+        }
+        CoordinationSubcommands::Chaos {
+            seed,
+            drop_rate,
+            delay_rate,
+            heartbeat_delay_rate,
+            messages,
+            max_retry_attempts,
+            retry_delay_ms,
+            breaker_threshold,
+            breaker_timeout,
+            agent_timeout,
+            max_wait,
+            output,
+        } => {
+            let mut suite = FaultToleranceSuite::new(
+                FaultToleranceConfig {
+                    max_retry_attempts: *max_retry_attempts,
+                    retry_delay_ms: *retry_delay_ms,
+                    circuit_breaker_threshold: *breaker_threshold,
+                    circuit_breaker_timeout_seconds: *breaker_timeout,
+                    ..Default::default()
+                },
+                ChaosConfig {
+                    enabled: true,
+                    seed: *seed,
+                    message_drop_rate: *drop_rate,
+                    message_delay_rate: *delay_rate,
+                    max_message_delay_ms: 20,
+                    heartbeat_delay_rate: *heartbeat_delay_rate,
+                    heartbeat_delay_ms: 300,
+                    ..Default::default()
+                },
+            );
+            suite.messages = *messages;
+            suite.coordination.agent_timeout_seconds = *agent_timeout;
+            suite.max_wait = Duration::from_secs(*max_wait);
+            handle_chaos(context, &suite, *output).await
+        }
+    }
+}
+
+async fn handle_chaos(
+    context: &CliContext,
+    suite: &FaultToleranceSuite,
+    output: InspectOutput,
+) -> RhemaResult<()> {
+    if output == InspectOutput::Text {
+        println!("🌪️  Chaos run with seed {}", suite.chaos.seed);
+    }
+    let report = context.handle_error(suite.run().await)?;
+
+    if output == InspectOutput::Json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        for assertion in &report.assertions {
+            let status = match assertion.status {
+                AssertionStatus::Passed => "PASS".green(),
+                AssertionStatus::Failed => "FAIL".red().bold(),
+                AssertionStatus::Skipped => "SKIP".yellow(),
+            };
+            println!("  {} {}: {}", status, assertion.name, assertion.detail);
+        }
+        let faults: Vec<String> = report
+            .faults
+            .iter()
+            .map(|(kind, count)| format!("{} {}", count, kind))
+            .collect();
+        println!("💉 Faults injected: {}", faults.join(", "));
+    }
+
+    if report.passed() {
+        if output == InspectOutput::Text {
+            println!("✅ Fault tolerance held under chaos");
+        }
+        Ok(())
+    } else {
+        Err(RhemaError::CoordinationError(format!(
+            "Fault tolerance assertions failed under chaos seed {}",
+            report.seed
+        )))
+    }
+}
+
+async fn handle_loadtest(
+    context: &CliContext,
+    config: SystemConfig,
+    workload: &WorkloadConfig,
+    slo: &SloConfig,
+    sweep: &CapacitySweep,
+    report_path: Option<&PathBuf>,
+    output: InspectOutput,
+) -> RhemaResult<()> {
+    if output == InspectOutput::Text {
+        println!(
+            "🏋️  Load testing with {} agents at {} msg/s each ({}s per stage)",
+            workload.agents, workload.rate_per_agent, workload.duration_secs
+        );
+        if !workload.payloads.is_empty() {
+            println!(
+                "   Sending {} generated fixture entries as message bodies",
+                workload.payloads.len()
+            );
+        }
+    }
+    let report = context.handle_error(
+        run_capacity(
+            || Arc::new(InProcessTarget::new(config.clone())) as Arc<dyn LoadTarget>,
+            workload,
+            slo,
+            sweep,
+        )
+        .await,
+    )?;
+
+    if let Some(path) = report_path {
+        let content = if path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+            serde_json::to_string_pretty(&report)?
+        } else {
+            report.to_markdown()
+        };
+        std::fs::write(path, content)?;
+    }
+
+    if output == InspectOutput::Json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+    for stage in &report.stages {
+        let status = if stage.passed() {
+            "PASS".green()
+        } else {
+            "FAIL".red().bold()
+        };
+        println!(
+            "  {} {} agents × {} msg/s: {:.1} sent/s, {:.1} delivered/s, p50 {:.2}ms, p99 {:.2}ms, drop {:.4}",
+            status,
+            stage.agents,
+            stage.rate_per_agent,
+            stage.achieved_rate,
+            stage.delivery_throughput,
+            stage.delivery_latency.p50_ms,
+            stage.delivery_latency.p99_ms,
+            stage.drop_rate
+        );
+        for violation in &stage.violations {
+            println!("      ✗ {}", violation);
+        }
+    }
+    match &report.max_sustainable {
+        Some(stage) => println!(
+            "✅ Capacity: {} agents at {} msg/s each within SLOs",
+            stage.agents, stage.rate_per_agent
+        ),
+        None => println!("❌ No stage met the SLOs"),
     }
+    if let Some(stage) = &report.breaking_point {
+        println!(
+            "💥 SLOs broke at {} agents at {} msg/s each",
+            stage.agents, stage.rate_per_agent
+        );
+        if let Some(error) = &stage.first_error {
+            println!("   First error: {}", error);
+        }
+    }
+    if let Some(path) = report_path {
+        println!("📄 Capacity report written to {}", path.display());
+    }
+    Ok(())
+}
+
+async fn handle_storage(context: &CliContext, subcommand: &StorageSubcommands) -> RhemaResult<()> {
+    match subcommand {
+        StorageSubcommands::Status { location } => {
+            let engine = context.handle_error(open_engine_url(location).await)?;
+            println!("🗄️  Engine: {}", engine.name());
+            println!(
+                "📋 Schema version: {} (latest {})",
+                engine.schema_version().await?,
+                CURRENT_SCHEMA_VERSION
+            );
+            for collection in Collection::ALL {
+                let records = engine.list(collection).await?.len();
+                let entries = engine.read_log(collection, 0, usize::MAX).await?.len();
+                println!(
+                    "  • {}: {} records, {} log entries",
+                    collection.as_str(),
+                    records,
+                    entries
+                );
+            }
+        }
+        StorageSubcommands::Migrate { from, to } => {
+            if from == to {
+                return Err(RhemaError::InvalidInput(
+                    "Source and target storage locations are the same".to_string(),
+                ));
+            }
+            let source = context.handle_error(open_engine_url(from).await)?;
+            let target = context.handle_error(open_engine_url(to).await)?;
+            println!("🔄 Migrating {} → {}", source.name(), target.name());
+
+            let report = context.handle_error(migrate_backend(&*source, &*target).await)?;
+            for (collection, count) in &report.per_collection {
+                println!("  • {}: {}", collection, count);
+            }
+            println!(
+                "✅ Copied {} records and {} log entries",
+                report.records, report.log_entries
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Resolve the session store directory relative to the repository root
+fn store_path(context: &CliContext, store: Option<&PathBuf>) -> PathBuf {
+    let store = store.cloned().unwrap_or_else(|| {
+        PersistenceConfig::default()
+            .storage_path
+            .unwrap_or_else(|| PathBuf::from("data"))
+    });
+    if store.is_absolute() {
+        store
+    } else {
+        context.rhema.repo_root().join(store)
+    }
+}
+
+async fn handle_sessions(
+    context: &CliContext,
+    inspector: &SessionInspector,
+    subcommand: &SessionsSubcommands,
+) -> RhemaResult<()> {
+    match subcommand {
+        SessionsSubcommands::List {
+            active,
+            agent,
+            output,
+        } => {
+            let sessions: Vec<SessionSummary> = context
+                .handle_error(inspector.list_sessions(*active).await)?
+                .into_iter()
+                .filter(|s| agent.as_ref().map_or(true, |a| s.participants.contains(a)))
+                .collect();
+
+            if *output == InspectOutput::Json {
+                println!("{}", serde_json::to_string_pretty(&sessions)?);
+                return Ok(());
+            }
+            if sessions.is_empty() {
+                println!("📭 No coordination sessions found");
+                return Ok(());
+            }
+            println!("📋 Found {} sessions:", sessions.len());
+            for session in &sessions {
+                println!(
+                    "  • {} {} [{}] {} participants, {} messages, last activity {}",
+                    session.id.bold(),
+                    session.topic,
+                    format!("{:?}", session.status).cyan(),
+                    session.participants.len(),
+                    session.message_count,
+                    session.last_activity.format("%Y-%m-%d %H:%M:%S")
+                );
+            }
+            Ok(())
+        }
+        SessionsSubcommands::Show {
+            session_id,
+            limit,
+            output,
+        } => {
+            let session = context.handle_error(inspector.get_session(session_id).await)?;
+
+            if *output == InspectOutput::Json {
+                println!("{}", serde_json::to_string_pretty(&session)?);
+                return Ok(());
+            }
+            let summary = SessionSummary::from(&session);
+            println!("💬 Session {}", session.id.bold());
+            println!("📝 Topic: {}", session.topic);
+            println!("📊 Status: {:?}", session.status);
+            println!(
+                "🕐 Started: {}",
+                session.started_at.format("%Y-%m-%d %H:%M:%S")
+            );
+            if let Some(ended_at) = session.ended_at {
+                println!("🏁 Ended: {}", ended_at.format("%Y-%m-%d %H:%M:%S"));
+            }
+            println!("👥 Participants: {}", session.participants.join(", "));
+            println!("⚖️  Decisions: {}", summary.decision_count);
+            for decision in &session.decisions {
+                println!("    • {}", decision.topic);
+            }
+            println!(
+                "✉️  Messages: {} (showing last {})",
+                summary.message_count,
+                (*limit).min(summary.message_count)
+            );
+            let skip = session.messages.len().saturating_sub(*limit);
+            for message in session.messages.into_iter().skip(skip) {
+                print_message(&SessionMessage {
+                    session_id: session.id.clone(),
+                    message,
+                });
+            }
+            Ok(())
+        }
+    }
+}
+
+async fn handle_tail(
+    context: &CliContext,
+    inspector: &mut SessionInspector,
+    session: Option<&str>,
+    filter: &MessageFilter,
+    from_start: bool,
+    interval: Duration,
+    output: InspectOutput,
+) -> RhemaResult<()> {
+    // The first poll marks everything already persisted as seen
+    let backlog = context.handle_error(inspector.poll_messages(session, filter).await)?;
+    if from_start {
+        for message in &backlog {
+            emit_message(message, output)?;
+        }
+    }
+
+    if output == InspectOutput::Text {
+        println!(
+            "👀 Tailing {} (Ctrl+C to stop)",
+            session.map_or("all sessions".to_string(), |s| format!("session {}", s))
+        );
+    }
+
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {
+                let messages = context.handle_error(inspector.poll_messages(session, filter).await)?;
+                for message in &messages {
+                    emit_message(message, output)?;
+                }
+            }
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+    Ok(())
+}
+
+fn emit_message(message: &SessionMessage, output: InspectOutput) -> RhemaResult<()> {
+    match output {
+        InspectOutput::Json => println!("{}", serde_json::to_string(message)?),
+        InspectOutput::Text => print_message(message),
+    }
+    Ok(())
+}
+
+fn print_message(entry: &SessionMessage) {
+    let message = &entry.message;
+    let priority = format!("{:?}", message.priority);
+    let priority = match message.priority {
+        MessagePriority::Low => priority.dimmed(),
+        MessagePriority::Normal => priority.normal(),
+        MessagePriority::High => priority.yellow(),
+        MessagePriority::Critical | MessagePriority::Emergency => priority.red().bold(),
+    };
+    println!(
+        "{} {} {} {} → {}: {}",
+        message.timestamp.format("%H:%M:%S").to_string().dimmed(),
+        format!("[{}]", entry.session_id).blue(),
+        priority,
+        message.sender_id.green(),
+        message_type_name(&message.message_type).cyan(),
+        message.content
+    );
 }
 
 fn handle_agent(context: &CliContext, subcommand: &AgentSubcommands) -> RhemaResult<()> {
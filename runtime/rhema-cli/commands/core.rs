@@ -15,7 +15,20 @@
  */
 
 use crate::CliContext;
+use rhema_api::init_wizard::{ProjectDetection, WizardPlan, ACTION_TOOLS, WORKFLOW_TEMPLATES};
 use rhema_api::RhemaResult;
+use rhema_core::profiling::{self, Phase};
+use rhema_mcp::watcher::{FileWatcher, WatcherConfig};
+use rhema_query::lint::{lint_query, LintSeverity};
+use rhema_query::mutation::{apply_mutation, plan_mutation, MutationOptions};
+use rhema_query::query::{
+    execute_query_with_config, execute_query_with_provenance, get_query_stats,
+    QueryExecutionConfig,
+};
+use rhema_query::subscription::{DeltaItem, QuerySubscriptionManager};
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
 
 pub fn handle_init(
     context: &CliContext,
@@ -46,6 +59,147 @@ pub fn handle_init(
     }
 }
 
+/// Detect project characteristics and walk the user through initial setup
+pub fn handle_init_wizard(context: &CliContext, yes: bool) -> RhemaResult<()> {
+    let repo_root = context.rhema.repo_root().clone();
+    println!("🔍 Detecting project characteristics...");
+    let detection = context.handle_error(ProjectDetection::detect(&repo_root))?;
+    print_detection(&detection);
+
+    let mut plan = WizardPlan::propose(&repo_root, detection);
+    if !yes {
+        let stdin = io::stdin();
+        let mut input = stdin.lock();
+        review_plan(&mut plan, &mut input)?;
+
+        println!();
+        println!("{}", context.handle_error(plan.render_config(&repo_root))?);
+        if !confirm(&mut input, "Write this configuration?", true)? {
+            println!("🚫 Setup cancelled, nothing was written");
+            return Ok(());
+        }
+    }
+
+    let report = context.handle_error(plan.apply(&repo_root))?;
+    for path in &report.created_scopes {
+        println!("✅ Created scope at {}", path.display());
+    }
+    for path in &report.existing_scopes {
+        println!("⏭️  Kept existing scope at {}", path.display());
+    }
+    println!("📝 Wrote {}", report.config_path.display());
+    println!();
+    println!("  Next steps:");
+    println!("    • Review .rhema/repository.yaml and adjust the enabled action tools");
+    println!("    • Run `rhema scopes` to see the created scopes");
+    Ok(())
+}
+
+fn print_detection(detection: &ProjectDetection) {
+    let join = |values: &[String]| {
+        if values.is_empty() {
+            "none detected".to_string()
+        } else {
+            values.join(", ")
+        }
+    };
+    println!("💻 Languages: {}", join(&detection.languages));
+    println!("🔧 Build systems: {}", join(&detection.build_systems));
+    let ci: Vec<String> = detection
+        .ci_providers
+        .iter()
+        .map(|p| p.to_string())
+        .collect();
+    println!("🚦 CI: {}", join(&ci));
+    if detection.is_monorepo() {
+        let tools: Vec<String> = detection
+            .monorepo_tools
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+        println!(
+            "📦 Monorepo with {} packages ({})",
+            detection.packages.len(),
+            join(&tools)
+        );
+    }
+}
+
+/// Let the user adjust proposed scopes, workflow and tools
+fn review_plan(plan: &mut WizardPlan, input: &mut impl BufRead) -> RhemaResult<()> {
+    println!();
+    println!("📁 Proposed scopes:");
+    for scope in &mut plan.scopes {
+        let location = if scope.path.as_os_str().is_empty() {
+            ".".to_string()
+        } else {
+            scope.path.display().to_string()
+        };
+        scope.selected = confirm(
+            input,
+            &format!(
+                "  Create {} scope '{}' at {}?",
+                scope.scope_type, scope.name, location
+            ),
+            true,
+        )?;
+    }
+
+    println!();
+    println!("🌿 Git workflow templates:");
+    let default_index = WORKFLOW_TEMPLATES
+        .iter()
+        .position(|(name, _)| *name == plan.workflow)
+        .unwrap_or(0);
+    for (index, (name, description)) in WORKFLOW_TEMPLATES.iter().enumerate() {
+        let marker = if index == default_index { "*" } else { " " };
+        println!("  {} {}. {:<12} {}", marker, index + 1, name, description);
+    }
+    let answer = prompt(input, &format!("Workflow [{}]: ", default_index + 1))?;
+    if let Ok(choice) = answer.parse::<usize>() {
+        if let Some((name, _)) = WORKFLOW_TEMPLATES.get(choice.wrapping_sub(1)) {
+            plan.workflow = name.to_string();
+        }
+    }
+
+    println!();
+    println!("🛠️  Available action tools: {}", ACTION_TOOLS.join(", "));
+    let answer = prompt(
+        input,
+        &format!("Enabled tools [{}]: ", plan.tools.join(", ")),
+    )?;
+    if !answer.is_empty() {
+        let mut tools = Vec::new();
+        for tool in answer.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+            if ACTION_TOOLS.contains(&tool) {
+                tools.push(tool.to_string());
+            } else {
+                println!("⚠️  Ignoring unknown tool '{}'", tool);
+            }
+        }
+        plan.tools = tools;
+    }
+    Ok(())
+}
+
+fn prompt(input: &mut impl BufRead, question: &str) -> RhemaResult<String> {
+    print!("{}", question);
+    io::stdout().flush()?;
+    let mut line = String::new();
+    input.read_line(&mut line)?;
+    Ok(line.trim().to_string())
+}
+
+fn confirm(input: &mut impl BufRead, question: &str, default: bool) -> RhemaResult<bool> {
+    let hint = if default { "[Y/n]" } else { "[y/N]" };
+    let answer = prompt(input, &format!("{} {} ", question, hint))?;
+    Ok(match answer.to_lowercase().as_str() {
+        "y" | "yes" => true,
+        "n" | "no" => false,
+        _ => default,
+    })
+}
+
 pub fn handle_query(
     context: &CliContext,
     query: &str,
@@ -53,31 +207,53 @@ pub fn handle_query(
     provenance: bool,
     field_provenance: bool,
     stats: bool,
+    workers: Option<usize>,
 ) -> RhemaResult<()> {
-    // TODO: Implement actual query functionality
-    // For now, just return a placeholder response
-    println!("🔍 Query: {}", query);
-    println!("📊 Format: {}", format);
-    println!("📚 Provenance: {}", provenance);
-    println!("🔍 Field provenance: {}", field_provenance);
-    println!("📈 Stats: {}", stats);
-
-    // Placeholder response
+    let repo_root = context.rhema.repo_root();
+    let mut config = context.handle_error(QueryExecutionConfig::load(repo_root))?;
+    if let Some(workers) = workers {
+        config = config.with_worker_threads(workers);
+    }
+    let result = context.handle_error(execute_query_with_config(repo_root, query, &config))?;
+
+    let mut sections = serde_yaml::Mapping::new();
+    if stats {
+        let stats = context.handle_error(get_query_stats(repo_root, query))?;
+        sections.insert("stats".into(), serde_yaml::to_value(stats)?);
+    }
+    if provenance || field_provenance {
+        let (_, provenance) =
+            context.handle_error(execute_query_with_provenance(repo_root, query))?;
+        sections.insert("provenance".into(), serde_yaml::to_value(provenance)?);
+    }
+
+    let _render = profiling::phase(Phase::Render);
     match format.to_lowercase().as_str() {
-        "json" => {
-            println!(
-                "{{\"query\": \"{}\", \"status\": \"not_implemented\"}}",
-                query
-            );
-        }
-        "yaml" => {
-            println!("query: {}", query);
-            println!("status: not_implemented");
+        "json" | "yaml" => {
+            let mut output = serde_yaml::Mapping::new();
+            output.insert("result".into(), result);
+            output.extend(sections);
+            if format.eq_ignore_ascii_case("json") {
+                println!("{}", serde_json::to_string_pretty(&output)?);
+            } else {
+                print!("{}", serde_yaml::to_string(&output)?);
+            }
         }
         "table" => {
-            println!("| Query | Status |");
-            println!("|-------|--------|");
-            println!("| {} | Not Implemented |", query);
+            // Results from several scopes come back ordered by scope path
+            match &result {
+                serde_yaml::Value::Sequence(rows) => {
+                    println!("| # | Result |");
+                    println!("|---|--------|");
+                    for (i, row) in rows.iter().enumerate() {
+                        println!("| {} | {} |", i + 1, serde_json::to_string(row)?);
+                    }
+                }
+                other => print!("{}", serde_yaml::to_string(other)?),
+            }
+            if !sections.is_empty() {
+                print!("{}", serde_yaml::to_string(&sections)?);
+            }
         }
         _ => {
             return Err(rhema_core::RhemaError::ConfigError(
@@ -88,3 +264,141 @@ pub fn handle_query(
 
     Ok(())
 }
+
+/// Run a standing query and print result changes as context files change
+/// Lint a query against the current scopes without running it
+pub fn handle_query_lint(context: &CliContext, query: &str, format: &str) -> RhemaResult<()> {
+    let scopes = context.handle_error(context.rhema.discover_scopes())?;
+    let report = lint_query(query, &scopes, context.rhema.repo_root());
+
+    if format.eq_ignore_ascii_case("json") {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else if report.is_clean() {
+        println!("✅ No lint findings");
+    } else {
+        for finding in &report.findings {
+            let icon = match finding.severity {
+                LintSeverity::Error => "❌",
+                LintSeverity::Warning => "⚠️",
+                LintSeverity::Info => "ℹ️",
+            };
+            println!("{} {}", icon, finding);
+        }
+        if let Some(rows) = report.estimated_rows {
+            println!("📊 Estimated rows: {}", rows);
+        }
+    }
+
+    if report.has_errors() {
+        return Err(rhema_core::RhemaError::InvalidQuery(format!(
+            "Query has {} lint error(s)",
+            report
+                .findings
+                .iter()
+                .filter(|f| f.severity == LintSeverity::Error)
+                .count()
+        )));
+    }
+    Ok(())
+}
+
+pub async fn handle_query_watch(context: &CliContext, query: &str) -> RhemaResult<()> {
+    let repo_root = context.rhema.repo_root().clone();
+    let manager = Arc::new(QuerySubscriptionManager::new(repo_root.clone()));
+    let (id, mut deltas) = context.handle_error(manager.subscribe(query))?;
+
+    let config = WatcherConfig {
+        watch_dirs: vec![PathBuf::from(".")],
+        file_patterns: vec!["*.yaml".to_string(), "*.yml".to_string()],
+        ..WatcherConfig::default()
+    };
+    let watcher = FileWatcher::new(&config, repo_root).await?;
+    let forwarder = watcher.forward_to_subscriptions(manager.clone()).await;
+    watcher.start().await?;
+
+    println!("👀 Watching query ({}), press Ctrl+C to stop", id);
+    loop {
+        tokio::select! {
+            delta = deltas.recv() => {
+                let Some(delta) = delta else { break };
+                for item in &delta.added {
+                    print_delta_item("+", item);
+                }
+                for item in &delta.changed {
+                    print_delta_item("~", item);
+                }
+                for item in &delta.removed {
+                    print_delta_item("-", item);
+                }
+            }
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+
+    manager.unsubscribe(&id);
+    forwarder.abort();
+    watcher.stop().await?;
+    Ok(())
+}
+
+fn print_delta_item(marker: &str, item: &DeltaItem) {
+    let value = serde_yaml::to_string(&item.value).unwrap_or_default();
+    println!("{} {}", marker, item.key);
+    for line in value.lines() {
+        println!("    {}", line);
+    }
+}
+
+pub fn handle_mutate(
+    context: &CliContext,
+    statement: &str,
+    dry_run: bool,
+    scopes: &[String],
+    max_rows: usize,
+    yes: bool,
+    all_rows: bool,
+) -> RhemaResult<()> {
+    let repo_root = context.rhema.repo_root();
+    let options = MutationOptions {
+        allowed_scopes: scopes.to_vec(),
+        max_rows,
+        confirmed: true,
+        all_rows,
+    };
+    // Plan with the row limit lifted so the preview is always shown; the
+    // limit is enforced below before anything is written.
+    let plan = context.handle_error(plan_mutation(repo_root, statement, &options))?;
+
+    if plan.affected_rows() == 0 {
+        println!("📭 No rows match; nothing to change");
+        return Ok(());
+    }
+    println!(
+        "📋 {} row(s) in {} file(s) would change:",
+        plan.affected_rows(),
+        plan.files.len()
+    );
+    print!("{}", plan.render_preview());
+
+    if dry_run {
+        println!("👀 Dry run, no files were written");
+        return Ok(());
+    }
+
+    if plan.affected_rows() > max_rows && !yes {
+        let stdin = io::stdin();
+        let question = format!(
+            "⚠️  This changes {} rows, above the limit of {}. Apply?",
+            plan.affected_rows(),
+            max_rows
+        );
+        if !confirm(&mut stdin.lock(), &question, false)? {
+            println!("❌ Mutation cancelled");
+            return Ok(());
+        }
+    }
+
+    let written = context.handle_error(apply_mutation(&plan))?;
+    println!("✅ Applied mutation to {} row(s)", written);
+    Ok(())
+}
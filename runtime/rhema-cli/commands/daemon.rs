@@ -0,0 +1,191 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CliContext;
+use clap::Subcommand;
+use rhema_api::RhemaResult;
+use rhema_core::help::DeadEnd;
+use rhema_core::RhemaError;
+use rhema_mcp::RequestTrace;
+use serde::de::DeserializeOwned;
+
+#[derive(Subcommand)]
+pub enum DaemonSubcommands {
+    /// Reconstruct the path of a single request through the daemon
+    Trace {
+        /// Request ID (full ID or a unique prefix)
+        #[arg(value_name = "REQUEST_ID")]
+        request_id: String,
+
+        /// Daemon base URL
+        #[arg(long, value_name = "URL", default_value = "http://127.0.0.1:8080")]
+        url: String,
+
+        /// API key used to authenticate against the daemon
+        #[arg(long, value_name = "KEY")]
+        api_key: Option<String>,
+    },
+
+    /// List the most recent request traces
+    Traces {
+        /// Maximum number of traces to show
+        #[arg(long, value_name = "N", default_value = "20")]
+        limit: usize,
+
+        /// Daemon base URL
+        #[arg(long, value_name = "URL", default_value = "http://127.0.0.1:8080")]
+        url: String,
+
+        /// API key used to authenticate against the daemon
+        #[arg(long, value_name = "KEY")]
+        api_key: Option<String>,
+    },
+}
+
+pub async fn handle_daemon(
+    context: &CliContext,
+    subcommand: &DaemonSubcommands,
+) -> RhemaResult<()> {
+    match subcommand {
+        DaemonSubcommands::Trace {
+            request_id,
+            url,
+            api_key,
+        } => {
+            let endpoint = format!("{}/traces/{}", url.trim_end_matches('/'), request_id);
+            let trace: RequestTrace =
+                fetch_or_explain(context, url, &endpoint, api_key.as_deref()).await?;
+            print_trace(&trace);
+            Ok(())
+        }
+        DaemonSubcommands::Traces {
+            limit,
+            url,
+            api_key,
+        } => {
+            let endpoint = format!("{}/traces?limit={}", url.trim_end_matches('/'), limit);
+            let traces: Vec<RequestTrace> =
+                fetch_or_explain(context, url, &endpoint, api_key.as_deref()).await?;
+            if traces.is_empty() {
+                println!("📭 No traces recorded");
+            } else {
+                println!("📋 Found {} traces:", traces.len());
+                for trace in traces {
+                    println!("  • {}", summary_line(&trace));
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Fetch from the daemon at `url`, saying how to start it when nothing answers
+async fn fetch_or_explain<T: DeserializeOwned>(
+    context: &CliContext,
+    url: &str,
+    endpoint: &str,
+    api_key: Option<&str>,
+) -> RhemaResult<T> {
+    let result = context.handle_error(fetch(endpoint, api_key).await);
+    if let Err(RhemaError::NetworkError(_)) = &result {
+        context.display_next_steps(&DeadEnd::DaemonUnreachable(url.to_string()))?;
+    }
+    result
+}
+
+async fn fetch<T: DeserializeOwned>(endpoint: &str, api_key: Option<&str>) -> RhemaResult<T> {
+    let mut request = reqwest::Client::new().get(endpoint);
+    if let Some(key) = api_key {
+        request = request.header("Authorization", format!("ApiKey {}", key));
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| RhemaError::NetworkError(format!("Failed to reach daemon: {}", e)))?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::NOT_FOUND {
+        return Err(RhemaError::NotFound(format!(
+            "No trace found at {} (traces are kept in memory and may have been evicted)",
+            endpoint
+        )));
+    }
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(RhemaError::DaemonError(format!(
+            "Daemon returned {}: {}",
+            status, body
+        )));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| RhemaError::SerializationError(format!("Invalid trace payload: {}", e)))
+}
+
+fn summary_line(trace: &RequestTrace) -> String {
+    format!(
+        "{} {} {} {} [{}] {}",
+        trace.request_id,
+        trace.transport,
+        trace.method,
+        trace.target,
+        trace.status.as_deref().unwrap_or("in-flight"),
+        trace
+            .duration_ms
+            .map(|ms| format!("{}ms", ms))
+            .unwrap_or_else(|| "-".to_string())
+    )
+}
+
+fn print_trace(trace: &RequestTrace) {
+    println!("🔎 Request {}", trace.request_id);
+    println!(
+        "   {} {} {} (started {})",
+        trace.transport,
+        trace.method,
+        trace.target,
+        trace.started_at.format("%Y-%m-%d %H:%M:%S%.3f")
+    );
+    println!(
+        "   Status: {}  Duration: {}",
+        trace.status.as_deref().unwrap_or("in-flight"),
+        trace
+            .duration_ms
+            .map(|ms| format!("{}ms", ms))
+            .unwrap_or_else(|| "-".to_string())
+    );
+
+    if trace.events.is_empty() {
+        println!("   (no subsystem calls recorded)");
+        return;
+    }
+
+    println!("   Path:");
+    for event in &trace.events {
+        println!(
+            "   +{:>6}ms  {}.{}  {}  ({}ms, {})",
+            event.offset_ms,
+            event.subsystem,
+            event.operation,
+            event.detail,
+            event.duration_ms,
+            event.outcome
+        );
+    }
+}
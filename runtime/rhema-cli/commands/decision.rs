@@ -14,10 +14,14 @@
  * limitations under the License.
  */
 
+use crate::commands::coordination::InspectOutput;
 use crate::CliContext;
 use clap::Subcommand;
 use rhema_api::RhemaResult;
-use rhema_core::DecisionStatus;
+use rhema_core::decision_outcomes::discover_implementing_commits;
+use rhema_core::{
+    DecisionIncident, DecisionOutcomeReport, DecisionStatus, OutcomePeriod, Priority, RhemaError,
+};
 
 #[derive(Subcommand)]
 pub enum DecisionSubcommands {
@@ -54,6 +58,10 @@ pub enum DecisionSubcommands {
         /// Consequences (comma-separated)
         #[arg(long, value_name = "CONSEQUENCES")]
         consequences: Option<String>,
+
+        /// Field required by an entry template, as NAME=VALUE (repeatable)
+        #[arg(long = "field", value_name = "NAME=VALUE")]
+        fields: Vec<String>,
     },
 
     /// List decisions
@@ -112,6 +120,78 @@ pub enum DecisionSubcommands {
         #[arg(value_name = "ID")]
         id: String,
     },
+
+    /// Link a commit implementing a decision
+    Link {
+        /// Decision ID
+        #[arg(value_name = "ID")]
+        id: String,
+
+        /// Commit SHA
+        #[arg(long, value_name = "SHA")]
+        commit: String,
+    },
+
+    /// Attribute an incident to a decision
+    Incident {
+        /// Decision ID
+        #[arg(value_name = "ID")]
+        id: String,
+
+        /// Incident ID in the tracking system
+        #[arg(long, value_name = "INCIDENT_ID")]
+        incident_id: String,
+
+        /// Incident summary
+        #[arg(long, value_name = "TITLE")]
+        title: String,
+
+        /// Incident severity
+        #[arg(long, value_enum, default_value = "medium")]
+        severity: Priority,
+
+        /// When the incident occurred (RFC 3339, defaults to now)
+        #[arg(long, value_name = "TIMESTAMP")]
+        occurred_at: Option<String>,
+
+        /// Link to the incident record or postmortem
+        #[arg(long, value_name = "URL")]
+        url: Option<String>,
+    },
+
+    /// Record that a decision was reversed by a later one
+    Reverse {
+        /// ID of the reversed decision
+        #[arg(value_name = "ID")]
+        id: String,
+
+        /// ID of the reversing decision
+        #[arg(long, value_name = "ID")]
+        by: String,
+    },
+
+    /// Score decision quality over time from commits, incidents and reversals
+    Outcomes {
+        /// Trend granularity
+        #[arg(long, value_enum, default_value = "quarter")]
+        period: OutcomePeriod,
+
+        /// Link commits whose message mentions a decision ID before scoring
+        #[arg(long)]
+        scan_commits: bool,
+
+        /// Maximum number of commits to scan back from HEAD
+        #[arg(long, default_value = "5000")]
+        max_commits: usize,
+
+        /// Number of lowest-scoring decisions to list
+        #[arg(long, default_value = "5")]
+        worst: usize,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        output: InspectOutput,
+    },
 }
 
 pub fn handle_decision(
@@ -129,6 +209,7 @@ pub fn handle_decision(
             alternatives,
             rationale,
             consequences,
+            fields,
         } => {
             match rhema_core::file_ops::add_decision(
                 &scope.path,
@@ -140,6 +221,7 @@ pub fn handle_decision(
                 alternatives.clone(),
                 rationale.clone(),
                 consequences.clone(),
+                rhema_core::templates::parse_entry_fields(fields)?,
             ) {
                 Ok(id) => {
                     println!("🎯 Decision recorded successfully with ID: {}", id);
@@ -236,5 +318,192 @@ pub fn handle_decision(
                 }
             }
         }
+        DecisionSubcommands::Link { id, commit } => {
+            context.handle_error(rhema_core::file_ops::link_decision_commit(
+                &scope.path,
+                id,
+                commit,
+            ))?;
+            println!("🔗 Linked commit {} to decision {}", commit, id);
+            Ok(())
+        }
+        DecisionSubcommands::Incident {
+            id,
+            incident_id,
+            title,
+            severity,
+            occurred_at,
+            url,
+        } => {
+            let occurred_at = match occurred_at {
+                Some(timestamp) => context.handle_error(
+                    chrono::DateTime::parse_from_rfc3339(timestamp)
+                        .map(|t| t.with_timezone(&chrono::Utc))
+                        .map_err(|e| {
+                            RhemaError::ValidationError(format!(
+                                "Invalid incident timestamp '{}': {}",
+                                timestamp, e
+                            ))
+                        }),
+                )?,
+                None => chrono::Utc::now(),
+            };
+            let incident = DecisionIncident {
+                id: incident_id.clone(),
+                title: title.clone(),
+                severity: severity.clone(),
+                occurred_at,
+                url: url.clone(),
+            };
+            context.handle_error(rhema_core::file_ops::link_decision_incident(
+                &scope.path,
+                id,
+                incident,
+            ))?;
+            println!(
+                "🚨 Attributed incident {} ({:?}) to decision {}",
+                incident_id, severity, id
+            );
+            Ok(())
+        }
+        DecisionSubcommands::Reverse { id, by } => {
+            context.handle_error(rhema_core::file_ops::record_decision_reversal(
+                &scope.path,
+                id,
+                by,
+            ))?;
+            println!("↩️  Decision {} reversed by {} (marked deprecated)", id, by);
+            Ok(())
+        }
+        DecisionSubcommands::Outcomes {
+            period,
+            scan_commits,
+            max_commits,
+            worst,
+            output,
+        } => handle_outcomes(
+            context,
+            scope,
+            *period,
+            *scan_commits,
+            *max_commits,
+            *worst,
+            *output,
+        ),
+    }
+}
+
+fn handle_outcomes(
+    context: &CliContext,
+    scope: &rhema_core::Scope,
+    period: OutcomePeriod,
+    scan_commits: bool,
+    max_commits: usize,
+    worst: usize,
+    output: InspectOutput,
+) -> RhemaResult<()> {
+    let mut decisions = context.handle_error(rhema_core::file_ops::list_decisions(
+        &scope.path,
+        None,
+        None,
+    ))?;
+
+    if scan_commits {
+        let found = context.handle_error(discover_implementing_commits(
+            context.rhema.repo_root(),
+            &decisions,
+            max_commits,
+        ))?;
+        let mut linked = 0;
+        for decision in &mut decisions {
+            let Some(commits) = found.get(&decision.id) else {
+                continue;
+            };
+            let known = decision.implementing_commits.get_or_insert_with(Vec::new);
+            for commit in commits {
+                if !known.contains(commit) {
+                    context.handle_error(rhema_core::file_ops::link_decision_commit(
+                        &scope.path,
+                        &decision.id,
+                        commit,
+                    ))?;
+                    known.push(commit.clone());
+                    linked += 1;
+                }
+            }
+        }
+        if output == InspectOutput::Text {
+            println!("🔍 Linked {} new implementing commits", linked);
+        }
+    }
+
+    let report = DecisionOutcomeReport::build(&decisions, period);
+
+    if output == InspectOutput::Json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    if report.decisions.is_empty() {
+        println!("📭 No decisions have been made yet");
+        return Ok(());
+    }
+
+    println!(
+        "🎯 Decision outcomes ({} scored, {} pending)",
+        report.decisions.len(),
+        report.unscored
+    );
+    println!("  Mean score:          {:.2}", report.mean_score);
+    println!(
+        "  Implemented:         {:.0}%",
+        report.implementation_rate * 100.0
+    );
+    println!(
+        "  Reversed:            {:.0}%",
+        report.reversal_rate * 100.0
+    );
+    println!("  Incidents:           {}", report.total_incidents);
+    if let Some(trend) = report.trend() {
+        let arrow = if trend >= 0.0 { "📈" } else { "📉" };
+        println!("  Trend:               {} {:+.2}", arrow, trend);
     }
+
+    println!();
+    println!("📅 By period:");
+    for p in &report.periods {
+        println!(
+            "  {:<10} {:>3} decisions  score {:.2}  implemented {}  reversed {}  incidents {}",
+            p.period, p.decisions, p.mean_score, p.implemented, p.reversed, p.incidents
+        );
+    }
+
+    let low: Vec<_> = report
+        .decisions
+        .iter()
+        .filter(|o| o.score < 1.0)
+        .take(worst)
+        .collect();
+    if !low.is_empty() {
+        println!();
+        println!("⚠️  Lowest scoring decisions:");
+        for outcome in low {
+            let mut notes = Vec::new();
+            if outcome.incident_count > 0 {
+                notes.push(format!("{} incidents", outcome.incident_count));
+            }
+            if let (Some(by), Some(days)) = (&outcome.reversed_by, outcome.days_to_reversal) {
+                notes.push(format!("reversed by {} after {} days", by, days));
+            }
+            println!(
+                "  • {:.2} {} - {} ({})",
+                outcome.score,
+                outcome.id,
+                outcome.title,
+                notes.join(", ")
+            );
+        }
+    }
+
+    Ok(())
 }
@@ -0,0 +1,127 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CliContext;
+use clap::Args;
+use rhema_action_tool::environment::{probe_for, EnvironmentCache, ToolStatus, TOOL_PROBES};
+use rhema_api::RhemaResult;
+use rhema_core::RhemaError;
+
+#[derive(Args)]
+pub struct DoctorArgs {
+    /// Check the external tools actions run, with installation hints for missing ones.
+    /// This is the default check.
+    #[arg(long)]
+    tools: bool,
+
+    /// Probe every tool again instead of using the per-machine cache
+    #[arg(long)]
+    refresh: bool,
+
+    /// Output as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+pub async fn handle_doctor(context: &CliContext, args: &DoctorArgs) -> RhemaResult<()> {
+    // Tool checks are the only section so far, so they run with or without --tools
+    if !args.tools && !args.json {
+        context.display_info("Running all checks")?;
+    }
+    let statuses = probe_tools(args.refresh).await?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&statuses)?);
+        return Ok(());
+    }
+
+    let width = statuses.iter().map(|s| s.tool.len()).max().unwrap_or(4);
+    println!("🩺 Tool environment:");
+    for status in &statuses {
+        let cached = if status.cached { " (cached)" } else { "" };
+        if status.available {
+            println!(
+                "  ✅ {:<width$}  {}  {}{}",
+                status.tool,
+                status.version.as_deref().unwrap_or("unknown version"),
+                status
+                    .path
+                    .as_ref()
+                    .map_or(status.program.clone(), |path| path.display().to_string()),
+                cached,
+                width = width
+            );
+        } else {
+            println!(
+                "  ❌ {:<width$}  not found{}",
+                status.tool,
+                cached,
+                width = width
+            );
+            if let Some(probe) = probe_for(&status.tool) {
+                println!(
+                    "     {:<width$}  install: {}",
+                    "",
+                    probe.install_hint,
+                    width = width
+                );
+            }
+        }
+    }
+
+    let missing = statuses.iter().filter(|s| !s.available).count();
+    if let Some(path) = EnvironmentCache::machine().path() {
+        println!();
+        println!("Probe results cached in {}", path.display());
+    }
+    if missing == 0 {
+        println!("✅ All {} tools available", statuses.len());
+    } else {
+        context.display_warning(&format!(
+            "{} of {} tools missing; actions that need them will fail",
+            missing,
+            statuses.len()
+        ))?;
+    }
+    Ok(())
+}
+
+/// Probe every built-in tool concurrently, in table order
+async fn probe_tools(refresh: bool) -> RhemaResult<Vec<ToolStatus>> {
+    let cache = EnvironmentCache::machine();
+    let handles: Vec<_> = TOOL_PROBES
+        .iter()
+        .map(|probe| {
+            tokio::spawn(async move {
+                if refresh {
+                    cache.refresh(probe).await
+                } else {
+                    cache.status(probe).await
+                }
+            })
+        })
+        .collect();
+
+    let mut statuses = Vec::with_capacity(handles.len());
+    for handle in handles {
+        statuses.push(
+            handle
+                .await
+                .map_err(|e| RhemaError::SystemError(format!("Tool probe failed: {}", e)))?,
+        );
+    }
+    Ok(statuses)
+}
@@ -0,0 +1,105 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CliContext;
+use clap::Subcommand;
+use rhema_api::RhemaResult;
+use rhema_coordination::EventExportBridge;
+use std::sync::Arc;
+
+#[derive(Subcommand)]
+pub enum EventsSubcommands {
+    /// Show undelivered and dead-lettered events per sink
+    Status {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Deliver pending events to their sinks, then exit
+    Flush {
+        /// Keep delivering until interrupted
+        #[arg(long)]
+        watch: bool,
+    },
+
+    /// Retry dead-lettered deliveries on the next flush
+    Retry,
+}
+
+pub async fn handle_events(
+    context: &CliContext,
+    subcommand: &EventsSubcommands,
+) -> RhemaResult<()> {
+    let bridge = context.handle_error(EventExportBridge::open(context.rhema.repo_root()))?;
+    if !bridge.is_enabled() {
+        context.display_warning("Event export is disabled; set event_export.enabled")?;
+    }
+
+    match subcommand {
+        EventsSubcommands::Status { json } => {
+            let backlog = context.handle_error(bridge.backlog())?;
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&backlog)?);
+                return Ok(());
+            }
+            if backlog.is_empty() {
+                println!("No event sinks configured");
+            }
+            for (sink, entry) in &backlog {
+                let icon = if entry.dead > 0 {
+                    "💀"
+                } else if entry.pending > 0 {
+                    "⏳"
+                } else {
+                    "✅"
+                };
+                println!(
+                    "{} {}: {} pending, {} dead-lettered",
+                    icon, sink, entry.pending, entry.dead
+                );
+                if let Some(error) = &entry.last_error {
+                    println!("     {}", error);
+                }
+            }
+            Ok(())
+        }
+
+        EventsSubcommands::Flush { watch: true } => {
+            context.display_info("Delivering events until interrupted")?;
+            let worker = Arc::new(bridge).spawn();
+            tokio::signal::ctrl_c().await?;
+            // Events stay in the outbox until delivered, so nothing is lost
+            worker.abort();
+            Ok(())
+        }
+
+        EventsSubcommands::Flush { watch: false } => {
+            let report = context.handle_error(bridge.flush().await)?;
+            println!(
+                "📤 Delivered {} event(s): {} failed, {} dead-lettered, {} still pending",
+                report.delivered, report.failed, report.dead_lettered, report.remaining
+            );
+            Ok(())
+        }
+
+        EventsSubcommands::Retry => {
+            let revived = context.handle_error(bridge.retry_dead())?;
+            println!("🔁 {} dead-lettered delivery(ies) will be retried", revived);
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,93 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CliContext;
+use clap::Args;
+use colored::*;
+use rhema_api::RhemaResult;
+use rhema_core::help::{self, TipState};
+use rhema_core::RhemaError;
+use std::io::IsTerminal;
+
+#[derive(Args)]
+pub struct ExplainArgs {
+    /// Error code such as RH002; lists every code when left out
+    #[arg(value_name = "CODE")]
+    code: Option<String>,
+}
+
+pub fn handle_explain(args: &ExplainArgs) -> RhemaResult<()> {
+    let Some(code) = &args.code else {
+        for (code, title) in help::error_codes() {
+            println!("{}  {}", code.bold(), title);
+        }
+        return Ok(());
+    };
+
+    match help::explain(code) {
+        Some(doc) => {
+            print_markdown(doc);
+            Ok(())
+        }
+        None => Err(RhemaError::NotFound(format!(
+            "No explanation for {}; run `rhema explain` to list the codes",
+            code
+        ))),
+    }
+}
+
+/// Show the next usage tip, at most one per run, on an interactive terminal
+pub fn show_tip(context: &CliContext) {
+    if context.quiet || help::tips_disabled() || !std::io::stderr().is_terminal() {
+        return;
+    }
+    let Some(path) = TipState::default_path() else {
+        return;
+    };
+    // Tips are best effort; unreadable state starts over
+    let mut state = TipState::load(&path).unwrap_or_default();
+    let tip = state.next_tip();
+    if state.save(&path).is_err() {
+        return;
+    }
+    if let Some(tip) = tip {
+        eprintln!("{} {}", "💡 Tip:".cyan(), tip.text);
+    }
+}
+
+/// Print a remediation doc with its headings and inline code highlighted
+fn print_markdown(doc: &str) {
+    for line in doc.lines() {
+        if let Some(title) = line.strip_prefix("# ") {
+            println!("{}", title.bold().underline());
+        } else if let Some(heading) = line.strip_prefix("## ") {
+            println!("{}", heading.bold());
+        } else {
+            let highlighted: Vec<String> = line
+                .split('`')
+                .enumerate()
+                .map(|(i, part)| {
+                    if i % 2 == 1 {
+                        part.cyan().to_string()
+                    } else {
+                        part.to_string()
+                    }
+                })
+                .collect();
+            println!("{}", highlighted.concat());
+        }
+    }
+}
@@ -0,0 +1,144 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CliContext;
+use clap::Args;
+use rhema_api::bulk_export::saved_checkpoint;
+use rhema_api::{
+    BulkExportConfig, BulkExportFormat, BulkExporter, RateLimitConfig, RedactionConfig, RhemaResult,
+};
+use std::path::PathBuf;
+
+#[derive(Args)]
+pub struct ExportArgs {
+    /// Output file (ndjson) or directory (archive)
+    #[arg(value_name = "OUTPUT")]
+    output: PathBuf,
+
+    /// Output layout
+    #[arg(long, value_enum, default_value = "ndjson")]
+    format: BulkExportFormat,
+
+    /// Continue an unfinished export from this checkpoint token
+    #[arg(long, value_name = "TOKEN", conflicts_with = "resume")]
+    from_token: Option<String>,
+
+    /// Continue the unfinished export found next to the output
+    #[arg(long)]
+    resume: bool,
+
+    /// Stop after this many scopes and print a checkpoint token
+    #[arg(long, value_name = "N")]
+    max_scopes: Option<usize>,
+
+    /// Maximum records per archive part
+    #[arg(long, default_value = "10000")]
+    chunk_records: u64,
+
+    /// Maximum megabytes per archive part
+    #[arg(long, default_value = "16")]
+    chunk_mb: u64,
+
+    /// Context file reads per minute (0 disables pacing)
+    #[arg(long, default_value = "1000")]
+    requests_per_minute: u32,
+
+    /// Strip private entries and apply the rules in .rhema/redaction.yaml
+    #[arg(long)]
+    redact: bool,
+
+    /// Redaction config to use instead of .rhema/redaction.yaml
+    #[arg(long, value_name = "FILE")]
+    redaction_config: Option<PathBuf>,
+
+    /// Write the redaction report as JSON to this file
+    #[arg(long, value_name = "FILE")]
+    redaction_report: Option<PathBuf>,
+}
+
+pub async fn handle_export(context: &CliContext, args: &ExportArgs) -> RhemaResult<()> {
+    let mut config = BulkExportConfig::new(args.format, args.output.clone());
+    config.chunk_records = args.chunk_records.max(1);
+    config.chunk_bytes = args.chunk_mb.max(1) * 1024 * 1024;
+    config.max_scopes = args.max_scopes;
+    config.redaction = match &args.redaction_config {
+        Some(path) => Some(context.handle_error(RedactionConfig::load(path))?),
+        None if args.redact || args.redaction_report.is_some() => {
+            Some(context.handle_error(RedactionConfig::for_repo(context.rhema.repo_root()))?)
+        }
+        None => None,
+    };
+
+    let token = match (&args.from_token, args.resume) {
+        (Some(token), _) => Some(token.clone()),
+        (None, true) => {
+            let saved = context.handle_error(saved_checkpoint(&config))?;
+            if saved.is_none() {
+                context
+                    .display_warning("No unfinished export found, starting from the beginning")?;
+            }
+            saved
+        }
+        (None, false) => None,
+    };
+
+    let rate_limit = RateLimitConfig {
+        requests_per_minute: args.requests_per_minute,
+        ..Default::default()
+    };
+    let mut exporter = BulkExporter::new(context.rhema.repo_root().clone(), config, &rate_limit);
+    let report = context.handle_error(exporter.run(token.as_deref()).await)?;
+
+    println!(
+        "📦 Exported {} records from {} scopes to {}",
+        report.records,
+        report.scopes_exported,
+        args.output.display()
+    );
+    if args.format == BulkExportFormat::Archive {
+        println!("🗂️  {} parts", report.parts.len());
+    }
+    if let Some(redaction) = &report.redaction {
+        println!(
+            "🔒 Redacted: {} private entries dropped, {} fields removed, {} pattern matches replaced",
+            redaction.dropped_entries.len(),
+            redaction.removed_fields.len(),
+            redaction.total_matches()
+        );
+        for (rule, count) in &redaction.rule_matches {
+            println!("   {}: {}", rule, count);
+        }
+        if let Some(path) = &args.redaction_report {
+            context.handle_error(redaction.save(path))?;
+            println!("📝 Redaction report written to {}", path.display());
+        }
+    }
+    match report.checkpoint_token {
+        Some(token) => {
+            println!(
+                "⏸️  Paused after {} scopes in total. Continue with:",
+                report.total_scopes_exported
+            );
+            println!("   rhema export {} --resume", args.output.display());
+            println!("   (or --from-token {})", token);
+        }
+        None => println!(
+            "✅ Export complete: {} records from {} scopes",
+            report.total_records, report.total_scopes_exported
+        ),
+    }
+    Ok(())
+}
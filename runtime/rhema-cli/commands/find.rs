@@ -0,0 +1,184 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CliContext;
+use clap::Args;
+use colored::*;
+use rhema_api::RhemaResult;
+use rhema_core::code_search::{CodeHit, CodeLinker, CodeSearch, LinkKind, LinkReason};
+use rhema_knowledge::embedding::{EmbeddingManager, EmbeddingManagerConfig};
+use rhema_knowledge::faceted_search::{
+    KnowledgeSearch, KnowledgeSearchConfig, SearchFacets, SearchHit,
+};
+use serde::Serialize;
+use std::sync::Arc;
+
+/// Which sources `rhema find` searches
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum FindSource {
+    All,
+    Code,
+    Context,
+}
+
+#[derive(Args)]
+pub struct FindArgs {
+    /// Text to find in code and context entries
+    #[arg(value_name = "QUERY")]
+    query: String,
+
+    /// Treat the query as a regex when searching code
+    #[arg(long)]
+    regex: bool,
+
+    /// Search only code or only context entries
+    #[arg(long, value_enum, default_value = "all")]
+    only: FindSource,
+
+    /// Maximum results from each source
+    #[arg(short, long, default_value = "10")]
+    limit: usize,
+
+    /// Output as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+/// A code or context result, in the order shown
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum FindResult<'a> {
+    Context(&'a SearchHit),
+    Code(&'a CodeHit),
+}
+
+pub async fn handle_find(context: &CliContext, args: &FindArgs) -> RhemaResult<()> {
+    let repo_root = context.rhema.repo_root();
+    let scopes = context.handle_error(context.rhema.discover_scopes())?;
+
+    let mut code_hits = Vec::new();
+    if args.only != FindSource::Context {
+        let search = context.handle_error(CodeSearch::new(repo_root, &args.query, args.regex))?;
+        code_hits = context.handle_error(search.search(args.limit))?;
+        let linker = context.handle_error(CodeLinker::new(repo_root, &scopes))?;
+        for hit in &mut code_hits {
+            linker.link(hit);
+        }
+    }
+
+    let mut context_hits = Vec::new();
+    if args.only != FindSource::Code {
+        let scopes: Vec<_> = scopes
+            .iter()
+            .map(|scope| (scope.definition.name.clone(), scope.path.clone()))
+            .collect();
+        let manager = EmbeddingManager::new(EmbeddingManagerConfig::default()).await?;
+        let search = context.handle_error(
+            KnowledgeSearch::build(&scopes, Arc::new(manager), KnowledgeSearchConfig::default())
+                .await,
+        )?;
+        context_hits = context.handle_error(
+            search
+                .search(&args.query, &SearchFacets::default(), args.limit)
+                .await,
+        )?;
+    }
+
+    // Alternate between the sources so both stay visible near the top
+    let mut results = Vec::new();
+    for index in 0..context_hits.len().max(code_hits.len()) {
+        if let Some(hit) = context_hits.get(index) {
+            results.push(FindResult::Context(hit));
+        }
+        if let Some(hit) = code_hits.get(index) {
+            results.push(FindResult::Code(hit));
+        }
+    }
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({ "results": results }))?
+        );
+        return Ok(());
+    }
+    if results.is_empty() {
+        println!("📭 No matches for '{}'", args.query);
+        return Ok(());
+    }
+
+    for (index, result) in results.iter().enumerate() {
+        match result {
+            FindResult::Context(hit) => print_context_hit(index + 1, hit),
+            FindResult::Code(hit) => print_code_hit(index + 1, hit),
+        }
+    }
+    println!(
+        "\n{}",
+        format!(
+            "{} context entries, {} files",
+            context_hits.len(),
+            code_hits.len()
+        )
+        .dimmed()
+    );
+    Ok(())
+}
+
+fn print_context_hit(position: usize, hit: &SearchHit) {
+    let document = &hit.document;
+    println!(
+        "{:>3}. 🧠 {} {} {}",
+        position,
+        document.title.bold(),
+        format!("[{}:{}]", document.content_type, document.id).cyan(),
+        format!("{} · {:.2}", document.scope, hit.score).dimmed()
+    );
+    println!("       {}", hit.snippet.replace('\n', " "));
+}
+
+fn print_code_hit(position: usize, hit: &CodeHit) {
+    let scope = hit.scope.as_deref().unwrap_or("no scope");
+    println!(
+        "{:>3}. 📄 {} {}",
+        position,
+        hit.path.display().to_string().bold(),
+        format!("{} · {} matches", scope, hit.match_count).dimmed()
+    );
+    for line in &hit.lines {
+        println!(
+            "       {} {}",
+            format!("{:>5}:", line.line).dimmed(),
+            line.text
+        );
+    }
+    for entry in &hit.governing {
+        let kind = match entry.kind {
+            LinkKind::Decision => "decision",
+            LinkKind::Pattern => "pattern",
+        };
+        let reason = match entry.reason {
+            LinkReason::Mentioned => "mentions this file",
+            LinkReason::ImplementingCommit => "an implementing commit changed this file",
+        };
+        println!(
+            "       ↳ {} {} {}",
+            format!("[{}:{}]", kind, entry.id).cyan(),
+            entry.title,
+            format!("({}, {})", entry.scope, reason).dimmed()
+        );
+    }
+}
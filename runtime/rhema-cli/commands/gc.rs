@@ -0,0 +1,104 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::commands::stats::format_bytes;
+use crate::CliContext;
+use clap::Args;
+use rhema_api::RhemaResult;
+use rhema_core::gc::{schedule_gc, GarbageCollector, GcReport};
+use rhema_core::jobs::JobQueue;
+use rhema_knowledge::EmbeddingIndexCollector;
+use std::path::Path;
+use std::sync::Arc;
+
+#[derive(Args)]
+pub struct GcArgs {
+    /// Report what would be reclaimed without deleting anything
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Schedule collections on the job queue per the `gc` config instead
+    #[arg(long, conflicts_with = "dry_run")]
+    schedule: bool,
+
+    /// Output as JSON
+    #[arg(long)]
+    json: bool,
+}
+
+/// Garbage collector with every collector the CLI can trace reachability for
+pub fn garbage_collector(repo_root: &Path) -> RhemaResult<GarbageCollector> {
+    Ok(GarbageCollector::open(repo_root)?.with_collector(Arc::new(EmbeddingIndexCollector)))
+}
+
+pub async fn handle_gc(context: &CliContext, args: &GcArgs) -> RhemaResult<()> {
+    let repo_root = context.rhema.repo_root();
+    let collector = context.handle_error(garbage_collector(repo_root))?;
+
+    if args.schedule {
+        let queue = context.handle_error(JobQueue::open(repo_root))?;
+        match context.handle_error(schedule_gc(&queue, collector.config()))? {
+            Some(job) => println!(
+                "⏰ Scheduled garbage collection {} every {}s",
+                job.id,
+                collector.config().interval_secs.max(3_600)
+            ),
+            None => context.display_info(
+                "Scheduled collection is off; set gc.scheduled in .rhema/repository.yaml",
+            )?,
+        }
+        return Ok(());
+    }
+
+    let report = context.handle_error(collector.collect(args.dry_run))?;
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+    Ok(())
+}
+
+fn print_report(report: &GcReport) {
+    if report.garbage.is_empty() {
+        println!("✨ Nothing to collect");
+        return;
+    }
+
+    for (collector, summary) in report.summary() {
+        println!(
+            "  🗑️  {}: {} artifact(s), {}",
+            collector,
+            summary.artifacts,
+            format_bytes(summary.bytes)
+        );
+    }
+    if report.dry_run {
+        println!(
+            "🔍 {} reclaimable (dry run, nothing deleted)",
+            format_bytes(report.reclaimable_bytes)
+        );
+    } else {
+        println!(
+            "🧹 Reclaimed {} of {}",
+            format_bytes(report.reclaimed_bytes),
+            format_bytes(report.reclaimable_bytes)
+        );
+    }
+    for error in &report.errors {
+        println!("  ⚠️  {}", error);
+    }
+}
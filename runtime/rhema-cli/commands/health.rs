@@ -0,0 +1,220 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CliContext;
+use chrono::Utc;
+use rhema_api::RhemaResult;
+use rhema_core::dependency_health::ProbeState;
+use rhema_core::freshness::{self, FreshnessReport};
+use rhema_core::i18n::{text, text_with};
+use rhema_core::ownership::{self, OwnershipConfig};
+use rhema_core::{RhemaError, Scope};
+use rhema_knowledge::configured_monitor;
+
+/// Probe the embedding, vector store and LLM provider dependencies
+pub async fn handle_dependency_health(context: &CliContext, json: bool) -> RhemaResult<()> {
+    let monitor = context.handle_error(configured_monitor(context.rhema.repo_root()).await)?;
+    let results = monitor.check_now().await;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&results)?);
+        return Ok(());
+    }
+
+    println!("🩺 {}", text("health.deps.title"));
+    for dependency in &results {
+        let icon = match dependency.state {
+            ProbeState::Healthy => "✅",
+            ProbeState::Degraded => "⚠️ ",
+            ProbeState::Unhealthy => "❌",
+        };
+        let mut line = format!(
+            "  {} {}",
+            icon,
+            text_with(
+                "health.deps.line",
+                &[
+                    ("name", &dependency.name),
+                    ("kind", &dependency.kind),
+                    ("state", &state_text(dependency.state)),
+                    ("latency", &dependency.latency_ms),
+                ],
+            )
+        );
+        if let Some(quota) = dependency.quota_remaining {
+            line.push_str(&text_with("health.deps.quota", &[("quota", &quota)]));
+        }
+        println!("{}", line);
+        if let Some(message) = &dependency.message {
+            println!("     {}", message);
+        }
+    }
+
+    match monitor.overall().await {
+        ProbeState::Healthy => println!("✅ {}", text("health.deps.all_healthy")),
+        state => context.display_warning(&text_with(
+            "health.deps.overall",
+            &[("state", &state_text(state))],
+        ))?,
+    }
+    Ok(())
+}
+
+fn state_text(state: ProbeState) -> String {
+    match state {
+        ProbeState::Healthy => text("health.state.healthy"),
+        ProbeState::Degraded => text("health.state.degraded"),
+        ProbeState::Unhealthy => text("health.state.unhealthy"),
+    }
+}
+
+/// Report freshness SLA compliance, optionally filing review todos for
+/// breached entries
+pub fn handle_freshness_health(
+    context: &CliContext,
+    scope_name: Option<&str>,
+    json: bool,
+    review_todos: bool,
+) -> RhemaResult<()> {
+    let mut scopes = context.handle_error(context.rhema.discover_scopes())?;
+    if let Some(name) = scope_name {
+        scopes.retain(|scope| scope.definition.name == name);
+        if scopes.is_empty() {
+            return context.handle_error(Err(RhemaError::ScopeNotFound(name.to_string())));
+        }
+    }
+    let report = context.handle_error(freshness::check(&scopes, Utc::now()))?;
+
+    let mut created = Vec::new();
+    if review_todos {
+        for checked in report.scopes.iter().filter(|s| s.breach_count() > 0) {
+            let Some(scope) = scopes.iter().find(|s| s.definition.name == checked.scope) else {
+                continue;
+            };
+            let fallback = if checked.owners.is_empty() {
+                top_maintainer(context, scope)
+            } else {
+                None
+            };
+            let ids = context.handle_error(freshness::create_review_todos(
+                scope,
+                checked,
+                fallback.as_deref(),
+            ))?;
+            created.push((checked.scope.clone(), ids.len()));
+        }
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    print_freshness(&report);
+    for (scope, count) in &created {
+        println!(
+            "📝 {}",
+            text_with(
+                "health.freshness.todos_created",
+                &[("count", count), ("scope", scope)]
+            )
+        );
+    }
+    if report.breach_count() > 0 {
+        let hint = if review_todos {
+            String::new()
+        } else {
+            text("health.freshness.review_hint")
+        };
+        context.display_warning(&format!(
+            "{}{}",
+            text_with(
+                "health.freshness.breaches",
+                &[("count", &report.breach_count())]
+            ),
+            hint
+        ))?;
+    }
+    Ok(())
+}
+
+/// Active maintainer holding the largest share of a scope's context
+fn top_maintainer(context: &CliContext, scope: &Scope) -> Option<String> {
+    let report = ownership::analyze(
+        context.rhema.repo_root(),
+        std::slice::from_ref(scope),
+        &OwnershipConfig::default(),
+    )
+    .ok()?;
+    report
+        .scopes
+        .first()?
+        .maintainers
+        .iter()
+        .find(|maintainer| maintainer.active)
+        .map(|maintainer| maintainer.email.clone())
+}
+
+fn print_freshness(report: &FreshnessReport) {
+    if report.scopes.is_empty() {
+        println!("🕰️  {}", text("health.freshness.none"));
+        return;
+    }
+
+    println!(
+        "🕰️  {}",
+        text_with(
+            "health.freshness.summary",
+            &[("percent", &format!("{:.0}", report.compliance() * 100.0))]
+        )
+    );
+    for scope in &report.scopes {
+        println!("  {}", scope.scope);
+        for sla in &scope.slas {
+            let icon = if sla.breaches.is_empty() {
+                "✅"
+            } else {
+                "⚠️ "
+            };
+            println!(
+                "    {} {}",
+                icon,
+                text_with(
+                    "health.freshness.sla",
+                    &[
+                        ("entries", &sla.entries),
+                        ("days", &sla.max_age_days),
+                        ("fresh", &sla.fresh),
+                        ("total", &sla.total),
+                        ("percent", &format!("{:.0}", sla.compliance() * 100.0)),
+                    ]
+                )
+            );
+            for breach in &sla.breaches {
+                let age = match breach.age_days {
+                    Some(days) => text_with("health.freshness.age_days", &[("days", &days)]),
+                    None => text("health.freshness.never_reviewed"),
+                };
+                println!(
+                    "       - {} {} ({})",
+                    breach.entry_id,
+                    breach.title.as_deref().unwrap_or(""),
+                    age
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,169 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CliContext;
+use clap::Subcommand;
+use rhema_api::{RhemaError, RhemaResult};
+use rhema_core::i18n::{self, I18nConfig};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+
+#[derive(Subcommand)]
+pub enum I18nSubcommands {
+    /// List the available locales, their coverage and the locales in use
+    Locales,
+
+    /// Write a catalog for translators with every message to translate
+    Extract {
+        /// Locale to translate into, e.g. `fr` or `pt-BR`
+        #[arg(long)]
+        locale: String,
+
+        /// Output file [default: .rhema/locales/<locale>.yaml]
+        #[arg(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+
+    /// Report missing, obsolete and mismatched translations; fails if any
+    Check {
+        /// Only check this locale
+        #[arg(long)]
+        locale: Option<String>,
+
+        /// Also report message ids used in these Rust sources but missing
+        /// from the English catalog
+        #[arg(long, value_name = "DIR")]
+        source: Vec<PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+pub fn handle_i18n(context: &CliContext, subcommand: &I18nSubcommands) -> RhemaResult<()> {
+    let repo_root = context.rhema.repo_root();
+
+    match subcommand {
+        I18nSubcommands::Locales => {
+            let config = context.handle_error(I18nConfig::load(repo_root))?;
+            println!("🌐 CLI locale: {}", config.cli_locale());
+            println!("📄 Documents locale: {}", config.docs_locale());
+            let none = BTreeSet::new();
+            for locale in context.handle_error(i18n::available_locales(Some(repo_root)))? {
+                let report =
+                    context.handle_error(i18n::check_catalog(&locale, Some(repo_root), &none))?;
+                println!(
+                    "  {} {:.0}% ({}/{})",
+                    locale,
+                    report.coverage() * 100.0,
+                    report.translated,
+                    report.total
+                );
+            }
+            Ok(())
+        }
+
+        I18nSubcommands::Extract { locale, output } => {
+            let locale = i18n::normalize_locale(locale)
+                .ok_or_else(|| RhemaError::InvalidInput("Empty locale".to_string()))?;
+            let path = output
+                .clone()
+                .unwrap_or_else(|| i18n::locales_dir(repo_root).join(format!("{}.yaml", locale)));
+            let template =
+                context.handle_error(i18n::translation_template(&locale, Some(repo_root)))?;
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, template)?;
+
+            let report = context.handle_error(i18n::check_catalog(
+                &locale,
+                Some(repo_root),
+                &BTreeSet::new(),
+            ))?;
+            println!(
+                "📝 Wrote {} messages for {} to {} ({} to translate)",
+                report.total,
+                locale,
+                path.display(),
+                report.missing.len()
+            );
+            Ok(())
+        }
+
+        I18nSubcommands::Check {
+            locale,
+            source,
+            json,
+        } => {
+            let used = context.handle_error(i18n::extract_message_ids(source))?;
+            let locales = match locale {
+                Some(locale) => vec![i18n::normalize_locale(locale)
+                    .ok_or_else(|| RhemaError::InvalidInput("Empty locale".to_string()))?],
+                None => context.handle_error(i18n::available_locales(Some(repo_root)))?,
+            };
+            let mut reports = Vec::new();
+            for locale in &locales {
+                reports.push(context.handle_error(i18n::check_catalog(
+                    locale,
+                    Some(repo_root),
+                    &used,
+                ))?);
+            }
+
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&reports)?);
+            } else {
+                for report in &reports {
+                    let icon = if report.is_complete() {
+                        "✅"
+                    } else {
+                        "⚠️ "
+                    };
+                    println!(
+                        "{} {}: {:.0}% translated",
+                        icon,
+                        report.locale,
+                        report.coverage() * 100.0
+                    );
+                    for (label, ids) in [
+                        ("missing", &report.missing),
+                        ("obsolete", &report.obsolete),
+                        ("placeholder mismatch", &report.placeholder_mismatches),
+                        ("undefined in English", &report.undefined),
+                    ] {
+                        for id in ids {
+                            println!("     {}: {}", label, id);
+                        }
+                    }
+                }
+            }
+
+            let incomplete = reports
+                .iter()
+                .filter(|report| !report.is_complete())
+                .count();
+            if incomplete > 0 {
+                return Err(RhemaError::ValidationError(format!(
+                    "{} locale(s) need translation work; run `rhema i18n extract --locale <locale>`",
+                    incomplete
+                )));
+            }
+            Ok(())
+        }
+    }
+}
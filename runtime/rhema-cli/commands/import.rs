@@ -0,0 +1,135 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CliContext;
+use clap::{Args, Subcommand};
+use rhema_api::RhemaResult;
+use rhema_core::importers::{apply_plan, importer_for, ImportOptions, ImportSource};
+use std::path::PathBuf;
+
+#[derive(Args)]
+pub struct ImportArgs {
+    /// Export file or directory
+    #[arg(value_name = "PATH")]
+    path: PathBuf,
+
+    /// Scope directory (relative to the repository root) for entries not
+    /// tied to a more specific scope
+    #[arg(long, value_name = "DIR", default_value = ".")]
+    scope: String,
+
+    /// Base URL of the source system, used for provenance links
+    #[arg(long, value_name = "URL")]
+    base_url: Option<String>,
+
+    /// Only show what would be imported
+    #[arg(long)]
+    dry_run: bool,
+
+    /// Write the mapping report to a file (Markdown, or JSON for .json)
+    #[arg(long, value_name = "FILE")]
+    report: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+pub enum ImportSubcommands {
+    /// Import Backstage catalog-info.yaml descriptors as scopes
+    Backstage {
+        #[command(flatten)]
+        args: ImportArgs,
+    },
+
+    /// Import a Notion Markdown & CSV export
+    Notion {
+        #[command(flatten)]
+        args: ImportArgs,
+    },
+
+    /// Import a Confluence HTML space export
+    Confluence {
+        #[command(flatten)]
+        args: ImportArgs,
+    },
+}
+
+pub fn handle_import(context: &CliContext, subcommand: &ImportSubcommands) -> RhemaResult<()> {
+    let (source, args) = match subcommand {
+        ImportSubcommands::Backstage { args } => (ImportSource::Backstage, args),
+        ImportSubcommands::Notion { args } => (ImportSource::Notion, args),
+        ImportSubcommands::Confluence { args } => (ImportSource::Confluence, args),
+    };
+    let options = ImportOptions {
+        target_scope: args.scope.clone(),
+        base_url: args.base_url.clone(),
+    };
+
+    let plan = context.handle_error(importer_for(source).plan(&args.path, &options))?;
+    let summary =
+        context.handle_error(apply_plan(context.rhema.repo_root(), &plan, args.dry_run))?;
+
+    if args.dry_run {
+        println!("🔍 Importing from {} would make these changes:", source);
+    } else {
+        println!("✅ Imported from {}", source);
+    }
+    println!("📁 Scopes created: {}", summary.scopes_created);
+    println!("📚 Knowledge entries: {}", summary.knowledge_added);
+    println!("🧭 Decisions: {}", summary.decisions_added);
+    if summary.already_imported > 0 {
+        println!(
+            "⏭️  Skipped {} entries imported by an earlier run",
+            summary.already_imported
+        );
+    }
+
+    let report = &plan.report;
+    println!(
+        "🗺️  Mapping: {} converted, {} partially converted, {} not converted",
+        report.converted.len(),
+        report.partial.len(),
+        report.unconverted.len()
+    );
+
+    match &args.report {
+        Some(path) => {
+            let content = if path.extension().and_then(|ext| ext.to_str()) == Some("json") {
+                context.handle_error(serde_json::to_string_pretty(report).map_err(Into::into))?
+            } else {
+                report.to_markdown(source)
+            };
+            context.handle_error(std::fs::write(path, content).map_err(Into::into))?;
+            println!("📄 Mapping report written to {}", path.display());
+        }
+        None => {
+            for item in &report.unconverted {
+                println!(
+                    "  ✗ {} ({}): {}",
+                    item.source_path,
+                    item.kind,
+                    item.reason.as_deref().unwrap_or("")
+                );
+            }
+            if !report.partial.is_empty() {
+                context.display_warning(&format!(
+                    "{} items lost information during conversion (use --report FILE for details)",
+                    report.partial.len()
+                ))?;
+            }
+        }
+    }
+
+    Ok(())
+}
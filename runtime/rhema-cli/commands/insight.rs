@@ -14,9 +14,17 @@
  * limitations under the License.
  */
 
+use crate::commands::coordination::InspectOutput;
 use crate::CliContext;
 use clap::Subcommand;
 use rhema_api::RhemaResult;
+use rhema_knowledge::auto_tag::{auto_tag_scope, AutoTagConfig, AutoTagMode};
+use rhema_knowledge::embedding::{EmbeddingManager, EmbeddingManagerConfig};
+use rhema_knowledge::insight_trends::{
+    load_scope_insights, InsightTrendAnalyzer, InsightTrendConfig, InsightTrendReport,
+};
+use std::path::PathBuf;
+use std::sync::Arc;
 
 #[derive(Subcommand)]
 pub enum InsightSubcommands {
@@ -41,6 +49,14 @@ pub enum InsightSubcommands {
         /// Tags (comma-separated)
         #[arg(long, value_name = "TAGS")]
         tags: Option<String>,
+
+        /// Suggest a category and tags, stored as pending until accepted
+        #[arg(long)]
+        auto_tag: bool,
+
+        /// Field required by an entry template, as NAME=VALUE (repeatable)
+        #[arg(long = "field", value_name = "NAME=VALUE")]
+        fields: Vec<String>,
     },
 
     /// List insights
@@ -91,9 +107,36 @@ pub enum InsightSubcommands {
         #[arg(value_name = "ID")]
         id: String,
     },
+
+    /// Cluster insights into themes and report how they trend per quarter and scope
+    Trends {
+        /// Analyse the insights of every scope instead of the current one
+        #[arg(long)]
+        all_scopes: bool,
+
+        /// Embedding model to use
+        #[arg(long, value_name = "MODEL")]
+        model: Option<String>,
+
+        /// Minimum time-adjusted similarity for insights to share a theme
+        #[arg(long, default_value = "0.6")]
+        threshold: f32,
+
+        /// Smallest cluster reported as a theme
+        #[arg(long, default_value = "2")]
+        min_size: usize,
+
+        /// Write the report as a Markdown page, e.g. into the knowledge site
+        #[arg(long, value_name = "FILE")]
+        write: Option<PathBuf>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value = "text")]
+        output: InspectOutput,
+    },
 }
 
-pub fn handle_insight(
+pub async fn handle_insight(
     context: &CliContext,
     scope: &rhema_core::Scope,
     subcommand: &InsightSubcommands,
@@ -105,6 +148,8 @@ pub fn handle_insight(
             confidence,
             category,
             tags,
+            auto_tag,
+            fields,
         } => {
             match rhema_core::file_ops::add_knowledge(
                 &scope.path,
@@ -113,6 +158,7 @@ pub fn handle_insight(
                 *confidence,
                 category.clone(),
                 tags.clone(),
+                rhema_core::templates::parse_entry_fields(fields)?,
             ) {
                 Ok(id) => {
                     println!("💡 Insight recorded successfully with ID: {}", id);
@@ -127,6 +173,9 @@ pub fn handle_insight(
                     if let Some(tag_list) = tags {
                         println!("🏷️  Tags: {}", tag_list);
                     }
+                    if *auto_tag {
+                        suggest_pending_tags(context, scope, &id).await?;
+                    }
                     Ok(())
                 }
                 Err(e) => {
@@ -207,5 +256,132 @@ pub fn handle_insight(
                 }
             }
         }
+        InsightSubcommands::Trends {
+            all_scopes,
+            model,
+            threshold,
+            min_size,
+            write,
+            output,
+        } => {
+            let scopes = if *all_scopes {
+                context.handle_error(context.rhema.discover_scopes())?
+            } else {
+                vec![scope.clone()]
+            };
+            let mut insights = Vec::new();
+            for scope in &scopes {
+                insights.extend(
+                    context
+                        .handle_error(load_scope_insights(&scope.definition.name, &scope.path))?,
+                );
+            }
+
+            let manager = EmbeddingManager::new(EmbeddingManagerConfig::default()).await?;
+            let analyzer = InsightTrendAnalyzer::new(
+                Arc::new(manager),
+                InsightTrendConfig {
+                    model: model.clone(),
+                    similarity_threshold: *threshold,
+                    min_cluster_size: *min_size,
+                    ..Default::default()
+                },
+            );
+            let report =
+                context.handle_error(analyzer.analyze(&insights).await.map_err(Into::into))?;
+
+            if let Some(path) = write {
+                context
+                    .handle_error(std::fs::write(path, report.to_markdown()).map_err(Into::into))?;
+                if *output == InspectOutput::Text {
+                    println!("📝 Wrote trend report to {}", path.display());
+                }
+            }
+
+            match output {
+                InspectOutput::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+                InspectOutput::Text => print_trends(&report),
+            }
+            Ok(())
+        }
+    }
+}
+
+fn print_trends(report: &InsightTrendReport) {
+    if report.clusters.is_empty() {
+        println!(
+            "📭 No recurring themes among {} insights",
+            report.insight_count
+        );
+        return;
+    }
+
+    println!(
+        "📊 {} themes across {} insights ({} unclustered)",
+        report.clusters.len(),
+        report.insight_count,
+        report.unclustered
+    );
+    for cluster in &report.clusters {
+        println!(
+            "  • {} ({} insights, {} to {})",
+            cluster.headline,
+            cluster.size(),
+            rhema_knowledge::insight_trends::quarter_of(cluster.first_seen),
+            rhema_knowledge::insight_trends::quarter_of(cluster.last_seen)
+        );
+    }
+
+    println!();
+    println!("📅 By quarter:");
+    for quarter in report.quarters.iter().rev() {
+        let themes = report.for_quarter(quarter);
+        if themes.is_empty() {
+            continue;
+        }
+        let summary: Vec<String> = themes
+            .iter()
+            .map(|(cluster, count)| format!("{} ({})", cluster.label, count))
+            .collect();
+        println!("  {:<8} {}", quarter, summary.join(", "));
+    }
+
+    println!();
+    println!("📁 By scope:");
+    for scope in report.scopes() {
+        let summary: Vec<String> = report
+            .for_scope(scope)
+            .iter()
+            .map(|(cluster, count)| format!("{} ({})", cluster.label, count))
+            .collect();
+        println!("  {:<20} {}", scope, summary.join(", "));
+    }
+}
+
+/// Attach pending tag suggestions to a freshly recorded insight
+async fn suggest_pending_tags(
+    context: &CliContext,
+    scope: &rhema_core::Scope,
+    id: &str,
+) -> RhemaResult<()> {
+    let manager = EmbeddingManager::new(EmbeddingManagerConfig::default()).await?;
+    let suggestions = context.handle_error(
+        auto_tag_scope(
+            &scope.path,
+            Arc::new(manager),
+            AutoTagConfig::default(),
+            AutoTagMode::Pending,
+            Some(&[id.to_string()]),
+        )
+        .await,
+    )?;
+
+    for (_, suggestion) in suggestions {
+        println!(
+            "🤖 Suggested category: {} | tags: {} (pending, accept with `rhema knowledge auto-tag --accept-pending`)",
+            suggestion.category.as_deref().unwrap_or("-"),
+            suggestion.tags.join(", ")
+        );
     }
+    Ok(())
 }
@@ -0,0 +1,180 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::commands::sync::KeepSide;
+use crate::CliContext;
+use clap::Subcommand;
+use rhema_api::RhemaResult;
+use rhema_core::issue_sync::{schedule_issue_sync, IssueSync, IssueSyncReport};
+use rhema_core::jobs::JobQueue;
+use rhema_core::sync::Resolution;
+
+#[derive(Subcommand)]
+pub enum IssuesSubcommands {
+    /// Sync the status and notes of linked todos with their issues
+    Sync {
+        /// Schedule syncs on the job queue per the `issue_sync` config instead
+        #[arg(long)]
+        schedule: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show linked todos, paused scopes and unresolved conflicts
+    Status,
+
+    /// Stop syncing the todos of a scope
+    Pause {
+        /// Scope name
+        #[arg(value_name = "SCOPE")]
+        scope: String,
+    },
+
+    /// Sync a paused scope again
+    Resume {
+        /// Scope name
+        #[arg(value_name = "SCOPE")]
+        scope: String,
+    },
+
+    /// Settle a status conflict by keeping one side
+    Resolve {
+        /// Todo as shown by `rhema issues status` (scope/todo-id)
+        #[arg(value_name = "TODO")]
+        key: String,
+
+        /// Side to keep: the todo (local) or the issue (remote)
+        #[arg(long, value_enum)]
+        keep: KeepSide,
+    },
+}
+
+pub async fn handle_issues(
+    context: &CliContext,
+    subcommand: &IssuesSubcommands,
+) -> RhemaResult<()> {
+    let repo_root = context.rhema.repo_root();
+    let mut sync = context.handle_error(IssueSync::open(repo_root))?;
+
+    match subcommand {
+        IssuesSubcommands::Sync { schedule: true, .. } => {
+            let queue = context.handle_error(JobQueue::open(repo_root))?;
+            match context.handle_error(schedule_issue_sync(&queue, sync.config()))? {
+                Some(job) => println!(
+                    "⏰ Scheduled issue sync {} every {}s",
+                    job.id,
+                    sync.config().interval_secs.max(300)
+                ),
+                None => context.display_info(
+                    "Scheduled issue sync is off; set issue_sync.scheduled in .rhema/repository.yaml",
+                )?,
+            }
+            Ok(())
+        }
+        IssuesSubcommands::Sync { json, .. } => {
+            let report = context.handle_error(sync.sync().await)?;
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                return Ok(());
+            }
+            print_report(context, &report)
+        }
+        IssuesSubcommands::Status => {
+            let ledger = sync.ledger();
+            match ledger.last_sync {
+                Some(at) => println!("🔄 Last issue sync: {}", at.format("%Y-%m-%d %H:%M UTC")),
+                None => println!("🔄 Never synced"),
+            }
+            if ledger.links.is_empty() {
+                println!("📭 No linked todos; link one with a github_issue field");
+            } else {
+                println!("🔗 {} linked todos:", ledger.links.len());
+                for (todo, link) in &ledger.links {
+                    println!("  • {} → {} ({})", todo, link.issue, link.state);
+                }
+            }
+            if !ledger.paused.is_empty() {
+                let paused: Vec<&str> = ledger.paused.iter().map(String::as_str).collect();
+                println!("⏸️  Paused: {}", paused.join(", "));
+            }
+            if !ledger.conflicts.is_empty() {
+                println!("⚠️  {} conflicts:", ledger.conflicts.len());
+                for conflict in &ledger.conflicts {
+                    println!(
+                        "  • {} is {} but {} is {}",
+                        conflict.key, conflict.local, conflict.issue, conflict.remote
+                    );
+                }
+                println!("   Settle with: rhema issues resolve <TODO> --keep local|remote");
+            }
+            Ok(())
+        }
+        IssuesSubcommands::Pause { scope } => {
+            context.handle_error(sync.pause(scope))?;
+            println!("⏸️  Paused issue sync for {}", scope);
+            Ok(())
+        }
+        IssuesSubcommands::Resume { scope } => {
+            if context.handle_error(sync.resume(scope))? {
+                println!("▶️  Resumed issue sync for {}", scope);
+            } else {
+                context.display_info(&format!("Issue sync was not paused for {}", scope))?;
+            }
+            Ok(())
+        }
+        IssuesSubcommands::Resolve { key, keep } => {
+            let resolution = match keep {
+                KeepSide::Local => Resolution::Local,
+                KeepSide::Remote => Resolution::Remote,
+            };
+            context.handle_error(sync.resolve(key, resolution))?;
+            println!("✅ Resolved {}", key);
+            println!("   Run `rhema issues sync` to apply the kept status");
+            Ok(())
+        }
+    }
+}
+
+fn print_report(context: &CliContext, report: &IssueSyncReport) -> RhemaResult<()> {
+    if report.offline {
+        context.display_warning("GitHub unreachable; the remaining todos sync next time")?;
+    }
+    println!(
+        "🔄 {} linked todos: {} todos updated, {} issues updated",
+        report.linked, report.todos_updated, report.issues_updated
+    );
+    if report.comments_mirrored + report.notes_posted > 0 {
+        println!(
+            "   {} comments mirrored into notes, {} notes posted",
+            report.comments_mirrored, report.notes_posted
+        );
+    }
+    if report.paused > 0 {
+        println!("   {} paused scopes skipped", report.paused);
+    }
+    for todo in &report.invalid_links {
+        context.display_warning(&format!("{}: github_issue names no issue", todo))?;
+    }
+    if report.conflicts > 0 {
+        println!(
+            "⚠️  {} new conflicts; see `rhema issues status`",
+            report.conflicts
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,197 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::commands::gc::garbage_collector;
+use crate::CliContext;
+use chrono::Utc;
+use clap::Subcommand;
+use rhema_api::RhemaResult;
+use rhema_config::{BackupJob, CONFIG_BACKUP_JOB};
+use rhema_core::gc::{GcJob, GC_JOB};
+use rhema_core::issue_sync::{IssueSyncJob, ISSUE_SYNC_JOB};
+use rhema_core::jobs::{Job, JobQueue, JobStatus};
+use rhema_core::readme_sync::{ReadmeSyncJob, README_SYNC_JOB};
+use rhema_knowledge::{IndexFilesJob, INDEX_FILES_JOB};
+use rhema_monitoring::{PerformanceMonitor, PerformanceReportJob, PERFORMANCE_REPORT_JOB};
+use std::sync::Arc;
+
+#[derive(Subcommand)]
+pub enum JobsSubcommands {
+    /// List background jobs, most urgent first
+    List {
+        /// Only show jobs with this status
+        #[arg(long, value_enum)]
+        status: Option<JobStatus>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Cancel a queued or running job
+    Cancel {
+        /// Job ID
+        #[arg(value_name = "ID")]
+        id: String,
+    },
+
+    /// Queue a failed or cancelled job again
+    Retry {
+        /// Job ID
+        #[arg(value_name = "ID")]
+        id: String,
+    },
+
+    /// Queue a performance report repeating every `hours`
+    Report {
+        /// Hours each report covers and between reports
+        #[arg(long, default_value_t = 24)]
+        hours: u64,
+    },
+
+    /// Run the jobs that are due, then exit
+    Run {
+        /// Keep running as a worker until interrupted
+        #[arg(long)]
+        watch: bool,
+    },
+}
+
+pub async fn handle_jobs(context: &CliContext, subcommand: &JobsSubcommands) -> RhemaResult<()> {
+    let queue = context.handle_error(open_queue(context))?;
+
+    match subcommand {
+        JobsSubcommands::List { status, json } => {
+            let jobs = context.handle_error(queue.list(*status))?;
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&jobs)?);
+            } else {
+                print_jobs(&jobs);
+            }
+            Ok(())
+        }
+
+        JobsSubcommands::Cancel { id } => {
+            let job = context.handle_error(queue.cancel(id))?;
+            println!("🛑 Cancelled job {} ({})", job.id, job.kind);
+            Ok(())
+        }
+
+        JobsSubcommands::Retry { id } => {
+            let job = context.handle_error(queue.retry(id))?;
+            println!("🔁 Queued job {} ({}) again", job.id, job.kind);
+            Ok(())
+        }
+
+        JobsSubcommands::Report { hours } => {
+            let job = context.handle_error(queue.enqueue(PerformanceReportJob::spec(*hours)))?;
+            println!(
+                "⏰ Scheduled performance report {} every {}h",
+                job.id,
+                (*hours).max(1)
+            );
+            Ok(())
+        }
+
+        JobsSubcommands::Run { watch: true } => {
+            context.display_info("Running background jobs until interrupted")?;
+            let worker = Arc::clone(&queue).spawn();
+            tokio::signal::ctrl_c().await?;
+            // Jobs cut off here are queued again once their lease lapses
+            worker.abort();
+            Ok(())
+        }
+
+        JobsSubcommands::Run { watch: false } => {
+            let mut attempts = 0;
+            loop {
+                let finished = context.handle_error(queue.run_due().await)?;
+                if finished.is_empty() {
+                    break;
+                }
+                for job in &finished {
+                    println!("  {} {} {}", status_icon(job.status), job.kind, job.status);
+                    if let Some(error) = &job.last_error {
+                        println!("     {}", error);
+                    }
+                }
+                attempts += finished.len();
+            }
+            println!("✅ Ran {} job attempt(s)", attempts);
+            Ok(())
+        }
+    }
+}
+
+/// Queue of the repository with the handlers the CLI can run
+fn open_queue(context: &CliContext) -> RhemaResult<Arc<JobQueue>> {
+    let repo_root = context.rhema.repo_root();
+    let monitor = Arc::new(PerformanceMonitor::new(
+        PerformanceMonitor::default_config(),
+    )?);
+    let queue = JobQueue::open(repo_root)?
+        .with_handler(CONFIG_BACKUP_JOB, Arc::new(BackupJob))
+        .with_handler(INDEX_FILES_JOB, Arc::new(IndexFilesJob::new(repo_root)))
+        .with_handler(README_SYNC_JOB, Arc::new(ReadmeSyncJob::new(repo_root)))
+        .with_handler(ISSUE_SYNC_JOB, Arc::new(IssueSyncJob::new(repo_root)))
+        .with_handler(
+            PERFORMANCE_REPORT_JOB,
+            Arc::new(PerformanceReportJob::new(monitor)),
+        )
+        .with_handler(
+            GC_JOB,
+            Arc::new(GcJob::new(Arc::new(garbage_collector(repo_root)?))),
+        );
+    Ok(Arc::new(queue))
+}
+
+fn status_icon(status: JobStatus) -> &'static str {
+    match status {
+        JobStatus::Queued => "⏳",
+        JobStatus::Running => "🏃",
+        JobStatus::Succeeded => "✅",
+        JobStatus::Failed => "❌",
+        JobStatus::Cancelled => "🛑",
+    }
+}
+
+fn print_jobs(jobs: &[Job]) {
+    if jobs.is_empty() {
+        println!("📋 No background jobs");
+        return;
+    }
+
+    let now = Utc::now();
+    println!("📋 Background jobs:");
+    for job in jobs {
+        println!(
+            "  {} {} {} [{}] {} attempt {}/{}",
+            status_icon(job.status),
+            job.id,
+            job.kind,
+            job.priority,
+            job.status,
+            job.attempts,
+            job.max_attempts
+        );
+        if job.status == JobStatus::Queued && job.run_after > now {
+            println!("     runs after {}", job.run_after.to_rfc3339());
+        }
+        if let Some(error) = &job.last_error {
+            println!("     last error: {}", error);
+        }
+    }
+}
@@ -0,0 +1,633 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CliContext;
+use clap::Subcommand;
+use indicatif::{ProgressBar, ProgressStyle};
+use rhema_api::RhemaResult;
+use rhema_core::file_ops::{get_or_create_knowledge_file, read_yaml_file};
+use rhema_core::Knowledge;
+use rhema_knowledge::auto_tag::{auto_tag_scope, AutoTagConfig, AutoTagMode};
+use rhema_knowledge::embedding::{EmbeddingManager, EmbeddingManagerConfig};
+use rhema_knowledge::embedding_batch::{
+    EmbeddingBatchConfig, EmbeddingProgress, EmbeddingProgressEvent, EmbeddingProgressObserver,
+};
+use rhema_knowledge::index_migration::{
+    scope_index_inputs, EmbeddingIndex, IndexMigration, IndexMigrationConfig, MigrationPhase,
+    ReindexPlan, EMBEDDINGS_CHECKPOINT_FILE, EMBEDDINGS_FILE,
+};
+use rhema_knowledge::ingestion::{
+    IngestionConfig, KnowledgeIngestor, KnowledgeProposal, ReviewDecision,
+};
+use rhema_knowledge::quota::{KnowledgeQuotas, KnowledgeUsageReport, QuotaLimits};
+use std::io::{self, BufRead, Write};
+use std::sync::Arc;
+
+#[derive(Subcommand)]
+pub enum KnowledgeSubcommands {
+    /// Ingest Markdown docs and tagged code comments as knowledge entries
+    Ingest {
+        /// Documentation directories to scan (defaults to `docs`)
+        #[arg(long = "docs", value_name = "DIR")]
+        doc_dirs: Vec<String>,
+
+        /// Skip README files
+        #[arg(long)]
+        no_readmes: bool,
+
+        /// Additional comment marker (defaults: `RHEMA:`, `@rhema`)
+        #[arg(long = "marker", value_name = "MARKER")]
+        markers: Vec<String>,
+
+        /// Accept all non-duplicate proposals without prompting
+        #[arg(long)]
+        yes: bool,
+
+        /// Only list proposals
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Suggest categories and tags for untagged knowledge entries and patterns
+    AutoTag {
+        /// Apply suggestions immediately instead of marking them pending
+        #[arg(long, conflicts_with_all = ["accept_pending", "dry_run"])]
+        apply: bool,
+
+        /// Apply previously stored pending suggestions
+        #[arg(long, conflicts_with = "dry_run")]
+        accept_pending: bool,
+
+        /// Only print suggestions
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Embedding model to use
+        #[arg(long, value_name = "MODEL")]
+        model: Option<String>,
+
+        /// Minimum similarity for a neighbour or taxonomy category to count
+        #[arg(long, value_name = "SCORE")]
+        min_similarity: Option<f32>,
+    },
+
+    /// Embed knowledge, pattern and decision entries in batches, resuming interrupted runs
+    Index {
+        /// Embedding model to use
+        #[arg(long, value_name = "MODEL")]
+        model: Option<String>,
+
+        /// Maximum texts per provider request
+        #[arg(long, value_name = "N")]
+        batch_size: Option<usize>,
+
+        /// Provider request budget per minute (0 disables pacing)
+        #[arg(long, value_name = "N")]
+        requests_per_minute: Option<u32>,
+
+        /// Ignore any checkpoint from an interrupted run and start over
+        #[arg(long)]
+        restart: bool,
+    },
+
+    /// Move the embedding index to another model, switching once parity checks pass
+    Migrate {
+        /// Model to migrate to (defaults to the configured model)
+        #[arg(long, value_name = "MODEL")]
+        model: Option<String>,
+
+        /// Only report the model mismatch and the re-indexing estimate
+        #[arg(long, conflicts_with_all = ["switch", "abort"])]
+        plan: bool,
+
+        /// Switch to an index staged by an earlier run
+        #[arg(long, conflicts_with = "abort")]
+        switch: bool,
+
+        /// Discard the staged index and keep the active one
+        #[arg(long)]
+        abort: bool,
+
+        /// Stage and verify the new index without switching to it
+        #[arg(long, conflicts_with_all = ["plan", "switch", "abort"])]
+        no_switch: bool,
+
+        /// Provider price per million tokens, for the cost estimate
+        #[arg(long, value_name = "USD")]
+        cost_per_million_tokens: Option<f64>,
+
+        /// Lowest acceptable mean nearest-neighbour overlap with the old index (0.0-1.0)
+        #[arg(long, value_name = "RATIO")]
+        min_neighbour_overlap: Option<f64>,
+
+        /// Maximum texts per provider request
+        #[arg(long, value_name = "N")]
+        batch_size: Option<usize>,
+
+        /// Provider request budget per minute (0 disables pacing)
+        #[arg(long, value_name = "N")]
+        requests_per_minute: Option<u32>,
+    },
+
+    /// Break down entries, index size and this month's embedding spend per scope against quotas
+    Usage {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+pub async fn handle_knowledge(
+    context: &CliContext,
+    scope: &rhema_core::Scope,
+    subcommand: &KnowledgeSubcommands,
+) -> RhemaResult<()> {
+    let quotas = context.handle_error(KnowledgeQuotas::open(context.rhema.repo_root()))?;
+    match subcommand {
+        KnowledgeSubcommands::Ingest {
+            doc_dirs,
+            no_readmes,
+            markers,
+            yes,
+            dry_run,
+        } => {
+            let mut config = IngestionConfig::default();
+            if !doc_dirs.is_empty() {
+                config.doc_dirs = doc_dirs.clone();
+            }
+            config.include_readmes = !no_readmes;
+            config.comment_markers.extend(markers.iter().cloned());
+
+            let knowledge_file = get_or_create_knowledge_file(&scope.path)?;
+            let knowledge: Knowledge = context.handle_error(read_yaml_file(&knowledge_file))?;
+
+            let ingestor = KnowledgeIngestor::new(context.rhema.repo_root(), config);
+            let proposals = context.handle_error(ingestor.scan(&knowledge.entries))?;
+            let duplicates = proposals
+                .iter()
+                .filter(|p| p.duplicate_of.is_some())
+                .count();
+            let proposals: Vec<KnowledgeProposal> = proposals
+                .into_iter()
+                .filter(|p| p.duplicate_of.is_none())
+                .collect();
+
+            println!(
+                "📚 Found {} proposals ({} duplicates skipped)",
+                proposals.len(),
+                duplicates
+            );
+            if proposals.is_empty() || *dry_run {
+                for proposal in &proposals {
+                    println!("  • {} ({})", proposal.entry.title, proposal.source);
+                }
+                return Ok(());
+            }
+
+            let decisions = if *yes {
+                vec![ReviewDecision::Accept; proposals.len()]
+            } else {
+                review_proposals(&proposals)?
+            };
+
+            let added = context.handle_error(KnowledgeIngestor::apply(
+                &scope.path,
+                &proposals,
+                &decisions,
+                Some(&quotas),
+            ))?;
+            println!(
+                "✅ Added {} knowledge entries to {}",
+                added, scope.definition.name
+            );
+            Ok(())
+        }
+        KnowledgeSubcommands::AutoTag {
+            apply,
+            accept_pending,
+            dry_run,
+            model,
+            min_similarity,
+        } => {
+            let mode = if *accept_pending {
+                AutoTagMode::AcceptPending
+            } else if *apply {
+                AutoTagMode::Apply
+            } else if *dry_run {
+                AutoTagMode::DryRun
+            } else {
+                AutoTagMode::Pending
+            };
+
+            let defaults = AutoTagConfig::default();
+            let config = AutoTagConfig {
+                model: model.clone(),
+                min_similarity: min_similarity.unwrap_or(defaults.min_similarity),
+                ..defaults
+            };
+
+            let manager = EmbeddingManager::new(EmbeddingManagerConfig::default()).await?;
+            let suggestions = context.handle_error(
+                auto_tag_scope(&scope.path, Arc::new(manager), config, mode, None).await,
+            )?;
+
+            if suggestions.is_empty() {
+                println!("📭 No tag suggestions");
+                return Ok(());
+            }
+            for (id, suggestion) in &suggestions {
+                println!(
+                    "  • {} → category: {} | tags: {} ({:.0}%)",
+                    id,
+                    suggestion.category.as_deref().unwrap_or("-"),
+                    suggestion.tags.join(", "),
+                    suggestion.confidence * 100.0
+                );
+            }
+            let verb = match mode {
+                AutoTagMode::Pending => "marked pending on",
+                AutoTagMode::Apply | AutoTagMode::AcceptPending => "applied to",
+                AutoTagMode::DryRun => "suggested for",
+            };
+            println!("🏷️  Suggestions {} {} entries", verb, suggestions.len());
+            Ok(())
+        }
+        KnowledgeSubcommands::Index {
+            model,
+            batch_size,
+            requests_per_minute,
+            restart,
+        } => {
+            let inputs = context.handle_error(scope_index_inputs(&scope.path))?;
+            if inputs.is_empty() {
+                println!("📭 Nothing to index in {}", scope.definition.name);
+                return Ok(());
+            }
+
+            let checkpoint_path = scope.path.join(EMBEDDINGS_CHECKPOINT_FILE);
+            if *restart && checkpoint_path.exists() {
+                std::fs::remove_file(&checkpoint_path)?;
+            }
+            let defaults = EmbeddingBatchConfig::default();
+            let config = EmbeddingBatchConfig {
+                max_batch_size: batch_size.unwrap_or(defaults.max_batch_size),
+                requests_per_minute: match requests_per_minute {
+                    Some(0) => None,
+                    Some(rpm) => Some(*rpm),
+                    None => defaults.requests_per_minute,
+                },
+                checkpoint_path: Some(checkpoint_path),
+                ..defaults
+            };
+
+            context.handle_error(
+                quotas
+                    .check_embedding(&scope.path, &inputs, None)
+                    .map_err(Into::into),
+            )?;
+            let manager = EmbeddingManager::new(EmbeddingManagerConfig::default()).await?;
+            let progress = IndexProgressBar::new(inputs.len());
+            let report = context.handle_error(
+                manager
+                    .embed_batched(&inputs, model.as_deref(), &config, Some(&progress))
+                    .await,
+            )?;
+            progress.bar.finish_and_clear();
+            context.handle_error(
+                quotas
+                    .record_embedding(&scope.path, report.progress.embedded_tokens(&inputs))
+                    .map_err(Into::into),
+            )?;
+
+            let index = EmbeddingIndex::new(report.model, report.embeddings.into_iter().collect());
+            let output = scope.path.join(EMBEDDINGS_FILE);
+            context.handle_error(index.save(&output).map_err(Into::into))?;
+
+            if report.progress.resumed > 0 {
+                println!(
+                    "♻️  Resumed {} entries from the previous run",
+                    report.progress.resumed
+                );
+            }
+            if report.progress.rate_limited > 0 {
+                println!(
+                    "⏳ Backed off {} times for provider rate limits",
+                    report.progress.rate_limited
+                );
+            }
+            println!(
+                "✅ Indexed {} entries in {} batches ({:.1}s) → {}",
+                report.progress.total,
+                report.progress.batches,
+                report.elapsed.as_secs_f64(),
+                output.display()
+            );
+            Ok(())
+        }
+        KnowledgeSubcommands::Migrate {
+            model,
+            plan,
+            switch,
+            abort,
+            no_switch,
+            cost_per_million_tokens,
+            min_neighbour_overlap,
+            batch_size,
+            requests_per_minute,
+        } => {
+            let defaults = IndexMigrationConfig::default();
+            let batch = EmbeddingBatchConfig {
+                max_batch_size: batch_size.unwrap_or(defaults.batch.max_batch_size),
+                requests_per_minute: match requests_per_minute {
+                    Some(0) => None,
+                    Some(rpm) => Some(*rpm),
+                    None => defaults.batch.requests_per_minute,
+                },
+                ..defaults.batch.clone()
+            };
+            let config = IndexMigrationConfig {
+                target_model: model.clone(),
+                batch,
+                cost_per_million_tokens: *cost_per_million_tokens,
+                min_neighbour_overlap: min_neighbour_overlap
+                    .unwrap_or(defaults.min_neighbour_overlap),
+                ..defaults
+            };
+            let manager = EmbeddingManager::new(EmbeddingManagerConfig::default()).await?;
+            let migration =
+                IndexMigration::new(&scope.path, Arc::new(manager), config).with_quotas(quotas);
+
+            if *abort {
+                context.handle_error(migration.abort().map_err(Into::into))?;
+                println!(
+                    "🗑️  Discarded the staged index for {}",
+                    scope.definition.name
+                );
+                return Ok(());
+            }
+            if *switch {
+                let index = context.handle_error(migration.switch().map_err(Into::into))?;
+                println!(
+                    "✅ {} now searches with {} ({} entries)",
+                    scope.definition.name,
+                    index.model,
+                    index.embeddings.len()
+                );
+                return Ok(());
+            }
+
+            let inputs = context.handle_error(scope_index_inputs(&scope.path))?;
+            let estimate =
+                context.handle_error(migration.plan(&inputs).await.map_err(Into::into))?;
+            print_reindex_plan(&estimate);
+            if *plan {
+                return Ok(());
+            }
+            if estimate.is_current() {
+                println!("✅ Index already built with {}", estimate.target_model);
+                return Ok(());
+            }
+            if inputs.is_empty() {
+                println!("📭 Nothing to index in {}", scope.definition.name);
+                return Ok(());
+            }
+
+            println!("🔁 Re-embedding; searches keep using the current index meanwhile");
+            let progress = IndexProgressBar::new(inputs.len());
+            let parity = context.handle_error(
+                migration
+                    .reindex(&inputs, Some(&progress))
+                    .await
+                    .map_err(Into::into),
+            )?;
+            progress.bar.finish_and_clear();
+
+            println!(
+                "🔍 Parity: {}/{} embedded, {} missing, {} wrong dimension, {} degenerate",
+                parity.embedded,
+                parity.expected,
+                parity.missing.len(),
+                parity.wrong_dimension.len(),
+                parity.degenerate.len()
+            );
+            if let Some(overlap) = parity.neighbour_overlap {
+                println!(
+                    "   Neighbour overlap with the current index: {:.0}% (minimum {:.0}%)",
+                    overlap * 100.0,
+                    parity.min_neighbour_overlap * 100.0
+                );
+            }
+
+            let ready = context
+                .handle_error(migration.state().map_err(Into::into))?
+                .is_some_and(|state| state.phase == MigrationPhase::Ready);
+            if !ready {
+                context.display_warning(
+                    "Parity checks failed; the current index stays active. Rerun, or use --abort",
+                )?;
+                return Ok(());
+            }
+            if *no_switch {
+                println!("⏸️  Staged index verified; run with --switch to activate it");
+                return Ok(());
+            }
+            let index = context.handle_error(migration.switch().map_err(Into::into))?;
+            println!(
+                "✅ Switched {} to {} ({} entries)",
+                scope.definition.name,
+                index.model,
+                index.embeddings.len()
+            );
+            Ok(())
+        }
+        KnowledgeSubcommands::Usage { json } => {
+            let report = context.handle_error(quotas.usage())?;
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                print_usage(&report);
+            }
+            Ok(())
+        }
+    }
+}
+
+fn print_usage(report: &KnowledgeUsageReport) {
+    println!("📊 Knowledge usage ({})", report.month);
+    for scope in &report.scopes {
+        println!(
+            "  {}: {}, {}, {}",
+            scope.scope,
+            with_limit(
+                scope.usage.entries.to_string() + " entries",
+                scope.limits.max_entries.map(|limit| limit.to_string())
+            ),
+            with_limit(
+                megabytes(scope.usage.index_bytes),
+                scope.limits.max_index_bytes.map(megabytes)
+            ),
+            with_limit(
+                format!(
+                    "${:.2} embedding ({} tokens)",
+                    scope.usage.embedding_usd, scope.usage.embedding_tokens
+                ),
+                scope
+                    .limits
+                    .max_monthly_embedding_usd
+                    .map(|limit| format!("${:.2}", limit))
+            )
+        );
+    }
+    let QuotaLimits {
+        max_entries,
+        max_index_bytes,
+        max_monthly_embedding_usd,
+    } = &report.global_limits;
+    println!(
+        "  Total: {}, {}, {}",
+        with_limit(
+            report.total.entries.to_string() + " entries",
+            max_entries.map(|limit| limit.to_string())
+        ),
+        with_limit(
+            megabytes(report.total.index_bytes),
+            max_index_bytes.map(megabytes)
+        ),
+        with_limit(
+            format!("${:.2} embedding", report.total.embedding_usd),
+            max_monthly_embedding_usd.map(|limit| format!("${:.2}", limit))
+        )
+    );
+    for violation in &report.violations {
+        println!("❌ {}", violation);
+    }
+}
+
+fn with_limit(used: String, limit: Option<String>) -> String {
+    match limit {
+        Some(limit) => format!("{} / {}", used, limit),
+        None => used,
+    }
+}
+
+fn megabytes(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / 1_000_000.0)
+}
+
+fn print_reindex_plan(plan: &ReindexPlan) {
+    match (&plan.mismatch, plan.indexed_entries) {
+        (Some(mismatch), _) => println!(
+            "⚠️  Index built with {} ({} dims), configured model is {} ({} dims){}",
+            mismatch.indexed_model,
+            mismatch.indexed_dimension,
+            mismatch.current_model,
+            mismatch.current_dimension,
+            if mismatch.dimension_changed() {
+                "; the index cannot be read"
+            } else {
+                ""
+            }
+        ),
+        (None, Some(_)) => println!("✅ Index matches {}", plan.target_model),
+        (None, None) => println!("📭 No index yet"),
+    }
+    println!(
+        "📋 Re-indexing {} entries with {}: ~{} tokens in {} batches, ≥{:.0}s at the request budget, ~{:.1} MB",
+        plan.entries,
+        plan.target_model,
+        plan.estimated_tokens,
+        plan.batches,
+        plan.estimated_duration.as_secs_f64(),
+        plan.estimated_index_bytes as f64 / 1_000_000.0
+    );
+    if let Some(cost) = plan.estimated_cost_usd {
+        println!("💰 Estimated cost: ${:.2}", cost);
+    }
+}
+
+/// Terminal progress bar fed by the embedding batch pipeline
+struct IndexProgressBar {
+    bar: ProgressBar,
+}
+
+impl IndexProgressBar {
+    fn new(total: usize) -> Self {
+        let bar = ProgressBar::new(total as u64);
+        bar.set_style(
+            ProgressStyle::default_bar()
+                .template(
+                    "{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}] {pos}/{len} ({eta}) {msg}",
+                )
+                .unwrap()
+                .progress_chars("#>-"),
+        );
+        Self { bar }
+    }
+}
+
+impl EmbeddingProgressObserver for IndexProgressBar {
+    fn on_progress(&self, event: &EmbeddingProgressEvent, progress: &EmbeddingProgress) {
+        self.bar.set_position(progress.completed as u64);
+        match event {
+            EmbeddingProgressEvent::RateLimited { retry_in } => self.bar.set_message(format!(
+                "rate limited, retrying in {:.1}s",
+                retry_in.as_secs_f64()
+            )),
+            EmbeddingProgressEvent::BatchCompleted { .. } => match progress.requests_per_minute {
+                Some(rpm) => self.bar.set_message(format!("{:.0} req/min", rpm)),
+                None => self.bar.set_message(""),
+            },
+            EmbeddingProgressEvent::Started | EmbeddingProgressEvent::Finished => {}
+        }
+    }
+}
+
+/// Walk the user through each proposal, collecting accept/reject decisions
+fn review_proposals(proposals: &[KnowledgeProposal]) -> RhemaResult<Vec<ReviewDecision>> {
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut decisions = Vec::with_capacity(proposals.len());
+
+    for (index, proposal) in proposals.iter().enumerate() {
+        println!();
+        println!(
+            "[{}/{}] {} ({})",
+            index + 1,
+            proposals.len(),
+            proposal.entry.title,
+            proposal.source
+        );
+        for line in proposal.entry.content.lines().take(8) {
+            println!("    {}", line);
+        }
+        print!("Accept? [y]es / [n]o / [a]ll / [q]uit: ");
+        io::stdout().flush()?;
+
+        let answer = lines.next().transpose()?.unwrap_or_default();
+        match answer.trim().to_lowercase().as_str() {
+            "y" | "yes" => decisions.push(ReviewDecision::Accept),
+            "a" | "all" => {
+                decisions.resize(proposals.len(), ReviewDecision::Accept);
+                break;
+            }
+            "q" | "quit" => break,
+            _ => decisions.push(ReviewDecision::Reject),
+        }
+    }
+
+    decisions.resize(proposals.len(), ReviewDecision::Reject);
+    Ok(decisions)
+}
@@ -0,0 +1,185 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CliContext;
+use clap::Subcommand;
+use rhema_api::RhemaResult;
+use rhema_core::lockfiles::{self, ChangeKind, Lockfile};
+use rhema_core::RhemaError;
+use std::path::PathBuf;
+
+#[derive(Subcommand)]
+pub enum LockSubcommands {
+    /// List Cargo, npm and Poetry lockfiles and whether they are stale
+    Status {
+        /// Print JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show dependency changes in lockfiles since a revision
+    Diff {
+        /// Base revision
+        #[arg(value_name = "REV")]
+        rev: String,
+
+        /// Revision to compare against (defaults to the working tree)
+        #[arg(long, value_name = "REV")]
+        to: Option<String>,
+
+        /// Print JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Explain why a package is in the dependency tree
+    Explain {
+        /// Package name
+        #[arg(value_name = "PACKAGE")]
+        package: String,
+
+        /// Only look in this lockfile
+        #[arg(long, value_name = "PATH")]
+        lockfile: Option<PathBuf>,
+
+        /// Dependency chains shown per lockfile
+        #[arg(long, value_name = "N", default_value = "5")]
+        max_paths: usize,
+
+        /// Print JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+pub fn handle_lock(context: &CliContext, subcommand: &LockSubcommands) -> RhemaResult<()> {
+    let repo_root = context.rhema.repo_root();
+
+    match subcommand {
+        LockSubcommands::Status { json } => {
+            let statuses = lockfiles::lockfile_statuses(repo_root);
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&statuses)?);
+                return Ok(());
+            }
+            if statuses.is_empty() {
+                println!("📭 No lockfiles found");
+                return Ok(());
+            }
+
+            println!("🔒 Found {} lockfiles:", statuses.len());
+            for status in &statuses {
+                let state = match (&status.error, status.is_stale()) {
+                    (Some(_), _) => "❌ unreadable",
+                    (None, true) => "⚠️  stale",
+                    (None, false) => "✅ up to date",
+                };
+                println!(
+                    "  • {} ({} packages) {}",
+                    status.path.display(),
+                    status.packages,
+                    state
+                );
+                if let Some(error) = &status.error {
+                    println!("    {}", error);
+                }
+                for reason in &status.stale {
+                    println!("    {}", reason);
+                }
+            }
+            Ok(())
+        }
+        LockSubcommands::Diff { rev, to, json } => {
+            let diffs =
+                context.handle_error(lockfiles::diff_revisions(repo_root, rev, to.as_deref()))?;
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&diffs)?);
+                return Ok(());
+            }
+            if diffs.is_empty() {
+                println!("✅ No dependency changes");
+                return Ok(());
+            }
+
+            for diff in &diffs {
+                let count =
+                    |kind: ChangeKind| diff.changes.iter().filter(|c| c.change == kind).count();
+                println!(
+                    "📦 {}: {} added, {} removed, {} upgraded, {} downgraded, {} changed",
+                    diff.path.display(),
+                    count(ChangeKind::Added),
+                    count(ChangeKind::Removed),
+                    count(ChangeKind::Upgraded),
+                    count(ChangeKind::Downgraded),
+                    count(ChangeKind::Changed)
+                );
+                for change in &diff.changes {
+                    println!("    {}", change);
+                }
+            }
+            Ok(())
+        }
+        LockSubcommands::Explain {
+            package,
+            lockfile,
+            max_paths,
+            json,
+        } => {
+            let paths = match lockfile {
+                Some(path) => vec![path.clone()],
+                None => lockfiles::discover_lockfiles(repo_root),
+            };
+            let mut explanations = Vec::new();
+            for path in &paths {
+                let lockfile = context.handle_error(Lockfile::load(repo_root, path))?;
+                explanations.extend(lockfile.explain(package, *max_paths));
+            }
+            if explanations.is_empty() {
+                return Err(RhemaError::NotFound(format!(
+                    "Package '{}' is not in any lockfile",
+                    package
+                )));
+            }
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&explanations)?);
+                return Ok(());
+            }
+
+            for why in &explanations {
+                println!(
+                    "📦 {} {} in {}",
+                    why.package,
+                    why.versions.join(", "),
+                    why.lockfile.display()
+                );
+                let kind = if why.direct { "direct" } else { "transitive" };
+                let scope = if why.dev_only {
+                    ", development only"
+                } else {
+                    ""
+                };
+                println!("    {} dependency{}", kind, scope);
+                if !why.dependents.is_empty() {
+                    println!("    required by: {}", why.dependents.join(", "));
+                }
+                for chain in &why.paths {
+                    println!("    {}", chain.join(" → "));
+                }
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,339 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! `rhema lsp`: a Language Server Protocol server over stdio for
+//! `.rhema/*.yaml` files, backed by [`rhema_core::editor`].
+
+use crate::CliContext;
+use rhema_api::RhemaResult;
+use rhema_core::editor::{
+    CompletionKind, ContextIndex, DiagnosticSeverity, EditorCodeAction, LineRange,
+};
+use rhema_core::RhemaError;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// JSON-RPC error code of unknown requests
+const METHOD_NOT_FOUND: i64 = -32601;
+
+pub fn handle_lsp(context: &CliContext) -> RhemaResult<()> {
+    let index = context.handle_error(ContextIndex::build(context.rhema.repo_root()))?;
+    let stdin = std::io::stdin();
+    let mut server = Server {
+        index,
+        documents: HashMap::new(),
+        output: std::io::stdout(),
+    };
+    let mut input = BufReader::new(stdin.lock());
+    while let Some(message) = read_message(&mut input)? {
+        if !server.dispatch(message)? {
+            break;
+        }
+    }
+    Ok(())
+}
+
+struct Server<W: Write> {
+    index: ContextIndex,
+    /// Open documents by URI
+    documents: HashMap<String, String>,
+    output: W,
+}
+
+impl<W: Write> Server<W> {
+    /// Handle one message; `false` once the client asked to exit
+    fn dispatch(&mut self, message: Value) -> RhemaResult<bool> {
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        let id = message.get("id").cloned();
+
+        let result = match method {
+            "initialize" => Some(json!({
+                "capabilities": {
+                    "textDocumentSync": 1,
+                    "completionProvider": { "triggerCharacters": [" ", "[", ",", "-"] },
+                    "hoverProvider": true,
+                    "codeActionProvider": true,
+                },
+                "serverInfo": { "name": "rhema", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "initialized" | "$/cancelRequest" => None,
+            "textDocument/didOpen" => {
+                let document = &params["textDocument"];
+                self.update(document["uri"].as_str(), document["text"].as_str())?;
+                None
+            }
+            "textDocument/didChange" => {
+                // Full sync: the last change holds the whole text
+                let text = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str());
+                self.update(params["textDocument"]["uri"].as_str(), text)?;
+                None
+            }
+            "textDocument/didSave" => {
+                self.publish_all()?;
+                None
+            }
+            "textDocument/didClose" => {
+                if let Some(uri) = params["textDocument"]["uri"].as_str() {
+                    self.documents.remove(uri);
+                    self.notify(
+                        "textDocument/publishDiagnostics",
+                        json!({ "uri": uri, "diagnostics": [] }),
+                    )?;
+                }
+                None
+            }
+            "textDocument/completion" => Some(self.at_position(
+                params,
+                |index, path, text, line, character| {
+                    let items: Vec<Value> = index
+                        .completions(path, text, line, character)
+                        .into_iter()
+                        .map(|item| {
+                            // Value, Constant and Folder completion item kinds
+                            let kind = match item.kind {
+                                CompletionKind::Reference => 12,
+                                CompletionKind::Tag => 21,
+                                CompletionKind::Scope => 19,
+                            };
+                            json!({ "label": item.label, "kind": kind, "detail": item.detail })
+                        })
+                        .collect();
+                    json!(items)
+                },
+            )),
+            "textDocument/hover" => Some(self.at_position(
+                params,
+                |index, path, text, line, character| {
+                    index.hover(path, text, line, character).map_or(
+                        Value::Null,
+                        |markdown| json!({ "contents": { "kind": "markdown", "value": markdown } }),
+                    )
+                },
+            )),
+            "textDocument/codeAction" => {
+                let line = params["range"]["start"]["line"].as_u64().unwrap_or(0) as u32;
+                Some(self.at_position(params, |index, path, text, _, _| {
+                    json!(index
+                        .code_actions(path, text, line)
+                        .iter()
+                        .map(code_action)
+                        .collect::<Vec<_>>())
+                }))
+            }
+            "shutdown" => Some(Value::Null),
+            "exit" => return Ok(false),
+            _ => {
+                if let Some(id) = id {
+                    self.send(json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": {
+                            "code": METHOD_NOT_FOUND,
+                            "message": format!("Unsupported method: {}", method),
+                        },
+                    }))?;
+                }
+                return Ok(true);
+            }
+        };
+
+        if let (Some(id), Some(result)) = (id, result) {
+            self.send(json!({ "jsonrpc": "2.0", "id": id, "result": result }))?;
+        }
+        Ok(true)
+    }
+
+    /// Re-index an edited document and refresh diagnostics of every open
+    /// document, since references across files may have changed
+    fn update(&mut self, uri: Option<&str>, text: Option<&str>) -> RhemaResult<()> {
+        let (Some(uri), Some(text)) = (uri, text) else {
+            return Ok(());
+        };
+        if let Some(path) = uri_to_path(uri) {
+            self.index.update_document(&path, text);
+        }
+        self.documents.insert(uri.to_string(), text.to_string());
+        self.publish_all()
+    }
+
+    fn publish_all(&mut self) -> RhemaResult<()> {
+        let mut notifications = Vec::new();
+        for (uri, text) in &self.documents {
+            let Some(path) = uri_to_path(uri) else {
+                continue;
+            };
+            let diagnostics: Vec<Value> = self
+                .index
+                .diagnostics(&path, text)
+                .into_iter()
+                .map(|diagnostic| {
+                    json!({
+                        "range": range(diagnostic.range),
+                        "severity": match diagnostic.severity {
+                            DiagnosticSeverity::Error => 1,
+                            DiagnosticSeverity::Warning => 2,
+                        },
+                        "source": "rhema",
+                        "code": diagnostic.code,
+                        "message": diagnostic.message,
+                    })
+                })
+                .collect();
+            notifications.push(json!({ "uri": uri, "diagnostics": diagnostics }));
+        }
+        for params in notifications {
+            self.notify("textDocument/publishDiagnostics", params)?;
+        }
+        Ok(())
+    }
+
+    /// Run `f` on the document and position of a request
+    fn at_position(
+        &self,
+        params: &Value,
+        f: impl FnOnce(&ContextIndex, &Path, &str, u32, u32) -> Value,
+    ) -> Value {
+        let uri = params["textDocument"]["uri"].as_str().unwrap_or_default();
+        let (Some(path), Some(text)) = (uri_to_path(uri), self.documents.get(uri)) else {
+            return Value::Null;
+        };
+        let line = params["position"]["line"].as_u64().unwrap_or(0) as u32;
+        let character = params["position"]["character"].as_u64().unwrap_or(0) as u32;
+        f(&self.index, &path, text, line, character)
+    }
+
+    fn notify(&mut self, method: &str, params: Value) -> RhemaResult<()> {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }))
+    }
+
+    fn send(&mut self, message: Value) -> RhemaResult<()> {
+        let body = serde_json::to_string(&message)?;
+        write!(
+            self.output,
+            "Content-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        )?;
+        self.output.flush()?;
+        Ok(())
+    }
+}
+
+fn code_action(action: &EditorCodeAction) -> Value {
+    let mut document_changes = Vec::new();
+    for edit in &action.edits {
+        let uri = path_to_uri(&edit.path);
+        if edit.create {
+            document_changes.push(json!({ "kind": "create", "uri": uri }));
+        }
+        let position = json!({ "line": edit.line, "character": edit.character });
+        document_changes.push(json!({
+            "textDocument": { "uri": uri, "version": null },
+            "edits": [{ "range": { "start": position, "end": position }, "newText": edit.text }],
+        }));
+    }
+    json!({
+        "title": action.title,
+        "kind": "quickfix",
+        "edit": { "documentChanges": document_changes },
+    })
+}
+
+fn range(range: LineRange) -> Value {
+    json!({
+        "start": { "line": range.line, "character": range.start },
+        "end": { "line": range.line, "character": range.end },
+    })
+}
+
+/// Read one `Content-Length` framed message, `None` at end of input
+fn read_message(input: &mut impl BufRead) -> RhemaResult<Option<Value>> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        if input.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+    let length = length.ok_or_else(|| {
+        RhemaError::InvalidInput("LSP message without a Content-Length header".to_string())
+    })?;
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    Ok(Some(serde_json::from_slice(&body)?))
+}
+
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let encoded = uri.strip_prefix("file://")?;
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| encoded.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    let path = String::from_utf8(decoded).ok()?;
+    // Windows URIs carry the drive after a slash: file:///C:/repo
+    let path = match path.as_bytes() {
+        [b'/', _, b':', ..] => &path[1..],
+        _ => &path[..],
+    };
+    Some(PathBuf::from(path))
+}
+
+fn path_to_uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut uri = String::from("file://");
+    if !path.starts_with('/') {
+        uri.push('/');
+    }
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' | b':' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
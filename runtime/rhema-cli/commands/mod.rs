@@ -15,17 +15,76 @@
  */
 
 // Import submodules
+pub mod auth;
 pub mod coordination;
 pub mod core;
+pub mod daemon;
 pub mod decision;
+pub mod doctor;
+pub mod events;
+pub mod explain;
+pub mod export;
+pub mod find;
+pub mod gc;
+pub mod health;
+pub mod i18n;
+pub mod import;
 pub mod insight;
+pub mod issues;
+pub mod jobs;
+pub mod knowledge;
+pub mod lock;
+pub mod lsp;
 pub mod pattern;
+pub mod perf;
+pub mod policy;
+pub mod readme;
+pub mod resolve;
+pub mod review;
+pub mod schema;
+pub mod search;
+pub mod security;
+pub mod snapshot;
+pub mod stats;
+pub mod sync;
 pub mod todo;
+pub mod validate;
 
 // Re-export command enums and handlers
+pub use auth::{handle_auth, AuthSubcommands};
 pub use coordination::{handle_coordination, CoordinationSubcommands};
-pub use core::{handle_init, handle_query};
+pub use core::{
+    handle_init, handle_init_wizard, handle_mutate, handle_query, handle_query_lint,
+    handle_query_watch,
+};
+pub use daemon::{handle_daemon, DaemonSubcommands};
 pub use decision::{handle_decision, DecisionSubcommands};
+pub use doctor::{handle_doctor, DoctorArgs};
+pub use events::{handle_events, EventsSubcommands};
+pub use explain::{handle_explain, show_tip, ExplainArgs};
+pub use export::{handle_export, ExportArgs};
+pub use find::{handle_find, FindArgs};
+pub use gc::{handle_gc, GcArgs};
+pub use health::{handle_dependency_health, handle_freshness_health};
+pub use i18n::{handle_i18n, I18nSubcommands};
+pub use import::{handle_import, ImportSubcommands};
 pub use insight::{handle_insight, InsightSubcommands};
+pub use issues::{handle_issues, IssuesSubcommands};
+pub use jobs::{handle_jobs, JobsSubcommands};
+pub use knowledge::{handle_knowledge, KnowledgeSubcommands};
+pub use lock::{handle_lock, LockSubcommands};
+pub use lsp::handle_lsp;
 pub use pattern::{handle_pattern, PatternSubcommands};
+pub use perf::{handle_perf, PerfSubcommands};
+pub use policy::{handle_policy, PolicySubcommands};
+pub use readme::{handle_readme, ReadmeSubcommands};
+pub use resolve::{handle_resolve, ResolveArgs};
+pub use review::{handle_review, ReviewSubcommands};
+pub use schema::{handle_schema, SchemaSubcommands};
+pub use search::{handle_search, SearchArgs};
+pub use security::{handle_security, SecuritySubcommands};
+pub use snapshot::{handle_snapshot, SnapshotSubcommands};
+pub use stats::{handle_stats, StatsSubcommands};
+pub use sync::{handle_sync, SyncSubcommands};
 pub use todo::{handle_todo, TodoSubcommands};
+pub use validate::{handle_validate, ValidateArgs};
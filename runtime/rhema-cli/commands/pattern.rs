@@ -50,6 +50,10 @@ pub enum PatternSubcommands {
         /// Anti-patterns to avoid (comma-separated)
         #[arg(long, value_name = "ANTI_PATTERNS")]
         anti_patterns: Option<String>,
+
+        /// Field required by an entry template, as NAME=VALUE (repeatable)
+        #[arg(long = "field", value_name = "NAME=VALUE")]
+        fields: Vec<String>,
     },
 
     /// List patterns
@@ -124,6 +128,7 @@ pub fn handle_pattern(
             effectiveness,
             examples,
             anti_patterns,
+            fields,
         } => {
             match rhema_core::file_ops::add_pattern(
                 &scope.path,
@@ -134,6 +139,7 @@ pub fn handle_pattern(
                 *effectiveness,
                 examples.clone(),
                 anti_patterns.clone(),
+                rhema_core::templates::parse_entry_fields(fields)?,
             ) {
                 Ok(id) => {
                     println!("🔧 Pattern added successfully with ID: {}", id);
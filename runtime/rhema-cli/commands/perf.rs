@@ -0,0 +1,142 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CliContext;
+use clap::Subcommand;
+use rhema_api::RhemaResult;
+use rhema_core::RhemaError;
+use rhema_query::benchmark::{
+    check_regression, run_benchmarks, BenchmarkReport, PerfConfig, RegressionCheck,
+    QUERY_BASELINE_FILE,
+};
+use std::path::PathBuf;
+
+#[derive(Subcommand)]
+pub enum PerfSubcommands {
+    /// Benchmark the query engine and store the results as the baseline
+    Baseline {
+        /// Baseline file (default: .rhema/perf/query-baseline.json)
+        #[arg(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+
+        /// Timed runs per operation, overriding the perf config
+        #[arg(long, value_name = "N")]
+        iterations: Option<usize>,
+    },
+
+    /// Benchmark the query engine and fail when p95 latencies regress
+    CheckRegression {
+        /// Baseline file (default: .rhema/perf/query-baseline.json)
+        #[arg(long, value_name = "FILE")]
+        baseline: Option<PathBuf>,
+
+        /// Timed runs per operation, overriding the perf config
+        #[arg(long, value_name = "N")]
+        iterations: Option<usize>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+pub fn handle_perf(context: &CliContext, subcommand: &PerfSubcommands) -> RhemaResult<()> {
+    let repo_root = context.rhema.repo_root();
+    let mut config = context.handle_error(PerfConfig::load(repo_root))?;
+
+    match subcommand {
+        PerfSubcommands::Baseline { output, iterations } => {
+            config.iterations = iterations.unwrap_or(config.iterations);
+            let path = output
+                .clone()
+                .unwrap_or_else(|| repo_root.join(QUERY_BASELINE_FILE));
+
+            context.display_info("Benchmarking the query engine...")?;
+            let report = context.handle_error(benchmark(&config))?;
+            context.handle_error(report.save(&path))?;
+            for result in &report.results {
+                println!(
+                    "  {:<10} {:>5} scopes  p50 {:>9}µs  p95 {:>9}µs",
+                    result.operation, result.scope_count, result.p50_us, result.p95_us
+                );
+            }
+            println!("✅ Baseline written to {}", path.display());
+            Ok(())
+        }
+
+        PerfSubcommands::CheckRegression {
+            baseline,
+            iterations,
+            json,
+        } => {
+            config.iterations = iterations.unwrap_or(config.iterations);
+            let path = baseline
+                .clone()
+                .unwrap_or_else(|| repo_root.join(QUERY_BASELINE_FILE));
+            if !path.exists() {
+                context.display_warning(&format!(
+                    "No baseline at {}; record one with `rhema perf baseline`",
+                    path.display()
+                ))?;
+                return Ok(());
+            }
+            let baseline = context.handle_error(BenchmarkReport::load(&path))?;
+
+            context.display_info("Benchmarking the query engine...")?;
+            let current = context.handle_error(benchmark(&config))?;
+            let check = check_regression(&baseline, &current, &config.thresholds);
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&check)?);
+            } else {
+                print_check(&check);
+            }
+
+            let regressions = check.regressions().count();
+            if regressions > 0 {
+                return context.handle_error(Err(RhemaError::PerformanceError(format!(
+                    "{} query benchmark(s) regressed beyond their p95 threshold",
+                    regressions
+                ))));
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Run the benchmarks in a temporary directory
+fn benchmark(config: &PerfConfig) -> RhemaResult<BenchmarkReport> {
+    let work_dir = tempfile::TempDir::new()?;
+    run_benchmarks(work_dir.path(), config)
+}
+
+fn print_check(check: &RegressionCheck) {
+    println!("📊 Query engine p95 latencies against the baseline:");
+    for comparison in &check.comparisons {
+        println!(
+            "  {} {:<10} {:>5} scopes  {:>9}µs → {:>9}µs  {:+.1}% (max {:+.1}%)",
+            if comparison.regressed { "❌" } else { "✅" },
+            comparison.operation,
+            comparison.scope_count,
+            comparison.baseline_p95_us,
+            comparison.current_p95_us,
+            comparison.change_percent,
+            comparison.allowed_percent
+        );
+    }
+    for missing in &check.missing_baseline {
+        println!("  ⚠️  {} has no baseline entry", missing);
+    }
+}
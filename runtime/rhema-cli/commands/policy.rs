@@ -0,0 +1,154 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CliContext;
+use clap::Subcommand;
+use rhema_api::RhemaResult;
+use rhema_core::policy::{
+    PolicyBundleSource, PolicyManager, SectionChangeKind, POLICY_BUNDLE_SECTION,
+};
+use rhema_core::RhemaError;
+
+#[derive(Subcommand)]
+pub enum PolicySubcommands {
+    /// Show the configured bundle and the commit this repository pins
+    Status {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Fetch the bundle, move the pin to its latest commit and show what changed
+    Update {
+        /// Branch, tag or commit to pin instead of the configured ref
+        #[arg(long = "ref", value_name = "REF")]
+        reference: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+pub fn handle_policy(context: &CliContext, subcommand: &PolicySubcommands) -> RhemaResult<()> {
+    let manager = PolicyManager::new(context.rhema.repo_root());
+    let source = context.handle_error(PolicyBundleSource::from_global_config())?;
+
+    match subcommand {
+        PolicySubcommands::Status { json } => {
+            let pin = context.handle_error(manager.pin())?;
+            let sections: Vec<String> = match pin {
+                Some(_) => context
+                    .handle_error(manager.bundle_config())?
+                    .as_mapping()
+                    .map(|map| {
+                        map.keys()
+                            .filter_map(|key| key.as_str().map(str::to_string))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                None => Vec::new(),
+            };
+
+            if *json {
+                let status = serde_json::json!({
+                    "source": source,
+                    "pin": pin,
+                    "sections": sections,
+                });
+                println!("{}", serde_json::to_string_pretty(&status)?);
+                return Ok(());
+            }
+
+            match &source {
+                Some(source) => println!("📦 Bundle: {} ({})", source.url, source.reference),
+                None => println!("📦 No {} in the global config", POLICY_BUNDLE_SECTION),
+            }
+            match &pin {
+                Some(pin) => {
+                    println!(
+                        "📌 Pinned: {} ({}) since {}",
+                        &pin.commit[..pin.commit.len().min(12)],
+                        pin.reference,
+                        pin.pinned_at.format("%Y-%m-%d %H:%M UTC")
+                    );
+                    if sections.is_empty() {
+                        println!("   The bundle defines no sections");
+                    } else {
+                        println!("   Sections: {}", sections.join(", "));
+                    }
+                    if source.as_ref().is_some_and(|source| source.url != pin.url) {
+                        context.display_warning(&format!(
+                            "Pin refers to {}; run `rhema policy update` to switch bundles",
+                            pin.url
+                        ))?;
+                    }
+                }
+                None => println!("📌 Not pinned; run `rhema policy update`"),
+            }
+            Ok(())
+        }
+        PolicySubcommands::Update { reference, json } => {
+            let Some(mut source) = source else {
+                return context.handle_error(Err(RhemaError::ConfigError(format!(
+                    "No policy bundle configured; set {}.url in the global config",
+                    POLICY_BUNDLE_SECTION
+                ))));
+            };
+            if let Some(reference) = reference {
+                source.reference = reference.clone();
+            }
+
+            let update = context.handle_error(manager.update(&source))?;
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&update)?);
+                return Ok(());
+            }
+            if update.is_unchanged() {
+                println!("✅ Already pinned to {}", &update.pin.commit[..12]);
+                return Ok(());
+            }
+
+            match &update.previous {
+                Some(previous) => println!(
+                    "📌 Pin moved {} → {}",
+                    &previous[..previous.len().min(12)],
+                    &update.pin.commit[..12]
+                ),
+                None => println!("📌 Pinned {}", &update.pin.commit[..12]),
+            }
+            if !update.commits.is_empty() {
+                println!("📝 Changelog:");
+                for entry in &update.commits {
+                    println!("  • {} {} ({})", entry.commit, entry.summary, entry.author);
+                }
+            }
+            if !update.sections.is_empty() {
+                println!("🔧 Sections:");
+                for change in &update.sections {
+                    let marker = match change.kind {
+                        SectionChangeKind::Added => "+",
+                        SectionChangeKind::Removed => "-",
+                        SectionChangeKind::Changed => "~",
+                    };
+                    println!("  {} {}", marker, change.section);
+                }
+            }
+            println!("   Commit .rhema/policy.lock to share the new pin");
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,167 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CliContext;
+use clap::Subcommand;
+use rhema_api::{RhemaError, RhemaResult};
+use rhema_core::i18n::{text, text_with};
+use rhema_core::jobs::JobQueue;
+use rhema_core::readme_sync::{schedule_readme_sync, ReadmeStatus, ReadmeSync, SectionState};
+use rhema_core::scope::Scope;
+
+#[derive(Subcommand)]
+pub enum ReadmeSubcommands {
+    /// Regenerate the context sections of scope READMEs
+    Sync {
+        /// Only sync this scope
+        #[arg(long)]
+        scope: Option<String>,
+
+        /// Also overwrite sections that were edited by hand
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Report READMEs out of sync with their context; fails if any are
+    Check {
+        /// Only check this scope
+        #[arg(long)]
+        scope: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Queue recurring README syncs as configured in readme_sync
+    Schedule,
+}
+
+pub fn handle_readme(context: &CliContext, subcommand: &ReadmeSubcommands) -> RhemaResult<()> {
+    let repo_root = context.rhema.repo_root();
+    let sync = context.handle_error(ReadmeSync::open(repo_root))?;
+
+    match subcommand {
+        ReadmeSubcommands::Sync { scope, force } => {
+            let statuses = match scope {
+                Some(name) => {
+                    vec![context.handle_error(sync.sync(&find_scope(context, name)?, *force))?]
+                }
+                None => context.handle_error(sync.sync_all(*force))?,
+            };
+            for status in &statuses {
+                if status.updated.is_empty() {
+                    println!(
+                        "✅ {}",
+                        text_with("readme.sync.up_to_date", &[("scope", &status.scope)])
+                    );
+                } else {
+                    let sections: Vec<String> =
+                        status.updated.iter().map(|s| s.to_string()).collect();
+                    println!(
+                        "📝 {}",
+                        text_with(
+                            "readme.sync.regenerated",
+                            &[
+                                ("scope", &status.scope),
+                                ("sections", &sections.join(", ")),
+                                ("path", &status.path.display()),
+                            ]
+                        )
+                    );
+                }
+                let drifted = status.drifted();
+                if !*force && !drifted.is_empty() {
+                    context.display_warning(&text_with(
+                        "readme.sync.kept_drifted",
+                        &[
+                            ("scope", &status.scope),
+                            ("sections", &format!("{:?}", drifted)),
+                        ],
+                    ))?;
+                }
+            }
+            Ok(())
+        }
+
+        ReadmeSubcommands::Check { scope, json } => {
+            let statuses = match scope {
+                Some(name) => vec![context.handle_error(sync.status(&find_scope(context, name)?))?],
+                None => context.handle_error(sync.status_all())?,
+            };
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&statuses)?);
+            } else {
+                statuses.iter().for_each(print_status);
+            }
+            let out_of_sync = statuses
+                .iter()
+                .filter(|status| status.out_of_sync())
+                .count();
+            if out_of_sync > 0 {
+                return Err(RhemaError::ValidationError(text_with(
+                    "readme.check.out_of_sync",
+                    &[("count", &out_of_sync)],
+                )));
+            }
+            Ok(())
+        }
+
+        ReadmeSubcommands::Schedule => {
+            let queue = context.handle_error(JobQueue::open(repo_root))?;
+            match context.handle_error(schedule_readme_sync(&queue, sync.config()))? {
+                Some(job) => println!(
+                    "⏰ {}",
+                    text_with(
+                        "readme.schedule.scheduled",
+                        &[
+                            ("id", &job.id),
+                            ("secs", &sync.config().interval_secs.max(60)),
+                        ]
+                    )
+                ),
+                None => context.display_info(&text("readme.schedule.off"))?,
+            }
+            Ok(())
+        }
+    }
+}
+
+fn find_scope(context: &CliContext, name: &str) -> RhemaResult<Scope> {
+    let scopes = context.handle_error(context.rhema.discover_scopes())?;
+    scopes
+        .into_iter()
+        .find(|scope| scope.definition.name == name)
+        .ok_or_else(|| RhemaError::ScopeNotFound(name.to_string()))
+}
+
+fn print_status(status: &ReadmeStatus) {
+    let icon = if status.drifted().is_empty() {
+        if status.out_of_sync() {
+            "⏳"
+        } else {
+            "✅"
+        }
+    } else {
+        "✏️"
+    };
+    println!("{} {} ({})", icon, status.scope, status.path.display());
+    for section in &status.sections {
+        if section.state != SectionState::UpToDate {
+            println!("     {}: {}", section.section, section.state);
+        }
+    }
+}
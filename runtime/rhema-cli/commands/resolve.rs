@@ -0,0 +1,327 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CliContext;
+use chrono::Utc;
+use clap::{Args, ValueEnum};
+use rhema_api::{RhemaError, RhemaResult};
+use rhema_core::merge::{
+    conflicted_files, EntryConflict, FileMerge, ResolutionRecord, ResolutionStrategy, Side,
+};
+use rhema_core::review::local_reviewer;
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
+use std::io::{self, BufRead, Lines, StdinLock, Write};
+
+/// Width of each side in the side-by-side view
+const COLUMN_WIDTH: usize = 48;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum TakeSide {
+    Ours,
+    Theirs,
+}
+
+#[derive(Args)]
+pub struct ResolveArgs {
+    /// Only list conflicting entries
+    #[arg(long)]
+    list: bool,
+
+    /// Resolve every conflicting entry with one side, without prompting
+    #[arg(long, value_enum, conflicts_with = "list")]
+    take: Option<TakeSide>,
+
+    /// Rationale recorded with --take resolutions
+    #[arg(long, requires = "take")]
+    rationale: Option<String>,
+
+    /// Identity recorded as the resolver (default: git user.email)
+    #[arg(long)]
+    resolver: Option<String>,
+}
+
+/// What to do with one conflict
+enum Choice {
+    Resolve(Option<Value>, ResolutionStrategy),
+    Skip,
+    Quit,
+}
+
+pub fn handle_resolve(context: &CliContext, args: &ResolveArgs) -> RhemaResult<()> {
+    let repo_root = context.rhema.repo_root();
+    let mut files = context.handle_error(conflicted_files(repo_root))?;
+    if files.is_empty() {
+        println!("✅ No conflicted context files");
+        return Ok(());
+    }
+
+    if args.list {
+        for file in &files {
+            println!("📄 {}", file.path.display());
+            for conflict in &file.conflicts {
+                println!("  ⚔️  {}{}", conflict.label(), describe(conflict));
+            }
+        }
+        return Ok(());
+    }
+
+    let resolver = args
+        .resolver
+        .clone()
+        .unwrap_or_else(|| local_reviewer(repo_root));
+    let record = |strategy, rationale: Option<String>| ResolutionRecord {
+        resolved_by: resolver.clone(),
+        resolved_at: Utc::now(),
+        strategy,
+        rationale,
+    };
+
+    if let Some(take) = args.take {
+        let (side, strategy) = match take {
+            TakeSide::Ours => (Side::Ours, ResolutionStrategy::TakeOurs),
+            TakeSide::Theirs => (Side::Theirs, ResolutionStrategy::TakeTheirs),
+        };
+        for file in &mut files {
+            for index in 0..file.conflicts.len() {
+                let value = file.conflicts[index].side(side).cloned();
+                file.resolve(index, value, record(strategy, args.rationale.clone()))?;
+            }
+            context.handle_error(file.write(repo_root))?;
+            println!(
+                "✅ Resolved {} ({} entries, {})",
+                file.path.display(),
+                file.conflicts.len(),
+                strategy
+            );
+        }
+        return Ok(());
+    }
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    let total: usize = files.iter().map(|file| file.conflicts.len()).sum();
+    let mut position = 0;
+    'files: for file in &mut files {
+        println!("\n📄 {}", file.path.display());
+        for index in 0..file.conflicts.len() {
+            position += 1;
+            loop {
+                let conflict = &file.conflicts[index];
+                println!(
+                    "\n[{}/{}] {}{}",
+                    position,
+                    total,
+                    conflict.label(),
+                    describe(conflict)
+                );
+                print_side_by_side(conflict);
+                let (value, strategy) = match choose(conflict, &mut lines)? {
+                    Choice::Resolve(value, strategy) => (value, strategy),
+                    Choice::Skip => break,
+                    Choice::Quit => break 'files,
+                };
+                let rationale = ask(&mut lines, "Rationale (optional): ")?;
+                let rationale = (!rationale.is_empty()).then_some(rationale);
+
+                let before = file.clone();
+                file.resolve(index, value, record(strategy, rationale))?;
+                match file.validate() {
+                    Ok(()) => break,
+                    Err(e) => {
+                        context.display_warning(&format!("Resolution rejected: {}", e))?;
+                        *file = before;
+                    }
+                }
+            }
+        }
+
+        if file.is_resolved() {
+            context.handle_error(file.write(repo_root))?;
+            println!("✅ Resolved and staged {}", file.path.display());
+        }
+    }
+
+    let remaining: Vec<&FileMerge> = files.iter().filter(|file| !file.is_resolved()).collect();
+    if !remaining.is_empty() {
+        return Err(RhemaError::ValidationError(format!(
+            "{} context file(s) still conflicted; rerun `rhema resolve`",
+            remaining.len()
+        )));
+    }
+    Ok(())
+}
+
+fn choose(conflict: &EntryConflict, lines: &mut Lines<StdinLock<'_>>) -> RhemaResult<Choice> {
+    let can_merge = conflict.ours.is_some() && conflict.theirs.is_some();
+    let prompt = if can_merge {
+        "[o]urs / [t]heirs / [m]erge fields / [e]dit / [s]kip / [q]uit: "
+    } else {
+        "[o]urs / [t]heirs / [e]dit / [s]kip / [q]uit: "
+    };
+    loop {
+        match ask(lines, prompt)?.to_lowercase().as_str() {
+            "o" | "ours" => {
+                return Ok(Choice::Resolve(
+                    conflict.ours.clone(),
+                    ResolutionStrategy::TakeOurs,
+                ))
+            }
+            "t" | "theirs" => {
+                return Ok(Choice::Resolve(
+                    conflict.theirs.clone(),
+                    ResolutionStrategy::TakeTheirs,
+                ))
+            }
+            "m" | "merge" if can_merge => {
+                let mut picks = HashMap::new();
+                for field in conflict.conflicting_fields() {
+                    let side = loop {
+                        let question = format!(
+                            "  {}: [o]urs {} / [t]heirs {}: ",
+                            field,
+                            render_field(conflict, Side::Ours, &field),
+                            render_field(conflict, Side::Theirs, &field)
+                        );
+                        match ask(lines, &question)?.to_lowercase().as_str() {
+                            "o" | "ours" => break Side::Ours,
+                            "t" | "theirs" => break Side::Theirs,
+                            _ => {}
+                        }
+                    };
+                    picks.insert(field, side);
+                }
+                let merged = conflict.merge_fields(|field| picks[field]);
+                return Ok(Choice::Resolve(merged, ResolutionStrategy::MergeFields));
+            }
+            "e" | "edit" => {
+                return Ok(Choice::Resolve(
+                    Some(edit(conflict, lines)?),
+                    ResolutionStrategy::Edit,
+                ))
+            }
+            "s" | "skip" => return Ok(Choice::Skip),
+            "q" | "quit" => return Ok(Choice::Quit),
+            _ => {}
+        }
+    }
+}
+
+/// Start from our version (theirs if we deleted the entry) and apply
+/// FIELD=VALUE lines until an empty line; `FIELD=` removes the field
+fn edit(conflict: &EntryConflict, lines: &mut Lines<StdinLock<'_>>) -> RhemaResult<Value> {
+    let mut entry = conflict
+        .ours
+        .clone()
+        .or_else(|| conflict.theirs.clone())
+        .unwrap_or(Value::Mapping(Mapping::new()));
+    println!("  Enter FIELD=VALUE lines (VALUE is parsed as YAML), then an empty line");
+    loop {
+        let line = ask(lines, "  > ")?;
+        if line.is_empty() {
+            return Ok(entry);
+        }
+        let Some((field, value)) = line.split_once('=') else {
+            println!("  Expected FIELD=VALUE");
+            continue;
+        };
+        let Some(fields) = entry.as_mapping_mut() else {
+            return Err(RhemaError::InvalidInput(format!(
+                "{} is not a mapping and cannot be edited field by field",
+                conflict.label()
+            )));
+        };
+        let key = Value::String(field.trim().to_string());
+        if value.trim().is_empty() {
+            fields.remove(&key);
+        } else {
+            let value: Value =
+                serde_yaml::from_str(value).unwrap_or_else(|_| Value::String(value.to_string()));
+            fields.insert(key, value);
+        }
+    }
+}
+
+fn ask(lines: &mut Lines<StdinLock<'_>>, question: &str) -> RhemaResult<String> {
+    print!("{}", question);
+    io::stdout().flush()?;
+    match lines.next().transpose()? {
+        Some(line) => Ok(line.trim().to_string()),
+        None => Err(RhemaError::InvalidInput(
+            "Input ended before the conflicts were resolved".to_string(),
+        )),
+    }
+}
+
+fn describe(conflict: &EntryConflict) -> String {
+    match (&conflict.ours, &conflict.theirs) {
+        (None, _) => " (deleted on our side, changed on theirs)".to_string(),
+        (_, None) => " (changed on our side, deleted on theirs)".to_string(),
+        _ => {
+            let fields = conflict.conflicting_fields();
+            if fields.is_empty() {
+                String::new()
+            } else {
+                format!(" (both changed: {})", fields.join(", "))
+            }
+        }
+    }
+}
+
+fn print_side_by_side(conflict: &EntryConflict) {
+    let render = |side: Side| -> Vec<String> {
+        match conflict.side(side) {
+            Some(value) => serde_yaml::to_string(value)
+                .unwrap_or_default()
+                .lines()
+                .map(str::to_string)
+                .collect(),
+            None => vec!["(deleted)".to_string()],
+        }
+    };
+    let (ours, theirs) = (render(Side::Ours), render(Side::Theirs));
+    println!("  {:<COLUMN_WIDTH$} │ {}", "OURS", "THEIRS");
+    println!("  {:─<COLUMN_WIDTH$}─┼─{:─<COLUMN_WIDTH$}", "", "");
+    for row in 0..ours.len().max(theirs.len()) {
+        let left = ours.get(row).map(String::as_str).unwrap_or_default();
+        let right = theirs.get(row).map(String::as_str).unwrap_or_default();
+        let marker = if left == right { ' ' } else { '≠' };
+        println!(
+            "{} {:<COLUMN_WIDTH$} │ {}",
+            marker,
+            truncate(left),
+            truncate(right)
+        );
+    }
+}
+
+fn render_field(conflict: &EntryConflict, side: Side, field: &str) -> String {
+    match conflict.side(side).and_then(|entry| entry.get(field)) {
+        Some(Value::String(text)) => format!("{:?}", text),
+        Some(other) => serde_json::to_string(other).unwrap_or_default(),
+        None => "(unset)".to_string(),
+    }
+}
+
+fn truncate(line: &str) -> String {
+    if line.chars().count() <= COLUMN_WIDTH {
+        line.to_string()
+    } else {
+        let mut short: String = line.chars().take(COLUMN_WIDTH - 1).collect();
+        short.push('…');
+        short
+    }
+}
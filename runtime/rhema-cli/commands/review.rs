@@ -0,0 +1,210 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CliContext;
+use clap::Subcommand;
+use rhema_api::RhemaResult;
+use rhema_core::review::{local_reviewer, FieldChange, ReviewItem, ReviewQueue, ReviewState};
+use rhema_core::RhemaError;
+use serde_yaml::{Mapping, Value};
+
+/// Review states that can be listed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum ListState {
+    Pending,
+    Rejected,
+}
+
+#[derive(Subcommand)]
+pub enum ReviewSubcommands {
+    /// List entries awaiting review with their contents
+    List {
+        /// Entries to list
+        #[arg(long, value_enum, default_value = "pending")]
+        state: ListState,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Approve an entry so it is queried and injected
+    Approve {
+        /// Entry id
+        #[arg(value_name = "ID")]
+        id: String,
+
+        /// Reason recorded with the decision
+        #[arg(long)]
+        note: Option<String>,
+
+        /// Reviewer identity (default: git user.email)
+        #[arg(long)]
+        reviewer: Option<String>,
+    },
+
+    /// Reject an entry; it stays hidden
+    Reject {
+        /// Entry id
+        #[arg(value_name = "ID")]
+        id: String,
+
+        /// Reason recorded with the decision
+        #[arg(long)]
+        note: Option<String>,
+
+        /// Reviewer identity (default: git user.email)
+        #[arg(long)]
+        reviewer: Option<String>,
+    },
+
+    /// Change fields of an entry, then approve it
+    Edit {
+        /// Entry id
+        #[arg(value_name = "ID")]
+        id: String,
+
+        /// Field to overwrite, as FIELD=VALUE (VALUE is parsed as YAML)
+        #[arg(long = "set", value_name = "FIELD=VALUE", required = true)]
+        set: Vec<String>,
+
+        /// Reason recorded with the decision
+        #[arg(long)]
+        note: Option<String>,
+
+        /// Reviewer identity (default: git user.email)
+        #[arg(long)]
+        reviewer: Option<String>,
+    },
+}
+
+pub fn handle_review(context: &CliContext, subcommand: &ReviewSubcommands) -> RhemaResult<()> {
+    let repo_root = context.rhema.repo_root();
+    let queue = ReviewQueue::new(repo_root);
+    let reviewer_or_default = |reviewer: &Option<String>| {
+        reviewer
+            .clone()
+            .unwrap_or_else(|| local_reviewer(repo_root))
+    };
+
+    match subcommand {
+        ReviewSubcommands::List { state, json } => {
+            let state = match state {
+                ListState::Pending => ReviewState::Pending,
+                ListState::Rejected => ReviewState::Rejected,
+            };
+            let items = context.handle_error(queue.list(state))?;
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&items)?);
+                return Ok(());
+            }
+            if items.is_empty() {
+                println!("✅ No {} entries", state);
+                return Ok(());
+            }
+
+            println!("📝 {} {} entries:", items.len(), state);
+            for item in &items {
+                print_item(item);
+                print_changes(&item.changes());
+            }
+            if state == ReviewState::Pending {
+                println!("   Decide with: rhema review approve|reject|edit <ID>");
+            }
+            Ok(())
+        }
+        ReviewSubcommands::Approve { id, note, reviewer } => {
+            let reviewer = reviewer_or_default(reviewer);
+            let item = context.handle_error(queue.approve(id, &reviewer, note.clone()))?;
+            println!("✅ Approved {} as {}", item.id, reviewer);
+            Ok(())
+        }
+        ReviewSubcommands::Reject { id, note, reviewer } => {
+            let reviewer = reviewer_or_default(reviewer);
+            let item = context.handle_error(queue.reject(id, &reviewer, note.clone()))?;
+            println!("🚫 Rejected {} as {}", item.id, reviewer);
+            Ok(())
+        }
+        ReviewSubcommands::Edit {
+            id,
+            set,
+            note,
+            reviewer,
+        } => {
+            let changes = context.handle_error(parse_changes(set))?;
+            let reviewer = reviewer_or_default(reviewer);
+            let (item, edits) =
+                context.handle_error(queue.edit(id, &reviewer, changes, note.clone()))?;
+            println!("✅ Edited and approved {} as {}", item.id, reviewer);
+            print_changes(&edits);
+            Ok(())
+        }
+    }
+}
+
+fn parse_changes(assignments: &[String]) -> RhemaResult<Mapping> {
+    let mut changes = Mapping::new();
+    for assignment in assignments {
+        let (field, value) = assignment.split_once('=').ok_or_else(|| {
+            RhemaError::InvalidInput(format!("Expected FIELD=VALUE, got '{}'", assignment))
+        })?;
+        let value: Value =
+            serde_yaml::from_str(value).unwrap_or_else(|_| Value::String(value.to_string()));
+        changes.insert(Value::String(field.trim().to_string()), value);
+    }
+    Ok(changes)
+}
+
+fn print_item(item: &ReviewItem) {
+    let scope = if item.scope.is_empty() {
+        "."
+    } else {
+        &item.scope
+    };
+    println!("\n  {} ({}/{})", item.id, scope, item.file);
+    if let Some(review) = &item.review {
+        println!(
+            "  submitted by {} at {}",
+            review.submitted_by,
+            review.submitted_at.format("%Y-%m-%d %H:%M UTC")
+        );
+        if let Some(reviewer) = &review.reviewed_by {
+            println!("  reviewed by {}", reviewer);
+        }
+        if let Some(note) = &review.note {
+            println!("  note: {}", note);
+        }
+    }
+}
+
+fn print_changes(changes: &[FieldChange]) {
+    for change in changes {
+        if let Some(before) = &change.before {
+            println!("  - {}: {}", change.field, render(before));
+        }
+        if let Some(after) = &change.after {
+            println!("  + {}: {}", change.field, render(after));
+        }
+    }
+}
+
+/// Single-line rendering of a field value
+fn render(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.replace('\n', "\\n"),
+        other => serde_json::to_string(other).unwrap_or_default(),
+    }
+}
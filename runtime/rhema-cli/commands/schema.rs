@@ -0,0 +1,106 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CliContext;
+use clap::Subcommand;
+use rhema_api::RhemaResult;
+use rhema_config::{SchemaDocument, SchemaExporter};
+use rhema_core::RhemaError;
+use rhema_mcp::McpConfig;
+use std::path::PathBuf;
+
+#[derive(Subcommand)]
+pub enum SchemaSubcommands {
+    /// List the available JSON Schemas
+    List,
+
+    /// Export JSON Schemas for configuration and context files
+    Export {
+        /// Directory to write the schemas to
+        #[arg(long, value_name = "DIR", default_value = ".rhema/schemas")]
+        output: PathBuf,
+
+        /// Only export the named schemas (repeatable)
+        #[arg(long = "name", value_name = "NAME")]
+        names: Vec<String>,
+
+        /// Print a single schema to stdout instead of writing files
+        #[arg(long, requires = "names")]
+        stdout: bool,
+    },
+}
+
+pub fn handle_schema(context: &CliContext, subcommand: &SchemaSubcommands) -> RhemaResult<()> {
+    let exporter = context.handle_error(exporter())?;
+
+    match subcommand {
+        SchemaSubcommands::List => {
+            println!("📋 Available schemas:");
+            for document in exporter.documents() {
+                println!(
+                    "  • {} → {}",
+                    document.name,
+                    document.file_patterns.join(", ")
+                );
+            }
+            Ok(())
+        }
+        SchemaSubcommands::Export {
+            output,
+            names,
+            stdout,
+        } => {
+            if *stdout {
+                if names.len() != 1 {
+                    return Err(RhemaError::InvalidInput(
+                        "--stdout prints exactly one schema; pass a single --name".to_string(),
+                    ));
+                }
+                let document = exporter.get(&names[0]).ok_or_else(|| {
+                    RhemaError::InvalidInput(format!("Unknown schema '{}'", names[0]))
+                })?;
+                println!("{}", serde_json::to_string_pretty(&document.schema)?);
+                return Ok(());
+            }
+
+            let written = context.handle_error(exporter.export(output, names))?;
+            println!(
+                "✅ Exported {} files to {}",
+                written.len(),
+                output.display()
+            );
+            for path in &written {
+                println!("  • {}", path.display());
+            }
+            println!(
+                "💡 Point yaml-language-server at {} (the `yaml.schemas` setting) for editor validation",
+                output.join(rhema_config::schema_export::ASSOCIATIONS_FILE).display()
+            );
+            Ok(())
+        }
+    }
+}
+
+/// Every schema Rhema publishes, including those owned by crates
+/// `rhema-config` does not depend on
+fn exporter() -> RhemaResult<SchemaExporter> {
+    let mut exporter = SchemaExporter::new()?;
+    exporter.add(SchemaDocument::generate::<McpConfig>(
+        "mcp-config",
+        &[".rhema/mcp.yaml"],
+    ));
+    Ok(exporter)
+}
@@ -0,0 +1,294 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CliContext;
+use clap::Args;
+use colored::*;
+use rhema_api::RhemaResult;
+use rhema_knowledge::embedding::{EmbeddingManager, EmbeddingManagerConfig};
+use rhema_knowledge::faceted_search::{
+    FacetCounts, KnowledgeSearch, KnowledgeSearchConfig, SearchFacets, SearchHit,
+};
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, Write};
+use std::sync::Arc;
+
+/// Facet values shown per facet under the results
+const FACET_VALUES_SHOWN: usize = 5;
+
+const FACET_NAMES: [&str; 5] = ["scope", "type", "tag", "since", "until"];
+
+#[derive(Args)]
+pub struct SearchArgs {
+    /// Search term (optional with --more-like or facet filters)
+    #[arg(value_name = "TERM")]
+    term: Option<String>,
+
+    /// Scan this file with a regex instead of searching semantically
+    #[arg(short, long)]
+    in_file: Option<String>,
+
+    /// Scan context files with a regex instead of searching semantically
+    #[arg(long)]
+    regex: bool,
+
+    /// Only entries in this scope (repeatable)
+    #[arg(long = "scope", value_name = "SCOPE")]
+    scopes: Vec<String>,
+
+    /// Only this content type: knowledge, todo, decision or pattern (repeatable)
+    #[arg(long = "type", value_name = "TYPE")]
+    types: Vec<String>,
+
+    /// Only entries with this tag (repeatable, all must match)
+    #[arg(long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
+
+    /// Only entries created on or after this date (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE")]
+    since: Option<String>,
+
+    /// Only entries created on or before this date (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE")]
+    until: Option<String>,
+
+    /// Find entries similar to this entry ID instead of matching a term
+    #[arg(long, value_name = "ID")]
+    more_like: Option<String>,
+
+    /// Maximum results
+    #[arg(short, long, default_value = "10")]
+    limit: usize,
+
+    /// Drop results scoring below this (0.0-1.0)
+    #[arg(long, value_name = "SCORE")]
+    min_score: Option<f32>,
+
+    /// Refine the results in a prompt loop
+    #[arg(long)]
+    interactive: bool,
+}
+
+pub async fn handle_search(context: &CliContext, args: &SearchArgs) -> RhemaResult<()> {
+    if args.regex || args.in_file.is_some() {
+        return regex_search(context, args);
+    }
+
+    let mut facets = SearchFacets::default();
+    let refinements = args
+        .scopes
+        .iter()
+        .map(|s| format!("scope:{}", s))
+        .chain(args.types.iter().map(|t| format!("type:{}", t)))
+        .chain(args.tags.iter().map(|t| format!("tag:{}", t)))
+        .chain(args.since.iter().map(|d| format!("since:{}", d)))
+        .chain(args.until.iter().map(|d| format!("until:{}", d)));
+    for refinement in refinements {
+        context.handle_error(facets.refine(&refinement))?;
+    }
+
+    let scopes: Vec<_> = context
+        .handle_error(context.rhema.discover_scopes())?
+        .into_iter()
+        .map(|scope| (scope.definition.name.clone(), scope.path.clone()))
+        .collect();
+    let defaults = KnowledgeSearchConfig::default();
+    let config = KnowledgeSearchConfig {
+        min_score: args.min_score.unwrap_or(defaults.min_score),
+        ..defaults
+    };
+    let manager = EmbeddingManager::new(EmbeddingManagerConfig::default()).await?;
+    let search =
+        context.handle_error(KnowledgeSearch::build(&scopes, Arc::new(manager), config).await)?;
+    if search.is_empty() {
+        println!("📭 No context entries to search");
+        return Ok(());
+    }
+
+    let mut query = args.term.clone().unwrap_or_default();
+    let mut hits = match &args.more_like {
+        Some(id) => context.handle_error(search.more_like_this(id, &facets, args.limit).await)?,
+        None => context.handle_error(search.search(&query, &facets, args.limit).await)?,
+    };
+    print_hits(&hits, &facets);
+
+    if !args.interactive {
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        "Refine with scope:/type:/tag:/since:/until: (prefix - to remove), new terms, \
+         'more N', 'show N', 'clear' or 'q'"
+            .dimmed()
+    );
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("refine> ");
+        io::stdout().flush()?;
+        let Some(line) = lines.next().transpose()? else {
+            break;
+        };
+        let line = line.trim();
+
+        let mut words = line.split_whitespace();
+        match (words.next(), words.next()) {
+            (None, _) | (Some("q" | "quit" | "exit"), None) => break,
+            (Some("clear"), None) => facets = SearchFacets::default(),
+            (Some("show"), Some(n)) => {
+                match pick(&hits, n) {
+                    Some(hit) => println!("\n{}\n", hit.document.text()),
+                    None => context.display_warning(&format!("No result {}", n))?,
+                }
+                continue;
+            }
+            (Some("more"), Some(n)) => {
+                let id = pick(&hits, n)
+                    .map(|hit| hit.document.key.clone())
+                    .unwrap_or_else(|| n.to_string());
+                match search.more_like_this(&id, &facets, args.limit).await {
+                    Ok(similar) => {
+                        hits = similar;
+                        print_hits(&hits, &facets);
+                    }
+                    Err(e) => context.display_warning(&e.to_string())?,
+                }
+                continue;
+            }
+            _ => {
+                let mut terms = Vec::new();
+                for token in line.split_whitespace() {
+                    if is_refinement(token) {
+                        if let Err(e) = facets.refine(token) {
+                            context.display_warning(&e.to_string())?;
+                        }
+                    } else {
+                        terms.push(token);
+                    }
+                }
+                if !terms.is_empty() {
+                    query = terms.join(" ");
+                }
+            }
+        }
+
+        match search.search(&query, &facets, args.limit).await {
+            Ok(found) => {
+                hits = found;
+                print_hits(&hits, &facets);
+            }
+            Err(e) => context.display_warning(&e.to_string())?,
+        }
+    }
+    Ok(())
+}
+
+fn regex_search(context: &CliContext, args: &SearchArgs) -> RhemaResult<()> {
+    let Some(term) = &args.term else {
+        context.display_warning("Regex search needs a search term")?;
+        return Ok(());
+    };
+    context.display_info(&format!("Searching for: {}", term))?;
+    if let Some(file) = &args.in_file {
+        context.display_info(&format!("In file: {}", file))?;
+    }
+
+    let results =
+        context.handle_error(context.rhema.search_regex(term, args.in_file.as_deref()))?;
+    if results.is_empty() {
+        context.display_info("No results found")?;
+    } else {
+        for result in results {
+            println!("Found: {:?}", result);
+        }
+    }
+    Ok(())
+}
+
+/// `tag:perf`, `-scope`, ... as opposed to a search word
+fn is_refinement(token: &str) -> bool {
+    let facet = token.trim_start_matches('-');
+    let facet = facet.split_once(':').map_or(facet, |(name, _)| name);
+    (token.contains(':') || token.starts_with('-')) && FACET_NAMES.contains(&facet)
+}
+
+/// A result by its 1-based position
+fn pick<'a>(hits: &'a [SearchHit], n: &str) -> Option<&'a SearchHit> {
+    n.parse::<usize>()
+        .ok()
+        .and_then(|n| n.checked_sub(1))
+        .and_then(|i| hits.get(i))
+}
+
+fn print_hits(hits: &[SearchHit], facets: &SearchFacets) {
+    println!();
+    if !facets.is_empty() {
+        println!("{} {}", "Filters:".bold(), facets);
+    }
+    if hits.is_empty() {
+        println!("📭 No results");
+        return;
+    }
+
+    for (index, hit) in hits.iter().enumerate() {
+        let document = &hit.document;
+        println!(
+            "{:>3}. {} {} {}",
+            index + 1,
+            document.title.bold(),
+            format!("[{}:{}]", document.content_type, document.id).cyan(),
+            format!("{} · {:.2}", document.scope, hit.score).dimmed()
+        );
+        println!("     {}", render_snippet(hit));
+        if !document.tags.is_empty() {
+            println!("     {}", format!("#{}", document.tags.join(" #")).dimmed());
+        }
+    }
+
+    let counts = FacetCounts::from_hits(hits);
+    println!();
+    print_facet("scope", &counts.scopes);
+    print_facet("type", &counts.content_types);
+    print_facet("tag", &counts.tags);
+}
+
+fn print_facet(name: &str, counts: &BTreeMap<String, usize>) {
+    if counts.is_empty() {
+        return;
+    }
+    let mut values: Vec<(&String, &usize)> = counts.iter().collect();
+    values.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+    let shown: Vec<String> = values
+        .iter()
+        .take(FACET_VALUES_SHOWN)
+        .map(|(value, count)| format!("{}:{} ({})", name, value, count))
+        .collect();
+    println!("  {}", shown.join("  ").dimmed());
+}
+
+/// Snippet with the query terms highlighted
+fn render_snippet(hit: &SearchHit) -> String {
+    let mut rendered = String::new();
+    let mut at = 0;
+    for range in &hit.highlights {
+        rendered.push_str(&hit.snippet[at..range.start]);
+        rendered.push_str(&hit.snippet[range.clone()].yellow().bold().to_string());
+        at = range.end;
+    }
+    rendered.push_str(&hit.snippet[at..]);
+    rendered
+}
@@ -0,0 +1,142 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CliContext;
+use clap::Subcommand;
+use rhema_api::RhemaResult;
+use rhema_core::prompt_guard::{PromptGuard, QuarantineRecord};
+use rhema_core::review::local_reviewer;
+
+#[derive(Subcommand)]
+pub enum SecuritySubcommands {
+    /// Scan every scope's context for prompt injection and quarantine matches
+    Scan {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List quarantined entries with their findings
+    Quarantine {
+        /// Include released entries
+        #[arg(long)]
+        all: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Serve a quarantined entry to agents again
+    Release {
+        /// Entry id or fingerprint prefix
+        #[arg(value_name = "ID")]
+        id: String,
+
+        /// Reviewer identity (default: git user.email)
+        #[arg(long)]
+        reviewer: Option<String>,
+    },
+}
+
+pub fn handle_security(context: &CliContext, subcommand: &SecuritySubcommands) -> RhemaResult<()> {
+    let repo_root = context.rhema.repo_root();
+    let guard = context.handle_error(PromptGuard::open(repo_root))?;
+
+    match subcommand {
+        SecuritySubcommands::Scan { json } => {
+            let scopes = context.rhema.discover_scopes()?;
+            let mut quarantined = Vec::new();
+            for scope in &scopes {
+                let mut files: Vec<_> = scope
+                    .files
+                    .iter()
+                    .filter(|(name, _)| name.ends_with(".yaml") && name.as_str() != "rhema.yaml")
+                    .collect();
+                files.sort();
+                for (_, path) in files {
+                    let content = std::fs::read_to_string(path)?;
+                    let source = path.strip_prefix(repo_root).unwrap_or(path);
+                    let (_, records) =
+                        guard.screened_content(&source.display().to_string(), &content);
+                    quarantined.extend(records);
+                }
+            }
+
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&quarantined)?);
+            } else if quarantined.is_empty() {
+                println!(
+                    "✅ No new suspicious entries in {} scope(s); {} entr(ies) remain quarantined",
+                    scopes.len(),
+                    guard.records(false).len()
+                );
+            } else {
+                println!("🛡️  Quarantined {} new entr(ies):", quarantined.len());
+                print_records(&quarantined);
+            }
+            Ok(())
+        }
+
+        SecuritySubcommands::Quarantine { all, json } => {
+            let records = guard.records(*all);
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&records)?);
+            } else if records.is_empty() {
+                println!("✅ No quarantined entries");
+            } else {
+                println!("🛡️  Quarantined entries:");
+                print_records(&records);
+            }
+            Ok(())
+        }
+
+        SecuritySubcommands::Release { id, reviewer } => {
+            let reviewer = reviewer
+                .clone()
+                .unwrap_or_else(|| local_reviewer(repo_root));
+            let record = context.handle_error(guard.release(id, &reviewer))?;
+            println!(
+                "🔓 Released {} from {}; agents will see it again",
+                record.entry_id, record.source
+            );
+            Ok(())
+        }
+    }
+}
+
+fn print_records(records: &[QuarantineRecord]) {
+    for record in records {
+        let released = match &record.released_by {
+            Some(reviewer) => format!(" (released by {})", reviewer),
+            None => String::new(),
+        };
+        println!(
+            "  {} {} in {} [{}]{}",
+            record.fingerprint,
+            record.entry_id,
+            record.source,
+            record.reason(),
+            released
+        );
+        for finding in &record.findings {
+            println!(
+                "     {:?} {} at {}: {}",
+                finding.level, finding.kind, finding.field, finding.excerpt
+            );
+        }
+    }
+}
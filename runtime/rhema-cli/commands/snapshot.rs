@@ -0,0 +1,187 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CliContext;
+use clap::Subcommand;
+use rhema_api::RhemaResult;
+use rhema_core::snapshot::{ChangeKind, SnapshotDiff, SnapshotManager};
+
+#[derive(Subcommand)]
+pub enum SnapshotSubcommands {
+    /// Record a snapshot of all context files
+    Create {
+        /// Snapshot name
+        #[arg(value_name = "NAME")]
+        name: String,
+
+        /// Snapshot description
+        #[arg(long, value_name = "DESCRIPTION")]
+        description: Option<String>,
+
+        /// Also create a git tag at HEAD
+        #[arg(long)]
+        tag: bool,
+    },
+
+    /// List snapshots
+    List,
+
+    /// Show entry-level differences between two snapshots
+    Diff {
+        /// Base snapshot
+        #[arg(value_name = "A")]
+        from: String,
+
+        /// Snapshot to compare against (defaults to the working tree)
+        #[arg(value_name = "B")]
+        to: Option<String>,
+    },
+
+    /// Restore context files from a snapshot
+    Restore {
+        /// Snapshot name
+        #[arg(value_name = "NAME")]
+        name: String,
+
+        /// Only show what would change
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Remove context files that did not exist in the snapshot
+        #[arg(long)]
+        prune: bool,
+    },
+
+    /// Delete a snapshot
+    Delete {
+        /// Snapshot name
+        #[arg(value_name = "NAME")]
+        name: String,
+    },
+}
+
+pub fn handle_snapshot(context: &CliContext, subcommand: &SnapshotSubcommands) -> RhemaResult<()> {
+    let manager = SnapshotManager::new(context.rhema.repo_root());
+
+    match subcommand {
+        SnapshotSubcommands::Create {
+            name,
+            description,
+            tag,
+        } => {
+            let manifest = context.handle_error(manager.create(name, description.clone(), *tag))?;
+            println!("📸 Snapshot '{}' created", manifest.name);
+            println!("📄 Files: {}", manifest.files.len());
+            if let Some(commit) = &manifest.git_commit {
+                println!("🔗 Commit: {}", commit);
+            }
+            if let Some(tag) = &manifest.git_tag {
+                println!("🏷️  Tag: {}", tag);
+            }
+            Ok(())
+        }
+        SnapshotSubcommands::List => {
+            let snapshots = context.handle_error(manager.list())?;
+            if snapshots.is_empty() {
+                println!("📭 No snapshots found");
+            } else {
+                println!("📋 Found {} snapshots:", snapshots.len());
+                for snapshot in snapshots {
+                    println!(
+                        "  • {} ({} files, {})",
+                        snapshot.name,
+                        snapshot.files.len(),
+                        snapshot.created_at.format("%Y-%m-%d %H:%M:%S")
+                    );
+                    if let Some(description) = &snapshot.description {
+                        println!("    {}", description);
+                    }
+                }
+            }
+            Ok(())
+        }
+        SnapshotSubcommands::Diff { from, to } => {
+            let diff = match to {
+                Some(to) => context.handle_error(manager.diff(from, to))?,
+                None => context.handle_error(manager.diff_working(from))?,
+            };
+            print_diff(&diff);
+            Ok(())
+        }
+        SnapshotSubcommands::Restore {
+            name,
+            dry_run,
+            prune,
+        } => {
+            let preview = context.handle_error(manager.restore_preview(name))?;
+            println!("🔍 Restoring '{}' would make these changes:", name);
+            print_diff(&preview);
+
+            if *dry_run || preview.is_empty() {
+                return Ok(());
+            }
+
+            let report = context.handle_error(manager.restore(name, *prune))?;
+            println!(
+                "✅ Restored {} files from snapshot '{}'",
+                report.restored.len(),
+                name
+            );
+            for file in &report.removed {
+                println!("🗑️  Removed {}", file);
+            }
+            if !report.kept.is_empty() {
+                context.display_warning(&format!(
+                    "{} files not in the snapshot were kept (use --prune to remove them)",
+                    report.kept.len()
+                ))?;
+            }
+            Ok(())
+        }
+        SnapshotSubcommands::Delete { name } => {
+            context.handle_error(manager.delete(name))?;
+            println!("🗑️  Snapshot '{}' deleted", name);
+            Ok(())
+        }
+    }
+}
+
+fn print_diff(diff: &SnapshotDiff) {
+    if diff.is_empty() {
+        println!("✅ No differences");
+        return;
+    }
+
+    for file in &diff.files {
+        println!("{} {}", change_marker(file.kind), file.file);
+        for entry in diff.entries.iter().filter(|e| e.file == file.file) {
+            println!(
+                "    {} {}/{}",
+                change_marker(entry.kind),
+                entry.collection,
+                entry.id
+            );
+        }
+    }
+}
+
+fn change_marker(kind: ChangeKind) -> &'static str {
+    match kind {
+        ChangeKind::Added => "+",
+        ChangeKind::Removed => "-",
+        ChangeKind::Modified => "~",
+    }
+}
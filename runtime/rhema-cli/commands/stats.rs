@@ -0,0 +1,379 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CliContext;
+use clap::Subcommand;
+use rhema_api::RhemaResult;
+use rhema_core::cache_report::{CacheReport, CacheTierReport};
+use rhema_core::ownership::{self, OwnershipConfig, OwnershipReport};
+use rhema_core::RhemaError;
+use rhema_knowledge::{
+    CacheOptimizer, CacheOptimizerConfig, UnifiedCacheConfig, UnifiedCacheManager,
+};
+use rhema_mcp::DaemonStatistics;
+
+/// Maintainer columns in the ownership heatmap
+const HEATMAP_COLUMNS: usize = 6;
+
+#[derive(Subcommand)]
+pub enum StatsSubcommands {
+    /// Who maintains each scope's context, bus factors and unowned stale scopes
+    Ownership {
+        /// Days without a change after which context is stale and maintainers inactive
+        #[arg(long, value_name = "DAYS", default_value = "180")]
+        stale_days: i64,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Hit ratios, evictions, memory and hot keys of every cache, with tuning recommendations
+    Cache {
+        /// MCP daemon base URL whose cache is included
+        #[arg(long, value_name = "URL", default_value = "http://127.0.0.1:8080")]
+        url: String,
+
+        /// API key used to authenticate against the daemon
+        #[arg(long, value_name = "KEY")]
+        api_key: Option<String>,
+
+        /// Leave out the MCP daemon cache
+        #[arg(long)]
+        no_daemon: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+pub async fn handle_stats(context: &CliContext, subcommand: &StatsSubcommands) -> RhemaResult<()> {
+    match subcommand {
+        StatsSubcommands::Ownership { stale_days, json } => {
+            let scopes = context.handle_error(context.rhema.discover_scopes())?;
+            let config = OwnershipConfig {
+                stale_days: *stale_days,
+            };
+            let report = context.handle_error(ownership::analyze(
+                context.rhema.repo_root(),
+                &scopes,
+                &config,
+            ))?;
+
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                print_ownership(&report);
+            }
+            Ok(())
+        }
+        StatsSubcommands::Cache {
+            url,
+            api_key,
+            no_daemon,
+            json,
+        } => {
+            let mut tiers = context.handle_error(context.rhema.get_cache_stats().await)?;
+            if !*no_daemon {
+                match fetch_daemon_statistics(url, api_key.as_deref()).await {
+                    Ok(statistics) => tiers.push(statistics.cache_tier),
+                    Err(e) => context
+                        .display_warning(&format!("Leaving out the MCP daemon cache: {}", e))?,
+                }
+            }
+            let knowledge = context.handle_error(
+                UnifiedCacheManager::new(UnifiedCacheConfig::default())
+                    .await
+                    .map_err(|e| RhemaError::CacheError(e.to_string())),
+            )?;
+            tiers.extend(knowledge.tier_reports().await);
+
+            let report = CacheOptimizer::new(CacheOptimizerConfig::default()).report(tiers);
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&report)?);
+            } else {
+                print_cache(&report);
+            }
+            Ok(())
+        }
+    }
+}
+
+async fn fetch_daemon_statistics(
+    url: &str,
+    api_key: Option<&str>,
+) -> RhemaResult<DaemonStatistics> {
+    let endpoint = format!("{}/stats", url.trim_end_matches('/'));
+    let mut request = reqwest::Client::new().get(&endpoint);
+    if let Some(key) = api_key {
+        request = request.header("Authorization", format!("ApiKey {}", key));
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| RhemaError::NetworkError(format!("Failed to reach daemon: {}", e)))?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(RhemaError::DaemonError(format!(
+            "Daemon returned {}: {}",
+            status, body
+        )));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| RhemaError::SerializationError(format!("Invalid statistics payload: {}", e)))
+}
+
+fn print_cache(report: &CacheReport) {
+    if report.tiers.is_empty() {
+        println!("📭 No caches enabled");
+        return;
+    }
+
+    let width = report
+        .tiers
+        .iter()
+        .map(|tier| tier.name().len())
+        .max()
+        .unwrap_or(4)
+        .max(4);
+    println!(
+        "🗄️  Cache statistics (overall hit ratio {:.0}%, {})",
+        report.overall_hit_ratio() * 100.0,
+        format_bytes(report.total_memory_bytes())
+    );
+    println!();
+    println!(
+        "  {:<width$}  {:>8}  {:>5}  {:>9}  {:>10}  {}",
+        "tier",
+        "entries",
+        "fill",
+        "hit ratio",
+        "memory",
+        "evictions (capacity/expired/invalidated)",
+        width = width
+    );
+    for tier in &report.tiers {
+        println!(
+            "  {:<width$}  {:>8}  {:>5}  {:>9}  {:>10}  {}/{}/{}",
+            tier.name(),
+            tier.entries,
+            tier.utilization()
+                .map_or("-".to_string(), |fill| format!("{:.0}%", fill * 100.0)),
+            hit_ratio(tier),
+            format_bytes(tier.memory_bytes),
+            tier.evictions.capacity,
+            tier.evictions.expired,
+            tier.evictions.invalidated,
+            width = width
+        );
+    }
+
+    let hot: Vec<&CacheTierReport> = report
+        .tiers
+        .iter()
+        .filter(|tier| !tier.hottest_keys.is_empty())
+        .collect();
+    if !hot.is_empty() {
+        println!();
+        println!("🔥 Hottest keys:");
+        for tier in hot {
+            let keys: Vec<String> = tier
+                .hottest_keys
+                .iter()
+                .map(|key| format!("{} ({})", truncate(&key.key, 40), key.hits))
+                .collect();
+            println!("  {}: {}", tier.name(), keys.join(", "));
+        }
+    }
+
+    println!();
+    if report.recommendations.is_empty() {
+        println!("✅ No tuning recommendations");
+    } else {
+        println!("💡 Tuning recommendations:");
+        for recommendation in &report.recommendations {
+            println!(
+                "  • {}: {} ({})",
+                recommendation.tier, recommendation.action, recommendation.reason
+            );
+        }
+    }
+}
+
+/// Hit ratio, or `idle` for a tier nothing asked yet
+fn hit_ratio(tier: &CacheTierReport) -> String {
+    if tier.requests() == 0 {
+        "idle".to_string()
+    } else {
+        format!("{:.0}%", tier.hit_ratio() * 100.0)
+    }
+}
+
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+fn print_ownership(report: &OwnershipReport) {
+    if report.scopes.is_empty() {
+        println!("📭 No scopes found");
+        return;
+    }
+
+    let columns = report.top_maintainers(HEATMAP_COLUMNS);
+    let width = report
+        .scopes
+        .iter()
+        .map(|scope| scope.scope.len())
+        .max()
+        .unwrap_or(5)
+        .max(5);
+
+    println!("🔥 Context ownership (share of each scope's context)");
+    println!();
+    let header: Vec<String> = columns
+        .iter()
+        .map(|email| format!("{:>10}", truncate(short_name(email), 10)))
+        .collect();
+    println!(
+        "  {:<width$}  {}  {:>4}  {:>10}",
+        "scope",
+        header.join(" "),
+        "bus",
+        "changed",
+        width = width
+    );
+    for scope in &report.scopes {
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|email| {
+                let share = scope
+                    .maintainers
+                    .iter()
+                    .find(|m| &m.email == email)
+                    .map_or(0.0, |m| m.share);
+                format!("{:>10}", heat(share))
+            })
+            .collect();
+        let mut flags = Vec::new();
+        if scope.stale {
+            flags.push("stale");
+        }
+        if scope.unowned {
+            flags.push("unowned");
+        }
+        println!(
+            "  {:<width$}  {}  {:>4}  {:>10}  {}",
+            scope.scope,
+            cells.join(" "),
+            scope.bus_factor,
+            scope
+                .last_change
+                .map_or("never".to_string(), |at| at.format("%Y-%m-%d").to_string()),
+            flags.join(", "),
+            width = width
+        );
+    }
+    println!();
+    println!("  ░ <25%  ▒ <50%  ▓ <75%  █ ≥75%");
+
+    if !report.single_maintainer.is_empty() {
+        println!();
+        println!("⚠️  Bus factor 1:");
+        for name in &report.single_maintainer {
+            if let Some(scope) = report.scopes.iter().find(|s| &s.scope == name) {
+                let owner = &scope.maintainers[0];
+                println!(
+                    "  • {} ({}) - {} holds {:.0}%",
+                    scope.scope,
+                    scope.path,
+                    owner.name,
+                    owner.share * 100.0
+                );
+            }
+        }
+    }
+
+    if !report.unowned_stale.is_empty() {
+        println!();
+        println!("🕸️  Unowned and unchanged for {}+ days:", report.stale_days);
+        for name in &report.unowned_stale {
+            if let Some(scope) = report.scopes.iter().find(|s| &s.scope == name) {
+                let mut notes = Vec::new();
+                if scope.agent_entries > 0 {
+                    notes.push(format!("{} unreviewed agent entries", scope.agent_entries));
+                }
+                if scope.imported_entries > 0 {
+                    notes.push(format!("{} imported entries", scope.imported_entries));
+                }
+                if notes.is_empty() {
+                    println!("  • {} ({})", scope.scope, scope.path);
+                } else {
+                    println!(
+                        "  • {} ({}) - {}",
+                        scope.scope,
+                        scope.path,
+                        notes.join(", ")
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Shaded cell with the share as a percentage
+fn heat(share: f64) -> String {
+    if share <= 0.0 {
+        return "·".to_string();
+    }
+    let shade = match share {
+        s if s < 0.25 => "░",
+        s if s < 0.5 => "▒",
+        s if s < 0.75 => "▓",
+        _ => "█",
+    };
+    format!("{} {:>3.0}%", shade, share * 100.0)
+}
+
+/// Local part of an email, for narrow columns
+fn short_name(email: &str) -> &str {
+    email.split('@').next().unwrap_or(email)
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        let mut short: String = text.chars().take(max - 1).collect();
+        short.push('…');
+        short
+    }
+}
@@ -0,0 +1,144 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CliContext;
+use clap::Subcommand;
+use rhema_api::RhemaResult;
+use rhema_core::sync::{EntryKey, Resolution, SyncEngine, SyncReport};
+
+/// Side of a conflict to keep
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum KeepSide {
+    Local,
+    Remote,
+}
+
+#[derive(Subcommand)]
+pub enum SyncSubcommands {
+    /// Pull remote changes, then push local ones
+    Run,
+
+    /// Send queued local changes to the knowledge service
+    Push,
+
+    /// Apply changes from the knowledge service
+    Pull,
+
+    /// Show queued changes and unresolved conflicts
+    Status,
+
+    /// Settle a conflict by keeping one side
+    Resolve {
+        /// Entry key as shown by `rhema sync status` (scope/file#collection/id)
+        #[arg(value_name = "KEY")]
+        key: String,
+
+        /// Side to keep
+        #[arg(long, value_enum)]
+        keep: KeepSide,
+    },
+}
+
+pub async fn handle_sync(context: &CliContext, subcommand: &SyncSubcommands) -> RhemaResult<()> {
+    let mut engine = context.handle_error(SyncEngine::open(context.rhema.repo_root()))?;
+
+    match subcommand {
+        SyncSubcommands::Run => {
+            let report = context.handle_error(engine.sync().await)?;
+            print_report(context, &report)
+        }
+        SyncSubcommands::Push => {
+            let report = context.handle_error(engine.push().await)?;
+            print_report(context, &report)
+        }
+        SyncSubcommands::Pull => {
+            let report = context.handle_error(engine.pull().await)?;
+            print_report(context, &report)
+        }
+        SyncSubcommands::Status => {
+            context.handle_error(engine.queue_local_changes())?;
+            let state = engine.state();
+            match state.last_sync {
+                Some(at) => println!("🔄 Last sync: {}", at.format("%Y-%m-%d %H:%M UTC")),
+                None => println!("🔄 Never synced"),
+            }
+            println!("   Replica: {}", state.replica_id);
+
+            if engine.queue().is_empty() {
+                println!("✅ No queued changes");
+            } else {
+                println!("📤 {} queued changes:", engine.queue().len());
+                for entry in engine.queue() {
+                    let action = if entry.is_deleted() { "-" } else { "~" };
+                    println!("  {} {} {}", action, entry.key, entry.version);
+                }
+            }
+
+            if !engine.conflicts().is_empty() {
+                println!("⚠️  {} conflicts:", engine.conflicts().len());
+                for conflict in engine.conflicts() {
+                    println!(
+                        "  • {} local {} vs remote {} (by {})",
+                        conflict.key,
+                        conflict.local.version,
+                        conflict.remote.version,
+                        conflict.remote.updated_by
+                    );
+                }
+                println!("   Settle with: rhema sync resolve <KEY> --keep local|remote");
+            }
+            Ok(())
+        }
+        SyncSubcommands::Resolve { key, keep } => {
+            let key: EntryKey = context.handle_error(key.parse())?;
+            let resolution = match keep {
+                KeepSide::Local => Resolution::Local,
+                KeepSide::Remote => Resolution::Remote,
+            };
+            context.handle_error(engine.resolve(&key, resolution))?;
+            println!("✅ Resolved {}", key);
+            if resolution == Resolution::Local {
+                println!("   The local version is queued; run `rhema sync push` to send it");
+            }
+            Ok(())
+        }
+    }
+}
+
+fn print_report(context: &CliContext, report: &SyncReport) -> RhemaResult<()> {
+    if report.offline {
+        context.display_warning(
+            "Knowledge service unreachable; local changes stay queued for the next sync",
+        )?;
+    }
+    println!(
+        "🔄 {} queued, {} pushed, {} pulled",
+        report.queued, report.pushed, report.pulled
+    );
+    if report.skipped > 0 {
+        println!(
+            "   {} remote entries skipped (scope not in this repository)",
+            report.skipped
+        );
+    }
+    if report.conflicts > 0 {
+        println!(
+            "⚠️  {} new conflicts; see `rhema sync status`",
+            report.conflicts
+        );
+    }
+    Ok(())
+}
@@ -42,6 +42,10 @@ pub enum TodoSubcommands {
         /// Due date (ISO format)
         #[arg(long, value_name = "DATE")]
         due_date: Option<String>,
+
+        /// Field required by an entry template, as NAME=VALUE (repeatable)
+        #[arg(long = "field", value_name = "NAME=VALUE")]
+        fields: Vec<String>,
     },
 
     /// List todos
@@ -121,6 +125,7 @@ pub fn handle_todo(
             priority,
             assignee,
             due_date,
+            fields,
         } => {
             match rhema_core::file_ops::add_todo(
                 &scope.path,
@@ -129,6 +134,7 @@ pub fn handle_todo(
                 priority.clone(),
                 assignee.clone(),
                 due_date.clone(),
+                rhema_core::templates::parse_entry_fields(fields)?,
             ) {
                 Ok(id) => {
                     println!("✅ Todo added successfully with ID: {}", id);
@@ -0,0 +1,145 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CliContext;
+use clap::Args;
+use colored::*;
+use rhema_api::RhemaResult;
+use rhema_core::affected::{changed_files, AffectedScopes};
+use rhema_core::scope::Scope;
+use rhema_core::RhemaError;
+use std::time::Instant;
+
+#[derive(Args)]
+pub struct ValidateArgs {
+    /// Validate recursively
+    #[arg(long)]
+    recursive: bool,
+
+    /// Use JSON schema validation
+    #[arg(long)]
+    json_schema: bool,
+
+    /// Migrate schemas if needed
+    #[arg(long)]
+    migrate: bool,
+
+    /// Validate only scopes owning changed files and the scopes depending on them
+    #[arg(long)]
+    changed: bool,
+
+    /// Revision to diff against with --changed; defaults to HEAD plus staged,
+    /// unstaged and untracked files
+    #[arg(long, value_name = "REV", requires = "changed")]
+    base: Option<String>,
+}
+
+pub async fn handle_validate(context: &CliContext, args: &ValidateArgs) -> RhemaResult<()> {
+    context.display_info("Validating repository...")?;
+    if args.json_schema {
+        context.display_info("Using JSON schema validation")?;
+    }
+    if args.migrate {
+        context.display_info("Migrating schemas if needed")?;
+    }
+
+    let repo_root = context.rhema.repo_root();
+    let scopes = context.handle_error(context.rhema.discover_scopes())?;
+    let (selected, affected): (Vec<Scope>, Option<AffectedScopes>) = if args.changed {
+        let files = context.handle_error(changed_files(repo_root, args.base.as_deref()))?;
+        let affected = AffectedScopes::compute(&scopes, repo_root, &files);
+        println!(
+            "🔎 {} changed files affect {} of {} scopes ({} changed, {} dependents)",
+            affected.changed_files,
+            affected.count(),
+            affected.total_scopes,
+            affected.changed.len(),
+            affected.dependents.len()
+        );
+        let selected = scopes
+            .iter()
+            .filter(|scope| affected.contains(&scope.definition.name))
+            .cloned()
+            .collect();
+        (selected, Some(affected))
+    } else if args.recursive {
+        (scopes, None)
+    } else {
+        (
+            vec![context.handle_error(context.find_current_scope())?],
+            None,
+        )
+    };
+
+    let start = Instant::now();
+    let mut errors = Vec::new();
+    for scope in &selected {
+        let name = &scope.definition.name;
+        println!("📁 Validating scope: {}", name.bright_blue());
+        if let Err(e) = context.rhema.validate_scope(scope).await {
+            println!("  ❌ {}", e);
+            errors.push(format!("{}: {}", name, e));
+            continue;
+        }
+
+        let mut files: Vec<_> = scope.files.iter().collect();
+        files.sort();
+        for (file_name, path) in files {
+            let parsed = std::fs::read_to_string(path)
+                .map_err(RhemaError::from)
+                .and_then(|content| {
+                    serde_yaml::from_str::<serde_yaml::Value>(&content).map_err(|e| {
+                        RhemaError::InvalidYaml {
+                            file: path.display().to_string(),
+                            message: e.to_string(),
+                        }
+                    })
+                });
+            match parsed {
+                Ok(_) => println!("  ✅ {}", file_name),
+                Err(e) => {
+                    println!("  ❌ {}: {}", file_name, e);
+                    errors.push(format!("{}/{}: {}", name, file_name, e));
+                }
+            }
+        }
+    }
+    let elapsed = start.elapsed();
+
+    println!("📊 Validated {} scopes in {:.2?}", selected.len(), elapsed);
+    if let Some(affected) = &affected {
+        let skipped = affected.total_scopes - selected.len();
+        println!(
+            "⏱️  Skipped {} unaffected scopes, about {:.2?} saved",
+            skipped,
+            affected.estimated_time_saved(elapsed)
+        );
+    }
+
+    if !errors.is_empty() {
+        println!("\n❌ Validation Errors:");
+        for (i, error) in errors.iter().enumerate() {
+            println!("  {}. {}", (i + 1).to_string().red(), error);
+        }
+        return Err(RhemaError::SchemaValidation(format!(
+            "Validation failed with {} errors",
+            errors.len()
+        )));
+    }
+
+    context.display_info("Validation completed successfully!")?;
+    Ok(())
+}
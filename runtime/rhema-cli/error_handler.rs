@@ -42,6 +42,15 @@ impl ErrorHandler {
         let mut stderr = io::stderr();
         writeln!(stderr, "{}", message)?;
 
+        if let Some(code) = rhema_core::help::error_code(error) {
+            let hint = format!("💡 Run `rhema explain {}` for how to fix this", code);
+            if self.color_enabled {
+                writeln!(stderr, "{}", hint.cyan())?;
+            } else {
+                writeln!(stderr, "{}", hint)?;
+            }
+        }
+
         if self.verbose {
             self.display_error_context(error)?;
         }
@@ -0,0 +1,48 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::CliContext;
+use rhema_core::lifecycle::{self, LifecycleHooks};
+use rhema_core::RhemaResult;
+use std::sync::Arc;
+
+/// Install the repository's entry lifecycle hooks when any are configured
+pub fn start(context: &CliContext) -> RhemaResult<()> {
+    match LifecycleHooks::load(context.rhema.repo_root()) {
+        Ok(hooks) if !hooks.config().hooks.is_empty() => {
+            lifecycle::install(Arc::new(hooks));
+        }
+        Ok(_) => {}
+        Err(e) => context.display_warning(&format!("Lifecycle hooks disabled: {}", e))?,
+    }
+    Ok(())
+}
+
+/// Wait for hook deliveries still running and report the ones that failed
+pub async fn finish(context: &CliContext) -> RhemaResult<()> {
+    let Some(hooks) = lifecycle::active() else {
+        return Ok(());
+    };
+    for outcome in hooks.flush().await {
+        if let Some(error) = &outcome.error {
+            context.display_warning(&format!(
+                "Hook '{}' failed on {} of {} after {} attempts: {}",
+                outcome.hook, outcome.event, outcome.entry_id, outcome.attempts, error
+            ))?;
+        }
+    }
+    Ok(())
+}
@@ -736,6 +736,11 @@ mod tests {
             schema_version: Some("1.0.0".to_string()),
             dependencies: None,
             protocol_info: None,
+            ai_policy: None,
+            freshness: None,
+            toolchains: None,
+            runtime: None,
+            entry_templates: None,
             custom: HashMap::new(),
         };
         
@@ -16,12 +16,16 @@
 
 mod commands;
 mod error_handler;
+mod lifecycle_hooks;
+mod profiler;
 
 use clap::{Parser, Subcommand};
 use commands::*;
 use error_handler::{display_error_and_exit, ErrorHandler};
 use rhema_api::{Rhema, RhemaResult};
+use rhema_core::help::{self, DeadEnd};
 use rhema_core::RhemaError;
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(name = "rhema")]
@@ -39,6 +43,16 @@ struct Cli {
     /// Suppress output
     #[arg(short, long)]
     quiet: bool,
+
+    /// Time major phases and sample memory, writing a Chrome trace to PATH
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        num_args = 0..=1,
+        default_missing_value = profiler::DEFAULT_TRACE_PATH
+    )]
+    profile: Option<PathBuf>,
 }
 
 #[derive(Subcommand)]
@@ -56,6 +70,14 @@ enum Commands {
         /// Auto-configure based on repository analysis
         #[arg(long)]
         auto_config: bool,
+
+        /// Run the interactive setup wizard
+        #[arg(long)]
+        wizard: bool,
+
+        /// Accept the wizard's proposals without prompting
+        #[arg(long, requires = "wizard")]
+        yes: bool,
     },
 
     /// List all scopes in the repository
@@ -65,6 +87,11 @@ enum Commands {
     Scope {
         /// Path to the scope
         path: Option<String>,
+
+        /// Detect the scope's languages, frameworks and toolchains and record
+        /// them in its rhema.yaml (every scope when no path is given)
+        #[arg(long)]
+        detect_toolchains: bool,
     },
 
     /// Show the scope tree
@@ -90,45 +117,106 @@ enum Commands {
         /// Include statistics
         #[arg(long)]
         stats: bool,
-    },
 
-    /// Search for content in the repository
-    Search {
-        /// Search term
-        term: String,
+        /// Keep running and print result changes as context files change
+        #[arg(long)]
+        watch: bool,
 
-        /// Search in specific file
-        #[arg(short, long)]
-        in_file: Option<String>,
+        /// Check the query for likely mistakes and expensive patterns without running it
+        #[arg(long, conflicts_with = "watch")]
+        lint: bool,
 
-        /// Use regex search
-        #[arg(long)]
-        regex: bool,
+        /// Worker threads evaluating scopes in parallel (overrides `query.worker_threads`)
+        #[arg(long, value_name = "N")]
+        workers: Option<usize>,
     },
 
-    /// Validate the repository
-    Validate {
-        /// Validate recursively
+    /// Execute a CQL mutation (INSERT, UPDATE or DELETE), previewing the changes first
+    Mutate {
+        /// The mutation statement, e.g. "UPDATE todos SET status='completed' WHERE id='T-12'"
+        statement: String,
+
+        /// Show the preview without writing any files
         #[arg(long)]
-        recursive: bool,
+        dry_run: bool,
+
+        /// Only allow changes in this scope (repeatable)
+        #[arg(long = "scope", value_name = "SCOPE")]
+        scopes: Vec<String>,
+
+        /// Rows that may change without confirmation
+        #[arg(long, default_value_t = rhema_query::mutation::DEFAULT_MAX_ROWS)]
+        max_rows: usize,
 
-        /// Use JSON schema validation
+        /// Apply mutations above the row limit without prompting
         #[arg(long)]
-        json_schema: bool,
+        yes: bool,
 
-        /// Migrate schemas if needed
+        /// Allow UPDATE or DELETE without WHERE to change every row
         #[arg(long)]
-        migrate: bool,
+        all: bool,
+    },
+
+    /// Search for content in the repository
+    Search {
+        #[command(flatten)]
+        args: SearchArgs,
+    },
+
+    /// Find a query in code and context entries, linking code to its decisions and patterns
+    Find {
+        #[command(flatten)]
+        args: FindArgs,
+    },
+
+    /// Validate the repository
+    Validate {
+        #[command(flatten)]
+        args: ValidateArgs,
     },
 
     /// Show health information
     Health {
         /// Scope to check health for
         scope: Option<String>,
+
+        /// Probe embedding, vector store and LLM provider dependencies
+        #[arg(long)]
+        deps: bool,
+
+        /// Create review todos, assigned to scope owners, for entries that
+        /// breach their freshness SLA
+        #[arg(long, conflicts_with = "deps")]
+        review_todos: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Check the local environment
+    Doctor {
+        #[command(flatten)]
+        args: DoctorArgs,
+    },
+
+    /// Reclaim space from orphaned snapshot objects, caches and indexes
+    Gc {
+        #[command(flatten)]
+        args: GcArgs,
+    },
+
+    /// Explain an error code with remediation steps, offline
+    Explain {
+        #[command(flatten)]
+        args: ExplainArgs,
     },
 
     /// Show statistics
-    Stats,
+    Stats {
+        #[command(subcommand)]
+        subcommand: Option<StatsSubcommands>,
+    },
 
     /// Manage todos
     Todo {
@@ -142,6 +230,12 @@ enum Commands {
         subcommand: InsightSubcommands,
     },
 
+    /// Ingest and maintain knowledge entries
+    Knowledge {
+        #[command(subcommand)]
+        subcommand: KnowledgeSubcommands,
+    },
+
     /// Manage patterns
     Pattern {
         #[command(subcommand)]
@@ -159,6 +253,117 @@ enum Commands {
         #[command(subcommand)]
         subcommand: CoordinationSubcommands,
     },
+
+    /// Manage context snapshots and named baselines
+    Snapshot {
+        #[command(subcommand)]
+        subcommand: SnapshotSubcommands,
+    },
+
+    /// Stream the context of every scope to NDJSON or a chunked archive
+    Export {
+        #[command(flatten)]
+        args: ExportArgs,
+    },
+
+    /// Import context from Backstage, Notion or Confluence exports
+    Import {
+        #[command(subcommand)]
+        subcommand: ImportSubcommands,
+    },
+
+    /// Export JSON Schemas for configuration and context files
+    Schema {
+        #[command(subcommand)]
+        subcommand: SchemaSubcommands,
+    },
+
+    /// Inspect Cargo, npm and Poetry lockfiles
+    Lock {
+        #[command(subcommand)]
+        subcommand: LockSubcommands,
+    },
+
+    /// Sync context entries with a central knowledge service
+    Sync {
+        #[command(subcommand)]
+        subcommand: SyncSubcommands,
+    },
+
+    /// Sync todos with their linked GitHub Issues, both ways
+    Issues {
+        #[command(subcommand)]
+        subcommand: IssuesSubcommands,
+    },
+
+    /// Review context entries written by agents
+    Review {
+        #[command(subcommand)]
+        subcommand: ReviewSubcommands,
+    },
+
+    /// Scan context for prompt injection and manage quarantined entries
+    Security {
+        #[command(subcommand)]
+        subcommand: SecuritySubcommands,
+    },
+
+    /// Manage the organization policy bundle
+    Policy {
+        #[command(subcommand)]
+        subcommand: PolicySubcommands,
+    },
+
+    /// Issue and revoke scoped agent tokens
+    Auth {
+        #[command(subcommand)]
+        subcommand: AuthSubcommands,
+    },
+
+    /// Inspect a running MCP daemon
+    Daemon {
+        #[command(subcommand)]
+        subcommand: DaemonSubcommands,
+    },
+
+    /// List, cancel, retry and run background jobs
+    Jobs {
+        #[command(subcommand)]
+        subcommand: JobsSubcommands,
+    },
+
+    /// Deliver coordination and action events to external event buses
+    Events {
+        #[command(subcommand)]
+        subcommand: EventsSubcommands,
+    },
+
+    /// Resolve merge conflicts in context files entry by entry
+    Resolve {
+        #[command(flatten)]
+        args: ResolveArgs,
+    },
+
+    /// Generate scope README sections from context and detect drift
+    Readme {
+        #[command(subcommand)]
+        subcommand: ReadmeSubcommands,
+    },
+
+    /// Benchmark the query engine and gate performance regressions
+    Perf {
+        #[command(subcommand)]
+        subcommand: PerfSubcommands,
+    },
+
+    /// List locales and manage translation catalogs
+    I18n {
+        #[command(subcommand)]
+        subcommand: I18nSubcommands,
+    },
+
+    /// Serve diagnostics, completions and hovers for context files over LSP
+    Lsp,
 }
 
 /// CLI application context
@@ -185,13 +390,28 @@ impl CliContext {
 
         let scopes = self.rhema.discover_scopes()?;
 
-        rhema_core::scope::find_nearest_scope(&current_dir, &scopes)
-            .ok_or_else(|| {
-                RhemaError::ConfigError(
+        match rhema_core::scope::find_nearest_scope(&current_dir, &scopes) {
+            Some(scope) => Ok(scope.clone()),
+            None => {
+                self.display_next_steps(&DeadEnd::OutsideScopes(current_dir))?;
+                Err(RhemaError::ConfigError(
                     "No Rhema scope found in current directory or parent directories".to_string(),
-                )
-            })
-            .cloned()
+                ))
+            }
+        }
+    }
+
+    /// Show what to do about a command that ended without a result, judged
+    /// from the repository
+    fn display_next_steps(&self, dead_end: &DeadEnd) -> RhemaResult<()> {
+        if self.quiet {
+            return Ok(());
+        }
+        for step in help::next_steps(self.rhema.repo_root(), dead_end) {
+            eprintln!("  → {}", step);
+        }
+        eprintln!("  See `rhema explain {}`", dead_end.code());
+        Ok(())
     }
 
     /// Display info message if not quiet
@@ -226,6 +446,36 @@ impl CliContext {
 async fn main() -> RhemaResult<()> {
     let cli = Cli::parse();
 
+    // Diagnostics stay quiet unless asked for; RHEMA_LOG overrides the level
+    let logging = rhema_monitoring::LoggingConfig {
+        level: match (cli.verbose, cli.quiet) {
+            (true, _) => "debug",
+            (_, true) => "error",
+            _ => "warn",
+        }
+        .to_string(),
+        with_target: cli.verbose,
+        ..Default::default()
+    }
+    .with_env()
+    .and_then(|config| rhema_monitoring::init_logging(&config));
+    if let Err(e) = logging {
+        display_error_and_exit(&e, cli.verbose, cli.quiet);
+    }
+
+    // Remediation docs are compiled in and need no repository
+    if let Some(Commands::Explain { args }) = &cli.command {
+        if let Err(e) = handle_explain(args) {
+            display_error_and_exit(&e, cli.verbose, cli.quiet);
+        }
+        return Ok(());
+    }
+
+    let profiler = cli.profile.as_ref().map(|_| profiler::start());
+
+    // The commands have not moved to `rhema_api::v1` yet; keep its
+    // deprecation warnings out of CLI output
+    rhema_api::compat::set_deprecation_warnings(false);
     let rhema = match Rhema::new() {
         Ok(rhema) => rhema,
         Err(e) => {
@@ -234,18 +484,61 @@ async fn main() -> RhemaResult<()> {
     };
 
     let context = CliContext::new(rhema, cli.verbose, cli.quiet);
+    // Localize CLI output; a broken catalog leaves it in English
+    match rhema_core::i18n::Localizer::for_cli(context.rhema.repo_root()) {
+        Ok(localizer) => rhema_core::i18n::init(localizer),
+        Err(e) => context.display_warning(&format!("Keeping CLI output in English: {}", e))?,
+    }
+    lifecycle_hooks::start(&context)?;
+    let result = run(&cli, &context).await;
+    lifecycle_hooks::finish(&context).await?;
+    if result.is_ok() {
+        show_tip(&context);
+    }
 
+    if let (Some(profiler), Some(trace_path)) = (&profiler, &cli.profile) {
+        if let Err(e) = profiler::finish(profiler, trace_path, cli.quiet) {
+            context.display_warning(&format!("Failed to write profile: {}", e))?;
+        }
+    }
+
+    result
+}
+
+/// Print the languages, frameworks and toolchains recorded for a scope
+fn print_toolchains(toolchains: &rhema_core::ScopeToolchains) {
+    let join = |set: &std::collections::BTreeSet<String>| {
+        if set.is_empty() {
+            "-".to_string()
+        } else {
+            set.iter().cloned().collect::<Vec<_>>().join(", ")
+        }
+    };
+    println!("Languages: {}", join(&toolchains.languages));
+    println!("Frameworks: {}", join(&toolchains.frameworks));
+    println!("Toolchains: {}", join(&toolchains.toolchains));
+}
+
+/// Dispatch the parsed command
+async fn run(cli: &Cli, context: &CliContext) -> RhemaResult<()> {
     match &cli.command {
         Some(Commands::Init {
             scope_type,
             scope_name,
             auto_config,
-        }) => handle_init(
-            &context,
-            scope_type.as_deref(),
-            scope_name.as_deref(),
-            *auto_config,
-        ),
+            wizard,
+            yes,
+        }) => {
+            if *wizard {
+                return handle_init_wizard(&context, *yes);
+            }
+            handle_init(
+                &context,
+                scope_type.as_deref(),
+                scope_name.as_deref(),
+                *auto_config,
+            )
+        }
 
         Some(Commands::Scopes) => {
             context.display_info("Discovering scopes...")?;
@@ -253,6 +546,7 @@ async fn main() -> RhemaResult<()> {
 
             if scopes.is_empty() {
                 context.display_info("No scopes found in repository")?;
+                context.display_next_steps(&DeadEnd::NoScopes)?;
             } else {
                 for scope in scopes {
                     println!("- {}", scope.definition.name);
@@ -261,12 +555,36 @@ async fn main() -> RhemaResult<()> {
             Ok(())
         }
 
-        Some(Commands::Scope { path }) => match path {
+        Some(Commands::Scope {
+            path,
+            detect_toolchains,
+        }) => match path {
             Some(scope_path) => {
                 context.display_info(&format!("Showing scope: {}", scope_path))?;
                 let scope = context.handle_error(context.rhema.get_scope(scope_path))?;
                 println!("Scope: {}", scope.definition.name);
                 println!("Path: {}", scope.path.display());
+                if *detect_toolchains {
+                    let toolchains = context
+                        .handle_error(rhema_core::toolchain::record_toolchains(&scope))?;
+                    print_toolchains(&toolchains);
+                } else if let Some(toolchains) = &scope.definition.toolchains {
+                    print_toolchains(toolchains);
+                }
+                Ok(())
+            }
+            None if *detect_toolchains => {
+                let scopes = context.handle_error(context.rhema.discover_scopes())?;
+                for scope in &scopes {
+                    let toolchains = context
+                        .handle_error(rhema_core::toolchain::record_toolchains(scope))?;
+                    println!("Scope: {}", scope.definition.name);
+                    print_toolchains(&toolchains);
+                }
+                context.display_info(&format!(
+                    "Recorded toolchains for {} scope(s)",
+                    scopes.len()
+                ))?;
                 Ok(())
             }
             None => {
@@ -281,6 +599,7 @@ async fn main() -> RhemaResult<()> {
 
             if scopes.is_empty() {
                 context.display_info("No scopes found in repository")?;
+                context.display_next_steps(&DeadEnd::NoScopes)?;
             } else {
                 for scope in scopes {
                     println!("├── {}", scope.definition.name);
@@ -295,8 +614,17 @@ async fn main() -> RhemaResult<()> {
             provenance,
             field_provenance,
             stats,
+            watch,
+            lint,
+            workers,
         }) => {
+            if *lint {
+                return handle_query_lint(&context, query, format);
+            }
             context.display_info(&format!("Executing query: {}", query))?;
+            if *watch {
+                return handle_query_watch(&context, query).await;
+            }
             handle_query(
                 &context,
                 query,
@@ -304,68 +632,47 @@ async fn main() -> RhemaResult<()> {
                 *provenance,
                 *field_provenance,
                 *stats,
+                *workers,
             )
         }
 
-        Some(Commands::Search {
-            term,
-            in_file,
-            regex,
+        Some(Commands::Mutate {
+            statement,
+            dry_run,
+            scopes,
+            max_rows,
+            yes,
+            all,
         }) => {
-            context.display_info(&format!("Searching for: {}", term))?;
-            if let Some(file) = in_file {
-                context.display_info(&format!("In file: {}", file))?;
-            }
-            if *regex {
-                context.display_info("Using regex search")?;
-            }
-
-            let results =
-                context.handle_error(context.rhema.search_regex(term, in_file.as_deref()))?;
-
-            if results.is_empty() {
-                context.display_info("No results found")?;
-            } else {
-                for result in results {
-                    println!("Found: {:?}", result);
-                }
-            }
-            Ok(())
+            context.display_info(&format!("Planning mutation: {}", statement))?;
+            handle_mutate(&context, statement, *dry_run, scopes, *max_rows, *yes, *all)
         }
 
-        Some(Commands::Validate {
-            recursive,
-            json_schema,
-            migrate,
-        }) => {
-            context.display_info("Validating repository...")?;
-            if *recursive {
-                context.display_info("Validating recursively")?;
-            }
-            if *json_schema {
-                context.display_info("Using JSON schema validation")?;
-            }
-            if *migrate {
-                context.display_info("Migrating schemas if needed")?;
-            }
+        Some(Commands::Search { args }) => handle_search(&context, args).await,
+        Some(Commands::Find { args }) => handle_find(&context, args).await,
 
-            // TODO: Implement actual validation logic
-            context.display_info("Validation completed successfully!")?;
-            Ok(())
+        Some(Commands::Validate { args }) => handle_validate(&context, args).await,
+
+        Some(Commands::Health { deps: true, json, .. }) => {
+            handle_dependency_health(&context, *json).await
         }
 
-        Some(Commands::Health { scope }) => {
-            context.display_info("Checking health...")?;
-            if let Some(scope_name) = scope {
-                context.display_info(&format!("For scope: {}", scope_name))?;
-            }
+        Some(Commands::Health {
+            scope,
+            json,
+            review_todos,
+            ..
+        }) => handle_freshness_health(&context, scope.as_deref(), *json, *review_todos),
 
-            // TODO: Implement actual health check logic
-            context.display_info("Health check completed successfully!")?;
-            Ok(())
-        }
+        Some(Commands::Doctor { args }) => handle_doctor(&context, args).await,
+        Some(Commands::Gc { args }) => handle_gc(&context, args).await,
+        Some(Commands::Explain { args }) => handle_explain(args),
+
+        Some(Commands::Stats {
+            subcommand: Some(subcommand),
+        }) => handle_stats(&context, subcommand).await,
 
-        Some(Commands::Stats) => {
+        Some(Commands::Stats { subcommand: None }) => {
             context.display_info("Showing statistics...")?;
 
             // TODO: Implement actual statistics logic
@@ -380,7 +687,12 @@ async fn main() -> RhemaResult<()> {
 
         Some(Commands::Insight { subcommand }) => {
             let scope = context.find_current_scope()?;
-            handle_insight(&context, &scope, subcommand)
+            handle_insight(&context, &scope, subcommand).await
+        }
+
+        Some(Commands::Knowledge { subcommand }) => {
+            let scope = context.find_current_scope()?;
+            handle_knowledge(&context, &scope, subcommand).await
         }
 
         Some(Commands::Pattern { subcommand }) => {
@@ -395,9 +707,38 @@ async fn main() -> RhemaResult<()> {
 
         Some(Commands::Coordination { subcommand }) => {
             context.display_info("Executing coordination command...")?;
-            handle_coordination(&context, subcommand)
+            handle_coordination(&context, subcommand).await
         }
 
+        Some(Commands::Snapshot { subcommand }) => handle_snapshot(&context, subcommand),
+
+        Some(Commands::Export { args }) => handle_export(&context, args).await,
+
+        Some(Commands::Import { subcommand }) => handle_import(&context, subcommand),
+
+        Some(Commands::Schema { subcommand }) => handle_schema(&context, subcommand),
+
+        Some(Commands::Lock { subcommand }) => handle_lock(&context, subcommand),
+
+        Some(Commands::Sync { subcommand }) => handle_sync(&context, subcommand).await,
+        Some(Commands::Issues { subcommand }) => handle_issues(&context, subcommand).await,
+
+        Some(Commands::Review { subcommand }) => handle_review(&context, subcommand),
+        Some(Commands::Security { subcommand }) => handle_security(&context, subcommand),
+        Some(Commands::Policy { subcommand }) => handle_policy(&context, subcommand),
+        Some(Commands::Auth { subcommand }) => handle_auth(&context, subcommand),
+
+        Some(Commands::Daemon { subcommand }) => handle_daemon(&context, subcommand).await,
+        Some(Commands::Jobs { subcommand }) => handle_jobs(&context, subcommand).await,
+        Some(Commands::Events { subcommand }) => handle_events(&context, subcommand).await,
+
+        Some(Commands::Resolve { args }) => handle_resolve(&context, args),
+        Some(Commands::Readme { subcommand }) => handle_readme(&context, subcommand),
+        Some(Commands::Perf { subcommand }) => handle_perf(&context, subcommand),
+        Some(Commands::I18n { subcommand }) => handle_i18n(&context, subcommand),
+
+        Some(Commands::Lsp) => handle_lsp(&context),
+
         None => {
             if !cli.quiet {
                 println!("Welcome to Rhema CLI!");
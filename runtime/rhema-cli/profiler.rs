@@ -0,0 +1,71 @@
+/*
+ * Copyright 2025 Cory Parent
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use colored::*;
+use rhema_core::profiling::{self, ProfileReport, Profiler};
+use rhema_core::RhemaResult;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Default trace file written by `--profile` without a path
+pub const DEFAULT_TRACE_PATH: &str = "rhema-profile.json";
+
+/// How often resident memory is sampled while profiling
+const MEMORY_SAMPLE_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Install the process profiler and start memory sampling
+pub fn start() -> Arc<Profiler> {
+    let profiler = profiling::install(Arc::new(Profiler::new()));
+    profiler.start_memory_sampler(MEMORY_SAMPLE_INTERVAL);
+    profiler
+}
+
+/// Stop profiling, write the Chrome trace and print the phase summary to stderr
+pub fn finish(profiler: &Profiler, trace_path: &Path, quiet: bool) -> RhemaResult<ProfileReport> {
+    let command = std::env::args()
+        .skip(1)
+        .filter(|arg| !arg.starts_with("--profile"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let report = profiler.finish(&command);
+    profiler.write_chrome_trace(&report, trace_path)?;
+
+    if !quiet {
+        let peak = report
+            .peak_memory_bytes
+            .map(|bytes| format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0)))
+            .unwrap_or_else(|| "unavailable".to_string());
+        eprintln!(
+            "{} {:.1} ms total, peak memory {}",
+            "⏱  Profile:".bold(),
+            report.total_ms,
+            peak
+        );
+        for phase in &report.phases {
+            eprintln!(
+                "  {:<16} {:>5} calls {:>10.1} ms (self {:.1} ms)",
+                phase.phase.name(),
+                phase.calls,
+                phase.total_ms,
+                phase.self_ms
+            );
+        }
+        eprintln!("  Trace written to {}", trace_path.display());
+    }
+
+    Ok(report)
+}
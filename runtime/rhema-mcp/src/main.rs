@@ -1,5 +1,6 @@
 use clap::Parser;
 use rhema_mcp_server::RhemaMcpServer;
+use rhema_monitoring::{init_logging, LoggingConfig};
 use tracing::{error, info};
 
 #[derive(Parser)]
@@ -28,19 +29,14 @@ struct Cli {
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
-    // Initialize logging
-    let log_level = if cli.debug {
-        tracing::Level::DEBUG
-    } else {
-        tracing::Level::INFO
-    };
-
-    tracing_subscriber::fmt()
-        .with_max_level(log_level)
-        .with_target(false)
-        .with_thread_ids(true)
-        .with_thread_names(true)
-        .init();
+    // Initialize logging; RHEMA_LOG and RHEMA_LOG_FORMAT override the defaults
+    let logging = LoggingConfig {
+        level: if cli.debug { "debug" } else { "info" }.to_string(),
+        with_target: false,
+        ..Default::default()
+    }
+    .with_env()?;
+    init_logging(&logging)?;
 
     info!("Starting Rhema MCP Server...");
     info!("Listening on {}:{}", cli.host, cli.port);
@@ -50,7 +46,16 @@ async fn main() -> anyhow::Result<()> {
     
     match server.start(&cli.host, cli.port).await {
         Ok(_) => {
-            info!("MCP Server stopped gracefully");
+            // Serve until SIGTERM/Ctrl-C, then drain in-flight work before exiting
+            let report = server
+                .service_mut()
+                .run_until_shutdown()
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to drain MCP service: {}", e))?;
+            info!(
+                "MCP Server stopped gracefully ({} requests abandoned, {} watcher events dropped)",
+                report.abandoned_requests, report.dropped_watcher_events
+            );
             Ok(())
         }
         Err(e) => {
@@ -0,0 +1,41 @@
+#!/usr/bin/env bash
+# Check that the stable `rhema_api::v1` surface only grows.
+#
+# Compares `cargo public-api` output for the v1 module with the committed
+# baseline in crates/rhema-api/public-api/v1.txt. Removed or changed items
+# fail the check, since integrators build against them; additions pass and
+# are recorded with `--bless`.
+#
+# Usage: scripts/check-public-api.sh [--bless]
+# Needs `cargo install cargo-public-api` and a nightly toolchain, which
+# cargo-public-api uses to build rustdoc JSON.
+set -euo pipefail
+
+root="$(cd "$(dirname "$0")/.." && pwd)"
+baseline="$root/crates/rhema-api/public-api/v1.txt"
+current="$(mktemp)"
+trap 'rm -f "$current"' EXIT
+
+cargo public-api --manifest-path "$root/crates/rhema-api/Cargo.toml" --simplified \
+  | grep -E 'rhema_api::v1(::|$)' \
+  | LC_ALL=C sort > "$current"
+
+if [[ "${1:-}" == "--bless" ]]; then
+  cp "$current" "$baseline"
+  echo "Recorded the rhema_api::v1 API in $baseline"
+  exit 0
+fi
+
+added="$(LC_ALL=C comm -13 "$baseline" "$current")"
+removed="$(LC_ALL=C comm -23 "$baseline" "$current")"
+
+if [[ -n "$added" ]]; then
+  echo "New rhema_api::v1 items; record them with scripts/check-public-api.sh --bless:"
+  echo "$added" | sed 's/^/  + /'
+fi
+if [[ -n "$removed" ]]; then
+  echo "Removed or changed rhema_api::v1 items break semver; put the new shape in a v2 module instead:"
+  echo "$removed" | sed 's/^/  - /'
+  exit 1
+fi
+echo "rhema_api::v1 is compatible with $baseline"
@@ -3,6 +3,7 @@
 use git2::Repository;
 use rhema_api::Rhema;
 use rhema_core::RhemaResult;
+use rhema_testkit::{RepoSpec, SyntheticRepo, TestRepo};
 use tempfile::TempDir;
 
 /// Test fixtures for different testing scenarios
@@ -143,6 +144,15 @@ required: [id, name, type]
         Ok((temp_dir, rhema))
     }
 
+    /// Create a test fixture with a synthetic repository generated from a
+    /// declarative spec, including its scopes, entries and git history
+    pub fn synthetic(spec: &RepoSpec) -> RhemaResult<(TempDir, Rhema, SyntheticRepo)> {
+        let (temp_dir, repo) = TestRepo::generate(spec)?.into_parts();
+        let rhema = Rhema::new_from_path(temp_dir.path().to_path_buf())?;
+
+        Ok((temp_dir, rhema, repo))
+    }
+
     /// Create a test fixture for performance testing
     pub fn for_performance_testing(size: usize) -> RhemaResult<(TempDir, Rhema)> {
         let (temp_dir, rhema) = Self::with_scope()?;
@@ -64,6 +64,7 @@ async fn test_basic_coordination_integration() {
         last_heartbeat: chrono::Utc::now(),
         is_online: true,
         performance_metrics: rhema_api::AgentPerformanceMetrics::default(),
+        liveness: Default::default(),
     };
 
     rhema.register_agent(agent).await.unwrap();
@@ -208,6 +209,7 @@ async fn test_advanced_coordination_integration() {
             last_heartbeat: chrono::Utc::now(),
             is_online: true,
             performance_metrics: rhema_api::AgentPerformanceMetrics::default(),
+            liveness: Default::default(),
         };
         rhema.register_agent(agent).await.unwrap();
     }
@@ -309,6 +311,7 @@ async fn test_coordination_integration_with_external_systems() {
         last_heartbeat: chrono::Utc::now(),
         is_online: true,
         performance_metrics: rhema_api::AgentPerformanceMetrics::default(),
+        liveness: Default::default(),
     };
 
     rhema.register_agent(agent).await.unwrap();
@@ -373,6 +376,7 @@ async fn test_coordination_error_handling() {
         last_heartbeat: chrono::Utc::now(),
         is_online: true,
         performance_metrics: rhema_api::AgentPerformanceMetrics::default(),
+        liveness: Default::default(),
     };
 
     // These should fail because coordination is not initialized
@@ -450,6 +454,7 @@ async fn test_coordination_performance_monitoring() {
         last_heartbeat: chrono::Utc::now(),
         is_online: true,
         performance_metrics: rhema_api::AgentPerformanceMetrics::default(),
+        liveness: Default::default(),
     };
 
     rhema.register_agent(agent).await.unwrap();
@@ -153,6 +153,7 @@ async fn test_agent_registration_with_coordination() -> RhemaResult<()> {
         is_online: true,
         performance_metrics:
             rhema_coordination::agent::real_time_coordination::AgentPerformanceMetrics::default(),
+        liveness: Default::default(),
     };
 
     let result = service.register_agent_with_coordination(agent_info).await;
@@ -261,6 +262,7 @@ async fn test_session_creation_with_coordination() -> RhemaResult<()> {
         last_heartbeat: chrono::Utc::now(),
         is_online: true,
         performance_metrics: rhema_api::AgentPerformanceMetrics::default(),
+        liveness: Default::default(),
     };
 
     let agent2 = AgentInfo {
@@ -274,6 +276,7 @@ async fn test_session_creation_with_coordination() -> RhemaResult<()> {
         last_heartbeat: chrono::Utc::now(),
         is_online: true,
         performance_metrics: rhema_api::AgentPerformanceMetrics::default(),
+        liveness: Default::default(),
     };
 
     service.register_agent_with_coordination(agent1).await?;
@@ -337,6 +340,7 @@ async fn test_session_join_and_message() -> RhemaResult<()> {
         last_heartbeat: chrono::Utc::now(),
         is_online: true,
         performance_metrics: rhema_api::AgentPerformanceMetrics::default(),
+        liveness: Default::default(),
     };
 
     let agent2 = AgentInfo {
@@ -350,6 +354,7 @@ async fn test_session_join_and_message() -> RhemaResult<()> {
         last_heartbeat: chrono::Utc::now(),
         is_online: true,
         performance_metrics: rhema_api::AgentPerformanceMetrics::default(),
+        liveness: Default::default(),
     };
 
     service.register_agent_with_coordination(agent1).await?;
@@ -437,6 +442,7 @@ async fn test_coordination_statistics() -> RhemaResult<()> {
         is_online: true,
         performance_metrics:
             rhema_coordination::agent::real_time_coordination::AgentPerformanceMetrics::default(),
+        liveness: Default::default(),
     };
 
     service.register_agent_with_coordination(agent_info).await?;
@@ -601,6 +607,7 @@ async fn test_coordination_disabled() -> RhemaResult<()> {
         is_online: true,
         performance_metrics:
             rhema_coordination::agent::real_time_coordination::AgentPerformanceMetrics::default(),
+        liveness: Default::default(),
     };
 
     let register_result = service.register_agent_with_coordination(agent_info).await;
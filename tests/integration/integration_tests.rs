@@ -4,8 +4,10 @@
 use git2::Repository;
 use rhema_cli::Rhema;
 use rhema_core::RhemaResult;
+use rhema_testkit::RepoSpec;
 
 // Import test utilities
+use crate::common::fixtures::TestFixtures;
 use crate::common::helpers::TestHelpers;
 
 /// Test basic Rhema initialization
@@ -93,3 +95,19 @@ fn test_search_integration() -> RhemaResult<()> {
 
     Ok(())
 }
+
+/// Test loading a synthetic repository generated from a spec
+#[test]
+fn test_synthetic_repository_integration() -> RhemaResult<()> {
+    let spec = RepoSpec::default().with_seed(11).with_scopes(5);
+    let (_temp_dir, rhema, repo) = TestFixtures::synthetic(&spec)?;
+
+    // Every generated scope is discovered with all of its todos
+    assert_eq!(rhema.discover_scopes()?.len(), repo.scopes.len());
+    for scope in &repo.scopes {
+        let todos = rhema.load_todos(scope.name())?;
+        assert_eq!(todos.todos.len(), scope.todos.len());
+    }
+
+    Ok(())
+}
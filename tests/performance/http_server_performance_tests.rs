@@ -319,6 +319,7 @@ fn create_test_config() -> McpConfig {
         logging: rhema_mcp::mcp::LoggingConfig::default(),
         use_official_sdk: false,
         startup: rhema_mcp::mcp::StartupConfig::default(),
+        query_guard: rhema_mcp::QueryGuardConfig::default(),
     }
 }
 
@@ -108,6 +108,11 @@ fn test_lock_file_validation() {
         schema_version: Some("1.0.0".to_string()),
         dependencies: None,
         protocol_info: None,
+        ai_policy: None,
+        freshness: None,
+        toolchains: None,
+        runtime: None,
+        entry_templates: None,
         custom: HashMap::new(),
     };
 
@@ -172,6 +177,11 @@ fn test_lock_file_with_invalid_content() {
         schema_version: Some("1.0.0".to_string()),
         dependencies: None,
         protocol_info: None,
+        ai_policy: None,
+        freshness: None,
+        toolchains: None,
+        runtime: None,
+        entry_templates: None,
         custom: HashMap::new(),
     };
 
@@ -231,6 +241,11 @@ fn test_lock_file_version_mismatch() {
         schema_version: Some("1.0.0".to_string()),
         dependencies: None,
         protocol_info: None,
+        ai_policy: None,
+        freshness: None,
+        toolchains: None,
+        runtime: None,
+        entry_templates: None,
         custom: HashMap::new(),
     };
 
@@ -299,6 +314,11 @@ fn test_lock_file_with_dependencies() {
             version: Some("1.0.0".to_string()),
         }]),
         protocol_info: None,
+        ai_policy: None,
+        freshness: None,
+        toolchains: None,
+        runtime: None,
+        entry_templates: None,
         custom: HashMap::new(),
     };
 
@@ -375,6 +395,11 @@ fn test_lock_file_staleness_check() {
         schema_version: Some("1.0.0".to_string()),
         dependencies: None,
         protocol_info: None,
+        ai_policy: None,
+        freshness: None,
+        toolchains: None,
+        runtime: None,
+        entry_templates: None,
         custom: HashMap::new(),
     };
 
@@ -441,6 +466,11 @@ fn test_checksum_validation() {
         schema_version: Some("1.0.0".to_string()),
         dependencies: None,
         protocol_info: None,
+        ai_policy: None,
+        freshness: None,
+        toolchains: None,
+        runtime: None,
+        entry_templates: None,
         custom: HashMap::new(),
     };
 